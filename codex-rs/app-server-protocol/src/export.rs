@@ -1,5 +1,6 @@
 use crate::ClientNotification;
 use crate::ClientRequest;
+use crate::PROTOCOL_VERSION;
 use crate::ServerNotification;
 use crate::ServerRequest;
 use crate::export_client_notification_schemas;
@@ -31,6 +32,7 @@ use std::process::Command;
 use ts_rs::TS;
 
 const HEADER: &str = "// GENERATED CODE! DO NOT MODIFY BY HAND!\n\n";
+const PROTOCOL_VERSION_TS_NAME: &str = "PROTOCOL_VERSION";
 const IGNORED_DEFINITIONS: &[&str] = &["Option<()>"];
 
 #[derive(Clone)]
@@ -100,6 +102,8 @@ pub fn generate_ts_with_options(
     export_server_responses(out_dir)?;
     ServerNotification::export_all_to(out_dir)?;
 
+    write_protocol_version_ts(out_dir)?;
+
     if options.generate_indices {
         generate_index_ts(out_dir)?;
         generate_index_ts(&v2_out_dir)?;
@@ -261,6 +265,10 @@ fn build_schema_bundle(schemas: Vec<GeneratedSchema>) -> Result<Value> {
         Value::String("CodexAppServerProtocol".into()),
     );
     root.insert("type".to_string(), Value::String("object".into()));
+    root.insert(
+        "protocolVersion".to_string(),
+        Value::Number(PROTOCOL_VERSION.into()),
+    );
     root.insert("definitions".to_string(), Value::Object(definitions));
 
     Ok(Value::Object(root))
@@ -639,6 +647,15 @@ fn rewrite_named_ref_to_namespace(value: &mut Value, ns: &str, name: &str) {
     }
 }
 
+/// Emits the protocol version as a value so TS clients can check it at
+/// compile time.
+fn write_protocol_version_ts(out_dir: &Path) -> Result<()> {
+    let path = out_dir.join(format!("{PROTOCOL_VERSION_TS_NAME}.ts"));
+    let content =
+        format!("{HEADER}export const {PROTOCOL_VERSION_TS_NAME} = {PROTOCOL_VERSION};\n");
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
 fn prepend_header_if_missing(path: &Path) -> Result<()> {
     let mut content = String::new();
     {
@@ -711,7 +728,11 @@ fn generate_index_ts(out_dir: &Path) -> Result<PathBuf> {
     stems.dedup();
 
     for name in stems {
-        entries.push(format!("export type {{ {name} }} from \"./{name}\";\n"));
+        if name == PROTOCOL_VERSION_TS_NAME {
+            entries.push(format!("export {{ {name} }} from \"./{name}\";\n"));
+        } else {
+            entries.push(format!("export type {{ {name} }} from \"./{name}\";\n"));
+        }
     }
 
     // If this is the root out_dir and a ./v2 folder exists with TS files,
@@ -741,6 +762,7 @@ fn generate_index_ts(out_dir: &Path) -> Result<PathBuf> {
 mod tests {
     use super::*;
     use anyhow::Result;
+    use pretty_assertions::assert_eq;
     use std::collections::BTreeSet;
     use std::fs;
     use std::path::PathBuf;
@@ -943,4 +965,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn protocol_version_is_exported_as_value() -> Result<()> {
+        let output_dir =
+            std::env::temp_dir().join(format!("codex_ts_protocol_version_{}", Uuid::now_v7()));
+        fs::create_dir(&output_dir)?;
+        let version_ts = output_dir.join("PROTOCOL_VERSION.ts");
+
+        write_protocol_version_ts(&output_dir)?;
+        let index_path = generate_index_ts(&output_dir)?;
+        let version_contents = fs::read_to_string(version_ts)?;
+        let index_contents = fs::read_to_string(index_path)?;
+        fs::remove_dir_all(&output_dir)?;
+
+        assert_eq!(
+            version_contents,
+            format!("{HEADER}export const PROTOCOL_VERSION = {PROTOCOL_VERSION};\n")
+        );
+        assert_eq!(
+            index_contents,
+            format!("{HEADER}export {{ PROTOCOL_VERSION }} from \"./PROTOCOL_VERSION\";\n")
+        );
+        Ok(())
+    }
 }
@@ -41,6 +41,42 @@ pub enum AuthMode {
     ChatgptAuthTokens,
 }
 
+/// Highest app-server protocol version understood by this build. Bump this
+/// whenever a change would break clients written against the previous version.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Optional protocol features a client can opt into via `initialize`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum Capability {
+    /// `sendUserTurn` accepts one-off `overrides`.
+    TurnOverrides,
+    /// `fuzzyFileSearch` is available.
+    FuzzyFileSearch,
+    /// `listCustomPrompts` is available.
+    ListCustomPrompts,
+    /// The server emits `turn/settings/updated` when a turn starts.
+    TurnSettingsUpdates,
+}
+
+impl Capability {
+    /// Every capability supported by this server.
+    pub const ALL: &[Capability] = &[
+        Capability::TurnOverrides,
+        Capability::FuzzyFileSearch,
+        Capability::ListCustomPrompts,
+        Capability::TurnSettingsUpdates,
+    ];
+
+    pub fn from_wire_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|capability| capability.to_string() == name)
+    }
+}
+
 /// Generates an `enum ClientRequest` where each variant is a request that the
 /// client can send to the server. Each variant has associated `params` and
 /// `response` types. Also generates a `export_client_responses()` function to
@@ -156,6 +192,14 @@ client_request_definitions! {
         params: v2::SkillsListParams,
         response: v2::SkillsListResponse,
     },
+    ToolList => "tool/list" {
+        params: v2::ToolListParams,
+        response: v2::ToolListResponse,
+    },
+    GitDiff => "git/diff" {
+        params: v2::GitDiffParams,
+        response: v2::GitDiffResponse,
+    },
     AppsList => "app/list" {
         params: v2::AppsListParams,
         response: v2::AppsListResponse,
@@ -265,6 +309,12 @@ client_request_definitions! {
         params: v1::GetConversationSummaryParams,
         response: v1::GetConversationSummaryResponse,
     },
+    /// Page through the completed items of a loaded conversation, so a client
+    /// attaching mid-session can render what it missed.
+    GetConversationHistory {
+        params: v1::GetConversationHistoryParams,
+        response: v1::GetConversationHistoryResponse,
+    },
     /// List recorded Codex conversations (rollouts) with optional pagination and search.
     ListConversations {
         params: v1::ListConversationsParams,
@@ -350,6 +400,12 @@ client_request_definitions! {
         params: FuzzyFileSearchParams,
         response: FuzzyFileSearchResponse,
     },
+    /// List custom prompts from `$CODEX_HOME/prompts` and the project's
+    /// `.codex/prompts`.
+    ListCustomPrompts {
+        params: v1::ListCustomPromptsParams,
+        response: v1::ListCustomPromptsResponse,
+    },
     /// Execute a command (argv vector) under the server's sandbox.
     ExecOneOffCommand {
         params: v1::ExecOneOffCommandParams,
@@ -460,6 +516,15 @@ macro_rules! server_notification_definitions {
         }
 
         impl ServerNotification {
+            /// Capability the client must negotiate during `initialize` before
+            /// the server will send this notification, if any.
+            pub fn required_capability(&self) -> Option<Capability> {
+                match self {
+                    Self::TurnSettingsUpdated(_) => Some(Capability::TurnSettingsUpdates),
+                    _ => None,
+                }
+            }
+
             pub fn to_params(self) -> Result<serde_json::Value, serde_json::Error> {
                 match self {
                     $(Self::$variant(params) => serde_json::to_value(params),)*
@@ -577,6 +642,11 @@ pub struct FuzzyFileSearchParams {
     pub roots: Vec<String>,
     // if provided, will cancel any previous request that used the same value
     pub cancellation_token: Option<String>,
+    /// Files recently mentioned in the conversation, ranked higher. Absolute
+    /// paths, or paths relative to their root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub recent_paths: Option<Vec<String>>,
 }
 
 /// Superset of [`codex_file_search::FileMatch`]
@@ -585,6 +655,8 @@ pub struct FuzzyFileSearchResult {
     pub root: String,
     pub path: String,
     pub file_name: String,
+    /// Relevance score; higher is better. Comparable across results of a
+    /// single request so clients can apply a threshold.
     pub score: u32,
     pub indices: Option<Vec<u32>>,
 }
@@ -599,11 +671,16 @@ server_notification_definitions! {
     Error => "error" (v2::ErrorNotification),
     ThreadStarted => "thread/started" (v2::ThreadStartedNotification),
     ThreadNameUpdated => "thread/name/updated" (v2::ThreadNameUpdatedNotification),
+    ThreadSuspended => "thread/suspended" (v2::ThreadSuspendedNotification),
+    ThreadResumed => "thread/resumed" (v2::ThreadResumedNotification),
     ThreadTokenUsageUpdated => "thread/tokenUsage/updated" (v2::ThreadTokenUsageUpdatedNotification),
     TurnStarted => "turn/started" (v2::TurnStartedNotification),
     TurnCompleted => "turn/completed" (v2::TurnCompletedNotification),
     TurnDiffUpdated => "turn/diff/updated" (v2::TurnDiffUpdatedNotification),
+    TurnFilesUpdated => "turn/files/updated" (v2::TurnFilesUpdatedNotification),
     TurnPlanUpdated => "turn/plan/updated" (v2::TurnPlanUpdatedNotification),
+    /// Only sent to clients that negotiated the `turnSettingsUpdates` capability.
+    TurnSettingsUpdated => "turn/settings/updated" (v2::TurnSettingsUpdatedNotification),
     ItemStarted => "item/started" (v2::ItemStartedNotification),
     ItemCompleted => "item/completed" (v2::ItemCompletedNotification),
     /// This event is internal-only. Used by Codex Cloud.
@@ -656,6 +733,7 @@ mod tests {
     use codex_protocol::protocol::AskForApproval;
     use pretty_assertions::assert_eq;
     use serde_json::json;
+    use std::collections::HashMap;
     use std::path::PathBuf;
 
     #[test]
@@ -995,4 +1073,57 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn serialize_initialize_with_capabilities() -> Result<()> {
+        let request = ClientRequest::Initialize {
+            request_id: RequestId::Integer(8),
+            params: v1::InitializeParams {
+                client_info: v1::ClientInfo {
+                    name: "codex_vscode".to_string(),
+                    title: None,
+                    version: "0.1.0".to_string(),
+                },
+                protocol_version: Some(PROTOCOL_VERSION),
+                capabilities: Some(HashMap::from([(
+                    Capability::TurnSettingsUpdates.to_string(),
+                    true,
+                )])),
+            },
+        };
+        assert_eq!(
+            json!({
+                "method": "initialize",
+                "id": 8,
+                "params": {
+                    "clientInfo": {
+                        "name": "codex_vscode",
+                        "title": null,
+                        "version": "0.1.0"
+                    },
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {
+                        "turnSettingsUpdates": true
+                    }
+                }
+            }),
+            serde_json::to_value(&request)?,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn capability_wire_names_round_trip() {
+        for capability in Capability::ALL {
+            assert_eq!(
+                Some(*capability),
+                Capability::from_wire_name(&capability.to_string())
+            );
+            assert_eq!(
+                json!(capability.to_string()),
+                serde_json::to_value(capability).expect("capability serializes"),
+            );
+        }
+        assert_eq!(None, Capability::from_wire_name("eventReplay"));
+    }
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -6,6 +7,7 @@ use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::config_types::Verbosity;
+use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::parse_command::ParsedCommand;
@@ -28,11 +30,23 @@ use uuid::Uuid;
 // Reuse shared types defined in `common.rs`.
 use crate::protocol::common::AuthMode;
 use crate::protocol::common::GitSha;
+use crate::protocol::v2::ThreadItem;
+use crate::protocol::v2::ThreadTokenUsage;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct InitializeParams {
     pub client_info: ClientInfo,
+    /// Protocol version the client was built against. The server rejects
+    /// versions newer than its own `PROTOCOL_VERSION`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub protocol_version: Option<u32>,
+    /// Optional features the client wants enabled, keyed by capability name.
+    /// Unknown names are ignored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub capabilities: Option<HashMap<String, bool>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
@@ -47,6 +61,11 @@ pub struct ClientInfo {
 #[serde(rename_all = "camelCase")]
 pub struct InitializeResponse {
     pub user_agent: String,
+    /// Protocol version spoken by the server.
+    pub protocol_version: u32,
+    /// Every optional feature the server supports, mapped to whether it was
+    /// enabled for this connection.
+    pub capabilities: BTreeMap<String, bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
@@ -113,12 +132,47 @@ pub struct GetConversationSummaryResponse {
     pub summary: ConversationSummary,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct GetConversationHistoryParams {
+    pub conversation_id: ThreadId,
+    /// Opaque cursor from a previous response's `nextCursor`; omit to start
+    /// from the first item.
+    pub cursor: Option<String>,
+    /// Maximum number of items to return. Defaults to 100, capped at 500.
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct GetConversationHistoryResponse {
+    pub conversation_id: ThreadId,
+    /// Completed items, oldest first, with the same ids as the live
+    /// `item/completed` notifications.
+    pub items: Vec<ConversationItem>,
+    /// Cursor for the next page; `None` once every item has been returned.
+    pub next_cursor: Option<String>,
+    pub model: String,
+    pub cwd: PathBuf,
+    /// Token usage totals as of the last completed model response.
+    pub token_usage: Option<ThreadTokenUsage>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationItem {
+    pub turn_id: String,
+    pub item: ThreadItem,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct ListConversationsParams {
     pub page_size: Option<usize>,
     pub cursor: Option<String>,
     pub model_providers: Option<Vec<String>>,
+    /// Only return conversations carrying every one of these tags.
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -134,6 +188,9 @@ pub struct ConversationSummary {
     pub cli_version: String,
     pub source: SessionSource,
     pub git_info: Option<ConversationGitInfo>,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -228,6 +285,9 @@ pub struct ApplyPatchApprovalParams {
     /// When set, the agent is asking the user to allow writes under this root
     /// for the remainder of the session (unclear if this is honored today).
     pub grant_root: Option<PathBuf>,
+    /// Files in `file_changes` that already had uncommitted changes in git.
+    #[serde(default)]
+    pub dirty_paths: Vec<PathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -303,6 +363,22 @@ pub struct ExecOneOffCommandResponse {
     pub stderr: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCustomPromptsParams {
+    /// Directory whose project prompts (`.codex/prompts` at the Git root) are
+    /// included. Defaults to the server's working directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cwd: Option<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCustomPromptsResponse {
+    pub custom_prompts: Vec<CustomPrompt>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct GetAuthStatusResponse {
@@ -406,6 +482,20 @@ pub struct SendUserTurnParams {
     pub summary: ReasoningSummary,
     /// Optional JSON Schema used to constrain the final assistant message for this turn.
     pub output_schema: Option<serde_json::Value>,
+    /// Settings that apply to this turn only. Once the turn finishes, the
+    /// conversation reverts to the values above.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub overrides: Option<SendUserTurnOverrides>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SendUserTurnOverrides {
+    pub model: Option<String>,
+    pub effort: Option<ReasoningEffort>,
+    pub approval_policy: Option<AskForApproval>,
+    pub sandbox_policy: Option<SandboxPolicy>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
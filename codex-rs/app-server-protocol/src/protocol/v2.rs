@@ -24,6 +24,7 @@ use codex_protocol::protocol::AskForApproval as CoreAskForApproval;
 use codex_protocol::protocol::CodexErrorInfo as CoreCodexErrorInfo;
 use codex_protocol::protocol::CreditsSnapshot as CoreCreditsSnapshot;
 use codex_protocol::protocol::NetworkAccess as CoreNetworkAccess;
+use codex_protocol::protocol::PrewarmPhaseTiming as CorePrewarmPhaseTiming;
 use codex_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use codex_protocol::protocol::RateLimitWindow as CoreRateLimitWindow;
 use codex_protocol::protocol::SessionSource as CoreSessionSource;
@@ -33,9 +34,13 @@ use codex_protocol::protocol::SkillInterface as CoreSkillInterface;
 use codex_protocol::protocol::SkillMetadata as CoreSkillMetadata;
 use codex_protocol::protocol::SkillScope as CoreSkillScope;
 use codex_protocol::protocol::SkillToolDependency as CoreSkillToolDependency;
+use codex_protocol::protocol::StartupAuth as CoreStartupAuth;
+use codex_protocol::protocol::StartupDiagnostics as CoreStartupDiagnostics;
 use codex_protocol::protocol::SubAgentSource as CoreSubAgentSource;
 use codex_protocol::protocol::TokenUsage as CoreTokenUsage;
 use codex_protocol::protocol::TokenUsageInfo as CoreTokenUsageInfo;
+use codex_protocol::protocol::ToolDescription as CoreToolDescription;
+use codex_protocol::protocol::ToolSource as CoreToolSource;
 use codex_protocol::user_input::ByteRange as CoreByteRange;
 use codex_protocol::user_input::TextElement as CoreTextElement;
 use codex_protocol::user_input::UserInput as CoreUserInput;
@@ -1155,6 +1160,58 @@ pub struct CommandExecResponse {
     pub stderr: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitDiffParams {
+    /// Directory inside the repository to diff.
+    pub cwd: PathBuf,
+    /// Defaults to `workingTree`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub target: Option<GitDiffTarget>,
+    /// Limit the diff to these paths, relative to `cwd`.
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type")]
+#[ts(export_to = "v2/")]
+pub enum GitDiffTarget {
+    /// Unstaged changes to tracked files, plus untracked files.
+    WorkingTree,
+    /// Changes staged in the index, compared with `HEAD`.
+    Staged,
+    /// A revision or range accepted by `git diff`, e.g. `HEAD~3..`.
+    Range { range: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitDiffResponse {
+    pub files: Vec<GitDiffFile>,
+    /// Unified diff text without ANSI colors.
+    pub diff: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitDiffFile {
+    /// Path relative to the repository root. For renames, the new path.
+    pub path: String,
+    pub old_path: Option<String>,
+    #[ts(type = "number")]
+    pub additions: u64,
+    #[ts(type = "number")]
+    pub deletions: u64,
+    pub binary: bool,
+    pub untracked: bool,
+}
+
 // === Threads, Turns, and Items ===
 // Thread APIs
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
@@ -1191,6 +1248,11 @@ pub struct ThreadStartResponse {
     pub approval_policy: AskForApproval,
     pub sandbox: SandboxPolicy,
     pub reasoning_effort: Option<ReasoningEffort>,
+    /// How the thread's session started. MCP servers are still connecting
+    /// when this is sent, so they are listed without timings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub startup: Option<StartupDiagnostics>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, JsonSchema, TS)]
@@ -1240,6 +1302,11 @@ pub struct ThreadResumeResponse {
     pub approval_policy: AskForApproval,
     pub sandbox: SandboxPolicy,
     pub reasoning_effort: Option<ReasoningEffort>,
+    /// How the thread's session started. MCP servers are still connecting
+    /// when this is sent, so they are listed without timings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub startup: Option<StartupDiagnostics>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, JsonSchema, TS)]
@@ -1281,6 +1348,146 @@ pub struct ThreadForkResponse {
     pub approval_policy: AskForApproval,
     pub sandbox: SandboxPolicy,
     pub reasoning_effort: Option<ReasoningEffort>,
+    /// How the thread's session started. MCP servers are still connecting
+    /// when this is sent, so they are listed without timings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub startup: Option<StartupDiagnostics>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct StartupDiagnostics {
+    /// Phases finished before the session was configured.
+    pub phases: Vec<StartupPhaseTiming>,
+    pub auth: StartupAuth,
+    /// Set when `startup_probe` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub provider_probe: Option<ProviderProbe>,
+    /// `AGENTS.md` files loaded into the instructions, in load order.
+    pub project_docs: Vec<ProjectDocFile>,
+    pub mcp_servers: Vec<McpServerStartup>,
+}
+
+impl From<CoreStartupDiagnostics> for StartupDiagnostics {
+    fn from(value: CoreStartupDiagnostics) -> Self {
+        Self {
+            phases: value.phases.into_iter().map(Into::into).collect(),
+            auth: value.auth.into(),
+            provider_probe: value.provider_probe.map(|probe| ProviderProbe {
+                reachable: probe.reachable,
+                latency_ms: probe.latency_ms,
+            }),
+            project_docs: value
+                .project_docs
+                .into_iter()
+                .map(|doc| ProjectDocFile {
+                    path: doc.path,
+                    bytes: doc.bytes,
+                })
+                .collect(),
+            mcp_servers: value
+                .mcp_servers
+                .into_iter()
+                .map(|server| McpServerStartup {
+                    server: server.server,
+                    connect_ms: server.connect_ms,
+                    tools: server.tools,
+                    error: server.error,
+                })
+                .collect(),
+        }
+    }
+}
+
+v2_enum_from_core!(
+    pub enum StartupPhase from codex_protocol::protocol::PrewarmPhase {
+        Config,
+        ProjectDocs,
+        Auth,
+        ModelCheck,
+        Rollout,
+        McpServers,
+        Sandbox,
+        ProviderProbe
+    }
+);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct StartupPhaseTiming {
+    pub phase: StartupPhase,
+    pub duration_ms: u64,
+}
+
+impl From<CorePrewarmPhaseTiming> for StartupPhaseTiming {
+    fn from(value: CorePrewarmPhaseTiming) -> Self {
+        Self {
+            phase: value.phase.into(),
+            duration_ms: value.duration_ms,
+        }
+    }
+}
+
+v2_enum_from_core!(
+    pub enum StartupAuthMode from codex_protocol::protocol::StartupAuthMode {
+        ApiKey,
+        Chatgpt,
+        ChatgptAuthTokens,
+        ProviderKey,
+        None
+    }
+);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct StartupAuth {
+    pub mode: StartupAuthMode,
+    /// Time since the ChatGPT tokens were last refreshed.
+    pub token_age_secs: Option<u64>,
+    pub refresh_due: bool,
+}
+
+impl From<CoreStartupAuth> for StartupAuth {
+    fn from(value: CoreStartupAuth) -> Self {
+        Self {
+            mode: value.mode.into(),
+            token_age_secs: value.token_age_secs,
+            refresh_due: value.refresh_due,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ProviderProbe {
+    pub reachable: bool,
+    pub latency_ms: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ProjectDocFile {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// One MCP server's startup; `connectMs` and `error` are unset while it is
+/// still connecting.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerStartup {
+    pub server: String,
+    pub connect_ms: Option<u64>,
+    pub tools: Option<u32>,
+    pub error: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1365,6 +1572,9 @@ pub struct ThreadListParams {
     /// Optional archived filter; when set to true, only archived threads are returned.
     /// If false or null, only non-archived threads are returned.
     pub archived: Option<bool>,
+    /// Optional tag filter; when set, only threads carrying every listed tag
+    /// are returned.
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
@@ -1441,6 +1651,66 @@ pub struct ThreadReadResponse {
     pub thread: Thread,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ToolListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ToolListResponse {
+    /// Tools the model could call on the thread's next turn, in the order
+    /// they are sent.
+    pub data: Vec<ModelTool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelTool {
+    /// Name the model uses to call the tool.
+    pub name: String,
+    pub source: ModelToolSource,
+    /// Tool definition as sent to the model, including its JSON schema.
+    pub spec: JsonValue,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type")]
+#[ts(export_to = "v2/")]
+pub enum ModelToolSource {
+    /// Provided by Codex itself.
+    BuiltIn,
+    /// Provided by the named MCP server.
+    Mcp { server: String },
+    /// Registered by the client when the thread started.
+    Dynamic,
+}
+
+impl From<CoreToolSource> for ModelToolSource {
+    fn from(value: CoreToolSource) -> Self {
+        match value {
+            CoreToolSource::BuiltIn => Self::BuiltIn,
+            CoreToolSource::Mcp { server } => Self::Mcp { server },
+            CoreToolSource::Dynamic => Self::Dynamic,
+        }
+    }
+}
+
+impl From<CoreToolDescription> for ModelTool {
+    fn from(value: CoreToolDescription) -> Self {
+        Self {
+            name: value.name,
+            source: value.source.into(),
+            spec: value.spec,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1670,6 +1940,11 @@ pub struct Thread {
     pub source: SessionSource,
     /// Optional Git metadata captured when the thread was created.
     pub git_info: Option<GitInfo>,
+    /// User-facing title, set explicitly or derived from the first user message.
+    pub title: Option<String>,
+    /// Tags attached to the thread when it was started.
+    #[serde(default)]
+    pub tags: Vec<String>,
     /// Only populated on `thread/resume`, `thread/rollback`, `thread/fork`, and `thread/read`
     /// (when `includeTurns` is true) responses.
     /// For all other responses and notifications returning a Thread,
@@ -1976,6 +2251,9 @@ pub enum UserInput {
     LocalImage {
         path: PathBuf,
     },
+    LocalFile {
+        path: PathBuf,
+    },
     Skill {
         name: String,
         path: PathBuf,
@@ -1998,6 +2276,7 @@ impl UserInput {
             },
             UserInput::Image { url } => CoreUserInput::Image { image_url: url },
             UserInput::LocalImage { path } => CoreUserInput::LocalImage { path },
+            UserInput::LocalFile { path } => CoreUserInput::LocalFile { path },
             UserInput::Skill { name, path } => CoreUserInput::Skill { name, path },
             UserInput::Mention { name, path } => CoreUserInput::Mention { name, path },
         }
@@ -2016,6 +2295,7 @@ impl From<CoreUserInput> for UserInput {
             },
             CoreUserInput::Image { image_url } => UserInput::Image { url: image_url },
             CoreUserInput::LocalImage { path } => UserInput::LocalImage { path },
+            CoreUserInput::LocalFile { path } => UserInput::LocalFile { path },
             CoreUserInput::Skill { name, path } => UserInput::Skill { name, path },
             CoreUserInput::Mention { name, path } => UserInput::Mention { name, path },
             _ => unreachable!("unsupported user input variant"),
@@ -2317,6 +2597,24 @@ pub struct ThreadNameUpdatedNotification {
     pub thread_name: Option<String>,
 }
 
+/// Sent when a loaded thread went unused for `thread_idle_timeout_ms` and
+/// was unloaded. The next request for the thread loads it again.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSuspendedNotification {
+    pub thread_id: String,
+}
+
+/// Sent when a suspended thread was loaded again from its rollout because a
+/// request referred to it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadResumedNotification {
+    pub thread_id: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2353,6 +2651,33 @@ pub struct TurnDiffUpdatedNotification {
     pub diff: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// Files the turn read or wrote, relative to the thread's working directory.
+/// Sent once when the turn finishes, and only if it touched any files. Paths
+/// from shell commands are inferred and advisory.
+pub struct TurnFilesUpdatedNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub files_read: Vec<String>,
+    pub files_written: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// Settings in effect for a turn, sent when the turn starts. Requires the
+/// `turnSettingsUpdates` capability.
+pub struct TurnSettingsUpdatedNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub model: String,
+    pub reasoning_effort: Option<ReasoningEffort>,
+    pub approval_policy: AskForApproval,
+    pub sandbox_policy: SandboxPolicy,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2598,6 +2923,10 @@ pub struct FileChangeRequestApprovalParams {
     /// [UNSTABLE] When set, the agent is asking the user to allow writes under this root
     /// for the remainder of the session (unclear if this is honored today).
     pub grant_root: Option<PathBuf>,
+    /// Files in the change that already had uncommitted changes in git
+    /// (modified, staged, or untracked) when the patch was proposed.
+    #[serde(default)]
+    pub dirty_paths: Vec<PathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -41,6 +41,7 @@ use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
 use codex_app_server_protocol::NewConversationParams;
 use codex_app_server_protocol::NewConversationResponse;
+use codex_app_server_protocol::PROTOCOL_VERSION;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::SandboxPolicy;
 use codex_app_server_protocol::SendUserMessageParams;
@@ -419,6 +420,8 @@ impl CodexClient {
                     title: Some("Codex Toy App Server".to_string()),
                     version: env!("CARGO_PKG_VERSION").to_string(),
                 },
+                protocol_version: Some(PROTOCOL_VERSION),
+                capabilities: None,
             },
         };
 
@@ -888,6 +891,7 @@ impl CodexClient {
             item_id,
             reason,
             grant_root,
+            dirty_paths,
         } = params;
 
         println!(
@@ -899,6 +903,9 @@ impl CodexClient {
         if let Some(grant_root) = grant_root.as_deref() {
             println!("< grant root: {}", grant_root.display());
         }
+        for path in &dirty_paths {
+            println!("< has uncommitted changes: {}", path.display());
+        }
 
         let response = FileChangeRequestApprovalResponse {
             decision: FileChangeApprovalDecision::Accept,
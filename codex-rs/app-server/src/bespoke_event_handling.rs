@@ -65,9 +65,11 @@ use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnCompletedNotification;
 use codex_app_server_protocol::TurnDiffUpdatedNotification;
 use codex_app_server_protocol::TurnError;
+use codex_app_server_protocol::TurnFilesUpdatedNotification;
 use codex_app_server_protocol::TurnInterruptResponse;
 use codex_app_server_protocol::TurnPlanStep;
 use codex_app_server_protocol::TurnPlanUpdatedNotification;
+use codex_app_server_protocol::TurnSettingsUpdatedNotification;
 use codex_app_server_protocol::TurnStatus;
 use codex_app_server_protocol::build_turns_from_event_msgs;
 use codex_core::CodexThread;
@@ -85,6 +87,7 @@ use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::TokenCountEvent;
 use codex_core::protocol::TurnDiffEvent;
+use codex_core::protocol::TurnFilesEvent;
 use codex_core::review_format::format_review_findings_block;
 use codex_core::review_prompts;
 use codex_protocol::ThreadId;
@@ -119,7 +122,23 @@ pub(crate) async fn apply_bespoke_event_handling(
         msg,
     } = event;
     match msg {
-        EventMsg::TurnStarted(_) => {}
+        EventMsg::TurnStarted(turn_started_event) => {
+            if let ApiVersion::V2 = api_version
+                && let Some(settings) = turn_started_event.settings
+            {
+                let notification = TurnSettingsUpdatedNotification {
+                    thread_id: conversation_id.to_string(),
+                    turn_id: event_turn_id,
+                    model: settings.model,
+                    reasoning_effort: settings.reasoning_effort,
+                    approval_policy: settings.approval_policy.into(),
+                    sandbox_policy: settings.sandbox_policy.into(),
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::TurnSettingsUpdated(notification))
+                    .await;
+            }
+        }
         EventMsg::TurnComplete(_ev) => {
             handle_turn_complete(
                 conversation_id,
@@ -135,6 +154,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             changes,
             reason,
             grant_root,
+            dirty_paths,
         }) => match api_version {
             ApiVersion::V1 => {
                 let params = ApplyPatchApprovalParams {
@@ -143,6 +163,7 @@ pub(crate) async fn apply_bespoke_event_handling(
                     file_changes: changes.clone(),
                     reason,
                     grant_root,
+                    dirty_paths,
                 };
                 let rx = outgoing
                     .send_request(ServerRequestPayload::ApplyPatchApproval(params))
@@ -184,6 +205,7 @@ pub(crate) async fn apply_bespoke_event_handling(
                     item_id: item_id.clone(),
                     reason,
                     grant_root,
+                    dirty_paths,
                 };
                 let rx = outgoing
                     .send_request(ServerRequestPayload::FileChangeRequestApproval(params))
@@ -211,6 +233,8 @@ pub(crate) async fn apply_bespoke_event_handling(
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
+            cwd_outside_workspace: _,
+            cwd_writable: _,
         }) => match api_version {
             ApiVersion::V1 => {
                 let params = ExecCommandApprovalParams {
@@ -703,7 +727,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             let turn_error = TurnError {
                 message: ev.message,
                 codex_error_info: ev.codex_error_info.map(V2CodexErrorInfo::from),
-                additional_details: None,
+                additional_details: ev.details,
             };
             handle_error(conversation_id, turn_error.clone(), &turn_summary_store).await;
             outgoing
@@ -1134,6 +1158,16 @@ pub(crate) async fn apply_bespoke_event_handling(
             )
             .await;
         }
+        EventMsg::TurnFiles(turn_files_event) => {
+            handle_turn_files(
+                conversation_id,
+                &event_turn_id,
+                turn_files_event,
+                api_version,
+                outgoing.as_ref(),
+            )
+            .await;
+        }
         EventMsg::PlanUpdate(plan_update_event) => {
             handle_turn_plan_update(
                 conversation_id,
@@ -1168,6 +1202,32 @@ async fn handle_turn_diff(
     }
 }
 
+async fn handle_turn_files(
+    conversation_id: ThreadId,
+    event_turn_id: &str,
+    turn_files_event: TurnFilesEvent,
+    api_version: ApiVersion,
+    outgoing: &OutgoingMessageSender,
+) {
+    if let ApiVersion::V2 = api_version {
+        let to_strings = |paths: Vec<PathBuf>| {
+            paths
+                .into_iter()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect()
+        };
+        let notification = TurnFilesUpdatedNotification {
+            thread_id: conversation_id.to_string(),
+            turn_id: event_turn_id.to_string(),
+            files_read: to_strings(turn_files_event.files_read),
+            files_written: to_strings(turn_files_event.files_written),
+        };
+        outgoing
+            .send_server_notification(ServerNotification::TurnFilesUpdated(notification))
+            .await;
+    }
+}
+
 async fn handle_turn_plan_update(
     conversation_id: ThreadId,
     event_turn_id: &str,
@@ -2394,6 +2454,7 @@ mod tests {
             },
             duration: Duration::from_nanos(92708),
             result: Ok(result),
+            argument_errors: Vec::new(),
         };
 
         let thread_id = ThreadId::new().to_string();
@@ -2437,6 +2498,7 @@ mod tests {
             },
             duration: Duration::from_millis(1),
             result: Err("boom".to_string()),
+            argument_errors: Vec::new(),
         };
 
         let thread_id = ThreadId::new().to_string();
@@ -2524,4 +2586,46 @@ mod tests {
         assert!(rx.try_recv().is_err(), "no messages expected");
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_handle_turn_files_emits_v2_notification() -> Result<()> {
+        let (tx, mut rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let outgoing = OutgoingMessageSender::new(tx);
+        let conversation_id = ThreadId::new();
+
+        handle_turn_files(
+            conversation_id,
+            "turn-1",
+            TurnFilesEvent {
+                files_read: vec![PathBuf::from("src/lib.rs")],
+                files_written: vec![PathBuf::from("README.md")],
+            },
+            ApiVersion::V2,
+            &outgoing,
+        )
+        .await;
+
+        let msg = rx
+            .recv()
+            .await
+            .ok_or_else(|| anyhow!("should send one notification"))?;
+        match msg {
+            OutgoingMessage::AppServerNotification(ServerNotification::TurnFilesUpdated(
+                notification,
+            )) => {
+                assert_eq!(
+                    notification,
+                    TurnFilesUpdatedNotification {
+                        thread_id: conversation_id.to_string(),
+                        turn_id: "turn-1".to_string(),
+                        files_read: vec!["src/lib.rs".to_string()],
+                        files_written: vec!["README.md".to_string()],
+                    }
+                );
+            }
+            other => bail!("unexpected message: {other:?}"),
+        }
+        assert!(rx.try_recv().is_err(), "no extra messages expected");
+        Ok(())
+    }
 }
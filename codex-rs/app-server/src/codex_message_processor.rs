@@ -2,6 +2,10 @@ use crate::bespoke_event_handling::apply_bespoke_event_handling;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::fuzzy_file_search::run_fuzzy_file_search;
+use crate::idle_threads::SuspendedThread;
+use crate::idle_threads::sweep_interval;
+use crate::idle_threads::thread_to_forget;
+use crate::idle_threads::thread_to_wake;
 use crate::models::supported_models;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotification;
@@ -43,10 +47,16 @@ use codex_app_server_protocol::GetAccountRateLimitsResponse;
 use codex_app_server_protocol::GetAccountResponse;
 use codex_app_server_protocol::GetAuthStatusParams;
 use codex_app_server_protocol::GetAuthStatusResponse;
+use codex_app_server_protocol::GetConversationHistoryParams;
+use codex_app_server_protocol::GetConversationHistoryResponse;
 use codex_app_server_protocol::GetConversationSummaryParams;
 use codex_app_server_protocol::GetConversationSummaryResponse;
 use codex_app_server_protocol::GetUserAgentResponse;
 use codex_app_server_protocol::GetUserSavedConfigResponse;
+use codex_app_server_protocol::GitDiffFile;
+use codex_app_server_protocol::GitDiffParams;
+use codex_app_server_protocol::GitDiffResponse;
+use codex_app_server_protocol::GitDiffTarget;
 use codex_app_server_protocol::GitDiffToRemoteResponse;
 use codex_app_server_protocol::GitInfo as ApiGitInfo;
 use codex_app_server_protocol::InputItem as WireInputItem;
@@ -54,6 +64,8 @@ use codex_app_server_protocol::InterruptConversationParams;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::ListConversationsParams;
 use codex_app_server_protocol::ListConversationsResponse;
+use codex_app_server_protocol::ListCustomPromptsParams;
+use codex_app_server_protocol::ListCustomPromptsResponse;
 use codex_app_server_protocol::ListMcpServerStatusParams;
 use codex_app_server_protocol::ListMcpServerStatusResponse;
 use codex_app_server_protocol::LoginAccountParams;
@@ -85,6 +97,7 @@ use codex_app_server_protocol::ReviewTarget as ApiReviewTarget;
 use codex_app_server_protocol::SandboxMode;
 use codex_app_server_protocol::SendUserMessageParams;
 use codex_app_server_protocol::SendUserMessageResponse;
+use codex_app_server_protocol::SendUserTurnOverrides;
 use codex_app_server_protocol::SendUserTurnParams;
 use codex_app_server_protocol::SendUserTurnResponse;
 use codex_app_server_protocol::ServerNotification;
@@ -109,6 +122,7 @@ use codex_app_server_protocol::ThreadReadParams;
 use codex_app_server_protocol::ThreadReadResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
+use codex_app_server_protocol::ThreadResumedNotification;
 use codex_app_server_protocol::ThreadRollbackParams;
 use codex_app_server_protocol::ThreadSetNameParams;
 use codex_app_server_protocol::ThreadSetNameResponse;
@@ -117,8 +131,11 @@ use codex_app_server_protocol::ThreadSourceKind;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadStartResponse;
 use codex_app_server_protocol::ThreadStartedNotification;
+use codex_app_server_protocol::ThreadSuspendedNotification;
 use codex_app_server_protocol::ThreadUnarchiveParams;
 use codex_app_server_protocol::ThreadUnarchiveResponse;
+use codex_app_server_protocol::ToolListParams;
+use codex_app_server_protocol::ToolListResponse;
 use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnError;
 use codex_app_server_protocol::TurnInterruptParams;
@@ -153,6 +170,7 @@ use codex_core::config::edit::ConfigEdit;
 use codex_core::config::edit::ConfigEditsBuilder;
 use codex_core::config::types::McpServerTransportConfig;
 use codex_core::config_loader::CloudRequirementsLoader;
+use codex_core::custom_prompts::discover_custom_prompts;
 use codex_core::default_client::get_codex_user_agent;
 use codex_core::error::CodexErr;
 use codex_core::exec::ExecParams;
@@ -160,9 +178,13 @@ use codex_core::exec_env::create_env;
 use codex_core::features::Feature;
 use codex_core::find_archived_thread_path_by_id_str;
 use codex_core::find_thread_path_by_id_str;
+use codex_core::git_diff::GitDiffOptions;
+use codex_core::git_diff::GitDiffTarget as CoreGitDiffTarget;
+use codex_core::git_diff::get_git_diff;
 use codex_core::git_info::git_diff_to_remote;
 use codex_core::mcp::collect_mcp_snapshot;
 use codex_core::mcp::group_tools_by_server;
+use codex_core::models_manager::manager::RefreshStrategy;
 use codex_core::parse_cursor;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
@@ -188,6 +210,7 @@ use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::dynamic_tools::DynamicToolSpec as CoreDynamicToolSpec;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::AgentStatus;
 use codex_protocol::protocol::GitInfo as CoreGitInfo;
 use codex_protocol::protocol::McpAuthStatus as CoreMcpAuthStatus;
@@ -237,6 +260,8 @@ pub(crate) type TurnSummaryStore = Arc<Mutex<HashMap<ThreadId, TurnSummary>>>;
 
 const THREAD_LIST_DEFAULT_LIMIT: usize = 25;
 const THREAD_LIST_MAX_LIMIT: usize = 100;
+const CONVERSATION_HISTORY_DEFAULT_LIMIT: usize = 100;
+const CONVERSATION_HISTORY_MAX_LIMIT: usize = 500;
 
 // Duration before a ChatGPT login attempt is abandoned.
 const LOGIN_CHATGPT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
@@ -266,7 +291,9 @@ pub(crate) struct CodexMessageProcessor {
     cli_overrides: Vec<(String, TomlValue)>,
     cloud_requirements: CloudRequirementsLoader,
     conversation_listeners: HashMap<Uuid, oneshot::Sender<()>>,
-    listener_thread_ids_by_subscription: HashMap<Uuid, ThreadId>,
+    listener_subscriptions: HashMap<Uuid, ListenerSubscription>,
+    /// Threads unloaded after going idle, loaded again on their next request.
+    suspended_threads: HashMap<ThreadId, SuspendedThread>,
     active_login: Arc<Mutex<Option<ActiveLogin>>>,
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: PendingInterrupts,
@@ -283,6 +310,14 @@ pub(crate) enum ApiVersion {
     V2,
 }
 
+/// A client's subscription to a thread's events.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ListenerSubscription {
+    pub(crate) thread_id: ThreadId,
+    pub(crate) experimental_raw_events: bool,
+    pub(crate) api_version: ApiVersion,
+}
+
 pub(crate) struct CodexMessageProcessorArgs {
     pub(crate) auth_manager: Arc<AuthManager>,
     pub(crate) thread_manager: Arc<ThreadManager>,
@@ -338,7 +373,8 @@ impl CodexMessageProcessor {
             cli_overrides,
             cloud_requirements,
             conversation_listeners: HashMap::new(),
-            listener_thread_ids_by_subscription: HashMap::new(),
+            listener_subscriptions: HashMap::new(),
+            suspended_threads: HashMap::new(),
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
             pending_rollbacks: Arc::new(Mutex::new(HashMap::new())),
@@ -421,6 +457,12 @@ impl CodexMessageProcessor {
     }
 
     pub async fn process_request(&mut self, request: ClientRequest) {
+        if let Some(thread_id) = thread_to_wake(&request) {
+            self.wake_suspended_thread(thread_id).await;
+        }
+        if let Some(thread_id) = thread_to_forget(&request) {
+            self.suspended_threads.remove(&thread_id);
+        }
         match request {
             ClientRequest::Initialize { .. } => {
                 panic!("Initialize should be handled in MessageProcessor");
@@ -459,6 +501,9 @@ impl CodexMessageProcessor {
             ClientRequest::SkillsList { request_id, params } => {
                 self.skills_list(request_id, params).await;
             }
+            ClientRequest::ToolList { request_id, params } => {
+                self.tool_list(request_id, params).await;
+            }
             ClientRequest::AppsList { request_id, params } => {
                 self.apps_list(request_id, params).await;
             }
@@ -483,6 +528,9 @@ impl CodexMessageProcessor {
             ClientRequest::GetConversationSummary { request_id, params } => {
                 self.get_thread_summary(request_id, params).await;
             }
+            ClientRequest::GetConversationHistory { request_id, params } => {
+                self.get_conversation_history(request_id, params).await;
+            }
             ClientRequest::ListConversations { request_id, params } => {
                 self.handle_list_conversations(request_id, params).await;
             }
@@ -555,6 +603,9 @@ impl CodexMessageProcessor {
             ClientRequest::GitDiffToRemote { request_id, params } => {
                 self.git_diff_to_origin(request_id, params.cwd).await;
             }
+            ClientRequest::GitDiff { request_id, params } => {
+                self.git_diff(request_id, params).await;
+            }
             ClientRequest::LoginApiKey { request_id, params } => {
                 self.login_api_key_v1(request_id, params).await;
             }
@@ -600,6 +651,9 @@ impl CodexMessageProcessor {
             ClientRequest::FuzzyFileSearch { request_id, params } => {
                 self.fuzzy_file_search(request_id, params).await;
             }
+            ClientRequest::ListCustomPrompts { request_id, params } => {
+                self.list_custom_prompts(request_id, params).await;
+            }
             ClientRequest::OneOffCommandExec { request_id, params } => {
                 self.exec_one_off_command(request_id, params).await;
             }
@@ -1422,7 +1476,10 @@ impl CodexMessageProcessor {
         }
 
         let cwd = params.cwd.unwrap_or_else(|| self.config.cwd.clone());
-        let env = create_env(&self.config.shell_environment_policy);
+        let env = create_env(
+            &self.config.shell_environment_policy,
+            self.config.dotenv.as_deref(),
+        );
         let timeout_ms = params
             .timeout_ms
             .and_then(|timeout_ms| u64::try_from(timeout_ms).ok());
@@ -1436,6 +1493,7 @@ impl CodexMessageProcessor {
             windows_sandbox_level,
             justification: None,
             arg0: None,
+            limits: self.config.exec_limits,
         };
 
         let requested_policy = params.sandbox_policy.map(|policy| policy.to_core());
@@ -1711,6 +1769,7 @@ impl CodexMessageProcessor {
                     approval_policy: config_snapshot.approval_policy.into(),
                     sandbox: config_snapshot.sandbox_policy.into(),
                     reasoning_effort: config_snapshot.reasoning_effort,
+                    startup: session_configured.startup.map(Into::into),
                 };
 
                 // Auto-attach a thread listener when starting a thread.
@@ -2059,6 +2118,7 @@ impl CodexMessageProcessor {
             model_providers,
             source_kinds,
             archived,
+            tags,
         } = params;
 
         let requested_page_size = limit
@@ -2075,6 +2135,7 @@ impl CodexMessageProcessor {
                 cursor,
                 model_providers,
                 source_kinds,
+                tags,
                 core_sort_key,
                 archived.unwrap_or(false),
             )
@@ -2245,9 +2306,9 @@ impl CodexMessageProcessor {
     /// Best-effort: attach a listener for thread_id if missing.
     pub(crate) async fn try_attach_thread_listener(&mut self, thread_id: ThreadId) {
         if self
-            .listener_thread_ids_by_subscription
+            .listener_subscriptions
             .values()
-            .any(|entry| *entry == thread_id)
+            .any(|subscription| subscription.thread_id == thread_id)
         {
             return;
         }
@@ -2263,6 +2324,167 @@ impl CodexMessageProcessor {
         }
     }
 
+    /// How often the server should call [`Self::suspend_idle_threads`], or
+    /// `None` when idle threads stay loaded.
+    pub(crate) fn idle_sweep_interval(&self) -> Option<Duration> {
+        self.config.thread_idle_timeout.map(sweep_interval)
+    }
+
+    /// Shuts down and unloads every thread that has gone without ops or
+    /// events for longer than `thread_idle_timeout`. Threads with a running
+    /// turn, and threads without a rollout to load them again from, are left
+    /// alone.
+    pub(crate) async fn suspend_idle_threads(&mut self) {
+        let Some(idle_timeout) = self.config.thread_idle_timeout else {
+            return;
+        };
+        let mut suspended = Vec::new();
+        for thread_id in self.thread_manager.list_thread_ids().await {
+            let Ok(thread) = self.thread_manager.get_thread(thread_id).await else {
+                continue;
+            };
+            if thread.idle_for() < idle_timeout
+                || matches!(thread.agent_status().await, AgentStatus::Running)
+            {
+                continue;
+            }
+            let Some(rollout_path) = thread.rollout_path() else {
+                continue;
+            };
+            let config = thread.config_snapshot().await;
+            self.thread_manager.remove_thread(&thread_id).await;
+            let subscriptions = self.detach_thread_listeners(thread_id);
+            self.suspended_threads.insert(
+                thread_id,
+                SuspendedThread {
+                    rollout_path,
+                    config,
+                    subscriptions,
+                },
+            );
+            suspended.push((thread_id, thread));
+        }
+
+        let mut shutdowns = tokio::task::JoinSet::new();
+        for (thread_id, thread) in suspended {
+            shutdowns.spawn(async move {
+                shutdown_thread(thread_id, &thread).await;
+                thread_id
+            });
+        }
+        while let Some(result) = shutdowns.join_next().await {
+            let Ok(thread_id) = result else {
+                continue;
+            };
+            info!("suspended idle thread {thread_id}");
+            self.outgoing
+                .send_server_notification(ServerNotification::ThreadSuspended(
+                    ThreadSuspendedNotification {
+                        thread_id: thread_id.to_string(),
+                    },
+                ))
+                .await;
+        }
+    }
+
+    /// Cancels the listeners attached to `thread_id` and returns their
+    /// subscriptions.
+    fn detach_thread_listeners(
+        &mut self,
+        thread_id: ThreadId,
+    ) -> Vec<(Uuid, ListenerSubscription)> {
+        let subscription_ids: Vec<Uuid> = self
+            .listener_subscriptions
+            .iter()
+            .filter(|(_, subscription)| subscription.thread_id == thread_id)
+            .map(|(subscription_id, _)| *subscription_id)
+            .collect();
+        subscription_ids
+            .into_iter()
+            .filter_map(|subscription_id| {
+                if let Some(cancel) = self.conversation_listeners.remove(&subscription_id) {
+                    let _ = cancel.send(());
+                }
+                self.listener_subscriptions
+                    .remove(&subscription_id)
+                    .map(|subscription| (subscription_id, subscription))
+            })
+            .collect()
+    }
+
+    /// Loads a suspended thread again from its rollout, with the settings it
+    /// had when it was suspended and its listeners restored under their old
+    /// subscription ids. Does nothing for threads that are not suspended.
+    async fn wake_suspended_thread(&mut self, thread_id: ThreadId) {
+        let Some(SuspendedThread {
+            rollout_path,
+            config: snapshot,
+            subscriptions,
+        }) = self.suspended_threads.remove(&thread_id)
+        else {
+            return;
+        };
+
+        let overrides = ConfigOverrides {
+            model: Some(snapshot.model.clone()),
+            model_provider: Some(snapshot.model_provider_id.clone()),
+            cwd: Some(snapshot.cwd.clone()),
+            codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.clone(),
+            model_personality: snapshot.personality,
+            ..Default::default()
+        };
+        let mut config = match derive_config_from_params(
+            &self.cli_overrides,
+            None,
+            overrides,
+            &self.cloud_requirements,
+        )
+        .await
+        {
+            Ok(config) => config,
+            Err(err) => {
+                error!("failed to derive config to resume suspended thread {thread_id}: {err}");
+                return;
+            }
+        };
+        if let Err(err) = config.approval_policy.set(snapshot.approval_policy) {
+            warn!("could not restore approval policy of thread {thread_id}: {err}");
+        }
+        if let Err(err) = config.sandbox_policy.set(snapshot.sandbox_policy) {
+            warn!("could not restore sandbox policy of thread {thread_id}: {err}");
+        }
+        config.model_reasoning_effort = snapshot.reasoning_effort;
+        config.tool_toggles = snapshot.tool_toggles;
+
+        if let Err(err) = self
+            .thread_manager
+            .resume_thread_from_rollout(config, rollout_path, self.auth_manager.clone())
+            .await
+        {
+            error!("failed to resume suspended thread {thread_id}: {err}");
+            return;
+        }
+        for (subscription_id, subscription) in subscriptions {
+            if let Err(err) = self
+                .attach_listener_subscription(subscription_id, subscription)
+                .await
+            {
+                warn!(
+                    "failed to restore listener for thread {thread_id}: {message}",
+                    message = err.message
+                );
+            }
+        }
+        info!("resumed suspended thread {thread_id}");
+        self.outgoing
+            .send_server_notification(ServerNotification::ThreadResumed(
+                ThreadResumedNotification {
+                    thread_id: thread_id.to_string(),
+                },
+            ))
+            .await;
+    }
+
     async fn thread_resume(&mut self, request_id: RequestId, params: ThreadResumeParams) {
         let ThreadResumeParams {
             thread_id,
@@ -2455,6 +2677,7 @@ impl CodexMessageProcessor {
                     approval_policy: session_configured.approval_policy.into(),
                     sandbox: session_configured.sandbox_policy.into(),
                     reasoning_effort: session_configured.reasoning_effort,
+                    startup: session_configured.startup.map(Into::into),
                 };
 
                 self.outgoing.send_response(request_id, response).await;
@@ -2667,6 +2890,7 @@ impl CodexMessageProcessor {
             approval_policy: session_configured.approval_policy.into(),
             sandbox: session_configured.sandbox_policy.into(),
             reasoning_effort: session_configured.reasoning_effort,
+            startup: session_configured.startup.map(Into::into),
         };
 
         self.outgoing.send_response(request_id, response).await;
@@ -2677,6 +2901,66 @@ impl CodexMessageProcessor {
             .await;
     }
 
+    async fn get_conversation_history(
+        &self,
+        request_id: RequestId,
+        params: GetConversationHistoryParams,
+    ) {
+        let GetConversationHistoryParams {
+            conversation_id,
+            cursor,
+            limit,
+        } = params;
+
+        let Ok(conversation) = self.thread_manager.get_thread(conversation_id).await else {
+            self.send_invalid_request_error(
+                request_id,
+                format!("conversation not loaded: {conversation_id}"),
+            )
+            .await;
+            return;
+        };
+        let offset = match cursor.as_deref().map(str::parse::<usize>) {
+            None => 0,
+            Some(Ok(offset)) => offset,
+            Some(Err(_)) => {
+                self.send_invalid_request_error(
+                    request_id,
+                    format!("invalid cursor: {}", cursor.unwrap_or_default()),
+                )
+                .await;
+                return;
+            }
+        };
+        let limit = limit
+            .map_or(CONVERSATION_HISTORY_DEFAULT_LIMIT, |limit| limit as usize)
+            .clamp(1, CONVERSATION_HISTORY_MAX_LIMIT);
+
+        // Threads nobody has listened to yet have not announced any items.
+        let page =
+            self.outgoing
+                .conversation_history()
+                .page(&conversation_id.to_string(), offset, limit);
+        let (items, next_cursor, token_usage) = match page {
+            Some(page) => (
+                page.items,
+                page.next_offset.map(|offset| offset.to_string()),
+                page.token_usage,
+            ),
+            None => (Vec::new(), None, None),
+        };
+        let config_snapshot = conversation.config_snapshot().await;
+        let response = GetConversationHistoryResponse {
+            conversation_id,
+            items,
+            next_cursor,
+            model: config_snapshot.model,
+            cwd: config_snapshot.cwd,
+            token_usage,
+        };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
     async fn get_thread_summary(
         &self,
         request_id: RequestId,
@@ -2743,6 +3027,7 @@ impl CodexMessageProcessor {
             page_size,
             cursor,
             model_providers,
+            tags,
         } = params;
         let requested_page_size = page_size
             .unwrap_or(THREAD_LIST_DEFAULT_LIMIT)
@@ -2754,6 +3039,7 @@ impl CodexMessageProcessor {
                 cursor,
                 model_providers,
                 None,
+                tags,
                 CoreThreadSortKey::UpdatedAt,
                 false,
             )
@@ -2775,6 +3061,7 @@ impl CodexMessageProcessor {
         cursor: Option<String>,
         model_providers: Option<Vec<String>>,
         source_kinds: Option<Vec<ThreadSourceKind>>,
+        tags: Option<Vec<String>>,
         sort_key: CoreThreadSortKey,
         archived: bool,
     ) -> Result<(Vec<ConversationSummary>, Option<String>), JSONRPCErrorError> {
@@ -2865,6 +3152,10 @@ impl CodexMessageProcessor {
                         .as_ref()
                         .is_none_or(|filter| source_kind_matches(&summary.source, filter))
                 })
+                .filter(|summary| {
+                    tags.as_ref()
+                        .is_none_or(|tags| tags.iter().all(|tag| summary.tags.contains(tag)))
+                })
                 .collect::<Vec<_>>();
             if filtered.len() > remaining {
                 filtered.truncate(remaining);
@@ -3742,33 +4033,14 @@ impl CodexMessageProcessor {
 
         // If the thread is active, request shutdown and wait briefly.
         if let Some(conversation) = self.thread_manager.remove_thread(&thread_id).await {
+            self.outgoing
+                .conversation_history()
+                .forget(&thread_id.to_string());
             if let Some(ctx) = conversation.state_db() {
                 state_db_ctx = Some(ctx);
             }
             info!("thread {thread_id} was active; shutting down");
-            // Request shutdown.
-            match conversation.submit(Op::Shutdown).await {
-                Ok(_) => {
-                    // Poll agent status rather than consuming events so attached listeners do not block shutdown.
-                    let wait_for_shutdown = async {
-                        loop {
-                            if matches!(conversation.agent_status().await, AgentStatus::Shutdown) {
-                                break;
-                            }
-                            tokio::time::sleep(Duration::from_millis(50)).await;
-                        }
-                    };
-                    if tokio::time::timeout(Duration::from_secs(10), wait_for_shutdown)
-                        .await
-                        .is_err()
-                    {
-                        warn!("thread {thread_id} shutdown timed out; proceeding with archive");
-                    }
-                }
-                Err(err) => {
-                    error!("failed to submit Shutdown to thread {thread_id}: {err}");
-                }
-            }
+            shutdown_thread(thread_id, &conversation).await;
         }
 
         if state_db_ctx.is_none() {
@@ -3855,6 +4127,7 @@ impl CodexMessageProcessor {
             effort,
             summary,
             output_schema,
+            overrides,
         } = params;
 
         let Ok(conversation) = self.thread_manager.get_thread(conversation_id).await else {
@@ -3882,26 +4155,112 @@ impl CodexMessageProcessor {
             })
             .collect();
 
+        let SendUserTurnOverrides {
+            model: model_override,
+            effort: effort_override,
+            approval_policy: approval_policy_override,
+            sandbox_policy: sandbox_policy_override,
+        } = overrides.unwrap_or_default();
+        if let Some(policy) = approval_policy_override
+            && let Err(err) = self.config.approval_policy.can_set(&policy)
+        {
+            self.send_invalid_request_error(request_id, format!("invalid approval policy: {err}"))
+                .await;
+            return;
+        }
+        if let Some(policy) = sandbox_policy_override.as_ref()
+            && let Err(err) = self.config.sandbox_policy.can_set(policy)
+        {
+            self.send_invalid_request_error(request_id, format!("invalid sandbox policy: {err}"))
+                .await;
+            return;
+        }
+        if (model_override.is_some() || effort_override.is_some())
+            && let Err(message) = self
+                .validate_model_override(&model, model_override.as_deref(), effort_override)
+                .await
+        {
+            self.send_invalid_request_error(request_id, message).await;
+            return;
+        }
+
+        let has_any_overrides = model_override.is_some()
+            || effort_override.is_some()
+            || approval_policy_override.is_some()
+            || sandbox_policy_override.is_some();
+        let restore = has_any_overrides.then(|| Op::RestoreTurnSettings {
+            approval_policy,
+            sandbox_policy: sandbox_policy.clone(),
+            model: model.clone(),
+            effort,
+        });
+
         let _ = conversation
             .submit(Op::UserTurn {
                 items: mapped_items,
                 cwd,
-                approval_policy,
-                sandbox_policy,
-                model,
-                effort,
+                approval_policy: approval_policy_override.unwrap_or(approval_policy),
+                sandbox_policy: sandbox_policy_override.unwrap_or(sandbox_policy),
+                model: model_override.unwrap_or(model),
+                effort: effort_override.or(effort),
                 summary,
                 final_output_json_schema: output_schema,
                 collaboration_mode: None,
                 personality: None,
+                max_output_tokens: None,
+                service_tier: None,
             })
             .await;
+        // Core reverts one-off overrides when the turn ends, whether or not
+        // anyone is listening to the conversation.
+        if let Some(restore) = restore
+            && let Err(err) = conversation.submit(restore).await
+        {
+            error!("failed to schedule turn settings restore for {conversation_id}: {err}");
+        }
 
         self.outgoing
             .send_response(request_id, SendUserTurnResponse {})
             .await;
     }
 
+    /// Checks a one-off model override against the available models and a
+    /// one-off effort override against what the turn's model supports.
+    async fn validate_model_override(
+        &self,
+        model: &str,
+        model_override: Option<&str>,
+        effort_override: Option<ReasoningEffort>,
+    ) -> Result<(), String> {
+        let presets = self
+            .thread_manager
+            .list_models(&self.config, RefreshStrategy::OnlineIfUncached)
+            .await;
+        let turn_model = model_override.unwrap_or(model);
+        let preset = presets
+            .iter()
+            .find(|preset| preset.model == turn_model || preset.id == turn_model);
+        let Some(preset) = preset else {
+            // The conversation's own model may be a custom one the list does
+            // not know about; only overrides have to name a listed model.
+            if model_override.is_some_and(|requested| requested != model) {
+                return Err(format!("unknown model: {turn_model}"));
+            }
+            return Ok(());
+        };
+        if let Some(effort) = effort_override
+            && !preset
+                .supported_reasoning_efforts
+                .iter()
+                .any(|supported| supported.effort == effort)
+        {
+            return Err(format!(
+                "{turn_model} does not support reasoning effort {effort}"
+            ));
+        }
+        Ok(())
+    }
+
     async fn apps_list(&self, request_id: RequestId, params: AppsListParams) {
         let AppsListParams { cursor, limit } = params;
         let config = match self.load_latest_config().await {
@@ -3987,6 +4346,29 @@ impl CodexMessageProcessor {
             .await;
     }
 
+    async fn tool_list(&self, request_id: RequestId, params: ToolListParams) {
+        let (_, thread) = match self.load_thread(&params.thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        match thread.list_tools().await {
+            Ok(tools) => {
+                let data = tools.tools.into_iter().map(Into::into).collect();
+                self.outgoing
+                    .send_response(request_id, ToolListResponse { data })
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to list tools: {err}"))
+                    .await;
+            }
+        }
+    }
+
     async fn skills_list(&self, request_id: RequestId, params: SkillsListParams) {
         let SkillsListParams { cwds, force_reload } = params;
         let cwds = if cwds.is_empty() {
@@ -4433,11 +4815,8 @@ impl CodexMessageProcessor {
             Some(sender) => {
                 // Signal the spawned task to exit and acknowledge.
                 let _ = sender.send(());
-                if let Some(thread_id) = self
-                    .listener_thread_ids_by_subscription
-                    .remove(&subscription_id)
-                {
-                    info!("removed listener for thread {thread_id}");
+                if let Some(subscription) = self.listener_subscriptions.remove(&subscription_id) {
+                    info!("removed listener for thread {}", subscription.thread_id);
                 }
                 let response = RemoveConversationSubscriptionResponse {};
                 self.outgoing.send_response(request_id, response).await;
@@ -4459,6 +4838,25 @@ impl CodexMessageProcessor {
         experimental_raw_events: bool,
         api_version: ApiVersion,
     ) -> Result<Uuid, JSONRPCErrorError> {
+        let subscription = ListenerSubscription {
+            thread_id: conversation_id,
+            experimental_raw_events,
+            api_version,
+        };
+        self.attach_listener_subscription(Uuid::new_v4(), subscription)
+            .await
+    }
+
+    async fn attach_listener_subscription(
+        &mut self,
+        subscription_id: Uuid,
+        subscription: ListenerSubscription,
+    ) -> Result<Uuid, JSONRPCErrorError> {
+        let ListenerSubscription {
+            thread_id: conversation_id,
+            experimental_raw_events,
+            api_version,
+        } = subscription;
         let conversation = match self.thread_manager.get_thread(conversation_id).await {
             Ok(conv) => conv,
             Err(_) => {
@@ -4470,12 +4868,26 @@ impl CodexMessageProcessor {
             }
         };
 
-        let subscription_id = Uuid::new_v4();
+        // Start the history from what the thread already had on disk (the same
+        // turns a resume returns); later items are recorded as they are sent.
+        let history = self.outgoing.conversation_history();
+        let history_key = conversation_id.to_string();
+        if !history.is_tracked(&history_key) {
+            let turns = match conversation.rollout_path() {
+                Some(path) => read_event_msgs_from_rollout(&path).await.map_or_else(
+                    |_| Vec::new(),
+                    |events| build_turns_from_event_msgs(&events),
+                ),
+                None => Vec::new(),
+            };
+            history.seed(&history_key, &turns);
+        }
+
         let (cancel_tx, mut cancel_rx) = oneshot::channel();
         self.conversation_listeners
             .insert(subscription_id, cancel_tx);
-        self.listener_thread_ids_by_subscription
-            .insert(subscription_id, conversation_id);
+        self.listener_subscriptions
+            .insert(subscription_id, subscription);
 
         let outgoing_for_task = self.outgoing.clone();
         let pending_interrupts = self.pending_interrupts.clone();
@@ -4576,11 +4988,62 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn git_diff(&self, request_id: RequestId, params: GitDiffParams) {
+        let GitDiffParams { cwd, target, paths } = params;
+        let target = match target.unwrap_or(GitDiffTarget::WorkingTree) {
+            GitDiffTarget::WorkingTree => CoreGitDiffTarget::WorkingTree,
+            GitDiffTarget::Staged => CoreGitDiffTarget::Staged,
+            GitDiffTarget::Range { range } => CoreGitDiffTarget::Range(range),
+        };
+        let options = GitDiffOptions {
+            target,
+            paths,
+            color: false,
+        };
+        match get_git_diff(&cwd, &options).await {
+            Ok(Some(diff)) => {
+                let files = diff
+                    .files
+                    .into_iter()
+                    .map(|file| GitDiffFile {
+                        path: file.path,
+                        old_path: file.old_path,
+                        additions: file.additions,
+                        deletions: file.deletions,
+                        binary: file.binary,
+                        untracked: file.untracked,
+                    })
+                    .collect();
+                let response = GitDiffResponse {
+                    files,
+                    diff: diff.unified_diff,
+                };
+                self.outgoing.send_response(request_id, response).await;
+            }
+            Ok(None) => {
+                self.send_invalid_request_error(
+                    request_id,
+                    format!("not inside a git repository: {}", cwd.display()),
+                )
+                .await;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::InvalidInput => {
+                self.send_invalid_request_error(request_id, err.to_string())
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to compute git diff: {err}"))
+                    .await;
+            }
+        }
+    }
+
     async fn fuzzy_file_search(&mut self, request_id: RequestId, params: FuzzyFileSearchParams) {
         let FuzzyFileSearchParams {
             query,
             roots,
             cancellation_token,
+            recent_paths,
         } = params;
 
         let cancel_flag = match cancellation_token.clone() {
@@ -4600,7 +5063,15 @@ impl CodexMessageProcessor {
 
         let results = match query.as_str() {
             "" => vec![],
-            _ => run_fuzzy_file_search(query, roots, cancel_flag.clone()).await,
+            _ => {
+                run_fuzzy_file_search(
+                    query,
+                    roots,
+                    recent_paths.unwrap_or_default(),
+                    cancel_flag.clone(),
+                )
+                .await
+            }
         };
 
         if let Some(token) = cancellation_token {
@@ -4616,6 +5087,13 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn list_custom_prompts(&self, request_id: RequestId, params: ListCustomPromptsParams) {
+        let cwd = params.cwd.unwrap_or_else(|| self.config.cwd.clone());
+        let custom_prompts = discover_custom_prompts(&cwd).await;
+        let response = ListCustomPromptsResponse { custom_prompts };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
     async fn upload_feedback(&self, request_id: RequestId, params: FeedbackUploadParams) {
         if !self.config.feedback_enabled {
             let error = JSONRPCErrorError {
@@ -4867,6 +5345,29 @@ async fn derive_config_for_cwd(
         .await
 }
 
+/// Shuts `conversation` down and waits briefly for it to finish.
+async fn shutdown_thread(thread_id: ThreadId, conversation: &CodexThread) {
+    if let Err(err) = conversation.submit(Op::Shutdown).await {
+        error!("failed to submit Shutdown to thread {thread_id}: {err}");
+        return;
+    }
+    // Poll agent status rather than consuming events so attached listeners do not block shutdown.
+    let wait_for_shutdown = async {
+        loop {
+            if matches!(conversation.agent_status().await, AgentStatus::Shutdown) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    };
+    if tokio::time::timeout(Duration::from_secs(10), wait_for_shutdown)
+        .await
+        .is_err()
+    {
+        warn!("thread {thread_id} shutdown timed out; proceeding");
+    }
+}
+
 pub(crate) async fn read_summary_from_rollout(
     path: &Path,
     fallback_provider: &str,
@@ -4932,6 +5433,8 @@ pub(crate) async fn read_summary_from_rollout(
         cli_version: session_meta.cli_version,
         source: session_meta.source,
         git_info,
+        title: session_meta.title,
+        tags: session_meta.tags,
     })
 }
 
@@ -4998,6 +5501,8 @@ fn extract_conversation_summary(
         cli_version: session_meta.cli_version.clone(),
         source: session_meta.source.clone(),
         git_info,
+        title: session_meta.title.clone(),
+        tags: session_meta.tags.clone(),
     })
 }
 
@@ -5042,6 +5547,8 @@ fn build_ephemeral_thread(thread_id: ThreadId, config_snapshot: &ThreadConfigSna
         cli_version: env!("CARGO_PKG_VERSION").to_string(),
         source: config_snapshot.session_source.clone().into(),
         git_info: None,
+        title: None,
+        tags: Vec::new(),
         turns: Vec::new(),
     }
 }
@@ -5058,6 +5565,8 @@ pub(crate) fn summary_to_thread(summary: ConversationSummary) -> Thread {
         cli_version,
         source,
         git_info,
+        title,
+        tags,
     } = summary;
 
     let created_at = parse_datetime(timestamp.as_deref());
@@ -5079,6 +5588,8 @@ pub(crate) fn summary_to_thread(summary: ConversationSummary) -> Thread {
         cli_version,
         source: source.into(),
         git_info,
+        title,
+        tags,
         turns: Vec::new(),
     }
 }
@@ -5170,6 +5681,8 @@ mod tests {
             cli_version: "0.0.0".to_string(),
             source: SessionSource::VSCode,
             git_info: None,
+            title: None,
+            tags: Vec::new(),
         };
 
         assert_eq!(summary, expected);
@@ -5226,6 +5739,8 @@ mod tests {
             cli_version: String::new(),
             source: SessionSource::VSCode,
             git_info: None,
+            title: None,
+            tags: Vec::new(),
         };
 
         assert_eq!(summary, expected);
@@ -144,6 +144,7 @@ mod tests {
             ]),
             mcp_servers: None,
             rules: None,
+            safety_floor: None,
         };
 
         let mapped = map_requirements_toml_to_api(requirements);
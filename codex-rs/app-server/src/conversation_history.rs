@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use codex_app_server_protocol::ConversationItem;
+use codex_app_server_protocol::ServerNotification;
+use codex_app_server_protocol::ThreadTokenUsage;
+use codex_app_server_protocol::Turn;
+
+/// Completed items the server has announced for each loaded conversation.
+///
+/// Items are recorded from the outgoing `item/completed` notifications
+/// themselves, so a client that attaches mid-session and pages through the
+/// history sees exactly the items, and item ids, that a client listening from
+/// the start received live.
+#[derive(Default)]
+pub(crate) struct ConversationHistoryStore {
+    transcripts: Mutex<HashMap<String, Transcript>>,
+}
+
+#[derive(Default)]
+struct Transcript {
+    items: Vec<ConversationItem>,
+    token_usage: Option<ThreadTokenUsage>,
+}
+
+pub(crate) struct HistoryPage {
+    pub(crate) items: Vec<ConversationItem>,
+    /// Offset of the first item not returned, if any remain.
+    pub(crate) next_offset: Option<usize>,
+    pub(crate) token_usage: Option<ThreadTokenUsage>,
+}
+
+impl ConversationHistoryStore {
+    /// Start tracking `thread_id` with the turns it already had when it was
+    /// loaded, as returned to clients on resume. Does nothing if the thread is
+    /// already tracked.
+    pub(crate) fn seed(&self, thread_id: &str, turns: &[Turn]) {
+        let Ok(mut transcripts) = self.transcripts.lock() else {
+            return;
+        };
+        transcripts
+            .entry(thread_id.to_string())
+            .or_insert_with(|| Transcript {
+                items: turns
+                    .iter()
+                    .flat_map(|turn| {
+                        turn.items.iter().map(|item| ConversationItem {
+                            turn_id: turn.id.clone(),
+                            item: item.clone(),
+                        })
+                    })
+                    .collect(),
+                token_usage: None,
+            });
+    }
+
+    pub(crate) fn is_tracked(&self, thread_id: &str) -> bool {
+        self.transcripts
+            .lock()
+            .is_ok_and(|transcripts| transcripts.contains_key(thread_id))
+    }
+
+    pub(crate) fn forget(&self, thread_id: &str) {
+        if let Ok(mut transcripts) = self.transcripts.lock() {
+            transcripts.remove(thread_id);
+        }
+    }
+
+    /// Record the parts of an outgoing notification that belong in the
+    /// history of a tracked thread.
+    pub(crate) fn record(&self, notification: &ServerNotification) {
+        let Ok(mut transcripts) = self.transcripts.lock() else {
+            return;
+        };
+        match notification {
+            ServerNotification::ItemCompleted(completed) => {
+                if let Some(transcript) = transcripts.get_mut(&completed.thread_id) {
+                    transcript.items.push(ConversationItem {
+                        turn_id: completed.turn_id.clone(),
+                        item: completed.item.clone(),
+                    });
+                }
+            }
+            ServerNotification::ThreadTokenUsageUpdated(updated) => {
+                if let Some(transcript) = transcripts.get_mut(&updated.thread_id) {
+                    transcript.token_usage = Some(updated.token_usage.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Up to `limit` items starting at `offset`, or `None` if the thread is
+    /// not tracked.
+    pub(crate) fn page(&self, thread_id: &str, offset: usize, limit: usize) -> Option<HistoryPage> {
+        let transcripts = self.transcripts.lock().ok()?;
+        let transcript = transcripts.get(thread_id)?;
+        let end = offset.saturating_add(limit).min(transcript.items.len());
+        let items = transcript
+            .items
+            .get(offset..end)
+            .map(<[ConversationItem]>::to_vec)
+            .unwrap_or_default();
+        Some(HistoryPage {
+            items,
+            next_offset: (end < transcript.items.len()).then_some(end),
+            token_usage: transcript.token_usage.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_app_server_protocol::ItemCompletedNotification;
+    use codex_app_server_protocol::ThreadItem;
+    use codex_app_server_protocol::TurnStatus;
+    use pretty_assertions::assert_eq;
+
+    fn agent_message(id: &str) -> ThreadItem {
+        ThreadItem::AgentMessage {
+            id: id.to_string(),
+            text: format!("text of {id}"),
+        }
+    }
+
+    fn completed(thread_id: &str, turn_id: &str, item_id: &str) -> ServerNotification {
+        ServerNotification::ItemCompleted(ItemCompletedNotification {
+            item: agent_message(item_id),
+            thread_id: thread_id.to_string(),
+            turn_id: turn_id.to_string(),
+        })
+    }
+
+    #[test]
+    fn pages_seeded_and_recorded_items_in_order() {
+        let store = ConversationHistoryStore::default();
+        store.seed(
+            "thread",
+            &[Turn {
+                id: "turn-1".to_string(),
+                items: vec![agent_message("item-1")],
+                status: TurnStatus::Completed,
+                error: None,
+            }],
+        );
+        store.record(&completed("thread", "live-turn", "msg-a"));
+        store.record(&completed("thread", "live-turn", "msg-b"));
+        // Untracked threads are ignored.
+        store.record(&completed("other", "turn", "msg-c"));
+
+        let first = store.page("thread", 0, 2).expect("tracked thread");
+        assert_eq!(
+            first.items,
+            vec![
+                ConversationItem {
+                    turn_id: "turn-1".to_string(),
+                    item: agent_message("item-1"),
+                },
+                ConversationItem {
+                    turn_id: "live-turn".to_string(),
+                    item: agent_message("msg-a"),
+                },
+            ]
+        );
+        assert_eq!(first.next_offset, Some(2));
+
+        let second = store.page("thread", 2, 2).expect("tracked thread");
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.next_offset, None);
+
+        assert!(store.page("other", 0, 10).is_none());
+        store.forget("thread");
+        assert!(!store.is_tracked("thread"));
+    }
+}
@@ -13,6 +13,7 @@ const MAX_THREADS: usize = 12;
 pub(crate) async fn run_fuzzy_file_search(
     query: String,
     roots: Vec<String>,
+    recent_paths: Vec<String>,
     cancellation_flag: Arc<AtomicBool>,
 ) -> Vec<FuzzyFileSearchResult> {
     if roots.is_empty() {
@@ -29,6 +30,7 @@ pub(crate) async fn run_fuzzy_file_search(
     #[expect(clippy::expect_used)]
     let threads = NonZero::new(threads.max(1)).expect("threads should be non-zero");
     let search_dirs: Vec<PathBuf> = roots.iter().map(PathBuf::from).collect();
+    let recent_paths: Vec<PathBuf> = recent_paths.into_iter().map(PathBuf::from).collect();
 
     let mut files = match tokio::task::spawn_blocking(move || {
         file_search::run(
@@ -38,6 +40,8 @@ pub(crate) async fn run_fuzzy_file_search(
                 limit,
                 threads,
                 compute_indices: true,
+                recent_paths,
+                boost_recently_modified: true,
                 ..Default::default()
             },
             Some(cancellation_flag),
@@ -0,0 +1,122 @@
+//! Suspends loaded threads that nobody has used for a while.
+//!
+//! Clients tend to start threads and forget them, and every loaded thread
+//! keeps its MCP servers, exec sessions and rollout file open. A thread that
+//! has seen no ops or events for `thread_idle_timeout_ms` is shut down and
+//! unloaded. The server remembers enough to load it again from its rollout
+//! the next time a request refers to it.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_app_server_protocol::ClientRequest;
+use codex_core::ThreadConfigSnapshot;
+use codex_protocol::ThreadId;
+use uuid::Uuid;
+
+use crate::codex_message_processor::ListenerSubscription;
+
+const MIN_SWEEP_INTERVAL: Duration = Duration::from_millis(50);
+const MAX_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What is kept of a suspended thread to load it again.
+pub(crate) struct SuspendedThread {
+    pub(crate) rollout_path: PathBuf,
+    /// Settings the thread had when it was suspended, which may differ from
+    /// the ones it was started with.
+    pub(crate) config: ThreadConfigSnapshot,
+    /// Listener subscriptions to restore, under the same ids, on resume.
+    pub(crate) subscriptions: Vec<(Uuid, ListenerSubscription)>,
+}
+
+/// How often to look for idle threads, so that a thread is suspended at most
+/// a quarter of `idle_timeout` late.
+pub(crate) fn sweep_interval(idle_timeout: Duration) -> Duration {
+    (idle_timeout / 4).clamp(MIN_SWEEP_INTERVAL, MAX_SWEEP_INTERVAL)
+}
+
+/// The thread a request operates on and that must be loaded to serve it.
+pub(crate) fn thread_to_wake(request: &ClientRequest) -> Option<ThreadId> {
+    match request {
+        ClientRequest::TurnStart { params, .. } => parse_thread_id(&params.thread_id),
+        ClientRequest::TurnInterrupt { params, .. } => parse_thread_id(&params.thread_id),
+        ClientRequest::ReviewStart { params, .. } => parse_thread_id(&params.thread_id),
+        ClientRequest::ThreadRollback { params, .. } => parse_thread_id(&params.thread_id),
+        ClientRequest::ThreadSetName { params, .. } => parse_thread_id(&params.thread_id),
+        ClientRequest::SendUserMessage { params, .. } => Some(params.conversation_id),
+        ClientRequest::SendUserTurn { params, .. } => Some(params.conversation_id),
+        ClientRequest::InterruptConversation { params, .. } => Some(params.conversation_id),
+        ClientRequest::AddConversationListener { params, .. } => Some(params.conversation_id),
+        ClientRequest::GetConversationHistory { params, .. } => Some(params.conversation_id),
+        _ => None,
+    }
+}
+
+/// The suspended thread a request reloads or removes by other means, which
+/// the server should stop tracking.
+pub(crate) fn thread_to_forget(request: &ClientRequest) -> Option<ThreadId> {
+    match request {
+        ClientRequest::ThreadResume { params, .. } => parse_thread_id(&params.thread_id),
+        ClientRequest::ThreadArchive { params, .. } => parse_thread_id(&params.thread_id),
+        ClientRequest::ArchiveConversation { params, .. } => Some(params.conversation_id),
+        _ => None,
+    }
+}
+
+fn parse_thread_id(thread_id: &str) -> Option<ThreadId> {
+    ThreadId::from_string(thread_id).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_app_server_protocol::RequestId;
+    use codex_app_server_protocol::ThreadArchiveParams;
+    use codex_app_server_protocol::ThreadLoadedListParams;
+    use codex_app_server_protocol::TurnStartParams;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sweep_interval_is_a_quarter_of_the_timeout_within_bounds() {
+        assert_eq!(
+            sweep_interval(Duration::from_secs(30 * 60)),
+            MAX_SWEEP_INTERVAL
+        );
+        assert_eq!(
+            sweep_interval(Duration::from_secs(2)),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            sweep_interval(Duration::from_millis(10)),
+            MIN_SWEEP_INTERVAL
+        );
+    }
+
+    #[test]
+    fn only_requests_on_a_thread_wake_it() {
+        let thread_id = ThreadId::new();
+        let turn_start = ClientRequest::TurnStart {
+            request_id: RequestId::Integer(1),
+            params: TurnStartParams {
+                thread_id: thread_id.to_string(),
+                ..Default::default()
+            },
+        };
+        let archive = ClientRequest::ThreadArchive {
+            request_id: RequestId::Integer(2),
+            params: ThreadArchiveParams {
+                thread_id: thread_id.to_string(),
+            },
+        };
+        let list = ClientRequest::ThreadLoadedList {
+            request_id: RequestId::Integer(3),
+            params: ThreadLoadedListParams::default(),
+        };
+
+        assert_eq!(thread_to_wake(&turn_start), Some(thread_id));
+        assert_eq!(thread_to_forget(&turn_start), None);
+        assert_eq!(thread_to_wake(&archive), None);
+        assert_eq!(thread_to_forget(&archive), Some(thread_id));
+        assert_eq!(thread_to_wake(&list), None);
+    }
+}
@@ -44,10 +44,12 @@ use tracing_subscriber::util::SubscriberInitExt;
 mod bespoke_event_handling;
 mod codex_message_processor;
 mod config_api;
+mod conversation_history;
 mod dynamic_tools;
 mod error_code;
 mod filters;
 mod fuzzy_file_search;
+mod idle_threads;
 mod message_processor;
 mod models;
 mod outgoing_message;
@@ -326,8 +328,13 @@ pub async fn run_main(
             config_warnings,
         });
         let mut thread_created_rx = processor.thread_created_receiver();
+        let idle_sweep_interval = processor.idle_sweep_interval();
         async move {
             let mut listen_for_threads = true;
+            let mut idle_sweep = tokio::time::interval(
+                idle_sweep_interval.unwrap_or(std::time::Duration::from_secs(60)),
+            );
+            idle_sweep.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
             loop {
                 tokio::select! {
                     msg = incoming_rx.recv() => {
@@ -358,6 +365,9 @@ pub async fn run_main(
                             }
                         }
                     }
+                    _ = idle_sweep.tick(), if idle_sweep_interval.is_some() => {
+                        processor.suspend_idle_threads().await;
+                    }
                 }
             }
 
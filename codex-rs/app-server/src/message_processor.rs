@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -7,6 +8,7 @@ use crate::config_api::ConfigApi;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
 use async_trait::async_trait;
+use codex_app_server_protocol::Capability;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshParams;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshReason;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshResponse;
@@ -16,12 +18,14 @@ use codex_app_server_protocol::ConfigBatchWriteParams;
 use codex_app_server_protocol::ConfigReadParams;
 use codex_app_server_protocol::ConfigValueWriteParams;
 use codex_app_server_protocol::ConfigWarningNotification;
+use codex_app_server_protocol::InitializeParams;
 use codex_app_server_protocol::InitializeResponse;
 use codex_app_server_protocol::JSONRPCError;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::PROTOCOL_VERSION;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequestPayload;
@@ -41,6 +45,7 @@ use codex_core::default_client::set_default_originator;
 use codex_feedback::CodexFeedback;
 use codex_protocol::ThreadId;
 use codex_protocol::protocol::SessionSource;
+use serde_json::json;
 use tokio::sync::broadcast;
 use tokio::time::Duration;
 use tokio::time::timeout;
@@ -215,11 +220,32 @@ impl MessageProcessor {
                     self.outgoing.send_error(request_id, error).await;
                     return;
                 } else {
+                    let InitializeParams {
+                        client_info,
+                        protocol_version,
+                        capabilities,
+                    } = params;
+                    if let Some(requested) = protocol_version
+                        && requested > PROTOCOL_VERSION
+                    {
+                        let error = JSONRPCErrorError {
+                            code: INVALID_REQUEST_ERROR_CODE,
+                            message: format!(
+                                "Unsupported protocol version {requested}; this server supports up to {PROTOCOL_VERSION}."
+                            ),
+                            data: Some(json!({
+                                "protocolVersion": requested,
+                                "maxProtocolVersion": PROTOCOL_VERSION,
+                            })),
+                        };
+                        self.outgoing.send_error(request_id, error).await;
+                        return;
+                    }
                     let ClientInfo {
                         name,
                         title: _title,
                         version,
-                    } = params.client_info;
+                    } = client_info;
                     if let Err(error) = set_default_originator(name.clone()) {
                         match error {
                             SetOriginatorError::InvalidHeaderValue => {
@@ -247,7 +273,22 @@ impl MessageProcessor {
                     }
 
                     let user_agent = get_codex_user_agent();
-                    let response = InitializeResponse { user_agent };
+                    let negotiated: HashSet<Capability> = capabilities
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|(_, enabled)| *enabled)
+                        .filter_map(|(name, _)| Capability::from_wire_name(&name))
+                        .collect();
+                    let capabilities = Capability::ALL
+                        .iter()
+                        .map(|capability| (capability.to_string(), negotiated.contains(capability)))
+                        .collect();
+                    self.outgoing.set_negotiated_capabilities(negotiated);
+                    let response = InitializeResponse {
+                        user_agent,
+                        protocol_version: PROTOCOL_VERSION,
+                        capabilities,
+                    };
                     self.outgoing.send_response(request_id, response).await;
 
                     self.initialized = true;
@@ -318,6 +359,14 @@ impl MessageProcessor {
             .await;
     }
 
+    pub(crate) fn idle_sweep_interval(&self) -> Option<Duration> {
+        self.codex_message_processor.idle_sweep_interval()
+    }
+
+    pub(crate) async fn suspend_idle_threads(&mut self) {
+        self.codex_message_processor.suspend_idle_threads().await;
+    }
+
     /// Handle a standalone JSON-RPC response originating from the peer.
     pub(crate) async fn process_response(&mut self, response: JSONRPCResponse) {
         tracing::info!("<- response: {:?}", response);
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::RwLock;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
 
+use codex_app_server_protocol::Capability;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::Result;
@@ -14,6 +17,7 @@ use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::warn;
 
+use crate::conversation_history::ConversationHistoryStore;
 use crate::error_code::INTERNAL_ERROR_CODE;
 
 #[cfg(test)]
@@ -24,6 +28,11 @@ pub(crate) struct OutgoingMessageSender {
     next_request_id: AtomicI64,
     sender: mpsc::Sender<OutgoingMessage>,
     request_id_to_callback: Mutex<HashMap<RequestId, oneshot::Sender<Result>>>,
+    /// Capabilities the client opted into during `initialize`.
+    negotiated_capabilities: RwLock<HashSet<Capability>>,
+    /// Completed items sent for each tracked conversation, served by
+    /// `getConversationHistory`.
+    conversation_history: ConversationHistoryStore,
 }
 
 impl OutgoingMessageSender {
@@ -32,9 +41,27 @@ impl OutgoingMessageSender {
             next_request_id: AtomicI64::new(0),
             sender,
             request_id_to_callback: Mutex::new(HashMap::new()),
+            negotiated_capabilities: RwLock::new(HashSet::new()),
+            conversation_history: ConversationHistoryStore::default(),
         }
     }
 
+    pub(crate) fn conversation_history(&self) -> &ConversationHistoryStore {
+        &self.conversation_history
+    }
+
+    pub(crate) fn set_negotiated_capabilities(&self, capabilities: HashSet<Capability>) {
+        if let Ok(mut negotiated) = self.negotiated_capabilities.write() {
+            *negotiated = capabilities;
+        }
+    }
+
+    fn has_capability(&self, capability: Capability) -> bool {
+        self.negotiated_capabilities
+            .read()
+            .is_ok_and(|negotiated| negotiated.contains(&capability))
+    }
+
     pub(crate) async fn send_request(
         &self,
         request: ServerRequestPayload,
@@ -130,6 +157,12 @@ impl OutgoingMessageSender {
     }
 
     pub(crate) async fn send_server_notification(&self, notification: ServerNotification) {
+        self.conversation_history.record(&notification);
+        if let Some(capability) = notification.required_capability()
+            && !self.has_capability(capability)
+        {
+            return;
+        }
         if let Err(err) = self
             .sender
             .send(OutgoingMessage::AppServerNotification(notification))
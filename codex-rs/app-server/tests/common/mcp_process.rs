@@ -24,8 +24,10 @@ use codex_app_server_protocol::ConfigReadParams;
 use codex_app_server_protocol::ConfigValueWriteParams;
 use codex_app_server_protocol::FeedbackUploadParams;
 use codex_app_server_protocol::ForkConversationParams;
+use codex_app_server_protocol::FuzzyFileSearchParams;
 use codex_app_server_protocol::GetAccountParams;
 use codex_app_server_protocol::GetAuthStatusParams;
+use codex_app_server_protocol::GetConversationHistoryParams;
 use codex_app_server_protocol::InitializeParams;
 use codex_app_server_protocol::InterruptConversationParams;
 use codex_app_server_protocol::JSONRPCError;
@@ -35,6 +37,7 @@ use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::ListConversationsParams;
+use codex_app_server_protocol::ListCustomPromptsParams;
 use codex_app_server_protocol::LoginAccountParams;
 use codex_app_server_protocol::LoginApiKeyParams;
 use codex_app_server_protocol::ModelListParams;
@@ -56,6 +59,7 @@ use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadRollbackParams;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadUnarchiveParams;
+use codex_app_server_protocol::ToolListParams;
 use codex_app_server_protocol::TurnInterruptParams;
 use codex_app_server_protocol::TurnStartParams;
 use codex_core::default_client::CODEX_INTERNAL_ORIGINATOR_OVERRIDE_ENV_VAR;
@@ -164,7 +168,20 @@ impl McpProcess {
         &mut self,
         client_info: ClientInfo,
     ) -> anyhow::Result<JSONRPCMessage> {
-        let params = Some(serde_json::to_value(InitializeParams { client_info })?);
+        self.initialize_with_params(InitializeParams {
+            client_info,
+            protocol_version: None,
+            capabilities: None,
+        })
+        .await
+    }
+
+    /// Sends initialize with the provided params and returns the response/error message.
+    pub async fn initialize_with_params(
+        &mut self,
+        params: InitializeParams,
+    ) -> anyhow::Result<JSONRPCMessage> {
+        let params = Some(serde_json::to_value(params)?);
         let request_id = self.send_request("initialize", params).await?;
         let message = self.read_jsonrpc_message().await?;
         match message {
@@ -276,6 +293,15 @@ impl McpProcess {
         self.send_request("getAuthStatus", params).await
     }
 
+    /// Send a `getConversationHistory` JSON-RPC request.
+    pub async fn send_get_conversation_history_request(
+        &mut self,
+        params: GetConversationHistoryParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("getConversationHistory", params).await
+    }
+
     /// Send a `getUserSavedConfig` JSON-RPC request.
     pub async fn send_get_user_saved_config_request(&mut self) -> anyhow::Result<i64> {
         self.send_request("getUserSavedConfig", None).await
@@ -427,6 +453,12 @@ impl McpProcess {
         self.send_request("thread/read", params).await
     }
 
+    /// Send a `tool/list` JSON-RPC request.
+    pub async fn send_tool_list_request(&mut self, params: ToolListParams) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("tool/list", params).await
+    }
+
     /// Send a `model/list` JSON-RPC request.
     pub async fn send_list_models_request(
         &mut self,
@@ -599,6 +631,24 @@ impl McpProcess {
         self.send_request("fuzzyFileSearch", Some(params)).await
     }
 
+    /// Send a `fuzzyFileSearch` JSON-RPC request with fully specified params.
+    pub async fn send_fuzzy_file_search_request_with_params(
+        &mut self,
+        params: FuzzyFileSearchParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("fuzzyFileSearch", params).await
+    }
+
+    /// Send a `listCustomPrompts` JSON-RPC request.
+    pub async fn send_list_custom_prompts_request(
+        &mut self,
+        params: ListCustomPromptsParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("listCustomPrompts", params).await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -748,6 +798,13 @@ impl McpProcess {
         self.pending_messages.clear();
     }
 
+    /// Returns whether a notification with `method` was read and buffered but not yet consumed.
+    pub fn has_pending_notification(&self, method: &str) -> bool {
+        self.pending_messages.iter().any(|message| {
+            matches!(message, JSONRPCMessage::Notification(notification) if notification.method == method)
+        })
+    }
+
     /// Reads the stream until a message matches `predicate`, buffering any non-matching messages
     /// for later reads.
     async fn read_stream_until_message<F>(&mut self, predicate: F) -> anyhow::Result<JSONRPCMessage>
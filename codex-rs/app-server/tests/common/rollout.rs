@@ -80,6 +80,9 @@ pub fn create_fake_rollout_with_source(
         cli_version: "0.0.0".to_string(),
         source,
         model_provider: model_provider.map(str::to_string),
+        title: None,
+        tags: Vec::new(),
+        sampling: None,
         base_instructions: None,
         dynamic_tools: None,
     };
@@ -159,6 +162,9 @@ pub fn create_fake_rollout_with_text_elements(
         cli_version: "0.0.0".to_string(),
         source: SessionSource::Cli,
         model_provider: model_provider.map(str::to_string),
+        title: None,
+        tags: Vec::new(),
+        sampling: None,
         base_instructions: None,
         dynamic_tools: None,
     };
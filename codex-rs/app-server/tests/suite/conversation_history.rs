@@ -0,0 +1,190 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_mock_responses_server_repeating_assistant;
+use app_test_support::to_response;
+use codex_app_server_protocol::ConversationItem;
+use codex_app_server_protocol::GetConversationHistoryParams;
+use codex_app_server_protocol::GetConversationHistoryResponse;
+use codex_app_server_protocol::ItemCompletedNotification;
+use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::UserInput;
+use codex_protocol::ThreadId;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn history_matches_transcript_built_from_live_notifications() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_req = mcp
+        .send_thread_start_request(ThreadStartParams::default())
+        .await?;
+    let thread_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_req)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(thread_resp)?;
+    let conversation_id = ThreadId::from_string(&thread.id)?;
+
+    let mut live = run_turn(&mut mcp, &thread.id, "first").await?;
+
+    // A client attaching now pages through everything announced so far.
+    let (history, model, cwd) = read_history(&mut mcp, conversation_id).await?;
+    assert_eq!(history, live);
+    assert_eq!(model, "mock-model");
+    assert!(cwd.is_absolute());
+
+    // Items announced after the attach extend the same transcript.
+    live.extend(run_turn(&mut mcp, &thread.id, "second").await?);
+    let (history, _, _) = read_history(&mut mcp, conversation_id).await?;
+    assert_eq!(history, live);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn history_rejects_invalid_cursor() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_req = mcp
+        .send_thread_start_request(ThreadStartParams::default())
+        .await?;
+    let thread_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_req)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(thread_resp)?;
+
+    let request_id = mcp
+        .send_get_conversation_history_request(GetConversationHistoryParams {
+            conversation_id: ThreadId::from_string(&thread.id)?,
+            cursor: Some("not-a-cursor".to_string()),
+            limit: None,
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(error.error.message, "invalid cursor: not-a-cursor");
+
+    Ok(())
+}
+
+/// Run one turn and return the items announced live via `item/completed`.
+async fn run_turn(
+    mcp: &mut McpProcess,
+    thread_id: &str,
+    text: &str,
+) -> Result<Vec<ConversationItem>> {
+    let turn_req = mcp
+        .send_turn_start_request(TurnStartParams {
+            thread_id: thread_id.to_string(),
+            input: vec![UserInput::Text {
+                text: text.to_string(),
+                text_elements: Vec::new(),
+            }],
+            ..Default::default()
+        })
+        .await?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(turn_req)),
+    )
+    .await??;
+
+    let mut items = Vec::new();
+    loop {
+        let message = timeout(DEFAULT_READ_TIMEOUT, mcp.read_next_message()).await??;
+        let JSONRPCMessage::Notification(notification) = message else {
+            continue;
+        };
+        match notification.method.as_str() {
+            "item/completed" => {
+                let completed: ItemCompletedNotification =
+                    serde_json::from_value(notification.params.unwrap_or_default())?;
+                items.push(ConversationItem {
+                    turn_id: completed.turn_id,
+                    item: completed.item,
+                });
+            }
+            "turn/completed" => return Ok(items),
+            _ => {}
+        }
+    }
+}
+
+/// Page through the whole history one item at a time.
+async fn read_history(
+    mcp: &mut McpProcess,
+    conversation_id: ThreadId,
+) -> Result<(Vec<ConversationItem>, String, std::path::PathBuf)> {
+    let mut items = Vec::new();
+    let mut cursor = None;
+    loop {
+        let request_id = mcp
+            .send_get_conversation_history_request(GetConversationHistoryParams {
+                conversation_id,
+                cursor,
+                limit: Some(1),
+            })
+            .await?;
+        let response: JSONRPCResponse = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+        )
+        .await??;
+        let page = to_response::<GetConversationHistoryResponse>(response)?;
+        assert!(page.items.len() <= 1);
+        items.extend(page.items);
+        if page.next_cursor.is_none() {
+            return Ok((items, page.model, page.cwd));
+        }
+        cursor = page.next_cursor;
+    }
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "responses"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
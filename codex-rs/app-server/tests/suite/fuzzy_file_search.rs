@@ -1,6 +1,9 @@
 use anyhow::Result;
 use anyhow::anyhow;
 use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::FuzzyFileSearchParams;
+use codex_app_server_protocol::FuzzyFileSearchResponse;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::RequestId;
 use pretty_assertions::assert_eq;
@@ -48,8 +51,9 @@ async fn test_fuzzy_file_search_sorts_and_includes_indices() -> Result<()> {
     .await??;
 
     let value = resp.result;
-    let expected_score = 72;
 
+    // Scores include the file-name prefix bonus for "abexy", the depth
+    // penalty for "sub/abce", and the recently-modified bonus for all three.
     assert_eq!(
         value,
         json!({
@@ -58,23 +62,23 @@ async fn test_fuzzy_file_search_sorts_and_includes_indices() -> Result<()> {
                     "root": root_path.clone(),
                     "path": "abexy",
                     "file_name": "abexy",
-                    "score": 84,
+                    "score": 124,
                     "indices": [0, 1, 2],
                 },
-                {
-                    "root": root_path.clone(),
-                    "path": sub_abce_rel,
-                    "file_name": "abce",
-                    "score": expected_score,
-                    "indices": [4, 5, 7],
-                },
                 {
                     "root": root_path.clone(),
                     "path": "abcde",
                     "file_name": "abcde",
-                    "score": 71,
+                    "score": 87,
                     "indices": [0, 1, 4],
                 },
+                {
+                    "root": root_path.clone(),
+                    "path": sub_abce_rel,
+                    "file_name": "abce",
+                    "score": 86,
+                    "indices": [4, 5, 7],
+                },
             ]
         })
     );
@@ -125,3 +129,41 @@ async fn test_fuzzy_file_search_accepts_cancellation_token() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fuzzy_file_search_tags_roots_and_boosts_recent_paths() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let root_a = TempDir::new()?;
+    let root_b = TempDir::new()?;
+
+    std::fs::write(root_a.path().join("notes.md"), "a")?;
+    std::fs::write(root_b.path().join("notes.md"), "b")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let root_a_path = root_a.path().to_string_lossy().to_string();
+    let root_b_path = root_b.path().to_string_lossy().to_string();
+    let request_id = mcp
+        .send_fuzzy_file_search_request_with_params(FuzzyFileSearchParams {
+            query: "notes".to_string(),
+            roots: vec![root_a_path.clone(), root_b_path.clone()],
+            cancellation_token: None,
+            recent_paths: Some(vec![
+                root_b.path().join("notes.md").to_string_lossy().to_string(),
+            ]),
+        })
+        .await?;
+
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let FuzzyFileSearchResponse { files } = to_response(resp)?;
+
+    let roots: Vec<String> = files.into_iter().map(|file| file.root).collect();
+    assert_eq!(roots, vec![root_b_path, root_a_path]);
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::ListCustomPromptsParams;
+use codex_app_server_protocol::ListCustomPromptsResponse;
+use codex_app_server_protocol::RequestId;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn list_custom_prompts_merges_user_and_project_prompts() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let user_prompts = codex_home.path().join("prompts");
+    std::fs::create_dir_all(&user_prompts)?;
+    std::fs::write(user_prompts.join("review.md"), "Review my changes")?;
+    std::fs::write(
+        user_prompts.join("test.md"),
+        "# Run the tests\nThen fix them",
+    )?;
+
+    let project = TempDir::new()?;
+    let project_prompts = project.path().join(".codex/prompts");
+    std::fs::create_dir_all(&project_prompts)?;
+    std::fs::write(
+        project_prompts.join("test.md"),
+        "---\ndescription: Project test run\n---\njust test",
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_list_custom_prompts_request(ListCustomPromptsParams {
+            cwd: Some(project.path().to_path_buf()),
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ListCustomPromptsResponse { custom_prompts } = to_response(response)?;
+
+    // The server may canonicalize CODEX_HOME, so record where each prompt
+    // came from instead of comparing full paths.
+    let summary: Vec<_> = custom_prompts
+        .into_iter()
+        .map(|prompt| {
+            let location = prompt
+                .path
+                .strip_prefix(project.path())
+                .map_or("user", |_| "project");
+            (prompt.name, location, prompt.content, prompt.description)
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "review".to_string(),
+                "user",
+                "Review my changes".to_string(),
+                Some("Review my changes".to_string()),
+            ),
+            (
+                "test".to_string(),
+                "project",
+                "just test".to_string(),
+                Some("Project test run".to_string()),
+            ),
+        ]
+    );
+    Ok(())
+}
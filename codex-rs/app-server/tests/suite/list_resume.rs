@@ -59,6 +59,7 @@ async fn test_list_and_resume_conversations() -> Result<()> {
             page_size: Some(2),
             cursor: None,
             model_providers: None,
+            tags: None,
         })
         .await?;
     let resp: JSONRPCResponse = timeout(
@@ -84,6 +85,7 @@ async fn test_list_and_resume_conversations() -> Result<()> {
             page_size: Some(2),
             cursor: next_cursor,
             model_providers: None,
+            tags: None,
         })
         .await?;
     let resp2: JSONRPCResponse = timeout(
@@ -117,6 +119,7 @@ async fn test_list_and_resume_conversations() -> Result<()> {
             page_size: Some(10),
             cursor: None,
             model_providers: Some(vec!["test-provider".to_string()]),
+            tags: None,
         })
         .await?;
     let filter_resp: JSONRPCResponse = timeout(
@@ -139,6 +142,7 @@ async fn test_list_and_resume_conversations() -> Result<()> {
             page_size: Some(10),
             cursor: None,
             model_providers: Some(Vec::new()),
+            tags: None,
         })
         .await?;
     let unfiltered_resp: JSONRPCResponse = timeout(
@@ -158,6 +162,7 @@ async fn test_list_and_resume_conversations() -> Result<()> {
             page_size: Some(10),
             cursor: None,
             model_providers: Some(vec!["other".to_string()]),
+            tags: None,
         })
         .await?;
     let empty_resp: JSONRPCResponse = timeout(
@@ -413,6 +418,7 @@ async fn list_conversations_fetches_through_filtered_pages() -> Result<()> {
             page_size: Some(3),
             cursor: None,
             model_providers: Some(vec!["target_provider".to_string()]),
+            tags: None,
         })
         .await?;
     let resp: JSONRPCResponse = timeout(
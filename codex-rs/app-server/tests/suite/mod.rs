@@ -2,10 +2,12 @@ mod archive_thread;
 mod auth;
 mod codex_message_processor_flow;
 mod config;
+mod conversation_history;
 mod create_thread;
 mod fork_thread;
 mod fuzzy_file_search;
 mod interrupt;
+mod list_custom_prompts;
 mod list_resume;
 mod login;
 mod output_schema;
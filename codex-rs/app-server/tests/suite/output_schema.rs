@@ -89,6 +89,7 @@ async fn send_user_turn_accepts_output_schema_v1() -> Result<()> {
             effort: Some(ReasoningEffort::Medium),
             summary: ReasoningSummary::Auto,
             output_schema: Some(output_schema.clone()),
+            overrides: None,
         })
         .await?;
     let _send_turn_resp: SendUserTurnResponse = to_response::<SendUserTurnResponse>(
@@ -191,6 +192,7 @@ async fn send_user_turn_output_schema_is_per_turn_v1() -> Result<()> {
             effort: Some(ReasoningEffort::Medium),
             summary: ReasoningSummary::Auto,
             output_schema: Some(output_schema.clone()),
+            overrides: None,
         })
         .await?;
     let _send_turn_resp: SendUserTurnResponse = to_response::<SendUserTurnResponse>(
@@ -239,6 +241,7 @@ async fn send_user_turn_output_schema_is_per_turn_v1() -> Result<()> {
             effort: Some(ReasoningEffort::Medium),
             summary: ReasoningSummary::Auto,
             output_schema: None,
+            overrides: None,
         })
         .await?;
     let _send_turn_resp_2: SendUserTurnResponse = to_response::<SendUserTurnResponse>(
@@ -8,10 +8,13 @@ use codex_app_server_protocol::DynamicToolCallParams;
 use codex_app_server_protocol::DynamicToolCallResponse;
 use codex_app_server_protocol::DynamicToolSpec;
 use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::ModelToolSource;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ServerRequest;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::ToolListParams;
+use codex_app_server_protocol::ToolListResponse;
 use codex_app_server_protocol::TurnStartParams;
 use codex_app_server_protocol::TurnStartResponse;
 use codex_app_server_protocol::UserInput as V2UserInput;
@@ -27,6 +30,69 @@ use wiremock::MockServer;
 
 const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Ensures `tool/list` reports dynamic tools next to the built-in tools.
+#[tokio::test]
+async fn tool_list_includes_dynamic_tools() -> Result<()> {
+    let server = create_mock_responses_server_sequence_unchecked(Vec::new()).await;
+
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let input_schema = json!({
+        "type": "object",
+        "properties": {
+            "city": { "type": "string" }
+        },
+        "required": ["city"],
+        "additionalProperties": false,
+    });
+    let dynamic_tool = DynamicToolSpec {
+        name: "demo_tool".to_string(),
+        description: "Demo dynamic tool".to_string(),
+        input_schema: input_schema.clone(),
+    };
+    let thread_req = mcp
+        .send_thread_start_request(ThreadStartParams {
+            dynamic_tools: Some(vec![dynamic_tool]),
+            ..Default::default()
+        })
+        .await?;
+    let thread_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_req)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(thread_resp)?;
+
+    let list_req = mcp
+        .send_tool_list_request(ToolListParams {
+            thread_id: thread.id,
+        })
+        .await?;
+    let list_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(list_req)),
+    )
+    .await??;
+    let ToolListResponse { data } = to_response::<ToolListResponse>(list_resp)?;
+
+    let tool = data
+        .iter()
+        .find(|tool| tool.name == "demo_tool")
+        .context("expected dynamic tool to be listed")?;
+    assert_eq!(tool.source, ModelToolSource::Dynamic);
+    assert_eq!(tool.spec.get("parameters"), Some(&input_schema));
+    assert!(
+        data.iter()
+            .any(|tool| tool.source == ModelToolSource::BuiltIn)
+    );
+
+    Ok(())
+}
+
 /// Ensures dynamic tool specs are serialized into the model request payload.
 #[tokio::test]
 async fn thread_start_injects_dynamic_tools_into_model_requests() -> Result<()> {
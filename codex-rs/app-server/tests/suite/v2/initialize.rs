@@ -1,11 +1,28 @@
 use anyhow::Result;
 use app_test_support::McpProcess;
+use app_test_support::create_final_assistant_message_sse_response;
 use app_test_support::create_mock_responses_server_sequence_unchecked;
 use app_test_support::to_response;
+use codex_app_server_protocol::AskForApproval;
 use codex_app_server_protocol::ClientInfo;
+use codex_app_server_protocol::InitializeParams;
 use codex_app_server_protocol::InitializeResponse;
 use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::PROTOCOL_VERSION;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::SandboxPolicy;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnSettingsUpdatedNotification;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::TurnStartResponse;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use codex_protocol::openai_models::ReasoningEffort;
 use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::path::Path;
 use tempfile::TempDir;
 use tokio::time::timeout;
@@ -33,7 +50,7 @@ async fn initialize_uses_client_info_name_as_originator() -> Result<()> {
     let JSONRPCMessage::Response(response) = message else {
         anyhow::bail!("expected initialize response, got {message:?}");
     };
-    let InitializeResponse { user_agent } = to_response::<InitializeResponse>(response)?;
+    let InitializeResponse { user_agent, .. } = to_response::<InitializeResponse>(response)?;
 
     assert!(user_agent.starts_with("codex_vscode/"));
     Ok(())
@@ -67,7 +84,7 @@ async fn initialize_respects_originator_override_env_var() -> Result<()> {
     let JSONRPCMessage::Response(response) = message else {
         anyhow::bail!("expected initialize response, got {message:?}");
     };
-    let InitializeResponse { user_agent } = to_response::<InitializeResponse>(response)?;
+    let InitializeResponse { user_agent, .. } = to_response::<InitializeResponse>(response)?;
 
     assert!(user_agent.starts_with("codex_originator_via_env_var/"));
     Ok(())
@@ -108,6 +125,182 @@ async fn initialize_rejects_invalid_client_name() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn initialize_negotiates_protocol_version_and_capabilities() -> Result<()> {
+    let responses = Vec::new();
+    let server = create_mock_responses_server_sequence_unchecked(responses).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+
+    let message = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.initialize_with_params(initialize_params(
+            Some(PROTOCOL_VERSION),
+            HashMap::from([
+                ("turnSettingsUpdates".to_string(), true),
+                ("fuzzyFileSearch".to_string(), false),
+                ("notARealCapability".to_string(), true),
+            ]),
+        )),
+    )
+    .await??;
+
+    let JSONRPCMessage::Response(response) = message else {
+        anyhow::bail!("expected initialize response, got {message:?}");
+    };
+    let InitializeResponse {
+        protocol_version,
+        capabilities,
+        ..
+    } = to_response::<InitializeResponse>(response)?;
+
+    assert_eq!(protocol_version, PROTOCOL_VERSION);
+    assert_eq!(
+        capabilities,
+        BTreeMap::from([
+            ("fuzzyFileSearch".to_string(), false),
+            ("listCustomPrompts".to_string(), false),
+            ("turnOverrides".to_string(), false),
+            ("turnSettingsUpdates".to_string(), true),
+        ])
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn initialize_rejects_newer_protocol_version() -> Result<()> {
+    let responses = Vec::new();
+    let server = create_mock_responses_server_sequence_unchecked(responses).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+
+    let requested = PROTOCOL_VERSION + 1;
+    let message = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.initialize_with_params(initialize_params(Some(requested), HashMap::new())),
+    )
+    .await??;
+
+    let JSONRPCMessage::Error(error) = message else {
+        anyhow::bail!("expected initialize error, got {message:?}");
+    };
+
+    assert_eq!(error.error.code, -32600);
+    assert_eq!(
+        error.error.message,
+        format!(
+            "Unsupported protocol version {requested}; this server supports up to {PROTOCOL_VERSION}."
+        )
+    );
+    assert_eq!(
+        error.error.data,
+        Some(json!({
+            "protocolVersion": requested,
+            "maxProtocolVersion": PROTOCOL_VERSION,
+        }))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn turn_settings_updated_requires_negotiated_capability() -> Result<()> {
+    for negotiated in [true, false] {
+        let responses = vec![create_final_assistant_message_sse_response("Done")?];
+        let server = create_mock_responses_server_sequence_unchecked(responses).await;
+        let codex_home = TempDir::new()?;
+        create_config_toml(codex_home.path(), &server.uri(), "never")?;
+        let mut mcp = McpProcess::new(codex_home.path()).await?;
+        timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.initialize_with_params(initialize_params(
+                Some(PROTOCOL_VERSION),
+                HashMap::from([("turnSettingsUpdates".to_string(), negotiated)]),
+            )),
+        )
+        .await??;
+
+        let thread_req = mcp
+            .send_thread_start_request(ThreadStartParams {
+                model: Some("mock-model".to_string()),
+                ..Default::default()
+            })
+            .await?;
+        let thread_resp: JSONRPCResponse = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(thread_req)),
+        )
+        .await??;
+        let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(thread_resp)?;
+
+        let turn_req = mcp
+            .send_turn_start_request(TurnStartParams {
+                thread_id: thread.id.clone(),
+                input: vec![V2UserInput::Text {
+                    text: "Hello".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                effort: Some(ReasoningEffort::High),
+                ..Default::default()
+            })
+            .await?;
+        let turn_resp: JSONRPCResponse = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(turn_req)),
+        )
+        .await??;
+        let TurnStartResponse { turn } = to_response::<TurnStartResponse>(turn_resp)?;
+
+        if negotiated {
+            let notification = timeout(
+                DEFAULT_READ_TIMEOUT,
+                mcp.read_stream_until_notification_message("turn/settings/updated"),
+            )
+            .await??;
+            let settings: TurnSettingsUpdatedNotification =
+                serde_json::from_value(notification.params.expect("params must be present"))?;
+            assert_eq!(
+                settings,
+                TurnSettingsUpdatedNotification {
+                    thread_id: thread.id.clone(),
+                    turn_id: turn.id.clone(),
+                    model: "mock-model".to_string(),
+                    reasoning_effort: Some(ReasoningEffort::High),
+                    approval_policy: AskForApproval::Never,
+                    sandbox_policy: SandboxPolicy::ReadOnly,
+                }
+            );
+        }
+
+        timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_notification_message("turn/completed"),
+        )
+        .await??;
+        assert!(
+            !mcp.has_pending_notification("turn/settings/updated"),
+            "negotiated: {negotiated}"
+        );
+    }
+    Ok(())
+}
+
+fn initialize_params(
+    protocol_version: Option<u32>,
+    capabilities: HashMap<String, bool>,
+) -> InitializeParams {
+    InitializeParams {
+        client_info: ClientInfo {
+            name: "codex_vscode".to_string(),
+            title: Some("Codex VS Code Extension".to_string()),
+            version: "0.1.0".to_string(),
+        },
+        protocol_version,
+        capabilities: Some(capabilities),
+    }
+}
+
 // Helper to create a config.toml pointing at the mock model server.
 fn create_config_toml(
     codex_home: &Path,
@@ -14,6 +14,7 @@ mod request_user_input;
 mod review;
 mod thread_archive;
 mod thread_fork;
+mod thread_idle;
 mod thread_list;
 mod thread_loaded_list;
 mod thread_read;
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_mock_responses_server_repeating_assistant;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadLoadedListParams;
+use codex_app_server_protocol::ThreadLoadedListResponse;
+use codex_app_server_protocol::ThreadResumedNotification;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::ThreadSuspendedNotification;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const THREAD_COUNT: usize = 10;
+
+#[tokio::test]
+async fn idle_threads_are_suspended_and_resume_with_history() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let mut thread_ids = Vec::new();
+    for index in 0..THREAD_COUNT {
+        let thread_id = start_thread(&mut mcp).await?;
+        run_turn(&mut mcp, &thread_id, &format!("message for thread {index}")).await?;
+        thread_ids.push(thread_id);
+    }
+
+    let mut suspended = Vec::new();
+    for _ in 0..THREAD_COUNT {
+        let notification: JSONRPCNotification = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_notification_message("thread/suspended"),
+        )
+        .await??;
+        let ThreadSuspendedNotification { thread_id } =
+            serde_json::from_value(notification.params.expect("thread/suspended params"))?;
+        suspended.push(thread_id);
+    }
+    suspended.sort();
+    let mut expected = thread_ids.clone();
+    expected.sort();
+    assert_eq!(suspended, expected);
+    assert_eq!(loaded_threads(&mut mcp).await?, Vec::<String>::new());
+
+    let resumed_id = thread_ids[3].clone();
+    let turn_req = mcp
+        .send_turn_start_request(turn_params(&resumed_id, "are you still there?"))
+        .await?;
+    let notification: JSONRPCNotification = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("thread/resumed"),
+    )
+    .await??;
+    let ThreadResumedNotification { thread_id } =
+        serde_json::from_value(notification.params.expect("thread/resumed params"))?;
+    assert_eq!(thread_id, resumed_id);
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(turn_req)),
+    )
+    .await??;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("turn/completed"),
+    )
+    .await??;
+
+    assert_eq!(loaded_threads(&mut mcp).await?, vec![resumed_id]);
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("failed to fetch received requests");
+    let last_body = String::from_utf8(requests.last().expect("model request").body.clone())?;
+    assert!(last_body.contains("message for thread 3"));
+    assert!(last_body.contains("are you still there?"));
+
+    Ok(())
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+thread_idle_timeout_ms = 200
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "responses"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
+
+async fn start_thread(mcp: &mut McpProcess) -> Result<String> {
+    let req_id = mcp
+        .send_thread_start_request(ThreadStartParams::default())
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(req_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(resp)?;
+    Ok(thread.id)
+}
+
+async fn run_turn(mcp: &mut McpProcess, thread_id: &str, text: &str) -> Result<()> {
+    let req_id = mcp
+        .send_turn_start_request(turn_params(thread_id, text))
+        .await?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(req_id)),
+    )
+    .await??;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("turn/completed"),
+    )
+    .await??;
+    Ok(())
+}
+
+fn turn_params(thread_id: &str, text: &str) -> TurnStartParams {
+    TurnStartParams {
+        thread_id: thread_id.to_string(),
+        input: vec![V2UserInput::Text {
+            text: text.to_string(),
+            text_elements: Vec::new(),
+        }],
+        ..Default::default()
+    }
+}
+
+async fn loaded_threads(mcp: &mut McpProcess) -> Result<Vec<String>> {
+    let list_id = mcp
+        .send_thread_loaded_list_request(ThreadLoadedListParams::default())
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(list_id)),
+    )
+    .await??;
+    let ThreadLoadedListResponse { mut data, .. } = to_response::<ThreadLoadedListResponse>(resp)?;
+    data.sort();
+    Ok(data)
+}
@@ -66,6 +66,7 @@ async fn list_threads_with_sort(
             model_providers: providers,
             source_kinds,
             archived,
+            tags: None,
         })
         .await?;
     let resp: JSONRPCResponse = timeout(
@@ -1106,6 +1107,7 @@ async fn thread_list_invalid_cursor_returns_error() -> Result<()> {
             model_providers: Some(vec!["mock_provider".to_string()]),
             source_kinds: None,
             archived: None,
+            tags: None,
         })
         .await?;
     let error: JSONRPCError = timeout(
@@ -46,9 +46,13 @@ async fn thread_start_creates_thread_and_emits_started() -> Result<()> {
     let ThreadStartResponse {
         thread,
         model_provider,
+        startup,
         ..
     } = to_response::<ThreadStartResponse>(resp)?;
     assert!(!thread.id.is_empty(), "thread id should not be empty");
+    let startup = startup.expect("startup diagnostics");
+    assert!(!startup.phases.is_empty());
+    assert_eq!(startup.provider_probe, None);
     assert!(
         thread.preview.is_empty(),
         "new threads should start with an empty preview"
@@ -34,6 +34,15 @@ pub enum ApplyPatchError {
     /// Error that occurs while computing replacements when applying patch chunks
     #[error("{0}")]
     ComputeReplacements(String),
+    /// A chunk's context line or expected lines are not in the file, usually
+    /// because it changed after the patch was written.
+    #[error("{message}")]
+    ContextMismatch {
+        path: PathBuf,
+        /// The lines that could not be found.
+        expected: Vec<String>,
+        message: String,
+    },
     /// A raw patch body was provided without an explicit `apply_patch` invocation.
     #[error(
         "patch detected without explicit call to apply_patch. Rerun as [\"apply_patch\", \"<patch>\"]"
@@ -241,7 +250,15 @@ pub fn apply_hunks(
             print_summary(&affected, stdout).map_err(ApplyPatchError::from)?;
             Ok(())
         }
-        Err(err) => {
+        Err(PartialApply {
+            applied,
+            failed_path,
+            error: err,
+        }) => {
+            if let Some(failed_path) = &failed_path {
+                print_partial_summary(&applied, failed_path, stdout)
+                    .map_err(ApplyPatchError::from)?;
+            }
             let msg = err.to_string();
             writeln!(stderr, "{msg}").map_err(ApplyPatchError::from)?;
             if let Some(io) = err.downcast_ref::<std::io::Error>() {
@@ -265,68 +282,92 @@ pub struct AffectedPaths {
     pub deleted: Vec<PathBuf>,
 }
 
+/// A patch that stopped at `failed_path`; the hunks before it stay applied.
+struct PartialApply {
+    applied: AffectedPaths,
+    failed_path: Option<PathBuf>,
+    error: anyhow::Error,
+}
+
 /// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
 /// Returns an error if the patch could not be applied.
-fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
+fn apply_hunks_to_files(hunks: &[Hunk]) -> Result<AffectedPaths, PartialApply> {
+    let mut affected = AffectedPaths {
+        added: Vec::new(),
+        modified: Vec::new(),
+        deleted: Vec::new(),
+    };
     if hunks.is_empty() {
-        anyhow::bail!("No files were modified.");
+        return Err(PartialApply {
+            applied: affected,
+            failed_path: None,
+            error: anyhow::anyhow!("No files were modified."),
+        });
     }
 
-    let mut added: Vec<PathBuf> = Vec::new();
-    let mut modified: Vec<PathBuf> = Vec::new();
-    let mut deleted: Vec<PathBuf> = Vec::new();
     for hunk in hunks {
-        match hunk {
-            Hunk::AddFile { path, contents } => {
-                if let Some(parent) = path.parent()
+        if let Err(error) = apply_hunk(hunk, &mut affected) {
+            let failed_path = match hunk {
+                Hunk::AddFile { path, .. }
+                | Hunk::DeleteFile { path }
+                | Hunk::UpdateFile { path, .. } => path.clone(),
+            };
+            return Err(PartialApply {
+                applied: affected,
+                failed_path: Some(failed_path),
+                error,
+            });
+        }
+    }
+    Ok(affected)
+}
+
+fn apply_hunk(hunk: &Hunk, affected: &mut AffectedPaths) -> anyhow::Result<()> {
+    match hunk {
+        Hunk::AddFile { path, contents } => {
+            if let Some(parent) = path.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                std::fs::create_dir_all(parent).with_context(|| {
+                    format!("Failed to create parent directories for {}", path.display())
+                })?;
+            }
+            std::fs::write(path, contents)
+                .with_context(|| format!("Failed to write file {}", path.display()))?;
+            affected.added.push(path.clone());
+        }
+        Hunk::DeleteFile { path } => {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to delete file {}", path.display()))?;
+            affected.deleted.push(path.clone());
+        }
+        Hunk::UpdateFile {
+            path,
+            move_path,
+            chunks,
+        } => {
+            let AppliedPatch { new_contents, .. } = derive_new_contents_from_chunks(path, chunks)?;
+            if let Some(dest) = move_path {
+                if let Some(parent) = dest.parent()
                     && !parent.as_os_str().is_empty()
                 {
                     std::fs::create_dir_all(parent).with_context(|| {
-                        format!("Failed to create parent directories for {}", path.display())
+                        format!("Failed to create parent directories for {}", dest.display())
                     })?;
                 }
-                std::fs::write(path, contents)
-                    .with_context(|| format!("Failed to write file {}", path.display()))?;
-                added.push(path.clone());
-            }
-            Hunk::DeleteFile { path } => {
+                std::fs::write(dest, new_contents)
+                    .with_context(|| format!("Failed to write file {}", dest.display()))?;
                 std::fs::remove_file(path)
-                    .with_context(|| format!("Failed to delete file {}", path.display()))?;
-                deleted.push(path.clone());
-            }
-            Hunk::UpdateFile {
-                path,
-                move_path,
-                chunks,
-            } => {
-                let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
-                if let Some(dest) = move_path {
-                    if let Some(parent) = dest.parent()
-                        && !parent.as_os_str().is_empty()
-                    {
-                        std::fs::create_dir_all(parent).with_context(|| {
-                            format!("Failed to create parent directories for {}", dest.display())
-                        })?;
-                    }
-                    std::fs::write(dest, new_contents)
-                        .with_context(|| format!("Failed to write file {}", dest.display()))?;
-                    std::fs::remove_file(path)
-                        .with_context(|| format!("Failed to remove original {}", path.display()))?;
-                    modified.push(dest.clone());
-                } else {
-                    std::fs::write(path, new_contents)
-                        .with_context(|| format!("Failed to write file {}", path.display()))?;
-                    modified.push(path.clone());
-                }
+                    .with_context(|| format!("Failed to remove original {}", path.display()))?;
+                affected.modified.push(dest.clone());
+            } else {
+                std::fs::write(path, new_contents)
+                    .with_context(|| format!("Failed to write file {}", path.display()))?;
+                affected.modified.push(path.clone());
             }
         }
     }
-    Ok(AffectedPaths {
-        added,
-        modified,
-        deleted,
-    })
+    Ok(())
 }
 
 struct AppliedPatch {
@@ -394,11 +435,15 @@ fn compute_replacements(
             ) {
                 line_index = idx + 1;
             } else {
-                return Err(ApplyPatchError::ComputeReplacements(format!(
-                    "Failed to find context '{}' in {}",
-                    ctx_line,
-                    path.display()
-                )));
+                return Err(ApplyPatchError::ContextMismatch {
+                    path: path.to_path_buf(),
+                    expected: vec![ctx_line.clone()],
+                    message: format!(
+                        "Failed to find context '{}' in {}",
+                        ctx_line,
+                        path.display()
+                    ),
+                });
             }
         }
 
@@ -451,11 +496,15 @@ fn compute_replacements(
             replacements.push((start_idx, pattern.len(), new_slice.to_vec()));
             line_index = start_idx + pattern.len();
         } else {
-            return Err(ApplyPatchError::ComputeReplacements(format!(
-                "Failed to find expected lines in {}:\n{}",
-                path.display(),
-                chunk.old_lines.join("\n"),
-            )));
+            return Err(ApplyPatchError::ContextMismatch {
+                path: path.to_path_buf(),
+                expected: chunk.old_lines.clone(),
+                message: format!(
+                    "Failed to find expected lines in {}:\n{}",
+                    path.display(),
+                    chunk.old_lines.join("\n"),
+                ),
+            });
         }
     }
 
@@ -542,6 +591,26 @@ pub fn print_summary(
     Ok(())
 }
 
+/// Write the files a failed patch changed before stopping, followed by the
+/// file it failed on marked with `!`.
+pub fn print_partial_summary(
+    applied: &AffectedPaths,
+    failed_path: &Path,
+    out: &mut impl std::io::Write,
+) -> std::io::Result<()> {
+    writeln!(out, "Failed. Updated the following files before the error:")?;
+    for path in &applied.added {
+        writeln!(out, "A {}", path.display())?;
+    }
+    for path in &applied.modified {
+        writeln!(out, "M {}", path.display())?;
+    }
+    for path in &applied.deleted {
+        writeln!(out, "D {}", path.display())?;
+    }
+    writeln!(out, "! {}", failed_path.display())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -864,6 +933,36 @@ mod tests {
         assert_eq!(expected, diff);
     }
 
+    #[test]
+    fn test_unified_diff_reports_context_mismatch() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stale.txt");
+        fs::write(&path, "foo\nbar\n").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+-baz
++BAZ"#,
+            path.display()
+        ));
+        let patch = parse_patch(&patch).unwrap();
+        let chunks = match patch.hunks.as_slice() {
+            [Hunk::UpdateFile { chunks, .. }] => chunks,
+            _ => panic!("Expected a single UpdateFile hunk"),
+        };
+
+        let err = unified_diff_from_chunks(&path, chunks).unwrap_err();
+
+        assert_eq!(
+            err,
+            ApplyPatchError::ContextMismatch {
+                path: path.clone(),
+                expected: vec!["baz".to_string()],
+                message: format!("Failed to find expected lines in {}:\nbaz", path.display()),
+            }
+        );
+    }
+
     #[test]
     fn test_unified_diff_first_line_replacement() {
         // Replace the very first line of the file.
@@ -248,10 +248,30 @@ fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow
         .arg("*** Begin Patch\n*** Add File: created.txt\n+hello\n*** Update File: missing.txt\n@@\n-old\n+new\n*** End Patch")
         .assert()
         .failure()
-        .stdout("")
+        .stdout("Failed. Updated the following files before the error:\nA created.txt\n! missing.txt\n")
         .stderr("Failed to read file to update missing.txt: No such file or directory (os error 2)\n");
 
     assert_eq!(fs::read_to_string(&new_file)?, "hello\n");
 
     Ok(())
 }
+
+#[test]
+fn test_apply_patch_cli_reports_files_applied_before_conflict() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    fs::write(tmp.path().join("first.txt"), "one\n")?;
+    fs::write(tmp.path().join("second.txt"), "two\n")?;
+
+    apply_patch_command(tmp.path())?
+        .arg("*** Begin Patch\n*** Update File: first.txt\n@@\n-one\n+uno\n*** Update File: second.txt\n@@\n-zwei\n+dos\n*** Add File: third.txt\n+tres\n*** End Patch")
+        .assert()
+        .failure()
+        .stdout("Failed. Updated the following files before the error:\nM first.txt\n! second.txt\n")
+        .stderr("Failed to find expected lines in second.txt:\nzwei\n");
+
+    assert_eq!(fs::read_to_string(tmp.path().join("first.txt"))?, "uno\n");
+    assert_eq!(fs::read_to_string(tmp.path().join("second.txt"))?, "two\n");
+    assert!(!tmp.path().join("third.txt").exists());
+
+    Ok(())
+}
@@ -10,12 +10,17 @@ use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::exec_env::create_env;
 use codex_core::landlock::spawn_command_under_linux_sandbox;
+use codex_core::sandbox_self_test;
+use codex_core::sandbox_self_test::ProbeOutcome;
 #[cfg(target_os = "macos")]
 use codex_core::seatbelt::spawn_command_under_seatbelt;
 use codex_core::spawn::StdioPolicy;
+use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_protocol::config_types::SandboxMode;
+use codex_protocol::config_types::WindowsSandboxLevel;
 
 use crate::LandlockCommand;
+use crate::SandboxTestCommand;
 use crate::SeatbeltCommand;
 use crate::WindowsCommand;
 use crate::exit_status::handle_exit_status;
@@ -130,14 +135,12 @@ async fn run_command_under_sandbox(
     let sandbox_policy_cwd = cwd.clone();
 
     let stdio_policy = StdioPolicy::Inherit;
-    let env = create_env(&config.shell_environment_policy);
+    let env = create_env(&config.shell_environment_policy, config.dotenv.as_deref());
 
     // Special-case Windows sandbox: execute and exit the process to emulate inherited stdio.
     if let SandboxType::Windows = sandbox_type {
         #[cfg(target_os = "windows")]
         {
-            use codex_core::windows_sandbox::WindowsSandboxLevelExt;
-            use codex_protocol::config_types::WindowsSandboxLevel;
             use codex_windows_sandbox::run_windows_sandbox_capture;
             use codex_windows_sandbox::run_windows_sandbox_capture_elevated;
 
@@ -270,6 +273,54 @@ async fn run_command_under_sandbox(
     handle_exit_status(status);
 }
 
+/// Probe sandbox enforcement on this machine, refresh the cached report the
+/// TUI and sessions read, and print the results. Exits with status 1 when a
+/// restriction is not enforced.
+pub async fn run_sandbox_self_test(
+    command: SandboxTestCommand,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let config = Config::load_with_cli_overrides_and_harness_overrides(
+        command
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?,
+        ConfigOverrides {
+            codex_linux_sandbox_exe,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let report = sandbox_self_test::run_sandbox_self_test(
+        &config.codex_linux_sandbox_exe,
+        WindowsSandboxLevel::from_config(&config),
+    )
+    .await;
+    if let Err(err) = sandbox_self_test::save_report(&config.codex_home, &report) {
+        eprintln!("Could not cache the report: {err}");
+    }
+
+    println!("Platform: {}", report.platform);
+    println!("Sandbox:  {}", report.sandbox);
+    println!();
+    for result in &report.results {
+        let outcome = match &result.outcome {
+            ProbeOutcome::Enforced => "enforced".to_string(),
+            ProbeOutcome::NotEnforced => "NOT ENFORCED".to_string(),
+            ProbeOutcome::Inconclusive { reason } => format!("inconclusive ({reason})"),
+        };
+        println!("  {:<24} {outcome}", result.capability.label());
+    }
+
+    if let Some(warning) = report.warning() {
+        println!();
+        println!("{warning}");
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 pub fn create_sandbox_mode(full_auto: bool) -> SandboxMode {
     if full_auto {
         SandboxMode::WorkspaceWrite
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use codex_core::config::ConfigBuilder;
+use codex_core::config::ConfigOverrides;
+use codex_core::config::find_codex_home;
+use codex_core::doctor::run_doctor;
+
+use crate::DoctorCommand;
+
+/// Runs `codex doctor` and exits with status 1 when any check failed.
+pub async fn run_doctor_command(
+    command: DoctorCommand,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let codex_home = find_codex_home()?;
+    let cwd = std::env::current_dir()?;
+    let config = ConfigBuilder::default()
+        .cli_overrides(
+            command
+                .config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?,
+        )
+        .harness_overrides(ConfigOverrides {
+            codex_linux_sandbox_exe,
+            ..Default::default()
+        });
+
+    let report = run_doctor(codex_home, cwd, config).await;
+    if command.json {
+        println!("{}", serde_json::to_string_pretty(&report.redacted())?);
+    } else {
+        println!("{}", report.to_text());
+    }
+
+    if report.has_errors() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
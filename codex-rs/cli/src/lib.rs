@@ -1,4 +1,5 @@
 pub mod debug_sandbox;
+pub mod doctor;
 mod exit_status;
 pub mod login;
 
@@ -50,3 +51,20 @@ pub struct WindowsCommand {
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
 }
+
+#[derive(Debug, Parser)]
+pub struct DoctorCommand {
+    /// Print the report as JSON, with credentials and home-directory paths
+    /// redacted, for attaching to a bug report.
+    #[arg(long = "json", default_value_t = false)]
+    pub json: bool,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Parser)]
+pub struct SandboxTestCommand {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
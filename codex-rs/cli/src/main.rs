@@ -6,7 +6,9 @@ use clap_complete::generate;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_chatgpt::apply_command::ApplyCommand;
 use codex_chatgpt::apply_command::run_apply_command;
+use codex_cli::DoctorCommand;
 use codex_cli::LandlockCommand;
+use codex_cli::SandboxTestCommand;
 use codex_cli::SeatbeltCommand;
 use codex_cli::WindowsCommand;
 use codex_cli::login::read_api_key_from_stdin;
@@ -17,6 +19,7 @@ use codex_cli::login::run_login_with_device_code;
 use codex_cli::login::run_logout;
 use codex_cloud_tasks::Cli as CloudTasksCli;
 use codex_common::CliConfigOverrides;
+use codex_exec::AskArgs;
 use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
@@ -83,12 +86,18 @@ enum Subcommand {
     /// Run a code review non-interactively.
     Review(ReviewArgs),
 
+    /// Answer a question about the code in one turn, without tools.
+    Ask(AskArgs),
+
     /// Manage login.
     Login(LoginCommand),
 
     /// Remove stored authentication credentials.
     Logout(LogoutCommand),
 
+    /// Diagnose the Codex installation: config, auth, connectivity, MCP servers and sandbox.
+    Doctor(DoctorCommand),
+
     /// [experimental] Run Codex as an MCP server and manage MCP servers.
     Mcp(McpCli),
 
@@ -119,6 +128,9 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// Play back a recording made with `codex --record`.
+    Replay(ReplayCommand),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -198,6 +210,9 @@ enum SandboxCommand {
 
     /// Run a command under Windows restricted token (Windows only).
     Windows(WindowsCommand),
+
+    /// Check which sandbox restrictions are enforced on this machine.
+    SandboxTest(SandboxTestCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -312,6 +327,21 @@ struct GenerateJsonSchemaCommand {
     out_dir: PathBuf,
 }
 
+#[derive(Debug, Parser)]
+struct ReplayCommand {
+    /// asciicast v2 file written by `codex --record`.
+    #[arg(value_name = "FILE")]
+    path: PathBuf,
+
+    /// Playback speed multiplier.
+    #[arg(long = "speed", default_value_t = 1.0)]
+    speed: f64,
+
+    /// Shorten pauses longer than this many seconds.
+    #[arg(long = "idle-limit", value_name = "SECONDS")]
+    idle_limit: Option<f64>,
+}
+
 #[derive(Debug, Parser)]
 struct StdioToUdsCommand {
     /// Path to the Unix domain socket to connect to.
@@ -520,6 +550,15 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
         }
+        Some(Subcommand::Ask(ask_args)) => {
+            let mut exec_cli = ExecCli::try_parse_from(["codex", "exec"])?;
+            exec_cli.command = Some(ExecCommand::Ask(ask_args));
+            prepend_config_flags(
+                &mut exec_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
+        }
         Some(Subcommand::McpServer) => {
             codex_mcp_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
         }
@@ -620,6 +659,13 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             run_logout(logout_cli.config_overrides).await;
         }
+        Some(Subcommand::Doctor(mut doctor_cli)) => {
+            prepend_config_flags(
+                &mut doctor_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            codex_cli::doctor::run_doctor_command(doctor_cli, codex_linux_sandbox_exe).await?;
+        }
         Some(Subcommand::Completion(completion_cli)) => {
             print_completion(completion_cli);
         }
@@ -664,6 +710,17 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 )
                 .await?;
             }
+            SandboxCommand::SandboxTest(mut sandbox_test_cli) => {
+                prepend_config_flags(
+                    &mut sandbox_test_cli.config_overrides,
+                    root_config_overrides.clone(),
+                );
+                codex_cli::debug_sandbox::run_sandbox_self_test(
+                    sandbox_test_cli,
+                    codex_linux_sandbox_exe,
+                )
+                .await?;
+            }
         },
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
@@ -679,6 +736,23 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             tokio::task::spawn_blocking(move || codex_responses_api_proxy::run_main(args))
                 .await??;
         }
+        Some(Subcommand::Replay(ReplayCommand {
+            path,
+            speed,
+            idle_limit,
+        })) => {
+            if !(speed.is_finite() && speed > 0.0) {
+                anyhow::bail!("--speed must be a positive number");
+            }
+            let idle_limit = idle_limit
+                .map(std::time::Duration::try_from_secs_f64)
+                .transpose()
+                .map_err(|_| anyhow::anyhow!("--idle-limit must be a non-negative number"))?;
+            tokio::task::spawn_blocking(move || {
+                codex_tui::replay_recording(&path, speed, idle_limit)
+            })
+            .await??;
+        }
         Some(Subcommand::StdioToUds(cmd)) => {
             let socket_path = cmd.socket_path;
             tokio::task::spawn_blocking(move || codex_stdio_to_uds::run(socket_path.as_path()))
@@ -922,6 +996,27 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
     if subcommand_cli.web_search {
         interactive.web_search = true;
     }
+    if subcommand_cli.read_only_chat {
+        interactive.read_only_chat = true;
+    }
+    if subcommand_cli.offline {
+        interactive.offline = true;
+    }
+    if subcommand_cli.dry_run_exec {
+        interactive.dry_run_exec = true;
+    }
+    if let Some(env_file) = subcommand_cli.env_file {
+        interactive.env_file = Some(env_file);
+    }
+    if subcommand_cli.no_wizard {
+        interactive.no_wizard = true;
+    }
+    if let Some(title) = subcommand_cli.title {
+        interactive.title = Some(title);
+    }
+    if !subcommand_cli.tags.is_empty() {
+        interactive.tags.extend(subcommand_cli.tags);
+    }
     if !subcommand_cli.images.is_empty() {
         interactive.images = subcommand_cli.images;
     }
@@ -1022,6 +1117,47 @@ mod tests {
         assert_eq!(args.prompt.as_deref(), Some("2+2"));
     }
 
+    #[test]
+    fn ask_parses_question_and_no_files() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "ask",
+            "--no-files",
+            "what does @src/lib.rs export?",
+        ])
+        .expect("parse should succeed");
+
+        let Some(Subcommand::Ask(ask)) = cli.subcommand else {
+            panic!("expected ask subcommand");
+        };
+        assert!(ask.no_files);
+        assert_eq!(
+            ask.question.as_deref(),
+            Some("what does @src/lib.rs export?")
+        );
+    }
+
+    #[test]
+    fn replay_parses_speed_and_idle_limit() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "replay",
+            "demo.cast",
+            "--speed",
+            "2",
+            "--idle-limit",
+            "1.5",
+        ])
+        .expect("parse should succeed");
+
+        let Some(Subcommand::Replay(replay)) = cli.subcommand else {
+            panic!("expected replay subcommand");
+        };
+        assert_eq!(replay.path, PathBuf::from("demo.cast"));
+        assert_eq!(replay.speed, 2.0);
+        assert_eq!(replay.idle_limit, Some(1.5));
+    }
+
     fn app_server_from_args(args: &[&str]) -> AppServerCommand {
         let cli = MultitoolCli::try_parse_from(args).expect("parse");
         let Subcommand::AppServer(app_server) = cli.subcommand.expect("app-server present") else {
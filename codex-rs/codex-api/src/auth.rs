@@ -1,4 +1,5 @@
 use codex_client::Request;
+use http::HeaderMap;
 
 /// Provides bearer and account identity information for API requests.
 ///
@@ -10,9 +11,18 @@ pub trait AuthProvider: Send + Sync {
     fn account_id(&self) -> Option<String> {
         None
     }
+
+    /// Headers resolved each time a request attempt is built, so values that
+    /// rotate during a long turn are picked up by retries.
+    fn attempt_headers(&self) -> HeaderMap {
+        HeaderMap::new()
+    }
 }
 
 pub(crate) fn add_auth_headers<A: AuthProvider>(auth: &A, mut req: Request) -> Request {
+    for (name, value) in &auth.attempt_headers() {
+        req.headers.insert(name.clone(), value.clone());
+    }
     if let Some(token) = auth.bearer_token()
         && let Ok(header) = format!("Bearer {token}").parse()
     {
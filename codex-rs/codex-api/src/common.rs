@@ -1,5 +1,7 @@
 use crate::error::ApiError;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::SamplingParameters;
+use codex_protocol::config_types::ServiceTier;
 use codex_protocol::config_types::Verbosity as VerbosityConfig;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
@@ -7,6 +9,7 @@ use codex_protocol::protocol::RateLimitSnapshot;
 use codex_protocol::protocol::TokenUsage;
 use futures::Stream;
 use serde::Serialize;
+use serde_json::Map;
 use serde_json::Value;
 use std::pin::Pin;
 use std::task::Context;
@@ -50,7 +53,17 @@ pub enum ResponseEvent {
         response_id: String,
         token_usage: Option<TokenUsage>,
     },
+    /// The response ended early, e.g. because it hit `max_output_tokens`.
+    /// Items already streamed are kept; the turn ends like `Completed`.
+    Incomplete {
+        response_id: String,
+        token_usage: Option<TokenUsage>,
+        reason: Option<String>,
+    },
     OutputTextDelta(String),
+    /// The model declined to answer. Carries the refusal text, which is
+    /// reported instead of an assistant message.
+    Refusal(String),
     ReasoningSummaryDelta {
         delta: String,
         summary_index: i64,
@@ -64,6 +77,19 @@ pub enum ResponseEvent {
     },
     RateLimits(RateLimitSnapshot),
     ModelsEtag(String),
+    /// Service tier the provider reports having processed the response with.
+    /// Sent before the terminal event when the provider echoes it.
+    ServiceTier(String),
+    /// Backend configuration fingerprint (`system_fingerprint`) the provider
+    /// reports, for spotting backend changes between runs.
+    SystemFingerprint(String),
+}
+
+impl ResponseEvent {
+    /// Whether this event ends the response stream.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed { .. } | Self::Incomplete { .. })
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -138,6 +164,12 @@ pub struct ResponsesApiRequest<'a> {
     pub prompt_cache_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<TextControls>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+    #[serde(flatten)]
+    pub sampling: SamplingParameters,
 }
 
 #[derive(Debug, Serialize)]
@@ -156,6 +188,14 @@ pub struct ResponseCreateWsRequest {
     pub prompt_cache_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<TextControls>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+    #[serde(flatten)]
+    pub sampling: SamplingParameters,
+    #[serde(flatten)]
+    pub extra_body: Map<String, Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -12,12 +12,15 @@ use crate::telemetry::SseTelemetry;
 use codex_client::HttpTransport;
 use codex_client::RequestCompression;
 use codex_client::RequestTelemetry;
+use codex_protocol::config_types::SamplingParameters;
+use codex_protocol::config_types::ServiceTier;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ReasoningItemContent;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::SessionSource;
 use futures::Stream;
 use http::HeaderMap;
+use serde_json::Map;
 use serde_json::Value;
 use std::collections::VecDeque;
 use std::pin::Pin;
@@ -50,12 +53,17 @@ impl<T: HttpTransport, A: AuthProvider> ChatClient<T, A> {
         self.stream(request.body, request.headers).await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn stream_prompt(
         &self,
         model: &str,
         prompt: &ApiPrompt,
         conversation_id: Option<String>,
         session_source: Option<SessionSource>,
+        max_tokens: Option<i64>,
+        service_tier: Option<ServiceTier>,
+        sampling: SamplingParameters,
+        extra_body: Map<String, Value>,
     ) -> Result<ResponseStream, ApiError> {
         use crate::requests::ChatRequestBuilder;
 
@@ -63,6 +71,10 @@ impl<T: HttpTransport, A: AuthProvider> ChatClient<T, A> {
             ChatRequestBuilder::new(model, &prompt.instructions, &prompt.input, &prompt.tools)
                 .conversation_id(conversation_id)
                 .session_source(session_source)
+                .max_tokens(max_tokens)
+                .service_tier(service_tier)
+                .sampling(sampling)
+                .extra_body(extra_body)
                 .build(self.streaming.provider())?;
 
         self.stream_request(request).await
@@ -166,10 +178,18 @@ impl Stream for AggregatedStream {
                 Poll::Ready(Some(Ok(ResponseEvent::ModelsEtag(etag)))) => {
                     return Poll::Ready(Some(Ok(ResponseEvent::ModelsEtag(etag))));
                 }
-                Poll::Ready(Some(Ok(ResponseEvent::Completed {
-                    response_id,
-                    token_usage,
-                }))) => {
+                Poll::Ready(Some(Ok(ResponseEvent::ServiceTier(tier)))) => {
+                    return Poll::Ready(Some(Ok(ResponseEvent::ServiceTier(tier))));
+                }
+                Poll::Ready(Some(Ok(ResponseEvent::SystemFingerprint(fingerprint)))) => {
+                    return Poll::Ready(Some(Ok(ResponseEvent::SystemFingerprint(fingerprint))));
+                }
+                Poll::Ready(Some(Ok(ResponseEvent::Refusal(refusal)))) => {
+                    return Poll::Ready(Some(Ok(ResponseEvent::Refusal(refusal))));
+                }
+                Poll::Ready(Some(Ok(
+                    event @ (ResponseEvent::Completed { .. } | ResponseEvent::Incomplete { .. }),
+                ))) => {
                     let mut emitted_any = false;
 
                     if !this.cumulative_reasoning.is_empty() {
@@ -201,19 +221,11 @@ impl Stream for AggregatedStream {
                     }
 
                     if emitted_any {
-                        this.pending.push_back(ResponseEvent::Completed {
-                            response_id: response_id.clone(),
-                            token_usage: token_usage.clone(),
-                        });
-                        if let Some(ev) = this.pending.pop_front() {
-                            return Poll::Ready(Some(Ok(ev)));
-                        }
+                        this.pending.push_back(event);
+                        return Poll::Ready(this.pending.pop_front().map(Ok));
                     }
 
-                    return Poll::Ready(Some(Ok(ResponseEvent::Completed {
-                        response_id,
-                        token_usage,
-                    })));
+                    return Poll::Ready(Some(Ok(event)));
                 }
                 Poll::Ready(Some(Ok(ResponseEvent::Created))) => {
                     continue;
@@ -10,6 +10,7 @@ use codex_protocol::openai_models::ModelsResponse;
 use http::HeaderMap;
 use http::Method;
 use http::header::ETAG;
+use serde::Deserialize;
 use std::sync::Arc;
 
 pub struct ModelsClient<T: HttpTransport, A: AuthProvider> {
@@ -77,6 +78,56 @@ impl<T: HttpTransport, A: AuthProvider> ModelsClient<T, A> {
 
         Ok((models, header_etag))
     }
+
+    /// Lists the ids of the models the provider serves, from an OpenAI-style
+    /// `GET /models` (`{"data": [{"id": ..}]}`) or a Codex-style one
+    /// (`{"models": [{"slug": ..}]}`). An empty list is reported as an error,
+    /// since it says nothing about which models exist.
+    pub async fn list_model_ids(&self, extra_headers: HeaderMap) -> Result<Vec<String>, ApiError> {
+        let builder = || {
+            let mut req = self.provider.build_request(Method::GET, self.path());
+            req.headers.extend(extra_headers.clone());
+            add_auth_headers(&self.auth, req)
+        };
+
+        let resp = run_with_request_telemetry(
+            self.provider.retry.to_policy(),
+            self.request_telemetry.clone(),
+            builder,
+            |req| self.transport.execute(req),
+        )
+        .await?;
+
+        let ModelIdsResponse { data, models } = serde_json::from_slice(&resp.body)
+            .map_err(|e| ApiError::Stream(format!("failed to decode models list: {e}")))?;
+        let ids: Vec<String> = data
+            .into_iter()
+            .map(|entry| entry.id)
+            .chain(models.into_iter().map(|entry| entry.slug))
+            .collect();
+        if ids.is_empty() {
+            return Err(ApiError::Stream("models list is empty".to_string()));
+        }
+        Ok(ids)
+    }
+}
+
+#[derive(Deserialize)]
+struct ModelIdsResponse {
+    #[serde(default)]
+    data: Vec<ModelIdEntry>,
+    #[serde(default)]
+    models: Vec<ModelSlugEntry>,
+}
+
+#[derive(Deserialize)]
+struct ModelIdEntry {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct ModelSlugEntry {
+    slug: String,
 }
 
 #[cfg(test)]
@@ -159,6 +210,7 @@ mod tests {
                 retry_transport: true,
             },
             stream_idle_timeout: Duration::from_secs(1),
+            first_byte_timeout: None,
         }
     }
 
@@ -276,4 +328,55 @@ mod tests {
         assert_eq!(models.len(), 0);
         assert_eq!(etag, Some("\"abc\"".to_string()));
     }
+
+    #[derive(Clone)]
+    struct JsonTransport(serde_json::Value);
+
+    #[async_trait]
+    impl HttpTransport for JsonTransport {
+        async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+            assert_eq!(req.url, "https://example.com/v1/models");
+            Ok(Response {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                body: serde_json::to_vec(&self.0).unwrap().into(),
+            })
+        }
+
+        async fn stream(&self, _req: Request) -> Result<StreamResponse, TransportError> {
+            Err(TransportError::Build("stream should not run".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn list_model_ids_accepts_openai_and_codex_shapes() {
+        for (body, expected) in [
+            (
+                json!({"object": "list", "data": [{"id": "gpt-4o", "object": "model"}]}),
+                vec!["gpt-4o".to_string()],
+            ),
+            (
+                json!({"models": [{"slug": "gpt-5.1-codex", "priority": 1}]}),
+                vec!["gpt-5.1-codex".to_string()],
+            ),
+        ] {
+            let client = ModelsClient::new(
+                JsonTransport(body),
+                provider("https://example.com/v1"),
+                DummyAuth,
+            );
+            let ids = client
+                .list_model_ids(HeaderMap::new())
+                .await
+                .expect("request should succeed");
+            assert_eq!(ids, expected);
+        }
+
+        let client = ModelsClient::new(
+            JsonTransport(json!({"data": []})),
+            provider("https://example.com/v1"),
+            DummyAuth,
+        );
+        assert!(client.list_model_ids(HeaderMap::new()).await.is_err());
+    }
 }
@@ -15,8 +15,11 @@ use crate::telemetry::SseTelemetry;
 use codex_client::HttpTransport;
 use codex_client::RequestCompression;
 use codex_client::RequestTelemetry;
+use codex_protocol::config_types::SamplingParameters;
+use codex_protocol::config_types::ServiceTier;
 use codex_protocol::protocol::SessionSource;
 use http::HeaderMap;
+use serde_json::Map;
 use serde_json::Value;
 use std::sync::Arc;
 use std::sync::OnceLock;
@@ -33,6 +36,10 @@ pub struct ResponsesOptions {
     pub prompt_cache_key: Option<String>,
     pub text: Option<TextControls>,
     pub store_override: Option<bool>,
+    pub max_output_tokens: Option<i64>,
+    pub service_tier: Option<ServiceTier>,
+    pub sampling: SamplingParameters,
+    pub extra_body: Map<String, Value>,
     pub conversation_id: Option<String>,
     pub session_source: Option<SessionSource>,
     pub extra_headers: HeaderMap,
@@ -84,6 +91,10 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
             prompt_cache_key,
             text,
             store_override,
+            max_output_tokens,
+            service_tier,
+            sampling,
+            extra_body,
             conversation_id,
             session_source,
             extra_headers,
@@ -101,6 +112,10 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
             .conversation(conversation_id)
             .session_source(session_source)
             .store_override(store_override)
+            .max_output_tokens(max_output_tokens)
+            .service_tier(service_tier)
+            .sampling(sampling)
+            .extra_body(extra_body)
             .extra_headers(extra_headers)
             .compression(compression)
             .build(self.streaming.provider())?;
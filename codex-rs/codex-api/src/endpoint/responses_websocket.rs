@@ -37,14 +37,21 @@ pub struct ResponsesWebsocketConnection {
     stream: Arc<Mutex<Option<WsStream>>>,
     // TODO (pakrym): is this the right place for timeout?
     idle_timeout: Duration,
+    first_byte_timeout: Option<Duration>,
     server_reasoning_included: bool,
 }
 
 impl ResponsesWebsocketConnection {
-    fn new(stream: WsStream, idle_timeout: Duration, server_reasoning_included: bool) -> Self {
+    fn new(
+        stream: WsStream,
+        idle_timeout: Duration,
+        first_byte_timeout: Option<Duration>,
+        server_reasoning_included: bool,
+    ) -> Self {
         Self {
             stream: Arc::new(Mutex::new(Some(stream))),
             idle_timeout,
+            first_byte_timeout,
             server_reasoning_included,
         }
     }
@@ -61,6 +68,7 @@ impl ResponsesWebsocketConnection {
             mpsc::channel::<std::result::Result<ResponseEvent, ApiError>>(1600);
         let stream = Arc::clone(&self.stream);
         let idle_timeout = self.idle_timeout;
+        let first_byte_timeout = self.first_byte_timeout;
         let server_reasoning_included = self.server_reasoning_included;
         let request_body = serde_json::to_value(&request).map_err(|err| {
             ApiError::Stream(format!("failed to encode websocket request: {err}"))
@@ -87,6 +95,7 @@ impl ResponsesWebsocketConnection {
                 tx_event.clone(),
                 request_body,
                 idle_timeout,
+                first_byte_timeout,
             )
             .await
             {
@@ -124,11 +133,19 @@ impl<A: AuthProvider> ResponsesWebsocketClient<A> {
         headers.extend(extra_headers);
         apply_auth_headers(&mut headers, &self.auth);
 
-        let (stream, server_reasoning_included) =
-            connect_websocket(ws_url, headers, turn_state).await?;
+        let connect = connect_websocket(ws_url, headers, turn_state);
+        let (stream, server_reasoning_included) = match self.provider.first_byte_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, connect).await.map_err(|_| {
+                ApiError::Stream(format!(
+                    "no websocket handshake response received within {timeout:?}"
+                ))
+            })??,
+            None => connect.await?,
+        };
         Ok(ResponsesWebsocketConnection::new(
             stream,
             self.provider.stream_idle_timeout,
+            self.provider.first_byte_timeout,
             server_reasoning_included,
         ))
     }
@@ -136,6 +153,9 @@ impl<A: AuthProvider> ResponsesWebsocketClient<A> {
 
 // TODO (pakrym): share with /auth
 fn apply_auth_headers(headers: &mut HeaderMap, auth: &impl AuthProvider) {
+    for (name, value) in &auth.attempt_headers() {
+        headers.insert(name.clone(), value.clone());
+    }
     if let Some(token) = auth.bearer_token()
         && let Ok(header) = HeaderValue::from_str(&format!("Bearer {token}"))
     {
@@ -218,6 +238,7 @@ async fn run_websocket_response_stream(
     tx_event: mpsc::Sender<std::result::Result<ResponseEvent, ApiError>>,
     request_body: Value,
     idle_timeout: Duration,
+    mut first_byte_timeout: Option<Duration>,
 ) -> Result<(), ApiError> {
     let request_text = match serde_json::to_string(&request_body) {
         Ok(text) => text,
@@ -235,9 +256,24 @@ async fn run_websocket_response_stream(
     }
 
     loop {
-        let response = tokio::time::timeout(idle_timeout, ws_stream.next())
-            .await
-            .map_err(|_| ApiError::Stream("idle timeout waiting for websocket".into()));
+        // Until the server sends anything, only the first-byte timeout applies.
+        let timeout = first_byte_timeout.unwrap_or(idle_timeout);
+        let response = tokio::select! {
+            response = tokio::time::timeout(timeout, ws_stream.next()) => response,
+            // The consumer dropped the stream, e.g. because the turn was
+            // interrupted. The response is still being generated on this
+            // connection, so close it rather than leave it for the next request.
+            () = tx_event.closed() => {
+                return Err(ApiError::Stream("response stream was dropped".into()));
+            }
+        }
+        .map_err(|_| match first_byte_timeout {
+            Some(timeout) => {
+                ApiError::Stream(format!("no response bytes received within {timeout:?}"))
+            }
+            None => ApiError::Stream("idle timeout waiting for websocket".into()),
+        });
+        first_byte_timeout = None;
         let message = match response {
             Ok(Some(Ok(msg))) => msg,
             Ok(Some(Err(err))) => {
@@ -263,9 +299,17 @@ async fn run_websocket_response_stream(
                         continue;
                     }
                 };
+                if let Some(tier) = event.served_service_tier() {
+                    let _ = tx_event.send(Ok(ResponseEvent::ServiceTier(tier))).await;
+                }
+                if let Some(fingerprint) = event.system_fingerprint() {
+                    let _ = tx_event
+                        .send(Ok(ResponseEvent::SystemFingerprint(fingerprint)))
+                        .await;
+                }
                 match process_responses_event(event) {
                     Ok(Some(event)) => {
-                        let is_completed = matches!(event, ResponseEvent::Completed { .. });
+                        let is_completed = event.is_terminal();
                         let _ = tx_event.send(Ok(event)).await;
                         if is_completed {
                             break;
@@ -297,3 +341,51 @@ async fn run_websocket_response_stream(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::net::TcpListener;
+
+    /// A server that accepts one request and then never answers it.
+    async fn silent_server() -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind websocket server");
+        let addr = listener.local_addr().expect("websocket server address");
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.expect("accept");
+            let mut ws = tokio_tungstenite::accept_async(stream)
+                .await
+                .expect("websocket handshake");
+            let _request = ws.next().await;
+            std::future::pending::<()>().await;
+        });
+        Url::parse(&format!("ws://{addr}")).expect("websocket url")
+    }
+
+    #[tokio::test]
+    async fn first_byte_timeout_fails_silent_websocket() {
+        let url = silent_server().await;
+        let (mut stream, _) = connect_websocket(url, HeaderMap::new(), None)
+            .await
+            .expect("connect");
+        let (tx_event, _rx_event) = mpsc::channel(16);
+
+        let err = run_websocket_response_stream(
+            &mut stream,
+            tx_event,
+            serde_json::json!({"type": "response.create"}),
+            Duration::from_secs(300),
+            Some(Duration::from_millis(50)),
+        )
+        .await
+        .expect_err("silent server should time out");
+
+        let ApiError::Stream(message) = err else {
+            panic!("expected a stream error, got {err:?}");
+        };
+        assert_eq!(message, "no response bytes received within 50ms");
+    }
+}
@@ -5,10 +5,13 @@ use crate::error::ApiError;
 use crate::provider::Provider;
 use crate::telemetry::SseTelemetry;
 use crate::telemetry::run_with_request_telemetry;
+use codex_client::ByteStream;
 use codex_client::HttpTransport;
 use codex_client::RequestCompression;
 use codex_client::RequestTelemetry;
 use codex_client::StreamResponse;
+use codex_client::TransportError;
+use futures::StreamExt;
 use http::HeaderMap;
 use http::Method;
 use serde_json::Value;
@@ -77,13 +80,17 @@ impl<T: HttpTransport, A: AuthProvider> StreamingClient<T, A> {
             add_auth_headers(&self.auth, req)
         };
 
-        let stream_response = run_with_request_telemetry(
+        let mut stream_response = run_with_request_telemetry(
             self.provider.retry.to_policy(),
             self.request_telemetry.clone(),
             builder,
             |req| self.transport.stream(req),
         )
         .await?;
+        if let Some(first_byte_timeout) = self.provider.first_byte_timeout {
+            stream_response.bytes =
+                with_first_byte_timeout(stream_response.bytes, first_byte_timeout);
+        }
 
         Ok(spawner(
             stream_response,
@@ -93,3 +100,63 @@ impl<T: HttpTransport, A: AuthProvider> StreamingClient<T, A> {
         ))
     }
 }
+
+/// Fails the stream if no bytes arrive within `first_byte_timeout`. Later
+/// chunks are passed through untouched so the SSE idle timeout governs the
+/// rest of the response.
+fn with_first_byte_timeout(bytes: ByteStream, first_byte_timeout: Duration) -> ByteStream {
+    futures::stream::unfold((bytes, true), move |(mut bytes, first)| async move {
+        let next = if first {
+            match tokio::time::timeout(first_byte_timeout, bytes.next()).await {
+                Ok(next) => next,
+                Err(_) => Some(Err(TransportError::Network(format!(
+                    "no response bytes received within {first_byte_timeout:?}"
+                )))),
+            }
+        } else {
+            bytes.next().await
+        };
+        next.map(|item| (item, (bytes, false)))
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn first_byte_timeout_fails_silent_stream() {
+        let silent: ByteStream = futures::stream::pending().boxed();
+
+        let mut bytes = with_first_byte_timeout(silent, Duration::from_millis(10));
+
+        let err = match bytes.next().await {
+            Some(Err(err)) => err.to_string(),
+            other => panic!("expected a timeout error, got {other:?}"),
+        };
+        assert_eq!(err, "network error: no response bytes received within 10ms");
+    }
+
+    #[tokio::test]
+    async fn first_byte_timeout_passes_through_chunks() {
+        let chunks: ByteStream = futures::stream::iter(vec![
+            Ok(Bytes::from_static(b"a")),
+            Ok(Bytes::from_static(b"b")),
+        ])
+        .boxed();
+
+        let bytes = with_first_byte_timeout(chunks, Duration::from_millis(10));
+
+        let received: Vec<Bytes> = bytes
+            .map(|chunk| chunk.expect("chunk should pass through"))
+            .collect()
+            .await;
+        assert_eq!(
+            received,
+            vec![Bytes::from_static(b"a"), Bytes::from_static(b"b")]
+        );
+    }
+}
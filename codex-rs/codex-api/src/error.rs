@@ -27,6 +27,9 @@ pub enum ApiError {
     RateLimit(String),
     #[error("invalid request: {message}")]
     InvalidRequest { message: String },
+    /// The provider's content filter blocked the request or the response.
+    #[error("content filtered: {message}")]
+    ContentFiltered { message: String },
 }
 
 impl From<RateLimitError> for ApiError {
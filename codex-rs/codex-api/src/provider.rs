@@ -46,7 +46,7 @@ impl RetryConfig {
 /// HTTP endpoint configuration used to talk to a concrete API deployment.
 ///
 /// Encapsulates base URL, default headers, query params, retry policy, and
-/// stream idle timeout, plus helper methods for building requests.
+/// stream timeouts, plus helper methods for building requests.
 #[derive(Debug, Clone)]
 pub struct Provider {
     pub name: String,
@@ -56,6 +56,9 @@ pub struct Provider {
     pub headers: HeaderMap,
     pub retry: RetryConfig,
     pub stream_idle_timeout: Duration,
+    /// Maximum wait for the first byte of a streaming response. Once any
+    /// bytes arrive, only `stream_idle_timeout` applies.
+    pub first_byte_timeout: Option<Duration>,
 }
 
 impl Provider {
@@ -3,12 +3,16 @@ use crate::provider::Provider;
 use crate::requests::headers::build_conversation_headers;
 use crate::requests::headers::insert_header;
 use crate::requests::headers::subagent_header;
+use crate::requests::merge_extra_body;
+use codex_protocol::config_types::SamplingParameters;
+use codex_protocol::config_types::ServiceTier;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::models::ReasoningItemContent;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::SessionSource;
 use http::HeaderMap;
+use serde_json::Map;
 use serde_json::Value;
 use serde_json::json;
 use std::collections::HashMap;
@@ -26,6 +30,10 @@ pub struct ChatRequestBuilder<'a> {
     tools: &'a [Value],
     conversation_id: Option<String>,
     session_source: Option<SessionSource>,
+    max_tokens: Option<i64>,
+    service_tier: Option<ServiceTier>,
+    sampling: SamplingParameters,
+    extra_body: Map<String, Value>,
 }
 
 impl<'a> ChatRequestBuilder<'a> {
@@ -42,6 +50,10 @@ impl<'a> ChatRequestBuilder<'a> {
             tools,
             conversation_id: None,
             session_source: None,
+            max_tokens: None,
+            service_tier: None,
+            sampling: SamplingParameters::default(),
+            extra_body: Map::new(),
         }
     }
 
@@ -55,6 +67,26 @@ impl<'a> ChatRequestBuilder<'a> {
         self
     }
 
+    pub fn max_tokens(mut self, max_tokens: Option<i64>) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn service_tier(mut self, service_tier: Option<ServiceTier>) -> Self {
+        self.service_tier = service_tier;
+        self
+    }
+
+    pub fn sampling(mut self, sampling: SamplingParameters) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn extra_body(mut self, extra_body: Map<String, Value>) -> Self {
+        self.extra_body = extra_body;
+        self
+    }
+
     pub fn build(self, _provider: &Provider) -> Result<ChatRequest, ApiError> {
         let mut messages = Vec::<Value>::new();
         messages.push(json!({"role": "system", "content": self.instructions}));
@@ -69,7 +101,7 @@ impl<'a> ChatRequestBuilder<'a> {
                     last_emitted_role = Some("assistant")
                 }
                 ResponseItem::FunctionCallOutput { .. } => last_emitted_role = Some("tool"),
-                ResponseItem::Reasoning { .. } | ResponseItem::Other => {}
+                ResponseItem::Reasoning { .. } | ResponseItem::Other(_) => {}
                 ResponseItem::CustomToolCall { .. } => {}
                 ResponseItem::CustomToolCallOutput { .. } => {}
                 ResponseItem::WebSearchCall { .. } => {}
@@ -169,6 +201,14 @@ impl<'a> ChatRequestBuilder<'a> {
                                     json!({"type":"image_url","image_url": {"url": image_url}}),
                                 );
                             }
+                            // Core inlines attachments for Chat Completions
+                            // providers; an id can only show up in history
+                            // carried over from a Responses provider.
+                            ContentItem::InputFile { file_id } => {
+                                let t = format!("[uploaded file {file_id}]");
+                                text.push_str(&t);
+                                items.push(json!({"type":"text","text": t}));
+                            }
                         }
                     }
 
@@ -231,7 +271,17 @@ impl<'a> ChatRequestBuilder<'a> {
                     push_tool_call_message(&mut messages, tool_call, reasoning);
                 }
                 ResponseItem::FunctionCallOutput { call_id, output } => {
-                    let content_value = if let Some(items) = &output.content_items {
+                    let has_image = output.content_items.as_ref().is_some_and(|items| {
+                        items.iter().any(|it| {
+                            matches!(it, FunctionCallOutputContentItem::InputImage { .. })
+                        })
+                    });
+                    // Many Chat Completions servers only accept a string as
+                    // `tool` content, so structured output is flattened
+                    // unless it carries an image.
+                    let content_value = if let Some(items) = &output.content_items
+                        && has_image
+                    {
                         let mapped: Vec<Value> = items
                             .iter()
                             .map(|it| match it {
@@ -244,6 +294,17 @@ impl<'a> ChatRequestBuilder<'a> {
                             })
                             .collect();
                         json!(mapped)
+                    } else if let Some(items) = &output.content_items {
+                        let text: Vec<&str> = items
+                            .iter()
+                            .filter_map(|it| match it {
+                                FunctionCallOutputContentItem::InputText { text } => {
+                                    Some(text.as_str())
+                                }
+                                FunctionCallOutputContentItem::InputImage { .. } => None,
+                            })
+                            .collect();
+                        json!(text.join("\n"))
                     } else {
                         json!(output.content)
                     };
@@ -284,19 +345,46 @@ impl<'a> ChatRequestBuilder<'a> {
                 }
                 ResponseItem::Reasoning { .. }
                 | ResponseItem::WebSearchCall { .. }
-                | ResponseItem::Other
+                | ResponseItem::Other(_)
                 | ResponseItem::Compaction { .. } => {
                     continue;
                 }
             }
         }
 
-        let payload = json!({
+        let mut payload = json!({
             "model": self.model,
             "messages": messages,
             "stream": true,
             "tools": self.tools,
         });
+        if let Some(max_tokens) = self.max_tokens
+            && let Some(obj) = payload.as_object_mut()
+        {
+            obj.insert("max_tokens".to_string(), json!(max_tokens));
+        }
+        if let Some(service_tier) = self.service_tier
+            && let Some(obj) = payload.as_object_mut()
+        {
+            obj.insert("service_tier".to_string(), json!(service_tier));
+        }
+        if let Some(obj) = payload.as_object_mut() {
+            let SamplingParameters {
+                seed,
+                temperature,
+                top_p,
+            } = self.sampling;
+            if let Some(seed) = seed {
+                obj.insert("seed".to_string(), json!(seed));
+            }
+            if let Some(temperature) = temperature {
+                obj.insert("temperature".to_string(), json!(temperature));
+            }
+            if let Some(top_p) = top_p {
+                obj.insert("top_p".to_string(), json!(top_p));
+            }
+        }
+        merge_extra_body(&mut payload, &self.extra_body);
 
         let mut headers = build_conversation_headers(self.conversation_id);
         if let Some(subagent) = subagent_header(&self.session_source) {
@@ -375,6 +463,7 @@ mod tests {
                 retry_transport: true,
             },
             stream_idle_timeout: Duration::from_secs(1),
+            first_byte_timeout: None,
         }
     }
 
@@ -404,6 +493,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn appends_extra_body_after_builder_fields() {
+        let extra_body = json!({"top_k": 40, "repetition_penalty": 1.1})
+            .as_object()
+            .cloned()
+            .expect("object");
+        let req = ChatRequestBuilder::new("gpt-test", "inst", &[], &[])
+            .max_tokens(Some(256))
+            .service_tier(Some(ServiceTier::Flex))
+            .extra_body(extra_body)
+            .build(&provider())
+            .expect("request");
+
+        assert_eq!(req.body["top_k"], json!(40));
+        assert_eq!(req.body["repetition_penalty"], json!(1.1));
+        assert_eq!(req.body["max_tokens"], json!(256));
+        assert_eq!(req.body["service_tier"], json!("flex"));
+        assert_eq!(req.body["model"], json!("gpt-test"));
+    }
+
+    #[test]
+    fn sampling_parameters_take_precedence_over_extra_body() {
+        let extra_body = json!({"temperature": 1.0})
+            .as_object()
+            .cloned()
+            .expect("object");
+        let req = ChatRequestBuilder::new("gpt-test", "inst", &[], &[])
+            .sampling(SamplingParameters {
+                seed: Some(7),
+                temperature: Some(0.2),
+                top_p: None,
+            })
+            .extra_body(extra_body)
+            .build(&provider())
+            .expect("request");
+
+        assert_eq!(req.body["seed"], json!(7));
+        assert_eq!(req.body["temperature"], json!(0.2));
+        assert_eq!(req.body.get("top_p"), None);
+    }
+
     #[test]
     fn groups_consecutive_tool_calls_into_a_single_assistant_message() {
         let prompt_input = vec![
@@ -489,4 +619,61 @@ mod tests {
         assert_eq!(messages[5]["role"], "tool");
         assert_eq!(messages[5]["tool_call_id"], "call-c");
     }
+
+    #[test]
+    fn flattens_text_only_tool_output_and_keeps_images_structured() {
+        let text_output = FunctionCallOutputPayload {
+            content: r#"[{"type":"text","text":"first"}]"#.to_string(),
+            content_items: Some(vec![
+                FunctionCallOutputContentItem::InputText {
+                    text: "first".to_string(),
+                },
+                FunctionCallOutputContentItem::InputText {
+                    text: "second".to_string(),
+                },
+            ]),
+            ..Default::default()
+        };
+        let image_output = FunctionCallOutputPayload {
+            content_items: Some(vec![FunctionCallOutputContentItem::InputImage {
+                image_url: "data:image/png;base64,AAA".to_string(),
+            }]),
+            ..Default::default()
+        };
+        let prompt_input = vec![
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "mcp__docs__search".to_string(),
+                arguments: "{}".to_string(),
+                call_id: "call-a".to_string(),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "view_image".to_string(),
+                arguments: "{}".to_string(),
+                call_id: "call-b".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-a".to_string(),
+                output: text_output,
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-b".to_string(),
+                output: image_output,
+            },
+        ];
+
+        let req = ChatRequestBuilder::new("gpt-test", "inst", &prompt_input, &[])
+            .build(&provider())
+            .expect("request");
+
+        let messages = req.body["messages"].as_array().expect("messages array");
+        assert_eq!(messages[2]["tool_call_id"], "call-a");
+        assert_eq!(messages[2]["content"], json!("first\nsecond"));
+        assert_eq!(messages[3]["tool_call_id"], "call-b");
+        assert_eq!(
+            messages[3]["content"],
+            json!([{"type": "image_url", "image_url": {"url": "data:image/png;base64,AAA"}}])
+        );
+    }
 }
@@ -2,7 +2,42 @@ pub mod chat;
 pub(crate) mod headers;
 pub mod responses;
 
+use serde_json::Map;
+use serde_json::Value;
+
 pub use chat::ChatRequest;
 pub use chat::ChatRequestBuilder;
 pub use responses::ResponsesRequest;
 pub use responses::ResponsesRequestBuilder;
+
+/// Top-level body fields set by the Responses and Chat Completions request
+/// builders. User-supplied `extra_body` entries must not use these keys.
+pub const RESERVED_REQUEST_FIELDS: &[&str] = &[
+    "model",
+    "instructions",
+    "input",
+    "messages",
+    "tools",
+    "tool_choice",
+    "parallel_tool_calls",
+    "reasoning",
+    "store",
+    "stream",
+    "include",
+    "prompt_cache_key",
+    "text",
+    "max_output_tokens",
+    "max_tokens",
+    "service_tier",
+];
+
+/// Appends `extra_body` entries to the top level of a serialized request,
+/// leaving fields the builder already set untouched.
+pub(crate) fn merge_extra_body(body: &mut Value, extra_body: &Map<String, Value>) {
+    let Some(obj) = body.as_object_mut() else {
+        return;
+    };
+    for (key, value) in extra_body {
+        obj.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+}
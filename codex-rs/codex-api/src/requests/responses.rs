@@ -6,9 +6,13 @@ use crate::provider::Provider;
 use crate::requests::headers::build_conversation_headers;
 use crate::requests::headers::insert_header;
 use crate::requests::headers::subagent_header;
+use crate::requests::merge_extra_body;
+use codex_protocol::config_types::SamplingParameters;
+use codex_protocol::config_types::ServiceTier;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::SessionSource;
 use http::HeaderMap;
+use serde_json::Map;
 use serde_json::Value;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -39,6 +43,10 @@ pub struct ResponsesRequestBuilder<'a> {
     conversation_id: Option<String>,
     session_source: Option<SessionSource>,
     store_override: Option<bool>,
+    max_output_tokens: Option<i64>,
+    service_tier: Option<ServiceTier>,
+    sampling: SamplingParameters,
+    extra_body: Map<String, Value>,
     headers: HeaderMap,
     compression: Compression,
 }
@@ -98,6 +106,26 @@ impl<'a> ResponsesRequestBuilder<'a> {
         self
     }
 
+    pub fn max_output_tokens(mut self, max_output_tokens: Option<i64>) -> Self {
+        self.max_output_tokens = max_output_tokens;
+        self
+    }
+
+    pub fn service_tier(mut self, service_tier: Option<ServiceTier>) -> Self {
+        self.service_tier = service_tier;
+        self
+    }
+
+    pub fn sampling(mut self, sampling: SamplingParameters) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn extra_body(mut self, extra_body: Map<String, Value>) -> Self {
+        self.extra_body = extra_body;
+        self
+    }
+
     pub fn extra_headers(mut self, headers: HeaderMap) -> Self {
         self.headers = headers;
         self
@@ -137,6 +165,9 @@ impl<'a> ResponsesRequestBuilder<'a> {
             include: self.include,
             prompt_cache_key: self.prompt_cache_key,
             text: self.text,
+            max_output_tokens: self.max_output_tokens,
+            service_tier: self.service_tier,
+            sampling: self.sampling,
         };
 
         let mut body = serde_json::to_value(&req)
@@ -145,6 +176,7 @@ impl<'a> ResponsesRequestBuilder<'a> {
         if store && provider.is_azure_responses_endpoint() {
             attach_item_ids(&mut body, input);
         }
+        merge_extra_body(&mut body, &self.extra_body);
 
         let mut headers = self.headers;
         headers.extend(build_conversation_headers(self.conversation_id));
@@ -212,6 +244,7 @@ mod tests {
                 retry_transport: true,
             },
             stream_idle_timeout: Duration::from_secs(5),
+            first_byte_timeout: None,
         }
     }
 
@@ -260,4 +293,25 @@ mod tests {
             Some(&HeaderValue::from_static("review"))
         );
     }
+
+    #[test]
+    fn appends_extra_body_without_overriding_builder_fields() {
+        let provider = provider("openai", "https://api.openai.com/v1");
+        let mut extra_body = Map::new();
+        extra_body.insert("top_k".to_string(), Value::from(40));
+        extra_body.insert("model".to_string(), Value::from("other-model"));
+
+        let request = ResponsesRequestBuilder::new("gpt-test", "inst", &[])
+            .max_output_tokens(Some(512))
+            .extra_body(extra_body)
+            .build(&provider)
+            .expect("request");
+
+        assert_eq!(request.body.get("top_k"), Some(&Value::from(40)));
+        assert_eq!(request.body.get("model"), Some(&Value::from("gpt-test")));
+        assert_eq!(
+            request.body.get("max_output_tokens"),
+            Some(&Value::from(512))
+        );
+    }
 }
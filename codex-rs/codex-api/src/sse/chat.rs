@@ -1,6 +1,7 @@
 use crate::common::ResponseEvent;
 use crate::common::ResponseStream;
 use crate::error::ApiError;
+use crate::sse::responses::provider_stream_error;
 use crate::telemetry::SseTelemetry;
 use codex_client::StreamResponse;
 use codex_protocol::models::ContentItem;
@@ -9,8 +10,8 @@ use codex_protocol::models::ResponseItem;
 use eventsource_stream::Eventsource;
 use futures::Stream;
 use futures::StreamExt;
+use serde_json::Value;
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::OnceLock;
 use std::time::Duration;
@@ -19,6 +20,7 @@ use tokio::time::Instant;
 use tokio::time::timeout;
 use tracing::debug;
 use tracing::trace;
+use uuid::Uuid;
 
 pub(crate) fn spawn_chat_stream(
     stream_response: StreamResponse,
@@ -58,27 +60,22 @@ pub async fn process_chat_sse<S>(
 {
     let mut stream = stream.eventsource();
 
-    #[derive(Default, Debug)]
-    struct ToolCallState {
-        id: Option<String>,
-        name: Option<String>,
-        arguments: String,
-    }
-
-    let mut tool_calls: HashMap<usize, ToolCallState> = HashMap::new();
-    let mut tool_call_order: Vec<usize> = Vec::new();
-    let mut tool_call_order_seen: HashSet<usize> = HashSet::new();
-    let mut tool_call_index_by_id: HashMap<String, usize> = HashMap::new();
-    let mut next_tool_call_index = 0usize;
-    let mut last_tool_call_index: Option<usize> = None;
+    let mut tool_calls = ToolCallAssembler::default();
     let mut assistant_item: Option<ResponseItem> = None;
     let mut reasoning_item: Option<ResponseItem> = None;
+    let mut refusal = String::new();
     let mut completed_sent = false;
+    let mut service_tier_sent = false;
+    let mut system_fingerprint_sent = false;
 
+    /// Ends the response when the stream stops without a `stop` finish
+    /// reason. Tool calls still pending here come from providers that end
+    /// the stream without any finish reason, so they are emitted as is.
     async fn flush_and_complete(
         tx_event: &mpsc::Sender<Result<ResponseEvent, ApiError>>,
         reasoning_item: &mut Option<ResponseItem>,
         assistant_item: &mut Option<ResponseItem>,
+        tool_calls: &mut ToolCallAssembler,
     ) {
         if let Some(reasoning) = reasoning_item.take() {
             let _ = tx_event
@@ -86,6 +83,10 @@ pub async fn process_chat_sse<S>(
                 .await;
         }
 
+        for item in tool_calls.drain() {
+            let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+        }
+
         if let Some(assistant) = assistant_item.take() {
             let _ = tx_event
                 .send(Ok(ResponseEvent::OutputItemDone(assistant)))
@@ -102,7 +103,12 @@ pub async fn process_chat_sse<S>(
 
     loop {
         let start = Instant::now();
-        let response = timeout(idle_timeout, stream.next()).await;
+        let response = tokio::select! {
+            response = timeout(idle_timeout, stream.next()) => response,
+            // The consumer dropped the stream, e.g. because the turn was
+            // interrupted: stop reading so the connection gets closed.
+            () = tx_event.closed() => return,
+        };
         if let Some(t) = telemetry.as_ref() {
             t.on_sse_poll(&response, start.elapsed());
         }
@@ -114,7 +120,13 @@ pub async fn process_chat_sse<S>(
             }
             Ok(None) => {
                 if !completed_sent {
-                    flush_and_complete(&tx_event, &mut reasoning_item, &mut assistant_item).await;
+                    flush_and_complete(
+                        &tx_event,
+                        &mut reasoning_item,
+                        &mut assistant_item,
+                        &mut tool_calls,
+                    )
+                    .await;
                 }
                 return;
             }
@@ -136,7 +148,13 @@ pub async fn process_chat_sse<S>(
 
         if data == "[DONE]" || data == "DONE" {
             if !completed_sent {
-                flush_and_complete(&tx_event, &mut reasoning_item, &mut assistant_item).await;
+                flush_and_complete(
+                    &tx_event,
+                    &mut reasoning_item,
+                    &mut assistant_item,
+                    &mut tool_calls,
+                )
+                .await;
             }
             return;
         }
@@ -152,6 +170,41 @@ pub async fn process_chat_sse<S>(
             }
         };
 
+        // Some providers report a failure mid-stream as a chunk carrying only
+        // an `error` object, then send `[DONE]`. Keep the text that already
+        // streamed; tool calls cut off mid-arguments are dropped.
+        if let Some(error) = value.get("error").filter(|error| !error.is_null()) {
+            for item in [reasoning_item.take(), assistant_item.take()]
+                .into_iter()
+                .flatten()
+            {
+                let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+            }
+            let _ = tx_event
+                .send(Err(provider_stream_error(Some(error), None, None)))
+                .await;
+            return;
+        }
+
+        // Every chunk repeats the tier the provider used; report it once.
+        if !service_tier_sent && let Some(tier) = value.get("service_tier").and_then(Value::as_str)
+        {
+            service_tier_sent = true;
+            let _ = tx_event
+                .send(Ok(ResponseEvent::ServiceTier(tier.to_string())))
+                .await;
+        }
+        if !system_fingerprint_sent
+            && let Some(fingerprint) = value.get("system_fingerprint").and_then(Value::as_str)
+        {
+            system_fingerprint_sent = true;
+            let _ = tx_event
+                .send(Ok(ResponseEvent::SystemFingerprint(
+                    fingerprint.to_string(),
+                )))
+                .await;
+        }
+
         let Some(choices) = value.get("choices").and_then(|c| c.as_array()) else {
             continue;
         };
@@ -189,57 +242,13 @@ pub async fn process_chat_sse<S>(
                     }
                 }
 
+                if let Some(text) = delta.get("refusal").and_then(|r| r.as_str()) {
+                    refusal.push_str(text);
+                }
+
                 if let Some(tool_call_values) = delta.get("tool_calls").and_then(|c| c.as_array()) {
                     for tool_call in tool_call_values {
-                        let mut index = tool_call
-                            .get("index")
-                            .and_then(serde_json::Value::as_u64)
-                            .map(|i| i as usize);
-
-                        let mut call_id_for_lookup = None;
-                        if let Some(call_id) = tool_call.get("id").and_then(|i| i.as_str()) {
-                            call_id_for_lookup = Some(call_id.to_string());
-                            if let Some(existing) = tool_call_index_by_id.get(call_id) {
-                                index = Some(*existing);
-                            }
-                        }
-
-                        if index.is_none() && call_id_for_lookup.is_none() {
-                            index = last_tool_call_index;
-                        }
-
-                        let index = index.unwrap_or_else(|| {
-                            while tool_calls.contains_key(&next_tool_call_index) {
-                                next_tool_call_index += 1;
-                            }
-                            let idx = next_tool_call_index;
-                            next_tool_call_index += 1;
-                            idx
-                        });
-
-                        let call_state = tool_calls.entry(index).or_default();
-                        if tool_call_order_seen.insert(index) {
-                            tool_call_order.push(index);
-                        }
-
-                        if let Some(id) = tool_call.get("id").and_then(|i| i.as_str()) {
-                            call_state.id.get_or_insert_with(|| id.to_string());
-                            tool_call_index_by_id.entry(id.to_string()).or_insert(index);
-                        }
-
-                        if let Some(func) = tool_call.get("function") {
-                            if let Some(fname) = func.get("name").and_then(|n| n.as_str())
-                                && !fname.is_empty()
-                            {
-                                call_state.name.get_or_insert_with(|| fname.to_string());
-                            }
-                            if let Some(arguments) = func.get("arguments").and_then(|a| a.as_str())
-                            {
-                                call_state.arguments.push_str(arguments);
-                            }
-                        }
-
-                        last_tool_call_index = Some(index);
+                        tool_calls.push_delta(tool_call);
                     }
                 }
             }
@@ -264,6 +273,12 @@ pub async fn process_chat_sse<S>(
                         .await;
                 }
 
+                if !refusal.is_empty() {
+                    let _ = tx_event
+                        .send(Ok(ResponseEvent::Refusal(std::mem::take(&mut refusal))))
+                        .await;
+                }
+
                 if let Some(assistant) = assistant_item.take() {
                     let _ = tx_event
                         .send(Ok(ResponseEvent::OutputItemDone(assistant)))
@@ -281,8 +296,32 @@ pub async fn process_chat_sse<S>(
                 continue;
             }
 
-            if finish_reason == Some("length") {
-                let _ = tx_event.send(Err(ApiError::ContextWindowExceeded)).await;
+            if let Some(reason @ ("length" | "content_filter")) = finish_reason {
+                // The output token cap was reached or the content filter cut
+                // the response off. Keep the text streamed so far; a
+                // partially streamed tool call is unusable and dropped.
+                if let Some(reasoning) = reasoning_item.take() {
+                    let _ = tx_event
+                        .send(Ok(ResponseEvent::OutputItemDone(reasoning)))
+                        .await;
+                }
+                if let Some(assistant) = assistant_item.take() {
+                    let _ = tx_event
+                        .send(Ok(ResponseEvent::OutputItemDone(assistant)))
+                        .await;
+                }
+                let reason = if reason == "length" {
+                    "max_output_tokens"
+                } else {
+                    reason
+                };
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::Incomplete {
+                        response_id: String::new(),
+                        token_usage: None,
+                        reason: Some(reason.to_string()),
+                    }))
+                    .await;
                 return;
             }
 
@@ -293,26 +332,7 @@ pub async fn process_chat_sse<S>(
                         .await;
                 }
 
-                for index in tool_call_order.drain(..) {
-                    let Some(state) = tool_calls.remove(&index) else {
-                        continue;
-                    };
-                    tool_call_order_seen.remove(&index);
-                    let ToolCallState {
-                        id,
-                        name,
-                        arguments,
-                    } = state;
-                    let Some(name) = name else {
-                        debug!("Skipping tool call at index {index} because name is missing");
-                        continue;
-                    };
-                    let item = ResponseItem::FunctionCall {
-                        id: None,
-                        name,
-                        arguments,
-                        call_id: id.unwrap_or_else(|| format!("tool-call-{index}")),
-                    };
+                for item in tool_calls.drain() {
                     let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
                 }
             }
@@ -320,6 +340,128 @@ pub async fn process_chat_sse<S>(
     }
 }
 
+#[derive(Default, Debug)]
+struct ToolCallState {
+    id: Option<String>,
+    name: Option<String>,
+    arguments: String,
+}
+
+/// Assembles streamed `tool_calls` deltas into function calls.
+///
+/// OpenAI sends `index` on every fragment and `id` only on the first.
+/// Other providers are looser: OpenRouter upstreams may omit `index`, and
+/// Ollama sends each call whole in one chunk, reusing `index: 0` for every
+/// call and sometimes leaving out `id`.
+#[derive(Default, Debug)]
+struct ToolCallAssembler {
+    /// Calls in the order they started.
+    calls: Vec<ToolCallState>,
+    slot_by_id: HashMap<String, usize>,
+    slot_by_index: HashMap<usize, usize>,
+    last_slot: Option<usize>,
+}
+
+impl ToolCallAssembler {
+    fn push_delta(&mut self, tool_call: &Value) {
+        let index = tool_call
+            .get("index")
+            .and_then(Value::as_u64)
+            .map(|i| i as usize);
+        let id = tool_call
+            .get("id")
+            .and_then(Value::as_str)
+            .filter(|id| !id.is_empty());
+        let function = tool_call.get("function");
+        let name = function
+            .and_then(|f| f.get("name"))
+            .and_then(Value::as_str)
+            .filter(|name| !name.is_empty());
+
+        let slot = match id.and_then(|id| self.slot_by_id.get(id)) {
+            Some(slot) => Some(*slot),
+            None => {
+                let candidate = match index {
+                    Some(index) => self.slot_by_index.get(&index).copied(),
+                    None if id.is_none() => self.last_slot,
+                    None => None,
+                };
+                candidate.filter(|slot| self.continues(*slot, id, name))
+            }
+        };
+        let slot = slot.unwrap_or_else(|| {
+            self.calls.push(ToolCallState::default());
+            self.calls.len() - 1
+        });
+        if let Some(index) = index {
+            self.slot_by_index.insert(index, slot);
+        }
+        self.last_slot = Some(slot);
+
+        let state = &mut self.calls[slot];
+        if let Some(id) = id
+            && state.id.is_none()
+        {
+            state.id = Some(id.to_string());
+            self.slot_by_id.insert(id.to_string(), slot);
+        }
+        if let Some(name) = name {
+            state.name.get_or_insert_with(|| name.to_string());
+        }
+        match function.and_then(|f| f.get("arguments")) {
+            Some(Value::String(arguments)) => state.arguments.push_str(arguments),
+            // Some providers send the arguments as a JSON object.
+            Some(arguments @ Value::Object(_)) => state.arguments.push_str(&arguments.to_string()),
+            _ => {}
+        }
+    }
+
+    /// Whether a fragment without a known `id` belongs to the call in
+    /// `slot`. A different `id`, or a new name after the call's arguments
+    /// already form a complete JSON value, starts a new call.
+    fn continues(&self, slot: usize, id: Option<&str>, name: Option<&str>) -> bool {
+        let state = &self.calls[slot];
+        if id.is_some() && state.id.is_some() {
+            return false;
+        }
+        let restarts = name.is_some()
+            && state.name.is_some()
+            && serde_json::from_str::<Value>(&state.arguments).is_ok();
+        !restarts
+    }
+
+    fn drain(&mut self) -> Vec<ResponseItem> {
+        self.slot_by_id.clear();
+        self.slot_by_index.clear();
+        self.last_slot = None;
+        self.calls
+            .drain(..)
+            .filter_map(|state| {
+                let Some(name) = state.name else {
+                    debug!("Skipping streamed tool call without a name");
+                    return None;
+                };
+                let arguments = if state.arguments.trim().is_empty() {
+                    "{}".to_string()
+                } else {
+                    state.arguments
+                };
+                // Calls without an id still need one that is unique across
+                // the conversation, since the output is matched by it.
+                let call_id = state
+                    .id
+                    .unwrap_or_else(|| format!("call_{}", Uuid::new_v4().simple()));
+                Some(ResponseItem::FunctionCall {
+                    id: None,
+                    name,
+                    arguments,
+                    call_id,
+                })
+            })
+            .collect()
+    }
+}
+
 async fn append_assistant_text(
     tx_event: &mpsc::Sender<Result<ResponseEvent, ApiError>>,
     assistant_item: &mut Option<ResponseItem>,
@@ -387,6 +529,7 @@ mod tests {
     use assert_matches::assert_matches;
     use codex_protocol::models::ResponseItem;
     use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
     use serde_json::json;
     use tokio::sync::mpsc;
     use tokio_util::io::ReaderStream;
@@ -427,6 +570,93 @@ mod tests {
         out
     }
 
+    #[tokio::test]
+    async fn error_chunk_keeps_streamed_text_and_fails_the_stream() {
+        let body = build_body(&[
+            json!({"choices": [{"delta": {"content": "Partial answer"}}]}),
+            json!({
+                "error": {
+                    "message": "Provider returned error",
+                    "code": 502,
+                    "metadata": {"provider_name": "Azure"}
+                }
+            }),
+        ]) + "event: message\ndata: [DONE]\n\n";
+        let reader = ReaderStream::new(std::io::Cursor::new(body))
+            .map_err(|err| codex_client::TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_chat_sse(
+            reader,
+            tx,
+            Duration::from_millis(1000),
+            None,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            events.push(ev);
+        }
+
+        let [.., Ok(ResponseEvent::OutputItemDone(item)), Err(err)] = &events[..] else {
+            panic!("unexpected events: {events:?}");
+        };
+        assert_matches!(
+            item,
+            ResponseItem::Message { role, content, .. }
+                if role == "assistant"
+                    && matches!(&content[..], [ContentItem::OutputText { text }] if text == "Partial answer")
+        );
+        assert_matches!(
+            err,
+            ApiError::Stream(message) if message == "Provider returned error (code: 502)"
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_service_tier_once() {
+        let chunk = |text: &str| {
+            json!({
+                "service_tier": "priority",
+                "choices": [{"delta": {"content": text}}]
+            })
+        };
+        let body = build_body(&[chunk("Hel"), chunk("lo")]);
+
+        let events = collect_events(&body).await;
+
+        let tiers: Vec<&str> = events
+            .iter()
+            .filter_map(|event| match event {
+                ResponseEvent::ServiceTier(tier) => Some(tier.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tiers, vec!["priority"]);
+        assert_matches!(events.last(), Some(ResponseEvent::Completed { .. }));
+    }
+
+    #[tokio::test]
+    async fn reports_system_fingerprint_once() {
+        let chunk = |text: &str| {
+            json!({
+                "system_fingerprint": "fp_44709d6fcb",
+                "choices": [{"delta": {"content": text}}]
+            })
+        };
+        let body = build_body(&[chunk("Hel"), chunk("lo")]);
+
+        let events = collect_events(&body).await;
+
+        let fingerprints: Vec<&str> = events
+            .iter()
+            .filter_map(|event| match event {
+                ResponseEvent::SystemFingerprint(fingerprint) => Some(fingerprint.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fingerprints, vec!["fp_44709d6fcb"]);
+    }
+
     #[tokio::test]
     async fn concatenates_tool_call_arguments_across_deltas() {
         let delta_name = json!({
@@ -644,6 +874,186 @@ mod tests {
         );
     }
 
+    fn function_calls(events: &[ResponseEvent]) -> Vec<(String, String, String)> {
+        events
+            .iter()
+            .filter_map(|ev| match ev {
+                ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                    call_id,
+                    name,
+                    arguments,
+                    ..
+                }) => Some((call_id.clone(), name.clone(), arguments.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Ollama streams each call whole, all at `index: 0` and without ids.
+    #[tokio::test]
+    async fn splits_whole_calls_that_share_an_index_without_ids() {
+        let shell_call = json!({
+            "choices": [{
+                "delta": {
+                    "tool_calls": [{
+                        "index": 0,
+                        "function": { "name": "shell", "arguments": "{\"command\":[\"ls\"]}" }
+                    }]
+                }
+            }]
+        });
+        let read_call = json!({
+            "choices": [{
+                "delta": {
+                    "tool_calls": [{
+                        "index": 0,
+                        "function": { "name": "read_file", "arguments": "{\"path\":\"a.txt\"}" }
+                    }]
+                }
+            }]
+        });
+        let finish = json!({
+            "choices": [{
+                "finish_reason": "tool_calls"
+            }]
+        });
+
+        let body = build_body(&[shell_call.clone(), finish.clone()]);
+        let earlier_calls = function_calls(&collect_events(&body).await);
+        let body = build_body(&[shell_call, read_call, finish]);
+        let calls = function_calls(&collect_events(&body).await);
+
+        let names_and_args: Vec<(&str, &str)> = calls
+            .iter()
+            .map(|(_, name, arguments)| (name.as_str(), arguments.as_str()))
+            .collect();
+        assert_eq!(
+            names_and_args,
+            vec![
+                ("shell", "{\"command\":[\"ls\"]}"),
+                ("read_file", "{\"path\":\"a.txt\"}"),
+            ]
+        );
+        // Generated ids must not repeat within or across responses.
+        let mut ids: Vec<&str> = calls
+            .iter()
+            .chain(earlier_calls.iter())
+            .map(|(call_id, _, _)| call_id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.iter().all(|id| !id.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn separates_calls_that_reuse_an_index_with_new_ids() {
+        let delta = json!({
+            "choices": [{
+                "delta": {
+                    "tool_calls": [
+                        {
+                            "index": 0,
+                            "id": "call_a",
+                            "function": { "name": "do_a", "arguments": "{}" }
+                        },
+                        {
+                            "index": 0,
+                            "id": "call_b",
+                            "function": { "name": "do_b", "arguments": "{}" }
+                        }
+                    ]
+                }
+            }]
+        });
+        let finish = json!({
+            "choices": [{
+                "finish_reason": "tool_calls"
+            }]
+        });
+
+        let body = build_body(&[delta, finish]);
+        let events = collect_events(&body).await;
+        assert_eq!(
+            function_calls(&events),
+            vec![
+                ("call_a".to_string(), "do_a".to_string(), "{}".to_string()),
+                ("call_b".to_string(), "do_b".to_string(), "{}".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn accepts_object_arguments_and_fills_in_missing_arguments() {
+        let delta = json!({
+            "choices": [{
+                "delta": {
+                    "tool_calls": [
+                        {
+                            "index": 0,
+                            "id": "call_a",
+                            "function": { "name": "read_file", "arguments": { "path": "a.txt" } }
+                        },
+                        {
+                            "index": 1,
+                            "id": "call_b",
+                            "function": { "name": "list_mcp_resources", "arguments": "" }
+                        }
+                    ]
+                }
+            }]
+        });
+        let finish = json!({
+            "choices": [{
+                "finish_reason": "tool_calls"
+            }]
+        });
+
+        let body = build_body(&[delta, finish]);
+        let events = collect_events(&body).await;
+        assert_eq!(
+            function_calls(&events),
+            vec![
+                (
+                    "call_a".to_string(),
+                    "read_file".to_string(),
+                    "{\"path\":\"a.txt\"}".to_string()
+                ),
+                (
+                    "call_b".to_string(),
+                    "list_mcp_resources".to_string(),
+                    "{}".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn emits_pending_tool_calls_when_stream_ends_without_finish_reason() {
+        let delta = json!({
+            "choices": [{
+                "delta": {
+                    "tool_calls": [{
+                        "index": 0,
+                        "id": "call_a",
+                        "function": { "name": "do_a", "arguments": "{}" }
+                    }]
+                }
+            }]
+        });
+
+        let mut body = build_body(&[delta]);
+        body.push_str("event: message\ndata: [DONE]\n\n");
+        let events = collect_events(&body).await;
+        assert_matches!(
+            &events[..],
+            [
+                ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { call_id, .. }),
+                ResponseEvent::Completed { .. }
+            ] if call_id == "call_a"
+        );
+    }
+
     #[tokio::test]
     async fn emits_tool_calls_even_when_content_and_reasoning_present() {
         let delta_content_and_tools = json!({
@@ -713,4 +1123,61 @@ mod tests {
         }));
         assert_matches!(events.last(), Some(ResponseEvent::Completed { .. }));
     }
+
+    #[tokio::test]
+    async fn length_finish_reason_keeps_text_and_reports_incomplete() {
+        let delta_text = json!({
+            "choices": [{
+                "delta": { "content": "partial answer" }
+            }]
+        });
+        let finish_length = json!({
+            "choices": [{
+                "finish_reason": "length"
+            }]
+        });
+
+        let body = build_body(&[delta_text, finish_length]);
+        let events = collect_events(&body).await;
+
+        assert_matches!(
+            &events[..],
+            [
+                ResponseEvent::OutputItemAdded(ResponseItem::Message { .. }),
+                ResponseEvent::OutputTextDelta(_),
+                ResponseEvent::OutputItemDone(ResponseItem::Message { .. }),
+                ResponseEvent::Incomplete { reason: Some(reason), .. }
+            ] if reason == "max_output_tokens"
+        );
+    }
+
+    #[tokio::test]
+    async fn refusal_delta_is_reported_as_refusal() {
+        let delta_refusal = json!({
+            "choices": [{
+                "delta": { "refusal": "I can't help " }
+            }]
+        });
+        let delta_refusal_rest = json!({
+            "choices": [{
+                "delta": { "refusal": "with that." }
+            }]
+        });
+        let finish_stop = json!({
+            "choices": [{
+                "finish_reason": "stop"
+            }]
+        });
+
+        let body = build_body(&[delta_refusal, delta_refusal_rest, finish_stop]);
+        let events = collect_events(&body).await;
+
+        assert_matches!(
+            &events[..],
+            [
+                ResponseEvent::Refusal(text),
+                ResponseEvent::Completed { .. }
+            ] if text == "I can't help with that."
+        );
+    }
 }
@@ -109,6 +109,20 @@ struct ResponseCompleted {
     usage: Option<ResponseCompletedUsage>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ResponseIncomplete {
+    id: String,
+    #[serde(default)]
+    usage: Option<ResponseCompletedUsage>,
+    #[serde(default)]
+    incomplete_details: Option<IncompleteDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IncompleteDetails {
+    reason: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ResponseDone {
     #[serde(default)]
@@ -156,13 +170,54 @@ struct ResponseCompletedOutputTokensDetails {
 
 #[derive(Deserialize, Debug)]
 pub struct ResponsesStreamEvent {
-    #[serde(rename = "type")]
+    /// Empty for the bare `{"error": ...}` chunks some providers send.
+    #[serde(rename = "type", default)]
     kind: String,
     response: Option<Value>,
     item: Option<Value>,
     delta: Option<String>,
     summary_index: Option<i64>,
     content_index: Option<i64>,
+    /// Details of an `error` event, for providers that nest them.
+    error: Option<Value>,
+    /// Details of an `error` event, for providers that put them at the top
+    /// level.
+    message: Option<String>,
+    code: Option<Value>,
+}
+
+impl ResponsesStreamEvent {
+    /// Whether this is an error the provider reported mid-stream, after
+    /// which no more output follows.
+    fn is_error(&self) -> bool {
+        self.kind == "error" || (self.kind.is_empty() && self.error.is_some())
+    }
+
+    /// `service_tier` the provider reports on the final response object,
+    /// which is the tier it actually used rather than the one requested.
+    pub fn served_service_tier(&self) -> Option<String> {
+        self.final_response_field("service_tier")
+    }
+
+    /// `system_fingerprint` on the final response object, for providers
+    /// that report one.
+    pub fn system_fingerprint(&self) -> Option<String> {
+        self.final_response_field("system_fingerprint")
+    }
+
+    fn final_response_field(&self, key: &str) -> Option<String> {
+        if !matches!(
+            self.kind.as_str(),
+            "response.completed" | "response.done" | "response.incomplete"
+        ) {
+            return None;
+        }
+        self.response
+            .as_ref()?
+            .get(key)?
+            .as_str()
+            .map(ToString::to_string)
+    }
 }
 
 #[derive(Debug)]
@@ -181,9 +236,19 @@ impl ResponsesEventError {
 pub fn process_responses_event(
     event: ResponsesStreamEvent,
 ) -> std::result::Result<Option<ResponseEvent>, ResponsesEventError> {
+    if event.is_error() {
+        return Err(ResponsesEventError::Api(provider_stream_error(
+            event.error.as_ref(),
+            event.message.as_deref(),
+            event.code.as_ref(),
+        )));
+    }
     match event.kind.as_str() {
         "response.output_item.done" => {
             if let Some(item_val) = event.item {
+                if let Some(refusal) = refusal_text(&item_val) {
+                    return Ok(Some(ResponseEvent::Refusal(refusal)));
+                }
                 if let Ok(item) = serde_json::from_value::<ResponseItem>(item_val) {
                     return Ok(Some(ResponseEvent::OutputItemDone(item)));
                 }
@@ -204,10 +269,12 @@ pub fn process_responses_event(
             }
         }
         "response.reasoning_text.delta" => {
-            if let (Some(delta), Some(content_index)) = (event.delta, event.content_index) {
+            // Some models that stream raw reasoning only ever produce one
+            // content part and omit its index.
+            if let Some(delta) = event.delta {
                 return Ok(Some(ResponseEvent::ReasoningContentDelta {
                     delta,
-                    content_index,
+                    content_index: event.content_index.unwrap_or(0),
                 }));
             }
         }
@@ -228,6 +295,11 @@ pub fn process_responses_event(
                         response_error = ApiError::QuotaExceeded;
                     } else if is_usage_not_included(&error) {
                         response_error = ApiError::UsageNotIncluded;
+                    } else if is_content_filter_error(&error) {
+                        let message = error.message.unwrap_or_else(|| {
+                            "The response was blocked by the content filter.".to_string()
+                        });
+                        response_error = ApiError::ContentFiltered { message };
                     } else if is_invalid_prompt_error(&error) {
                         let message = error
                             .message
@@ -263,6 +335,24 @@ pub fn process_responses_event(
                 }
             }
         }
+        "response.incomplete" => {
+            if let Some(resp_val) = event.response {
+                match serde_json::from_value::<ResponseIncomplete>(resp_val) {
+                    Ok(resp) => {
+                        return Ok(Some(ResponseEvent::Incomplete {
+                            response_id: resp.id,
+                            token_usage: resp.usage.map(Into::into),
+                            reason: resp.incomplete_details.and_then(|details| details.reason),
+                        }));
+                    }
+                    Err(err) => {
+                        let error = format!("failed to parse ResponseIncomplete: {err}");
+                        debug!("{error}");
+                        return Err(ResponsesEventError::Api(ApiError::Stream(error)));
+                    }
+                }
+            }
+        }
         "response.done" => {
             if let Some(resp_val) = event.response {
                 match serde_json::from_value::<ResponseDone>(resp_val) {
@@ -320,7 +410,12 @@ pub async fn process_sse(
 
     loop {
         let start = Instant::now();
-        let response = timeout(idle_timeout, stream.next()).await;
+        let response = tokio::select! {
+            response = timeout(idle_timeout, stream.next()) => response,
+            // The consumer dropped the stream, e.g. because the turn was
+            // interrupted: stop reading so the connection gets closed.
+            () = tx_event.closed() => return,
+        };
         if let Some(t) = telemetry.as_ref() {
             t.on_sse_poll(&response, start.elapsed());
         }
@@ -348,6 +443,16 @@ pub async fn process_sse(
 
         trace!("SSE event: {}", &sse.data);
 
+        // Chat-style terminator sent by some compatible backends, with or
+        // without a preceding error.
+        if sse.data.trim() == "[DONE]" {
+            let error = response_error.unwrap_or(ApiError::Stream(
+                "stream ended with [DONE] before response.completed".into(),
+            ));
+            let _ = tx_event.send(Err(error)).await;
+            return;
+        }
+
         let event: ResponsesStreamEvent = match serde_json::from_str(&sse.data) {
             Ok(event) => event,
             Err(e) => {
@@ -356,9 +461,27 @@ pub async fn process_sse(
             }
         };
 
+        if let Some(tier) = event.served_service_tier()
+            && tx_event
+                .send(Ok(ResponseEvent::ServiceTier(tier)))
+                .await
+                .is_err()
+        {
+            return;
+        }
+        if let Some(fingerprint) = event.system_fingerprint()
+            && tx_event
+                .send(Ok(ResponseEvent::SystemFingerprint(fingerprint)))
+                .await
+                .is_err()
+        {
+            return;
+        }
+
+        let is_error = event.is_error();
         match process_responses_event(event) {
             Ok(Some(event)) => {
-                let is_completed = matches!(event, ResponseEvent::Completed { .. });
+                let is_completed = event.is_terminal();
                 if tx_event.send(Ok(event)).await.is_err() {
                     return;
                 }
@@ -367,6 +490,10 @@ pub async fn process_sse(
                 }
             }
             Ok(None) => {}
+            Err(error) if is_error => {
+                let _ = tx_event.send(Err(error.into_api_error())).await;
+                return;
+            }
             Err(error) => {
                 response_error = Some(error.into_api_error());
             }
@@ -400,6 +527,41 @@ fn try_parse_retry_after(err: &Error) -> Option<Duration> {
     None
 }
 
+/// An error the provider reported inside the stream rather than with
+/// `response.failed`. Providers put the message and code either under
+/// `error` or at the top level of the event, and some use numeric codes.
+pub(crate) fn provider_stream_error(
+    error: Option<&Value>,
+    message: Option<&str>,
+    code: Option<&Value>,
+) -> ApiError {
+    let message = error
+        .and_then(|error| error.as_str().or_else(|| error.get("message")?.as_str()))
+        .or(message)
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .unwrap_or("the provider reported an error");
+    let code = error
+        .and_then(|error| {
+            error
+                .get("code")
+                .filter(|code| !code.is_null())
+                .or_else(|| error.get("type"))
+        })
+        .or(code)
+        .and_then(|code| match code {
+            Value::String(code) => Some(code.clone()),
+            Value::Number(code) => Some(code.to_string()),
+            _ => None,
+        });
+    match code.as_deref() {
+        Some("context_length_exceeded") => ApiError::ContextWindowExceeded,
+        Some("insufficient_quota") => ApiError::QuotaExceeded,
+        Some(code) => ApiError::Stream(format!("{message} (code: {code})")),
+        None => ApiError::Stream(message.to_string()),
+    }
+}
+
 fn is_context_window_error(error: &Error) -> bool {
     error.code.as_deref() == Some("context_length_exceeded")
 }
@@ -416,6 +578,26 @@ fn is_invalid_prompt_error(error: &Error) -> bool {
     error.code.as_deref() == Some("invalid_prompt")
 }
 
+fn is_content_filter_error(error: &Error) -> bool {
+    error.code.as_deref() == Some("content_filter")
+}
+
+/// The text of any `refusal` content parts in an assistant message item.
+/// Refusals replace the message, so other parts are ignored.
+fn refusal_text(item: &Value) -> Option<String> {
+    if item.get("type").and_then(Value::as_str) != Some("message") {
+        return None;
+    }
+    let refusals: Vec<&str> = item
+        .get("content")?
+        .as_array()?
+        .iter()
+        .filter(|part| part.get("type").and_then(Value::as_str) == Some("refusal"))
+        .filter_map(|part| part.get("refusal").and_then(Value::as_str))
+        .collect();
+    (!refusals.is_empty()).then(|| refusals.join("\n"))
+}
+
 fn rate_limit_regex() -> &'static regex_lite::Regex {
     static RE: std::sync::OnceLock<regex_lite::Regex> = std::sync::OnceLock::new();
     #[expect(clippy::unwrap_used)]
@@ -545,6 +727,112 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn incomplete_response_ends_stream_with_reason() {
+        let item = json!({
+            "type": "response.output_item.done",
+            "item": {
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "output_text", "text": "Partial"}]
+            }
+        })
+        .to_string();
+
+        let incomplete = json!({
+            "type": "response.incomplete",
+            "response": {
+                "id": "resp1",
+                "status": "incomplete",
+                "incomplete_details": { "reason": "max_output_tokens" },
+                "usage": {
+                    "input_tokens": 10,
+                    "output_tokens": 64,
+                    "total_tokens": 74
+                }
+            }
+        })
+        .to_string();
+
+        let sse1 = format!("event: response.output_item.done\ndata: {item}\n\n");
+        let sse2 = format!("event: response.incomplete\ndata: {incomplete}\n\n");
+
+        let events = collect_events(&[sse1.as_bytes(), sse2.as_bytes()]).await;
+
+        assert_eq!(events.len(), 2);
+        match &events[1] {
+            Ok(ResponseEvent::Incomplete {
+                response_id,
+                token_usage,
+                reason,
+            }) => {
+                assert_eq!(response_id, "resp1");
+                assert_eq!(
+                    token_usage.as_ref().map(|usage| usage.output_tokens),
+                    Some(64)
+                );
+                assert_eq!(reason.as_deref(), Some("max_output_tokens"));
+            }
+            other => panic!("unexpected second event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_item_types_are_kept_verbatim() {
+        let future_item = json!({
+            "type": "future_widget",
+            "id": "fw_1",
+            "payload": {"nested": [1, 2, 3]}
+        });
+        let events = run_sse(vec![
+            json!({"type": "response.output_item.added", "item": future_item}),
+            json!({"type": "response.output_item.done", "item": future_item}),
+            json!({"type": "response.completed", "response": {"id": "resp1"}}),
+        ])
+        .await;
+
+        assert_eq!(events.len(), 3);
+        assert_matches!(
+            &events[0],
+            ResponseEvent::OutputItemAdded(ResponseItem::Other(value)) if value == &future_item
+        );
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemDone(ResponseItem::Other(value)) if value == &future_item
+        );
+        assert_matches!(&events[2], ResponseEvent::Completed { .. });
+    }
+
+    #[tokio::test]
+    async fn served_service_tier_precedes_completed() {
+        let events = run_sse(vec![
+            json!({"type": "response.created", "response": {"id": "resp1", "service_tier": "auto"}}),
+            json!({"type": "response.completed", "response": {"id": "resp1", "service_tier": "flex"}}),
+        ])
+        .await;
+
+        assert_eq!(events.len(), 3);
+        assert_matches!(&events[0], ResponseEvent::Created);
+        assert_matches!(&events[1], ResponseEvent::ServiceTier(tier) if tier == "flex");
+        assert_matches!(&events[2], ResponseEvent::Completed { .. });
+    }
+
+    #[tokio::test]
+    async fn system_fingerprint_precedes_completed() {
+        let events = run_sse(vec![
+            json!({"type": "response.created", "response": {"id": "resp1"}}),
+            json!({"type": "response.completed", "response": {"id": "resp1", "system_fingerprint": "fp_44709d6fcb"}}),
+        ])
+        .await;
+
+        assert_eq!(events.len(), 3);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::SystemFingerprint(fingerprint) if fingerprint == "fp_44709d6fcb"
+        );
+        assert_matches!(&events[2], ResponseEvent::Completed { .. });
+    }
+
     #[tokio::test]
     async fn error_when_missing_completed() {
         let item1 = json!({
@@ -692,6 +980,82 @@ mod tests {
         }
     }
 
+    async fn stream_error_message(raw: &str) -> (Vec<Result<ResponseEvent, ApiError>>, String) {
+        let mut events = collect_events(&[raw.as_bytes()]).await;
+        match events.pop() {
+            Some(Err(ApiError::Stream(message))) => (events, message),
+            other => panic!("unexpected last event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn error_event_with_top_level_details_keeps_earlier_output() {
+        let item = json!({
+            "type": "response.output_item.done",
+            "item": {
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "output_text", "text": "Partial"}]
+            }
+        });
+        let raw = format!(
+            "event: response.output_item.done\ndata: {item}\n\n\
+             event: error\ndata: {}\n\n",
+            r#"{"type":"error","sequence_number":7,"code":"server_error","message":"The server had an error while processing your request. Sorry about that!","param":null}"#
+        );
+
+        let (events, message) = stream_error_message(&raw).await;
+
+        assert_matches!(&events[..], [Ok(ResponseEvent::OutputItemDone(_))]);
+        assert_eq!(
+            message,
+            "The server had an error while processing your request. Sorry about that! (code: server_error)"
+        );
+    }
+
+    #[tokio::test]
+    async fn error_event_with_nested_details() {
+        let raw = "event: error\ndata: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n";
+
+        let (events, message) = stream_error_message(raw).await;
+
+        assert!(events.is_empty());
+        assert_eq!(message, "Overloaded (code: overloaded_error)");
+    }
+
+    #[tokio::test]
+    async fn bare_error_object_before_done_sentinel() {
+        let raw = "data: {\"error\":{\"message\":\"Rate limit exceeded: free-models-per-min\",\"code\":429,\"metadata\":{\"headers\":{}}}}\n\n\
+                   data: [DONE]\n\n";
+
+        let (events, message) = stream_error_message(raw).await;
+
+        assert!(events.is_empty());
+        assert_eq!(
+            message,
+            "Rate limit exceeded: free-models-per-min (code: 429)"
+        );
+    }
+
+    #[tokio::test]
+    async fn done_sentinel_without_completed_is_an_error() {
+        let (_, message) = stream_error_message("data: [DONE]\n\n").await;
+
+        assert_eq!(
+            message,
+            "stream ended with [DONE] before response.completed"
+        );
+    }
+
+    #[tokio::test]
+    async fn error_event_with_context_window_code_is_fatal() {
+        let raw = "event: error\ndata: {\"type\":\"error\",\"code\":\"context_length_exceeded\",\"message\":\"Too long\"}\n\n";
+
+        let events = collect_events(&[raw.as_bytes()]).await;
+
+        assert_matches!(&events[..], [Err(ApiError::ContextWindowExceeded)]);
+    }
+
     #[tokio::test]
     async fn context_window_error_is_fatal() {
         let raw_error = r#"{"type":"response.failed","sequence_number":3,"response":{"id":"resp_5c66275b97b9baef1ed95550adb3b7ec13b17aafd1d2f11b","object":"response","created_at":1759510079,"status":"failed","background":false,"error":{"code":"context_length_exceeded","message":"Your input exceeds the context window of this model. Please adjust your input and try again."},"usage":null,"user":null,"metadata":{}}}"#;
@@ -731,6 +1095,75 @@ mod tests {
         assert_matches!(events[0], Err(ApiError::QuotaExceeded));
     }
 
+    #[tokio::test]
+    async fn content_filter_failure_is_content_filtered() {
+        let raw_error = r#"{"type":"response.failed","sequence_number":3,"response":{"id":"resp_filtered","object":"response","created_at":1759771630,"status":"failed","background":false,"error":{"code":"content_filter","message":"The response was filtered due to the prompt triggering content management policy."},"incomplete_details":null}}"#;
+
+        let sse1 = format!("event: response.failed\ndata: {raw_error}\n\n");
+
+        let events = collect_events(&[sse1.as_bytes()]).await;
+
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            Err(ApiError::ContentFiltered { message }) => assert_eq!(
+                message,
+                "The response was filtered due to the prompt triggering content management policy."
+            ),
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn reasoning_text_delta_without_content_index_is_first_part() {
+        let events = run_sse(vec![
+            json!({"type": "response.reasoning_text.delta", "delta": "first"}),
+            json!({
+                "type": "response.reasoning_text.delta",
+                "delta": "second",
+                "content_index": 1
+            }),
+            json!({"type": "response.completed", "response": {"id": "resp1"}}),
+        ])
+        .await;
+
+        let deltas: Vec<(String, i64)> = events
+            .into_iter()
+            .filter_map(|event| match event {
+                ResponseEvent::ReasoningContentDelta {
+                    delta,
+                    content_index,
+                } => Some((delta, content_index)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            deltas,
+            vec![("first".to_string(), 0), ("second".to_string(), 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn refusal_content_part_is_reported_as_refusal() {
+        let item = json!({
+            "type": "response.output_item.done",
+            "item": {
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "refusal", "refusal": "I can't help with that."}]
+            }
+        })
+        .to_string();
+        let sse1 = format!("event: response.output_item.done\ndata: {item}\n\n");
+
+        let events = collect_events(&[sse1.as_bytes()]).await;
+
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            Ok(ResponseEvent::Refusal(text)) => assert_eq!(text, "I can't help with that."),
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn invalid_prompt_without_type_is_invalid_request() {
         let raw_error = r#"{"type":"response.failed","sequence_number":3,"response":{"id":"resp_invalid_prompt_no_type","object":"response","created_at":1759771628,"status":"failed","background":false,"error":{"code":"invalid_prompt","message":"Invalid prompt: we've limited access to this content for safety reasons."},"incomplete_details":null}}"#;
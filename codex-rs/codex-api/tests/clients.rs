@@ -134,12 +134,40 @@ fn provider(name: &str, wire: WireApi) -> Provider {
             retry_transport: true,
         },
         stream_idle_timeout: Duration::from_millis(10),
+        first_byte_timeout: None,
+    }
+}
+
+/// Stands in for an env-backed provider whose signed header rotates between
+/// reads.
+#[derive(Clone, Default)]
+struct RotatingHeaderAuth {
+    reads: Arc<Mutex<u64>>,
+}
+
+impl AuthProvider for RotatingHeaderAuth {
+    fn bearer_token(&self) -> Option<String> {
+        None
+    }
+
+    fn attempt_headers(&self) -> HeaderMap {
+        let mut reads = self
+            .reads
+            .lock()
+            .unwrap_or_else(|err| panic!("mutex poisoned: {err}"));
+        *reads += 1;
+        let mut headers = HeaderMap::new();
+        if let Ok(value) = format!("sig-{reads}").parse() {
+            headers.insert("X-Gateway-Signature", value);
+        }
+        headers
     }
 }
 
 #[derive(Clone)]
 struct FlakyTransport {
     state: Arc<Mutex<i64>>,
+    requests: RecordingState,
 }
 
 impl Default for FlakyTransport {
@@ -152,6 +180,7 @@ impl FlakyTransport {
     fn new() -> Self {
         Self {
             state: Arc::new(Mutex::new(0)),
+            requests: RecordingState::default(),
         }
     }
 
@@ -169,7 +198,8 @@ impl HttpTransport for FlakyTransport {
         Err(TransportError::Build("execute should not run".to_string()))
     }
 
-    async fn stream(&self, _req: Request) -> Result<StreamResponse, TransportError> {
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        self.requests.record(req);
         let mut attempts = self
             .state
             .lock()
@@ -321,3 +351,35 @@ async fn streaming_client_retries_on_transport_error() -> Result<()> {
     assert_eq!(transport.attempts(), 2);
     Ok(())
 }
+
+#[tokio::test]
+async fn streaming_client_resolves_attempt_headers_on_each_retry() -> Result<()> {
+    let transport = FlakyTransport::new();
+
+    let mut provider = provider("openai", WireApi::Responses);
+    provider.retry.max_attempts = 2;
+
+    let client = ResponsesClient::new(transport.clone(), provider, RotatingHeaderAuth::default());
+
+    let body = serde_json::json!({ "model": "gpt-test" });
+    let _stream = client
+        .stream(body, HeaderMap::new(), Compression::None, None)
+        .await?;
+
+    let signatures: Vec<Option<String>> = transport
+        .requests
+        .take_stream_requests()
+        .iter()
+        .map(|req| {
+            req.headers
+                .get("X-Gateway-Signature")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        })
+        .collect();
+    assert_eq!(
+        signatures,
+        vec![Some("sig-1".to_string()), Some("sig-2".to_string())]
+    );
+    Ok(())
+}
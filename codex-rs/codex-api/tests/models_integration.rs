@@ -43,6 +43,7 @@ fn provider(base_url: &str) -> Provider {
             retry_transport: true,
         },
         stream_idle_timeout: std::time::Duration::from_secs(1),
+        first_byte_timeout: None,
     }
 }
 
@@ -78,6 +79,7 @@ async fn models_client_hits_models_endpoint() {
             upgrade: None,
             base_instructions: "base instructions".to_string(),
             model_messages: None,
+            supports_reasoning: false,
             supports_reasoning_summaries: false,
             support_verbosity: false,
             default_verbosity: None,
@@ -88,6 +90,7 @@ async fn models_client_hits_models_endpoint() {
             auto_compact_token_limit: None,
             effective_context_window_percent: 95,
             experimental_supported_tools: Vec::new(),
+            max_output_tokens: None,
         }],
     };
 
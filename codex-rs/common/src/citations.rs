@@ -0,0 +1,240 @@
+//! Detection of file references in agent messages.
+//!
+//! Two forms are recognized: the bracketed `【F:path†L12】` citations some
+//! models emit, and free-form mentions such as `core/src/client.rs:120` or
+//! "`client.rs` around line 120". Free-form candidates are only reported when
+//! they name a file that exists relative to the session's working directory,
+//! which keeps ordinary prose like "e.g." or version numbers out of the list.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use regex_lite::Regex;
+
+/// Messages longer than this are not scanned, so that rendering a huge
+/// message never waits on thousands of filesystem checks.
+pub const MAX_CITATION_SCAN_BYTES: usize = 32 * 1024;
+
+/// A file mentioned in a message, checked against the filesystem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Citation {
+    /// Byte range of the mention in the scanned text, including any
+    /// `:line` or `#Lline` suffix attached to the path.
+    pub range: Range<usize>,
+    /// The file the mention resolved to.
+    pub path: PathBuf,
+    pub line: Option<u32>,
+    pub end_line: Option<u32>,
+}
+
+#[expect(clippy::expect_used)]
+static BRACKETED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"【F:([^†】]+)†L(\d+)(?:-L(\d+))?】").expect("bracketed citation regex is valid")
+});
+
+#[expect(clippy::expect_used)]
+static FREE_FORM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r#"(?:^|[\s(\[{<"'`])"#,
+        // The path, optionally followed by `:12`, `:12-20`, `:12:5` or `#L12-L20`.
+        r"((?:\.{1,2}/|/)?[\w.@+-]+(?:/[\w.@+-]+)*)",
+        r"(?::(\d+)(?:-(\d+))?(?::\d+)?|#L(\d+)(?:-L?(\d+))?)?",
+        // Or followed by prose such as "around line 120" or "lines 10-20".
+        r"(?:`?,?\s+\(?(?:(?:around|near|at|on|in)\s+)?lines?\s+(\d+)(?:\s*(?:-|–|to)\s*(\d+))?)?",
+    ))
+    .expect("free-form citation regex is valid")
+});
+
+/// Finds file citations in messages, remembering which paths exist so that
+/// each distinct path is checked at most once per message.
+#[derive(Debug)]
+pub struct CitationResolver {
+    cwd: PathBuf,
+    verified: HashMap<String, Option<PathBuf>>,
+}
+
+impl CitationResolver {
+    pub fn new(cwd: impl Into<PathBuf>) -> Self {
+        Self {
+            cwd: cwd.into(),
+            verified: HashMap::new(),
+        }
+    }
+
+    /// Citations in `text`, ordered by position. Returns nothing for text
+    /// longer than [`MAX_CITATION_SCAN_BYTES`].
+    pub fn find(&mut self, text: &str) -> Vec<Citation> {
+        if text.len() > MAX_CITATION_SCAN_BYTES {
+            return Vec::new();
+        }
+
+        let mut citations = Vec::new();
+        for caps in BRACKETED.captures_iter(text) {
+            let (Some(whole), Some(path)) = (caps.get(0), caps.get(1)) else {
+                continue;
+            };
+            // Bracketed citations are deliberate, but they still have to name
+            // a real file to be openable.
+            if let Some(resolved) = self.resolve(path.as_str().trim()) {
+                citations.push(Citation {
+                    range: whole.range(),
+                    path: resolved,
+                    line: parse_line(caps.get(2)),
+                    end_line: parse_line(caps.get(3)),
+                });
+            }
+        }
+
+        let bracketed: Vec<Range<usize>> = citations.iter().map(|c| c.range.clone()).collect();
+        for caps in FREE_FORM.captures_iter(text) {
+            let Some(path) = caps.get(1) else {
+                continue;
+            };
+            // Sentence punctuation is not part of the path: "see main.rs."
+            let candidate = path.as_str().trim_end_matches('.');
+            let start = path.start();
+            if !looks_like_path(candidate)
+                || bracketed
+                    .iter()
+                    .any(|range| range.start <= start && start < range.end)
+            {
+                continue;
+            }
+            let Some(resolved) = self.resolve(candidate) else {
+                continue;
+            };
+            let suffix_end = [2, 3, 4, 5]
+                .into_iter()
+                .filter_map(|group| caps.get(group).map(|m| m.end()))
+                .max();
+            let (line, end_line) = if caps.get(2).is_some() {
+                (parse_line(caps.get(2)), parse_line(caps.get(3)))
+            } else if caps.get(4).is_some() {
+                (parse_line(caps.get(4)), parse_line(caps.get(5)))
+            } else {
+                (parse_line(caps.get(6)), parse_line(caps.get(7)))
+            };
+            citations.push(Citation {
+                range: start..suffix_end.unwrap_or(start + candidate.len()),
+                path: resolved,
+                line,
+                end_line,
+            });
+        }
+
+        citations.sort_by_key(|citation| citation.range.start);
+        citations
+    }
+
+    fn resolve(&mut self, candidate: &str) -> Option<PathBuf> {
+        if let Some(resolved) = self.verified.get(candidate) {
+            return resolved.clone();
+        }
+        let path = Path::new(candidate);
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.cwd.join(path)
+        };
+        let resolved = path.is_file().then_some(path);
+        self.verified
+            .insert(candidate.to_string(), resolved.clone());
+        resolved
+    }
+}
+
+/// Convenience wrapper for scanning a single message.
+pub fn find_citations(text: &str, cwd: &Path) -> Vec<Citation> {
+    CitationResolver::new(cwd).find(text)
+}
+
+/// Skip words that cannot be file references before touching the
+/// filesystem: a candidate needs a directory separator or an extension.
+fn looks_like_path(candidate: &str) -> bool {
+    if candidate.contains('/') {
+        return candidate.trim_matches('/').chars().any(char::is_alphabetic);
+    }
+    match candidate.rsplit_once('.') {
+        Some((stem, extension)) => {
+            !stem.is_empty() && !extension.is_empty() && extension.chars().any(char::is_alphabetic)
+        }
+        None => false,
+    }
+}
+
+fn parse_line(group: Option<regex_lite::Match<'_>>) -> Option<u32> {
+    group.and_then(|m| m.as_str().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn workspace() -> TempDir {
+        let dir = TempDir::new().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("core/src")).expect("mkdir");
+        std::fs::write(dir.path().join("core/src/client.rs"), "").expect("write");
+        std::fs::write(dir.path().join("README.md"), "").expect("write");
+        dir
+    }
+
+    fn summarize<'a>(
+        text: &'a str,
+        citations: &[Citation],
+    ) -> Vec<(&'a str, Option<u32>, Option<u32>)> {
+        citations
+            .iter()
+            .map(|c| (&text[c.range.clone()], c.line, c.end_line))
+            .collect()
+    }
+
+    #[test]
+    fn finds_free_form_mentions_of_existing_files() {
+        let dir = workspace();
+        let text = "The retry loop in core/src/client.rs around line 120 calls \
+                    `README.md`, see core/src/client.rs:40-52 and README.md#L3.";
+        let citations = find_citations(text, dir.path());
+
+        assert_eq!(
+            summarize(text, &citations),
+            vec![
+                ("core/src/client.rs", Some(120), None),
+                ("README.md", None, None),
+                ("core/src/client.rs:40-52", Some(40), Some(52)),
+                ("README.md#L3", Some(3), None),
+            ]
+        );
+        assert_eq!(citations[0].path, dir.path().join("core/src/client.rs"));
+    }
+
+    #[test]
+    fn ignores_paths_that_do_not_exist() {
+        let dir = workspace();
+        let text = "Version 1.2.3, e.g. src/missing.rs:10 or https://example.com/a.rs";
+        assert_eq!(find_citations(text, dir.path()), Vec::new());
+    }
+
+    #[test]
+    fn finds_bracketed_citations() {
+        let dir = workspace();
+        let text = "Before 【F:core/src/client.rs†L1-L4】 after";
+        let citations = find_citations(text, dir.path());
+
+        assert_eq!(
+            summarize(text, &citations),
+            vec![("【F:core/src/client.rs†L1-L4】", Some(1), Some(4))]
+        );
+    }
+
+    #[test]
+    fn skips_messages_over_the_scan_limit() {
+        let dir = workspace();
+        let text = format!("README.md {}", "x".repeat(MAX_CITATION_SCAN_BYTES));
+        assert_eq!(find_citations(&text, dir.path()), Vec::new());
+    }
+}
@@ -15,6 +15,9 @@ pub fn create_config_summary_entries(config: &Config, model: &str) -> Vec<(&'sta
             summarize_sandbox_policy(config.sandbox_policy.get()),
         ),
     ];
+    if let Some(dotenv) = &config.dotenv {
+        entries.push(("env file", dotenv.path.display().to_string()));
+    }
     if config.model_provider.wire_api == WireApi::Responses {
         let reasoning_effort = config
             .model_reasoning_effort
@@ -37,3 +37,5 @@ pub mod fuzzy_match;
 pub mod approval_presets;
 // Shared OSS provider utilities used by TUI and exec
 pub mod oss;
+// File citations in agent messages, shared by the TUI and exec JSON output
+pub mod citations;
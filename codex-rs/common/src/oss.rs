@@ -47,6 +47,64 @@ pub async fn ollama_chat_deprecation_notice(
     Ok(None)
 }
 
+/// Probes the local Ollama or LM Studio server for the configured model and
+/// records what it reports under `config.model_capabilities`, unless the user
+/// already declared it: the context length, and `prompted_tools` when the
+/// model cannot call tools natively. Returns warnings to show before the
+/// session starts: the server is unreachable, the model is missing, or its
+/// tools have to go through the prompt.
+pub async fn apply_oss_model_details(config: &mut Config) -> Vec<String> {
+    let Some(model) = config.model.clone() else {
+        return Vec::new();
+    };
+    let (server, details) = match config.model_provider_id.as_str() {
+        OLLAMA_OSS_PROVIDER_ID | OLLAMA_CHAT_PROVIDER_ID => (
+            "Ollama",
+            codex_ollama::fetch_local_model_details(&config.model_provider, &model)
+                .await
+                .map(|details| (details.context_window, details.supports_tools)),
+        ),
+        LMSTUDIO_OSS_PROVIDER_ID => (
+            "LM Studio",
+            codex_lmstudio::fetch_local_model_details(config, &model)
+                .await
+                .map(|details| (details.context_window, details.supports_tools)),
+        ),
+        _ => return Vec::new(),
+    };
+    let (context_window, supports_tools) = match details {
+        Ok(details) => details,
+        Err(err) => return vec![err.to_string()],
+    };
+
+    let mut warnings = Vec::new();
+    if supports_tools == Some(false) {
+        let declared = config
+            .model_capabilities
+            .get(&model)
+            .and_then(|capabilities| capabilities.prompted_tools);
+        if declared == Some(false) {
+            warnings.push(format!(
+                "{server} reports that `{model}` does not support tool calling, and `prompted_tools` is off for it, so Codex cannot run commands or edit files with it."
+            ));
+        } else {
+            config
+                .model_capabilities
+                .entry(model.clone())
+                .or_default()
+                .prompted_tools = Some(true);
+            warnings.push(format!(
+                "{server} reports that `{model}` does not support tool calling, so Codex describes its tools in the prompt instead. Commands and edits only work as well as the model follows that format; choose a model with tool support for reliable results."
+            ));
+        }
+    }
+    if let Some(context_window) = context_window {
+        let capabilities = config.model_capabilities.entry(model).or_default();
+        capabilities.context_window.get_or_insert(context_window);
+    }
+    warnings
+}
+
 /// Ensures the specified OSS provider is ready (models downloaded, service reachable).
 pub async fn ensure_oss_provider_ready(
     provider_id: &str,
@@ -73,6 +131,155 @@ pub async fn ensure_oss_provider_ready(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_core::config::ConfigBuilder;
+    use codex_core::create_oss_provider_with_base_url;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    async fn oss_config(provider_id: &str, base_url: &str, model: &str) -> (TempDir, Config) {
+        let codex_home = TempDir::new().expect("create temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        let provider = create_oss_provider_with_base_url(base_url, WireApi::Chat);
+        config.model = Some(model.to_string());
+        config.model_provider_id = provider_id.to_string();
+        config.model_provider = provider.clone();
+        config
+            .model_providers
+            .insert(provider_id.to_string(), provider);
+        (codex_home, config)
+    }
+
+    /// Context window and `prompted_tools` recorded for `model`.
+    fn recorded(config: &Config, model: &str) -> (Option<i64>, Option<bool>) {
+        let capabilities = &config.model_capabilities[model];
+        (capabilities.context_window, capabilities.prompted_tools)
+    }
+
+    #[tokio::test]
+    async fn ollama_model_without_tools_uses_prompted_tools() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [{ "name": "gemma3:4b" }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/show"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model_info": { "gemma3.context_length": 32768 },
+                "capabilities": ["completion"]
+            })))
+            .mount(&server)
+            .await;
+        let (_codex_home, mut config) =
+            oss_config(OLLAMA_OSS_PROVIDER_ID, &server.uri(), "gemma3:4b").await;
+
+        let warnings = apply_oss_model_details(&mut config).await;
+
+        assert_eq!(
+            warnings,
+            vec![
+                "Ollama reports that `gemma3:4b` does not support tool calling, so Codex describes its tools in the prompt instead. Commands and edits only work as well as the model follows that format; choose a model with tool support for reliable results.".to_string()
+            ]
+        );
+        assert_eq!(recorded(&config, "gemma3:4b"), (Some(32768), Some(true)));
+    }
+
+    #[tokio::test]
+    async fn lmstudio_model_details_are_recorded() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "plain-model" }, { "id": "tool-model" }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v0/models/plain-model"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "max_context_length": 4096,
+                "capabilities": []
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v0/models/tool-model"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "max_context_length": 16384,
+                "capabilities": ["tool_use"]
+            })))
+            .mount(&server)
+            .await;
+        let base_url = format!("{}/v1", server.uri());
+
+        let (_codex_home, mut config) =
+            oss_config(LMSTUDIO_OSS_PROVIDER_ID, &base_url, "plain-model").await;
+        let warnings = apply_oss_model_details(&mut config).await;
+        assert_eq!(
+            warnings,
+            vec![
+                "LM Studio reports that `plain-model` does not support tool calling, so Codex describes its tools in the prompt instead. Commands and edits only work as well as the model follows that format; choose a model with tool support for reliable results.".to_string()
+            ]
+        );
+        assert_eq!(recorded(&config, "plain-model"), (Some(4096), Some(true)));
+
+        let (_codex_home, mut config) =
+            oss_config(LMSTUDIO_OSS_PROVIDER_ID, &base_url, "tool-model").await;
+        assert_eq!(
+            apply_oss_model_details(&mut config).await,
+            Vec::<String>::new()
+        );
+        assert_eq!(recorded(&config, "tool-model"), (Some(16384), None));
+    }
+
+    #[tokio::test]
+    async fn declined_prompted_tools_is_kept() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "plain-model" }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v0/models/plain-model"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "capabilities": []
+            })))
+            .mount(&server)
+            .await;
+        let base_url = format!("{}/v1", server.uri());
+        let (_codex_home, mut config) =
+            oss_config(LMSTUDIO_OSS_PROVIDER_ID, &base_url, "plain-model").await;
+        config
+            .model_capabilities
+            .entry("plain-model".to_string())
+            .or_default()
+            .prompted_tools = Some(false);
+
+        let warnings = apply_oss_model_details(&mut config).await;
+
+        assert_eq!(
+            warnings,
+            vec![
+                "LM Studio reports that `plain-model` does not support tool calling, and `prompted_tools` is off for it, so Codex cannot run commands or edit files with it.".to_string()
+            ]
+        );
+        assert_eq!(recorded(&config, "plain-model"), (None, Some(false)));
+    }
 
     #[test]
     fn test_get_default_model_for_provider_lmstudio() {
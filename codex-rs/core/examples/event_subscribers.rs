@@ -0,0 +1,85 @@
+//! Two independent consumers of one session's events, next to the primary `next_event` loop.
+//!
+//! Run with `cargo run -p codex-core --example event_subscribers -- "list the files here"`.
+//! Uses the regular `~/.codex` configuration and credentials.
+
+use codex_core::AuthManager;
+use codex_core::NewThread;
+use codex_core::ThreadManager;
+use codex_core::config::ConfigBuilder;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SessionSource;
+use codex_protocol::user_input::UserInput;
+use futures::StreamExt;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let prompt = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "Say hello.".to_string());
+
+    let config = ConfigBuilder::default().build().await?;
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        true,
+        config.cli_auth_credentials_store_mode,
+    );
+    let thread_manager =
+        ThreadManager::new(config.codex_home.clone(), auth_manager, SessionSource::Exec);
+    let NewThread {
+        thread_id, thread, ..
+    } = thread_manager.start_thread(config).await?;
+
+    // Logger: records every event it sees and reports gaps if it falls behind.
+    let mut log_events = thread.subscribe();
+    let logger = tokio::spawn(async move {
+        while let Some(event) = log_events.next().await {
+            eprintln!("[log] {} {:?}", event.id, event.msg);
+        }
+        eprintln!(
+            "[log] stream ended, skipped {}",
+            log_events.skipped_events()
+        );
+    });
+
+    // UI: only cares about streamed assistant text.
+    let mut ui_events = thread.subscribe();
+    let ui = tokio::spawn(async move {
+        while let Some(event) = ui_events.next().await {
+            if let EventMsg::AgentMessageDelta(delta) = event.msg {
+                print!("{}", delta.delta);
+            }
+        }
+        println!();
+    });
+
+    thread
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: prompt,
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    // The primary consumer drives the session: it decides when the turn is over.
+    loop {
+        let event = thread.next_event().await?;
+        match event.msg {
+            EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_) => {
+                thread.submit(Op::Shutdown).await?;
+            }
+            EventMsg::ShutdownComplete => break,
+            _ => {}
+        }
+    }
+
+    // Dropping the last handle to the thread ends both subscriber streams.
+    thread_manager.remove_thread(&thread_id).await;
+    drop(thread);
+    logger.await?;
+    ui.await?;
+    Ok(())
+}
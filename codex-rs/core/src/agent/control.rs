@@ -233,6 +233,7 @@ mod tests {
         let status = agent_status_from_event(&EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }));
         assert_eq!(status, Some(AgentStatus::Running));
     }
@@ -241,6 +242,7 @@ mod tests {
     async fn on_event_updates_status_from_task_complete() {
         let status = agent_status_from_event(&EventMsg::TurnComplete(TurnCompleteEvent {
             last_agent_message: Some("done".to_string()),
+            system_fingerprint: None,
         }));
         let expected = AgentStatus::Completed(Some("done".to_string()));
         assert_eq!(status, Some(expected));
@@ -251,6 +253,7 @@ mod tests {
         let status = agent_status_from_event(&EventMsg::Error(ErrorEvent {
             message: "boom".to_string(),
             codex_error_info: None,
+            ..Default::default()
         }));
 
         let expected = AgentStatus::Errored("boom".to_string());
@@ -261,6 +264,7 @@ mod tests {
     async fn on_event_updates_status_from_turn_aborted() {
         let status = agent_status_from_event(&EventMsg::TurnAborted(TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            cancelled: Vec::new(),
         }));
 
         let expected = AgentStatus::Errored("Interrupted".to_string());
@@ -6,6 +6,7 @@ use codex_api::error::ApiError;
 use codex_api::rate_limits::parse_rate_limit;
 use http::HeaderMap;
 use serde::Deserialize;
+use std::sync::Arc;
 
 use crate::auth::CodexAuth;
 use crate::error::CodexErr;
@@ -14,6 +15,7 @@ use crate::error::RetryLimitReachedError;
 use crate::error::UnexpectedResponseError;
 use crate::error::UsageLimitReachedError;
 use crate::model_provider_info::ModelProviderInfo;
+use crate::provider_env::DotenvFile;
 use crate::token_data::PlanType;
 
 pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
@@ -23,13 +25,11 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
         ApiError::UsageNotIncluded => CodexErr::UsageNotIncluded,
         ApiError::Retryable { message, delay } => CodexErr::Stream(message, delay),
         ApiError::Stream(msg) => CodexErr::Stream(msg, None),
-        ApiError::Api { status, message } => CodexErr::UnexpectedStatus(UnexpectedResponseError {
-            status,
-            body: message,
-            url: None,
-            request_id: None,
-        }),
+        ApiError::Api { status, message } => {
+            CodexErr::UnexpectedStatus(UnexpectedResponseError::new(status, message, None, None))
+        }
         ApiError::InvalidRequest { message } => CodexErr::InvalidRequest(message),
+        ApiError::ContentFiltered { message } => CodexErr::ContentFiltered(message),
         ApiError::Transport(transport) => match transport {
             TransportError::Http {
                 status,
@@ -44,6 +44,8 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
                         .contains("The image data you provided does not represent a valid image")
                     {
                         CodexErr::InvalidImageRequest()
+                    } else if let Some(message) = content_filter_message(&body_text) {
+                        CodexErr::ContentFiltered(message)
                     } else {
                         CodexErr::InvalidRequest(body_text)
                     }
@@ -89,12 +91,12 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
                         request_id: extract_request_id(headers.as_ref()),
                     })
                 } else {
-                    CodexErr::UnexpectedStatus(UnexpectedResponseError {
+                    CodexErr::UnexpectedStatus(UnexpectedResponseError::new(
                         status,
-                        body: body_text,
+                        body_text,
                         url,
-                        request_id: extract_request_id(headers.as_ref()),
-                    })
+                        extract_request_id(headers.as_ref()),
+                    ))
                 }
             }
             TransportError::RetryLimit => CodexErr::RetryLimit(RetryLimitReachedError {
@@ -116,9 +118,11 @@ const MODEL_CAP_RESET_AFTER_HEADER: &str = "x-codex-model-cap-reset-after-second
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model_provider_info::UrlTemplateVars;
     use codex_api::TransportError;
     use http::HeaderMap;
     use http::StatusCode;
+    use std::collections::HashMap;
 
     #[test]
     fn map_api_error_maps_model_cap_headers() {
@@ -144,6 +148,100 @@ mod tests {
         assert_eq!(model_cap.model, "boomslang");
         assert_eq!(model_cap.reset_after_seconds, Some(120));
     }
+
+    #[test]
+    fn map_api_error_maps_content_filter_bad_request() {
+        let body = r#"{"error":{"code":"content_filter","message":"The prompt was filtered.","status":400}}"#;
+        let err = map_api_error(ApiError::Transport(TransportError::Http {
+            status: StatusCode::BAD_REQUEST,
+            url: None,
+            headers: None,
+            body: Some(body.to_string()),
+        }));
+
+        let CodexErr::ContentFiltered(message) = err else {
+            panic!("expected CodexErr::ContentFiltered, got {err:?}");
+        };
+        assert_eq!(message, "The prompt was filtered.");
+    }
+
+    #[test]
+    fn refreshing_auth_provider_rereads_env_on_every_attempt() {
+        let provider = ModelProviderInfo {
+            env_key: Some("GATEWAY_KEY".to_string()),
+            env_http_headers: Some(
+                [(
+                    "X-Gateway-Signature".to_string(),
+                    "GATEWAY_SIGNATURE".to_string(),
+                )]
+                .into_iter()
+                .collect(),
+            ),
+            refresh_headers_per_attempt: true,
+            ..ModelProviderInfo::create_openai_provider()
+        };
+        let reads = Arc::new(std::sync::Mutex::new(HashMap::<String, usize>::new()));
+        let lookup: EnvLookup = {
+            let reads = Arc::clone(&reads);
+            Arc::new(move |var: &str| {
+                let mut reads = reads
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                let count = reads.entry(var.to_string()).or_default();
+                *count += 1;
+                Some(format!("{var}-{count}"))
+            })
+        };
+        let auth = CoreAuthProvider {
+            token: Some("GATEWAY_KEY-0".to_string()),
+            account_id: None,
+            refresh: Some(AttemptRefresh { provider, lookup }),
+        };
+
+        let attempts: Vec<(Option<String>, Option<String>)> = (0..2)
+            .map(|_| {
+                let headers = auth.attempt_headers();
+                let signature = headers
+                    .get("X-Gateway-Signature")
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                (auth.bearer_token(), signature)
+            })
+            .collect();
+
+        assert_eq!(
+            attempts,
+            vec![
+                (
+                    Some("GATEWAY_KEY-1".to_string()),
+                    Some("GATEWAY_SIGNATURE-1".to_string())
+                ),
+                (
+                    Some("GATEWAY_KEY-2".to_string()),
+                    Some("GATEWAY_SIGNATURE-2".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn refreshing_provider_leaves_env_headers_out_of_static_headers() {
+        let provider = ModelProviderInfo {
+            env_http_headers: Some(
+                [("X-Gateway-Signature".to_string(), "PATH".to_string())]
+                    .into_iter()
+                    .collect(),
+            ),
+            refresh_headers_per_attempt: true,
+            ..ModelProviderInfo::create_openai_provider()
+        };
+
+        let api_provider = provider
+            .to_api_provider(None, UrlTemplateVars::default(), None)
+            .expect("api provider should build");
+
+        assert_eq!(api_provider.headers.get("X-Gateway-Signature"), None);
+    }
 }
 
 fn extract_request_id(headers: Option<&HeaderMap>) -> Option<String> {
@@ -158,14 +256,33 @@ fn extract_request_id(headers: Option<&HeaderMap>) -> Option<String> {
     })
 }
 
+/// Resolves how requests to `provider` authenticate. `dotenv` is the
+/// session's dotenv file, consulted after the process environment.
 pub(crate) fn auth_provider_from_auth(
     auth: Option<CodexAuth>,
     provider: &ModelProviderInfo,
+    dotenv: Option<Arc<DotenvFile>>,
 ) -> crate::error::Result<CoreAuthProvider> {
-    if let Some(api_key) = provider.api_key()? {
+    let mut auth_provider = resolve_auth_provider(auth, provider, dotenv.as_deref())?;
+    if provider.refresh_headers_per_attempt {
+        auth_provider.refresh = Some(AttemptRefresh {
+            provider: provider.clone(),
+            lookup: Arc::new(move |var: &str| crate::provider_env::var(var, dotenv.as_deref())),
+        });
+    }
+    Ok(auth_provider)
+}
+
+fn resolve_auth_provider(
+    auth: Option<CodexAuth>,
+    provider: &ModelProviderInfo,
+    dotenv: Option<&DotenvFile>,
+) -> crate::error::Result<CoreAuthProvider> {
+    if let Some(api_key) = provider.api_key(dotenv)? {
         return Ok(CoreAuthProvider {
             token: Some(api_key),
             account_id: None,
+            refresh: None,
         });
     }
 
@@ -173,6 +290,7 @@ pub(crate) fn auth_provider_from_auth(
         return Ok(CoreAuthProvider {
             token: Some(token),
             account_id: None,
+            refresh: None,
         });
     }
 
@@ -181,15 +299,39 @@ pub(crate) fn auth_provider_from_auth(
         Ok(CoreAuthProvider {
             token: Some(token),
             account_id: auth.get_account_id(),
+            refresh: None,
         })
     } else {
         Ok(CoreAuthProvider {
             token: None,
             account_id: None,
+            refresh: None,
         })
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct ProviderErrorResponse {
+    error: ProviderErrorBody,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderErrorBody {
+    code: Option<String>,
+    message: Option<String>,
+}
+
+/// The message of a 400 response rejected by the provider's content filter,
+/// which Azure OpenAI reports with the `content_filter` error code.
+fn content_filter_message(body: &str) -> Option<String> {
+    let err = serde_json::from_str::<ProviderErrorResponse>(body).ok()?;
+    (err.error.code.as_deref() == Some("content_filter")).then(|| {
+        err.error
+            .message
+            .unwrap_or_else(|| "The request was blocked by the content filter.".to_string())
+    })
+}
+
 #[derive(Debug, Deserialize)]
 struct UsageErrorResponse {
     error: UsageErrorBody,
@@ -203,18 +345,49 @@ struct UsageErrorBody {
     resets_at: Option<i64>,
 }
 
+/// Reads an environment variable by name.
+type EnvLookup = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// Provider env settings re-read on every request attempt when
+/// `refresh_headers_per_attempt` is enabled.
+#[derive(Clone)]
+struct AttemptRefresh {
+    provider: ModelProviderInfo,
+    lookup: EnvLookup,
+}
+
 #[derive(Clone, Default)]
 pub(crate) struct CoreAuthProvider {
     token: Option<String>,
     account_id: Option<String>,
+    refresh: Option<AttemptRefresh>,
 }
 
 impl ApiAuthProvider for CoreAuthProvider {
     fn bearer_token(&self) -> Option<String> {
+        if let Some(refresh) = &self.refresh
+            && refresh.provider.env_key.is_some()
+        {
+            // Keep the key resolved at the start of the turn if the variable
+            // is briefly empty while it is being rotated.
+            return refresh
+                .provider
+                .api_key_from(|var| (refresh.lookup)(var))
+                .ok()
+                .flatten()
+                .or_else(|| self.token.clone());
+        }
         self.token.clone()
     }
 
     fn account_id(&self) -> Option<String> {
         self.account_id.clone()
     }
+
+    fn attempt_headers(&self) -> HeaderMap {
+        self.refresh
+            .as_ref()
+            .map(|refresh| refresh.provider.env_header_map(|var| (refresh.lookup)(var)))
+            .unwrap_or_default()
+    }
 }
@@ -1,5 +1,7 @@
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::git_info::dirty_paths;
+use crate::protocol::AskForApproval;
 use crate::protocol::FileChange;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
@@ -31,31 +33,57 @@ pub(crate) struct ApplyPatchExec {
     pub(crate) action: ApplyPatchAction,
     pub(crate) auto_approved: bool,
     pub(crate) exec_approval_requirement: ExecApprovalRequirement,
+    /// Target files that already had uncommitted changes in git.
+    pub(crate) dirty_paths: Vec<PathBuf>,
 }
 
+/// `approval_policy` is the policy in effect for this call, which can be
+/// stricter than `turn_context.approval_policy` (see
+/// `Session::effective_approval_policy`).
+///
+/// Target files with uncommitted changes are reported in the approval
+/// request. With `protect_dirty_files` set, they also prevent
+/// auto-approval.
 pub(crate) async fn apply_patch(
     turn_context: &TurnContext,
+    approval_policy: AskForApproval,
     action: ApplyPatchAction,
 ) -> InternalApplyPatchInvocation {
+    let dirty_paths = dirty_paths(&turn_context.cwd, &target_paths(&action)).await;
+    let protect_dirty_files =
+        !dirty_paths.is_empty() && turn_context.client.config().protect_dirty_files;
     match assess_patch_safety(
         &action,
-        turn_context.approval_policy,
+        approval_policy,
         &turn_context.sandbox_policy,
         &turn_context.cwd,
         turn_context.windows_sandbox_level,
     ) {
+        SafetyCheck::AutoApprove { .. }
+            if protect_dirty_files && approval_policy == AskForApproval::Never =>
+        {
+            let paths = dirty_paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(format!(
+                "patch rejected: files with uncommitted changes are protected ({paths})"
+            ))))
+        }
         SafetyCheck::AutoApprove {
             user_explicitly_approved,
             ..
-        } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+        } if !protect_dirty_files => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
             action,
             auto_approved: !user_explicitly_approved,
             exec_approval_requirement: ExecApprovalRequirement::Skip {
                 bypass_sandbox: false,
                 proposed_execpolicy_amendment: None,
             },
+            dirty_paths,
         }),
-        SafetyCheck::AskUser => {
+        SafetyCheck::AutoApprove { .. } | SafetyCheck::AskUser => {
             // Delegate the approval prompt (including cached approvals) to the
             // tool runtime, consistent with how shell/unified_exec approvals
             // are orchestrator-driven.
@@ -66,6 +94,7 @@ pub(crate) async fn apply_patch(
                     reason: None,
                     proposed_execpolicy_amendment: None,
                 },
+                dirty_paths,
             })
         }
         SafetyCheck::Reject { reason } => InternalApplyPatchInvocation::Output(Err(
@@ -74,6 +103,24 @@ pub(crate) async fn apply_patch(
     }
 }
 
+/// Every file the patch writes or removes, including move destinations.
+fn target_paths(action: &ApplyPatchAction) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for (path, change) in action.changes() {
+        paths.push(path.clone());
+        if let ApplyPatchFileChange::Update {
+            move_path: Some(dest),
+            ..
+        } = change
+        {
+            paths.push(dest.clone());
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
 pub(crate) fn convert_apply_patch_to_protocol(
     action: &ApplyPatchAction,
 ) -> HashMap<PathBuf, FileChange> {
@@ -0,0 +1,150 @@
+//! Ask mode: single-turn answers without tools.
+//!
+//! An ask turn sends [`ASK_INSTRUCTIONS`] instead of the regular base
+//! instructions and no tool specs, and skips the ghost snapshot. Sessions
+//! started with `Config::ask_mode` also skip connecting to MCP servers. Since
+//! the model cannot read files, callers attach the files a question refers to
+//! with `@path` up front.
+
+use std::path::Path;
+
+use codex_protocol::user_input::UserInput;
+
+pub(crate) const ASK_INSTRUCTIONS: &str = include_str!("../templates/ask/instructions.md");
+
+/// Most files attached to a single question.
+const MAX_ATTACHED_FILES: usize = 8;
+
+/// Files are cut off after this many bytes.
+const MAX_ATTACHED_FILE_BYTES: usize = 64 * 1024;
+
+/// Reads the files that `question` refers to as `@path`, relative to `cwd`,
+/// and returns them as input items to send after the question.
+///
+/// References that are not readable UTF-8 files are left alone, so e-mail
+/// addresses and `@mentions` pass through untouched.
+pub fn referenced_file_inputs(question: &str, cwd: &Path) -> Vec<UserInput> {
+    let mut seen = Vec::new();
+    let mut inputs = Vec::new();
+    for reference in file_references(question) {
+        if inputs.len() == MAX_ATTACHED_FILES {
+            break;
+        }
+        if seen.contains(&reference) {
+            continue;
+        }
+        seen.push(reference);
+        let path = cwd.join(reference);
+        if !path.is_file() {
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        let Some(text) = file_text(&bytes) else {
+            continue;
+        };
+        inputs.push(UserInput::Text {
+            text: format!("<file path=\"{reference}\">\n{text}\n</file>"),
+            text_elements: Vec::new(),
+        });
+    }
+    inputs
+}
+
+/// The `@path` tokens in `question`, without the `@` and trailing punctuation.
+fn file_references(question: &str) -> impl Iterator<Item = &str> {
+    question.split_whitespace().filter_map(|token| {
+        let reference = token
+            .strip_prefix('@')?
+            .trim_end_matches(['.', ',', ';', ':', '?', '!', ')', '"', '\'', '`']);
+        (!reference.is_empty()).then_some(reference)
+    })
+}
+
+/// The file contents as text, cut at [`MAX_ATTACHED_FILE_BYTES`], or `None`
+/// for binary files.
+fn file_text(bytes: &[u8]) -> Option<String> {
+    let truncated = bytes.len() > MAX_ATTACHED_FILE_BYTES;
+    let head = &bytes[..bytes.len().min(MAX_ATTACHED_FILE_BYTES)];
+    let text = match std::str::from_utf8(head) {
+        Ok(text) => text,
+        // The cut may split a character; anything else is binary.
+        Err(err) if truncated && err.error_len().is_none() => {
+            std::str::from_utf8(&head[..err.valid_up_to()]).ok()?
+        }
+        Err(_) => return None,
+    };
+    if text.contains('\0') {
+        return None;
+    }
+    if truncated {
+        return Some(format!(
+            "{text}\n[truncated after {MAX_ATTACHED_FILE_BYTES} bytes]"
+        ));
+    }
+    Some(text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn texts(inputs: Vec<UserInput>) -> Vec<String> {
+        inputs
+            .into_iter()
+            .map(|input| match input {
+                UserInput::Text { text, .. } => text,
+                other => panic!("unexpected input {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn attaches_referenced_files_once() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("src")).expect("mkdir");
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "pub fn answer() -> u32 { 42 }",
+        )
+        .expect("write");
+
+        let inputs = referenced_file_inputs(
+            "What does @src/lib.rs return? Ask @alice, or see @src/lib.rs.",
+            dir.path(),
+        );
+
+        assert_eq!(
+            texts(inputs),
+            vec!["<file path=\"src/lib.rs\">\npub fn answer() -> u32 { 42 }\n</file>".to_string()]
+        );
+    }
+
+    #[test]
+    fn skips_binary_files_and_truncates_large_ones() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("logo.png"),
+            [0x89, b'P', b'N', b'G', 0, 0xff],
+        )
+        .expect("write");
+        std::fs::write(
+            dir.path().join("big.txt"),
+            "é".repeat(MAX_ATTACHED_FILE_BYTES),
+        )
+        .expect("write");
+
+        let texts = texts(referenced_file_inputs(
+            "Compare @logo.png with @big.txt",
+            dir.path(),
+        ));
+
+        assert_eq!(texts.len(), 1);
+        assert!(texts[0].starts_with("<file path=\"big.txt\">\néé"));
+        assert!(texts[0].ends_with(&format!(
+            "[truncated after {MAX_ATTACHED_FILE_BYTES} bytes]\n</file>"
+        )));
+    }
+}
@@ -275,6 +275,19 @@ impl CodexAuth {
             })
     }
 
+    /// When the ChatGPT tokens were last refreshed. Returns `None` if
+    /// `is_chatgpt_auth()` is false.
+    pub fn last_refresh(&self) -> Option<chrono::DateTime<Utc>> {
+        self.get_current_auth_json()
+            .and_then(|auth_dot_json| auth_dot_json.last_refresh)
+    }
+
+    /// Whether the ChatGPT tokens are old enough to be refreshed before the
+    /// next request.
+    pub fn refresh_due(&self) -> bool {
+        stale_refresh_token(self).is_some()
+    }
+
     /// Returns `None` if `is_chatgpt_auth()` is false.
     fn get_current_auth_json(&self) -> Option<AuthDotJson> {
         let state = match self {
@@ -347,9 +360,11 @@ impl ChatgptAuth {
 pub const OPENAI_API_KEY_ENV_VAR: &str = "OPENAI_API_KEY";
 pub const CODEX_API_KEY_ENV_VAR: &str = "CODEX_API_KEY";
 
-pub fn read_openai_api_key_from_env() -> Option<String> {
-    env::var(OPENAI_API_KEY_ENV_VAR)
-        .ok()
+/// Reads `OPENAI_API_KEY` from the environment, falling back to `dotenv`.
+pub fn read_openai_api_key_from_env(
+    dotenv: Option<&crate::provider_env::DotenvFile>,
+) -> Option<String> {
+    crate::provider_env::var(OPENAI_API_KEY_ENV_VAR, dotenv)
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty())
 }
@@ -923,6 +938,9 @@ pub struct AuthManager {
     enable_codex_api_key_env: bool,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
     forced_chatgpt_workspace_id: RwLock<Option<String>>,
+    /// Serializes refreshes of stale tokens so threads sharing this manager
+    /// refresh once between them.
+    stale_refresh: tokio::sync::Mutex<()>,
 }
 
 impl AuthManager {
@@ -951,6 +969,7 @@ impl AuthManager {
             enable_codex_api_key_env,
             auth_credentials_store_mode,
             forced_chatgpt_workspace_id: RwLock::new(None),
+            stale_refresh: tokio::sync::Mutex::new(()),
         }
     }
 
@@ -968,6 +987,7 @@ impl AuthManager {
             enable_codex_api_key_env: false,
             auth_credentials_store_mode: AuthCredentialsStoreMode::File,
             forced_chatgpt_workspace_id: RwLock::new(None),
+            stale_refresh: tokio::sync::Mutex::new(()),
         })
     }
 
@@ -984,6 +1004,7 @@ impl AuthManager {
             enable_codex_api_key_env: false,
             auth_credentials_store_mode: AuthCredentialsStoreMode::File,
             forced_chatgpt_workspace_id: RwLock::new(None),
+            stale_refresh: tokio::sync::Mutex::new(()),
         })
     }
 
@@ -1169,28 +1190,22 @@ impl AuthManager {
     }
 
     async fn refresh_if_stale(&self, auth: &CodexAuth) -> Result<bool, RefreshTokenError> {
-        let chatgpt_auth = match auth {
-            CodexAuth::Chatgpt(chatgpt_auth) => chatgpt_auth,
-            _ => return Ok(false),
-        };
+        if stale_refresh_token(auth).is_none() {
+            return Ok(false);
+        }
 
-        let auth_dot_json = match chatgpt_auth.current_auth_json() {
-            Some(auth_dot_json) => auth_dot_json,
-            None => return Ok(false),
-        };
-        let tokens = match auth_dot_json.tokens {
-            Some(tokens) => tokens,
-            None => return Ok(false),
-        };
-        let last_refresh = match auth_dot_json.last_refresh {
-            Some(last_refresh) => last_refresh,
-            None => return Ok(false),
+        // Another caller may have refreshed while we waited for the lock, so
+        // check the current auth again rather than the snapshot we were given.
+        let _refreshing = self.stale_refresh.lock().await;
+        let Some(current) = self.auth_cached() else {
+            return Ok(false);
         };
-        if last_refresh >= Utc::now() - chrono::Duration::days(TOKEN_REFRESH_INTERVAL) {
+        let (CodexAuth::Chatgpt(chatgpt_auth), Some(refresh_token)) =
+            (&current, stale_refresh_token(&current))
+        else {
             return Ok(false);
-        }
-        self.refresh_tokens(chatgpt_auth, tokens.refresh_token)
-            .await?;
+        };
+        self.refresh_tokens(chatgpt_auth, refresh_token).await?;
         self.reload();
         Ok(true)
     }
@@ -1267,6 +1282,18 @@ impl AuthManager {
     }
 }
 
+/// The refresh token of `auth` if its ChatGPT tokens are due for a refresh.
+fn stale_refresh_token(auth: &CodexAuth) -> Option<String> {
+    let CodexAuth::Chatgpt(chatgpt_auth) = auth else {
+        return None;
+    };
+    let auth_dot_json = chatgpt_auth.current_auth_json()?;
+    let tokens = auth_dot_json.tokens?;
+    let last_refresh = auth_dot_json.last_refresh?;
+    (last_refresh < Utc::now() - chrono::Duration::days(TOKEN_REFRESH_INTERVAL))
+        .then_some(tokens.refresh_token)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
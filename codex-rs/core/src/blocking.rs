@@ -0,0 +1,199 @@
+//! Synchronous facade for callers that do not run their own tokio runtime.
+//!
+//! [`BlockingCodex`] owns a multi-threaded tokio runtime, so session tasks
+//! keep running between calls, and one Codex thread (conversation). It
+//! drives the same session machinery as the async API: each
+//! [`BlockingCodex::run_turn`] call submits an `Op::UserInput` and waits for
+//! the turn to finish.
+//!
+//! ```no_run
+//! let codex = codex_core::blocking::BlockingCodex::new()?;
+//! let outcome = codex.run_turn("Summarize README.md in one sentence.")?;
+//! println!("{}", outcome.last_agent_message.unwrap_or_default());
+//! # Ok::<(), codex_core::error::CodexErr>(())
+//! ```
+//!
+//! Do not use this from inside an async context: every call blocks the
+//! current thread on the internal runtime.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::PatchFileStatus;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::TokenUsage;
+use codex_protocol::user_input::UserInput;
+use tokio::runtime::Runtime;
+
+use crate::AuthManager;
+use crate::CodexThread;
+use crate::NewThread;
+use crate::ThreadManager;
+use crate::config::Config;
+use crate::config::ConfigBuilder;
+use crate::config::ConfigOverrides;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::protocol::EventMsg;
+use crate::protocol::Op;
+
+/// Result of one [`BlockingCodex::run_turn`] call.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TurnOutcome {
+    /// The last assistant message of the turn, if the model sent one.
+    pub last_agent_message: Option<String>,
+    /// Tokens used by the turn's final model request.
+    pub token_usage: Option<TokenUsage>,
+    /// Files changed by patches during the turn, including the files a
+    /// partially applied patch wrote before it failed.
+    pub file_changes: HashMap<PathBuf, FileChange>,
+}
+
+/// A Codex thread driven from synchronous code.
+pub struct BlockingCodex {
+    runtime: Runtime,
+    // Keeps the thread registered for the lifetime of this value.
+    _thread_manager: ThreadManager,
+    thread: Arc<CodexThread>,
+}
+
+impl BlockingCodex {
+    /// Load the user's configuration with approvals set to `never` and a
+    /// read-only sandbox.
+    pub fn new() -> CodexResult<Self> {
+        Self::with_overrides(ConfigOverrides::default())
+    }
+
+    /// Load the user's configuration with `overrides` applied. Approval policy
+    /// and sandbox mode default to `never` and read-only when not set.
+    pub fn with_overrides(mut overrides: ConfigOverrides) -> CodexResult<Self> {
+        overrides
+            .approval_policy
+            .get_or_insert(AskForApproval::Never);
+        overrides.sandbox_mode.get_or_insert(SandboxMode::ReadOnly);
+        let runtime = new_runtime()?;
+        let config = runtime.block_on(
+            ConfigBuilder::default()
+                .harness_overrides(overrides)
+                .build(),
+        )?;
+        Self::start(runtime, config)
+    }
+
+    /// Use a fully resolved `config` as is.
+    pub fn with_config(config: Config) -> CodexResult<Self> {
+        Self::start(new_runtime()?, config)
+    }
+
+    fn start(runtime: Runtime, config: Config) -> CodexResult<Self> {
+        let auth_manager = AuthManager::shared(
+            config.codex_home.clone(),
+            true,
+            config.cli_auth_credentials_store_mode,
+        );
+        let thread_manager =
+            ThreadManager::new(config.codex_home.clone(), auth_manager, SessionSource::Exec);
+        let NewThread { thread, .. } = runtime.block_on(thread_manager.start_thread(config))?;
+        Ok(Self {
+            runtime,
+            _thread_manager: thread_manager,
+            thread,
+        })
+    }
+
+    /// Send `prompt` as a user turn and block until the turn completes.
+    pub fn run_turn(&self, prompt: &str) -> CodexResult<TurnOutcome> {
+        self.runtime.block_on(run_turn(&self.thread, prompt))
+    }
+}
+
+impl Drop for BlockingCodex {
+    fn drop(&mut self) {
+        // Let the session flush its rollout before the runtime goes away.
+        let thread = Arc::clone(&self.thread);
+        self.runtime.block_on(async move {
+            if thread.submit(Op::Shutdown).await.is_err() {
+                return;
+            }
+            while let Ok(event) = thread.next_event().await {
+                if matches!(event.msg, EventMsg::ShutdownComplete) {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+fn new_runtime() -> CodexResult<Runtime> {
+    Ok(tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?)
+}
+
+async fn run_turn(thread: &CodexThread, prompt: &str) -> CodexResult<TurnOutcome> {
+    thread
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: prompt.to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let mut outcome = TurnOutcome::default();
+    loop {
+        let event = thread.next_event().await?;
+        match event.msg {
+            EventMsg::TokenCount(ev) => {
+                if let Some(info) = ev.info {
+                    outcome.token_usage = Some(info.last_token_usage);
+                }
+            }
+            EventMsg::PatchApplyEnd(ev) => {
+                let applied: HashSet<&PathBuf> = ev
+                    .per_file
+                    .iter()
+                    .filter(|result| result.status == PatchFileStatus::Applied)
+                    .map(|result| &result.path)
+                    .collect();
+                outcome.file_changes.extend(
+                    ev.changes
+                        .into_iter()
+                        .filter(|(path, _)| ev.success || applied.contains(path)),
+                );
+            }
+            // Only reachable when the caller overrode the approval policy; there
+            // is nobody to ask, so decline.
+            EventMsg::ExecApprovalRequest(_) => {
+                thread
+                    .submit(Op::ExecApproval {
+                        id: event.id,
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await?;
+            }
+            EventMsg::ApplyPatchApprovalRequest(_) => {
+                thread
+                    .submit(Op::PatchApproval {
+                        id: event.id,
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await?;
+            }
+            EventMsg::Error(ev) => return Err(CodexErr::Fatal(ev.message)),
+            EventMsg::TurnAborted(_) => return Err(CodexErr::TurnAborted),
+            EventMsg::TurnComplete(ev) => {
+                outcome.last_agent_message = ev.last_agent_message;
+                return Ok(outcome);
+            }
+            _ => {}
+        }
+    }
+}
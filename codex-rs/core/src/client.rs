@@ -6,10 +6,12 @@ use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::UnauthorizedRecovery;
 use codex_api::AggregateStreamExt;
+use codex_api::AuthProvider as _;
 use codex_api::ChatClient as ApiChatClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
 use codex_api::Prompt as ApiPrompt;
+use codex_api::Provider as ApiProvider;
 use codex_api::RequestTelemetry;
 use codex_api::ReqwestTransport;
 use codex_api::ResponseAppendWsRequest;
@@ -31,6 +33,8 @@ use codex_otel::OtelManager;
 
 use codex_protocol::ThreadId;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::SamplingParameters;
+use codex_protocol::config_types::ServiceTier;
 use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ModelInfo;
@@ -55,14 +59,21 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::config::Config;
+use crate::connectivity;
 use crate::default_client::build_reqwest_client;
 use crate::error::CodexErr;
+use crate::error::ConnectionFailedError;
 use crate::error::Result;
+use crate::error::UnexpectedResponseError;
 use crate::features::FEATURES;
 use crate::features::Feature;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
+use crate::model_provider_info::UrlTemplateVars;
 use crate::model_provider_info::WireApi;
+use crate::models_manager::model_info::clamp_max_output_tokens;
+use crate::models_manager::model_info::supported_sampling_parameters;
+use crate::prompted_tools;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
 use crate::tools::spec::create_tools_json_for_responses_api;
 use crate::transport_manager::TransportManager;
@@ -82,6 +93,65 @@ struct ModelClientState {
     summary: ReasoningSummaryConfig,
     session_source: SessionSource,
     transport_manager: TransportManager,
+    /// The process-wide client from [`build_reqwest_client`], so turns reuse
+    /// its pooled connections.
+    http_client: reqwest::Client,
+}
+
+impl ModelClientState {
+    /// Converts `provider` for the wire layer, filling `{model}` and
+    /// `{session_id}` URL placeholders with this session's model and the same
+    /// id sent in the `session_id` header.
+    fn api_provider(
+        &self,
+        provider: &ModelProviderInfo,
+        auth: Option<&CodexAuth>,
+    ) -> Result<ApiProvider> {
+        let session_id = self.conversation_id.to_string();
+        provider.to_api_provider(
+            auth.map(CodexAuth::internal_auth_mode),
+            UrlTemplateVars {
+                model: Some(&self.model_info.slug),
+                session_id: Some(&session_id),
+            },
+            self.config.dotenv.as_deref(),
+        )
+    }
+
+    fn max_output_tokens(&self) -> Option<i64> {
+        clamp_max_output_tokens(self.config.model_max_output_tokens, &self.model_info)
+    }
+
+    /// The output limit for `prompt`: the configured limit, lowered to the
+    /// prompt's own budget when it sets one.
+    fn prompt_max_output_tokens(&self, prompt: &Prompt) -> Option<i64> {
+        match (self.max_output_tokens(), prompt.max_output_tokens) {
+            (Some(configured), Some(budget)) => Some(configured.min(budget)),
+            (configured, budget) => budget.or(configured),
+        }
+    }
+
+    /// Whether `[model_capabilities]` asks for tools to be described in the
+    /// prompt instead of sent natively.
+    fn prompted_tools(&self) -> bool {
+        self.config
+            .model_capabilities
+            .get(&self.model_info.slug)
+            .and_then(|capabilities| capabilities.prompted_tools)
+            .unwrap_or(false)
+    }
+
+    fn sampling(&self, wire_api: WireApi) -> SamplingParameters {
+        supported_sampling_parameters(self.config.model_sampling, &self.model_info, wire_api)
+    }
+
+    /// Configured service tier, unless the provider rejected it earlier in
+    /// this session.
+    fn service_tier(&self) -> Option<ServiceTier> {
+        self.config
+            .service_tier
+            .filter(|_| !self.transport_manager.disable_service_tier())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -105,6 +175,9 @@ pub struct ModelClientSession {
     /// keep sending it unchanged between turn requests (e.g., for retries, incremental
     /// appends, or continuation requests), and must not send it between different turns.
     turn_state: Arc<OnceLock<String>>,
+    /// Provider serving this turn after a failover; `None` while the
+    /// configured provider is in use.
+    fallback_provider: Option<ModelProviderInfo>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -133,6 +206,7 @@ impl ModelClient {
                 summary,
                 session_source,
                 transport_manager,
+                http_client: build_reqwest_client(),
             }),
         }
     }
@@ -144,6 +218,7 @@ impl ModelClient {
             websocket_last_items: Vec::new(),
             transport_manager: self.state.transport_manager.clone(),
             turn_state: Arc::new(OnceLock::new()),
+            fallback_provider: None,
         }
     }
 }
@@ -200,10 +275,38 @@ impl ModelClient {
         self.state.summary
     }
 
+    /// Returns the output token cap sent with requests, clamped to the
+    /// model's maximum.
+    pub fn get_max_output_tokens(&self) -> Option<i64> {
+        self.state.max_output_tokens()
+    }
+
+    /// Returns the service tier requests are sent with, if any.
+    pub fn get_service_tier(&self) -> Option<ServiceTier> {
+        self.state.service_tier()
+    }
+
     pub fn get_auth_manager(&self) -> Option<Arc<AuthManager>> {
         self.state.auth_manager.clone()
     }
 
+    /// Whether the provider answers a quick probe. Returns `true` when the
+    /// provider URL cannot be built so the turn itself reports that error,
+    /// and when responses are replayed from a fixture.
+    pub(crate) async fn provider_reachable(&self) -> bool {
+        if CODEX_RS_SSE_FIXTURE.is_some() {
+            return true;
+        }
+        let auth = match self.state.auth_manager.as_ref() {
+            Some(manager) => manager.auth().await,
+            None => None,
+        };
+        let Ok(api_provider) = self.state.api_provider(&self.state.provider, auth.as_ref()) else {
+            return true;
+        };
+        connectivity::is_reachable(&api_provider.base_url, connectivity::PROBE_TIMEOUT).await
+    }
+
     /// Compacts the current conversation history using the Compact endpoint.
     ///
     /// This is a unary call (no streaming) that returns a new list of
@@ -212,6 +315,9 @@ impl ModelClient {
         if prompt.input.is_empty() {
             return Ok(Vec::new());
         }
+        if self.state.config.offline {
+            return Err(CodexErr::Offline);
+        }
         let auth_manager = self.state.auth_manager.clone();
         let auth = match auth_manager.as_ref() {
             Some(manager) => manager.auth().await,
@@ -219,10 +325,13 @@ impl ModelClient {
         };
         let api_provider = self
             .state
-            .provider
-            .to_api_provider(auth.as_ref().map(CodexAuth::internal_auth_mode))?;
-        let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+            .api_provider(&self.state.provider, auth.as_ref())?;
+        let api_auth = auth_provider_from_auth(
+            auth.clone(),
+            &self.state.provider,
+            self.state.config.dotenv.clone(),
+        )?;
+        let transport = ReqwestTransport::new(self.state.http_client.clone());
         let request_telemetry = self.build_request_telemetry();
         let client = ApiCompactClient::new(transport, api_provider, api_auth)
             .with_telemetry(Some(request_telemetry));
@@ -251,6 +360,123 @@ impl ModelClient {
             .await
             .map_err(map_api_error)
     }
+
+    /// Whether attached files are uploaded rather than inlined: the user
+    /// opted in with `upload_attachments` and the provider accepts
+    /// `input_file` items on its Responses endpoint.
+    pub(crate) fn uploads_attachments(&self) -> bool {
+        let config = &self.state.config;
+        config.upload_attachments
+            && !config.offline
+            && self.state.provider.supports_file_uploads
+            && self.state.provider.wire_api == WireApi::Responses
+    }
+
+    /// Uploads `contents` to the provider's `files` endpoint with purpose
+    /// `user_data` and returns the new file id.
+    pub(crate) async fn upload_file(&self, filename: &str, contents: &[u8]) -> Result<String> {
+        let boundary = format!("codex-{:016x}", rand::random::<u64>());
+        let filename = filename.replace(['"', '\\', '\r', '\n'], "_");
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"purpose\"\r\n\r\nuser_data\r\n\
+             --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(contents);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+        let response = self
+            .send_files_request(http::Method::POST, "files", |request| {
+                request
+                    .header(
+                        http::header::CONTENT_TYPE,
+                        format!("multipart/form-data; boundary={boundary}"),
+                    )
+                    .body(body)
+            })
+            .await?;
+        let uploaded: UploadedFile = response
+            .json()
+            .await
+            .map_err(|source| CodexErr::ConnectionFailed(ConnectionFailedError { source }))?;
+        Ok(uploaded.id)
+    }
+
+    /// Deletes a file created by [`ModelClient::upload_file`].
+    pub(crate) async fn delete_file(&self, file_id: &str) -> Result<()> {
+        self.send_files_request(
+            http::Method::DELETE,
+            &format!("files/{file_id}"),
+            |request| request,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn send_files_request(
+        &self,
+        method: http::Method,
+        path: &str,
+        build: impl FnOnce(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        if self.state.config.offline {
+            return Err(CodexErr::Offline);
+        }
+        let auth = match self.state.auth_manager.as_ref() {
+            Some(manager) => manager.auth().await,
+            None => None,
+        };
+        let api_provider = self
+            .state
+            .api_provider(&self.state.provider, auth.as_ref())?;
+        let api_auth =
+            auth_provider_from_auth(auth, &self.state.provider, self.state.config.dotenv.clone())?;
+        let request = api_provider.build_request(method, path);
+        let mut headers = request.headers;
+        headers.extend(api_auth.attempt_headers());
+        if let Some(token) = api_auth.bearer_token()
+            && let Ok(value) = HeaderValue::from_str(&format!("Bearer {token}"))
+        {
+            headers.insert(http::header::AUTHORIZATION, value);
+        }
+        if let Some(account_id) = api_auth.account_id()
+            && let Ok(value) = HeaderValue::from_str(&account_id)
+        {
+            headers.insert("ChatGPT-Account-ID", value);
+        }
+
+        let builder = self
+            .state
+            .http_client
+            .request(request.method, &request.url)
+            .headers(headers);
+        let response = build(builder)
+            .send()
+            .await
+            .map_err(|source| CodexErr::ConnectionFailed(ConnectionFailedError { source }))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let request_id = response
+            .headers()
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response.text().await.unwrap_or_default();
+        Err(CodexErr::UnexpectedStatus(UnexpectedResponseError::new(
+            status,
+            body,
+            Some(request.url),
+            request_id,
+        )))
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct UploadedFile {
+    id: String,
 }
 
 impl ModelClientSession {
@@ -258,9 +484,22 @@ impl ModelClientSession {
     /// Completions wire API, depending on the configured provider.
     ///
     /// For Chat providers, the underlying stream is optionally aggregated
-    /// based on the `show_raw_agent_reasoning` flag in the config.
+    /// based on the `show_raw_agent_reasoning` flag in the config. Models
+    /// declared with `prompted_tools` get their tools through the prompt.
     pub async fn stream(&mut self, prompt: &Prompt) -> Result<ResponseStream> {
-        let wire_api = self.state.provider.wire_api;
+        if self.state.config.offline {
+            return Err(CodexErr::Offline);
+        }
+        if self.state.prompted_tools() {
+            let prompt = prompted_tools::describe_tools(prompt)?;
+            let stream = self.stream_prompt(&prompt).await?;
+            return Ok(prompted_tools::read_tool_calls(stream));
+        }
+        self.stream_prompt(prompt).await
+    }
+
+    async fn stream_prompt(&mut self, prompt: &Prompt) -> Result<ResponseStream> {
+        let wire_api = self.provider().wire_api;
         match wire_api {
             WireApi::Responses => {
                 let websocket_enabled = self.responses_websocket_enabled()
@@ -309,8 +548,101 @@ impl ModelClientSession {
         activated
     }
 
+    /// `store` to send with Responses requests. Forced off when the provider
+    /// disables storage or rejected stored items earlier in this session;
+    /// otherwise the endpoint default applies.
+    fn response_store_override(&self) -> Option<bool> {
+        (self.provider().disable_response_storage
+            || self.transport_manager.disable_response_storage())
+        .then_some(false)
+    }
+
+    /// Stops asking the provider to store responses for the rest of the
+    /// session after `err` shows it does not keep them, so later requests
+    /// resend full context instead of failing. Returns `true` only the first
+    /// time, so callers warn once per session.
+    pub(crate) fn try_disable_response_storage(&mut self, err: &CodexErr) -> bool {
+        let storing = self.response_store_override().is_none()
+            && self.provider().is_azure_responses_endpoint();
+        if !storing || !err.is_stored_response_not_found() {
+            return false;
+        }
+        let activated = self.transport_manager.activate_stateless_fallback();
+        if activated {
+            warn!("provider rejected stored responses; disabling response storage");
+            self.state
+                .otel_manager
+                .counter("codex.responses.storage_fallback", 1, &[]);
+
+            self.connection = None;
+            self.websocket_last_items.clear();
+        }
+        activated
+    }
+
+    /// Sends requests without `service_tier` for the rest of the session after
+    /// `err` shows the provider rejected it. Returns `true` only the first
+    /// time, so callers warn once per session.
+    pub(crate) fn try_use_default_service_tier(&mut self, err: &CodexErr) -> bool {
+        if self.state.service_tier().is_none() || !err.is_service_tier_rejected() {
+            return false;
+        }
+        let activated = self.transport_manager.activate_default_service_tier();
+        if activated {
+            warn!("provider rejected the service tier; using the default tier");
+            self.state
+                .otel_manager
+                .counter("codex.responses.service_tier_fallback", 1, &[]);
+        }
+        activated
+    }
+
+    /// Provider that requests in this turn are sent to.
+    pub(crate) fn provider(&self) -> &ModelProviderInfo {
+        self.fallback_provider
+            .as_ref()
+            .unwrap_or(&self.state.provider)
+    }
+
+    /// Extra request body fields: the active provider's `extra_body` with the
+    /// profile or top-level table layered on top.
+    fn extra_body(&self) -> serde_json::Map<String, Value> {
+        let mut extra_body = self.provider().extra_body.clone().unwrap_or_default();
+        extra_body.extend(self.state.config.extra_body.clone());
+        extra_body
+    }
+
+    /// Switches the rest of this turn to the configured `fallback_provider`.
+    /// Returns the fallback's name, or `None` when there is nothing to switch
+    /// to or the switch already happened.
+    pub(crate) fn try_switch_fallback_provider(&mut self) -> Option<String> {
+        if self.fallback_provider.is_some() {
+            return None;
+        }
+        let fallback_id = self.state.provider.fallback_provider.as_ref()?;
+        let Some(fallback) = self.state.config.model_providers.get(fallback_id) else {
+            warn!("fallback provider `{fallback_id}` is not defined in model_providers");
+            return None;
+        };
+        warn!(
+            "failing over from provider {} to {}",
+            self.state.provider.name, fallback.name
+        );
+        self.state
+            .otel_manager
+            .counter("codex.provider.failover", 1, &[]);
+
+        // Connection state and sticky routing belong to the previous backend.
+        self.connection = None;
+        self.websocket_last_items.clear();
+        self.turn_state = Arc::new(OnceLock::new());
+        let name = fallback.name.clone();
+        self.fallback_provider = Some(fallback.clone());
+        Some(name)
+    }
+
     fn responses_websocket_enabled(&self) -> bool {
-        self.state.provider.supports_websockets
+        self.provider().supports_websockets
             && self
                 .state
                 .config
@@ -374,7 +706,11 @@ impl ModelClientSession {
             include,
             prompt_cache_key: Some(conversation_id.clone()),
             text,
-            store_override: None,
+            store_override: self.response_store_override(),
+            max_output_tokens: self.state.prompt_max_output_tokens(prompt),
+            service_tier: self.state.service_tier(),
+            sampling: self.state.sampling(WireApi::Responses),
+            extra_body: self.extra_body(),
             conversation_id: Some(conversation_id),
             session_source: Some(self.state.session_source.clone()),
             extra_headers: build_responses_headers(&self.state.config, Some(&self.turn_state)),
@@ -415,6 +751,10 @@ impl ModelClientSession {
             prompt_cache_key,
             text,
             store_override,
+            max_output_tokens,
+            service_tier,
+            sampling,
+            extra_body,
             ..
         } = options;
 
@@ -432,6 +772,10 @@ impl ModelClientSession {
             include: include.clone(),
             prompt_cache_key: prompt_cache_key.clone(),
             text: text.clone(),
+            max_output_tokens: *max_output_tokens,
+            service_tier: *service_tier,
+            sampling: *sampling,
+            extra_body: extra_body.clone(),
         };
 
         ResponsesWsRequest::ResponseCreate(payload)
@@ -470,7 +814,7 @@ impl ModelClientSession {
             .features
             .enabled(Feature::EnableRequestCompression)
             && auth.is_some_and(CodexAuth::is_chatgpt_auth)
-            && self.state.provider.is_openai()
+            && self.provider().is_openai()
         {
             Compression::Zstd
         } else {
@@ -504,12 +848,13 @@ impl ModelClientSession {
                 Some(manager) => manager.auth().await,
                 None => None,
             };
-            let api_provider = self
-                .state
-                .provider
-                .to_api_provider(auth.as_ref().map(CodexAuth::internal_auth_mode))?;
-            let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let api_provider = self.state.api_provider(self.provider(), auth.as_ref())?;
+            let api_auth = auth_provider_from_auth(
+                auth.clone(),
+                self.provider(),
+                self.state.config.dotenv.clone(),
+            )?;
+            let transport = ReqwestTransport::new(self.state.http_client.clone());
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiChatClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
@@ -520,6 +865,10 @@ impl ModelClientSession {
                     &api_prompt,
                     Some(conversation_id.clone()),
                     Some(session_source.clone()),
+                    self.state.prompt_max_output_tokens(prompt),
+                    self.state.service_tier(),
+                    self.state.sampling(WireApi::Chat),
+                    self.extra_body(),
                 )
                 .await;
 
@@ -544,7 +893,7 @@ impl ModelClientSession {
         if let Some(path) = &*CODEX_RS_SSE_FIXTURE {
             warn!(path, "Streaming from fixture");
             let stream =
-                codex_api::stream_from_fixture(path, self.state.provider.stream_idle_timeout())
+                codex_api::stream_from_fixture(path, self.provider().stream_idle_timeout())
                     .map_err(map_api_error)?;
             return Ok(map_response_stream(stream, self.state.otel_manager.clone()));
         }
@@ -560,12 +909,13 @@ impl ModelClientSession {
                 Some(manager) => manager.auth().await,
                 None => None,
             };
-            let api_provider = self
-                .state
-                .provider
-                .to_api_provider(auth.as_ref().map(CodexAuth::internal_auth_mode))?;
-            let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let api_provider = self.state.api_provider(self.provider(), auth.as_ref())?;
+            let api_auth = auth_provider_from_auth(
+                auth.clone(),
+                self.provider(),
+                self.state.config.dotenv.clone(),
+            )?;
+            let transport = ReqwestTransport::new(self.state.http_client.clone());
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let compression = self.responses_request_compression(auth.as_ref());
 
@@ -606,11 +956,12 @@ impl ModelClientSession {
                 Some(manager) => manager.auth().await,
                 None => None,
             };
-            let api_provider = self
-                .state
-                .provider
-                .to_api_provider(auth.as_ref().map(CodexAuth::internal_auth_mode))?;
-            let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
+            let api_provider = self.state.api_provider(self.provider(), auth.as_ref())?;
+            let api_auth = auth_provider_from_auth(
+                auth.clone(),
+                self.provider(),
+                self.state.config.dotenv.clone(),
+            )?;
             let compression = self.responses_request_compression(auth.as_ref());
 
             let options = self.build_responses_options(prompt, compression);
@@ -733,11 +1084,18 @@ where
         let mut api_stream = api_stream;
         while let Some(event) = api_stream.next().await {
             match event {
-                Ok(ResponseEvent::Completed {
-                    response_id,
-                    token_usage,
-                }) => {
-                    if let Some(usage) = &token_usage {
+                Ok(
+                    event @ (ResponseEvent::Completed { .. } | ResponseEvent::Incomplete { .. }),
+                ) => {
+                    if let ResponseEvent::Completed {
+                        token_usage: Some(usage),
+                        ..
+                    }
+                    | ResponseEvent::Incomplete {
+                        token_usage: Some(usage),
+                        ..
+                    } = &event
+                    {
                         otel_manager.sse_event_completed(
                             usage.input_tokens,
                             usage.output_tokens,
@@ -746,14 +1104,7 @@ where
                             usage.total_tokens,
                         );
                     }
-                    if tx_event
-                        .send(Ok(ResponseEvent::Completed {
-                            response_id,
-                            token_usage,
-                        }))
-                        .await
-                        .is_err()
-                    {
+                    if tx_event.send(Ok(event)).await.is_err() {
                         return;
                     }
                 }
@@ -1,6 +1,7 @@
 use crate::client_common::tools::ToolSpec;
 use crate::config::types::Personality;
 use crate::error::Result;
+use crate::injection_guard::wrap_tool_outputs;
 pub use codex_api::common::ResponseEvent;
 use codex_protocol::models::BaseInstructions;
 use codex_protocol::models::ResponseItem;
@@ -41,6 +42,14 @@ pub struct Prompt {
 
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+
+    /// Wrap tool outputs in untrusted-content markers
+    /// (`injection_guard.wrap_tool_output`).
+    pub(crate) wrap_untrusted_content: bool,
+
+    /// Output budget for side calls, applied below the configured
+    /// `model_max_output_tokens`.
+    pub(crate) max_output_tokens: Option<i64>,
 }
 
 impl Prompt {
@@ -58,6 +67,9 @@ impl Prompt {
         if is_freeform_apply_patch_tool_present {
             reserialize_shell_outputs(&mut input);
         }
+        if self.wrap_untrusted_content {
+            wrap_tool_outputs(&mut input);
+        }
 
         input
     }
@@ -237,6 +249,7 @@ mod tests {
     use codex_api::common::OpenAiVerbosity;
     use codex_api::common::TextControls;
     use codex_api::create_text_param_for_request;
+    use codex_protocol::config_types::SamplingParameters;
     use pretty_assertions::assert_eq;
 
     use super::*;
@@ -261,6 +274,9 @@ mod tests {
                 verbosity: Some(OpenAiVerbosity::Low),
                 format: None,
             }),
+            max_output_tokens: None,
+            service_tier: None,
+            sampling: SamplingParameters::default(),
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -299,6 +315,9 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: Some(text_controls),
+            max_output_tokens: None,
+            service_tier: None,
+            sampling: SamplingParameters::default(),
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -335,6 +354,9 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: None,
+            max_output_tokens: None,
+            service_tier: None,
+            sampling: SamplingParameters::default(),
         };
 
         let v = serde_json::to_value(&req).expect("json");
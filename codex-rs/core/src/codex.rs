@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
 
 use crate::AuthManager;
 use crate::CodexAuth;
@@ -23,8 +26,13 @@ use crate::exec_policy::ExecPolicyManager;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::features::maybe_push_unstable_features_warning;
+use crate::file_provenance::FileProvenance;
+use crate::is_safe_command::SafeCommands;
 use crate::models_manager::manager::ModelsManager;
+use crate::models_manager::model_info::supported_sampling_parameters;
+use crate::parse_command::extract_shell_command;
 use crate::parse_command::parse_command;
+use crate::parse_command::shlex_join;
 use crate::parse_turn_item;
 use crate::rollout::session_index;
 use crate::stream_events_utils::HandleOutputCtx;
@@ -32,12 +40,16 @@ use crate::stream_events_utils::handle_non_tool_response_item;
 use crate::stream_events_utils::handle_output_item_done;
 use crate::stream_events_utils::last_assistant_message_from_item;
 use crate::terminal;
+use crate::transcript_export::Redactor;
 use crate::transport_manager::TransportManager;
 use crate::truncate::TruncationPolicy;
+use crate::truncate::approx_tokens_from_byte_count;
 use crate::user_notification::UserNotifier;
 use crate::util::error_or_panic;
 use async_channel::Receiver;
 use async_channel::Sender;
+use chrono::SecondsFormat;
+use chrono::Utc;
 use codex_protocol::ThreadId;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::config_types::ModeKind;
@@ -50,10 +62,13 @@ use codex_protocol::items::TurnItem;
 use codex_protocol::items::UserMessageItem;
 use codex_protocol::models::BaseInstructions;
 use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::protocol::ApprovalGrantItem;
+use codex_protocol::protocol::ApprovalGrantsRestoredEvent;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::HasLegacyEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
 use codex_protocol::protocol::ItemStartedEvent;
+use codex_protocol::protocol::PrewarmPhase;
 use codex_protocol::protocol::RawResponseItemEvent;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::RolloutItem;
@@ -61,11 +76,13 @@ use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::protocol::TurnAbortReason;
 use codex_protocol::protocol::TurnContextItem;
+use codex_protocol::protocol::TurnSettings;
 use codex_protocol::protocol::TurnStartedEvent;
 use codex_protocol::request_user_input::RequestUserInputArgs;
 use codex_protocol::request_user_input::RequestUserInputResponse;
 use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::future::BoxFuture;
 use futures::prelude::*;
 use futures::stream::FuturesOrdered;
@@ -100,22 +117,35 @@ use crate::client::ModelClientSession;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::codex_thread::ThreadConfigSnapshot;
+use crate::command_explanation::CommandExplanations;
 use crate::compact::collect_user_messages;
 use crate::config::Config;
 use crate::config::Constrained;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
 use crate::config::resolve_web_search_mode_for_turn;
+use crate::config::types::ApprovalTimeout;
+use crate::config::types::ApprovalTimeoutAction;
 use crate::config::types::McpServerConfig;
+use crate::config::types::NotifyEventKind;
 use crate::config::types::ShellEnvironmentPolicy;
+use crate::connectivity::OFFLINE_RETRY_INTERVAL;
 use crate::context_manager::ContextManager;
 use crate::environment_context::EnvironmentContext;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
+use crate::event_subscription::EventBroadcast;
+use crate::event_subscription::EventSubscription;
+use crate::event_subscription::spawn_event_fanout;
 #[cfg(test)]
 use crate::exec::StreamOutput;
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
+use crate::file_uploads;
+use crate::file_uploads::FileUploads;
+use crate::hooks;
+use crate::hooks::HookDetails;
+use crate::injection_guard::InjectionScanner;
 use crate::instructions::UserInstructions;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::auth::compute_auth_statuses;
@@ -128,23 +158,36 @@ use crate::mentions::build_skill_name_counts;
 use crate::mentions::collect_explicit_app_paths;
 use crate::mentions::collect_tool_mentions_from_messages;
 use crate::model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
+use crate::preferred_language::language_message;
+use crate::preferred_language::with_preferred_language;
 use crate::project_doc::get_user_instructions;
+use crate::prompt_commands::expand_prompt_commands;
 use crate::proposed_plan_parser::ProposedPlanParser;
 use crate::proposed_plan_parser::ProposedPlanSegment;
 use crate::proposed_plan_parser::extract_proposed_plan_text;
 use crate::protocol::AgentMessageContentDeltaEvent;
 use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
+use crate::protocol::ApprovalKind;
+use crate::protocol::ApprovalResolvedEvent;
+use crate::protocol::ApprovalResolver;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::CancelledWork;
+use crate::protocol::ConnectivityRestoredEvent;
 use crate::protocol::DeprecationNoticeEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
+use crate::protocol::HookPoint;
+use crate::protocol::ListToolsResponseEvent;
 use crate::protocol::McpServerRefreshConfig;
 use crate::protocol::Op;
+use crate::protocol::OutputTruncatedEvent;
+use crate::protocol::PatchApplyEndEvent;
 use crate::protocol::PlanDeltaEvent;
+use crate::protocol::PromptQueuedOfflineEvent;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
 use crate::protocol::ReasoningRawContentDeltaEvent;
@@ -157,17 +200,28 @@ use crate::protocol::SkillErrorInfo;
 use crate::protocol::SkillInterface as ProtocolSkillInterface;
 use crate::protocol::SkillMetadata as ProtocolSkillMetadata;
 use crate::protocol::SkillToolDependency as ProtocolSkillToolDependency;
+use crate::protocol::SteerDeliveredEvent;
 use crate::protocol::StreamErrorEvent;
 use crate::protocol::Submission;
 use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
+use crate::protocol::ToolDescription;
+use crate::protocol::ToolSource;
+use crate::protocol::ToolToggles;
 use crate::protocol::TurnDiffEvent;
+use crate::protocol::TurnRefusedEvent;
 use crate::protocol::WarningEvent;
+use crate::request_size;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
 use crate::rollout::metadata;
+use crate::session_startup::StartupReport;
+use crate::session_startup::probe_provider;
+use crate::session_startup::project_doc_files;
+use crate::session_startup::startup_auth;
+use crate::session_startup::timed;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -181,11 +235,13 @@ use crate::skills::injection::ToolMentionKind;
 use crate::skills::injection::app_id_from_path;
 use crate::skills::injection::tool_kind_for_path;
 use crate::skills::resolve_skill_dependencies_for_turn;
+use crate::stale_patch;
 use crate::state::ActiveTurn;
 use crate::state::SessionServices;
 use crate::state::SessionState;
 use crate::state_db;
 use crate::tasks::GhostSnapshotTask;
+use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
@@ -195,16 +251,23 @@ use crate::tools::parallel::ToolCallRuntime;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
+use crate::tools::spec::create_tools_json_for_responses_api;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
+use crate::user_notification::ApprovalRequestKind;
 use crate::user_notification::UserNotification;
 use crate::util::backoff;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
+use crate::workspace_summary::WorkspaceSummaries;
+use crate::workspace_summary::workspace_summary_item;
+use crate::workspace_watch;
+use crate::workspace_watch::WorkspaceWatcher;
 use codex_async_utils::OrCancelExt;
 use codex_otel::OtelManager;
 use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::ServiceTier;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::DeveloperInstructions;
@@ -224,6 +287,8 @@ pub struct Codex {
     pub(crate) next_id: AtomicU64,
     pub(crate) tx_sub: Sender<Submission>,
     pub(crate) rx_event: Receiver<Event>,
+    /// Fan-out of the event stream for [`Codex::subscribe`].
+    pub(crate) event_broadcast: EventBroadcast,
     // Last known status of the agent.
     pub(crate) agent_status: watch::Receiver<AgentStatus>,
     pub(crate) session: Arc<Session>,
@@ -267,11 +332,224 @@ fn maybe_push_chat_wire_api_deprecation(
     });
 }
 
+/// Warns when `model_max_output_tokens` exceeds what the model accepts; the
+/// value sent on requests is clamped to the model's maximum.
+fn maybe_push_max_output_tokens_warning(
+    config: &Config,
+    model_info: &ModelInfo,
+    post_session_configured_events: &mut Vec<Event>,
+) {
+    let (Some(requested), Some(max)) =
+        (config.model_max_output_tokens, model_info.max_output_tokens)
+    else {
+        return;
+    };
+    if requested <= max {
+        return;
+    }
+
+    post_session_configured_events.push(Event {
+        id: INITIAL_SUBMIT_ID.to_owned(),
+        msg: EventMsg::Warning(WarningEvent {
+            message: format!(
+                "model_max_output_tokens = {requested} exceeds the {max}-token maximum of {}; using {max}.",
+                model_info.slug
+            ),
+        }),
+    });
+}
+
+/// Builds the `notify` payload for an approval request that has been
+/// waiting for `pending`.
+fn approval_notification(
+    thread_id: String,
+    request: &ApprovalResolvedEvent,
+    pending: Duration,
+) -> UserNotification {
+    let (kind, summary) = match request.kind {
+        ApprovalKind::Exec => (
+            ApprovalRequestKind::Exec,
+            extract_shell_command(&request.command).map_or_else(
+                || shlex_join(&request.command),
+                |(_, script)| script.to_string(),
+            ),
+        ),
+        ApprovalKind::Patch => (
+            ApprovalRequestKind::Patch,
+            request
+                .paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+    };
+    UserNotification::ApprovalRequested {
+        thread_id,
+        turn_id: request.turn_id.clone(),
+        call_id: request.call_id.clone(),
+        cwd: request.cwd.display().to_string(),
+        kind,
+        summary,
+        reason: request.reason.clone(),
+        pending_seconds: pending.as_secs(),
+    }
+}
+
+/// Sampling parameters that reasoning models do not honor.
+const REASONING_MODEL_IGNORED_PARAMS: &[&str] = &[
+    "temperature",
+    "top_p",
+    "presence_penalty",
+    "frequency_penalty",
+];
+
+/// Warns when `extra_body` sets parameters the model is known to ignore.
+fn maybe_push_ignored_extra_body_warning(
+    config: &Config,
+    model_info: &ModelInfo,
+    post_session_configured_events: &mut Vec<Event>,
+) {
+    if !model_info.supports_reasoning {
+        return;
+    }
+    let provider_keys = config
+        .model_provider
+        .extra_body
+        .iter()
+        .flat_map(serde_json::Map::keys);
+    let mut ignored: Vec<&str> = provider_keys
+        .chain(config.extra_body.keys())
+        .map(String::as_str)
+        .filter(|key| REASONING_MODEL_IGNORED_PARAMS.contains(key))
+        .collect();
+    if ignored.is_empty() {
+        return;
+    }
+    ignored.sort_unstable();
+    ignored.dedup();
+
+    post_session_configured_events.push(Event {
+        id: INITIAL_SUBMIT_ID.to_owned(),
+        msg: EventMsg::Warning(WarningEvent {
+            message: format!(
+                "extra_body parameters ignored by reasoning model {}: {}.",
+                model_info.slug,
+                ignored.join(", ")
+            ),
+        }),
+    });
+}
+
+/// Warns about configured sampling parameters that will not be sent because
+/// the model or the provider's wire API does not accept them.
+fn maybe_push_sampling_parameters_warning(
+    config: &Config,
+    model_info: &ModelInfo,
+    post_session_configured_events: &mut Vec<Event>,
+) {
+    let requested = config.model_sampling;
+    let sent = supported_sampling_parameters(requested, model_info, config.model_provider.wire_api);
+    let dropped: Vec<&str> = [
+        (
+            "model_seed",
+            requested.seed.is_some() && sent.seed.is_none(),
+        ),
+        (
+            "model_temperature",
+            requested.temperature.is_some() && sent.temperature.is_none(),
+        ),
+        (
+            "model_top_p",
+            requested.top_p.is_some() && sent.top_p.is_none(),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(key, dropped)| dropped.then_some(key))
+    .collect();
+    if dropped.is_empty() {
+        return;
+    }
+    let reason = if model_info.supports_reasoning {
+        format!(
+            "reasoning model {} does not accept sampling parameters",
+            model_info.slug
+        )
+    } else {
+        format!(
+            "{} uses the Responses API, which has no seed",
+            config.model_provider.name
+        )
+    };
+
+    post_session_configured_events.push(Event {
+        id: INITIAL_SUBMIT_ID.to_owned(),
+        msg: EventMsg::Warning(WarningEvent {
+            message: format!("Ignoring {}: {reason}.", dropped.join(", ")),
+        }),
+    });
+}
+
+/// Warns when base instructions from config exceed
+/// `base_instructions_token_budget`.
+fn maybe_push_base_instructions_budget_warning(
+    config: &Config,
+    post_session_configured_events: &mut Vec<Event>,
+) {
+    let Some(tokens) = config.base_instructions_tokens() else {
+        return;
+    };
+    let budget = config.base_instructions_token_budget;
+    if tokens <= budget {
+        return;
+    }
+
+    post_session_configured_events.push(Event {
+        id: INITIAL_SUBMIT_ID.to_owned(),
+        msg: EventMsg::Warning(WarningEvent {
+            message: format!(
+                "Configured base instructions are about {tokens} tokens, over the {budget}-token base_instructions_token_budget. Large instructions leave less room for the conversation."
+            ),
+        }),
+    });
+}
+
+/// Warns about workspace roots that are missing, left read-only because
+/// their project is untrusted, or outside version control.
+fn maybe_push_workspace_root_warnings(
+    config: &Config,
+    post_session_configured_events: &mut Vec<Event>,
+) {
+    for root in &config.workspace_roots {
+        let path = root.path.as_path();
+        let message = if !path.is_dir() {
+            format!("Workspace root {} is not a directory.", path.display())
+        } else if root.project.is_untrusted() {
+            format!(
+                "Workspace root {} belongs to an untrusted project; Codex can read it but not write to it.",
+                path.display()
+            )
+        } else if get_git_repo_root(path).is_none() {
+            format!(
+                "Workspace root {} is not inside a Git repository; changes made there are not tracked.",
+                path.display()
+            )
+        } else {
+            continue;
+        };
+        post_session_configured_events.push(Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        });
+    }
+}
+
 impl Codex {
-    /// Spawn a new [`Codex`] and initialize the session.
+    /// Spawn a new [`Codex`] and initialize the session. `config` is only
+    /// copied if this session needs to change it.
     #[allow(clippy::too_many_arguments)]
     pub(crate) async fn spawn(
-        mut config: Config,
+        mut config: Arc<Config>,
         auth_manager: Arc<AuthManager>,
         models_manager: Arc<ModelsManager>,
         skills_manager: Arc<SkillsManager>,
@@ -281,8 +559,16 @@ impl Codex {
         dynamic_tools: Vec<DynamicToolSpec>,
     ) -> CodexResult<CodexSpawnOk> {
         let (tx_sub, rx_sub) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
-        let (tx_event, rx_event) = async_channel::unbounded();
+        let (tx_event, rx_session_event) = async_channel::unbounded();
+        let (tx_primary_event, rx_event) = async_channel::unbounded();
+        let event_broadcast = spawn_event_fanout(
+            config.event_subscriber_capacity,
+            rx_session_event,
+            tx_primary_event,
+        );
 
+        let mut startup = StartupReport::default();
+        let config_started = Instant::now();
         let loaded_skills = skills_manager.skills_for_config(&config);
 
         for err in &loaded_skills.errors {
@@ -291,22 +577,27 @@ impl Codex {
                 err.path.display(),
                 err.message
             );
+            startup.degrade(
+                PrewarmPhase::Config,
+                format!(
+                    "Skill {} failed to load: {}",
+                    err.path.display(),
+                    err.message
+                ),
+            );
         }
 
         if let SessionSource::SubAgent(SubAgentSource::ThreadSpawn { depth, .. }) = session_source
             && depth >= MAX_THREAD_SPAWN_DEPTH
         {
-            config.features.disable(Feature::Collab);
+            Arc::make_mut(&mut config).features.disable(Feature::Collab);
         }
 
-        let enabled_skills = loaded_skills.enabled_skills();
-        let user_instructions = get_user_instructions(&config, Some(&enabled_skills)).await;
-
         let exec_policy = ExecPolicyManager::load(&config.features, &config.config_layer_stack)
             .await
-            .map_err(|err| CodexErr::Fatal(format!("failed to load rules: {err}")))?;
+            .map_err(|err| CodexErr::Fatal(format!("failed to load rules: {err}")))?
+            .with_safe_commands(SafeCommands::new(&config.safe_commands));
 
-        let config = Arc::new(config);
         let _ = models_manager
             .list_models(
                 &config,
@@ -320,6 +611,12 @@ impl Codex {
                 crate::models_manager::manager::RefreshStrategy::OnlineIfUncached,
             )
             .await;
+        startup.record(PrewarmPhase::Config, config_started.elapsed());
+
+        let enabled_skills = loaded_skills.enabled_skills();
+        let (user_instructions, project_docs_elapsed) =
+            timed(get_user_instructions(&config, Some(&enabled_skills))).await;
+        startup.record(PrewarmPhase::ProjectDocs, project_docs_elapsed);
 
         // Resolve base instructions for the session. Priority order:
         // 1. config.base_instructions override
@@ -366,6 +663,11 @@ impl Codex {
             original_config_do_not_use: Arc::clone(&config),
             session_source,
             dynamic_tools,
+            tool_toggles: config.tool_toggles,
+            // A resumed session keeps the language it was recorded with.
+            preferred_language: conversation_history
+                .get_preferred_language()
+                .or_else(|| config.preferred_language.clone()),
         };
 
         // Generate a unique ID for the lifetime of this Codex session.
@@ -385,6 +687,7 @@ impl Codex {
             session_source_clone,
             skills_manager,
             agent_control,
+            startup,
         )
         .instrument(session_init_span)
         .await
@@ -403,6 +706,7 @@ impl Codex {
             next_id: AtomicU64::new(0),
             tx_sub,
             rx_event,
+            event_broadcast,
             agent_status: agent_status_rx,
             session,
         };
@@ -445,6 +749,14 @@ impl Codex {
         Ok(event)
     }
 
+    /// Subscribe to every event emitted from now on, independently of [`Codex::next_event`].
+    ///
+    /// See [`EventSubscription`] for ordering and lag behavior. The stream ends once this
+    /// `Codex` is dropped.
+    pub fn subscribe(&self) -> EventSubscription {
+        EventSubscription::new(&self.event_broadcast)
+    }
+
     pub(crate) async fn agent_status(&self) -> AgentStatus {
         self.agent_status.borrow().clone()
     }
@@ -454,6 +766,10 @@ impl Codex {
         state.session_configuration.thread_config_snapshot()
     }
 
+    pub(crate) async fn list_tools(&self) -> CodexResult<ListToolsResponseEvent> {
+        list_tools_for_next_turn(&self.session).await
+    }
+
     pub(crate) fn state_db(&self) -> Option<state_db::StateDbHandle> {
         self.session.state_db()
     }
@@ -490,6 +806,7 @@ pub(crate) struct TurnContext {
     pub(crate) user_instructions: Option<String>,
     pub(crate) collaboration_mode_kind: ModeKind,
     pub(crate) personality: Option<Personality>,
+    pub(crate) preferred_language: Option<String>,
     pub(crate) approval_policy: AskForApproval,
     pub(crate) sandbox_policy: SandboxPolicy,
     pub(crate) windows_sandbox_level: WindowsSandboxLevel,
@@ -500,6 +817,8 @@ pub(crate) struct TurnContext {
     pub(crate) codex_linux_sandbox_exe: Option<PathBuf>,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) truncation_policy: TruncationPolicy,
+    /// Maximum lines of command output sent back to the model, if configured.
+    pub(crate) tool_output_max_lines: Option<usize>,
     pub(crate) dynamic_tools: Vec<DynamicToolSpec>,
 }
 
@@ -510,10 +829,52 @@ impl TurnContext {
             .map_or_else(|| self.cwd.clone(), |p| self.cwd.join(p))
     }
 
-    pub(crate) fn compact_prompt(&self) -> &str {
-        self.compact_prompt
+    pub(crate) fn compact_prompt(&self) -> String {
+        let prompt = self
+            .compact_prompt
             .as_deref()
-            .unwrap_or(compact::SUMMARIZATION_PROMPT)
+            .unwrap_or(compact::SUMMARIZATION_PROMPT);
+        with_preferred_language(prompt, self.preferred_language.as_deref())
+    }
+
+    /// Whether this turn answers without tools; see [`crate::ask`].
+    pub(crate) fn is_ask_turn(&self) -> bool {
+        self.client.config().ask_mode
+    }
+
+    /// Whether `path` is the session cwd or a workspace root, or lies under
+    /// one. `..` components are resolved before comparing.
+    pub(crate) fn is_in_workspace(&self, path: &Path) -> bool {
+        let Ok(path) = AbsolutePathBuf::resolve_path_against_base(path, &self.cwd) else {
+            return false;
+        };
+        path.as_path().starts_with(&self.cwd)
+            || self
+                .client
+                .config()
+                .workspace_roots
+                .iter()
+                .any(|root| path.as_path().starts_with(root.path.as_path()))
+    }
+
+    /// Whether the sandbox lets commands write to `path`.
+    pub(crate) fn is_sandbox_writable(&self, path: &Path) -> bool {
+        self.sandbox_policy.has_full_disk_write_access()
+            || self
+                .sandbox_policy
+                .get_writable_roots_with_cwd(&self.cwd)
+                .iter()
+                .any(|root| root.is_path_writable(path))
+    }
+
+    pub(crate) fn turn_settings(&self) -> TurnSettings {
+        TurnSettings {
+            model: self.client.get_model(),
+            reasoning_effort: self.client.get_reasoning_effort(),
+            approval_policy: self.approval_policy,
+            sandbox_policy: self.sandbox_policy.clone(),
+            service_tier: self.client.get_service_tier(),
+        }
     }
 }
 
@@ -564,6 +925,10 @@ pub(crate) struct SessionConfiguration {
     /// Source of the session (cli, vscode, exec, mcp, ...)
     session_source: SessionSource,
     dynamic_tools: Vec<DynamicToolSpec>,
+    /// Groups of tools the model may call; changed by `Op::SetToolToggles`.
+    tool_toggles: ToolToggles,
+    /// Language the model answers in; changed by `Op::SetPreferredLanguage`.
+    preferred_language: Option<String>,
 }
 
 impl SessionConfiguration {
@@ -581,6 +946,7 @@ impl SessionConfiguration {
             reasoning_effort: self.collaboration_mode.reasoning_effort(),
             personality: self.personality,
             session_source: self.session_source.clone(),
+            tool_toggles: self.tool_toggles,
         }
     }
 
@@ -599,7 +965,11 @@ impl SessionConfiguration {
             next_configuration.approval_policy.set(approval_policy)?;
         }
         if let Some(sandbox_policy) = updates.sandbox_policy.clone() {
-            next_configuration.sandbox_policy.set(sandbox_policy)?;
+            // Clients send presets that know nothing of the workspace roots.
+            let workspace_roots = self.original_config_do_not_use.writable_workspace_roots();
+            next_configuration
+                .sandbox_policy
+                .set(sandbox_policy.with_additional_writable_roots(&workspace_roots))?;
         }
         if let Some(windows_sandbox_level) = updates.windows_sandbox_level {
             next_configuration.windows_sandbox_level = windows_sandbox_level;
@@ -607,6 +977,12 @@ impl SessionConfiguration {
         if let Some(cwd) = updates.cwd.clone() {
             next_configuration.cwd = cwd;
         }
+        if let Some(tool_toggles) = updates.tool_toggles {
+            next_configuration.tool_toggles = tool_toggles;
+        }
+        if let Some(preferred_language) = updates.preferred_language.clone() {
+            next_configuration.preferred_language = preferred_language;
+        }
         Ok(next_configuration)
     }
 }
@@ -621,6 +997,14 @@ pub(crate) struct SessionSettingsUpdate {
     pub(crate) reasoning_summary: Option<ReasoningSummaryConfig>,
     pub(crate) final_output_json_schema: Option<Option<Value>>,
     pub(crate) personality: Option<Personality>,
+    pub(crate) tool_toggles: Option<ToolToggles>,
+    pub(crate) preferred_language: Option<Option<String>>,
+    /// Output token cap for this turn only; not persisted to the session.
+    pub(crate) max_output_tokens: Option<i64>,
+    /// Service tier for this turn only; not persisted to the session.
+    pub(crate) service_tier: Option<ServiceTier>,
+    /// Answer this turn without tools (`Op::Ask`); not persisted to the session.
+    pub(crate) ask_mode: bool,
 }
 
 impl Session {
@@ -633,6 +1017,8 @@ impl Session {
             session_configuration.collaboration_mode.reasoning_effort();
         per_turn_config.model_reasoning_summary = session_configuration.model_reasoning_summary;
         per_turn_config.model_personality = session_configuration.personality;
+        per_turn_config.tool_toggles = session_configuration.tool_toggles;
+        per_turn_config.preferred_language = session_configuration.preferred_language.clone();
         per_turn_config.web_search_mode = Some(resolve_web_search_mode_for_turn(
             per_turn_config.web_search_mode,
             session_configuration.provider.is_azure_responses_endpoint(),
@@ -681,7 +1067,8 @@ impl Session {
             model_info: &model_info,
             features: &per_turn_config.features,
             web_search_mode: per_turn_config.web_search_mode,
-        });
+        })
+        .with_tool_toggles(per_turn_config.tool_toggles);
 
         TurnContext {
             sub_id,
@@ -692,6 +1079,7 @@ impl Session {
             user_instructions: session_configuration.user_instructions.clone(),
             collaboration_mode_kind: session_configuration.collaboration_mode.mode,
             personality: session_configuration.personality,
+            preferred_language: session_configuration.preferred_language.clone(),
             approval_policy: session_configuration.approval_policy.value(),
             sandbox_policy: session_configuration.sandbox_policy.get().clone(),
             windows_sandbox_level: session_configuration.windows_sandbox_level,
@@ -702,6 +1090,7 @@ impl Session {
             codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
             truncation_policy: model_info.truncation_policy.into(),
+            tool_output_max_lines: per_turn_config.tool_output_max_lines,
             dynamic_tools: session_configuration.dynamic_tools.clone(),
         }
     }
@@ -719,6 +1108,7 @@ impl Session {
         session_source: SessionSource,
         skills_manager: Arc<SkillsManager>,
         agent_control: AgentControl,
+        mut startup: StartupReport,
     ) -> anyhow::Result<Arc<Self>> {
         debug!(
             "Configuring session: model={}; provider={:?}",
@@ -734,6 +1124,19 @@ impl Session {
 
         let forked_from_id = initial_history.forked_from_id();
 
+        // `--title`/`--tag` describe the session the user started, not the
+        // sub-agents it spawns.
+        let (session_title, session_tags) = match &session_source {
+            SessionSource::SubAgent(_) => (None, Vec::new()),
+            _ => (
+                config
+                    .session_title
+                    .as_deref()
+                    .and_then(crate::util::normalize_thread_name),
+                crate::util::normalize_session_tags(&config.session_tags),
+            ),
+        };
+
         let (conversation_id, rollout_params) = match &initial_history {
             InitialHistory::New | InitialHistory::Forked(_) => {
                 let conversation_id = ThreadId::default();
@@ -742,12 +1145,13 @@ impl Session {
                     RolloutRecorderParams::new(
                         conversation_id,
                         forked_from_id,
-                        session_source,
+                        session_source.clone(),
                         BaseInstructions {
                             text: session_configuration.base_instructions.clone(),
                         },
                         session_configuration.dynamic_tools.clone(),
-                    ),
+                    )
+                    .with_session_metadata(session_title.clone(), session_tags),
                 )
             }
             InitialHistory::Resumed(resumed_history) => (
@@ -789,7 +1193,12 @@ impl Session {
         let config_for_mcp = Arc::clone(&config);
         let auth_and_mcp_fut = async move {
             let auth = auth_manager_clone.auth().await;
-            let mcp_servers = effective_mcp_servers(&config_for_mcp, auth.as_ref());
+            // Ask sessions never call tools, so they skip connecting to MCP servers.
+            let mcp_servers = if config_for_mcp.ask_mode {
+                HashMap::new()
+            } else {
+                effective_mcp_servers(&config_for_mcp, auth.as_ref())
+            };
             let auth_statuses = compute_auth_statuses(
                 mcp_servers.iter(),
                 config_for_mcp.mcp_oauth_credentials_store_mode,
@@ -797,13 +1206,57 @@ impl Session {
             .await;
             (auth, mcp_servers, auth_statuses)
         };
+        // Sub-agents inherit a model the parent session already checked.
+        let model_check_fut = async {
+            if matches!(session_source, SessionSource::SubAgent(_)) {
+                return None;
+            }
+            let auth = auth_manager.auth().await;
+            models_manager
+                .check_model(
+                    session_configuration.collaboration_mode.model(),
+                    &config,
+                    auth,
+                )
+                .await
+        };
+        let provider_probe_fut = async {
+            if !config.startup_probe {
+                return None;
+            }
+            let auth = auth_manager.auth().await;
+            probe_provider(
+                &config,
+                session_configuration.collaboration_mode.model(),
+                auth.as_ref(),
+            )
+            .await
+        };
 
         // Join all independent futures.
         let (
-            rollout_recorder_and_state_db,
+            (rollout_recorder_and_state_db, rollout_elapsed),
             (history_log_id, history_entry_count),
-            (auth, mcp_servers, auth_statuses),
-        ) = tokio::join!(rollout_fut, history_meta_fut, auth_and_mcp_fut);
+            ((auth, mcp_servers, auth_statuses), auth_elapsed),
+            (unknown_model, model_check_elapsed),
+            provider_probe,
+        ) = tokio::join!(
+            timed(rollout_fut),
+            history_meta_fut,
+            timed(auth_and_mcp_fut),
+            timed(model_check_fut),
+            provider_probe_fut
+        );
+        startup.record(PrewarmPhase::Rollout, rollout_elapsed);
+        startup.record(PrewarmPhase::Auth, auth_elapsed);
+        startup.record(PrewarmPhase::ModelCheck, model_check_elapsed);
+        startup.check_provider_probe(&config.model_provider, provider_probe.as_ref());
+        startup.check_auth(
+            &config.model_provider,
+            auth.as_ref(),
+            config.dotenv.as_deref(),
+        );
+        startup.check_sandbox(config.sandbox_policy.get(), &config.codex_home);
 
         let (rollout_recorder, state_db_ctx) = rollout_recorder_and_state_db.map_err(|e| {
             error!("failed to initialize rollout recorder: {e:#}");
@@ -839,6 +1292,32 @@ impl Session {
         }
         maybe_push_chat_wire_api_deprecation(&config, &mut post_session_configured_events);
         maybe_push_unstable_features_warning(&config, &mut post_session_configured_events);
+        maybe_push_base_instructions_budget_warning(&config, &mut post_session_configured_events);
+        maybe_push_workspace_root_warnings(&config, &mut post_session_configured_events);
+        if config.model_max_output_tokens.is_some()
+            || config.model_provider.extra_body.is_some()
+            || !config.extra_body.is_empty()
+            || !config.model_sampling.is_empty()
+        {
+            let model_info = models_manager
+                .get_model_info(session_configuration.collaboration_mode.model(), &config)
+                .await;
+            maybe_push_max_output_tokens_warning(
+                &config,
+                &model_info,
+                &mut post_session_configured_events,
+            );
+            maybe_push_ignored_extra_body_warning(
+                &config,
+                &model_info,
+                &mut post_session_configured_events,
+            );
+            maybe_push_sampling_parameters_warning(
+                &config,
+                &model_info,
+                &mut post_session_configured_events,
+            );
+        }
 
         let auth = auth.as_ref();
         let otel_manager = OtelManager::new(
@@ -897,14 +1376,32 @@ impl Session {
                     None
                 }
             };
+        let thread_name = match (thread_name, session_title) {
+            (None, Some(title)) if rollout_recorder.is_some() => {
+                // Index explicit titles so `codex resume <title>` finds the session.
+                if let Err(err) =
+                    session_index::append_thread_name(&config.codex_home, conversation_id, &title)
+                        .await
+                {
+                    warn!("Failed to record thread name in session index: {err}");
+                }
+                Some(title)
+            }
+            (thread_name, _) => thread_name,
+        };
         session_configuration.thread_name = thread_name.clone();
-        let state = SessionState::new(session_configuration.clone());
+        let mut state = SessionState::new(session_configuration.clone());
+        state.auto_title_pending = matches!(initial_history, InitialHistory::New)
+            && thread_name.is_none()
+            && rollout_recorder.is_some()
+            && !matches!(session_source, SessionSource::SubAgent(_));
 
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: Mutex::new(CancellationToken::new()),
             unified_exec_manager: UnifiedExecProcessManager::default(),
-            notifier: UserNotifier::new(config.notify.clone()),
+            notifier: UserNotifier::new(config.notify.clone())
+                .with_events(config.notify_events.clone()),
             rollout: Mutex::new(rollout_recorder),
             user_shell: Arc::new(default_shell),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -917,6 +1414,21 @@ impl Session {
             agent_control,
             state_db: state_db_ctx.clone(),
             transport_manager: TransportManager::new(),
+            injection_scanner: InjectionScanner::from_config(&config.injection_guard),
+            file_uploads: Mutex::new(FileUploads::default()),
+            command_explanations: Mutex::new(CommandExplanations::default()),
+            workspace_summaries: Mutex::new(WorkspaceSummaries::default()),
+            workspace_watcher: config.watch_workspace.then(|| {
+                let roots = std::iter::once(config.cwd.clone())
+                    .chain(
+                        config
+                            .workspace_roots
+                            .iter()
+                            .map(|root| root.path.to_path_buf()),
+                    )
+                    .collect();
+                WorkspaceWatcher::spawn(roots)
+            }),
         };
 
         let sess = Arc::new(Session {
@@ -934,6 +1446,16 @@ impl Session {
         // Dispatch the SessionConfiguredEvent first and then report any errors.
         // If resuming, include converted initial messages in the payload so UIs can render them immediately.
         let initial_messages = initial_history.get_event_msgs();
+        let startup_diagnostics = startup.diagnostics(
+            startup_auth(&config.model_provider, auth, config.dotenv.as_deref()),
+            provider_probe,
+            project_doc_files(&config),
+            mcp_servers
+                .iter()
+                .filter(|(_, server)| server.enabled)
+                .map(|(name, _)| name.clone())
+                .collect(),
+        );
         let events = std::iter::once(Event {
             id: INITIAL_SUBMIT_ID.to_owned(),
             msg: EventMsg::SessionConfigured(SessionConfiguredEvent {
@@ -950,6 +1472,13 @@ impl Session {
                 history_entry_count,
                 initial_messages,
                 rollout_path,
+                tool_toggles: session_configuration.tool_toggles,
+                base_instructions_tokens: Some(approx_tokens_from_byte_count(
+                    session_configuration.base_instructions.len(),
+                )),
+                unknown_model,
+                sampling: (!config.model_sampling.is_empty()).then_some(config.model_sampling),
+                startup: Some(startup_diagnostics),
             }),
         })
         .chain(post_session_configured_events.into_iter());
@@ -966,7 +1495,9 @@ impl Session {
         };
         let cancel_token = sess.mcp_startup_cancellation_token().await;
 
-        sess.services
+        let mcp_started = Instant::now();
+        let mcp_startup = sess
+            .services
             .mcp_connection_manager
             .write()
             .await
@@ -979,6 +1510,7 @@ impl Session {
                 sandbox_state,
             )
             .await;
+        tokio::spawn(startup.finish(mcp_startup, mcp_started, tx_event.clone()));
 
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
         sess.record_initial_history(initial_history).await;
@@ -1089,6 +1621,14 @@ impl Session {
                     state.set_token_info(Some(info));
                 }
 
+                self.restore_approval_grants(&turn_context, &rollout_items)
+                    .await;
+                self.services
+                    .file_uploads
+                    .lock()
+                    .await
+                    .restore(&rollout_items);
+
                 // Defer seeding the session's initial context until the first turn starts so
                 // turn/start overrides can be merged before we write to the rollout.
                 self.flush_rollout().await;
@@ -1110,6 +1650,12 @@ impl Session {
                     state.set_token_info(Some(info));
                 }
 
+                self.services
+                    .file_uploads
+                    .lock()
+                    .await
+                    .restore(&rollout_items);
+
                 // If persisting, persist all rollout items as-is (recorder filters)
                 if !rollout_items.is_empty() {
                     self.persist_rollout_items(&rollout_items).await;
@@ -1129,6 +1675,31 @@ impl Session {
         }
     }
 
+    /// Re-applies "approve for session" decisions recorded in a resumed
+    /// rollout. Grants made in a different working directory are dropped
+    /// because their keys refer to paths that may no longer mean the same thing.
+    async fn restore_approval_grants(
+        &self,
+        turn_context: &TurnContext,
+        rollout_items: &[RolloutItem],
+    ) {
+        let (grants, discarded) = approval_grants_to_restore(rollout_items, &turn_context.cwd);
+        if grants.is_empty() && discarded == 0 {
+            return;
+        }
+        {
+            let mut store = self.services.tool_approvals.lock().await;
+            for grant in &grants {
+                store.insert_session_approval(grant.key.clone());
+            }
+        }
+        self.send_event(
+            turn_context,
+            EventMsg::ApprovalGrantsRestored(ApprovalGrantsRestoredEvent { grants, discarded }),
+        )
+        .await;
+    }
+
     fn last_token_info_from_rollout(rollout_items: &[RolloutItem]) -> Option<TokenUsageInfo> {
         rollout_items.iter().rev().find_map(|item| match item {
             RolloutItem::EventMsg(EventMsg::TokenCount(ev)) => ev.info.clone(),
@@ -1175,6 +1746,7 @@ impl Session {
                         msg: EventMsg::Error(ErrorEvent {
                             message: err.to_string(),
                             codex_error_info: Some(CodexErrorInfo::BadRequest),
+                            ..Default::default()
                         }),
                     })
                     .await;
@@ -1188,6 +1760,9 @@ impl Session {
                 sub_id,
                 session_configuration,
                 updates.final_output_json_schema,
+                updates.max_output_tokens,
+                updates.service_tier,
+                updates.ask_mode,
                 sandbox_policy_changed,
             )
             .await)
@@ -1198,9 +1773,19 @@ impl Session {
         sub_id: String,
         session_configuration: SessionConfiguration,
         final_output_json_schema: Option<Option<Value>>,
+        max_output_tokens: Option<i64>,
+        service_tier: Option<ServiceTier>,
+        ask_mode: bool,
         sandbox_policy_changed: bool,
     ) -> Arc<TurnContext> {
-        let per_turn_config = Self::build_per_turn_config(&session_configuration);
+        let mut per_turn_config = Self::build_per_turn_config(&session_configuration);
+        if let Some(max_output_tokens) = max_output_tokens {
+            per_turn_config.model_max_output_tokens = Some(max_output_tokens);
+        }
+        if let Some(service_tier) = service_tier {
+            per_turn_config.service_tier = Some(service_tier);
+        }
+        per_turn_config.ask_mode |= ask_mode;
 
         if sandbox_policy_changed {
             let sandbox_state = SandboxState {
@@ -1263,8 +1848,16 @@ impl Session {
             let state = self.state.lock().await;
             state.session_configuration.clone()
         };
-        self.new_turn_from_configuration(sub_id, session_configuration, None, false)
-            .await
+        self.new_turn_from_configuration(
+            sub_id,
+            session_configuration,
+            None,
+            None,
+            None,
+            false,
+            false,
+        )
+        .await
     }
 
     pub(crate) async fn current_collaboration_mode(&self) -> CollaborationMode {
@@ -1340,6 +1933,18 @@ impl Session {
         }
     }
 
+    fn build_language_update_item(
+        &self,
+        previous: Option<&Arc<TurnContext>>,
+        next: &TurnContext,
+    ) -> Option<ResponseItem> {
+        let previous = previous?;
+        if previous.preferred_language == next.preferred_language {
+            return None;
+        }
+        Some(language_message(next.preferred_language.as_deref()).into())
+    }
+
     fn personality_message_for(model_info: &ModelInfo, personality: Personality) -> Option<String> {
         model_info
             .model_messages
@@ -1394,6 +1999,11 @@ impl Session {
         {
             update_items.push(personality_item);
         }
+        if let Some(language_item) =
+            self.build_language_update_item(previous_context, current_context)
+        {
+            update_items.push(language_item);
+        }
         update_items
     }
 
@@ -1545,7 +2155,8 @@ impl Session {
     ///
     /// The request is keyed by `sub_id`/`call_id` so matching responses are delivered
     /// to the correct in-flight turn. If the task is aborted, this returns the
-    /// default `ReviewDecision` (`Denied`).
+    /// default `ReviewDecision` (`Denied`). If `approval_timeout` is configured
+    /// and expires first, this returns the configured timeout decision.
     #[allow(clippy::too_many_arguments)]
     pub async fn request_command_approval(
         &self,
@@ -1574,7 +2185,22 @@ impl Session {
             warn!("Overwriting existing pending approval for sub_id: {event_id}");
         }
 
+        let resolution = ApprovalResolvedEvent {
+            call_id: call_id.clone(),
+            turn_id: turn_context.sub_id.clone(),
+            kind: ApprovalKind::Exec,
+            command: command.clone(),
+            paths: Vec::new(),
+            cwd: cwd.clone(),
+            reason: reason.clone(),
+            decision: ReviewDecision::default(),
+            resolved_by: ApprovalResolver::User,
+            account: None,
+            resolved_at: String::new(),
+        };
         let parsed_cmd = parse_command(&command);
+        let cwd_outside_workspace = !turn_context.is_in_workspace(&cwd);
+        let cwd_writable = turn_context.is_sandbox_writable(&cwd);
         let event = EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
@@ -1583,11 +2209,18 @@ impl Session {
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
+            cwd_outside_workspace,
+            cwd_writable,
         });
         self.send_event(turn_context, event).await;
-        rx_approve.await.unwrap_or_default()
+        let timeout = turn_context.client.config().approval_timeout;
+        self.await_approval_decision(turn_context, rx_approve, timeout, "command", resolution)
+            .await
     }
 
+    /// Emit a patch approval request event and await the user's decision,
+    /// with the same abort and timeout behavior as
+    /// [`Session::request_command_approval`].
     pub async fn request_patch_approval(
         &self,
         turn_context: &TurnContext,
@@ -1595,7 +2228,8 @@ impl Session {
         changes: HashMap<PathBuf, FileChange>,
         reason: Option<String>,
         grant_root: Option<PathBuf>,
-    ) -> oneshot::Receiver<ReviewDecision> {
+        dirty_paths: Vec<PathBuf>,
+    ) -> ReviewDecision {
         let sub_id = turn_context.sub_id.clone();
         // Add the tx_approve callback to the map before sending the request.
         let (tx_approve, rx_approve) = oneshot::channel();
@@ -1614,15 +2248,147 @@ impl Session {
             warn!("Overwriting existing pending approval for sub_id: {event_id}");
         }
 
+        let mut paths: Vec<PathBuf> = changes.keys().cloned().collect();
+        paths.sort();
+        let resolution = ApprovalResolvedEvent {
+            call_id: call_id.clone(),
+            turn_id: turn_context.sub_id.clone(),
+            kind: ApprovalKind::Patch,
+            command: Vec::new(),
+            paths,
+            cwd: turn_context.cwd.clone(),
+            reason: reason.clone(),
+            decision: ReviewDecision::default(),
+            resolved_by: ApprovalResolver::User,
+            account: None,
+            resolved_at: String::new(),
+        };
         let event = EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
             changes,
             reason,
             grant_root,
+            dirty_paths,
         });
         self.send_event(turn_context, event).await;
-        rx_approve
+        let timeout = turn_context.client.config().approval_timeout;
+        self.await_approval_decision(turn_context, rx_approve, timeout, "edit", resolution)
+            .await
+    }
+
+    /// Waits for the decision on a pending approval and records it as an
+    /// `ApprovalResolved` event built from `resolution`, which carries the
+    /// details of the request.
+    async fn await_approval_decision(
+        &self,
+        turn_context: &TurnContext,
+        rx_approve: oneshot::Receiver<ReviewDecision>,
+        timeout: Option<ApprovalTimeout>,
+        subject: &str,
+        resolution: ApprovalResolvedEvent,
+    ) -> ReviewDecision {
+        let requested_at = Instant::now();
+        let wait = self.wait_for_approval_decision(turn_context, rx_approve, timeout, subject);
+        // Notify the `notify` program once the debounce slot comes up, unless
+        // the request was answered first.
+        let (decision, resolved_by) = match self
+            .notifier()
+            .reserve_delayed(NotifyEventKind::ApprovalRequested)
+        {
+            Some(delay) => {
+                tokio::pin!(wait);
+                tokio::select! {
+                    outcome = &mut wait => outcome,
+                    () = tokio::time::sleep(delay) => {
+                        self.notifier().send_reserved(&approval_notification(
+                            self.conversation_id.to_string(),
+                            &resolution,
+                            requested_at.elapsed(),
+                        ));
+                        wait.await
+                    }
+                }
+            }
+            None => wait.await,
+        };
+        let config = turn_context.client.config();
+        let reason = resolution.reason.map(|reason| {
+            match Redactor::new(&config.export_redact_patterns).or_else(|_| Redactor::new(&[])) {
+                Ok(redactor) => redactor.redact(&reason),
+                Err(_) => reason,
+            }
+        });
+        let account = self
+            .services
+            .auth_manager
+            .auth_cached()
+            .and_then(|auth| auth.get_account_email());
+        let event = ApprovalResolvedEvent {
+            reason,
+            decision: decision.clone(),
+            resolved_by,
+            account,
+            resolved_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            ..resolution
+        };
+        self.send_event(turn_context, EventMsg::ApprovalResolved(event))
+            .await;
+        decision
+    }
+
+    /// Waits for the decision on a pending approval. When `timeout` is set and
+    /// nobody answers in time, resolves the request with the configured action
+    /// and emits a warning explaining why. A request whose turn ends first is
+    /// denied.
+    async fn wait_for_approval_decision(
+        &self,
+        turn_context: &TurnContext,
+        mut rx_approve: oneshot::Receiver<ReviewDecision>,
+        timeout: Option<ApprovalTimeout>,
+        subject: &str,
+    ) -> (ReviewDecision, ApprovalResolver) {
+        let answered = |decision: Result<ReviewDecision, oneshot::error::RecvError>| match decision
+        {
+            Ok(decision) => (decision, ApprovalResolver::User),
+            Err(_) => (ReviewDecision::default(), ApprovalResolver::TurnEnded),
+        };
+        let Some(timeout) = timeout else {
+            return answered(rx_approve.await);
+        };
+        if let Ok(decision) = tokio::time::timeout(timeout.duration, &mut rx_approve).await {
+            return answered(decision);
+        }
+
+        // Whoever removes the pending entry resolves the request. If a user
+        // decision already took it, that decision is in flight on the channel.
+        let entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.remove_pending_approval(&turn_context.sub_id)
+                }
+                None => None,
+            }
+        };
+        if entry.is_none() {
+            return answered(rx_approve.await);
+        }
+
+        let outcome = match timeout.action {
+            ApprovalTimeoutAction::Deny => format!("the {subject} was denied"),
+            ApprovalTimeoutAction::Abort => {
+                format!("the {subject} was denied and the turn aborted")
+            }
+        };
+        let message = format!(
+            "No approval decision within {}s; {outcome}.",
+            timeout.duration.as_secs()
+        );
+        self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+        (timeout.action.decision(), ApprovalResolver::Timeout)
     }
 
     pub async fn request_user_input(
@@ -1920,10 +2686,16 @@ impl Session {
                 .into(),
             );
         }
+        if let Some(language) = turn_context.preferred_language.as_deref() {
+            items.push(language_message(Some(language)).into());
+        }
         items.push(ResponseItem::from(EnvironmentContext::new(
             Some(turn_context.cwd.clone()),
             shell.as_ref().clone(),
         )));
+        if let Some(summary) = workspace_summary_item(self, turn_context).await {
+            items.push(summary);
+        }
         items
     }
 
@@ -2034,10 +2806,41 @@ impl Session {
         state.set_server_reasoning_included(included);
     }
 
-    async fn send_token_count_event(&self, turn_context: &TurnContext) {
-        let (info, rate_limits) = {
-            let state = self.state.lock().await;
-            state.token_info_and_rate_limits()
+    /// Records the tier the provider reports having used and warns when it
+    /// differs from the requested one. Warns again only after the served
+    /// tier changes.
+    pub(crate) async fn record_served_service_tier(
+        &self,
+        turn_context: &TurnContext,
+        served: String,
+    ) {
+        let changed = {
+            let mut state = self.state.lock().await;
+            let changed = state.served_service_tier.as_deref() != Some(served.as_str());
+            state.served_service_tier = Some(served.clone());
+            changed
+        };
+        let Some(requested) = turn_context.client.get_service_tier() else {
+            return;
+        };
+        if !changed || requested == ServiceTier::Auto || requested.to_string() == served {
+            return;
+        }
+        self.send_event(
+            turn_context,
+            EventMsg::Warning(WarningEvent {
+                message: format!(
+                    "Requested the {requested} service tier, but the provider processed the response with the {served} tier."
+                ),
+            }),
+        )
+        .await;
+    }
+
+    async fn send_token_count_event(&self, turn_context: &TurnContext) {
+        let (info, rate_limits) = {
+            let state = self.state.lock().await;
+            state.token_info_and_rate_limits()
         };
         let event = EventMsg::TokenCount(TokenCountEvent { info, rate_limits });
         self.send_event(turn_context, event).await;
@@ -2081,6 +2884,40 @@ impl Session {
         let turn_item = TurnItem::UserMessage(UserMessageItem::new(input));
         self.emit_turn_item_started(turn_context, &turn_item).await;
         self.emit_turn_item_completed(turn_context, turn_item).await;
+        self.maybe_record_auto_title(input).await;
+    }
+
+    /// Title an untitled new session after its first user message. The title
+    /// only goes into the rollout header; it does not become the thread name.
+    async fn maybe_record_auto_title(&self, input: &[UserInput]) {
+        let title = {
+            let mut state = self.state.lock().await;
+            if !state.auto_title_pending {
+                return;
+            }
+            let text = input
+                .iter()
+                .filter_map(|item| match item {
+                    UserInput::Text { text, .. } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let Some(title) = crate::util::session_title_from_message(&text) else {
+                return;
+            };
+            state.auto_title_pending = false;
+            title
+        };
+        let recorder = {
+            let guard = self.services.rollout.lock().await;
+            guard.clone()
+        };
+        if let Some(recorder) = recorder
+            && let Err(err) = recorder.set_title(title).await
+        {
+            warn!("failed to record session title: {err}");
+        }
     }
 
     pub(crate) async fn notify_background_event(
@@ -2117,7 +2954,7 @@ impl Session {
         turn_context: Arc<TurnContext>,
         cancellation_token: CancellationToken,
     ) {
-        if !self.enabled(Feature::GhostCommit) {
+        if !self.enabled(Feature::GhostCommit) || turn_context.is_ask_turn() {
             return;
         }
         let token = match turn_context.tool_call_gate.subscribe().await {
@@ -2153,6 +2990,88 @@ impl Session {
         }
     }
 
+    /// Queue `input` instead of starting a turn when the model provider
+    /// cannot be reached, rather than letting the turn spend minutes in the
+    /// retry loop. Returns the input if a turn should start now.
+    async fn queue_if_offline(
+        self: &Arc<Self>,
+        turn_context: &TurnContext,
+        input: Vec<UserInput>,
+    ) -> Option<Vec<UserInput>> {
+        // In offline mode there is nothing to wait for: the turn's model
+        // requests fail immediately instead.
+        if turn_context.client.config().offline {
+            return Some(input);
+        }
+        // Later prompts wait behind earlier ones without probing again.
+        let already_queued = !self.state.lock().await.offline_queue.is_empty();
+        if !already_queued && turn_context.client.provider_reachable().await {
+            return Some(input);
+        }
+        let queued_prompts = {
+            let mut state = self.state.lock().await;
+            state.offline_queue.push(input);
+            state.offline_queue.len()
+        };
+        if queued_prompts == 1 {
+            self.spawn_offline_retry(turn_context.client.clone());
+        }
+        self.send_event(
+            turn_context,
+            EventMsg::PromptQueuedOffline(PromptQueuedOfflineEvent {
+                queued_prompts: u32::try_from(queued_prompts).unwrap_or(u32::MAX),
+            }),
+        )
+        .await;
+        None
+    }
+
+    /// Probe the provider every [`OFFLINE_RETRY_INTERVAL`] until it answers,
+    /// then submit the queued prompts. Stops once the session is dropped.
+    fn spawn_offline_retry(self: &Arc<Self>, client: ModelClient) {
+        let session = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(OFFLINE_RETRY_INTERVAL).await;
+                if session.strong_count() == 0 {
+                    return;
+                }
+                if client.provider_reachable().await {
+                    break;
+                }
+            }
+            if let Some(sess) = session.upgrade() {
+                sess.submit_offline_queue().await;
+            }
+        });
+    }
+
+    /// Start a turn with the oldest queued prompt; the rest join it as pending
+    /// input, just like prompts submitted while a turn is running.
+    async fn submit_offline_queue(self: &Arc<Self>) {
+        let queued = std::mem::take(&mut self.state.lock().await.offline_queue);
+        if queued.is_empty() {
+            return;
+        }
+        let turn_context = self
+            .new_default_turn_with_sub_id(self.next_internal_sub_id())
+            .await;
+        self.send_event(
+            &turn_context,
+            EventMsg::ConnectivityRestored(ConnectivityRestoredEvent {
+                submitted_prompts: u32::try_from(queued.len()).unwrap_or(u32::MAX),
+            }),
+        )
+        .await;
+        for input in queued {
+            if let Err(input) = self.inject_input(input).await {
+                self.seed_initial_context_if_needed(&turn_context).await;
+                self.spawn_task(Arc::clone(&turn_context), input, RegularTask)
+                    .await;
+            }
+        }
+    }
+
     /// Returns the input if there was no task running to inject into
     pub async fn inject_response_items(
         &self,
@@ -2193,6 +3112,208 @@ impl Session {
         }
     }
 
+    /// Remembers the backend fingerprint reported for the running turn so it
+    /// can be reported when the turn completes.
+    pub(crate) async fn record_system_fingerprint(&self, fingerprint: String) {
+        let active = self.active_turn.lock().await;
+        if let Some(at) = active.as_ref() {
+            let mut ts = at.turn_state.lock().await;
+            ts.set_system_fingerprint(fingerprint);
+        }
+    }
+
+    /// Hand `text` to the running turn for its next model request. Returns
+    /// the text if there was no turn to steer.
+    pub(crate) async fn steer_active_turn(&self, text: String) -> Result<(), String> {
+        let active = self.active_turn.lock().await;
+        match active.as_ref() {
+            Some(at) => {
+                at.turn_state.lock().await.push_steer(text);
+                Ok(())
+            }
+            None => Err(text),
+        }
+    }
+
+    pub(crate) async fn take_steers(&self) -> Vec<String> {
+        let active = self.active_turn.lock().await;
+        match active.as_ref() {
+            Some(at) => at.turn_state.lock().await.take_steers(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Start a turn with steers that arrived too late for the turn they were
+    /// sent to, as if they had been submitted once it ended.
+    pub(crate) async fn submit_deferred_steers(self: &Arc<Self>, steers: Vec<String>) {
+        if steers.is_empty() {
+            return;
+        }
+        let turn_context = self
+            .new_default_turn_with_sub_id(self.next_internal_sub_id())
+            .await;
+        let mut input = Vec::with_capacity(steers.len());
+        for text in steers {
+            self.send_event(
+                &turn_context,
+                EventMsg::SteerDelivered(SteerDeliveredEvent {
+                    text: text.clone(),
+                    deferred: true,
+                }),
+            )
+            .await;
+            input.push(UserInput::Text {
+                text,
+                text_elements: Vec::new(),
+            });
+        }
+        if let Err(input) = self.inject_input(input).await {
+            self.spawn_task(turn_context, input, RegularTask).await;
+        }
+    }
+
+    /// Update the running turn's file provenance and tell the workspace
+    /// watcher about the files it recorded; a no-op between turns.
+    pub(crate) async fn record_file_provenance(&self, record: impl FnOnce(&mut FileProvenance)) {
+        let active = self.active_turn.lock().await;
+        if let Some(at) = active.as_ref() {
+            let mut ts = at.turn_state.lock().await;
+            record(ts.file_provenance_mut());
+            let touched = ts.file_provenance_mut().take_touched();
+            if let Some(watcher) = self.services.workspace_watcher.as_ref() {
+                watcher.note_session_access(touched);
+            }
+        }
+    }
+
+    /// Note work the running turn has started that an abort would cancel,
+    /// until [`Session::finish_cancellable_work`]; a no-op between turns.
+    pub(crate) async fn start_cancellable_work(&self, key: &str, work: CancelledWork) {
+        let active = self.active_turn.lock().await;
+        if let Some(at) = active.as_ref() {
+            at.turn_state.lock().await.start_work(key.to_string(), work);
+        }
+    }
+
+    pub(crate) async fn finish_cancellable_work(&self, key: &str) {
+        let active = self.active_turn.lock().await;
+        if let Some(at) = active.as_ref() {
+            at.turn_state.lock().await.finish_work(key);
+        }
+    }
+
+    /// Remember the end event to send for `end_on_abort.call_id` if the turn
+    /// is aborted before the patch reports its own result.
+    pub(crate) async fn track_patch_apply(&self, end_on_abort: PatchApplyEndEvent) {
+        let mut state = self.state.lock().await;
+        state
+            .unfinished_patch_applies
+            .insert(end_on_abort.call_id.clone(), end_on_abort);
+    }
+
+    pub(crate) async fn finish_patch_apply(&self, call_id: &str) {
+        let mut state = self.state.lock().await;
+        state.unfinished_patch_applies.remove(call_id);
+    }
+
+    /// Remove and return the end events for `turn_id`'s unfinished patches.
+    pub(crate) async fn take_unfinished_patch_applies(
+        &self,
+        turn_id: &str,
+    ) -> Vec<PatchApplyEndEvent> {
+        let mut state = self.state.lock().await;
+        let call_ids: Vec<String> = state
+            .unfinished_patch_applies
+            .iter()
+            .filter(|(_, end)| end.turn_id == turn_id)
+            .map(|(call_id, _)| call_id.clone())
+            .collect();
+        call_ids
+            .iter()
+            .filter_map(|call_id| state.unfinished_patch_applies.remove(call_id))
+            .collect()
+    }
+
+    /// Flag the running turn as having read a suspected prompt injection; a
+    /// no-op between turns.
+    pub(crate) async fn mark_injection_suspected(&self) {
+        let active = self.active_turn.lock().await;
+        if let Some(at) = active.as_ref() {
+            at.turn_state.lock().await.mark_injection_suspected();
+        }
+    }
+
+    /// Note that a patch to `path` failed on stale context in the running
+    /// turn; a no-op between turns. See [`crate::stale_patch`].
+    pub(crate) async fn record_stale_patch(&self, path: PathBuf) {
+        let active = self.active_turn.lock().await;
+        if let Some(at) = active.as_ref() {
+            at.turn_state.lock().await.record_stale_patch(path);
+        }
+    }
+
+    pub(crate) async fn clear_stale_patches(&self, patched: &[PathBuf]) {
+        let active = self.active_turn.lock().await;
+        if let Some(at) = active.as_ref() {
+            at.turn_state.lock().await.clear_stale_patches(patched);
+        }
+    }
+
+    async fn take_stale_patch_retry(&self) -> Option<Vec<PathBuf>> {
+        let active = self.active_turn.lock().await;
+        match active.as_ref() {
+            Some(at) => at.turn_state.lock().await.take_stale_patch_retry(),
+            None => None,
+        }
+    }
+
+    /// The approval policy tool calls should honor: `UnlessTrusted` once the
+    /// running turn has been flagged by the injection guard, otherwise the
+    /// turn's own policy.
+    pub(crate) async fn effective_approval_policy(&self, turn: &TurnContext) -> AskForApproval {
+        let active = self.active_turn.lock().await;
+        let flagged = match active.as_ref() {
+            Some(at) => at.turn_state.lock().await.injection_suspected(),
+            None => false,
+        };
+        if flagged {
+            AskForApproval::UnlessTrusted
+        } else {
+            turn.approval_policy
+        }
+    }
+
+    /// Remember files written during a turn so `/commit` can stage them.
+    pub(crate) async fn record_files_written(&self, files: &[PathBuf]) {
+        let mut state = self.state.lock().await;
+        state.files_written.extend(files.iter().cloned());
+    }
+
+    /// Counts a newly started model turn and returns its 1-based number.
+    pub(crate) async fn start_turn_number(&self) -> u64 {
+        let mut state = self.state.lock().await;
+        state.turns_started += 1;
+        state.turns_started
+    }
+
+    pub(crate) async fn turn_number(&self) -> u64 {
+        self.state.lock().await.turns_started
+    }
+
+    /// Files written by any turn of this session, in path order.
+    pub(crate) async fn files_written(&self) -> Vec<PathBuf> {
+        let state = self.state.lock().await;
+        state.files_written.iter().cloned().collect()
+    }
+
+    pub(crate) async fn take_file_provenance(&self) -> FileProvenance {
+        let active = self.active_turn.lock().await;
+        match active.as_ref() {
+            Some(at) => at.turn_state.lock().await.take_file_provenance(),
+            None => FileProvenance::default(),
+        }
+    }
+
     pub async fn list_resources(
         &self,
         server: &str,
@@ -2415,6 +3536,18 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                         None,
                     )
                 };
+                if handlers::reject_unsupported_reasoning_override(
+                    &sess,
+                    &config,
+                    &sub.id,
+                    collaboration_mode.model(),
+                    effort,
+                    summary,
+                )
+                .await
+                {
+                    continue;
+                }
                 handlers::override_turn_context(
                     &sess,
                     sub.id.clone(),
@@ -2431,13 +3564,51 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 )
                 .await;
             }
+            Op::RestoreTurnSettings {
+                approval_policy,
+                sandbox_policy,
+                model,
+                effort,
+            } => {
+                let collaboration_mode = {
+                    let state = sess.state.lock().await;
+                    state.session_configuration.collaboration_mode.with_updates(
+                        Some(model),
+                        Some(effort),
+                        None,
+                    )
+                };
+                handlers::restore_turn_settings(
+                    &sess,
+                    SessionSettingsUpdate {
+                        approval_policy: Some(approval_policy),
+                        sandbox_policy: Some(sandbox_policy),
+                        collaboration_mode: Some(collaboration_mode),
+                        ..Default::default()
+                    },
+                )
+                .await;
+            }
             Op::UserInput { .. } | Op::UserTurn { .. } => {
                 handlers::user_input_or_turn(&sess, sub.id.clone(), sub.op, &mut previous_context)
                     .await;
             }
+            Op::SteerTask { text } => {
+                handlers::steer_task(&sess, text).await;
+            }
+            Op::Ask { items } => {
+                handlers::ask(&sess, sub.id.clone(), items, &mut previous_context).await;
+            }
             Op::ExecApproval { id, decision } => {
                 handlers::exec_approval(&sess, id, decision).await;
             }
+            Op::ExplainCommand {
+                call_id,
+                command,
+                cwd,
+            } => {
+                handlers::explain_command(&sess, sub.id.clone(), call_id, command, cwd).await;
+            }
             Op::PatchApproval { id, decision } => {
                 handlers::patch_approval(&sess, id, decision).await;
             }
@@ -2454,9 +3625,46 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 handlers::get_history_entry_request(&sess, &config, sub.id.clone(), offset, log_id)
                     .await;
             }
+            Op::ClearHistory => {
+                handlers::clear_history(&sess, &config, sub.id.clone()).await;
+            }
             Op::ListMcpTools => {
                 handlers::list_mcp_tools(&sess, &config, sub.id.clone()).await;
             }
+            Op::ListTools => {
+                handlers::list_tools(&sess, sub.id.clone()).await;
+            }
+            Op::GetTokenBreakdown => {
+                handlers::token_breakdown(&sess, sub.id.clone()).await;
+            }
+            Op::SetToolToggles { toggles } => {
+                handlers::override_turn_context(
+                    &sess,
+                    sub.id.clone(),
+                    SessionSettingsUpdate {
+                        tool_toggles: Some(toggles),
+                        ..Default::default()
+                    },
+                )
+                .await;
+            }
+            Op::SetPreferredLanguage { language } => {
+                let language = language
+                    .map(|language| language.trim().to_string())
+                    .filter(|language| !language.is_empty());
+                handlers::override_turn_context(
+                    &sess,
+                    sub.id.clone(),
+                    SessionSettingsUpdate {
+                        preferred_language: Some(language),
+                        ..Default::default()
+                    },
+                )
+                .await;
+            }
+            Op::RevokeSessionApprovals => {
+                handlers::revoke_session_approvals(&sess, sub.id.clone()).await;
+            }
             Op::RefreshMcpServers { config } => {
                 handlers::refresh_mcp_servers(&sess, config).await;
             }
@@ -2469,6 +3677,23 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
+            Op::ListGhostSnapshots => {
+                handlers::list_ghost_snapshots(&sess, sub.id.clone()).await;
+            }
+            Op::ReadGhostSnapshotFile { commit_id, path } => {
+                handlers::read_ghost_snapshot_file(&sess, sub.id.clone(), commit_id, path).await;
+            }
+            Op::DraftCommit { sign_off, amend } => {
+                handlers::draft_commit(&sess, sub.id.clone(), sign_off, amend).await;
+            }
+            Op::Commit {
+                message,
+                files,
+                sign_off,
+                amend,
+            } => {
+                handlers::commit(&sess, sub.id.clone(), message, files, sign_off, amend).await;
+            }
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
@@ -2514,29 +3739,36 @@ mod handlers {
     use crate::codex::SessionSettingsUpdate;
     use crate::codex::TurnContext;
 
+    use crate::codex::list_tools_for_next_turn;
     use crate::codex::spawn_review_thread;
     use crate::config::Config;
+    use crate::features::Feature;
+    use crate::snapshot_history;
 
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
     use crate::mcp::effective_mcp_servers;
     use crate::review_prompts::resolve_review_request;
     use crate::rollout::session_index;
+    use crate::tasks::CommitTask;
     use crate::tasks::CompactTask;
     use crate::tasks::RegularTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
+    use crate::token_breakdown::token_breakdown_for_next_request;
     use codex_protocol::custom_prompts::CustomPrompt;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::HistoryClearedEvent;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::McpServerRefreshConfig;
     use codex_protocol::protocol::Op;
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
+    use codex_protocol::protocol::SessionApprovalsRevokedEvent;
     use codex_protocol::protocol::SkillsListEntry;
     use codex_protocol::protocol::ThreadNameUpdatedEvent;
     use codex_protocol::protocol::ThreadRolledBackEvent;
@@ -2547,8 +3779,11 @@ mod handlers {
     use crate::context_manager::is_user_turn_boundary;
     use codex_protocol::config_types::CollaborationMode;
     use codex_protocol::config_types::ModeKind;
+    use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
     use codex_protocol::config_types::Settings;
     use codex_protocol::dynamic_tools::DynamicToolResponse;
+    use codex_protocol::message_history::HistoryLogMetadata;
+    use codex_protocol::openai_models::ReasoningEffort;
     use codex_protocol::user_input::UserInput;
     use codex_rmcp_client::ElicitationAction;
     use codex_rmcp_client::ElicitationResponse;
@@ -2562,6 +3797,59 @@ mod handlers {
         sess.interrupt_task().await;
     }
 
+    /// Rejects explicit reasoning effort or summary overrides for models that do not
+    /// accept reasoning parameters; the request builder would otherwise drop them
+    /// silently. Returns `true` when the override was rejected.
+    pub async fn reject_unsupported_reasoning_override(
+        sess: &Session,
+        config: &Config,
+        sub_id: &str,
+        model: &str,
+        effort: Option<Option<ReasoningEffort>>,
+        summary: Option<ReasoningSummaryConfig>,
+    ) -> bool {
+        let requests_effort =
+            matches!(effort, Some(Some(effort)) if effort != ReasoningEffort::None);
+        let requests_summary =
+            matches!(summary, Some(summary) if summary != ReasoningSummaryConfig::None);
+        if !requests_effort && !requests_summary {
+            return false;
+        }
+        let model_info = sess
+            .services
+            .models_manager
+            .get_model_info(model, config)
+            .await;
+        if model_info.supports_reasoning {
+            return false;
+        }
+        sess.send_event_raw(Event {
+            id: sub_id.to_string(),
+            msg: EventMsg::Error(ErrorEvent {
+                message: format!(
+                    "{model} does not support reasoning; the reasoning effort and summary were not changed."
+                ),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+                ..Default::default()
+            }),
+        })
+        .await;
+        true
+    }
+
+    /// Restores `updates` once the running turn ends, or right away when no
+    /// turn is running. The active turn stays locked while the update is
+    /// stored so a turn finishing concurrently cannot miss it.
+    pub async fn restore_turn_settings(sess: &Session, updates: SessionSettingsUpdate) {
+        let active = sess.active_turn.lock().await;
+        if active.is_some() {
+            sess.state.lock().await.settings_after_turn = Some(updates);
+            return;
+        }
+        drop(active);
+        let _ = sess.update_settings(updates).await;
+    }
+
     pub async fn override_turn_context(
         sess: &Session,
         sub_id: String,
@@ -2585,6 +3873,7 @@ mod handlers {
                 msg: EventMsg::Error(ErrorEvent {
                     message: err.to_string(),
                     codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    ..Default::default()
                 }),
             })
             .await;
@@ -2627,6 +3916,8 @@ mod handlers {
                 items,
                 collaboration_mode,
                 personality,
+                max_output_tokens,
+                service_tier,
             } => {
                 let collaboration_mode = collaboration_mode.or_else(|| {
                     Some(CollaborationMode {
@@ -2649,6 +3940,11 @@ mod handlers {
                         reasoning_summary: Some(summary),
                         final_output_json_schema: Some(final_output_json_schema),
                         personality,
+                        tool_toggles: None,
+                        preferred_language: None,
+                        max_output_tokens,
+                        service_tier,
+                        ask_mode: false,
                     },
                 )
             }
@@ -2684,6 +3980,9 @@ mod handlers {
 
         // Attempt to inject input into current task
         if let Err(items) = sess.inject_input(items).await {
+            let Some(items) = sess.queue_if_offline(&current_context, items).await else {
+                return;
+            };
             sess.seed_initial_context_if_needed(&current_context).await;
             let update_items = sess.build_settings_update_items(
                 previous_context.as_ref(),
@@ -2704,6 +4003,37 @@ mod handlers {
         }
     }
 
+    /// Runs `items` as a single turn without tools; see [`crate::ask`].
+    pub async fn ask(
+        sess: &Arc<Session>,
+        sub_id: String,
+        items: Vec<UserInput>,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        let updates = SessionSettingsUpdate {
+            ask_mode: true,
+            ..Default::default()
+        };
+        let Ok(current_context) = sess.new_turn_with_sub_id(sub_id, updates).await else {
+            // new_turn_with_sub_id already emits the error event.
+            return;
+        };
+        current_context
+            .client
+            .get_otel_manager()
+            .user_prompt(&items);
+        sess.seed_initial_context_if_needed(&current_context).await;
+        sess.spawn_task(Arc::clone(&current_context), items, RegularTask)
+            .await;
+        *previous_context = Some(current_context);
+    }
+
+    pub async fn steer_task(sess: &Arc<Session>, text: String) {
+        if let Err(text) = sess.steer_active_turn(text).await {
+            sess.submit_deferred_steers(vec![text]).await;
+        }
+    }
+
     pub async fn run_user_shell_command(
         sess: &Arc<Session>,
         sub_id: String,
@@ -2752,6 +4082,11 @@ mod handlers {
     /// Propagate a user's exec approval decision to the session.
     /// Also optionally applies an execpolicy amendment.
     pub async fn exec_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
+        sess.services
+            .command_explanations
+            .lock()
+            .await
+            .cancel_for_turn(&id);
         if let ReviewDecision::ApprovedExecpolicyAmendment {
             proposed_execpolicy_amendment,
         } = &decision
@@ -2784,6 +4119,16 @@ mod handlers {
         }
     }
 
+    pub async fn explain_command(
+        sess: &Arc<Session>,
+        sub_id: String,
+        call_id: String,
+        command: Vec<String>,
+        cwd: PathBuf,
+    ) {
+        crate::command_explanation::explain_command(sess, sub_id, call_id, command, cwd).await;
+    }
+
     pub async fn patch_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
         match decision {
             ReviewDecision::Abort => {
@@ -2831,12 +4176,26 @@ mod handlers {
 
         tokio::spawn(async move {
             // Run lookup in blocking thread because it does file IO + locking.
+            let lookup_config = Arc::clone(&config);
             let entry_opt = tokio::task::spawn_blocking(move || {
-                crate::message_history::lookup(log_id, offset, &config)
+                crate::message_history::lookup(log_id, offset, &lookup_config)
             })
             .await
             .unwrap_or(None);
 
+            // A miss may mean the history was compacted or cleared since the
+            // client learned `log_id`; tell it which log to use instead.
+            let refreshed = if entry_opt.is_none() && log_id != 0 {
+                let (current_log_id, entry_count) =
+                    crate::message_history::history_metadata(&config).await;
+                (current_log_id != log_id).then_some(HistoryLogMetadata {
+                    log_id: current_log_id,
+                    entry_count,
+                })
+            } else {
+                None
+            };
+
             let event = Event {
                 id: sub_id,
                 msg: EventMsg::GetHistoryEntryResponse(
@@ -2848,6 +4207,7 @@ mod handlers {
                             ts: e.ts,
                             text: e.text,
                         }),
+                        refreshed,
                     },
                 ),
             };
@@ -2856,6 +4216,20 @@ mod handlers {
         });
     }
 
+    pub async fn clear_history(sess: &Arc<Session>, config: &Arc<Config>, sub_id: String) {
+        let msg = match crate::message_history::clear(config).await {
+            Ok(log_id) => EventMsg::HistoryCleared(HistoryClearedEvent { log_id }),
+            Err(e) => {
+                warn!("failed to clear message history: {e}");
+                EventMsg::Error(ErrorEvent {
+                    message: format!("Failed to clear message history: {e}"),
+                    ..Default::default()
+                })
+            }
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
     pub async fn refresh_mcp_servers(sess: &Arc<Session>, refresh_config: McpServerRefreshConfig) {
         let mut guard = sess.pending_mcp_server_refresh_config.lock().await;
         *guard = Some(refresh_config);
@@ -2878,13 +4252,44 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_tools(sess: &Session, sub_id: String) {
+        let msg = match list_tools_for_next_turn(sess).await {
+            Ok(event) => EventMsg::ListToolsResponse(event),
+            Err(err) => {
+                EventMsg::Error(err.to_error_event(Some("Failed to list tools".to_string())))
+            }
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn token_breakdown(sess: &Session, sub_id: String) {
+        let msg = match token_breakdown_for_next_request(sess).await {
+            Ok(event) => EventMsg::TokenBreakdown(event),
+            Err(err) => EventMsg::Error(
+                err.to_error_event(Some("Failed to estimate token usage".to_string())),
+            ),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn revoke_session_approvals(sess: &Session, sub_id: String) {
+        let revoked = sess.services.tool_approvals.lock().await.clear();
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::SessionApprovalsRevoked(SessionApprovalsRevokedEvent {
+                revoked: u32::try_from(revoked).unwrap_or(u32::MAX),
+            }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
+        let cwd = {
+            let state = sess.state.lock().await;
+            state.session_configuration.cwd.clone()
+        };
         let custom_prompts: Vec<CustomPrompt> =
-            if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
-                crate::custom_prompts::discover_prompts_in(&dir).await
-            } else {
-                Vec::new()
-            };
+            crate::custom_prompts::discover_custom_prompts(&cwd).await;
 
         let event = Event {
             id: sub_id,
@@ -2928,19 +4333,79 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_ghost_snapshots(sess: &Session, sub_id: String) {
+        let cwd = {
+            let state = sess.state.lock().await;
+            state.session_configuration.cwd.clone()
+        };
+        let history = sess.clone_history().await;
+        let snapshots = snapshot_history::recorded_snapshots(history.raw_items());
+        let response =
+            snapshot_history::list_snapshots(cwd, snapshots, sess.enabled(Feature::GhostCommit))
+                .await;
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::GhostSnapshotsResponse(response),
+        })
+        .await;
+    }
+
+    pub async fn read_ghost_snapshot_file(
+        sess: &Session,
+        sub_id: String,
+        commit_id: String,
+        path: PathBuf,
+    ) {
+        let cwd = {
+            let state = sess.state.lock().await;
+            state.session_configuration.cwd.clone()
+        };
+        let history = sess.clone_history().await;
+        let snapshots = snapshot_history::recorded_snapshots(history.raw_items());
+        let response = snapshot_history::read_snapshot_file(cwd, snapshots, commit_id, path).await;
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::GhostSnapshotFileResponse(response),
+        })
+        .await;
+    }
+
     pub async fn undo(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
         sess.spawn_task(turn_context, Vec::new(), UndoTask::new())
             .await;
     }
 
+    pub async fn draft_commit(sess: &Arc<Session>, sub_id: String, sign_off: bool, amend: bool) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(turn_context, Vec::new(), CommitTask::draft(sign_off, amend))
+            .await;
+    }
+
+    pub async fn commit(
+        sess: &Arc<Session>,
+        sub_id: String,
+        message: Option<String>,
+        files: Vec<PathBuf>,
+        sign_off: bool,
+        amend: bool,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(
+            turn_context,
+            Vec::new(),
+            CommitTask::commit(message, files, sign_off, amend),
+        )
+        .await;
+    }
+
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
 
         sess.spawn_task(
             Arc::clone(&turn_context),
             vec![UserInput::Text {
-                text: turn_context.compact_prompt().to_string(),
+                text: turn_context.compact_prompt(),
                 // Compaction prompt is synthesized; no UI element ranges to preserve.
                 text_elements: Vec::new(),
             }],
@@ -2956,6 +4421,7 @@ mod handlers {
                 msg: EventMsg::Error(ErrorEvent {
                     message: "num_turns must be >= 1".to_string(),
                     codex_error_info: Some(CodexErrorInfo::ThreadRollbackFailed),
+                    ..Default::default()
                 }),
             })
             .await;
@@ -2969,6 +4435,7 @@ mod handlers {
                 msg: EventMsg::Error(ErrorEvent {
                     message: "Cannot rollback while a turn is in progress.".to_string(),
                     codex_error_info: Some(CodexErrorInfo::ThreadRollbackFailed),
+                    ..Default::default()
                 }),
             })
             .await;
@@ -2996,7 +4463,8 @@ mod handlers {
     /// a `ThreadNameUpdated` event on success.
     ///
     /// This appends the name to `CODEX_HOME/sessions_index.jsonl` via `session_index::append_thread_name` for the
-    /// current `thread_id`, then updates `SessionConfiguration::thread_name`.
+    /// current `thread_id`, records it as the title in the rollout header, then updates
+    /// `SessionConfiguration::thread_name`.
     ///
     /// Returns an error event if the name is empty or session persistence is disabled.
     pub async fn set_thread_name(sess: &Arc<Session>, sub_id: String, name: String) {
@@ -3006,6 +4474,7 @@ mod handlers {
                 msg: EventMsg::Error(ErrorEvent {
                     message: "Thread name cannot be empty.".to_string(),
                     codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    ..Default::default()
                 }),
             };
             sess.send_event_raw(event).await;
@@ -3022,6 +4491,7 @@ mod handlers {
                 msg: EventMsg::Error(ErrorEvent {
                     message: "Session persistence is disabled; cannot rename thread.".to_string(),
                     codex_error_info: Some(CodexErrorInfo::Other),
+                    ..Default::default()
                 }),
             };
             sess.send_event_raw(event).await;
@@ -3037,15 +4507,27 @@ mod handlers {
                 msg: EventMsg::Error(ErrorEvent {
                     message: format!("Failed to set thread name: {e}"),
                     codex_error_info: Some(CodexErrorInfo::Other),
+                    ..Default::default()
                 }),
             };
             sess.send_event_raw(event).await;
             return;
         }
 
+        let recorder = {
+            let guard = sess.services.rollout.lock().await;
+            guard.clone()
+        };
+        if let Some(recorder) = recorder
+            && let Err(err) = recorder.set_title(name.clone()).await
+        {
+            warn!("failed to record session title in rollout header: {err}");
+        }
+
         {
             let mut state = sess.state.lock().await;
             state.session_configuration.thread_name = Some(name.clone());
+            state.auto_title_pending = false;
         }
 
         sess.send_event_raw(Event {
@@ -3064,6 +4546,7 @@ mod handlers {
             .unified_exec_manager
             .terminate_all_processes()
             .await;
+        crate::file_uploads::delete_uploaded_files(sess).await;
         info!("Shutting down Codex instance");
         let history = sess.clone_history().await;
         let turn_count = history
@@ -3092,6 +4575,7 @@ mod handlers {
                 msg: EventMsg::Error(ErrorEvent {
                     message: "Failed to shutdown rollout recorder".to_string(),
                     codex_error_info: Some(CodexErrorInfo::Other),
+                    ..Default::default()
                 }),
             };
             sess.send_event_raw(event).await;
@@ -3130,6 +4614,7 @@ mod handlers {
                     msg: EventMsg::Error(ErrorEvent {
                         message: err.to_string(),
                         codex_error_info: Some(CodexErrorInfo::Other),
+                        ..Default::default()
                     }),
                 };
                 sess.send_event(&turn_context, event.msg).await;
@@ -3165,7 +4650,8 @@ async fn spawn_review_thread(
         model_info: &review_model_info,
         features: &review_features,
         web_search_mode: Some(review_web_search_mode),
-    });
+    })
+    .with_tool_toggles(parent_turn_context.tools_config.tool_toggles);
 
     let review_prompt = resolved.prompt.clone();
     let provider = parent_turn_context.client.get_provider();
@@ -3207,6 +4693,7 @@ async fn spawn_review_thread(
         compact_prompt: parent_turn_context.compact_prompt.clone(),
         collaboration_mode_kind: parent_turn_context.collaboration_mode_kind,
         personality: parent_turn_context.personality,
+        preferred_language: parent_turn_context.preferred_language.clone(),
         approval_policy: parent_turn_context.approval_policy,
         sandbox_policy: parent_turn_context.sandbox_policy.clone(),
         windows_sandbox_level: parent_turn_context.windows_sandbox_level,
@@ -3217,6 +4704,7 @@ async fn spawn_review_thread(
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         dynamic_tools: parent_turn_context.dynamic_tools.clone(),
         truncation_policy: model_info.truncation_policy.into(),
+        tool_output_max_lines: parent_turn_context.tool_output_max_lines,
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -3321,12 +4809,40 @@ pub(crate) async fn run_turn(
     let event = EventMsg::TurnStarted(TurnStartedEvent {
         model_context_window: turn_context.client.get_model_context_window(),
         collaboration_mode_kind: turn_context.collaboration_mode_kind,
+        settings: Some(turn_context.turn_settings()),
     });
     sess.send_event(&turn_context, event).await;
+    sess.start_turn_number().await;
+    if let Err(message) = hooks::run_hooks(
+        sess.as_ref(),
+        turn_context.as_ref(),
+        HookPoint::PreTurn,
+        HookDetails::default(),
+    )
+    .await
+    {
+        let event = EventMsg::Error(ErrorEvent {
+            message,
+            ..Default::default()
+        });
+        sess.send_event(&turn_context, event).await;
+        return None;
+    }
+    if let Some(message) = sess
+        .services
+        .models_manager
+        .unknown_model_warning(&model_info.slug, &turn_context.client.config())
+        .await
+    {
+        sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+    }
     if total_usage_tokens >= auto_compact_limit {
         run_auto_compact(&sess, &turn_context).await;
     }
 
+    let input = expand_prompt_commands(sess.as_ref(), turn_context.as_ref(), input).await;
+
     let skills_outcome = Some(
         sess.services
             .skills_manager
@@ -3395,8 +4911,33 @@ pub(crate) async fn run_turn(
             .await;
     }
 
-    let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input.clone());
-    let response_item: ResponseItem = initial_input_for_turn.clone().into();
+    for event in request_size::local_image_events(&input, config.image_limits) {
+        sess.send_event(&turn_context, event).await;
+    }
+    let uploaded_files =
+        file_uploads::attach_local_files(sess.as_ref(), turn_context.as_ref(), &input).await;
+    let initial_input_for_turn = ResponseInputItem::from_user_input_with_uploads(
+        input.clone(),
+        config.image_limits,
+        &uploaded_files,
+    );
+    let response_item: ResponseItem = initial_input_for_turn.into();
+
+    // Check the size before recording the input, so an oversized message does
+    // not get stuck in the history and break every later turn.
+    let history = sess.clone_history().await.for_prompt();
+    let base_instructions = sess.get_base_instructions().await;
+    let estimated_bytes = request_size::estimate_request_bytes(
+        history.iter().chain(std::iter::once(&response_item)),
+        &base_instructions.text,
+    );
+    if let Err(err) =
+        request_size::ensure_request_fits(estimated_bytes, config.max_request_body_bytes)
+    {
+        sess.send_event(&turn_context, EventMsg::Error(err.to_error_event(None)))
+            .await;
+        return None;
+    }
     sess.record_user_prompt_and_emit_turn_item(turn_context.as_ref(), &input, response_item)
         .await;
 
@@ -3418,19 +4959,38 @@ pub(crate) async fn run_turn(
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
-        let pending_input = sess
+        let mut pending_input = sess
             .get_pending_input()
             .await
             .into_iter()
             .map(ResponseItem::from)
             .collect::<Vec<ResponseItem>>();
+        // Steers go last so they are the most recent thing the model reads.
+        let steers = sess.take_steers().await;
+        pending_input.extend(steers.iter().map(|text| {
+            ResponseItem::from(ResponseInputItem::from(vec![UserInput::Text {
+                text: text.clone(),
+                text_elements: Vec::new(),
+            }]))
+        }));
 
         // Construct the input that we will send to the model.
         let sampling_request_input: Vec<ResponseItem> = {
             sess.record_conversation_items(&turn_context, &pending_input)
                 .await;
+            workspace_watch::note_external_changes(&sess, &turn_context).await;
             sess.clone_history().await.for_prompt()
         };
+        for text in steers {
+            sess.send_event(
+                &turn_context,
+                EventMsg::SteerDelivered(SteerDeliveredEvent {
+                    text,
+                    deferred: false,
+                }),
+            )
+            .await;
+        }
 
         let sampling_request_input_messages = sampling_request_input
             .iter()
@@ -3470,6 +5030,12 @@ pub(crate) async fn run_turn(
                 }
 
                 if !needs_follow_up {
+                    if turn_context.client.config().retry_stale_patches
+                        && let Some(paths) = sess.take_stale_patch_retry().await
+                    {
+                        stale_patch::start_retry(&sess, &turn_context, paths).await;
+                        continue;
+                    }
                     last_agent_message = sampling_request_last_agent_message;
                     sess.notifier()
                         .notify(&UserNotification::AgentTurnComplete {
@@ -3487,6 +5053,10 @@ pub(crate) async fn run_turn(
                 // Aborted turn is reported via a different event.
                 break;
             }
+            Err(CodexErr::ContentFiltered(message)) => {
+                report_refusal(&sess, &turn_context, Refusal::ContentFilter(message)).await;
+                break;
+            }
             Err(CodexErr::InvalidImageRequest()) => {
                 let mut state = sess.state.lock().await;
                 error_or_panic(
@@ -3499,6 +5069,7 @@ pub(crate) async fn run_turn(
                     message: "Invalid image in your last message. Please remove it and try again."
                         .to_string(),
                     codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    ..Default::default()
                 });
                 sess.send_event(&turn_context, event).await;
                 break;
@@ -3513,6 +5084,36 @@ pub(crate) async fn run_turn(
         }
     }
 
+    let file_provenance = sess.take_file_provenance().await;
+    let mut files_changed = Vec::new();
+    if !file_provenance.is_empty() {
+        let event = file_provenance.into_event();
+        sess.record_files_written(&event.files_written).await;
+        files_changed = event.files_written.clone();
+        sess.send_event(&turn_context, EventMsg::TurnFiles(event))
+            .await;
+    }
+
+    if !cancellation_token.is_cancelled()
+        && let Err(message) = hooks::run_hooks(
+            sess.as_ref(),
+            turn_context.as_ref(),
+            HookPoint::PostTurn,
+            HookDetails {
+                files_changed,
+                last_agent_message: last_agent_message.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+    {
+        let event = EventMsg::Error(ErrorEvent {
+            message,
+            ..Default::default()
+        });
+        sess.send_event(&turn_context, event).await;
+    }
+
     last_agent_message
 }
 
@@ -3622,6 +5223,125 @@ struct SamplingRequestToolSelection<'a> {
     skill_name_counts_lower: &'a HashMap<String, usize>,
 }
 
+/// Narrows the MCP tools offered on a request for `input`: with apps enabled,
+/// only connectors the input mentions (or that were selected explicitly) keep
+/// their tools.
+fn filter_mcp_tools_for_request(
+    turn_context: &TurnContext,
+    mcp_tools: HashMap<String, crate::mcp_connection_manager::ToolInfo>,
+    input: &[ResponseItem],
+    tool_selection: &SamplingRequestToolSelection<'_>,
+) -> HashMap<String, crate::mcp_connection_manager::ToolInfo> {
+    if !turn_context.client.config().features.enabled(Feature::Apps) {
+        return mcp_tools;
+    }
+    let connectors = filter_connectors_for_input(
+        connectors::accessible_connectors_from_mcp_tools(&mcp_tools),
+        input,
+        tool_selection.explicit_app_paths,
+        tool_selection.skill_name_counts_lower,
+    );
+    filter_codex_apps_mcp_tools(mcp_tools, &connectors)
+}
+
+/// Builds the router, and with it the tool specs sent to the model, for a
+/// request that may call `mcp_tools`.
+fn build_tool_router(
+    turn_context: &TurnContext,
+    mcp_tools: HashMap<String, crate::mcp_connection_manager::ToolInfo>,
+) -> ToolRouter {
+    if turn_context.is_ask_turn() {
+        return ToolRouter::without_tools();
+    }
+    ToolRouter::from_config(
+        &turn_context.tools_config,
+        Some(
+            mcp_tools
+                .into_iter()
+                .map(|(name, tool)| (name, tool.tool))
+                .collect(),
+        ),
+        turn_context.dynamic_tools.as_slice(),
+    )
+}
+
+/// Describes the tools the model could call on the next turn, resolved the
+/// same way a sampling request resolves them. The next input is not known
+/// yet, so app connectors count as unmentioned.
+pub(crate) async fn list_tools_for_next_turn(
+    sess: &Session,
+) -> CodexResult<ListToolsResponseEvent> {
+    let turn_context = sess.new_default_turn().await;
+    let mcp_tools = sess
+        .services
+        .mcp_connection_manager
+        .read()
+        .await
+        .list_all_tools()
+        .await;
+    let skill_name_counts_lower = HashMap::new();
+    let tool_selection = SamplingRequestToolSelection {
+        explicit_app_paths: &[],
+        skill_name_counts_lower: &skill_name_counts_lower,
+    };
+    let mcp_tools = filter_mcp_tools_for_request(&turn_context, mcp_tools, &[], &tool_selection);
+    let mcp_servers: HashMap<String, String> = mcp_tools
+        .iter()
+        .map(|(name, tool)| (name.clone(), tool.server_name.clone()))
+        .collect();
+    let specs = build_tool_router(&turn_context, mcp_tools).specs();
+    let specs_json = create_tools_json_for_responses_api(&specs)?;
+    let tools = specs
+        .iter()
+        .zip(specs_json)
+        .map(|(spec, spec_json)| {
+            let name = spec.name().to_string();
+            let source = if let Some(server) = mcp_servers.get(&name) {
+                ToolSource::Mcp {
+                    server: server.clone(),
+                }
+            } else if turn_context
+                .dynamic_tools
+                .iter()
+                .any(|tool| tool.name == name)
+            {
+                ToolSource::Dynamic
+            } else {
+                ToolSource::BuiltIn
+            };
+            ToolDescription {
+                name,
+                source,
+                spec: spec_json,
+            }
+        })
+        .collect();
+    Ok(ListToolsResponseEvent { tools })
+}
+
+/// Returns the approval grants recorded in `rollout_items` since the last
+/// revocation that were made in `cwd`, plus how many were made elsewhere.
+fn approval_grants_to_restore(
+    rollout_items: &[RolloutItem],
+    cwd: &Path,
+) -> (Vec<ApprovalGrantItem>, u32) {
+    let mut grants: Vec<ApprovalGrantItem> = Vec::new();
+    for item in rollout_items {
+        match item {
+            RolloutItem::ApprovalGrant(grant) => {
+                if !grants.contains(grant) {
+                    grants.push(grant.clone());
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::SessionApprovalsRevoked(_)) => grants.clear(),
+            _ => {}
+        }
+    }
+    let (restored, discarded): (Vec<_>, Vec<_>) =
+        grants.into_iter().partition(|grant| grant.cwd == cwd);
+    (restored, u32::try_from(discarded.len()).unwrap_or(u32::MAX))
+}
+
 #[instrument(level = "trace",
     skip_all,
     fields(
@@ -3639,7 +5359,7 @@ async fn run_sampling_request(
     tool_selection: SamplingRequestToolSelection<'_>,
     cancellation_token: CancellationToken,
 ) -> CodexResult<SamplingRequestResult> {
-    let mut mcp_tools = sess
+    let mcp_tools = sess
         .services
         .mcp_connection_manager
         .read()
@@ -3647,48 +5367,45 @@ async fn run_sampling_request(
         .list_all_tools()
         .or_cancel(&cancellation_token)
         .await?;
-    let connectors_for_tools = if turn_context.client.config().features.enabled(Feature::Apps) {
-        let connectors = connectors::accessible_connectors_from_mcp_tools(&mcp_tools);
-        Some(filter_connectors_for_input(
-            connectors,
-            &input,
-            tool_selection.explicit_app_paths,
-            tool_selection.skill_name_counts_lower,
-        ))
-    } else {
-        None
-    };
-    if let Some(connectors) = connectors_for_tools.as_ref() {
-        mcp_tools = filter_codex_apps_mcp_tools(mcp_tools, connectors);
-    }
-    let router = Arc::new(ToolRouter::from_config(
-        &turn_context.tools_config,
-        Some(
-            mcp_tools
-                .into_iter()
-                .map(|(name, tool)| (name, tool.tool))
-                .collect(),
-        ),
-        turn_context.dynamic_tools.as_slice(),
-    ));
+    let mcp_tools = filter_mcp_tools_for_request(&turn_context, mcp_tools, &input, &tool_selection);
+    let router = Arc::new(build_tool_router(&turn_context, mcp_tools));
 
     let model_supports_parallel = turn_context
         .client
         .get_model_info()
         .supports_parallel_tool_calls;
 
-    let base_instructions = sess.get_base_instructions().await;
+    let base_instructions = if turn_context.is_ask_turn() {
+        BaseInstructions {
+            text: crate::ask::ASK_INSTRUCTIONS.to_string(),
+        }
+    } else {
+        sess.get_base_instructions().await
+    };
 
-    let prompt = Prompt {
+    let input = file_uploads::resolve_uploaded_files(&sess, &turn_context, input).await;
+    let mut prompt = Prompt {
         input,
         tools: router.specs(),
-        parallel_tool_calls: model_supports_parallel,
+        parallel_tool_calls: model_supports_parallel && !turn_context.is_ask_turn(),
         base_instructions,
         personality: turn_context.personality,
         output_schema: turn_context.final_output_json_schema.clone(),
+        wrap_untrusted_content: turn_context
+            .client
+            .config()
+            .injection_guard
+            .wrap_tool_output,
+        max_output_tokens: None,
     };
 
+    request_size::ensure_request_fits(
+        request_size::estimate_request_bytes(&prompt.input, &prompt.base_instructions.text),
+        turn_context.client.config().max_request_body_bytes,
+    )?;
+
     let mut retries = 0;
+    let mut resume = StreamResume::default();
     loop {
         let err = match try_run_sampling_request(
             Arc::clone(&router),
@@ -3697,6 +5414,7 @@ async fn run_sampling_request(
             client_session,
             Arc::clone(&turn_diff_tracker),
             &prompt,
+            &mut resume,
             cancellation_token.child_token(),
         )
         .await
@@ -3718,12 +5436,68 @@ async fn run_sampling_request(
             Err(err) => err,
         };
 
-        if !err.is_retryable() {
+        if client_session.try_disable_response_storage(&err) {
+            sess.send_event(
+                &turn_context,
+                EventMsg::Warning(WarningEvent {
+                    message: format!(
+                        "{} does not keep stored responses; resending full context without storage for the rest of this session. Set `disable_response_storage = true` on the provider to skip this check.",
+                        client_session.provider().name
+                    ),
+                }),
+            )
+            .await;
+            continue;
+        }
+
+        if let Some(tier) = turn_context.client.get_service_tier()
+            && client_session.try_use_default_service_tier(&err)
+        {
+            sess.send_event(
+                &turn_context,
+                EventMsg::Warning(WarningEvent {
+                    message: format!(
+                        "{} rejected the `{tier}` service tier; using the default tier for the rest of this session.",
+                        client_session.provider().name
+                    ),
+                }),
+            )
+            .await;
+            continue;
+        }
+
+        let retryable = err.is_retryable();
+        // `RetryLimit` means request-level retries already ran out, so failing
+        // over is the only remaining option for it.
+        if !retryable && !matches!(err, CodexErr::RetryLimit(_)) {
             return Err(err);
         }
 
-        // Use the configured provider-specific stream retry budget.
-        let max_retries = turn_context.client.get_provider().stream_max_retries();
+        // Use the retry budget of the provider currently serving this turn.
+        let max_retries = client_session.provider().stream_max_retries();
+        let failures = retries + 1;
+        let failover_due = !retryable
+            || failures >= client_session.provider().failover_after_failures()
+            || retries >= max_retries;
+        if failover_due {
+            let primary = client_session.provider().name.clone();
+            if let Some(fallback) = client_session.try_switch_fallback_provider() {
+                sess.send_event(
+                    &turn_context,
+                    EventMsg::Warning(WarningEvent {
+                        message: format!(
+                            "Switching from {primary} to fallback provider {fallback} after {failures} failed attempts. {err:#}"
+                        ),
+                    }),
+                )
+                .await;
+                retries = 0;
+                continue;
+            }
+        }
+        if !retryable {
+            return Err(err);
+        }
         if retries >= max_retries && client_session.try_switch_fallback_transport() {
             sess.send_event(
                 &turn_context,
@@ -3757,17 +5531,71 @@ async fn run_sampling_request(
             )
             .await;
 
+            // Items the dropped attempt completed are already in history and
+            // on screen; send them back so the model continues after them
+            // instead of generating them again.
+            prompt.input = sess.clone_history().await.for_prompt();
+
             tokio::time::sleep(delay).await;
         } else {
-            return Err(err);
+            return Err(resume.cut_short(err).with_attempts(retries + 1));
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SamplingRequestResult {
+    needs_follow_up: bool,
+    last_agent_message: Option<String>,
+}
+
+/// What a dropped sampling attempt already streamed, so the retry does not
+/// show it twice.
+///
+/// Completed items are recorded in history and resent with the retry, so only
+/// an assistant message that was cut off mid-stream needs reconciling: the
+/// retried response usually starts with the same text, and the part of it the
+/// user has already seen is dropped.
+#[derive(Debug, Default)]
+struct StreamResume {
+    /// Assistant text shown before the stream dropped that the retried
+    /// response has not repeated yet.
+    partial_message: String,
+    /// Whether any attempt streamed output before failing.
+    shown_output: bool,
+}
+
+impl StreamResume {
+    /// Notes on a stream error that ends the request that output streamed
+    /// before it was kept; the error otherwise reads as if nothing arrived.
+    fn cut_short(&self, err: CodexErr) -> CodexErr {
+        match err {
+            CodexErr::Stream(message, delay) if self.shown_output => CodexErr::Stream(
+                format!("{message} (the response was cut short; the output above is kept)"),
+                delay,
+            ),
+            err => err,
         }
     }
-}
 
-#[derive(Debug)]
-struct SamplingRequestResult {
-    needs_follow_up: bool,
-    last_agent_message: Option<String>,
+    /// Returns the part of `delta` that has not been shown yet, if any.
+    fn dedupe_delta(&mut self, delta: String) -> Option<String> {
+        if self.partial_message.is_empty() {
+            return Some(delta);
+        }
+        if let Some(rest) = self.partial_message.strip_prefix(delta.as_str()) {
+            self.partial_message = rest.to_string();
+            return None;
+        }
+        // Either the delta runs past the shown text, or the retried response
+        // diverged from it; in both cases stop comparing.
+        let delta = match delta.strip_prefix(self.partial_message.as_str()) {
+            Some(rest) => rest.to_string(),
+            None => delta,
+        };
+        self.partial_message.clear();
+        (!delta.is_empty()).then_some(delta)
+    }
 }
 
 /// Ephemeral per-response state for streaming a single proposed plan.
@@ -4145,6 +5973,88 @@ async fn handle_assistant_item_done_in_plan_mode(
     false
 }
 
+/// Tells the user, and the model on its next request, that the last response
+/// stopped early. Partial output has already been recorded.
+async fn report_incomplete_response(
+    sess: &Session,
+    turn_context: &TurnContext,
+    reason: Option<&str>,
+) {
+    if reason == Some("content_filter") {
+        let message = "The response was blocked by the content filter.".to_string();
+        report_refusal(sess, turn_context, Refusal::ContentFilter(message)).await;
+        return;
+    }
+    if reason != Some("max_output_tokens") {
+        let reason = reason.unwrap_or("unknown reason");
+        sess.send_event(
+            turn_context,
+            EventMsg::Warning(WarningEvent {
+                message: format!(
+                    "The model response ended early ({reason}) and may be incomplete."
+                ),
+            }),
+        )
+        .await;
+        return;
+    }
+
+    let max_output_tokens = turn_context.client.get_max_output_tokens();
+    let limit = match max_output_tokens {
+        Some(limit) => format!("the {limit}-token output limit"),
+        None => "the output token limit".to_string(),
+    };
+    let message: ResponseItem = DeveloperInstructions::new(format!(
+        "Your previous response was cut off at {limit} and is incomplete."
+    ))
+    .into();
+    sess.record_conversation_items(turn_context, std::slice::from_ref(&message))
+        .await;
+    sess.send_event(
+        turn_context,
+        EventMsg::OutputTruncated(OutputTruncatedEvent { max_output_tokens }),
+    )
+    .await;
+}
+
+/// Why a model request produced no answer.
+enum Refusal {
+    /// The model declined; carries its refusal text.
+    Model(String),
+    /// The provider's content filter blocked the request or the response.
+    ContentFilter(String),
+}
+
+/// Records the refusal in history, so the model and resumed sessions know why
+/// the turn produced nothing, and reports it in place of an agent message.
+async fn report_refusal(sess: &Session, turn_context: &TurnContext, refusal: Refusal) {
+    let (item, reason): (ResponseItem, String) = match refusal {
+        Refusal::Model(text) => (
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text: text.clone() }],
+                end_turn: None,
+            },
+            text,
+        ),
+        Refusal::ContentFilter(message) => (
+            DeveloperInstructions::new(format!(
+                "Your previous response was blocked by the provider's content filter: {message}"
+            ))
+            .into(),
+            message,
+        ),
+    };
+    sess.record_conversation_items(turn_context, std::slice::from_ref(&item))
+        .await;
+    sess.send_event(
+        turn_context,
+        EventMsg::TurnRefused(TurnRefusedEvent { reason }),
+    )
+    .await;
+}
+
 async fn drain_in_flight(
     in_flight: &mut FuturesOrdered<BoxFuture<'static, CodexResult<ResponseInputItem>>>,
     sess: Arc<Session>,
@@ -4164,6 +6074,9 @@ async fn drain_in_flight(
     Ok(())
 }
 
+/// [`CancelledWork`] key for the model response of the running turn.
+const MODEL_RESPONSE_WORK_KEY: &str = "model_response";
+
 #[allow(clippy::too_many_arguments)]
 #[instrument(level = "trace",
     skip_all,
@@ -4179,6 +6092,7 @@ async fn try_run_sampling_request(
     client_session: &mut ModelClientSession,
     turn_diff_tracker: SharedTurnDiffTracker,
     prompt: &Prompt,
+    resume: &mut StreamResume,
     cancellation_token: CancellationToken,
 ) -> CodexResult<SamplingRequestResult> {
     let collaboration_mode = sess.current_collaboration_mode().await;
@@ -4195,6 +6109,7 @@ async fn try_run_sampling_request(
         developer_instructions: turn_context.developer_instructions.clone(),
         final_output_json_schema: turn_context.final_output_json_schema.clone(),
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        preferred_language: turn_context.preferred_language.clone(),
     });
 
     feedback_tags!(
@@ -4207,11 +6122,17 @@ async fn try_run_sampling_request(
     );
 
     sess.persist_rollout_items(&[rollout_item]).await;
-    let mut stream = client_session
+    sess.start_cancellable_work(MODEL_RESPONSE_WORK_KEY, CancelledWork::ModelResponse)
+        .await;
+    let stream = client_session
         .stream(prompt)
         .instrument(trace_span!("stream_request"))
         .or_cancel(&cancellation_token)
-        .await??;
+        .await;
+    if !matches!(stream, Ok(Ok(_))) {
+        sess.finish_cancellable_work(MODEL_RESPONSE_WORK_KEY).await;
+    }
+    let mut stream = stream??;
 
     let tool_runtime = ToolCallRuntime::new(
         Arc::clone(&router),
@@ -4224,6 +6145,8 @@ async fn try_run_sampling_request(
     let mut needs_follow_up = false;
     let mut last_agent_message: Option<String> = None;
     let mut active_item: Option<TurnItem> = None;
+    // Text of the assistant message currently streaming, for `resume`.
+    let mut streamed_message = String::new();
     let mut should_emit_turn_diff = false;
     let plan_mode = turn_context.collaboration_mode_kind == ModeKind::Plan;
     let mut plan_mode_state = plan_mode.then(|| PlanModeStreamState::new(&turn_context.sub_id));
@@ -4248,7 +6171,10 @@ async fn try_run_sampling_request(
         };
 
         let event = match event {
-            Some(res) => res?,
+            Some(Ok(event)) => event,
+            // Let in-flight tool calls finish so their output is recorded
+            // before a retry resends history.
+            Some(Err(err)) => break Err(err),
             None => {
                 break Err(CodexErr::Stream(
                     "stream closed before response.completed".into(),
@@ -4264,6 +6190,11 @@ async fn try_run_sampling_request(
         match event {
             ResponseEvent::Created => {}
             ResponseEvent::OutputItemDone(item) => {
+                resume.shown_output = true;
+                streamed_message.clear();
+                if matches!(&item, ResponseItem::Message { role, .. } if role == "assistant") {
+                    resume.partial_message.clear();
+                }
                 let previously_active_item = active_item.take();
                 if let Some(state) = plan_mode_state.as_mut() {
                     if let Some(previous) = previously_active_item.as_ref() {
@@ -4311,6 +6242,7 @@ async fn try_run_sampling_request(
                 needs_follow_up |= output_result.needs_follow_up;
             }
             ResponseEvent::OutputItemAdded(item) => {
+                streamed_message.clear();
                 if let Some(turn_item) = handle_non_tool_response_item(&item, plan_mode).await {
                     if let Some(state) = plan_mode_state.as_mut()
                         && matches!(turn_item, TurnItem::AgentMessage(_))
@@ -4328,6 +6260,10 @@ async fn try_run_sampling_request(
             ResponseEvent::ServerReasoningIncluded(included) => {
                 sess.set_server_reasoning_included(included).await;
             }
+            ResponseEvent::Refusal(text) => {
+                streamed_message.clear();
+                report_refusal(&sess, &turn_context, Refusal::Model(text)).await;
+            }
             ResponseEvent::RateLimits(snapshot) => {
                 // Update internal state with latest rate limits, but defer sending until
                 // token usage is available to avoid duplicate TokenCount events.
@@ -4341,6 +6277,12 @@ async fn try_run_sampling_request(
                     .refresh_if_new_etag(etag, &config)
                     .await;
             }
+            ResponseEvent::ServiceTier(served) => {
+                sess.record_served_service_tier(&turn_context, served).await;
+            }
+            ResponseEvent::SystemFingerprint(fingerprint) => {
+                sess.record_system_fingerprint(fingerprint).await;
+            }
             ResponseEvent::Completed {
                 response_id: _,
                 token_usage,
@@ -4359,7 +6301,28 @@ async fn try_run_sampling_request(
                     last_agent_message,
                 });
             }
+            ResponseEvent::Incomplete {
+                response_id: _,
+                token_usage,
+                reason,
+            } => {
+                if let Some(state) = plan_mode_state.as_mut() {
+                    flush_proposed_plan_segments_all(&sess, &turn_context, state).await;
+                }
+                sess.update_token_usage_info(&turn_context, token_usage.as_ref())
+                    .await;
+                should_emit_turn_diff = true;
+                report_incomplete_response(&sess, &turn_context, reason.as_deref()).await;
+
+                needs_follow_up |= sess.has_pending_input().await;
+
+                break Ok(SamplingRequestResult {
+                    needs_follow_up,
+                    last_agent_message,
+                });
+            }
             ResponseEvent::OutputTextDelta(delta) => {
+                resume.shown_output = true;
                 // In review child threads, suppress assistant text deltas; the
                 // UI will show a selection popup from the final ReviewOutput.
                 if let Some(active) = active_item.as_ref() {
@@ -4373,6 +6336,10 @@ async fn try_run_sampling_request(
                             .parse(&delta);
                         handle_plan_segments(&sess, &turn_context, state, &item_id, segments).await;
                     } else {
+                        streamed_message.push_str(&delta);
+                        let Some(delta) = resume.dedupe_delta(delta) else {
+                            continue;
+                        };
                         let event = AgentMessageContentDeltaEvent {
                             thread_id: sess.conversation_id.to_string(),
                             turn_id: turn_context.sub_id.clone(),
@@ -4437,6 +6404,18 @@ async fn try_run_sampling_request(
         }
     };
 
+    // Dropping the stream closes the connection, so an interrupted response
+    // stops generating instead of streaming to completion in the background.
+    drop(stream);
+    sess.finish_cancellable_work(MODEL_RESPONSE_WORK_KEY).await;
+
+    if outcome.is_err() {
+        // Whatever the retry has not repeated yet is still on screen after
+        // the text this attempt streamed.
+        streamed_message.push_str(&resume.partial_message);
+        resume.partial_message = streamed_message;
+    }
+
     drain_in_flight(&mut in_flight, sess.clone(), turn_context.clone()).await?;
 
     if should_emit_turn_diff {
@@ -4485,6 +6464,8 @@ mod tests {
     use super::*;
     use crate::CodexAuth;
     use crate::config::ConfigBuilder;
+    use crate::config::ProjectConfig;
+    use crate::config::WorkspaceRoot;
     use crate::config::test_config;
     use crate::exec::ExecToolCallOutput;
     use crate::function_tool::FunctionCallError;
@@ -4492,6 +6473,8 @@ mod tests {
     use crate::tools::format_exec_output_str;
 
     use codex_protocol::ThreadId;
+    use codex_protocol::config_types::SamplingParameters;
+    use codex_protocol::config_types::TrustLevel;
     use codex_protocol::models::FunctionCallOutputPayload;
 
     use crate::protocol::CompactedItem;
@@ -4500,6 +6483,8 @@ mod tests {
     use crate::protocol::RateLimitSnapshot;
     use crate::protocol::RateLimitWindow;
     use crate::protocol::ResumedHistory;
+    use crate::protocol::RolloutLine;
+    use crate::protocol::SessionApprovalsRevokedEvent;
     use crate::protocol::TokenCountEvent;
     use crate::protocol::TokenUsage;
     use crate::protocol::TokenUsageInfo;
@@ -4626,6 +6611,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn stream_resume_drops_text_already_shown() {
+        let mut resume = StreamResume {
+            partial_message: "Hello, wor".to_string(),
+            shown_output: true,
+        };
+
+        let emitted: Vec<Option<String>> = ["Hello, ", "world", "!"]
+            .into_iter()
+            .map(|delta| resume.dedupe_delta(delta.to_string()))
+            .collect();
+
+        assert_eq!(
+            emitted,
+            vec![None, Some("ld".to_string()), Some("!".to_string())]
+        );
+        assert_eq!(resume.partial_message, "");
+    }
+
+    #[test]
+    fn stream_resume_stops_comparing_when_the_retry_diverges() {
+        let mut resume = StreamResume {
+            partial_message: "Hello, wor".to_string(),
+            shown_output: true,
+        };
+
+        assert_eq!(
+            resume.dedupe_delta("Hi there".to_string()),
+            Some("Hi there".to_string())
+        );
+        assert_eq!(
+            resume.dedupe_delta("Hello".to_string()),
+            Some("Hello".to_string())
+        );
+    }
+
+    #[test]
+    fn stream_errors_after_output_say_the_response_was_cut_short() {
+        let overloaded = || CodexErr::Stream("Overloaded (code: overloaded_error)".into(), None);
+
+        let err = StreamResume::default().cut_short(overloaded());
+        assert_eq!(
+            err.to_string(),
+            "stream disconnected before completion: Overloaded (code: overloaded_error)"
+        );
+
+        let resume = StreamResume {
+            partial_message: String::new(),
+            shown_output: true,
+        };
+        assert_eq!(
+            resume.cut_short(overloaded()).to_string(),
+            "stream disconnected before completion: Overloaded (code: overloaded_error) (the response was cut short; the output above is kept)"
+        );
+        assert!(matches!(
+            resume.cut_short(CodexErr::ContextWindowExceeded),
+            CodexErr::ContextWindowExceeded
+        ));
+    }
+
     #[test]
     fn filter_connectors_for_input_skips_duplicate_slug_mentions() {
         let connectors = vec![
@@ -4795,6 +6840,106 @@ mod tests {
         assert_eq!(actual, Some(info2));
     }
 
+    #[tokio::test]
+    async fn record_initial_history_restores_approval_grants_from_rollout() {
+        let (session, turn_context, rx) = make_session_and_context_with_rx().await;
+        let revoked_path = turn_context.cwd.join("revoked.rs");
+        let kept_path = turn_context.cwd.join("kept.rs");
+        let grant = |cwd: &Path, path: &Path| {
+            RolloutItem::ApprovalGrant(ApprovalGrantItem {
+                tool: "apply_patch".to_string(),
+                cwd: cwd.to_path_buf(),
+                key: serde_json::to_string(path).expect("serialize key"),
+            })
+        };
+        let other_cwd = turn_context.cwd.join("other-checkout");
+        let recorded = vec![
+            grant(&turn_context.cwd, &revoked_path),
+            RolloutItem::EventMsg(EventMsg::SessionApprovalsRevoked(
+                SessionApprovalsRevokedEvent { revoked: 1 },
+            )),
+            grant(&turn_context.cwd, &kept_path),
+            grant(&other_cwd, &other_cwd.join("kept.rs")),
+        ];
+
+        // Round-trip through the on-disk rollout line format.
+        let history = recorded
+            .into_iter()
+            .map(|item| {
+                let line = serde_json::to_string(&RolloutLine {
+                    timestamp: "2025-01-01T00:00:00.000Z".to_string(),
+                    item,
+                })
+                .expect("serialize rollout line");
+                serde_json::from_str::<RolloutLine>(&line)
+                    .expect("parse rollout line")
+                    .item
+            })
+            .collect();
+
+        session
+            .record_initial_history(InitialHistory::Resumed(ResumedHistory {
+                conversation_id: ThreadId::default(),
+                history,
+                rollout_path: PathBuf::from("/tmp/resume.jsonl"),
+            }))
+            .await;
+
+        {
+            let store = session.services.tool_approvals.lock().await;
+            assert_eq!(
+                (store.get(&kept_path), store.get(&revoked_path)),
+                (Some(ReviewDecision::ApprovedForSession), None)
+            );
+        }
+        let restored = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|event| match event.msg {
+                EventMsg::ApprovalGrantsRestored(event) => Some(event),
+                _ => None,
+            })
+            .expect("restored grants event");
+        assert_eq!(
+            restored,
+            ApprovalGrantsRestoredEvent {
+                grants: vec![ApprovalGrantItem {
+                    tool: "apply_patch".to_string(),
+                    cwd: turn_context.cwd.clone(),
+                    key: serde_json::to_string(&kept_path).expect("serialize key"),
+                }],
+                discarded: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn revoke_session_approvals_clears_store() {
+        let (session, _turn_context, rx) = make_session_and_context_with_rx().await;
+        {
+            let mut store = session.services.tool_approvals.lock().await;
+            store.put("src/lib.rs", ReviewDecision::ApprovedForSession);
+            store.put("src/main.rs", ReviewDecision::ApprovedForSession);
+        }
+
+        handlers::revoke_session_approvals(&session, "sub-1".to_string()).await;
+
+        assert_eq!(
+            session
+                .services
+                .tool_approvals
+                .lock()
+                .await
+                .get(&"src/lib.rs"),
+            None
+        );
+        let revoked = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|event| match event.msg {
+                EventMsg::SessionApprovalsRevoked(event) => Some(event),
+                _ => None,
+            })
+            .expect("revoked event");
+        assert_eq!(revoked, SessionApprovalsRevokedEvent { revoked: 2 });
+    }
+
     #[tokio::test]
     async fn record_initial_history_reconstructs_forked_transcript() {
         let (session, turn_context) = make_session_and_context().await;
@@ -4897,6 +7042,96 @@ mod tests {
         assert_eq!(initial_context, history.raw_items());
     }
 
+    #[tokio::test]
+    async fn approval_timeout_resolves_only_unanswered_requests() {
+        let (sess, tc, rx) = make_session_and_context_with_rx().await;
+        *sess.active_turn.lock().await = Some(crate::state::ActiveTurn::default());
+        let timeout = Some(ApprovalTimeout {
+            duration: Duration::from_secs(1),
+            action: ApprovalTimeoutAction::Abort,
+        });
+        async fn insert_pending(sess: &Session, sub_id: &str) -> oneshot::Receiver<ReviewDecision> {
+            let (tx_approve, rx_approve) = oneshot::channel();
+            let mut active = sess.active_turn.lock().await;
+            let turn = active.as_mut().expect("active turn");
+            turn.turn_state
+                .lock()
+                .await
+                .insert_pending_approval(sub_id.to_string(), tx_approve);
+            rx_approve
+        }
+
+        let rx_approve = insert_pending(&sess, &tc.sub_id).await;
+        sess.notify_approval(&tc.sub_id, ReviewDecision::Approved)
+            .await;
+        let answered = sess
+            .wait_for_approval_decision(tc.as_ref(), rx_approve, timeout, "command")
+            .await;
+
+        let rx_approve = insert_pending(&sess, &tc.sub_id).await;
+        let unanswered = sess
+            .wait_for_approval_decision(tc.as_ref(), rx_approve, timeout, "command")
+            .await;
+
+        assert_eq!(
+            (answered, unanswered),
+            (
+                (ReviewDecision::Approved, ApprovalResolver::User),
+                (ReviewDecision::Abort, ApprovalResolver::Timeout)
+            )
+        );
+        match rx.recv().await.expect("timeout warning").msg {
+            EventMsg::Warning(WarningEvent { message }) => assert_eq!(
+                message,
+                "No approval decision within 1s; the command was denied and the turn aborted."
+            ),
+            other => panic!("expected timeout warning, got {other:?}"),
+        }
+        assert!(rx.is_empty());
+    }
+
+    #[tokio::test]
+    async fn approval_resolution_is_recorded_with_redacted_reason() {
+        let (sess, tc, rx) = make_session_and_context_with_rx().await;
+        let (tx_approve, rx_approve) = oneshot::channel();
+        // The turn ended before anyone answered.
+        drop(tx_approve);
+        let resolution = ApprovalResolvedEvent {
+            call_id: "call-1".to_string(),
+            turn_id: tc.sub_id.clone(),
+            kind: ApprovalKind::Exec,
+            command: vec!["curl".to_string(), "example.com".to_string()],
+            paths: Vec::new(),
+            cwd: tc.cwd.clone(),
+            reason: Some("retry with sk-abcdefghijklmnopqrstuvwx".to_string()),
+            decision: ReviewDecision::default(),
+            resolved_by: ApprovalResolver::User,
+            account: None,
+            resolved_at: String::new(),
+        };
+
+        let decision = sess
+            .await_approval_decision(tc.as_ref(), rx_approve, None, "command", resolution)
+            .await;
+
+        assert_eq!(decision, ReviewDecision::Denied);
+        let event = match rx.recv().await.expect("approval resolution").msg {
+            EventMsg::ApprovalResolved(event) => event,
+            other => panic!("expected ApprovalResolved, got {other:?}"),
+        };
+        assert_eq!(event.reason.as_deref(), Some("retry with [REDACTED]"));
+        assert_eq!(event.resolved_by, ApprovalResolver::TurnEnded);
+        assert_eq!(event.decision, ReviewDecision::Denied);
+        assert!(
+            chrono::DateTime::parse_from_rfc3339(&event.resolved_at).is_ok(),
+            "{}",
+            event.resolved_at
+        );
+        assert!(crate::rollout::policy::should_persist_event_msg(
+            &EventMsg::ApprovalResolved(event)
+        ));
+    }
+
     #[tokio::test]
     async fn thread_rollback_fails_when_turn_in_progress() {
         let (sess, tc, rx) = make_session_and_context_with_rx().await;
@@ -4939,6 +7174,165 @@ mod tests {
         assert_eq!(initial_context, history.raw_items());
     }
 
+    #[tokio::test]
+    async fn base_instructions_over_budget_warn_at_startup() {
+        let codex_home = tempfile::tempdir().expect("create temp dir");
+        let mut config = build_test_config(codex_home.path()).await;
+        config.base_instructions = Some("x".repeat(404));
+        config.base_instructions_token_budget = 100;
+
+        let mut events = Vec::new();
+        maybe_push_base_instructions_budget_warning(&config, &mut events);
+        let [
+            Event {
+                msg: EventMsg::Warning(WarningEvent { message }),
+                ..
+            },
+        ] = events.as_slice()
+        else {
+            panic!("expected a single warning, got {events:?}");
+        };
+        assert!(message.starts_with("Configured base instructions are about 101 tokens"));
+
+        config.base_instructions_token_budget = 1_000;
+        let mut events = Vec::new();
+        maybe_push_base_instructions_budget_warning(&config, &mut events);
+        assert!(events.is_empty());
+
+        config.base_instructions = None;
+        config.base_instructions_token_budget = 0;
+        maybe_push_base_instructions_budget_warning(&config, &mut events);
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn workspace_roots_warn_when_missing_untrusted_or_untracked() {
+        let codex_home = tempfile::tempdir().expect("create temp dir");
+        let work = tempfile::tempdir().expect("create temp dir");
+        let repo = work.path().join("repo");
+        let plain = work.path().join("plain");
+        let vendor = work.path().join("vendor");
+        let missing = work.path().join("missing");
+        for dir in [&repo, &plain, &vendor] {
+            std::fs::create_dir_all(dir).expect("create dir");
+        }
+        std::fs::create_dir_all(repo.join(".git")).expect("create .git");
+        let root = |path: &Path, trust_level| WorkspaceRoot {
+            path: AbsolutePathBuf::try_from(path.to_path_buf()).expect("absolute path"),
+            project: ProjectConfig { trust_level },
+        };
+        let mut config = build_test_config(codex_home.path()).await;
+        config.workspace_roots = vec![
+            root(&repo, None),
+            root(&plain, None),
+            root(&vendor, Some(TrustLevel::Untrusted)),
+            root(&missing, None),
+        ];
+
+        let mut events = Vec::new();
+        maybe_push_workspace_root_warnings(&config, &mut events);
+        let messages: Vec<&str> = events
+            .iter()
+            .filter_map(|event| match &event.msg {
+                EventMsg::Warning(WarningEvent { message }) => Some(message.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                format!(
+                    "Workspace root {} is not inside a Git repository; changes made there are not tracked.",
+                    plain.display()
+                ),
+                format!(
+                    "Workspace root {} belongs to an untrusted project; Codex can read it but not write to it.",
+                    vendor.display()
+                ),
+                format!("Workspace root {} is not a directory.", missing.display()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn extra_body_sampling_params_warn_for_reasoning_models() {
+        let codex_home = tempfile::tempdir().expect("create temp dir");
+        let mut config = build_test_config(codex_home.path()).await;
+        config
+            .extra_body
+            .insert("temperature".to_string(), serde_json::json!(0.2));
+        config
+            .extra_body
+            .insert("top_k".to_string(), serde_json::json!(40));
+        let mut model_info = ModelsManager::construct_model_info_offline("gpt-5.1", &config);
+        model_info.supports_reasoning = true;
+
+        let mut events = Vec::new();
+        maybe_push_ignored_extra_body_warning(&config, &model_info, &mut events);
+        let [
+            Event {
+                msg: EventMsg::Warning(WarningEvent { message }),
+                ..
+            },
+        ] = events.as_slice()
+        else {
+            panic!("expected a single warning, got {events:?}");
+        };
+        assert_eq!(
+            message,
+            "extra_body parameters ignored by reasoning model gpt-5.1: temperature."
+        );
+
+        model_info.supports_reasoning = false;
+        let mut events = Vec::new();
+        maybe_push_ignored_extra_body_warning(&config, &model_info, &mut events);
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unsupported_sampling_parameters_warn_once_at_startup() {
+        let codex_home = tempfile::tempdir().expect("create temp dir");
+        let mut config = build_test_config(codex_home.path()).await;
+        config.model_sampling = SamplingParameters {
+            seed: Some(7),
+            temperature: Some(0.2),
+            top_p: None,
+        };
+        config.model_provider.wire_api = WireApi::Responses;
+
+        let warnings = |model_info: &ModelInfo| {
+            let mut events = Vec::new();
+            maybe_push_sampling_parameters_warning(&config, model_info, &mut events);
+            events
+                .into_iter()
+                .filter_map(|event| match event.msg {
+                    EventMsg::Warning(WarningEvent { message }) => Some(message),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let gpt_4o = ModelsManager::construct_model_info_offline("gpt-4o", &config);
+        assert!(!gpt_4o.supports_reasoning);
+        assert_eq!(
+            warnings(&gpt_4o),
+            vec![format!(
+                "Ignoring model_seed: {} uses the Responses API, which has no seed.",
+                config.model_provider.name
+            )]
+        );
+
+        let gpt_5_1 = ModelsManager::construct_model_info_offline("gpt-5.1", &config);
+        assert!(gpt_5_1.supports_reasoning);
+        assert_eq!(
+            warnings(&gpt_5_1),
+            vec![
+                "Ignoring model_seed, model_temperature: reasoning model gpt-5.1 does not accept sampling parameters."
+                    .to_string()
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn set_rate_limits_retains_previous_credits() {
         let codex_home = tempfile::tempdir().expect("create temp dir");
@@ -4976,6 +7370,8 @@ mod tests {
             original_config_do_not_use: Arc::clone(&config),
             session_source: SessionSource::Exec,
             dynamic_tools: Vec::new(),
+            tool_toggles: config.tool_toggles,
+            preferred_language: config.preferred_language.clone(),
         };
 
         let mut state = SessionState::new(session_configuration);
@@ -5059,6 +7455,8 @@ mod tests {
             original_config_do_not_use: Arc::clone(&config),
             session_source: SessionSource::Exec,
             dynamic_tools: Vec::new(),
+            tool_toggles: config.tool_toggles,
+            preferred_language: config.preferred_language.clone(),
         };
 
         let mut state = SessionState::new(session_configuration);
@@ -5143,7 +7541,11 @@ mod tests {
         };
         let (_, turn_context) = make_session_and_context().await;
 
-        let out = format_exec_output_str(&exec, turn_context.truncation_policy);
+        let out = format_exec_output_str(
+            &exec,
+            turn_context.truncation_policy,
+            turn_context.tool_output_max_lines,
+        );
 
         assert_eq!(
             out,
@@ -5326,6 +7728,8 @@ mod tests {
             original_config_do_not_use: Arc::clone(&config),
             session_source: SessionSource::Exec,
             dynamic_tools: Vec::new(),
+            tool_toggles: config.tool_toggles,
+            preferred_language: config.preferred_language.clone(),
         };
         let per_turn_config = Session::build_per_turn_config(&session_configuration);
         let model_info = ModelsManager::construct_model_info_offline(
@@ -5360,6 +7764,11 @@ mod tests {
             agent_control,
             state_db: None,
             transport_manager: TransportManager::new(),
+            injection_scanner: None,
+            file_uploads: Mutex::new(FileUploads::default()),
+            command_explanations: Mutex::new(CommandExplanations::default()),
+            workspace_summaries: Mutex::new(WorkspaceSummaries::default()),
+            workspace_watcher: None,
         };
 
         let turn_context = Session::make_turn_context(
@@ -5442,6 +7851,8 @@ mod tests {
             original_config_do_not_use: Arc::clone(&config),
             session_source: SessionSource::Exec,
             dynamic_tools: Vec::new(),
+            tool_toggles: config.tool_toggles,
+            preferred_language: config.preferred_language.clone(),
         };
         let per_turn_config = Session::build_per_turn_config(&session_configuration);
         let model_info = ModelsManager::construct_model_info_offline(
@@ -5476,6 +7887,11 @@ mod tests {
             agent_control,
             state_db: None,
             transport_manager: TransportManager::new(),
+            injection_scanner: None,
+            file_uploads: Mutex::new(FileUploads::default()),
+            command_explanations: Mutex::new(CommandExplanations::default()),
+            workspace_summaries: Mutex::new(WorkspaceSummaries::default()),
+            workspace_watcher: None,
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -5750,6 +8166,43 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn list_tools_for_next_turn_describes_request_tools() {
+        let (session, _turn_context, _rx) = make_session_and_context_with_rx().await;
+
+        let listed = list_tools_for_next_turn(&session)
+            .await
+            .expect("list tools");
+
+        let turn_context = session.new_default_turn().await;
+        let mcp_tools = session
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .list_all_tools()
+            .await;
+        let expected = create_tools_json_for_responses_api(
+            &build_tool_router(&turn_context, mcp_tools).specs(),
+        )
+        .expect("tools json");
+        assert_eq!(
+            listed
+                .tools
+                .iter()
+                .map(|tool| tool.spec.clone())
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert!(!listed.tools.is_empty());
+        assert!(
+            listed
+                .tools
+                .iter()
+                .all(|tool| tool.source == ToolSource::BuiltIn)
+        );
+    }
+
     #[tokio::test]
     async fn fatal_tool_error_stops_turn_and_reports_error() {
         let (session, turn_context, _rx) = make_session_and_context_with_rx().await;
@@ -5916,6 +8369,7 @@ mod tests {
     #[tokio::test]
     async fn rejects_escalated_permissions_when_policy_not_on_request() {
         use crate::exec::ExecParams;
+        use crate::exec_limits::ExecLimits;
         use crate::protocol::AskForApproval;
         use crate::protocol::SandboxPolicy;
         use crate::sandboxing::SandboxPermissions;
@@ -5951,6 +8405,7 @@ mod tests {
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: Some("test".to_string()),
             arg0: None,
+            limits: ExecLimits::default(),
         };
 
         let params2 = ExecParams {
@@ -5962,6 +8417,7 @@ mod tests {
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: params.justification.clone(),
             arg0: None,
+            limits: ExecLimits::default(),
         };
 
         let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
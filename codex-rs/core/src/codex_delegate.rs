@@ -50,7 +50,7 @@ pub(crate) async fn run_codex_thread_interactive(
     let (tx_ops, rx_ops) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
 
     let CodexSpawnOk { codex, .. } = Codex::spawn(
-        config,
+        Arc::new(config),
         auth_manager,
         models_manager,
         Arc::clone(&parent_session.services.skills_manager),
@@ -92,6 +92,7 @@ pub(crate) async fn run_codex_thread_interactive(
         next_id: AtomicU64::new(0),
         tx_sub: tx_ops,
         rx_event: rx_sub,
+        event_broadcast: Arc::clone(&codex.event_broadcast),
         agent_status: codex.agent_status.clone(),
         session: Arc::clone(&codex.session),
     })
@@ -136,6 +137,7 @@ pub(crate) async fn run_codex_thread_one_shot(
     let (tx_bridge, rx_bridge) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
     let ops_tx = io.tx_sub.clone();
     let agent_status = io.agent_status.clone();
+    let event_broadcast = Arc::clone(&io.event_broadcast);
     let session = Arc::clone(&io.session);
     let io_for_bridge = io;
     tokio::spawn(async move {
@@ -168,6 +170,7 @@ pub(crate) async fn run_codex_thread_one_shot(
         next_id: AtomicU64::new(0),
         rx_event: rx_bridge,
         tx_sub: tx_closed,
+        event_broadcast,
         agent_status,
         session,
     })
@@ -341,17 +344,16 @@ async fn handle_patch_approval(
     event: ApplyPatchApprovalRequestEvent,
     cancel_token: &CancellationToken,
 ) {
-    let decision_rx = parent_session
-        .request_patch_approval(
-            parent_ctx,
-            parent_ctx.sub_id.clone(),
-            event.changes,
-            event.reason,
-            event.grant_root,
-        )
-        .await;
+    let approval_fut = parent_session.request_patch_approval(
+        parent_ctx,
+        parent_ctx.sub_id.clone(),
+        event.changes,
+        event.reason,
+        event.grant_root,
+        event.dirty_paths,
+    );
     let decision = await_approval_with_cancel(
-        async move { decision_rx.await.unwrap_or_default() },
+        approval_fut,
         parent_session,
         &parent_ctx.sub_id,
         cancel_token,
@@ -455,6 +457,7 @@ mod tests {
             next_id: AtomicU64::new(0),
             tx_sub,
             rx_event: rx_events,
+            event_broadcast: Arc::new(tokio::sync::broadcast::channel(1).0),
             agent_status,
             session: Arc::clone(&session),
         });
@@ -465,6 +468,7 @@ mod tests {
                 id: "full".to_string(),
                 msg: EventMsg::TurnAborted(TurnAbortedEvent {
                     reason: TurnAbortReason::Interrupted,
+                    cancelled: Vec::new(),
                 }),
             })
             .await
@@ -1,7 +1,9 @@
 use crate::agent::AgentStatus;
 use crate::codex::Codex;
 use crate::error::Result as CodexResult;
+use crate::event_subscription::EventSubscription;
 use crate::protocol::Event;
+use crate::protocol::ListToolsResponseEvent;
 use crate::protocol::Op;
 use crate::protocol::Submission;
 use codex_protocol::config_types::Personality;
@@ -9,7 +11,11 @@ use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::ToolToggles;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 use tokio::sync::watch;
 
 use crate::state_db::StateDbHandle;
@@ -24,11 +30,14 @@ pub struct ThreadConfigSnapshot {
     pub reasoning_effort: Option<ReasoningEffort>,
     pub personality: Option<Personality>,
     pub session_source: SessionSource,
+    pub tool_toggles: ToolToggles,
 }
 
 pub struct CodexThread {
     codex: Codex,
     rollout_path: Option<PathBuf>,
+    /// When an op was last submitted or an event last delivered.
+    last_activity: Mutex<Instant>,
 }
 
 /// Conduit for the bidirectional stream of messages that compose a thread
@@ -38,20 +47,46 @@ impl CodexThread {
         Self {
             codex,
             rollout_path,
+            last_activity: Mutex::new(Instant::now()),
         }
     }
 
     pub async fn submit(&self, op: Op) -> CodexResult<String> {
+        self.touch();
         self.codex.submit(op).await
     }
 
     /// Use sparingly: this is intended to be removed soon.
     pub async fn submit_with_id(&self, sub: Submission) -> CodexResult<()> {
+        self.touch();
         self.codex.submit_with_id(sub).await
     }
 
     pub async fn next_event(&self) -> CodexResult<Event> {
-        self.codex.next_event().await
+        let event = self.codex.next_event().await;
+        self.touch();
+        event
+    }
+
+    /// How long it has been since an op was submitted to this thread or one
+    /// of its events was delivered.
+    pub fn idle_for(&self) -> Duration {
+        self.lock_last_activity().elapsed()
+    }
+
+    fn touch(&self) {
+        *self.lock_last_activity() = Instant::now();
+    }
+
+    fn lock_last_activity(&self) -> std::sync::MutexGuard<'_, Instant> {
+        self.last_activity
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Subscribe to this thread's events alongside the primary `next_event` consumer.
+    pub fn subscribe(&self) -> EventSubscription {
+        self.codex.subscribe()
     }
 
     pub async fn agent_status(&self) -> AgentStatus {
@@ -73,4 +108,9 @@ impl CodexThread {
     pub async fn config_snapshot(&self) -> ThreadConfigSnapshot {
         self.codex.thread_config_snapshot().await
     }
+
+    /// Tools the model could call on the next turn, as they would be sent.
+    pub async fn list_tools(&self) -> CodexResult<ListToolsResponseEvent> {
+        self.codex.list_tools().await
+    }
 }
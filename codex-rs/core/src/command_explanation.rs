@@ -0,0 +1,258 @@
+//! Plain-English explanations of commands awaiting approval.
+//!
+//! `Op::ExplainCommand` asks the model, without tools and with output capped
+//! at [`MAX_EXPLANATION_TOKENS`], what a command does and what could go
+//! wrong. The call runs beside the turn rather than as a session task, so the
+//! pending approval keeps waiting, and its usage is counted with the turn's.
+//! Explanations are reused for the same command and directory for the rest
+//! of the session. A call still running when its approval is decided or the
+//! turn ends is cancelled.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_async_utils::OrCancelExt;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::prelude::*;
+use tokio_util::sync::CancellationToken;
+
+use crate::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::compact::content_items_to_text;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::parse_command::shlex_join;
+use crate::preferred_language::with_preferred_language;
+use crate::protocol::CommandExplanationEvent;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+
+const EXPLAIN_INSTRUCTIONS: &str = include_str!("../templates/explain/instructions.md");
+
+/// Output budget for one explanation, reasoning included.
+const MAX_EXPLANATION_TOKENS: i64 = 1_024;
+
+#[derive(Default)]
+pub(crate) struct CommandExplanations {
+    /// Explanations by command and working directory.
+    cache: HashMap<(Vec<String>, PathBuf), String>,
+    /// Calls in flight by approval call id, with the turn they belong to.
+    in_flight: HashMap<String, (String, CancellationToken)>,
+}
+
+impl CommandExplanations {
+    /// Cancels the calls explaining approvals of the turn `sub_id`.
+    pub(crate) fn cancel_for_turn(&mut self, sub_id: &str) {
+        self.in_flight.retain(|_, (turn, token)| {
+            if turn == sub_id {
+                token.cancel();
+            }
+            turn != sub_id
+        });
+    }
+}
+
+/// Replies to `Op::ExplainCommand` from the cache, or starts a model call
+/// for the turn that is waiting on the approval.
+pub(crate) async fn explain_command(
+    sess: &Arc<Session>,
+    sub_id: String,
+    call_id: String,
+    command: Vec<String>,
+    cwd: PathBuf,
+) {
+    let running = {
+        let active = sess.active_turn.lock().await;
+        active.as_ref().and_then(|turn| {
+            turn.tasks.values().next().map(|task| {
+                (
+                    Arc::clone(&task.turn_context),
+                    task.cancellation_token.child_token(),
+                )
+            })
+        })
+    };
+    let key = (command, cwd);
+    let mut explanations = sess.services.command_explanations.lock().await;
+    if explanations.in_flight.contains_key(&call_id) {
+        return;
+    }
+    if let Some(explanation) = explanations.cache.get(&key).cloned() {
+        drop(explanations);
+        send_explanation(sess, sub_id, call_id, Ok(explanation)).await;
+        return;
+    }
+    let Some((turn_context, cancellation_token)) = running else {
+        drop(explanations);
+        let error = "the command is no longer awaiting approval".to_string();
+        send_explanation(sess, sub_id, call_id, Err(error)).await;
+        return;
+    };
+    explanations.in_flight.insert(
+        call_id.clone(),
+        (turn_context.sub_id.clone(), cancellation_token.clone()),
+    );
+    drop(explanations);
+
+    let sess = Arc::clone(sess);
+    tokio::spawn(async move {
+        let (command, cwd) = &key;
+        let result = request_explanation(&sess, &turn_context, command, cwd)
+            .or_cancel(&cancellation_token)
+            .await;
+        let mut explanations = sess.services.command_explanations.lock().await;
+        explanations.in_flight.remove(&call_id);
+        let Ok(result) = result else {
+            return;
+        };
+        if let Ok(explanation) = &result {
+            explanations.cache.insert(key.clone(), explanation.clone());
+        }
+        drop(explanations);
+        let result = result.map_err(|err| err.to_string());
+        send_explanation(&sess, turn_context.sub_id.clone(), call_id, result).await;
+    });
+}
+
+async fn send_explanation(
+    sess: &Session,
+    sub_id: String,
+    call_id: String,
+    result: Result<String, String>,
+) {
+    let (explanation, error) = match result {
+        Ok(explanation) => (Some(explanation), None),
+        Err(error) => (None, Some(error)),
+    };
+    sess.send_event_raw(Event {
+        id: sub_id,
+        msg: EventMsg::CommandExplanation(CommandExplanationEvent {
+            call_id,
+            explanation,
+            error,
+        }),
+    })
+    .await;
+}
+
+async fn request_explanation(
+    sess: &Session,
+    turn_context: &TurnContext,
+    command: &[String],
+    cwd: &Path,
+) -> CodexResult<String> {
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: explanation_request(command, cwd),
+            }],
+            end_turn: None,
+        }],
+        base_instructions: BaseInstructions {
+            text: with_preferred_language(
+                EXPLAIN_INSTRUCTIONS,
+                turn_context.preferred_language.as_deref(),
+            ),
+        },
+        max_output_tokens: Some(MAX_EXPLANATION_TOKENS),
+        ..Default::default()
+    };
+
+    let mut client_session = turn_context.client.new_session();
+    let mut stream = client_session.stream(&prompt).await?;
+    let mut reply = String::new();
+    loop {
+        let Some(event) = stream.next().await else {
+            return Err(CodexErr::Stream(
+                "stream closed before response.completed".into(),
+                None,
+            ));
+        };
+        match event? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. })
+                if role == "assistant" =>
+            {
+                if let Some(part) = content_items_to_text(&content) {
+                    reply.push_str(&part);
+                }
+            }
+            ResponseEvent::RateLimits(snapshot) => {
+                sess.update_rate_limits(turn_context, snapshot).await;
+            }
+            ResponseEvent::Completed { token_usage, .. }
+            | ResponseEvent::Incomplete { token_usage, .. } => {
+                sess.update_token_usage_info(turn_context, token_usage.as_ref())
+                    .await;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let explanation = reply.split_whitespace().collect::<Vec<_>>().join(" ");
+    if explanation.is_empty() {
+        return Err(CodexErr::Stream(
+            "the model returned no explanation".into(),
+            None,
+        ));
+    }
+    Ok(explanation)
+}
+
+fn explanation_request(command: &[String], cwd: &Path) -> String {
+    format!(
+        "Working directory: {}\n\nCommand:\n{}",
+        cwd.display(),
+        shlex_join(command)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cancel_for_turn_only_cancels_that_turns_calls() {
+        let first = CancellationToken::new();
+        let other = CancellationToken::new();
+        let mut explanations = CommandExplanations::default();
+        explanations
+            .in_flight
+            .insert("call-1".to_string(), ("turn-1".to_string(), first.clone()));
+        explanations
+            .in_flight
+            .insert("call-2".to_string(), ("turn-2".to_string(), other.clone()));
+
+        explanations.cancel_for_turn("turn-1");
+
+        assert!(first.is_cancelled());
+        assert!(!other.is_cancelled());
+        assert_eq!(
+            explanations.in_flight.keys().collect::<Vec<_>>(),
+            vec!["call-2"]
+        );
+    }
+
+    #[test]
+    fn request_quotes_the_exact_command() {
+        let command = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "rm -rf build && make".to_string(),
+        ];
+
+        assert_eq!(
+            explanation_request(&command, Path::new("/repo")),
+            "Working directory: /repo\n\nCommand:\nbash -lc 'rm -rf build && make'"
+        );
+    }
+}
@@ -1,197 +1,482 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
 use crate::bash::parse_shell_lc_plain_commands;
 use crate::command_safety::windows_safe_commands::is_safe_command_windows;
+use crate::config::types::SafeCommandToml;
+
+/// How deeply `bash -c "bash -c '...'"` wrappers are unwrapped before giving up.
+const MAX_SHELL_NESTING: usize = 3;
+
+/// A program that only reads, with the arguments that would make it do more.
+struct ReadOnlyProgram {
+    program: &'static str,
+    /// When non-empty, the first argument must be one of these.
+    subcommands: &'static [&'static str],
+    /// Options that make an invocation unsafe. See [`option_matches`].
+    deny_options: &'static [&'static str],
+    check: Option<ArgumentCheck>,
+}
 
-pub fn is_known_safe_command(command: &[String]) -> bool {
-    let command: Vec<String> = command
-        .iter()
-        .map(|s| {
-            if s == "zsh" {
-                "bash".to_string()
-            } else {
-                s.clone()
-            }
-        })
-        .collect();
+const fn program(program: &'static str) -> ReadOnlyProgram {
+    ReadOnlyProgram {
+        program,
+        subcommands: &[],
+        deny_options: &[],
+        check: None,
+    }
+}
 
-    if is_safe_command_windows(&command) {
-        return true;
+#[rustfmt::skip]
+const READ_ONLY_PROGRAMS: &[ReadOnlyProgram] = &[
+    program("cat"),
+    program("cd"),
+    program("cut"),
+    program("echo"),
+    program("expr"),
+    program("false"),
+    program("grep"),
+    program("head"),
+    program("id"),
+    program("jq"),
+    program("ls"),
+    program("nl"),
+    program("paste"),
+    program("pwd"),
+    program("rev"),
+    program("seq"),
+    program("stat"),
+    program("tail"),
+    program("tr"),
+    program("true"),
+    program("uname"),
+    program("uniq"),
+    program("wc"),
+    program("which"),
+    program("whoami"),
+    ReadOnlyProgram {
+        check: Some(ArgumentCheck::AwkWithoutSideEffects),
+        ..program("awk")
+    },
+    ReadOnlyProgram {
+        deny_options: &["-o", "--output"],
+        ..program("base64")
+    },
+    // Rust
+    ReadOnlyProgram {
+        subcommands: &["check"],
+        ..program("cargo")
+    },
+    ReadOnlyProgram {
+        deny_options: &[
+            // Options that execute a command for each match.
+            "-x", "--exec", "-X", "--exec-batch",
+        ],
+        ..program("fd")
+    },
+    ReadOnlyProgram {
+        deny_options: &[
+            // Options that can execute arbitrary commands.
+            "-exec", "-execdir", "-ok", "-okdir",
+            // Option that deletes matching files.
+            "-delete",
+            // Options that write pathnames to a file.
+            "-fls", "-fprint", "-fprint0", "-fprintf",
+        ],
+        ..program("find")
+    },
+    ReadOnlyProgram {
+        subcommands: &["branch", "status", "log", "diff", "show"],
+        deny_options: &[
+            // Writes the output to a file.
+            "--output",
+            // Runs the external diff driver.
+            "--ext-diff",
+        ],
+        check: Some(ArgumentCheck::GitBranchListsOnly),
+        ..program("git")
+    },
+    // Ripgrep
+    ReadOnlyProgram {
+        deny_options: &[
+            // Takes an arbitrary command that is executed for each match.
+            "--pre",
+            // Takes a command that can be used to obtain the local hostname.
+            "--hostname-bin",
+            // Calls out to other decompression tools, so do not auto-approve
+            // out of an abundance of caution.
+            "--search-zip", "-z",
+        ],
+        ..program("rg")
+    },
+    ReadOnlyProgram {
+        check: Some(ArgumentCheck::SedPrintsOnly),
+        ..program("sed")
+    },
+    ReadOnlyProgram {
+        deny_options: &[
+            // Rewrites the input files.
+            "-i", "--inplace", "--in-place",
+            // Writes each result to its own file.
+            "-s", "--split-exp",
+        ],
+        ..program("yq")
+    },
+];
+
+/// GNU coreutils programs that are not available elsewhere under these names.
+const LINUX_READ_ONLY_PROGRAMS: &[ReadOnlyProgram] = &[program("numfmt"), program("tac")];
+
+/// Program-specific checks that a list of options cannot express.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgumentCheck {
+    AwkWithoutSideEffects,
+    GitBranchListsOnly,
+    SedPrintsOnly,
+}
+
+impl ArgumentCheck {
+    fn allows(self, args: &[String]) -> bool {
+        match self {
+            ArgumentCheck::AwkWithoutSideEffects => awk_has_no_side_effects(args),
+            ArgumentCheck::GitBranchListsOnly => match args.split_first() {
+                Some((subcommand, rest)) if subcommand == "branch" => git_branch_lists_only(rest),
+                _ => true,
+            },
+            ArgumentCheck::SedPrintsOnly => sed_prints_only(args),
+        }
     }
+}
 
-    if is_safe_to_call_with_exec(&command) {
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SafeCommandRule {
+    subcommands: Vec<String>,
+    deny_options: Vec<String>,
+    check: Option<ArgumentCheck>,
+}
+
+impl SafeCommandRule {
+    fn allows(&self, args: &[String]) -> bool {
+        if !self.subcommands.is_empty()
+            && !args
+                .first()
+                .is_some_and(|subcommand| self.subcommands.contains(subcommand))
+        {
+            return false;
+        }
+        if args.iter().any(|arg| {
+            self.deny_options
+                .iter()
+                .any(|option| option_matches(option, arg))
+        }) {
+            return false;
+        }
+        self.check.is_none_or(|check| check.allows(args))
+    }
+}
+
+impl From<&ReadOnlyProgram> for SafeCommandRule {
+    fn from(program: &ReadOnlyProgram) -> Self {
+        Self {
+            subcommands: program
+                .subcommands
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            deny_options: program
+                .deny_options
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            check: program.check,
+        }
+    }
+}
+
+/// Whether `arg` is `option`. Long options also match `--option=value`, and
+/// single-letter options also match inside clusters and with an attached
+/// value (`-z` matches `-nz`, `-o` matches `-oout.txt`).
+fn option_matches(option: &str, arg: &str) -> bool {
+    if arg == option {
         return true;
     }
+    if option.starts_with("--") {
+        return arg
+            .strip_prefix(option)
+            .is_some_and(|rest| rest.starts_with('='));
+    }
+    match (option.strip_prefix('-'), arg.strip_prefix('-')) {
+        (Some(letter), Some(cluster)) if letter.len() == 1 && !cluster.starts_with('-') => {
+            cluster.contains(letter)
+        }
+        _ => false,
+    }
+}
+
+/// The programs that run without approval because they only read, plus the
+/// `[safe_commands]` changes from config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafeCommands {
+    rules: HashMap<String, SafeCommandRule>,
+}
 
-    // Support `bash -lc "..."` where the script consists solely of one or
-    // more "plain" commands (only bare words / quoted strings) combined with
-    // a conservative allow‑list of shell operators that themselves do not
-    // introduce side effects ( "&&", "||", ";", and "|" ). If every
-    // individual command in the script is itself a known‑safe command, then
-    // the composite expression is considered safe.
-    if let Some(all_commands) = parse_shell_lc_plain_commands(&command)
-        && !all_commands.is_empty()
-        && all_commands
+impl Default for SafeCommands {
+    fn default() -> Self {
+        let linux_programs = if cfg!(target_os = "linux") {
+            LINUX_READ_ONLY_PROGRAMS
+        } else {
+            &[]
+        };
+        let rules = READ_ONLY_PROGRAMS
             .iter()
-            .all(|cmd| is_safe_to_call_with_exec(cmd))
-    {
-        return true;
+            .chain(linux_programs)
+            .map(|program| (program.program.to_string(), SafeCommandRule::from(program)))
+            .collect();
+        Self { rules }
     }
-    false
 }
 
-fn is_safe_to_call_with_exec(command: &[String]) -> bool {
-    let Some(cmd0) = command.first().map(String::as_str) else {
-        return false;
-    };
+impl SafeCommands {
+    /// The built-in table with `overrides` applied: `enabled = false` removes
+    /// a program, `subcommands` replaces its subcommand list and
+    /// `deny_options` adds to its denied options. Programs that are not
+    /// built in are added.
+    pub fn new(overrides: &BTreeMap<String, SafeCommandToml>) -> Self {
+        let mut commands = Self::default();
+        for (program, toml) in overrides {
+            if toml.enabled == Some(false) {
+                commands.rules.remove(program);
+                continue;
+            }
+            let rule = commands
+                .rules
+                .entry(program.clone())
+                .or_insert_with(|| SafeCommandRule {
+                    subcommands: Vec::new(),
+                    deny_options: Vec::new(),
+                    check: None,
+                });
+            if let Some(subcommands) = &toml.subcommands {
+                rule.subcommands = subcommands.clone();
+            }
+            if let Some(deny_options) = &toml.deny_options {
+                rule.deny_options.extend(deny_options.iter().cloned());
+            }
+        }
+        commands
+    }
 
-    match std::path::Path::new(&cmd0)
-        .file_name()
-        .and_then(|osstr| osstr.to_str())
-    {
-        Some(cmd) if cfg!(target_os = "linux") && matches!(cmd, "numfmt" | "tac") => true,
-
-        #[rustfmt::skip]
-        Some(
-            "cat" |
-            "cd" |
-            "cut" |
-            "echo" |
-            "expr" |
-            "false" |
-            "grep" |
-            "head" |
-            "id" |
-            "ls" |
-            "nl" |
-            "paste" |
-            "pwd" |
-            "rev" |
-            "seq" |
-            "stat" |
-            "tail" |
-            "tr" |
-            "true" |
-            "uname" |
-            "uniq" |
-            "wc" |
-            "which" |
-            "whoami") => {
-            true
-        },
-
-        Some("base64") => {
-            const UNSAFE_BASE64_OPTIONS: &[&str] = &["-o", "--output"];
-
-            !command.iter().skip(1).any(|arg| {
-                UNSAFE_BASE64_OPTIONS.contains(&arg.as_str())
-                    || arg.starts_with("--output=")
-                    || (arg.starts_with("-o") && arg != "-o")
+    pub fn is_known_safe_command(&self, command: &[String]) -> bool {
+        self.is_safe_at_depth(command, 0)
+    }
+
+    fn is_safe_at_depth(&self, command: &[String], depth: usize) -> bool {
+        let command: Vec<String> = command
+            .iter()
+            .map(|s| {
+                if s == "zsh" {
+                    "bash".to_string()
+                } else {
+                    s.clone()
+                }
             })
-        }
+            .collect();
 
-        Some("find") => {
-            // Certain options to `find` can delete files, write to files, or
-            // execute arbitrary commands, so we cannot auto-approve the
-            // invocation of `find` in such cases.
-            #[rustfmt::skip]
-            const UNSAFE_FIND_OPTIONS: &[&str] = &[
-                // Options that can execute arbitrary commands.
-                "-exec", "-execdir", "-ok", "-okdir",
-                // Option that deletes matching files.
-                "-delete",
-                // Options that write pathnames to a file.
-                "-fls", "-fprint", "-fprint0", "-fprintf",
-            ];
-
-            !command
-                .iter()
-                .any(|arg| UNSAFE_FIND_OPTIONS.contains(&arg.as_str()))
+        if is_safe_command_windows(&command) {
+            return true;
         }
 
-        // Ripgrep
-        Some("rg") => {
-            const UNSAFE_RIPGREP_OPTIONS_WITH_ARGS: &[&str] = &[
-                // Takes an arbitrary command that is executed for each match.
-                "--pre",
-                // Takes a command that can be used to obtain the local hostname.
-                "--hostname-bin",
-            ];
-            const UNSAFE_RIPGREP_OPTIONS_WITHOUT_ARGS: &[&str] = &[
-                // Calls out to other decompression tools, so do not auto-approve
-                // out of an abundance of caution.
-                "--search-zip",
-                "-z",
-            ];
-
-            !command.iter().any(|arg| {
-                UNSAFE_RIPGREP_OPTIONS_WITHOUT_ARGS.contains(&arg.as_str())
-                    || UNSAFE_RIPGREP_OPTIONS_WITH_ARGS
-                        .iter()
-                        .any(|&opt| arg == opt || arg.starts_with(&format!("{opt}=")))
-            })
+        if self.is_safe_to_call_with_exec(&command) {
+            return true;
         }
 
-        // Git
-        Some("git") => matches!(
-            command.get(1).map(String::as_str),
-            Some("branch" | "status" | "log" | "diff" | "show")
-        ),
-
-        // Rust
-        Some("cargo") if command.get(1).map(String::as_str) == Some("check") => true,
-
-        // Special-case `sed -n {N|M,N}p`
-        Some("sed")
-            if {
-                command.len() <= 4
-                    && command.get(1).map(String::as_str) == Some("-n")
-                    && is_valid_sed_n_arg(command.get(2).map(String::as_str))
-            } =>
+        // Support `bash -lc "..."` and `bash -c "..."` where the script
+        // consists solely of one or more "plain" commands (only bare words /
+        // quoted strings) combined with a conservative allow‑list of shell
+        // operators that themselves do not introduce side effects ( "&&",
+        // "||", ";", and "|" ). Redirections and substitutions make the
+        // script unparseable here. If every individual command in the script
+        // is itself a known‑safe command (possibly another `bash -c`
+        // wrapper), then the composite expression is considered safe.
+        if depth < MAX_SHELL_NESTING
+            && let Some(all_commands) = parse_shell_lc_plain_commands(&command)
+            && !all_commands.is_empty()
+            && all_commands
+                .iter()
+                .all(|cmd| self.is_safe_at_depth(cmd, depth + 1))
         {
-            true
+            return true;
         }
+        false
+    }
 
-        // ── anything else ─────────────────────────────────────────────────
-        _ => false,
+    fn is_safe_to_call_with_exec(&self, command: &[String]) -> bool {
+        let Some((cmd0, args)) = command.split_first() else {
+            return false;
+        };
+        std::path::Path::new(cmd0)
+            .file_name()
+            .and_then(|osstr| osstr.to_str())
+            .and_then(|program| self.rules.get(program))
+            .is_some_and(|rule| rule.allows(args))
     }
 }
 
-// (bash parsing helpers implemented in crate::bash)
+static BUILT_IN_SAFE_COMMANDS: LazyLock<SafeCommands> = LazyLock::new(SafeCommands::default);
 
-/* ----------------------------------------------------------
-Example
----------------------------------------------------------- */
+/// Whether `command` only reads, judged by the built-in table. Sessions use
+/// [`SafeCommands::new`] to include the `[safe_commands]` config.
+pub fn is_known_safe_command(command: &[String]) -> bool {
+    BUILT_IN_SAFE_COMMANDS.is_known_safe_command(command)
+}
 
-/// Returns true if `arg` matches /^(\d+,)?\d+p$/
-fn is_valid_sed_n_arg(arg: Option<&str>) -> bool {
-    // unwrap or bail
-    let s = match arg {
-        Some(s) => s,
-        None => return false,
-    };
+/// `git branch` without arguments that create, rename, copy or delete
+/// branches. Positional arguments are only patterns under `--list`.
+fn git_branch_lists_only(args: &[String]) -> bool {
+    #[rustfmt::skip]
+    const LISTING_OPTIONS: &[&str] = &[
+        "-a", "--all", "-r", "--remotes", "-v", "-vv", "--verbose", "-l", "--list",
+        "-i", "--ignore-case", "--show-current", "--color", "--no-color", "--column",
+        "--no-column",
+    ];
+    const LISTING_OPTIONS_WITH_VALUE: &[&str] = &[
+        "--sort",
+        "--format",
+        "--contains",
+        "--no-contains",
+        "--merged",
+        "--no-merged",
+        "--points-at",
+        "--color",
+        "--column",
+    ];
+
+    let lists = args.iter().any(|arg| arg == "-l" || arg == "--list");
+    args.iter().all(|arg| {
+        if arg.starts_with('-') {
+            LISTING_OPTIONS.contains(&arg.as_str())
+                || LISTING_OPTIONS_WITH_VALUE
+                    .iter()
+                    .any(|option| option_matches(option, arg) && arg != option)
+        } else {
+            lists
+        }
+    })
+}
 
-    // must end with 'p', strip it
-    let core = match s.strip_suffix('p') {
-        Some(rest) => rest,
-        None => return false,
-    };
+/// `sed -n` whose scripts only print lines, e.g. `sed -n 10,20p` or
+/// `sed -n '/^fn /p'`. In-place editing, script files and any command other
+/// than `p` (notably `w` and GNU `e`) are rejected.
+fn sed_prints_only(args: &[String]) -> bool {
+    #[rustfmt::skip]
+    const READ_ONLY_FLAGS: &[&str] = &[
+        "-E", "-r", "--regexp-extended", "-s", "--separate", "-u", "--unbuffered",
+        "-z", "--null-data",
+    ];
+
+    let mut quiet = false;
+    let mut scripts = Vec::new();
+    let mut operands = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-n" | "--quiet" | "--silent" => quiet = true,
+            "-e" | "--expression" => match iter.next() {
+                Some(script) => scripts.push(script),
+                None => return false,
+            },
+            "--" => operands.extend(iter.by_ref()),
+            flag if READ_ONLY_FLAGS.contains(&flag) => {}
+            // Covers `-i`, `-f`, `--in-place` and flag clusters like `-ni`.
+            flag if flag.starts_with('-') && flag != "-" => return false,
+            _ => operands.push(arg),
+        }
+    }
+    if scripts.is_empty() {
+        let Some(script) = operands.first() else {
+            return false;
+        };
+        scripts.push(script);
+    }
+    quiet
+        && scripts
+            .iter()
+            .all(|script| is_print_only_sed_script(script))
+}
 
-    // split on ',' and ensure 1 or 2 numeric parts
-    let parts: Vec<&str> = core.split(',').collect();
-    match parts.as_slice() {
-        // single number, e.g. "10"
-        [num] => !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()),
-
-        // two numbers, e.g. "1,5"
-        [a, b] => {
-            !a.is_empty()
-                && !b.is_empty()
-                && a.chars().all(|c| c.is_ascii_digit())
-                && b.chars().all(|c| c.is_ascii_digit())
+/// Whether every command in `script` is `[address[,address]]p`, with
+/// addresses that are line numbers, `$` or `/regex/`.
+fn is_print_only_sed_script(script: &str) -> bool {
+    script.split(['\n', ';']).all(|command| {
+        let command = command.trim();
+        if command.is_empty() {
+            return true;
         }
+        let mut rest = command;
+        if let Some(after) = strip_sed_address(rest) {
+            rest = after;
+            if let Some(after) = rest.strip_prefix(',') {
+                let Some(after) = strip_sed_address(after) else {
+                    return false;
+                };
+                rest = after;
+            }
+        }
+        rest == "p"
+    })
+}
 
-        // anything else (more than one comma) is invalid
-        _ => false,
+/// `text` after a leading sed address, if it starts with one.
+fn strip_sed_address(text: &str) -> Option<&str> {
+    if let Some(rest) = text.strip_prefix('$') {
+        return Some(rest);
     }
+    if let Some(regex) = text.strip_prefix('/') {
+        let mut escaped = false;
+        for (index, c) in regex.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '/' => return Some(&regex[index + 1..]),
+                _ => {}
+            }
+        }
+        return None;
+    }
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    (digits > 0).then(|| &text[digits..])
+}
+
+/// `awk` whose program cannot run commands or write files: no `system()`,
+/// pipes, output redirection or gawk `@` directives, and no program file or
+/// gawk extension options.
+fn awk_has_no_side_effects(args: &[String]) -> bool {
+    const UNSAFE_PROGRAM_TEXT: &[&str] = &["system", "|", ">", "@"];
+
+    let mut iter = args.iter();
+    let program = loop {
+        let Some(arg) = iter.next() else {
+            return false;
+        };
+        match arg.as_str() {
+            "--" => break iter.next(),
+            "-F" | "-v" => {
+                if iter.next().is_none() {
+                    return false;
+                }
+            }
+            option if option.starts_with("-F") || option.starts_with("-v") => {}
+            // `-f progfile`, and gawk options that load code or write files.
+            option if option.starts_with('-') => return false,
+            _ => break Some(arg),
+        }
+    };
+    program.is_some_and(|program| {
+        !UNSAFE_PROGRAM_TEXT
+            .iter()
+            .any(|text| program.contains(text))
+    })
 }
 
 #[cfg(test)]
@@ -203,6 +488,19 @@ mod tests {
         args.iter().map(ToString::to_string).collect()
     }
 
+    fn is_safe_to_call_with_exec(command: &[String]) -> bool {
+        SafeCommands::default().is_safe_to_call_with_exec(command)
+    }
+
+    fn assert_unsafe(commands: &[&[&str]]) {
+        for command in commands {
+            assert!(
+                !is_known_safe_command(&vec_str(command)),
+                "expected {command:?} to be unsafe"
+            );
+        }
+    }
+
     #[test]
     fn known_safe_examples() {
         assert!(is_safe_to_call_with_exec(&vec_str(&["ls"])));
@@ -422,4 +720,166 @@ mod tests {
             "> redirection should be rejected"
         );
     }
+
+    #[test]
+    fn read_only_tools_are_safe() {
+        let commands: &[&[&str]] = &[
+            &["jq", ".dependencies", "package.json"],
+            &["yq", ".services", "compose.yaml"],
+            &["fd", "-e", "rs", "src"],
+            &["git", "log", "--oneline", "-n", "5"],
+            &["git", "diff", "--stat", "HEAD~1"],
+            &["git", "show", "HEAD:Cargo.toml"],
+            &["git", "branch", "-a", "-v"],
+            &["git", "branch", "--list", "release/*"],
+            &["sed", "-n", "/^fn /p", "lib.rs"],
+            &["sed", "-n", "-e", "1p", "-e", "$p", "lib.rs"],
+            &["sed", "-E", "-n", "--", "10,/^}/p", "lib.rs"],
+            &["awk", "{print $1}", "access.log"],
+            &[
+                "awk",
+                "-F",
+                ":",
+                "-v",
+                "OFS=,",
+                "{print $1, $7}",
+                "/etc/passwd",
+            ],
+        ];
+        for command in commands {
+            assert!(
+                is_known_safe_command(&vec_str(command)),
+                "expected {command:?} to be safe"
+            );
+        }
+    }
+
+    #[test]
+    fn read_only_tools_with_side_effects_are_unsafe() {
+        assert_unsafe(&[
+            &["rg", "--pre", "rm -rf", "pattern"],
+            &["rg", "-nz", "pattern", "logs.gz"],
+            &["fd", "-x", "rm"],
+            &["fd", "-HX", "rm"],
+            &["fd", "--exec=rm", "-rf"],
+            &["yq", "-i", ".version = \"2\"", "Chart.yaml"],
+            &["yq", "--inplace", ".a = 1", "x.yaml"],
+            &["yq", "-s", ".name", "all.yaml"],
+            &["git", "diff", "--output=/tmp/patch"],
+            &["git", "log", "--ext-diff", "-p"],
+            &["git", "-c", "core.pager=sh -c id", "log"],
+            &["git", "branch", "-D", "main"],
+            &["git", "branch", "new-feature"],
+            &["git", "branch", "--set-upstream-to=origin/main"],
+            &["git", "push"],
+        ]);
+    }
+
+    #[test]
+    fn sed_must_only_print() {
+        assert_unsafe(&[
+            &["sed", "1p", "file.txt"],
+            &["sed", "-n", "-i", "1p", "file.txt"],
+            &["sed", "-ni", "1p", "file.txt"],
+            &["sed", "-n", "--in-place=.bak", "1p", "file.txt"],
+            &["sed", "-n", "1w /tmp/out", "file.txt"],
+            &["sed", "-n", "1e rm -rf /", "file.txt"],
+            &["sed", "-n", "/x/p;s/a/b/w out", "file.txt"],
+            &["sed", "-n", "/unterminated", "file.txt"],
+            &["sed", "-n", "-f", "script.sed", "file.txt"],
+            &["sed", "-n"],
+        ]);
+        assert!(is_known_safe_command(&vec_str(&[
+            "sed",
+            "-n",
+            r"/a\/b/,$p",
+            "file.txt"
+        ])));
+    }
+
+    #[test]
+    fn awk_must_not_run_commands_or_write_files() {
+        assert_unsafe(&[
+            &["awk", "BEGIN {system(\"rm -rf /\")}"],
+            &["awk", "{print > \"/etc/passwd\"}", "file.txt"],
+            &["awk", "{print | \"sh\"}", "file.txt"],
+            &["awk", "BEGIN {\"id\" | getline x; print x}"],
+            &["awk", "-f", "prog.awk", "file.txt"],
+            &["awk", "-l", "filefuncs", "BEGIN {}"],
+            &["awk", "@load \"readdir\"; {print}"],
+            &["awk"],
+        ]);
+    }
+
+    #[test]
+    fn shell_wrappers_are_unwrapped() {
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-c",
+            "git log --oneline | head -n 5"
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "/bin/sh",
+            "-c",
+            "jq .name package.json"
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            "bash -c 'rg -n TODO src'"
+        ])));
+        assert_unsafe(&[
+            &["bash", "-c", "rg --pre 'rm -rf' pattern"],
+            &["bash", "-lc", "bash -c 'rm -rf /'"],
+            &["bash", "-c", "rg pattern > out.txt"],
+            &["bash", "-c", "cat < /etc/shadow | wc -l"],
+            &["bash", "-c", "jq . <<< '{}'"],
+            &["bash", "-c", "cat $(echo /etc/passwd)"],
+            &["bash", "-c", "cat `which rm`"],
+            &["bash", "-c", "echo \"$HOME\""],
+            &["bash", "-c", "ls & rm -rf /"],
+        ]);
+    }
+
+    #[test]
+    fn config_extends_and_overrides_the_table() {
+        let overrides = BTreeMap::from([
+            ("tree".to_string(), SafeCommandToml::default()),
+            (
+                "git".to_string(),
+                SafeCommandToml {
+                    subcommands: Some(vec!["status".to_string()]),
+                    ..Default::default()
+                },
+            ),
+            (
+                "rg".to_string(),
+                SafeCommandToml {
+                    deny_options: Some(vec!["--follow".to_string()]),
+                    ..Default::default()
+                },
+            ),
+            (
+                "cat".to_string(),
+                SafeCommandToml {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let commands = SafeCommands::new(&overrides);
+        let safe = |command: &[&str]| commands.is_known_safe_command(&vec_str(command));
+
+        assert!(safe(&["tree", "-L", "2"]));
+        assert!(safe(&["git", "status"]));
+        assert!(!safe(&["git", "log"]));
+        assert!(safe(&["rg", "TODO"]));
+        assert!(!safe(&["rg", "--follow", "TODO"]));
+        assert!(
+            !safe(&["rg", "--pre", "sh", "TODO"]),
+            "built-in denials stay"
+        );
+        assert!(!safe(&["cat", "Cargo.toml"]));
+        assert!(!safe(&["bash", "-lc", "cat Cargo.toml"]));
+    }
 }
@@ -44,9 +44,8 @@ pub(crate) async fn run_inline_auto_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
 ) {
-    let prompt = turn_context.compact_prompt().to_string();
     let input = vec![UserInput::Text {
-        text: prompt,
+        text: turn_context.compact_prompt(),
         // Compaction prompt is synthesized; no UI element ranges to preserve.
         text_elements: Vec::new(),
     }];
@@ -62,6 +61,7 @@ pub(crate) async fn run_compact_task(
     let start_event = EventMsg::TurnStarted(TurnStartedEvent {
         model_context_window: turn_context.client.get_model_context_window(),
         collaboration_mode_kind: turn_context.collaboration_mode_kind,
+        settings: Some(turn_context.turn_settings()),
     });
     sess.send_event(&turn_context, start_event).await;
     run_compact_task_inner(sess.clone(), turn_context, input).await;
@@ -106,6 +106,7 @@ async fn run_compact_task_inner(
         developer_instructions: turn_context.developer_instructions.clone(),
         final_output_json_schema: turn_context.final_output_json_schema.clone(),
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        preferred_language: turn_context.preferred_language.clone(),
     });
     sess.persist_rollout_items(&[rollout_item]).await;
 
@@ -214,7 +215,7 @@ pub fn content_items_to_text(content: &[ContentItem]) -> Option<String> {
                     pieces.push(text.as_str());
                 }
             }
-            ContentItem::InputImage { .. } => {}
+            ContentItem::InputImage { .. } | ContentItem::InputFile { .. } => {}
         }
     }
     if pieces.is_empty() {
@@ -356,7 +357,10 @@ async fn drain_to_completed(
             Ok(ResponseEvent::RateLimits(snapshot)) => {
                 sess.update_rate_limits(turn_context, snapshot).await;
             }
-            Ok(ResponseEvent::Completed { token_usage, .. }) => {
+            Ok(
+                ResponseEvent::Completed { token_usage, .. }
+                | ResponseEvent::Incomplete { token_usage, .. },
+            ) => {
                 sess.update_token_usage_info(turn_context, token_usage.as_ref())
                     .await;
                 return Ok(());
@@ -423,7 +427,7 @@ mod tests {
                 }],
                 end_turn: None,
             },
-            ResponseItem::Other,
+            ResponseItem::Other(serde_json::json!({"type": "future_item"})),
         ];
 
         let collected = collect_user_messages(&items);
@@ -23,6 +23,7 @@ pub(crate) async fn run_remote_compact_task(sess: Arc<Session>, turn_context: Ar
     let start_event = EventMsg::TurnStarted(TurnStartedEvent {
         model_context_window: turn_context.client.get_model_context_window(),
         collaboration_mode_kind: turn_context.collaboration_mode_kind,
+        settings: Some(turn_context.turn_settings()),
     });
     sess.send_event(&turn_context, start_event).await;
 
@@ -62,6 +63,8 @@ async fn run_remote_compact_task_inner_impl(
         base_instructions: sess.get_base_instructions().await,
         personality: turn_context.personality,
         output_schema: None,
+        wrap_untrusted_content: false,
+        max_output_tokens: None,
     };
 
     let mut new_history = turn_context
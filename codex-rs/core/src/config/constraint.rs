@@ -16,6 +16,17 @@ pub enum ConstraintError {
         requirement_source: RequirementSource,
     },
 
+    #[error(
+        "{field_name} `{candidate}` is not allowed: {rule} limits it to `{floor}` (set by {requirement_source})"
+    )]
+    ExceedsSafetyFloor {
+        field_name: &'static str,
+        candidate: String,
+        floor: String,
+        rule: String,
+        requirement_source: RequirementSource,
+    },
+
     #[error("field `{field_name}` cannot be empty")]
     EmptyField { field_name: String },
 
@@ -125,6 +136,27 @@ impl<T: Send + Sync> Constrained<T> {
         self.value = value;
         Ok(())
     }
+
+    /// Narrow the allowed values: candidates must also pass `validator`. The
+    /// current value is checked against it as well.
+    pub fn and_validator(
+        self,
+        validator: impl Fn(&T) -> ConstraintResult<()> + Send + Sync + 'static,
+    ) -> ConstraintResult<Self>
+    where
+        T: 'static,
+    {
+        validator(&self.value)?;
+        let existing = self.validator;
+        Ok(Self {
+            value: self.value,
+            validator: Arc::new(move |candidate| {
+                existing(candidate)?;
+                validator(candidate)
+            }),
+            normalizer: self.normalizer,
+        })
+    }
 }
 
 impl<T> std::ops::Deref for Constrained<T> {
@@ -218,6 +250,44 @@ mod tests {
         assert_eq!(constrained.value(), 1);
     }
 
+    #[test]
+    fn constrained_and_validator_requires_both_validators() {
+        let positive = Constrained::new(5, |value| {
+            if *value > 0 {
+                Ok(())
+            } else {
+                Err(invalid_value(value.to_string(), "positive values"))
+            }
+        })
+        .expect("initial value should be accepted");
+        let at_most_ten = |value: &i32| {
+            if *value <= 10 {
+                Ok(())
+            } else {
+                Err(invalid_value(value.to_string(), "values up to 10"))
+            }
+        };
+
+        let mut constrained = positive
+            .and_validator(at_most_ten)
+            .expect("initial value should pass both validators");
+        assert_eq!(
+            constrained.set(11),
+            Err(invalid_value("11", "values up to 10"))
+        );
+        assert_eq!(
+            constrained.set(-1),
+            Err(invalid_value("-1", "positive values"))
+        );
+        assert_eq!(constrained.set(7), Ok(()));
+
+        let result = Constrained::allow_any(20).and_validator(at_most_ten);
+        assert_eq!(
+            result.map(|c| c.value()),
+            Err(invalid_value("20", "values up to 10"))
+        );
+    }
+
     #[test]
     fn constrained_can_set_allows_probe_without_setting() {
         let constrained = Constrained::new(1, |value| {
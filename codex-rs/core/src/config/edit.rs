@@ -5,11 +5,19 @@ use crate::path_utils::resolve_symlink_write_paths;
 use crate::path_utils::write_atomically;
 use anyhow::Context;
 use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::SandboxMode;
 use codex_protocol::config_types::TrustLevel;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::protocol::AskForApproval;
+use sha2::Digest;
+use sha2::Sha256;
 use std::collections::BTreeMap;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::fs::TryLockError;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 use tokio::task;
 use toml_edit::ArrayOfTables;
 use toml_edit::DocumentMut;
@@ -57,6 +65,7 @@ pub enum ConfigEdit {
 
 // TODO(jif) move to a dedicated file
 mod document_helpers {
+    use crate::config::types::McpArgumentValidation;
     use crate::config::types::McpServerConfig;
     use crate::config::types::McpServerTransportConfig;
     use toml_edit::Array as TomlArray;
@@ -172,6 +181,14 @@ mod document_helpers {
         {
             entry["scopes"] = array_from_iter(scopes.iter().cloned());
         }
+        if !config.argument_validation.is_strict() {
+            let validation = match config.argument_validation {
+                McpArgumentValidation::Strict => "strict",
+                McpArgumentValidation::Warn => "warn",
+                McpArgumentValidation::Off => "off",
+            };
+            entry["argument_validation"] = value(validation);
+        }
 
         entry
     }
@@ -627,7 +644,29 @@ fn normalize_skill_config_path(path: &Path) -> String {
         .to_string()
 }
 
+/// Lock file under `codex_home` that serializes writers of `config.toml`.
+/// `config.toml` itself is replaced by rename, so it cannot carry the lock.
+const CONFIG_LOCK_FILE: &str = "config.toml.lock";
+const LOCK_MAX_ATTEMPTS: usize = 50;
+const LOCK_RETRY_SLEEP: Duration = Duration::from_millis(100);
+/// Attempts made when `config.toml` changes between our read and our write.
+const WRITE_MAX_ATTEMPTS: usize = 5;
+
+/// Outcome of one read-modify-write pass over `config.toml`.
+#[derive(Debug, PartialEq, Eq)]
+enum WriteOutcome {
+    Written,
+    Unchanged,
+    /// The file changed on disk after it was read; nothing was written.
+    Conflict,
+}
+
 /// Persist edits using a blocking strategy.
+///
+/// Writers inside Codex are serialized by a lock file in `codex_home`. Other
+/// programs editing `config.toml` are detected by re-checking the file just
+/// before the write, in which case the edits are reapplied to the new
+/// contents.
 pub fn apply_blocking(
     codex_home: &Path,
     profile: Option<&str>,
@@ -637,16 +676,74 @@ pub fn apply_blocking(
         return Ok(());
     }
 
+    let _lock = lock_codex_home(codex_home)?;
     let config_path = codex_home.join(CONFIG_TOML_FILE);
-    let write_paths = resolve_symlink_write_paths(&config_path)?;
-    let serialized = match write_paths.read_path {
-        Some(path) => match std::fs::read_to_string(&path) {
-            Ok(contents) => contents,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
-            Err(err) => return Err(err.into()),
+    for _ in 0..WRITE_MAX_ATTEMPTS {
+        match apply_once(&config_path, profile, edits, || {})? {
+            WriteOutcome::Written | WriteOutcome::Unchanged => return Ok(()),
+            WriteOutcome::Conflict => continue,
+        }
+    }
+
+    anyhow::bail!(
+        "{} kept changing while Codex was updating it; try again",
+        config_path.display()
+    )
+}
+
+/// Takes the exclusive `config.toml` write lock, retrying while another
+/// writer holds it. The lock is released when the returned file is dropped.
+fn lock_codex_home(codex_home: &Path) -> anyhow::Result<File> {
+    std::fs::create_dir_all(codex_home)?;
+    let lock_path = codex_home.join(CONFIG_LOCK_FILE);
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)
+        .with_context(|| format!("failed to open {}", lock_path.display()))?;
+    for _ in 0..LOCK_MAX_ATTEMPTS {
+        match file.try_lock() {
+            Ok(()) => return Ok(file),
+            Err(TryLockError::WouldBlock) => std::thread::sleep(LOCK_RETRY_SLEEP),
+            Err(TryLockError::Error(err)) => return Err(err.into()),
+        }
+    }
+
+    anyhow::bail!(
+        "timed out waiting for another process to finish writing {}",
+        codex_home.join(CONFIG_TOML_FILE).display()
+    )
+}
+
+fn read_config(read_path: Option<&Path>) -> std::io::Result<String> {
+    match read_path {
+        Some(path) => match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(contents),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(err) => Err(err),
         },
-        None => String::new(),
-    };
+        None => Ok(String::new()),
+    }
+}
+
+fn fingerprint(contents: &str) -> [u8; 32] {
+    Sha256::digest(contents.as_bytes()).into()
+}
+
+/// Reads `config.toml`, applies `edits` and writes the result, unless the
+/// file changed after it was read. `before_write` runs between the two and
+/// lets tests simulate an external edit.
+fn apply_once(
+    config_path: &Path,
+    profile: Option<&str>,
+    edits: &[ConfigEdit],
+    before_write: impl FnOnce(),
+) -> anyhow::Result<WriteOutcome> {
+    let write_paths = resolve_symlink_write_paths(config_path)?;
+    let serialized = read_config(write_paths.read_path.as_deref())?;
+    let read_fingerprint = fingerprint(&serialized);
 
     let doc = if serialized.is_empty() {
         DocumentMut::new()
@@ -668,7 +765,13 @@ pub fn apply_blocking(
     }
 
     if !mutated {
-        return Ok(());
+        return Ok(WriteOutcome::Unchanged);
+    }
+
+    before_write();
+    let current = read_config(write_paths.read_path.as_deref())?;
+    if fingerprint(&current) != read_fingerprint {
+        return Ok(WriteOutcome::Conflict);
     }
 
     write_atomically(&write_paths.write_path, &document.doc.to_string()).with_context(|| {
@@ -678,7 +781,7 @@ pub fn apply_blocking(
         )
     })?;
 
-    Ok(())
+    Ok(WriteOutcome::Written)
 }
 
 /// Persist edits asynchronously by offloading the blocking writer.
@@ -798,6 +901,41 @@ impl ConfigEditsBuilder {
         self
     }
 
+    /// Set the top-level `approval_policy` and `sandbox_mode`.
+    pub fn set_permissions(mut self, approval: AskForApproval, sandbox_mode: SandboxMode) -> Self {
+        self.edits.push(ConfigEdit::SetPath {
+            segments: vec!["approval_policy".to_string()],
+            value: value(approval.to_string()),
+        });
+        self.edits.push(ConfigEdit::SetPath {
+            segments: vec!["sandbox_mode".to_string()],
+            value: value(sandbox_mode.to_string()),
+        });
+        self
+    }
+
+    /// Set (or clear) the top-level `profile` used when no `--profile` is given.
+    pub fn set_default_profile(mut self, profile: Option<&str>) -> Self {
+        let segments = vec!["profile".to_string()];
+        self.edits.push(match profile {
+            Some(profile) => ConfigEdit::SetPath {
+                segments,
+                value: value(profile),
+            },
+            None => ConfigEdit::ClearPath { segments },
+        });
+        self
+    }
+
+    /// Turn desktop notifications from the TUI on or off under `[tui]`.
+    pub fn set_tui_notifications(mut self, enabled: bool) -> Self {
+        self.edits.push(ConfigEdit::SetPath {
+            segments: vec!["tui".to_string(), "notifications".to_string()],
+            value: value(enabled),
+        });
+        self
+    }
+
     pub fn with_edits<I>(mut self, edits: I) -> Self
     where
         I: IntoIterator<Item = ConfigEdit>,
@@ -824,6 +962,7 @@ impl ConfigEditsBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::types::McpArgumentValidation;
     use crate::config::types::McpServerTransportConfig;
     use codex_protocol::openai_models::ReasoningEffort;
     use pretty_assertions::assert_eq;
@@ -1033,6 +1172,133 @@ model_reasoning_effort = "high"
         assert_eq!(contents, expected);
     }
 
+    #[test]
+    fn typed_setters_preserve_comments_and_unknown_keys() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        let original = r#"# Personal settings
+model = "gpt-5"
+future_setting = "kept"
+
+[tui]
+# desktop alerts
+notifications = false
+theme = "dark"
+
+[unknown_section]
+answer = 42
+"#;
+        std::fs::write(codex_home.join(CONFIG_TOML_FILE), original).expect("seed config");
+
+        ConfigEditsBuilder::new(codex_home)
+            .set_model(Some("gpt-5.1-codex"), None)
+            .set_tui_notifications(true)
+            .set_default_profile(Some("work"))
+            .apply_blocking()
+            .expect("apply");
+
+        let updated =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        for kept in [
+            "# Personal settings",
+            "future_setting = \"kept\"",
+            "# desktop alerts",
+            "theme = \"dark\"",
+            "[unknown_section]\nanswer = 42",
+        ] {
+            assert!(updated.contains(kept), "missing {kept:?} in:\n{updated}");
+        }
+        let parsed: TomlValue = toml::from_str(&updated).expect("parse config");
+        assert_eq!(
+            parsed.get("model").and_then(TomlValue::as_str),
+            Some("gpt-5.1-codex")
+        );
+        assert_eq!(
+            parsed.get("profile").and_then(TomlValue::as_str),
+            Some("work")
+        );
+        assert_eq!(
+            parsed
+                .get("tui")
+                .and_then(|tui| tui.get("notifications"))
+                .and_then(TomlValue::as_bool),
+            Some(true)
+        );
+
+        ConfigEditsBuilder::new(codex_home)
+            .set_default_profile(None)
+            .apply_blocking()
+            .expect("clear profile");
+        let cleared: TomlValue = toml::from_str(
+            &std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config"),
+        )
+        .expect("parse config");
+        assert_eq!(cleared.get("profile"), None);
+    }
+
+    #[test]
+    fn external_edit_between_read_and_write_is_not_clobbered() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        let config_path = codex_home.join(CONFIG_TOML_FILE);
+        std::fs::write(&config_path, "model = \"gpt-5\"\n").expect("seed config");
+        let edits = [ConfigEdit::SetModel {
+            model: Some("gpt-5.1-codex".to_string()),
+            effort: None,
+        }];
+
+        let outcome = apply_once(&config_path, None, &edits, || {
+            std::fs::write(
+                &config_path,
+                "model = \"gpt-5\"\nsandbox_mode = \"read-only\"\n",
+            )
+            .expect("external edit");
+        })
+        .expect("apply once");
+        assert_eq!(outcome, WriteOutcome::Conflict);
+        assert_eq!(
+            std::fs::read_to_string(&config_path).expect("read config"),
+            "model = \"gpt-5\"\nsandbox_mode = \"read-only\"\n"
+        );
+
+        // The retry reapplies the edit on top of the external change.
+        apply_blocking(codex_home, None, &edits).expect("apply");
+        assert_eq!(
+            std::fs::read_to_string(&config_path).expect("read config"),
+            "model = \"gpt-5.1-codex\"\nsandbox_mode = \"read-only\"\n"
+        );
+    }
+
+    #[test]
+    fn concurrent_writers_do_not_lose_edits() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path().to_path_buf();
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let codex_home = codex_home.clone();
+                std::thread::spawn(move || {
+                    ConfigEditsBuilder::new(&codex_home)
+                        .set_feature_enabled(&format!("feature_{i}"), true)
+                        .apply_blocking()
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("writer thread").expect("apply");
+        }
+
+        let parsed: TomlValue = toml::from_str(
+            &std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config"),
+        )
+        .expect("parse config");
+        let features = parsed
+            .get("features")
+            .and_then(TomlValue::as_table)
+            .expect("features table");
+        assert_eq!(features.len(), 8);
+    }
+
     #[test]
     fn batch_write_table_upsert_preserves_inline_comments() {
         let tmp = tempdir().expect("tmpdir");
@@ -1379,6 +1645,7 @@ gpt-5 = "gpt-5.1"
                 enabled_tools: Some(vec!["one".to_string(), "two".to_string()]),
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
 
@@ -1402,6 +1669,7 @@ gpt-5 = "gpt-5.1"
                 enabled_tools: None,
                 disabled_tools: Some(vec!["forbidden".to_string()]),
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
 
@@ -1468,6 +1736,7 @@ foo = { command = "cmd" }
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
 
@@ -1513,6 +1782,7 @@ foo = { command = "cmd" } # keep me
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
 
@@ -1557,6 +1827,7 @@ foo = { command = "cmd", args = ["--flag"] } # keep me
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
 
@@ -1602,6 +1873,7 @@ foo = { command = "cmd" }
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
 
@@ -1637,6 +1909,39 @@ foo = { command = "cmd" , enabled = false }
         );
     }
 
+    #[test]
+    fn builder_set_permissions_and_notifications_round_trip() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            "# keep me\nmodel = \"gpt-5\"\n",
+        )
+        .expect("seed config");
+
+        ConfigEditsBuilder::new(codex_home)
+            .set_permissions(AskForApproval::OnRequest, SandboxMode::WorkspaceWrite)
+            .set_tui_notifications(false)
+            .apply_blocking()
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        assert!(contents.starts_with("# keep me\n"), "{contents}");
+        let expected: TomlValue = toml::from_str(
+            r#"model = "gpt-5"
+approval_policy = "on-request"
+sandbox_mode = "workspace-write"
+
+[tui]
+notifications = false
+"#,
+        )
+        .expect("parse expected");
+        let actual: TomlValue = toml::from_str(&contents).expect("parse config");
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn blocking_set_path_updates_notifications() {
         let tmp = tempdir().expect("tmpdir");
@@ -1,36 +1,53 @@
 use crate::auth::AuthCredentialsStoreMode;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::types::ApprovalTimeout;
+use crate::config::types::ApprovalTimeoutAction;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::HooksConfig;
+use crate::config::types::HooksToml;
+use crate::config::types::InjectionGuardConfig;
+use crate::config::types::InjectionGuardToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
+use crate::config::types::ModelCapabilitiesToml;
 use crate::config::types::Notice;
 use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
+use crate::config::types::NotifyEventKind;
+use crate::config::types::NotifyEventToml;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::PasteToFileSettings;
+use crate::config::types::SafeCommandToml;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
+use crate::config::types::WorkspaceSummaryConfig;
+use crate::config::types::WorkspaceSummaryToml;
 use crate::config_loader::CloudRequirementsLoader;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::LoaderOverrides;
 use crate::config_loader::McpServerIdentity;
 use crate::config_loader::McpServerRequirement;
+use crate::config_loader::SafetyFloor;
 use crate::config_loader::Sourced;
 use crate::config_loader::load_config_layers_state;
+use crate::event_subscription::DEFAULT_EVENT_SUBSCRIBER_CAPACITY;
+use crate::exec_limits::ExecLimits;
 use crate::features::Feature;
 use crate::features::FeatureOverrides;
 use crate::features::Features;
 use crate::features::FeaturesToml;
 use crate::git_info::resolve_root_git_project_for_trust;
+use crate::injection_guard::InjectionScanner;
 use crate::model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::OLLAMA_CHAT_PROVIDER_ID;
@@ -40,6 +57,10 @@ use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::ToolToggles;
+use crate::provider_env;
+use crate::provider_env::DotenvFile;
+use crate::truncate::approx_tokens_from_byte_count;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
@@ -48,11 +69,14 @@ use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::config_types::SamplingParameters;
 use codex_protocol::config_types::SandboxMode;
+use codex_protocol::config_types::ServiceTier;
 use codex_protocol::config_types::TrustLevel;
 use codex_protocol::config_types::Verbosity;
 use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::config_types::WindowsSandboxLevel;
+use codex_protocol::models::ExecLimitOverrides;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -66,6 +90,8 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 #[cfg(test)]
 use tempfile::tempdir;
 
@@ -87,15 +113,29 @@ pub use service::ConfigService;
 pub use service::ConfigServiceError;
 
 pub use codex_git::GhostSnapshotConfig;
+pub use codex_utils_image::ImageLimits;
 
 /// Maximum number of bytes of the documentation that will be embedded. Larger
 /// files are *silently truncated* to this size so we do not take up too much of
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 pub(crate) const DEFAULT_AGENT_MAX_THREADS: Option<usize> = Some(6);
+/// Default for `max_request_body_bytes`; providers commonly reject bodies
+/// around 50 MB with a bare 413.
+pub(crate) const DEFAULT_MAX_REQUEST_BODY_BYTES: u64 = 48 * 1024 * 1024;
+/// Default for `base_instructions_token_budget`, well above the size of the
+/// built-in prompts.
+pub(crate) const DEFAULT_BASE_INSTRUCTIONS_TOKEN_BUDGET: u64 = 16_000;
 
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
+/// `-c` overrides applied by `--read-only-chat`: the model can read the
+/// conversation and call MCP tools, but cannot run commands or edit files.
+pub const READ_ONLY_CHAT_OVERRIDES: [&str; 2] = ["tools.shell=false", "tools.apply_patch=false"];
+
+/// How long an app-server thread may sit unused before it is suspended.
+pub const DEFAULT_THREAD_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
 #[cfg(test)]
 pub(crate) fn test_config() -> Config {
     let codex_home = tempdir().expect("create temp dir");
@@ -126,6 +166,13 @@ pub struct Config {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Maximum number of output tokens per model response. Clamped to the
+    /// model's maximum when it declares one.
+    pub model_max_output_tokens: Option<i64>,
+
+    /// Seed, temperature and top_p sent to models that accept them.
+    pub model_sampling: SamplingParameters,
+
     /// Key into the model_providers map that specifies which provider to use.
     pub model_provider_id: String,
 
@@ -135,9 +182,39 @@ pub struct Config {
     /// Optionally specify the personality of the model
     pub model_personality: Option<Personality>,
 
+    /// Language the model answers in, e.g. "Japanese". Changed mid-session
+    /// with `Op::SetPreferredLanguage`.
+    pub preferred_language: Option<String>,
+
     /// Approval policy for executing commands.
     pub approval_policy: Constrained<AskForApproval>,
 
+    /// How long to wait for an exec or patch approval before resolving it
+    /// automatically. `None` waits indefinitely.
+    pub approval_timeout: Option<ApprovalTimeout>,
+
+    /// How long a thread loaded by the app server may go without ops or
+    /// events before it is suspended. `None` keeps threads loaded.
+    pub thread_idle_timeout: Option<Duration>,
+
+    /// When true, patches touching files with uncommitted changes always ask
+    /// for approval (or are rejected under `never`), even if the sandbox
+    /// policy would otherwise auto-approve them.
+    pub protect_dirty_files: bool,
+
+    /// When true, a turn that would end with a patch still failing on stale
+    /// context gets one automatic follow-up asking the model to regenerate
+    /// the patch for just those files.
+    pub retry_stale_patches: bool,
+
+    /// When true, the provider is probed while the session starts and its
+    /// latency is reported with the startup diagnostics.
+    pub startup_probe: bool,
+
+    /// When true, the workspace roots are watched for edits made outside the
+    /// session, and the model is told about files it has seen that changed.
+    pub watch_workspace: bool,
+
     pub sandbox_policy: Constrained<SandboxPolicy>,
 
     /// True if the user passed in an override or set a value in config.toml
@@ -165,6 +242,10 @@ pub struct Config {
     /// Base instructions override.
     pub base_instructions: Option<String>,
 
+    /// Approximate size in tokens above which configured base instructions
+    /// produce a warning when a session starts.
+    pub base_instructions_token_budget: u64,
+
     /// Developer instructions override injected as a separate message.
     pub developer_instructions: Option<String>,
 
@@ -193,6 +274,10 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
+    /// Which event types reach `notify`, and how often
+    /// (`[notify_events.<kind>]` in `config.toml`).
+    pub notify_events: HashMap<NotifyEventKind, NotifyEventToml>,
+
     /// TUI notifications preference. When set, the TUI will send terminal notifications on
     /// approvals and turn completions when not focused.
     pub tui_notifications: Notifications,
@@ -217,11 +302,39 @@ pub struct Config {
     /// - `never`: Never use alternate screen (inline mode, preserves scrollback).
     pub tui_alternate_screen: AltScreenMode,
 
+    /// How the TUI handles large pastes (`tui.paste_to_file*` in `config.toml`).
+    pub tui_paste_to_file: PasteToFileSettings,
+
+    /// Key binding that cycles reasoning effort in the TUI (`tui.reasoning_cycle_key`).
+    pub tui_reasoning_cycle_key: Option<String>,
+
+    /// Show reasoning summary headings in the TUI status line (`tui.reasoning_headlines`).
+    pub tui_reasoning_headlines: bool,
+
+    /// Screen-reader friendly TUI rendering (`tui.accessible`).
+    pub tui_accessible: bool,
+
+    /// Append streamed output without the line-by-line reveal
+    /// (`tui.reduced_motion`, implied by `tui.accessible`).
+    pub tui_reduced_motion: bool,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
     pub cwd: PathBuf,
 
+    /// Directories the session works in beside `cwd`, from `workspace_roots`
+    /// and `--add-dir`. Never contains `cwd` itself.
+    pub workspace_roots: Vec<WorkspaceRoot>,
+
+    /// Title recorded in the rollout header of new sessions (`--title`).
+    /// Not read from `config.toml`.
+    pub session_title: Option<String>,
+
+    /// Tags recorded in the rollout header of new sessions (`--tag`).
+    /// Not read from `config.toml`.
+    pub session_tags: Vec<String>,
+
     /// Preferred store for CLI auth credentials.
     /// file (default): Use a file in the Codex home directory.
     /// keyring: Use an OS-specific keyring service.
@@ -257,9 +370,17 @@ pub struct Config {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Maximum number of lines of command output sent back to the model. The
+    /// full output is still shown in the UI and recorded in the rollout.
+    pub tool_output_max_lines: Option<usize>,
+
     /// Maximum number of agent threads that can be open concurrently.
     pub agent_max_threads: Option<usize>,
 
+    /// Number of events buffered for each `subscribe()` stream before a slow
+    /// subscriber starts skipping the oldest ones.
+    pub event_subscriber_capacity: usize,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: PathBuf,
@@ -267,9 +388,35 @@ pub struct Config {
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
+    /// Extra regular expressions redacted from `/export` transcripts, on top
+    /// of the built-in sensitive patterns.
+    pub export_redact_patterns: Vec<String>,
+
     /// When true, session is not persisted on disk. Default to `false`
     pub ephemeral: bool,
 
+    /// When true, model requests fail immediately instead of being attempted,
+    /// and no reachability probe runs before a turn.
+    pub offline: bool,
+
+    /// Upload attached files to providers that accept file inputs and refer to
+    /// them by file id, instead of inlining their contents in the message.
+    pub upload_attachments: bool,
+
+    /// When true, every command spawned by the shell tools is reported as an
+    /// `ExecCommandSpawn` event with its full spawn specification.
+    pub exec_audit: bool,
+
+    /// When true, shell tool commands are reported like `exec_audit` but never
+    /// run; the model receives a placeholder output instead.
+    pub dry_run_exec: bool,
+
+    /// Dotenv file whose values back this config's provider credential
+    /// lookups, set by `load_dotenv` or `--env-file`. Its values are never
+    /// exported to spawned commands unless
+    /// `shell_environment_policy.inherit_dotenv` is set.
+    pub dotenv: Option<Arc<DotenvFile>>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: UriBasedFileOpener,
@@ -293,9 +440,22 @@ pub struct Config {
     /// Optional override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
+    /// Capabilities declared for model names Codex has no built-in metadata
+    /// for (`[model_capabilities."<model>"]` in `config.toml`).
+    pub model_capabilities: HashMap<String, ModelCapabilitiesToml>,
+
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
+    /// Processing tier sent as `service_tier`. `None` leaves the choice to
+    /// the provider.
+    pub service_tier: Option<ServiceTier>,
+
+    /// Extra top-level fields merged into model request bodies from the
+    /// active profile or the top-level `extra_body` table. Provider-level
+    /// `extra_body` entries are applied underneath these.
+    pub extra_body: serde_json::Map<String, serde_json::Value>,
+
     /// Base URL for requests to ChatGPT (as opposed to the OpenAI API).
     pub chatgpt_base_url: String,
 
@@ -313,12 +473,46 @@ pub struct Config {
     /// Explicit or feature-derived web search mode.
     pub web_search_mode: Option<WebSearchMode>,
 
+    /// Groups of tools the model may call (`tools.shell`, `tools.apply_patch`,
+    /// `tools.mcp`). Can be changed mid-session with `Op::SetToolToggles`.
+    pub tool_toggles: ToolToggles,
+
+    /// Answer in single turns without tools, MCP servers or ghost snapshots,
+    /// using a trimmed instruction set. Set by `codex ask`; one turn of a
+    /// regular session can do the same with `Op::Ask`.
+    pub ask_mode: bool,
+
+    /// Tool-output tagging, scanning and approval downgrade (`[injection_guard]`).
+    pub injection_guard: InjectionGuardConfig,
+
+    /// Changes to the read-only programs that run without approval
+    /// (`[safe_commands]`).
+    pub safe_commands: BTreeMap<String, SafeCommandToml>,
+
+    /// Repository overview added to the initial context
+    /// (`[workspace_summary]`).
+    pub workspace_summary: WorkspaceSummaryConfig,
+
+    /// Commands run before and after turns, commands and patches (`[hooks]`).
+    pub hooks: HooksConfig,
+
     /// If set to `true`, used only the experimental unified exec tool.
     pub use_experimental_unified_exec_tool: bool,
 
     /// Settings for ghost snapshots (used for undo).
     pub ghost_snapshot: GhostSnapshotConfig,
 
+    /// Bounds local images are downscaled to before being attached (`[images]`).
+    pub image_limits: ImageLimits,
+
+    /// Default resource limits for commands run by the shell tools
+    /// (`[exec_limits]`).
+    pub exec_limits: ExecLimits,
+
+    /// Upper bound on the estimated size of a model request body. Turns over
+    /// the limit fail before anything is sent.
+    pub max_request_body_bytes: u64,
+
     /// Centralized feature flags; source of truth for feature gating.
     pub features: Features,
 
@@ -747,6 +941,29 @@ pub fn set_project_trust_level(
         .apply_blocking()
 }
 
+/// Directory whose trust decision covers `cwd`: the root git project when `cwd`
+/// is inside one (so worktrees share it), otherwise the canonical `cwd`.
+pub fn project_trust_target(cwd: &Path) -> PathBuf {
+    resolve_root_git_project_for_trust(cwd)
+        .unwrap_or_else(|| dunce::canonicalize(cwd).unwrap_or_else(|_| cwd.to_path_buf()))
+}
+
+/// CLI override that marks `project_path` as trusted for a single session
+/// without writing to `CODEX_HOME/config.toml`.
+pub fn session_project_trust_override(project_path: &Path) -> (String, TomlValue) {
+    let mut project = toml::map::Map::new();
+    project.insert(
+        "trust_level".to_string(),
+        TomlValue::String(TrustLevel::Trusted.to_string()),
+    );
+    let mut projects = toml::map::Map::new();
+    projects.insert(
+        project_path.to_string_lossy().to_string(),
+        TomlValue::Table(project),
+    );
+    ("projects".to_string(), TomlValue::Table(projects))
+}
+
 /// Save the default OSS provider preference to config.toml
 pub fn set_default_oss_provider(codex_home: &Path, provider: &str) -> std::io::Result<()> {
     // Validate that the provider is one of the known OSS providers
@@ -794,12 +1011,71 @@ pub struct ConfigToml {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Maximum number of output tokens per model response. Clamped to the
+    /// model's maximum when it declares one.
+    pub model_max_output_tokens: Option<i64>,
+
+    /// Seed for sampling, to make repeated runs more comparable. Sent only to
+    /// Chat Completions providers, and not to reasoning models.
+    pub model_seed: Option<i64>,
+
+    /// Sampling temperature, between 0 and 2. Not sent to reasoning models.
+    pub model_temperature: Option<f64>,
+
+    /// Nucleus sampling probability mass, greater than 0 and at most 1. Not
+    /// sent to reasoning models.
+    pub model_top_p: Option<f64>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
+    /// Seconds to wait for an exec or patch approval before applying
+    /// `approval_timeout_action`. Unset or `0` waits indefinitely.
+    pub approval_timeout_seconds: Option<u64>,
+
+    /// Decision applied when an approval request times out. Defaults to `deny`.
+    pub approval_timeout_action: Option<ApprovalTimeoutAction>,
+
+    /// Milliseconds an app-server thread may go without ops or events before
+    /// it is suspended. Defaults to 30 minutes; `0` keeps threads loaded.
+    pub thread_idle_timeout_ms: Option<u64>,
+
+    /// Require approval before patching files that have uncommitted changes
+    /// in git, even when the patch would otherwise be auto-approved.
+    /// Defaults to `false`.
+    pub protect_dirty_files: Option<bool>,
+
+    /// When a patch fails because a file changed since the model read it and
+    /// the model ends its turn without fixing it, ask it once to regenerate
+    /// the patch for just those files. Defaults to `false`.
+    pub retry_stale_patches: Option<bool>,
+
+    /// Probe the model provider while the session starts and report its
+    /// latency with the startup diagnostics. Defaults to `false`.
+    pub startup_probe: Option<bool>,
+
+    /// Watch the workspace roots for edits made outside the session and tell
+    /// the model which files it has read or written changed since. Uses one
+    /// inotify watch per directory on Linux. Defaults to `false`.
+    pub watch_workspace: Option<bool>,
+
     #[serde(default)]
     pub shell_environment_policy: ShellEnvironmentPolicyToml,
 
+    /// Defenses against prompt injection through tool output.
+    pub injection_guard: Option<InjectionGuardToml>,
+
+    /// Programs to treat as read-only, or to stop treating as read-only,
+    /// when deciding whether a command needs approval.
+    #[serde(default)]
+    pub safe_commands: BTreeMap<String, SafeCommandToml>,
+
+    /// Summary of the git repository added at session start.
+    pub workspace_summary: Option<WorkspaceSummaryToml>,
+
+    /// Commands to run at fixed points of a session.
+    pub hooks: Option<HooksToml>,
+
     /// Sandbox mode to use.
     pub sandbox_mode: Option<SandboxMode>,
 
@@ -810,6 +1086,11 @@ pub struct ConfigToml {
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// Per-event-type settings for `notify`, keyed by event type
+    /// (`agent-turn-complete`, `approval-requested`).
+    #[serde(default)]
+    pub notify_events: HashMap<NotifyEventKind, NotifyEventToml>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -821,8 +1102,22 @@ pub struct ConfigToml {
     /// the built-in instructions for the selected model. Users are STRONGLY
     /// DISCOURAGED from using this field, as deviating from the instructions
     /// sanctioned by Codex will likely degrade model performance.
+    #[serde(alias = "base_instructions_file")]
     pub model_instructions_file: Option<AbsolutePathBuf>,
 
+    /// Inline base instructions. Takes precedence over
+    /// `model_instructions_file` when both are set.
+    pub base_instructions: Option<String>,
+
+    /// Files appended, in order, to the base instructions. When neither
+    /// `base_instructions` nor `model_instructions_file` is set, these files
+    /// make up the whole instructions.
+    pub base_instructions_include: Option<Vec<AbsolutePathBuf>>,
+
+    /// Approximate size in tokens above which configured base instructions
+    /// produce a warning when a session starts. Defaults to 16,000.
+    pub base_instructions_token_budget: Option<u64>,
+
     /// Compact prompt used for history compaction.
     pub compact_prompt: Option<String>,
 
@@ -869,9 +1164,21 @@ pub struct ConfigToml {
     /// Ordered list of fallback filenames to look for when AGENTS.md is missing.
     pub project_doc_fallback_filenames: Option<Vec<String>>,
 
+    /// Directories to work in beside the cwd, such as a second repository
+    /// checked out next to it. Relative paths are resolved against the cwd.
+    pub workspace_roots: Option<Vec<PathBuf>>,
+
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Maximum number of lines of command output sent back to the model. The
+    /// full output is still shown in the UI and recorded in the rollout.
+    pub tool_output_max_lines: Option<usize>,
+
+    /// Number of events buffered for each event subscriber of an embedded
+    /// session before a slow subscriber starts skipping the oldest ones.
+    pub event_subscriber_capacity: Option<usize>,
+
     /// Profile to use from the `profiles` map.
     pub profile: Option<String>,
 
@@ -883,6 +1190,10 @@ pub struct ConfigToml {
     #[serde(default)]
     pub history: Option<History>,
 
+    /// Extra regular expressions redacted from `/export` transcripts, on top
+    /// of the built-in sensitive patterns.
+    pub export_redact_patterns: Option<Vec<String>>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: Option<UriBasedFileOpener>,
@@ -903,13 +1214,30 @@ pub struct ConfigToml {
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
+    /// Processing tier requested from the provider (`service_tier`): `auto`,
+    /// `default`, `flex` or `priority`.
+    pub service_tier: Option<ServiceTier>,
+
+    /// Extra top-level fields merged into every model request body, such as
+    /// provider-specific sampling parameters.
+    pub extra_body: Option<serde_json::Map<String, serde_json::Value>>,
+
     /// Override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
+    /// Capabilities of custom or proxied models, keyed by model name. Entries
+    /// override Codex's built-in metadata for that name.
+    #[serde(default)]
+    pub model_capabilities: HashMap<String, ModelCapabilitiesToml>,
+
     /// EXPERIMENTAL
     /// Optionally specify a personality for the model
     pub model_personality: Option<Personality>,
 
+    /// Language the model should answer in, e.g. "Japanese". Also applies to
+    /// reviews, commit messages, command explanations and compaction summaries.
+    pub preferred_language: Option<String>,
+
     /// Base URL for requests to ChatGPT (as opposed to the OpenAI API).
     pub chatgpt_base_url: Option<String>,
 
@@ -940,6 +1268,39 @@ pub struct ConfigToml {
     #[serde(default)]
     pub ghost_snapshot: Option<GhostSnapshotToml>,
 
+    /// Limits applied to local images before they are attached to a request.
+    #[serde(default)]
+    pub images: Option<ImagesToml>,
+
+    /// Resource limits applied to commands run by the shell tools.
+    #[serde(default)]
+    pub exec_limits: Option<ExecLimitsToml>,
+
+    /// Reject model requests immediately instead of retrying them; local
+    /// operations keep working. Also settable with `--offline`.
+    pub offline: Option<bool>,
+
+    /// Upload attached files to providers that accept file inputs (see
+    /// `supports_file_uploads`) instead of inlining their contents.
+    pub upload_attachments: Option<bool>,
+
+    /// Report the exact spawn specification (argv, sandbox wrapper, cwd,
+    /// redacted environment and limits) of every shell tool command.
+    pub exec_audit: Option<bool>,
+
+    /// Report shell tool commands like `exec_audit` without running them. Also
+    /// settable with `--dry-run-exec`.
+    pub dry_run_exec: Option<bool>,
+
+    /// Read provider credentials from `.env` in the working directory without
+    /// exporting them to spawned commands. Also settable with
+    /// `--env-file PATH`.
+    pub load_dotenv: Option<bool>,
+
+    /// Fail a turn before sending when the estimated request body is larger
+    /// than this many bytes. Defaults to 48 MiB.
+    pub max_request_body_bytes: Option<u64>,
+
     /// Markers used to detect the project root when searching parent
     /// directories for `.codex` folders. Defaults to [".git"] when unset.
     #[serde(default)]
@@ -1025,6 +1386,38 @@ impl ProjectConfig {
     }
 }
 
+/// A directory the session works in beside `cwd`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceRoot {
+    pub path: AbsolutePathBuf,
+    /// Trust recorded for the project containing `path`, resolved the same way
+    /// as `Config::active_project` is for `cwd`.
+    pub project: ProjectConfig,
+}
+
+impl WorkspaceRoot {
+    /// Roots of projects marked untrusted stay readable but are not added to
+    /// the sandbox's writable roots.
+    pub fn is_writable(&self) -> bool {
+        !self.project.is_untrusted()
+    }
+}
+
+impl Config {
+    /// Workspace roots the sandbox should let the session write to.
+    pub fn writable_workspace_roots(&self) -> Vec<AbsolutePathBuf> {
+        writable_root_paths(&self.workspace_roots)
+    }
+}
+
+fn writable_root_paths(roots: &[WorkspaceRoot]) -> Vec<AbsolutePathBuf> {
+    roots
+        .iter()
+        .filter(|root| root.is_writable())
+        .map(|root| root.path.clone())
+        .collect()
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct ToolsToml {
@@ -1034,6 +1427,28 @@ pub struct ToolsToml {
     /// Enable the `view_image` tool that lets the agent attach local images.
     #[serde(default)]
     pub view_image: Option<bool>,
+
+    /// Offer the shell execution tools. Defaults to `true`.
+    #[serde(default)]
+    pub shell: Option<bool>,
+
+    /// Offer the `apply_patch` tool. Defaults to `true`.
+    #[serde(default)]
+    pub apply_patch: Option<bool>,
+
+    /// Offer tools from configured MCP servers. Defaults to `true`.
+    #[serde(default)]
+    pub mcp: Option<bool>,
+}
+
+impl ToolsToml {
+    fn tool_toggles(&self) -> ToolToggles {
+        ToolToggles {
+            shell: self.shell.unwrap_or(true),
+            apply_patch: self.apply_patch.unwrap_or(true),
+            mcp: self.mcp.unwrap_or(true),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
@@ -1068,6 +1483,28 @@ pub struct GhostSnapshotToml {
     pub disable_warnings: Option<bool>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ImagesToml {
+    /// Longest allowed side, in pixels, of an attached image. Larger images
+    /// are downscaled, preserving their aspect ratio.
+    pub max_dimension: Option<u32>,
+    /// Largest allowed encoded image, in bytes. Larger images are re-encoded
+    /// as JPEG and shrunk further until they fit.
+    pub max_bytes: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ExecLimitsToml {
+    /// CPU time, in seconds, a command may use before it is killed.
+    pub cpu_time_seconds: Option<u64>,
+    /// Maximum memory, in MiB, each process of a command may map.
+    pub memory_mb: Option<u64>,
+    /// Combined stdout and stderr, in bytes, after which a command is killed.
+    pub max_output_bytes: Option<u64>,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct SandboxPolicyResolution {
     pub policy: SandboxPolicy,
@@ -1139,6 +1576,14 @@ impl ConfigToml {
             return Some(project_config.clone());
         }
 
+        // Trust decisions are keyed by canonical path, so a symlinked cwd still
+        // finds the entry recorded for its target.
+        if let Ok(canonical_cwd) = dunce::canonicalize(resolved_cwd)
+            && let Some(project_config) = projects.get(&canonical_cwd.to_string_lossy().to_string())
+        {
+            return Some(project_config.clone());
+        }
+
         // If cwd lives inside a git repo/worktree, check whether the root git project
         // (the primary repository working directory) is trusted. This lets
         // worktrees inherit trust from the main project.
@@ -1193,6 +1638,10 @@ pub struct ConfigOverrides {
     pub show_raw_agent_reasoning: Option<bool>,
     pub tools_web_search_request: Option<bool>,
     pub ephemeral: Option<bool>,
+    pub offline: Option<bool>,
+    pub dry_run_exec: Option<bool>,
+    /// Dotenv file to load provider credentials from, relative to `cwd`.
+    pub env_file: Option<PathBuf>,
     /// Additional directories that should be treated as writable roots for this session.
     pub additional_writable_roots: Vec<PathBuf>,
 }
@@ -1225,6 +1674,27 @@ pub fn resolve_oss_provider(
     }
 }
 
+/// Rejects `extra_body` keys that would collide with fields Codex sets itself
+/// on Responses or Chat Completions requests.
+fn validate_extra_body(
+    scope: &str,
+    extra_body: Option<&serde_json::Map<String, serde_json::Value>>,
+) -> std::io::Result<()> {
+    let Some(extra_body) = extra_body else {
+        return Ok(());
+    };
+    if let Some(key) = extra_body
+        .keys()
+        .find(|key| codex_api::RESERVED_REQUEST_FIELDS.contains(&key.as_str()))
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{scope}.{key} collides with a field Codex sets on every request"),
+        ));
+    }
+    Ok(())
+}
+
 /// Resolve the web search mode from explicit config and feature flags.
 fn resolve_web_search_mode(
     config_toml: &ConfigToml,
@@ -1300,6 +1770,9 @@ impl Config {
             show_raw_agent_reasoning,
             tools_web_search_request: override_tools_web_search_request,
             ephemeral,
+            offline,
+            dry_run_exec,
+            env_file,
             additional_writable_roots,
         } = overrides;
 
@@ -1345,63 +1818,69 @@ impl Config {
                 }
             }
         };
-        let additional_writable_roots: Vec<AbsolutePathBuf> = additional_writable_roots
+        let mut workspace_roots: Vec<WorkspaceRoot> = Vec::new();
+        for path in cfg
+            .workspace_roots
+            .clone()
+            .unwrap_or_default()
             .into_iter()
-            .map(|path| AbsolutePathBuf::resolve_path_against_base(path, &resolved_cwd))
-            .collect::<Result<Vec<_>, _>>()?;
+            .chain(additional_writable_roots)
+        {
+            let path = AbsolutePathBuf::resolve_path_against_base(path, &resolved_cwd)?;
+            if path.as_path() == resolved_cwd.as_path()
+                || workspace_roots.iter().any(|root| root.path == path)
+            {
+                continue;
+            }
+            let project = cfg
+                .get_active_project(path.as_path())
+                .unwrap_or(ProjectConfig { trust_level: None });
+            workspace_roots.push(WorkspaceRoot { path, project });
+        }
         let active_project = cfg
             .get_active_project(&resolved_cwd)
             .unwrap_or(ProjectConfig { trust_level: None });
 
-        let windows_sandbox_level = WindowsSandboxLevel::from_features(&features);
-        let SandboxPolicyResolution {
-            policy: mut sandbox_policy,
-            forced_auto_mode_downgraded_on_windows,
-        } = cfg.derive_sandbox_policy(
-            sandbox_mode,
-            config_profile.sandbox_mode,
-            windows_sandbox_level,
-            &resolved_cwd,
-        );
-        if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy {
-            for path in additional_writable_roots {
-                if !writable_roots.iter().any(|existing| existing == &path) {
-                    writable_roots.push(path);
-                }
+        // An explicit `--env-file` must exist; `load_dotenv` skips a missing
+        // `.env`. The values only back `provider_env::var` lookups made with
+        // this config.
+        let dotenv_path = match env_file {
+            Some(path) => Some(
+                AbsolutePathBuf::resolve_path_against_base(path, &resolved_cwd)?.into_path_buf(),
+            ),
+            None if cfg.load_dotenv.unwrap_or(false) => {
+                Some(resolved_cwd.join(".env")).filter(|path| path.is_file())
             }
+            None => None,
+        };
+        let dotenv = dotenv_path
+            .as_deref()
+            .map(DotenvFile::load)
+            .transpose()?
+            .map(Arc::new);
+
+        for (key, provider) in &cfg.model_providers {
+            validate_extra_body(
+                &format!("model_providers.{key}.extra_body"),
+                provider.extra_body.as_ref(),
+            )?;
         }
-        let approval_policy = approval_policy_override
-            .or(config_profile.approval_policy)
-            .or(cfg.approval_policy)
-            .unwrap_or_else(|| {
-                if active_project.is_trusted() {
-                    AskForApproval::OnRequest
-                } else if active_project.is_untrusted() {
-                    AskForApproval::UnlessTrusted
-                } else {
-                    AskForApproval::default()
-                }
-            });
-        let web_search_mode = resolve_web_search_mode(&cfg, &config_profile, &features);
-        // TODO(dylan): We should be able to leverage ConfigLayerStack so that
-        // we can reliably check this at every config level.
-        let did_user_set_custom_approval_policy_or_sandbox_mode = approval_policy_override
-            .is_some()
-            || config_profile.approval_policy.is_some()
-            || cfg.approval_policy.is_some()
-            || sandbox_mode.is_some()
-            || config_profile.sandbox_mode.is_some()
-            || cfg.sandbox_mode.is_some();
+        validate_extra_body("extra_body", cfg.extra_body.as_ref())?;
+        validate_extra_body("profile extra_body", config_profile.extra_body.as_ref())?;
+        let mut extra_body = cfg.extra_body.clone().unwrap_or_default();
+        extra_body.extend(config_profile.extra_body.clone().unwrap_or_default());
 
         let mut model_providers = built_in_model_providers();
         // Merge user-defined providers into the built-in list.
-        for (key, provider) in cfg.model_providers.into_iter() {
-            model_providers.entry(key).or_insert(provider);
+        for (key, provider) in &cfg.model_providers {
+            model_providers
+                .entry(key.clone())
+                .or_insert_with(|| provider.clone());
         }
 
         let model_provider_id = model_provider
-            .or(config_profile.model_provider)
-            .or(cfg.model_provider)
+            .or(config_profile.model_provider.clone())
+            .or(cfg.model_provider.clone())
             .unwrap_or_else(|| "openai".to_string());
         let model_provider = model_providers
             .get(&model_provider_id)
@@ -1412,9 +1891,127 @@ impl Config {
                 )
             })?
             .clone();
+        model_provider
+            .validate_url_templates(|name| provider_env::var(name, dotenv.as_deref()))
+            .map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("model provider `{model_provider_id}`: {err}"),
+                )
+            })?;
+
+        // Settings from the command line, the profile, the model provider or
+        // the top level that go past the safety floor are errors; defaults
+        // derived from project trust are lowered to the floor instead.
+        let safety_floor = SafetyFloor::resolve(
+            requirements.safety_floor.as_ref(),
+            active_profile_name.as_deref(),
+            &model_provider_id,
+        );
+        let explicit_sandbox_mode = sandbox_mode
+            .or(config_profile.sandbox_mode)
+            .or(model_provider.sandbox_mode)
+            .or(cfg.sandbox_mode);
+        if let Some(mode) = explicit_sandbox_mode {
+            safety_floor.check_sandbox_mode(mode).map_err(|e| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}"))
+            })?;
+        }
+        let explicit_approval_policy = approval_policy_override
+            .or(config_profile.approval_policy)
+            .or(model_provider.approval_policy)
+            .or(cfg.approval_policy);
+        if let Some(policy) = explicit_approval_policy {
+            safety_floor.check_approval_policy(policy).map_err(|e| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}"))
+            })?;
+        }
+
+        let windows_sandbox_level = WindowsSandboxLevel::from_features(&features);
+        let sandbox_resolution = cfg.derive_sandbox_policy(
+            sandbox_mode,
+            config_profile.sandbox_mode.or(model_provider.sandbox_mode),
+            windows_sandbox_level,
+            &resolved_cwd,
+        );
+        let SandboxPolicyResolution {
+            policy: sandbox_policy,
+            forced_auto_mode_downgraded_on_windows,
+        } = match &safety_floor.max_sandbox_mode {
+            Some(limit)
+                if safety_floor
+                    .check_sandbox_policy(&sandbox_resolution.policy)
+                    .is_err() =>
+            {
+                cfg.derive_sandbox_policy(
+                    Some(limit.value),
+                    None,
+                    windows_sandbox_level,
+                    &resolved_cwd,
+                )
+            }
+            _ => sandbox_resolution,
+        };
+        let sandbox_policy =
+            sandbox_policy.with_additional_writable_roots(&writable_root_paths(&workspace_roots));
+        let approval_policy = explicit_approval_policy.unwrap_or_else(|| {
+            safety_floor.clamp_approval_policy(if active_project.is_trusted() {
+                AskForApproval::OnRequest
+            } else if active_project.is_untrusted() {
+                AskForApproval::UnlessTrusted
+            } else {
+                AskForApproval::default()
+            })
+        });
+        let web_search_mode = resolve_web_search_mode(&cfg, &config_profile, &features);
+        let tool_toggles = cfg
+            .tools
+            .as_ref()
+            .map(ToolsToml::tool_toggles)
+            .unwrap_or_default();
+        // TODO(dylan): We should be able to leverage ConfigLayerStack so that
+        // we can reliably check this at every config level.
+        let did_user_set_custom_approval_policy_or_sandbox_mode =
+            explicit_approval_policy.is_some() || explicit_sandbox_mode.is_some();
+
+        if let Some(max_output_tokens) = cfg.model_max_output_tokens
+            && max_output_tokens <= 0
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("model_max_output_tokens must be positive, got {max_output_tokens}"),
+            ));
+        }
+        if let Some(temperature) = cfg.model_temperature
+            && !(0.0..=2.0).contains(&temperature)
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("model_temperature must be between 0 and 2, got {temperature}"),
+            ));
+        }
+        if let Some(top_p) = cfg.model_top_p
+            && !(top_p > 0.0 && top_p <= 1.0)
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("model_top_p must be greater than 0 and at most 1, got {top_p}"),
+            ));
+        }
 
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
+        let injection_guard: InjectionGuardConfig = cfg.injection_guard.unwrap_or_default().into();
+        if let Err(err) = InjectionScanner::new(&injection_guard) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid injection_guard.extra_patterns: {err}"),
+            ));
+        }
+
+        let hooks = HooksConfig::try_from(cfg.hooks.unwrap_or_default())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+
         let history = cfg.history.unwrap_or_default();
 
         let agent_max_threads = cfg
@@ -1454,6 +2051,55 @@ impl Config {
             config
         };
 
+        let image_limits = {
+            let mut limits = ImageLimits::default();
+            if let Some(images) = cfg.images.as_ref() {
+                if let Some(max_dimension) = images.max_dimension {
+                    if max_dimension == 0 {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            "images.max_dimension must be positive",
+                        ));
+                    }
+                    limits.max_width = max_dimension;
+                    limits.max_height = max_dimension;
+                }
+                if let Some(max_bytes) = images.max_bytes {
+                    if max_bytes == 0 {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            "images.max_bytes must be positive",
+                        ));
+                    }
+                    limits.max_bytes = usize::try_from(max_bytes).unwrap_or(usize::MAX);
+                }
+            }
+            limits
+        };
+
+        let exec_limits = match cfg.exec_limits.as_ref() {
+            Some(exec_limits) => {
+                for (key, value) in [
+                    ("cpu_time_seconds", exec_limits.cpu_time_seconds),
+                    ("memory_mb", exec_limits.memory_mb),
+                    ("max_output_bytes", exec_limits.max_output_bytes),
+                ] {
+                    if value == Some(0) {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!("exec_limits.{key} must be positive"),
+                        ));
+                    }
+                }
+                ExecLimits::default().with_overrides(Some(&ExecLimitOverrides {
+                    cpu_time_seconds: exec_limits.cpu_time_seconds,
+                    memory_mb: exec_limits.memory_mb,
+                    max_output_bytes: exec_limits.max_output_bytes,
+                }))
+            }
+            None => ExecLimits::default(),
+        };
+
         let include_apply_patch_tool_flag = features.enabled(Feature::ApplyPatchFreeform);
         let use_experimental_unified_exec_tool = features.enabled(Feature::UnifiedExec);
 
@@ -1480,20 +2126,34 @@ impl Config {
             }
         });
 
-        // Load base instructions override from a file if specified. If the
-        // path is relative, resolve it against the effective cwd so the
-        // behaviour matches other path-like config values.
-        let model_instructions_path = config_profile
-            .model_instructions_file
-            .as_ref()
-            .or(cfg.model_instructions_file.as_ref());
-        let file_base_instructions =
-            Self::try_read_non_empty_file(model_instructions_path, "model instructions file")?;
-        let base_instructions = base_instructions.or(file_base_instructions);
+        // Base instructions come from the first of: the programmatic
+        // override, the active profile, the top level of config.toml. Each
+        // layer is composed as a whole, so a profile that sets any of the
+        // instruction keys replaces the top-level instructions entirely.
+        let base_instructions = match base_instructions {
+            Some(base_instructions) => Some(base_instructions),
+            None => match Self::compose_base_instructions(
+                config_profile.base_instructions.as_deref(),
+                config_profile.model_instructions_file.as_ref(),
+                config_profile.base_instructions_include.as_deref(),
+            )? {
+                Some(base_instructions) => Some(base_instructions),
+                None => Self::compose_base_instructions(
+                    cfg.base_instructions.as_deref(),
+                    cfg.model_instructions_file.as_ref(),
+                    cfg.base_instructions_include.as_deref(),
+                )?,
+            },
+        };
         let developer_instructions = developer_instructions.or(cfg.developer_instructions);
         let model_personality = model_personality
             .or(config_profile.model_personality)
             .or(cfg.model_personality);
+        let preferred_language = config_profile
+            .preferred_language
+            .or(cfg.preferred_language)
+            .map(|language| language.trim().to_string())
+            .filter(|language| !language.is_empty());
 
         let experimental_compact_prompt_path = config_profile
             .experimental_compact_prompt_file
@@ -1516,6 +2176,7 @@ impl Config {
             sandbox_policy: mut constrained_sandbox_policy,
             mcp_servers,
             exec_policy: _,
+            safety_floor: _,
         } = requirements;
 
         constrained_approval_policy
@@ -1524,6 +2185,9 @@ impl Config {
         constrained_sandbox_policy
             .set(sandbox_policy)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
+        let (constrained_approval_policy, constrained_sandbox_policy) = safety_floor
+            .constrain(constrained_approval_policy, constrained_sandbox_policy)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
         let mcp_servers = constrain_mcp_servers(cfg.mcp_servers.clone(), mcp_servers.as_ref())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
@@ -1533,18 +2197,48 @@ impl Config {
             review_model,
             model_context_window: cfg.model_context_window,
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
+            model_max_output_tokens: cfg.model_max_output_tokens,
+            model_sampling: SamplingParameters {
+                seed: cfg.model_seed,
+                temperature: cfg.model_temperature,
+                top_p: cfg.model_top_p,
+            },
             model_provider_id,
             model_provider,
             cwd: resolved_cwd,
+            workspace_roots,
+            session_title: None,
+            session_tags: Vec::new(),
             approval_policy: constrained_approval_policy,
+            approval_timeout: cfg
+                .approval_timeout_seconds
+                .filter(|seconds| *seconds > 0)
+                .map(|seconds| ApprovalTimeout {
+                    duration: Duration::from_secs(seconds),
+                    action: cfg.approval_timeout_action.unwrap_or_default(),
+                }),
+            thread_idle_timeout: match cfg.thread_idle_timeout_ms {
+                Some(0) => None,
+                Some(millis) => Some(Duration::from_millis(millis)),
+                None => Some(DEFAULT_THREAD_IDLE_TIMEOUT),
+            },
+            protect_dirty_files: cfg.protect_dirty_files.unwrap_or(false),
+            retry_stale_patches: cfg.retry_stale_patches.unwrap_or(false),
+            startup_probe: cfg.startup_probe.unwrap_or(false),
+            watch_workspace: cfg.watch_workspace.unwrap_or(false),
             sandbox_policy: constrained_sandbox_policy,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             forced_auto_mode_downgraded_on_windows,
             shell_environment_policy,
             notify: cfg.notify,
+            notify_events: cfg.notify_events,
             user_instructions,
             base_instructions,
+            base_instructions_token_budget: cfg
+                .base_instructions_token_budget
+                .unwrap_or(DEFAULT_BASE_INSTRUCTIONS_TOKEN_BUDGET),
             model_personality,
+            preferred_language,
             developer_instructions,
             compact_prompt,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
@@ -1571,11 +2265,21 @@ impl Config {
                 })
                 .collect(),
             tool_output_token_limit: cfg.tool_output_token_limit,
+            tool_output_max_lines: cfg.tool_output_max_lines,
             agent_max_threads,
+            event_subscriber_capacity: cfg
+                .event_subscriber_capacity
+                .unwrap_or(DEFAULT_EVENT_SUBSCRIBER_CAPACITY),
             codex_home,
             config_layer_stack,
             history,
+            export_redact_patterns: cfg.export_redact_patterns.unwrap_or_default(),
             ephemeral: ephemeral.unwrap_or_default(),
+            offline: offline.or(cfg.offline).unwrap_or(false),
+            upload_attachments: cfg.upload_attachments.unwrap_or(false),
+            exec_audit: cfg.exec_audit.unwrap_or(false),
+            dry_run_exec: dry_run_exec.or(cfg.dry_run_exec).unwrap_or(false),
+            dotenv,
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
 
@@ -1592,7 +2296,10 @@ impl Config {
                 .or(cfg.model_reasoning_summary)
                 .unwrap_or_default(),
             model_supports_reasoning_summaries: cfg.model_supports_reasoning_summaries,
+            model_capabilities: cfg.model_capabilities,
             model_verbosity: config_profile.model_verbosity.or(cfg.model_verbosity),
+            service_tier: config_profile.service_tier.or(cfg.service_tier),
+            extra_body,
             chatgpt_base_url: config_profile
                 .chatgpt_base_url
                 .or(cfg.chatgpt_base_url)
@@ -1601,8 +2308,19 @@ impl Config {
             forced_login_method,
             include_apply_patch_tool: include_apply_patch_tool_flag,
             web_search_mode,
+            tool_toggles,
+            ask_mode: false,
+            injection_guard,
+            safe_commands: cfg.safe_commands,
+            workspace_summary: cfg.workspace_summary.unwrap_or_default().into(),
+            hooks,
             use_experimental_unified_exec_tool,
             ghost_snapshot,
+            image_limits,
+            exec_limits,
+            max_request_body_bytes: cfg
+                .max_request_body_bytes
+                .unwrap_or(DEFAULT_MAX_REQUEST_BODY_BYTES),
             features,
             suppress_unstable_features_warning: cfg
                 .suppress_unstable_features_warning
@@ -1633,14 +2351,31 @@ impl Config {
                 .as_ref()
                 .map(|t| t.notification_method)
                 .unwrap_or_default(),
-            animations: cfg.tui.as_ref().map(|t| t.animations).unwrap_or(true),
+            animations: cfg
+                .tui
+                .as_ref()
+                .map(Tui::effective_animations)
+                .unwrap_or(true),
             show_tooltips: cfg.tui.as_ref().map(|t| t.show_tooltips).unwrap_or(true),
             experimental_mode: cfg.tui.as_ref().and_then(|t| t.experimental_mode),
             tui_alternate_screen: cfg
                 .tui
                 .as_ref()
-                .map(|t| t.alternate_screen)
+                .map(Tui::effective_alternate_screen)
+                .unwrap_or_default(),
+            tui_paste_to_file: cfg
+                .tui
+                .as_ref()
+                .map(Tui::paste_to_file_settings)
                 .unwrap_or_default(),
+            tui_reasoning_cycle_key: cfg.tui.as_ref().and_then(|t| t.reasoning_cycle_key.clone()),
+            tui_reasoning_headlines: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.reasoning_headlines)
+                .unwrap_or(true),
+            tui_accessible: cfg.tui.as_ref().is_some_and(|t| t.accessible),
+            tui_reduced_motion: cfg.tui.as_ref().is_some_and(Tui::effective_reduced_motion),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1676,6 +2411,37 @@ impl Config {
         None
     }
 
+    /// Composes the base instructions configured in one layer: the inline
+    /// text or the instructions file, followed by each include file in
+    /// order, separated by blank lines. Returns `None` when the layer sets
+    /// none of them.
+    fn compose_base_instructions(
+        inline: Option<&str>,
+        file: Option<&AbsolutePathBuf>,
+        includes: Option<&[AbsolutePathBuf]>,
+    ) -> std::io::Result<Option<String>> {
+        let head = match inline.map(str::trim).filter(|text| !text.is_empty()) {
+            Some(text) => Some(text.to_string()),
+            None => Self::try_read_non_empty_file(file, "model instructions file")?,
+        };
+        let mut parts: Vec<String> = head.into_iter().collect();
+        for include in includes.unwrap_or_default() {
+            parts.extend(Self::try_read_non_empty_file(
+                Some(include),
+                "base instructions include",
+            )?);
+        }
+        Ok((!parts.is_empty()).then(|| parts.join("\n\n")))
+    }
+
+    /// Approximate token size of the configured base instructions, or `None`
+    /// when the model's built-in instructions are used.
+    pub fn base_instructions_tokens(&self) -> Option<u64> {
+        self.base_instructions
+            .as_deref()
+            .map(|text| approx_tokens_from_byte_count(text.len()))
+    }
+
     /// If `path` is `Some`, attempts to read the file at the given path and
     /// returns its contents as a trimmed `String`. If the file is empty, or
     /// is `Some` but cannot be read, returns an `Err`.
@@ -1775,9 +2541,12 @@ mod tests {
     use crate::config::edit::apply_blocking;
     use crate::config::types::FeedbackConfigToml;
     use crate::config::types::HistoryPersistence;
+    use crate::config::types::McpArgumentValidation;
     use crate::config::types::McpServerTransportConfig;
     use crate::config::types::NotificationMethod;
     use crate::config::types::Notifications;
+    use crate::config::types::PasteToFileMode;
+    use crate::config_loader::ConfigRequirementsToml;
     use crate::config_loader::RequirementSource;
     use crate::features::Feature;
 
@@ -1806,6 +2575,7 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            argument_validation: McpArgumentValidation::Strict,
         }
     }
 
@@ -1824,6 +2594,7 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            argument_validation: McpArgumentValidation::Strict,
         }
     }
 
@@ -1838,7 +2609,7 @@ persistence = "save-all"
         assert_eq!(
             Some(History {
                 persistence: HistoryPersistence::SaveAll,
-                max_bytes: None,
+                ..Default::default()
             }),
             history_with_persistence_cfg.history
         );
@@ -1853,10 +2624,31 @@ persistence = "none"
         assert_eq!(
             Some(History {
                 persistence: HistoryPersistence::None,
-                max_bytes: None,
+                ..Default::default()
             }),
             history_no_persistence_cfg.history
         );
+
+        let history_with_limits = r#"
+[history]
+persistence = "save-all"
+max_entries = 500
+max_entry_bytes = 4096
+dedup_window = 0
+"#;
+
+        let history_with_limits_cfg = toml::from_str::<ConfigToml>(history_with_limits)
+            .expect("TOML deserialization should succeed");
+        assert_eq!(
+            Some(History {
+                persistence: HistoryPersistence::SaveAll,
+                max_bytes: None,
+                max_entries: Some(500),
+                max_entry_bytes: Some(4096),
+                dedup_window: Some(0),
+            }),
+            history_with_limits_cfg.history
+        );
     }
 
     #[test]
@@ -1878,17 +2670,123 @@ persistence = "none"
                 show_tooltips: true,
                 experimental_mode: None,
                 alternate_screen: AltScreenMode::Auto,
+                inline: false,
+                paste_to_file: PasteToFileMode::Ask,
+                paste_to_file_min_lines: 200,
+                paste_to_file_min_bytes: 16 * 1024,
+                reasoning_cycle_key: None,
+                reasoning_headlines: true,
+                accessible: false,
+                reduced_motion: false,
             }
         );
     }
 
     #[test]
-    fn test_sandbox_config_parsing() {
-        let sandbox_full_access = r#"
-sandbox_mode = "danger-full-access"
-
-[sandbox_workspace_write]
-network_access = false  # This should be ignored.
+    fn tui_inline_forces_alternate_screen_off() {
+        let cfg = r#"
+[tui]
+alternate_screen = "always"
+inline = true
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("TUI inline config should parse");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(tui.effective_alternate_screen(), AltScreenMode::Never);
+    }
+
+    #[test]
+    fn tui_accessible_implies_reduced_motion() {
+        let cfg = r#"
+[tui]
+accessible = true
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("TUI accessible config should parse");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(
+            (tui.effective_reduced_motion(), tui.effective_animations()),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn tui_paste_to_file_settings_parse() {
+        let cfg = r#"
+[tui]
+paste_to_file = "auto"
+paste_to_file_min_lines = 50
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("TUI paste config should parse");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(
+            tui.paste_to_file_settings(),
+            PasteToFileSettings {
+                mode: PasteToFileMode::Auto,
+                min_lines: 50,
+                min_bytes: 16 * 1024,
+            }
+        );
+    }
+
+    #[test]
+    fn tools_toggles_parse_and_default_to_enabled() {
+        let cfg = r#"
+[tools]
+shell = false
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("tools config should parse");
+        let tools = parsed.tools.expect("config should include tools section");
+
+        assert_eq!(
+            tools.tool_toggles(),
+            ToolToggles {
+                shell: false,
+                apply_patch: true,
+                mcp: true,
+            }
+        );
+    }
+
+    #[test]
+    fn approval_timeout_resolves_from_config() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+approval_timeout_seconds = 300
+approval_timeout_action = "abort"
+"#,
+        )
+        .expect("approval timeout config should parse");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.approval_timeout,
+            Some(ApprovalTimeout {
+                duration: Duration::from_secs(300),
+                action: ApprovalTimeoutAction::Abort,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sandbox_config_parsing() {
+        let sandbox_full_access = r#"
+sandbox_mode = "danger-full-access"
+
+[sandbox_workspace_write]
+network_access = false  # This should be ignored.
 "#;
         let sandbox_full_access_cfg = toml::from_str::<ConfigToml>(sandbox_full_access)
             .expect("TOML deserialization should succeed");
@@ -2162,6 +3060,62 @@ trust_level = "trusted"
         );
     }
 
+    #[test]
+    fn workspace_roots_are_writable_unless_their_project_is_untrusted() -> std::io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let frontend = temp_dir.path().join("frontend");
+        let backend = temp_dir.path().join("backend");
+        let vendor = temp_dir.path().join("vendor");
+        for dir in [&frontend, &backend, &vendor] {
+            std::fs::create_dir_all(dir)?;
+        }
+        let vendor_key = dunce::canonicalize(&vendor)?.to_string_lossy().to_string();
+        let cfg = ConfigToml {
+            workspace_roots: Some(vec![
+                PathBuf::from("../backend"),
+                PathBuf::from("../vendor"),
+                PathBuf::from("."),
+            ]),
+            projects: Some(HashMap::from([(
+                vendor_key,
+                ProjectConfig {
+                    trust_level: Some(TrustLevel::Untrusted),
+                },
+            )])),
+            ..Default::default()
+        };
+        let overrides = ConfigOverrides {
+            cwd: Some(frontend.clone()),
+            sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+            additional_writable_roots: vec![backend.clone()],
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            overrides,
+            temp_dir.path().to_path_buf(),
+        )?;
+
+        let backend = AbsolutePathBuf::try_from(backend).unwrap();
+        let vendor = AbsolutePathBuf::try_from(vendor).unwrap();
+        assert_eq!(
+            config
+                .workspace_roots
+                .iter()
+                .map(|root| root.path.clone())
+                .collect::<Vec<_>>(),
+            vec![backend.clone(), vendor.clone()]
+        );
+        assert_eq!(config.writable_workspace_roots(), vec![backend.clone()]);
+        if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = config.sandbox_policy.get() {
+            assert!(writable_roots.contains(&backend));
+            assert!(!writable_roots.contains(&vendor));
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn add_dir_override_extends_workspace_writable_roots() -> std::io::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -2254,6 +3208,308 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn base_instructions_precedence() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let global_file = codex_home.path().join("global.md");
+        let shared_file = codex_home.path().join("shared.md");
+        std::fs::write(&global_file, "global file\n")?;
+        std::fs::write(&shared_file, "shared rules\n")?;
+        let cfg: ConfigToml = toml::from_str(&format!(
+            r#"
+base_instructions_file = {global_file:?}
+base_instructions_include = [{shared_file:?}]
+
+[profiles.inline]
+base_instructions = "profile inline"
+base_instructions_include = [{shared_file:?}]
+
+[profiles.plain]
+model = "o3"
+"#
+        ))
+        .expect("TOML deserialization should succeed");
+        let load = |profile: Option<&str>, base_instructions: Option<&str>| {
+            Config::load_from_base_config_with_overrides(
+                cfg.clone(),
+                ConfigOverrides {
+                    config_profile: profile.map(str::to_string),
+                    base_instructions: base_instructions.map(str::to_string),
+                    ..Default::default()
+                },
+                codex_home.path().to_path_buf(),
+            )
+            .map(|config| config.base_instructions)
+        };
+
+        assert_eq!(
+            load(None, None)?,
+            Some("global file\n\nshared rules".to_string())
+        );
+        assert_eq!(
+            load(Some("plain"), None)?,
+            Some("global file\n\nshared rules".to_string())
+        );
+        assert_eq!(
+            load(Some("inline"), None)?,
+            Some("profile inline\n\nshared rules".to_string())
+        );
+        assert_eq!(
+            load(Some("inline"), Some("from the caller"))?,
+            Some("from the caller".to_string())
+        );
+
+        let built_in = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(built_in.base_instructions, None);
+        assert_eq!(built_in.base_instructions_tokens(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_base_instructions_include_is_an_error() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let missing = codex_home.path().join("missing.md");
+        let cfg: ConfigToml = toml::from_str(&format!(
+            "base_instructions = \"inline\"\nbase_instructions_include = [{missing:?}]\n"
+        ))
+        .expect("TOML deserialization should succeed");
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("missing include should fail");
+
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("base instructions include"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_out_of_range_sampling_parameters() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        for (cfg, key) in [
+            (
+                ConfigToml {
+                    model_temperature: Some(2.5),
+                    ..Default::default()
+                },
+                "model_temperature",
+            ),
+            (
+                ConfigToml {
+                    model_top_p: Some(0.0),
+                    ..Default::default()
+                },
+                "model_top_p",
+            ),
+        ] {
+            let err = Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+            .expect_err("out-of-range sampling parameters should be rejected");
+
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+            assert!(err.to_string().contains(key), "unexpected error: {err}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_non_positive_model_max_output_tokens() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            model_max_output_tokens: Some(0),
+            ..Default::default()
+        };
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("zero output tokens should be rejected");
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("model_max_output_tokens"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn extra_body_merges_profile_over_top_level() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+profile = "sampling"
+
+[extra_body]
+top_k = 20
+seed = 7
+
+[profiles.sampling.extra_body]
+top_k = 40
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            serde_json::Value::Object(config.extra_body),
+            serde_json::json!({"top_k": 40, "seed": 7})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn extra_body_rejects_fields_codex_sends() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[model_providers.local]
+name = "Local"
+base_url = "http://localhost:8080/v1"
+
+[model_providers.local.extra_body]
+stream = false
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("reserved extra_body key should be rejected");
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(
+            err.to_string()
+                .contains("model_providers.local.extra_body.stream")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn exec_limits_table_sets_command_limits() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[exec_limits]
+cpu_time_seconds = 120
+memory_mb = 2048
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.exec_limits,
+            ExecLimits {
+                cpu_time: Some(Duration::from_secs(120)),
+                memory_bytes: Some(2048 * 1024 * 1024),
+                max_output_bytes: None,
+            }
+        );
+
+        let cfg: ConfigToml = toml::from_str("[exec_limits]\nmax_output_bytes = 0\n")
+            .expect("TOML deserialization should succeed");
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("zero limits are rejected");
+        assert_eq!(
+            err.to_string(),
+            "exec_limits.max_output_bytes must be positive"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn offline_is_set_by_config_or_override() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |toml: &str, offline: Option<bool>| {
+            let cfg: ConfigToml =
+                toml::from_str(toml).expect("TOML deserialization should succeed");
+            Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides {
+                    offline,
+                    ..Default::default()
+                },
+                codex_home.path().to_path_buf(),
+            )
+            .map(|config| config.offline)
+        };
+
+        assert!(!load("", None)?);
+        assert!(load("offline = true", None)?);
+        assert!(load("", Some(true))?);
+        assert!(!load("offline = true", Some(false))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn images_table_sets_image_limits() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[images]
+max_dimension = 1024
+max_bytes = 1048576
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.image_limits,
+            ImageLimits {
+                max_width: 1024,
+                max_height: 1024,
+                max_bytes: 1024 * 1024,
+            }
+        );
+        assert_eq!(
+            config.max_request_body_bytes,
+            DEFAULT_MAX_REQUEST_BODY_BYTES
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn config_defaults_to_auto_oauth_store_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2439,6 +3695,166 @@ profile = "project"
         Ok(())
     }
 
+    #[tokio::test]
+    async fn dotenv_values_stay_with_the_config_that_loaded_them() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let workspace = TempDir::new()?;
+        let key = "CODEX_CONFIG_DOTENV_SCOPE_TEST_KEY";
+        std::fs::write(workspace.path().join(".env"), format!("{key}=sk-dotenv\n"))?;
+        let load = |env_file: Option<PathBuf>| {
+            ConfigBuilder::default()
+                .codex_home(codex_home.path().to_path_buf())
+                .harness_overrides(ConfigOverrides {
+                    cwd: Some(workspace.path().to_path_buf()),
+                    env_file,
+                    ..Default::default()
+                })
+                .build()
+        };
+
+        let with_dotenv = load(Some(PathBuf::from(".env"))).await?;
+        let without_dotenv = load(None).await?;
+
+        let provider = ModelProviderInfo {
+            env_key: Some(key.to_string()),
+            ..ModelProviderInfo::create_openai_provider()
+        };
+        assert_eq!(
+            provider
+                .api_key(with_dotenv.dotenv.as_deref())
+                .ok()
+                .flatten(),
+            Some("sk-dotenv".to_string())
+        );
+        assert_eq!(without_dotenv.dotenv, None);
+        assert!(provider.api_key(without_dotenv.dotenv.as_deref()).is_err());
+
+        Ok(())
+    }
+
+    const SAFETY_FLOOR_CONFIG: &str = r#"
+sandbox_mode = "workspace-write"
+
+[model_providers.local-experimental]
+name = "Local"
+base_url = "http://localhost:8080/v1"
+sandbox_mode = "read-only"
+
+[profiles.prod-incident]
+sandbox_mode = "workspace-write"
+approval_policy = "on-request"
+
+[profiles.local]
+model_provider = "local-experimental"
+"#;
+
+    const SAFETY_FLOOR_REQUIREMENTS: &str = r#"
+[safety_floor]
+max_sandbox_mode = "workspace-write"
+
+[safety_floor.model_providers.local-experimental]
+max_sandbox_mode = "workspace-write"
+min_approval_policy = "untrusted"
+"#;
+
+    async fn load_with_safety_floor(
+        profile: &str,
+        overrides: ConfigOverrides,
+    ) -> std::io::Result<Config> {
+        let codex_home = TempDir::new()?;
+        let workspace = TempDir::new()?;
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            SAFETY_FLOOR_CONFIG,
+        )?;
+        let requirements: ConfigRequirementsToml =
+            toml::from_str(SAFETY_FLOOR_REQUIREMENTS).map_err(std::io::Error::other)?;
+        ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .harness_overrides(ConfigOverrides {
+                cwd: Some(workspace.path().to_path_buf()),
+                config_profile: Some(profile.to_string()),
+                ..overrides
+            })
+            .cloud_requirements(CloudRequirementsLoader::new(
+                async move { Some(requirements) },
+            ))
+            .build()
+            .await
+    }
+
+    #[tokio::test]
+    async fn safety_floor_allows_profiles_within_it() -> std::io::Result<()> {
+        let config = load_with_safety_floor("prod-incident", ConfigOverrides::default()).await?;
+
+        assert_eq!(
+            config.sandbox_policy.get(),
+            &SandboxPolicy::new_workspace_write_policy()
+        );
+        assert_eq!(config.approval_policy.value(), AskForApproval::OnRequest);
+        assert!(
+            config
+                .sandbox_policy
+                .can_set(&SandboxPolicy::DangerFullAccess)
+                .is_err(),
+            "the floor also applies to changes made after load"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn safety_floor_rejects_explicit_settings_past_it() -> std::io::Result<()> {
+        let err = load_with_safety_floor(
+            "prod-incident",
+            ConfigOverrides {
+                sandbox_mode: Some(SandboxMode::DangerFullAccess),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect_err("danger-full-access is past the global floor");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "sandbox_mode `danger-full-access` is not allowed: safety_floor limits it to `workspace-write` (set by cloud requirements)"
+        );
+
+        let err = load_with_safety_floor(
+            "local",
+            ConfigOverrides {
+                approval_policy: Some(AskForApproval::OnRequest),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect_err("on-request is past the provider floor");
+        assert_eq!(
+            err.to_string(),
+            "approval_policy `on-request` is not allowed: safety_floor.model_providers.local-experimental limits it to `untrusted` (set by cloud requirements)"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn safety_floor_lowers_defaults_and_keeps_provider_presets() -> std::io::Result<()> {
+        let config = load_with_safety_floor("local", ConfigOverrides::default()).await?;
+
+        // The provider preset wins over the top-level sandbox mode, and the
+        // unset approval policy is lowered to the provider's floor.
+        assert_eq!(config.sandbox_policy.get(), &SandboxPolicy::ReadOnly);
+        assert_eq!(
+            config.approval_policy.value(),
+            AskForApproval::UnlessTrusted
+        );
+        assert!(
+            config
+                .approval_policy
+                .can_set(&AskForApproval::Never)
+                .is_err()
+        );
+        Ok(())
+    }
+
     #[test]
     fn profile_sandbox_mode_overrides_base() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2693,6 +4109,7 @@ profile = "project"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
 
@@ -2737,6 +4154,43 @@ profile = "project"
         Ok(())
     }
 
+    #[tokio::test]
+    async fn session_trust_override_enables_project_config_without_persisting() -> anyhow::Result<()>
+    {
+        let codex_home = TempDir::new()?;
+        let project = TempDir::new()?;
+        std::fs::create_dir_all(project.path().join(".codex"))?;
+        std::fs::write(
+            project.path().join(".codex").join(CONFIG_TOML_FILE),
+            "model = \"project\"\n",
+        )?;
+
+        let cwd = AbsolutePathBuf::try_from(dunce::canonicalize(project.path())?)?;
+        let trust_override = session_project_trust_override(&project_trust_target(cwd.as_path()));
+        let config_layer_stack = load_config_layers_state(
+            codex_home.path(),
+            Some(cwd.clone()),
+            &[trust_override],
+            LoaderOverrides::default(),
+            CloudRequirementsLoader::default(),
+        )
+        .await?;
+        let cfg = deserialize_config_toml_with_base(
+            config_layer_stack.effective_config(),
+            codex_home.path(),
+        )?;
+
+        assert_eq!(cfg.model.as_deref(), Some("project"));
+        assert_eq!(
+            cfg.get_active_project(cwd.as_path()),
+            Some(ProjectConfig {
+                trust_level: Some(TrustLevel::Trusted),
+            })
+        );
+        assert!(!codex_home.path().join(CONFIG_TOML_FILE).exists());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn managed_config_wins_over_cli_overrides() -> anyhow::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2849,6 +4303,7 @@ bearer_token = "secret"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
 
@@ -2919,6 +4374,7 @@ ZIG_VAR = "3"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
 
@@ -2969,6 +4425,7 @@ ZIG_VAR = "3"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
 
@@ -3017,6 +4474,7 @@ ZIG_VAR = "3"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
 
@@ -3081,6 +4539,7 @@ startup_timeout_sec = 2.0
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
         apply_blocking(
@@ -3157,6 +4616,7 @@ X-Auth = "DOCS_AUTH"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
 
@@ -3186,6 +4646,7 @@ X-Auth = "DOCS_AUTH"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
         apply_blocking(
@@ -3253,6 +4714,7 @@ url = "https://example.com/mcp"
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    argument_validation: McpArgumentValidation::Strict,
                 },
             ),
             (
@@ -3272,6 +4734,7 @@ url = "https://example.com/mcp"
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    argument_validation: McpArgumentValidation::Strict,
                 },
             ),
         ]);
@@ -3354,6 +4817,7 @@ url = "https://example.com/mcp"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
 
@@ -3398,6 +4862,7 @@ url = "https://example.com/mcp"
                 enabled_tools: Some(vec!["allowed".to_string()]),
                 disabled_tools: Some(vec!["blocked".to_string()]),
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
 
@@ -3708,8 +5173,17 @@ model_verbosity = "high"
             request_max_retries: Some(4),
             stream_max_retries: Some(10),
             stream_idle_timeout_ms: Some(300_000),
+            first_byte_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            refresh_headers_per_attempt: false,
+            fallback_provider: None,
+            failover_after_failures: None,
+            disable_response_storage: false,
+            supports_file_uploads: false,
+            sandbox_mode: None,
+            approval_policy: None,
+            extra_body: None,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -3767,16 +5241,28 @@ model_verbosity = "high"
                 review_model: None,
                 model_context_window: None,
                 model_auto_compact_token_limit: None,
+                model_max_output_tokens: None,
+                model_sampling: SamplingParameters::default(),
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
+                approval_timeout: None,
+                thread_idle_timeout: Some(DEFAULT_THREAD_IDLE_TIMEOUT),
+                protect_dirty_files: false,
+                retry_stale_patches: false,
+                startup_probe: false,
+                watch_workspace: false,
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 forced_auto_mode_downgraded_on_windows: false,
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 user_instructions: None,
                 notify: None,
+                notify_events: HashMap::new(),
                 cwd: fixture.cwd(),
+                workspace_roots: Vec::new(),
+                session_title: None,
+                session_tags: Vec::new(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: Constrained::allow_any(HashMap::new()),
                 mcp_oauth_credentials_store_mode: Default::default(),
@@ -3785,11 +5271,19 @@ model_verbosity = "high"
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
+                tool_output_max_lines: None,
                 agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+                event_subscriber_capacity: DEFAULT_EVENT_SUBSCRIBER_CAPACITY,
                 codex_home: fixture.codex_home(),
                 config_layer_stack: Default::default(),
                 history: History::default(),
+                export_redact_patterns: Vec::new(),
                 ephemeral: false,
+                offline: false,
+                upload_attachments: false,
+                exec_audit: false,
+                dry_run_exec: false,
+                dotenv: None,
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
                 hide_agent_reasoning: false,
@@ -3797,18 +5291,32 @@ model_verbosity = "high"
                 model_reasoning_effort: Some(ReasoningEffort::High),
                 model_reasoning_summary: ReasoningSummary::Detailed,
                 model_supports_reasoning_summaries: None,
+                model_capabilities: HashMap::new(),
                 model_verbosity: None,
+                service_tier: None,
+                extra_body: serde_json::Map::new(),
                 model_personality: None,
+                preferred_language: None,
                 chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
                 base_instructions: None,
+                base_instructions_token_budget: DEFAULT_BASE_INSTRUCTIONS_TOKEN_BUDGET,
                 developer_instructions: None,
                 compact_prompt: None,
                 forced_chatgpt_workspace_id: None,
                 forced_login_method: None,
                 include_apply_patch_tool: false,
                 web_search_mode: None,
+                tool_toggles: ToolToggles::default(),
+                ask_mode: false,
+                injection_guard: InjectionGuardConfig::default(),
+                safe_commands: BTreeMap::new(),
+                workspace_summary: WorkspaceSummaryConfig::default(),
+                hooks: HooksConfig::default(),
                 use_experimental_unified_exec_tool: false,
                 ghost_snapshot: GhostSnapshotConfig::default(),
+                image_limits: ImageLimits::default(),
+                exec_limits: ExecLimits::default(),
+                max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
                 features: Features::with_defaults(),
                 suppress_unstable_features_warning: false,
                 active_profile: Some("o3".to_string()),
@@ -3825,6 +5333,11 @@ model_verbosity = "high"
                 analytics_enabled: Some(true),
                 feedback_enabled: true,
                 tui_alternate_screen: AltScreenMode::Auto,
+                tui_paste_to_file: PasteToFileSettings::default(),
+                tui_reasoning_cycle_key: None,
+                tui_reasoning_headlines: true,
+                tui_accessible: false,
+                tui_reduced_motion: false,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3851,16 +5364,28 @@ model_verbosity = "high"
             review_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_max_output_tokens: None,
+            model_sampling: SamplingParameters::default(),
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
+            approval_timeout: None,
+            thread_idle_timeout: Some(DEFAULT_THREAD_IDLE_TIMEOUT),
+            protect_dirty_files: false,
+            retry_stale_patches: false,
+            startup_probe: false,
+            watch_workspace: false,
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_events: HashMap::new(),
             cwd: fixture.cwd(),
+            workspace_roots: Vec::new(),
+            session_title: None,
+            session_tags: Vec::new(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
@@ -3869,11 +5394,19 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            tool_output_max_lines: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+            event_subscriber_capacity: DEFAULT_EVENT_SUBSCRIBER_CAPACITY,
             codex_home: fixture.codex_home(),
             config_layer_stack: Default::default(),
             history: History::default(),
+            export_redact_patterns: Vec::new(),
             ephemeral: false,
+            offline: false,
+            upload_attachments: false,
+            exec_audit: false,
+            dry_run_exec: false,
+            dotenv: None,
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3881,18 +5414,32 @@ model_verbosity = "high"
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
+            model_capabilities: HashMap::new(),
             model_verbosity: None,
+            service_tier: None,
+            extra_body: serde_json::Map::new(),
             model_personality: None,
+            preferred_language: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
+            base_instructions_token_budget: DEFAULT_BASE_INSTRUCTIONS_TOKEN_BUDGET,
             developer_instructions: None,
             compact_prompt: None,
             forced_chatgpt_workspace_id: None,
             forced_login_method: None,
             include_apply_patch_tool: false,
             web_search_mode: None,
+            tool_toggles: ToolToggles::default(),
+            ask_mode: false,
+            injection_guard: InjectionGuardConfig::default(),
+            safe_commands: BTreeMap::new(),
+            workspace_summary: WorkspaceSummaryConfig::default(),
+            hooks: HooksConfig::default(),
             use_experimental_unified_exec_tool: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            image_limits: ImageLimits::default(),
+            exec_limits: ExecLimits::default(),
+            max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt3".to_string()),
@@ -3909,6 +5456,11 @@ model_verbosity = "high"
             analytics_enabled: Some(true),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_paste_to_file: PasteToFileSettings::default(),
+            tui_reasoning_cycle_key: None,
+            tui_reasoning_headlines: true,
+            tui_accessible: false,
+            tui_reduced_motion: false,
             otel: OtelConfig::default(),
         };
 
@@ -3950,16 +5502,28 @@ model_verbosity = "high"
             review_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_max_output_tokens: None,
+            model_sampling: SamplingParameters::default(),
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
+            approval_timeout: None,
+            thread_idle_timeout: Some(DEFAULT_THREAD_IDLE_TIMEOUT),
+            protect_dirty_files: false,
+            retry_stale_patches: false,
+            startup_probe: false,
+            watch_workspace: false,
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_events: HashMap::new(),
             cwd: fixture.cwd(),
+            workspace_roots: Vec::new(),
+            session_title: None,
+            session_tags: Vec::new(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
@@ -3968,11 +5532,19 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            tool_output_max_lines: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+            event_subscriber_capacity: DEFAULT_EVENT_SUBSCRIBER_CAPACITY,
             codex_home: fixture.codex_home(),
             config_layer_stack: Default::default(),
             history: History::default(),
+            export_redact_patterns: Vec::new(),
             ephemeral: false,
+            offline: false,
+            upload_attachments: false,
+            exec_audit: false,
+            dry_run_exec: false,
+            dotenv: None,
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3980,18 +5552,32 @@ model_verbosity = "high"
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
+            model_capabilities: HashMap::new(),
             model_verbosity: None,
+            service_tier: None,
+            extra_body: serde_json::Map::new(),
             model_personality: None,
+            preferred_language: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
+            base_instructions_token_budget: DEFAULT_BASE_INSTRUCTIONS_TOKEN_BUDGET,
             developer_instructions: None,
             compact_prompt: None,
             forced_chatgpt_workspace_id: None,
             forced_login_method: None,
             include_apply_patch_tool: false,
             web_search_mode: None,
+            tool_toggles: ToolToggles::default(),
+            ask_mode: false,
+            injection_guard: InjectionGuardConfig::default(),
+            safe_commands: BTreeMap::new(),
+            workspace_summary: WorkspaceSummaryConfig::default(),
+            hooks: HooksConfig::default(),
             use_experimental_unified_exec_tool: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            image_limits: ImageLimits::default(),
+            exec_limits: ExecLimits::default(),
+            max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("zdr".to_string()),
@@ -4008,6 +5594,11 @@ model_verbosity = "high"
             analytics_enabled: Some(false),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_paste_to_file: PasteToFileSettings::default(),
+            tui_reasoning_cycle_key: None,
+            tui_reasoning_headlines: true,
+            tui_accessible: false,
+            tui_reduced_motion: false,
             otel: OtelConfig::default(),
         };
 
@@ -4035,16 +5626,28 @@ model_verbosity = "high"
             review_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_max_output_tokens: None,
+            model_sampling: SamplingParameters::default(),
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
+            approval_timeout: None,
+            thread_idle_timeout: Some(DEFAULT_THREAD_IDLE_TIMEOUT),
+            protect_dirty_files: false,
+            retry_stale_patches: false,
+            startup_probe: false,
+            watch_workspace: false,
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_events: HashMap::new(),
             cwd: fixture.cwd(),
+            workspace_roots: Vec::new(),
+            session_title: None,
+            session_tags: Vec::new(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
@@ -4053,11 +5656,19 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            tool_output_max_lines: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+            event_subscriber_capacity: DEFAULT_EVENT_SUBSCRIBER_CAPACITY,
             codex_home: fixture.codex_home(),
             config_layer_stack: Default::default(),
             history: History::default(),
+            export_redact_patterns: Vec::new(),
             ephemeral: false,
+            offline: false,
+            upload_attachments: false,
+            exec_audit: false,
+            dry_run_exec: false,
+            dotenv: None,
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -4065,18 +5676,32 @@ model_verbosity = "high"
             model_reasoning_effort: Some(ReasoningEffort::High),
             model_reasoning_summary: ReasoningSummary::Detailed,
             model_supports_reasoning_summaries: None,
+            model_capabilities: HashMap::new(),
             model_verbosity: Some(Verbosity::High),
+            service_tier: None,
+            extra_body: serde_json::Map::new(),
             model_personality: None,
+            preferred_language: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
+            base_instructions_token_budget: DEFAULT_BASE_INSTRUCTIONS_TOKEN_BUDGET,
             developer_instructions: None,
             compact_prompt: None,
             forced_chatgpt_workspace_id: None,
             forced_login_method: None,
             include_apply_patch_tool: false,
             web_search_mode: None,
+            tool_toggles: ToolToggles::default(),
+            ask_mode: false,
+            injection_guard: InjectionGuardConfig::default(),
+            safe_commands: BTreeMap::new(),
+            workspace_summary: WorkspaceSummaryConfig::default(),
+            hooks: HooksConfig::default(),
             use_experimental_unified_exec_tool: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            image_limits: ImageLimits::default(),
+            exec_limits: ExecLimits::default(),
+            max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt5".to_string()),
@@ -4093,6 +5718,11 @@ model_verbosity = "high"
             analytics_enabled: Some(true),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_paste_to_file: PasteToFileSettings::default(),
+            tui_reasoning_cycle_key: None,
+            tui_reasoning_headlines: true,
+            tui_accessible: false,
+            tui_reduced_motion: false,
             otel: OtelConfig::default(),
         };
 
@@ -7,6 +7,7 @@ use crate::config::types::Personality;
 use crate::protocol::AskForApproval;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::config_types::SandboxMode;
+use codex_protocol::config_types::ServiceTier;
 use codex_protocol::config_types::Verbosity;
 use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::openai_models::ReasoningEffort;
@@ -25,10 +26,20 @@ pub struct ConfigProfile {
     pub model_reasoning_effort: Option<ReasoningEffort>,
     pub model_reasoning_summary: Option<ReasoningSummary>,
     pub model_verbosity: Option<Verbosity>,
+    pub service_tier: Option<ServiceTier>,
+    /// Extra request body fields for this profile; merged over the top-level
+    /// `extra_body` table.
+    pub extra_body: Option<serde_json::Map<String, serde_json::Value>>,
     pub model_personality: Option<Personality>,
+    pub preferred_language: Option<String>,
     pub chatgpt_base_url: Option<String>,
     /// Optional path to a file containing model instructions.
+    #[serde(alias = "base_instructions_file")]
     pub model_instructions_file: Option<AbsolutePathBuf>,
+    /// Inline base instructions for this profile.
+    pub base_instructions: Option<String>,
+    /// Files appended, in order, to this profile's base instructions.
+    pub base_instructions_include: Option<Vec<AbsolutePathBuf>>,
     /// Deprecated: ignored. Use `model_instructions_file`.
     #[schemars(skip)]
     pub experimental_instructions_file: Option<AbsolutePathBuf>,
@@ -8,6 +8,9 @@ pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::openai_models::ApplyPatchToolType;
+use codex_protocol::protocol::HookPoint;
+use codex_protocol::protocol::ReviewDecision;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -77,6 +80,30 @@ pub struct McpServerConfig {
     /// Optional OAuth scopes to request during MCP login.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scopes: Option<Vec<String>>,
+
+    /// How tool call arguments are checked against each tool's `inputSchema`.
+    #[serde(default, skip_serializing_if = "McpArgumentValidation::is_strict")]
+    pub argument_validation: McpArgumentValidation,
+}
+
+/// How strictly tool call arguments are validated against the tool's declared
+/// `inputSchema` before the call is sent to the MCP server.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum McpArgumentValidation {
+    /// Reject calls with mismatched arguments and tell the model what to fix.
+    #[default]
+    Strict,
+    /// Log mismatches but send the call anyway.
+    Warn,
+    /// Send arguments as-is, for servers with intentionally loose schemas.
+    Off,
+}
+
+impl McpArgumentValidation {
+    pub fn is_strict(&self) -> bool {
+        matches!(self, Self::Strict)
+    }
 }
 
 // Raw MCP config shape used for deserialization and JSON Schema generation.
@@ -119,6 +146,8 @@ pub(crate) struct RawMcpServerConfig {
     pub disabled_tools: Option<Vec<String>>,
     #[serde(default)]
     pub scopes: Option<Vec<String>>,
+    #[serde(default)]
+    pub argument_validation: Option<McpArgumentValidation>,
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -141,6 +170,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
         let enabled_tools = raw.enabled_tools.clone();
         let disabled_tools = raw.disabled_tools.clone();
         let scopes = raw.scopes.clone();
+        let argument_validation = raw.argument_validation.unwrap_or_default();
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
         where
@@ -196,6 +226,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             enabled_tools,
             disabled_tools,
             scopes,
+            argument_validation,
         })
     }
 }
@@ -303,6 +334,18 @@ pub struct History {
     /// If set, the maximum size of the history file in bytes. The oldest entries
     /// are dropped once the file exceeds this limit.
     pub max_bytes: Option<usize>,
+
+    /// If set, the maximum number of entries kept in the history file. The
+    /// oldest entries are dropped once the file holds more than this many.
+    pub max_entries: Option<usize>,
+
+    /// If set, entries longer than this many bytes are truncated before they
+    /// are written, with a marker noting the truncation.
+    pub max_entry_bytes: Option<usize>,
+
+    /// How many of the most recent entries are checked for an identical entry
+    /// before appending a new one. Defaults to 20; `0` disables deduplication.
+    pub dedup_window: Option<usize>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Default, JsonSchema)]
@@ -332,6 +375,49 @@ pub struct FeedbackConfigToml {
     pub enabled: Option<bool>,
 }
 
+// ===== Model capabilities =====
+
+/// Capabilities declared in config.toml for a model name, typically a custom
+/// or proxied model Codex has no metadata for. Unset fields keep the values of
+/// the `based_on` model, or conservative defaults when there is none.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelCapabilitiesToml {
+    /// Known model whose metadata (instructions, tools, limits) this model
+    /// starts from, e.g. `gpt-5.1-codex`.
+    pub based_on: Option<String>,
+
+    /// Size of the context window, in tokens.
+    pub context_window: Option<i64>,
+
+    /// Token usage threshold that triggers automatic compaction.
+    pub auto_compact_token_limit: Option<i64>,
+
+    /// Largest `max_output_tokens` the model accepts.
+    pub max_output_tokens: Option<i64>,
+
+    /// Whether the model is a reasoning model: it takes a reasoning effort
+    /// and rejects sampling parameters such as `temperature`.
+    pub supports_reasoning: Option<bool>,
+
+    /// Whether the model accepts `reasoning.summary` on requests.
+    pub supports_reasoning_summaries: Option<bool>,
+
+    /// Whether the model may issue several tool calls in one turn.
+    pub supports_parallel_tool_calls: Option<bool>,
+
+    /// Dedicated `apply_patch` tool to expose: `freeform` or `function`.
+    pub apply_patch_tool_type: Option<ApplyPatchToolType>,
+
+    /// Use the generic base instructions with (`true`) or without (`false`)
+    /// the embedded `apply_patch` guide, for models without a dedicated tool.
+    pub needs_apply_patch_instructions: Option<bool>,
+
+    /// Describe tools in the instructions and read calls from the model's
+    /// text, for models that cannot call tools natively.
+    pub prompted_tools: Option<bool>,
+}
+
 // ===== OTEL configuration =====
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
@@ -415,6 +501,28 @@ impl Default for OtelConfig {
     }
 }
 
+/// Event types that can be delivered to the external `notify` program.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotifyEventKind {
+    /// A turn finished and the agent is waiting for input.
+    AgentTurnComplete,
+    /// A command or patch is blocked on an approval decision.
+    ApprovalRequested,
+}
+
+/// Per-event settings under `[notify_events.<kind>]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct NotifyEventToml {
+    /// Whether this event type is sent to `notify`. Defaults to `true`.
+    pub enabled: Option<bool>,
+    /// Minimum time between two notifications of this type. Approval
+    /// requests raised inside the window are sent when it ends, if they are
+    /// still pending; other events inside the window are dropped.
+    pub debounce_ms: Option<u64>,
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum Notifications {
@@ -486,12 +594,164 @@ pub struct Tui {
     /// scrollback in terminal multiplexers like Zellij that follow the xterm spec.
     #[serde(default)]
     pub alternate_screen: AltScreenMode,
+
+    /// Run the TUI inline: finalized history stays in the terminal's native
+    /// scrollback (including after exit) and only the composer and status area
+    /// are redrawn. Shorthand for `alternate_screen = "never"`.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub inline: bool,
+
+    /// What to do with pastes of at least `paste_to_file_min_lines` lines or
+    /// `paste_to_file_min_bytes` bytes.
+    ///
+    /// - `ask` (default): offer to save the paste under `.codex/pastes/` in the
+    ///   workspace and send a reference to the file instead of the text.
+    /// - `auto`: always save large pastes to a file.
+    /// - `off`: keep large pastes inline.
+    #[serde(default)]
+    pub paste_to_file: PasteToFileMode,
+
+    /// Line count at which a paste is handled by `paste_to_file`.
+    /// Defaults to `200`.
+    #[serde(default = "default_paste_to_file_min_lines")]
+    pub paste_to_file_min_lines: usize,
+
+    /// Size in bytes at which a paste is handled by `paste_to_file`.
+    /// Defaults to `16384`.
+    #[serde(default = "default_paste_to_file_min_bytes")]
+    pub paste_to_file_min_bytes: usize,
+
+    /// Key that cycles the reasoning effort used for the next turn, written
+    /// like `ctrl-r` or `alt-e`. Defaults to `ctrl-r`.
+    #[serde(default)]
+    pub reasoning_cycle_key: Option<String>,
+
+    /// Show the latest `**heading**` from the reasoning summary in the status
+    /// line while the model is thinking. Applies even when
+    /// `hide_agent_reasoning` is set. Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub reasoning_headlines: bool,
+
+    /// Screen-reader friendly rendering: no spinners or animation, plain text
+    /// labels in place of borders and bullets, streamed output appended whole
+    /// lines at a time, and approval prompts spelled out as questions.
+    /// Implies `reduced_motion`. Defaults to `false`.
+    #[serde(default)]
+    pub accessible: bool,
+
+    /// Turn off animations and append streamed output as soon as it arrives
+    /// instead of revealing it line by line. Defaults to `false`.
+    #[serde(default)]
+    pub reduced_motion: bool,
+}
+
+impl Tui {
+    /// Whether animations stay on once `reduced_motion` and `accessible` are
+    /// taken into account.
+    pub fn effective_animations(&self) -> bool {
+        self.animations && !self.effective_reduced_motion()
+    }
+
+    /// `reduced_motion`, which `accessible` turns on as well.
+    pub fn effective_reduced_motion(&self) -> bool {
+        self.reduced_motion || self.accessible
+    }
+
+    /// Alternate screen mode after applying the `inline` shorthand.
+    pub fn effective_alternate_screen(&self) -> AltScreenMode {
+        if self.inline {
+            AltScreenMode::Never
+        } else {
+            self.alternate_screen
+        }
+    }
+
+    pub fn paste_to_file_settings(&self) -> PasteToFileSettings {
+        PasteToFileSettings {
+            mode: self.paste_to_file,
+            min_lines: self.paste_to_file_min_lines,
+            min_bytes: self.paste_to_file_min_bytes,
+        }
+    }
+}
+
+/// Decision applied to an exec or patch approval request that receives no
+/// answer within `approval_timeout_seconds`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalTimeoutAction {
+    /// Deny the request and let the agent continue.
+    #[default]
+    Deny,
+    /// Deny the request and abort the turn.
+    Abort,
+}
+
+impl ApprovalTimeoutAction {
+    pub fn decision(self) -> ReviewDecision {
+        match self {
+            ApprovalTimeoutAction::Deny => ReviewDecision::Denied,
+            ApprovalTimeoutAction::Abort => ReviewDecision::Abort,
+        }
+    }
+}
+
+/// Resolved `approval_timeout_*` settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovalTimeout {
+    pub duration: Duration,
+    pub action: ApprovalTimeoutAction,
+}
+
+/// How the TUI handles large pastes. See [`Tui::paste_to_file`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PasteToFileMode {
+    #[default]
+    Ask,
+    Auto,
+    Off,
+}
+
+/// Resolved `tui.paste_to_file*` settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasteToFileSettings {
+    pub mode: PasteToFileMode,
+    pub min_lines: usize,
+    pub min_bytes: usize,
+}
+
+impl Default for PasteToFileSettings {
+    fn default() -> Self {
+        Self {
+            mode: PasteToFileMode::default(),
+            min_lines: default_paste_to_file_min_lines(),
+            min_bytes: default_paste_to_file_min_bytes(),
+        }
+    }
+}
+
+impl PasteToFileSettings {
+    /// Whether `text` is large enough to be handled by these settings.
+    pub fn applies_to(&self, text: &str) -> bool {
+        self.mode != PasteToFileMode::Off
+            && (text.len() >= self.min_bytes || text.lines().count() >= self.min_lines)
+    }
 }
 
 const fn default_true() -> bool {
     true
 }
 
+const fn default_paste_to_file_min_lines() -> usize {
+    200
+}
+
+const fn default_paste_to_file_min_bytes() -> usize {
+    16 * 1024
+}
+
 /// Settings for notices we display to users via the tui and app-server clients
 /// (primarily the Codex IDE extension). NOTE: these are different from
 /// notifications - notices are warnings, NUX screens, acknowledgements, etc.
@@ -589,6 +849,10 @@ pub struct ShellEnvironmentPolicyToml {
     pub include_only: Option<Vec<String>>,
 
     pub experimental_use_profile: Option<bool>,
+
+    /// Pass variables from the loaded dotenv file (`load_dotenv` or
+    /// `--env-file`) to spawned commands. Defaults to false.
+    pub inherit_dotenv: Option<bool>,
 }
 
 pub type EnvironmentVariablePattern = WildMatchPattern<'*', '?'>;
@@ -620,6 +884,10 @@ pub struct ShellEnvironmentPolicy {
 
     /// If true, the shell profile will be used to run the command.
     pub use_profile: bool,
+
+    /// If true, variables from the loaded dotenv file are added to the
+    /// starting set, below any real environment variable of the same name.
+    pub inherit_dotenv: bool,
 }
 
 impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
@@ -641,6 +909,7 @@ impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
             .map(|s| EnvironmentVariablePattern::new_case_insensitive(&s))
             .collect();
         let use_profile = toml.experimental_use_profile.unwrap_or(false);
+        let inherit_dotenv = toml.inherit_dotenv.unwrap_or(false);
 
         Self {
             inherit,
@@ -649,6 +918,7 @@ impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
             r#set,
             include_only,
             use_profile,
+            inherit_dotenv,
         }
     }
 }
@@ -662,10 +932,210 @@ impl Default for ShellEnvironmentPolicy {
             r#set: HashMap::new(),
             include_only: Vec::new(),
             use_profile: false,
+            inherit_dotenv: false,
         }
     }
 }
 
+/// Defenses against instructions smuggled in through tool output. Every knob
+/// is off unless set.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct InjectionGuardToml {
+    /// Wrap tool output in `<untrusted_content>` markers in the model request.
+    pub wrap_tool_output: Option<bool>,
+
+    /// Scan tool output for instruction-like text and emit
+    /// `EventMsg::InjectionSuspected` on a match.
+    pub scan: Option<bool>,
+
+    /// After a match, require approval for every untrusted command for the
+    /// rest of the turn (as if `approval_policy = "untrusted"`). Needs `scan`.
+    pub downgrade_approval: Option<bool>,
+
+    /// Additional case-insensitive regular expressions to flag.
+    pub extra_patterns: Option<Vec<String>>,
+
+    /// Ids of built-in patterns to turn off.
+    pub disabled_patterns: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InjectionGuardConfig {
+    pub wrap_tool_output: bool,
+    pub scan: bool,
+    pub downgrade_approval: bool,
+    pub extra_patterns: Vec<String>,
+    pub disabled_patterns: Vec<String>,
+}
+
+impl From<InjectionGuardToml> for InjectionGuardConfig {
+    fn from(toml: InjectionGuardToml) -> Self {
+        Self {
+            wrap_tool_output: toml.wrap_tool_output.unwrap_or(false),
+            scan: toml.scan.unwrap_or(false),
+            downgrade_approval: toml.downgrade_approval.unwrap_or(false),
+            extra_patterns: toml.extra_patterns.unwrap_or_default(),
+            disabled_patterns: toml.disabled_patterns.unwrap_or_default(),
+        }
+    }
+}
+
+pub const DEFAULT_WORKSPACE_SUMMARY_MAX_TOKENS: usize = 1_500;
+
+/// Overview of the git repository added to the initial context
+/// (`[workspace_summary]`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct WorkspaceSummaryToml {
+    /// Set to `false` to keep the repository layout, manifests and commit
+    /// history out of the context. Defaults to `true`.
+    pub enabled: Option<bool>,
+
+    /// Approximate token budget for the summary. Defaults to 1500.
+    pub max_tokens: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceSummaryConfig {
+    pub enabled: bool,
+    pub max_tokens: usize,
+}
+
+impl Default for WorkspaceSummaryConfig {
+    fn default() -> Self {
+        WorkspaceSummaryToml::default().into()
+    }
+}
+
+impl From<WorkspaceSummaryToml> for WorkspaceSummaryConfig {
+    fn from(toml: WorkspaceSummaryToml) -> Self {
+        Self {
+            enabled: toml.enabled.unwrap_or(true),
+            max_tokens: toml
+                .max_tokens
+                .unwrap_or(DEFAULT_WORKSPACE_SUMMARY_MAX_TOKENS),
+        }
+    }
+}
+
+/// Changes to the table of read-only programs that run without approval
+/// (`[safe_commands.<program>]`). Programs that are not built in are added.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SafeCommandToml {
+    /// Set to `false` to stop treating a built-in program as read-only.
+    pub enabled: Option<bool>,
+
+    /// Subcommands allowed as the first argument, e.g. `["status", "log"]`
+    /// for `git`. Replaces the built-in list.
+    pub subcommands: Option<Vec<String>>,
+
+    /// Options that make an invocation unsafe, added to the built-in ones.
+    /// `--opt` also matches `--opt=value`; `-x` also matches `-x` inside a
+    /// cluster such as `-Hx`.
+    pub deny_options: Option<Vec<String>>,
+}
+
+/// Commands run at fixed points of a session (`[hooks]`). Each list runs
+/// in order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HooksToml {
+    #[serde(default)]
+    pub pre_turn: Vec<HookToml>,
+    #[serde(default)]
+    pub post_turn: Vec<HookToml>,
+    #[serde(default)]
+    pub pre_exec: Vec<HookToml>,
+    #[serde(default)]
+    pub post_patch: Vec<HookToml>,
+}
+
+/// One command under `[[hooks.<point>]]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HookToml {
+    /// Program and arguments. The hook runs in the session's working
+    /// directory under the turn's sandbox and receives a JSON context object
+    /// on stdin.
+    pub command: Vec<String>,
+    /// Kill the hook after this long. Defaults to 60 seconds.
+    pub timeout_ms: Option<u64>,
+    /// What a failing hook does to the turn. Defaults to `warn`.
+    #[serde(default)]
+    pub on_failure: HookFailurePolicy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HookFailurePolicy {
+    /// Report the failure and carry on.
+    #[default]
+    Warn,
+    /// Report the failure and end the turn.
+    Abort,
+}
+
+pub const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HooksConfig {
+    pub pre_turn: Vec<HookCommand>,
+    pub post_turn: Vec<HookCommand>,
+    pub pre_exec: Vec<HookCommand>,
+    pub post_patch: Vec<HookCommand>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookCommand {
+    pub command: Vec<String>,
+    pub timeout: Duration,
+    pub on_failure: HookFailurePolicy,
+}
+
+impl HooksConfig {
+    pub fn for_point(&self, point: HookPoint) -> &[HookCommand] {
+        match point {
+            HookPoint::PreTurn => &self.pre_turn,
+            HookPoint::PostTurn => &self.post_turn,
+            HookPoint::PreExec => &self.pre_exec,
+            HookPoint::PostPatch => &self.post_patch,
+        }
+    }
+}
+
+impl TryFrom<HooksToml> for HooksConfig {
+    type Error = String;
+
+    fn try_from(toml: HooksToml) -> Result<Self, Self::Error> {
+        fn resolve(point: HookPoint, hooks: Vec<HookToml>) -> Result<Vec<HookCommand>, String> {
+            hooks
+                .into_iter()
+                .map(|hook| {
+                    if hook.command.first().is_none_or(String::is_empty) {
+                        return Err(format!("hooks.{point} has an empty command"));
+                    }
+                    Ok(HookCommand {
+                        command: hook.command,
+                        timeout: hook
+                            .timeout_ms
+                            .map_or(DEFAULT_HOOK_TIMEOUT, Duration::from_millis),
+                        on_failure: hook.on_failure,
+                    })
+                })
+                .collect()
+        }
+
+        Ok(Self {
+            pre_turn: resolve(HookPoint::PreTurn, toml.pre_turn)?,
+            post_turn: resolve(HookPoint::PostTurn, toml.post_turn)?,
+            pre_exec: resolve(HookPoint::PreExec, toml.pre_exec)?,
+            post_patch: resolve(HookPoint::PostPatch, toml.post_patch)?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
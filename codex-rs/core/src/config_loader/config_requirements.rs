@@ -8,6 +8,7 @@ use std::fmt;
 
 use super::requirements_exec_policy::RequirementsExecPolicy;
 use super::requirements_exec_policy::RequirementsExecPolicyToml;
+use super::safety_floor::SafetyFloorToml;
 use crate::config::Constrained;
 use crate::config::ConstraintError;
 
@@ -52,6 +53,8 @@ pub struct ConfigRequirements {
     pub sandbox_policy: Constrained<SandboxPolicy>,
     pub mcp_servers: Option<Sourced<BTreeMap<String, McpServerRequirement>>>,
     pub(crate) exec_policy: Option<Sourced<RequirementsExecPolicy>>,
+    /// Resolved per session once the profile and model provider are known.
+    pub safety_floor: Option<Sourced<SafetyFloorToml>>,
 }
 
 impl Default for ConfigRequirements {
@@ -61,6 +64,7 @@ impl Default for ConfigRequirements {
             sandbox_policy: Constrained::allow_any(SandboxPolicy::ReadOnly),
             mcp_servers: None,
             exec_policy: None,
+            safety_floor: None,
         }
     }
 }
@@ -84,6 +88,7 @@ pub struct ConfigRequirementsToml {
     pub allowed_sandbox_modes: Option<Vec<SandboxModeRequirement>>,
     pub mcp_servers: Option<BTreeMap<String, McpServerRequirement>>,
     pub rules: Option<RequirementsExecPolicyToml>,
+    pub safety_floor: Option<SafetyFloorToml>,
 }
 
 /// Value paired with the requirement source it came from, for better error
@@ -114,6 +119,7 @@ pub struct ConfigRequirementsWithSources {
     pub allowed_sandbox_modes: Option<Sourced<Vec<SandboxModeRequirement>>>,
     pub mcp_servers: Option<Sourced<BTreeMap<String, McpServerRequirement>>>,
     pub rules: Option<Sourced<RequirementsExecPolicyToml>>,
+    pub safety_floor: Option<Sourced<SafetyFloorToml>>,
 }
 
 impl ConfigRequirementsWithSources {
@@ -146,6 +152,7 @@ impl ConfigRequirementsWithSources {
                 allowed_sandbox_modes,
                 mcp_servers,
                 rules,
+                safety_floor,
             }
         );
     }
@@ -156,12 +163,14 @@ impl ConfigRequirementsWithSources {
             allowed_sandbox_modes,
             mcp_servers,
             rules,
+            safety_floor,
         } = self;
         ConfigRequirementsToml {
             allowed_approval_policies: allowed_approval_policies.map(|sourced| sourced.value),
             allowed_sandbox_modes: allowed_sandbox_modes.map(|sourced| sourced.value),
             mcp_servers: mcp_servers.map(|sourced| sourced.value),
             rules: rules.map(|sourced| sourced.value),
+            safety_floor: safety_floor.map(|sourced| sourced.value),
         }
     }
 }
@@ -199,6 +208,7 @@ impl ConfigRequirementsToml {
             && self.allowed_sandbox_modes.is_none()
             && self.mcp_servers.is_none()
             && self.rules.is_none()
+            && self.safety_floor.is_none()
     }
 }
 
@@ -211,6 +221,7 @@ impl TryFrom<ConfigRequirementsWithSources> for ConfigRequirements {
             allowed_sandbox_modes,
             mcp_servers,
             rules,
+            safety_floor,
         } = toml;
 
         let approval_policy: Constrained<AskForApproval> = match allowed_approval_policies {
@@ -303,6 +314,7 @@ impl TryFrom<ConfigRequirementsWithSources> for ConfigRequirements {
             sandbox_policy,
             mcp_servers,
             exec_policy,
+            safety_floor,
         })
     }
 }
@@ -329,6 +341,7 @@ mod tests {
             allowed_sandbox_modes,
             mcp_servers,
             rules,
+            safety_floor,
         } = toml;
         ConfigRequirementsWithSources {
             allowed_approval_policies: allowed_approval_policies
@@ -337,6 +350,7 @@ mod tests {
                 .map(|value| Sourced::new(value, RequirementSource::Unknown)),
             mcp_servers: mcp_servers.map(|value| Sourced::new(value, RequirementSource::Unknown)),
             rules: rules.map(|value| Sourced::new(value, RequirementSource::Unknown)),
+            safety_floor: safety_floor.map(|value| Sourced::new(value, RequirementSource::Unknown)),
         }
     }
 
@@ -358,6 +372,7 @@ mod tests {
             allowed_sandbox_modes: Some(allowed_sandbox_modes.clone()),
             mcp_servers: None,
             rules: None,
+            safety_floor: Some(SafetyFloorToml::default()),
         };
 
         target.merge_unset_fields(source.clone(), other);
@@ -369,9 +384,10 @@ mod tests {
                     allowed_approval_policies,
                     source.clone()
                 )),
-                allowed_sandbox_modes: Some(Sourced::new(allowed_sandbox_modes, source)),
+                allowed_sandbox_modes: Some(Sourced::new(allowed_sandbox_modes, source.clone())),
                 mcp_servers: None,
                 rules: None,
+                safety_floor: Some(Sourced::new(SafetyFloorToml::default(), source)),
             }
         );
     }
@@ -401,6 +417,7 @@ mod tests {
                 allowed_sandbox_modes: None,
                 mcp_servers: None,
                 rules: None,
+                safety_floor: None,
             }
         );
         Ok(())
@@ -438,6 +455,7 @@ mod tests {
                 allowed_sandbox_modes: None,
                 mcp_servers: None,
                 rules: None,
+                safety_floor: None,
             }
         );
         Ok(())
@@ -8,6 +8,7 @@ mod macos;
 mod merge;
 mod overrides;
 mod requirements_exec_policy;
+mod safety_floor;
 mod state;
 
 #[cfg(test)]
@@ -51,6 +52,10 @@ pub use diagnostics::format_config_error_with_source;
 pub(crate) use diagnostics::io_error_from_config_error;
 pub use merge::merge_toml_values;
 pub(crate) use overrides::build_cli_overrides_layer;
+pub use safety_floor::FloorLimit;
+pub use safety_floor::SafetyFloor;
+pub use safety_floor::SafetyFloorRule;
+pub use safety_floor::SafetyFloorToml;
 pub use state::ConfigLayerEntry;
 pub use state::ConfigLayerStack;
 pub use state::ConfigLayerStackOrdering;
@@ -59,6 +64,11 @@ pub use state::LoaderOverrides;
 /// On Unix systems, load requirements from this file path, if present.
 const DEFAULT_REQUIREMENTS_TOML_FILE_UNIX: &str = "/etc/codex/requirements.toml";
 
+/// On Unix systems, load the `safety_floor` requirement from this file path,
+/// if present. It takes precedence over `[safety_floor]` in
+/// `requirements.toml` and must be owned by root.
+const DEFAULT_SAFETY_FLOOR_TOML_FILE_UNIX: &str = "/etc/codex/safety_floor.toml";
+
 /// On Unix systems, load default settings from this file path, if present.
 /// Note that /etc/codex/ is treated as a "config folder," so subfolders such
 /// as skills/ and rules/ will also be honored.
@@ -72,6 +82,7 @@ const DEFAULT_PROJECT_ROOT_MARKERS: &[&str] = &[".git"];
 ///
 /// - admin:    managed preferences (*)
 /// - cloud:    managed cloud requirements
+/// - system    `/etc/codex/safety_floor.toml` (only the `safety_floor` section)
 /// - system    `/etc/codex/requirements.toml`
 ///
 /// For backwards compatibility, we also load from
@@ -119,8 +130,13 @@ pub async fn load_config_layers_state(
             .merge_unset_fields(RequirementSource::CloudRequirements, requirements);
     }
 
-    // Honor /etc/codex/requirements.toml.
+    // Honor /etc/codex/safety_floor.toml, then /etc/codex/requirements.toml.
     if cfg!(unix) {
+        load_safety_floor_toml(
+            &mut config_requirements_toml,
+            DEFAULT_SAFETY_FLOOR_TOML_FILE_UNIX,
+        )
+        .await?;
         load_requirements_toml(
             &mut config_requirements_toml,
             DEFAULT_REQUIREMENTS_TOML_FILE_UNIX,
@@ -333,6 +349,66 @@ async fn load_config_toml_for_required_layer(
     Ok(create_entry(toml_value))
 }
 
+/// If available, use `/etc/codex/safety_floor.toml` as the `safety_floor`
+/// requirement. The file is rejected unless it is owned by root and not
+/// writable by anyone else, since a floor that users can edit is no floor.
+async fn load_safety_floor_toml(
+    config_requirements_toml: &mut ConfigRequirementsWithSources,
+    safety_floor_toml_file: impl AsRef<Path>,
+) -> io::Result<()> {
+    let safety_floor_toml_file =
+        AbsolutePathBuf::from_absolute_path(safety_floor_toml_file.as_ref())?;
+    let metadata = match tokio::fs::metadata(&safety_floor_toml_file).await {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => {
+            return Err(io::Error::new(
+                e.kind(),
+                format!(
+                    "Failed to read safety floor file {}: {e}",
+                    safety_floor_toml_file.as_ref().display(),
+                ),
+            ));
+        }
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        if metadata.uid() != 0 || metadata.mode() & 0o022 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "Refusing to use safety floor file {}: it must be owned by root and not writable by group or others",
+                    safety_floor_toml_file.as_ref().display(),
+                ),
+            ));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = metadata;
+
+    let contents = tokio::fs::read_to_string(&safety_floor_toml_file).await?;
+    let safety_floor: SafetyFloorToml = toml::from_str(&contents).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Error parsing safety floor file {}: {e}",
+                safety_floor_toml_file.as_ref().display(),
+            ),
+        )
+    })?;
+    config_requirements_toml.merge_unset_fields(
+        RequirementSource::SystemRequirementsToml {
+            file: safety_floor_toml_file.clone(),
+        },
+        ConfigRequirementsToml {
+            safety_floor: Some(safety_floor),
+            ..Default::default()
+        },
+    );
+    Ok(())
+}
+
 /// If available, apply requirements from `/etc/codex/requirements.toml` to
 /// `config_requirements_toml` by filling in any unset fields.
 async fn load_requirements_toml(
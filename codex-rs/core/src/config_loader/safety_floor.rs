@@ -0,0 +1,346 @@
+//! Admin-enforced limits on how permissive a session's sandbox and approval
+//! policy may be, optionally narrowed per profile and per model provider.
+//!
+//! Unlike `allowed_sandbox_modes` and `allowed_approval_policies`, which apply
+//! to every session alike, a safety floor can depend on which profile and
+//! model provider the session resolves to. The strictest applicable rule wins.
+
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use super::RequirementSource;
+use super::Sourced;
+use crate::config::Constrained;
+use crate::config::ConstraintError;
+use crate::config::ConstraintResult;
+
+/// `[safety_floor]` in `requirements.toml`, or the whole of
+/// `/etc/codex/safety_floor.toml`.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SafetyFloorToml {
+    /// The most permissive sandbox mode any session may use.
+    pub max_sandbox_mode: Option<SandboxMode>,
+    /// The most permissive approval policy any session may use.
+    pub min_approval_policy: Option<AskForApproval>,
+    /// Additional limits for sessions using the named profile.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, SafetyFloorRule>,
+    /// Additional limits for sessions using the named model provider.
+    #[serde(default)]
+    pub model_providers: BTreeMap<String, SafetyFloorRule>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct SafetyFloorRule {
+    pub max_sandbox_mode: Option<SandboxMode>,
+    pub min_approval_policy: Option<AskForApproval>,
+}
+
+/// One limit of a resolved [`SafetyFloor`], with the rule that imposed it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloorLimit<T> {
+    pub value: T,
+    /// e.g. `safety_floor.profiles.prod-incident`.
+    pub rule: String,
+    pub source: RequirementSource,
+}
+
+/// The limits that apply to a single session.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SafetyFloor {
+    pub max_sandbox_mode: Option<FloorLimit<SandboxMode>>,
+    pub min_approval_policy: Option<FloorLimit<AskForApproval>>,
+}
+
+impl SafetyFloor {
+    /// Combine the global, model provider and profile rules that apply to a
+    /// session. When several rules limit the same setting, the strictest one
+    /// wins; on a tie the more general rule is reported.
+    pub fn resolve(
+        floor: Option<&Sourced<SafetyFloorToml>>,
+        profile: Option<&str>,
+        model_provider: &str,
+    ) -> Self {
+        let Some(floor) = floor else {
+            return Self::default();
+        };
+        let mut rules = vec![(
+            "safety_floor".to_string(),
+            SafetyFloorRule {
+                max_sandbox_mode: floor.max_sandbox_mode,
+                min_approval_policy: floor.min_approval_policy,
+            },
+        )];
+        if let Some(rule) = floor.model_providers.get(model_provider) {
+            rules.push((
+                format!("safety_floor.model_providers.{model_provider}"),
+                *rule,
+            ));
+        }
+        if let Some(profile) = profile
+            && let Some(rule) = floor.profiles.get(profile)
+        {
+            rules.push((format!("safety_floor.profiles.{profile}"), *rule));
+        }
+
+        let mut resolved = Self::default();
+        for (name, rule) in rules {
+            if let Some(mode) = rule.max_sandbox_mode
+                && resolved
+                    .max_sandbox_mode
+                    .as_ref()
+                    .is_none_or(|current| sandbox_rank(mode) < sandbox_rank(current.value))
+            {
+                resolved.max_sandbox_mode = Some(FloorLimit {
+                    value: mode,
+                    rule: name.clone(),
+                    source: floor.source.clone(),
+                });
+            }
+            if let Some(policy) = rule.min_approval_policy
+                && resolved
+                    .min_approval_policy
+                    .as_ref()
+                    .is_none_or(|current| approval_rank(policy) < approval_rank(current.value))
+            {
+                resolved.min_approval_policy = Some(FloorLimit {
+                    value: policy,
+                    rule: name,
+                    source: floor.source.clone(),
+                });
+            }
+        }
+        resolved
+    }
+
+    /// Reject a sandbox mode that is more permissive than the floor.
+    pub fn check_sandbox_mode(&self, mode: SandboxMode) -> ConstraintResult<()> {
+        self.check_sandbox_rank(sandbox_rank(mode), &mode.to_string())
+    }
+
+    /// Reject a sandbox policy that is more permissive than the floor.
+    /// `external-sandbox` counts as `danger-full-access`, since Codex cannot
+    /// tell what the external sandbox allows.
+    pub fn check_sandbox_policy(&self, policy: &SandboxPolicy) -> ConstraintResult<()> {
+        let (rank, name) = match policy {
+            SandboxPolicy::ReadOnly => (0, "read-only"),
+            SandboxPolicy::WorkspaceWrite { .. } => (1, "workspace-write"),
+            SandboxPolicy::ExternalSandbox { .. } => (2, "external-sandbox"),
+            SandboxPolicy::DangerFullAccess => (2, "danger-full-access"),
+        };
+        self.check_sandbox_rank(rank, name)
+    }
+
+    /// Reject an approval policy that is more permissive than the floor.
+    pub fn check_approval_policy(&self, policy: AskForApproval) -> ConstraintResult<()> {
+        match &self.min_approval_policy {
+            Some(limit) if approval_rank(policy) > approval_rank(limit.value) => {
+                Err(ConstraintError::ExceedsSafetyFloor {
+                    field_name: "approval_policy",
+                    candidate: policy.to_string(),
+                    floor: limit.value.to_string(),
+                    rule: limit.rule.clone(),
+                    requirement_source: limit.source.clone(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `policy`, or the floor's approval policy if `policy` is more
+    /// permissive than it.
+    pub fn clamp_approval_policy(&self, policy: AskForApproval) -> AskForApproval {
+        match &self.min_approval_policy {
+            Some(limit) if approval_rank(policy) > approval_rank(limit.value) => limit.value,
+            _ => policy,
+        }
+    }
+
+    /// Extend the requirement constraints so that later changes, such as
+    /// `/approvals`, cannot go past the floor either.
+    pub fn constrain(
+        &self,
+        approval_policy: Constrained<AskForApproval>,
+        sandbox_policy: Constrained<SandboxPolicy>,
+    ) -> ConstraintResult<(Constrained<AskForApproval>, Constrained<SandboxPolicy>)> {
+        let approval_floor = self.clone();
+        let sandbox_floor = self.clone();
+        Ok((
+            approval_policy
+                .and_validator(move |policy| approval_floor.check_approval_policy(*policy))?,
+            sandbox_policy
+                .and_validator(move |policy| sandbox_floor.check_sandbox_policy(policy))?,
+        ))
+    }
+
+    fn check_sandbox_rank(&self, rank: u8, candidate: &str) -> ConstraintResult<()> {
+        match &self.max_sandbox_mode {
+            Some(limit) if rank > sandbox_rank(limit.value) => {
+                Err(ConstraintError::ExceedsSafetyFloor {
+                    field_name: "sandbox_mode",
+                    candidate: candidate.to_string(),
+                    floor: limit.value.to_string(),
+                    rule: limit.rule.clone(),
+                    requirement_source: limit.source.clone(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Higher is more permissive.
+fn sandbox_rank(mode: SandboxMode) -> u8 {
+    match mode {
+        SandboxMode::ReadOnly => 0,
+        SandboxMode::WorkspaceWrite => 1,
+        SandboxMode::DangerFullAccess => 2,
+    }
+}
+
+/// Higher is more permissive.
+fn approval_rank(policy: AskForApproval) -> u8 {
+    match policy {
+        AskForApproval::UnlessTrusted => 0,
+        AskForApproval::OnFailure => 1,
+        AskForApproval::OnRequest => 2,
+        AskForApproval::Never => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn floor(toml: &str) -> Sourced<SafetyFloorToml> {
+        Sourced::new(
+            toml::from_str(toml).expect("valid safety floor"),
+            RequirementSource::Unknown,
+        )
+    }
+
+    const ORG_FLOOR: &str = r#"
+max_sandbox_mode = "workspace-write"
+
+[profiles.prod-incident]
+min_approval_policy = "on-request"
+
+[model_providers.local-experimental]
+max_sandbox_mode = "read-only"
+min_approval_policy = "untrusted"
+"#;
+
+    #[test]
+    fn strictest_applicable_rule_wins() {
+        let floor = floor(ORG_FLOOR);
+
+        let resolved = SafetyFloor::resolve(Some(&floor), Some("prod-incident"), "openai");
+        assert_eq!(
+            resolved
+                .max_sandbox_mode
+                .as_ref()
+                .map(|limit| (limit.value, limit.rule.as_str())),
+            Some((SandboxMode::WorkspaceWrite, "safety_floor"))
+        );
+        assert_eq!(
+            resolved
+                .min_approval_policy
+                .as_ref()
+                .map(|limit| (limit.value, limit.rule.as_str())),
+            Some((
+                AskForApproval::OnRequest,
+                "safety_floor.profiles.prod-incident"
+            ))
+        );
+
+        let resolved =
+            SafetyFloor::resolve(Some(&floor), Some("prod-incident"), "local-experimental");
+        assert_eq!(
+            resolved
+                .max_sandbox_mode
+                .as_ref()
+                .map(|limit| (limit.value, limit.rule.as_str())),
+            Some((
+                SandboxMode::ReadOnly,
+                "safety_floor.model_providers.local-experimental"
+            ))
+        );
+        assert_eq!(
+            resolved
+                .min_approval_policy
+                .as_ref()
+                .map(|limit| (limit.value, limit.rule.as_str())),
+            Some((
+                AskForApproval::UnlessTrusted,
+                "safety_floor.model_providers.local-experimental"
+            ))
+        );
+    }
+
+    #[test]
+    fn checks_and_clamps_against_the_floor() {
+        let floor = floor(ORG_FLOOR);
+        let resolved = SafetyFloor::resolve(Some(&floor), None, "openai");
+
+        assert_eq!(
+            resolved.check_sandbox_mode(SandboxMode::WorkspaceWrite),
+            Ok(())
+        );
+        assert_eq!(
+            resolved
+                .check_sandbox_mode(SandboxMode::DangerFullAccess)
+                .map_err(|err| err.to_string()),
+            Err("sandbox_mode `danger-full-access` is not allowed: safety_floor limits it to `workspace-write` (set by <unspecified>)".to_string())
+        );
+        assert!(
+            resolved
+                .check_sandbox_policy(&SandboxPolicy::ExternalSandbox {
+                    network_access: Default::default(),
+                })
+                .is_err()
+        );
+        assert_eq!(
+            resolved.check_sandbox_policy(&SandboxPolicy::ReadOnly),
+            Ok(())
+        );
+        assert_eq!(
+            resolved.clamp_approval_policy(AskForApproval::Never),
+            AskForApproval::Never,
+            "no approval floor applies outside the listed profiles and providers"
+        );
+
+        let resolved = SafetyFloor::resolve(Some(&floor), None, "local-experimental");
+        assert_eq!(
+            resolved.clamp_approval_policy(AskForApproval::OnRequest),
+            AskForApproval::UnlessTrusted
+        );
+        assert!(
+            resolved
+                .check_approval_policy(AskForApproval::OnFailure)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn constrained_values_cannot_be_loosened_later() -> anyhow::Result<()> {
+        let floor = floor(ORG_FLOOR);
+        let resolved = SafetyFloor::resolve(Some(&floor), None, "local-experimental");
+        let (mut approval_policy, mut sandbox_policy) = resolved.constrain(
+            Constrained::allow_any(AskForApproval::UnlessTrusted),
+            Constrained::allow_any(SandboxPolicy::ReadOnly),
+        )?;
+
+        assert!(approval_policy.set(AskForApproval::Never).is_err());
+        assert!(
+            sandbox_policy
+                .set(SandboxPolicy::new_workspace_write_policy())
+                .is_err()
+        );
+        assert_eq!(approval_policy.value(), AskForApproval::UnlessTrusted);
+        Ok(())
+    }
+}
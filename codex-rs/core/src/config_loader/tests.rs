@@ -15,6 +15,7 @@ use crate::config_loader::config_requirements::ConfigRequirementsWithSources;
 use crate::config_loader::config_requirements::RequirementSource;
 use crate::config_loader::fingerprint::version_for_toml;
 use crate::config_loader::load_requirements_toml;
+use crate::config_loader::load_safety_floor_toml;
 use codex_protocol::config_types::TrustLevel;
 use codex_protocol::protocol::AskForApproval;
 #[cfg(target_os = "macos")]
@@ -483,6 +484,26 @@ allowed_approval_policies = ["never", "on-request"]
     Ok(())
 }
 
+#[cfg(unix)]
+#[tokio::test(flavor = "current_thread")]
+async fn safety_floor_file_writable_by_others_is_rejected() -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempdir()?;
+    let safety_floor_file = tmp.path().join("safety_floor.toml");
+    tokio::fs::write(&safety_floor_file, "max_sandbox_mode = \"read-only\"\n").await?;
+    tokio::fs::set_permissions(&safety_floor_file, std::fs::Permissions::from_mode(0o666)).await?;
+
+    let mut config_requirements_toml = ConfigRequirementsWithSources::default();
+    let err = load_safety_floor_toml(&mut config_requirements_toml, &safety_floor_file)
+        .await
+        .expect_err("world-writable safety floor should be rejected");
+
+    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    assert_eq!(config_requirements_toml.safety_floor, None);
+    Ok(())
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn cloud_requirements_are_not_overwritten_by_system_requirements() -> anyhow::Result<()> {
     let tmp = tempdir()?;
@@ -503,6 +524,7 @@ allowed_approval_policies = ["on-request"]
             allowed_sandbox_modes: None,
             mcp_servers: None,
             rules: None,
+            safety_floor: None,
         },
     );
     load_requirements_toml(&mut config_requirements_toml, &requirements_file).await?;
@@ -537,6 +559,7 @@ async fn load_config_layers_includes_cloud_requirements() -> anyhow::Result<()>
         allowed_sandbox_modes: None,
         mcp_servers: None,
         rules: None,
+        safety_floor: None,
     };
     let expected = requirements.clone();
     let cloud_requirements = CloudRequirementsLoader::new(async move { Some(requirements) });
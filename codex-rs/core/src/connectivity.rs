@@ -0,0 +1,62 @@
+//! Cheap reachability checks for the model provider.
+//!
+//! A turn started without network access spends minutes in the request retry
+//! loop before failing. Probing the provider first lets the session queue the
+//! prompt instead, and resubmit it once a later probe succeeds.
+
+use std::time::Duration;
+
+use crate::default_client::build_reqwest_client;
+
+/// How long a single probe waits before treating the provider as unreachable.
+pub(crate) const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often prompts queued while offline are retried.
+pub(crate) const OFFLINE_RETRY_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Whether `url` answers a `HEAD` request within `timeout`. Only connection
+/// failures and timeouts mean the network is down: any response, including an
+/// error status or something that is not HTTP at all, proves the host is up.
+pub(crate) async fn is_reachable(url: &str, timeout: Duration) -> bool {
+    match build_reqwest_client()
+        .head(url)
+        .timeout(timeout)
+        .send()
+        .await
+    {
+        Err(err) if err.is_connect() || err.is_timeout() => {
+            tracing::debug!("reachability probe to {url} failed: {err}");
+            false
+        }
+        Ok(_) | Err(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+
+    #[tokio::test]
+    async fn any_http_response_counts_as_reachable() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        assert!(is_reachable(&format!("{}/v1", server.uri()), PROBE_TIMEOUT).await);
+    }
+
+    #[tokio::test]
+    async fn refused_connection_is_unreachable() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        drop(listener);
+
+        assert!(!is_reachable(&format!("http://{addr}/v1"), PROBE_TIMEOUT).await);
+    }
+}
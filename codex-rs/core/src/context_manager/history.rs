@@ -93,24 +93,7 @@ impl ContextManager {
         let base_tokens = i64::try_from(approx_token_count(&base_instructions)).unwrap_or(i64::MAX);
 
         let items_tokens = self.items.iter().fold(0i64, |acc, item| {
-            acc + match item {
-                ResponseItem::GhostSnapshot { .. } => 0,
-                ResponseItem::Reasoning {
-                    encrypted_content: Some(content),
-                    ..
-                }
-                | ResponseItem::Compaction {
-                    encrypted_content: content,
-                } => {
-                    let reasoning_bytes = estimate_reasoning_length(content.len());
-                    i64::try_from(approx_tokens_from_byte_count(reasoning_bytes))
-                        .unwrap_or(i64::MAX)
-                }
-                item => {
-                    let serialized = serde_json::to_string(item).unwrap_or_default();
-                    i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
-                }
-            }
+            acc.saturating_add(estimate_item_tokens(item))
         });
 
         Some(base_tokens.saturating_add(items_tokens))
@@ -301,7 +284,7 @@ impl ContextManager {
             | ResponseItem::CustomToolCall { .. }
             | ResponseItem::Compaction { .. }
             | ResponseItem::GhostSnapshot { .. }
-            | ResponseItem::Other => item.clone(),
+            | ResponseItem::Other(_) => item.clone(),
         }
     }
 }
@@ -319,8 +302,29 @@ fn is_api_message(message: &ResponseItem) -> bool {
         | ResponseItem::Reasoning { .. }
         | ResponseItem::WebSearchCall { .. }
         | ResponseItem::Compaction { .. } => true,
-        ResponseItem::GhostSnapshot { .. } => false,
-        ResponseItem::Other => false,
+        ResponseItem::GhostSnapshot { .. } | ResponseItem::Other(_) => false,
+    }
+}
+
+/// Byte-heuristic token estimate for one history item, as used by
+/// [`ContextManager::estimate_token_count`].
+pub(crate) fn estimate_item_tokens(item: &ResponseItem) -> i64 {
+    match item {
+        ResponseItem::GhostSnapshot { .. } => 0,
+        ResponseItem::Reasoning {
+            encrypted_content: Some(content),
+            ..
+        }
+        | ResponseItem::Compaction {
+            encrypted_content: content,
+        } => {
+            let reasoning_bytes = estimate_reasoning_length(content.len());
+            i64::try_from(approx_tokens_from_byte_count(reasoning_bytes)).unwrap_or(i64::MAX)
+        }
+        item => {
+            let serialized = serde_json::to_string(item).unwrap_or_default();
+            i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
+        }
     }
 }
 
@@ -359,7 +363,7 @@ pub(crate) fn is_user_turn_boundary(item: &ResponseItem) -> bool {
                     return false;
                 }
             }
-            ContentItem::InputImage { .. } => {}
+            ContentItem::InputImage { .. } | ContentItem::InputFile { .. } => {}
         }
     }
 
@@ -99,7 +99,8 @@ fn filters_non_api_messages() {
         end_turn: None,
     };
     let reasoning = reasoning_msg("thinking...");
-    h.record_items([&system, &reasoning, &ResponseItem::Other], policy);
+    let unknown = ResponseItem::Other(serde_json::json!({"type": "future_item"}));
+    h.record_items([&system, &reasoning, &unknown], policy);
 
     // User and assistant should be retained.
     let u = user_msg("hi");
@@ -166,6 +167,7 @@ fn non_last_reasoning_tokens_ignore_entries_after_last_user() {
 fn get_history_for_prompt_drops_ghost_commits() {
     let items = vec![ResponseItem::GhostSnapshot {
         ghost_commit: GhostCommit::new("ghost-1".to_string(), None, Vec::new(), Vec::new()),
+        turn: None,
     }];
     let history = create_history_with_items(items);
     let filtered = history.for_prompt();
@@ -2,4 +2,5 @@ mod history;
 mod normalize;
 
 pub(crate) use history::ContextManager;
+pub(crate) use history::estimate_item_tokens;
 pub(crate) use history::is_user_turn_boundary;
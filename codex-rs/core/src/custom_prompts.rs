@@ -1,4 +1,6 @@
+use crate::git_info::get_git_repo_root;
 use codex_protocol::custom_prompts::CustomPrompt;
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
@@ -12,6 +14,42 @@ pub fn default_prompts_dir() -> Option<PathBuf> {
         .map(|home| home.join("prompts"))
 }
 
+/// Return the project prompts directory: `.codex/prompts` under the Git root
+/// containing `cwd`, or under `cwd` itself outside a repository.
+pub fn project_prompts_dir(cwd: &Path) -> PathBuf {
+    get_git_repo_root(cwd)
+        .unwrap_or_else(|| cwd.to_path_buf())
+        .join(".codex")
+        .join("prompts")
+}
+
+/// Discover prompts from `$CODEX_HOME/prompts` and the project prompts
+/// directory for `cwd`. A project prompt replaces a user prompt with the same
+/// name. Returns entries sorted by name.
+pub async fn discover_custom_prompts(cwd: &Path) -> Vec<CustomPrompt> {
+    let mut dirs: Vec<PathBuf> = default_prompts_dir().into_iter().collect();
+    dirs.push(project_prompts_dir(cwd));
+    discover_prompts_in_dirs(&dirs).await
+}
+
+/// Discover prompts across `dirs`, where a prompt in a later directory
+/// replaces one with the same name from an earlier directory. Returns entries
+/// sorted by name.
+pub async fn discover_prompts_in_dirs(dirs: &[PathBuf]) -> Vec<CustomPrompt> {
+    let mut by_name: BTreeMap<String, CustomPrompt> = BTreeMap::new();
+    let mut seen_dirs: HashSet<PathBuf> = HashSet::new();
+    for dir in dirs {
+        let canonical = dunce::canonicalize(dir).unwrap_or_else(|_| dir.clone());
+        if !seen_dirs.insert(canonical) {
+            continue;
+        }
+        for prompt in discover_prompts_in(dir).await {
+            by_name.insert(prompt.name.clone(), prompt);
+        }
+    }
+    by_name.into_values().collect()
+}
+
 /// Discover prompt files in the given directory, returning entries sorted by name.
 /// Non-files are ignored. If the directory does not exist or cannot be read, returns empty.
 pub async fn discover_prompts_in(dir: &Path) -> Vec<CustomPrompt> {
@@ -63,6 +101,7 @@ pub async fn discover_prompts_in_excluding(
             Err(_) => continue,
         };
         let (description, argument_hint, body) = parse_frontmatter(&content);
+        let description = description.or_else(|| first_line_description(&body));
         out.push(CustomPrompt {
             name,
             path,
@@ -75,6 +114,14 @@ pub async fn discover_prompts_in_excluding(
     out
 }
 
+/// Description used when a prompt has no `description` frontmatter: the first
+/// non-empty line of the body, without leading Markdown heading markers.
+fn first_line_description(body: &str) -> Option<String> {
+    let line = body.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let line = line.trim_start_matches('#').trim();
+    (!line.is_empty()).then(|| line.to_string())
+}
+
 /// Parse optional YAML-like frontmatter at the beginning of `content`.
 /// Supported keys:
 /// - `description`: short description shown in the slash popup
@@ -147,6 +194,7 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
     use std::fs;
     use tempfile::tempdir;
 
@@ -233,6 +281,47 @@ mod tests {
         assert_eq!(p.content, "Actual body with $1 and $ARGUMENTS");
     }
 
+    #[tokio::test]
+    async fn description_falls_back_to_first_line() {
+        let tmp = tempdir().expect("create TempDir");
+        let dir = tmp.path();
+        fs::write(
+            dir.join("commit.md"),
+            "\n# Write a conventional commit message\n\nUse the staged diff.\n",
+        )
+        .unwrap();
+
+        let found = discover_prompts_in(dir).await;
+        assert_eq!(
+            found[0].description.as_deref(),
+            Some("Write a conventional commit message")
+        );
+    }
+
+    #[tokio::test]
+    async fn project_prompts_replace_user_prompts_with_same_name() {
+        let user = tempdir().expect("create TempDir");
+        let project = tempdir().expect("create TempDir");
+        fs::write(user.path().join("commit.md"), "user commit").unwrap();
+        fs::write(user.path().join("docs.md"), "user docs").unwrap();
+        fs::write(project.path().join("commit.md"), "project commit").unwrap();
+
+        let found =
+            discover_prompts_in_dirs(&[user.path().to_path_buf(), project.path().to_path_buf()])
+                .await;
+        let contents: Vec<(String, String)> = found
+            .into_iter()
+            .map(|prompt| (prompt.name, prompt.content))
+            .collect();
+        assert_eq!(
+            contents,
+            vec![
+                ("commit".to_string(), "project commit".to_string()),
+                ("docs".to_string(), "user docs".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn parse_frontmatter_preserves_body_newlines() {
         let content = "---\r\ndescription: \"Line endings\"\r\nargument_hint: \"[arg]\"\r\n---\r\nFirst line\r\nSecond line\r\n";
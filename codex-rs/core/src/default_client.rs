@@ -5,6 +5,7 @@ use reqwest::header::HeaderValue;
 use std::sync::LazyLock;
 use std::sync::Mutex;
 use std::sync::RwLock;
+use std::time::Duration;
 
 /// Set this to add a suffix to the User-Agent string.
 ///
@@ -159,24 +160,75 @@ fn sanitize_user_agent(candidate: String, fallback: &str) -> String {
     }
 }
 
+/// How long an idle pooled connection is kept before it is closed.
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Idle connections kept per host. A session rarely has more than a couple of
+/// requests to the same provider in flight.
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+/// Interval for TCP and HTTP/2 keep-alive probes, short enough to keep proxies
+/// from silently dropping a connection between turns.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+const HTTP2_KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Everything that is baked into a `reqwest::Client` when it is built. Provider
+/// headers and auth are added per request, so providers share one client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ClientKey {
+    user_agent: String,
+    originator: HeaderValue,
+    no_proxy: bool,
+}
+
+/// The process-wide client and the settings it was built with. It is rebuilt
+/// only when those settings change, e.g. after `USER_AGENT_SUFFIX` is set.
+static SHARED_CLIENT: LazyLock<Mutex<Option<(ClientKey, reqwest::Client)>>> =
+    LazyLock::new(|| Mutex::new(None));
+
 /// Create an HTTP client with default `originator` and `User-Agent` headers set.
 pub fn create_client() -> CodexHttpClient {
     let inner = build_reqwest_client();
     CodexHttpClient::new(inner)
 }
 
+/// Returns the shared HTTP client. Clones share one connection pool, so
+/// sequential requests to the same host reuse a warm TCP/TLS connection
+/// instead of paying for a new handshake each time.
 pub fn build_reqwest_client() -> reqwest::Client {
+    let key = ClientKey {
+        user_agent: get_codex_user_agent(),
+        originator: originator().header_value,
+        no_proxy: is_sandboxed(),
+    };
+    let Ok(mut shared) = SHARED_CLIENT.lock() else {
+        return new_reqwest_client(&key);
+    };
+    if let Some((cached_key, client)) = shared.as_ref()
+        && *cached_key == key
+    {
+        return client.clone();
+    }
+    let client = new_reqwest_client(&key);
+    *shared = Some((key, client.clone()));
+    client
+}
+
+fn new_reqwest_client(key: &ClientKey) -> reqwest::Client {
     use reqwest::header::HeaderMap;
 
     let mut headers = HeaderMap::new();
-    headers.insert("originator", originator().header_value);
-    let ua = get_codex_user_agent();
+    headers.insert("originator", key.originator.clone());
 
     let mut builder = reqwest::Client::builder()
         // Set UA via dedicated helper to avoid header validation pitfalls
-        .user_agent(ua)
-        .default_headers(headers);
-    if is_sandboxed() {
+        .user_agent(key.user_agent.as_str())
+        .default_headers(headers)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .tcp_keepalive(KEEP_ALIVE_INTERVAL)
+        .http2_keep_alive_interval(KEEP_ALIVE_INTERVAL)
+        .http2_keep_alive_timeout(HTTP2_KEEP_ALIVE_TIMEOUT)
+        .http2_keep_alive_while_idle(true);
+    if key.no_proxy {
         builder = builder.no_proxy();
     }
 
@@ -258,6 +310,63 @@ mod tests {
         assert_eq!(ua_header.to_str().unwrap(), expected_ua);
     }
 
+    #[tokio::test]
+    async fn sequential_requests_reuse_one_connection() {
+        skip_if_no_network!();
+
+        use std::sync::Arc;
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::atomic::Ordering;
+        use tokio::io::AsyncReadExt;
+        use tokio::io::AsyncWriteExt;
+        use tokio::net::TcpListener;
+
+        // wiremock does not report connections, so count accepts on a minimal
+        // keep-alive HTTP/1.1 server instead.
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let accepts = Arc::new(AtomicUsize::new(0));
+        let server_accepts = Arc::clone(&accepts);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                server_accepts.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 1024];
+                    while let Ok(n) = socket.read(&mut chunk).await {
+                        if n == 0 {
+                            break;
+                        }
+                        buf.extend_from_slice(&chunk[..n]);
+                        while let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                            buf.drain(..end + 4);
+                            let response = b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok";
+                            if socket.write_all(response).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        let url = format!("http://{addr}/");
+        for _ in 0..2 {
+            // Each call stands in for a turn asking for a client of its own.
+            let body = build_reqwest_client()
+                .get(&url)
+                .send()
+                .await
+                .expect("request")
+                .text()
+                .await
+                .expect("body");
+            assert_eq!(body, "ok");
+        }
+
+        assert_eq!(accepts.load(Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn test_invalid_suffix_is_sanitized() {
         let prefix = "codex_cli_rs/0.0.0";
@@ -0,0 +1,909 @@
+//! Installation diagnostics behind `codex doctor` and the TUI's `/doctor`.
+//!
+//! [`run_doctor`] checks the configuration, credentials, provider
+//! connectivity, MCP servers, sandbox, git and `codex_home` one after another.
+//! Every check is timed and bounded by a timeout, and a failing check never
+//! stops the ones after it. [`DoctorReport::redacted`] removes credentials and
+//! the home directory so the report can be attached to a bug report.
+
+use std::fmt::Write as _;
+use std::future::Future;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::config_types::WindowsSandboxLevel;
+use serde::Serialize;
+
+use crate::auth::CodexAuth;
+use crate::auth::read_codex_api_key_from_env;
+use crate::config::Config;
+use crate::config::ConfigBuilder;
+use crate::config::types::McpServerTransportConfig;
+use crate::config_loader::ConfigLoadError;
+use crate::config_loader::format_config_error_with_source;
+use crate::connectivity;
+use crate::git_info::get_git_repo_root;
+use crate::model_provider_info::UrlTemplateVars;
+use crate::sandbox_self_test;
+use crate::sandbox_self_test::ProbeOutcome;
+use crate::token_data::jwt_expiration;
+use crate::transcript_export::Redactor;
+use crate::windows_sandbox::WindowsSandboxLevelExt;
+
+/// Upper bound for a single check, so one hung probe cannot stall the report.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The sandbox self-test runs several probes with their own timeouts.
+const SANDBOX_CHECK_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// How many of the largest entries in `codex_home` are listed.
+const LARGEST_ENTRIES: usize = 5;
+
+/// Secrets shorter than this are not redacted verbatim, so that an empty or
+/// trivial value cannot blank out unrelated text.
+const MIN_SECRET_LEN: usize = 8;
+
+/// JWTs that show up in the report without being known secrets, e.g. inside
+/// an error message.
+const JWT_PATTERN: &str = r"eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]*";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+    /// The check could not run because an earlier one failed.
+    Skipped,
+}
+
+impl CheckStatus {
+    fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warn",
+            CheckStatus::Error => "FAIL",
+            CheckStatus::Skipped => "skip",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub summary: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub codex_version: String,
+    pub generated_at: DateTime<Utc>,
+    pub checks: Vec<DoctorCheck>,
+    /// Credentials seen while running the checks, removed by
+    /// [`DoctorReport::redacted`].
+    #[serde(skip)]
+    secrets: Vec<String>,
+}
+
+impl DoctorReport {
+    pub fn has_errors(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|check| check.status == CheckStatus::Error)
+    }
+
+    /// A copy with known credentials, API keys, JWTs and the home directory
+    /// removed from every message.
+    pub fn redacted(&self) -> DoctorReport {
+        self.redacted_with_home(std::env::home_dir().as_deref())
+    }
+
+    fn redacted_with_home(&self, home: Option<&Path>) -> DoctorReport {
+        let mut patterns: Vec<String> = self
+            .secrets
+            .iter()
+            .filter(|secret| secret.len() >= MIN_SECRET_LEN)
+            .map(|secret| regex_lite::escape(secret))
+            .collect();
+        patterns.push(JWT_PATTERN.to_string());
+        let redactor = Redactor::new(&patterns).ok();
+        let home = home
+            .map(|home| home.to_string_lossy().into_owned())
+            .filter(|home| home.len() > 1);
+        let scrub = |text: &str| {
+            let text = match &redactor {
+                Some(redactor) => redactor.redact(text),
+                None => text.to_string(),
+            };
+            match &home {
+                Some(home) => text.replace(home.as_str(), "~"),
+                None => text,
+            }
+        };
+        DoctorReport {
+            codex_version: self.codex_version.clone(),
+            generated_at: self.generated_at,
+            checks: self
+                .checks
+                .iter()
+                .map(|check| DoctorCheck {
+                    summary: scrub(&check.summary),
+                    details: check.details.iter().map(|detail| scrub(detail)).collect(),
+                    ..check.clone()
+                })
+                .collect(),
+            secrets: Vec::new(),
+        }
+    }
+
+    /// One line per check followed by its details, ending with a tally.
+    pub fn to_text(&self) -> String {
+        let name_width = self
+            .checks
+            .iter()
+            .map(|check| check.name.len())
+            .max()
+            .unwrap_or(0);
+        let mut out = format!("codex doctor (codex {})\n\n", self.codex_version);
+        for check in &self.checks {
+            let _ = writeln!(
+                out,
+                "[{:<4}] {:<name_width$}  {} ({} ms)",
+                check.status.label(),
+                check.name,
+                check.summary,
+                check.duration_ms,
+            );
+            for detail in &check.details {
+                let _ = writeln!(out, "       {:<name_width$}    {detail}", "");
+            }
+        }
+        let count = |status| {
+            self.checks
+                .iter()
+                .filter(|check| check.status == status)
+                .count()
+        };
+        let _ = write!(
+            out,
+            "\n{} failed, {} warnings, {} skipped",
+            count(CheckStatus::Error),
+            count(CheckStatus::Warning),
+            count(CheckStatus::Skipped),
+        );
+        out
+    }
+}
+
+/// The result of a check before it is timed.
+struct CheckOutcome {
+    status: CheckStatus,
+    summary: String,
+    details: Vec<String>,
+}
+
+impl CheckOutcome {
+    fn new(status: CheckStatus, summary: impl Into<String>) -> Self {
+        Self {
+            status,
+            summary: summary.into(),
+            details: Vec::new(),
+        }
+    }
+
+    fn ok(summary: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Ok, summary)
+    }
+
+    fn warning(summary: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Warning, summary)
+    }
+
+    fn error(summary: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Error, summary)
+    }
+
+    fn needs_config() -> Self {
+        Self::new(
+            CheckStatus::Skipped,
+            "skipped because the configuration did not load",
+        )
+    }
+
+    fn details(mut self, details: impl IntoIterator<Item = String>) -> Self {
+        self.details.extend(details);
+        self
+    }
+}
+
+async fn timed(
+    name: &str,
+    timeout: Duration,
+    check: impl Future<Output = CheckOutcome>,
+) -> DoctorCheck {
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(timeout, check)
+        .await
+        .unwrap_or_else(|_| {
+            CheckOutcome::error(format!("timed out after {} s", timeout.as_secs_f32()))
+        });
+    DoctorCheck {
+        name: name.to_string(),
+        status: outcome.status,
+        summary: outcome.summary,
+        details: outcome.details,
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+    }
+}
+
+/// Runs every check against `codex_home`. `config` is built as the first
+/// check; checks that need the configuration are skipped when it fails to
+/// load, and all others still run.
+pub async fn run_doctor(codex_home: PathBuf, cwd: PathBuf, config: ConfigBuilder) -> DoctorReport {
+    let mut checks = Vec::new();
+    let mut secrets = Vec::new();
+
+    let mut loaded = None;
+    checks.push(
+        timed("config", CHECK_TIMEOUT, async {
+            match config.codex_home(codex_home.clone()).build().await {
+                Ok(config) => {
+                    let outcome = describe_config(&config);
+                    loaded = Some(config);
+                    outcome
+                }
+                Err(err) => describe_config_error(&err),
+            }
+        })
+        .await,
+    );
+    let config = loaded.as_ref();
+    let cwd = config.map_or(cwd, |config| config.cwd.clone());
+
+    let mut auth = None;
+    checks.push(
+        timed(
+            "auth",
+            CHECK_TIMEOUT,
+            check_auth(&codex_home, config, &mut auth, &mut secrets),
+        )
+        .await,
+    );
+    checks.push(
+        timed("provider", CHECK_TIMEOUT, async {
+            match config {
+                Some(config) => check_provider(config, auth.as_ref()).await,
+                None => CheckOutcome::needs_config(),
+            }
+        })
+        .await,
+    );
+    checks.push(
+        timed("mcp", CHECK_TIMEOUT, async {
+            match config {
+                Some(config) => check_mcp_servers(config, &cwd).await,
+                None => CheckOutcome::needs_config(),
+            }
+        })
+        .await,
+    );
+    checks.push(
+        timed(
+            "sandbox",
+            SANDBOX_CHECK_TIMEOUT,
+            check_sandbox(&codex_home, config),
+        )
+        .await,
+    );
+    checks.push(timed("git", CHECK_TIMEOUT, check_git(&cwd)).await);
+    checks.push(timed("codex_home", CHECK_TIMEOUT, check_codex_home(&codex_home)).await);
+    checks.push(timed("versions", CHECK_TIMEOUT, async { check_versions() }).await);
+
+    DoctorReport {
+        codex_version: env!("CARGO_PKG_VERSION").to_string(),
+        generated_at: Utc::now(),
+        checks,
+        secrets,
+    }
+}
+
+fn describe_config(config: &Config) -> CheckOutcome {
+    let config_path = config.codex_home.join(crate::config::CONFIG_TOML_FILE);
+    let summary = if config_path.exists() {
+        format!("loaded {}", config_path.display())
+    } else {
+        format!("no {} yet; using defaults", config_path.display())
+    };
+    let mut details = vec![
+        format!("model: {}", config.model.as_deref().unwrap_or("(default)")),
+        format!(
+            "provider: {} ({})",
+            config.model_provider_id, config.model_provider.name
+        ),
+    ];
+    if let Some(profile) = &config.active_profile {
+        details.push(format!("profile: {profile}"));
+    }
+    CheckOutcome::ok(summary).details(details)
+}
+
+fn describe_config_error(err: &std::io::Error) -> CheckOutcome {
+    let formatted = err
+        .get_ref()
+        .and_then(|err| err.downcast_ref::<ConfigLoadError>())
+        .map(|err| format_config_error_with_source(err.config_error()));
+    match formatted {
+        Some(formatted) => CheckOutcome::error("configuration does not parse")
+            .details(formatted.lines().map(str::to_string)),
+        None => CheckOutcome::error(format!("failed to load configuration: {err}")),
+    }
+}
+
+async fn check_auth(
+    codex_home: &Path,
+    config: Option<&Config>,
+    auth: &mut Option<CodexAuth>,
+    secrets: &mut Vec<String>,
+) -> CheckOutcome {
+    if let Some(config) = config
+        && !config.model_provider.requires_openai_auth
+    {
+        let provider = &config.model_provider;
+        return match (
+            &provider.env_key,
+            provider.api_key(config.dotenv.as_deref()),
+        ) {
+            (None, _) => CheckOutcome::ok(format!("{} does not need credentials", provider.name)),
+            (Some(env_key), Ok(Some(key))) => {
+                secrets.push(key);
+                CheckOutcome::ok(format!("API key found in {env_key}"))
+            }
+            (Some(env_key), Ok(None) | Err(_)) => {
+                CheckOutcome::error(format!("{env_key} is not set"))
+                    .details(provider.env_key_instructions.clone())
+            }
+        };
+    }
+
+    let store_mode = config
+        .map(|config| config.cli_auth_credentials_store_mode)
+        .unwrap_or_default();
+    let stored = match CodexAuth::from_auth_storage(codex_home, store_mode) {
+        Ok(stored) => stored,
+        Err(err) => return CheckOutcome::error(format!("failed to read credentials: {err}")),
+    };
+    let Some(stored) = stored else {
+        return match read_codex_api_key_from_env() {
+            Some(key) => {
+                secrets.push(key);
+                CheckOutcome::ok("using the API key in CODEX_API_KEY")
+            }
+            None => CheckOutcome::error("not logged in")
+                .details(["Run `codex login` to sign in.".to_string()]),
+        };
+    };
+
+    let outcome = if let Some(key) = stored.api_key() {
+        secrets.push(key.to_string());
+        CheckOutcome::ok("logged in with an API key")
+    } else {
+        match stored.get_token_data() {
+            Ok(tokens) => {
+                secrets.extend([
+                    tokens.access_token.clone(),
+                    tokens.refresh_token.clone(),
+                    tokens.id_token.raw_jwt.clone(),
+                ]);
+                describe_chatgpt_tokens(
+                    &tokens.access_token,
+                    tokens.id_token.get_chatgpt_plan_type(),
+                    Utc::now(),
+                )
+            }
+            Err(err) => CheckOutcome::error(format!("ChatGPT login is incomplete: {err}"))
+                .details(["Run `codex login` to sign in again.".to_string()]),
+        }
+    };
+    *auth = Some(stored);
+    outcome
+}
+
+fn describe_chatgpt_tokens(
+    access_token: &str,
+    plan: Option<String>,
+    now: DateTime<Utc>,
+) -> CheckOutcome {
+    let plan = plan.map(|plan| format!("plan: {plan}"));
+    let outcome = match jwt_expiration(access_token) {
+        Some(expires_at) if expires_at <= now => CheckOutcome::warning(format!(
+            "logged in with ChatGPT; access token expired {} ago",
+            format_duration(now - expires_at)
+        ))
+        .details([
+            "It is refreshed on the next request; run `codex login` if that fails.".to_string(),
+        ]),
+        Some(expires_at) => CheckOutcome::ok(format!(
+            "logged in with ChatGPT; access token valid for {}",
+            format_duration(expires_at - now)
+        )),
+        None => CheckOutcome::ok("logged in with ChatGPT"),
+    };
+    outcome.details(plan)
+}
+
+fn format_duration(duration: chrono::TimeDelta) -> String {
+    let minutes = duration.num_minutes();
+    if minutes >= 2 * 24 * 60 {
+        format!("{} days", minutes / (24 * 60))
+    } else if minutes >= 120 {
+        format!("{} hours", minutes / 60)
+    } else {
+        format!("{} min", minutes.max(0))
+    }
+}
+
+async fn check_provider(config: &Config, auth: Option<&CodexAuth>) -> CheckOutcome {
+    let provider = match config.model_provider.to_api_provider(
+        auth.map(CodexAuth::internal_auth_mode),
+        UrlTemplateVars {
+            model: config.model.as_deref(),
+            session_id: None,
+        },
+        config.dotenv.as_deref(),
+    ) {
+        Ok(provider) => provider,
+        Err(err) => return CheckOutcome::error(format!("cannot build the provider URL: {err}")),
+    };
+    let started = Instant::now();
+    if connectivity::is_reachable(&provider.base_url, connectivity::PROBE_TIMEOUT).await {
+        CheckOutcome::ok(format!(
+            "{} reachable in {} ms",
+            provider.base_url,
+            started.elapsed().as_millis()
+        ))
+    } else {
+        CheckOutcome::error(format!("{} is unreachable", provider.base_url)).details([format!(
+            "No response within {} s; check the network, proxy and `base_url`.",
+            connectivity::PROBE_TIMEOUT.as_secs()
+        )])
+    }
+}
+
+async fn check_mcp_servers(config: &Config, cwd: &Path) -> CheckOutcome {
+    let servers = config.mcp_servers.get();
+    if servers.is_empty() {
+        return CheckOutcome::ok("no MCP servers configured");
+    }
+    let mut names: Vec<&String> = servers.keys().collect();
+    names.sort();
+
+    let path = std::env::var_os("PATH");
+    let mut problems = 0;
+    let mut details = Vec::new();
+    for name in names {
+        let server = &servers[name];
+        if !server.enabled {
+            details.push(format!("{name}: disabled"));
+            continue;
+        }
+        let detail = match &server.transport {
+            McpServerTransportConfig::Stdio {
+                command,
+                cwd: server_cwd,
+                ..
+            } => {
+                let dir = server_cwd.as_deref().unwrap_or(cwd);
+                match which::which_in(command, path.as_ref(), dir) {
+                    Ok(resolved) => format!("{name}: {}", resolved.display()),
+                    Err(_) => {
+                        problems += 1;
+                        format!("{name}: command `{command}` not found")
+                    }
+                }
+            }
+            McpServerTransportConfig::StreamableHttp { url, .. } => {
+                if connectivity::is_reachable(url, connectivity::PROBE_TIMEOUT).await {
+                    format!("{name}: {url} reachable")
+                } else {
+                    problems += 1;
+                    format!("{name}: {url} is unreachable")
+                }
+            }
+        };
+        details.push(detail);
+    }
+    let outcome = if problems == 0 {
+        CheckOutcome::ok(format!("{} servers can be started", servers.len()))
+    } else {
+        CheckOutcome::warning(format!(
+            "{problems} of {} servers cannot be started",
+            servers.len()
+        ))
+    };
+    outcome.details(details)
+}
+
+async fn check_sandbox(codex_home: &Path, config: Option<&Config>) -> CheckOutcome {
+    let (report, cached) = match sandbox_self_test::load_cached_report(codex_home) {
+        Some(report) => (report, true),
+        None => {
+            let (exe, level) = match config {
+                Some(config) => (
+                    config.codex_linux_sandbox_exe.clone(),
+                    WindowsSandboxLevel::from_config(config),
+                ),
+                None => (None, WindowsSandboxLevel::default()),
+            };
+            let report = sandbox_self_test::run_sandbox_self_test(&exe, level).await;
+            if let Err(err) = sandbox_self_test::save_report(codex_home, &report) {
+                tracing::warn!("failed to cache sandbox self-test report: {err}");
+            }
+            (report, false)
+        }
+    };
+    let source = if cached {
+        "cached self-test"
+    } else {
+        "self-test"
+    };
+    let details = report.results.iter().map(|result| {
+        let outcome = match &result.outcome {
+            ProbeOutcome::Enforced => "enforced".to_string(),
+            ProbeOutcome::NotEnforced => "NOT enforced".to_string(),
+            ProbeOutcome::Inconclusive { reason } => format!("inconclusive ({reason})"),
+        };
+        format!("{}: {outcome}", result.capability.label())
+    });
+    let inconclusive = report
+        .results
+        .iter()
+        .filter(|result| matches!(result.outcome, ProbeOutcome::Inconclusive { .. }))
+        .count();
+    let outcome = match report.warning() {
+        Some(warning) => CheckOutcome::warning(warning),
+        None if inconclusive > 0 => CheckOutcome::warning(format!(
+            "{}: {inconclusive} probes were inconclusive",
+            report.sandbox
+        )),
+        None => CheckOutcome::ok(format!("{} enforces every restriction", report.sandbox)),
+    };
+    outcome
+        .details([format!("{source} on {}", report.platform)])
+        .details(details)
+}
+
+async fn check_git(cwd: &Path) -> CheckOutcome {
+    let output = tokio::process::Command::new("git")
+        .arg("--version")
+        .output()
+        .await;
+    let version = match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        Ok(output) => {
+            return CheckOutcome::warning(format!("`git --version` failed: {}", output.status));
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return CheckOutcome::warning("git is not installed")
+                .details(["Diffs, undo and review need git on PATH.".to_string()]);
+        }
+        Err(err) => return CheckOutcome::warning(format!("failed to run git: {err}")),
+    };
+    let repo = match get_git_repo_root(cwd) {
+        Some(root) => format!("repository: {}", root.display()),
+        None => format!("{} is not inside a git repository", cwd.display()),
+    };
+    CheckOutcome::ok(version).details([repo])
+}
+
+async fn check_codex_home(codex_home: &Path) -> CheckOutcome {
+    let codex_home = codex_home.to_path_buf();
+    match tokio::task::spawn_blocking(move || inspect_codex_home(&codex_home)).await {
+        Ok(outcome) => outcome,
+        Err(err) => CheckOutcome::error(format!("failed to inspect codex_home: {err}")),
+    }
+}
+
+fn inspect_codex_home(codex_home: &Path) -> CheckOutcome {
+    match std::fs::metadata(codex_home) {
+        Ok(metadata) if metadata.is_dir() => {}
+        Ok(_) => {
+            return CheckOutcome::error(format!("{} is not a directory", codex_home.display()));
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return CheckOutcome::warning(format!("{} does not exist yet", codex_home.display()));
+        }
+        Err(err) => {
+            return CheckOutcome::error(format!("cannot access {}: {err}", codex_home.display()));
+        }
+    }
+
+    let mut problems = Vec::new();
+    if let Err(err) = tempfile::NamedTempFile::new_in(codex_home) {
+        problems.push(format!("{} is not writable: {err}", codex_home.display()));
+    }
+    problems.extend(permission_problems(codex_home));
+
+    let mut entries: Vec<(String, u64)> = std::fs::read_dir(codex_home)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| {
+            (
+                entry.file_name().to_string_lossy().into_owned(),
+                disk_usage(&entry.path()),
+            )
+        })
+        .collect();
+    let total: u64 = entries.iter().map(|(_, size)| size).sum();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let summary = format!("{} uses {}", codex_home.display(), format_bytes(total));
+    let usage = entries
+        .into_iter()
+        .take(LARGEST_ENTRIES)
+        .map(|(name, size)| format!("{name}: {}", format_bytes(size)));
+    if problems.is_empty() {
+        CheckOutcome::ok(summary).details(usage)
+    } else {
+        CheckOutcome::warning(summary)
+            .details(problems)
+            .details(usage)
+    }
+}
+
+/// Total size of the files under `path`, without following symlinks.
+fn disk_usage(path: &Path) -> u64 {
+    let mut total = 0;
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            pending.extend(
+                std::fs::read_dir(&path)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|entry| entry.path()),
+            );
+        } else {
+            total += metadata.len();
+        }
+    }
+    total
+}
+
+#[cfg(unix)]
+fn permission_problems(codex_home: &Path) -> Vec<String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = |path: &Path| {
+        std::fs::metadata(path)
+            .ok()
+            .map(|metadata| metadata.permissions().mode() & 0o777)
+    };
+    let mut problems = Vec::new();
+    if let Some(mode) = mode(codex_home)
+        && mode & 0o002 != 0
+    {
+        problems.push(format!(
+            "{} is writable by every user (mode {mode:o})",
+            codex_home.display()
+        ));
+    }
+    for file in ["auth.json", "history.jsonl"] {
+        let path = codex_home.join(file);
+        if let Some(mode) = mode(&path)
+            && mode & 0o077 != 0
+        {
+            problems.push(format!(
+                "{file} is readable by other users (mode {mode:o}); run `chmod 600 {}`",
+                path.display()
+            ));
+        }
+    }
+    problems
+}
+
+#[cfg(not(unix))]
+fn permission_problems(_codex_home: &Path) -> Vec<String> {
+    Vec::new()
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn check_versions() -> CheckOutcome {
+    CheckOutcome::ok(format!("codex {}", env!("CARGO_PKG_VERSION"))).details([
+        format!(
+            "platform: {} ({})",
+            sandbox_self_test::platform_fingerprint(),
+            std::env::consts::ARCH
+        ),
+        format!("terminal: {}", crate::terminal::user_agent()),
+        format!("shell: {}", crate::shell::default_user_shell().name()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sandbox_self_test::SandboxSelfTestReport;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn check(name: &str, status: CheckStatus, summary: &str, details: &[&str]) -> DoctorCheck {
+        DoctorCheck {
+            name: name.to_string(),
+            status,
+            summary: summary.to_string(),
+            details: details.iter().map(|detail| detail.to_string()).collect(),
+            duration_ms: 3,
+        }
+    }
+
+    fn report(checks: Vec<DoctorCheck>, secrets: &[&str]) -> DoctorReport {
+        DoctorReport {
+            codex_version: "1.2.3".to_string(),
+            generated_at: DateTime::from_timestamp(0, 0).expect("timestamp"),
+            checks,
+            secrets: secrets.iter().map(|secret| secret.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn redaction_removes_credentials_tokens_and_home() {
+        let report = report(
+            vec![
+                check(
+                    "auth",
+                    CheckStatus::Error,
+                    "refresh failed for token-abcdef123456",
+                    &["id eyJhbGciOiJub25lIn0.eyJzdWIiOiJ4In0.sig rejected"],
+                ),
+                check(
+                    "codex_home",
+                    CheckStatus::Ok,
+                    "/home/alice/.codex uses 1.0 KiB",
+                    &[],
+                ),
+            ],
+            &["token-abcdef123456", "short"],
+        );
+
+        let redacted = report.redacted_with_home(Some(Path::new("/home/alice")));
+
+        assert_eq!(redacted.checks[0].summary, "refresh failed for [REDACTED]");
+        assert_eq!(redacted.checks[0].details, vec!["id [REDACTED] rejected"]);
+        assert_eq!(redacted.checks[1].summary, "~/.codex uses 1.0 KiB");
+        let json = serde_json::to_string(&redacted).expect("serialize");
+        assert!(!json.contains("abcdef123456"), "{json}");
+        assert!(!json.contains("secrets"), "{json}");
+    }
+
+    #[test]
+    fn text_lists_checks_with_details_and_tally() {
+        let report = report(
+            vec![
+                check(
+                    "config",
+                    CheckStatus::Error,
+                    "configuration does not parse",
+                    &["config.toml:1:5: expected a value"],
+                ),
+                check("provider", CheckStatus::Skipped, "skipped", &[]),
+                check("git", CheckStatus::Ok, "git version 2.43.0", &[]),
+            ],
+            &[],
+        );
+
+        assert_eq!(
+            report.to_text(),
+            "codex doctor (codex 1.2.3)\n\
+             \n\
+             [FAIL] config    configuration does not parse (3 ms)\n\
+             \x20                  config.toml:1:5: expected a value\n\
+             [skip] provider  skipped (3 ms)\n\
+             [ok  ] git       git version 2.43.0 (3 ms)\n\
+             \n\
+             1 failed, 0 warnings, 1 skipped"
+        );
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn expired_chatgpt_tokens_are_a_warning() {
+        let now = DateTime::from_timestamp(10_000, 0).expect("timestamp");
+        let token = |exp: i64| {
+            use base64::Engine as _;
+            let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .encode(format!(r#"{{"exp":{exp}}}"#));
+            format!("eyJhbGciOiJub25lIn0.{payload}.sig")
+        };
+
+        let expired = describe_chatgpt_tokens(&token(10_000 - 3 * 3600), None, now);
+        assert_eq!(expired.status, CheckStatus::Warning);
+        assert_eq!(
+            expired.summary,
+            "logged in with ChatGPT; access token expired 3 hours ago"
+        );
+
+        let valid = describe_chatgpt_tokens(&token(10_000 + 5 * 3600), Some("plus".into()), now);
+        assert_eq!(valid.status, CheckStatus::Ok);
+        assert_eq!(
+            valid.summary,
+            "logged in with ChatGPT; access token valid for 5 hours"
+        );
+        assert_eq!(valid.details, vec!["plan: plus"]);
+    }
+
+    #[tokio::test]
+    async fn timed_out_checks_fail_without_blocking() {
+        let check = timed("slow", Duration::from_millis(10), std::future::pending()).await;
+        assert_eq!(check.status, CheckStatus::Error);
+        assert_eq!(check.summary, "timed out after 0.01 s");
+    }
+
+    #[tokio::test]
+    async fn broken_config_skips_dependent_checks_only() {
+        let codex_home = TempDir::new().expect("tempdir");
+        std::fs::write(codex_home.path().join("config.toml"), "model = \n").expect("write config");
+        sandbox_self_test::save_report(
+            codex_home.path(),
+            &SandboxSelfTestReport {
+                platform: sandbox_self_test::platform_fingerprint(),
+                sandbox: "test sandbox".to_string(),
+                results: Vec::new(),
+            },
+        )
+        .expect("save sandbox report");
+
+        let report = run_doctor(
+            codex_home.path().to_path_buf(),
+            codex_home.path().to_path_buf(),
+            ConfigBuilder::default(),
+        )
+        .await;
+
+        let statuses: Vec<(&str, CheckStatus)> = report
+            .checks
+            .iter()
+            .map(|check| (check.name.as_str(), check.status))
+            .filter(|(name, _)| !matches!(*name, "auth" | "git"))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("config", CheckStatus::Error),
+                ("provider", CheckStatus::Skipped),
+                ("mcp", CheckStatus::Skipped),
+                ("sandbox", CheckStatus::Ok),
+                ("codex_home", CheckStatus::Ok),
+                ("versions", CheckStatus::Ok),
+            ]
+        );
+        assert!(report.has_errors());
+    }
+}
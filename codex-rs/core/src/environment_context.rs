@@ -12,12 +12,19 @@ use std::path::PathBuf;
 #[serde(rename = "environment_context", rename_all = "snake_case")]
 pub(crate) struct EnvironmentContext {
     pub cwd: Option<PathBuf>,
+    /// Directories the session works in beside `cwd`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspace_roots: Vec<PathBuf>,
     pub shell: Shell,
 }
 
 impl EnvironmentContext {
     pub fn new(cwd: Option<PathBuf>, shell: Shell) -> Self {
-        Self { cwd, shell }
+        Self {
+            cwd,
+            workspace_roots: Vec::new(),
+            shell,
+        }
     }
 
     /// Compares two environment contexts, ignoring the shell. Useful when
@@ -26,11 +33,12 @@ impl EnvironmentContext {
     pub fn equals_except_shell(&self, other: &EnvironmentContext) -> bool {
         let EnvironmentContext {
             cwd,
+            workspace_roots,
             // should compare all fields except shell
             shell: _,
         } = other;
 
-        self.cwd == *cwd
+        self.cwd == *cwd && self.workspace_roots == *workspace_roots
     }
 
     pub fn diff(before: &TurnContext, after: &TurnContext, shell: &Shell) -> Self {
@@ -43,7 +51,15 @@ impl EnvironmentContext {
     }
 
     pub fn from_turn_context(turn_context: &TurnContext, shell: &Shell) -> Self {
-        Self::new(Some(turn_context.cwd.clone()), shell.clone())
+        let mut context = Self::new(Some(turn_context.cwd.clone()), shell.clone());
+        context.workspace_roots = turn_context
+            .client
+            .config()
+            .workspace_roots
+            .iter()
+            .map(|root| root.path.as_path().to_path_buf())
+            .collect();
+        context
     }
 }
 
@@ -55,6 +71,7 @@ impl EnvironmentContext {
     /// ```xml
     /// <environment_context>
     ///   <cwd>...</cwd>
+    ///   <workspace_root>...</workspace_root>
     ///   <shell>...</shell>
     /// </environment_context>
     /// ```
@@ -63,6 +80,12 @@ impl EnvironmentContext {
         if let Some(cwd) = self.cwd {
             lines.push(format!("  <cwd>{}</cwd>", cwd.to_string_lossy()));
         }
+        for root in &self.workspace_roots {
+            lines.push(format!(
+                "  <workspace_root>{}</workspace_root>",
+                root.to_string_lossy()
+            ));
+        }
 
         let shell_name = self.shell.name();
         lines.push(format!("  <shell>{shell_name}</shell>"));
@@ -116,6 +139,26 @@ mod tests {
         assert_eq!(context.serialize_to_xml(), expected);
     }
 
+    #[test]
+    fn serialize_environment_context_with_workspace_roots() {
+        let cwd = test_path_buf("/work/frontend");
+        let backend = test_path_buf("/work/backend");
+        let mut context = EnvironmentContext::new(Some(cwd.clone()), fake_shell());
+        context.workspace_roots = vec![backend.clone()];
+
+        let expected = format!(
+            r#"<environment_context>
+  <cwd>{cwd}</cwd>
+  <workspace_root>{backend}</workspace_root>
+  <shell>bash</shell>
+</environment_context>"#,
+            cwd = cwd.display(),
+            backend = backend.display(),
+        );
+
+        assert_eq!(context.serialize_to_xml(), expected);
+    }
+
     #[test]
     fn serialize_read_only_environment_context() {
         let context = EnvironmentContext::new(None, fake_shell());
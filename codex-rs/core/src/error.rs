@@ -11,7 +11,9 @@ use codex_async_utils::CancelErr;
 use codex_protocol::ThreadId;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::ExecLimitKind;
 use codex_protocol::protocol::RateLimitSnapshot;
+use codex_protocol::protocol::RequestErrorDetails;
 use reqwest::StatusCode;
 use serde_json;
 use std::io;
@@ -51,6 +53,13 @@ pub enum SandboxErr {
     #[error("command was killed by a signal")]
     Signal(i32),
 
+    /// Command was stopped by one of its `[exec_limits]`
+    #[error("command exceeded its {}", .kind.description())]
+    LimitExceeded {
+        kind: ExecLimitKind,
+        output: Box<ExecToolCallOutput>,
+    },
+
     /// Error from linux landlock
     #[error("Landlock was not able to fully enforce all sandbox rules")]
     LandlockRestrict,
@@ -106,6 +115,10 @@ pub enum CodexErr {
     #[error("{0}")]
     InvalidRequest(String),
 
+    /// The provider's content filter blocked the request or the response.
+    #[error("{0}")]
+    ContentFiltered(String),
+
     /// Invalid image.
     #[error("Image poisoning")]
     InvalidImageRequest(),
@@ -113,6 +126,10 @@ pub enum CodexErr {
     #[error("{0}")]
     UsageLimitReached(UsageLimitReachedError),
 
+    /// The request body would exceed `max_request_body_bytes`; nothing was sent.
+    #[error("{0}")]
+    RequestTooLarge(RequestTooLargeError),
+
     #[error("{0}")]
     ModelCap(ModelCapError),
 
@@ -122,6 +139,12 @@ pub enum CodexErr {
     #[error("{0}")]
     ConnectionFailed(ConnectionFailedError),
 
+    /// Returned instead of sending a model request when offline mode is on.
+    #[error(
+        "offline mode is on, so no model requests are sent. Restart without `--offline` to reconnect."
+    )]
+    Offline,
+
     #[error("Quota exceeded. Check your plan and billing details.")]
     QuotaExceeded,
 
@@ -198,6 +221,7 @@ impl CodexErr {
             | CodexErr::QuotaExceeded
             | CodexErr::InvalidImageRequest()
             | CodexErr::InvalidRequest(_)
+            | CodexErr::ContentFiltered(_)
             | CodexErr::RefreshTokenFailed(_)
             | CodexErr::UnsupportedOperation(_)
             | CodexErr::Sandbox(_)
@@ -209,7 +233,9 @@ impl CodexErr {
             | CodexErr::Spawn
             | CodexErr::SessionConfiguredNotFirstEvent
             | CodexErr::UsageLimitReached(_)
-            | CodexErr::ModelCap(_) => false,
+            | CodexErr::RequestTooLarge(_)
+            | CodexErr::ModelCap(_)
+            | CodexErr::Offline => false,
             CodexErr::Stream(..)
             | CodexErr::Timeout
             | CodexErr::UnexpectedStatus(_)
@@ -224,6 +250,126 @@ impl CodexErr {
             CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => false,
         }
     }
+
+    /// Whether the server rejected a request because it referred to a stored
+    /// response or item the server does not have, which is how gateways that
+    /// ignore `store: true` fail on the next turn.
+    pub fn is_stored_response_not_found(&self) -> bool {
+        let text = match self {
+            CodexErr::InvalidRequest(body) | CodexErr::Stream(body, _) => body.as_str(),
+            CodexErr::UnexpectedStatus(err) => {
+                if err.error_code.as_deref() == Some(PREVIOUS_RESPONSE_NOT_FOUND) {
+                    return true;
+                }
+                err.body.as_str()
+            }
+            _ => return false,
+        };
+        text.contains(PREVIOUS_RESPONSE_NOT_FOUND)
+            || (text.contains("Item with id") && text.contains("not found"))
+    }
+
+    /// Whether the server rejected the request's `service_tier`, e.g. because
+    /// the model or account does not offer that tier.
+    pub fn is_service_tier_rejected(&self) -> bool {
+        matches!(self, CodexErr::InvalidRequest(body) if body.contains("service_tier"))
+    }
+}
+
+const PREVIOUS_RESPONSE_NOT_FOUND: &str = "previous_response_not_found";
+
+const HINT_CHECK_NETWORK: &str =
+    "Check your network connection and proxy settings, then try again.";
+const HINT_LOGIN: &str = "Run `codex login` to sign in again.";
+const HINT_COMPACT: &str =
+    "Run /compact to summarize earlier history, or start a new thread with /new.";
+const HINT_TRY_AGAIN: &str = "This is usually temporary. Try again in a moment.";
+const HINT_CHECK_MODEL: &str =
+    "Check the `model` setting and the provider's `base_url` in config.toml.";
+const HINT_BILLING: &str = "Check your plan and billing details.";
+const HINT_REPORT: &str = "If this keeps happening, use /feedback to report it.";
+
+/// How an error is shown to users. See [`CodexErr::to_error_event`].
+struct ErrorPresentation {
+    code: &'static str,
+    /// Replaces the error's own message, which then becomes the details.
+    headline: Option<&'static str>,
+    hint: Option<&'static str>,
+}
+
+impl ErrorPresentation {
+    fn new(code: &'static str) -> Self {
+        Self {
+            code,
+            headline: None,
+            hint: None,
+        }
+    }
+
+    fn headline(mut self, headline: &'static str) -> Self {
+        self.headline = Some(headline);
+        self
+    }
+
+    fn hint(mut self, hint: &'static str) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+}
+
+/// Known provider error codes first, then the HTTP status.
+fn unexpected_status_presentation(err: &UnexpectedResponseError) -> ErrorPresentation {
+    match err.error_code.as_deref() {
+        Some("context_length_exceeded") => {
+            return ErrorPresentation::new("context_window_exceeded")
+                .headline("The conversation no longer fits in the model's context window.")
+                .hint(HINT_COMPACT);
+        }
+        Some("invalid_api_key") => {
+            return ErrorPresentation::new("unauthorized")
+                .headline("The model provider rejected your API key.")
+                .hint(HINT_LOGIN);
+        }
+        Some("insufficient_quota") => {
+            return ErrorPresentation::new("quota_exceeded")
+                .headline("Your account has run out of quota.")
+                .hint(HINT_BILLING);
+        }
+        Some("model_not_found") => {
+            return ErrorPresentation::new("model_not_found")
+                .headline("The model provider does not recognize this model.")
+                .hint(HINT_CHECK_MODEL);
+        }
+        Some("rate_limit_exceeded") => {
+            return ErrorPresentation::new("rate_limited")
+                .headline("The model provider is rate limiting requests.")
+                .hint(HINT_TRY_AGAIN);
+        }
+        _ => {}
+    }
+    // The Cloudflare message is already written for users.
+    if err.friendly_message().is_some() {
+        return ErrorPresentation::new("blocked");
+    }
+    match err.status {
+        StatusCode::UNAUTHORIZED => ErrorPresentation::new("unauthorized")
+            .headline("The model provider rejected your credentials.")
+            .hint(HINT_LOGIN),
+        StatusCode::FORBIDDEN => ErrorPresentation::new("forbidden")
+            .headline("The model provider refused the request.")
+            .hint("Check that your account has access to this model."),
+        StatusCode::NOT_FOUND => ErrorPresentation::new("not_found")
+            .headline("The model provider could not find the model or endpoint.")
+            .hint(HINT_CHECK_MODEL),
+        StatusCode::TOO_MANY_REQUESTS => ErrorPresentation::new("rate_limited")
+            .headline("The model provider is rate limiting requests.")
+            .hint(HINT_TRY_AGAIN),
+        status if status.is_server_error() => ErrorPresentation::new("server_error")
+            .headline("The model provider had an internal error.")
+            .hint(HINT_TRY_AGAIN),
+        _ => ErrorPresentation::new("request_rejected")
+            .headline("The model provider rejected the request."),
+    }
 }
 
 #[derive(Debug)]
@@ -284,15 +430,62 @@ pub enum RefreshTokenFailedReason {
 #[derive(Debug)]
 pub struct UnexpectedResponseError {
     pub status: StatusCode,
+    /// Response body, capped at [`UNEXPECTED_RESPONSE_BODY_MAX_BYTES`].
     pub body: String,
     pub url: Option<String>,
     pub request_id: Option<String>,
+    /// `error.type` from a JSON error body.
+    pub error_type: Option<String>,
+    /// `error.code` from a JSON error body.
+    pub error_code: Option<String>,
+    /// Attempts made before giving up, once retries are exhausted.
+    pub attempts: Option<u64>,
 }
 
 const CLOUDFLARE_BLOCKED_MESSAGE: &str =
     "Access blocked by Cloudflare. This usually happens when connecting from a restricted region";
 
+/// Error bodies can be whole HTML pages; keep only the start.
+const UNEXPECTED_RESPONSE_BODY_MAX_BYTES: usize = 4 * 1024;
+
 impl UnexpectedResponseError {
+    /// Builds the error from a raw response body, parsing `error.type` and
+    /// `error.code` from JSON bodies and capping the stored body.
+    pub fn new(
+        status: StatusCode,
+        body: String,
+        url: Option<String>,
+        request_id: Option<String>,
+    ) -> Self {
+        let error = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|value| value.get("error").cloned());
+        let field = |name: &str| {
+            error
+                .as_ref()
+                .and_then(|error| error.get(name))
+                .and_then(|value| match value {
+                    serde_json::Value::String(text) => Some(text.clone()),
+                    serde_json::Value::Number(number) => Some(number.to_string()),
+                    _ => None,
+                })
+        };
+        let error_type = field("type");
+        let error_code = field("code");
+        Self {
+            status,
+            body: truncate_text(
+                &body,
+                TruncationPolicy::Bytes(UNEXPECTED_RESPONSE_BODY_MAX_BYTES),
+            ),
+            url,
+            request_id,
+            error_type,
+            error_code,
+            attempts: None,
+        }
+    }
+
     fn friendly_message(&self) -> Option<String> {
         if self.status != StatusCode::FORBIDDEN {
             return None;
@@ -355,6 +548,24 @@ impl std::fmt::Display for RetryLimitReachedError {
     }
 }
 
+#[derive(Debug)]
+pub struct RequestTooLargeError {
+    pub(crate) estimated_bytes: u64,
+    pub(crate) max_bytes: u64,
+}
+
+impl std::fmt::Display for RequestTooLargeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const MIB: f64 = 1024.0 * 1024.0;
+        write!(
+            f,
+            "This request would be about {:.1} MiB, over the {:.1} MiB limit, so it was not sent. Remove large images or attachments, or start a new thread. If your provider accepts larger requests, raise `max_request_body_bytes`.",
+            self.estimated_bytes as f64 / MIB,
+            self.max_bytes as f64 / MIB
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct UsageLimitReachedError {
     pub(crate) plan_type: Option<PlanType>,
@@ -549,24 +760,134 @@ impl CodexErr {
             | CodexErr::InternalAgentDied => CodexErrorInfo::InternalServerError,
             CodexErr::UnsupportedOperation(_)
             | CodexErr::ThreadNotFound(_)
-            | CodexErr::AgentLimitReached { .. } => CodexErrorInfo::BadRequest,
+            | CodexErr::AgentLimitReached { .. }
+            | CodexErr::RequestTooLarge(_) => CodexErrorInfo::BadRequest,
             CodexErr::Sandbox(_) => CodexErrorInfo::SandboxError,
             _ => CodexErrorInfo::Other,
         }
     }
 
+    /// Builds the event shown to users: a short headline, a remediation hint
+    /// when there is one, and the full error as `details` whenever the
+    /// headline replaces it.
     pub fn to_error_event(&self, message_prefix: Option<String>) -> ErrorEvent {
+        let presentation = self.presentation();
         let error_message = self.to_string();
+        let (headline, details) = match presentation.headline {
+            Some(headline) => (headline.to_string(), Some(error_message)),
+            None => (error_message, None),
+        };
         let message: String = match message_prefix {
-            Some(prefix) => format!("{prefix}: {error_message}"),
-            None => error_message,
+            Some(prefix) => format!("{prefix}: {headline}"),
+            None => headline,
         };
         ErrorEvent {
             message,
             codex_error_info: Some(self.to_codex_protocol_error()),
+            request_details: self.request_error_details(),
+            hint: presentation.hint.map(str::to_string),
+            code: Some(presentation.code.to_string()),
+            details,
         }
     }
 
+    /// Maps the error to how it is shown to users. Errors whose message is
+    /// already readable keep it; transport and parsing errors, whose messages
+    /// are raw library error chains, get a plain headline instead.
+    fn presentation(&self) -> ErrorPresentation {
+        match self {
+            CodexErr::TurnAborted => ErrorPresentation::new("turn_aborted"),
+            CodexErr::Stream(..) => ErrorPresentation::new("stream_disconnected")
+                .headline(
+                    "The connection to the model provider dropped before the response finished.",
+                )
+                .hint(HINT_CHECK_NETWORK),
+            CodexErr::ContextWindowExceeded => {
+                ErrorPresentation::new("context_window_exceeded").hint(HINT_COMPACT)
+            }
+            CodexErr::ThreadNotFound(_) => ErrorPresentation::new("thread_not_found"),
+            CodexErr::AgentLimitReached { .. } => ErrorPresentation::new("agent_limit_reached"),
+            CodexErr::Timeout => ErrorPresentation::new("timeout"),
+            CodexErr::Spawn => ErrorPresentation::new("spawn_failed"),
+            CodexErr::Interrupted => ErrorPresentation::new("interrupted"),
+            CodexErr::UnexpectedStatus(err) => unexpected_status_presentation(err),
+            CodexErr::InvalidRequest(_) => ErrorPresentation::new("invalid_request"),
+            CodexErr::ContentFiltered(_) => ErrorPresentation::new("content_filtered"),
+            CodexErr::InvalidImageRequest() => ErrorPresentation::new("invalid_image")
+                .headline("The model provider could not process an attached image.")
+                .hint("Remove the image and try again."),
+            CodexErr::UsageLimitReached(_) => ErrorPresentation::new("usage_limit_reached"),
+            CodexErr::RequestTooLarge(_) => ErrorPresentation::new("request_too_large"),
+            CodexErr::ModelCap(_) => ErrorPresentation::new("model_cap"),
+            CodexErr::ResponseStreamFailed(_) => ErrorPresentation::new("response_stream_failed")
+                .headline("The connection failed while reading the model's response.")
+                .hint(HINT_CHECK_NETWORK),
+            CodexErr::ConnectionFailed(_) => ErrorPresentation::new("connection_failed")
+                .headline("Could not connect to the model provider.")
+                .hint(HINT_CHECK_NETWORK),
+            CodexErr::Offline => ErrorPresentation::new("offline"),
+            CodexErr::QuotaExceeded => ErrorPresentation::new("quota_exceeded"),
+            CodexErr::UsageNotIncluded => ErrorPresentation::new("usage_not_included"),
+            CodexErr::InternalServerError => {
+                ErrorPresentation::new("server_error").hint(HINT_TRY_AGAIN)
+            }
+            CodexErr::RetryLimit(_) => ErrorPresentation::new("retry_limit_reached")
+                .headline("The model provider kept failing, so Codex stopped retrying.")
+                .hint(HINT_TRY_AGAIN),
+            CodexErr::InternalAgentDied
+            | CodexErr::SessionConfiguredNotFirstEvent
+            | CodexErr::TokioJoin(_) => ErrorPresentation::new("internal_error")
+                .headline("Codex hit an internal error.")
+                .hint(HINT_REPORT),
+            CodexErr::Sandbox(_) | CodexErr::LandlockSandboxExecutableNotProvided => {
+                ErrorPresentation::new("sandbox_error")
+            }
+            #[cfg(target_os = "linux")]
+            CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => {
+                ErrorPresentation::new("sandbox_error")
+            }
+            CodexErr::UnsupportedOperation(_) => ErrorPresentation::new("unsupported_operation"),
+            CodexErr::RefreshTokenFailed(_) => {
+                ErrorPresentation::new("unauthorized").hint(HINT_LOGIN)
+            }
+            CodexErr::Fatal(_) => ErrorPresentation::new("fatal"),
+            CodexErr::Io(_) => ErrorPresentation::new("io_error"),
+            CodexErr::Json(_) => ErrorPresentation::new("invalid_json")
+                .headline("Codex received data it could not parse.")
+                .hint(HINT_REPORT),
+            CodexErr::EnvVar(_) => ErrorPresentation::new("missing_env_var"),
+        }
+    }
+
+    /// Identifiers of the failed model request, for errors that carry them.
+    pub fn request_error_details(&self) -> Option<RequestErrorDetails> {
+        match self {
+            CodexErr::UnexpectedStatus(err) => Some(RequestErrorDetails {
+                request_id: err.request_id.clone(),
+                error_type: err.error_type.clone(),
+                error_code: err.error_code.clone(),
+                attempts: err.attempts,
+            }),
+            CodexErr::RetryLimit(RetryLimitReachedError { request_id, .. })
+            | CodexErr::ResponseStreamFailed(ResponseStreamFailed { request_id, .. }) => {
+                request_id.as_ref().map(|request_id| RequestErrorDetails {
+                    request_id: Some(request_id.clone()),
+                    ..Default::default()
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Records the number of attempts on an error returned after retries
+    /// were exhausted.
+    pub(crate) fn with_attempts(mut self, attempts: u64) -> Self {
+        if let CodexErr::UnexpectedStatus(err) = &mut self {
+            err.attempts = Some(attempts);
+        }
+        self
+    }
+
     pub fn http_status_code_value(&self) -> Option<u16> {
         let http_status_code = match self {
             CodexErr::RetryLimit(err) => Some(err.status),
@@ -606,6 +927,13 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
                 output.duration.as_millis()
             )
         }
+        CodexErr::Sandbox(SandboxErr::LimitExceeded { kind, output }) => {
+            format!(
+                "error: command exceeded its {} after {} ms",
+                kind.description(),
+                output.duration.as_millis()
+            )
+        }
         _ => e.to_string(),
     };
 
@@ -781,8 +1109,16 @@ mod tests {
 
         assert_eq!(
             event.message,
-            "prefix: Error while reading the server response: HTTP status client error (429 Too Many Requests) for url (http://example.com/), request id: req-123"
+            "prefix: The connection failed while reading the model's response."
+        );
+        assert_eq!(
+            event.details.as_deref(),
+            Some(
+                "Error while reading the server response: HTTP status client error (429 Too Many Requests) for url (http://example.com/), request id: req-123"
+            )
         );
+        assert_eq!(event.hint.as_deref(), Some(HINT_CHECK_NETWORK));
+        assert_eq!(event.code.as_deref(), Some("response_stream_failed"));
         assert_eq!(
             event.codex_error_info,
             Some(CodexErrorInfo::ResponseStreamConnectionFailed {
@@ -791,6 +1127,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_error_event_keeps_readable_messages_and_adds_hints() {
+        let event = CodexErr::ContextWindowExceeded.to_error_event(None);
+
+        assert_eq!(event.message, CodexErr::ContextWindowExceeded.to_string());
+        assert_eq!(event.hint.as_deref(), Some(HINT_COMPACT));
+        assert_eq!(event.code.as_deref(), Some("context_window_exceeded"));
+        assert_eq!(event.details, None);
+    }
+
+    #[test]
+    fn to_error_event_maps_provider_error_codes_before_status() {
+        let body = serde_json::json!({
+            "error": {
+                "message": "Incorrect API key provided: sk-***.",
+                "type": "invalid_request_error",
+                "code": "invalid_api_key",
+            }
+        })
+        .to_string();
+        let invalid_key = CodexErr::UnexpectedStatus(UnexpectedResponseError::new(
+            StatusCode::BAD_REQUEST,
+            body,
+            None,
+            None,
+        ));
+        let server_error = CodexErr::UnexpectedStatus(UnexpectedResponseError::new(
+            StatusCode::BAD_GATEWAY,
+            String::new(),
+            None,
+            None,
+        ));
+
+        let invalid_key_event = invalid_key.to_error_event(None);
+        let server_error_event = server_error.to_error_event(None);
+
+        assert_eq!(
+            (
+                invalid_key_event.message.as_str(),
+                invalid_key_event.hint.as_deref(),
+                invalid_key_event.code.as_deref(),
+            ),
+            (
+                "The model provider rejected your API key.",
+                Some(HINT_LOGIN),
+                Some("unauthorized"),
+            )
+        );
+        assert_eq!(invalid_key_event.details, Some(invalid_key.to_string()));
+        assert_eq!(
+            (
+                server_error_event.hint.as_deref(),
+                server_error_event.code.as_deref(),
+            ),
+            (Some(HINT_TRY_AGAIN), Some("server_error"))
+        );
+    }
+
+    #[test]
+    fn to_error_event_includes_request_details_for_unexpected_status() {
+        let body = serde_json::json!({
+            "error": {
+                "message": "The server had an error processing your request.",
+                "type": "server_error",
+                "code": "upstream_timeout",
+                "detail": "x".repeat(UNEXPECTED_RESPONSE_BODY_MAX_BYTES),
+            }
+        })
+        .to_string();
+        let err = CodexErr::UnexpectedStatus(UnexpectedResponseError::new(
+            StatusCode::BAD_GATEWAY,
+            body,
+            None,
+            Some("req_abc123".to_string()),
+        ))
+        .with_attempts(5);
+
+        let event = err.to_error_event(None);
+
+        assert_eq!(
+            event.request_details,
+            Some(RequestErrorDetails {
+                request_id: Some("req_abc123".to_string()),
+                error_type: Some("server_error".to_string()),
+                error_code: Some("upstream_timeout".to_string()),
+                attempts: Some(5),
+            })
+        );
+        let CodexErr::UnexpectedStatus(err) = err else {
+            unreachable!("constructed as UnexpectedStatus");
+        };
+        assert!(err.body.len() < UNEXPECTED_RESPONSE_BODY_MAX_BYTES + 64);
+    }
+
     #[test]
     fn sandbox_denied_reports_exit_code_when_no_output_available() {
         let output = ExecToolCallOutput {
@@ -935,6 +1365,9 @@ mod tests {
                 .to_string(),
             url: Some("http://example.com/blocked".to_string()),
             request_id: Some("ray-id".to_string()),
+            error_type: None,
+            error_code: None,
+            attempts: None,
         };
         let status = StatusCode::FORBIDDEN.to_string();
         let url = "http://example.com/blocked";
@@ -953,6 +1386,9 @@ mod tests {
             body: "plain text error".to_string(),
             url: Some("http://example.com/plain".to_string()),
             request_id: None,
+            error_type: None,
+            error_code: None,
+            attempts: None,
         };
         let status = StatusCode::FORBIDDEN.to_string();
         let url = "http://example.com/plain";
@@ -1012,4 +1448,39 @@ mod tests {
             assert_eq!(err.to_string(), expected);
         });
     }
+
+    #[test]
+    fn detects_missing_stored_responses() {
+        let not_found = CodexErr::UnexpectedStatus(UnexpectedResponseError::new(
+            StatusCode::NOT_FOUND,
+            r#"{"error":{"code":"previous_response_not_found","message":"gone"}}"#.to_string(),
+            None,
+            None,
+        ));
+        assert!(not_found.is_stored_response_not_found());
+        assert!(
+            CodexErr::InvalidRequest(
+                "Item with id 'rs_123' not found. Items are not persisted when `store` is set to false."
+                    .to_string()
+            )
+            .is_stored_response_not_found()
+        );
+        assert!(
+            !CodexErr::InvalidRequest("bad tool schema".to_string()).is_stored_response_not_found()
+        );
+        assert!(!CodexErr::Timeout.is_stored_response_not_found());
+    }
+
+    #[test]
+    fn detects_rejected_service_tier() {
+        let rejected = CodexErr::InvalidRequest(
+            r#"{"error":{"message":"Invalid value for 'service_tier': flex is not available for this model.","param":"service_tier"}}"#
+                .to_string(),
+        );
+        assert!(rejected.is_service_tier_rejected());
+        assert!(
+            !CodexErr::InvalidRequest("bad tool schema".to_string()).is_service_tier_rejected()
+        );
+        assert!(!CodexErr::Stream("service_tier".to_string(), None).is_service_tier_rejected());
+    }
 }
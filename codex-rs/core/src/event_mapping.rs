@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use codex_protocol::items::AgentMessageContent;
 use codex_protocol::items::AgentMessageItem;
 use codex_protocol::items::ReasoningItem;
@@ -9,6 +11,9 @@ use codex_protocol::models::ReasoningItemContent;
 use codex_protocol::models::ReasoningItemReasoningSummary;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::models::WebSearchAction;
+use codex_protocol::models::file_close_tag_text;
+use codex_protocol::models::file_tag_path;
+use codex_protocol::models::is_file_close_tag_text;
 use codex_protocol::models::is_image_close_tag_text;
 use codex_protocol::models::is_image_open_tag_text;
 use codex_protocol::models::is_local_image_close_tag_text;
@@ -43,6 +48,23 @@ fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
                 {
                     continue;
                 }
+                // Uploaded attachments are shown from their `InputFile` item.
+                if (file_tag_path(text).is_some()
+                    && matches!(message.get(idx + 1), Some(ContentItem::InputFile { .. })))
+                    || (idx > 0
+                        && is_file_close_tag_text(text)
+                        && matches!(message.get(idx - 1), Some(ContentItem::InputFile { .. })))
+                {
+                    continue;
+                }
+                if let Some(path) = file_tag_path(text)
+                    && text.ends_with(&file_close_tag_text())
+                {
+                    content.push(UserInput::LocalFile {
+                        path: PathBuf::from(path),
+                    });
+                    continue;
+                }
                 if is_session_prefix(text) || is_user_shell_command_text(text) {
                     return None;
                 }
@@ -57,6 +79,16 @@ fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
                     image_url: image_url.clone(),
                 });
             }
+            ContentItem::InputFile { .. } => {
+                if let Some(ContentItem::InputText { text }) =
+                    idx.checked_sub(1).and_then(|prev| message.get(prev))
+                    && let Some(path) = file_tag_path(text)
+                {
+                    content.push(UserInput::LocalFile {
+                        path: PathBuf::from(path),
+                    });
+                }
+            }
             ContentItem::OutputText { text } => {
                 if is_session_prefix(text) {
                     return None;
@@ -238,6 +270,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn shows_attached_files_by_path() {
+        let mut content = vec![
+            ContentItem::InputText {
+                text: "Summarize these.".to_string(),
+            },
+            ContentItem::InputText {
+                text: "<file path=\"notes.md\">\nremember the milk\n</file>".to_string(),
+            },
+        ];
+        content.extend(codex_protocol::models::uploaded_file_content_items(
+            std::path::Path::new("spec.pdf"),
+            "file-abc".to_string(),
+        ));
+        let item = ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content,
+            end_turn: None,
+        };
+
+        let Some(TurnItem::UserMessage(user)) = parse_turn_item(&item) else {
+            panic!("expected user message turn item");
+        };
+        assert_eq!(
+            user.content,
+            vec![
+                UserInput::Text {
+                    text: "Summarize these.".to_string(),
+                    text_elements: Vec::new(),
+                },
+                UserInput::LocalFile {
+                    path: "notes.md".into(),
+                },
+                UserInput::LocalFile {
+                    path: "spec.pdf".into(),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn skips_unnamed_image_label_text() {
         let image_url = "data:image/png;base64,abc".to_string();
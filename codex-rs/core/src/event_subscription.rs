@@ -0,0 +1,193 @@
+//! Broadcast tap over a session's event stream.
+//!
+//! [`crate::codex::Codex::next_event`] serves a single primary consumer. Programs that embed
+//! codex-core and need the same events in several places (a logger, a UI, metrics) can call
+//! `subscribe()` instead of multiplexing the primary stream themselves.
+//!
+//! Ordering guarantees:
+//! - Every subscriber sees events in the order the session emitted them, which is also the
+//!   order `next_event` returns them.
+//! - A subscriber only sees events emitted after it subscribed.
+//! - A subscriber that falls more than `event_subscriber_capacity` events behind skips the
+//!   oldest ones; [`EventSubscription::skipped_events`] reports how many. The primary consumer
+//!   and other subscribers are unaffected.
+//!
+//! Streams end once the owning `Codex` is dropped and every event emitted before that has been
+//! delivered.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::task::Context;
+use std::task::Poll;
+
+use async_channel::Receiver;
+use async_channel::Sender;
+use futures::Stream;
+use futures::stream::BoxStream;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+use crate::protocol::Event;
+
+pub(crate) const DEFAULT_EVENT_SUBSCRIBER_CAPACITY: usize = 1024;
+
+/// Sending half shared by a `Codex` and its subscribers.
+pub(crate) type EventBroadcast = Arc<broadcast::Sender<Event>>;
+
+/// Forwards every event from `rx_session` to `tx_primary` after publishing it to subscribers.
+///
+/// The returned sender is the only strong reference; the forwarding task holds a weak one, so
+/// dropping it (with the `Codex` that owns it) closes every subscriber stream.
+pub(crate) fn spawn_event_fanout(
+    capacity: usize,
+    rx_session: Receiver<Event>,
+    tx_primary: Sender<Event>,
+) -> EventBroadcast {
+    let (tx_broadcast, _) = broadcast::channel(capacity.max(1));
+    let tx_broadcast = Arc::new(tx_broadcast);
+    let weak_broadcast: Weak<broadcast::Sender<Event>> = Arc::downgrade(&tx_broadcast);
+    tokio::spawn(async move {
+        while let Ok(event) = rx_session.recv().await {
+            let subscribers = weak_broadcast.upgrade();
+            if let Some(subscribers) = &subscribers {
+                // No receivers is not an error: subscribing is optional.
+                let _ = subscribers.send(event.clone());
+            }
+            let primary_open = tx_primary.send(event).await.is_ok();
+            if !primary_open && subscribers.is_none() {
+                break;
+            }
+        }
+    });
+    tx_broadcast
+}
+
+/// Stream of session events for one subscriber, returned by `Codex::subscribe` and
+/// `CodexThread::subscribe`.
+pub struct EventSubscription {
+    events: BoxStream<'static, Event>,
+    skipped: Arc<AtomicU64>,
+}
+
+impl EventSubscription {
+    pub(crate) fn new(broadcast: &EventBroadcast) -> Self {
+        let skipped = Arc::new(AtomicU64::new(0));
+        let skipped_for_stream = Arc::clone(&skipped);
+        let events = futures::stream::unfold(broadcast.subscribe(), move |mut rx| {
+            let skipped = Arc::clone(&skipped_for_stream);
+            async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(event) => return Some((event, rx)),
+                        Err(RecvError::Lagged(count)) => {
+                            warn!("event subscriber lagged; skipped {count} events");
+                            skipped.fetch_add(count, Ordering::Relaxed);
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            }
+        });
+        Self {
+            events: Box::pin(events),
+            skipped,
+        }
+    }
+
+    /// Number of events this subscriber missed because it fell behind.
+    pub fn skipped_events(&self) -> u64 {
+        self.skipped.load(Ordering::Relaxed)
+    }
+}
+
+impl Stream for EventSubscription {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::EventMsg;
+    use crate::protocol::WarningEvent;
+    use futures::StreamExt;
+    use pretty_assertions::assert_eq;
+
+    fn warning(id: &str) -> Event {
+        Event {
+            id: id.to_string(),
+            msg: EventMsg::Warning(WarningEvent {
+                message: id.to_string(),
+            }),
+        }
+    }
+
+    fn ids(events: &[Event]) -> Vec<String> {
+        events.iter().map(|event| event.id.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn subscribers_and_primary_see_events_in_order() {
+        let (tx_session, rx_session) = async_channel::unbounded();
+        let (tx_primary, rx_primary) = async_channel::unbounded();
+        let broadcast = spawn_event_fanout(8, rx_session, tx_primary);
+        let logger = EventSubscription::new(&broadcast);
+        let ui = EventSubscription::new(&broadcast);
+
+        for id in ["a", "b", "c"] {
+            tx_session.send(warning(id)).await.expect("send");
+        }
+        drop(tx_session);
+
+        let mut primary = Vec::new();
+        while let Ok(event) = rx_primary.recv().await {
+            primary.push(event);
+        }
+        drop(broadcast);
+
+        let logger: Vec<Event> = logger.collect().await;
+        let ui: Vec<Event> = ui.collect().await;
+        assert_eq!(ids(&primary), vec!["a", "b", "c"]);
+        assert_eq!(ids(&logger), vec!["a", "b", "c"]);
+        assert_eq!(ids(&ui), vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn lagging_subscriber_reports_skipped_events() {
+        let (tx_session, rx_session) = async_channel::unbounded();
+        let (tx_primary, rx_primary) = async_channel::unbounded();
+        let broadcast = spawn_event_fanout(2, rx_session, tx_primary);
+        let mut slow = EventSubscription::new(&broadcast);
+
+        for id in ["a", "b", "c", "d"] {
+            tx_session.send(warning(id)).await.expect("send");
+        }
+        for _ in 0..4 {
+            rx_primary.recv().await.expect("primary event");
+        }
+
+        let next = slow.next().await.expect("event after lag");
+        assert_eq!(next.id, "c");
+        assert_eq!(slow.skipped_events(), 2);
+    }
+
+    #[tokio::test]
+    async fn dropping_the_broadcast_ends_subscriber_streams() {
+        let (_tx_session, rx_session) = async_channel::unbounded();
+        let (tx_primary, _rx_primary) = async_channel::unbounded();
+        let broadcast = spawn_event_fanout(8, rx_session, tx_primary);
+        let subscription = EventSubscription::new(&broadcast);
+
+        drop(broadcast);
+
+        let events: Vec<Event> = subscription.collect().await;
+        assert_eq!(ids(&events), Vec::<String>::new());
+    }
+}
@@ -6,12 +6,16 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitStatus;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
 use async_channel::Sender;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
 use tokio_util::sync::CancellationToken;
@@ -19,10 +23,16 @@ use tokio_util::sync::CancellationToken;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
+use crate::exec_limits::ExecLimits;
+use crate::exec_limits::exceeded_limit;
 use crate::get_platform_sandbox;
+use crate::output_sanitizer::binary_output_summary;
+use crate::output_sanitizer::looks_binary;
+use crate::output_sanitizer::sanitize_terminal_output;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
+use crate::protocol::ExecLimitKind;
 use crate::protocol::ExecOutputStream;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
@@ -32,7 +42,9 @@ use crate::sandboxing::SandboxPermissions;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use crate::text_encoding::bytes_to_string_smart;
+use crate::util::utf8_prefix_len;
 use codex_utils_pty::process_group::kill_child_process_group;
+use codex_utils_pty::process_group::kill_process_group_by_pid;
 
 pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
 
@@ -67,6 +79,7 @@ pub struct ExecParams {
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    pub limits: ExecLimits,
 }
 
 /// Mechanism to terminate an exec invocation before it finishes naturally.
@@ -141,6 +154,27 @@ pub async fn process_exec_tool_call(
     sandbox_cwd: &Path,
     codex_linux_sandbox_exe: &Option<PathBuf>,
     stdout_stream: Option<StdoutStream>,
+) -> Result<ExecToolCallOutput> {
+    process_exec_tool_call_with_stdin(
+        params,
+        sandbox_policy,
+        sandbox_cwd,
+        codex_linux_sandbox_exe,
+        stdout_stream,
+        None,
+    )
+    .await
+}
+
+/// Like [`process_exec_tool_call`], but writes `stdin` to the command's
+/// standard input.
+pub(crate) async fn process_exec_tool_call_with_stdin(
+    params: ExecParams,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_cwd: &Path,
+    codex_linux_sandbox_exe: &Option<PathBuf>,
+    stdout_stream: Option<StdoutStream>,
+    stdin: Option<Vec<u8>>,
 ) -> Result<ExecToolCallOutput> {
     let windows_sandbox_level = params.windows_sandbox_level;
     let sandbox_type = match &sandbox_policy {
@@ -163,6 +197,7 @@ pub async fn process_exec_tool_call(
         windows_sandbox_level,
         justification,
         arg0: _,
+        limits,
     } = params;
 
     let (program, args) = command.split_first().ok_or_else(|| {
@@ -180,6 +215,7 @@ pub async fn process_exec_tool_call(
         expiration,
         sandbox_permissions,
         justification,
+        limits,
     };
 
     let manager = SandboxManager::new();
@@ -194,14 +230,25 @@ pub async fn process_exec_tool_call(
         )
         .map_err(CodexErr::from)?;
 
-    // Route through the sandboxing module for a single, unified execution path.
-    crate::sandboxing::execute_env(exec_env, sandbox_policy, stdout_stream).await
+    // The same execution path as `sandboxing::execute_env`, plus stdin.
+    execute_exec_env_with_stdin(exec_env, sandbox_policy, stdout_stream, stdin).await
 }
 
 pub(crate) async fn execute_exec_env(
     env: ExecEnv,
     sandbox_policy: &SandboxPolicy,
     stdout_stream: Option<StdoutStream>,
+) -> Result<ExecToolCallOutput> {
+    execute_exec_env_with_stdin(env, sandbox_policy, stdout_stream, None).await
+}
+
+/// Like [`execute_exec_env`], but writes `stdin` to the command's standard
+/// input and then closes it. Without it the command's stdin is `/dev/null`.
+async fn execute_exec_env_with_stdin(
+    env: ExecEnv,
+    sandbox_policy: &SandboxPolicy,
+    stdout_stream: Option<StdoutStream>,
+    stdin: Option<Vec<u8>>,
 ) -> Result<ExecToolCallOutput> {
     let ExecEnv {
         command,
@@ -213,6 +260,7 @@ pub(crate) async fn execute_exec_env(
         sandbox_permissions,
         justification,
         arg0,
+        limits,
     } = env;
 
     let params = ExecParams {
@@ -224,10 +272,11 @@ pub(crate) async fn execute_exec_env(
         windows_sandbox_level,
         justification,
         arg0,
+        limits,
     };
 
     let start = Instant::now();
-    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
+    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream, stdin).await;
     let duration = start.elapsed();
     finalize_exec_result(raw_output_result, sandbox, duration)
 }
@@ -381,11 +430,11 @@ async fn exec_windows_sandbox(
     let exit_status = synthetic_exit_status(capture.exit_code);
     let mut stdout_text = capture.stdout;
     if stdout_text.len() > EXEC_OUTPUT_MAX_BYTES {
-        stdout_text.truncate(EXEC_OUTPUT_MAX_BYTES);
+        stdout_text.truncate(utf8_prefix_len(&stdout_text, EXEC_OUTPUT_MAX_BYTES));
     }
     let mut stderr_text = capture.stderr;
     if stderr_text.len() > EXEC_OUTPUT_MAX_BYTES {
-        stderr_text.truncate(EXEC_OUTPUT_MAX_BYTES);
+        stderr_text.truncate(utf8_prefix_len(&stderr_text, EXEC_OUTPUT_MAX_BYTES));
     }
     let stdout = StreamOutput {
         text: stdout_text,
@@ -403,6 +452,7 @@ async fn exec_windows_sandbox(
         stderr,
         aggregated_output,
         timed_out: capture.timed_out,
+        limit_exceeded: None,
     })
 }
 
@@ -413,6 +463,14 @@ fn finalize_exec_result(
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
+            if let Some(kind) = raw_output.limit_exceeded {
+                let exit_code = exit_code_with_signal(raw_output.exit_status);
+                return Err(CodexErr::Sandbox(SandboxErr::LimitExceeded {
+                    kind,
+                    output: Box::new(decode_exec_output(&raw_output, exit_code, duration, false)),
+                }));
+            }
+
             #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
 
@@ -432,17 +490,7 @@ fn finalize_exec_result(
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
 
-            let stdout = raw_output.stdout.from_utf8_lossy();
-            let stderr = raw_output.stderr.from_utf8_lossy();
-            let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
-            let exec_output = ExecToolCallOutput {
-                exit_code,
-                stdout,
-                stderr,
-                aggregated_output,
-                duration,
-                timed_out,
-            };
+            let exec_output = decode_exec_output(&raw_output, exit_code, duration, timed_out);
 
             if timed_out {
                 return Err(CodexErr::Sandbox(SandboxErr::Timeout {
@@ -465,6 +513,42 @@ fn finalize_exec_result(
     }
 }
 
+fn decode_exec_output(
+    raw_output: &RawExecToolCallOutput,
+    exit_code: i32,
+    duration: Duration,
+    timed_out: bool,
+) -> ExecToolCallOutput {
+    let stdout = decode_and_sanitize(&raw_output.stdout);
+    let stderr = decode_and_sanitize(&raw_output.stderr);
+    let aggregated_output =
+        if looks_binary(&raw_output.stdout.text) || looks_binary(&raw_output.stderr.text) {
+            // Summarize each stream on its own so text on the other one, such
+            // as an error message, is kept.
+            StreamOutput::new(format!("{}{}", stdout.text, stderr.text))
+        } else {
+            decode_and_sanitize(&raw_output.aggregated_output)
+        };
+    ExecToolCallOutput {
+        exit_code,
+        stdout,
+        stderr,
+        aggregated_output,
+        duration,
+        timed_out,
+    }
+}
+
+/// The exit code, or `128 + signal` as a shell would report it when the
+/// process was killed by a signal.
+fn exit_code_with_signal(exit_status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    if let Some(signal) = exit_status.signal() {
+        return EXIT_CODE_SIGNAL_BASE + signal;
+    }
+    exit_status.code().unwrap_or(-1)
+}
+
 pub(crate) mod errors {
     use super::CodexErr;
     use crate::sandboxing::SandboxTransformError;
@@ -493,15 +577,25 @@ pub(crate) fn is_likely_sandbox_denied(
     sandbox_type: SandboxType,
     exec_output: &ExecToolCallOutput,
 ) -> bool {
+    sandbox_denial_reason(sandbox_type, exec_output).is_some()
+}
+
+/// Returns a short description of why the sandbox appears to have blocked the
+/// command: the first output line that matches a known denial message, or a
+/// note about the signal that killed it.
+pub(crate) fn sandbox_denial_reason(
+    sandbox_type: SandboxType,
+    exec_output: &ExecToolCallOutput,
+) -> Option<String> {
     if sandbox_type == SandboxType::None || exec_output.exit_code == 0 {
-        return false;
+        return None;
     }
 
     // Quick rejects: well-known non-sandbox shell exit codes
     // 2: misuse of shell builtins
     // 126: permission denied
     // 127: command not found
-    const SANDBOX_DENIED_KEYWORDS: [&str; 7] = [
+    const SANDBOX_DENIED_KEYWORDS: [&str; 9] = [
         "operation not permitted",
         "permission denied",
         "read-only file system",
@@ -509,28 +603,33 @@ pub(crate) fn is_likely_sandbox_denied(
         "sandbox",
         "landlock",
         "failed to write file",
+        // Seatbelt violation reports, e.g. `Sandbox: bash(123) deny(1) file-write-create /tmp/x`.
+        "deny(1)",
+        // Landlock denials surface as EACCES from tools that print errno names.
+        "eacces",
     ];
 
-    let has_sandbox_keyword = [
+    let matching_line = [
         &exec_output.stderr.text,
         &exec_output.stdout.text,
         &exec_output.aggregated_output.text,
     ]
     .into_iter()
-    .any(|section| {
-        let lower = section.to_lowercase();
+    .flat_map(|section| section.lines())
+    .find(|line| {
+        let lower = line.to_lowercase();
         SANDBOX_DENIED_KEYWORDS
             .iter()
             .any(|needle| lower.contains(needle))
     });
 
-    if has_sandbox_keyword {
-        return true;
+    if let Some(line) = matching_line {
+        return Some(line.trim().to_string());
     }
 
     const QUICK_REJECT_EXIT_CODES: [i32; 3] = [2, 126, 127];
     if QUICK_REJECT_EXIT_CODES.contains(&exec_output.exit_code) {
-        return false;
+        return None;
     }
 
     #[cfg(unix)]
@@ -539,11 +638,11 @@ pub(crate) fn is_likely_sandbox_denied(
         if sandbox_type == SandboxType::LinuxSeccomp
             && exec_output.exit_code == EXIT_CODE_SIGNAL_BASE + SIGSYS_CODE
         {
-            return true;
+            return Some("killed by seccomp (SIGSYS)".to_string());
         }
     }
 
-    false
+    None
 }
 
 #[derive(Debug, Clone)]
@@ -559,6 +658,7 @@ struct RawExecToolCallOutput {
     pub stderr: StreamOutput<Vec<u8>>,
     pub aggregated_output: StreamOutput<Vec<u8>>,
     pub timed_out: bool,
+    pub limit_exceeded: Option<ExecLimitKind>,
 }
 
 impl StreamOutput<String> {
@@ -579,14 +679,38 @@ impl StreamOutput<Vec<u8>> {
     }
 }
 
-#[inline]
-fn append_capped(dst: &mut Vec<u8>, src: &[u8], max_bytes: usize) {
-    if dst.len() >= max_bytes {
-        return;
+/// Decodes captured bytes and strips terminal escapes and control characters so
+/// both the model and history cells see the output as a terminal would show it.
+/// Binary output is replaced by a summary.
+fn decode_and_sanitize(output: &StreamOutput<Vec<u8>>) -> StreamOutput<String> {
+    if looks_binary(&output.text) {
+        return StreamOutput {
+            text: binary_output_summary(&output.text),
+            truncated_after_lines: output.truncated_after_lines,
+        };
+    }
+    let decoded = output.from_utf8_lossy();
+    StreamOutput {
+        text: sanitize_terminal_output(&decoded.text),
+        truncated_after_lines: decoded.truncated_after_lines,
     }
+}
+
+/// Append `src` to `dst` without exceeding `max_bytes`. Returns `true` once
+/// the cap is reached; callers must stop appending after that so later chunks
+/// are not spliced onto the cut.
+#[inline]
+fn append_capped(dst: &mut Vec<u8>, src: &[u8], max_bytes: usize) -> bool {
     let remaining = max_bytes.saturating_sub(dst.len());
-    let take = remaining.min(src.len());
-    dst.extend_from_slice(&src[..take]);
+    if src.len() < remaining {
+        dst.extend_from_slice(src);
+        return false;
+    }
+    dst.extend_from_slice(&src[..remaining]);
+    // Drop a trailing partial character, which may have started in an
+    // earlier chunk.
+    dst.truncate(utf8_prefix_len(dst, dst.len()));
+    true
 }
 
 fn aggregate_output(
@@ -613,8 +737,8 @@ fn aggregate_output(
     let remaining = max_bytes.saturating_sub(want_stdout + stderr_take);
     let stdout_take = want_stdout + remaining.min(stdout.text.len().saturating_sub(want_stdout));
 
-    aggregated.extend_from_slice(&stdout.text[..stdout_take]);
-    aggregated.extend_from_slice(&stderr.text[..stderr_take]);
+    aggregated.extend_from_slice(&stdout.text[..utf8_prefix_len(&stdout.text, stdout_take)]);
+    aggregated.extend_from_slice(&stderr.text[..utf8_prefix_len(&stderr.text, stderr_take)]);
 
     StreamOutput {
         text: aggregated,
@@ -651,6 +775,7 @@ async fn exec(
     sandbox: SandboxType,
     sandbox_policy: &SandboxPolicy,
     stdout_stream: Option<StdoutStream>,
+    stdin: Option<Vec<u8>>,
 ) -> Result<RawExecToolCallOutput> {
     #[cfg(target_os = "windows")]
     if sandbox == SandboxType::WindowsRestrictedToken
@@ -659,6 +784,9 @@ async fn exec(
             SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. }
         )
     {
+        if stdin.is_some() {
+            tracing::warn!("the Windows sandbox does not support stdin; input is not delivered");
+        }
         return exec_windows_sandbox(params, sandbox_policy).await;
     }
     let ExecParams {
@@ -668,6 +796,7 @@ async fn exec(
         arg0,
         expiration,
         windows_sandbox_level: _,
+        limits,
         ..
     } = params;
 
@@ -678,24 +807,104 @@ async fn exec(
         ))
     })?;
     let arg0_ref = arg0.as_deref();
-    let child = spawn_child_async(
+    let stdio_policy = if stdin.is_some() {
+        StdioPolicy::RedirectWithStdin
+    } else {
+        StdioPolicy::RedirectForShellTool
+    };
+    let mut child = spawn_child_async(
         PathBuf::from(program),
         args.into(),
         arg0_ref,
         cwd,
         sandbox_policy,
-        StdioPolicy::RedirectForShellTool,
+        stdio_policy,
         env,
+        &limits,
     )
     .await?;
-    consume_truncated_output(child, expiration, stdout_stream).await
+    if let Some(input) = stdin
+        && let Some(mut pipe) = child.stdin.take()
+    {
+        // Write from a separate task so a command that fills its output pipes
+        // before reading all of its input cannot deadlock against us. Dropping
+        // the pipe closes the command's stdin.
+        tokio::spawn(async move {
+            if let Err(err) = pipe.write_all(&input).await {
+                tracing::debug!("failed to write command stdin: {err}");
+            }
+        });
+    }
+    // Keep the job alive until the child exits; closing it early would lift
+    // the limits.
+    #[cfg(windows)]
+    let _job = limits.assign_job(&child)?;
+
+    let mut raw_output =
+        consume_truncated_output(child, expiration, limits.max_output_bytes, stdout_stream).await?;
+    if raw_output.limit_exceeded.is_none() && !raw_output.timed_out {
+        #[cfg(unix)]
+        let signal = raw_output.exit_status.signal();
+        #[cfg(not(unix))]
+        let signal = None;
+        raw_output.limit_exceeded = exceeded_limit(
+            &limits,
+            signal,
+            raw_output.exit_status.code().unwrap_or(-1),
+            &String::from_utf8_lossy(&raw_output.aggregated_output.text),
+        );
+    }
+    Ok(raw_output)
+}
+
+/// Shared by the stdout and stderr readers of one command to enforce its
+/// `max_output_bytes` limit across both streams.
+#[derive(Clone)]
+struct OutputBudget {
+    max_bytes: u64,
+    used: Arc<AtomicU64>,
+    exceeded: CancellationToken,
+}
+
+impl OutputBudget {
+    fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            used: Arc::new(AtomicU64::new(0)),
+            exceeded: CancellationToken::new(),
+        }
+    }
+
+    fn consume(&self, bytes: usize) {
+        let used = self.used.fetch_add(bytes as u64, Ordering::Relaxed) + bytes as u64;
+        if used > self.max_bytes {
+            self.exceeded.cancel();
+        }
+    }
+}
+
+/// Kills the process group of `pid` when dropped while still set.
+struct ProcessGroupGuard {
+    pid: Option<u32>,
+}
+
+impl Drop for ProcessGroupGuard {
+    fn drop(&mut self) {
+        if let Some(pid) = self.pid.take()
+            && let Err(err) = kill_process_group_by_pid(pid)
+        {
+            tracing::warn!("failed to kill process group of {pid}: {err}");
+        }
+    }
 }
 
 /// Consumes the output of a child process, truncating it so it is suitable for
-/// use as the output of a `shell` tool call. Also enforces specified timeout.
+/// use as the output of a `shell` tool call. Also enforces specified timeout,
+/// and kills the command once it writes more than `max_output_bytes`.
 async fn consume_truncated_output(
     mut child: Child,
     expiration: ExecExpiration,
+    max_output_bytes: Option<u64>,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     // Both stdout and stderr were configured with `Stdio::piped()`
@@ -713,33 +922,55 @@ async fn consume_truncated_output(
         ))
     })?;
 
+    let output_budget = max_output_bytes.map(OutputBudget::new);
+    // A token that is never cancelled stands in when there is no limit.
+    let output_exceeded = output_budget
+        .as_ref()
+        .map(|budget| budget.exceeded.clone())
+        .unwrap_or_default();
+
     let stdout_handle = tokio::spawn(read_capped(
         BufReader::new(stdout_reader),
         stdout_stream.clone(),
         false,
+        output_budget.clone(),
     ));
     let stderr_handle = tokio::spawn(read_capped(
         BufReader::new(stderr_reader),
         stdout_stream.clone(),
         true,
+        output_budget,
     ));
 
-    let (exit_status, timed_out) = tokio::select! {
+    // Dropping this future, as interrupting the turn does, must not leave the
+    // command running: `kill_on_drop` only reaches the direct child.
+    let mut process_group_guard = ProcessGroupGuard { pid: child.id() };
+    let (exit_status, timed_out, limit_exceeded) = tokio::select! {
         status_result = child.wait() => {
             let exit_status = status_result?;
-            (exit_status, false)
+            (exit_status, false, None)
         }
         _ = expiration.wait() => {
             kill_child_process_group(&mut child)?;
             child.start_kill()?;
-            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + TIMEOUT_CODE), true)
+            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + TIMEOUT_CODE), true, None)
+        }
+        _ = output_exceeded.cancelled() => {
+            kill_child_process_group(&mut child)?;
+            child.start_kill()?;
+            (
+                synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE),
+                false,
+                Some(ExecLimitKind::Output),
+            )
         }
         _ = tokio::signal::ctrl_c() => {
             kill_child_process_group(&mut child)?;
             child.start_kill()?;
-            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false)
+            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false, None)
         }
     };
+    process_group_guard.pid = None;
 
     // Wait for the stdout/stderr collection tasks but guard against them
     // hanging forever. In the normal case, both pipes are closed once the child
@@ -795,6 +1026,7 @@ async fn consume_truncated_output(
         stderr,
         aggregated_output,
         timed_out,
+        limit_exceeded,
     })
 }
 
@@ -802,16 +1034,21 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     mut reader: R,
     stream: Option<StdoutStream>,
     is_stderr: bool,
+    output_budget: Option<OutputBudget>,
 ) -> io::Result<StreamOutput<Vec<u8>>> {
     let mut buf = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY.min(EXEC_OUTPUT_MAX_BYTES));
     let mut tmp = [0u8; READ_CHUNK_SIZE];
     let mut emitted_deltas: usize = 0;
+    let mut capped = false;
 
     loop {
         let n = reader.read(&mut tmp).await?;
         if n == 0 {
             break;
         }
+        if let Some(budget) = &output_budget {
+            budget.consume(n);
+        }
 
         if let Some(stream) = &stream
             && emitted_deltas < MAX_EXEC_OUTPUT_DELTAS_PER_CALL
@@ -835,7 +1072,9 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
             emitted_deltas += 1;
         }
 
-        append_capped(&mut buf, &tmp[..n], EXEC_OUTPUT_MAX_BYTES);
+        if !capped {
+            capped = append_capped(&mut buf, &tmp[..n], EXEC_OUTPUT_MAX_BYTES);
+        }
         // Continue reading to EOF to avoid back-pressure
     }
 
@@ -862,6 +1101,8 @@ fn synthetic_exit_status(code: i32) -> ExitStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::models::ResponseItem;
     use pretty_assertions::assert_eq;
     use std::time::Duration;
     use tokio::io::AsyncWriteExt;
@@ -882,6 +1123,96 @@ mod tests {
         }
     }
 
+    fn raw_output(stdout: Vec<u8>, stderr: Vec<u8>) -> RawExecToolCallOutput {
+        let stdout = StreamOutput {
+            text: stdout,
+            truncated_after_lines: None,
+        };
+        let stderr = StreamOutput {
+            text: stderr,
+            truncated_after_lines: None,
+        };
+        let aggregated_output = aggregate_output(&stdout, &stderr);
+        RawExecToolCallOutput {
+            exit_status: synthetic_exit_status(0),
+            stdout,
+            stderr,
+            aggregated_output,
+            timed_out: false,
+            limit_exceeded: None,
+        }
+    }
+
+    /// Deterministic stand-in for reading `/dev/urandom`.
+    fn pseudo_random_bytes(len: usize) -> Vec<u8> {
+        let mut state: u32 = 0x2545_f491;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state.to_le_bytes()[0]
+            })
+            .collect()
+    }
+
+    /// Serialize `text` the way the rollout records a tool output and read it back.
+    fn rollout_round_trip(text: &str) -> String {
+        let item = ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload {
+                content: text.to_string(),
+                ..Default::default()
+            },
+        };
+        let line = serde_json::to_string(&item).expect("serialize rollout item");
+        match serde_json::from_str(&line).expect("rollout line is valid JSON") {
+            ResponseItem::FunctionCallOutput { output, .. } => output.content,
+            other => panic!("unexpected item: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn random_bytes_are_summarized_for_model_and_ui() {
+        let raw = raw_output(
+            pseudo_random_bytes(64 * 1024),
+            b"cat: data.bin: input file is output file\n".to_vec(),
+        );
+
+        let output = decode_exec_output(&raw, 0, Duration::ZERO, false);
+
+        assert!(
+            output
+                .stdout
+                .text
+                .starts_with("binary output, 64.0KB, first bytes: "),
+            "{}",
+            output.stdout.text
+        );
+        assert_eq!(
+            output.aggregated_output.text,
+            format!(
+                "{}cat: data.bin: input file is output file\n",
+                output.stdout.text
+            )
+        );
+        assert_eq!(
+            rollout_round_trip(&output.aggregated_output.text),
+            output.aggregated_output.text
+        );
+    }
+
+    #[test]
+    fn single_megabyte_line_is_kept_whole() {
+        let line = "function f(){return 1};".repeat(EXEC_OUTPUT_MAX_BYTES / 23);
+        let raw = raw_output(line.clone().into_bytes(), Vec::new());
+
+        let output = decode_exec_output(&raw, 0, Duration::ZERO, false);
+
+        assert_eq!(output.aggregated_output.text, line);
+        assert_eq!(rollout_round_trip(&output.aggregated_output.text), line);
+    }
+
     #[test]
     fn sandbox_detection_requires_keywords() {
         let output = make_exec_output(1, "", "", "");
@@ -912,6 +1243,60 @@ mod tests {
         assert!(!is_likely_sandbox_denied(SandboxType::None, &output));
     }
 
+    #[test]
+    fn sandbox_denial_reason_reports_landlock_eacces_line() {
+        let output = make_exec_output(
+            1,
+            "",
+            "running tests\ntouch: cannot touch '/usr/lib/x': Permission denied\n",
+            "",
+        );
+        assert_eq!(
+            sandbox_denial_reason(SandboxType::LinuxSeccomp, &output),
+            Some("touch: cannot touch '/usr/lib/x': Permission denied".to_string())
+        );
+
+        let output = make_exec_output(1, "", "open(/etc/hosts): EACCES", "");
+        assert_eq!(
+            sandbox_denial_reason(SandboxType::LinuxSeccomp, &output),
+            Some("open(/etc/hosts): EACCES".to_string())
+        );
+    }
+
+    #[test]
+    fn sandbox_denial_reason_reports_seatbelt_violation_line() {
+        let output = make_exec_output(
+            1,
+            "",
+            "",
+            "Compiling foo\nSandbox: cargo(4242) deny(1) file-write-create /Users/me/.cargo/x\n",
+        );
+        assert_eq!(
+            sandbox_denial_reason(SandboxType::MacosSeatbelt, &output),
+            Some("Sandbox: cargo(4242) deny(1) file-write-create /Users/me/.cargo/x".to_string())
+        );
+
+        let output = make_exec_output(1, "", "zsh:1: operation not permitted: out.txt", "");
+        assert_eq!(
+            sandbox_denial_reason(SandboxType::MacosSeatbelt, &output),
+            Some("zsh:1: operation not permitted: out.txt".to_string())
+        );
+    }
+
+    #[test]
+    fn sandbox_denial_reason_ignores_ordinary_test_failures() {
+        let output = make_exec_output(
+            101,
+            "test result: FAILED. 3 passed; 1 failed",
+            "assertion `left == right` failed",
+            "",
+        );
+        assert_eq!(
+            sandbox_denial_reason(SandboxType::MacosSeatbelt, &output),
+            None
+        );
+    }
+
     #[test]
     fn sandbox_detection_uses_aggregated_output() {
         let output = make_exec_output(
@@ -934,7 +1319,7 @@ mod tests {
             writer.write_all(&bytes).await.expect("write");
         });
 
-        let out = read_capped(reader, None, false).await.expect("read");
+        let out = read_capped(reader, None, false, None).await.expect("read");
         assert_eq!(out.text.len(), EXEC_OUTPUT_MAX_BYTES);
     }
 
@@ -1017,6 +1402,22 @@ mod tests {
         assert_eq!(aggregated.truncated_after_lines, None);
     }
 
+    #[test]
+    fn append_capped_stops_at_a_character_boundary() {
+        // "🚀" is four bytes; the cap lands inside the second spinner glyph,
+        // which itself straddles the chunk boundary.
+        let spinner = "🚀🚀".as_bytes();
+        let mut buf = Vec::new();
+
+        let capped = [&spinner[..5], &spinner[5..]]
+            .into_iter()
+            .map(|chunk| append_capped(&mut buf, chunk, 7))
+            .collect::<Vec<_>>();
+
+        assert_eq!(capped, vec![false, true]);
+        assert_eq!(buf, "🚀".as_bytes());
+    }
+
     #[cfg(unix)]
     #[test]
     fn sandbox_detection_flags_sigsys_exit_code() {
@@ -1025,6 +1426,38 @@ mod tests {
         assert!(is_likely_sandbox_denied(SandboxType::LinuxSeccomp, &output));
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn exec_writes_stdin_and_closes_it() -> Result<()> {
+        let params = ExecParams {
+            command: vec!["cat".to_string()],
+            cwd: std::env::current_dir()?,
+            expiration: 5_000.into(),
+            env: std::env::vars().collect(),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
+            justification: None,
+            arg0: None,
+            limits: ExecLimits::default(),
+        };
+
+        let output = exec(
+            params,
+            SandboxType::None,
+            &SandboxPolicy::DangerFullAccess,
+            None,
+            Some(b"{\"hook\":\"pre_turn\"}".to_vec()),
+        )
+        .await?;
+
+        assert!(!output.timed_out);
+        assert_eq!(
+            output.stdout.from_utf8_lossy().text,
+            "{\"hook\":\"pre_turn\"}"
+        );
+        Ok(())
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn kill_child_process_group_kills_grandchildren_on_timeout() -> Result<()> {
@@ -1052,9 +1485,17 @@ mod tests {
             windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
             justification: None,
             arg0: None,
+            limits: ExecLimits::default(),
         };
 
-        let output = exec(params, SandboxType::None, &SandboxPolicy::ReadOnly, None).await?;
+        let output = exec(
+            params,
+            SandboxType::None,
+            &SandboxPolicy::ReadOnly,
+            None,
+            None,
+        )
+        .await?;
         assert!(output.timed_out);
 
         let stdout = output.stdout.from_utf8_lossy().text;
@@ -1098,6 +1539,7 @@ mod tests {
             windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
             justification: None,
             arg0: None,
+            limits: ExecLimits::default(),
         };
         tokio::spawn(async move {
             tokio::time::sleep(Duration::from_millis(1_000)).await;
@@ -1120,6 +1562,63 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    async fn run_with_limits(script: &str, limits: ExecLimits) -> Result<ExecToolCallOutput> {
+        let cwd = std::env::current_dir()?;
+        let params = ExecParams {
+            command: vec!["/bin/sh".to_string(), "-c".to_string(), script.to_string()],
+            cwd: cwd.clone(),
+            expiration: 20_000.into(),
+            env: std::env::vars().collect(),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
+            justification: None,
+            arg0: None,
+            limits,
+        };
+        process_exec_tool_call(
+            params,
+            &SandboxPolicy::DangerFullAccess,
+            cwd.as_path(),
+            &None,
+            None,
+        )
+        .await
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn output_limit_kills_command() -> Result<()> {
+        let limits = ExecLimits {
+            max_output_bytes: Some(64 * 1024),
+            ..Default::default()
+        };
+        let (kind, output) = match run_with_limits("yes", limits).await {
+            Err(CodexErr::Sandbox(SandboxErr::LimitExceeded { kind, output })) => (kind, output),
+            other => panic!("expected output limit error, got {other:?}"),
+        };
+        assert_eq!(kind, ExecLimitKind::Output);
+        assert_eq!(output.exit_code, EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE);
+        assert!(!output.timed_out);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cpu_time_limit_stops_busy_loop() -> Result<()> {
+        let limits = ExecLimits {
+            cpu_time: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+        let (kind, output) = match run_with_limits("while :; do :; done", limits).await {
+            Err(CodexErr::Sandbox(SandboxErr::LimitExceeded { kind, output })) => (kind, output),
+            other => panic!("expected CPU limit error, got {other:?}"),
+        };
+        assert_eq!(kind, ExecLimitKind::CpuTime);
+        assert_eq!(output.exit_code, EXIT_CODE_SIGNAL_BASE + libc::SIGXCPU);
+        Ok(())
+    }
+
     #[cfg(unix)]
     fn long_running_command() -> Vec<String> {
         vec![
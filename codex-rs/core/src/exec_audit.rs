@@ -0,0 +1,186 @@
+//! Spawn specifications for `exec_audit` and `--dry-run-exec`.
+//!
+//! The event is built from the [`ExecEnv`] produced by the sandbox transform,
+//! so it shows the wrapper command line, the final environment and the limits
+//! exactly as the process would be spawned.
+
+use std::collections::BTreeMap;
+
+use codex_protocol::protocol::ExecCommandSpawnEvent;
+use codex_protocol::protocol::ExecSandboxKind;
+use codex_protocol::protocol::ExecSpawnLimits;
+
+use crate::exec::ExecToolCallOutput;
+use crate::exec::SandboxType;
+use crate::exec::StreamOutput;
+use crate::exec_env::is_sensitive_var;
+use crate::sandboxing::ExecEnv;
+use crate::transcript_export::REDACTED_PLACEHOLDER;
+use crate::transcript_export::Redactor;
+
+/// Output returned to the model in place of running the command.
+pub(crate) const DRY_RUN_OUTPUT: &str =
+    "Dry run: the command was not executed because Codex is running with --dry-run-exec.";
+
+/// What a dry-run command reports back in place of its output.
+pub(crate) fn dry_run_output() -> ExecToolCallOutput {
+    ExecToolCallOutput {
+        stdout: StreamOutput::new(DRY_RUN_OUTPUT.to_string()),
+        aggregated_output: StreamOutput::new(DRY_RUN_OUTPUT.to_string()),
+        ..Default::default()
+    }
+}
+
+pub(crate) struct SpawnAudit<'a> {
+    pub call_id: &'a str,
+    pub turn_id: &'a str,
+    /// The command before the sandbox transform.
+    pub command: &'a [String],
+    pub redactor: &'a Redactor,
+    pub dry_run: bool,
+}
+
+impl SpawnAudit<'_> {
+    pub(crate) fn event(&self, exec_env: &ExecEnv) -> ExecCommandSpawnEvent {
+        let redact_args = |args: &[String]| -> Vec<String> {
+            args.iter().map(|arg| self.redactor.redact(arg)).collect()
+        };
+        ExecCommandSpawnEvent {
+            call_id: self.call_id.to_string(),
+            turn_id: self.turn_id.to_string(),
+            command: redact_args(self.command),
+            spawn_command: redact_args(&exec_env.command),
+            arg0: exec_env.arg0.clone(),
+            cwd: exec_env.cwd.clone(),
+            env: redact_env(&exec_env.env, self.redactor),
+            sandbox: sandbox_kind(exec_env.sandbox),
+            limits: ExecSpawnLimits {
+                cpu_time_seconds: exec_env.limits.cpu_time.map(|cpu| cpu.as_secs()),
+                memory_bytes: exec_env.limits.memory_bytes,
+                max_output_bytes: exec_env.limits.max_output_bytes,
+            },
+            dry_run: self.dry_run,
+        }
+    }
+}
+
+/// Hide values of credential-like variables entirely and scrub known secret
+/// formats from the rest.
+fn redact_env<'a>(
+    env: impl IntoIterator<Item = (&'a String, &'a String)>,
+    redactor: &Redactor,
+) -> BTreeMap<String, String> {
+    env.into_iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive_var(name) {
+                REDACTED_PLACEHOLDER.to_string()
+            } else {
+                redactor.redact(value)
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+fn sandbox_kind(sandbox: SandboxType) -> ExecSandboxKind {
+    match sandbox {
+        SandboxType::None => ExecSandboxKind::None,
+        SandboxType::MacosSeatbelt => ExecSandboxKind::MacosSeatbelt,
+        SandboxType::LinuxSeccomp => ExecSandboxKind::LinuxSeccomp,
+        SandboxType::WindowsRestrictedToken => ExecSandboxKind::WindowsRestrictedToken,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::ExecExpiration;
+    use crate::exec_limits::ExecLimits;
+    use crate::sandboxing::SandboxPermissions;
+    use codex_protocol::config_types::WindowsSandboxLevel;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn event_redacts_credentials_and_keeps_the_wrapper_command() {
+        let exec_env = ExecEnv {
+            command: vec![
+                "/usr/bin/codex-linux-sandbox".to_string(),
+                "--".to_string(),
+                "curl".to_string(),
+                "-H".to_string(),
+                "Authorization: Bearer abc.def".to_string(),
+            ],
+            cwd: PathBuf::from("/repo"),
+            env: HashMap::from([
+                ("PATH".to_string(), "/usr/bin".to_string()),
+                ("GITHUB_TOKEN".to_string(), "plain-value".to_string()),
+                (
+                    "NOTES".to_string(),
+                    "key sk-abcdefghijklmnopqrstuvwxyz".to_string(),
+                ),
+            ]),
+            expiration: ExecExpiration::DefaultTimeout,
+            sandbox: SandboxType::LinuxSeccomp,
+            windows_sandbox_level: WindowsSandboxLevel::Disabled,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+            arg0: Some("codex-linux-sandbox".to_string()),
+            limits: ExecLimits {
+                cpu_time: Some(Duration::from_secs(30)),
+                memory_bytes: None,
+                max_output_bytes: Some(1024),
+            },
+        };
+        let command = vec![
+            "curl".to_string(),
+            "-H".to_string(),
+            "Authorization: Bearer abc.def".to_string(),
+        ];
+        let redactor = Redactor::new(&[]).expect("built-in patterns compile");
+
+        let event = SpawnAudit {
+            call_id: "call-1",
+            turn_id: "turn-1",
+            command: &command,
+            redactor: &redactor,
+            dry_run: true,
+        }
+        .event(&exec_env);
+
+        assert_eq!(
+            event.command,
+            vec!["curl", "-H", "Authorization: [REDACTED]"]
+        );
+        assert_eq!(
+            event.spawn_command,
+            vec![
+                "/usr/bin/codex-linux-sandbox",
+                "--",
+                "curl",
+                "-H",
+                "Authorization: [REDACTED]",
+            ]
+        );
+        assert_eq!(
+            event.env,
+            BTreeMap::from([
+                ("GITHUB_TOKEN".to_string(), "[REDACTED]".to_string()),
+                ("NOTES".to_string(), "key [REDACTED]".to_string()),
+                ("PATH".to_string(), "/usr/bin".to_string()),
+            ])
+        );
+        assert_eq!(event.sandbox, ExecSandboxKind::LinuxSeccomp);
+        assert_eq!(
+            event.limits,
+            ExecSpawnLimits {
+                cpu_time_seconds: Some(30),
+                memory_bytes: None,
+                max_output_bytes: Some(1024),
+            }
+        );
+        assert!(event.dry_run);
+    }
+}
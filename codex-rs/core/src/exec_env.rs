@@ -1,6 +1,7 @@
 use crate::config::types::EnvironmentVariablePattern;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyInherit;
+use crate::provider_env::DotenvFile;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
@@ -10,9 +11,26 @@ use std::collections::HashSet;
 /// process.
 ///
 /// The derivation follows the algorithm documented in the struct-level comment
-/// for [`ShellEnvironmentPolicy`].
-pub fn create_env(policy: &ShellEnvironmentPolicy) -> HashMap<String, String> {
-    populate_env(std::env::vars(), policy)
+/// for [`ShellEnvironmentPolicy`]. Variables from `dotenv`, the session's
+/// dotenv file, are only included when the policy sets `inherit_dotenv`.
+pub fn create_env(
+    policy: &ShellEnvironmentPolicy,
+    dotenv: Option<&DotenvFile>,
+) -> HashMap<String, String> {
+    // Dotenv values go first so that the real environment wins.
+    let dotenv = dotenv
+        .filter(|_| policy.inherit_dotenv)
+        .into_iter()
+        .flat_map(DotenvFile::vars);
+    populate_env(dotenv.chain(std::env::vars()), policy)
+}
+
+/// Whether `name` looks like it holds a credential. Such variables are dropped
+/// by the default excludes and redacted wherever an environment is reported.
+pub(crate) fn is_sensitive_var(name: &str) -> bool {
+    ["*KEY*", "*SECRET*", "*TOKEN*"]
+        .into_iter()
+        .any(|pattern| EnvironmentVariablePattern::new_case_insensitive(pattern).matches(name))
 }
 
 fn populate_env<I>(vars: I, policy: &ShellEnvironmentPolicy) -> HashMap<String, String>
@@ -49,12 +67,7 @@ where
 
     // Step 2 – Apply the default exclude if not disabled.
     if !policy.ignore_default_excludes {
-        let default_excludes = vec![
-            EnvironmentVariablePattern::new_case_insensitive("*KEY*"),
-            EnvironmentVariablePattern::new_case_insensitive("*SECRET*"),
-            EnvironmentVariablePattern::new_case_insensitive("*TOKEN*"),
-        ];
-        env_map.retain(|k, _| !matches_any(k, &default_excludes));
+        env_map.retain(|k, _| !is_sensitive_var(k));
     }
 
     // Step 3 – Apply custom excludes.
@@ -0,0 +1,308 @@
+//! OS-level resource limits for commands run by the shell tools.
+//!
+//! CPU time and memory are enforced by the kernel: with `setrlimit(2)` in the
+//! child before it execs on Unix, and with a Job Object on Windows. Because the
+//! limits are applied to the process we spawn, they are inherited by the
+//! seatbelt and landlock wrappers and everything those wrappers exec. The
+//! output limit is enforced by the reader in [`crate::exec`].
+
+use std::time::Duration;
+
+use codex_protocol::models::ExecLimitOverrides;
+use codex_protocol::protocol::ExecLimitKind;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Resource limits for a single command. `None` leaves a resource unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecLimits {
+    /// CPU time the command may consume.
+    pub cpu_time: Option<Duration>,
+    /// Maximum virtual address space of each process, in bytes.
+    pub memory_bytes: Option<u64>,
+    /// Combined stdout and stderr after which the command is killed.
+    pub max_output_bytes: Option<u64>,
+}
+
+impl ExecLimits {
+    /// The limits with any fields set in `overrides` replacing the defaults.
+    pub fn with_overrides(self, overrides: Option<&ExecLimitOverrides>) -> Self {
+        let Some(overrides) = overrides else {
+            return self;
+        };
+        Self {
+            cpu_time: overrides
+                .cpu_time_seconds
+                .map(Duration::from_secs)
+                .or(self.cpu_time),
+            memory_bytes: overrides
+                .memory_mb
+                .map(|mb| mb.saturating_mul(BYTES_PER_MB))
+                .or(self.memory_bytes),
+            max_output_bytes: overrides.max_output_bytes.or(self.max_output_bytes),
+        }
+    }
+
+    /// Apply the CPU and memory limits to the current process. Only call this
+    /// in a freshly forked child, before it execs the command.
+    #[cfg(unix)]
+    pub(crate) fn apply_to_current_process(&self) -> std::io::Result<()> {
+        if let Some(cpu_time) = self.cpu_time {
+            let seconds = cpu_time.as_secs().max(1) as libc::rlim_t;
+            // The soft limit delivers SIGXCPU, which `exceeded_limit` reports;
+            // the hard limit a second later kills processes that ignore it.
+            lower_rlimit(libc::RLIMIT_CPU, seconds, seconds.saturating_add(1))?;
+        }
+        if let Some(memory_bytes) = self.memory_bytes {
+            let bytes = memory_bytes as libc::rlim_t;
+            lower_rlimit(libc::RLIMIT_AS, bytes, bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Put `child` in a Job Object that enforces the CPU and memory limits.
+    /// The returned job must outlive the child.
+    #[cfg(windows)]
+    pub(crate) fn assign_job(
+        &self,
+        child: &tokio::process::Child,
+    ) -> std::io::Result<Option<windows_job::Job>> {
+        if self.cpu_time.is_none() && self.memory_bytes.is_none() {
+            return Ok(None);
+        }
+        let Some(process) = child.raw_handle() else {
+            return Ok(None);
+        };
+        let job = windows_job::Job::new(self.cpu_time, self.memory_bytes)?;
+        job.assign(process)?;
+        Ok(Some(job))
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type RlimitResource = libc::c_int;
+
+/// Lower `resource` to `soft`/`hard` without raising it: an unprivileged
+/// process cannot raise its hard limit, and a stricter inherited limit wins.
+/// Only async-signal-safe calls are made, as this runs in `pre_exec`.
+#[cfg(unix)]
+fn lower_rlimit(
+    resource: RlimitResource,
+    soft: libc::rlim_t,
+    hard: libc::rlim_t,
+) -> std::io::Result<()> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `limit` is a valid out-pointer for getrlimit.
+    if unsafe { libc::getrlimit(resource, &mut limit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    limit.rlim_max = limit.rlim_max.min(hard);
+    limit.rlim_cur = limit.rlim_cur.min(soft).min(limit.rlim_max);
+    // SAFETY: `limit` is a valid, initialized `rlimit`.
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Exit code Windows gives processes a Job Object terminates for exceeding
+/// their CPU time (`ERROR_NOT_ENOUGH_QUOTA`).
+#[cfg(windows)]
+const WINDOWS_JOB_TIME_LIMIT_EXIT_CODE: i32 = 1816;
+
+/// Output fragments printed by common runtimes when an allocation fails.
+const OUT_OF_MEMORY_MARKERS: [&str; 6] = [
+    "out of memory",
+    "cannot allocate memory",
+    "memoryerror",
+    "bad_alloc",
+    "memory allocation failed",
+    "failed to allocate",
+];
+
+/// Best-effort guess at which kernel-enforced limit stopped a command.
+///
+/// `signal` is the signal that killed the process, if any, and `exit_code` its
+/// exit status otherwise. Shells report a child killed by a signal as
+/// `128 + signal`, so both forms are recognized. Memory exhaustion has no
+/// dedicated signal: allocations simply fail, so a failed command is only
+/// attributed to the memory limit when its output says it ran out of memory.
+pub(crate) fn exceeded_limit(
+    limits: &ExecLimits,
+    #[cfg_attr(not(unix), allow(unused_variables))] signal: Option<i32>,
+    exit_code: i32,
+    output: &str,
+) -> Option<ExecLimitKind> {
+    if limits.cpu_time.is_some() {
+        #[cfg(unix)]
+        if signal == Some(libc::SIGXCPU) || exit_code == 128 + libc::SIGXCPU {
+            return Some(ExecLimitKind::CpuTime);
+        }
+        #[cfg(windows)]
+        if exit_code == WINDOWS_JOB_TIME_LIMIT_EXIT_CODE {
+            return Some(ExecLimitKind::CpuTime);
+        }
+    }
+    if limits.memory_bytes.is_some() && exit_code != 0 {
+        let output = output.to_lowercase();
+        if OUT_OF_MEMORY_MARKERS
+            .iter()
+            .any(|marker| output.contains(marker))
+        {
+            return Some(ExecLimitKind::Memory);
+        }
+    }
+    None
+}
+
+#[cfg(windows)]
+pub(crate) mod windows_job {
+    use std::io;
+    use std::time::Duration;
+
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::Foundation::HANDLE;
+    use windows_sys::Win32::System::JobObjects::AssignProcessToJobObject;
+    use windows_sys::Win32::System::JobObjects::CreateJobObjectW;
+    use windows_sys::Win32::System::JobObjects::JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+    use windows_sys::Win32::System::JobObjects::JOB_OBJECT_LIMIT_PROCESS_TIME;
+    use windows_sys::Win32::System::JobObjects::JOBOBJECT_EXTENDED_LIMIT_INFORMATION;
+    use windows_sys::Win32::System::JobObjects::JobObjectExtendedLimitInformation;
+    use windows_sys::Win32::System::JobObjects::SetInformationJobObject;
+
+    /// Job Object times are expressed in 100-nanosecond ticks.
+    const TICKS_PER_SECOND: i64 = 10_000_000;
+
+    /// An owned Job Object handle, closed on drop. Closing the handle does not
+    /// terminate the processes in the job.
+    pub(crate) struct Job(HANDLE);
+
+    impl Job {
+        pub(crate) fn new(
+            cpu_time: Option<Duration>,
+            memory_bytes: Option<u64>,
+        ) -> io::Result<Self> {
+            // SAFETY: null attributes and name create an anonymous job.
+            let handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+            if handle == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let job = Job(handle);
+
+            // SAFETY: the struct is plain data for which all-zero is valid.
+            let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+            if let Some(cpu_time) = cpu_time {
+                info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_TIME;
+                info.BasicLimitInformation.PerProcessUserTimeLimit =
+                    (cpu_time.as_secs() as i64).saturating_mul(TICKS_PER_SECOND);
+            }
+            if let Some(memory_bytes) = memory_bytes {
+                info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+                info.ProcessMemoryLimit = usize::try_from(memory_bytes).unwrap_or(usize::MAX);
+            }
+            // SAFETY: `info` is a valid JOBOBJECT_EXTENDED_LIMIT_INFORMATION
+            // and the size matches.
+            let ok = unsafe {
+                SetInformationJobObject(
+                    job.0,
+                    JobObjectExtendedLimitInformation,
+                    &info as *const _ as *const _,
+                    std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+                )
+            };
+            if ok == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(job)
+        }
+
+        pub(crate) fn assign(&self, process: std::os::windows::io::RawHandle) -> io::Result<()> {
+            // SAFETY: both handles are valid for the duration of the call.
+            if unsafe { AssignProcessToJobObject(self.0, process as HANDLE) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for Job {
+        fn drop(&mut self) {
+            // SAFETY: we own the handle and close it exactly once.
+            unsafe {
+                CloseHandle(self.0);
+            }
+        }
+    }
+
+    // SAFETY: a Job Object handle may be used and closed from any thread.
+    unsafe impl Send for Job {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn overrides_replace_only_the_fields_they_set() {
+        let defaults = ExecLimits {
+            cpu_time: Some(Duration::from_secs(60)),
+            memory_bytes: None,
+            max_output_bytes: Some(1_000),
+        };
+
+        assert_eq!(defaults.with_overrides(None), defaults);
+        assert_eq!(
+            defaults.with_overrides(Some(&ExecLimitOverrides {
+                cpu_time_seconds: Some(5),
+                memory_mb: Some(2),
+                max_output_bytes: None,
+            })),
+            ExecLimits {
+                cpu_time: Some(Duration::from_secs(5)),
+                memory_bytes: Some(2 * BYTES_PER_MB),
+                max_output_bytes: Some(1_000),
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn classifies_cpu_limit_only_when_one_was_set() {
+        let limits = ExecLimits {
+            cpu_time: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+        assert_eq!(
+            exceeded_limit(&limits, Some(libc::SIGXCPU), -1, ""),
+            Some(ExecLimitKind::CpuTime)
+        );
+        assert_eq!(
+            exceeded_limit(&limits, None, 128 + libc::SIGXCPU, ""),
+            Some(ExecLimitKind::CpuTime)
+        );
+        assert_eq!(
+            exceeded_limit(&ExecLimits::default(), Some(libc::SIGXCPU), -1, ""),
+            None
+        );
+    }
+
+    #[test]
+    fn classifies_memory_limit_from_allocation_failures() {
+        let limits = ExecLimits {
+            memory_bytes: Some(BYTES_PER_MB),
+            ..Default::default()
+        };
+        assert_eq!(
+            exceeded_limit(&limits, None, 1, "Traceback ...\nMemoryError\n"),
+            Some(ExecLimitKind::Memory)
+        );
+        assert_eq!(exceeded_limit(&limits, None, 1, "test failed"), None);
+        assert_eq!(exceeded_limit(&limits, None, 0, "out of memory"), None);
+    }
+}
@@ -8,7 +8,7 @@ use arc_swap::ArcSwap;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigLayerStackOrdering;
 use crate::is_dangerous_command::command_might_be_dangerous;
-use crate::is_safe_command::is_known_safe_command;
+use crate::is_safe_command::SafeCommands;
 use codex_execpolicy::AmendError;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
@@ -85,6 +85,7 @@ pub enum ExecPolicyUpdateError {
 
 pub(crate) struct ExecPolicyManager {
     policy: ArcSwap<Policy>,
+    safe_commands: SafeCommands,
 }
 
 pub(crate) struct ExecApprovalRequest<'a> {
@@ -94,15 +95,31 @@ pub(crate) struct ExecApprovalRequest<'a> {
     pub(crate) sandbox_policy: &'a SandboxPolicy,
     pub(crate) sandbox_permissions: SandboxPermissions,
     pub(crate) prefix_rule: Option<Vec<String>>,
+    /// The command runs outside the session cwd and the workspace roots, so
+    /// the safe list does not vouch for it.
+    pub(crate) cwd_outside_workspace: bool,
 }
 
 impl ExecPolicyManager {
     pub(crate) fn new(policy: Arc<Policy>) -> Self {
         Self {
             policy: ArcSwap::from(policy),
+            safe_commands: SafeCommands::default(),
         }
     }
 
+    /// Replaces the built-in read-only command table, e.g. with one that
+    /// includes the `[safe_commands]` config.
+    pub(crate) fn with_safe_commands(mut self, safe_commands: SafeCommands) -> Self {
+        self.safe_commands = safe_commands;
+        self
+    }
+
+    /// Whether `command` only reads and can run without approval.
+    pub(crate) fn is_known_safe_command(&self, command: &[String]) -> bool {
+        self.safe_commands.is_known_safe_command(command)
+    }
+
     pub(crate) async fn load(
         features: &Features,
         config_stack: &ConfigLayerStack,
@@ -130,16 +147,19 @@ impl ExecPolicyManager {
             sandbox_policy,
             sandbox_permissions,
             prefix_rule,
+            cwd_outside_workspace,
         } = req;
         let exec_policy = self.current();
         let commands =
             parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
         let exec_policy_fallback = |cmd: &[String]| {
             render_decision_for_unmatched_command(
+                &self.safe_commands,
                 approval_policy,
                 sandbox_policy,
                 cmd,
                 sandbox_permissions,
+                cwd_outside_workspace,
             )
         };
         let evaluation = exec_policy.check_multiple(commands.iter(), &exec_policy_fallback);
@@ -296,12 +316,20 @@ pub async fn load_exec_policy(config_stack: &ConfigLayerStack) -> Result<Policy,
 
 /// If a command is not matched by any execpolicy rule, derive a [`Decision`].
 pub fn render_decision_for_unmatched_command(
+    safe_commands: &SafeCommands,
     approval_policy: AskForApproval,
     sandbox_policy: &SandboxPolicy,
     command: &[String],
     sandbox_permissions: SandboxPermissions,
+    cwd_outside_workspace: bool,
 ) -> Decision {
-    if is_known_safe_command(command) {
+    // Even a read-only command is only trusted where the user expects the
+    // agent to work: `cat .env` means something else in their home directory.
+    if cwd_outside_workspace {
+        if !matches!(approval_policy, AskForApproval::Never) {
+            return Decision::Prompt;
+        }
+    } else if safe_commands.is_known_safe_command(command) {
         return Decision::Allow;
     }
 
@@ -819,6 +847,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -858,6 +887,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -888,6 +918,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -919,6 +950,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -943,6 +975,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -955,6 +988,41 @@ prefix_rule(
         );
     }
 
+    #[tokio::test]
+    async fn safe_command_in_hostile_cwd_needs_approval() {
+        let command = vec!["cat".to_string(), ".ssh/id_rsa".to_string()];
+        let manager = ExecPolicyManager::default();
+        let features = Features::with_defaults();
+        let request = |cwd_outside_workspace| ExecApprovalRequest {
+            features: &features,
+            command: &command,
+            approval_policy: AskForApproval::UnlessTrusted,
+            sandbox_policy: &SandboxPolicy::ReadOnly,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            prefix_rule: None,
+            cwd_outside_workspace,
+        };
+
+        assert_eq!(
+            manager
+                .create_exec_approval_requirement_for_command(request(false))
+                .await,
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command.clone())),
+            }
+        );
+        assert_eq!(
+            manager
+                .create_exec_approval_requirement_for_command(request(true))
+                .await,
+            ExecApprovalRequirement::NeedsApproval {
+                reason: None,
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command.clone())),
+            }
+        );
+    }
+
     #[tokio::test]
     async fn request_rule_uses_prefix_rule() {
         let command = vec![
@@ -974,6 +1042,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -1012,6 +1081,7 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::DangerFullAccess,
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     prefix_rule: None,
+                    cwd_outside_workspace: false,
                 })
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -1087,6 +1157,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -1115,6 +1186,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -1146,6 +1218,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -1174,6 +1247,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -1213,6 +1287,7 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::ReadOnly,
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     prefix_rule: None,
+                    cwd_outside_workspace: false,
                 })
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -1237,6 +1312,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -1268,6 +1344,7 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd_outside_workspace: false,
             })
             .await;
 
@@ -1336,6 +1413,7 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::ReadOnly,
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    cwd_outside_workspace: false,
                 })
                 .await,
             "{pwsh_approval_reason}"
@@ -1360,6 +1438,7 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::ReadOnly,
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    cwd_outside_workspace: false,
                 })
                 .await,
             r#"On all platforms, a forbidden command should require approval
@@ -1380,6 +1459,7 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::ReadOnly,
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    cwd_outside_workspace: false,
                 })
                 .await,
             r#"On all platforms, a forbidden command should require approval
@@ -105,6 +105,8 @@ pub enum Feature {
     Sqlite,
     /// Append additional AGENTS.md guidance to user instructions.
     ChildAgentsMd,
+    /// Let the agent save notes to `.codex/memory.md` and read them back in later sessions.
+    ProjectMemory,
     /// Enforce UTF8 output in Powershell.
     PowershellUtf8,
     /// Compress request bodies (zstd) when sending streaming requests to codex-backend.
@@ -449,6 +451,12 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::ProjectMemory,
+        key: "project_memory",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::ApplyPatchFreeform,
         key: "apply_patch_freeform",
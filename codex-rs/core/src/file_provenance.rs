@@ -0,0 +1,320 @@
+//! Per-turn record of the files a turn read or wrote, reported through
+//! `EventMsg::TurnFiles` so IDE integrations can badge touched files.
+//!
+//! Patch applications are exact: the paths come straight from the
+//! `FileChange` map. Shell commands are best effort. Reads come from
+//! `parse_command`, and writes come from output redirections and a short list
+//! of well-known tools (`touch`, `rm`, `cp`, `mv`, `tee`, `sed -i`, ...).
+//! Anything a script does indirectly (a build, a test run, an interpreter
+//! writing files) is not seen. The result is advisory and must not be treated
+//! as a security boundary.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::parse_command::ParsedCommand;
+
+use crate::bash::extract_bash_command;
+use crate::protocol::FileChange;
+use crate::protocol::TurnFilesEvent;
+
+const COMMAND_SEPARATORS: [&str; 5] = [";", "&&", "||", "|", "&"];
+const OUTPUT_REDIRECTS: [&str; 7] = [">", ">>", "1>", "1>>", "2>", "2>>", "&>"];
+
+#[derive(Debug, Default)]
+pub(crate) struct FileProvenance {
+    read: BTreeSet<PathBuf>,
+    written: BTreeSet<PathBuf>,
+    /// Absolute paths recorded since the last [`FileProvenance::take_touched`].
+    touched: Vec<PathBuf>,
+}
+
+impl FileProvenance {
+    /// Record the files touched by a patch. `workspace` is the turn's working
+    /// directory; relative patch paths are resolved against it.
+    pub(crate) fn record_patch(
+        &mut self,
+        workspace: &Path,
+        changes: &HashMap<PathBuf, FileChange>,
+    ) {
+        for (path, change) in changes {
+            self.insert_written(workspace, workspace, path);
+            if let FileChange::Update {
+                move_path: Some(dest),
+                ..
+            } = change
+            {
+                self.insert_written(workspace, workspace, dest);
+            }
+        }
+    }
+
+    /// Record the files a shell command probably read or wrote. `cwd` is the
+    /// directory the command ran in.
+    pub(crate) fn record_command(
+        &mut self,
+        workspace: &Path,
+        cwd: &Path,
+        command: &[String],
+        parsed_cmd: &[ParsedCommand],
+    ) {
+        for parsed in parsed_cmd {
+            if let ParsedCommand::Read { path, .. } = parsed {
+                self.insert_read(workspace, cwd, path);
+            }
+        }
+        let words = match extract_bash_command(command) {
+            Some((_, script)) => shlex::split(script).unwrap_or_default(),
+            None => command.to_vec(),
+        };
+        let accesses = command_file_accesses(&words);
+        for path in accesses.read {
+            self.insert_read(workspace, cwd, Path::new(&path));
+        }
+        for path in accesses.written {
+            self.insert_written(workspace, cwd, Path::new(&path));
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.read.is_empty() && self.written.is_empty()
+    }
+
+    /// Absolute paths of the files recorded since the previous call, for the
+    /// workspace watcher.
+    pub(crate) fn take_touched(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.touched)
+    }
+
+    /// Files that were both read and written are only listed as written.
+    pub(crate) fn into_event(self) -> TurnFilesEvent {
+        let Self { read, written, .. } = self;
+        TurnFilesEvent {
+            files_read: read.difference(&written).cloned().collect(),
+            files_written: written.into_iter().collect(),
+        }
+    }
+
+    fn insert_read(&mut self, workspace: &Path, cwd: &Path, path: &Path) {
+        if let Some(path) = workspace_relative(workspace, cwd, path) {
+            self.touched
+                .push(normalize_lexically(workspace).join(&path));
+            self.read.insert(path);
+        }
+    }
+
+    fn insert_written(&mut self, workspace: &Path, cwd: &Path, path: &Path) {
+        if let Some(path) = workspace_relative(workspace, cwd, path) {
+            self.touched
+                .push(normalize_lexically(workspace).join(&path));
+            self.written.insert(path);
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct CommandFileAccesses {
+    read: Vec<String>,
+    written: Vec<String>,
+}
+
+fn command_file_accesses(words: &[String]) -> CommandFileAccesses {
+    let mut accesses = CommandFileAccesses::default();
+    let mut segment: Vec<&str> = Vec::new();
+    // `shlex` keeps a trailing `;` on the word before it (`a.txt;`).
+    let mut words = words.iter().flat_map(|word| match word.strip_suffix(';') {
+        Some(stripped) if !stripped.is_empty() => vec![stripped, ";"],
+        _ => vec![word.as_str()],
+    });
+    while let Some(word) = words.next() {
+        if COMMAND_SEPARATORS.contains(&word) {
+            record_known_tool(&segment, &mut accesses);
+            segment.clear();
+        } else if OUTPUT_REDIRECTS.contains(&word) {
+            if let Some(target) = words.next() {
+                push_path(&mut accesses.written, target);
+            }
+        } else if word == "<" {
+            if let Some(target) = words.next() {
+                push_path(&mut accesses.read, target);
+            }
+        } else if let Some(target) = attached_redirect_target(word) {
+            push_path(&mut accesses.written, target);
+        } else {
+            segment.push(word);
+        }
+    }
+    record_known_tool(&segment, &mut accesses);
+    accesses
+}
+
+/// `>out.txt`, `>>log`, `2>err.txt`.
+fn attached_redirect_target(word: &str) -> Option<&str> {
+    let rest = word
+        .strip_prefix("2>")
+        .or_else(|| word.strip_prefix("1>"))
+        .or_else(|| word.strip_prefix('>'))?;
+    let target = rest.strip_prefix('>').unwrap_or(rest);
+    (!target.is_empty()).then_some(target)
+}
+
+fn record_known_tool(segment: &[&str], accesses: &mut CommandFileAccesses) {
+    let Some((program, args)) = segment.split_first() else {
+        return;
+    };
+    let program = program.rsplit('/').next().unwrap_or(program);
+    let operands: Vec<&str> = args
+        .iter()
+        .copied()
+        .filter(|arg| !arg.starts_with('-'))
+        .collect();
+    match program {
+        "touch" | "rm" | "tee" | "truncate" | "mv" => {
+            for operand in operands {
+                push_path(&mut accesses.written, operand);
+            }
+        }
+        "cp" | "install" => {
+            if let Some((dest, sources)) = operands.split_last() {
+                for source in sources {
+                    push_path(&mut accesses.read, source);
+                }
+                push_path(&mut accesses.written, dest);
+            }
+        }
+        "sed"
+            if args
+                .iter()
+                .any(|arg| arg.starts_with("-i") || *arg == "--in-place") =>
+        {
+            // The first operand is the sed script.
+            for operand in operands.iter().skip(1) {
+                push_path(&mut accesses.written, operand);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn push_path(paths: &mut Vec<String>, candidate: &str) {
+    let is_pattern_or_fd = candidate.starts_with('&')
+        || candidate.starts_with("/dev/")
+        || candidate.contains(['*', '?', '$', '`']);
+    if !is_pattern_or_fd {
+        paths.push(candidate.to_string());
+    }
+}
+
+/// `path` relative to `workspace`, or `None` when it resolves outside it.
+fn workspace_relative(workspace: &Path, cwd: &Path, path: &Path) -> Option<PathBuf> {
+    let absolute = normalize_lexically(&cwd.join(path));
+    let relative = absolute.strip_prefix(normalize_lexically(workspace)).ok()?;
+    (!relative.as_os_str().is_empty()).then(|| relative.to_path_buf())
+}
+
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn words(script: &str) -> Vec<String> {
+        shlex::split(script).expect("valid script")
+    }
+
+    #[test]
+    fn redirects_and_known_tools_are_detected() {
+        let accesses = command_file_accesses(&words(
+            "cat a.txt > out.txt; echo hi >>log.txt 2>/dev/null && cp src.rs dst.rs | sed -i 's/a/b/' notes.md < input.txt",
+        ));
+        assert_eq!(
+            accesses,
+            CommandFileAccesses {
+                read: vec!["src.rs".to_string(), "input.txt".to_string()],
+                written: vec![
+                    "out.txt".to_string(),
+                    "log.txt".to_string(),
+                    "dst.rs".to_string(),
+                    "notes.md".to_string(),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn globs_and_fd_redirects_are_ignored() {
+        let accesses = command_file_accesses(&words("rm -f *.tmp 2>&1 > $OUT"));
+        assert_eq!(accesses, CommandFileAccesses::default());
+    }
+
+    #[test]
+    fn paths_are_workspace_relative_and_deduplicated() {
+        let workspace = Path::new("/repo");
+        let mut provenance = FileProvenance::default();
+        provenance.record_command(
+            workspace,
+            Path::new("/repo/src"),
+            &[
+                "bash".to_string(),
+                "-lc".to_string(),
+                "cat lib.rs && touch ../README.md /tmp/outside.txt".to_string(),
+            ],
+            &[ParsedCommand::Read {
+                cmd: "cat lib.rs".to_string(),
+                name: "lib.rs".to_string(),
+                path: PathBuf::from("lib.rs"),
+            }],
+        );
+        let touched = provenance.take_touched();
+        assert!(touched.contains(&PathBuf::from("/repo/src/lib.rs")));
+        assert!(touched.contains(&PathBuf::from("/repo/README.md")));
+        assert!(!touched.contains(&PathBuf::from("/tmp/outside.txt")));
+        provenance.record_patch(
+            workspace,
+            &HashMap::from([
+                (
+                    PathBuf::from("/repo/README.md"),
+                    FileChange::Update {
+                        unified_diff: String::new(),
+                        move_path: None,
+                    },
+                ),
+                (
+                    PathBuf::from("src/old.rs"),
+                    FileChange::Update {
+                        unified_diff: String::new(),
+                        move_path: Some(PathBuf::from("/repo/src/new.rs")),
+                    },
+                ),
+            ]),
+        );
+
+        assert_eq!(
+            provenance.into_event(),
+            TurnFilesEvent {
+                files_read: vec![PathBuf::from("src/lib.rs")],
+                files_written: vec![
+                    PathBuf::from("README.md"),
+                    PathBuf::from("src/new.rs"),
+                    PathBuf::from("src/old.rs"),
+                ],
+            }
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Provider-side uploads for `UserInput::LocalFile` attachments.
+//!
+//! With `upload_attachments` set and a provider that accepts uploads, an
+//! attached file is uploaded once and referenced by its file id; later
+//! attachments with the same contents reuse the id for the rest of the
+//! session. Other providers, and files that fail to upload, get the contents
+//! inline.
+//!
+//! Every upload is recorded in the rollout as a [`FileUploadItem`]. Uploads
+//! are deleted on shutdown and may expire sooner, so the ids in a resumed
+//! history are not trusted: before a request is sent they are replaced by a
+//! fresh upload of the same path when its contents still match, or by the
+//! contents inline when the provider takes no uploads.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::FileAttachedEvent;
+use codex_protocol::protocol::FileUploadItem;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::WarningEvent;
+use codex_protocol::user_input::UserInput;
+use sha2::Digest;
+use sha2::Sha256;
+use tracing::warn;
+
+use crate::client::ModelClient;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::protocol::EventMsg;
+
+#[derive(Default)]
+pub(crate) struct FileUploads {
+    /// Uploads made by this process, by content hash.
+    by_hash: HashMap<String, FileUploadItem>,
+    /// Ids to delete on shutdown, with the client that uploaded them.
+    to_delete: Vec<(String, ModelClient)>,
+    /// Uploads recorded in a resumed rollout, by file id.
+    restored: HashMap<String, FileUploadItem>,
+    /// What each restored id is sent as once resolved.
+    resolved: HashMap<String, ContentItem>,
+}
+
+impl FileUploads {
+    pub(crate) fn restore(&mut self, rollout_items: &[RolloutItem]) {
+        for item in rollout_items {
+            if let RolloutItem::FileUpload(upload) = item {
+                self.restored.insert(upload.file_id.clone(), upload.clone());
+            }
+        }
+    }
+
+    /// The upload made by this process as `file_id`, if any.
+    fn current(&self, file_id: &str) -> Option<&FileUploadItem> {
+        self.by_hash
+            .values()
+            .find(|upload| upload.file_id == file_id)
+    }
+}
+
+/// Uploads the local files attached to `input`, or leaves them to be inlined,
+/// and reports each one with a [`FileAttachedEvent`]. Returns the file id of
+/// every uploaded attachment, by path.
+pub(crate) async fn attach_local_files(
+    sess: &Session,
+    turn_context: &TurnContext,
+    input: &[UserInput],
+) -> HashMap<PathBuf, String> {
+    let mut uploaded = HashMap::new();
+    for item in input {
+        let UserInput::LocalFile { path } = item else {
+            continue;
+        };
+        // Unreadable files are reported in the message itself.
+        let Ok(contents) = tokio::fs::read(path).await else {
+            continue;
+        };
+        let file_id = if turn_context.client.uploads_attachments() {
+            upload(sess, turn_context, path, &contents, None).await
+        } else {
+            None
+        };
+        if let Some(file_id) = &file_id {
+            uploaded.insert(path.clone(), file_id.clone());
+        }
+        sess.send_event(
+            turn_context,
+            EventMsg::FileAttached(FileAttachedEvent {
+                path: path.clone(),
+                bytes: contents.len() as u64,
+                file_id,
+            }),
+        )
+        .await;
+    }
+    uploaded
+}
+
+/// Replaces the file ids in `input` that cannot be sent as they are: ids from
+/// an earlier process become a fresh upload of the same file, and any id
+/// becomes the file's contents when the provider takes no uploads.
+pub(crate) async fn resolve_uploaded_files(
+    sess: &Session,
+    turn_context: &TurnContext,
+    mut input: Vec<ResponseItem>,
+) -> Vec<ResponseItem> {
+    for item in &mut input {
+        let ResponseItem::Message { content, .. } = item else {
+            continue;
+        };
+        for content_item in content.iter_mut() {
+            let ContentItem::InputFile { file_id } = content_item else {
+                continue;
+            };
+            if let Some(replacement) = resolve_file_id(sess, turn_context, file_id).await {
+                *content_item = replacement;
+            }
+        }
+    }
+    input
+}
+
+/// What to send in place of `file_id`, or `None` to send it unchanged.
+async fn resolve_file_id(
+    sess: &Session,
+    turn_context: &TurnContext,
+    file_id: &str,
+) -> Option<ContentItem> {
+    let uploads_enabled = turn_context.client.uploads_attachments();
+    let recorded = {
+        let uploads = sess.services.file_uploads.lock().await;
+        let current = uploads.current(file_id);
+        if uploads_enabled && current.is_some() {
+            return None;
+        }
+        if let Some(resolved) = uploads.resolved.get(file_id) {
+            return Some(resolved.clone());
+        }
+        current.or_else(|| uploads.restored.get(file_id)).cloned()
+    };
+    let Some(recorded) = recorded else {
+        // An id with no record can only be kept where uploads are accepted.
+        return (!uploads_enabled).then(|| ContentItem::InputText {
+            text: format!("[Uploaded file {file_id} is no longer available.]"),
+        });
+    };
+
+    let contents = tokio::fs::read(&recorded.path)
+        .await
+        .ok()
+        .filter(|contents| content_hash(contents) == recorded.sha256);
+    let resolved = match contents {
+        Some(contents) if uploads_enabled => {
+            match upload(
+                sess,
+                turn_context,
+                &recorded.path,
+                &contents,
+                Some(file_id.to_string()),
+            )
+            .await
+            {
+                Some(new_id) => ContentItem::InputFile { file_id: new_id },
+                None => inline_contents(&recorded.path, &contents),
+            }
+        }
+        Some(contents) => inline_contents(&recorded.path, &contents),
+        None => ContentItem::InputText {
+            text: format!(
+                "[The uploaded copy of `{}` is no longer available and the file has changed or been removed since it was attached.]",
+                recorded.path.display()
+            ),
+        },
+    };
+    sess.services
+        .file_uploads
+        .lock()
+        .await
+        .resolved
+        .insert(file_id.to_string(), resolved.clone());
+    Some(resolved)
+}
+
+/// Uploads `contents` unless identical contents were uploaded earlier in this
+/// process, records the upload in the rollout, and returns the file id. Falls
+/// back to `None` with a warning when the upload fails.
+async fn upload(
+    sess: &Session,
+    turn_context: &TurnContext,
+    path: &Path,
+    contents: &[u8],
+    replaces: Option<String>,
+) -> Option<String> {
+    let sha256 = content_hash(contents);
+    let mut uploads = sess.services.file_uploads.lock().await;
+    if let Some(upload) = uploads.by_hash.get(&sha256) {
+        return Some(upload.file_id.clone());
+    }
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "attachment".to_string());
+    let file_id = match turn_context.client.upload_file(&filename, contents).await {
+        Ok(file_id) => file_id,
+        Err(err) => {
+            drop(uploads);
+            sess.send_event(
+                turn_context,
+                EventMsg::Warning(WarningEvent {
+                    message: format!(
+                        "Could not upload `{}`; attaching it inline instead: {err}",
+                        path.display()
+                    ),
+                }),
+            )
+            .await;
+            return None;
+        }
+    };
+    let upload = FileUploadItem {
+        file_id: file_id.clone(),
+        path: path.to_path_buf(),
+        sha256: sha256.clone(),
+        bytes: contents.len() as u64,
+        replaces,
+    };
+    uploads.by_hash.insert(sha256, upload.clone());
+    uploads
+        .to_delete
+        .push((file_id.clone(), turn_context.client.clone()));
+    drop(uploads);
+    sess.persist_rollout_items(&[RolloutItem::FileUpload(upload)])
+        .await;
+    Some(file_id)
+}
+
+/// Deletes the files uploaded by this process. Failures are only logged: the
+/// provider expires uploads on its own.
+pub(crate) async fn delete_uploaded_files(sess: &Session) {
+    let to_delete = std::mem::take(&mut sess.services.file_uploads.lock().await.to_delete);
+    for (file_id, client) in to_delete {
+        if let Err(err) = client.delete_file(&file_id).await {
+            warn!("failed to delete uploaded file {file_id}: {err}");
+        }
+    }
+}
+
+fn content_hash(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+/// `contents` as the text between the tags of an uploaded attachment.
+fn inline_contents(path: &Path, contents: &[u8]) -> ContentItem {
+    let text = match std::str::from_utf8(contents) {
+        Ok(text) if !text.contains('\0') => text.to_string(),
+        _ => format!(
+            "[The uploaded copy of `{}` is no longer available, and it is not a text file that can be attached inline.]",
+            path.display()
+        ),
+    };
+    ContentItem::InputText { text }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn restore_indexes_uploads_by_id() {
+        let upload = FileUploadItem {
+            file_id: "file-abc".to_string(),
+            path: PathBuf::from("/repo/spec.md"),
+            sha256: content_hash(b"the spec"),
+            bytes: 8,
+            replaces: None,
+        };
+        let mut uploads = FileUploads::default();
+
+        uploads.restore(&[RolloutItem::FileUpload(upload.clone())]);
+
+        assert_eq!(uploads.restored.get("file-abc"), Some(&upload));
+        assert_eq!(uploads.current("file-abc"), None);
+    }
+
+    #[test]
+    fn content_hash_is_hex_sha256() {
+        assert_eq!(
+            content_hash(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
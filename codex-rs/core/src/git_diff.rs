@@ -0,0 +1,468 @@
+//! Structured `git diff` for a working directory.
+//!
+//! Backs the TUI `/diff` command and the app-server `git/diff` request. The
+//! result carries per-file stats alongside the unified diff text so callers
+//! can render either. Untracked files are included when diffing the working
+//! tree, and binary files are reported without their contents.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::process::Command;
+
+/// Bytes inspected when deciding whether an untracked file is binary. Matches
+/// the heuristic git itself uses.
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+/// Which changes to diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GitDiffTarget {
+    /// Unstaged changes to tracked files, plus untracked files.
+    #[default]
+    WorkingTree,
+    /// Changes staged in the index, compared with `HEAD`.
+    Staged,
+    /// A revision or range accepted by `git diff`, e.g. `HEAD~3..` or `main...feature`.
+    Range(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitDiffOptions {
+    pub target: GitDiffTarget,
+    /// Limit the diff to these paths, relative to the working directory.
+    pub paths: Vec<PathBuf>,
+    /// Emit ANSI colors in the unified diff text.
+    pub color: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitDiffFileStat {
+    /// Path relative to the repository root. For renames, the new path.
+    pub path: String,
+    /// Previous path when git detected a rename or copy.
+    pub old_path: Option<String>,
+    pub additions: u64,
+    pub deletions: u64,
+    /// Binary files report zero additions and deletions.
+    pub binary: bool,
+    pub untracked: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitDiff {
+    pub files: Vec<GitDiffFileStat>,
+    pub unified_diff: String,
+}
+
+/// Compute the diff selected by `options` for the repository containing `cwd`.
+///
+/// Returns `Ok(None)` when `cwd` is not inside a git work tree (or git is not
+/// installed).
+pub async fn get_git_diff(cwd: &Path, options: &GitDiffOptions) -> io::Result<Option<GitDiff>> {
+    if !inside_git_repo(cwd).await? {
+        return Ok(None);
+    }
+
+    let mut base_args: Vec<String> = vec!["diff".to_string()];
+    match &options.target {
+        GitDiffTarget::WorkingTree => {}
+        GitDiffTarget::Staged => base_args.push("--cached".to_string()),
+        GitDiffTarget::Range(range) => {
+            // Keep a range from being parsed as an option.
+            if range.is_empty() || range.starts_with('-') {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid diff range `{range}`"),
+                ));
+            }
+            base_args.push(range.clone());
+        }
+    }
+    let pathspec: Vec<String> = options
+        .paths
+        .iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+
+    let color = if options.color {
+        "--color"
+    } else {
+        "--no-color"
+    };
+    let mut unified_args = base_args.clone();
+    unified_args.push(color.to_string());
+    unified_args.push("--".to_string());
+    unified_args.extend(pathspec.iter().cloned());
+
+    let mut numstat_args = base_args;
+    numstat_args.extend(["--numstat".to_string(), "-z".to_string(), "--".to_string()]);
+    numstat_args.extend(pathspec.iter().cloned());
+
+    let (unified_res, numstat_res) = tokio::join!(
+        run_git_capture_diff(cwd, &unified_args),
+        run_git_capture_diff(cwd, &numstat_args),
+    );
+    let mut diff = GitDiff {
+        files: parse_numstat_z(&numstat_res?),
+        unified_diff: unified_res?,
+    };
+
+    if options.target == GitDiffTarget::WorkingTree {
+        append_untracked(cwd, &pathspec, color, &mut diff).await?;
+    }
+
+    Ok(Some(diff))
+}
+
+async fn append_untracked(
+    cwd: &Path,
+    pathspec: &[String],
+    color: &str,
+    diff: &mut GitDiff,
+) -> io::Result<()> {
+    let mut ls_args = vec![
+        "ls-files".to_string(),
+        "--others".to_string(),
+        "--exclude-standard".to_string(),
+        "-z".to_string(),
+        "--".to_string(),
+    ];
+    ls_args.extend(pathspec.iter().cloned());
+    let untracked = run_git_capture_stdout(cwd, &ls_args).await?;
+
+    if untracked.is_empty() {
+        return Ok(());
+    }
+    // `ls-files` reports paths relative to `cwd`; report them relative to the
+    // repository root like the tracked stats.
+    let prefix = run_git_capture_stdout(cwd, &["rev-parse".to_string(), "--show-prefix".to_string()])
+        .await?
+        .trim_end_matches('\n')
+        .to_string();
+
+    let null_path = if cfg!(windows) { "NUL" } else { "/dev/null" };
+    let mut join_set: tokio::task::JoinSet<io::Result<(usize, String, GitDiffFileStat)>> =
+        tokio::task::JoinSet::new();
+    for (index, file) in untracked.split('\0').filter(|s| !s.is_empty()).enumerate() {
+        let cwd = cwd.to_path_buf();
+        let file = file.to_string();
+        let color = color.to_string();
+        let prefix = prefix.clone();
+        join_set.spawn(async move {
+            let args = [
+                "diff".to_string(),
+                color,
+                "--no-index".to_string(),
+                "--".to_string(),
+                null_path.to_string(),
+                file.clone(),
+            ];
+            let text = run_git_capture_diff(&cwd, &args).await?;
+            let stat = untracked_file_stat(&cwd, &prefix, &file).await?;
+            Ok((index, text, stat))
+        });
+    }
+
+    // Keep the untracked section in `ls-files` order despite running in parallel.
+    let mut entries = Vec::new();
+    while let Some(res) = join_set.join_next().await {
+        match res {
+            Ok(Ok(entry)) => entries.push(entry),
+            // The file disappeared between listing and diffing.
+            Ok(Err(err)) if err.kind() == io::ErrorKind::NotFound => {}
+            Ok(Err(err)) => return Err(err),
+            Err(_) => {}
+        }
+    }
+    entries.sort_by_key(|(index, _, _)| *index);
+    for (_, text, stat) in entries {
+        diff.unified_diff.push_str(&text);
+        diff.files.push(stat);
+    }
+    Ok(())
+}
+
+async fn untracked_file_stat(cwd: &Path, prefix: &str, file: &str) -> io::Result<GitDiffFileStat> {
+    let bytes = tokio::fs::read(cwd.join(file)).await?;
+    let binary = bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0);
+    let additions = if binary {
+        0
+    } else {
+        let newlines = bytes.iter().filter(|b| **b == b'\n').count() as u64;
+        let trailing = u64::from(!bytes.is_empty() && !bytes.ends_with(b"\n"));
+        newlines + trailing
+    };
+    Ok(GitDiffFileStat {
+        path: format!("{prefix}{file}"),
+        old_path: None,
+        additions,
+        deletions: 0,
+        binary,
+        untracked: true,
+    })
+}
+
+/// Parse `git diff --numstat -z` output. Each record is
+/// `added\tdeleted\tpath\0`, or for renames `added\tdeleted\t\0old\0new\0`.
+/// Binary files report `-` for both counts.
+fn parse_numstat_z(output: &str) -> Vec<GitDiffFileStat> {
+    let mut files = Vec::new();
+    let mut fields = output.split('\0');
+    while let Some(record) = fields.next() {
+        if record.is_empty() {
+            continue;
+        }
+        let mut parts = record.splitn(3, '\t');
+        let (Some(added), Some(deleted), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let (path, old_path) = if path.is_empty() {
+            let old = fields.next().unwrap_or_default().to_string();
+            let new = fields.next().unwrap_or_default().to_string();
+            (new, Some(old))
+        } else {
+            (path.to_string(), None)
+        };
+        let binary = added == "-" && deleted == "-";
+        files.push(GitDiffFileStat {
+            path,
+            old_path,
+            additions: added.parse().unwrap_or(0),
+            deletions: deleted.parse().unwrap_or(0),
+            binary,
+            untracked: false,
+        });
+    }
+    files
+}
+
+async fn git_output(cwd: &Path, args: &[String]) -> io::Result<std::process::Output> {
+    Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+}
+
+fn git_error(args: &[String], output: &std::process::Output) -> io::Error {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr.trim();
+    if message.is_empty() {
+        io::Error::other(format!(
+            "git {} failed with status {}",
+            args.join(" "),
+            output.status
+        ))
+    } else {
+        io::Error::other(message.to_string())
+    }
+}
+
+/// Run `git` and return stdout. Any non-zero exit status is an error.
+async fn run_git_capture_stdout(cwd: &Path, args: &[String]) -> io::Result<String> {
+    let output = git_output(cwd, args).await?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(git_error(args, &output))
+    }
+}
+
+/// Like [`run_git_capture_stdout`] but treats exit status 1 as success, which
+/// `git diff --no-index` uses to signal that differences were found.
+async fn run_git_capture_diff(cwd: &Path, args: &[String]) -> io::Result<String> {
+    let output = git_output(cwd, args).await?;
+    if output.status.success() || output.status.code() == Some(1) {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(git_error(args, &output))
+    }
+}
+
+async fn inside_git_repo(cwd: &Path) -> io::Result<bool> {
+    let status = Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(cwd)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+
+    match status {
+        Ok(s) if s.success() => Ok(true),
+        Ok(_) => Ok(false),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false), // git not installed
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_test_support::skip_if_sandbox;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .envs([("GIT_CONFIG_GLOBAL", "/dev/null"), ("GIT_CONFIG_NOSYSTEM", "1")])
+            .args(args)
+            .current_dir(repo)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// Repo with two commits: `a.txt` gains a line in the second one.
+    fn fixture_repo() -> TempDir {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let repo = dir.path();
+        git(repo, &["init", "-q"]);
+        git(repo, &["config", "user.name", "Test User"]);
+        git(repo, &["config", "user.email", "test@example.com"]);
+        std::fs::write(repo.join("a.txt"), "one\n").expect("write a.txt");
+        std::fs::write(repo.join("b.txt"), "keep\n").expect("write b.txt");
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "first"]);
+        std::fs::write(repo.join("a.txt"), "one\ntwo\n").expect("write a.txt");
+        git(repo, &["commit", "-q", "-am", "second"]);
+        dir
+    }
+
+    fn stat(path: &str, additions: u64, deletions: u64) -> GitDiffFileStat {
+        GitDiffFileStat {
+            path: path.to_string(),
+            old_path: None,
+            additions,
+            deletions,
+            binary: false,
+            untracked: false,
+        }
+    }
+
+    #[test]
+    fn parses_numstat_records_including_renames_and_binaries() {
+        let output = "1\t2\tsrc/lib.rs\0-\t-\tlogo.png\03\t0\t\0old.rs\0new.rs\0";
+
+        assert_eq!(
+            parse_numstat_z(output),
+            vec![
+                stat("src/lib.rs", 1, 2),
+                GitDiffFileStat {
+                    binary: true,
+                    ..stat("logo.png", 0, 0)
+                },
+                GitDiffFileStat {
+                    old_path: Some("old.rs".to_string()),
+                    ..stat("new.rs", 3, 0)
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn working_tree_includes_unstaged_and_untracked_files() {
+        skip_if_sandbox!();
+        let dir = fixture_repo();
+        let repo = dir.path();
+        std::fs::write(repo.join("b.txt"), "changed\n").expect("write b.txt");
+        std::fs::write(repo.join("new.txt"), "x\ny").expect("write new.txt");
+        std::fs::write(repo.join("blob.bin"), [0u8, 1, 2]).expect("write blob.bin");
+
+        let diff = get_git_diff(repo, &GitDiffOptions::default())
+            .await
+            .expect("diff")
+            .expect("inside repo");
+
+        assert_eq!(
+            diff.files,
+            vec![
+                stat("b.txt", 1, 1),
+                GitDiffFileStat {
+                    binary: true,
+                    untracked: true,
+                    ..stat("blob.bin", 0, 0)
+                },
+                GitDiffFileStat {
+                    untracked: true,
+                    ..stat("new.txt", 2, 0)
+                },
+            ]
+        );
+        assert!(diff.unified_diff.contains("+changed"));
+        assert!(diff.unified_diff.contains("+++ b/new.txt"));
+        assert!(!diff.unified_diff.contains('\u{1b}'));
+    }
+
+    #[tokio::test]
+    async fn staged_range_and_paths_select_the_expected_changes() {
+        skip_if_sandbox!();
+        let dir = fixture_repo();
+        let repo = dir.path();
+        std::fs::write(repo.join("b.txt"), "staged\n").expect("write b.txt");
+        git(repo, &["add", "b.txt"]);
+        std::fs::write(repo.join("a.txt"), "unstaged\n").expect("write a.txt");
+
+        let staged = GitDiffOptions {
+            target: GitDiffTarget::Staged,
+            ..Default::default()
+        };
+        let range = GitDiffOptions {
+            target: GitDiffTarget::Range("HEAD~1..HEAD".to_string()),
+            ..Default::default()
+        };
+        let only_a = GitDiffOptions {
+            paths: vec![PathBuf::from("a.txt")],
+            ..Default::default()
+        };
+        let mut files = Vec::new();
+        for options in [staged, range, only_a] {
+            let diff = get_git_diff(repo, &options)
+                .await
+                .expect("diff")
+                .expect("inside repo");
+            files.push(diff.files);
+        }
+
+        assert_eq!(
+            files,
+            vec![
+                vec![stat("b.txt", 1, 1)],
+                vec![stat("a.txt", 1, 0)],
+                vec![stat("a.txt", 1, 2)],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_option_like_ranges_and_reports_non_repos() {
+        skip_if_sandbox!();
+        let dir = fixture_repo();
+        let options = GitDiffOptions {
+            target: GitDiffTarget::Range("--output=/tmp/x".to_string()),
+            ..Default::default()
+        };
+        let err = get_git_diff(dir.path(), &options)
+            .await
+            .expect_err("option-like range is rejected");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let not_repo = tempfile::tempdir().expect("tempdir");
+        assert_eq!(
+            get_git_diff(not_repo.path(), &GitDiffOptions::default())
+                .await
+                .expect("diff"),
+            None
+        );
+    }
+}
@@ -584,6 +584,176 @@ pub async fn local_git_branches(cwd: &Path) -> Vec<String> {
     branches
 }
 
+/// Returns the entries of `paths` that have uncommitted changes in the git
+/// repository containing `cwd`: modified or staged relative to `HEAD`, or
+/// untracked. Returns an empty list outside a git repository or when git
+/// fails, so callers can treat the check as best effort.
+pub async fn dirty_paths(cwd: &Path, paths: &[PathBuf]) -> Vec<PathBuf> {
+    if paths.is_empty() || get_git_repo_root(cwd).is_none() {
+        return Vec::new();
+    }
+
+    let checks = paths.iter().map(|path| async move {
+        let path_str = path.to_string_lossy();
+        let out = run_git_command_with_timeout(
+            &[
+                "--literal-pathspecs",
+                "status",
+                "--porcelain",
+                "--untracked-files=all",
+                "--",
+                &path_str,
+            ],
+            cwd,
+        )
+        .await?;
+        (out.status.success() && !out.stdout.is_empty()).then(|| path.clone())
+    });
+    join_all(checks).await.into_iter().flatten().collect()
+}
+
+/// Why committing in `cwd` should not be attempted right now, or `None` when
+/// HEAD is on a branch and no rebase is in progress.
+pub async fn commit_blocker(cwd: &Path) -> Option<String> {
+    let git_dir = run_git_command_with_timeout(&["rev-parse", "--absolute-git-dir"], cwd)
+        .await
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|dir| PathBuf::from(dir.trim()));
+    let Some(git_dir) = git_dir else {
+        return Some("Not inside a git repository.".to_string());
+    };
+    if git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists() {
+        return Some("A rebase is in progress. Finish or abort it before committing.".to_string());
+    }
+    let on_branch = run_git_command_with_timeout(&["symbolic-ref", "-q", "HEAD"], cwd)
+        .await
+        .is_some_and(|out| out.status.success());
+    if !on_branch {
+        return Some("HEAD is detached. Check out a branch before committing.".to_string());
+    }
+    None
+}
+
+/// Upper bound on the diff text handed to the commit-message drafter.
+const COMMIT_DIFF_MAX_BYTES: usize = 48 * 1024;
+
+/// The combined diff of `paths` against HEAD, including untracked files, capped
+/// at a size that fits comfortably in a single model request.
+pub async fn diff_for_paths(cwd: &Path, paths: &[PathBuf]) -> String {
+    if paths.is_empty() {
+        return String::new();
+    }
+    let path_args: Vec<String> = paths
+        .iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+
+    let mut args = vec!["--literal-pathspecs", "diff", "HEAD", "--"];
+    args.extend(path_args.iter().map(String::as_str));
+    let mut diff = run_git_command_with_timeout(&args, cwd)
+        .await
+        .map(|out| String::from_utf8_lossy(&out.stdout).into_owned())
+        .unwrap_or_default();
+
+    let mut args = vec![
+        "--literal-pathspecs",
+        "ls-files",
+        "--others",
+        "--exclude-standard",
+        "--",
+    ];
+    args.extend(path_args.iter().map(String::as_str));
+    let untracked = run_git_command_with_timeout(&args, cwd)
+        .await
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).into_owned())
+        .unwrap_or_default();
+    for file in untracked.lines().filter(|line| !line.is_empty()) {
+        // `--no-index` exits with 1 when the files differ, so ignore the status.
+        if let Some(out) =
+            run_git_command_with_timeout(&["diff", "--no-index", "--", "/dev/null", file], cwd)
+                .await
+        {
+            diff.push_str(&String::from_utf8_lossy(&out.stdout));
+        }
+    }
+
+    if diff.len() > COMMIT_DIFF_MAX_BYTES {
+        let mut end = COMMIT_DIFF_MAX_BYTES;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        diff.truncate(end);
+        diff.push_str("\n[diff truncated]\n");
+    }
+    diff
+}
+
+/// The full message of the HEAD commit, used as the starting point when
+/// amending.
+pub async fn head_commit_message(cwd: &Path) -> Option<String> {
+    let out = run_git_command_with_timeout(&["log", "-1", "--format=%B"], cwd).await?;
+    if !out.status.success() {
+        return None;
+    }
+    String::from_utf8(out.stdout)
+        .ok()
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty())
+}
+
+/// The full SHA of HEAD.
+pub async fn head_sha(cwd: &Path) -> Option<String> {
+    let out = run_git_command_with_timeout(&["rev-parse", "HEAD"], cwd).await?;
+    if !out.status.success() {
+        return None;
+    }
+    String::from_utf8(out.stdout)
+        .ok()
+        .map(|sha| sha.trim().to_string())
+}
+
+/// `git status --porcelain` for the whole repository containing `cwd`,
+/// untracked files included, or `None` when git fails.
+pub async fn status_porcelain(cwd: &Path) -> Option<String> {
+    let out = run_git_command_with_timeout(
+        &["status", "--porcelain", "-z", "--untracked-files=all"],
+        cwd,
+    )
+    .await?;
+    if !out.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// The tracked files and the untracked files that are not ignored, relative
+/// to `cwd`, or `None` when git fails.
+pub async fn listed_files(cwd: &Path) -> Option<Vec<String>> {
+    let out = run_git_command_with_timeout(
+        &[
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ],
+        cwd,
+    )
+    .await?;
+    if !out.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&out.stdout)
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
 /// Returns the current checked out branch name.
 pub async fn current_branch_name(cwd: &Path) -> Option<String> {
     let out = run_git_command_with_timeout(&["branch", "--show-current"], cwd).await?;
@@ -780,6 +950,115 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[tokio::test]
+    async fn test_dirty_paths_non_git_directory_returns_empty() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file = temp_dir.path().join("file.txt");
+        fs::write(&file, "content").expect("write file");
+
+        assert!(dirty_paths(temp_dir.path(), &[file]).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dirty_paths_reports_modified_staged_and_untracked() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+
+        let clean = repo_path.join("clean.txt");
+        let modified = repo_path.join("test.txt");
+        let staged = repo_path.join("staged.txt");
+        let untracked = repo_path.join("untracked.txt");
+        let missing = repo_path.join("missing.txt");
+        fs::write(&clean, "clean").expect("write clean");
+        fs::write(&staged, "committed").expect("write staged");
+        Command::new("git")
+            .args(["add", "clean.txt", "staged.txt"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("git add");
+        Command::new("git")
+            .args(["commit", "-m", "more files"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("git commit");
+
+        fs::write(&modified, "edited in the worktree").expect("modify");
+        // Staged-only: the worktree matches the index but not HEAD.
+        fs::write(&staged, "staged edit").expect("edit staged");
+        Command::new("git")
+            .args(["add", "staged.txt"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("git add staged");
+        fs::write(&untracked, "new").expect("write untracked");
+
+        let paths = vec![
+            clean,
+            modified.clone(),
+            staged.clone(),
+            untracked.clone(),
+            missing,
+        ];
+        assert_eq!(
+            dirty_paths(&repo_path, &paths).await,
+            vec![modified, staged, untracked]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commit_blocker_reports_detached_head_and_rebase() {
+        skip_if_sandbox!();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        assert_eq!(
+            commit_blocker(temp_dir.path()).await.as_deref(),
+            Some("Not inside a git repository.")
+        );
+
+        let repo_path = create_test_git_repo(&temp_dir).await;
+        assert_eq!(commit_blocker(&repo_path).await, None);
+
+        let rebase_dir = repo_path.join(".git").join("rebase-merge");
+        fs::create_dir(&rebase_dir).expect("create rebase-merge");
+        assert_eq!(
+            commit_blocker(&repo_path).await.as_deref(),
+            Some("A rebase is in progress. Finish or abort it before committing.")
+        );
+        fs::remove_dir(&rebase_dir).expect("remove rebase-merge");
+
+        Command::new("git")
+            .args(["checkout", "--detach"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("git checkout --detach");
+        assert_eq!(
+            commit_blocker(&repo_path).await.as_deref(),
+            Some("HEAD is detached. Check out a branch before committing.")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_for_paths_includes_untracked_files() {
+        skip_if_sandbox!();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+        fs::write(repo_path.join("test.txt"), "changed content").expect("modify");
+        fs::write(repo_path.join("new.txt"), "brand new").expect("write new");
+        fs::write(repo_path.join("other.txt"), "not ours").expect("write other");
+
+        let diff = diff_for_paths(
+            &repo_path,
+            &[repo_path.join("test.txt"), repo_path.join("new.txt")],
+        )
+        .await;
+        assert!(diff.contains("+changed content"), "{diff}");
+        assert!(diff.contains("+brand new"), "{diff}");
+        assert!(!diff.contains("not ours"), "{diff}");
+    }
+
     #[tokio::test]
     async fn test_collect_git_info_git_repository() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
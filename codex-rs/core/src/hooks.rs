@@ -0,0 +1,245 @@
+//! Runs the commands configured under `[hooks]` at fixed points of a turn.
+//!
+//! Hooks for one point run one at a time, in configuration order, under the
+//! turn's sandbox policy. Each receives a JSON description of the point it
+//! runs at on stdin, and each run is reported with `EventMsg::HookBegin` and
+//! `EventMsg::HookEnd` before the caller continues.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::HookCommand;
+use crate::config::types::HookFailurePolicy;
+use crate::error::CodexErr;
+use crate::error::SandboxErr;
+use crate::exec::ExecExpiration;
+use crate::exec::ExecParams;
+use crate::exec::process_exec_tool_call_with_stdin;
+use crate::exec_env::create_env;
+use crate::protocol::EventMsg;
+use crate::protocol::HookBeginEvent;
+use crate::protocol::HookEndEvent;
+use crate::protocol::HookPoint;
+use crate::sandboxing::SandboxPermissions;
+
+/// What a hook is told about the point it runs at, beyond the session and
+/// turn it belongs to.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct HookDetails {
+    /// Files written during the turn (`post_turn`) or by the patch
+    /// (`post_patch`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) files_changed: Vec<PathBuf>,
+    /// The command about to run (`pre_exec`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) command: Option<Vec<String>>,
+    /// The turn's final agent message (`post_turn`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) last_agent_message: Option<String>,
+}
+
+/// The JSON object written to a hook's stdin.
+#[derive(Debug, Serialize)]
+struct HookPayload<'a> {
+    hook: HookPoint,
+    session_id: String,
+    turn_id: &'a str,
+    turn_number: u64,
+    cwd: &'a Path,
+    #[serde(flatten)]
+    details: &'a HookDetails,
+}
+
+/// Runs the hooks configured for `point`. When a hook with
+/// `on_failure = "abort"` fails, the remaining hooks are skipped and the
+/// returned error says why the turn should end.
+pub(crate) async fn run_hooks(
+    sess: &Session,
+    turn: &TurnContext,
+    point: HookPoint,
+    details: HookDetails,
+) -> Result<(), String> {
+    let config = turn.client.config();
+    let hooks = config.hooks.for_point(point);
+    if hooks.is_empty() {
+        return Ok(());
+    }
+
+    let payload = HookPayload {
+        hook: point,
+        session_id: sess.conversation_id.to_string(),
+        turn_id: &turn.sub_id,
+        turn_number: sess.turn_number().await,
+        cwd: &turn.cwd,
+        details: &details,
+    };
+    let stdin = match serde_json::to_vec(&payload) {
+        Ok(stdin) => stdin,
+        Err(err) => {
+            error!("failed to serialize {point} hook payload: {err}");
+            return Ok(());
+        }
+    };
+
+    for hook in hooks {
+        let end = run_hook(sess, turn, point, hook, stdin.clone()).await;
+        if end.aborts_turn {
+            return Err(abort_message(&end));
+        }
+    }
+    Ok(())
+}
+
+async fn run_hook(
+    sess: &Session,
+    turn: &TurnContext,
+    point: HookPoint,
+    hook: &HookCommand,
+    stdin: Vec<u8>,
+) -> HookEndEvent {
+    let call_id = Uuid::new_v4().to_string();
+    sess.send_event(
+        turn,
+        EventMsg::HookBegin(HookBeginEvent {
+            call_id: call_id.clone(),
+            turn_id: turn.sub_id.clone(),
+            hook: point,
+            command: hook.command.clone(),
+        }),
+    )
+    .await;
+
+    let params = ExecParams {
+        command: hook.command.clone(),
+        cwd: turn.cwd.clone(),
+        expiration: ExecExpiration::Timeout(hook.timeout),
+        env: create_env(
+            &turn.shell_environment_policy,
+            turn.client.config().dotenv.as_deref(),
+        ),
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        windows_sandbox_level: turn.windows_sandbox_level,
+        justification: None,
+        arg0: None,
+        limits: turn.client.config().exec_limits,
+    };
+    let start = Instant::now();
+    let result = process_exec_tool_call_with_stdin(
+        params,
+        &turn.sandbox_policy,
+        &turn.cwd,
+        &turn.codex_linux_sandbox_exe,
+        None,
+        Some(stdin),
+    )
+    .await;
+    let duration = start.elapsed();
+
+    let (exit_code, timed_out, output) = match result {
+        Ok(output) => (Some(output.exit_code), false, output.aggregated_output.text),
+        Err(CodexErr::Sandbox(SandboxErr::Timeout { output })) => {
+            (Some(output.exit_code), true, output.aggregated_output.text)
+        }
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => {
+            (Some(output.exit_code), false, output.aggregated_output.text)
+        }
+        Err(err) => (None, false, err.to_string()),
+    };
+    let mut end = HookEndEvent {
+        call_id,
+        turn_id: turn.sub_id.clone(),
+        hook: point,
+        command: hook.command.clone(),
+        exit_code,
+        timed_out,
+        output,
+        duration,
+        aborts_turn: false,
+    };
+    end.aborts_turn = !end.success() && hook.on_failure == HookFailurePolicy::Abort;
+    sess.send_event(turn, EventMsg::HookEnd(end.clone())).await;
+    end
+}
+
+fn abort_message(end: &HookEndEvent) -> String {
+    let command = shlex::try_join(end.command.iter().map(String::as_str))
+        .unwrap_or_else(|_| end.command.join(" "));
+    let reason = match end.exit_code {
+        _ if end.timed_out => "timed out".to_string(),
+        Some(code) => format!("exited with code {code}"),
+        None => "could not be started".to_string(),
+    };
+    format!("{} hook `{command}` {reason}; ending the turn.", end.hook)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn end_event(exit_code: Option<i32>, timed_out: bool) -> HookEndEvent {
+        HookEndEvent {
+            call_id: "call-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            hook: HookPoint::PostPatch,
+            command: vec!["cargo".to_string(), "fmt".to_string()],
+            exit_code,
+            timed_out,
+            output: String::new(),
+            duration: Duration::ZERO,
+            aborts_turn: true,
+        }
+    }
+
+    #[test]
+    fn payload_flattens_details_and_omits_empty_fields() {
+        let details = HookDetails {
+            files_changed: vec![PathBuf::from("/repo/src/lib.rs")],
+            ..Default::default()
+        };
+        let payload = HookPayload {
+            hook: HookPoint::PostPatch,
+            session_id: "session-1".to_string(),
+            turn_id: "turn-1",
+            turn_number: 3,
+            cwd: Path::new("/repo"),
+            details: &details,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&payload).expect("serialize payload"),
+            serde_json::json!({
+                "hook": "post_patch",
+                "session_id": "session-1",
+                "turn_id": "turn-1",
+                "turn_number": 3,
+                "cwd": "/repo",
+                "files_changed": ["/repo/src/lib.rs"],
+            })
+        );
+    }
+
+    #[test]
+    fn abort_message_names_the_hook_and_reason() {
+        assert_eq!(
+            abort_message(&end_event(Some(2), false)),
+            "post_patch hook `cargo fmt` exited with code 2; ending the turn."
+        );
+        assert_eq!(
+            abort_message(&end_event(Some(124), true)),
+            "post_patch hook `cargo fmt` timed out; ending the turn."
+        );
+        assert_eq!(
+            abort_message(&end_event(None, false)),
+            "post_patch hook `cargo fmt` could not be started; ending the turn."
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::exec_limits::ExecLimits;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
@@ -35,6 +36,7 @@ where
         sandbox_policy,
         stdio_policy,
         env,
+        &ExecLimits::default(),
     )
     .await
 }
@@ -7,8 +7,11 @@
 
 pub mod api_bridge;
 mod apply_patch;
+pub mod ask;
 pub mod auth;
 pub mod bash;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod client;
 mod client_common;
 pub mod codex;
@@ -18,21 +21,33 @@ pub use codex_thread::CodexThread;
 pub use codex_thread::ThreadConfigSnapshot;
 mod agent;
 mod codex_delegate;
+mod command_explanation;
 mod command_safety;
 pub mod config;
 pub mod config_loader;
+mod connectivity;
 pub mod connectors;
 mod context_manager;
 pub mod custom_prompts;
+pub mod doctor;
 pub mod env;
 mod environment_context;
 pub mod error;
+mod event_subscription;
+pub use event_subscription::EventSubscription;
 pub mod exec;
+mod exec_audit;
 pub mod exec_env;
+pub mod exec_limits;
 mod exec_policy;
 pub mod features;
+mod file_provenance;
+mod file_uploads;
 mod flags;
+pub mod git_diff;
 pub mod git_info;
+mod hooks;
+mod injection_guard;
 pub mod instructions;
 pub mod landlock;
 pub mod mcp;
@@ -46,15 +61,26 @@ mod mcp_tool_call;
 mod mentions;
 mod message_history;
 mod model_provider_info;
+mod output_sanitizer;
 pub mod parse_command;
 pub mod path_utils;
 pub mod powershell;
+mod preferred_language;
+mod prompt_commands;
+mod prompted_tools;
 mod proposed_plan_parser;
+pub mod provider_env;
+mod request_size;
+pub mod sandbox_self_test;
 pub mod sandboxing;
 mod session_prefix;
+mod session_startup;
+mod snapshot_history;
+mod stale_patch;
 mod stream_events_utils;
 mod tagged_block_parser;
 mod text_encoding;
+mod token_breakdown;
 pub mod token_data;
 mod truncate;
 mod unified_exec;
@@ -76,6 +102,7 @@ mod thread_manager;
 pub mod web_search;
 pub use codex_protocol::protocol::InitialHistory;
 pub use thread_manager::NewThread;
+pub use thread_manager::PrewarmedThread;
 pub use thread_manager::ThreadManager;
 #[deprecated(note = "use ThreadManager")]
 pub type ConversationManager = ThreadManager;
@@ -88,6 +115,7 @@ pub use auth::AuthManager;
 pub use auth::CodexAuth;
 pub mod default_client;
 pub mod project_doc;
+pub mod project_memory;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
@@ -98,6 +126,7 @@ pub mod spawn;
 pub mod state_db;
 pub mod terminal;
 mod tools;
+pub mod transcript_export;
 pub mod turn_diff_tracker;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
@@ -125,6 +154,8 @@ mod tasks;
 mod user_notification;
 mod user_shell_command;
 pub mod util;
+mod workspace_summary;
+mod workspace_watch;
 
 pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
 pub use client::WEB_SEARCH_ELIGIBLE_HEADER;
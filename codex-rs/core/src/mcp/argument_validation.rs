@@ -0,0 +1,578 @@
+//! Checks MCP tool call arguments against the tool's declared `inputSchema`
+//! before the call is dispatched, so the model gets an actionable error
+//! instead of whatever the server returns for malformed input.
+//!
+//! Only the keywords MCP servers commonly publish are checked: `type`,
+//! `enum`, `const`, numeric and length bounds, `pattern`, `items`,
+//! `required`, `properties`, `additionalProperties` and the `allOf`, `anyOf`,
+//! `oneOf` and `not` combinators. Other keywords are ignored, so an
+//! incomplete check can let a bad call through but never blocks a valid one.
+
+use codex_protocol::protocol::McpArgumentError;
+use mcp_types::ToolInputSchema;
+use serde_json::Map;
+use serde_json::Value;
+use tracing::debug;
+
+/// References cannot be resolved here; schemas using them are not enforced.
+const REFERENCE_KEYWORDS: [&str; 3] = ["$ref", "$dynamicRef", "$recursiveRef"];
+
+/// Return every mismatch between `arguments` and `input_schema`. Missing
+/// arguments are checked as an empty object. Schemas that use references
+/// (for example to definitions the server did not send) are not enforced.
+pub(crate) fn validate_arguments(
+    input_schema: &ToolInputSchema,
+    arguments: Option<&Value>,
+) -> Vec<McpArgumentError> {
+    let schema = match serde_json::to_value(input_schema) {
+        Ok(schema) => schema,
+        Err(err) => {
+            debug!("failed to serialize MCP tool input schema: {err}");
+            return Vec::new();
+        }
+    };
+    if uses_references(&schema) {
+        debug!("skipping argument validation for an input schema that uses references");
+        return Vec::new();
+    }
+
+    let empty = Value::Object(Map::new());
+    let mut errors = Vec::new();
+    check(&schema, arguments.unwrap_or(&empty), "", &mut errors);
+    errors
+}
+
+fn uses_references(schema: &Value) -> bool {
+    match schema {
+        Value::Object(map) => map.iter().any(|(key, value)| {
+            REFERENCE_KEYWORDS.contains(&key.as_str()) || uses_references(value)
+        }),
+        Value::Array(items) => items.iter().any(uses_references),
+        _ => false,
+    }
+}
+
+/// Append the mismatches between `instance` and `schema` to `errors`, with
+/// `path` as the JSON pointer to `instance`.
+fn check(schema: &Value, instance: &Value, path: &str, errors: &mut Vec<McpArgumentError>) {
+    let schema = match schema {
+        Value::Object(schema) => schema,
+        Value::Bool(false) => {
+            push(
+                errors,
+                path,
+                format!("False schema does not allow {instance}"),
+            );
+            return;
+        }
+        _ => return,
+    };
+
+    if let Some(message) = type_mismatch(schema, instance) {
+        // Nothing else is meaningful for a value of the wrong type.
+        push(errors, path, message);
+        return;
+    }
+    if let Some(Value::Array(options)) = schema.get("enum")
+        && !options.contains(instance)
+    {
+        push(
+            errors,
+            path,
+            format!("{instance} is not one of {}", Value::Array(options.clone())),
+        );
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != instance
+    {
+        push(errors, path, format!("{expected} was expected"));
+    }
+
+    match instance {
+        Value::Number(number) => check_number(schema, instance, number.as_f64(), path, errors),
+        Value::String(text) => check_string(schema, instance, text, path, errors),
+        Value::Array(items) => check_array(schema, instance, items, path, errors),
+        Value::Object(object) => check_object(schema, object, path, errors),
+        Value::Null | Value::Bool(_) => {}
+    }
+    check_combinators(schema, instance, path, errors);
+}
+
+fn type_mismatch(schema: &Map<String, Value>, instance: &Value) -> Option<String> {
+    let types: Vec<&str> = match schema.get("type")? {
+        Value::String(name) => vec![name.as_str()],
+        Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+        _ => return None,
+    };
+    if types.is_empty() || types.iter().any(|name| has_type(instance, name)) {
+        return None;
+    }
+    Some(match types.as_slice() {
+        [name] => format!("{instance} is not of type \"{name}\""),
+        _ => format!(
+            "{instance} is not of types {}",
+            types
+                .iter()
+                .map(|name| format!("\"{name}\""))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    })
+}
+
+fn has_type(instance: &Value, name: &str) -> bool {
+    match name {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "string" => instance.is_string(),
+        "array" => instance.is_array(),
+        "object" => instance.is_object(),
+        "number" => instance.is_number(),
+        "integer" => {
+            instance.is_i64()
+                || instance.is_u64()
+                || instance.as_f64().is_some_and(|value| value.fract() == 0.0)
+        }
+        // An unknown type name is not something the model can fix.
+        _ => true,
+    }
+}
+
+fn check_number(
+    schema: &Map<String, Value>,
+    instance: &Value,
+    value: Option<f64>,
+    path: &str,
+    errors: &mut Vec<McpArgumentError>,
+) {
+    let Some(value) = value else {
+        return;
+    };
+    let bound = |keyword: &str| {
+        schema
+            .get(keyword)
+            .and_then(|bound| Some((bound, bound.as_f64()?)))
+    };
+    if let Some((bound, min)) = bound("minimum")
+        && value < min
+    {
+        push(
+            errors,
+            path,
+            format!("{instance} is less than the minimum of {bound}"),
+        );
+    }
+    if let Some((bound, max)) = bound("maximum")
+        && value > max
+    {
+        push(
+            errors,
+            path,
+            format!("{instance} is greater than the maximum of {bound}"),
+        );
+    }
+    if let Some((bound, min)) = bound("exclusiveMinimum")
+        && value <= min
+    {
+        push(
+            errors,
+            path,
+            format!("{instance} is less than or equal to the minimum of {bound}"),
+        );
+    }
+    if let Some((bound, max)) = bound("exclusiveMaximum")
+        && value >= max
+    {
+        push(
+            errors,
+            path,
+            format!("{instance} is greater than or equal to the maximum of {bound}"),
+        );
+    }
+}
+
+fn check_string(
+    schema: &Map<String, Value>,
+    instance: &Value,
+    text: &str,
+    path: &str,
+    errors: &mut Vec<McpArgumentError>,
+) {
+    let length = text.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+        && length < min
+    {
+        push(
+            errors,
+            path,
+            format!(
+                "{instance} is shorter than {min} {}",
+                plural(min, "character")
+            ),
+        );
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+        && length > max
+    {
+        push(
+            errors,
+            path,
+            format!(
+                "{instance} is longer than {max} {}",
+                plural(max, "character")
+            ),
+        );
+    }
+    // ECMA-262 patterns the regex crate cannot compile are not checked.
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str)
+        && let Ok(regex) = regex::Regex::new(pattern)
+        && !regex.is_match(text)
+    {
+        push(
+            errors,
+            path,
+            format!("{instance} does not match \"{pattern}\""),
+        );
+    }
+}
+
+fn check_array(
+    schema: &Map<String, Value>,
+    instance: &Value,
+    items: &[Value],
+    path: &str,
+    errors: &mut Vec<McpArgumentError>,
+) {
+    let count = items.len() as u64;
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+        && count < min
+    {
+        push(
+            errors,
+            path,
+            format!("{instance} has less than {min} {}", plural(min, "item")),
+        );
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+        && count > max
+    {
+        push(
+            errors,
+            path,
+            format!("{instance} has more than {max} {}", plural(max, "item")),
+        );
+    }
+
+    // `prefixItems` (or the older array form of `items`) describes leading
+    // positions; `items` then applies to the rest.
+    let (prefix, rest) = match (schema.get("prefixItems"), schema.get("items")) {
+        (Some(Value::Array(prefix)), rest) => (prefix.as_slice(), rest),
+        (None, Some(Value::Array(prefix))) => (prefix.as_slice(), schema.get("additionalItems")),
+        (_, rest) => (&[][..], rest),
+    };
+    for (index, item) in items.iter().enumerate() {
+        let item_schema = prefix
+            .get(index)
+            .or(if index >= prefix.len() { rest } else { None });
+        if let Some(item_schema) = item_schema {
+            check(item_schema, item, &format!("{path}/{index}"), errors);
+        }
+    }
+}
+
+fn check_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<McpArgumentError>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(name) {
+                push(errors, path, format!("\"{name}\" is a required property"));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let mut unexpected = Vec::new();
+    for (name, value) in object {
+        let child = format!("{path}/{}", escape_pointer(name));
+        match properties.and_then(|properties| properties.get(name)) {
+            Some(property) => check(property, value, &child, errors),
+            // Names matched by `patternProperties` are not checked.
+            None if schema.contains_key("patternProperties") => {}
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => unexpected.push(format!("'{name}'")),
+                Some(additional) => check(additional, value, &child, errors),
+                None => {}
+            },
+        }
+    }
+    if !unexpected.is_empty() {
+        let verb = if unexpected.len() == 1 { "was" } else { "were" };
+        push(
+            errors,
+            path,
+            format!(
+                "Additional properties are not allowed ({} {verb} unexpected)",
+                unexpected.join(", ")
+            ),
+        );
+    }
+}
+
+fn check_combinators(
+    schema: &Map<String, Value>,
+    instance: &Value,
+    path: &str,
+    errors: &mut Vec<McpArgumentError>,
+) {
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        for sub in all {
+            check(sub, instance, path, errors);
+        }
+    }
+    if let Some(Value::Array(any)) = schema.get("anyOf")
+        && !any.iter().any(|sub| is_valid(sub, instance))
+    {
+        push(
+            errors,
+            path,
+            format!(
+                "{instance} is not valid under any of the schemas listed in the 'anyOf' keyword"
+            ),
+        );
+    }
+    if let Some(Value::Array(one)) = schema.get("oneOf") {
+        match one.iter().filter(|sub| is_valid(sub, instance)).count() {
+            0 => push(
+                errors,
+                path,
+                format!(
+                    "{instance} is not valid under any of the schemas listed in the 'oneOf' keyword"
+                ),
+            ),
+            1 => {}
+            _ => push(
+                errors,
+                path,
+                format!(
+                    "{instance} is valid under more than one of the schemas listed in the 'oneOf' keyword"
+                ),
+            ),
+        }
+    }
+    if let Some(not) = schema.get("not")
+        && is_valid(not, instance)
+    {
+        push(errors, path, format!("{not} is not allowed for {instance}"));
+    }
+}
+
+fn is_valid(schema: &Value, instance: &Value) -> bool {
+    let mut errors = Vec::new();
+    check(schema, instance, "", &mut errors);
+    errors.is_empty()
+}
+
+fn push(errors: &mut Vec<McpArgumentError>, path: &str, message: String) {
+    errors.push(McpArgumentError {
+        path: path.to_string(),
+        message,
+    });
+}
+
+fn plural(count: u64, noun: &str) -> String {
+    if count == 1 {
+        noun.to_string()
+    } else {
+        format!("{noun}s")
+    }
+}
+
+/// Escape a property name for use as a JSON pointer segment.
+fn escape_pointer(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// The message returned to the model when a call is rejected.
+pub(crate) fn format_argument_errors(
+    server: &str,
+    tool: &str,
+    errors: &[McpArgumentError],
+) -> String {
+    let mut message = format!(
+        "Arguments for `{server}/{tool}` do not match its input schema, so the call was not sent. Fix these and call the tool again:"
+    );
+    for error in errors {
+        message.push_str(&format!(
+            "\n- {}: {}",
+            field_name(&error.path),
+            error.message
+        ));
+    }
+    message
+}
+
+/// Render a JSON pointer such as `/filters/0/name` as `filters[0].name`.
+fn field_name(pointer: &str) -> String {
+    let mut name = String::new();
+    for segment in pointer.split('/').skip(1) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        if segment.parse::<usize>().is_ok() {
+            name.push_str(&format!("[{segment}]"));
+        } else {
+            if !name.is_empty() {
+                name.push('.');
+            }
+            name.push_str(&segment);
+        }
+    }
+    if name.is_empty() {
+        "arguments".to_string()
+    } else {
+        format!("`{name}`")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn schema() -> ToolInputSchema {
+        ToolInputSchema {
+            properties: Some(json!({
+                "query": { "type": "string" },
+                "limit": { "type": "integer", "minimum": 1 },
+                "filters": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } },
+                        "required": ["name"]
+                    }
+                }
+            })),
+            required: Some(vec!["query".to_string()]),
+            r#type: "object".to_string(),
+        }
+    }
+
+    fn paths(errors: &[McpArgumentError]) -> Vec<&str> {
+        errors.iter().map(|error| error.path.as_str()).collect()
+    }
+
+    #[test]
+    fn matching_arguments_pass() {
+        let arguments = json!({ "query": "rust", "limit": 5, "filters": [{ "name": "lang" }] });
+        assert_eq!(validate_arguments(&schema(), Some(&arguments)), Vec::new());
+    }
+
+    #[test]
+    fn reports_missing_required_fields() {
+        let errors = validate_arguments(&schema(), None);
+        assert_eq!(paths(&errors), vec![""]);
+        assert!(errors[0].message.contains("query"), "{errors:?}");
+    }
+
+    #[test]
+    fn reports_wrong_types_and_nested_fields() {
+        let arguments = json!({ "query": "rust", "limit": "ten", "filters": [{ "name": 3 }] });
+        let mut errors = validate_arguments(&schema(), Some(&arguments));
+        errors.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(paths(&errors), vec!["/filters/0/name", "/limit"]);
+        assert!(errors[1].message.contains("integer"), "{errors:?}");
+    }
+
+    #[test]
+    fn reports_out_of_range_values() {
+        let arguments = json!({ "query": "rust", "limit": 0 });
+        let errors = validate_arguments(&schema(), Some(&arguments));
+        assert_eq!(paths(&errors), vec!["/limit"]);
+    }
+
+    #[test]
+    fn unsupported_schemas_are_not_enforced() {
+        let schema = ToolInputSchema {
+            properties: Some(json!({ "item": { "$ref": "#/$defs/Item" } })),
+            required: None,
+            r#type: "object".to_string(),
+        };
+        let arguments = json!({ "item": 1 });
+        assert_eq!(validate_arguments(&schema, Some(&arguments)), Vec::new());
+    }
+
+    #[test]
+    fn reports_enum_and_unexpected_properties() {
+        let schema = ToolInputSchema {
+            properties: Some(json!({
+                "mode": { "type": "string", "enum": ["fast", "full"] },
+                "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 2 }
+            })),
+            required: None,
+            r#type: "object".to_string(),
+        };
+        let mut schema_value = serde_json::to_value(&schema).expect("schema");
+        schema_value["additionalProperties"] = json!(false);
+        let arguments = json!({ "mode": "slow", "tags": ["a", "b", 3], "extra": true });
+
+        let mut errors = Vec::new();
+        check(&schema_value, &arguments, "", &mut errors);
+
+        let messages: Vec<(&str, &str)> = errors
+            .iter()
+            .map(|error| (error.path.as_str(), error.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    "",
+                    "Additional properties are not allowed ('extra' was unexpected)"
+                ),
+                ("/mode", "\"slow\" is not one of [\"fast\",\"full\"]"),
+                ("/tags", "[\"a\",\"b\",3] has more than 2 items"),
+                ("/tags/2", "3 is not of type \"string\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn any_of_accepts_a_matching_branch() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": { "anyOf": [{ "type": "integer" }, { "type": "string", "minLength": 1 }] }
+            }
+        });
+        assert!(is_valid(&schema, &json!({ "id": 7 })));
+        assert!(is_valid(&schema, &json!({ "id": "abc" })));
+
+        let mut errors = Vec::new();
+        check(&schema, &json!({ "id": "" }), "", &mut errors);
+        assert_eq!(paths(&errors), vec!["/id"]);
+    }
+
+    #[test]
+    fn message_names_each_field() {
+        let errors = vec![
+            McpArgumentError {
+                path: String::new(),
+                message: "\"query\" is a required property".to_string(),
+            },
+            McpArgumentError {
+                path: "/filters/0/name".to_string(),
+                message: "3 is not of type \"string\"".to_string(),
+            },
+        ];
+        assert_eq!(
+            format_argument_errors("search", "find", &errors),
+            "Arguments for `search/find` do not match its input schema, so the call was not sent. Fix these and call the tool again:\n\
+             - arguments: \"query\" is a required property\n\
+             - `filters[0].name`: 3 is not of type \"string\""
+        );
+    }
+}
@@ -1,6 +1,9 @@
+mod argument_validation;
 pub mod auth;
 mod skill_dependencies;
 
+pub(crate) use argument_validation::format_argument_errors;
+pub(crate) use argument_validation::validate_arguments;
 pub(crate) use skill_dependencies::maybe_prompt_and_install_mcp_dependencies;
 
 use std::collections::HashMap;
@@ -17,6 +20,7 @@ use tokio_util::sync::CancellationToken;
 use crate::AuthManager;
 use crate::CodexAuth;
 use crate::config::Config;
+use crate::config::types::McpArgumentValidation;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
 use crate::features::Feature;
@@ -103,6 +107,7 @@ fn codex_apps_mcp_server_config(config: &Config, auth: Option<&CodexAuth>) -> Mc
         enabled_tools: None,
         disabled_tools: None,
         scopes: None,
+        argument_validation: McpArgumentValidation::Strict,
     }
 }
 
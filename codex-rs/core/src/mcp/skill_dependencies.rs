@@ -19,6 +19,7 @@ use crate::codex::TurnContext;
 use crate::config::Config;
 use crate::config::edit::ConfigEditsBuilder;
 use crate::config::load_global_mcp_servers;
+use crate::config::types::McpArgumentValidation;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
 use crate::default_client::is_first_party_originator;
@@ -385,6 +386,7 @@ fn mcp_dependency_to_server_config(
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            argument_validation: McpArgumentValidation::Strict,
         });
     }
 
@@ -408,6 +410,7 @@ fn mcp_dependency_to_server_config(
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            argument_validation: McpArgumentValidation::Strict,
         });
     }
 
@@ -461,6 +464,7 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
 
@@ -508,6 +512,7 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         )]);
 
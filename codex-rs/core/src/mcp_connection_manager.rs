@@ -13,6 +13,7 @@ use std::ffi::OsString;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::auth::McpAuthStatusEntry;
@@ -25,6 +26,7 @@ use codex_async_utils::OrCancelExt;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpServerStartup;
 use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
@@ -57,6 +59,7 @@ use sha1::Digest;
 use sha1::Sha1;
 use tokio::sync::Mutex;
 use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 use tracing::instrument;
@@ -313,6 +316,8 @@ pub(crate) struct McpConnectionManager {
 }
 
 impl McpConnectionManager {
+    /// Start every enabled server in the background. The returned handle
+    /// resolves to the startup summary once each server is ready or failed.
     pub async fn initialize(
         &mut self,
         mcp_servers: &HashMap<String, McpServerConfig>,
@@ -321,9 +326,9 @@ impl McpConnectionManager {
         tx_event: Sender<Event>,
         cancel_token: CancellationToken,
         initial_sandbox_state: SandboxState,
-    ) {
+    ) -> Option<JoinHandle<McpStartupReport>> {
         if cancel_token.is_cancelled() {
-            return;
+            return None;
         }
         let mut clients = HashMap::new();
         let mut join_set = JoinSet::new();
@@ -331,6 +336,7 @@ impl McpConnectionManager {
         let mcp_servers = mcp_servers.clone();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
             let cancel_token = cancel_token.child_token();
+            let started = Instant::now();
             let _ = emit_update(
                 &tx_event,
                 McpStartupUpdateEvent {
@@ -353,8 +359,9 @@ impl McpConnectionManager {
             let sandbox_state = initial_sandbox_state.clone();
             join_set.spawn(async move {
                 let outcome = async_managed_client.client().await;
+                let elapsed = started.elapsed();
                 if cancel_token.is_cancelled() {
-                    return (server_name, Err(StartupOutcomeError::Cancelled));
+                    return (server_name, Err(StartupOutcomeError::Cancelled), elapsed);
                 }
                 let status = match &outcome {
                     Ok(_) => {
@@ -388,33 +395,50 @@ impl McpConnectionManager {
                 )
                 .await;
 
-                (server_name, outcome)
+                (server_name, outcome, elapsed)
             });
         }
         self.clients = clients;
         self.elicitation_requests = elicitation_requests.clone();
-        tokio::spawn(async move {
+        let startup = tokio::spawn(async move {
             let outcomes = join_set.join_all().await;
             let mut summary = McpStartupCompleteEvent::default();
-            for (server_name, outcome) in outcomes {
+            let mut servers = Vec::new();
+            for (server_name, outcome, elapsed) in outcomes {
+                let mut server = McpServerStartup::pending(server_name.clone());
+                let connect_ms = Some(u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX));
                 match outcome {
-                    Ok(_) => summary.ready.push(server_name),
+                    Ok(client) => {
+                        let tools = client
+                            .tools
+                            .iter()
+                            .filter(|tool| client.tool_filter.allows(&tool.tool_name))
+                            .count();
+                        server.connect_ms = connect_ms;
+                        server.tools = Some(u32::try_from(tools).unwrap_or(u32::MAX));
+                        summary.ready.push(server_name);
+                    }
                     Err(StartupOutcomeError::Cancelled) => summary.cancelled.push(server_name),
                     Err(StartupOutcomeError::Failed { error }) => {
+                        server.connect_ms = connect_ms;
+                        server.error = Some(error.clone());
                         summary.failed.push(McpStartupFailure {
                             server: server_name,
                             error,
                         })
                     }
                 }
+                servers.push(server);
             }
             let _ = tx_event
                 .send(Event {
                     id: INITIAL_SUBMIT_ID.to_owned(),
-                    msg: EventMsg::McpStartupComplete(summary),
+                    msg: EventMsg::McpStartupComplete(summary.clone()),
                 })
                 .await;
+            McpStartupReport { summary, servers }
         });
+        Some(startup)
     }
 
     async fn client_by_name(&self, name: &str) -> Result<ManagedClient> {
@@ -822,6 +846,13 @@ fn resolve_bearer_token(
     }
 }
 
+/// How MCP startup went once every server has connected, failed or been
+/// cancelled.
+pub(crate) struct McpStartupReport {
+    pub(crate) summary: McpStartupCompleteEvent,
+    pub(crate) servers: Vec<McpServerStartup>,
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 enum StartupOutcomeError {
     #[error("MCP startup cancelled")]
@@ -1046,6 +1077,7 @@ mod mcp_init_error_display_tests {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::types::McpArgumentValidation;
     use codex_protocol::protocol::McpAuthStatus;
     use mcp_types::ToolInputSchema;
     use std::collections::HashSet;
@@ -1241,6 +1273,7 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -1287,6 +1320,7 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -5,8 +5,12 @@ use tracing::error;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::config::types::McpArgumentValidation;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
+use crate::mcp::format_argument_errors;
+use crate::mcp::validate_arguments;
 use crate::protocol::EventMsg;
+use crate::protocol::McpArgumentError;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
 use crate::protocol::McpToolCallEndEvent;
@@ -19,6 +23,7 @@ use codex_protocol::request_user_input::RequestUserInputQuestion;
 use codex_protocol::request_user_input::RequestUserInputQuestionOption;
 use codex_protocol::request_user_input::RequestUserInputResponse;
 use mcp_types::ToolAnnotations;
+use mcp_types::ToolInputSchema;
 use std::sync::Arc;
 
 /// Handles the specified tool call dispatches the appropriate
@@ -58,6 +63,41 @@ pub(crate) async fn handle_mcp_tool_call(
         arguments: arguments_value.clone(),
     };
 
+    let argument_errors = check_arguments(
+        sess.as_ref(),
+        turn_context,
+        &server,
+        &tool_name,
+        arguments_value.as_ref(),
+    )
+    .await;
+    if !argument_errors.is_empty() {
+        let message = format_argument_errors(&server, &tool_name, &argument_errors);
+        let _ = notify_mcp_tool_call_skip(
+            sess.as_ref(),
+            turn_context,
+            &call_id,
+            invocation,
+            message.clone(),
+            argument_errors,
+        )
+        .await;
+        turn_context.client.get_otel_manager().counter(
+            "codex.mcp.call",
+            1,
+            &[("status", "invalid_arguments")],
+        );
+        // Returned as plain text: the `Err` form of an MCP result is escaped.
+        return ResponseInputItem::FunctionCallOutput {
+            call_id,
+            output: FunctionCallOutputPayload {
+                content: message,
+                success: Some(false),
+                ..Default::default()
+            },
+        };
+    }
+
     if let Some(decision) =
         maybe_request_mcp_tool_approval(sess.as_ref(), turn_context, &call_id, &server, &tool_name)
             .await
@@ -84,6 +124,7 @@ pub(crate) async fn handle_mcp_tool_call(
                     invocation,
                     duration: start.elapsed(),
                     result: result.clone(),
+                    argument_errors: Vec::new(),
                 });
                 notify_mcp_tool_call_event(
                     sess.as_ref(),
@@ -101,6 +142,7 @@ pub(crate) async fn handle_mcp_tool_call(
                     &call_id,
                     invocation,
                     message,
+                    Vec::new(),
                 )
                 .await
             }
@@ -112,6 +154,7 @@ pub(crate) async fn handle_mcp_tool_call(
                     &call_id,
                     invocation,
                     message,
+                    Vec::new(),
                 )
                 .await
             }
@@ -146,6 +189,7 @@ pub(crate) async fn handle_mcp_tool_call(
         invocation,
         duration: start.elapsed(),
         result: result.clone(),
+        argument_errors: Vec::new(),
     });
 
     notify_mcp_tool_call_event(sess.as_ref(), turn_context, tool_call_end_event.clone()).await;
@@ -159,6 +203,59 @@ pub(crate) async fn handle_mcp_tool_call(
     ResponseInputItem::McpToolCallOutput { call_id, result }
 }
 
+/// Validate `arguments` against the tool's `inputSchema` according to the
+/// server's `argument_validation` setting. Returns the mismatches only when the
+/// call must not be dispatched.
+async fn check_arguments(
+    sess: &Session,
+    turn_context: &TurnContext,
+    server: &str,
+    tool_name: &str,
+    arguments: Option<&serde_json::Value>,
+) -> Vec<McpArgumentError> {
+    let validation = turn_context
+        .client
+        .config()
+        .mcp_servers
+        .get()
+        .get(server)
+        .map(|config| config.argument_validation)
+        .unwrap_or_default();
+    if validation == McpArgumentValidation::Off {
+        return Vec::new();
+    }
+    let Some(input_schema) = lookup_mcp_tool_input_schema(sess, server, tool_name).await else {
+        return Vec::new();
+    };
+    let errors = validate_arguments(&input_schema, arguments);
+    if validation == McpArgumentValidation::Warn && !errors.is_empty() {
+        tracing::warn!(
+            "arguments for MCP tool `{server}/{tool_name}` do not match its input schema: {errors:?}"
+        );
+        return Vec::new();
+    }
+    errors
+}
+
+async fn lookup_mcp_tool_input_schema(
+    sess: &Session,
+    server: &str,
+    tool_name: &str,
+) -> Option<ToolInputSchema> {
+    let tools = sess
+        .services
+        .mcp_connection_manager
+        .read()
+        .await
+        .list_all_tools()
+        .await;
+
+    tools.into_values().find_map(|tool_info| {
+        (tool_info.server_name == server && tool_info.tool_name == tool_name)
+            .then_some(tool_info.tool.input_schema)
+    })
+}
+
 async fn notify_mcp_tool_call_event(sess: &Session, turn_context: &TurnContext, event: EventMsg) {
     sess.send_event(turn_context, event).await;
 }
@@ -341,6 +438,7 @@ async fn notify_mcp_tool_call_skip(
     call_id: &str,
     invocation: McpInvocation,
     message: String,
+    argument_errors: Vec<McpArgumentError>,
 ) -> Result<mcp_types::CallToolResult, String> {
     let tool_call_begin_event = EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
         call_id: call_id.to_string(),
@@ -353,6 +451,7 @@ async fn notify_mcp_tool_call_skip(
         invocation,
         duration: Duration::ZERO,
         result: Err(message.clone()),
+        argument_errors,
     });
     notify_mcp_tool_call_event(sess, turn_context, tool_call_end_event).await;
     Err(message)
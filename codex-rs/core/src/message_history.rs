@@ -15,6 +15,13 @@
 //! that writes up to `PIPE_BUF` bytes are atomic in that case.
 //! Note: `conversation_id` stores the thread id; the field name is preserved for
 //! backwards compatibility with existing history files.
+//!
+//! Once the file grows past `history.max_bytes` or `history.max_entries`, the
+//! newest entries are written to a temporary file that is renamed over
+//! `history.jsonl`. The replacement file has a new `log_id`, so clients holding
+//! offsets into the old file are told to refresh rather than being handed a
+//! different entry. When the rename fails the file is left as it is, and
+//! compaction is tried again on the next write.
 
 use std::fs::File;
 use std::fs::OpenOptions;
@@ -27,6 +34,8 @@ use std::io::SeekFrom;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -47,12 +56,35 @@ use std::os::unix::fs::PermissionsExt;
 /// Filename that stores the message history inside `~/.codex`.
 const HISTORY_FILENAME: &str = "history.jsonl";
 
-/// When history exceeds the hard cap, trim it down to this fraction of `max_bytes`.
+/// When history exceeds the hard cap, trim it down to this fraction of `max_bytes`
+/// or `max_entries`.
 const HISTORY_SOFT_CAP_RATIO: f64 = 0.8;
 
+/// Number of recent entries checked for duplicates when `history.dedup_window`
+/// is not set.
+const DEFAULT_DEDUP_WINDOW: usize = 20;
+
+/// Appended to entries cut short by `history.max_entry_bytes`.
+const TRUNCATION_MARKER: &str = "… [truncated]";
+
+/// Chunk size used when reading recent entries from the end of the file.
+const TAIL_CHUNK_BYTES: u64 = 8 * 1024;
+
 const MAX_RETRIES: usize = 10;
 const RETRY_SLEEP: Duration = Duration::from_millis(100);
 
+/// Entry count of the history file after this process last appended to it,
+/// so `max_entries` can be checked without rereading the whole file.
+static ENTRY_COUNT: Mutex<Option<EntryCount>> = Mutex::new(None);
+
+#[derive(Debug, Clone)]
+struct EntryCount {
+    path: PathBuf,
+    log_id: Option<u64>,
+    len: u64,
+    entries: usize,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct HistoryEntry {
     pub session_id: String,
@@ -60,6 +92,24 @@ pub struct HistoryEntry {
     pub text: String,
 }
 
+/// Limits applied while appending, resolved from `[history]` in the config.
+#[derive(Debug, Clone, Copy)]
+struct HistoryLimits {
+    max_bytes: Option<usize>,
+    max_entries: Option<usize>,
+    dedup_window: usize,
+}
+
+impl HistoryLimits {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            max_bytes: config.history.max_bytes,
+            max_entries: config.history.max_entries,
+            dedup_window: config.history.dedup_window.unwrap_or(DEFAULT_DEDUP_WINDOW),
+        }
+    }
+}
+
 fn history_filepath(config: &Config) -> PathBuf {
     let mut path = config.codex_home.clone();
     path.push(HISTORY_FILENAME);
@@ -69,6 +119,9 @@ fn history_filepath(config: &Config) -> PathBuf {
 /// Append a `text` entry associated with `conversation_id` to the history file. Uses
 /// advisory file locking to ensure that concurrent writes do not interleave,
 /// which entails a small amount of blocking I/O internally.
+///
+/// The entry is skipped when it repeats one of the most recent entries, and
+/// truncated first when it is longer than `history.max_entry_bytes`.
 pub(crate) async fn append_entry(
     text: &str,
     conversation_id: &ThreadId,
@@ -102,13 +155,112 @@ pub(crate) async fn append_entry(
     let entry = HistoryEntry {
         session_id: conversation_id.to_string(),
         ts,
-        text: text.to_string(),
+        text: truncate_entry(text, config.history.max_entry_bytes),
     };
     let mut line = serde_json::to_string(&entry)
         .map_err(|e| std::io::Error::other(format!("failed to serialise history entry: {e}")))?;
     line.push('\n');
 
-    // Open the history file for read/write access (append-only on Unix).
+    let limits = HistoryLimits::from_config(config);
+
+    // Perform a blocking write under an advisory write lock using std::fs.
+    tokio::task::spawn_blocking(move || append_line(&path, &entry.text, &line, limits)).await?
+}
+
+/// Write `line` to the history file unless `text` repeats one of the most
+/// recent entries, then compact the file if it grew past its limits.
+fn append_line(path: &Path, text: &str, line: &str, limits: HistoryLimits) -> Result<()> {
+    with_exclusive_lock(path, |history_file| {
+        if is_recent_duplicate(history_file, text, limits.dedup_window)? {
+            return Ok(());
+        }
+
+        // We do not open the file with `append(true)` on Windows, so ensure the
+        // cursor is positioned at the end before writing.
+        let len_before = history_file.seek(SeekFrom::End(0))?;
+        history_file.write_all(line.as_bytes())?;
+        history_file.flush()?;
+        let entries = match limits.max_entries {
+            Some(max) if max > 0 => Some(entry_count_after_append(history_file, path, len_before)?),
+            _ => None,
+        };
+        compact_history(history_file, path, limits, entries)
+    })
+}
+
+/// Number of entries in the history file right after one was appended at
+/// `len_before`. The count is carried over from this process's previous
+/// append when the file is still the same one at the same length; another
+/// process appending, a compaction or a clear forces a single recount.
+fn entry_count_after_append(file: &mut File, path: &Path, len_before: u64) -> Result<usize> {
+    let metadata = file.metadata()?;
+    let log_id = history_log_id(&metadata);
+    let mut cached = ENTRY_COUNT.lock().unwrap_or_else(PoisonError::into_inner);
+    let entries = match cached.as_ref() {
+        Some(count) if count.path == path && count.log_id == log_id && count.len == len_before => {
+            count.entries + 1
+        }
+        _ => read_line_lengths(file)?.len(),
+    };
+    *cached = Some(EntryCount {
+        path: path.to_path_buf(),
+        log_id,
+        len: metadata.len(),
+        entries,
+    });
+    Ok(entries)
+}
+
+/// Remove every entry from the history file and return the `log_id` of the
+/// new, empty file.
+pub(crate) async fn clear(config: &Config) -> Result<u64> {
+    let path = history_filepath(config);
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    tokio::task::spawn_blocking(move || clear_history_file(&path)).await?
+}
+
+fn clear_history_file(path: &Path) -> Result<u64> {
+    with_exclusive_lock(path, |history_file| {
+        replace_history_file(history_file, path, &[])
+    })?;
+    let metadata = std::fs::metadata(path)?;
+    Ok(history_log_id(&metadata).unwrap_or(0))
+}
+
+/// Open the history file and run `f` while holding an exclusive lock on it.
+///
+/// Compaction renames a new file over the path while the old one is still
+/// locked, so a handle opened just before that happened points at a file
+/// nobody reads anymore. Such handles are dropped and the path reopened.
+fn with_exclusive_lock<T>(path: &Path, mut f: impl FnMut(&mut File) -> Result<T>) -> Result<T> {
+    // Retry a few times to avoid indefinite blocking when contended.
+    for _ in 0..MAX_RETRIES {
+        let mut history_file = open_history_file(path)?;
+        match history_file.try_lock() {
+            Ok(()) => {
+                if !is_current_history_file(&history_file, path)? {
+                    continue;
+                }
+                return f(&mut history_file);
+            }
+            Err(std::fs::TryLockError::WouldBlock) => {
+                std::thread::sleep(RETRY_SLEEP);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Err(std::io::Error::new(
+        std::io::ErrorKind::WouldBlock,
+        "could not acquire exclusive lock on history file after multiple attempts",
+    ))
+}
+
+/// Open the history file for read/write access (append-only on Unix).
+fn open_history_file(path: &Path) -> Result<File> {
     let mut options = OpenOptions::new();
     options.read(true).write(true).create(true);
     #[cfg(unix)]
@@ -117,73 +269,156 @@ pub(crate) async fn append_entry(
         options.mode(0o600);
     }
 
-    let mut history_file = options.open(&path)?;
+    let history_file = options.open(path)?;
+    ensure_owner_only_permissions(&history_file)?;
+    Ok(history_file)
+}
+
+fn is_current_history_file(file: &File, path: &Path) -> Result<bool> {
+    let current = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    Ok(history_log_id(&file.metadata()?) == history_log_id(&current))
+}
 
-    // Ensure permissions.
-    ensure_owner_only_permissions(&history_file).await?;
+/// Cut `text` down to `max_bytes`, including the truncation marker, without
+/// splitting a character. Limits too small to hold the marker cut the text
+/// without one.
+fn truncate_entry(text: &str, max_bytes: Option<usize>) -> String {
+    let Some(max_bytes) = max_bytes.filter(|max| *max > 0) else {
+        return text.to_string();
+    };
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
 
-    let history_max_bytes = config.history.max_bytes;
+    let marker = if max_bytes > TRUNCATION_MARKER.len() {
+        TRUNCATION_MARKER
+    } else {
+        ""
+    };
+    let mut end = max_bytes - marker.len();
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{marker}", &text[..end])
+}
 
-    // Perform a blocking write under an advisory write lock using std::fs.
-    tokio::task::spawn_blocking(move || -> Result<()> {
-        // Retry a few times to avoid indefinite blocking when contended.
-        for _ in 0..MAX_RETRIES {
-            match history_file.try_lock() {
-                Ok(()) => {
-                    // While holding the exclusive lock, write the full line.
-                    // We do not open the file with `append(true)` on Windows, so ensure the
-                    // cursor is positioned at the end before writing.
-                    history_file.seek(SeekFrom::End(0))?;
-                    history_file.write_all(line.as_bytes())?;
-                    history_file.flush()?;
-                    enforce_history_limit(&mut history_file, history_max_bytes)?;
-                    return Ok(());
-                }
-                Err(std::fs::TryLockError::WouldBlock) => {
-                    std::thread::sleep(RETRY_SLEEP);
-                }
-                Err(e) => return Err(e.into()),
-            }
-        }
+fn is_recent_duplicate(file: &mut File, text: &str, window: usize) -> Result<bool> {
+    if window == 0 {
+        return Ok(false);
+    }
 
-        Err(std::io::Error::new(
-            std::io::ErrorKind::WouldBlock,
-            "could not acquire exclusive lock on history file after multiple attempts",
-        ))
-    })
-    .await??;
+    Ok(read_tail_lines(file, window)?.iter().any(|line| {
+        serde_json::from_str::<HistoryEntry>(line).is_ok_and(|entry| entry.text == text)
+    }))
+}
 
-    Ok(())
+/// Read up to `count` complete lines from the end of the file, without
+/// reading more of the file than needed.
+fn read_tail_lines(file: &mut File, count: usize) -> Result<Vec<String>> {
+    let mut start = file.metadata()?.len();
+    let mut tail = Vec::new();
+
+    // Stop once the tail holds more newlines than wanted lines, which
+    // guarantees that the last `count` lines are complete.
+    while start > 0 && tail.iter().filter(|&&b| b == b'\n').count() <= count {
+        let chunk_start = start.saturating_sub(TAIL_CHUNK_BYTES);
+        let mut chunk = vec![0; (start - chunk_start) as usize];
+        file.seek(SeekFrom::Start(chunk_start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+        start = chunk_start;
+    }
+
+    let tail = String::from_utf8_lossy(&tail);
+    let mut lines: Vec<&str> = tail.lines().collect();
+    if start > 0 && !lines.is_empty() {
+        // The first line started before the bytes that were read.
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(count);
+    Ok(lines[skip..]
+        .iter()
+        .map(|line| (*line).to_string())
+        .collect())
 }
 
-/// Trim the history file to honor `max_bytes`, dropping the oldest lines while holding
-/// the write lock so the newest entry is always retained. When the file exceeds the
-/// hard cap, it rewrites the remaining tail to a soft cap to avoid trimming again
-/// immediately on the next write.
-fn enforce_history_limit(file: &mut File, max_bytes: Option<usize>) -> Result<()> {
-    let Some(max_bytes) = max_bytes else {
+/// Drop the oldest lines once the file exceeds `max_bytes` or `max_entries`,
+/// while holding the write lock so the newest entry is always retained. The
+/// remaining tail is cut to a soft cap to avoid compacting again immediately
+/// on the next write, and replaces the file atomically. `entries` is the
+/// file's entry count, known whenever `max_entries` is set.
+fn compact_history(
+    file: &mut File,
+    path: &Path,
+    limits: HistoryLimits,
+    entries: Option<usize>,
+) -> Result<()> {
+    let max_bytes = limits
+        .max_bytes
+        .filter(|max| *max > 0)
+        .and_then(|max| u64::try_from(max).ok());
+    let max_entries = limits.max_entries.filter(|max| *max > 0);
+
+    let current_len = file.metadata()?.len();
+    let over_bytes = max_bytes.is_some_and(|max| current_len > max);
+    let over_entries = max_entries
+        .zip(entries)
+        .is_some_and(|(max, entries)| entries > max);
+    if !over_bytes && !over_entries {
         return Ok(());
-    };
+    }
 
-    if max_bytes == 0 {
+    let line_lengths = read_line_lengths(file)?;
+    if line_lengths.is_empty() {
         return Ok(());
     }
 
-    let max_bytes = match u64::try_from(max_bytes) {
-        Ok(value) => value,
-        Err(_) => return Ok(()),
-    };
+    let last_index = line_lengths.len() - 1;
+    let mut drop_lines = 0usize;
 
-    let mut current_len = file.metadata()?.len();
+    if let Some(max_entries) = max_entries
+        && line_lengths.len() > max_entries
+    {
+        drop_lines = line_lengths.len() - trim_target_entries(max_entries);
+    }
+
+    if let Some(max_bytes) = max_bytes
+        && over_bytes
+    {
+        let trim_target = trim_target_bytes(max_bytes, line_lengths[last_index]);
+        let mut remaining_len = current_len;
+        let mut idx = 0usize;
+
+        while remaining_len > trim_target && idx < last_index {
+            remaining_len = remaining_len.saturating_sub(line_lengths[idx]);
+            idx += 1;
+        }
+
+        drop_lines = drop_lines.max(idx);
+    }
 
-    if current_len <= max_bytes {
+    if drop_lines == 0 {
         return Ok(());
     }
 
-    let mut reader_file = file.try_clone()?;
-    reader_file.seek(SeekFrom::Start(0))?;
+    let drop_bytes: u64 = line_lengths[..drop_lines].iter().sum();
+    let capacity = usize::try_from(current_len - drop_bytes).unwrap_or(0);
+    let mut tail = Vec::with_capacity(capacity);
+
+    file.seek(SeekFrom::Start(drop_bytes))?;
+    file.read_to_end(&mut tail)?;
+
+    replace_history_file(path, &tail)
+}
 
-    let mut buf_reader = BufReader::new(reader_file);
+fn read_line_lengths(file: &mut File) -> Result<Vec<u64>> {
+    file.seek(SeekFrom::Start(0))?;
+    let mut buf_reader = BufReader::new(file);
     let mut line_lengths = Vec::new();
     let mut line_buf = String::new();
 
@@ -199,39 +434,27 @@ fn enforce_history_limit(file: &mut File, max_bytes: Option<usize>) -> Result<()
         line_lengths.push(bytes as u64);
     }
 
-    if line_lengths.is_empty() {
-        return Ok(());
-    }
-
-    let last_index = line_lengths.len() - 1;
-    let trim_target = trim_target_bytes(max_bytes, line_lengths[last_index]);
-
-    let mut drop_bytes = 0u64;
-    let mut idx = 0usize;
-
-    while current_len > trim_target && idx < last_index {
-        current_len = current_len.saturating_sub(line_lengths[idx]);
-        drop_bytes += line_lengths[idx];
-        idx += 1;
-    }
+    Ok(line_lengths)
+}
 
-    if drop_bytes == 0 {
-        return Ok(());
+/// Write `contents` to a temporary file next to the history file and rename it
+/// into place, so readers see either the old or the new history. If the rename
+/// is refused, which can happen on Windows while other handles are open, the
+/// history is left untouched and compaction is retried on a later write:
+/// rewriting the file in place would keep its `log_id` while shifting every
+/// offset clients may hold.
+fn replace_history_file(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut temp = tempfile::Builder::new()
+        .prefix(".history.jsonl.")
+        .tempfile_in(dir)?;
+    temp.write_all(contents)?;
+    temp.as_file().sync_all()?;
+
+    if let Err(e) = temp.persist(path) {
+        tracing::warn!(error = %e.error, "failed to replace history file; skipping compaction");
     }
 
-    let mut reader = buf_reader.into_inner();
-    reader.seek(SeekFrom::Start(drop_bytes))?;
-
-    let capacity = usize::try_from(current_len).unwrap_or(0);
-    let mut tail = Vec::with_capacity(capacity);
-
-    reader.read_to_end(&mut tail)?;
-
-    file.set_len(0)?;
-    file.seek(SeekFrom::Start(0))?;
-    file.write_all(&tail)?;
-    file.flush()?;
-
     Ok(())
 }
 
@@ -243,6 +466,12 @@ fn trim_target_bytes(max_bytes: u64, newest_entry_len: u64) -> u64 {
     soft_cap_bytes.max(newest_entry_len)
 }
 
+fn trim_target_entries(max_entries: usize) -> usize {
+    ((max_entries as f64) * HISTORY_SOFT_CAP_RATIO)
+        .floor()
+        .clamp(1.0, max_entries as f64) as usize
+}
+
 /// Asynchronously fetch the history file's *identifier* (inode on Unix) and
 /// the current number of entries by counting newline characters.
 pub(crate) async fn history_metadata(config: &Config) -> (u64, usize) {
@@ -266,22 +495,20 @@ pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<Hist
 /// On Unix systems, ensure the file permissions are `0o600` (rw-------). If the
 /// permissions cannot be changed the error is propagated to the caller.
 #[cfg(unix)]
-async fn ensure_owner_only_permissions(file: &File) -> Result<()> {
+fn ensure_owner_only_permissions(file: &File) -> Result<()> {
     let metadata = file.metadata()?;
     let current_mode = metadata.permissions().mode() & 0o777;
     if current_mode != 0o600 {
         let mut perms = metadata.permissions();
         perms.set_mode(0o600);
-        let perms_clone = perms.clone();
-        let file_clone = file.try_clone()?;
-        tokio::task::spawn_blocking(move || file_clone.set_permissions(perms_clone)).await??;
+        file.set_permissions(perms)?;
     }
     Ok(())
 }
 
 #[cfg(windows)]
 // On Windows, simply succeed.
-async fn ensure_owner_only_permissions(_file: &File) -> Result<()> {
+fn ensure_owner_only_permissions(_file: &File) -> Result<()> {
     Ok(())
 }
 
@@ -546,6 +773,9 @@ mod tests {
             .await
             .expect("load config");
 
+        // The same entry is appended twice below.
+        config.history.dedup_window = Some(0);
+
         let conversation_id = ThreadId::new();
 
         let short_entry = "a".repeat(200);
@@ -613,4 +843,266 @@ mod tests {
         assert_eq!(pruned_len, long_entry_len);
         assert!(pruned_len <= soft_cap_bytes.max(long_entry_len));
     }
+
+    async fn test_config(codex_home: &TempDir) -> Config {
+        ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load config")
+    }
+
+    fn read_entries(path: &Path) -> Vec<HistoryEntry> {
+        std::fs::read_to_string(path)
+            .expect("read history")
+            .lines()
+            .map(|line| serde_json::from_str::<HistoryEntry>(line).expect("parse entry"))
+            .collect()
+    }
+
+    fn texts(entries: &[HistoryEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.text.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn append_entry_skips_recent_duplicates() {
+        let codex_home = TempDir::new().expect("create temp dir");
+        let mut config = test_config(&codex_home).await;
+        let conversation_id = ThreadId::new();
+        let history_path = codex_home.path().join(HISTORY_FILENAME);
+
+        for text in ["fix the tests", "run clippy", "fix the tests"] {
+            append_entry(text, &conversation_id, &config)
+                .await
+                .expect("append entry");
+        }
+        assert_eq!(
+            texts(&read_entries(&history_path)),
+            vec!["fix the tests", "run clippy"]
+        );
+
+        config.history.dedup_window = Some(1);
+        append_entry("fix the tests", &conversation_id, &config)
+            .await
+            .expect("append entry");
+        append_entry("fix the tests", &conversation_id, &config)
+            .await
+            .expect("append entry");
+        assert_eq!(
+            texts(&read_entries(&history_path)),
+            vec!["fix the tests", "run clippy", "fix the tests"]
+        );
+    }
+
+    #[tokio::test]
+    async fn append_entry_truncates_long_entries_with_marker() {
+        let codex_home = TempDir::new().expect("create temp dir");
+        let mut config = test_config(&codex_home).await;
+        config.history.max_entry_bytes = Some(64);
+
+        append_entry(&"é".repeat(100), &ThreadId::new(), &config)
+            .await
+            .expect("append entry");
+
+        let entries = read_entries(&codex_home.path().join(HISTORY_FILENAME));
+        assert_eq!(entries.len(), 1);
+        let text = &entries[0].text;
+        assert!(text.len() <= 64, "entry is {} bytes", text.len());
+        assert!(text.ends_with(TRUNCATION_MARKER));
+        assert!(
+            text.trim_end_matches(TRUNCATION_MARKER)
+                .chars()
+                .all(|c| c == 'é')
+        );
+    }
+
+    #[test]
+    fn truncate_entry_drops_marker_when_limit_is_smaller() {
+        let text = "é".repeat(20);
+
+        assert_eq!(truncate_entry(&text, Some(5)), "éé");
+        assert_eq!(truncate_entry(&text, Some(1)), "");
+        assert_eq!(
+            truncate_entry(&text, Some(TRUNCATION_MARKER.len())),
+            "é".repeat(TRUNCATION_MARKER.len() / 2)
+        );
+        for max_bytes in 1..=TRUNCATION_MARKER.len() + 4 {
+            let truncated = truncate_entry(&text, Some(max_bytes));
+            assert!(
+                truncated.len() <= max_bytes,
+                "{max_bytes}-byte limit produced {} bytes",
+                truncated.len()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn append_entry_evicts_oldest_entries_beyond_max_entries() {
+        let codex_home = TempDir::new().expect("create temp dir");
+        let mut config = test_config(&codex_home).await;
+        config.history.max_entries = Some(5);
+        let conversation_id = ThreadId::new();
+        let history_path = codex_home.path().join(HISTORY_FILENAME);
+
+        for idx in 0..5 {
+            append_entry(&format!("entry {idx}"), &conversation_id, &config)
+                .await
+                .expect("append entry");
+        }
+        let (log_id, count) = history_metadata_for_file(&history_path).await;
+        assert_eq!(count, 5);
+
+        append_entry("entry 5", &conversation_id, &config)
+            .await
+            .expect("append entry");
+
+        // Six entries exceed the cap, so the history is cut to the soft cap of four.
+        assert_eq!(
+            texts(&read_entries(&history_path)),
+            vec!["entry 2", "entry 3", "entry 4", "entry 5"]
+        );
+
+        let (new_log_id, new_count) = history_metadata_for_file(&history_path).await;
+        assert_eq!(new_count, 4);
+        #[cfg(unix)]
+        {
+            assert_ne!(new_log_id, log_id, "compaction should start a new log");
+            assert_eq!(lookup_history_entry(&history_path, log_id, 0), None);
+        }
+        assert_eq!(
+            lookup_history_entry(&history_path, new_log_id, 0).map(|entry| entry.text),
+            Some("entry 2".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn max_entries_counts_entries_appended_by_other_writers() {
+        let codex_home = TempDir::new().expect("create temp dir");
+        let mut config = test_config(&codex_home).await;
+        config.history.max_entries = Some(5);
+        let conversation_id = ThreadId::new();
+        let history_path = codex_home.path().join(HISTORY_FILENAME);
+
+        for idx in 0..4 {
+            append_entry(&format!("entry {idx}"), &conversation_id, &config)
+                .await
+                .expect("append entry");
+        }
+        // Another process appends without going through this process's count.
+        let outside = HistoryEntry {
+            session_id: "other".to_string(),
+            ts: 0,
+            text: "outside".to_string(),
+        };
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&history_path)
+            .expect("open history");
+        writeln!(
+            file,
+            "{}",
+            serde_json::to_string(&outside).expect("serialize entry")
+        )
+        .expect("append outside entry");
+
+        append_entry("entry 4", &conversation_id, &config)
+            .await
+            .expect("append entry");
+
+        assert_eq!(
+            texts(&read_entries(&history_path)),
+            vec!["entry 2", "entry 3", "outside", "entry 4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn clear_empties_history_and_starts_a_new_log() {
+        let codex_home = TempDir::new().expect("create temp dir");
+        let config = test_config(&codex_home).await;
+        let history_path = codex_home.path().join(HISTORY_FILENAME);
+
+        append_entry("first", &ThreadId::new(), &config)
+            .await
+            .expect("append entry");
+        let (log_id, _) = history_metadata(&config).await;
+
+        let new_log_id = clear(&config).await.expect("clear history");
+
+        assert_eq!(history_metadata(&config).await, (new_log_id, 0));
+        #[cfg(unix)]
+        assert_ne!(new_log_id, log_id);
+        assert_eq!(lookup_history_entry(&history_path, log_id, 0), None);
+
+        append_entry("first", &ThreadId::new(), &config)
+            .await
+            .expect("append after clear");
+        assert_eq!(texts(&read_entries(&history_path)), vec!["first"]);
+    }
+
+    /// Two writers with their own file handles append while the history is
+    /// compacted repeatedly. Advisory locks taken through separate handles
+    /// contend exactly like locks held by separate processes.
+    #[test]
+    fn concurrent_appends_survive_compaction() {
+        const WRITERS: usize = 2;
+        const ENTRIES_PER_WRITER: usize = 40;
+        const MAX_ENTRIES: usize = 10;
+
+        let codex_home = TempDir::new().expect("create temp dir");
+        let history_path = codex_home.path().join(HISTORY_FILENAME);
+        let limits = HistoryLimits {
+            max_bytes: None,
+            max_entries: Some(MAX_ENTRIES),
+            dedup_window: DEFAULT_DEDUP_WINDOW,
+        };
+
+        let writers: Vec<_> = (0..WRITERS)
+            .map(|writer| {
+                let path = history_path.clone();
+                std::thread::spawn(move || {
+                    for idx in 0..ENTRIES_PER_WRITER {
+                        let entry = HistoryEntry {
+                            session_id: format!("writer-{writer}"),
+                            ts: idx as u64,
+                            text: format!("writer {writer} entry {idx}"),
+                        };
+                        let line = format!(
+                            "{}\n",
+                            serde_json::to_string(&entry).expect("serialize entry")
+                        );
+                        append_line(&path, &entry.text, &line, limits).expect("append line");
+                        // Pause like an interactive session would, so neither
+                        // writer exhausts its lock retries.
+                        std::thread::sleep(Duration::from_millis(10));
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().expect("writer thread");
+        }
+
+        let entries = read_entries(&history_path);
+        assert!(
+            (trim_target_entries(MAX_ENTRIES)..=MAX_ENTRIES).contains(&entries.len()),
+            "unexpected entry count {}",
+            entries.len()
+        );
+
+        for writer in 0..WRITERS {
+            let session_id = format!("writer-{writer}");
+            let survivors: Vec<u64> = entries
+                .iter()
+                .filter(|entry| entry.session_id == session_id)
+                .map(|entry| entry.ts)
+                .collect();
+            let Some(&first) = survivors.first() else {
+                continue;
+            };
+            // Only the oldest entries are evicted, so whatever is left of a
+            // writer's entries is an unbroken run ending with its last one.
+            let expected: Vec<u64> = (first..ENTRIES_PER_WRITER as u64).collect();
+            assert_eq!(survivors, expected, "entries from {session_id}");
+        }
+    }
 }
@@ -6,11 +6,16 @@
 //!      key. These override or extend the defaults at runtime.
 
 use crate::auth::AuthMode;
+use crate::error::CodexErr;
 use crate::error::EnvVarError;
+use crate::provider_env;
+use crate::provider_env::DotenvFile;
 use codex_api::Provider as ApiProvider;
 use codex_api::WireApi as ApiWireApi;
 use codex_api::is_azure_responses_wire_base_url;
 use codex_api::provider::RetryConfig as ApiRetryConfig;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::protocol::AskForApproval;
 use http::HeaderMap;
 use http::header::HeaderName;
 use http::header::HeaderValue;
@@ -18,12 +23,12 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
-use std::env::VarError;
 use std::time::Duration;
 
 const DEFAULT_STREAM_IDLE_TIMEOUT_MS: u64 = 300_000;
 const DEFAULT_STREAM_MAX_RETRIES: u64 = 5;
 const DEFAULT_REQUEST_MAX_RETRIES: u64 = 4;
+const DEFAULT_FAILOVER_AFTER_FAILURES: u64 = 2;
 /// Hard cap for user-configured `stream_max_retries`.
 const MAX_STREAM_MAX_RETRIES: u64 = 100;
 /// Hard cap for user-configured `request_max_retries`.
@@ -49,13 +54,24 @@ pub enum WireApi {
     Chat,
 }
 
+/// Values substituted for the `{model}` and `{session_id}` placeholders in
+/// `base_url` and `query_params`. Requests made outside a session (e.g. the
+/// models listing) leave these unset, and URLs that need them fail to build.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct UrlTemplateVars<'a> {
+    pub model: Option<&'a str>,
+    pub session_id: Option<&'a str>,
+}
+
 /// Serializable representation of a provider definition.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct ModelProviderInfo {
     /// Friendly display name.
     pub name: String,
-    /// Base URL for the provider's OpenAI-compatible API.
+    /// Base URL for the provider's OpenAI-compatible API. May contain
+    /// `{env:VAR}`, `{model}` and `{session_id}` placeholders, which are
+    /// substituted when each request is built.
     pub base_url: Option<String>,
     /// Environment variable that stores the user's API key for this provider.
     pub env_key: Option<String>,
@@ -73,7 +89,8 @@ pub struct ModelProviderInfo {
     #[serde(default)]
     pub wire_api: WireApi,
 
-    /// Optional query parameters to append to the base URL.
+    /// Optional query parameters to append to the base URL. Values accept the
+    /// same placeholders as `base_url`.
     pub query_params: Option<HashMap<String, String>>,
 
     /// Additional HTTP headers to include in requests to this provider where
@@ -96,6 +113,12 @@ pub struct ModelProviderInfo {
     /// the connection as lost.
     pub stream_idle_timeout_ms: Option<u64>,
 
+    /// Timeout (in milliseconds) to wait for the first byte of a streaming
+    /// response. Unlike `stream_idle_timeout_ms`, this only applies until the
+    /// server starts sending, so a backend that accepts the request and then
+    /// stays silent is detected quickly.
+    pub first_byte_timeout_ms: Option<u64>,
+
     /// Does this provider require an OpenAI API Key or ChatGPT login token? If true,
     /// user is presented with login screen on first run, and login preference and token/key
     /// are stored in auth.json. If false (which is the default), login screen is skipped,
@@ -106,10 +129,53 @@ pub struct ModelProviderInfo {
     /// Whether this provider supports the Responses API WebSocket transport.
     #[serde(default)]
     pub supports_websockets: bool,
+
+    /// Re-read `env_key` and `env_http_headers` from the environment on every
+    /// request attempt, including retries, instead of once per turn. Useful
+    /// when those variables hold short-lived credentials that rotate.
+    #[serde(default)]
+    pub refresh_headers_per_attempt: bool,
+
+    /// Key in `model_providers` of a provider to switch to after repeated
+    /// failures of this one. The switch lasts for the rest of the turn; the
+    /// next turn starts on this provider again.
+    pub fallback_provider: Option<String>,
+
+    /// Number of consecutive failed attempts before switching to
+    /// `fallback_provider`.
+    pub failover_after_failures: Option<u64>,
+
+    /// Always send `store: false` to this provider's Responses endpoint, for
+    /// gateways that accept `store: true` but do not keep the stored items
+    /// later requests refer to.
+    #[serde(default)]
+    pub disable_response_storage: bool,
+
+    /// Whether this provider accepts file uploads (`POST /files`) and
+    /// `input_file` content parts on its Responses endpoint, which
+    /// `upload_attachments` uses instead of inlining attached files.
+    #[serde(default)]
+    pub supports_file_uploads: bool,
+
+    /// Sandbox mode for sessions using this provider when neither the command
+    /// line nor the active profile sets one.
+    pub sandbox_mode: Option<SandboxMode>,
+
+    /// Approval policy for sessions using this provider when neither the
+    /// command line nor the active profile sets one.
+    pub approval_policy: Option<AskForApproval>,
+
+    /// Extra top-level fields merged into every request body sent to this
+    /// provider, e.g. `top_k` or vendor-specific sampling knobs. Keys that
+    /// Codex already sends are rejected when the config is loaded.
+    pub extra_body: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl ModelProviderInfo {
-    fn build_header_map(&self) -> crate::error::Result<HeaderMap> {
+    fn build_header_map(
+        &self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> crate::error::Result<HeaderMap> {
         let mut headers = HeaderMap::new();
         if let Some(extra) = &self.http_headers {
             for (k, v) in extra {
@@ -119,9 +185,21 @@ impl ModelProviderInfo {
             }
         }
 
+        // Providers that refresh per attempt resolve these when each request
+        // is built instead; see `api_bridge::auth_provider_from_auth`.
+        if !self.refresh_headers_per_attempt {
+            headers.extend(self.env_header_map(lookup));
+        }
+
+        Ok(headers)
+    }
+
+    /// Resolves `env_http_headers`, reading each variable through `lookup`.
+    pub(crate) fn env_header_map(&self, lookup: impl Fn(&str) -> Option<String>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
         if let Some(env_headers) = &self.env_http_headers {
             for (header, env_var) in env_headers {
-                if let Ok(val) = std::env::var(env_var)
+                if let Some(val) = lookup(env_var)
                     && !val.trim().is_empty()
                     && let (Ok(name), Ok(value)) =
                         (HeaderName::try_from(header), HeaderValue::try_from(val))
@@ -130,25 +208,51 @@ impl ModelProviderInfo {
                 }
             }
         }
-
-        Ok(headers)
+        headers
     }
 
+    /// Converts this provider for the wire layer, reading `{env:VAR}`
+    /// placeholders and `env_http_headers` from the environment and `dotenv`.
     pub(crate) fn to_api_provider(
         &self,
         auth_mode: Option<AuthMode>,
+        vars: UrlTemplateVars<'_>,
+        dotenv: Option<&DotenvFile>,
+    ) -> crate::error::Result<ApiProvider> {
+        self.to_api_provider_with(auth_mode, vars, |name| provider_env::var(name, dotenv))
+    }
+
+    /// Like [`Self::to_api_provider`], reading `{env:VAR}` placeholders
+    /// through `lookup`.
+    pub(crate) fn to_api_provider_with(
+        &self,
+        auth_mode: Option<AuthMode>,
+        vars: UrlTemplateVars<'_>,
+        lookup: impl Fn(&str) -> Option<String>,
     ) -> crate::error::Result<ApiProvider> {
         let default_base_url = if matches!(auth_mode, Some(AuthMode::Chatgpt)) {
             "https://chatgpt.com/backend-api/codex"
         } else {
             "https://api.openai.com/v1"
         };
-        let base_url = self
-            .base_url
-            .clone()
-            .unwrap_or_else(|| default_base_url.to_string());
+        let base_url = match &self.base_url {
+            Some(base_url) => expand_url_template(base_url, vars, &lookup)?,
+            None => default_base_url.to_string(),
+        };
+        let query_params = self
+            .query_params
+            .as_ref()
+            .map(|params| {
+                params
+                    .iter()
+                    .map(|(key, value)| {
+                        expand_url_template(value, vars, &lookup).map(|value| (key.clone(), value))
+                    })
+                    .collect::<crate::error::Result<HashMap<_, _>>>()
+            })
+            .transpose()?;
 
-        let headers = self.build_header_map()?;
+        let headers = self.build_header_map(&lookup)?;
         let retry = ApiRetryConfig {
             max_attempts: self.request_max_retries(),
             base_delay: Duration::from_millis(200),
@@ -160,7 +264,7 @@ impl ModelProviderInfo {
         Ok(ApiProvider {
             name: self.name.clone(),
             base_url,
-            query_params: self.query_params.clone(),
+            query_params,
             wire: match self.wire_api {
                 WireApi::Responses => ApiWireApi::Responses,
                 WireApi::Chat => ApiWireApi::Chat,
@@ -168,9 +272,32 @@ impl ModelProviderInfo {
             headers,
             retry,
             stream_idle_timeout: self.stream_idle_timeout(),
+            first_byte_timeout: self.first_byte_timeout(),
         })
     }
 
+    /// Checks that every placeholder in `base_url` and `query_params` is
+    /// known and that each `{env:VAR}` is set, reading variables through
+    /// `lookup`. Run when the config is loaded so a typo surfaces at startup
+    /// rather than on the first request.
+    pub fn validate_url_templates(
+        &self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> crate::error::Result<()> {
+        let vars = UrlTemplateVars {
+            model: Some(""),
+            session_id: Some(""),
+        };
+        let templates = self
+            .base_url
+            .iter()
+            .chain(self.query_params.iter().flat_map(HashMap::values));
+        for template in templates {
+            expand_url_template(template, vars, &lookup)?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn is_azure_responses_endpoint(&self) -> bool {
         let wire = match self.wire_api {
             WireApi::Responses => ApiWireApi::Responses,
@@ -181,27 +308,27 @@ impl ModelProviderInfo {
     }
 
     /// If `env_key` is Some, returns the API key for this provider if present
-    /// (and non-empty) in the environment. If `env_key` is required but
+    /// (and non-empty) in the environment or `dotenv`. If `env_key` is required but
     /// cannot be found, returns an error.
-    pub fn api_key(&self) -> crate::error::Result<Option<String>> {
+    pub fn api_key(&self, dotenv: Option<&DotenvFile>) -> crate::error::Result<Option<String>> {
+        self.api_key_from(|name| provider_env::var(name, dotenv))
+    }
+
+    /// Like [`Self::api_key`], reading `env_key` through `lookup`.
+    pub(crate) fn api_key_from(
+        &self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> crate::error::Result<Option<String>> {
         match &self.env_key {
-            Some(env_key) => {
-                let env_value = std::env::var(env_key);
-                env_value
-                    .and_then(|v| {
-                        if v.trim().is_empty() {
-                            Err(VarError::NotPresent)
-                        } else {
-                            Ok(Some(v))
-                        }
-                    })
-                    .map_err(|_| {
-                        crate::error::CodexErr::EnvVar(EnvVarError {
-                            var: env_key.clone(),
-                            instructions: self.env_key_instructions.clone(),
-                        })
+            Some(env_key) => lookup(env_key)
+                .filter(|v| !v.trim().is_empty())
+                .map(Some)
+                .ok_or_else(|| {
+                    crate::error::CodexErr::EnvVar(EnvVarError {
+                        var: env_key.clone(),
+                        instructions: self.env_key_instructions.clone(),
                     })
-            }
+                }),
             None => Ok(None),
         }
     }
@@ -226,6 +353,20 @@ impl ModelProviderInfo {
             .map(Duration::from_millis)
             .unwrap_or(Duration::from_millis(DEFAULT_STREAM_IDLE_TIMEOUT_MS))
     }
+
+    /// Timeout for the first byte of a streaming response, if configured.
+    pub fn first_byte_timeout(&self) -> Option<Duration> {
+        self.first_byte_timeout_ms.map(Duration::from_millis)
+    }
+
+    /// Effective number of consecutive failures before failing over to
+    /// `fallback_provider`.
+    pub fn failover_after_failures(&self) -> u64 {
+        self.failover_after_failures
+            .unwrap_or(DEFAULT_FAILOVER_AFTER_FAILURES)
+            .max(1)
+    }
+
     pub fn create_openai_provider() -> ModelProviderInfo {
         ModelProviderInfo {
             name: OPENAI_PROVIDER_NAME.into(),
@@ -262,8 +403,17 @@ impl ModelProviderInfo {
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            first_byte_timeout_ms: None,
             requires_openai_auth: true,
             supports_websockets: true,
+            refresh_headers_per_attempt: false,
+            fallback_provider: None,
+            failover_after_failures: None,
+            disable_response_storage: false,
+            supports_file_uploads: true,
+            sandbox_mode: None,
+            approval_policy: None,
+            extra_body: None,
         }
     }
 
@@ -272,6 +422,55 @@ impl ModelProviderInfo {
     }
 }
 
+/// Substitutes `{env:VAR}`, `{model}` and `{session_id}` in `template`.
+/// Values are inserted verbatim, so an env var may supply several path
+/// segments. Unknown or unresolvable placeholders are errors rather than
+/// being sent to the server as-is.
+fn expand_url_template(
+    template: &str,
+    vars: UrlTemplateVars<'_>,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> crate::error::Result<String> {
+    let invalid =
+        |reason: String| CodexErr::InvalidRequest(format!("invalid URL `{template}`: {reason}"));
+
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        expanded.push_str(&rest[..open]);
+        let after_open = &rest[open + 1..];
+        let Some(close) = after_open.find('}') else {
+            return Err(invalid("unterminated `{`".to_string()));
+        };
+        let name = &after_open[..close];
+        let value = match name {
+            "model" => vars
+                .model
+                .ok_or_else(|| invalid("`{model}` is not available for this request".into()))?
+                .to_string(),
+            "session_id" => vars
+                .session_id
+                .ok_or_else(|| invalid("`{session_id}` is not available for this request".into()))?
+                .to_string(),
+            _ => match name.strip_prefix("env:") {
+                Some(var) if !var.is_empty() => lookup(var)
+                    .filter(|v| !v.trim().is_empty())
+                    .ok_or_else(|| {
+                        CodexErr::EnvVar(EnvVarError {
+                            var: var.to_string(),
+                            instructions: None,
+                        })
+                    })?,
+                _ => return Err(invalid(format!("unknown placeholder `{{{name}}}`"))),
+            },
+        };
+        expanded.push_str(&value);
+        rest = &after_open[close + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
 pub const DEFAULT_LMSTUDIO_PORT: u16 = 1234;
 pub const DEFAULT_OLLAMA_PORT: u16 = 11434;
 
@@ -341,8 +540,17 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        first_byte_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
     }
 }
 
@@ -370,8 +578,17 @@ base_url = "http://localhost:11434/v1"
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            first_byte_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            refresh_headers_per_attempt: false,
+            fallback_provider: None,
+            failover_after_failures: None,
+            disable_response_storage: false,
+            supports_file_uploads: false,
+            sandbox_mode: None,
+            approval_policy: None,
+            extra_body: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -401,8 +618,17 @@ query_params = { api-version = "2025-04-01-preview" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            first_byte_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            refresh_headers_per_attempt: false,
+            fallback_provider: None,
+            failover_after_failures: None,
+            disable_response_storage: false,
+            supports_file_uploads: false,
+            sandbox_mode: None,
+            approval_policy: None,
+            extra_body: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -435,11 +661,94 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            first_byte_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            refresh_headers_per_attempt: false,
+            fallback_provider: None,
+            failover_after_failures: None,
+            disable_response_storage: false,
+            supports_file_uploads: false,
+            sandbox_mode: None,
+            approval_policy: None,
+            extra_body: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
         assert_eq!(expected_provider, provider);
     }
+
+    fn templated_provider(wire_api: WireApi) -> ModelProviderInfo {
+        let provider_toml = r#"
+name = "Gateway"
+base_url = "https://gw.internal/{env:GW_TENANT}/{model}/v1"
+query_params = { team = "{env:GW_TEAM}", session = "{session_id}" }
+        "#;
+        ModelProviderInfo {
+            wire_api,
+            ..toml::from_str(provider_toml).unwrap()
+        }
+    }
+
+    fn gateway_env(var: &str) -> Option<String> {
+        match var {
+            "GW_TENANT" => Some("acme".to_string()),
+            "GW_TEAM" => Some("abc".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn url_templates_resolve_for_both_wire_apis() {
+        let vars = UrlTemplateVars {
+            model: Some("gpt-5"),
+            session_id: Some("0199-session"),
+        };
+        for (wire_api, path) in [
+            (WireApi::Responses, "responses"),
+            (WireApi::Chat, "chat/completions"),
+        ] {
+            let api_provider = templated_provider(wire_api)
+                .to_api_provider_with(None, vars, gateway_env)
+                .unwrap();
+
+            assert_eq!(api_provider.base_url, "https://gw.internal/acme/gpt-5/v1");
+            assert_eq!(
+                api_provider.query_params,
+                Some(maplit::hashmap! {
+                    "team".to_string() => "abc".to_string(),
+                    "session".to_string() => "0199-session".to_string(),
+                })
+            );
+            let url = api_provider.url_for_path(path);
+            assert!(
+                url.starts_with(&format!("https://gw.internal/acme/gpt-5/v1/{path}?")),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn url_templates_report_unresolvable_placeholders() {
+        let provider = templated_provider(WireApi::Responses);
+
+        assert!(provider.validate_url_templates(gateway_env).is_ok());
+        assert!(matches!(
+            provider.validate_url_templates(|_| None),
+            Err(CodexErr::EnvVar(EnvVarError { var, .. })) if var == "GW_TENANT" || var == "GW_TEAM"
+        ));
+
+        let typo = ModelProviderInfo {
+            base_url: Some("https://gw.internal/{tenant}/v1".into()),
+            ..provider.clone()
+        };
+        assert!(matches!(
+            typo.validate_url_templates(gateway_env),
+            Err(CodexErr::InvalidRequest(msg)) if msg.contains("unknown placeholder `{tenant}`")
+        ));
+
+        let outside_session =
+            provider.to_api_provider_with(None, UrlTemplateVars::default(), gateway_env);
+        assert!(matches!(outside_session, Err(CodexErr::InvalidRequest(_))));
+    }
 }
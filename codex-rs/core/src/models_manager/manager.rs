@@ -3,24 +3,30 @@ use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::AuthManager;
 use crate::auth::AuthMode;
+use crate::auth::CodexAuth;
 use crate::config::Config;
 use crate::default_client::build_reqwest_client;
 use crate::error::CodexErr;
 use crate::error::Result as CoreResult;
 use crate::features::Feature;
 use crate::model_provider_info::ModelProviderInfo;
+use crate::model_provider_info::UrlTemplateVars;
 use crate::models_manager::collaboration_mode_presets::builtin_collaboration_mode_presets;
 use crate::models_manager::model_info;
 use crate::models_manager::model_presets::builtin_model_presets;
+use crate::models_manager::provider_models;
 use codex_api::ModelsClient;
 use codex_api::ReqwestTransport;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelsResponse;
+use codex_protocol::protocol::UnknownModel;
 use http::HeaderMap;
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio::sync::TryLockError;
@@ -51,6 +57,8 @@ pub struct ModelsManager {
     etag: RwLock<Option<String>>,
     cache_manager: ModelsCacheManager,
     provider: ModelProviderInfo,
+    /// Unknown model slugs already reported by [`Self::unknown_model_warning`].
+    warned_unknown_models: Mutex<HashSet<String>>,
 }
 
 impl ModelsManager {
@@ -67,6 +75,7 @@ impl ModelsManager {
             etag: RwLock::new(None),
             cache_manager,
             provider: ModelProviderInfo::create_openai_provider(),
+            warned_unknown_models: Mutex::new(HashSet::new()),
         }
     }
 
@@ -144,11 +153,77 @@ impl ModelsManager {
         let model = if let Some(remote) = remote {
             remote
         } else {
-            model_info::find_model_info_for_slug(model)
+            model_info::find_model_info(model, config)
         };
         model_info::with_config_overrides(model, config)
     }
 
+    /// Returns a warning the first time `model` is used without any metadata
+    /// from the server, the built-in list, or `[model_capabilities]` in config.
+    pub(crate) async fn unknown_model_warning(
+        &self,
+        model: &str,
+        config: &Config,
+    ) -> Option<String> {
+        if model_info::is_known_model(model, config)
+            || self
+                .get_remote_models(config)
+                .await
+                .iter()
+                .any(|remote| remote.slug == model)
+        {
+            return None;
+        }
+        let first_use = self
+            .warned_unknown_models
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(model.to_string());
+        first_use.then(|| {
+            format!(
+                "Codex has no metadata for model `{model}`, so it is using conservative defaults (unknown context window, no reasoning summaries, no parallel tool calls). Declare its capabilities under `[model_capabilities.\"{model}\"]` in config.toml."
+            )
+        })
+    }
+
+    /// Checks that `model` exists. Models in Codex's registry are accepted
+    /// as-is; anything else is looked up in the provider's model list, or
+    /// reported against the registry when that list is unavailable.
+    pub(crate) async fn check_model(
+        &self,
+        model: &str,
+        config: &Config,
+        auth: Option<CodexAuth>,
+    ) -> Option<UnknownModel> {
+        let known_models = self.known_model_slugs(config).await;
+        if model_info::is_known_model(model, config)
+            || known_models.iter().any(|known| known == model)
+        {
+            return None;
+        }
+        provider_models::check_model(model, config, auth, known_models).await
+    }
+
+    /// Every model slug Codex has metadata for, without asking the provider.
+    async fn known_model_slugs(&self, config: &Config) -> Vec<String> {
+        let mut slugs: Vec<String> = self
+            .local_models
+            .iter()
+            .map(|preset| preset.model.clone())
+            .chain(
+                self.remote_models
+                    .read()
+                    .await
+                    .iter()
+                    .map(|model| model.slug.clone()),
+            )
+            .chain(config.model_capabilities.keys().cloned())
+            .collect();
+        slugs.sort();
+        slugs.dedup();
+        slugs
+    }
+
     /// Refresh models if the provided ETag differs from the cached ETag.
     ///
     /// Uses `Online` strategy to fetch latest models when ETags differ.
@@ -191,22 +266,27 @@ impl ModelsManager {
                 if self.try_load_cache().await {
                     return Ok(());
                 }
-                self.fetch_and_update_models().await
+                self.fetch_and_update_models(config).await
             }
             RefreshStrategy::Online => {
                 // Always fetch from network
-                self.fetch_and_update_models().await
+                self.fetch_and_update_models(config).await
             }
         }
     }
 
-    async fn fetch_and_update_models(&self) -> CoreResult<()> {
+    async fn fetch_and_update_models(&self, config: &Config) -> CoreResult<()> {
         let _timer =
             codex_otel::start_global_timer("codex.remote_models.fetch_update.duration_ms", &[]);
         let auth = self.auth_manager.auth().await;
         let auth_mode = self.auth_manager.get_internal_auth_mode();
-        let api_provider = self.provider.to_api_provider(auth_mode)?;
-        let api_auth = auth_provider_from_auth(auth.clone(), &self.provider)?;
+        let api_provider = self.provider.to_api_provider(
+            auth_mode,
+            UrlTemplateVars::default(),
+            config.dotenv.as_deref(),
+        )?;
+        let api_auth =
+            auth_provider_from_auth(auth.clone(), &self.provider, config.dotenv.clone())?;
         let transport = ReqwestTransport::new(build_reqwest_client());
         let client = ModelsClient::new(transport, api_provider, api_auth);
 
@@ -325,6 +405,7 @@ impl ModelsManager {
             etag: RwLock::new(None),
             cache_manager,
             provider,
+            warned_unknown_models: Mutex::new(HashSet::new()),
         }
     }
 
@@ -346,7 +427,7 @@ impl ModelsManager {
     #[cfg(any(test, feature = "test-support"))]
     /// Build `ModelInfo` without consulting remote state or cache.
     pub fn construct_model_info_offline(model: &str, config: &Config) -> ModelInfo {
-        model_info::with_config_overrides(model_info::find_model_info_for_slug(model), config)
+        model_info::with_config_overrides(model_info::find_model_info(model, config), config)
     }
 }
 
@@ -366,6 +447,7 @@ mod tests {
     use crate::CodexAuth;
     use crate::auth::AuthCredentialsStoreMode;
     use crate::config::ConfigBuilder;
+    use crate::config::types::ModelCapabilitiesToml;
     use crate::features::Feature;
     use crate::model_provider_info::WireApi;
     use chrono::Utc;
@@ -435,8 +517,17 @@ mod tests {
             request_max_retries: Some(0),
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(5_000),
+            first_byte_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            refresh_headers_per_attempt: false,
+            fallback_provider: None,
+            failover_after_failures: None,
+            disable_response_storage: false,
+            supports_file_uploads: false,
+            sandbox_mode: None,
+            approval_policy: None,
+            extra_body: None,
         }
     }
 
@@ -704,6 +795,87 @@ mod tests {
         assert_eq!(available, vec![expected_hidden, expected_visible]);
     }
 
+    #[tokio::test]
+    async fn declared_capabilities_extend_based_on_model() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_capabilities.insert(
+            "proxy/codex".to_string(),
+            ModelCapabilitiesToml {
+                based_on: Some("gpt-5.1-codex".to_string()),
+                context_window: Some(128_000),
+                supports_parallel_tool_calls: Some(true),
+                ..Default::default()
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let provider = provider_for("http://example.test".to_string());
+        let manager =
+            ModelsManager::with_provider(codex_home.path().to_path_buf(), auth_manager, provider);
+
+        let mut expected = ModelsManager::construct_model_info_offline("gpt-5.1-codex", &config);
+        expected.slug = "proxy/codex".to_string();
+        expected.display_name = "proxy/codex".to_string();
+        expected.context_window = Some(128_000);
+        expected.supports_parallel_tool_calls = true;
+
+        assert_eq!(
+            manager.get_model_info("proxy/codex", &config).await,
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_model_warning_is_reported_once() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_capabilities.insert(
+            "declared-model".to_string(),
+            ModelCapabilitiesToml::default(),
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let provider = provider_for("http://example.test".to_string());
+        let manager =
+            ModelsManager::with_provider(codex_home.path().to_path_buf(), auth_manager, provider);
+
+        let warnings = vec![
+            manager
+                .unknown_model_warning("mystery-model", &config)
+                .await,
+            manager
+                .unknown_model_warning("mystery-model", &config)
+                .await,
+            manager
+                .unknown_model_warning("gpt-5.1-codex", &config)
+                .await,
+            manager
+                .unknown_model_warning("declared-model", &config)
+                .await,
+        ];
+
+        assert_eq!(
+            warnings,
+            vec![
+                Some(
+                    "Codex has no metadata for model `mystery-model`, so it is using conservative defaults (unknown context window, no reasoning summaries, no parallel tool calls). Declare its capabilities under `[model_capabilities.\"mystery-model\"]` in config.toml.".to_string()
+                ),
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
     #[test]
     fn bundled_models_json_roundtrips() {
         let file_contents = include_str!("../../models.json");
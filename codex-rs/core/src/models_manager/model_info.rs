@@ -1,3 +1,4 @@
+use codex_protocol::config_types::SamplingParameters;
 use codex_protocol::config_types::Verbosity;
 use codex_protocol::openai_models::ApplyPatchToolType;
 use codex_protocol::openai_models::ConfigShellToolType;
@@ -11,7 +12,9 @@ use codex_protocol::openai_models::TruncationMode;
 use codex_protocol::openai_models::TruncationPolicyConfig;
 
 use crate::config::Config;
+use crate::config::types::ModelCapabilitiesToml;
 use crate::features::Feature;
+use crate::model_provider_info::WireApi;
 use crate::truncate::approx_bytes_for_tokens;
 use tracing::warn;
 
@@ -56,6 +59,7 @@ macro_rules! model_info {
             upgrade: None,
             base_instructions: BASE_INSTRUCTIONS.to_string(),
             model_messages: None,
+            supports_reasoning: false,
             supports_reasoning_summaries: false,
             support_verbosity: false,
             default_verbosity: None,
@@ -66,6 +70,7 @@ macro_rules! model_info {
             auto_compact_token_limit: None,
             effective_context_window_percent: 95,
             experimental_supported_tools: Vec::new(),
+            max_output_tokens: None,
         };
 
         $(
@@ -76,6 +81,9 @@ macro_rules! model_info {
 }
 
 pub(crate) fn with_config_overrides(mut model: ModelInfo, config: &Config) -> ModelInfo {
+    if let Some(capabilities) = config.model_capabilities.get(&model.slug) {
+        apply_declared_capabilities(&mut model, capabilities);
+    }
     if let Some(supports_reasoning_summaries) = config.model_supports_reasoning_summaries {
         model.supports_reasoning_summaries = supports_reasoning_summaries;
     }
@@ -109,22 +117,130 @@ pub(crate) fn with_config_overrides(mut model: ModelInfo, config: &Config) -> Mo
     model
 }
 
+fn apply_declared_capabilities(model: &mut ModelInfo, capabilities: &ModelCapabilitiesToml) {
+    if let Some(context_window) = capabilities.context_window {
+        model.context_window = Some(context_window);
+    }
+    if let Some(auto_compact_token_limit) = capabilities.auto_compact_token_limit {
+        model.auto_compact_token_limit = Some(auto_compact_token_limit);
+    }
+    if let Some(max_output_tokens) = capabilities.max_output_tokens {
+        model.max_output_tokens = Some(max_output_tokens);
+    }
+    if let Some(supports_reasoning) = capabilities.supports_reasoning {
+        model.supports_reasoning = supports_reasoning;
+    }
+    if let Some(supports_reasoning_summaries) = capabilities.supports_reasoning_summaries {
+        model.supports_reasoning_summaries = supports_reasoning_summaries;
+    }
+    if let Some(supports_parallel_tool_calls) = capabilities.supports_parallel_tool_calls {
+        model.supports_parallel_tool_calls = supports_parallel_tool_calls;
+    }
+    if let Some(apply_patch_tool_type) = &capabilities.apply_patch_tool_type {
+        model.apply_patch_tool_type = Some(apply_patch_tool_type.clone());
+    }
+    if capabilities.prompted_tools == Some(true) {
+        // Freeform tools cannot be described in the prompt, so prompted
+        // models always get the JSON variant of `apply_patch`.
+        model.apply_patch_tool_type = Some(ApplyPatchToolType::Function);
+    }
+    if let Some(needs_apply_patch_instructions) = capabilities.needs_apply_patch_instructions {
+        model.base_instructions = if needs_apply_patch_instructions {
+            BASE_INSTRUCTIONS_WITH_APPLY_PATCH.to_string()
+        } else {
+            BASE_INSTRUCTIONS.to_string()
+        };
+        model.model_messages = None;
+    }
+}
+
+/// Output token cap to request from `model`: `requested`, lowered to the
+/// model's maximum when it declares one.
+pub(crate) fn clamp_max_output_tokens(requested: Option<i64>, model: &ModelInfo) -> Option<i64> {
+    match (requested, model.max_output_tokens) {
+        (Some(requested), Some(max)) => Some(requested.min(max)),
+        (requested, _) => requested,
+    }
+}
+
+/// The part of `requested` that can be sent for `model` over `wire_api`.
+/// Reasoning models accept no sampling parameters, and the Responses API has
+/// no `seed`.
+pub(crate) fn supported_sampling_parameters(
+    requested: SamplingParameters,
+    model: &ModelInfo,
+    wire_api: WireApi,
+) -> SamplingParameters {
+    if model.supports_reasoning {
+        return SamplingParameters::default();
+    }
+    SamplingParameters {
+        seed: requested.seed.filter(|_| wire_api == WireApi::Chat),
+        ..requested
+    }
+}
+
+/// Metadata for `slug` when the server does not provide it: the built-in
+/// entry, or the `based_on` model of a `[model_capabilities]` entry in config.
+/// Declared capabilities themselves are applied by [`with_config_overrides`].
+pub(crate) fn find_model_info(slug: &str, config: &Config) -> ModelInfo {
+    let Some(capabilities) = config.model_capabilities.get(slug) else {
+        return find_model_info_for_slug(slug);
+    };
+    let base = capabilities.based_on.as_deref().unwrap_or(slug);
+    let mut model = builtin_model_info(base).unwrap_or_else(|| {
+        if base != slug {
+            warn!("model_capabilities.{slug}.based_on names unknown model {base}");
+        }
+        unknown_model_info(slug)
+    });
+    model.slug = slug.to_string();
+    model.display_name = slug.to_string();
+    model
+}
+
+/// Whether Codex has metadata for `slug` without consulting the server.
+pub(crate) fn is_known_model(slug: &str, config: &Config) -> bool {
+    config.model_capabilities.contains_key(slug) || builtin_model_info(slug).is_some()
+}
+
+fn find_model_info_for_slug(slug: &str) -> ModelInfo {
+    builtin_model_info(slug).unwrap_or_else(|| {
+        warn!("Unknown model {slug} is used. This will degrade the performance of Codex.");
+        unknown_model_info(slug)
+    })
+}
+
+/// Conservative defaults for a model Codex knows nothing about.
+fn unknown_model_info(slug: &str) -> ModelInfo {
+    model_info!(
+        slug,
+        context_window: None,
+        supported_reasoning_levels: Vec::new(),
+        default_reasoning_level: None
+    )
+}
+
 // todo(aibrahim): remove most of the entries here when enabling models.json
-pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
-    if slug.starts_with("o3") || slug.starts_with("o4-mini") {
+fn builtin_model_info(slug: &str) -> Option<ModelInfo> {
+    let model = if slug.starts_with("o3") || slug.starts_with("o4-mini") {
         model_info!(
             slug,
             base_instructions: BASE_INSTRUCTIONS_WITH_APPLY_PATCH.to_string(),
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             context_window: Some(200_000),
+            max_output_tokens: Some(100_000),
         )
     } else if slug.starts_with("codex-mini-latest") {
         model_info!(
             slug,
             base_instructions: BASE_INSTRUCTIONS_WITH_APPLY_PATCH.to_string(),
             shell_type: ConfigShellToolType::Local,
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             context_window: Some(200_000),
+            max_output_tokens: Some(100_000),
         )
     } else if slug.starts_with("gpt-4.1") {
         model_info!(
@@ -132,6 +248,7 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             base_instructions: BASE_INSTRUCTIONS_WITH_APPLY_PATCH.to_string(),
             supports_reasoning_summaries: false,
             context_window: Some(1_047_576),
+            max_output_tokens: Some(32_768),
         )
     } else if slug.starts_with("gpt-oss") || slug.starts_with("openai/gpt-oss") {
         model_info!(
@@ -145,6 +262,7 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             base_instructions: BASE_INSTRUCTIONS_WITH_APPLY_PATCH.to_string(),
             supports_reasoning_summaries: false,
             context_window: Some(128_000),
+            max_output_tokens: Some(16_384),
         )
     } else if slug.starts_with("gpt-3.5") {
         model_info!(
@@ -152,6 +270,7 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             base_instructions: BASE_INSTRUCTIONS_WITH_APPLY_PATCH.to_string(),
             supports_reasoning_summaries: false,
             context_window: Some(16_385),
+            max_output_tokens: Some(4_096),
         )
     } else if slug.starts_with("test-gpt-5") {
         model_info!(
@@ -164,6 +283,7 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
                 "test_sync_tool".to_string(),
             ],
             supports_parallel_tool_calls: true,
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             shell_type: ConfigShellToolType::ShellCommand,
             support_verbosity: true,
@@ -184,15 +304,18 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             apply_patch_tool_type: Some(ApplyPatchToolType::Freeform),
             shell_type: ConfigShellToolType::ShellCommand,
             supports_parallel_tool_calls: true,
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             support_verbosity: false,
             truncation_policy: TruncationPolicyConfig::tokens(10_000),
             context_window: Some(CONTEXT_WINDOW_272K),
+            max_output_tokens: Some(128_000),
         )
     } else if slug.starts_with("exp-") {
         model_info!(
             slug,
             apply_patch_tool_type: Some(ApplyPatchToolType::Freeform),
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             support_verbosity: true,
             default_verbosity: Some(Verbosity::Low),
@@ -202,6 +325,7 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             shell_type: ConfigShellToolType::UnifiedExec,
             supports_parallel_tool_calls: true,
             context_window: Some(CONTEXT_WINDOW_272K),
+            max_output_tokens: Some(128_000),
         )
     } else if slug.starts_with("gpt-5.2-codex") || slug.starts_with("bengalfox") {
         model_info!(
@@ -210,10 +334,12 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             apply_patch_tool_type: Some(ApplyPatchToolType::Freeform),
             shell_type: ConfigShellToolType::ShellCommand,
             supports_parallel_tool_calls: true,
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             support_verbosity: false,
             truncation_policy: TruncationPolicyConfig::tokens(10_000),
             context_window: Some(CONTEXT_WINDOW_272K),
+            max_output_tokens: Some(128_000),
             supported_reasoning_levels: supported_reasoning_level_low_medium_high_xhigh(),
             base_instructions: GPT_5_2_CODEX_INSTRUCTIONS.to_string(),
             model_messages: Some(ModelMessages {
@@ -232,10 +358,12 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             apply_patch_tool_type: Some(ApplyPatchToolType::Freeform),
             shell_type: ConfigShellToolType::ShellCommand,
             supports_parallel_tool_calls: false,
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             support_verbosity: false,
             truncation_policy: TruncationPolicyConfig::tokens(10_000),
             context_window: Some(CONTEXT_WINDOW_272K),
+            max_output_tokens: Some(128_000),
             supported_reasoning_levels: supported_reasoning_level_low_medium_high_xhigh(),
         )
     } else if (slug.starts_with("gpt-5-codex")
@@ -249,10 +377,12 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             apply_patch_tool_type: Some(ApplyPatchToolType::Freeform),
             shell_type: ConfigShellToolType::ShellCommand,
             supports_parallel_tool_calls: false,
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             support_verbosity: false,
             truncation_policy: TruncationPolicyConfig::tokens(10_000),
             context_window: Some(CONTEXT_WINDOW_272K),
+            max_output_tokens: Some(128_000),
             supported_reasoning_levels: supported_reasoning_level_low_medium_high(),
         )
     } else if slug.starts_with("gpt-5-codex")
@@ -265,15 +395,18 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             apply_patch_tool_type: Some(ApplyPatchToolType::Freeform),
             shell_type: ConfigShellToolType::ShellCommand,
             supports_parallel_tool_calls: false,
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             support_verbosity: false,
             truncation_policy: TruncationPolicyConfig::tokens(10_000),
             context_window: Some(CONTEXT_WINDOW_272K),
+            max_output_tokens: Some(128_000),
         )
     } else if slug.starts_with("gpt-5.2") || slug.starts_with("boomslang") {
         model_info!(
             slug,
             apply_patch_tool_type: Some(ApplyPatchToolType::Freeform),
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             support_verbosity: true,
             default_verbosity: Some(Verbosity::Low),
@@ -283,12 +416,14 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             shell_type: ConfigShellToolType::ShellCommand,
             supports_parallel_tool_calls: true,
             context_window: Some(CONTEXT_WINDOW_272K),
+            max_output_tokens: Some(128_000),
             supported_reasoning_levels: supported_reasoning_level_low_medium_high_xhigh_non_codex(),
         )
     } else if slug.starts_with("gpt-5.1") {
         model_info!(
             slug,
             apply_patch_tool_type: Some(ApplyPatchToolType::Freeform),
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             support_verbosity: true,
             default_verbosity: Some(Verbosity::Low),
@@ -298,6 +433,7 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             shell_type: ConfigShellToolType::ShellCommand,
             supports_parallel_tool_calls: true,
             context_window: Some(CONTEXT_WINDOW_272K),
+            max_output_tokens: Some(128_000),
             supported_reasoning_levels: supported_reasoning_level_low_medium_high_non_codex(),
         )
     } else if slug.starts_with("gpt-5") {
@@ -305,20 +441,17 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
             slug,
             base_instructions: BASE_INSTRUCTIONS_WITH_APPLY_PATCH.to_string(),
             shell_type: ConfigShellToolType::Default,
+            supports_reasoning: true,
             supports_reasoning_summaries: true,
             support_verbosity: true,
             truncation_policy: TruncationPolicyConfig::bytes(10_000),
             context_window: Some(CONTEXT_WINDOW_272K),
+            max_output_tokens: Some(128_000),
         )
     } else {
-        warn!("Unknown model {slug} is used. This will degrade the performance of Codex.");
-        model_info!(
-            slug,
-            context_window: None,
-            supported_reasoning_levels: Vec::new(),
-            default_reasoning_level: None
-        )
-    }
+        return None;
+    };
+    Some(model)
 }
 
 fn supported_reasoning_level_low_medium_high() -> Vec<ReasoningEffortPreset> {
@@ -396,3 +529,83 @@ fn supported_reasoning_level_low_medium_high_xhigh_non_codex() -> Vec<ReasoningE
         },
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn clamp_max_output_tokens_respects_model_maximum() {
+        let gpt_4o = builtin_model_info("gpt-4o").expect("gpt-4o is built in");
+        let unknown = unknown_model_info("my-proxy/model");
+
+        assert_eq!(
+            (
+                clamp_max_output_tokens(Some(100_000), &gpt_4o),
+                clamp_max_output_tokens(Some(4_096), &gpt_4o),
+                clamp_max_output_tokens(None, &gpt_4o),
+                clamp_max_output_tokens(Some(100_000), &unknown),
+            ),
+            (Some(16_384), Some(4_096), None, Some(100_000))
+        );
+    }
+
+    #[test]
+    fn prompted_tools_use_function_apply_patch() {
+        let mut model = builtin_model_info("gpt-5.1-codex").expect("gpt-5.1-codex is built in");
+        assert_eq!(
+            model.apply_patch_tool_type,
+            Some(ApplyPatchToolType::Freeform)
+        );
+
+        apply_declared_capabilities(
+            &mut model,
+            &ModelCapabilitiesToml {
+                prompted_tools: Some(true),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            model.apply_patch_tool_type,
+            Some(ApplyPatchToolType::Function)
+        );
+    }
+
+    #[test]
+    fn sampling_parameters_are_gated_by_model_and_wire_api() {
+        let gpt_4o = builtin_model_info("gpt-4o").expect("gpt-4o is built in");
+        let codex = builtin_model_info("gpt-5.1-codex").expect("gpt-5.1-codex is built in");
+        let requested = SamplingParameters {
+            seed: Some(7),
+            temperature: Some(0.2),
+            top_p: Some(0.9),
+        };
+
+        assert_eq!(
+            supported_sampling_parameters(requested, &gpt_4o, WireApi::Chat),
+            requested
+        );
+        assert_eq!(
+            supported_sampling_parameters(requested, &gpt_4o, WireApi::Responses),
+            SamplingParameters {
+                seed: None,
+                ..requested
+            }
+        );
+        assert_eq!(
+            supported_sampling_parameters(requested, &codex, WireApi::Chat),
+            SamplingParameters::default()
+        );
+
+        let reasoning_without_summaries = ModelInfo {
+            supports_reasoning_summaries: false,
+            ..codex
+        };
+        assert_eq!(
+            supported_sampling_parameters(requested, &reasoning_without_summaries, WireApi::Chat),
+            SamplingParameters::default()
+        );
+    }
+}
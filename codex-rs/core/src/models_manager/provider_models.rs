@@ -0,0 +1,248 @@
+//! Checks the configured model against the models its provider serves.
+//!
+//! The provider's `GET /models` list is cached per provider in `codex_home`
+//! for a day, so only the first session of the day pays for the request.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_api::ModelsClient;
+use codex_api::ReqwestTransport;
+use codex_protocol::protocol::ModelListSource;
+use codex_protocol::protocol::UnknownModel;
+use http::HeaderMap;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::fs;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::warn;
+
+use crate::api_bridge::auth_provider_from_auth;
+use crate::api_bridge::map_api_error;
+use crate::auth::CodexAuth;
+use crate::config::Config;
+use crate::default_client::build_reqwest_client;
+use crate::error::CodexErr;
+use crate::error::Result as CoreResult;
+use crate::model_provider_info::UrlTemplateVars;
+
+const PROVIDER_MODELS_DIR: &str = "provider_models";
+const PROVIDER_MODELS_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const PROVIDER_MODELS_TIMEOUT: Duration = Duration::from_secs(3);
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A provider's model list as cached on disk.
+#[derive(Debug, Serialize, Deserialize)]
+struct ProviderModelsCache {
+    fetched_at: DateTime<Utc>,
+    /// Base URL the list was fetched from; a different URL invalidates it.
+    base_url: String,
+    models: Vec<String>,
+}
+
+/// Checks `model` against the configured provider's model list, falling back
+/// to `known_models` (Codex's registry) when the provider cannot be asked.
+/// Returns `None` when the model was found.
+pub(crate) async fn check_model(
+    model: &str,
+    config: &Config,
+    auth: Option<CodexAuth>,
+    known_models: Vec<String>,
+) -> Option<UnknownModel> {
+    let provider_models = if config.offline {
+        None
+    } else {
+        match provider_model_ids(config, auth).await {
+            Ok(models) => Some(models),
+            Err(err) => {
+                debug!(
+                    "could not list models for provider {}: {err}",
+                    config.model_provider_id
+                );
+                None
+            }
+        }
+    };
+    let (source, candidates) = match provider_models {
+        Some(models) => (ModelListSource::Provider, models),
+        None => (ModelListSource::Builtin, known_models),
+    };
+    if candidates.iter().any(|candidate| candidate == model) {
+        return None;
+    }
+    Some(UnknownModel {
+        model: model.to_string(),
+        provider: config.model_provider.name.clone(),
+        source,
+        suggestions: closest_models(model, &candidates),
+    })
+}
+
+async fn provider_model_ids(config: &Config, auth: Option<CodexAuth>) -> CoreResult<Vec<String>> {
+    let provider = &config.model_provider;
+    let auth_mode = auth.as_ref().map(CodexAuth::internal_auth_mode);
+    let api_provider = provider.to_api_provider(
+        auth_mode,
+        UrlTemplateVars::default(),
+        config.dotenv.as_deref(),
+    )?;
+    let cache_path = cache_path(&config.codex_home, &config.model_provider_id);
+    if let Some(models) = load_fresh(&cache_path, &api_provider.base_url).await {
+        return Ok(models);
+    }
+
+    let api_auth = auth_provider_from_auth(auth, provider, config.dotenv.clone())?;
+    let transport = ReqwestTransport::new(build_reqwest_client());
+    let base_url = api_provider.base_url.clone();
+    let client = ModelsClient::new(transport, api_provider, api_auth);
+    let models = timeout(
+        PROVIDER_MODELS_TIMEOUT,
+        client.list_model_ids(HeaderMap::new()),
+    )
+    .await
+    .map_err(|_| CodexErr::Timeout)?
+    .map_err(map_api_error)?;
+
+    let cache = ProviderModelsCache {
+        fetched_at: Utc::now(),
+        base_url,
+        models,
+    };
+    if let Err(err) = persist(&cache_path, &cache).await {
+        warn!("failed to write provider models cache: {err}");
+    }
+    Ok(cache.models)
+}
+
+fn cache_path(codex_home: &Path, provider_id: &str) -> PathBuf {
+    let file_name: String = provider_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    codex_home
+        .join(PROVIDER_MODELS_DIR)
+        .join(format!("{file_name}.json"))
+}
+
+async fn load_fresh(path: &Path, base_url: &str) -> Option<Vec<String>> {
+    let contents = fs::read(path).await.ok()?;
+    let cache: ProviderModelsCache = serde_json::from_slice(&contents).ok()?;
+    let age = Utc::now().signed_duration_since(cache.fetched_at);
+    let fresh = chrono::Duration::from_std(PROVIDER_MODELS_CACHE_TTL)
+        .is_ok_and(|ttl| age >= chrono::Duration::zero() && age <= ttl);
+    (fresh && cache.base_url == base_url).then_some(cache.models)
+}
+
+async fn persist(path: &Path, cache: &ProviderModelsCache) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(cache)?).await
+}
+
+/// The candidates closest to `model` by case-insensitive edit distance.
+fn closest_models(model: &str, candidates: &[String]) -> Vec<String> {
+    let model = model.to_lowercase();
+    let mut scored: Vec<(usize, &String)> = candidates
+        .iter()
+        .map(|candidate| (edit_distance(&model, &candidate.to_lowercase()), candidate))
+        .collect();
+    scored.sort();
+    scored.dedup_by(|a, b| a.1 == b.1);
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.clone())
+        .collect()
+}
+
+/// Levenshtein distance between `a` and `b`, counted in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn edit_distance_counts_insertions_deletions_and_substitutions() {
+        assert_eq!(edit_distance("gpt5-codex", "gpt-5-codex"), 1);
+        assert_eq!(edit_distance("gpt-5", "gpt-4"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("o3", "o3"), 0);
+    }
+
+    #[test]
+    fn closest_models_ranks_by_distance_then_name() {
+        let candidates = strings(&[
+            "o3",
+            "gpt-5.1-codex",
+            "gpt-5-codex",
+            "gpt-5-codex-mini",
+            "gpt-4o",
+        ]);
+        assert_eq!(
+            closest_models("GPT5-codex", &candidates),
+            strings(&["gpt-5-codex", "gpt-5.1-codex", "gpt-4o"])
+        );
+    }
+
+    #[test]
+    fn cache_file_name_is_sanitized_per_provider() {
+        assert_eq!(
+            cache_path(Path::new("/home/.codex"), "my provider/v2"),
+            Path::new("/home/.codex")
+                .join("provider_models")
+                .join("my_provider_v2.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn cache_is_ignored_when_stale_or_for_another_url() {
+        let dir = tempdir().expect("tempdir");
+        let path = cache_path(dir.path(), "ollama");
+        let mut cache = ProviderModelsCache {
+            fetched_at: Utc::now(),
+            base_url: "http://localhost:11434/v1".to_string(),
+            models: strings(&["llama3.2"]),
+        };
+        persist(&path, &cache).await.expect("persist");
+        assert_eq!(
+            load_fresh(&path, "http://localhost:11434/v1").await,
+            Some(strings(&["llama3.2"]))
+        );
+        assert_eq!(load_fresh(&path, "http://other:11434/v1").await, None);
+
+        cache.fetched_at = Utc::now() - chrono::Duration::hours(25);
+        persist(&path, &cache).await.expect("persist");
+        assert_eq!(load_fresh(&path, "http://localhost:11434/v1").await, None);
+    }
+}
@@ -0,0 +1,296 @@
+//! Cleans terminal control sequences out of captured command output.
+//!
+//! Tools such as pip, npm, and cargo color their output and redraw progress
+//! bars with carriage returns even when stdout is not a terminal. Left as-is,
+//! the model sees `\x1b[32m` noise and history cells render garbage. This
+//! module replays the output the way a terminal would display it: ANSI
+//! CSI/OSC/DCS sequences are dropped, `\r` and backspace rewrite the current
+//! line so progress bars collapse to their final state, and any remaining
+//! control characters are replaced. Colors are discarded; the TUI renders exec
+//! output without theming today.
+//!
+//! Output that is not text at all, such as `cat` on an executable, is replaced
+//! by a one-line summary instead, since no amount of cleaning makes it readable.
+
+const REPLACEMENT: char = '\u{FFFD}';
+const ESC: char = '\u{1b}';
+const BEL: char = '\u{7}';
+const CSI_8BIT: char = '\u{9b}';
+
+/// How much of the output is inspected to decide whether it is binary.
+const BINARY_SAMPLE_BYTES: usize = 8 * 1024;
+/// Output is binary when more than 1 in this many sampled bytes is a control
+/// byte that text output does not use.
+const BINARY_CONTROL_BYTE_RATIO: usize = 20;
+const BINARY_SUMMARY_PREFIX_BYTES: usize = 16;
+
+/// Whether captured output looks like binary data rather than text: it
+/// contains NUL bytes or an unusually high share of control bytes.
+pub(crate) fn looks_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(BINARY_SAMPLE_BYTES)];
+    if sample.contains(&0) {
+        return true;
+    }
+    let control_bytes = sample
+        .iter()
+        .filter(|&&byte| {
+            (byte < 0x20 && !matches!(byte, b'\n' | b'\r' | b'\t' | 0x07 | 0x08 | 0x0c | 0x1b))
+                || byte == 0x7f
+        })
+        .count();
+    control_bytes * BINARY_CONTROL_BYTE_RATIO > sample.len()
+}
+
+/// One-line description of binary output, shown in place of its bytes.
+pub(crate) fn binary_output_summary(bytes: &[u8]) -> String {
+    let prefix = bytes
+        .iter()
+        .take(BINARY_SUMMARY_PREFIX_BYTES)
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let ellipsis = if bytes.len() > BINARY_SUMMARY_PREFIX_BYTES {
+        " …"
+    } else {
+        ""
+    };
+    format!(
+        "binary output, {}, first bytes: {prefix}{ellipsis}",
+        format_size(bytes.len())
+    )
+}
+
+fn format_size(bytes: usize) -> String {
+    const KB: usize = 1024;
+    const MB: usize = 1024 * KB;
+    if bytes >= MB {
+        format!("{:.1}MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1}KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{bytes}B")
+    }
+}
+
+/// Returns `text` as a terminal would finally display it, without escape
+/// sequences or control characters other than `\n` and `\t`.
+pub(crate) fn sanitize_terminal_output(text: &str) -> String {
+    if !text.chars().any(needs_sanitizing) {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut line = LineBuffer::default();
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\n' => {
+                line.flush_into(&mut out);
+                out.push('\n');
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\r' => line.carriage_return(),
+            '\u{8}' => line.backspace(),
+            '\t' => line.put('\t'),
+            BEL => {}
+            ESC => match chars.next() {
+                Some('[') => {
+                    let (params, final_byte) = read_csi(&mut chars);
+                    if final_byte == Some('K') {
+                        line.erase(&params);
+                    }
+                }
+                Some(']') => skip_string_sequence(&mut chars, true),
+                Some('P' | 'X' | '^' | '_') => skip_string_sequence(&mut chars, false),
+                // Character set designations take one more byte, e.g. `ESC ( B`.
+                Some('(' | ')' | '*' | '+' | '-' | '.' | '/') => {
+                    chars.next();
+                }
+                Some(_) | None => {}
+            },
+            CSI_8BIT => {
+                let (params, final_byte) = read_csi(&mut chars);
+                if final_byte == Some('K') {
+                    line.erase(&params);
+                }
+            }
+            ch if ch.is_control() => line.put(REPLACEMENT),
+            ch => line.put(ch),
+        }
+    }
+    line.flush_into(&mut out);
+    out
+}
+
+fn needs_sanitizing(ch: char) -> bool {
+    ch.is_control() && ch != '\n' && ch != '\t'
+}
+
+/// Consumes a CSI sequence after `ESC [`, returning its parameter bytes and
+/// final byte (if the sequence was terminated).
+fn read_csi(chars: &mut impl Iterator<Item = char>) -> (String, Option<char>) {
+    let mut params = String::new();
+    for ch in chars.by_ref() {
+        if ('\u{40}'..='\u{7e}').contains(&ch) {
+            return (params, Some(ch));
+        }
+        params.push(ch);
+    }
+    (params, None)
+}
+
+/// Consumes an OSC, DCS, SOS, PM, or APC payload up to its string terminator
+/// (`ESC \`). OSC sequences may also end with BEL.
+fn skip_string_sequence(
+    chars: &mut std::iter::Peekable<impl Iterator<Item = char>>,
+    bel_terminates: bool,
+) {
+    while let Some(ch) = chars.next() {
+        if bel_terminates && ch == BEL {
+            return;
+        }
+        if ch == ESC && chars.peek() == Some(&'\\') {
+            chars.next();
+            return;
+        }
+    }
+}
+
+/// The line currently being drawn, with a cursor column for `\r` rewrites.
+#[derive(Default)]
+struct LineBuffer {
+    chars: Vec<char>,
+    col: usize,
+}
+
+impl LineBuffer {
+    fn put(&mut self, ch: char) {
+        if let Some(slot) = self.chars.get_mut(self.col) {
+            *slot = ch;
+        } else {
+            self.chars.push(ch);
+        }
+        self.col += 1;
+    }
+
+    fn carriage_return(&mut self) {
+        self.col = 0;
+    }
+
+    fn backspace(&mut self) {
+        self.col = self.col.saturating_sub(1);
+    }
+
+    /// Applies `CSI n K` (erase in line).
+    fn erase(&mut self, params: &str) {
+        match params {
+            "" | "0" => self.chars.truncate(self.col),
+            "1" => {
+                let end = self.col.min(self.chars.len());
+                self.chars[..end].fill(' ');
+            }
+            _ => self.chars.clear(),
+        }
+    }
+
+    fn flush_into(&mut self, out: &mut String) {
+        out.extend(self.chars.drain(..));
+        self.col = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::binary_output_summary;
+    use super::looks_binary;
+    use super::sanitize_terminal_output;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn plain_text_is_unchanged() {
+        let text = "line one\n\tindented\n";
+        assert_eq!(sanitize_terminal_output(text), text);
+    }
+
+    #[test]
+    fn strips_colors_and_collapses_cargo_progress() {
+        // `cargo build --color=always` output as seen through a pipe.
+        let captured = "\u{1b}[1m\u{1b}[32m   Compiling\u{1b}[0m demo v0.1.0 (/tmp/demo)\n\
+                        \u{1b}[1m\u{1b}[36m    Building\u{1b}[0m [=====>                  ] 1/4: demo\r\u{1b}[K\
+                        \u{1b}[1m\u{1b}[36m    Building\u{1b}[0m [=================>      ] 3/4: demo\r\u{1b}[K\
+                        \u{1b}[1m\u{1b}[32m    Finished\u{1b}[0m `dev` profile [unoptimized + debuginfo] target(s) in 0.42s\n";
+
+        assert_eq!(
+            sanitize_terminal_output(captured),
+            "   Compiling demo v0.1.0 (/tmp/demo)\n    Finished `dev` profile [unoptimized + debuginfo] target(s) in 0.42s\n"
+        );
+    }
+
+    #[test]
+    fn collapses_pip_progress_bar_to_final_state() {
+        // `pip install requests` output with `FORCE_COLOR=1`.
+        let captured = "Collecting requests\n\
+                        \x20 Downloading requests-2.31.0-py3-none-any.whl.metadata (4.6 kB)\n\
+                        \u{1b}[?25l   \u{1b}[90m━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\u{1b}[0m \u{1b}[32m0.0/62.6 kB\u{1b}[0m \u{1b}[31m?\u{1b}[0m eta \u{1b}[36m-:--:--\u{1b}[0m\r\u{1b}[2K   \u{1b}[38;2;114;156;31m━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\u{1b}[0m \u{1b}[32m62.6/62.6 kB\u{1b}[0m \u{1b}[31m2.1 MB/s\u{1b}[0m eta \u{1b}[36m0:00:00\u{1b}[0m\n\
+                        \u{1b}[?25hInstalling collected packages: requests\n";
+
+        assert_eq!(
+            sanitize_terminal_output(captured),
+            "Collecting requests\n  Downloading requests-2.31.0-py3-none-any.whl.metadata (4.6 kB)\n   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ 62.6/62.6 kB 2.1 MB/s eta 0:00:00\nInstalling collected packages: requests\n"
+        );
+    }
+
+    #[test]
+    fn carriage_return_overwrites_in_place() {
+        assert_eq!(sanitize_terminal_output("50%\r100%\n"), "100%\n");
+        assert_eq!(sanitize_terminal_output("abcdef\rXY\n"), "XYcdef\n");
+        assert_eq!(
+            sanitize_terminal_output("windows\r\nline\r\n"),
+            "windows\nline\n"
+        );
+    }
+
+    #[test]
+    fn drops_osc_sequences_and_replaces_other_controls() {
+        let captured =
+            "\u{1b}]8;;https://example.com\u{7}link\u{1b}]8;;\u{1b}\\ done\u{7}\u{0}\u{8}!\n";
+        assert_eq!(sanitize_terminal_output(captured), "link done!\n");
+
+        assert_eq!(sanitize_terminal_output("a\u{1}b\n"), "a\u{FFFD}b\n");
+    }
+
+    #[test]
+    fn unterminated_sequences_do_not_panic() {
+        assert_eq!(sanitize_terminal_output("ok\u{1b}["), "ok");
+        assert_eq!(sanitize_terminal_output("ok\u{1b}]title"), "ok");
+        assert_eq!(sanitize_terminal_output("ok\u{1b}"), "ok");
+    }
+
+    #[test]
+    fn detects_binary_output() {
+        let elf = [0x7f, b'E', b'L', b'F', 2, 1, 1, 0, 0, 0];
+        assert!(looks_binary(&elf));
+        assert!(looks_binary(b"\x01\x02\x03 text \x04\x05"));
+
+        assert!(!looks_binary(b""));
+        assert!(!looks_binary("plain text\n\tindented\r\n".as_bytes()));
+        assert!(!looks_binary(
+            "\u{1b}[32mgreen\u{1b}[0m 50%\r100%\n".as_bytes()
+        ));
+        assert!(!looks_binary("Привет, мир\n".as_bytes()));
+    }
+
+    #[test]
+    fn summarizes_binary_output() {
+        let mut bytes = vec![0x7f, b'E', b'L', b'F'];
+        bytes.resize(4_404_019, 0);
+        assert_eq!(
+            binary_output_summary(&bytes),
+            "binary output, 4.2MB, first bytes: 7f 45 4c 46 00 00 00 00 00 00 00 00 00 00 00 00 …"
+        );
+        assert_eq!(
+            binary_output_summary(&[0, 1, 2]),
+            "binary output, 3B, first bytes: 00 01 02"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashSet;
 use std::io;
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use tempfile::NamedTempFile;
@@ -98,6 +99,8 @@ pub fn resolve_symlink_write_paths(path: &Path) -> io::Result<SymlinkWritePaths>
     }
 }
 
+/// Writes `contents` to a temp file beside `write_path`, syncs it to disk and
+/// renames it into place, so readers see either the old or the new file.
 pub fn write_atomically(write_path: &Path, contents: &str) -> io::Result<()> {
     let parent = write_path.parent().ok_or_else(|| {
         io::Error::new(
@@ -106,9 +109,14 @@ pub fn write_atomically(write_path: &Path, contents: &str) -> io::Result<()> {
         )
     })?;
     std::fs::create_dir_all(parent)?;
-    let tmp = NamedTempFile::new_in(parent)?;
-    std::fs::write(tmp.path(), contents)?;
+    let mut tmp = NamedTempFile::new_in(parent)?;
+    tmp.write_all(contents.as_bytes())?;
+    tmp.as_file().sync_all()?;
     tmp.persist(write_path)?;
+    // Make the rename itself durable. Directories cannot be opened this way
+    // on Windows, where the rename is already flushed by the filesystem.
+    #[cfg(unix)]
+    std::fs::File::open(parent)?.sync_all()?;
     Ok(())
 }
 
@@ -0,0 +1,59 @@
+//! The `preferred_language` setting: the language the model answers in.
+//!
+//! The session's initial context carries a developer message naming the
+//! language, and a new one is added when `Op::SetPreferredLanguage` changes
+//! it, so turns in the session (including `/init`) and review sub-agents
+//! follow it. Built-in prompts sent without that context, such as the commit
+//! message and command explanation prompts, and the compaction prompt get the
+//! same directive appended.
+
+use codex_protocol::models::DeveloperInstructions;
+
+const OPEN_TAG: &str = "<preferred_language>";
+const CLOSE_TAG: &str = "</preferred_language>";
+
+fn directive(language: &str) -> String {
+    format!(
+        "Write every reply, summary and message meant for the user in {language}, even when \
+         the user, the instructions or the code use another language. Keep code, commands, \
+         file paths and identifiers as they are."
+    )
+}
+
+/// The developer message stating the preferred language, or that there is
+/// none any more.
+pub(crate) fn language_message(language: Option<&str>) -> DeveloperInstructions {
+    let text = match language {
+        Some(language) => directive(language),
+        None => "The user no longer has a preferred language; answer in the language they write in."
+            .to_string(),
+    };
+    DeveloperInstructions::new(format!("{OPEN_TAG}{text}{CLOSE_TAG}"))
+}
+
+/// `prompt` with the directive for `language` appended, when one is set.
+pub(crate) fn with_preferred_language(prompt: &str, language: Option<&str>) -> String {
+    match language {
+        Some(language) => format!("{}\n\n{}\n", prompt.trim_end(), directive(language)),
+        None => prompt.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn prompts_are_unchanged_without_a_language() {
+        assert_eq!(with_preferred_language("Summarize.\n", None), "Summarize.\n");
+    }
+
+    #[test]
+    fn directive_is_appended_to_prompts() {
+        let prompt = with_preferred_language("Summarize.\n", Some("Japanese"));
+
+        assert!(prompt.starts_with("Summarize.\n\nWrite every reply"));
+        assert!(prompt.contains("meant for the user in Japanese"));
+    }
+}
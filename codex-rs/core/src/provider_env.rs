@@ -0,0 +1,233 @@
+//! Environment lookups for provider credentials, backed by an opt-in dotenv
+//! file.
+//!
+//! A dotenv file loaded through `load_dotenv = true` or `--env-file` is parsed
+//! into `Config::dotenv` and never copied into the process environment. Its
+//! values are only visible to lookups that are handed that config's file, so
+//! sessions with different configs never see each other's values, and spawned
+//! commands do not inherit them unless the shell environment policy sets
+//! `inherit_dotenv`.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// The parsed contents of a dotenv file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotenvFile {
+    pub path: PathBuf,
+    vars: HashMap<String, String>,
+}
+
+impl DotenvFile {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("failed to read env file {}: {err}", path.display()),
+            )
+        })?;
+        Self::parse(path.to_path_buf(), &contents)
+    }
+
+    /// Parses `KEY=VALUE` lines. Blank lines, `#` comments and a leading
+    /// `export ` are accepted; values may be single- or double-quoted. When a
+    /// key repeats, the last assignment wins.
+    pub fn parse(path: PathBuf, contents: &str) -> io::Result<Self> {
+        let mut vars = HashMap::new();
+        for (idx, line) in contents.lines().enumerate() {
+            match parse_line(line) {
+                Ok(Some((key, value))) => {
+                    vars.insert(key, value);
+                }
+                Ok(None) => {}
+                Err(reason) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "failed to parse env file {} at line {}: {reason}",
+                            path.display(),
+                            idx + 1
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(Self { path, vars })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    /// Every variable in the file, for shell environment policies that opt
+    /// into `inherit_dotenv`.
+    pub(crate) fn vars(&self) -> impl Iterator<Item = (String, String)> + '_ {
+        self.vars
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+    }
+}
+
+fn parse_line(line: &str) -> Result<Option<(String, String)>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let Some((key, value)) = line.split_once('=') else {
+        return Err("expected KEY=VALUE".to_string());
+    };
+    let key = key.trim();
+    let valid_key = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_key {
+        return Err(format!("invalid variable name `{key}`"));
+    }
+    Ok(Some((key.to_string(), parse_value(value.trim())?)))
+}
+
+fn parse_value(value: &str) -> Result<String, String> {
+    let (parsed, rest) = if let Some(quoted) = value.strip_prefix('"') {
+        let mut parsed = String::new();
+        let mut chars = quoted.char_indices();
+        let mut end = None;
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '"' => {
+                    end = Some(idx + 1);
+                    break;
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => parsed.push('\n'),
+                    Some((_, 't')) => parsed.push('\t'),
+                    Some((_, c)) => parsed.push(c),
+                    None => {}
+                },
+                c => parsed.push(c),
+            }
+        }
+        let Some(end) = end else {
+            return Err("unterminated double quote".to_string());
+        };
+        (parsed, &quoted[end..])
+    } else if let Some(quoted) = value.strip_prefix('\'') {
+        let Some((parsed, rest)) = quoted.split_once('\'') else {
+            return Err("unterminated single quote".to_string());
+        };
+        (parsed.to_string(), rest)
+    } else {
+        // An unquoted `#` only starts a comment after whitespace, so values
+        // such as `abc#123` survive.
+        let end = value
+            .char_indices()
+            .find(|&(idx, c)| c == '#' && value[..idx].ends_with(char::is_whitespace))
+            .map_or(value.len(), |(idx, _)| idx);
+        return Ok(value[..end].trim_end().to_string());
+    };
+    let rest = rest.trim_start();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(parsed)
+    } else {
+        Err(format!("unexpected `{rest}` after quoted value"))
+    }
+}
+
+/// Reads `name` from the process environment, falling back to `dotenv`. A
+/// variable that is set in the real environment always wins, even when it is
+/// empty.
+pub fn var(name: &str, dotenv: Option<&DotenvFile>) -> Option<String> {
+    let real = std::env::var_os(name).map(|value| value.to_string_lossy().into_owned());
+    lookup(name, real, dotenv)
+}
+
+fn lookup(name: &str, real: Option<String>, dotenv: Option<&DotenvFile>) -> Option<String> {
+    real.or_else(|| dotenv.and_then(|file| file.get(name)).map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::ShellEnvironmentPolicy;
+    use crate::exec_env::create_env;
+    use pretty_assertions::assert_eq;
+
+    fn parse(contents: &str) -> io::Result<DotenvFile> {
+        DotenvFile::parse(PathBuf::from("/work/.env"), contents)
+    }
+
+    #[test]
+    fn parses_comments_exports_and_quotes() {
+        let file = parse(
+            "# provider keys\n\
+             export OPENROUTER_API_KEY=sk-or-1 # personal\n\
+             \n\
+             QUOTED=\"two words\\n\" # trailing\n\
+             SINGLE='$literal'\n\
+             HASH=abc#123\n\
+             EMPTY=\n",
+        )
+        .expect("parse");
+
+        assert_eq!(file.get("OPENROUTER_API_KEY"), Some("sk-or-1"));
+        assert_eq!(file.get("QUOTED"), Some("two words\n"));
+        assert_eq!(file.get("SINGLE"), Some("$literal"));
+        assert_eq!(file.get("HASH"), Some("abc#123"));
+        assert_eq!(file.get("EMPTY"), Some(""));
+    }
+
+    #[test]
+    fn parse_errors_name_the_line() {
+        let err = parse("A=1\n\nnot an assignment\n").expect_err("should fail");
+        assert_eq!(
+            err.to_string(),
+            "failed to parse env file /work/.env at line 3: expected KEY=VALUE"
+        );
+
+        let err = parse("B=\"open\n").expect_err("should fail");
+        assert_eq!(
+            err.to_string(),
+            "failed to parse env file /work/.env at line 1: unterminated double quote"
+        );
+    }
+
+    #[test]
+    fn real_environment_wins_over_dotenv() {
+        let file = parse("PROVIDER_KEY=from-dotenv\n").expect("parse");
+        let from =
+            |real: Option<&str>| lookup("PROVIDER_KEY", real.map(str::to_string), Some(&file));
+
+        assert_eq!(from(Some("from-env")), Some("from-env".to_string()));
+        assert_eq!(from(Some("")), Some(String::new()));
+        assert_eq!(from(None), Some("from-dotenv".to_string()));
+        assert_eq!(lookup("OTHER_KEY", None, Some(&file)), None);
+    }
+
+    #[test]
+    fn dotenv_values_are_not_inherited_by_child_processes() {
+        let key = "CODEX_PROVIDER_ENV_TEST_VALUE";
+        let file = parse(&format!("{key}=from-dotenv\nPATH=/dotenv/bin\n")).expect("parse");
+
+        assert_eq!(var(key, Some(&file)), Some("from-dotenv".to_string()));
+        assert_eq!(var(key, None), None);
+        assert_eq!(var("PATH", Some(&file)), std::env::var("PATH").ok());
+        assert_eq!(std::env::var_os(key), None);
+        assert_eq!(
+            create_env(&ShellEnvironmentPolicy::default(), Some(&file)).get(key),
+            None
+        );
+
+        let inheriting = ShellEnvironmentPolicy {
+            inherit_dotenv: true,
+            ..Default::default()
+        };
+        assert_eq!(create_env(&inheriting, None).get(key), None);
+        let env = create_env(&inheriting, Some(&file));
+        assert_eq!(env.get(key).map(String::as_str), Some("from-dotenv"));
+        assert_eq!(env.get("PATH"), std::env::var("PATH").ok().as_ref());
+    }
+}
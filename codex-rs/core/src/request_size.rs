@@ -0,0 +1,114 @@
+//! Pre-flight checks that keep oversized payloads from reaching the provider,
+//! where they fail with an unhelpful 413 or burn image tokens.
+
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ImageDownscaledEvent;
+use codex_protocol::protocol::WarningEvent;
+use codex_protocol::user_input::UserInput;
+use codex_utils_image::ImageLimits;
+use codex_utils_image::load_and_resize_with_limits;
+
+use crate::error::CodexErr;
+use crate::error::RequestTooLargeError;
+
+/// Approximate size of the JSON body carrying `items` and `instructions`.
+/// Tool definitions and request metadata are small enough to ignore.
+pub(crate) fn estimate_request_bytes<'a>(
+    items: impl IntoIterator<Item = &'a ResponseItem>,
+    instructions: &str,
+) -> u64 {
+    let items_bytes: usize = items
+        .into_iter()
+        .map(|item| serde_json::to_vec(item).map_or(0, |bytes| bytes.len()))
+        .sum();
+    (items_bytes + instructions.len()) as u64
+}
+
+pub(crate) fn ensure_request_fits(estimated_bytes: u64, max_bytes: u64) -> Result<(), CodexErr> {
+    if estimated_bytes > max_bytes {
+        return Err(CodexErr::RequestTooLarge(RequestTooLargeError {
+            estimated_bytes,
+            max_bytes,
+        }));
+    }
+    Ok(())
+}
+
+/// Events telling the user how each local image in `input` will be sent:
+/// an [`ImageDownscaledEvent`] when it is shrunk, or a warning when it
+/// cannot be attached at all. Processed images are cached, so converting the
+/// input afterwards does not decode them again.
+pub(crate) fn local_image_events(input: &[UserInput], limits: ImageLimits) -> Vec<EventMsg> {
+    input
+        .iter()
+        .filter_map(|item| match item {
+            UserInput::LocalImage { path } => Some(path),
+            _ => None,
+        })
+        .filter_map(|path| match load_and_resize_with_limits(path, limits) {
+            Ok(image) if image.was_downscaled() => {
+                Some(EventMsg::ImageDownscaled(ImageDownscaledEvent {
+                    path: path.clone(),
+                    original_width: image.original_width,
+                    original_height: image.original_height,
+                    original_bytes: image.original_bytes as u64,
+                    sent_width: image.width,
+                    sent_height: image.height,
+                    sent_bytes: image.bytes.len() as u64,
+                }))
+            }
+            Ok(_) => None,
+            Err(err) => Some(EventMsg::Warning(WarningEvent {
+                message: format!("Could not attach {}: {err}", path.display()),
+            })),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn rejects_requests_over_the_limit() {
+        let item = ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "x".repeat(1024),
+            }],
+            end_turn: None,
+        };
+        let estimated = estimate_request_bytes([&item], "instructions");
+        assert!(estimated > 1024);
+
+        assert!(ensure_request_fits(estimated, estimated).is_ok());
+        let err = ensure_request_fits(estimated, 1024).expect_err("over the limit");
+        assert!(matches!(
+            err,
+            CodexErr::RequestTooLarge(RequestTooLargeError {
+                max_bytes: 1024,
+                ..
+            })
+        ));
+        assert!(err.to_string().contains("max_request_body_bytes"));
+    }
+
+    #[test]
+    fn warns_about_images_that_cannot_be_attached() {
+        let input = vec![UserInput::LocalImage {
+            path: "/definitely/not/here.png".into(),
+        }];
+
+        let events = local_image_events(&input, ImageLimits::default());
+
+        assert_eq!(events.len(), 1);
+        let EventMsg::Warning(WarningEvent { message }) = &events[0] else {
+            panic!("expected a warning, got {:?}", events[0]);
+        };
+        assert!(message.starts_with("Could not attach /definitely/not/here.png"));
+    }
+}
@@ -3,6 +3,8 @@ use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
 use std::path::Path;
 
+use crate::util::truncate_graphemes;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ResolvedReviewRequest {
     pub target: ReviewTarget,
@@ -72,7 +74,7 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
         ReviewTarget::UncommittedChanges => "current changes".to_string(),
         ReviewTarget::BaseBranch { branch } => format!("changes against '{branch}'"),
         ReviewTarget::Commit { sha, title } => {
-            let short_sha: String = sha.chars().take(7).collect();
+            let short_sha = truncate_graphemes(sha, 7);
             if let Some(title) = title {
                 format!("commit {short_sha}: {title}")
             } else {
@@ -0,0 +1,146 @@
+//! Versioning of the rollout file format.
+//!
+//! The session meta record on the first line of a rollout carries a `format`
+//! stamp with the format version and the Codex version that wrote the file.
+//! Rollouts written before the stamp existed are format 0.
+//!
+//! Adding record types or fields does not need a new version: readers skip
+//! records whose `type` they do not know, ignore fields they do not know, and
+//! header rewrites keep both. Bump [`ROLLOUT_FORMAT_VERSION`] only when
+//! existing records change shape or meaning, so that older builds refuse the
+//! file instead of misreading it.
+
+use std::io;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Format written by this build.
+pub(crate) const ROLLOUT_FORMAT_VERSION: u32 = 1;
+
+/// Oldest format this build can still resume.
+pub(crate) const OLDEST_READABLE_FORMAT_VERSION: u32 = 0;
+
+/// Record types this build understands. Keep in sync with `RolloutItem`.
+const KNOWN_RECORD_TYPES: &[&str] = &[
+    "session_meta",
+    "response_item",
+    "compacted",
+    "turn_context",
+    "event_msg",
+    "approval_grant",
+    "file_upload",
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RolloutFormat {
+    pub(crate) version: u32,
+    /// Version of the Codex build that wrote the rollout.
+    pub(crate) writer: String,
+}
+
+impl RolloutFormat {
+    pub(crate) fn current() -> Self {
+        Self {
+            version: ROLLOUT_FORMAT_VERSION,
+            writer: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Reads the stamp from the first line of a rollout. Unstamped headers
+    /// are format 0, attributed to the `cli_version` in their session meta.
+    pub(crate) fn from_header(header: &Value) -> io::Result<Self> {
+        if let Some(format) = header.get("format") {
+            return serde_json::from_value(format.clone()).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unrecognized rollout format stamp {format}: {err}"),
+                )
+            });
+        }
+        let writer = header
+            .get("payload")
+            .and_then(|payload| payload.get("cli_version"))
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string();
+        Ok(Self { version: 0, writer })
+    }
+
+    /// Fails with a message naming the writer when this build cannot read
+    /// the rollout.
+    pub(crate) fn ensure_readable(&self) -> io::Result<()> {
+        let Self { version, writer } = self;
+        let message = if *version > ROLLOUT_FORMAT_VERSION {
+            format!(
+                "session was written by codex {writer} (rollout format {version}); upgrade to resume it"
+            )
+        } else if *version < OLDEST_READABLE_FORMAT_VERSION {
+            format!(
+                "session was written by codex {writer} (rollout format {version}), which this version can no longer resume"
+            )
+        } else {
+            return Ok(());
+        };
+        Err(io::Error::new(io::ErrorKind::Unsupported, message))
+    }
+}
+
+/// Whether `line` is a record whose `type` this build does not know, which
+/// readers skip rather than report as a parse error.
+pub(crate) fn is_unknown_record(line: &Value) -> bool {
+    line.get("type")
+        .and_then(Value::as_str)
+        .is_some_and(|record_type| !KNOWN_RECORD_TYPES.contains(&record_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn unstamped_headers_are_format_zero() {
+        let header = json!({
+            "timestamp": "2025-06-01T12:00:00.000Z",
+            "type": "session_meta",
+            "payload": {"cli_version": "0.20.0"}
+        });
+
+        let format = RolloutFormat::from_header(&header).expect("format");
+
+        assert_eq!(
+            format,
+            RolloutFormat {
+                version: 0,
+                writer: "0.20.0".to_string()
+            }
+        );
+        assert!(format.ensure_readable().is_ok());
+    }
+
+    #[test]
+    fn newer_formats_name_the_writer() {
+        let header = json!({"format": {"version": 9, "writer": "0.9.0"}});
+
+        let err = RolloutFormat::from_header(&header)
+            .expect("format")
+            .ensure_readable()
+            .expect_err("format 9 is not readable");
+
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        assert_eq!(
+            err.to_string(),
+            "session was written by codex 0.9.0 (rollout format 9); upgrade to resume it"
+        );
+    }
+
+    #[test]
+    fn only_unknown_record_types_are_skipped() {
+        assert!(is_unknown_record(&json!({"type": "bookmark"})));
+        assert!(!is_unknown_record(&json!({"type": "event_msg"})));
+        assert!(!is_unknown_record(&json!({"record_type": "state"})));
+    }
+}
@@ -61,7 +61,9 @@ pub(crate) fn builder_from_items(
         RolloutItem::ResponseItem(_)
         | RolloutItem::Compacted(_)
         | RolloutItem::TurnContext(_)
-        | RolloutItem::EventMsg(_) => None,
+        | RolloutItem::EventMsg(_)
+        | RolloutItem::ApprovalGrant(_)
+        | RolloutItem::FileUpload(_) => None,
     }) && let Some(builder) = builder_from_session_meta(session_meta, rollout_path)
     {
         return Some(builder);
@@ -314,6 +316,9 @@ mod tests {
             cli_version: "0.0.0".to_string(),
             source: SessionSource::default(),
             model_provider: Some("openai".to_string()),
+            title: None,
+            tags: Vec::new(),
+            sampling: None,
             base_instructions: None,
             dynamic_tools: None,
         };
@@ -8,6 +8,7 @@ pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
     &[SessionSource::Cli, SessionSource::VSCode];
 
 pub(crate) mod error;
+pub(crate) mod format;
 pub mod list;
 pub(crate) mod metadata;
 pub(crate) mod policy;
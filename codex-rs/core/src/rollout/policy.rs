@@ -9,9 +9,12 @@ pub(crate) fn is_persisted_response_item(item: &RolloutItem) -> bool {
         RolloutItem::ResponseItem(item) => should_persist_response_item(item),
         RolloutItem::EventMsg(ev) => should_persist_event_msg(ev),
         // Persist Codex executive markers so we can analyze flows (e.g., compaction, API turns).
-        RolloutItem::Compacted(_) | RolloutItem::TurnContext(_) | RolloutItem::SessionMeta(_) => {
-            true
-        }
+        // Approval grants and file uploads are replayed on resume.
+        RolloutItem::Compacted(_)
+        | RolloutItem::TurnContext(_)
+        | RolloutItem::SessionMeta(_)
+        | RolloutItem::ApprovalGrant(_)
+        | RolloutItem::FileUpload(_) => true,
     }
 }
 
@@ -28,8 +31,8 @@ pub(crate) fn should_persist_response_item(item: &ResponseItem) -> bool {
         | ResponseItem::CustomToolCallOutput { .. }
         | ResponseItem::WebSearchCall { .. }
         | ResponseItem::GhostSnapshot { .. }
-        | ResponseItem::Compaction { .. } => true,
-        ResponseItem::Other => false,
+        | ResponseItem::Compaction { .. }
+        | ResponseItem::Other(_) => true,
     }
 }
 
@@ -46,8 +49,15 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::EnteredReviewMode(_)
         | EventMsg::ExitedReviewMode(_)
         | EventMsg::ThreadRolledBack(_)
+        | EventMsg::SessionApprovalsRevoked(_)
+        | EventMsg::ApprovalResolved(_)
         | EventMsg::UndoCompleted(_)
+        | EventMsg::CommitCompleted(_)
+        | EventMsg::TurnRefused(_)
         | EventMsg::TurnAborted(_) => true,
+        // A deferred steer starts a turn whose user message is persisted
+        // already; one delivered mid-turn has no other event to replay.
+        EventMsg::SteerDelivered(event) => !event.deferred,
         EventMsg::ItemCompleted(event) => {
             // Plan items are derived from streaming tags and are not part of the
             // raw ResponseItem history, so we persist their completion to replay
@@ -79,20 +89,44 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::ElicitationRequest(_)
         | EventMsg::ApplyPatchApprovalRequest(_)
         | EventMsg::BackgroundEvent(_)
+        | EventMsg::HookBegin(_)
+        | EventMsg::HookEnd(_)
         | EventMsg::StreamError(_)
         | EventMsg::PatchApplyBegin(_)
         | EventMsg::PatchApplyEnd(_)
         | EventMsg::TurnDiff(_)
+        | EventMsg::TurnFiles(_)
+        | EventMsg::InjectionSuspected(_)
+        | EventMsg::ToolArgumentsRepaired(_)
+        | EventMsg::StalePatchContext(_)
+        | EventMsg::StalePatchRetry(_)
+        | EventMsg::ExternalFilesChanged(_)
+        | EventMsg::OutputTruncated(_)
         | EventMsg::GetHistoryEntryResponse(_)
+        | EventMsg::HistoryCleared(_)
         | EventMsg::UndoStarted(_)
+        | EventMsg::CommitDraft(_)
+        | EventMsg::CommandExplanation(_)
+        | EventMsg::WorkspaceSummary(_)
         | EventMsg::McpListToolsResponse(_)
+        | EventMsg::ListToolsResponse(_)
+        | EventMsg::TokenBreakdown(_)
+        | EventMsg::ApprovalGrantsRestored(_)
         | EventMsg::McpStartupUpdate(_)
         | EventMsg::McpStartupComplete(_)
+        | EventMsg::SessionPrewarmed(_)
+        | EventMsg::ExecCommandSpawn(_)
         | EventMsg::ListCustomPromptsResponse(_)
+        | EventMsg::GhostSnapshotsResponse(_)
+        | EventMsg::GhostSnapshotFileResponse(_)
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::PlanUpdate(_)
         | EventMsg::ShutdownComplete
         | EventMsg::ViewImageToolCall(_)
+        | EventMsg::ImageDownscaled(_)
+        | EventMsg::FileAttached(_)
+        | EventMsg::PromptQueuedOffline(_)
+        | EventMsg::ConnectivityRestored(_)
         | EventMsg::DeprecationNotice(_)
         | EventMsg::ItemStarted(_)
         | EventMsg::AgentMessageContentDelta(_)
@@ -22,6 +22,8 @@ use tracing::warn;
 
 use super::ARCHIVED_SESSIONS_SUBDIR;
 use super::SESSIONS_SUBDIR;
+use super::format::RolloutFormat;
+use super::format::is_unknown_record;
 use super::list::Cursor;
 use super::list::ThreadListConfig;
 use super::list::ThreadListLayout;
@@ -70,6 +72,8 @@ pub enum RolloutRecorderParams {
         source: SessionSource,
         base_instructions: BaseInstructions,
         dynamic_tools: Vec<DynamicToolSpec>,
+        title: Option<String>,
+        tags: Vec<String>,
     },
     Resume {
         path: PathBuf,
@@ -82,6 +86,11 @@ enum RolloutCmd {
     Flush {
         ack: oneshot::Sender<()>,
     },
+    /// Rewrite the title stored in the session meta header record.
+    SetTitle {
+        title: String,
+        ack: oneshot::Sender<std::io::Result<()>>,
+    },
     Shutdown {
         ack: oneshot::Sender<()>,
     },
@@ -101,9 +110,25 @@ impl RolloutRecorderParams {
             source,
             base_instructions,
             dynamic_tools,
+            title: None,
+            tags: Vec::new(),
         }
     }
 
+    /// Attach a title and tags to the session meta header of a new rollout.
+    /// Has no effect when resuming.
+    pub fn with_session_metadata(
+        mut self,
+        session_title: Option<String>,
+        session_tags: Vec<String>,
+    ) -> Self {
+        if let Self::Create { title, tags, .. } = &mut self {
+            *title = session_title;
+            *tags = session_tags;
+        }
+        self
+    }
+
     pub fn resume(path: PathBuf) -> Self {
         Self::Resume { path }
     }
@@ -264,6 +289,8 @@ impl RolloutRecorder {
                 source,
                 base_instructions,
                 dynamic_tools,
+                title,
+                tags,
             } => {
                 let LogFileInfo {
                     file,
@@ -292,6 +319,10 @@ impl RolloutRecorder {
                         cli_version: env!("CARGO_PKG_VERSION").to_string(),
                         source,
                         model_provider: Some(config.model_provider_id.clone()),
+                        title,
+                        tags,
+                        sampling: (!config.model_sampling.is_empty())
+                            .then_some(config.model_sampling),
                         base_instructions: Some(base_instructions),
                         dynamic_tools: if dynamic_tools.is_empty() {
                             None
@@ -367,6 +398,19 @@ impl RolloutRecorder {
             .map_err(|e| IoError::other(format!("failed to queue rollout items: {e}")))
     }
 
+    /// Replace the session title stored in the rollout header. Only the header
+    /// record is rewritten; the rest of the file is left untouched whenever the
+    /// new header fits in the space reserved for it.
+    pub(crate) async fn set_title(&self, title: String) -> std::io::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.tx
+            .send(RolloutCmd::SetTitle { title, ack: tx })
+            .await
+            .map_err(|e| IoError::other(format!("failed to queue rollout title update: {e}")))?;
+        rx.await
+            .map_err(|e| IoError::other(format!("failed waiting for rollout title update: {e}")))?
+    }
+
     /// Flush all queued writes and wait until they are committed by the writer task.
     pub async fn flush(&self) -> std::io::Result<()> {
         let (tx, rx) = oneshot::channel();
@@ -390,6 +434,8 @@ impl RolloutRecorder {
         let mut items: Vec<RolloutItem> = Vec::new();
         let mut thread_id: Option<ThreadId> = None;
         let mut parse_errors = 0usize;
+        let mut unknown_records = 0usize;
+        let mut checked_format = false;
         for line in text.lines() {
             if line.trim().is_empty() {
                 continue;
@@ -402,6 +448,15 @@ impl RolloutRecorder {
                     continue;
                 }
             };
+            if !checked_format {
+                // Refuse rollouts this build cannot read before misparsing them.
+                RolloutFormat::from_header(&v)?.ensure_readable()?;
+                checked_format = true;
+            }
+            if is_unknown_record(&v) {
+                unknown_records = unknown_records.saturating_add(1);
+                continue;
+            }
 
             // Parse the rollout line structure
             match serde_json::from_value::<RolloutLine>(v.clone()) {
@@ -426,6 +481,12 @@ impl RolloutRecorder {
                     RolloutItem::EventMsg(_ev) => {
                         items.push(RolloutItem::EventMsg(_ev));
                     }
+                    RolloutItem::ApprovalGrant(item) => {
+                        items.push(RolloutItem::ApprovalGrant(item));
+                    }
+                    RolloutItem::FileUpload(item) => {
+                        items.push(RolloutItem::FileUpload(item));
+                    }
                 },
                 Err(e) => {
                     warn!("failed to parse rollout line: {e}");
@@ -435,10 +496,11 @@ impl RolloutRecorder {
         }
 
         tracing::debug!(
-            "Resumed rollout with {} items, thread ID: {:?}, parse errors: {}",
+            "Resumed rollout with {} items, thread ID: {:?}, parse errors: {}, unknown records: {}",
             items.len(),
             thread_id,
             parse_errors,
+            unknown_records,
         );
         Ok((items, thread_id, parse_errors))
     }
@@ -553,9 +615,10 @@ async fn rollout_writer(
                 metadata::builder_from_session_meta(&session_meta_line, rollout_path.as_path());
         }
 
-        // Write the SessionMeta as the first item in the file, wrapped in a rollout line
+        // Write the SessionMeta as the first item in the file, wrapped in a rollout line.
+        // Trailing padding leaves room to edit the title in place later on.
         let rollout_item = RolloutItem::SessionMeta(session_meta_line);
-        writer.write_rollout_item(&rollout_item).await?;
+        writer.write_header_item(&rollout_item).await?;
         state_db::reconcile_rollout(
             state_db_ctx.as_deref(),
             rollout_path.as_path(),
@@ -564,6 +627,11 @@ async fn rollout_writer(
             std::slice::from_ref(&rollout_item),
         )
         .await;
+    } else if let Err(err) = writer.upgrade_header_format(rollout_path.as_path()).await {
+        warn!(
+            "failed to stamp rollout format on {}: {err}",
+            rollout_path.display()
+        );
     }
 
     // Process rollout commands
@@ -601,6 +669,23 @@ async fn rollout_writer(
                 }
                 let _ = ack.send(());
             }
+            RolloutCmd::SetTitle { title, ack } => {
+                let result = writer
+                    .set_header_title(rollout_path.as_path(), &title)
+                    .await;
+                if let Ok(item) = &result {
+                    state_db::apply_rollout_items(
+                        state_db_ctx.as_deref(),
+                        rollout_path.as_path(),
+                        default_provider.as_str(),
+                        state_builder.as_ref(),
+                        std::slice::from_ref(item),
+                        "rollout_writer",
+                    )
+                    .await;
+                }
+                let _ = ack.send(result.map(|_| ()));
+            }
             RolloutCmd::Shutdown { ack } => {
                 let _ = ack.send(());
             }
@@ -610,6 +695,68 @@ async fn rollout_writer(
     Ok(())
 }
 
+/// Bytes of trailing whitespace reserved after the session meta header so the
+/// title can later be changed without rewriting the rest of the rollout.
+const HEADER_RESERVED_BYTES: usize = 256;
+
+enum HeaderRewrite {
+    /// The header was overwritten in place.
+    InPlace(RolloutItem),
+    /// The header no longer fit, so the whole file was rewritten.
+    FullFile(RolloutItem),
+}
+
+/// Apply `edit` to the session meta record on the first line of `path`.
+///
+/// The header is edited as raw JSON so fields and stamps this build does not
+/// know about survive the rewrite.
+async fn rewrite_header(
+    path: &Path,
+    edit: impl FnOnce(&mut Value),
+) -> std::io::Result<HeaderRewrite> {
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncSeekExt;
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .await?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents).await?;
+    let header_len = contents
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .unwrap_or(contents.len());
+
+    let mut header: Value = serde_json::from_slice(&contents[..header_len])?;
+    if header.get("type").and_then(Value::as_str) != Some("session_meta") {
+        return Err(IoError::other(format!(
+            "rollout at {} does not start with session metadata",
+            path.display()
+        )));
+    }
+    RolloutFormat::from_header(&header)?.ensure_readable()?;
+    edit(&mut header);
+    let item = serde_json::from_value::<RolloutLine>(header.clone())?.item;
+    let mut line = serde_json::to_vec(&header)?;
+
+    if line.len() <= header_len {
+        line.resize(header_len, b' ');
+        file.seek(std::io::SeekFrom::Start(0)).await?;
+        file.write_all(&line).await?;
+        file.flush().await?;
+        return Ok(HeaderRewrite::InPlace(item));
+    }
+
+    line.resize(line.len() + HEADER_RESERVED_BYTES, b' ');
+    line.extend_from_slice(&contents[header_len..]);
+    let tmp_path = path.with_extension("jsonl.tmp");
+    tokio::fs::write(&tmp_path, &line).await?;
+    tokio::fs::rename(&tmp_path, path).await?;
+    Ok(HeaderRewrite::FullFile(item))
+}
+
 struct JsonlWriter {
     file: tokio::fs::File,
 }
@@ -617,12 +764,33 @@ struct JsonlWriter {
 #[derive(serde::Serialize)]
 struct RolloutLineRef<'a> {
     timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<RolloutFormat>,
     #[serde(flatten)]
     item: &'a RolloutItem,
 }
 
 impl JsonlWriter {
     async fn write_rollout_item(&mut self, rollout_item: &RolloutItem) -> std::io::Result<()> {
+        self.write_padded_rollout_item(rollout_item, None, 0).await
+    }
+
+    /// Write the header record, stamped with the current rollout format.
+    async fn write_header_item(&mut self, rollout_item: &RolloutItem) -> std::io::Result<()> {
+        self.write_padded_rollout_item(
+            rollout_item,
+            Some(RolloutFormat::current()),
+            HEADER_RESERVED_BYTES,
+        )
+        .await
+    }
+
+    async fn write_padded_rollout_item(
+        &mut self,
+        rollout_item: &RolloutItem,
+        format: Option<RolloutFormat>,
+        padding: usize,
+    ) -> std::io::Result<()> {
         let timestamp_format: &[FormatItem] = format_description!(
             "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z"
         );
@@ -632,12 +800,63 @@ impl JsonlWriter {
 
         let line = RolloutLineRef {
             timestamp,
+            format,
             item: rollout_item,
         };
-        self.write_line(&line).await
+        self.write_line(&line, padding).await
+    }
+
+    /// Update the title in the header record and return the new header item.
+    async fn set_header_title(&mut self, path: &Path, title: &str) -> std::io::Result<RolloutItem> {
+        self.rewrite_header(path, |header| {
+            if let Some(payload) = header.get_mut("payload").and_then(Value::as_object_mut) {
+                payload.insert("title".to_string(), Value::String(title.to_string()));
+            }
+        })
+        .await
     }
-    async fn write_line(&mut self, item: &impl serde::Serialize) -> std::io::Result<()> {
+
+    /// Stamp a resumed rollout with the current format before appending to
+    /// it, so older builds do not misread the records this one adds.
+    async fn upgrade_header_format(&mut self, path: &Path) -> std::io::Result<()> {
+        let current = RolloutFormat::current();
+        self.rewrite_header(path, |header| {
+            if let (Some(header), Ok(format)) =
+                (header.as_object_mut(), serde_json::to_value(&current))
+            {
+                header.insert("format".to_string(), format);
+            }
+        })
+        .await
+        .map(|_| ())
+    }
+
+    async fn rewrite_header(
+        &mut self,
+        path: &Path,
+        edit: impl FnOnce(&mut Value),
+    ) -> std::io::Result<RolloutItem> {
+        self.file.flush().await?;
+        match rewrite_header(path, edit).await? {
+            HeaderRewrite::InPlace(item) => Ok(item),
+            HeaderRewrite::FullFile(item) => {
+                // The file was replaced, so reopen the append handle on the new inode.
+                self.file = tokio::fs::OpenOptions::new()
+                    .append(true)
+                    .open(path)
+                    .await?;
+                Ok(item)
+            }
+        }
+    }
+
+    async fn write_line(
+        &mut self,
+        item: &impl serde::Serialize,
+        padding: usize,
+    ) -> std::io::Result<()> {
         let mut json = serde_json::to_string(item)?;
+        json.extend(std::iter::repeat_n(' ', padding));
         json.push('\n');
         self.file.write_all(json.as_bytes()).await?;
         self.file.flush().await?;
@@ -17,6 +17,7 @@ use time::macros::format_description;
 use uuid::Uuid;
 
 use crate::rollout::INTERACTIVE_SESSION_SOURCES;
+use crate::rollout::RolloutRecorder;
 use crate::rollout::list::Cursor;
 use crate::rollout::list::ThreadItem;
 use crate::rollout::list::ThreadSortKey;
@@ -28,6 +29,7 @@ use codex_protocol::ThreadId;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::RolloutLine;
 use codex_protocol::protocol::SessionMeta;
@@ -714,6 +716,45 @@ async fn test_get_thread_contents() {
     assert_eq!(content, expected_content);
 }
 
+#[tokio::test]
+async fn resume_keeps_unknown_response_items() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+    let uuid = Uuid::new_v4();
+    let ts = "2025-04-01T10-30-00";
+    write_session_file(home, ts, uuid, 0, Some(SessionSource::Cli)).unwrap();
+    let path = home
+        .join("sessions")
+        .join("2025")
+        .join("04")
+        .join("01")
+        .join(format!("rollout-{ts}-{uuid}.jsonl"));
+
+    // A line written by a newer Codex that knows an item type this one does not.
+    let future_item = serde_json::json!({
+        "type": "future_widget",
+        "id": "fw_1",
+        "payload": {"nested": [1, 2, 3]}
+    });
+    let line = serde_json::json!({
+        "timestamp": ts,
+        "type": "response_item",
+        "payload": future_item,
+    });
+    let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+    writeln!(file, "{line}").unwrap();
+
+    let InitialHistory::Resumed(resumed) =
+        RolloutRecorder::get_rollout_history(&path).await.unwrap()
+    else {
+        panic!("expected resumed history");
+    };
+    assert_eq!(
+        resumed.history.last(),
+        Some(&RolloutItem::ResponseItem(ResponseItem::Other(future_item)))
+    );
+}
+
 #[tokio::test]
 async fn test_base_instructions_missing_in_meta_defaults_to_null() {
     let temp = TempDir::new().unwrap();
@@ -872,6 +913,9 @@ async fn test_updated_at_uses_file_mtime() -> Result<()> {
                 cli_version: "test_version".into(),
                 source: SessionSource::VSCode,
                 model_provider: Some("test-provider".into()),
+                title: None,
+                tags: Vec::new(),
+                sampling: None,
                 base_instructions: None,
                 dynamic_tools: None,
             },
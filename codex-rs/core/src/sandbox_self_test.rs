@@ -0,0 +1,494 @@
+//! Checks that the platform sandbox actually enforces what the sandbox
+//! policies promise on this machine.
+//!
+//! Old Linux kernels without Landlock or hardened macOS setups can leave the
+//! sandbox half working without any error. The self-test runs a few probe
+//! commands under a workspace-write policy and records which restrictions
+//! held. Results are cached in `codex_home` together with the OS and kernel
+//! version, so the test runs again after an upgrade.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::TcpListener;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_protocol::config_types::WindowsSandboxLevel;
+use codex_protocol::protocol::SandboxPolicy;
+use serde::Deserialize;
+use serde::Serialize;
+use tempfile::TempDir;
+
+use crate::config::Config;
+use crate::config::types::ShellEnvironmentPolicy;
+use crate::exec::ExecExpiration;
+use crate::exec::ExecParams;
+use crate::exec::SandboxType;
+use crate::exec::process_exec_tool_call;
+use crate::exec_env::create_env;
+use crate::exec_limits::ExecLimits;
+use crate::safety::get_platform_sandbox;
+use crate::sandboxing::SandboxPermissions;
+use crate::windows_sandbox::WindowsSandboxLevelExt;
+
+const REPORT_FILENAME: &str = "sandbox_self_test.json";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SandboxCapability {
+    /// Writing outside the writable roots is denied.
+    OutOfRootWrite,
+    /// Outbound network connections are denied.
+    Network,
+    /// Writing to a protected folder such as `.git` inside a writable root is
+    /// denied. Sandbox policies never restrict reads, so this is the only
+    /// denial that applies inside the workspace.
+    ProtectedPath,
+}
+
+impl SandboxCapability {
+    pub const ALL: [SandboxCapability; 3] = [
+        SandboxCapability::OutOfRootWrite,
+        SandboxCapability::Network,
+        SandboxCapability::ProtectedPath,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SandboxCapability::OutOfRootWrite => "write outside workspace",
+            SandboxCapability::Network => "network access",
+            SandboxCapability::ProtectedPath => "write to .git",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ProbeOutcome {
+    /// The probe was blocked under the sandbox.
+    Enforced,
+    /// The probe succeeded under the sandbox.
+    NotEnforced,
+    /// The probe could not tell, e.g. because the probe command does not work
+    /// on this machine even without the sandbox.
+    Inconclusive { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProbeResult {
+    pub capability: SandboxCapability,
+    #[serde(flatten)]
+    pub outcome: ProbeOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxSelfTestReport {
+    /// OS and kernel version the report was produced on.
+    pub platform: String,
+    /// Sandbox detected by `get_platform_sandbox`.
+    pub sandbox: String,
+    pub results: Vec<ProbeResult>,
+}
+
+impl SandboxSelfTestReport {
+    /// Restrictions the sandbox failed to enforce.
+    pub fn unenforced(&self) -> Vec<SandboxCapability> {
+        self.results
+            .iter()
+            .filter(|result| result.outcome == ProbeOutcome::NotEnforced)
+            .map(|result| result.capability)
+            .collect()
+    }
+
+    /// Warning to show for sessions that rely on the sandbox, if any
+    /// restriction is not enforced.
+    pub fn warning(&self) -> Option<String> {
+        let unenforced = self.unenforced();
+        if unenforced.is_empty() {
+            return None;
+        }
+        let labels: Vec<&str> = unenforced.iter().map(|c| c.label()).collect();
+        Some(format!(
+            "The sandbox ({}) does not block {} on this machine, so read-only and \
+             workspace-write commands are not fully contained. Run `codex debug \
+             sandbox-test` for details.",
+            self.sandbox,
+            labels.join(", ")
+        ))
+    }
+}
+
+/// OS, OS version and, on Linux, kernel release. A cached report is reused
+/// only while this stays the same.
+pub fn platform_fingerprint() -> String {
+    let info = os_info::get();
+    let mut fingerprint = format!(
+        "{} {} {}",
+        info.os_type(),
+        info.version(),
+        std::env::consts::ARCH
+    );
+    if cfg!(target_os = "linux")
+        && let Ok(release) = std::fs::read_to_string("/proc/sys/kernel/osrelease")
+    {
+        fingerprint.push_str(&format!(" (kernel {})", release.trim()));
+    }
+    fingerprint
+}
+
+fn report_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(REPORT_FILENAME)
+}
+
+/// The cached report for this machine, unless it is missing, unreadable or
+/// was produced on a different OS or kernel version.
+pub fn load_cached_report(codex_home: &Path) -> Option<SandboxSelfTestReport> {
+    let contents = std::fs::read_to_string(report_path(codex_home)).ok()?;
+    let report: SandboxSelfTestReport = serde_json::from_str(&contents).ok()?;
+    (report.platform == platform_fingerprint()).then_some(report)
+}
+
+pub fn save_report(codex_home: &Path, report: &SandboxSelfTestReport) -> io::Result<()> {
+    let contents = serde_json::to_string_pretty(report).map_err(io::Error::other)?;
+    std::fs::create_dir_all(codex_home)?;
+    std::fs::write(report_path(codex_home), contents)
+}
+
+/// Runs the self-test when `config` relies on the sandbox and this machine
+/// has no up-to-date cached report. `announce` is called before the probes
+/// start. Returns the new report, if one was produced.
+pub async fn run_if_stale(
+    config: &Config,
+    announce: impl FnOnce(),
+) -> Option<SandboxSelfTestReport> {
+    if config.sandbox_policy.get().has_full_disk_write_access()
+        || load_cached_report(&config.codex_home).is_some()
+    {
+        return None;
+    }
+    announce();
+    let report = run_sandbox_self_test(
+        &config.codex_linux_sandbox_exe,
+        WindowsSandboxLevel::from_config(config),
+    )
+    .await;
+    if let Err(err) = save_report(&config.codex_home, &report) {
+        tracing::warn!("failed to cache sandbox self-test report: {err}");
+    }
+    Some(report)
+}
+
+/// Probes each [`SandboxCapability`] under a workspace-write policy. Every
+/// probe is first run without the sandbox to make sure it can succeed at all.
+pub async fn run_sandbox_self_test(
+    codex_linux_sandbox_exe: &Option<PathBuf>,
+    windows_sandbox_level: WindowsSandboxLevel,
+) -> SandboxSelfTestReport {
+    let sandbox_type = get_platform_sandbox(windows_sandbox_level != WindowsSandboxLevel::Disabled);
+    let sandbox = describe_sandbox(sandbox_type);
+    let results = match SelfTest::new(codex_linux_sandbox_exe.clone(), windows_sandbox_level) {
+        Ok(test) => test.run().await,
+        Err(err) => SandboxCapability::ALL
+            .into_iter()
+            .map(|capability| ProbeResult {
+                capability,
+                outcome: ProbeOutcome::Inconclusive {
+                    reason: format!("could not prepare the probe directories: {err}"),
+                },
+            })
+            .collect(),
+    };
+    SandboxSelfTestReport {
+        platform: platform_fingerprint(),
+        sandbox,
+        results,
+    }
+}
+
+fn describe_sandbox(sandbox_type: Option<SandboxType>) -> String {
+    match sandbox_type {
+        Some(SandboxType::MacosSeatbelt) => "seatbelt",
+        Some(SandboxType::LinuxSeccomp) => "landlock+seccomp",
+        Some(SandboxType::WindowsRestrictedToken) => "windows restricted token",
+        Some(SandboxType::None) | None => "none",
+    }
+    .to_string()
+}
+
+struct SelfTest {
+    /// The only writable root of the sandbox policy.
+    workspace: TempDir,
+    /// A directory outside every writable root.
+    outside: TempDir,
+    listener: TcpListener,
+    policy: SandboxPolicy,
+    env: HashMap<String, String>,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+    windows_sandbox_level: WindowsSandboxLevel,
+}
+
+impl SelfTest {
+    fn new(
+        codex_linux_sandbox_exe: Option<PathBuf>,
+        windows_sandbox_level: WindowsSandboxLevel,
+    ) -> io::Result<Self> {
+        let workspace = TempDir::new()?;
+        std::fs::create_dir(workspace.path().join(".git"))?;
+        let outside = TempDir::new()?;
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            workspace,
+            outside,
+            listener,
+            // Both temp directories may live under TMPDIR or /tmp, so neither
+            // can be writable by default.
+            policy: SandboxPolicy::WorkspaceWrite {
+                writable_roots: Vec::new(),
+                network_access: false,
+                exclude_tmpdir_env_var: true,
+                exclude_slash_tmp: true,
+            },
+            env: create_env(&ShellEnvironmentPolicy::default(), None),
+            codex_linux_sandbox_exe,
+            windows_sandbox_level,
+        })
+    }
+
+    async fn run(&self) -> Vec<ProbeResult> {
+        // A write inside the workspace must work under the sandbox, otherwise
+        // every denial below would only mean that nothing runs at all.
+        let canary = self.workspace.path().join("canary");
+        if let Err(reason) = self.try_write(&canary, &self.policy).await {
+            return SandboxCapability::ALL
+                .into_iter()
+                .map(|capability| ProbeResult {
+                    capability,
+                    outcome: ProbeOutcome::Inconclusive {
+                        reason: format!("commands do not run under the sandbox: {reason}"),
+                    },
+                })
+                .collect();
+        }
+
+        let mut results = Vec::new();
+        for capability in SandboxCapability::ALL {
+            let outcome = if let Err(reason) = self
+                .attempt(capability, &SandboxPolicy::DangerFullAccess)
+                .await
+            {
+                ProbeOutcome::Inconclusive {
+                    reason: format!("the probe fails even without the sandbox: {reason}"),
+                }
+            } else if self.attempt(capability, &self.policy).await.is_ok() {
+                ProbeOutcome::NotEnforced
+            } else {
+                ProbeOutcome::Enforced
+            };
+            results.push(ProbeResult {
+                capability,
+                outcome,
+            });
+        }
+        results
+    }
+
+    /// Tries the action `capability` restricts. `Ok` means it succeeded.
+    async fn attempt(
+        &self,
+        capability: SandboxCapability,
+        policy: &SandboxPolicy,
+    ) -> Result<(), String> {
+        match capability {
+            SandboxCapability::OutOfRootWrite => {
+                self.try_write(&self.outside.path().join("probe"), policy)
+                    .await
+            }
+            SandboxCapability::ProtectedPath => {
+                self.try_write(&self.workspace.path().join(".git").join("probe"), policy)
+                    .await
+            }
+            SandboxCapability::Network => {
+                let port = self
+                    .listener
+                    .local_addr()
+                    .map_err(|e| e.to_string())?
+                    .port();
+                let output = self.exec(connect_command(port), policy).await;
+                match self.listener.accept() {
+                    Ok(_) => Ok(()),
+                    Err(_) => Err(output.err().unwrap_or_else(|| "no connection".to_string())),
+                }
+            }
+        }
+    }
+
+    async fn try_write(&self, target: &Path, policy: &SandboxPolicy) -> Result<(), String> {
+        let _ = std::fs::remove_file(target);
+        let output = self.exec(write_command(target), policy).await;
+        let written = target.exists();
+        let _ = std::fs::remove_file(target);
+        if written {
+            Ok(())
+        } else {
+            Err(output
+                .err()
+                .unwrap_or_else(|| "file was not written".to_string()))
+        }
+    }
+
+    async fn exec(&self, command: Vec<String>, policy: &SandboxPolicy) -> Result<(), String> {
+        let params = ExecParams {
+            command,
+            cwd: self.workspace.path().to_path_buf(),
+            expiration: ExecExpiration::Timeout(PROBE_TIMEOUT),
+            env: self.env.clone(),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            windows_sandbox_level: self.windows_sandbox_level,
+            justification: None,
+            arg0: None,
+            limits: ExecLimits::default(),
+        };
+        match process_exec_tool_call(
+            params,
+            policy,
+            self.workspace.path(),
+            &self.codex_linux_sandbox_exe,
+            None,
+        )
+        .await
+        {
+            Ok(output) if output.exit_code == 0 => Ok(()),
+            Ok(output) => Err(format!("exited with code {}", output.exit_code)),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
+fn write_command(target: &Path) -> Vec<String> {
+    if cfg!(windows) {
+        vec![
+            "cmd".to_string(),
+            "/c".to_string(),
+            format!("echo probe> \"{}\"", target.display()),
+        ]
+    } else {
+        vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            "echo probe > \"$1\"".to_string(),
+            "sh".to_string(),
+            target.display().to_string(),
+        ]
+    }
+}
+
+fn connect_command(port: u16) -> Vec<String> {
+    if cfg!(windows) {
+        vec![
+            "powershell".to_string(),
+            "-NoProfile".to_string(),
+            "-Command".to_string(),
+            format!("(New-Object Net.Sockets.TcpClient).Connect('127.0.0.1', {port})"),
+        ]
+    } else {
+        vec![
+            "bash".to_string(),
+            "-c".to_string(),
+            format!("exec 3<>/dev/tcp/127.0.0.1/{port}"),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn report(outcomes: [ProbeOutcome; 3]) -> SandboxSelfTestReport {
+        SandboxSelfTestReport {
+            platform: platform_fingerprint(),
+            sandbox: "landlock+seccomp".to_string(),
+            results: SandboxCapability::ALL
+                .into_iter()
+                .zip(outcomes)
+                .map(|(capability, outcome)| ProbeResult {
+                    capability,
+                    outcome,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn warns_only_about_unenforced_restrictions() {
+        let enforced = report([
+            ProbeOutcome::Enforced,
+            ProbeOutcome::Inconclusive {
+                reason: "bash not found".to_string(),
+            },
+            ProbeOutcome::Enforced,
+        ]);
+        assert_eq!(enforced.warning(), None);
+
+        let weak = report([
+            ProbeOutcome::NotEnforced,
+            ProbeOutcome::Enforced,
+            ProbeOutcome::NotEnforced,
+        ]);
+        assert_eq!(
+            weak.unenforced(),
+            vec![
+                SandboxCapability::OutOfRootWrite,
+                SandboxCapability::ProtectedPath
+            ]
+        );
+        assert_eq!(
+            weak.warning(),
+            Some(
+                "The sandbox (landlock+seccomp) does not block write outside workspace, write \
+                 to .git on this machine, so read-only and workspace-write commands are not \
+                 fully contained. Run `codex debug sandbox-test` for details."
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn cached_report_is_dropped_after_an_upgrade() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let current = report([
+            ProbeOutcome::Enforced,
+            ProbeOutcome::Enforced,
+            ProbeOutcome::Enforced,
+        ]);
+        save_report(codex_home.path(), &current).expect("save");
+        assert_eq!(load_cached_report(codex_home.path()), Some(current.clone()));
+
+        let stale = SandboxSelfTestReport {
+            platform: "Linux 4.4 (kernel 4.4.0)".to_string(),
+            ..current
+        };
+        save_report(codex_home.path(), &stale).expect("save");
+        assert_eq!(load_cached_report(codex_home.path()), None);
+    }
+
+    #[tokio::test]
+    async fn probes_succeed_without_the_sandbox() {
+        let test = SelfTest::new(None, WindowsSandboxLevel::Disabled).expect("self test");
+        let policy = SandboxPolicy::DangerFullAccess;
+        assert_eq!(
+            test.attempt(SandboxCapability::OutOfRootWrite, &policy)
+                .await,
+            Ok(())
+        );
+        assert_eq!(
+            test.attempt(SandboxCapability::ProtectedPath, &policy)
+                .await,
+            Ok(())
+        );
+        assert!(!test.outside.path().join("probe").exists());
+    }
+}
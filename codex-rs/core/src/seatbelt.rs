@@ -6,6 +6,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use tokio::process::Child;
 
+use crate::exec_limits::ExecLimits;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::StdioPolicy;
@@ -39,6 +40,7 @@ pub async fn spawn_command_under_seatbelt(
         sandbox_policy,
         stdio_policy,
         env,
+        &ExecLimits::default(),
     )
     .await
 }
@@ -0,0 +1,417 @@
+//! Timings and degraded components collected while a session starts. What is
+//! known when the session is configured goes out with `SessionConfigured` as
+//! [`StartupDiagnostics`]; the rest is reported as a [`SessionPrewarmedEvent`]
+//! once MCP startup settles.
+
+use std::future::Future;
+use std::path::Path;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_channel::Sender;
+use chrono::Utc;
+use codex_app_server_protocol::AuthMode as ApiAuthMode;
+use codex_protocol::protocol::DegradedComponent;
+use codex_protocol::protocol::McpServerStartup;
+use codex_protocol::protocol::PrewarmPhase;
+use codex_protocol::protocol::PrewarmPhaseTiming;
+use codex_protocol::protocol::ProjectDocFile;
+use codex_protocol::protocol::ProviderProbe;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::SessionPrewarmedEvent;
+use codex_protocol::protocol::StartupAuth;
+use codex_protocol::protocol::StartupAuthMode;
+use codex_protocol::protocol::StartupDiagnostics;
+use tokio::task::JoinHandle;
+
+use crate::CodexAuth;
+use crate::ModelProviderInfo;
+use crate::codex::INITIAL_SUBMIT_ID;
+use crate::config::Config;
+use crate::connectivity;
+use crate::mcp_connection_manager::McpStartupReport;
+use crate::model_provider_info::UrlTemplateVars;
+use crate::project_doc::discover_project_doc_paths;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+use crate::provider_env::DotenvFile;
+use crate::sandbox_self_test;
+
+#[derive(Debug, Default)]
+pub(crate) struct StartupReport {
+    phases: Vec<PrewarmPhaseTiming>,
+    degraded: Vec<DegradedComponent>,
+}
+
+impl StartupReport {
+    pub(crate) fn record(&mut self, phase: PrewarmPhase, elapsed: Duration) {
+        self.phases.push(PrewarmPhaseTiming {
+            phase,
+            duration_ms: millis(elapsed),
+        });
+    }
+
+    pub(crate) fn degrade(&mut self, phase: PrewarmPhase, detail: impl Into<String>) {
+        self.degraded.push(DegradedComponent {
+            phase,
+            detail: detail.into(),
+        });
+    }
+
+    /// Report missing credentials now rather than on the first model request.
+    pub(crate) fn check_auth(
+        &mut self,
+        provider: &ModelProviderInfo,
+        auth: Option<&CodexAuth>,
+        dotenv: Option<&DotenvFile>,
+    ) {
+        if provider.requires_openai_auth {
+            if auth.is_none() {
+                self.degrade(
+                    PrewarmPhase::Auth,
+                    "Not signed in. Run `codex login` before sending a prompt.",
+                );
+            }
+        } else if let Err(err) = provider.api_key(dotenv) {
+            self.degrade(PrewarmPhase::Auth, err.to_string());
+        }
+    }
+
+    /// Record the provider probe, if one ran, and warn when it got no answer.
+    pub(crate) fn check_provider_probe(
+        &mut self,
+        provider: &ModelProviderInfo,
+        probe: Option<&ProviderProbe>,
+    ) {
+        let Some(probe) = probe else {
+            return;
+        };
+        self.phases.push(PrewarmPhaseTiming {
+            phase: PrewarmPhase::ProviderProbe,
+            duration_ms: probe.latency_ms,
+        });
+        if !probe.reachable {
+            self.degrade(
+                PrewarmPhase::ProviderProbe,
+                format!(
+                    "{} did not answer within {} s. Check the network, proxy and `base_url`.",
+                    provider.name,
+                    connectivity::PROBE_TIMEOUT.as_secs()
+                ),
+            );
+        }
+    }
+
+    /// What `SessionConfigured` reports of startup: the phases finished so
+    /// far, with every MCP server in `mcp_servers` still pending.
+    pub(crate) fn diagnostics(
+        &self,
+        auth: StartupAuth,
+        provider_probe: Option<ProviderProbe>,
+        project_docs: Vec<ProjectDocFile>,
+        mut mcp_servers: Vec<String>,
+    ) -> StartupDiagnostics {
+        mcp_servers.sort();
+        StartupDiagnostics {
+            phases: self.phases.clone(),
+            auth,
+            provider_probe,
+            project_docs,
+            mcp_servers: mcp_servers
+                .into_iter()
+                .map(McpServerStartup::pending)
+                .collect(),
+        }
+    }
+
+    /// Warn when the cached sandbox self-test shows that the sandbox this
+    /// session relies on is not enforced on this machine.
+    pub(crate) fn check_sandbox(&mut self, sandbox_policy: &SandboxPolicy, codex_home: &Path) {
+        if sandbox_policy.has_full_disk_write_access() {
+            return;
+        }
+        if let Some(warning) =
+            sandbox_self_test::load_cached_report(codex_home).and_then(|report| report.warning())
+        {
+            self.degrade(PrewarmPhase::Sandbox, warning);
+        }
+    }
+
+    /// Wait for MCP startup to settle, then send the report. MCP servers
+    /// connect in the background, so this runs in its own task.
+    pub(crate) async fn finish(
+        mut self,
+        mcp_startup: Option<JoinHandle<McpStartupReport>>,
+        mcp_started: Instant,
+        tx_event: Sender<Event>,
+    ) {
+        let mut mcp_servers = Vec::new();
+        if let Some(mcp_startup) = mcp_startup {
+            match mcp_startup.await {
+                Ok(McpStartupReport { summary, servers }) => {
+                    mcp_servers = servers;
+                    mcp_servers.sort_by(|a, b| a.server.cmp(&b.server));
+                    for failure in summary.failed {
+                        self.degrade(
+                            PrewarmPhase::McpServers,
+                            format!(
+                                "MCP server `{}` failed to start: {}",
+                                failure.server, failure.error
+                            ),
+                        );
+                    }
+                }
+                Err(err) => self.degrade(
+                    PrewarmPhase::McpServers,
+                    format!("MCP startup did not complete: {err}"),
+                ),
+            }
+        }
+        self.record(PrewarmPhase::McpServers, mcp_started.elapsed());
+
+        let _ = tx_event
+            .send(Event {
+                id: INITIAL_SUBMIT_ID.to_owned(),
+                msg: EventMsg::SessionPrewarmed(SessionPrewarmedEvent {
+                    phases: self.phases,
+                    degraded: self.degraded,
+                    mcp_servers,
+                }),
+            })
+            .await;
+    }
+}
+
+/// How the session authenticates with its provider, and how fresh the
+/// ChatGPT tokens are.
+pub(crate) fn startup_auth(
+    provider: &ModelProviderInfo,
+    auth: Option<&CodexAuth>,
+    dotenv: Option<&DotenvFile>,
+) -> StartupAuth {
+    if !provider.requires_openai_auth {
+        let mode = match provider.api_key(dotenv) {
+            Ok(Some(_)) => StartupAuthMode::ProviderKey,
+            Ok(None) | Err(_) => StartupAuthMode::None,
+        };
+        return StartupAuth {
+            mode,
+            token_age_secs: None,
+            refresh_due: false,
+        };
+    }
+    let mode = match auth.map(CodexAuth::api_auth_mode) {
+        Some(ApiAuthMode::ApiKey) => StartupAuthMode::ApiKey,
+        Some(ApiAuthMode::Chatgpt) => StartupAuthMode::Chatgpt,
+        Some(ApiAuthMode::ChatgptAuthTokens) => StartupAuthMode::ChatgptAuthTokens,
+        None => StartupAuthMode::None,
+    };
+    StartupAuth {
+        mode,
+        token_age_secs: auth.and_then(CodexAuth::last_refresh).map(|last_refresh| {
+            u64::try_from((Utc::now() - last_refresh).num_seconds()).unwrap_or(0)
+        }),
+        refresh_due: auth.is_some_and(CodexAuth::refresh_due),
+    }
+}
+
+/// Time a reachability probe of the provider. `None` when its URL cannot be
+/// built; the first turn reports that error.
+pub(crate) async fn probe_provider(
+    config: &Config,
+    model: &str,
+    auth: Option<&CodexAuth>,
+) -> Option<ProviderProbe> {
+    let provider = config
+        .model_provider
+        .to_api_provider(
+            auth.map(CodexAuth::internal_auth_mode),
+            UrlTemplateVars {
+                model: Some(model),
+                session_id: None,
+            },
+            config.dotenv.as_deref(),
+        )
+        .ok()?;
+    let (reachable, elapsed) = timed(connectivity::is_reachable(
+        &provider.base_url,
+        connectivity::PROBE_TIMEOUT,
+    ))
+    .await;
+    Some(ProviderProbe {
+        reachable,
+        latency_ms: millis(elapsed),
+    })
+}
+
+/// The `AGENTS.md` files the instructions were built from, with their sizes.
+pub(crate) fn project_doc_files(config: &Config) -> Vec<ProjectDocFile> {
+    discover_project_doc_paths(config)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|path| {
+            let bytes = std::fs::metadata(&path).ok()?.len();
+            Some(ProjectDocFile { path, bytes })
+        })
+        .collect()
+}
+
+fn millis(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Run `fut` and return its output along with how long it took.
+pub(crate) async fn timed<F: Future>(fut: F) -> (F::Output, Duration) {
+    let started = Instant::now();
+    let output = fut.await;
+    (output, started.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::McpStartupCompleteEvent;
+    use codex_protocol::protocol::McpStartupFailure;
+    use pretty_assertions::assert_eq;
+
+    fn provider(requires_openai_auth: bool, env_key: Option<&str>) -> ModelProviderInfo {
+        ModelProviderInfo {
+            env_key: env_key.map(str::to_string),
+            requires_openai_auth,
+            ..ModelProviderInfo::create_openai_provider()
+        }
+    }
+
+    #[test]
+    fn missing_credentials_degrade_auth() {
+        let mut report = StartupReport::default();
+        let dotenv = DotenvFile::parse(
+            "/work/.env".into(),
+            "CODEX_TEST_PREWARM_KEY_THAT_IS_NOT_SET=sk-from-dotenv\n",
+        )
+        .expect("parse dotenv");
+        report.check_auth(&provider(true, None), None, None);
+        report.check_auth(
+            &provider(false, Some("CODEX_TEST_PREWARM_KEY_THAT_IS_NOT_SET")),
+            None,
+            None,
+        );
+        report.check_auth(
+            &provider(false, Some("CODEX_TEST_PREWARM_KEY_THAT_IS_NOT_SET")),
+            None,
+            Some(&dotenv),
+        );
+        report.check_auth(
+            &provider(true, None),
+            Some(&CodexAuth::from_api_key("sk-test")),
+            None,
+        );
+        report.check_auth(&provider(false, None), None, None);
+
+        let phases: Vec<PrewarmPhase> = report.degraded.iter().map(|d| d.phase).collect();
+        assert_eq!(phases, vec![PrewarmPhase::Auth, PrewarmPhase::Auth]);
+    }
+
+    #[tokio::test]
+    async fn finish_reports_failed_mcp_servers_after_startup() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let mut report = StartupReport::default();
+        report.record(PrewarmPhase::Config, Duration::from_millis(5));
+        let search = McpServerStartup {
+            connect_ms: Some(30_000),
+            error: Some("handshake timed out".to_string()),
+            ..McpServerStartup::pending("search".to_string())
+        };
+        let docs = McpServerStartup {
+            connect_ms: Some(40),
+            tools: Some(3),
+            ..McpServerStartup::pending("docs".to_string())
+        };
+        let servers = vec![search.clone(), docs.clone()];
+        let mcp_startup = tokio::spawn(async move {
+            McpStartupReport {
+                summary: McpStartupCompleteEvent {
+                    ready: vec!["docs".to_string()],
+                    failed: vec![McpStartupFailure {
+                        server: "search".to_string(),
+                        error: "handshake timed out".to_string(),
+                    }],
+                    cancelled: Vec::new(),
+                },
+                servers,
+            }
+        });
+
+        report
+            .finish(Some(mcp_startup), Instant::now(), tx_event)
+            .await;
+
+        let Ok(Event {
+            msg: EventMsg::SessionPrewarmed(prewarmed),
+            ..
+        }) = rx_event.recv().await
+        else {
+            panic!("expected a SessionPrewarmed event");
+        };
+        let phases: Vec<PrewarmPhase> = prewarmed.phases.iter().map(|p| p.phase).collect();
+        assert_eq!(phases, vec![PrewarmPhase::Config, PrewarmPhase::McpServers]);
+        assert_eq!(
+            prewarmed.degraded,
+            vec![DegradedComponent {
+                phase: PrewarmPhase::McpServers,
+                detail: "MCP server `search` failed to start: handshake timed out".to_string(),
+            }]
+        );
+        assert_eq!(prewarmed.mcp_servers, vec![docs, search]);
+    }
+
+    #[test]
+    fn diagnostics_list_mcp_servers_as_pending() {
+        let mut report = StartupReport::default();
+        report.record(PrewarmPhase::Config, Duration::from_millis(5));
+        report.check_provider_probe(
+            &provider(true, None),
+            Some(&ProviderProbe {
+                reachable: false,
+                latency_ms: 2_000,
+            }),
+        );
+
+        let diagnostics = report.diagnostics(
+            startup_auth(&provider(false, None), None, None),
+            None,
+            Vec::new(),
+            vec!["search".to_string(), "docs".to_string()],
+        );
+
+        let phases: Vec<PrewarmPhase> = diagnostics.phases.iter().map(|p| p.phase).collect();
+        assert_eq!(
+            phases,
+            vec![PrewarmPhase::Config, PrewarmPhase::ProviderProbe]
+        );
+        assert_eq!(diagnostics.auth.mode, StartupAuthMode::None);
+        assert_eq!(
+            diagnostics.mcp_servers,
+            vec![
+                McpServerStartup::pending("docs".to_string()),
+                McpServerStartup::pending("search".to_string()),
+            ]
+        );
+        let degraded: Vec<PrewarmPhase> = report.degraded.iter().map(|d| d.phase).collect();
+        assert_eq!(degraded, vec![PrewarmPhase::ProviderProbe]);
+    }
+
+    #[test]
+    fn api_key_auth_has_no_token_age() {
+        let auth = CodexAuth::from_api_key("sk-test");
+
+        assert_eq!(
+            startup_auth(&provider(true, None), Some(&auth), None),
+            StartupAuth {
+                mode: StartupAuthMode::ApiKey,
+                token_age_secs: None,
+                refresh_due: false,
+            }
+        );
+    }
+}
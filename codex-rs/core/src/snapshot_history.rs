@@ -0,0 +1,243 @@
+//! Read-only access to the ghost snapshots recorded for undo, so a client can
+//! look at a file as it was before an earlier turn without restoring it.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_git::GhostCommit;
+use codex_git::list_ghost_commit_files;
+use codex_git::read_ghost_commit_file;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::GhostSnapshotEntry;
+use codex_protocol::protocol::GhostSnapshotFileResponseEvent;
+use codex_protocol::protocol::GhostSnapshotsResponseEvent;
+use tracing::warn;
+
+use crate::git_info::get_git_repo_root;
+
+/// Ghost snapshots still in `items` (undo removes the ones it restores),
+/// oldest first, with the turn each was taken at.
+pub(crate) fn recorded_snapshots(items: &[ResponseItem]) -> Vec<(GhostCommit, Option<u64>)> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            ResponseItem::GhostSnapshot { ghost_commit, turn } => {
+                Some((ghost_commit.clone(), *turn))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+pub(crate) async fn list_snapshots(
+    cwd: PathBuf,
+    snapshots: Vec<(GhostCommit, Option<u64>)>,
+    snapshots_enabled: bool,
+) -> GhostSnapshotsResponseEvent {
+    tokio::task::spawn_blocking(move || list_snapshots_blocking(&cwd, snapshots, snapshots_enabled))
+        .await
+        .unwrap_or_else(|err| GhostSnapshotsResponseEvent {
+            snapshots: Vec::new(),
+            unavailable: Some(format!("Failed to list snapshots: {err}")),
+        })
+}
+
+fn list_snapshots_blocking(
+    cwd: &Path,
+    snapshots: Vec<(GhostCommit, Option<u64>)>,
+    snapshots_enabled: bool,
+) -> GhostSnapshotsResponseEvent {
+    let unavailable = if get_git_repo_root(cwd).is_none() {
+        Some(format!(
+            "Snapshots unavailable: {} is not inside a git repository.",
+            cwd.display()
+        ))
+    } else if snapshots.is_empty() && !snapshots_enabled {
+        Some(
+            "Snapshots unavailable: enable the `undo` feature to snapshot the workspace before each turn."
+                .to_string(),
+        )
+    } else {
+        None
+    };
+    if unavailable.is_some() {
+        return GhostSnapshotsResponseEvent {
+            snapshots: Vec::new(),
+            unavailable,
+        };
+    }
+
+    let snapshots = snapshots
+        .into_iter()
+        .filter_map(
+            |(commit, turn)| match list_ghost_commit_files(cwd, &commit) {
+                Ok(files) => Some(GhostSnapshotEntry {
+                    commit_id: commit.id().to_string(),
+                    turn,
+                    files,
+                }),
+                // Ghost commits are unreferenced, so `git gc` may have pruned
+                // snapshots from old sessions.
+                Err(err) => {
+                    warn!("skipping ghost snapshot {}: {err}", commit.id());
+                    None
+                }
+            },
+        )
+        .collect();
+    GhostSnapshotsResponseEvent {
+        snapshots,
+        unavailable: None,
+    }
+}
+
+/// Read `path` at `commit_id` alongside its current contents. Only snapshots
+/// recorded in this session can be read.
+pub(crate) async fn read_snapshot_file(
+    cwd: PathBuf,
+    snapshots: Vec<(GhostCommit, Option<u64>)>,
+    commit_id: String,
+    path: PathBuf,
+) -> GhostSnapshotFileResponseEvent {
+    let mut response = GhostSnapshotFileResponseEvent {
+        commit_id: commit_id.clone(),
+        path: path.clone(),
+        snapshot: None,
+        current: None,
+        error: None,
+    };
+    let Some((commit, _)) = snapshots
+        .into_iter()
+        .find(|(commit, _)| commit.id() == commit_id)
+    else {
+        response.error = Some(format!("No snapshot {commit_id} in this session."));
+        return response;
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        let snapshot = read_ghost_commit_file(&cwd, &commit, &path)
+            .map_err(|err| format!("Failed to read {} from snapshot: {err}", path.display()))?
+            .map(|bytes| decode(&path, bytes))
+            .transpose()?;
+        let current = match std::fs::read(cwd.join(&path)) {
+            Ok(bytes) => Some(decode(&path, bytes)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(format!("Failed to read {}: {err}", path.display())),
+        };
+        Ok((snapshot, current))
+    })
+    .await;
+
+    match result {
+        Ok(Ok((snapshot, current))) => {
+            response.snapshot = snapshot;
+            response.current = current;
+        }
+        Ok(Err(err)) => response.error = Some(err),
+        Err(err) => response.error = Some(format!("Failed to read snapshot: {err}")),
+    }
+    response
+}
+
+fn decode(path: &Path, bytes: Vec<u8>) -> Result<String, String> {
+    String::from_utf8(bytes).map_err(|_| format!("{} is not a UTF-8 text file.", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    }
+
+    #[test]
+    fn recorded_snapshots_keep_turns_in_order() {
+        let commit = |id: &str| GhostCommit::new(id.to_string(), None, Vec::new(), Vec::new());
+        let items = vec![
+            ResponseItem::GhostSnapshot {
+                ghost_commit: commit("a"),
+                turn: None,
+            },
+            ResponseItem::Other(serde_json::json!({"type": "future_item"})),
+            ResponseItem::GhostSnapshot {
+                ghost_commit: commit("b"),
+                turn: Some(2),
+            },
+        ];
+
+        let ids: Vec<(String, Option<u64>)> = recorded_snapshots(&items)
+            .into_iter()
+            .map(|(commit, turn)| (commit.id().to_string(), turn))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![("a".to_string(), None), ("b".to_string(), Some(2))]
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_snapshot_and_current_contents() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo = temp.path().to_path_buf();
+        git(&repo, &["init", "--initial-branch=main"]);
+        std::fs::write(repo.join("lib.rs"), "fn before() {}\n").expect("write");
+        let commit =
+            codex_git::create_ghost_commit(&codex_git::CreateGhostCommitOptions::new(&repo))
+                .expect("ghost commit");
+        std::fs::write(repo.join("lib.rs"), "fn after() {}\n").expect("write");
+        let snapshots = vec![(commit.clone(), Some(1))];
+
+        let listed = list_snapshots(repo.clone(), snapshots.clone(), true).await;
+        assert_eq!(
+            listed,
+            GhostSnapshotsResponseEvent {
+                snapshots: vec![GhostSnapshotEntry {
+                    commit_id: commit.id().to_string(),
+                    turn: Some(1),
+                    files: vec![PathBuf::from("lib.rs")],
+                }],
+                unavailable: None,
+            }
+        );
+
+        let file = read_snapshot_file(
+            repo.clone(),
+            snapshots.clone(),
+            commit.id().to_string(),
+            PathBuf::from("lib.rs"),
+        )
+        .await;
+        assert_eq!(file.snapshot.as_deref(), Some("fn before() {}\n"));
+        assert_eq!(file.current.as_deref(), Some("fn after() {}\n"));
+        assert_eq!(file.error, None);
+
+        let unknown =
+            read_snapshot_file(repo, snapshots, "0000".to_string(), PathBuf::from("lib.rs")).await;
+        assert_eq!(
+            unknown.error.as_deref(),
+            Some("No snapshot 0000 in this session.")
+        );
+    }
+
+    #[tokio::test]
+    async fn non_git_sessions_report_snapshots_unavailable() {
+        let temp = tempfile::tempdir().expect("tempdir");
+
+        let listed = list_snapshots(temp.path().to_path_buf(), Vec::new(), true).await;
+
+        assert_eq!(listed.snapshots, Vec::new());
+        assert!(
+            listed
+                .unavailable
+                .is_some_and(|reason| reason.starts_with("Snapshots unavailable"))
+        );
+    }
+}
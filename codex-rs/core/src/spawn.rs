@@ -5,6 +5,7 @@ use tokio::process::Child;
 use tokio::process::Command;
 use tracing::trace;
 
+use crate::exec_limits::ExecLimits;
 use crate::protocol::SandboxPolicy;
 
 /// Experimental environment variable that will be set to some non-empty value
@@ -25,6 +26,8 @@ pub const CODEX_SANDBOX_ENV_VAR: &str = "CODEX_SANDBOX";
 #[derive(Debug, Clone, Copy)]
 pub enum StdioPolicy {
     RedirectForShellTool,
+    /// Like `RedirectForShellTool`, but stdin is a pipe the caller writes to.
+    RedirectWithStdin,
     Inherit,
 }
 
@@ -35,6 +38,11 @@ pub enum StdioPolicy {
 /// For now, we take `SandboxPolicy` as a parameter to spawn_child() because
 /// we need to determine whether to set the
 /// `CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR` environment variable.
+///
+/// On Unix the CPU and memory `limits` are applied to the child before it
+/// execs, so they also bind a sandbox wrapper and the command it runs. Callers
+/// on Windows apply them with a Job Object once the child exists.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn spawn_child_async(
     program: PathBuf,
     args: Vec<String>,
@@ -43,6 +51,7 @@ pub(crate) async fn spawn_child_async(
     sandbox_policy: &SandboxPolicy,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
+    #[cfg_attr(not(unix), allow(unused_variables))] limits: &ExecLimits,
 ) -> std::io::Result<Child> {
     trace!(
         "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?}"
@@ -66,9 +75,13 @@ pub(crate) async fn spawn_child_async(
 
     #[cfg(unix)]
     unsafe {
-        let detach_from_tty = matches!(stdio_policy, StdioPolicy::RedirectForShellTool);
+        let detach_from_tty = matches!(
+            stdio_policy,
+            StdioPolicy::RedirectForShellTool | StdioPolicy::RedirectWithStdin
+        );
         #[cfg(target_os = "linux")]
         let parent_pid = libc::getpid();
+        let limits = *limits;
         cmd.pre_exec(move || {
             if detach_from_tty {
                 codex_utils_pty::process_group::detach_from_tty()?;
@@ -81,6 +94,8 @@ pub(crate) async fn spawn_child_async(
                 // current parent dies."
                 codex_utils_pty::process_group::set_parent_death_signal(parent_pid)?;
             }
+
+            limits.apply_to_current_process()?;
             Ok(())
         });
     }
@@ -95,6 +110,11 @@ pub(crate) async fn spawn_child_async(
 
             cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
         }
+        StdioPolicy::RedirectWithStdin => {
+            cmd.stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+        }
         StdioPolicy::Inherit => {
             // Inherit stdin, stdout, and stderr from the parent process.
             cmd.stdin(Stdio::inherit())
@@ -0,0 +1,198 @@
+//! Grounding for patches written against an outdated copy of a file.
+//!
+//! When `apply_patch` fails because a file no longer contains the lines a
+//! chunk expects, the failure returned to the model carries the current
+//! contents of the file around where those lines should be, numbered and
+//! capped at [`MAX_EXCERPT_BYTES`], so its next attempt is based on the file
+//! as it is now. The file stays on the turn's list of stale patches until a
+//! later patch to it applies. With `retry_stale_patches` set, a turn that
+//! would end with files still on that list gets one follow-up asking the
+//! model to regenerate the patch for just those files.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_apply_patch::ApplyPatchError;
+use codex_protocol::models::DeveloperInstructions;
+use codex_protocol::models::ResponseItem;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::EventMsg;
+use crate::protocol::StalePatchContextEvent;
+use crate::protocol::StalePatchRetryEvent;
+
+/// Cap on the excerpt appended to a failure, tags included.
+const MAX_EXCERPT_BYTES: usize = 8 * 1024;
+
+/// Lines shown before and after the region the patch expected.
+const CONTEXT_LINES: usize = 20;
+
+/// Longer lines are cut so one of them cannot use up the excerpt.
+const MAX_LINE_CHARS: usize = 400;
+
+/// The error to return for a patch that failed verification. A context
+/// mismatch also gets the file's current contents, is reported with
+/// [`StalePatchContextEvent`], and is remembered for the automatic retry.
+pub(crate) async fn verification_failure(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    err: ApplyPatchError,
+) -> FunctionCallError {
+    let mut message = format!("apply_patch verification failed: {err}");
+    if let ApplyPatchError::ContextMismatch { path, expected, .. } = err
+        && let Ok(contents) = tokio::fs::read_to_string(&path).await
+    {
+        let excerpt = current_excerpt(&path, &contents, &expected);
+        message.push_str("\n\n");
+        message.push_str(&excerpt);
+        session.record_stale_patch(path.clone()).await;
+        session
+            .send_event(
+                turn,
+                EventMsg::StalePatchContext(StalePatchContextEvent {
+                    call_id: call_id.to_string(),
+                    path,
+                    bytes: excerpt.len() as u64,
+                }),
+            )
+            .await;
+    }
+    FunctionCallError::RespondToModel(message)
+}
+
+/// Asks the model to regenerate the patch for `paths`, whose last patch
+/// failed on stale context and still has not applied.
+pub(crate) async fn start_retry(session: &Session, turn: &TurnContext, paths: Vec<PathBuf>) {
+    let item: ResponseItem = DeveloperInstructions::new(retry_message(&paths)).into();
+    session.record_conversation_items(turn, &[item]).await;
+    session
+        .send_event(
+            turn,
+            EventMsg::StalePatchRetry(StalePatchRetryEvent { paths }),
+        )
+        .await;
+}
+
+fn retry_message(paths: &[PathBuf]) -> String {
+    let paths = paths
+        .iter()
+        .map(|path| format!("- {}", path.display()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "Your patch did not apply to these files because they changed after you read them, \
+         and they are still unpatched:\n{paths}\nUsing the current contents returned with the \
+         failure, regenerate the patch for only these files. Do not change any other file."
+    )
+}
+
+/// The current contents of `path` around the first of the `expected` lines
+/// it still has, or from the top when it has none of them.
+fn current_excerpt(path: &Path, contents: &str, expected: &[String]) -> String {
+    let lines: Vec<&str> = contents.lines().collect();
+    if lines.is_empty() {
+        return format!("{} is now empty.", path.display());
+    }
+    let anchor = expected
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .find_map(|needle| lines.iter().position(|line| line.trim() == needle));
+    let (start, end) = match anchor {
+        Some(index) => (
+            index.saturating_sub(CONTEXT_LINES),
+            (index + expected.len() + CONTEXT_LINES).min(lines.len()),
+        ),
+        None => (0, lines.len()),
+    };
+
+    // The widest header this file can get, so the result stays under the cap.
+    let overhead = header(path, lines.len(), lines.len(), lines.len()).len() + FOOTER.len();
+    let budget = MAX_EXCERPT_BYTES.saturating_sub(overhead);
+    let mut body = String::new();
+    let mut last = start;
+    for (index, line) in lines[start..end].iter().enumerate() {
+        let numbered = match line.char_indices().nth(MAX_LINE_CHARS) {
+            Some((cut, _)) => format!("{}\t{}…\n", start + index + 1, &line[..cut]),
+            None => format!("{}\t{line}\n", start + index + 1),
+        };
+        if body.len() + numbered.len() > budget {
+            break;
+        }
+        body.push_str(&numbered);
+        last = start + index + 1;
+    }
+    format!(
+        "{}{body}{FOOTER}",
+        header(path, start + 1, last, lines.len())
+    )
+}
+
+const FOOTER: &str = "</current_contents>\nWrite the next patch against these lines.";
+
+fn header(path: &Path, first: usize, last: usize, total: usize) -> String {
+    let path = path.display();
+    format!(
+        "{path} has changed since the patch was written. Its current lines {first}-{last} of \
+         {total} follow, each prefixed with its line number and a tab:\n\
+         <current_contents path=\"{path}\" lines=\"{first}-{last}\">\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn numbered_file(lines: usize) -> String {
+        (1..=lines).map(|n| format!("line {n}\n")).collect()
+    }
+
+    #[test]
+    fn excerpt_surrounds_the_expected_lines() {
+        let contents = numbered_file(100);
+        let expected = vec!["gone".to_string(), "line 50".to_string()];
+
+        let excerpt = current_excerpt(Path::new("/repo/a.txt"), &contents, &expected);
+
+        assert!(excerpt.contains("lines 30-71 of 100"), "{excerpt}");
+        assert!(excerpt.contains("\n30\tline 30\n"));
+        assert!(excerpt.contains("\n71\tline 71\n"));
+        assert!(!excerpt.contains("line 29\n"));
+        assert!(excerpt.ends_with(FOOTER));
+    }
+
+    #[test]
+    fn excerpt_starts_at_the_top_without_a_match() {
+        let excerpt = current_excerpt(
+            Path::new("/repo/a.txt"),
+            "alpha\nbeta\n",
+            &["gamma".to_string()],
+        );
+
+        assert_eq!(
+            excerpt,
+            "/repo/a.txt has changed since the patch was written. Its current lines 1-2 of 2 \
+             follow, each prefixed with its line number and a tab:\n\
+             <current_contents path=\"/repo/a.txt\" lines=\"1-2\">\n\
+             1\talpha\n2\tbeta\n\
+             </current_contents>\nWrite the next patch against these lines."
+        );
+    }
+
+    #[test]
+    fn excerpt_stays_under_the_cap() {
+        let contents = numbered_file(10_000);
+        let long_line = "x".repeat(10_000);
+        let contents = format!("{long_line}\n{contents}");
+
+        let excerpt = current_excerpt(Path::new("/repo/big.txt"), &contents, &[]);
+
+        assert!(excerpt.len() <= MAX_EXCERPT_BYTES, "{}", excerpt.len());
+        assert!(excerpt.contains(&format!("1\t{}…\n", "x".repeat(MAX_LINE_CHARS))));
+        assert!(excerpt.ends_with(FOOTER));
+    }
+}
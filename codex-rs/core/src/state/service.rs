@@ -3,7 +3,10 @@ use std::sync::Arc;
 use crate::AuthManager;
 use crate::RolloutRecorder;
 use crate::agent::AgentControl;
+use crate::command_explanation::CommandExplanations;
 use crate::exec_policy::ExecPolicyManager;
+use crate::file_uploads::FileUploads;
+use crate::injection_guard::InjectionScanner;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
 use crate::skills::SkillsManager;
@@ -12,6 +15,8 @@ use crate::tools::sandboxing::ApprovalStore;
 use crate::transport_manager::TransportManager;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::user_notification::UserNotifier;
+use crate::workspace_summary::WorkspaceSummaries;
+use crate::workspace_watch::WorkspaceWatcher;
 use codex_otel::OtelManager;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
@@ -34,4 +39,11 @@ pub(crate) struct SessionServices {
     pub(crate) agent_control: AgentControl,
     pub(crate) state_db: Option<StateDbHandle>,
     pub(crate) transport_manager: TransportManager,
+    /// Set when `injection_guard.scan` is enabled.
+    pub(crate) injection_scanner: Option<InjectionScanner>,
+    pub(crate) file_uploads: Mutex<FileUploads>,
+    pub(crate) command_explanations: Mutex<CommandExplanations>,
+    pub(crate) workspace_summaries: Mutex<WorkspaceSummaries>,
+    /// Set when `watch_workspace` is enabled.
+    pub(crate) workspace_watcher: Option<WorkspaceWatcher>,
 }
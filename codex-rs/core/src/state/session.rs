@@ -1,11 +1,16 @@
 //! Session-wide mutable state.
 
 use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 use crate::codex::SessionConfiguration;
+use crate::codex::SessionSettingsUpdate;
 use crate::context_manager::ContextManager;
+use crate::protocol::PatchApplyEndEvent;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
@@ -17,6 +22,8 @@ pub(crate) struct SessionState {
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
     pub(crate) server_reasoning_included: bool,
+    /// Service tier the provider last reported processing a response with.
+    pub(crate) served_service_tier: Option<String>,
     pub(crate) dependency_env: HashMap<String, String>,
     pub(crate) mcp_dependency_prompted: HashSet<String>,
     /// Whether the session's initial context has been seeded into history.
@@ -24,6 +31,20 @@ pub(crate) struct SessionState {
     /// TODO(owen): This is a temporary solution to avoid updating a thread's updated_at
     /// timestamp when resuming a session. Remove this once SQLite is in place.
     pub(crate) initial_context_seeded: bool,
+    /// Whether the session title should still be derived from the next user message.
+    pub(crate) auto_title_pending: bool,
+    /// Prompts submitted while the model provider was unreachable, oldest
+    /// first. They are submitted once a probe succeeds.
+    pub(crate) offline_queue: Vec<Vec<UserInput>>,
+    /// End events to send for patches whose begin event went out but whose
+    /// result has not, keyed by call id. Drained when a turn is aborted.
+    pub(crate) unfinished_patch_applies: HashMap<String, PatchApplyEndEvent>,
+    /// Every file a turn in this session wrote, for `/commit`.
+    pub(crate) files_written: BTreeSet<PathBuf>,
+    /// Model turns started since the session was created or resumed.
+    pub(crate) turns_started: u64,
+    /// Settings to restore when the running turn ends (`Op::RestoreTurnSettings`).
+    pub(crate) settings_after_turn: Option<SessionSettingsUpdate>,
 }
 
 impl SessionState {
@@ -35,9 +56,16 @@ impl SessionState {
             history,
             latest_rate_limits: None,
             server_reasoning_included: false,
+            served_service_tier: None,
             dependency_env: HashMap::new(),
             mcp_dependency_prompted: HashSet::new(),
             initial_context_seeded: false,
+            auto_title_pending: false,
+            offline_queue: Vec::new(),
+            unfinished_patch_applies: HashMap::new(),
+            files_written: BTreeSet::new(),
+            turns_started: 0,
+            settings_after_turn: None,
         }
     }
 
@@ -2,6 +2,7 @@
 
 use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::sync::Notify;
@@ -14,6 +15,8 @@ use codex_protocol::request_user_input::RequestUserInputResponse;
 use tokio::sync::oneshot;
 
 use crate::codex::TurnContext;
+use crate::file_provenance::FileProvenance;
+use crate::protocol::CancelledWork;
 use crate::protocol::ReviewDecision;
 use crate::tasks::SessionTask;
 
@@ -73,6 +76,19 @@ pub(crate) struct TurnState {
     pending_user_input: HashMap<String, oneshot::Sender<RequestUserInputResponse>>,
     pending_dynamic_tools: HashMap<String, oneshot::Sender<DynamicToolResponse>>,
     pending_input: Vec<ResponseInputItem>,
+    /// Text from `Op::SteerTask`, oldest first, waiting for the next model
+    /// request.
+    pending_steers: Vec<String>,
+    file_provenance: FileProvenance,
+    injection_suspected: bool,
+    /// Last `system_fingerprint` the provider reported during this turn.
+    system_fingerprint: Option<String>,
+    /// Work an abort would cancel, by key: the call id for tool calls.
+    running_work: IndexMap<String, CancelledWork>,
+    /// Files whose patch failed on stale context and has not applied since.
+    stale_patches: Vec<PathBuf>,
+    /// Whether the automatic stale patch retry already ran this turn.
+    stale_patch_retried: bool,
 }
 
 impl TurnState {
@@ -96,6 +112,7 @@ impl TurnState {
         self.pending_user_input.clear();
         self.pending_dynamic_tools.clear();
         self.pending_input.clear();
+        self.pending_steers.clear();
     }
 
     pub(crate) fn insert_pending_user_input(
@@ -128,6 +145,22 @@ impl TurnState {
         self.pending_dynamic_tools.remove(key)
     }
 
+    pub(crate) fn file_provenance_mut(&mut self) -> &mut FileProvenance {
+        &mut self.file_provenance
+    }
+
+    pub(crate) fn take_file_provenance(&mut self) -> FileProvenance {
+        std::mem::take(&mut self.file_provenance)
+    }
+
+    pub(crate) fn mark_injection_suspected(&mut self) {
+        self.injection_suspected = true;
+    }
+
+    pub(crate) fn injection_suspected(&self) -> bool {
+        self.injection_suspected
+    }
+
     pub(crate) fn push_pending_input(&mut self, input: ResponseInputItem) {
         self.pending_input.push(input);
     }
@@ -145,6 +178,54 @@ impl TurnState {
     pub(crate) fn has_pending_input(&self) -> bool {
         !self.pending_input.is_empty()
     }
+
+    pub(crate) fn push_steer(&mut self, text: String) {
+        self.pending_steers.push(text);
+    }
+
+    pub(crate) fn take_steers(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending_steers)
+    }
+
+    pub(crate) fn set_system_fingerprint(&mut self, fingerprint: String) {
+        self.system_fingerprint = Some(fingerprint);
+    }
+
+    pub(crate) fn take_system_fingerprint(&mut self) -> Option<String> {
+        self.system_fingerprint.take()
+    }
+
+    pub(crate) fn start_work(&mut self, key: String, work: CancelledWork) {
+        self.running_work.insert(key, work);
+    }
+
+    pub(crate) fn finish_work(&mut self, key: &str) {
+        self.running_work.shift_remove(key);
+    }
+
+    pub(crate) fn take_running_work(&mut self) -> Vec<CancelledWork> {
+        self.running_work.drain(..).map(|(_, work)| work).collect()
+    }
+
+    pub(crate) fn record_stale_patch(&mut self, path: PathBuf) {
+        if !self.stale_patches.contains(&path) {
+            self.stale_patches.push(path);
+        }
+    }
+
+    pub(crate) fn clear_stale_patches(&mut self, patched: &[PathBuf]) {
+        self.stale_patches.retain(|path| !patched.contains(path));
+    }
+
+    /// The files to retry, once per turn, when a stale patch is still
+    /// unapplied.
+    pub(crate) fn take_stale_patch_retry(&mut self) -> Option<Vec<PathBuf>> {
+        if self.stale_patch_retried || self.stale_patches.is_empty() {
+            return None;
+        }
+        self.stale_patch_retried = true;
+        Some(std::mem::take(&mut self.stale_patches))
+    }
 }
 
 impl ActiveTurn {
@@ -0,0 +1,497 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_async_utils::OrCancelExt;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use futures::prelude::*;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::compact::content_items_to_text;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::error::SandboxErr;
+use crate::exec_env::create_env;
+use crate::exec_limits::ExecLimits;
+use crate::exec_policy::ExecApprovalRequest;
+use crate::git_info::commit_blocker;
+use crate::git_info::diff_for_paths;
+use crate::git_info::dirty_paths;
+use crate::git_info::get_git_repo_root;
+use crate::git_info::head_commit_message;
+use crate::git_info::head_sha;
+use crate::preferred_language::with_preferred_language;
+use crate::protocol::CommitCompletedEvent;
+use crate::protocol::CommitDraftEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::ExecCommandSource;
+use crate::sandboxing::SandboxPermissions;
+use crate::state::TaskKind;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::runtimes::shell::ShellRequest;
+use crate::tools::runtimes::shell::ShellRuntime;
+use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::ToolError;
+
+use super::SessionTask;
+use super::SessionTaskContext;
+
+const COMMIT_PROMPT: &str = include_str!("../../templates/commit/prompt.md");
+const GIT_TIMEOUT_MS: u64 = 60 * 1000;
+const COMMIT_TOOL_NAME: &str = "commit";
+const COMMIT_JUSTIFICATION: &str = "Commit the files this session changed.";
+
+/// Drafts a commit message for the files this session changed and, unless
+/// only a draft was requested, stages those files and commits them.
+///
+/// The git commands go through the same approval and sandbox pipeline as
+/// the model's commands; see [`run_git`].
+pub(crate) struct CommitTask {
+    message: Option<String>,
+    files: Vec<PathBuf>,
+    sign_off: bool,
+    amend: bool,
+    draft_only: bool,
+}
+
+impl CommitTask {
+    pub(crate) fn draft(sign_off: bool, amend: bool) -> Self {
+        Self {
+            message: None,
+            files: Vec::new(),
+            sign_off,
+            amend,
+            draft_only: true,
+        }
+    }
+
+    pub(crate) fn commit(
+        message: Option<String>,
+        files: Vec<PathBuf>,
+        sign_off: bool,
+        amend: bool,
+    ) -> Self {
+        Self {
+            message,
+            files,
+            sign_off,
+            amend,
+            draft_only: false,
+        }
+    }
+
+    fn refused(&self, error: String) -> EventMsg {
+        EventMsg::CommitCompleted(CommitCompletedEvent {
+            success: false,
+            commit: None,
+            message: None,
+            files: Vec::new(),
+            error: Some(error),
+        })
+    }
+}
+
+#[async_trait]
+impl SessionTask for CommitTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let _ = session
+            .session
+            .services
+            .otel_manager
+            .counter("codex.task.commit", 1, &[]);
+        let sess = session.clone_session();
+        let cwd = ctx.cwd.clone();
+
+        if let Some(error) = commit_blocker(&cwd).await {
+            sess.send_event(ctx.as_ref(), self.refused(error)).await;
+            return None;
+        }
+
+        let candidates = if self.files.is_empty() {
+            sess.files_written().await
+        } else {
+            self.files.clone()
+        };
+        let files = dirty_paths(&cwd, &candidates).await;
+        if files.is_empty() && !self.amend {
+            sess.send_event(
+                ctx.as_ref(),
+                self.refused("No uncommitted changes to files this session touched.".to_string()),
+            )
+            .await;
+            return None;
+        }
+
+        let message = match self
+            .message
+            .as_deref()
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+        {
+            Some(message) => message.to_string(),
+            None => {
+                let drafted = draft_message(&sess, &ctx, &files, self.amend)
+                    .or_cancel(&cancellation_token)
+                    .await;
+                match drafted {
+                    Ok(Ok(message)) => message,
+                    Ok(Err(err)) => {
+                        sess.send_event(
+                            ctx.as_ref(),
+                            self.refused(format!("Failed to draft a commit message: {err}")),
+                        )
+                        .await;
+                        return None;
+                    }
+                    Err(_) => return None,
+                }
+            }
+        };
+
+        if self.draft_only {
+            sess.send_event(
+                ctx.as_ref(),
+                EventMsg::CommitDraft(CommitDraftEvent {
+                    message,
+                    files,
+                    sign_off: self.sign_off,
+                    amend: self.amend,
+                }),
+            )
+            .await;
+            return None;
+        }
+
+        let repo_root = get_git_repo_root(&cwd).unwrap_or_else(|| cwd.clone());
+        if !files.is_empty() {
+            let add = git_add_command(&files);
+            if let Err(error) = run_git(&sess, &ctx, add, &repo_root, &cancellation_token).await {
+                sess.send_event(ctx.as_ref(), self.refused(error)).await;
+                return None;
+            }
+        }
+        let commit = git_commit_command(&message, &files, self.sign_off, self.amend);
+        if let Err(error) = run_git(&sess, &ctx, commit, &repo_root, &cancellation_token).await {
+            sess.send_event(ctx.as_ref(), self.refused(error)).await;
+            return None;
+        }
+
+        sess.send_event(
+            ctx.as_ref(),
+            EventMsg::CommitCompleted(CommitCompletedEvent {
+                success: true,
+                commit: head_sha(&repo_root).await,
+                message: Some(message),
+                files,
+                error: None,
+            }),
+        )
+        .await;
+        None
+    }
+}
+
+/// Asks the model for a conventional-commit message describing `files`. The
+/// request carries no tools and no conversation history, only the diff.
+async fn draft_message(
+    sess: &Session,
+    turn_context: &TurnContext,
+    files: &[PathBuf],
+    amend: bool,
+) -> CodexResult<String> {
+    let mut text = String::new();
+    if amend && let Some(previous) = head_commit_message(&turn_context.cwd).await {
+        text.push_str("Message of the commit being amended:\n\n");
+        text.push_str(&previous);
+        text.push_str("\n\n");
+    }
+    let diff = diff_for_paths(&turn_context.cwd, files).await;
+    if diff.is_empty() {
+        text.push_str("The amend changes no files.\n");
+    } else {
+        text.push_str("Diff:\n\n");
+        text.push_str(&diff);
+    }
+
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText { text }],
+            end_turn: None,
+        }],
+        base_instructions: BaseInstructions {
+            text: with_preferred_language(
+                COMMIT_PROMPT,
+                turn_context.preferred_language.as_deref(),
+            ),
+        },
+        ..Default::default()
+    };
+
+    let mut client_session = turn_context.client.new_session();
+    let mut stream = client_session.stream(&prompt).await?;
+    let mut reply = String::new();
+    loop {
+        let Some(event) = stream.next().await else {
+            return Err(CodexErr::Stream(
+                "stream closed before response.completed".into(),
+                None,
+            ));
+        };
+        match event? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. })
+                if role == "assistant" =>
+            {
+                if let Some(part) = content_items_to_text(&content) {
+                    reply.push_str(&part);
+                }
+            }
+            ResponseEvent::RateLimits(snapshot) => {
+                sess.update_rate_limits(turn_context, snapshot).await;
+            }
+            ResponseEvent::Completed { token_usage, .. }
+            | ResponseEvent::Incomplete { token_usage, .. } => {
+                sess.update_token_usage_info(turn_context, token_usage.as_ref())
+                    .await;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    parse_commit_message(&reply)
+        .ok_or_else(|| CodexErr::Stream("the model returned an empty commit message".into(), None))
+}
+
+/// Strips the code fence a model may wrap the message in and trailing
+/// whitespace on each line.
+fn parse_commit_message(reply: &str) -> Option<String> {
+    let mut lines: Vec<&str> = reply.trim().lines().map(str::trim_end).collect();
+    if lines.first().is_some_and(|line| line.starts_with("```"))
+        && lines.last().is_some_and(|line| line.trim() == "```")
+        && lines.len() >= 2
+    {
+        lines.remove(0);
+        lines.pop();
+    }
+    let message = lines.join("\n").trim().to_string();
+    (!message.is_empty()).then_some(message)
+}
+
+fn path_args(files: &[PathBuf]) -> impl Iterator<Item = String> + '_ {
+    files.iter().map(|file| file.to_string_lossy().into_owned())
+}
+
+fn git_add_command(files: &[PathBuf]) -> Vec<String> {
+    ["git", "add", "--"]
+        .into_iter()
+        .map(str::to_string)
+        .chain(path_args(files))
+        .collect()
+}
+
+/// `--only` commits exactly `files`, leaving anything else the user staged
+/// out of the commit. With no files and `--amend` it rewrites the message
+/// alone.
+fn git_commit_command(
+    message: &str,
+    files: &[PathBuf],
+    sign_off: bool,
+    amend: bool,
+) -> Vec<String> {
+    let mut command: Vec<String> = ["git", "commit", "--only", "-m"]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    command.push(message.to_string());
+    if sign_off {
+        command.push("--signoff".to_string());
+    }
+    if amend {
+        command.push("--amend".to_string());
+    }
+    command.push("--".to_string());
+    command.extend(path_args(files));
+    command
+}
+
+/// Runs one git command through the exec pipeline and reports it like a
+/// user shell command. Exec policy rules and the turn's approval policy
+/// decide whether it runs: git writes `.git`, which the sandbox keeps
+/// read-only, so it asks to run outside the sandbox, and repository hooks
+/// only run unsandboxed once that is approved. Returns the command's output
+/// as the error when it fails.
+async fn run_git(
+    sess: &Arc<Session>,
+    turn_context: &TurnContext,
+    command: Vec<String>,
+    cwd: &Path,
+    cancellation_token: &CancellationToken,
+) -> Result<(), String> {
+    let call_id = Uuid::new_v4().to_string();
+    let command_display = command.join(" ");
+    let emitter = ToolEmitter::shell(
+        command.clone(),
+        cwd.to_path_buf(),
+        ExecCommandSource::UserShell,
+        false,
+    );
+    emitter
+        .begin(ToolEventCtx::new(
+            sess.as_ref(),
+            turn_context,
+            &call_id,
+            None,
+        ))
+        .await;
+
+    let features = sess.features();
+    let exec_approval_requirement = sess
+        .services
+        .exec_policy
+        .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+            features: &features,
+            command: &command,
+            approval_policy: turn_context.approval_policy,
+            sandbox_policy: &turn_context.sandbox_policy,
+            sandbox_permissions: SandboxPermissions::RequireEscalated,
+            prefix_rule: None,
+            cwd_outside_workspace: !turn_context.is_in_workspace(cwd),
+        })
+        .await;
+    let mut env = create_env(
+        &turn_context.shell_environment_policy,
+        turn_context.client.config().dotenv.as_deref(),
+    );
+    // Session file names are paths, not pathspec patterns.
+    env.insert("GIT_LITERAL_PATHSPECS".to_string(), "1".to_string());
+    let req = ShellRequest {
+        command,
+        cwd: cwd.to_path_buf(),
+        timeout_ms: Some(GIT_TIMEOUT_MS),
+        env,
+        sandbox_permissions: SandboxPermissions::RequireEscalated,
+        justification: Some(COMMIT_JUSTIFICATION.to_string()),
+        exec_approval_requirement,
+        limits: ExecLimits::default(),
+    };
+    let mut orchestrator = ToolOrchestrator::new();
+    let mut runtime = ShellRuntime::new();
+    let tool_ctx = ToolCtx {
+        session: sess.as_ref(),
+        turn: turn_context,
+        call_id: call_id.clone(),
+        tool_name: COMMIT_TOOL_NAME.to_string(),
+    };
+    let out = match orchestrator
+        .run(
+            &mut runtime,
+            &req,
+            &tool_ctx,
+            turn_context,
+            turn_context.approval_policy,
+        )
+        .or_cancel(cancellation_token)
+        .await
+    {
+        Ok(out) => out,
+        Err(_) => return Err("Commit cancelled.".to_string()),
+    };
+
+    let result = match &out {
+        Ok(output) if output.exit_code == 0 => Ok(()),
+        Ok(output) => Err(output.aggregated_output.text.trim().to_string()),
+        Err(ToolError::Rejected(reason)) => Err(format!("{command_display} was not run: {reason}")),
+        Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { .. }))) => Err(format!(
+            "{command_display} was blocked by the sandbox, which keeps .git read-only. \
+             Approve running it outside the sandbox, or allow it with an execpolicy rule."
+        )),
+        Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { .. }))) => {
+            Err(format!("{command_display} timed out."))
+        }
+        Err(ToolError::Codex(err)) => Err(format!("{command_display} failed: {err}")),
+    };
+    // The end event carries the output; the model-facing text is not needed.
+    let _ = emitter
+        .finish(
+            ToolEventCtx::new(sess.as_ref(), turn_context, &call_id, None),
+            out,
+        )
+        .await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_commit_message_strips_code_fences() {
+        assert_eq!(
+            parse_commit_message(
+                "```text\nfix(tui): keep the cursor visible\n\nBody line.  \n```\n"
+            ),
+            Some("fix(tui): keep the cursor visible\n\nBody line.".to_string())
+        );
+        assert_eq!(
+            parse_commit_message("feat: add /commit"),
+            Some("feat: add /commit".to_string())
+        );
+        assert_eq!(parse_commit_message("```\n```"), None);
+        assert_eq!(parse_commit_message("  \n"), None);
+    }
+
+    #[test]
+    fn commit_command_only_includes_session_files() {
+        let files = vec![
+            PathBuf::from("/repo/src/lib.rs"),
+            PathBuf::from("/repo/a b.txt"),
+        ];
+        assert_eq!(
+            git_add_command(&files),
+            vec!["git", "add", "--", "/repo/src/lib.rs", "/repo/a b.txt",]
+        );
+        assert_eq!(
+            git_commit_command("feat: x", &files, true, true),
+            vec![
+                "git",
+                "commit",
+                "--only",
+                "-m",
+                "feat: x",
+                "--signoff",
+                "--amend",
+                "--",
+                "/repo/src/lib.rs",
+                "/repo/a b.txt",
+            ]
+        );
+        assert_eq!(
+            git_commit_command("docs: y", &[], false, true),
+            vec!["git", "commit", "--only", "-m", "docs: y", "--amend", "--",]
+        );
+    }
+}
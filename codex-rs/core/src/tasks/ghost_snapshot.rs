@@ -39,6 +39,7 @@ impl SessionTask for GhostSnapshotTask {
         _input: Vec<UserInput>,
         cancellation_token: CancellationToken,
     ) -> Option<String> {
+        let turn = session.session.turn_number().await;
         tokio::task::spawn(async move {
             let token = self.token;
             let warnings_enabled = !ctx.ghost_snapshot.disable_warnings;
@@ -108,6 +109,7 @@ impl SessionTask for GhostSnapshotTask {
                                 .session
                                 .record_conversation_items(&ctx, &[ResponseItem::GhostSnapshot {
                                     ghost_commit: ghost_commit.clone(),
+                                    turn: Some(turn),
                                 }])
                                 .await;
                             info!("ghost commit captured: {}", ghost_commit.id());
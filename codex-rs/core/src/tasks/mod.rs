@@ -1,3 +1,4 @@
+mod commit;
 mod compact;
 mod ghost_snapshot;
 mod regular;
@@ -5,6 +6,8 @@ mod review;
 mod undo;
 mod user_shell;
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -22,6 +25,7 @@ use crate::AuthManager;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::models_manager::manager::ModelsManager;
+use crate::protocol::CancelledWork;
 use crate::protocol::EventMsg;
 use crate::protocol::TurnAbortReason;
 use crate::protocol::TurnAbortedEvent;
@@ -35,6 +39,7 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::user_input::UserInput;
 
+pub(crate) use commit::CommitTask;
 pub(crate) use compact::CompactTask;
 pub(crate) use ghost_snapshot::GhostSnapshotTask;
 pub(crate) use regular::RegularTask;
@@ -176,8 +181,12 @@ impl Session {
     }
 
     pub async fn abort_all_tasks(self: &Arc<Self>, reason: TurnAbortReason) {
-        for task in self.take_all_running_tasks().await {
-            self.handle_task_abort(task, reason.clone()).await;
+        let (tasks, mut cancelled) = self.take_all_running_tasks().await;
+        for task in tasks {
+            // Tasks share the turn's running work, so report it only once.
+            let cancelled = std::mem::take(&mut cancelled);
+            self.handle_task_abort(task, reason.clone(), cancelled)
+                .await;
         }
         self.close_unified_exec_processes().await;
     }
@@ -188,20 +197,42 @@ impl Session {
         last_agent_message: Option<String>,
     ) {
         let mut active = self.active_turn.lock().await;
-        let should_close_processes = if let Some(at) = active.as_mut()
+        let (should_close_processes, late_steers, system_fingerprint) = if let Some(at) =
+            active.as_mut()
             && at.remove_task(&turn_context.sub_id)
         {
+            let mut ts = at.turn_state.lock().await;
+            let late_steers = ts.take_steers();
+            let system_fingerprint = ts.take_system_fingerprint();
+            drop(ts);
             *active = None;
-            true
+            (true, late_steers, system_fingerprint)
         } else {
-            false
+            (false, Vec::new(), None)
         };
         drop(active);
         if should_close_processes {
             self.close_unified_exec_processes().await;
+            self.restore_settings_after_turn().await;
         }
-        let event = EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message });
+        let event = EventMsg::TurnComplete(TurnCompleteEvent {
+            last_agent_message,
+            system_fingerprint,
+        });
         self.send_event(turn_context.as_ref(), event).await;
+        if !late_steers.is_empty() {
+            self.spawn_deferred_steers(late_steers);
+        }
+    }
+
+    /// Submits steers that missed their turn from a separate task. Starting a
+    /// turn from inside the finishing one would make the task future contain
+    /// itself.
+    fn spawn_deferred_steers(self: &Arc<Self>, steers: Vec<String>) {
+        let sess = Arc::clone(self);
+        let submit: Pin<Box<dyn Future<Output = ()> + Send>> =
+            Box::pin(async move { sess.submit_deferred_steers(steers).await });
+        tokio::spawn(submit);
     }
 
     async fn register_new_active_task(&self, task: RunningTask) {
@@ -211,15 +242,26 @@ impl Session {
         *active = Some(turn);
     }
 
-    async fn take_all_running_tasks(&self) -> Vec<RunningTask> {
+    /// Takes the running tasks along with the work they had in progress,
+    /// which has to be read before cancellation lets the tasks finish it.
+    async fn take_all_running_tasks(&self) -> (Vec<RunningTask>, Vec<CancelledWork>) {
         let mut active = self.active_turn.lock().await;
         match active.take() {
             Some(mut at) => {
                 at.clear_pending().await;
+                let cancelled = at.turn_state.lock().await.take_running_work();
 
-                at.drain_tasks()
+                (at.drain_tasks(), cancelled)
             }
-            None => Vec::new(),
+            None => (Vec::new(), Vec::new()),
+        }
+    }
+
+    /// Applies settings that were scoped to the turn that just ended.
+    async fn restore_settings_after_turn(&self) {
+        let updates = self.state.lock().await.settings_after_turn.take();
+        if let Some(updates) = updates {
+            let _ = self.update_settings(updates).await;
         }
     }
 
@@ -230,7 +272,12 @@ impl Session {
             .await;
     }
 
-    async fn handle_task_abort(self: &Arc<Self>, task: RunningTask, reason: TurnAbortReason) {
+    async fn handle_task_abort(
+        self: &Arc<Self>,
+        task: RunningTask,
+        reason: TurnAbortReason,
+        cancelled: Vec<CancelledWork>,
+    ) {
         let sub_id = task.turn_context.sub_id.clone();
         if task.cancellation_token.is_cancelled() {
             return;
@@ -250,6 +297,13 @@ impl Session {
 
         task.handle.abort();
 
+        // A patch interrupted between its begin and end events still gets an
+        // end event so clients can close it out.
+        for end in self.take_unfinished_patch_applies(&sub_id).await {
+            self.send_event(task.turn_context.as_ref(), EventMsg::PatchApplyEnd(end))
+                .await;
+        }
+
         let session_ctx = Arc::new(SessionTaskContext::new(Arc::clone(self)));
         session_task
             .abort(session_ctx, Arc::clone(&task.turn_context))
@@ -275,7 +329,8 @@ impl Session {
             self.flush_rollout().await;
         }
 
-        let event = EventMsg::TurnAborted(TurnAbortedEvent { reason });
+        self.restore_settings_after_turn().await;
+        let event = EventMsg::TurnAborted(TurnAbortedEvent { reason, cancelled });
         self.send_event(task.turn_context.as_ref(), event).await;
     }
 }
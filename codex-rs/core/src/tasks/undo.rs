@@ -7,6 +7,7 @@ use crate::protocol::UndoStartedEvent;
 use crate::state::TaskKind;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::util::truncate_graphemes;
 use async_trait::async_trait;
 use codex_git::RestoreGhostCommitOptions;
 use codex_git::restore_ghost_commit_with_options;
@@ -77,7 +78,7 @@ impl SessionTask for UndoTask {
                 .enumerate()
                 .rev()
                 .find_map(|(idx, item)| match item {
-                    ResponseItem::GhostSnapshot { ghost_commit } => {
+                    ResponseItem::GhostSnapshot { ghost_commit, .. } => {
                         Some((idx, ghost_commit.clone()))
                     }
                     _ => None,
@@ -102,7 +103,7 @@ impl SessionTask for UndoTask {
             Ok(Ok(())) => {
                 items.remove(idx);
                 sess.replace_history(items).await;
-                let short_id: String = commit_id.chars().take(7).collect();
+                let short_id = truncate_graphemes(&commit_id, 7);
                 info!(commit_id = commit_id, "Undo restored ghost snapshot");
                 completed.success = true;
                 completed.message = Some(format!("Undo restored snapshot {short_id}."));
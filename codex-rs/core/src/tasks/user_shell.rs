@@ -16,6 +16,7 @@ use crate::exec::StdoutStream;
 use crate::exec::StreamOutput;
 use crate::exec::execute_exec_env;
 use crate::exec_env::create_env;
+use crate::exec_limits::ExecLimits;
 use crate::parse_command::parse_command;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandBeginEvent;
@@ -26,8 +27,8 @@ use crate::protocol::TurnStartedEvent;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxPermissions;
 use crate::state::TaskKind;
-use crate::tools::format_exec_output_str;
 use crate::tools::runtimes::maybe_wrap_shell_lc_with_snapshot;
+use crate::tools::shape_exec_output;
 use crate::user_shell_command::user_shell_command_record_item;
 
 use super::SessionTask;
@@ -68,6 +69,7 @@ impl SessionTask for UserShellCommandTask {
         let event = EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: turn_context.client.get_model_context_window(),
             collaboration_mode_kind: turn_context.collaboration_mode_kind,
+            settings: Some(turn_context.turn_settings()),
         });
         let session = session.clone_session();
         session.send_event(turn_context.as_ref(), event).await;
@@ -105,7 +107,10 @@ impl SessionTask for UserShellCommandTask {
         let exec_env = ExecEnv {
             command: exec_command.clone(),
             cwd: cwd.clone(),
-            env: create_env(&turn_context.shell_environment_policy),
+            env: create_env(
+                &turn_context.shell_environment_policy,
+                turn_context.client.config().dotenv.as_deref(),
+            ),
             // TODO(zhao-oai): Now that we have ExecExpiration::Cancellation, we
             // should use that instead of an "arbitrarily large" timeout here.
             expiration: USER_SHELL_TIMEOUT_MS.into(),
@@ -114,6 +119,7 @@ impl SessionTask for UserShellCommandTask {
             sandbox_permissions: SandboxPermissions::UseDefault,
             justification: None,
             arg0: None,
+            limits: ExecLimits::default(),
         };
 
         let stdout_stream = Some(StdoutStream {
@@ -164,11 +170,19 @@ impl SessionTask for UserShellCommandTask {
                             exit_code: -1,
                             duration: Duration::ZERO,
                             formatted_output: aborted_message,
+                            formatted_output_truncated: false,
+                            denied_by_sandbox: false,
+                            limit_exceeded: None,
                         }),
                     )
                     .await;
             }
             Ok(Ok(output)) => {
+                let shaped = shape_exec_output(
+                    &output,
+                    turn_context.truncation_policy,
+                    turn_context.tool_output_max_lines,
+                );
                 session
                     .send_event(
                         turn_context.as_ref(),
@@ -186,10 +200,10 @@ impl SessionTask for UserShellCommandTask {
                             aggregated_output: output.aggregated_output.text.clone(),
                             exit_code: output.exit_code,
                             duration: output.duration,
-                            formatted_output: format_exec_output_str(
-                                &output,
-                                turn_context.truncation_policy,
-                            ),
+                            formatted_output: shaped.text,
+                            formatted_output_truncated: shaped.truncated,
+                            denied_by_sandbox: false,
+                            limit_exceeded: None,
                         }),
                     )
                     .await;
@@ -214,6 +228,11 @@ impl SessionTask for UserShellCommandTask {
                     duration: Duration::ZERO,
                     timed_out: false,
                 };
+                let shaped = shape_exec_output(
+                    &exec_output,
+                    turn_context.truncation_policy,
+                    turn_context.tool_output_max_lines,
+                );
                 session
                     .send_event(
                         turn_context.as_ref(),
@@ -231,10 +250,10 @@ impl SessionTask for UserShellCommandTask {
                             aggregated_output: exec_output.aggregated_output.text.clone(),
                             exit_code: exec_output.exit_code,
                             duration: exec_output.duration,
-                            formatted_output: format_exec_output_str(
-                                &exec_output,
-                                turn_context.truncation_policy,
-                            ),
+                            formatted_output: shaped.text,
+                            formatted_output_truncated: shaped.truncated,
+                            denied_by_sandbox: false,
+                            limit_exceeded: None,
                         }),
                     )
                     .await;
@@ -33,6 +33,7 @@ use std::sync::Arc;
 use tempfile::TempDir;
 use tokio::sync::RwLock;
 use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
 use tracing::warn;
 
 const THREAD_CREATED_CHANNEL_CAPACITY: usize = 1024;
@@ -45,8 +46,55 @@ pub struct NewThread {
     pub session_configured: SessionConfiguredEvent,
 }
 
+/// A new thread whose session is starting in the background, returned by
+/// [`ThreadManager::prewarm_thread`]. Startup failures surface from
+/// [`PrewarmedThread::activate`].
+pub struct PrewarmedThread {
+    state: Arc<ThreadManagerState>,
+    startup: JoinHandle<CodexResult<NewThread>>,
+}
+
+impl PrewarmedThread {
+    /// Wait for the session to finish starting and hand over the thread. This
+    /// is cheap once startup is done; MCP servers may still be connecting,
+    /// which [`EventMsg::SessionPrewarmed`] reports.
+    pub async fn activate(self) -> CodexResult<NewThread> {
+        self.startup
+            .await
+            .map_err(|err| CodexErr::Fatal(format!("session startup task failed: {err}")))?
+    }
+
+    /// Shut the session down without using it, e.g. because the config it
+    /// was started with is no longer current.
+    pub async fn discard(self) {
+        let state = Arc::clone(&self.state);
+        let Ok(NewThread {
+            thread_id, thread, ..
+        }) = self.activate().await
+        else {
+            return;
+        };
+        if let Err(err) = thread.submit(Op::Shutdown).await {
+            warn!("failed to shut down discarded thread {thread_id}: {err}");
+        }
+        state.threads.write().await.remove(&thread_id);
+    }
+}
+
 /// [`ThreadManager`] is responsible for creating threads and maintaining
 /// them in memory.
+///
+/// It is the entry point for embedding Codex: one manager can run any number
+/// of concurrent threads, which share its [`AuthManager`] (so a token refresh
+/// happens once for all of them), its models cache and its skills cache. Pass
+/// the same `Arc<Config>` to several threads to avoid copying the config for
+/// each one. Each thread still connects to its own MCP servers.
+///
+/// All methods take `&self` and the manager is `Send + Sync`, so it can be
+/// shared across tasks behind an `Arc`. Each thread runs on its own tokio
+/// task. A [`CodexThread`] handle can be used from any task; each event goes
+/// to one caller of [`CodexThread::next_event`], so other observers should use
+/// [`CodexThread::subscribe`].
 pub struct ThreadManager {
     state: Arc<ThreadManagerState>,
     #[cfg(any(test, feature = "test-support"))]
@@ -143,6 +191,11 @@ impl ThreadManager {
         self.state.skills_manager.clone()
     }
 
+    /// The auth manager shared by every thread this manager starts.
+    pub fn auth_manager(&self) -> Arc<AuthManager> {
+        self.state.auth_manager.clone()
+    }
+
     pub fn get_models_manager(&self) -> Arc<ModelsManager> {
         self.state.models_manager.clone()
     }
@@ -195,18 +248,42 @@ impl ThreadManager {
         self.state.get_thread(thread_id).await
     }
 
-    pub async fn start_thread(&self, config: Config) -> CodexResult<NewThread> {
+    pub async fn start_thread(&self, config: impl Into<Arc<Config>>) -> CodexResult<NewThread> {
         self.start_thread_with_tools(config, Vec::new()).await
     }
 
+    /// Start a new thread's session in the background so that loading config,
+    /// checking auth, opening the rollout and connecting MCP servers overlap
+    /// with whatever the caller does before the first prompt.
+    pub fn prewarm_thread(&self, config: impl Into<Arc<Config>>) -> PrewarmedThread {
+        let config = config.into();
+        let state = Arc::clone(&self.state);
+        let agent_control = self.agent_control();
+        let startup = tokio::spawn(async move {
+            state
+                .spawn_thread(
+                    config,
+                    InitialHistory::New,
+                    Arc::clone(&state.auth_manager),
+                    agent_control,
+                    Vec::new(),
+                )
+                .await
+        });
+        PrewarmedThread {
+            state: Arc::clone(&self.state),
+            startup,
+        }
+    }
+
     pub async fn start_thread_with_tools(
         &self,
-        config: Config,
+        config: impl Into<Arc<Config>>,
         dynamic_tools: Vec<codex_protocol::dynamic_tools::DynamicToolSpec>,
     ) -> CodexResult<NewThread> {
         self.state
             .spawn_thread(
-                config,
+                config.into(),
                 InitialHistory::New,
                 Arc::clone(&self.state.auth_manager),
                 self.agent_control(),
@@ -217,7 +294,7 @@ impl ThreadManager {
 
     pub async fn resume_thread_from_rollout(
         &self,
-        config: Config,
+        config: impl Into<Arc<Config>>,
         rollout_path: PathBuf,
         auth_manager: Arc<AuthManager>,
     ) -> CodexResult<NewThread> {
@@ -228,13 +305,13 @@ impl ThreadManager {
 
     pub async fn resume_thread_with_history(
         &self,
-        config: Config,
+        config: impl Into<Arc<Config>>,
         initial_history: InitialHistory,
         auth_manager: Arc<AuthManager>,
     ) -> CodexResult<NewThread> {
         self.state
             .spawn_thread(
-                config,
+                config.into(),
                 initial_history,
                 auth_manager,
                 self.agent_control(),
@@ -243,6 +320,13 @@ impl ThreadManager {
             .await
     }
 
+    /// Interrupt the running turn of a thread, if any. The thread stays
+    /// registered and reports [`EventMsg::TurnAborted`] when the turn stops.
+    pub async fn interrupt_thread(&self, thread_id: ThreadId) -> CodexResult<()> {
+        self.state.send_op(thread_id, Op::Interrupt).await?;
+        Ok(())
+    }
+
     /// Removes the thread from the manager's internal map, though the thread is stored
     /// as `Arc<CodexThread>`, it is possible that other references to it exist elsewhere.
     /// Returns the thread if the thread was found and removed.
@@ -266,14 +350,14 @@ impl ThreadManager {
     pub async fn fork_thread(
         &self,
         nth_user_message: usize,
-        config: Config,
+        config: impl Into<Arc<Config>>,
         path: PathBuf,
     ) -> CodexResult<NewThread> {
         let history = RolloutRecorder::get_rollout_history(&path).await?;
         let history = truncate_before_nth_user_message(history, nth_user_message);
         self.state
             .spawn_thread(
-                config,
+                config.into(),
                 history,
                 Arc::clone(&self.state.auth_manager),
                 self.agent_control(),
@@ -341,7 +425,7 @@ impl ThreadManagerState {
         session_source: SessionSource,
     ) -> CodexResult<NewThread> {
         self.spawn_thread_with_source(
-            config,
+            Arc::new(config),
             InitialHistory::New,
             Arc::clone(&self.auth_manager),
             agent_control,
@@ -354,7 +438,7 @@ impl ThreadManagerState {
     /// Spawn a new thread with optional history and register it with the manager.
     pub(crate) async fn spawn_thread(
         &self,
-        config: Config,
+        config: Arc<Config>,
         initial_history: InitialHistory,
         auth_manager: Arc<AuthManager>,
         agent_control: AgentControl,
@@ -373,7 +457,7 @@ impl ThreadManagerState {
 
     pub(crate) async fn spawn_thread_with_source(
         &self,
-        config: Config,
+        config: Arc<Config>,
         initial_history: InitialHistory,
         auth_manager: Arc<AuthManager>,
         agent_control: AgentControl,
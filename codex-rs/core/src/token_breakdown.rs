@@ -0,0 +1,223 @@
+//! Attributes the estimated size of the next request to its parts, so users
+//! can see whether instructions, tool schemas, tool output or reasoning
+//! dominate their token usage.
+//!
+//! Nothing here runs while a turn samples: the breakdown is computed only
+//! when a client asks for it with `Op::GetTokenBreakdown`.
+
+use std::collections::HashMap;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenBreakdownEvent;
+use codex_protocol::protocol::TokenComponent;
+use codex_protocol::protocol::TokenComponentEstimate;
+
+use crate::codex::Session;
+use crate::codex::list_tools_for_next_turn;
+use crate::context_manager::estimate_item_tokens;
+use crate::error::Result as CodexResult;
+use crate::instructions::UserInstructions;
+use crate::session_prefix::is_session_prefix;
+use crate::truncate::approx_token_count;
+
+/// Estimates the request the next turn would send, resolving its tools the
+/// same way `Op::ListTools` does.
+pub(crate) async fn token_breakdown_for_next_request(
+    sess: &Session,
+) -> CodexResult<TokenBreakdownEvent> {
+    let tools = list_tools_for_next_turn(sess).await?.tools;
+    let tool_specs: Vec<&serde_json::Value> = tools.iter().map(|tool| &tool.spec).collect();
+    let tools_json = serde_json::to_string(&tool_specs)?;
+    let base_instructions = sess.get_base_instructions().await;
+    let history = sess.clone_history().await.for_prompt();
+    Ok(token_breakdown(
+        &base_instructions.text,
+        &tools_json,
+        tools.len(),
+        &history,
+    ))
+}
+
+fn token_breakdown(
+    base_instructions: &str,
+    tools_json: &str,
+    tool_count: usize,
+    history: &[ResponseItem],
+) -> TokenBreakdownEvent {
+    let mut totals: HashMap<TokenComponent, (i64, usize)> = HashMap::new();
+    let mut add = |component, tokens: i64, items| {
+        let entry = totals.entry(component).or_default();
+        entry.0 = entry.0.saturating_add(tokens);
+        entry.1 += items;
+    };
+    add(
+        TokenComponent::Instructions,
+        tokens_for_text(base_instructions),
+        0,
+    );
+    add(
+        TokenComponent::Tools,
+        tokens_for_text(tools_json),
+        tool_count,
+    );
+    for item in history {
+        if let Some(component) = component_for(item) {
+            add(component, estimate_item_tokens(item), 1);
+        }
+    }
+
+    let mut components: Vec<TokenComponentEstimate> = totals
+        .into_iter()
+        .filter(|(_, (tokens, _))| *tokens > 0)
+        .map(|(component, (tokens, items))| TokenComponentEstimate {
+            component,
+            tokens,
+            items,
+        })
+        .collect();
+    components.sort_by(|a, b| {
+        b.tokens
+            .cmp(&a.tokens)
+            .then_with(|| a.component.label().cmp(b.component.label()))
+    });
+    let total_tokens = components
+        .iter()
+        .fold(0i64, |acc, estimate| acc.saturating_add(estimate.tokens));
+    TokenBreakdownEvent {
+        components,
+        total_tokens,
+    }
+}
+
+fn tokens_for_text(text: &str) -> i64 {
+    i64::try_from(approx_token_count(text)).unwrap_or(i64::MAX)
+}
+
+/// Which component an item counts toward, or `None` for items that are
+/// never sent to the model.
+fn component_for(item: &ResponseItem) -> Option<TokenComponent> {
+    let component = match item {
+        ResponseItem::Message { role, content, .. } => match role.as_str() {
+            "assistant" => TokenComponent::AssistantMessages,
+            "user" if is_context_message(content) => TokenComponent::Instructions,
+            "user" => TokenComponent::UserMessages,
+            _ => TokenComponent::Instructions,
+        },
+        ResponseItem::Reasoning { .. } => TokenComponent::Reasoning,
+        ResponseItem::LocalShellCall { .. }
+        | ResponseItem::FunctionCall { .. }
+        | ResponseItem::CustomToolCall { .. }
+        | ResponseItem::WebSearchCall { .. } => TokenComponent::FunctionCalls,
+        ResponseItem::FunctionCallOutput { .. } | ResponseItem::CustomToolCallOutput { .. } => {
+            TokenComponent::FunctionOutputs
+        }
+        ResponseItem::Compaction { .. } | ResponseItem::Other(_) => TokenComponent::Other,
+        ResponseItem::GhostSnapshot { .. } => return None,
+    };
+    Some(component)
+}
+
+/// User-role messages Codex adds itself: `AGENTS.md` instructions and
+/// session prefixes such as the environment context.
+fn is_context_message(content: &[ContentItem]) -> bool {
+    UserInstructions::is_user_instructions(content)
+        || content.iter().any(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                is_session_prefix(text)
+            }
+            ContentItem::InputImage { .. } | ContentItem::InputFile { .. } => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        let text = text.to_string();
+        let content = if role == "assistant" {
+            ContentItem::OutputText { text }
+        } else {
+            ContentItem::InputText { text }
+        };
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![content],
+            end_turn: None,
+        }
+    }
+
+    fn components_by_label(event: &TokenBreakdownEvent) -> Vec<(TokenComponent, usize)> {
+        let mut components: Vec<(TokenComponent, usize)> = event
+            .components
+            .iter()
+            .map(|estimate| (estimate.component, estimate.items))
+            .collect();
+        components.sort_by_key(|(component, _)| component.label());
+        components
+    }
+
+    #[test]
+    fn breakdown_groups_items_and_sorts_largest_first() {
+        let history = vec![
+            message(
+                "user",
+                "<environment_context>\n  <cwd>/repo</cwd>\n</environment_context>",
+            ),
+            message("user", "run the tests"),
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["cargo","test"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "test output line\n".repeat(200),
+                    ..Default::default()
+                },
+            },
+            message("assistant", "All tests pass."),
+        ];
+
+        let event = token_breakdown("You are Codex.", "[]", 0, &history);
+
+        assert_eq!(
+            components_by_label(&event),
+            vec![
+                (TokenComponent::AssistantMessages, 1),
+                (TokenComponent::FunctionCalls, 1),
+                (TokenComponent::FunctionOutputs, 1),
+                (TokenComponent::Instructions, 1),
+                (TokenComponent::Tools, 0),
+                (TokenComponent::UserMessages, 1),
+            ]
+        );
+        assert_eq!(
+            event.components[0].component,
+            TokenComponent::FunctionOutputs
+        );
+        assert!(
+            event
+                .components
+                .windows(2)
+                .all(|pair| pair[0].tokens >= pair[1].tokens)
+        );
+        assert_eq!(
+            event.total_tokens,
+            event.components.iter().map(|c| c.tokens).sum::<i64>()
+        );
+    }
+
+    #[test]
+    fn empty_components_are_left_out() {
+        let event = token_breakdown("", "", 0, &[]);
+        assert_eq!(event.components, Vec::new());
+        assert_eq!(event.total_tokens, 0);
+    }
+}
@@ -1,4 +1,6 @@
 use base64::Engine;
+use chrono::DateTime;
+use chrono::Utc;
 use serde::Deserialize;
 use serde::Serialize;
 use thiserror::Error;
@@ -143,6 +145,21 @@ pub fn parse_id_token(id_token: &str) -> Result<IdTokenInfo, IdTokenInfoError> {
     }
 }
 
+#[derive(Deserialize)]
+struct ExpirationClaim {
+    exp: i64,
+}
+
+/// The `exp` claim of a JWT such as the access token, if it has one.
+pub fn jwt_expiration(jwt: &str) -> Option<DateTime<Utc>> {
+    let payload_b64 = jwt.split('.').nth(1)?;
+    let payload_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload_b64)
+        .ok()?;
+    let claim: ExpirationClaim = serde_json::from_slice(&payload_bytes).ok()?;
+    DateTime::from_timestamp(claim.exp, 0)
+}
+
 fn deserialize_id_token<'de, D>(deserializer: D) -> Result<IdTokenInfo, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -255,6 +272,32 @@ mod tests {
         assert!(info.get_chatgpt_plan_type().is_none());
     }
 
+    #[test]
+    fn jwt_expiration_reads_exp_claim() {
+        fn b64url_no_pad(bytes: &[u8]) -> String {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+        }
+
+        let jwt = |payload: serde_json::Value| {
+            format!(
+                "{}.{}.{}",
+                b64url_no_pad(br#"{"alg":"none"}"#),
+                b64url_no_pad(&serde_json::to_vec(&payload).unwrap()),
+                b64url_no_pad(b"sig")
+            )
+        };
+
+        assert_eq!(
+            jwt_expiration(&jwt(serde_json::json!({ "exp": 1_700_000_000 }))),
+            DateTime::from_timestamp(1_700_000_000, 0)
+        );
+        assert_eq!(
+            jwt_expiration(&jwt(serde_json::json!({ "sub": "123" }))),
+            None
+        );
+        assert_eq!(jwt_expiration("not-a-jwt"), None);
+    }
+
     #[test]
     fn workspace_account_detection_matches_workspace_plans() {
         let workspace = IdTokenInfo {
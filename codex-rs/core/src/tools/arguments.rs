@@ -0,0 +1,394 @@
+//! Lenient parsing of function call arguments.
+//!
+//! Models, third-party ones especially, sometimes send arguments that are
+//! almost JSON: trailing commas, single quotes, raw newlines inside strings.
+//! When strict parsing fails, a single pass of targeted fixups is tried. If
+//! that does not help either, the model gets an error that points at the
+//! problem and quotes the schema it should follow, so it can correct itself
+//! instead of repeating the same mistake.
+
+use codex_protocol::protocol::ArgumentRepair;
+use serde_json::json;
+
+/// Characters of context quoted on each side of a parse error.
+const EXCERPT_RADIUS: usize = 24;
+/// Longest schema excerpt included in an invalid-arguments error.
+const MAX_SCHEMA_EXCERPT_CHARS: usize = 1_500;
+
+/// The outcome of checking a call's raw arguments.
+#[derive(Debug)]
+pub(crate) enum ArgumentsCheck {
+    /// Strict JSON, or empty, which tools handle themselves.
+    Valid,
+    /// Not JSON as sent, but valid after `repairs`.
+    Repaired {
+        arguments: String,
+        repairs: Vec<ArgumentRepair>,
+    },
+    /// Not JSON even after repair. Holds the strict parser's error.
+    Invalid(serde_json::Error),
+}
+
+pub(crate) fn check_arguments(raw: &str) -> ArgumentsCheck {
+    if raw.trim().is_empty() {
+        return ArgumentsCheck::Valid;
+    }
+    let err = match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(_) => return ArgumentsCheck::Valid,
+        Err(err) => err,
+    };
+    match repair(raw) {
+        Some((arguments, repairs))
+            if !repairs.is_empty()
+                && serde_json::from_str::<serde_json::Value>(&arguments).is_ok() =>
+        {
+            ArgumentsCheck::Repaired { arguments, repairs }
+        }
+        _ => ArgumentsCheck::Invalid(err),
+    }
+}
+
+/// Structured error returned to the model when `raw` could not be parsed,
+/// quoting the text around the error and the start of `schema`.
+pub(crate) fn invalid_arguments_message(
+    tool_name: &str,
+    raw: &str,
+    err: &serde_json::Error,
+    schema: Option<&str>,
+) -> String {
+    let mut message = json!({
+        "error": "invalid_arguments",
+        "tool": tool_name,
+        "message": format!(
+            "The arguments are not valid JSON: {err}. Call {tool_name} again with arguments that are a single JSON object matching the expected schema."
+        ),
+        "line": err.line(),
+        "column": err.column(),
+        "near": excerpt_at(raw, err.line(), err.column()),
+    });
+    if let Some(schema) = schema {
+        message["expected_schema"] = schema_excerpt(schema).into();
+    }
+    message.to_string()
+}
+
+/// Rewrites `raw` with every fixup it needs. Returns `None` when the input
+/// ends inside a string, which no fixup can make sense of.
+fn repair(raw: &str) -> Option<(String, Vec<ArgumentRepair>)> {
+    let mut repairs = Vec::new();
+    let mut note = |repair: ArgumentRepair| {
+        if !repairs.contains(&repair) {
+            repairs.push(repair);
+        }
+    };
+
+    let input = match strip_code_fence(raw) {
+        Some(inner) => {
+            note(ArgumentRepair::CodeFence);
+            inner
+        }
+        None => raw,
+    };
+    let chars: Vec<char> = input.chars().collect();
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' => {
+                if c == '\'' {
+                    note(ArgumentRepair::SingleQuotes);
+                }
+                i = copy_string(&chars, i, &mut out, &mut note)?;
+                continue;
+            }
+            ',' if next_non_whitespace(&chars, i + 1).is_some_and(|c| c == '}' || c == ']') => {
+                note(ArgumentRepair::TrailingComma);
+            }
+            c if c.is_ascii_alphabetic() || c == '_' || c == '$' => {
+                let end = chars[i..]
+                    .iter()
+                    .position(|c| !(c.is_ascii_alphanumeric() || *c == '_' || *c == '$'))
+                    .map_or(chars.len(), |offset| i + offset);
+                let word: String = chars[i..end].iter().collect();
+                if next_non_whitespace(&chars, end) == Some(':') {
+                    note(ArgumentRepair::UnquotedKey);
+                    out.push('"');
+                    out.push_str(&word);
+                    out.push('"');
+                } else if let Some(literal) = python_literal(&word) {
+                    note(ArgumentRepair::PythonLiteral);
+                    out.push_str(literal);
+                } else {
+                    out.push_str(&word);
+                }
+                i = end;
+                continue;
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+    Some((out, repairs))
+}
+
+/// Copies the string starting at `chars[start]` (a `"` or `'`) to `out` as a
+/// valid double-quoted JSON string. Returns the index just past its closing
+/// quote, or `None` if it is never closed.
+fn copy_string(
+    chars: &[char],
+    start: usize,
+    out: &mut String,
+    note: &mut impl FnMut(ArgumentRepair),
+) -> Option<usize> {
+    let quote = chars[start];
+    out.push('"');
+    let mut i = start + 1;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' => {
+                let escaped = *chars.get(i + 1)?;
+                match escaped {
+                    '\'' => {
+                        if quote == '"' {
+                            note(ArgumentRepair::InvalidEscape);
+                        }
+                        out.push('\'');
+                    }
+                    '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't' | 'u' => {
+                        out.push('\\');
+                        out.push(escaped);
+                    }
+                    _ => {
+                        note(ArgumentRepair::InvalidEscape);
+                        out.push_str("\\\\");
+                        i += 1;
+                        continue;
+                    }
+                }
+                i += 2;
+                continue;
+            }
+            c if c == quote => {
+                out.push('"');
+                return Some(i + 1);
+            }
+            '"' => out.push_str("\\\""),
+            '\n' => {
+                note(ArgumentRepair::UnescapedControlCharacter);
+                out.push_str("\\n");
+            }
+            '\r' => {
+                note(ArgumentRepair::UnescapedControlCharacter);
+                out.push_str("\\r");
+            }
+            '\t' => {
+                note(ArgumentRepair::UnescapedControlCharacter);
+                out.push_str("\\t");
+            }
+            c if (c as u32) < 0x20 => {
+                note(ArgumentRepair::UnescapedControlCharacter);
+                out.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+    None
+}
+
+/// The body of a Markdown code fence wrapping the whole input, if any.
+fn strip_code_fence(raw: &str) -> Option<&str> {
+    let trimmed = raw.trim();
+    let body = trimmed.strip_prefix("```")?.strip_suffix("```")?;
+    // Drop the info string (`json`) on the opening line.
+    let (_, body) = body.split_once('\n')?;
+    Some(body)
+}
+
+fn next_non_whitespace(chars: &[char], from: usize) -> Option<char> {
+    chars[from.min(chars.len())..]
+        .iter()
+        .copied()
+        .find(|c| !c.is_whitespace())
+}
+
+fn python_literal(word: &str) -> Option<&'static str> {
+    match word {
+        "True" => Some("true"),
+        "False" => Some("false"),
+        "None" => Some("null"),
+        _ => None,
+    }
+}
+
+/// Up to `EXCERPT_RADIUS` characters on each side of the 1-based `line` and
+/// `column` serde_json reports.
+fn excerpt_at(raw: &str, line: usize, column: usize) -> String {
+    let Some(text) = raw.lines().nth(line.saturating_sub(1)) else {
+        return String::new();
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let at = column.min(chars.len());
+    let start = at.saturating_sub(EXCERPT_RADIUS);
+    let end = (at + EXCERPT_RADIUS).min(chars.len());
+    chars[start..end].iter().collect()
+}
+
+fn schema_excerpt(schema: &str) -> String {
+    if schema.chars().count() <= MAX_SCHEMA_EXCERPT_CHARS {
+        return schema.to_string();
+    }
+    let mut excerpt: String = schema.chars().take(MAX_SCHEMA_EXCERPT_CHARS).collect();
+    excerpt.push('…');
+    excerpt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+
+    use ArgumentRepair::*;
+
+    /// Malformed arguments seen from real models, with the JSON they should
+    /// repair to and the fixups that gets them there.
+    const CORPUS: &[(&str, &str, &[ArgumentRepair])] = &[
+        (
+            r#"{"command": ["ls", "-la"],}"#,
+            r#"{"command": ["ls", "-la"]}"#,
+            &[TrailingComma],
+        ),
+        (
+            r#"{"command": ["rg", "TODO", "src",], "workdir": "/repo",}"#,
+            r#"{"command": ["rg", "TODO", "src"], "workdir": "/repo"}"#,
+            &[TrailingComma],
+        ),
+        (
+            r#"{'path': '/repo/src/main.rs', 'offset': 1}"#,
+            r#"{"path": "/repo/src/main.rs", "offset": 1}"#,
+            &[SingleQuotes],
+        ),
+        (
+            r#"{'cmd': 'echo "hi"', 'justification': 'it\'s safe'}"#,
+            r#"{"cmd": "echo \"hi\"", "justification": "it's safe"}"#,
+            &[SingleQuotes],
+        ),
+        (
+            "{\"input\": \"*** Begin Patch\n*** Add File: a.txt\n+hello\n*** End Patch\"}",
+            r#"{"input": "*** Begin Patch\n*** Add File: a.txt\n+hello\n*** End Patch"}"#,
+            &[UnescapedControlCharacter],
+        ),
+        (
+            "{\"chars\": \"col1\tcol2\r\n\"}",
+            r#"{"chars": "col1\tcol2\r\n"}"#,
+            &[UnescapedControlCharacter],
+        ),
+        (
+            r#"{command: ["pwd"], timeout_ms: 1000}"#,
+            r#"{"command": ["pwd"], "timeout_ms": 1000}"#,
+            &[UnquotedKey],
+        ),
+        (
+            r#"{"pattern": "fn \w+\(", "include": "*.rs"}"#,
+            r#"{"pattern": "fn \\w+\\(", "include": "*.rs"}"#,
+            &[InvalidEscape],
+        ),
+        (
+            r#"{"message": "don\'t stop"}"#,
+            r#"{"message": "don't stop"}"#,
+            &[InvalidEscape],
+        ),
+        (
+            r#"{"recursive": True, "limit": None, "hidden": False}"#,
+            r#"{"recursive": true, "limit": null, "hidden": false}"#,
+            &[PythonLiteral],
+        ),
+        (
+            "```json\n{\"query\": \"rust\",}\n```",
+            "{\"query\": \"rust\"}\n",
+            &[CodeFence, TrailingComma],
+        ),
+        (
+            "{'plan': [{'step': 'read\nthe code', 'status': 'pending',},],}",
+            r#"{"plan": [{"step": "read\nthe code", "status": "pending"}]}"#,
+            &[SingleQuotes, UnescapedControlCharacter, TrailingComma],
+        ),
+    ];
+
+    #[test]
+    fn corpus_repairs_to_expected_json() {
+        for (raw, expected, expected_repairs) in CORPUS {
+            let ArgumentsCheck::Repaired { arguments, repairs } = check_arguments(raw) else {
+                panic!("expected {raw:?} to be repaired");
+            };
+            assert_eq!(arguments, *expected, "repairing {raw:?}");
+            assert_eq!(repairs, expected_repairs.to_vec(), "repairing {raw:?}");
+            serde_json::from_str::<Value>(&arguments).expect("repaired arguments parse");
+        }
+    }
+
+    #[test]
+    fn valid_and_empty_arguments_are_left_alone() {
+        for raw in [
+            r#"{"command": ["echo", "it's, fine"]}"#,
+            r#"{"text": "True, None"}"#,
+            "",
+            "  ",
+        ] {
+            assert!(
+                matches!(check_arguments(raw), ArgumentsCheck::Valid),
+                "{raw:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn unrepairable_arguments_keep_the_strict_error() {
+        for raw in [
+            r#"{"command": ["ls""#,
+            r#"{"path": "/repo/unterminated}"#,
+            r#"{"a" 1}"#,
+            "run ls please",
+        ] {
+            assert!(
+                matches!(check_arguments(raw), ArgumentsCheck::Invalid(_)),
+                "{raw:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_arguments_message_points_at_the_error_and_quotes_the_schema() {
+        let raw = r#"{"command": ["ls" "-la"]}"#;
+        let ArgumentsCheck::Invalid(err) = check_arguments(raw) else {
+            panic!("expected invalid arguments");
+        };
+        let schema = r#"{"type":"object","properties":{"command":{"type":"array"}}}"#;
+        let message: Value =
+            serde_json::from_str(&invalid_arguments_message("shell", raw, &err, Some(schema)))
+                .expect("message is json");
+
+        assert_eq!(message["error"], "invalid_arguments");
+        assert_eq!(message["tool"], "shell");
+        assert_eq!(message["line"], 1);
+        assert_eq!(message["column"], 19);
+        assert_eq!(message["near"], r#"{"command": ["ls" "-la"]}"#);
+        assert_eq!(message["expected_schema"], schema);
+        assert!(
+            message["message"]
+                .as_str()
+                .is_some_and(|text| text.contains("line 1 column 19"))
+        );
+    }
+
+    #[test]
+    fn long_schemas_are_cut_short() {
+        let schema = "x".repeat(MAX_SCHEMA_EXCERPT_CHARS + 10);
+        let excerpt = schema_excerpt(&schema);
+        assert_eq!(excerpt.chars().count(), MAX_SCHEMA_EXCERPT_CHARS + 1);
+        assert!(excerpt.ends_with('…'));
+    }
+}
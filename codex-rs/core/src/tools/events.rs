@@ -9,19 +9,23 @@ use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandBeginEvent;
 use crate::protocol::ExecCommandEndEvent;
 use crate::protocol::ExecCommandSource;
+use crate::protocol::ExecLimitKind;
 use crate::protocol::FileChange;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
+use crate::protocol::PatchFileResult;
+use crate::protocol::PatchFileStatus;
 use crate::protocol::TurnDiffEvent;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
 use codex_protocol::parse_command::ParsedCommand;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 
-use super::format_exec_output_str;
+use super::shape_exec_output;
 
 #[derive(Clone, Copy)]
 pub(crate) struct ToolEventCtx<'a> {
@@ -55,6 +59,10 @@ pub(crate) enum ToolEventStage {
 
 pub(crate) enum ToolEventFailure {
     Output(ExecToolCallOutput),
+    /// The sandbox blocked the command; carries the output of the blocked attempt.
+    SandboxDenied(ExecToolCallOutput),
+    /// The command was stopped by one of its resource limits.
+    LimitExceeded(ExecLimitKind, ExecToolCallOutput),
     Message(String),
 }
 
@@ -94,6 +102,8 @@ pub(crate) enum ToolEmitter {
     },
     ApplyPatch {
         changes: HashMap<PathBuf, FileChange>,
+        /// Directory the patch paths are relative to.
+        cwd: PathBuf,
         auto_approved: bool,
     },
     UnifiedExec {
@@ -122,9 +132,14 @@ impl ToolEmitter {
         }
     }
 
-    pub fn apply_patch(changes: HashMap<PathBuf, FileChange>, auto_approved: bool) -> Self {
+    pub fn apply_patch(
+        changes: HashMap<PathBuf, FileChange>,
+        cwd: PathBuf,
+        auto_approved: bool,
+    ) -> Self {
         Self::ApplyPatch {
             changes,
+            cwd,
             auto_approved,
         }
     }
@@ -169,6 +184,7 @@ impl ToolEmitter {
                 Self::ApplyPatch {
                     changes,
                     auto_approved,
+                    ..
                 },
                 ToolEventStage::Begin,
             ) => {
@@ -176,6 +192,26 @@ impl ToolEmitter {
                     let mut guard = tracker.lock().await;
                     guard.on_patch_begin(changes);
                 }
+                let mut aborted: Vec<PatchFileResult> = changes
+                    .keys()
+                    .map(|path| PatchFileResult {
+                        path: path.clone(),
+                        status: PatchFileStatus::Aborted,
+                        error: None,
+                    })
+                    .collect();
+                aborted.sort_by(|a, b| a.path.cmp(&b.path));
+                ctx.session
+                    .track_patch_apply(PatchApplyEndEvent {
+                        call_id: ctx.call_id.to_string(),
+                        turn_id: ctx.turn.sub_id.clone(),
+                        stdout: String::new(),
+                        stderr: "turn aborted before the patch finished".to_string(),
+                        success: false,
+                        changes: changes.clone(),
+                        per_file: aborted,
+                    })
+                    .await;
                 ctx.session
                     .send_event(
                         ctx.turn,
@@ -188,23 +224,19 @@ impl ToolEmitter {
                     )
                     .await;
             }
-            (Self::ApplyPatch { changes, .. }, ToolEventStage::Success(output)) => {
-                emit_patch_end(
-                    ctx,
-                    changes.clone(),
-                    output.stdout.text.clone(),
-                    output.stderr.text.clone(),
-                    output.exit_code == 0,
-                )
-                .await;
-            }
             (
-                Self::ApplyPatch { changes, .. },
-                ToolEventStage::Failure(ToolEventFailure::Output(output)),
+                Self::ApplyPatch { changes, cwd, .. },
+                ToolEventStage::Success(output)
+                | ToolEventStage::Failure(
+                    ToolEventFailure::Output(output)
+                    | ToolEventFailure::SandboxDenied(output)
+                    | ToolEventFailure::LimitExceeded(_, output),
+                ),
             ) => {
                 emit_patch_end(
                     ctx,
                     changes.clone(),
+                    cwd,
                     output.stdout.text.clone(),
                     output.stderr.text.clone(),
                     output.exit_code == 0,
@@ -212,12 +244,13 @@ impl ToolEmitter {
                 .await;
             }
             (
-                Self::ApplyPatch { changes, .. },
+                Self::ApplyPatch { changes, cwd, .. },
                 ToolEventStage::Failure(ToolEventFailure::Message(message)),
             ) => {
                 emit_patch_end(
                     ctx,
                     changes.clone(),
+                    cwd,
                     String::new(),
                     (*message).to_string(),
                     false,
@@ -261,10 +294,30 @@ impl ToolEmitter {
         ctx: ToolEventCtx<'_>,
     ) -> String {
         match self {
-            Self::Shell { freeform: true, .. } => {
-                super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
+            Self::Shell { freeform: true, .. } => super::format_exec_output_for_model_freeform(
+                output,
+                ctx.turn.truncation_policy,
+                ctx.turn.tool_output_max_lines,
+            ),
+            Self::ApplyPatch { changes, cwd, .. } if output.exit_code != 0 => {
+                super::format_patch_failure_for_model(
+                    output,
+                    &patch_file_results(
+                        changes,
+                        cwd,
+                        &output.stdout.text,
+                        &output.stderr.text,
+                        false,
+                    ),
+                    ctx.turn.truncation_policy,
+                    ctx.turn.tool_output_max_lines,
+                )
             }
-            _ => super::format_exec_output_for_model_structured(output, ctx.turn.truncation_policy),
+            _ => super::format_exec_output_for_model_structured(
+                output,
+                ctx.turn.truncation_policy,
+                ctx.turn.tool_output_max_lines,
+            ),
         }
     }
 
@@ -285,13 +338,31 @@ impl ToolEmitter {
                 };
                 (event, result)
             }
-            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
-            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output }))) => {
                 let response = self.format_exec_output_for_model(&output, ctx);
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
             }
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
+                let response = self.format_exec_output_for_model(&output, ctx);
+                let event = ToolEventStage::Failure(ToolEventFailure::SandboxDenied(*output));
+                let result = Err(FunctionCallError::RespondToModel(response));
+                (event, result)
+            }
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::LimitExceeded {
+                kind,
+                output,
+            }))) => {
+                let response = format!(
+                    "command was killed after exceeding its {}\n{}",
+                    kind.description(),
+                    self.format_exec_output_for_model(&output, ctx)
+                );
+                let event = ToolEventStage::Failure(ToolEventFailure::LimitExceeded(kind, *output));
+                let result = Err(FunctionCallError::RespondToModel(response));
+                (event, result)
+            }
             Err(ToolError::Codex(err)) => {
                 let message = format!("execution error: {err:?}");
                 let event = ToolEventStage::Failure(ToolEventFailure::Message(message.clone()));
@@ -357,6 +428,9 @@ struct ExecCommandResult {
     exit_code: i32,
     duration: Duration,
     formatted_output: String,
+    formatted_output_truncated: bool,
+    denied_by_sandbox: bool,
+    limit_exceeded: Option<ExecLimitKind>,
 }
 
 async fn emit_exec_stage(
@@ -379,15 +453,13 @@ async fn emit_exec_stage(
         }
         ToolEventStage::Success(output)
         | ToolEventStage::Failure(ToolEventFailure::Output(output)) => {
-            let exec_result = ExecCommandResult {
-                stdout: output.stdout.text.clone(),
-                stderr: output.stderr.text.clone(),
-                aggregated_output: output.aggregated_output.text.clone(),
-                exit_code: output.exit_code,
-                duration: output.duration,
-                formatted_output: format_exec_output_str(&output, ctx.turn.truncation_policy),
-            };
-            emit_exec_end(ctx, exec_input, exec_result).await;
+            emit_exec_output_end(ctx, exec_input, output, false, None).await;
+        }
+        ToolEventStage::Failure(ToolEventFailure::SandboxDenied(output)) => {
+            emit_exec_output_end(ctx, exec_input, output, true, None).await;
+        }
+        ToolEventStage::Failure(ToolEventFailure::LimitExceeded(kind, output)) => {
+            emit_exec_output_end(ctx, exec_input, output, false, Some(kind)).await;
         }
         ToolEventStage::Failure(ToolEventFailure::Message(message)) => {
             let text = message.to_string();
@@ -398,12 +470,51 @@ async fn emit_exec_stage(
                 exit_code: -1,
                 duration: Duration::ZERO,
                 formatted_output: text,
+                formatted_output_truncated: false,
+                denied_by_sandbox: false,
+                limit_exceeded: None,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
     }
 }
 
+async fn emit_exec_output_end(
+    ctx: ToolEventCtx<'_>,
+    exec_input: ExecCommandInput<'_>,
+    output: ExecToolCallOutput,
+    denied_by_sandbox: bool,
+    limit_exceeded: Option<ExecLimitKind>,
+) {
+    let shaped = shape_exec_output(
+        &output,
+        ctx.turn.truncation_policy,
+        ctx.turn.tool_output_max_lines,
+    );
+    ctx.session
+        .record_file_provenance(|provenance| {
+            provenance.record_command(
+                &ctx.turn.cwd,
+                exec_input.cwd,
+                exec_input.command,
+                exec_input.parsed_cmd,
+            );
+        })
+        .await;
+    let exec_result = ExecCommandResult {
+        stdout: output.stdout.text.clone(),
+        stderr: output.stderr.text.clone(),
+        aggregated_output: output.aggregated_output.text.clone(),
+        exit_code: output.exit_code,
+        duration: output.duration,
+        formatted_output: shaped.text,
+        formatted_output_truncated: shaped.truncated,
+        denied_by_sandbox,
+        limit_exceeded,
+    };
+    emit_exec_end(ctx, exec_input, exec_result).await;
+}
+
 async fn emit_exec_end(
     ctx: ToolEventCtx<'_>,
     exec_input: ExecCommandInput<'_>,
@@ -427,18 +538,86 @@ async fn emit_exec_end(
                 exit_code: exec_result.exit_code,
                 duration: exec_result.duration,
                 formatted_output: exec_result.formatted_output,
+                formatted_output_truncated: exec_result.formatted_output_truncated,
+                denied_by_sandbox: exec_result.denied_by_sandbox,
+                limit_exceeded: exec_result.limit_exceeded,
             }),
         )
         .await;
 }
 
+/// Attributes a patch result to the files in `changes` using the summary
+/// apply_patch prints: listed files were applied, the file marked `!` failed
+/// with `error`, and the other files were never reached. A failure without a
+/// `!` line (a rejection, a sandbox denial) applies to every unlisted file.
+pub(crate) fn patch_file_results(
+    changes: &HashMap<PathBuf, FileChange>,
+    cwd: &Path,
+    stdout: &str,
+    error: &str,
+    success: bool,
+) -> Vec<PatchFileResult> {
+    let mut applied = HashSet::new();
+    let mut failed = None;
+    for line in stdout.lines() {
+        if let Some(path) = line.strip_prefix("! ") {
+            failed = Some(cwd.join(path));
+        } else if let Some(path) = ["A ", "M ", "D "]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix))
+        {
+            applied.insert(cwd.join(path));
+        }
+    }
+    let error = error.trim();
+
+    let mut results: Vec<PatchFileResult> = changes
+        .iter()
+        .map(|(path, change)| {
+            let written = match change {
+                FileChange::Update {
+                    move_path: Some(dest),
+                    ..
+                } => dest,
+                _ => path,
+            };
+            let status = if applied.contains(written) {
+                PatchFileStatus::Applied
+            } else if success {
+                // Nothing was written, e.g. under `--dry-run-exec`.
+                PatchFileStatus::Skipped
+            } else if failed.as_ref().is_none_or(|failed| failed == path) {
+                PatchFileStatus::Failed
+            } else {
+                PatchFileStatus::Skipped
+            };
+            PatchFileResult {
+                path: path.clone(),
+                status,
+                error: (status == PatchFileStatus::Failed && !error.is_empty())
+                    .then(|| error.to_string()),
+            }
+        })
+        .collect();
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    results
+}
+
 async fn emit_patch_end(
     ctx: ToolEventCtx<'_>,
     changes: HashMap<PathBuf, FileChange>,
+    cwd: &Path,
     stdout: String,
     stderr: String,
     success: bool,
 ) {
+    if success {
+        ctx.session
+            .record_file_provenance(|provenance| provenance.record_patch(&ctx.turn.cwd, &changes))
+            .await;
+    }
+    let per_file = patch_file_results(&changes, cwd, &stdout, &stderr, success);
+    ctx.session.finish_patch_apply(ctx.call_id).await;
     ctx.session
         .send_event(
             ctx.turn,
@@ -449,6 +628,7 @@ async fn emit_patch_end(
                 stderr,
                 success,
                 changes,
+                per_file,
             }),
         )
         .await;
@@ -465,3 +645,97 @@ async fn emit_patch_end(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn update(move_path: Option<&str>) -> FileChange {
+        FileChange::Update {
+            unified_diff: String::new(),
+            move_path: move_path.map(PathBuf::from),
+        }
+    }
+
+    fn result(path: &str, status: PatchFileStatus, error: Option<&str>) -> PatchFileResult {
+        PatchFileResult {
+            path: PathBuf::from(path),
+            status,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn second_file_conflict_marks_later_files_skipped() {
+        let changes = HashMap::from([
+            (PathBuf::from("/repo/a.txt"), update(None)),
+            (PathBuf::from("/repo/b.txt"), update(None)),
+            (
+                PathBuf::from("/repo/c.txt"),
+                FileChange::Add {
+                    content: "c\n".to_string(),
+                },
+            ),
+        ]);
+        let stdout = "Failed. Updated the following files before the error:\nM a.txt\n! b.txt\n";
+        let stderr = "Failed to find expected lines in b.txt:\nzwei\n";
+
+        assert_eq!(
+            patch_file_results(&changes, Path::new("/repo"), stdout, stderr, false),
+            vec![
+                result("/repo/a.txt", PatchFileStatus::Applied, None),
+                result(
+                    "/repo/b.txt",
+                    PatchFileStatus::Failed,
+                    Some("Failed to find expected lines in b.txt:\nzwei")
+                ),
+                result("/repo/c.txt", PatchFileStatus::Skipped, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn moved_files_match_their_destination() {
+        let changes = HashMap::from([(
+            PathBuf::from("/repo/old.txt"),
+            update(Some("/repo/new.txt")),
+        )]);
+        let stdout = "Success. Updated the following files:\nM new.txt\n";
+
+        assert_eq!(
+            patch_file_results(&changes, Path::new("/repo"), stdout, "", true),
+            vec![result("/repo/old.txt", PatchFileStatus::Applied, None)]
+        );
+    }
+
+    #[test]
+    fn unattributed_failure_fails_every_file() {
+        let changes = HashMap::from([
+            (PathBuf::from("/repo/a.txt"), update(None)),
+            (PathBuf::from("/repo/b.txt"), update(None)),
+        ]);
+
+        assert_eq!(
+            patch_file_results(
+                &changes,
+                Path::new("/repo"),
+                "",
+                "patch rejected by user",
+                false
+            ),
+            vec![
+                result(
+                    "/repo/a.txt",
+                    PatchFileStatus::Failed,
+                    Some("patch rejected by user")
+                ),
+                result(
+                    "/repo/b.txt",
+                    PatchFileStatus::Failed,
+                    Some("patch rejected by user")
+                ),
+            ]
+        );
+    }
+}
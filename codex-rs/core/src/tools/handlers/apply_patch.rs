@@ -1,7 +1,9 @@
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use crate::apply_patch;
+use crate::apply_patch::ApplyPatchExec;
 use crate::apply_patch::InternalApplyPatchInvocation;
 use crate::apply_patch::convert_apply_patch_to_protocol;
 use crate::client_common::tools::FreeformTool;
@@ -11,6 +13,10 @@ use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::hooks;
+use crate::hooks::HookDetails;
+use crate::protocol::HookPoint;
+use crate::stale_patch;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -21,6 +27,7 @@ use crate::tools::handlers::parse_arguments;
 use crate::tools::orchestrator::ToolOrchestrator;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::tools::router::tool_disabled_message;
 use crate::tools::runtimes::apply_patch::ApplyPatchRequest;
 use crate::tools::runtimes::apply_patch::ApplyPatchRuntime;
 use crate::tools::sandboxing::ToolCtx;
@@ -105,7 +112,8 @@ impl ToolHandler for ApplyPatchHandler {
         let command = vec!["apply_patch".to_string(), patch_input.clone()];
         match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd) {
             codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
-                match apply_patch::apply_patch(turn.as_ref(), changes).await {
+                let approval_policy = session.effective_approval_policy(&turn).await;
+                match apply_patch::apply_patch(turn.as_ref(), approval_policy, changes).await {
                     InternalApplyPatchInvocation::Output(item) => {
                         let content = item?;
                         Ok(ToolOutput::Function {
@@ -115,45 +123,16 @@ impl ToolHandler for ApplyPatchHandler {
                         })
                     }
                     InternalApplyPatchInvocation::DelegateToExec(apply) => {
-                        let changes = convert_apply_patch_to_protocol(&apply.action);
-                        let file_paths = file_paths_for_action(&apply.action);
-                        let emitter =
-                            ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
-                        let event_ctx = ToolEventCtx::new(
+                        let content = run_apply_patch_exec(
+                            apply,
+                            None,
                             session.as_ref(),
                             turn.as_ref(),
-                            &call_id,
                             Some(&tracker),
-                        );
-                        emitter.begin(event_ctx).await;
-
-                        let req = ApplyPatchRequest {
-                            action: apply.action,
-                            file_paths,
-                            changes,
-                            exec_approval_requirement: apply.exec_approval_requirement,
-                            timeout_ms: None,
-                            codex_exe: turn.codex_linux_sandbox_exe.clone(),
-                        };
-
-                        let mut orchestrator = ToolOrchestrator::new();
-                        let mut runtime = ApplyPatchRuntime::new();
-                        let tool_ctx = ToolCtx {
-                            session: session.as_ref(),
-                            turn: turn.as_ref(),
-                            call_id: call_id.clone(),
-                            tool_name: tool_name.to_string(),
-                        };
-                        let out = orchestrator
-                            .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
-                            .await;
-                        let event_ctx = ToolEventCtx::new(
-                            session.as_ref(),
-                            turn.as_ref(),
                             &call_id,
-                            Some(&tracker),
-                        );
-                        let content = emitter.finish(event_ctx, out).await?;
+                            tool_name.as_str(),
+                        )
+                        .await?;
                         Ok(ToolOutput::Function {
                             content,
                             content_items: None,
@@ -163,9 +142,7 @@ impl ToolHandler for ApplyPatchHandler {
                 }
             }
             codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
-                Err(FunctionCallError::RespondToModel(format!(
-                    "apply_patch verification failed: {parse_error}"
-                )))
+                Err(stale_patch::verification_failure(&session, &turn, &call_id, parse_error).await)
             }
             codex_apply_patch::MaybeApplyPatchVerified::ShellParseError(error) => {
                 tracing::trace!("Failed to parse apply_patch input, {error:?}");
@@ -195,13 +172,20 @@ pub(crate) async fn intercept_apply_patch(
 ) -> Result<Option<ToolOutput>, FunctionCallError> {
     match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
+            if !turn.tools_config.tool_toggles.apply_patch {
+                return Err(FunctionCallError::RespondToModel(tool_disabled_message(
+                    "apply_patch",
+                    "apply_patch",
+                )));
+            }
             session
                 .record_model_warning(
                     format!("apply_patch was requested via {tool_name}. Use the apply_patch tool instead of exec_command."),
                     turn,
                 )
                 .await;
-            match apply_patch::apply_patch(turn, changes).await {
+            let approval_policy = session.effective_approval_policy(turn).await;
+            match apply_patch::apply_patch(turn, approval_policy, changes).await {
                 InternalApplyPatchInvocation::Output(item) => {
                     let content = item?;
                     Ok(Some(ToolOutput::Function {
@@ -211,36 +195,10 @@ pub(crate) async fn intercept_apply_patch(
                     }))
                 }
                 InternalApplyPatchInvocation::DelegateToExec(apply) => {
-                    let changes = convert_apply_patch_to_protocol(&apply.action);
-                    let approval_keys = file_paths_for_action(&apply.action);
-                    let emitter = ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
-                    let event_ctx =
-                        ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
-                    emitter.begin(event_ctx).await;
-
-                    let req = ApplyPatchRequest {
-                        action: apply.action,
-                        file_paths: approval_keys,
-                        changes,
-                        exec_approval_requirement: apply.exec_approval_requirement,
-                        timeout_ms,
-                        codex_exe: turn.codex_linux_sandbox_exe.clone(),
-                    };
-
-                    let mut orchestrator = ToolOrchestrator::new();
-                    let mut runtime = ApplyPatchRuntime::new();
-                    let tool_ctx = ToolCtx {
-                        session,
-                        turn,
-                        call_id: call_id.to_string(),
-                        tool_name: tool_name.to_string(),
-                    };
-                    let out = orchestrator
-                        .run(&mut runtime, &req, &tool_ctx, turn, turn.approval_policy)
-                        .await;
-                    let event_ctx =
-                        ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
-                    let content = emitter.finish(event_ctx, out).await?;
+                    let content = run_apply_patch_exec(
+                        apply, timeout_ms, session, turn, tracker, call_id, tool_name,
+                    )
+                    .await?;
                     Ok(Some(ToolOutput::Function {
                         content,
                         content_items: None,
@@ -250,9 +208,7 @@ pub(crate) async fn intercept_apply_patch(
             }
         }
         codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
-            Err(FunctionCallError::RespondToModel(format!(
-                "apply_patch verification failed: {parse_error}"
-            )))
+            Err(stale_patch::verification_failure(session, turn, call_id, parse_error).await)
         }
         codex_apply_patch::MaybeApplyPatchVerified::ShellParseError(error) => {
             tracing::trace!("Failed to parse apply_patch input, {error:?}");
@@ -262,6 +218,70 @@ pub(crate) async fn intercept_apply_patch(
     }
 }
 
+/// Runs a verified patch through the apply_patch runtime: emits the begin and
+/// end events, requests approval when `apply` requires it, and returns the
+/// content to send back to the model.
+pub(crate) async fn run_apply_patch_exec(
+    apply: ApplyPatchExec,
+    timeout_ms: Option<u64>,
+    session: &Session,
+    turn: &TurnContext,
+    tracker: Option<&SharedTurnDiffTracker>,
+    call_id: &str,
+    tool_name: &str,
+) -> Result<String, FunctionCallError> {
+    let changes = convert_apply_patch_to_protocol(&apply.action);
+    let mut files_changed: Vec<PathBuf> = changes.keys().cloned().collect();
+    files_changed.sort();
+    let file_paths = file_paths_for_action(&apply.action);
+    let emitter = ToolEmitter::apply_patch(
+        changes.clone(),
+        apply.action.cwd.clone(),
+        apply.auto_approved,
+    );
+    let event_ctx = ToolEventCtx::new(session, turn, call_id, tracker);
+    emitter.begin(event_ctx).await;
+
+    let req = ApplyPatchRequest {
+        action: apply.action,
+        file_paths,
+        changes,
+        dirty_paths: apply.dirty_paths,
+        exec_approval_requirement: apply.exec_approval_requirement,
+        timeout_ms,
+        codex_exe: turn.codex_linux_sandbox_exe.clone(),
+    };
+
+    let mut orchestrator = ToolOrchestrator::new();
+    let mut runtime = ApplyPatchRuntime::new();
+    let tool_ctx = ToolCtx {
+        session,
+        turn,
+        call_id: call_id.to_string(),
+        tool_name: tool_name.to_string(),
+    };
+    let approval_policy = session.effective_approval_policy(turn).await;
+    let out = orchestrator
+        .run(&mut runtime, &req, &tool_ctx, turn, approval_policy)
+        .await;
+    let event_ctx = ToolEventCtx::new(session, turn, call_id, tracker);
+    let content = emitter.finish(event_ctx, out).await?;
+    session.clear_stale_patches(&files_changed).await;
+
+    hooks::run_hooks(
+        session,
+        turn,
+        HookPoint::PostPatch,
+        HookDetails {
+            files_changed,
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(FunctionCallError::Fatal)?;
+    Ok(content)
+}
+
 /// Returns a custom tool that can be used to edit files. Well-suited for GPT-5 models
 /// https://platform.openai.com/docs/guides/function-calling#custom-tools
 pub(crate) fn create_apply_patch_freeform_tool() -> ToolSpec {
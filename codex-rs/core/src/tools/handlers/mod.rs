@@ -7,6 +7,7 @@ mod mcp;
 mod mcp_resource;
 mod plan;
 mod read_file;
+mod remember;
 mod request_user_input;
 mod shell;
 mod test_sync;
@@ -26,6 +27,7 @@ pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use plan::PlanHandler;
 pub use read_file::ReadFileHandler;
+pub use remember::RememberHandler;
 pub use request_user_input::RequestUserInputHandler;
 pub use shell::ShellCommandHandler;
 pub use shell::ShellHandler;
@@ -0,0 +1,138 @@
+use std::io::ErrorKind;
+
+use async_trait::async_trait;
+use codex_apply_patch::MaybeApplyPatchVerified;
+use codex_protocol::protocol::AskForApproval;
+use serde::Deserialize;
+
+use crate::apply_patch;
+use crate::apply_patch::ApplyPatchExec;
+use crate::apply_patch::InternalApplyPatchInvocation;
+use crate::function_tool::FunctionCallError;
+use crate::project_memory::normalize_note;
+use crate::project_memory::project_memory_path;
+use crate::project_memory::remember_patch;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::apply_patch::run_apply_patch_exec;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+
+pub struct RememberHandler;
+
+#[derive(Deserialize)]
+struct RememberArgs {
+    note: String,
+}
+
+#[async_trait]
+impl ToolHandler for RememberHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tracker,
+            call_id,
+            tool_name,
+            payload,
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "remember handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let args: RememberArgs = parse_arguments(&arguments)?;
+        let note = normalize_note(&args.note).map_err(FunctionCallError::RespondToModel)?;
+
+        let path = project_memory_path(&turn.cwd);
+        let existing = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => Some(contents),
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "failed to read project memory {}: {err}",
+                    path.display()
+                )));
+            }
+        };
+
+        // Route the edit through apply_patch so it gets the same approval
+        // prompt, diff tracking, and events as any other file change.
+        let patch = remember_patch(&path, existing.as_deref(), &note);
+        let command = vec!["apply_patch".to_string(), patch];
+        let action = match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &turn.cwd)
+        {
+            MaybeApplyPatchVerified::Body(action) => action,
+            MaybeApplyPatchVerified::CorrectnessError(err) => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "failed to update project memory: {err}"
+                )));
+            }
+            MaybeApplyPatchVerified::ShellParseError(_)
+            | MaybeApplyPatchVerified::NotApplyPatch => {
+                return Err(FunctionCallError::RespondToModel(
+                    "failed to update project memory".to_string(),
+                ));
+            }
+        };
+
+        let approval_policy = session.effective_approval_policy(&turn).await;
+        let apply = match apply_patch::apply_patch(turn.as_ref(), approval_policy, action).await {
+            InternalApplyPatchInvocation::Output(item) => {
+                return Ok(ToolOutput::Function {
+                    content: item?,
+                    content_items: None,
+                    success: Some(true),
+                });
+            }
+            InternalApplyPatchInvocation::DelegateToExec(apply) => {
+                require_review(apply, approval_policy)
+            }
+        };
+        let content = run_apply_patch_exec(
+            apply,
+            None,
+            session.as_ref(),
+            turn.as_ref(),
+            Some(&tracker),
+            &call_id,
+            tool_name.as_str(),
+        )
+        .await?;
+
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// Notes are shown to the user before they are written, even when the sandbox
+/// policy would auto-approve the edit. Only `approval_policy = "never"` skips
+/// the prompt.
+fn require_review(mut apply: ApplyPatchExec, approval_policy: AskForApproval) -> ApplyPatchExec {
+    if !matches!(approval_policy, AskForApproval::Never) {
+        apply.auto_approved = false;
+        apply.exec_approval_requirement = ExecApprovalRequirement::NeedsApproval {
+            reason: None,
+            proposed_execpolicy_amendment: None,
+        };
+    }
+    apply
+}
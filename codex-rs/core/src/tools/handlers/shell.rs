@@ -8,8 +8,10 @@ use crate::exec::ExecParams;
 use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::function_tool::FunctionCallError;
-use crate::is_safe_command::is_known_safe_command;
+use crate::hooks;
+use crate::hooks::HookDetails;
 use crate::protocol::ExecCommandSource;
+use crate::protocol::HookPoint;
 use crate::shell::Shell;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -46,11 +48,19 @@ impl ShellHandler {
             command: params.command.clone(),
             cwd: turn_context.resolve_path(params.workdir.clone()),
             expiration: params.timeout_ms.into(),
-            env: create_env(&turn_context.shell_environment_policy),
+            env: create_env(
+                &turn_context.shell_environment_policy,
+                turn_context.client.config().dotenv.as_deref(),
+            ),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: params.justification.clone(),
             arg0: None,
+            limits: turn_context
+                .client
+                .config()
+                .exec_limits
+                .with_overrides(params.limits.as_ref()),
         }
     }
 }
@@ -73,11 +83,19 @@ impl ShellCommandHandler {
             command,
             cwd: turn_context.resolve_path(params.workdir.clone()),
             expiration: params.timeout_ms.into(),
-            env: create_env(&turn_context.shell_environment_policy),
+            env: create_env(
+                &turn_context.shell_environment_policy,
+                turn_context.client.config().dotenv.as_deref(),
+            ),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: params.justification.clone(),
             arg0: None,
+            limits: turn_context
+                .client
+                .config()
+                .exec_limits
+                .with_overrides(params.limits.as_ref()),
         }
     }
 }
@@ -96,13 +114,16 @@ impl ToolHandler for ShellHandler {
     }
 
     async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        let exec_policy = &invocation.session.services.exec_policy;
         match &invocation.payload {
             ToolPayload::Function { arguments } => {
                 serde_json::from_str::<ShellToolCallParams>(arguments)
-                    .map(|params| !is_known_safe_command(&params.command))
+                    .map(|params| !exec_policy.is_known_safe_command(&params.command))
                     .unwrap_or(true)
             }
-            ToolPayload::LocalShell { params } => !is_known_safe_command(&params.command),
+            ToolPayload::LocalShell { params } => {
+                !exec_policy.is_known_safe_command(&params.command)
+            }
             _ => true, // unknown payloads => assume mutating
         }
     }
@@ -174,7 +195,11 @@ impl ToolHandler for ShellCommandHandler {
             .map(|params| {
                 let shell = invocation.session.user_shell();
                 let command = Self::base_command(shell.as_ref(), &params.command, params.login);
-                !is_known_safe_command(&command)
+                !invocation
+                    .session
+                    .services
+                    .exec_policy
+                    .is_known_safe_command(&command)
             })
             .unwrap_or(true)
     }
@@ -239,16 +264,17 @@ impl ShellHandler {
             exec_params.env.extend(dependency_env);
         }
 
+        let approval_policy = session.effective_approval_policy(&turn).await;
+
         // Approval policy guard for explicit escalation in non-OnRequest modes.
         if exec_params
             .sandbox_permissions
             .requires_escalated_permissions()
             && !matches!(
-                turn.approval_policy,
+                approval_policy,
                 codex_protocol::protocol::AskForApproval::OnRequest
             )
         {
-            let approval_policy = turn.approval_policy;
             return Err(FunctionCallError::RespondToModel(format!(
                 "approval policy is {approval_policy:?}; reject command — you should not ask for escalated permissions if the approval policy is {approval_policy:?}"
             )));
@@ -270,6 +296,18 @@ impl ShellHandler {
             return Ok(output);
         }
 
+        hooks::run_hooks(
+            session.as_ref(),
+            turn.as_ref(),
+            HookPoint::PreExec,
+            HookDetails {
+                command: Some(exec_params.command.clone()),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(FunctionCallError::Fatal)?;
+
         let source = ExecCommandSource::Agent;
         let emitter = ToolEmitter::shell(
             exec_params.command.clone(),
@@ -286,10 +324,11 @@ impl ShellHandler {
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 features: &features,
                 command: &exec_params.command,
-                approval_policy: turn.approval_policy,
+                approval_policy,
                 sandbox_policy: &turn.sandbox_policy,
                 sandbox_permissions: exec_params.sandbox_permissions,
                 prefix_rule,
+                cwd_outside_workspace: !turn.is_in_workspace(&exec_params.cwd),
             })
             .await;
 
@@ -301,6 +340,7 @@ impl ShellHandler {
             sandbox_permissions: exec_params.sandbox_permissions,
             justification: exec_params.justification.clone(),
             exec_approval_requirement,
+            limits: exec_params.limits,
         };
         let mut orchestrator = ToolOrchestrator::new();
         let mut runtime = ShellRuntime::new();
@@ -311,7 +351,7 @@ impl ShellHandler {
             tool_name,
         };
         let out = orchestrator
-            .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
+            .run(&mut runtime, &req, &tool_ctx, &turn, approval_policy)
             .await;
         let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
         let content = emitter.finish(event_ctx, out).await?;
@@ -403,7 +443,10 @@ mod tests {
 
         let expected_command = session.user_shell().derive_exec_args(&command, true);
         let expected_cwd = turn_context.resolve_path(workdir.clone());
-        let expected_env = create_env(&turn_context.shell_environment_policy);
+        let expected_env = create_env(
+            &turn_context.shell_environment_policy,
+            turn_context.client.config().dotenv.as_deref(),
+        );
 
         let params = ShellCommandToolCallParams {
             command,
@@ -413,6 +456,7 @@ mod tests {
             sandbox_permissions: Some(sandbox_permissions),
             prefix_rule: None,
             justification: justification.clone(),
+            limits: None,
         };
 
         let exec_params = ShellCommandHandler::to_exec_params(&params, &session, &turn_context);
@@ -1,6 +1,8 @@
 use crate::function_tool::FunctionCallError;
-use crate::is_safe_command::is_known_safe_command;
+use crate::hooks;
+use crate::hooks::HookDetails;
 use crate::protocol::EventMsg;
+use crate::protocol::HookPoint;
 use crate::protocol::TerminalInteractionEvent;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::Shell;
@@ -57,6 +59,8 @@ struct WriteStdinArgs {
     yield_time_ms: u64,
     #[serde(default)]
     max_output_tokens: Option<usize>,
+    #[serde(default)]
+    terminate: bool,
 }
 
 fn default_exec_yield_time_ms() -> u64 {
@@ -98,7 +102,11 @@ impl ToolHandler for UnifiedExecHandler {
             return true;
         };
         let command = get_command(&params, invocation.session.user_shell());
-        !is_known_safe_command(&command)
+        !invocation
+            .session
+            .services
+            .exec_policy
+            .is_known_safe_command(&command)
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
@@ -149,13 +157,13 @@ impl ToolHandler for UnifiedExecHandler {
                     None
                 };
 
+                let approval_policy = session.effective_approval_policy(&context.turn).await;
                 if sandbox_permissions.requires_escalated_permissions()
                     && !matches!(
-                        context.turn.approval_policy,
+                        approval_policy,
                         codex_protocol::protocol::AskForApproval::OnRequest
                     )
                 {
-                    let approval_policy = context.turn.approval_policy;
                     manager.release_process_id(&process_id).await;
                     return Err(FunctionCallError::RespondToModel(format!(
                         "approval policy is {approval_policy:?}; reject command — you cannot ask for escalated permissions if the approval policy is {approval_policy:?}"
@@ -183,6 +191,21 @@ impl ToolHandler for UnifiedExecHandler {
                     return Ok(output);
                 }
 
+                if let Err(message) = hooks::run_hooks(
+                    context.session.as_ref(),
+                    context.turn.as_ref(),
+                    HookPoint::PreExec,
+                    HookDetails {
+                        command: Some(command.clone()),
+                        ..Default::default()
+                    },
+                )
+                .await
+                {
+                    manager.release_process_id(&process_id).await;
+                    return Err(FunctionCallError::Fatal(message));
+                }
+
                 manager
                     .exec_command(
                         ExecCommandRequest {
@@ -211,6 +234,7 @@ impl ToolHandler for UnifiedExecHandler {
                         input: &args.chars,
                         yield_time_ms: args.yield_time_ms,
                         max_output_tokens: args.max_output_tokens,
+                        terminate: args.terminate,
                     })
                     .await
                     .map_err(|err| {
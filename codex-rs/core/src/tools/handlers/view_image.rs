@@ -5,6 +5,7 @@ use tokio::fs;
 use crate::function_tool::FunctionCallError;
 use crate::protocol::EventMsg;
 use crate::protocol::ViewImageToolCallEvent;
+use crate::request_size;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -13,7 +14,8 @@ use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseInputItem;
-use codex_protocol::models::local_image_content_items_with_label_number;
+use codex_protocol::models::local_image_content_items_with_limits;
+use codex_protocol::user_input::UserInput;
 
 pub struct ViewImageHandler;
 
@@ -65,8 +67,15 @@ impl ToolHandler for ViewImageHandler {
         }
         let event_path = abs_path.clone();
 
+        let image_limits = turn.client.config().image_limits;
+        let downscale_events = request_size::local_image_events(
+            &[UserInput::LocalImage {
+                path: abs_path.clone(),
+            }],
+            image_limits,
+        );
         let content: Vec<ContentItem> =
-            local_image_content_items_with_label_number(&abs_path, None);
+            local_image_content_items_with_limits(&abs_path, None, image_limits);
         let input = ResponseInputItem::Message {
             role: "user".to_string(),
             content,
@@ -81,6 +90,9 @@ impl ToolHandler for ViewImageHandler {
                 )
             })?;
 
+        for event in downscale_events {
+            session.send_event(turn.as_ref(), event).await;
+        }
         session
             .send_event(
                 turn.as_ref(),
@@ -1,7 +1,9 @@
+pub(crate) mod arguments;
 pub mod context;
 pub mod events;
 pub(crate) mod handlers;
 pub mod orchestrator;
+pub(crate) mod output_shaping;
 pub mod parallel;
 pub mod registry;
 pub mod router;
@@ -10,9 +12,10 @@ pub mod sandboxing;
 pub mod spec;
 
 use crate::exec::ExecToolCallOutput;
+use crate::protocol::PatchFileResult;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::formatted_truncate_text;
-use crate::truncate::truncate_text;
+use output_shaping::ShapedOutput;
+use output_shaping::shape_output_for_model;
 pub use router::ToolRouter;
 use serde::Serialize;
 
@@ -27,6 +30,27 @@ pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
 pub fn format_exec_output_for_model_structured(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    max_lines: Option<usize>,
+) -> String {
+    format_structured_output(exec_output, None, truncation_policy, max_lines)
+}
+
+/// Like [`format_exec_output_for_model_structured`], with the per-file
+/// results of a failed patch so the model knows which files were written.
+pub(crate) fn format_patch_failure_for_model(
+    exec_output: &ExecToolCallOutput,
+    per_file: &[PatchFileResult],
+    truncation_policy: TruncationPolicy,
+    max_lines: Option<usize>,
+) -> String {
+    format_structured_output(exec_output, Some(per_file), truncation_policy, max_lines)
+}
+
+fn format_structured_output(
+    exec_output: &ExecToolCallOutput,
+    per_file: Option<&[PatchFileResult]>,
+    truncation_policy: TruncationPolicy,
+    max_lines: Option<usize>,
 ) -> String {
     let ExecToolCallOutput {
         exit_code,
@@ -44,12 +68,14 @@ pub fn format_exec_output_for_model_structured(
     struct ExecOutput<'a> {
         output: &'a str,
         metadata: ExecMetadata,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        per_file: Option<&'a [PatchFileResult]>,
     }
 
     // round to 1 decimal place
     let duration_seconds = ((duration.as_secs_f32()) * 10.0).round() / 10.0;
 
-    let formatted_output = format_exec_output_str(exec_output, truncation_policy);
+    let formatted_output = format_exec_output_str(exec_output, truncation_policy, max_lines);
 
     let payload = ExecOutput {
         output: &formatted_output,
@@ -57,6 +83,7 @@ pub fn format_exec_output_for_model_structured(
             exit_code: *exit_code,
             duration_seconds,
         },
+        per_file,
     };
 
     #[expect(clippy::expect_used)]
@@ -66,6 +93,7 @@ pub fn format_exec_output_for_model_structured(
 pub fn format_exec_output_for_model_freeform(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    max_lines: Option<usize>,
 ) -> String {
     // round to 1 decimal place
     let duration_seconds = ((exec_output.duration.as_secs_f32()) * 10.0).round() / 10.0;
@@ -74,7 +102,7 @@ pub fn format_exec_output_for_model_freeform(
 
     let total_lines = content.lines().count();
 
-    let formatted_output = truncate_text(&content, truncation_policy);
+    let formatted_output = shape_output_for_model(&content, truncation_policy, max_lines).text;
 
     let mut sections = Vec::new();
 
@@ -93,11 +121,28 @@ pub fn format_exec_output_for_model_freeform(
 pub fn format_exec_output_str(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    max_lines: Option<usize>,
 ) -> String {
-    let content = build_content_with_timeout(exec_output);
+    shape_exec_output(exec_output, truncation_policy, max_lines).text
+}
 
-    // Truncate for model consumption before serialization.
-    formatted_truncate_text(&content, truncation_policy)
+/// Shapes exec output for model consumption, prefixing the total line count
+/// when anything was cut so the model knows it saw an abridged copy.
+pub(crate) fn shape_exec_output(
+    exec_output: &ExecToolCallOutput,
+    truncation_policy: TruncationPolicy,
+    max_lines: Option<usize>,
+) -> ShapedOutput {
+    let content = build_content_with_timeout(exec_output);
+    let shaped = shape_output_for_model(&content, truncation_policy, max_lines);
+    if !shaped.truncated {
+        return shaped;
+    }
+    let total_lines = content.lines().count();
+    ShapedOutput {
+        text: format!("Total output lines: {total_lines}\n\n{}", shaped.text),
+        truncated: true,
+    }
 }
 
 /// Extracts exec output content and prepends a timeout message if the command timed out.
@@ -8,6 +8,8 @@ retry without sandbox on denial (no re‑approval thanks to caching).
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
+use crate::exec::SandboxType;
+use crate::exec::sandbox_denial_reason;
 use crate::sandboxing::SandboxManager;
 use crate::tools::sandboxing::ApprovalCtx;
 use crate::tools::sandboxing::ExecApprovalRequirement;
@@ -127,7 +129,8 @@ impl ToolOrchestrator {
 
                 // Ask for approval before retrying without sandbox.
                 if !tool.should_bypass_approval(approval_policy, already_approved) {
-                    let reason_msg = build_denial_reason_from_output(output.as_ref());
+                    let reason_msg =
+                        build_denial_reason_from_output(initial_sandbox, output.as_ref());
                     let approval_ctx = ApprovalCtx {
                         session: tool_ctx.session,
                         turn: turn_ctx,
@@ -165,8 +168,9 @@ impl ToolOrchestrator {
     }
 }
 
-fn build_denial_reason_from_output(_output: &ExecToolCallOutput) -> String {
-    // Keep approval reason terse and stable for UX/tests, but accept the
-    // output so we can evolve heuristics later without touching call sites.
-    "command failed; retry without sandbox?".to_string()
+fn build_denial_reason_from_output(sandbox: SandboxType, output: &ExecToolCallOutput) -> String {
+    match sandbox_denial_reason(sandbox, output) {
+        Some(reason) => format!("command blocked by sandbox ({reason}); retry without sandbox?"),
+        None => "command failed; retry without sandbox?".to_string(),
+    }
 }
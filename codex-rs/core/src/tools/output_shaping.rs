@@ -0,0 +1,271 @@
+//! Shapes command output before it is sent back to the model.
+//!
+//! Only the model-facing copy is shaped; the UI and rollout keep the full
+//! output. Plain overflow falls back to the head+tail truncation in
+//! [`crate::truncate`]. When a line limit is configured, or when the output
+//! has a recognizable section the model needs (the last failing `cargo test`
+//! block, compiler errors), whole lines are kept from that section first and
+//! then from the head and tail, with an elision marker for each gap.
+
+use std::ops::Range;
+
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+/// Bytes reserved per elision marker. Shaped output has at most three gaps:
+/// before the focus section, after it, and between head and tail.
+const ELISION_MARKER_RESERVE: usize = 48;
+const MAX_ELISION_MARKERS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ShapedOutput {
+    pub(crate) text: String,
+    /// Whether anything was cut from the original output.
+    pub(crate) truncated: bool,
+}
+
+pub(crate) fn shape_output_for_model(
+    content: &str,
+    policy: TruncationPolicy,
+    max_lines: Option<usize>,
+) -> ShapedOutput {
+    let lines: Vec<&str> = content.lines().collect();
+    let over_lines = max_lines.is_some_and(|max| lines.len() > max);
+    let over_bytes = content.len() > policy.byte_budget();
+    if !over_lines && !over_bytes {
+        return ShapedOutput {
+            text: content.to_string(),
+            truncated: false,
+        };
+    }
+
+    let focus = find_focus_section(&lines);
+    if over_lines || focus.is_some() {
+        let keep = select_lines(&lines, focus, max_lines.unwrap_or(usize::MAX), policy);
+        if keep.iter().any(|kept| *kept) {
+            return ShapedOutput {
+                text: truncate_text(&render_kept_lines(&lines, &keep), policy),
+                truncated: true,
+            };
+        }
+    }
+
+    ShapedOutput {
+        text: truncate_text(content, policy),
+        truncated: true,
+    }
+}
+
+/// Returns the line range the model most needs to see, if the output has a
+/// recognizable format: the last failing test block from `cargo test`, or
+/// else the compiler error section.
+fn find_focus_section(lines: &[&str]) -> Option<Range<usize>> {
+    last_failing_test_block(lines).or_else(|| compiler_error_section(lines))
+}
+
+fn last_failing_test_block(lines: &[&str]) -> Option<Range<usize>> {
+    let start = lines
+        .iter()
+        .rposition(|line| line.starts_with("---- ") && line.ends_with(" stdout ----"))?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.starts_with("---- ") || *line == "failures:")
+        .map_or(lines.len(), |offset| start + 1 + offset);
+    Some(start..end)
+}
+
+fn compiler_error_section(lines: &[&str]) -> Option<Range<usize>> {
+    let is_error_header = |line: &&str| line.starts_with("error[") || line.starts_with("error:");
+    let start = lines.iter().position(is_error_header)?;
+    let last_header = lines.iter().rposition(is_error_header)?;
+    let end = lines[last_header..]
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .map_or(lines.len(), |offset| last_header + offset);
+    Some(start..end)
+}
+
+/// Picks whole lines to keep within both the line and byte budgets. The focus
+/// section gets up to half of each budget; the rest alternates between head
+/// and tail.
+fn select_lines(
+    lines: &[&str],
+    focus: Option<Range<usize>>,
+    max_lines: usize,
+    policy: TruncationPolicy,
+) -> Vec<bool> {
+    let max_bytes = policy
+        .byte_budget()
+        .saturating_sub(ELISION_MARKER_RESERVE * MAX_ELISION_MARKERS);
+    let mut keep = vec![false; lines.len()];
+    let mut used_lines = 0usize;
+    let mut used_bytes = 0usize;
+    let mut try_keep = |idx: usize, line_cap: usize, byte_cap: usize, keep: &mut [bool]| {
+        let cost = lines[idx].len() + 1;
+        if used_lines >= line_cap || used_bytes + cost > byte_cap {
+            return false;
+        }
+        keep[idx] = true;
+        used_lines += 1;
+        used_bytes += cost;
+        true
+    };
+
+    if let Some(focus) = focus {
+        for idx in focus {
+            if !try_keep(idx, max_lines / 2, max_bytes / 2, &mut keep) {
+                break;
+            }
+        }
+    }
+
+    let (mut head, mut tail) = (0usize, lines.len());
+    let (mut head_open, mut tail_open) = (true, true);
+    while (head_open || tail_open) && head < tail {
+        if head_open {
+            if keep[head] || try_keep(head, max_lines, max_bytes, &mut keep) {
+                head += 1;
+            } else {
+                head_open = false;
+            }
+        }
+        if tail_open && head < tail {
+            if keep[tail - 1] || try_keep(tail - 1, max_lines, max_bytes, &mut keep) {
+                tail -= 1;
+            } else {
+                tail_open = false;
+            }
+        }
+    }
+    keep
+}
+
+fn render_kept_lines(lines: &[&str], keep: &[bool]) -> String {
+    let mut out = String::new();
+    let mut omitted = 0usize;
+    for (line, kept) in lines.iter().zip(keep) {
+        if !kept {
+            omitted += 1;
+            continue;
+        }
+        if omitted > 0 {
+            out.push_str(&format!("…{omitted} lines omitted…\n"));
+            omitted = 0;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    if omitted > 0 {
+        out.push_str(&format!("…{omitted} lines omitted…\n"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn output_within_limits_is_unchanged() {
+        let content = "one\ntwo\nthree\n";
+
+        assert_eq!(
+            shape_output_for_model(content, TruncationPolicy::Bytes(1_000), Some(3)),
+            ShapedOutput {
+                text: content.to_string(),
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn line_limit_keeps_head_and_tail_with_elision_marker() {
+        let content: String = (0..10).map(|idx| format!("line {idx}\n")).collect();
+
+        assert_eq!(
+            shape_output_for_model(&content, TruncationPolicy::Bytes(1_000), Some(4)),
+            ShapedOutput {
+                text: "line 0\nline 1\n…6 lines omitted…\nline 8\nline 9\n".to_string(),
+                truncated: true,
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_last_failing_test_block() {
+        let mut content = String::from("running 200 tests\n");
+        for idx in 0..100 {
+            content.push_str(&format!("test ok_{idx} ... ok\n"));
+        }
+        content.push_str("\nfailures:\n\n");
+        content.push_str("---- first stdout ----\nfirst panicked\n\n");
+        content.push_str("---- second stdout ----\nsecond panicked at src/lib.rs:3\n\n");
+        content.push_str("failures:\n    first\n    second\n");
+        content.push_str("test result: FAILED. 98 passed; 2 failed\n");
+
+        let shaped = shape_output_for_model(&content, TruncationPolicy::Bytes(10_000), Some(12));
+
+        assert_eq!(
+            shaped,
+            ShapedOutput {
+                text: [
+                    "running 200 tests",
+                    "test ok_0 ... ok",
+                    "test ok_1 ... ok",
+                    "test ok_2 ... ok",
+                    "test ok_3 ... ok",
+                    "…102 lines omitted…",
+                    "---- second stdout ----",
+                    "second panicked at src/lib.rs:3",
+                    "",
+                    "failures:",
+                    "    first",
+                    "    second",
+                    "test result: FAILED. 98 passed; 2 failed",
+                    "",
+                ]
+                .join("\n"),
+                truncated: true,
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_compiler_errors_when_over_byte_budget() {
+        let mut content = String::new();
+        for idx in 0..200 {
+            content.push_str(&format!("   Compiling crate_{idx} v0.1.0\n"));
+        }
+        content.push_str("error[E0308]: mismatched types\n --> src/main.rs:2:18\n\n");
+        for idx in 0..200 {
+            content.push_str(&format!("   Compiling late_{idx} v0.1.0\n"));
+        }
+
+        let shaped = shape_output_for_model(&content, TruncationPolicy::Bytes(400), None);
+
+        assert!(shaped.truncated);
+        assert!(
+            shaped
+                .text
+                .contains("error[E0308]: mismatched types\n --> src/main.rs:2:18\n"),
+            "expected compiler error to survive: {}",
+            shaped.text
+        );
+        assert!(shaped.text.starts_with("   Compiling crate_0 v0.1.0\n"));
+        assert!(shaped.text.ends_with("   Compiling late_199 v0.1.0\n"));
+    }
+
+    #[test]
+    fn single_long_line_falls_back_to_byte_truncation() {
+        let content = "a".repeat(100);
+
+        assert_eq!(
+            shape_output_for_model(&content, TruncationPolicy::Bytes(10), Some(5)),
+            ShapedOutput {
+                text: truncate_text(&content, TruncationPolicy::Bytes(10)),
+                truncated: true,
+            }
+        );
+    }
+}
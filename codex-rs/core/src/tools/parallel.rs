@@ -13,6 +13,7 @@ use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::function_tool::FunctionCallError;
+use crate::protocol::CancelledWork;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolPayload;
 use crate::tools::router::ToolCall;
@@ -70,7 +71,18 @@ impl ToolCallRuntime {
 
         let handle: AbortOnDropHandle<Result<ResponseInputItem, FunctionCallError>> =
             AbortOnDropHandle::new(tokio::spawn(async move {
-                tokio::select! {
+                session
+                    .start_cancellable_work(
+                        &call.call_id,
+                        CancelledWork::ToolCall {
+                            call_id: call.call_id.clone(),
+                            tool_name: call.tool_name.clone(),
+                        },
+                    )
+                    .await;
+                // Cancelling drops the dispatch future, which kills a running
+                // command's process group and cancels an MCP request.
+                let result = tokio::select! {
                     _ = cancellation_token.cancelled() => {
                         let secs = started.elapsed().as_secs_f32().max(0.1);
                         dispatch_span.record("aborted", true);
@@ -84,11 +96,13 @@ impl ToolCallRuntime {
                         };
 
                         router
-                            .dispatch_tool_call(session, turn, tracker, call.clone())
+                            .dispatch_tool_call(Arc::clone(&session), turn, tracker, call.clone())
                             .instrument(dispatch_span.clone())
                             .await
                     } => res,
-                }
+                };
+                session.finish_cancellable_work(&call.call_id).await;
+                result
             }));
 
         async move {
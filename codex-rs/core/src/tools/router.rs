@@ -2,7 +2,13 @@ use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::injection_guard;
+use crate::protocol::EventMsg;
+use crate::protocol::ToolArgumentsRepairedEvent;
 use crate::sandboxing::SandboxPermissions;
+use crate::tools::arguments::ArgumentsCheck;
+use crate::tools::arguments::check_arguments;
+use crate::tools::arguments::invalid_arguments_message;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolPayload;
@@ -15,6 +21,8 @@ use codex_protocol::models::LocalShellAction;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::protocol::ToolToggles;
+use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::instrument;
@@ -37,12 +45,22 @@ impl ToolRouter {
         mcp_tools: Option<HashMap<String, mcp_types::Tool>>,
         dynamic_tools: &[DynamicToolSpec],
     ) -> Self {
+        let mcp_tools = mcp_tools.filter(|_| config.tool_toggles.mcp);
         let builder = build_specs(config, mcp_tools, dynamic_tools);
         let (specs, registry) = builder.build();
 
         Self { registry, specs }
     }
 
+    /// A router that offers no tools, for ask turns. Any call the model makes
+    /// anyway is answered as unsupported.
+    pub fn without_tools() -> Self {
+        Self {
+            registry: ToolRegistry::new(HashMap::new()),
+            specs: Vec::new(),
+        }
+    }
+
     pub fn specs(&self) -> Vec<ToolSpec> {
         self.specs
             .iter()
@@ -116,6 +134,7 @@ impl ToolRouter {
                             sandbox_permissions: Some(SandboxPermissions::UseDefault),
                             prefix_rule: None,
                             justification: None,
+                            limits: None,
                         };
                         Ok(Some(ToolCall {
                             tool_name: "local_shell".to_string(),
@@ -140,22 +159,54 @@ impl ToolRouter {
         let ToolCall {
             tool_name,
             call_id,
-            payload,
+            mut payload,
         } = call;
         let payload_outputs_custom = matches!(payload, ToolPayload::Custom { .. });
         let failure_call_id = call_id.clone();
 
+        // The model may still call a tool it saw earlier in the conversation.
+        if let Some(group) =
+            disabled_tool_group(&tool_name, &payload, turn.tools_config.tool_toggles)
+        {
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                FunctionCallError::RespondToModel(tool_disabled_message(&tool_name, group)),
+            ));
+        }
+
+        if let Err(message) = self
+            .repair_arguments(&session, &turn, &tool_name, &failure_call_id, &mut payload)
+            .await
+        {
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                FunctionCallError::RespondToModel(message),
+            ));
+        }
+
         let invocation = ToolInvocation {
-            session,
-            turn,
+            session: Arc::clone(&session),
+            turn: Arc::clone(&turn),
             tracker,
             call_id,
-            tool_name,
+            tool_name: tool_name.clone(),
             payload,
         };
 
         match self.registry.dispatch(invocation).await {
-            Ok(response) => Ok(response),
+            Ok(response) => {
+                injection_guard::inspect_tool_output(
+                    &session,
+                    &turn,
+                    &failure_call_id,
+                    &tool_name,
+                    &response,
+                )
+                .await;
+                Ok(response)
+            }
             Err(FunctionCallError::Fatal(message)) => Err(FunctionCallError::Fatal(message)),
             Err(err) => Ok(Self::failure_response(
                 failure_call_id,
@@ -165,6 +216,66 @@ impl ToolRouter {
         }
     }
 
+    /// Replaces almost-JSON function arguments with their repaired form, or
+    /// returns the error to send the model when they cannot be parsed.
+    async fn repair_arguments(
+        &self,
+        session: &Session,
+        turn: &TurnContext,
+        tool_name: &str,
+        call_id: &str,
+        payload: &mut ToolPayload,
+    ) -> Result<(), String> {
+        let arguments = match payload {
+            ToolPayload::Function { arguments }
+            | ToolPayload::Mcp {
+                raw_arguments: arguments,
+                ..
+            } => arguments,
+            _ => return Ok(()),
+        };
+        match check_arguments(arguments) {
+            ArgumentsCheck::Valid => Ok(()),
+            ArgumentsCheck::Repaired {
+                arguments: repaired,
+                repairs,
+            } => {
+                *arguments = repaired;
+                session
+                    .send_event(
+                        turn,
+                        EventMsg::ToolArgumentsRepaired(ToolArgumentsRepairedEvent {
+                            call_id: call_id.to_string(),
+                            tool_name: tool_name.to_string(),
+                            repairs,
+                        }),
+                    )
+                    .await;
+                Ok(())
+            }
+            ArgumentsCheck::Invalid(err) => {
+                let schema = self.parameters_schema(tool_name);
+                Err(invalid_arguments_message(
+                    tool_name,
+                    arguments,
+                    &err,
+                    schema.as_deref(),
+                ))
+            }
+        }
+    }
+
+    /// The JSON schema of a function tool's parameters.
+    fn parameters_schema(&self, tool_name: &str) -> Option<String> {
+        self.specs
+            .iter()
+            .find_map(|config| match &config.spec {
+                ToolSpec::Function(tool) if tool.name == tool_name => Some(&tool.parameters),
+                _ => None,
+            })
+            .and_then(|parameters| serde_json::to_string(parameters).ok())
+    }
+
     fn failure_response(
         call_id: String,
         payload_outputs_custom: bool,
@@ -188,3 +299,120 @@ impl ToolRouter {
         }
     }
 }
+
+const SHELL_TOOL_NAMES: [&str; 6] = [
+    "shell",
+    "container.exec",
+    "local_shell",
+    "shell_command",
+    "exec_command",
+    "write_stdin",
+];
+const MCP_RESOURCE_TOOL_NAMES: [&str; 3] = [
+    "list_mcp_resources",
+    "list_mcp_resource_templates",
+    "read_mcp_resource",
+];
+
+/// Name of the disabled toggle group that `tool_name` belongs to, if any.
+fn disabled_tool_group(
+    tool_name: &str,
+    payload: &ToolPayload,
+    toggles: ToolToggles,
+) -> Option<&'static str> {
+    let is_shell =
+        matches!(payload, ToolPayload::LocalShell { .. }) || SHELL_TOOL_NAMES.contains(&tool_name);
+    let is_mcp =
+        matches!(payload, ToolPayload::Mcp { .. }) || MCP_RESOURCE_TOOL_NAMES.contains(&tool_name);
+    if is_shell && !toggles.shell {
+        Some("shell")
+    } else if tool_name == "apply_patch" && !toggles.apply_patch {
+        Some("apply_patch")
+    } else if is_mcp && !toggles.mcp {
+        Some("mcp")
+    } else {
+        None
+    }
+}
+
+/// Structured refusal returned to the model for a call to a disabled tool.
+pub(crate) fn tool_disabled_message(tool_name: &str, group: &str) -> String {
+    json!({
+        "error": "tool_disabled",
+        "tool": tool_name,
+        "message": format!(
+            "The {group} tools are disabled for this session. Do not call {tool_name}; continue without it."
+        ),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn function_payload() -> ToolPayload {
+        ToolPayload::Function {
+            arguments: "{}".to_string(),
+        }
+    }
+
+    #[test]
+    fn enabled_tools_are_not_refused() {
+        let toggles = ToolToggles::default();
+        for name in ["shell", "apply_patch", "read_mcp_resource", "update_plan"] {
+            assert_eq!(
+                disabled_tool_group(name, &function_payload(), toggles),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn disabled_groups_cover_their_tools() {
+        let toggles = ToolToggles {
+            shell: false,
+            apply_patch: false,
+            mcp: false,
+        };
+        let mcp_payload = ToolPayload::Mcp {
+            server: "docs".to_string(),
+            tool: "search".to_string(),
+            raw_arguments: "{}".to_string(),
+        };
+        let groups = vec![
+            disabled_tool_group("exec_command", &function_payload(), toggles),
+            disabled_tool_group("shell_command", &function_payload(), toggles),
+            disabled_tool_group("apply_patch", &function_payload(), toggles),
+            disabled_tool_group("mcp__docs__search", &mcp_payload, toggles),
+            disabled_tool_group("list_mcp_resources", &function_payload(), toggles),
+            disabled_tool_group("update_plan", &function_payload(), toggles),
+        ];
+        assert_eq!(
+            groups,
+            vec![
+                Some("shell"),
+                Some("shell"),
+                Some("apply_patch"),
+                Some("mcp"),
+                Some("mcp"),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn refusal_is_structured_json() {
+        let message: serde_json::Value =
+            serde_json::from_str(&tool_disabled_message("shell", "shell")).expect("json");
+        assert_eq!(
+            message,
+            json!({
+                "error": "tool_disabled",
+                "tool": "shell",
+                "message": "The shell tools are disabled for this session. Do not call shell; continue without it.",
+            })
+        );
+    }
+}
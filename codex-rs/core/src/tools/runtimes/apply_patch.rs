@@ -6,9 +6,12 @@
 //! `SandboxAttempt` with a minimal environment.
 use crate::CODEX_APPLY_PATCH_ARG1;
 use crate::exec::ExecToolCallOutput;
+use crate::exec_audit::dry_run_output;
+use crate::exec_limits::ExecLimits;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::execute_env;
+use crate::tools::runtimes::report_spawn;
 use crate::tools::sandboxing::Approvable;
 use crate::tools::sandboxing::ApprovalCtx;
 use crate::tools::sandboxing::ExecApprovalRequirement;
@@ -33,6 +36,9 @@ pub struct ApplyPatchRequest {
     pub action: ApplyPatchAction,
     pub file_paths: Vec<AbsolutePathBuf>,
     pub changes: std::collections::HashMap<PathBuf, FileChange>,
+    /// Paths in `changes` with uncommitted changes in git, shown in the
+    /// approval request.
+    pub dirty_paths: Vec<PathBuf>,
     pub exec_approval_requirement: ExecApprovalRequirement,
     pub timeout_ms: Option<u64>,
     pub codex_exe: Option<PathBuf>,
@@ -64,6 +70,7 @@ impl ApplyPatchRuntime {
             env: HashMap::new(),
             sandbox_permissions: SandboxPermissions::UseDefault,
             justification: None,
+            limits: ExecLimits::default(),
         })
     }
 
@@ -103,25 +110,26 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
         let retry_reason = ctx.retry_reason.clone();
         let approval_keys = self.approval_keys(req);
         let changes = req.changes.clone();
+        let dirty_paths = req.dirty_paths.clone();
         Box::pin(async move {
             if let Some(reason) = retry_reason {
-                let rx_approve = session
-                    .request_patch_approval(turn, call_id, changes.clone(), Some(reason), None)
+                return session
+                    .request_patch_approval(
+                        turn,
+                        call_id,
+                        changes.clone(),
+                        Some(reason),
+                        None,
+                        dirty_paths.clone(),
+                    )
                     .await;
-                return rx_approve.await.unwrap_or_default();
             }
 
-            with_cached_approval(
-                &session.services,
-                "apply_patch",
-                approval_keys,
-                || async move {
-                    let rx_approve = session
-                        .request_patch_approval(turn, call_id, changes, None, None)
-                        .await;
-                    rx_approve.await.unwrap_or_default()
-                },
-            )
+            with_cached_approval(session, turn, "apply_patch", approval_keys, || async move {
+                session
+                    .request_patch_approval(turn, call_id, changes, None, None, dirty_paths)
+                    .await
+            })
             .await
         })
     }
@@ -150,9 +158,15 @@ impl ToolRuntime<ApplyPatchRequest, ExecToolCallOutput> for ApplyPatchRuntime {
         ctx: &ToolCtx<'_>,
     ) -> Result<ExecToolCallOutput, ToolError> {
         let spec = Self::build_command_spec(req)?;
+        let command: Vec<String> = std::iter::once(spec.program.clone())
+            .chain(spec.args.iter().cloned())
+            .collect();
         let env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
+        if report_spawn(ctx, &command, &env).await {
+            return Ok(dry_run_output());
+        }
         let out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
             .await
             .map_err(ToolError::Codex)?;
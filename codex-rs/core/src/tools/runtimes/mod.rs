@@ -5,10 +5,16 @@ Concrete ToolRuntime implementations for specific tools. Each runtime stays
 small and focused and reuses the orchestrator for approvals + sandbox + retry.
 */
 use crate::exec::ExecExpiration;
+use crate::exec_audit::SpawnAudit;
+use crate::exec_limits::ExecLimits;
+use crate::protocol::EventMsg;
 use crate::sandboxing::CommandSpec;
+use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::Shell;
+use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
+use crate::transcript_export::Redactor;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -25,6 +31,7 @@ pub(crate) fn build_command_spec(
     expiration: ExecExpiration,
     sandbox_permissions: SandboxPermissions,
     justification: Option<String>,
+    limits: ExecLimits,
 ) -> Result<CommandSpec, ToolError> {
     let (program, args) = command
         .split_first()
@@ -37,9 +44,42 @@ pub(crate) fn build_command_spec(
         expiration,
         sandbox_permissions,
         justification,
+        limits,
     })
 }
 
+/// Report how `exec_env` is about to be spawned when `exec_audit` or
+/// `dry_run_exec` is enabled. `command` is the argv before the sandbox
+/// transform. Returns true when the command must not run.
+pub(crate) async fn report_spawn(
+    ctx: &ToolCtx<'_>,
+    command: &[String],
+    exec_env: &ExecEnv,
+) -> bool {
+    let config = ctx.turn.client.config();
+    if !config.exec_audit && !config.dry_run_exec {
+        return false;
+    }
+    let redactor = Redactor::new(&config.export_redact_patterns).or_else(|err| {
+        tracing::warn!("ignoring export_redact_patterns when reporting a spawn: {err}");
+        Redactor::new(&[])
+    });
+    if let Ok(redactor) = redactor {
+        let event = SpawnAudit {
+            call_id: &ctx.call_id,
+            turn_id: &ctx.turn.sub_id,
+            command,
+            redactor: &redactor,
+            dry_run: config.dry_run_exec,
+        }
+        .event(exec_env);
+        ctx.session
+            .send_event(ctx.turn, EventMsg::ExecCommandSpawn(event))
+            .await;
+    }
+    config.dry_run_exec
+}
+
 /// POSIX-only helper: for commands produced by `Shell::derive_exec_args`
 /// for Bash/Zsh/sh of the form `[shell_path, "-lc", "<script>"]`, and
 /// when a snapshot is configured on the session shell, rewrite the argv
@@ -5,6 +5,8 @@ Executes shell requests under the orchestrator: asks for approval when needed,
 builds a CommandSpec, and runs it under the current SandboxAttempt.
 */
 use crate::exec::ExecToolCallOutput;
+use crate::exec_audit::dry_run_output;
+use crate::exec_limits::ExecLimits;
 use crate::features::Feature;
 use crate::powershell::prefix_powershell_script_with_utf8;
 use crate::sandboxing::SandboxPermissions;
@@ -12,6 +14,7 @@ use crate::sandboxing::execute_env;
 use crate::shell::ShellType;
 use crate::tools::runtimes::build_command_spec;
 use crate::tools::runtimes::maybe_wrap_shell_lc_with_snapshot;
+use crate::tools::runtimes::report_spawn;
 use crate::tools::sandboxing::Approvable;
 use crate::tools::sandboxing::ApprovalCtx;
 use crate::tools::sandboxing::ExecApprovalRequirement;
@@ -36,6 +39,7 @@ pub struct ShellRequest {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub exec_approval_requirement: ExecApprovalRequirement,
+    pub limits: ExecLimits,
 }
 
 #[derive(Default)]
@@ -98,7 +102,7 @@ impl Approvable<ShellRequest> for ShellRuntime {
         let turn = ctx.turn;
         let call_id = ctx.call_id.to_string();
         Box::pin(async move {
-            with_cached_approval(&session.services, "shell", keys, move || async move {
+            with_cached_approval(session, turn, "shell", keys, move || async move {
                 session
                     .request_command_approval(
                         turn,
@@ -162,10 +166,14 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
             req.timeout_ms.into(),
             req.sandbox_permissions,
             req.justification.clone(),
+            req.limits,
         )?;
         let env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
+        if report_spawn(ctx, base_command, &env).await {
+            return Ok(dry_run_output());
+        }
         let out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
             .await
             .map_err(ToolError::Codex)?;
@@ -7,12 +7,15 @@ the process manager to spawn PTYs once an ExecEnv is prepared.
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecExpiration;
+use crate::exec_audit::DRY_RUN_OUTPUT;
+use crate::exec_limits::ExecLimits;
 use crate::features::Feature;
 use crate::powershell::prefix_powershell_script_with_utf8;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::ShellType;
 use crate::tools::runtimes::build_command_spec;
 use crate::tools::runtimes::maybe_wrap_shell_lc_with_snapshot;
+use crate::tools::runtimes::report_spawn;
 use crate::tools::sandboxing::Approvable;
 use crate::tools::sandboxing::ApprovalCtx;
 use crate::tools::sandboxing::ExecApprovalRequirement;
@@ -121,7 +124,7 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
             .clone()
             .or_else(|| req.justification.clone());
         Box::pin(async move {
-            with_cached_approval(&session.services, "unified_exec", keys, || async move {
+            with_cached_approval(session, turn, "unified_exec", keys, || async move {
                 session
                     .request_command_approval(
                         turn,
@@ -188,11 +191,15 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
             ExecExpiration::DefaultTimeout,
             req.sandbox_permissions,
             req.justification.clone(),
+            ExecLimits::default(),
         )
         .map_err(|_| ToolError::Rejected("missing command line for PTY".to_string()))?;
         let exec_env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
+        if report_spawn(ctx, base_command, &exec_env).await {
+            return Err(ToolError::Rejected(DRY_RUN_OUTPUT.to_string()));
+        }
         self.manager
             .open_session_with_exec_env(&exec_env, req.tty)
             .await
@@ -11,10 +11,11 @@ use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxTransformError;
-use crate::state::SessionServices;
 use codex_protocol::approvals::ExecPolicyAmendment;
+use codex_protocol::protocol::ApprovalGrantItem;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::RolloutItem;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -47,6 +48,18 @@ impl ApprovalStore {
             self.map.insert(s, value);
         }
     }
+
+    /// Records a session approval under an already-serialized key.
+    pub fn insert_session_approval(&mut self, key: String) {
+        self.map.insert(key, ReviewDecision::ApprovedForSession);
+    }
+
+    /// Forgets every cached decision and returns how many were dropped.
+    pub fn clear(&mut self) -> usize {
+        let cleared = self.map.len();
+        self.map.clear();
+        cleared
+    }
 }
 
 /// Takes a vector of approval keys and returns a ReviewDecision.
@@ -54,10 +67,12 @@ impl ApprovalStore {
 ///
 /// - If all keys are already approved for session, we skip prompting.
 /// - If the user approves for session, we store the decision for each key individually
-///   so future requests touching any subset can also skip prompting.
+///   so future requests touching any subset can also skip prompting, and record each
+///   key in the rollout so the approval survives resume.
 pub(crate) async fn with_cached_approval<K, F, Fut>(
-    services: &SessionServices,
-    // Name of the tool, used for metrics collection.
+    session: &Session,
+    turn: &TurnContext,
+    // Name of the tool, used for metrics collection and recorded grants.
     tool_name: &str,
     keys: Vec<K>,
     fetch: F,
@@ -72,6 +87,7 @@ where
         return fetch().await;
     }
 
+    let services = &session.services;
     let already_approved = {
         let store = services.tool_approvals.lock().await;
         keys.iter()
@@ -94,10 +110,21 @@ where
     );
 
     if matches!(decision, ReviewDecision::ApprovedForSession) {
-        let mut store = services.tool_approvals.lock().await;
-        for key in keys {
-            store.put(key, ReviewDecision::ApprovedForSession);
-        }
+        let grants: Vec<RolloutItem> = {
+            let mut store = services.tool_approvals.lock().await;
+            keys.into_iter()
+                .filter_map(|key| {
+                    let serialized = serde_json::to_string(&key).ok()?;
+                    store.insert_session_approval(serialized.clone());
+                    Some(RolloutItem::ApprovalGrant(ApprovalGrantItem {
+                        tool: tool_name.to_string(),
+                        cwd: turn.cwd.clone(),
+                        key: serialized,
+                    }))
+                })
+                .collect()
+        };
+        session.persist_rollout_items(&grants).await;
     }
 
     decision
@@ -3,6 +3,7 @@ use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
 use crate::features::Feature;
 use crate::features::Features;
+use crate::project_memory::MAX_NOTE_CHARS;
 use crate::tools::handlers::PLAN_TOOL;
 use crate::tools::handlers::apply_patch::create_apply_patch_freeform_tool;
 use crate::tools::handlers::apply_patch::create_apply_patch_json_tool;
@@ -16,6 +17,7 @@ use codex_protocol::models::VIEW_IMAGE_TOOL_NAME;
 use codex_protocol::openai_models::ApplyPatchToolType;
 use codex_protocol::openai_models::ConfigShellToolType;
 use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::protocol::ToolToggles;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
@@ -31,7 +33,9 @@ pub(crate) struct ToolsConfig {
     pub collab_tools: bool,
     pub collaboration_modes_tools: bool,
     pub request_rule_enabled: bool,
+    pub project_memory_tools: bool,
     pub experimental_supported_tools: Vec<String>,
+    pub tool_toggles: ToolToggles,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -51,6 +55,7 @@ impl ToolsConfig {
         let include_collab_tools = features.enabled(Feature::Collab);
         let include_collaboration_modes_tools = features.enabled(Feature::CollaborationModes);
         let request_rule_enabled = features.enabled(Feature::RequestRule);
+        let include_project_memory_tools = features.enabled(Feature::ProjectMemory);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
@@ -84,9 +89,24 @@ impl ToolsConfig {
             collab_tools: include_collab_tools,
             collaboration_modes_tools: include_collaboration_modes_tools,
             request_rule_enabled,
+            project_memory_tools: include_project_memory_tools,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
+            tool_toggles: ToolToggles::default(),
         }
     }
+
+    /// Drop the tool groups that `tool_toggles` turns off. MCP tools are
+    /// filtered when the router is built, since they are not part of this config.
+    pub fn with_tool_toggles(mut self, tool_toggles: ToolToggles) -> Self {
+        if !tool_toggles.shell {
+            self.shell_type = ConfigShellToolType::Disabled;
+        }
+        if !tool_toggles.apply_patch {
+            self.apply_patch_tool_type = None;
+        }
+        self.tool_toggles = tool_toggles;
+        self
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -187,6 +207,40 @@ fn create_approval_parameters(include_prefix_rule: bool) -> BTreeMap<String, Jso
     properties
 }
 
+/// Optional per-command overrides of the configured `[exec_limits]`.
+fn create_exec_limits_parameter() -> JsonSchema {
+    let properties = BTreeMap::from([
+        (
+            "cpu_time_seconds".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "CPU time in seconds the command may use before it is killed.".to_string(),
+                ),
+            },
+        ),
+        (
+            "memory_mb".to_string(),
+            JsonSchema::Number {
+                description: Some("Maximum memory in MiB per process.".to_string()),
+            },
+        ),
+        (
+            "max_output_bytes".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Combined stdout and stderr bytes after which the command is killed."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+    JsonSchema::Object {
+        properties,
+        required: None,
+        additional_properties: Some(false.into()),
+    }
+}
+
 fn create_exec_command_tool(include_prefix_rule: bool) -> ToolSpec {
     let mut properties = BTreeMap::from([
         (
@@ -292,6 +346,14 @@ fn create_write_stdin_tool() -> ToolSpec {
                 ),
             },
         ),
+        (
+            "terminate".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Kill the session after writing `chars` and returning its output.".to_string(),
+                ),
+            },
+        ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
@@ -329,6 +391,7 @@ fn create_shell_tool(include_prefix_rule: bool) -> ToolSpec {
                 description: Some("The timeout for the command in milliseconds".to_string()),
             },
         ),
+        ("limits".to_string(), create_exec_limits_parameter()),
     ]);
     properties.extend(create_approval_parameters(include_prefix_rule));
 
@@ -392,6 +455,7 @@ fn create_shell_command_tool(include_prefix_rule: bool) -> ToolSpec {
                 description: Some("The timeout for the command in milliseconds".to_string()),
             },
         ),
+        ("limits".to_string(), create_exec_limits_parameter()),
     ]);
     properties.extend(create_approval_parameters(include_prefix_rule));
 
@@ -635,6 +699,30 @@ fn create_request_user_input_tool() -> ToolSpec {
     })
 }
 
+fn create_remember_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "note".to_string(),
+        JsonSchema::String {
+            description: Some(format!(
+                "One short sentence to remember (at most {MAX_NOTE_CHARS} characters)."
+            )),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "remember".to_string(),
+        description: "Save a note to this project's memory file (`.codex/memory.md`) so future sessions can see it. Use it for durable, project-specific facts you had to discover, such as the right command to run the tests or a known flaky test. The user reviews each note before it is saved; the oldest notes are dropped when the file is full."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["note".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_close_agent_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -1266,6 +1354,7 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
+    use crate::tools::handlers::RememberHandler;
     use crate::tools::handlers::RequestUserInputHandler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
@@ -1316,12 +1405,14 @@ pub(crate) fn build_specs(
         builder.register_handler("shell_command", shell_command_handler);
     }
 
-    builder.push_spec_with_parallel_support(create_list_mcp_resources_tool(), true);
-    builder.push_spec_with_parallel_support(create_list_mcp_resource_templates_tool(), true);
-    builder.push_spec_with_parallel_support(create_read_mcp_resource_tool(), true);
-    builder.register_handler("list_mcp_resources", mcp_resource_handler.clone());
-    builder.register_handler("list_mcp_resource_templates", mcp_resource_handler.clone());
-    builder.register_handler("read_mcp_resource", mcp_resource_handler);
+    if config.tool_toggles.mcp {
+        builder.push_spec_with_parallel_support(create_list_mcp_resources_tool(), true);
+        builder.push_spec_with_parallel_support(create_list_mcp_resource_templates_tool(), true);
+        builder.push_spec_with_parallel_support(create_read_mcp_resource_tool(), true);
+        builder.register_handler("list_mcp_resources", mcp_resource_handler.clone());
+        builder.register_handler("list_mcp_resource_templates", mcp_resource_handler.clone());
+        builder.register_handler("read_mcp_resource", mcp_resource_handler);
+    }
 
     builder.push_spec(PLAN_TOOL.clone());
     builder.register_handler("update_plan", plan_handler);
@@ -1343,6 +1434,11 @@ pub(crate) fn build_specs(
         builder.register_handler("apply_patch", apply_patch_handler);
     }
 
+    if config.project_memory_tools {
+        builder.push_spec(create_remember_tool());
+        builder.register_handler("remember", Arc::new(RememberHandler));
+    }
+
     if config
         .experimental_supported_tools
         .contains(&"grep_files".to_string())
@@ -1626,6 +1722,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tool_toggles_remove_disabled_groups() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::UnifiedExec);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        })
+        .with_tool_toggles(ToolToggles {
+            shell: false,
+            apply_patch: false,
+            mcp: false,
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        let names: Vec<&str> = tools.iter().map(|t| t.spec.name()).collect();
+        for disabled in [
+            "exec_command",
+            "write_stdin",
+            "shell",
+            "shell_command",
+            "apply_patch",
+            "list_mcp_resources",
+            "list_mcp_resource_templates",
+            "read_mcp_resource",
+        ] {
+            assert!(
+                !names.contains(&disabled),
+                "{disabled} should be disabled, got {names:?}"
+            );
+        }
+        assert_contains_tool_names(&tools, &["update_plan"]);
+    }
+
     #[test]
     fn request_user_input_requires_collaboration_modes_feature() {
         let config = test_config();
@@ -1653,6 +1785,32 @@ mod tests {
         assert_contains_tool_names(&tools, &["request_user_input"]);
     }
 
+    #[test]
+    fn remember_requires_project_memory_feature() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert!(
+            !tools.iter().any(|t| t.spec.name() == "remember"),
+            "remember should be disabled when project_memory feature is off"
+        );
+
+        features.enable(Feature::ProjectMemory);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert_contains_tool_names(&tools, &["remember"]);
+    }
+
     fn assert_model_tools(
         model_slug: &str,
         features: &Features,
@@ -2438,6 +2596,29 @@ Examples of valid command strings:
         assert_eq!(description, &expected);
     }
 
+    #[test]
+    fn shell_tools_accept_limit_overrides() {
+        for tool in [
+            super::create_shell_tool(false),
+            super::create_shell_command_tool(false),
+        ] {
+            let ToolSpec::Function(ResponsesApiTool {
+                parameters: JsonSchema::Object { properties, .. },
+                ..
+            }) = &tool
+            else {
+                panic!("expected function tool with object parameters");
+            };
+            let Some(JsonSchema::Object { properties, .. }) = properties.get("limits") else {
+                panic!("expected a limits object parameter");
+            };
+            assert_eq!(
+                properties.keys().collect::<Vec<_>>(),
+                vec!["cpu_time_seconds", "max_output_bytes", "memory_mb"]
+            );
+        }
+    }
+
     #[test]
     fn test_shell_command_tool() {
         let tool = super::create_shell_command_tool(false);
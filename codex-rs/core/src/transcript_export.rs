@@ -0,0 +1,431 @@
+//! Shareable HTML transcripts of recorded sessions.
+//!
+//! [`export_html_transcript`] reads a rollout file and renders what happened
+//! in the session as a single self-contained HTML page: user and assistant
+//! messages, collapsible reasoning, commands with their output (collapsed
+//! when long), highlighted patches, and who resolved each approval request. Before anything is rendered, every
+//! piece of text goes through a [`Redactor`] that replaces matches of
+//! [`SENSITIVE_PATTERNS`] and any user-supplied patterns with
+//! [`REDACTED_PLACEHOLDER`], so the page can be handed to someone else.
+
+use std::io;
+use std::path::Path;
+
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use regex_lite::Regex;
+use serde_json::Value;
+
+use crate::parse_command::shlex_join;
+use crate::protocol::ApprovalKind;
+use crate::protocol::ApprovalResolvedEvent;
+use crate::protocol::ApprovalResolver;
+use crate::protocol::EventMsg;
+use crate::protocol::ReviewDecision;
+use crate::protocol::RolloutItem;
+use crate::rollout::RolloutRecorder;
+
+/// Patterns for credentials that must never leave the machine in an export.
+pub const SENSITIVE_PATTERNS: &[&str] = &[
+    // OpenAI-style API keys.
+    r"sk-[A-Za-z0-9_-]{20,}",
+    // GitHub tokens.
+    r"gh[pousr]_[A-Za-z0-9]{36,}",
+    r"github_pat_[A-Za-z0-9_]{22,}",
+    // AWS access key ids.
+    r"AKIA[0-9A-Z]{16}",
+    // Slack tokens.
+    r"xox[abprs]-[A-Za-z0-9-]{10,}",
+    // Bearer credentials in headers or curl invocations.
+    r"(?i)bearer\s+[A-Za-z0-9._~+/-]+=*",
+    // PEM private keys.
+    r"-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?-----END [A-Z ]*PRIVATE KEY-----",
+];
+
+/// Text that replaces every redacted match.
+pub const REDACTED_PLACEHOLDER: &str = "[REDACTED]";
+
+/// Command output longer than this many lines is collapsed by default.
+const COLLAPSE_OUTPUT_LINES: usize = 20;
+
+const STYLE: &str = "\
+body { margin: 0 auto; max-width: 960px; padding: 24px; font-family: -apple-system, BlinkMacSystemFont, \"Segoe UI\", sans-serif; color: #1f2328; background: #ffffff; }
+header { border-bottom: 1px solid #d0d7de; margin-bottom: 16px; }
+h1 { font-size: 20px; }
+.meta { color: #656d76; font-size: 13px; }
+.entry { margin: 12px 0; padding: 8px 12px; border-radius: 6px; border: 1px solid #d0d7de; }
+.entry .role { font-size: 12px; font-weight: 600; color: #656d76; text-transform: uppercase; }
+.user { background: #f6f8fa; }
+.approval .by { color: #656d76; font-size: 13px; }
+.text { white-space: pre-wrap; word-wrap: break-word; }
+pre { margin: 4px 0; padding: 8px; overflow-x: auto; background: #f6f8fa; border-radius: 4px; font-size: 12px; }
+summary { cursor: pointer; color: #656d76; font-size: 13px; }
+.reasoning .text { color: #656d76; font-style: italic; }
+.diff .add { color: #116329; background: #dafbe1; }
+.diff .del { color: #82071e; background: #ffebe9; }
+.diff .hunk { color: #0550ae; }
+.diff .file { font-weight: 600; }
+";
+
+/// Options for [`export_html_transcript`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranscriptExportOptions {
+    /// Extra regular expressions to redact, on top of [`SENSITIVE_PATTERNS`].
+    pub redact_patterns: Vec<String>,
+}
+
+/// Replaces sensitive substrings before they reach an export.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+}
+
+impl Redactor {
+    /// Builds a redactor from [`SENSITIVE_PATTERNS`] plus `extra_patterns`.
+    /// Fails with `InvalidInput` when one of the extra patterns does not
+    /// compile.
+    pub fn new(extra_patterns: &[String]) -> io::Result<Self> {
+        let patterns = SENSITIVE_PATTERNS
+            .iter()
+            .copied()
+            .chain(extra_patterns.iter().map(String::as_str))
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("invalid redaction pattern `{pattern}`: {err}"),
+                    )
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self { patterns })
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        let mut redacted = text.to_string();
+        for pattern in &self.patterns {
+            if pattern.is_match(&redacted) {
+                redacted = pattern
+                    .replace_all(&redacted, REDACTED_PLACEHOLDER)
+                    .into_owned();
+            }
+        }
+        redacted
+    }
+}
+
+/// Reads the rollout at `rollout_path` and renders it as a standalone HTML
+/// page with sensitive strings redacted.
+pub async fn export_html_transcript(
+    rollout_path: &Path,
+    options: &TranscriptExportOptions,
+) -> io::Result<String> {
+    let redactor = Redactor::new(&options.redact_patterns)?;
+    let (items, _, _) = RolloutRecorder::load_rollout_items(rollout_path).await?;
+    Ok(render_html_transcript(&items, &redactor))
+}
+
+/// Renders rollout items as a standalone HTML page.
+pub fn render_html_transcript(items: &[RolloutItem], redactor: &Redactor) -> String {
+    let mut writer = TranscriptWriter {
+        redactor,
+        body: String::new(),
+    };
+    let mut meta = None;
+    for item in items {
+        match item {
+            RolloutItem::SessionMeta(line) => {
+                if meta.is_none() {
+                    meta = Some(format!(
+                        "Session {} · started {} · {}",
+                        line.meta.id,
+                        line.meta.timestamp,
+                        line.meta.cwd.display()
+                    ));
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                writer.message("user", "User", &event.message);
+            }
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                writer.message("assistant", "Codex", &event.message);
+            }
+            RolloutItem::EventMsg(EventMsg::AgentReasoning(event)) => {
+                writer.reasoning(&event.text);
+            }
+            RolloutItem::EventMsg(EventMsg::ApprovalResolved(event)) => writer.approval(event),
+            RolloutItem::ResponseItem(item) => writer.response_item(item),
+            RolloutItem::EventMsg(_)
+            | RolloutItem::Compacted(_)
+            | RolloutItem::TurnContext(_)
+            | RolloutItem::ApprovalGrant(_)
+            | RolloutItem::FileUpload(_) => {}
+        }
+    }
+
+    let meta = meta
+        .map(|meta| format!("<p class=\"meta\">{}</p>\n", writer.escaped(&meta)))
+        .unwrap_or_default();
+    let body = writer.body;
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Codex session</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n<header>\n<h1>Codex session</h1>\n{meta}</header>\n<main>\n{body}</main>\n</body>\n</html>\n"
+    )
+}
+
+struct TranscriptWriter<'a> {
+    redactor: &'a Redactor,
+    body: String,
+}
+
+impl TranscriptWriter<'_> {
+    fn escaped(&self, text: &str) -> String {
+        escape_html(&self.redactor.redact(text))
+    }
+
+    fn message(&mut self, class: &str, role: &str, text: &str) {
+        let text = self.escaped(text);
+        self.body.push_str(&format!(
+            "<section class=\"entry {class}\">\n<div class=\"role\">{role}</div>\n<div class=\"text\">{text}</div>\n</section>\n"
+        ));
+    }
+
+    fn reasoning(&mut self, text: &str) {
+        let text = self.escaped(text);
+        self.body.push_str(&format!(
+            "<details class=\"entry reasoning\">\n<summary>Reasoning</summary>\n<div class=\"text\">{text}</div>\n</details>\n"
+        ));
+    }
+
+    fn command(&mut self, command: &str) {
+        let command = self.escaped(command);
+        self.body.push_str(&format!(
+            "<section class=\"entry command\">\n<div class=\"role\">Command</div>\n<pre>$ {command}</pre>\n</section>\n"
+        ));
+    }
+
+    fn tool_call(&mut self, name: &str, input: &str) {
+        let name = self.escaped(name);
+        let input = self.escaped(input);
+        self.body.push_str(&format!(
+            "<section class=\"entry tool\">\n<div class=\"role\">Tool call · {name}</div>\n<pre>{input}</pre>\n</section>\n"
+        ));
+    }
+
+    fn patch(&mut self, patch: &str) {
+        let lines: String = self
+            .redactor
+            .redact(patch)
+            .lines()
+            .map(|line| {
+                let class = if line.starts_with("*** ") {
+                    Some("file")
+                } else if line.starts_with("@@") {
+                    Some("hunk")
+                } else if line.starts_with('+') {
+                    Some("add")
+                } else if line.starts_with('-') {
+                    Some("del")
+                } else {
+                    None
+                };
+                let line = escape_html(line);
+                match class {
+                    Some(class) => format!("<span class=\"{class}\">{line}</span>\n"),
+                    None => format!("{line}\n"),
+                }
+            })
+            .collect();
+        self.body.push_str(&format!(
+            "<section class=\"entry diff\">\n<div class=\"role\">Patch</div>\n<pre>{lines}</pre>\n</section>\n"
+        ));
+    }
+
+    fn approval(&mut self, event: &ApprovalResolvedEvent) {
+        let decision = match event.decision {
+            ReviewDecision::Approved => "Approved",
+            ReviewDecision::ApprovedExecpolicyAmendment { .. } => "Always approved",
+            ReviewDecision::ApprovedForSession => "Approved for the session",
+            ReviewDecision::Denied => "Denied",
+            ReviewDecision::Abort => "Canceled",
+        };
+        let subject = match event.kind {
+            ApprovalKind::Exec => format!("$ {}", display_command(&event.command)),
+            ApprovalKind::Patch => event
+                .paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let mut text = self.escaped(&subject);
+        if let Some(reason) = &event.reason {
+            text.push_str(&format!("\nReason: {}", self.escaped(reason)));
+        }
+        let by = match (event.resolved_by, &event.account) {
+            (ApprovalResolver::User, Some(account)) => format!("by {account}"),
+            (ApprovalResolver::User, None) => "by the user".to_string(),
+            (ApprovalResolver::Timeout, _) => "after the approval timeout".to_string(),
+            (ApprovalResolver::TurnEnded, _) => "when the turn ended".to_string(),
+        };
+        let by = self.escaped(&format!("{by} at {}", event.resolved_at));
+        self.body.push_str(&format!(
+            "<section class=\"entry approval\">\n<div class=\"role\">Approval · {decision}</div>\n<pre>{text}</pre>\n<div class=\"by\">{by}</div>\n</section>\n"
+        ));
+    }
+
+    fn output(&mut self, output: &str) {
+        let line_count = output.lines().count();
+        let output = self.escaped(output.trim_end());
+        if line_count > COLLAPSE_OUTPUT_LINES {
+            self.body.push_str(&format!(
+                "<details class=\"entry output\">\n<summary>Output ({line_count} lines)</summary>\n<pre>{output}</pre>\n</details>\n"
+            ));
+        } else {
+            self.body.push_str(&format!(
+                "<section class=\"entry output\">\n<div class=\"role\">Output</div>\n<pre>{output}</pre>\n</section>\n"
+            ));
+        }
+    }
+
+    fn response_item(&mut self, item: &ResponseItem) {
+        match item {
+            ResponseItem::FunctionCall {
+                name,
+                arguments: raw_arguments,
+                ..
+            } => {
+                let arguments = serde_json::from_str::<Value>(raw_arguments).ok();
+                if name == "apply_patch"
+                    && let Some(patch) = arguments
+                        .as_ref()
+                        .and_then(|value| value.get("input"))
+                        .and_then(Value::as_str)
+                {
+                    self.patch(patch);
+                } else if let Some(command) = arguments.as_ref().and_then(command_from_arguments) {
+                    self.command(&command);
+                } else {
+                    self.tool_call(name, &raw_arguments);
+                }
+            }
+            ResponseItem::LocalShellCall {
+                action: LocalShellAction::Exec(exec),
+                ..
+            } => self.command(&display_command(&exec.command)),
+            ResponseItem::CustomToolCall { name, input, .. } => {
+                if name == "apply_patch" {
+                    self.patch(input);
+                } else {
+                    self.tool_call(name, input);
+                }
+            }
+            ResponseItem::FunctionCallOutput { output, .. } => self.output(&output.content),
+            ResponseItem::CustomToolCallOutput { output, .. } => self.output(output),
+            // Messages and reasoning are rendered from their events, which
+            // carry the user-facing text.
+            ResponseItem::Message { .. }
+            | ResponseItem::Reasoning { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other(_) => {}
+        }
+    }
+}
+
+/// Extracts the command line from shell-like tool arguments.
+fn command_from_arguments(arguments: &Value) -> Option<String> {
+    match arguments.get("command").or_else(|| arguments.get("cmd"))? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(tokens) => {
+            let tokens = tokens
+                .iter()
+                .map(|token| token.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()?;
+            Some(display_command(&tokens))
+        }
+        _ => None,
+    }
+}
+
+/// Shows the script for `bash -lc <script>` style invocations and the joined
+/// argv otherwise.
+fn display_command(command: &[String]) -> String {
+    match command {
+        [_, flag, script] if flag == "-lc" || flag == "-c" => script.clone(),
+        _ => shlex_join(command),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn redactor_applies_builtin_and_extra_patterns() {
+        let redactor = Redactor::new(&["internal\\.example\\.com".to_string()]).expect("redactor");
+
+        assert_eq!(
+            redactor.redact(
+                "curl -H 'Authorization: Bearer abc.def' https://internal.example.com?key=sk-abcdefghijklmnopqrstuvwx"
+            ),
+            "curl -H 'Authorization: [REDACTED]' https://[REDACTED]?key=[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn approval_resolutions_record_who_and_when() {
+        let redactor = Redactor::new(&[]).expect("redactor");
+        let resolved = ApprovalResolvedEvent {
+            call_id: "call-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            kind: ApprovalKind::Exec,
+            command: vec![
+                "bash".to_string(),
+                "-lc".to_string(),
+                "curl -H 'Authorization: Bearer abc.def' example.com".to_string(),
+            ],
+            paths: Vec::new(),
+            cwd: "/work".into(),
+            reason: Some("uses sk-abcdefghijklmnopqrstuvwx".to_string()),
+            decision: ReviewDecision::Denied,
+            resolved_by: ApprovalResolver::User,
+            account: Some("dev@example.com".to_string()),
+            resolved_at: "2025-01-02T03:04:05Z".to_string(),
+        };
+
+        let html = render_html_transcript(
+            &[RolloutItem::EventMsg(EventMsg::ApprovalResolved(resolved))],
+            &redactor,
+        );
+
+        assert!(html.contains(
+            "<section class=\"entry approval\">\n<div class=\"role\">Approval · Denied</div>\n\
+             <pre>$ curl -H &#39;Authorization: [REDACTED]&#39; example.com\nReason: uses [REDACTED]</pre>\n\
+             <div class=\"by\">by dev@example.com at 2025-01-02T03:04:05Z</div>\n</section>\n"
+        ));
+    }
+
+    #[test]
+    fn redactor_rejects_invalid_patterns() {
+        let err = Redactor::new(&["(".to_string()]).expect_err("invalid pattern");
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -5,6 +5,8 @@ use std::sync::atomic::Ordering;
 #[derive(Clone, Debug, Default)]
 pub struct TransportManager {
     disable_websockets: Arc<AtomicBool>,
+    disable_response_storage: Arc<AtomicBool>,
+    disable_service_tier: Arc<AtomicBool>,
 }
 
 impl TransportManager {
@@ -19,4 +21,27 @@ impl TransportManager {
     pub fn activate_http_fallback(&self, websocket_enabled: bool) -> bool {
         websocket_enabled && !self.disable_websockets.swap(true, Ordering::Relaxed)
     }
+
+    /// Whether the provider rejected stored responses earlier in this session.
+    pub fn disable_response_storage(&self) -> bool {
+        self.disable_response_storage.load(Ordering::Relaxed)
+    }
+
+    /// Sends `store: false` for the rest of the session. Returns `true` only
+    /// for the call that turned storage off.
+    pub fn activate_stateless_fallback(&self) -> bool {
+        !self.disable_response_storage.swap(true, Ordering::Relaxed)
+    }
+
+    /// Whether the provider rejected the configured service tier earlier in
+    /// this session.
+    pub fn disable_service_tier(&self) -> bool {
+        self.disable_service_tier.load(Ordering::Relaxed)
+    }
+
+    /// Omits `service_tier` for the rest of the session. Returns `true` only
+    /// for the call that turned it off.
+    pub fn activate_default_service_tier(&self) -> bool {
+        !self.disable_service_tier.swap(true, Ordering::Relaxed)
+    }
 }
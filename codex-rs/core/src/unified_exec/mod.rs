@@ -56,6 +56,10 @@ pub(crate) const MAX_UNIFIED_EXEC_PROCESSES: usize = 64;
 // Send a warning message to the models when it reaches this number of processes.
 pub(crate) const WARNING_UNIFIED_EXEC_PROCESSES: usize = 60;
 
+// Processes that have not been written to or polled for this long are terminated
+// the next time a process is opened.
+pub(crate) const UNIFIED_EXEC_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
 pub(crate) struct UnifiedExecContext {
     pub session: Arc<Session>,
     pub turn: Arc<TurnContext>,
@@ -91,6 +95,8 @@ pub(crate) struct WriteStdinRequest<'a> {
     pub input: &'a str,
     pub yield_time_ms: u64,
     pub max_output_tokens: Option<usize>,
+    /// Terminate the process after collecting output for this call.
+    pub terminate: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -227,6 +233,7 @@ mod tests {
                 input,
                 yield_time_ms,
                 max_output_tokens: None,
+                terminate: false,
             })
             .await
     }
@@ -438,6 +445,41 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn write_stdin_can_terminate_process() -> anyhow::Result<()> {
+        skip_if_sandbox!(Ok(()));
+
+        let (session, turn) = test_session_and_turn().await;
+
+        let open_shell = exec_command(&session, &turn, "bash -i", 2_500).await?;
+        let process_id = open_shell
+            .process_id
+            .as_ref()
+            .expect("expected process id")
+            .as_str();
+
+        let closed = session
+            .services
+            .unified_exec_manager
+            .write_stdin(WriteStdinRequest {
+                process_id,
+                input: "echo closing\n",
+                yield_time_ms: 2_500,
+                max_output_tokens: None,
+                terminate: true,
+            })
+            .await?;
+        assert!(closed.output.contains("closing"));
+        assert_eq!(closed.process_id, None);
+
+        let err = write_stdin(&session, process_id, "", 100)
+            .await
+            .expect_err("expected unknown process error");
+        assert!(matches!(err, UnifiedExecError::UnknownProcessId { .. }));
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn reusing_completed_process_returns_unknown_process() -> anyhow::Result<()> {
         skip_if_sandbox!(Ok(()));
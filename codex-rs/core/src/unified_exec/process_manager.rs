@@ -30,6 +30,7 @@ use crate::unified_exec::MAX_YIELD_TIME_MS;
 use crate::unified_exec::MIN_EMPTY_YIELD_TIME_MS;
 use crate::unified_exec::ProcessEntry;
 use crate::unified_exec::ProcessStore;
+use crate::unified_exec::UNIFIED_EXEC_IDLE_TIMEOUT;
 use crate::unified_exec::UnifiedExecContext;
 use crate::unified_exec::UnifiedExecError;
 use crate::unified_exec::UnifiedExecProcessManager;
@@ -294,7 +295,11 @@ impl UnifiedExecProcessManager {
         // still alive or has exited and been removed from the store; we thread
         // that through so the handler can tag TerminalInteraction with an
         // appropriate process_id and exit_code.
-        let status = self.refresh_process_state(process_id.as_str()).await;
+        let status = if request.terminate {
+            self.terminate_process(process_id.as_str()).await
+        } else {
+            self.refresh_process_state(process_id.as_str()).await
+        };
         let (process_id, exit_code, event_call_id) = match status {
             ProcessStatus::Alive {
                 exit_code,
@@ -353,6 +358,17 @@ impl UnifiedExecProcessManager {
         }
     }
 
+    async fn terminate_process(&self, process_id: &str) -> ProcessStatus {
+        let Some(entry) = self.process_store.lock().await.remove(process_id) else {
+            return ProcessStatus::Unknown;
+        };
+        entry.process.terminate();
+        ProcessStatus::Exited {
+            exit_code: entry.process.exit_code(),
+            entry: Box::new(entry),
+        }
+    }
+
     async fn prepare_process_handles(
         &self,
         process_id: &str,
@@ -415,6 +431,7 @@ impl UnifiedExecProcessManager {
         };
         let number_processes = {
             let mut store = self.process_store.lock().await;
+            Self::reap_idle_processes(&mut store, Instant::now());
             Self::prune_processes_if_needed(&mut store);
             store.processes.insert(process_id.clone(), entry);
             store.processes.len()
@@ -483,10 +500,17 @@ impl UnifiedExecProcessManager {
         cwd: PathBuf,
         context: &UnifiedExecContext,
     ) -> Result<UnifiedExecProcess, UnifiedExecError> {
-        let env = apply_unified_exec_env(create_env(&context.turn.shell_environment_policy));
+        let env = apply_unified_exec_env(create_env(
+            &context.turn.shell_environment_policy,
+            context.turn.client.config().dotenv.as_deref(),
+        ));
         let features = context.session.features();
         let mut orchestrator = ToolOrchestrator::new();
         let mut runtime = UnifiedExecRuntime::new(self);
+        let approval_policy = context
+            .session
+            .effective_approval_policy(&context.turn)
+            .await;
         let exec_approval_requirement = context
             .session
             .services
@@ -494,10 +518,11 @@ impl UnifiedExecProcessManager {
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 features: &features,
                 command: &request.command,
-                approval_policy: context.turn.approval_policy,
+                approval_policy,
                 sandbox_policy: &context.turn.sandbox_policy,
                 sandbox_permissions: request.sandbox_permissions,
                 prefix_rule: request.prefix_rule.clone(),
+                cwd_outside_workspace: !context.turn.is_in_workspace(&cwd),
             })
             .await;
         let req = UnifiedExecToolRequest::new(
@@ -521,7 +546,7 @@ impl UnifiedExecProcessManager {
                 &req,
                 &tool_ctx,
                 context.turn.as_ref(),
-                context.turn.approval_policy,
+                approval_policy,
             )
             .await
             .map_err(|e| UnifiedExecError::create_process(format!("{e:?}")))
@@ -609,6 +634,29 @@ impl UnifiedExecProcessManager {
         false
     }
 
+    fn reap_idle_processes(store: &mut ProcessStore, now: Instant) {
+        let meta: Vec<(String, Instant)> = store
+            .processes
+            .iter()
+            .map(|(id, entry)| (id.clone(), entry.last_used))
+            .collect();
+
+        for process_id in Self::idle_process_ids_from_meta(&meta, now) {
+            if let Some(entry) = store.remove(&process_id) {
+                entry.process.terminate();
+            }
+        }
+    }
+
+    fn idle_process_ids_from_meta(meta: &[(String, Instant)], now: Instant) -> Vec<String> {
+        meta.iter()
+            .filter(|(_, last_used)| {
+                now.saturating_duration_since(*last_used) >= UNIFIED_EXEC_IDLE_TIMEOUT
+            })
+            .map(|(process_id, _)| process_id.clone())
+            .collect()
+    }
+
     // Centralized pruning policy so we can easily swap strategies later.
     fn process_id_to_prune_from_meta(meta: &[(String, Instant, bool)]) -> Option<String> {
         if meta.is_empty() {
@@ -773,4 +821,21 @@ mod tests {
         // (10) is exited but among the last 8; we should drop the LRU outside that set.
         assert_eq!(candidate, Some(id(1)));
     }
+
+    #[test]
+    fn idle_reaping_selects_processes_past_timeout() {
+        let start = Instant::now();
+        let now = start + UNIFIED_EXEC_IDLE_TIMEOUT + Duration::from_secs(60);
+        let id = |n: i32| n.to_string();
+        let meta = vec![
+            (id(1), start),
+            (id(2), now - Duration::from_secs(60)),
+            (id(3), now - UNIFIED_EXEC_IDLE_TIMEOUT),
+            (id(4), now),
+        ];
+
+        let idle = UnifiedExecProcessManager::idle_process_ids_from_meta(&meta, now);
+
+        assert_eq!(idle, vec![id(1), id(3)]);
+    }
 }
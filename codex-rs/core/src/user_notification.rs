@@ -1,21 +1,93 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
 use serde::Serialize;
 use tracing::error;
 use tracing::warn;
 
+use crate::config::types::NotifyEventKind;
+use crate::config::types::NotifyEventToml;
+
 #[derive(Debug, Default)]
 pub(crate) struct UserNotifier {
     notify_command: Option<Vec<String>>,
+    events: HashMap<NotifyEventKind, NotifyEventToml>,
+    /// Earliest time the next notification of each kind may be sent.
+    next_allowed: Mutex<HashMap<NotifyEventKind, Instant>>,
 }
 
 impl UserNotifier {
+    /// Sends `notification` now, unless its type is disabled or one of the
+    /// same type went out within the debounce window.
     pub(crate) fn notify(&self, notification: &UserNotification) {
-        if let Some(notify_command) = &self.notify_command
-            && !notify_command.is_empty()
+        if let Some(notify_command) = self.command_for(notification.kind())
+            && self.reserve_now(notification.kind())
         {
             self.invoke_notify(notify_command, notification)
         }
     }
 
+    /// Reserves the next slot for a notification of `kind` and returns how
+    /// long to wait before sending it with [`UserNotifier::send_reserved`].
+    /// Returns `None` when the type is disabled or there is no `notify`
+    /// program.
+    pub(crate) fn reserve_delayed(&self, kind: NotifyEventKind) -> Option<Duration> {
+        self.command_for(kind)?;
+        let now = Instant::now();
+        let mut next_allowed = self.lock_next_allowed();
+        let send_at = next_allowed
+            .get(&kind)
+            .copied()
+            .map_or(now, |at| at.max(now));
+        next_allowed.insert(kind, send_at + self.debounce(kind));
+        Some(send_at - now)
+    }
+
+    /// Sends a notification whose slot was taken by
+    /// [`UserNotifier::reserve_delayed`].
+    pub(crate) fn send_reserved(&self, notification: &UserNotification) {
+        if let Some(notify_command) = self.command_for(notification.kind()) {
+            self.invoke_notify(notify_command, notification)
+        }
+    }
+
+    fn command_for(&self, kind: NotifyEventKind) -> Option<&[String]> {
+        let notify_command = self.notify_command.as_deref()?;
+        let enabled = self
+            .events
+            .get(&kind)
+            .and_then(|event| event.enabled)
+            .unwrap_or(true);
+        (enabled && !notify_command.is_empty()).then_some(notify_command)
+    }
+
+    fn debounce(&self, kind: NotifyEventKind) -> Duration {
+        let millis = self
+            .events
+            .get(&kind)
+            .and_then(|event| event.debounce_ms)
+            .unwrap_or(0);
+        Duration::from_millis(millis)
+    }
+
+    fn reserve_now(&self, kind: NotifyEventKind) -> bool {
+        let now = Instant::now();
+        let mut next_allowed = self.lock_next_allowed();
+        if next_allowed.get(&kind).is_some_and(|at| *at > now) {
+            return false;
+        }
+        next_allowed.insert(kind, now + self.debounce(kind));
+        true
+    }
+
+    fn lock_next_allowed(&self) -> std::sync::MutexGuard<'_, HashMap<NotifyEventKind, Instant>> {
+        self.next_allowed
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
     fn invoke_notify(&self, notify_command: &[String], notification: &UserNotification) {
         let Ok(json) = serde_json::to_string(&notification) else {
             error!("failed to serialise notification payload");
@@ -37,8 +109,22 @@ impl UserNotifier {
     pub(crate) fn new(notify: Option<Vec<String>>) -> Self {
         Self {
             notify_command: notify,
+            ..Default::default()
         }
     }
+
+    pub(crate) fn with_events(mut self, events: HashMap<NotifyEventKind, NotifyEventToml>) -> Self {
+        self.events = events;
+        self
+    }
+}
+
+/// What an approval request is asking to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ApprovalRequestKind {
+    Exec,
+    Patch,
 }
 
 /// User can configure a program that will receive notifications. Each
@@ -59,6 +145,33 @@ pub(crate) enum UserNotification {
         /// The last message sent by the assistant in the turn.
         last_assistant_message: Option<String>,
     },
+
+    #[serde(rename_all = "kebab-case")]
+    ApprovalRequested {
+        thread_id: String,
+        turn_id: String,
+        call_id: String,
+        cwd: String,
+        kind: ApprovalRequestKind,
+
+        /// The command line to run, or the files a patch would change.
+        summary: String,
+
+        /// Why the agent is asking, when it gave a reason.
+        reason: Option<String>,
+
+        /// Seconds the request had been waiting when this was sent.
+        pending_seconds: u64,
+    },
+}
+
+impl UserNotification {
+    fn kind(&self) -> NotifyEventKind {
+        match self {
+            UserNotification::AgentTurnComplete { .. } => NotifyEventKind::AgentTurnComplete,
+            UserNotification::ApprovalRequested { .. } => NotifyEventKind::ApprovalRequested,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -84,4 +197,79 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn approval_requested_payload() -> Result<()> {
+        let notification = UserNotification::ApprovalRequested {
+            thread_id: "b5f6c1c2-1111-2222-3333-444455556666".to_string(),
+            turn_id: "12345".to_string(),
+            call_id: "call-1".to_string(),
+            cwd: "/Users/example/project".to_string(),
+            kind: ApprovalRequestKind::Exec,
+            summary: "cargo publish".to_string(),
+            reason: None,
+            pending_seconds: 12,
+        };
+        let serialized = serde_json::to_string(&notification)?;
+        assert_eq!(
+            serialized,
+            r#"{"type":"approval-requested","thread-id":"b5f6c1c2-1111-2222-3333-444455556666","turn-id":"12345","call-id":"call-1","cwd":"/Users/example/project","kind":"exec","summary":"cargo publish","reason":null,"pending-seconds":12}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn debounce_spaces_out_reserved_slots_and_drops_immediate_repeats() {
+        let notifier =
+            UserNotifier::new(Some(vec!["true".to_string()])).with_events(HashMap::from([
+                (
+                    NotifyEventKind::ApprovalRequested,
+                    NotifyEventToml {
+                        enabled: None,
+                        debounce_ms: Some(60_000),
+                    },
+                ),
+                (
+                    NotifyEventKind::AgentTurnComplete,
+                    NotifyEventToml {
+                        enabled: None,
+                        debounce_ms: Some(60_000),
+                    },
+                ),
+            ]));
+
+        assert_eq!(
+            notifier.reserve_delayed(NotifyEventKind::ApprovalRequested),
+            Some(Duration::ZERO)
+        );
+        let second = notifier
+            .reserve_delayed(NotifyEventKind::ApprovalRequested)
+            .expect("second slot");
+        assert!(second > Duration::from_secs(59), "{second:?}");
+
+        assert!(notifier.reserve_now(NotifyEventKind::AgentTurnComplete));
+        assert!(!notifier.reserve_now(NotifyEventKind::AgentTurnComplete));
+    }
+
+    #[test]
+    fn disabled_event_types_are_not_reserved() {
+        let notifier =
+            UserNotifier::new(Some(vec!["true".to_string()])).with_events(HashMap::from([(
+                NotifyEventKind::ApprovalRequested,
+                NotifyEventToml {
+                    enabled: Some(false),
+                    debounce_ms: None,
+                },
+            )]));
+        assert_eq!(
+            notifier.reserve_delayed(NotifyEventKind::ApprovalRequested),
+            None
+        );
+
+        let without_program = UserNotifier::new(None);
+        assert_eq!(
+            without_program.reserve_delayed(NotifyEventKind::AgentTurnComplete),
+            None
+        );
+    }
 }
@@ -6,6 +6,7 @@ use codex_protocol::ThreadId;
 use rand::Rng;
 use tracing::debug;
 use tracing::error;
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::parse_command::shlex_join;
 
@@ -85,6 +86,67 @@ pub fn normalize_thread_name(name: &str) -> Option<String> {
     }
 }
 
+/// Trim session tags, dropping empty ones and duplicates while keeping the
+/// order they were given in.
+pub fn normalize_session_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|existing| existing == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+const AUTO_TITLE_MAX_GRAPHEMES: usize = 60;
+
+/// Derive a session title from the first line of a user message, collapsing
+/// whitespace and truncating long lines with an ellipsis.
+pub fn session_title_from_message(message: &str) -> Option<String> {
+    let line = message.lines().find(|line| !line.trim().is_empty())?;
+    let collapsed = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    let truncated = truncate_graphemes(&collapsed, AUTO_TITLE_MAX_GRAPHEMES);
+    if truncated.len() < collapsed.len() {
+        Some(format!("{}…", truncated.trim_end()))
+    } else {
+        Some(collapsed)
+    }
+}
+
+/// Return the longest prefix of `text` containing at most `max_graphemes`
+/// extended grapheme clusters, so emoji, flags and combining sequences are
+/// never split.
+pub fn truncate_graphemes(text: &str, max_graphemes: usize) -> &str {
+    match text.grapheme_indices(true).nth(max_graphemes) {
+        Some((byte_index, _)) => &text[..byte_index],
+        None => text,
+    }
+}
+
+/// Return the largest length `<= max_len` at which `bytes` can be cut without
+/// leaving a partial UTF-8 sequence at the end. Use it before capping raw
+/// process output so lossy decoding does not produce a trailing `\u{FFFD}`.
+pub fn utf8_prefix_len(bytes: &[u8], max_len: usize) -> usize {
+    let end = max_len.min(bytes.len());
+    // Find the start of the last (possibly partial) sequence: at most three
+    // continuation bytes can precede the cut.
+    let Some(start) = (end.saturating_sub(4)..end)
+        .rev()
+        .find(|&i| bytes[i] & 0b1100_0000 != 0b1000_0000)
+    else {
+        return end;
+    };
+    let expected = match bytes[start] {
+        b if b < 0x80 => 1,
+        b if b >= 0xF0 => 4,
+        b if b >= 0xE0 => 3,
+        b if b >= 0xC0 => 2,
+        _ => 1,
+    };
+    if start + expected > end { start } else { end }
+}
+
 pub fn resume_command(thread_name: Option<&str>, thread_id: Option<ThreadId>) -> Option<String> {
     let resume_target = thread_name
         .filter(|name| !name.is_empty())
@@ -122,6 +184,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn truncate_graphemes_keeps_clusters_intact() {
+        assert_eq!(truncate_graphemes("héllo", 2), "hé");
+        assert_eq!(truncate_graphemes("👩‍💻🚀x", 1), "👩‍💻");
+        assert_eq!(truncate_graphemes("e\u{301}a", 1), "e\u{301}");
+        assert_eq!(truncate_graphemes("abc", 10), "abc");
+        assert_eq!(truncate_graphemes("abc", 0), "");
+    }
+
+    #[test]
+    fn utf8_prefix_len_backs_off_partial_sequences() {
+        let spinner = "ok ⠋".as_bytes();
+        assert_eq!(utf8_prefix_len(spinner, 4), 3);
+        assert_eq!(utf8_prefix_len(spinner, 5), 3);
+        assert_eq!(utf8_prefix_len(spinner, 6), 6);
+        assert_eq!(utf8_prefix_len(spinner, 100), 6);
+        assert_eq!(utf8_prefix_len(&spinner[..5], 100), 3);
+    }
+
+    #[test]
+    fn truncation_helpers_never_split_random_unicode() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        const ALPHABET: &[&str] = &[
+            "a",
+            "é",
+            "e\u{301}",
+            "中",
+            "ｶ",
+            "🚀",
+            "👩‍💻",
+            "🇯🇵",
+            "⠋",
+            "\u{200d}",
+            "\n",
+        ];
+        // One seeded case per seed, so a failure names the seed to replay.
+        for seed in 0..500 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let len = rng.random_range(0..24);
+            let text: String = (0..len)
+                .map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())])
+                .collect();
+            let max = rng.random_range(0..30);
+
+            let prefix = truncate_graphemes(&text, max);
+            assert!(text.starts_with(prefix), "seed {seed}: {text:?} at {max}");
+            assert!(
+                prefix.graphemes(true).count() <= max,
+                "seed {seed}: {text:?} at {max}"
+            );
+
+            let cut = utf8_prefix_len(text.as_bytes(), max);
+            assert!(
+                cut <= max.min(text.len()),
+                "seed {seed}: {text:?} cut at {cut}"
+            );
+            assert!(
+                text.is_char_boundary(cut),
+                "seed {seed}: {text:?} cut at {cut}"
+            );
+        }
+    }
+
     #[test]
     fn test_try_parse_error_message_no_error() {
         let text = r#"{"message": "test"}"#;
@@ -146,6 +273,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn normalize_session_tags_trims_and_dedupes() {
+        let tags = vec![
+            " auth ".to_string(),
+            String::new(),
+            "refactor".to_string(),
+            "auth".to_string(),
+        ];
+        assert_eq!(
+            normalize_session_tags(&tags),
+            vec!["auth".to_string(), "refactor".to_string()]
+        );
+    }
+
+    #[test]
+    fn session_title_uses_first_non_empty_line() {
+        assert_eq!(
+            session_title_from_message("\n  Fix   the auth\trefactor \nmore details"),
+            Some("Fix the auth refactor".to_string())
+        );
+        assert_eq!(session_title_from_message("  \n "), None);
+
+        let long = "word ".repeat(30);
+        let title = session_title_from_message(&long).expect("title");
+        assert!(title.ends_with('…'), "unexpected title: {title}");
+        assert_eq!(title.chars().count(), AUTO_TITLE_MAX_GRAPHEMES);
+    }
+
     #[test]
     fn resume_command_prefers_name_over_id() {
         let thread_id = ThreadId::from_string("123e4567-e89b-12d3-a456-426614174000").unwrap();
@@ -0,0 +1,444 @@
+//! Workspace summary added to the initial context.
+//!
+//! When the session starts inside a git repository, the model gets a short
+//! overview of it up front: the detected languages and build systems, the
+//! current branch and last few commits, the layout to depth 2 (ignored files
+//! left out) and the start of the main manifests. The summary is cut to
+//! `workspace_summary.max_tokens`.
+//!
+//! Summaries are cached in `codex_home/workspace_summaries`, keyed by the
+//! repository root, HEAD and the `git status` of the tree, so a session in an
+//! unchanged repository reuses the last one without reading the tree again.
+//! Each session builds or loads its summary once and reports it with a
+//! [`WorkspaceSummaryEvent`].
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::WorkspaceSummaryEvent;
+use sha2::Digest;
+use sha2::Sha256;
+use tracing::warn;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::git_info::CommitLogEntry;
+use crate::git_info::current_branch_name;
+use crate::git_info::get_git_repo_root;
+use crate::git_info::head_sha;
+use crate::git_info::listed_files;
+use crate::git_info::recent_commits;
+use crate::git_info::status_porcelain;
+use crate::protocol::EventMsg;
+use crate::session_prefix::WORKSPACE_SUMMARY_CLOSE_TAG;
+use crate::session_prefix::WORKSPACE_SUMMARY_OPEN_TAG;
+use crate::truncate::approx_bytes_for_tokens;
+use crate::truncate::approx_token_count;
+
+/// Bump when the rendered summary changes so stale cache entries are ignored.
+const SUMMARY_FORMAT_VERSION: u32 = 1;
+
+const CACHE_DIR: &str = "workspace_summaries";
+
+/// Cached summaries kept in `codex_home`; the oldest are removed beyond this.
+const MAX_CACHED_SUMMARIES: usize = 64;
+
+const RECENT_COMMITS: usize = 5;
+
+/// Entries listed per directory before the rest are counted instead.
+const MAX_ENTRIES_PER_DIR: usize = 20;
+
+const MAX_MANIFEST_EXCERPTS: usize = 4;
+
+const MANIFEST_EXCERPT_LINES: usize = 20;
+
+/// Files that identify a language or build system, with whether their start
+/// is worth quoting.
+const MARKERS: &[(&str, &str, bool)] = &[
+    ("Cargo.toml", "Rust (Cargo)", true),
+    ("package.json", "JavaScript/TypeScript (npm)", true),
+    ("pnpm-workspace.yaml", "pnpm", false),
+    ("yarn.lock", "Yarn", false),
+    ("tsconfig.json", "TypeScript", false),
+    ("pyproject.toml", "Python", true),
+    ("requirements.txt", "Python (pip)", false),
+    ("setup.py", "Python (setuptools)", false),
+    ("go.mod", "Go", true),
+    ("pom.xml", "Java (Maven)", true),
+    ("build.gradle", "JVM (Gradle)", true),
+    ("build.gradle.kts", "JVM (Gradle)", true),
+    ("Gemfile", "Ruby (Bundler)", true),
+    ("composer.json", "PHP (Composer)", true),
+    ("Package.swift", "Swift (SwiftPM)", true),
+    ("mix.exs", "Elixir (Mix)", true),
+    ("CMakeLists.txt", "C/C++ (CMake)", true),
+    ("Makefile", "Make", false),
+    ("justfile", "just", false),
+    ("flake.nix", "Nix", false),
+    ("MODULE.bazel", "Bazel", false),
+    ("WORKSPACE", "Bazel", false),
+];
+
+/// Summaries built or loaded by this session, by repository root. `None`
+/// records that there is nothing to summarize.
+#[derive(Default)]
+pub(crate) struct WorkspaceSummaries {
+    by_root: HashMap<PathBuf, Option<String>>,
+}
+
+/// The workspace summary for the turn's working directory as a user message,
+/// or `None` when disabled or outside a git repository.
+pub(crate) async fn workspace_summary_item(
+    sess: &Session,
+    turn_context: &TurnContext,
+) -> Option<ResponseItem> {
+    let config = turn_context.client.config();
+    if !config.workspace_summary.enabled {
+        return None;
+    }
+    let root = get_git_repo_root(&turn_context.cwd)?;
+    let mut summaries = sess.services.workspace_summaries.lock().await;
+    let cached = summaries.by_root.get(&root).cloned();
+    let summary = match cached {
+        Some(summary) => summary,
+        None => {
+            let loaded = load_or_build(
+                &root,
+                &config.codex_home,
+                config.workspace_summary.max_tokens,
+            )
+            .await;
+            let summary = loaded.map(|(summary, cache_hit)| {
+                let event = summary_event(&summary, cache_hit);
+                (summary, event)
+            });
+            summaries
+                .by_root
+                .insert(root, summary.as_ref().map(|(text, _)| text.clone()));
+            drop(summaries);
+            let (summary, event) = summary?;
+            sess.send_event(turn_context, EventMsg::WorkspaceSummary(event))
+                .await;
+            Some(summary)
+        }
+    }?;
+    Some(ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText { text: summary }],
+        end_turn: None,
+    })
+}
+
+/// The summary of the repository at `root` and whether it came from the cache.
+async fn load_or_build(
+    root: &Path,
+    codex_home: &Path,
+    max_tokens: usize,
+) -> Option<(String, bool)> {
+    let status = status_porcelain(root).await?;
+    let head = head_sha(root).await;
+    let path = codex_home.join(CACHE_DIR).join(format!(
+        "{}.md",
+        cache_key(root, head.as_deref(), &status, max_tokens)
+    ));
+    if let Ok(summary) = tokio::fs::read_to_string(&path).await {
+        return Some((summary, true));
+    }
+
+    let files = listed_files(root).await?;
+    let branch = current_branch_name(root).await;
+    let commits = recent_commits(root, RECENT_COMMITS).await;
+    let manifests = manifest_excerpts(root, &files).await;
+    let summary = render(
+        &WorkspaceFacts {
+            root,
+            branch: branch.as_deref(),
+            commits: &commits,
+            files: &files,
+            manifests: &manifests,
+        },
+        approx_bytes_for_tokens(max_tokens),
+    );
+    if let Err(err) = store(&path, &summary).await {
+        warn!(
+            "failed to cache workspace summary at {}: {err}",
+            path.display()
+        );
+    }
+    Some((summary, false))
+}
+
+fn cache_key(root: &Path, head: Option<&str>, status: &str, max_tokens: usize) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("v{SUMMARY_FORMAT_VERSION}\0{max_tokens}\0").as_bytes());
+    hasher.update(root.to_string_lossy().as_bytes());
+    hasher.update(b"\0");
+    hasher.update(head.unwrap_or("unborn").as_bytes());
+    hasher.update(b"\0");
+    hasher.update(status.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Writes `summary` to the cache, then trims the cache to
+/// [`MAX_CACHED_SUMMARIES`] entries.
+async fn store(path: &Path, summary: &str) -> std::io::Result<()> {
+    let Some(dir) = path.parent() else {
+        return Ok(());
+    };
+    tokio::fs::create_dir_all(dir).await?;
+    tokio::fs::write(path, summary).await?;
+
+    let mut entries = Vec::new();
+    let mut read_dir = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = read_dir.next_entry().await? {
+        let modified = entry.metadata().await?.modified()?;
+        entries.push((modified, entry.path()));
+    }
+    if entries.len() > MAX_CACHED_SUMMARIES {
+        entries.sort();
+        for (_, stale) in &entries[..entries.len() - MAX_CACHED_SUMMARIES] {
+            tokio::fs::remove_file(stale).await?;
+        }
+    }
+    Ok(())
+}
+
+/// The first lines of up to [`MAX_MANIFEST_EXCERPTS`] manifests at depth 1
+/// or 2, shallowest first.
+async fn manifest_excerpts(root: &Path, files: &[String]) -> Vec<(String, String)> {
+    let mut manifests: Vec<&String> = files
+        .iter()
+        .filter(|path| {
+            marker(path).is_some_and(|(_, _, quote)| quote) && path.matches('/').count() <= 1
+        })
+        .collect();
+    manifests.sort_by(|a, b| (a.matches('/').count(), a).cmp(&(b.matches('/').count(), b)));
+
+    let mut excerpts = Vec::new();
+    for path in manifests.into_iter().take(MAX_MANIFEST_EXCERPTS) {
+        let Ok(contents) = tokio::fs::read_to_string(root.join(path)).await else {
+            continue;
+        };
+        let excerpt = contents
+            .lines()
+            .take(MANIFEST_EXCERPT_LINES)
+            .collect::<Vec<_>>()
+            .join("\n");
+        excerpts.push((path.clone(), excerpt));
+    }
+    excerpts
+}
+
+/// The [`MARKERS`] entry matching the file name of `path`.
+fn marker(path: &str) -> Option<(&'static str, &'static str, bool)> {
+    let name = path.rsplit('/').next()?;
+    MARKERS
+        .iter()
+        .find(|(marker, _, _)| *marker == name)
+        .copied()
+}
+
+struct WorkspaceFacts<'a> {
+    root: &'a Path,
+    branch: Option<&'a str>,
+    commits: &'a [CommitLogEntry],
+    files: &'a [String],
+    manifests: &'a [(String, String)],
+}
+
+/// Renders the summary, most useful sections first, cutting it off at a line
+/// boundary once it would exceed `max_bytes`.
+fn render(facts: &WorkspaceFacts<'_>, max_bytes: usize) -> String {
+    let mut lines = vec![format!("Repository root: {}", facts.root.display())];
+    if let Some(branch) = facts.branch {
+        lines.push(format!("Branch: {branch}"));
+    }
+    let mut languages: Vec<&str> = Vec::new();
+    for (_, label, _) in facts
+        .files
+        .iter()
+        .filter(|path| path.matches('/').count() <= 1)
+        .filter_map(|path| marker(path))
+    {
+        if !languages.contains(&label) {
+            languages.push(label);
+        }
+    }
+    if !languages.is_empty() {
+        lines.push(format!(
+            "Languages and build systems: {}",
+            languages.join(", ")
+        ));
+    }
+
+    if !facts.commits.is_empty() {
+        lines.push(String::new());
+        lines.push("Recent commits:".to_string());
+        for commit in facts.commits {
+            let short_sha: String = commit.sha.chars().take(8).collect();
+            lines.push(format!("- {short_sha} {}", commit.subject));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("Layout (depth 2, ignored files omitted):".to_string());
+    lines.extend(layout(facts.files));
+
+    for (path, excerpt) in facts.manifests {
+        lines.push(String::new());
+        lines.push(format!(
+            "--- {path} (first {MANIFEST_EXCERPT_LINES} lines) ---"
+        ));
+        lines.extend(excerpt.lines().map(str::to_string));
+    }
+
+    let footer = format!("[summary truncated]\n{WORKSPACE_SUMMARY_CLOSE_TAG}");
+    let mut summary = format!("{WORKSPACE_SUMMARY_OPEN_TAG}\n");
+    for line in &lines {
+        if summary.len() + line.len() + 1 + footer.len() > max_bytes {
+            summary.push_str(&footer);
+            return summary;
+        }
+        summary.push_str(line);
+        summary.push('\n');
+    }
+    summary.push_str(WORKSPACE_SUMMARY_CLOSE_TAG);
+    summary
+}
+
+/// The top two levels of `files`, directories first, as indented lines.
+fn layout(files: &[String]) -> Vec<String> {
+    let mut top: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    let mut top_files = BTreeSet::new();
+    for path in files {
+        match path.split_once('/') {
+            Some((dir, rest)) => {
+                let child = match rest.split_once('/') {
+                    Some((child, _)) => format!("{child}/"),
+                    None => rest.to_string(),
+                };
+                top.entry(dir).or_default().insert(child);
+            }
+            None => {
+                top_files.insert(path.as_str());
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (dir, children) in &top {
+        lines.push(format!("{dir}/"));
+        let (child_dirs, child_files): (Vec<_>, Vec<_>) =
+            children.iter().partition(|child| child.ends_with('/'));
+        let children: Vec<_> = child_dirs.into_iter().chain(child_files).collect();
+        for child in children.iter().take(MAX_ENTRIES_PER_DIR) {
+            lines.push(format!("  {child}"));
+        }
+        if children.len() > MAX_ENTRIES_PER_DIR {
+            lines.push(format!(
+                "  ... {} more",
+                children.len() - MAX_ENTRIES_PER_DIR
+            ));
+        }
+    }
+    lines.extend(top_files.iter().map(|file| (*file).to_string()));
+    lines
+}
+
+fn summary_event(summary: &str, cache_hit: bool) -> WorkspaceSummaryEvent {
+    WorkspaceSummaryEvent {
+        bytes: summary.len() as u64,
+        approx_tokens: approx_token_count(summary) as u64,
+        cache_hit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|path| (*path).to_string()).collect()
+    }
+
+    #[test]
+    fn layout_lists_two_levels_with_directories_first() {
+        let files = files(&[
+            "README.md",
+            "Cargo.toml",
+            "core/src/lib.rs",
+            "core/Cargo.toml",
+            "core/tests/all.rs",
+            "docs/config.md",
+        ]);
+
+        assert_eq!(
+            layout(&files),
+            vec![
+                "core/",
+                "  src/",
+                "  tests/",
+                "  Cargo.toml",
+                "docs/",
+                "  config.md",
+                "Cargo.toml",
+                "README.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn render_detects_build_systems_and_stays_within_budget() {
+        let files = files(&["Cargo.toml", "web/package.json", "Makefile", "src/main.rs"]);
+        let commits = vec![CommitLogEntry {
+            sha: "0123456789abcdef".to_string(),
+            timestamp: 0,
+            subject: "Initial commit".to_string(),
+        }];
+        let manifests = vec![(
+            "Cargo.toml".to_string(),
+            "[package]\nname = \"demo\"".to_string(),
+        )];
+        let facts = WorkspaceFacts {
+            root: Path::new("/repo"),
+            branch: Some("main"),
+            commits: &commits,
+            files: &files,
+            manifests: &manifests,
+        };
+
+        let full = render(&facts, usize::MAX);
+        assert!(full.starts_with(
+            "<workspace_summary>\nRepository root: /repo\nBranch: main\n\
+             Languages and build systems: Rust (Cargo), JavaScript/TypeScript (npm), Make\n\n\
+             Recent commits:\n- 01234567 Initial commit\n"
+        ));
+        assert!(full.ends_with("[package]\nname = \"demo\"\n</workspace_summary>"));
+
+        let cut = render(&facts, 200);
+        assert!(cut.len() <= 200, "{cut}");
+        assert!(cut.ends_with("[summary truncated]\n</workspace_summary>"));
+        assert!(cut.contains("Recent commits:"));
+    }
+
+    #[test]
+    fn cache_key_changes_with_head_and_status() {
+        let root = Path::new("/repo");
+        let clean = cache_key(root, Some("abc"), "", 1_500);
+
+        assert_eq!(clean, cache_key(root, Some("abc"), "", 1_500));
+        assert_ne!(clean, cache_key(root, Some("def"), "", 1_500));
+        assert_ne!(
+            clean,
+            cache_key(root, Some("abc"), " M src/lib.rs\0", 1_500)
+        );
+        assert_ne!(clean, cache_key(root, Some("abc"), "", 500));
+    }
+}
@@ -0,0 +1,334 @@
+//! Opt-in watch over the workspace roots for edits made outside the session.
+//!
+//! With `watch_workspace` set, the roots are watched recursively. Only files
+//! the session has read or written (as recorded by
+//! [`crate::file_provenance::FileProvenance`]) are tracked; a change to one of
+//! them after the session last touched it is held until it has been quiet for
+//! [`DEBOUNCE`], then listed in a developer note on the next model request
+//! and reported with [`ExternalFilesChangedEvent`]. Changes that land within
+//! [`OWN_WRITE_GRACE`] of the session touching a file are taken to be the
+//! session's own writes. Like file provenance this is best effort: a file a
+//! command rewrote without provenance noticing may be reported too.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::models::DeveloperInstructions;
+use codex_protocol::models::ResponseItem;
+use notify::Event;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use notify::event::ModifyKind;
+use tracing::warn;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::protocol::EventMsg;
+use crate::protocol::ExternalFilesChangedEvent;
+
+/// How long a file must go without further events before its change is
+/// reported, so an editor's save burst is reported once.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Events this soon after the session read or wrote a file are attributed to
+/// the session.
+const OWN_WRITE_GRACE: Duration = Duration::from_secs(2);
+
+/// Files listed per note; the rest are only counted.
+const MAX_REPORTED_FILES: usize = 20;
+
+pub(crate) struct WorkspaceWatcher {
+    state: Arc<Mutex<WatchState>>,
+    /// Set once the background setup finishes; dropping it stops the watch.
+    _watcher: Arc<Mutex<Option<RecommendedWatcher>>>,
+}
+
+impl WorkspaceWatcher {
+    /// Start watching `roots` in the background, since registering a large
+    /// tree can take a while. The first root is the session's `cwd`.
+    pub(crate) fn spawn(roots: Vec<PathBuf>) -> Self {
+        let state = Arc::new(Mutex::new(WatchState::default()));
+        let watcher = Arc::new(Mutex::new(None));
+        let cwd = roots.first().cloned().unwrap_or_default();
+        let task_state = Arc::clone(&state);
+        let task_watcher = Arc::clone(&watcher);
+        tokio::task::spawn_blocking(move || match watch(&roots, task_state) {
+            Ok(watcher) => *lock(&task_watcher) = Some(watcher),
+            Err(err) => warn!("failed to watch {}: {err}", cwd.display()),
+        });
+        Self {
+            state,
+            _watcher: watcher,
+        }
+    }
+
+    /// Note that the session just read or wrote `paths` (absolute).
+    pub(crate) fn note_session_access(&self, paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let mut state = lock(&self.state);
+        for path in paths {
+            state.note_access(path, now);
+        }
+    }
+
+    /// Changes that have settled since the last call, sorted by path, with
+    /// how many were left off the list.
+    fn take_changes(&self) -> (Vec<PathBuf>, usize) {
+        let mut changed = lock(&self.state).take_settled(Instant::now());
+        let omitted = changed.len().saturating_sub(MAX_REPORTED_FILES);
+        changed.truncate(MAX_REPORTED_FILES);
+        (changed, omitted)
+    }
+
+    #[cfg(test)]
+    fn is_watching(&self) -> bool {
+        lock(&self._watcher).is_some()
+    }
+}
+
+/// Tell the model which files it has seen were changed outside the session
+/// since, before it builds its next request. A no-op unless
+/// `watch_workspace` is set.
+pub(crate) async fn note_external_changes(session: &Session, turn: &TurnContext) {
+    let Some(watcher) = session.services.workspace_watcher.as_ref() else {
+        return;
+    };
+    let (changed, omitted) = watcher.take_changes();
+    if changed.is_empty() {
+        return;
+    }
+    let paths: Vec<PathBuf> = changed
+        .iter()
+        .map(|path| display_path(&turn.cwd, path))
+        .collect();
+    let item: ResponseItem = DeveloperInstructions::new(note_message(&paths, omitted)).into();
+    session.record_conversation_items(turn, &[item]).await;
+    session
+        .send_event(
+            turn,
+            EventMsg::ExternalFilesChanged(ExternalFilesChangedEvent {
+                paths,
+                omitted: u32::try_from(omitted).unwrap_or(u32::MAX),
+            }),
+        )
+        .await;
+}
+
+fn note_message(paths: &[PathBuf], omitted: usize) -> String {
+    let mut lines: Vec<String> = paths
+        .iter()
+        .map(|path| format!("- {}", path.display()))
+        .collect();
+    if omitted > 0 {
+        lines.push(format!("- ...and {omitted} more"));
+    }
+    format!(
+        "The user modified these files outside this session since you last read or wrote them:\n{}\n\
+         Read them again before relying on or patching their contents.",
+        lines.join("\n")
+    )
+}
+
+/// `path` relative to the session's `cwd` when it is under it.
+fn display_path(cwd: &Path, path: &Path) -> PathBuf {
+    match path.strip_prefix(cwd) {
+        Ok(relative) if !relative.as_os_str().is_empty() => relative.to_path_buf(),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn watch(roots: &[PathBuf], state: Arc<Mutex<WatchState>>) -> notify::Result<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) if is_content_change(event.kind) => {
+            let now = Instant::now();
+            let mut state = lock(&state);
+            for path in &event.paths {
+                state.record_change(path, now);
+            }
+        }
+        Ok(_) => {}
+        Err(err) => warn!("workspace watch error: {err}"),
+    })?;
+    for root in roots {
+        watcher.watch(root, RecursiveMode::Recursive)?;
+    }
+    Ok(watcher)
+}
+
+/// Reads and permission or timestamp changes do not make the model's copy
+/// stale.
+fn is_content_change(kind: EventKind) -> bool {
+    match kind {
+        EventKind::Create(_) | EventKind::Remove(_) => true,
+        EventKind::Modify(ModifyKind::Metadata(_)) => false,
+        EventKind::Modify(_) => true,
+        EventKind::Access(_) | EventKind::Any | EventKind::Other => false,
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[derive(Debug, Default)]
+struct WatchState {
+    /// When the session last read or wrote each file.
+    seen: HashMap<PathBuf, Instant>,
+    /// Latest unreported outside change to each seen file.
+    changed: HashMap<PathBuf, Instant>,
+}
+
+impl WatchState {
+    fn note_access(&mut self, path: PathBuf, now: Instant) {
+        self.changed.remove(&path);
+        self.seen.insert(path, now);
+    }
+
+    fn record_change(&mut self, path: &Path, now: Instant) {
+        let Some(seen) = self.seen.get(path) else {
+            return;
+        };
+        if now.saturating_duration_since(*seen) >= OWN_WRITE_GRACE {
+            self.changed.insert(path.to_path_buf(), now);
+        }
+    }
+
+    /// Remove and return, sorted, the changes quiet for at least
+    /// [`DEBOUNCE`]. Reporting counts as the session seeing the file.
+    fn take_settled(&mut self, now: Instant) -> Vec<PathBuf> {
+        let mut settled: Vec<PathBuf> = self
+            .changed
+            .iter()
+            .filter(|(_, at)| now.saturating_duration_since(**at) >= DEBOUNCE)
+            .map(|(path, _)| path.clone())
+            .collect();
+        settled.sort();
+        for path in &settled {
+            self.changed.remove(path);
+            self.seen.insert(path.clone(), now);
+        }
+        settled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn path(name: &str) -> PathBuf {
+        PathBuf::from("/repo").join(name)
+    }
+
+    #[test]
+    fn only_reports_files_the_session_has_seen() {
+        let start = Instant::now();
+        let mut state = WatchState::default();
+        state.note_access(path("src/app.rs"), start);
+
+        let later = start + OWN_WRITE_GRACE;
+        state.record_change(&path("src/app.rs"), later);
+        state.record_change(&path("target/debug/app"), later);
+
+        assert_eq!(
+            state.take_settled(later + DEBOUNCE),
+            vec![path("src/app.rs")]
+        );
+        assert_eq!(
+            state.take_settled(later + DEBOUNCE * 2),
+            Vec::<PathBuf>::new()
+        );
+    }
+
+    #[test]
+    fn ignores_events_from_the_sessions_own_writes() {
+        let start = Instant::now();
+        let mut state = WatchState::default();
+
+        // The event for a patch can arrive just before or just after the
+        // session records the write.
+        state.note_access(path("a.rs"), start);
+        state.record_change(&path("a.rs"), start + Duration::from_millis(50));
+        state.record_change(&path("b.rs"), start);
+        state.note_access(path("b.rs"), start);
+
+        assert_eq!(
+            state.take_settled(start + OWN_WRITE_GRACE * 2),
+            Vec::<PathBuf>::new()
+        );
+    }
+
+    #[test]
+    fn waits_for_bursts_to_settle() {
+        let start = Instant::now();
+        let mut state = WatchState::default();
+        state.note_access(path("a.rs"), start);
+
+        let first = start + OWN_WRITE_GRACE;
+        state.record_change(&path("a.rs"), first);
+        let second = first + DEBOUNCE / 2;
+        state.record_change(&path("a.rs"), second);
+
+        assert_eq!(state.take_settled(first + DEBOUNCE), Vec::<PathBuf>::new());
+        assert_eq!(state.take_settled(second + DEBOUNCE), vec![path("a.rs")]);
+    }
+
+    #[test]
+    fn note_lists_files_and_counts_the_rest() {
+        let message = note_message(&[PathBuf::from("src/app.rs")], 3);
+        assert_eq!(
+            message,
+            "The user modified these files outside this session since you last read or wrote them:\n\
+             - src/app.rs\n\
+             - ...and 3 more\n\
+             Read them again before relying on or patching their contents."
+        );
+        assert_eq!(
+            display_path(Path::new("/repo"), Path::new("/repo/src/app.rs")),
+            PathBuf::from("src/app.rs")
+        );
+        assert_eq!(
+            display_path(Path::new("/repo"), Path::new("/other/lib.rs")),
+            PathBuf::from("/other/lib.rs")
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_an_outside_edit_to_a_seen_file() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        // FSEvents reports canonical paths (`/private/var/...` on macOS).
+        let root = temp.path().canonicalize().expect("canonical tempdir");
+        let file = root.join("app.rs");
+        std::fs::write(&file, "fn main() {}\n").expect("write");
+
+        let watcher = WorkspaceWatcher::spawn(vec![root]);
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !watcher.is_watching() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(watcher.is_watching(), "watcher did not start");
+
+        // Backdate the read so the edit is not mistaken for the session's.
+        lock(&watcher.state).note_access(file.clone(), Instant::now() - OWN_WRITE_GRACE);
+        std::fs::write(&file, "fn main() { edited(); }\n").expect("write");
+
+        let mut reported = (Vec::new(), 0);
+        while reported.0.is_empty() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            reported = watcher.take_changes();
+        }
+        assert_eq!(reported, (vec![file], 0));
+    }
+}
@@ -58,8 +58,17 @@ async fn run_stream_with_bytes(sse_body: &[u8]) -> Vec<ResponseEvent> {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        first_byte_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
     };
 
     let codex_home = match TempDir::new() {
@@ -395,6 +395,19 @@ pub fn ev_done() -> Value {
     })
 }
 
+/// SSE event for a response that ended early with `incomplete_details.reason`.
+pub fn ev_incomplete(id: &str, reason: &str) -> Value {
+    serde_json::json!({
+        "type": "response.incomplete",
+        "response": {
+            "id": id,
+            "status": "incomplete",
+            "incomplete_details": {"reason": reason},
+            "usage": {"input_tokens":0,"input_tokens_details":null,"output_tokens":0,"output_tokens_details":null,"total_tokens":0}
+        }
+    })
+}
+
 /// Convenience: SSE event for a created response with a specific id.
 pub fn ev_response_created(id: &str) -> Value {
     serde_json::json!({
@@ -434,6 +447,19 @@ pub fn ev_assistant_message(id: &str, text: &str) -> Value {
     })
 }
 
+/// SSE event for an assistant message whose only content is a refusal.
+pub fn ev_refusal(id: &str, refusal: &str) -> Value {
+    serde_json::json!({
+        "type": "response.output_item.done",
+        "item": {
+            "type": "message",
+            "role": "assistant",
+            "id": id,
+            "content": [{"type": "refusal", "refusal": refusal}]
+        }
+    })
+}
+
 pub fn ev_message_item_added(id: &str, text: &str) -> Value {
     serde_json::json!({
         "type": "response.output_item.added",
@@ -7,6 +7,7 @@ use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 use tokio::net::TcpListener;
 use tokio::sync::Mutex as TokioMutex;
+use tokio::sync::Notify;
 use tokio::sync::oneshot;
 
 /// Streaming SSE chunk payload gated by a per-chunk signal.
@@ -20,6 +21,7 @@ pub struct StreamingSseChunk {
 pub struct StreamingSseServer {
     uri: String,
     requests: Arc<TokioMutex<Vec<Vec<u8>>>>,
+    disconnected: Arc<Notify>,
     shutdown: oneshot::Sender<()>,
     task: tokio::task::JoinHandle<()>,
 }
@@ -33,6 +35,12 @@ impl StreamingSseServer {
         self.requests.lock().await.clone()
     }
 
+    /// Resolves once a client hangs up on a response stream that is waiting
+    /// for a gate.
+    pub async fn wait_for_disconnect(&self) {
+        self.disconnected.notified().await;
+    }
+
     pub async fn shutdown(self) {
         let _ = self.shutdown.send(());
         let _ = self.task.await;
@@ -68,6 +76,8 @@ pub async fn start_streaming_sse_server(
     }));
     let requests = Arc::new(TokioMutex::new(Vec::new()));
     let requests_for_task = Arc::clone(&requests);
+    let disconnected = Arc::new(Notify::new());
+    let disconnected_for_task = Arc::clone(&disconnected);
     let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
 
     let task = tokio::spawn(async move {
@@ -78,6 +88,7 @@ pub async fn start_streaming_sse_server(
                     let (mut stream, _) = accept_res.expect("accept streaming SSE connection");
                     let state = Arc::clone(&state);
                     let requests = Arc::clone(&requests_for_task);
+                    let disconnected = Arc::clone(&disconnected_for_task);
                     tokio::spawn(async move {
                         let (request, body_prefix) = read_http_request(&mut stream).await;
                         let Some((method, path)) = parse_request_line(&request) else {
@@ -123,10 +134,17 @@ pub async fn start_streaming_sse_server(
                             }
 
                             for chunk in chunks {
-                                if let Some(gate) = chunk.gate
-                                    && gate.await.is_err() {
-                                        return;
+                                if let Some(gate) = chunk.gate {
+                                    tokio::select! {
+                                        opened = gate => if opened.is_err() {
+                                            return;
+                                        },
+                                        () = client_closed(&mut stream) => {
+                                            disconnected.notify_one();
+                                            return;
+                                        }
                                     }
+                                }
                                 if stream.write_all(chunk.body.as_bytes()).await.is_err() {
                                     return;
                                 }
@@ -149,6 +167,7 @@ pub async fn start_streaming_sse_server(
         StreamingSseServer {
             uri,
             requests,
+            disconnected,
             shutdown: shutdown_tx,
             task,
         },
@@ -189,6 +208,17 @@ async fn read_http_request(stream: &mut tokio::net::TcpStream) -> (String, Vec<u
     (String::from_utf8_lossy(&buf).into_owned(), Vec::new())
 }
 
+/// Resolves once the client closes the connection.
+async fn client_closed(stream: &mut tokio::net::TcpStream) {
+    let mut scratch = [0u8; 256];
+    loop {
+        match stream.read(&mut scratch).await {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+    }
+}
+
 fn parse_request_line(request: &str) -> Option<(&str, &str)> {
     let line = request.lines().next()?;
     let mut parts = line.split_whitespace();
@@ -295,6 +295,8 @@ impl TestCodex {
                 summary: ReasoningSummary::Auto,
                 collaboration_mode: None,
                 personality: None,
+                max_output_tokens: None,
+                service_tier: None,
             })
             .await?;
 
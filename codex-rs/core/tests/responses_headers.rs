@@ -56,8 +56,17 @@ async fn responses_stream_includes_subagent_header_on_review() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        first_byte_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -154,8 +163,17 @@ async fn responses_stream_includes_subagent_header_on_other() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        first_byte_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -308,8 +326,17 @@ async fn responses_respects_model_info_overrides_from_config() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        first_byte_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -2,20 +2,27 @@ use assert_matches::assert_matches;
 use std::sync::Arc;
 use std::time::Duration;
 
+use codex_core::protocol::CancelledWork;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
 use codex_protocol::user_input::UserInput;
 use core_test_support::responses::ev_completed;
 use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_message_item_added;
+use core_test_support::responses::ev_output_text_delta;
 use core_test_support::responses::ev_response_created;
 use core_test_support::responses::mount_sse_once;
 use core_test_support::responses::mount_sse_sequence;
 use core_test_support::responses::sse;
 use core_test_support::responses::start_mock_server;
+use core_test_support::streaming_sse::StreamingSseChunk;
+use core_test_support::streaming_sse::start_streaming_sse_server;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
 use regex_lite::Regex;
 use serde_json::json;
+use tokio::sync::oneshot;
 
 /// Integration test: spawn a long‑running shell_command tool via a mocked Responses SSE
 /// function call, then interrupt the session and expect TurnAborted.
@@ -61,7 +68,79 @@ async fn interrupt_long_running_tool_emits_turn_aborted() {
     codex.submit(Op::Interrupt).await.unwrap();
 
     // Expect TurnAborted soon after.
-    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnAborted(_))).await;
+    let EventMsg::TurnAborted(aborted) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnAborted(_))).await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert_eq!(
+        aborted.cancelled,
+        vec![CancelledWork::ToolCall {
+            call_id: "call_sleep".to_string(),
+            tool_name: "shell_command".to_string(),
+        }]
+    );
+}
+
+/// Interrupting while the model is still streaming closes the connection
+/// instead of reading the response to the end in the background.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn interrupt_during_stream_drops_the_connection() {
+    let (_gate_completed_tx, gate_completed_rx) = oneshot::channel();
+    let chunks = vec![
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![ev_response_created("resp-1")]),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![ev_message_item_added("msg-1", "")]),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![ev_output_text_delta("Thinking it ")]),
+        },
+        // Never opened: the response stalls until the client hangs up.
+        StreamingSseChunk {
+            gate: Some(gate_completed_rx),
+            body: sse(vec![ev_completed("resp-1")]),
+        },
+    ];
+    let (server, _completions) = start_streaming_sse_server(vec![chunks]).await;
+
+    let codex = test_codex()
+        .with_model("gpt-5.1")
+        .build_with_streaming_server(&server)
+        .await
+        .unwrap()
+        .codex;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "explain slowly".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |ev| {
+        matches!(ev, EventMsg::AgentMessageContentDelta(_))
+    })
+    .await;
+
+    codex.submit(Op::Interrupt).await.unwrap();
+
+    let EventMsg::TurnAborted(aborted) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnAborted(_))).await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert_eq!(aborted.cancelled, vec![CancelledWork::ModelResponse]);
+    tokio::time::timeout(Duration::from_secs(5), server.wait_for_disconnect())
+        .await
+        .expect("the interrupted response stream should be closed");
 }
 
 /// After an interrupt we expect the next request to the model to include both
@@ -313,6 +313,8 @@ async fn apply_patch_cli_move_without_content_change_has_no_turn_diff(
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -902,6 +904,8 @@ async fn apply_patch_shell_command_heredoc_with_cd_emits_turn_diff() -> Result<(
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -982,6 +986,8 @@ async fn apply_patch_shell_command_failure_propagates_error_and_skips_diff() ->
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -1132,6 +1138,8 @@ async fn apply_patch_emits_turn_diff_event_with_unified_diff(
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -1195,6 +1203,8 @@ async fn apply_patch_turn_diff_for_rename_with_content_change(
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -1266,6 +1276,8 @@ async fn apply_patch_aggregates_diff_across_multiple_tool_calls() -> Result<()>
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -1337,6 +1349,8 @@ async fn apply_patch_aggregates_diff_preserves_success_after_failure() -> Result
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -1405,3 +1419,180 @@ async fn apply_patch_change_context_disambiguates_target(
     assert_eq!(contents, "fn a\nx=10\ny=2\nfn b\nx=11\ny=20\n");
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[test_case(ApplyPatchModelOutput::Freeform)]
+#[test_case(ApplyPatchModelOutput::ShellViaHeredoc)]
+async fn apply_patch_stale_context_returns_current_contents(
+    model_output: ApplyPatchModelOutput,
+) -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = apply_patch_harness().await?;
+
+    let target = harness.path("main.rs");
+    fs::write(&target, "fn main() {\n    println!(\"new\");\n}\n")?;
+
+    let patch = "*** Begin Patch\n*** Update File: main.rs\n@@\n fn main() {\n-    println!(\"old\");\n+    println!(\"patched\");\n }\n*** End Patch";
+    let call_id = "apply-stale";
+    mount_apply_patch(&harness, call_id, patch, "ok", model_output).await;
+
+    harness.submit("apply stale patch").await?;
+
+    let out = harness.apply_patch_output(call_id, model_output).await;
+    assert!(out.contains("Failed to find expected lines in"), "{out}");
+    assert!(
+        out.contains(&format!(
+            "<current_contents path=\"{}\" lines=\"1-3\">\n1\tfn main() {{\n2\t    println!(\"new\");\n3\t}}\n</current_contents>",
+            target.display()
+        )),
+        "expected the current file contents: {out}"
+    );
+    assert_eq!(
+        fs::read_to_string(&target)?,
+        "fn main() {\n    println!(\"new\");\n}\n"
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn apply_patch_stale_context_excerpt_is_capped() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = apply_patch_harness().await?;
+
+    let target = harness.path("big.txt");
+    let contents: String = (1..=5_000)
+        .map(|n| format!("line {n} {}\n", "x".repeat(60)))
+        .collect();
+    fs::write(&target, &contents)?;
+
+    let patch = "*** Begin Patch\n*** Update File: big.txt\n@@\n-line 2500 gone\n+line 2500 changed\n*** End Patch";
+    let call_id = "apply-stale-big";
+    mount_apply_patch(
+        &harness,
+        call_id,
+        patch,
+        "ok",
+        ApplyPatchModelOutput::Freeform,
+    )
+    .await;
+
+    harness.submit("apply stale patch to a big file").await?;
+
+    let out = harness
+        .apply_patch_output(call_id, ApplyPatchModelOutput::Freeform)
+        .await;
+    let (error, excerpt) = out
+        .split_once("\n\n")
+        .expect("current contents after the error");
+    assert!(
+        error.contains("Failed to find expected lines in"),
+        "{error}"
+    );
+    assert!(excerpt.contains("<current_contents"), "{excerpt}");
+    assert!(excerpt.contains(&format!("\n1\tline 1 {}\n", "x".repeat(60))));
+    assert!(
+        excerpt.len() <= 8 * 1024,
+        "excerpt is {} bytes",
+        excerpt.len()
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn apply_patch_stale_context_is_retried_once_when_enabled() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = apply_patch_harness_with(|builder| {
+        builder.with_config(|config| config.retry_stale_patches = true)
+    })
+    .await?;
+    let test = harness.test();
+    let codex = test.codex.clone();
+    let cwd = test.cwd.clone();
+
+    let target = cwd.path().join("notes.txt");
+    fs::write(&target, "alpha\nbeta\n")?;
+
+    let stale =
+        "*** Begin Patch\n*** Update File: notes.txt\n@@\n-alpha\n-gamma\n+ALPHA\n*** End Patch";
+    let fixed = "*** Begin Patch\n*** Update File: notes.txt\n@@\n-alpha\n+ALPHA\n*** End Patch";
+    let bodies = vec![
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_apply_patch_custom_tool_call("stale-call", stale),
+            ev_completed("resp-1"),
+        ]),
+        sse(vec![
+            ev_assistant_message("msg-1", "the patch did not apply"),
+            ev_completed("resp-2"),
+        ]),
+        sse(vec![
+            ev_response_created("resp-3"),
+            ev_apply_patch_custom_tool_call("retry-call", fixed),
+            ev_completed("resp-3"),
+        ]),
+        sse(vec![
+            ev_assistant_message("msg-2", "done"),
+            ev_completed("resp-4"),
+        ]),
+    ];
+    let response = mount_sse_sequence(harness.server(), bodies).await;
+
+    let model = test.session_configured.model.clone();
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "uppercase alpha".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+            max_output_tokens: None,
+            service_tier: None,
+        })
+        .await?;
+
+    let mut context_events = Vec::new();
+    let mut retry_events = Vec::new();
+    wait_for_event(&codex, |event| match event {
+        EventMsg::StalePatchContext(event) => {
+            context_events.push(event.clone());
+            false
+        }
+        EventMsg::StalePatchRetry(event) => {
+            retry_events.push(event.clone());
+            false
+        }
+        EventMsg::TurnComplete(_) => true,
+        _ => false,
+    })
+    .await;
+
+    assert_eq!(context_events.len(), 1);
+    assert_eq!(context_events[0].call_id, "stale-call");
+    assert_eq!(context_events[0].path, target);
+    assert_eq!(retry_events.len(), 1);
+    assert_eq!(retry_events[0].paths, vec![target.clone()]);
+    assert_eq!(fs::read_to_string(&target)?, "ALPHA\nbeta\n");
+
+    let requests = response.requests();
+    assert_eq!(requests.len(), 4);
+    let retry_prompt = requests[2].message_input_texts("developer");
+    assert!(
+        retry_prompt.iter().any(
+            |text| text.contains("regenerate the patch for only these files")
+                && text.contains(&target.display().to_string())
+        ),
+        "{retry_prompt:?}"
+    );
+    Ok(())
+}
@@ -503,6 +503,8 @@ async fn submit_turn(
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -956,7 +958,7 @@ fn scenarios() -> Vec<ScenarioSpec> {
             model_override: Some("gpt-5"),
             outcome: Outcome::ExecApproval {
                 decision: ReviewDecision::Approved,
-                expected_reason: Some("command failed; retry without sandbox?"),
+                expected_reason: Some("command blocked by sandbox ("),
             },
             expectation: Expectation::FileCreated {
                 target: TargetPath::Workspace("ro_on_failure.txt"),
@@ -977,7 +979,7 @@ fn scenarios() -> Vec<ScenarioSpec> {
             model_override: Some("gpt-5.1"),
             outcome: Outcome::ExecApproval {
                 decision: ReviewDecision::Approved,
-                expected_reason: Some("command failed; retry without sandbox?"),
+                expected_reason: Some("command blocked by sandbox ("),
             },
             expectation: Expectation::FileCreatedNoExitCode {
                 target: TargetPath::Workspace("ro_on_failure_5_1.txt"),
@@ -1335,7 +1337,7 @@ fn scenarios() -> Vec<ScenarioSpec> {
             model_override: Some("gpt-5"),
             outcome: Outcome::ExecApproval {
                 decision: ReviewDecision::Approved,
-                expected_reason: Some("command failed; retry without sandbox?"),
+                expected_reason: Some("command blocked by sandbox ("),
             },
             expectation: Expectation::FileCreated {
                 target: TargetPath::OutsideWorkspace("ww_on_failure.txt"),
@@ -1519,10 +1521,12 @@ async fn run_scenario(scenario: &ScenarioSpec) -> Result<()> {
                 .expect("exec approval requires shell command");
             let approval = expect_exec_approval(&test, command).await;
             if let Some(expected_reason) = expected_reason {
-                assert_eq!(
-                    approval.reason.as_deref(),
-                    Some(*expected_reason),
-                    "unexpected approval reason for {}",
+                // Sandbox retry reasons quote the platform's denial message, so only the
+                // stable prefix is compared.
+                let reason = approval.reason.as_deref().unwrap_or_default();
+                assert!(
+                    reason.starts_with(expected_reason),
+                    "unexpected approval reason {reason:?} for {}",
                     scenario.name
                 );
             }
@@ -1849,3 +1853,90 @@ async fn approving_execpolicy_amendment_persists_policy_and_skips_future_prompts
 
     Ok(())
 }
+
+#[tokio::test(flavor = "current_thread")]
+#[cfg(unix)]
+async fn protect_dirty_files_requires_approval_for_uncommitted_edits() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let approval_policy = AskForApproval::OnRequest;
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        exclude_tmpdir_env_var: false,
+        exclude_slash_tmp: false,
+    };
+    let sandbox_policy_for_config = sandbox_policy.clone();
+
+    let mut builder = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(move |config| {
+            config.approval_policy = Constrained::allow_any(approval_policy);
+            config.sandbox_policy = Constrained::allow_any(sandbox_policy_for_config);
+            config.protect_dirty_files = true;
+        });
+    let test = builder.build(&server).await?;
+
+    // A committed file that the user has since edited without committing.
+    let cwd = test.cwd.path();
+    let path = cwd.join("notes.txt");
+    fs::write(&path, "before\n")?;
+    for args in [
+        vec!["init"],
+        vec!["add", "notes.txt"],
+        vec![
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "initial",
+        ],
+    ] {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .status()?;
+        assert!(status.success());
+    }
+    fs::write(&path, "before\nuser edit\n")?;
+
+    let call_id = "apply_patch_dirty_file";
+    let patch = "*** Begin Patch\n*** Update File: notes.txt\n@@\n-before\n+after\n*** End Patch\n";
+    let _ = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_apply_patch_function_call(call_id, patch),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let _ = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    submit_turn(&test, "edit notes", approval_policy, sandbox_policy).await?;
+
+    // The workspace-write sandbox would auto-approve this patch; the dirty
+    // target forces a prompt that names it.
+    let approval = expect_patch_approval(&test, call_id).await;
+    assert_eq!(approval.dirty_paths, vec![path.clone()]);
+    test.codex
+        .submit(Op::PatchApproval {
+            id: "0".into(),
+            decision: ReviewDecision::Approved,
+        })
+        .await?;
+    wait_for_completion(&test).await;
+    assert_eq!(fs::read_to_string(&path)?, "after\nuser edit\n");
+
+    Ok(())
+}
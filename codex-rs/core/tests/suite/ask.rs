@@ -0,0 +1,73 @@
+use anyhow::Result;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+fn tool_count(body: &Value) -> usize {
+    body.get("tools")
+        .and_then(Value::as_array)
+        .map_or(0, Vec::len)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn ask_turn_offers_no_tools_and_later_turns_do() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "It returns 42."),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![ev_response_created("resp-2"), ev_completed("resp-2")]),
+        ],
+    )
+    .await;
+
+    let test = test_codex().build(&server).await?;
+
+    test.codex
+        .submit(Op::Ask {
+            items: vec![UserInput::Text {
+                text: "What does answer() return?".into(),
+                text_elements: Vec::new(),
+            }],
+        })
+        .await?;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    test.submit_turn("thanks, now fix the test").await?;
+
+    let requests = responses.requests();
+    assert_eq!(requests.len(), 2);
+    let ask = &requests[0];
+    assert_eq!(tool_count(&ask.body_json()), 0);
+    assert!(
+        ask.instructions_text()
+            .starts_with("You are Codex, answering a question about the user's code."),
+        "unexpected instructions: {}",
+        ask.instructions_text()
+    );
+    assert!(tool_count(&requests[1].body_json()) > 0);
+    assert!(
+        !requests[1]
+            .instructions_text()
+            .contains("answering a question about the user's code")
+    );
+
+    Ok(())
+}
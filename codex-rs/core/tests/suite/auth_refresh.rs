@@ -197,6 +197,48 @@ async fn refreshes_token_when_last_refresh_is_stale() -> Result<()> {
     Ok(())
 }
 
+#[serial_test::serial(auth_refresh)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn concurrent_callers_refresh_a_stale_token_once() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "access_token": "new-access-token",
+                    "refresh_token": "new-refresh-token"
+                }))
+                .set_delay(std::time::Duration::from_millis(200)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ctx = RefreshTokenTestContext::new(&server)?;
+    ctx.write_auth(&AuthDotJson {
+        auth_mode: Some(AuthMode::Chatgpt),
+        openai_api_key: None,
+        tokens: Some(build_tokens(INITIAL_ACCESS_TOKEN, INITIAL_REFRESH_TOKEN)),
+        last_refresh: Some(Utc::now() - Duration::days(9)),
+    })?;
+
+    let callers = (0..3).map(|_| {
+        let auth_manager = Arc::clone(&ctx.auth_manager);
+        tokio::spawn(async move { auth_manager.auth().await })
+    });
+    for caller in callers.collect::<Vec<_>>() {
+        let auth = caller.await?.context("auth should be cached")?;
+        let tokens = auth.get_token_data().context("token data")?;
+        assert_eq!(tokens.access_token, "new-access-token");
+    }
+
+    server.verify().await;
+    Ok(())
+}
+
 #[serial_test::serial(auth_refresh)]
 #[tokio::test]
 async fn refresh_token_returns_permanent_error_for_expired_refresh_token() -> Result<()> {
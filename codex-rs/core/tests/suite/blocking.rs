@@ -0,0 +1,59 @@
+use codex_core::ModelProviderInfo;
+use codex_core::blocking::BlockingCodex;
+use codex_core::blocking::TurnOutcome;
+use codex_core::built_in_model_providers;
+use codex_core::protocol::TokenUsage;
+use core_test_support::load_default_config_for_test;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed_with_tokens;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use pretty_assertions::assert_eq;
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+#[test]
+fn blocking_codex_runs_a_turn_without_a_caller_runtime() -> anyhow::Result<()> {
+    // The mock server needs a runtime of its own; the code under test must not.
+    let server_runtime = tokio::runtime::Runtime::new()?;
+    let server = server_runtime.block_on(start_mock_server());
+    let response_mock = server_runtime.block_on(mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "hello from codex"),
+            ev_completed_with_tokens("resp-1", 42),
+        ]),
+    ));
+
+    let home = TempDir::new()?;
+    let mut config = server_runtime.block_on(load_default_config_for_test(&home));
+    // Use an existing env var (PATH) to satisfy the auth plumbing without
+    // requiring a real secret.
+    config.model_provider = ModelProviderInfo {
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: Some("PATH".to_string()),
+        requires_openai_auth: false,
+        ..built_in_model_providers()["openai"].clone()
+    };
+
+    let codex = BlockingCodex::with_config(config)?;
+    let outcome = codex.run_turn("say hello")?;
+
+    assert_eq!(
+        outcome,
+        TurnOutcome {
+            last_agent_message: Some("hello from codex".to_string()),
+            token_usage: Some(TokenUsage {
+                input_tokens: 42,
+                total_tokens: 42,
+                ..TokenUsage::default()
+            }),
+            file_changes: HashMap::new(),
+        }
+    );
+    assert_eq!(response_mock.requests().len(), 1);
+    Ok(())
+}
@@ -60,7 +60,7 @@ async fn chat_mode_stream_cli() {
     let bin = codex_utils_cargo_bin::cargo_bin("codex").unwrap();
     let mut cmd = AssertCommand::new(bin);
     cmd.arg("exec")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-c")
         .arg(&provider_override)
         .arg("-c")
@@ -145,7 +145,7 @@ async fn exec_cli_applies_model_instructions_file() {
     let bin = codex_utils_cargo_bin::cargo_bin("codex").unwrap();
     let mut cmd = AssertCommand::new(bin);
     cmd.arg("exec")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-c")
         .arg(&provider_override)
         .arg("-c")
@@ -197,7 +197,7 @@ async fn responses_api_stream_cli() {
     let bin = codex_utils_cargo_bin::cargo_bin("codex").unwrap();
     let mut cmd = AssertCommand::new(bin);
     cmd.arg("exec")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg("hello?");
@@ -233,7 +233,7 @@ async fn integration_creates_and_checks_session_file() -> anyhow::Result<()> {
     let bin = codex_utils_cargo_bin::cargo_bin("codex").unwrap();
     let mut cmd = AssertCommand::new(bin);
     cmd.arg("exec")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg(&prompt);
@@ -354,7 +354,7 @@ async fn integration_creates_and_checks_session_file() -> anyhow::Result<()> {
     let bin2 = codex_utils_cargo_bin::cargo_bin("codex").unwrap();
     let mut cmd2 = AssertCommand::new(bin2);
     cmd2.arg("exec")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg(&prompt2)
@@ -390,6 +390,51 @@ async fn includes_conversation_id_and_model_headers_in_request() {
     assert_eq!(request_authorization, "Bearer Test API Key");
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn provider_url_templates_use_model_and_session_header_id() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let resp_mock = mount_sse_once(&server, sse_completed("resp1")).await;
+
+    let base_url = format!("{}/gw/{{model}}/v1", server.uri());
+    let mut builder = test_codex()
+        .with_auth(CodexAuth::from_api_key("Test API Key"))
+        .with_config(move |config| {
+            config.model_provider.base_url = Some(base_url);
+            config.model_provider.query_params = Some(std::collections::HashMap::from([(
+                "session".to_string(),
+                "{session_id}".to_string(),
+            )]));
+        });
+    let test = builder
+        .build(&server)
+        .await
+        .expect("create new conversation");
+    let codex = test.codex.clone();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let request = resp_mock.single_request();
+    assert_eq!(
+        request.path(),
+        format!("/gw/{}/v1/responses", test.session_configured.model)
+    );
+    let request_session_id = request.header("session_id").expect("session_id header");
+    assert_eq!(request.query_param("session"), Some(request_session_id));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn includes_base_instructions_override_in_request() {
     skip_if_no_network!();
@@ -847,6 +892,8 @@ async fn user_turn_collaboration_mode_overrides_model_and_effort() -> anyhow::Re
             collaboration_mode: Some(collaboration_mode),
             final_output_json_schema: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -1150,8 +1197,17 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        first_byte_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
     };
 
     let codex_home = TempDir::new().unwrap();
@@ -1671,8 +1727,17 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        first_byte_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
     };
 
     // Init session
@@ -1752,8 +1817,17 @@ async fn env_var_overrides_loaded_auth() {
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        first_byte_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
     };
 
     // Init session
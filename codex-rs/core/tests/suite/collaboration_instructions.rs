@@ -158,6 +158,8 @@ async fn collaboration_instructions_added_on_user_turn() -> Result<()> {
             collaboration_mode: Some(collaboration_mode),
             final_output_json_schema: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
     wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
@@ -210,6 +212,8 @@ async fn override_then_user_turn_uses_updated_collaboration_instructions() -> Re
             collaboration_mode: None,
             final_output_json_schema: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
     wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
@@ -264,6 +268,8 @@ async fn user_turn_overrides_collaboration_instructions_after_override() -> Resu
             collaboration_mode: Some(turn_mode),
             final_output_json_schema: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
     wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
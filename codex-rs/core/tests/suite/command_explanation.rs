@@ -0,0 +1,142 @@
+use anyhow::Result;
+use codex_core::config::Constrained;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::CommandExplanationEvent;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::sandboxing::SandboxPermissions;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_completed_with_tokens;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::ev_shell_command_call_with_args;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+const EXPLANATION: &str =
+    "Deletes the build directory and everything in it; this cannot be undone.";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn explains_pending_command_once_per_session() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let args = json!({
+        "command": "rm -rf build",
+        "sandbox_permissions": SandboxPermissions::RequireEscalated,
+    });
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_shell_command_call_with_args("call-1", &args),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", EXPLANATION),
+                ev_completed_with_tokens("resp-2", 42),
+            ]),
+            sse(vec![
+                ev_response_created("resp-3"),
+                ev_assistant_message("msg-3", "Left the build directory alone."),
+                ev_completed("resp-3"),
+            ]),
+        ],
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| {
+            config.approval_policy = Constrained::allow_any(AskForApproval::OnRequest);
+            config.sandbox_policy = Constrained::allow_any(SandboxPolicy::ReadOnly);
+        })
+        .build(&server)
+        .await?;
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "clean the build".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    let approval = wait_for_event_match(&test.codex, |ev| match ev {
+        EventMsg::ExecApprovalRequest(approval) => Some(approval.clone()),
+        _ => None,
+    })
+    .await;
+
+    let expected = CommandExplanationEvent {
+        call_id: "call-1".to_string(),
+        explanation: Some(EXPLANATION.to_string()),
+        error: None,
+    };
+    let explain = Op::ExplainCommand {
+        call_id: approval.call_id.clone(),
+        command: approval.command.clone(),
+        cwd: approval.cwd.clone(),
+    };
+    test.codex.submit(explain.clone()).await?;
+    // The side call's usage is counted with the session's.
+    let total_tokens = wait_for_event_match(&test.codex, |ev| match ev {
+        EventMsg::TokenCount(event) => event
+            .info
+            .as_ref()
+            .map(|info| info.total_token_usage.total_tokens)
+            .filter(|total| *total > 0),
+        _ => None,
+    })
+    .await;
+    assert_eq!(total_tokens, 42);
+    let next_explanation = || {
+        wait_for_event_match(&test.codex, |ev| match ev {
+            EventMsg::CommandExplanation(event) => Some(event.clone()),
+            _ => None,
+        })
+    };
+    assert_eq!(next_explanation().await, expected);
+    test.codex.submit(explain).await?;
+    assert_eq!(next_explanation().await, expected);
+
+    test.codex
+        .submit(Op::ExecApproval {
+            id: approval.turn_id.clone(),
+            decision: ReviewDecision::Denied,
+        })
+        .await?;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    // The second explanation came from the cache.
+    let requests = responses.requests();
+    assert_eq!(requests.len(), 3);
+    let explain = requests[1].body_json();
+    assert_eq!(explain["max_output_tokens"], json!(1024));
+    assert!(
+        explain["tools"].as_array().is_none_or(Vec::is_empty),
+        "explanations are tool-free: {explain}"
+    );
+    let texts = requests[1].message_input_texts("user");
+    assert!(
+        texts.iter().any(|text| text.contains("-lc 'rm -rf build'")),
+        "the exact command should be sent: {texts:?}"
+    );
+    assert!(
+        !requests[2].body_json().to_string().contains(EXPLANATION),
+        "explanations stay out of the conversation"
+    );
+
+    Ok(())
+}
@@ -1527,6 +1527,8 @@ async fn auto_compact_runs_after_resume_when_token_usage_is_over_limit() {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await
         .unwrap();
@@ -7,6 +7,7 @@ use codex_core::exec::ExecParams;
 use codex_core::exec::ExecToolCallOutput;
 use codex_core::exec::SandboxType;
 use codex_core::exec::process_exec_tool_call;
+use codex_core::exec_limits::ExecLimits;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::sandboxing::SandboxPermissions;
 use codex_core::spawn::CODEX_SANDBOX_ENV_VAR;
@@ -40,6 +41,7 @@ async fn run_test_cmd(tmp: TempDir, cmd: Vec<&str>) -> Result<ExecToolCallOutput
         windows_sandbox_level: WindowsSandboxLevel::Disabled,
         justification: None,
         arg0: None,
+        limits: ExecLimits::default(),
     };
 
     let policy = SandboxPolicy::new_read_only_policy();
@@ -0,0 +1,106 @@
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ExecSandboxKind;
+use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::skip_if_windows;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn dry_run_reports_the_spawn_spec_without_running_the_command() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_windows!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_config(|config| {
+            config.dry_run_exec = true;
+        })
+        .build(&server)
+        .await?;
+
+    let call_id = "dry-run-call";
+    let arguments = serde_json::to_string(&json!({ "command": "touch created.txt" }))?;
+    let mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, "shell_command", &arguments),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "create a file".to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_configured.model.clone(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+            max_output_tokens: None,
+            service_tier: None,
+        })
+        .await?;
+
+    let spawn = wait_for_event_match(&codex, |event| match event {
+        EventMsg::ExecCommandSpawn(spawn) => Some(spawn.clone()),
+        _ => None,
+    })
+    .await;
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    assert_eq!(spawn.call_id, call_id);
+    assert!(spawn.dry_run);
+    assert_eq!(spawn.sandbox, ExecSandboxKind::None);
+    assert!(
+        spawn
+            .spawn_command
+            .last()
+            .is_some_and(|arg| arg.contains("touch created.txt")),
+        "unexpected spawn command: {:?}",
+        spawn.spawn_command
+    );
+    assert!(!cwd.path().join("created.txt").exists());
+
+    let output = mock
+        .function_call_output_text(call_id)
+        .expect("function call output");
+    assert!(output.contains("Dry run"), "unexpected output: {output}");
+
+    Ok(())
+}
@@ -83,6 +83,8 @@ async fn execpolicy_blocks_shell_invocation() -> Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
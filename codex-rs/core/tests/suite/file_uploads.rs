@@ -0,0 +1,176 @@
+use anyhow::Result;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::FileAttachedEvent;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const SPEC: &str = "The retry budget is three attempts.";
+
+/// Attaches `spec.md` to a message and returns the attachment event.
+async fn submit_with_attachment(test: &TestCodex) -> Result<FileAttachedEvent> {
+    let spec = test.cwd_path().join("spec.md");
+    std::fs::write(&spec, SPEC)?;
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![
+                UserInput::Text {
+                    text: "Summarize the spec.".into(),
+                    text_elements: Vec::new(),
+                },
+                UserInput::LocalFile { path: spec },
+            ],
+            final_output_json_schema: None,
+        })
+        .await?;
+    let EventMsg::FileAttached(attached) =
+        wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::FileAttached(_))).await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    Ok(attached)
+}
+
+async fn mount_files_api(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/v1/files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "file-abc",
+            "object": "file",
+            "purpose": "user_data"
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/files/file-abc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "file-abc",
+            "deleted": true
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+fn user_content(body: &Value) -> Vec<Value> {
+    body["input"]
+        .as_array()
+        .expect("input array")
+        .iter()
+        .filter(|item| item["role"] == "user")
+        .flat_map(|item| item["content"].as_array().cloned().unwrap_or_default())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn attachments_are_uploaded_once_and_deleted_on_shutdown() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_files_api(&server).await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| config.upload_attachments = true)
+        .build(&server)
+        .await?;
+    let attached = submit_with_attachment(&test).await?;
+
+    assert_eq!(attached.file_id.as_deref(), Some("file-abc"));
+    assert_eq!(attached.bytes, SPEC.len() as u64);
+    let content = user_content(&response.single_request().body_json());
+    assert!(
+        content.contains(&json!({"type": "input_file", "file_id": "file-abc"})),
+        "file should be referenced by id: {content:?}"
+    );
+    assert!(
+        !content.iter().any(|item| item["text"]
+            .as_str()
+            .is_some_and(|text| text.contains(SPEC))),
+        "uploaded file should not be inlined"
+    );
+
+    let rollout_path = test
+        .session_configured
+        .rollout_path
+        .clone()
+        .expect("rollout path");
+    test.codex.submit(Op::Shutdown).await?;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::ShutdownComplete)).await;
+
+    let rollout = std::fs::read_to_string(rollout_path)?;
+    let upload = rollout
+        .lines()
+        .map(serde_json::from_str::<Value>)
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .find(|line| line["type"] == "file_upload")
+        .expect("file_upload record");
+    assert_eq!(upload["payload"]["file_id"], json!("file-abc"));
+    assert_eq!(upload["payload"]["bytes"], json!(SPEC.len()));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn attachments_are_inlined_without_upload_support() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| {
+            config.upload_attachments = true;
+            config.model_provider.supports_file_uploads = false;
+        })
+        .build(&server)
+        .await?;
+    let attached = submit_with_attachment(&test).await?;
+
+    assert_eq!(attached.file_id, None);
+    assert_eq!(attached.summary(), "attached inline (35 B)");
+    let request = response.single_request();
+    let texts = request.message_input_texts("user");
+    let spec = test.cwd_path().join("spec.md");
+    assert!(
+        texts.contains(&format!(
+            "<file path=\"{}\">\n{SPEC}\n</file>",
+            spec.display()
+        )),
+        "file should be inlined: {texts:?}"
+    );
+    assert!(
+        !user_content(&request.body_json())
+            .iter()
+            .any(|item| item["type"] == "input_file")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+#![cfg(not(target_os = "windows"))]
+
+use std::fs;
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_core::config::types::HookCommand;
+use codex_core::config::types::HookFailurePolicy;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::HookPoint;
+use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_apply_patch_call;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::ApplyPatchModelOutput;
+use core_test_support::test_codex::TestCodexHarness;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+const PATCH: &str = "*** Begin Patch\n*** Add File: app.txt\n+patched\n*** End Patch";
+
+async fn post_patch_harness(hook: HookCommand) -> Result<TestCodexHarness> {
+    let builder = test_codex().with_config(move |config| {
+        config.include_apply_patch_tool = true;
+        config.hooks.post_patch = vec![hook];
+    });
+    TestCodexHarness::with_builder(builder).await
+}
+
+fn shell_hook(script: &str, on_failure: HookFailurePolicy) -> HookCommand {
+    HookCommand {
+        command: vec!["/bin/sh".to_string(), "-c".to_string(), script.to_string()],
+        timeout: Duration::from_secs(10),
+        on_failure,
+    }
+}
+
+async fn submit(harness: &TestCodexHarness, prompt: &str) -> Result<()> {
+    let test = harness.test();
+    test.codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: prompt.into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: harness.cwd().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: test.session_configured.model.clone(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+            max_output_tokens: None,
+            service_tier: None,
+        })
+        .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_patch_hook_edits_the_file_before_the_model_continues() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = post_patch_harness(shell_hook(
+        "cat > hook-payload.json && echo formatted >> app.txt",
+        HookFailurePolicy::Warn,
+    ))
+    .await?;
+    let responses = mount_sse_sequence(
+        harness.server(),
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_apply_patch_call("call-1", PATCH, ApplyPatchModelOutput::Function),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    submit(&harness, "add app.txt").await?;
+    let mut order = Vec::new();
+    wait_for_event(&harness.test().codex, |event| {
+        match event {
+            EventMsg::PatchApplyEnd(_) => order.push("patch_end"),
+            EventMsg::HookBegin(begin) => {
+                assert_eq!(begin.hook, HookPoint::PostPatch);
+                order.push("hook_begin");
+            }
+            EventMsg::HookEnd(end) => {
+                assert!(end.success(), "hook failed: {end:?}");
+                order.push("hook_end");
+            }
+            _ => {}
+        }
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    assert_eq!(order, vec!["patch_end", "hook_begin", "hook_end"]);
+    // The hook ran after the patch and before the follow-up request, so the
+    // next turn starts from the edited file.
+    assert_eq!(
+        fs::read_to_string(harness.path("app.txt"))?,
+        "patched\nformatted\n"
+    );
+    assert_eq!(responses.requests().len(), 2);
+
+    let payload: Value =
+        serde_json::from_str(&fs::read_to_string(harness.path("hook-payload.json"))?)?;
+    assert_eq!(payload["hook"], json!("post_patch"));
+    assert_eq!(payload["turn_number"], json!(1));
+    assert_eq!(
+        payload["files_changed"],
+        json!([harness.path("app.txt").to_string_lossy()])
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn failing_abort_hook_ends_the_turn() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = post_patch_harness(shell_hook("exit 3", HookFailurePolicy::Abort)).await?;
+    // Only the first request is answered: the turn must end before the
+    // patch result is sent back to the model.
+    mount_sse_sequence(
+        harness.server(),
+        vec![sse(vec![
+            ev_response_created("resp-1"),
+            ev_apply_patch_call("call-1", PATCH, ApplyPatchModelOutput::Function),
+            ev_completed("resp-1"),
+        ])],
+    )
+    .await;
+
+    submit(&harness, "add app.txt").await?;
+    let mut error = None;
+    wait_for_event(&harness.test().codex, |event| {
+        if let EventMsg::Error(ev) = event {
+            error = Some(ev.message.clone());
+        }
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    let error = error.expect("turn should end with an error");
+    assert!(
+        error.contains("post_patch hook `/bin/sh -c 'exit 3'` exited with code 3"),
+        "{error}"
+    );
+    assert_eq!(fs::read_to_string(harness.path("app.txt"))?, "patched\n");
+    Ok(())
+}
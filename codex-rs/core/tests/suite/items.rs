@@ -26,6 +26,7 @@ use core_test_support::responses::ev_response_created;
 use core_test_support::responses::ev_web_search_call_added_partial;
 use core_test_support::responses::ev_web_search_call_done;
 use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::mount_sse_sequence;
 use core_test_support::responses::sse;
 use core_test_support::responses::start_mock_server;
 use core_test_support::skip_if_no_network;
@@ -379,6 +380,8 @@ async fn plan_mode_emits_plan_item_from_proposed_plan_block() -> anyhow::Result<
             summary: codex_protocol::config_types::ReasoningSummary::Auto,
             collaboration_mode: Some(collaboration_mode),
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -454,6 +457,8 @@ async fn plan_mode_strips_plan_from_agent_messages() -> anyhow::Result<()> {
             summary: codex_protocol::config_types::ReasoningSummary::Auto,
             collaboration_mode: Some(collaboration_mode),
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -550,6 +555,8 @@ async fn plan_mode_handles_missing_plan_close_tag() -> anyhow::Result<()> {
             summary: codex_protocol::config_types::ReasoningSummary::Auto,
             collaboration_mode: Some(collaboration_mode),
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -706,3 +713,81 @@ async fn reasoning_raw_content_delta_respects_flag() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn encrypted_reasoning_is_resent_on_the_next_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    let TestCodex { codex, .. } = test_codex().build(&server).await?;
+
+    let reasoning = ev_reasoning_item("reasoning-1", &["weighing options"], &[]);
+    let encrypted_content = reasoning["item"]["encrypted_content"].clone();
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                reasoning,
+                ev_assistant_message("msg-1", "first answer"),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "second answer"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    for text in ["first question", "second question"] {
+        codex
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: text.into(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .await?;
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    }
+
+    let requests = responses.requests();
+    assert_eq!(requests.len(), 2);
+    let second = &requests[1];
+    assert_eq!(second.body_json()["store"], false);
+
+    // The reasoning item comes back as the API returned it, minus the id that
+    // is only valid while the response is stored.
+    let resent = second.inputs_of_type("reasoning");
+    assert_eq!(resent.len(), 1, "unexpected reasoning inputs: {resent:?}");
+    assert_eq!(resent[0]["encrypted_content"], encrypted_content);
+    assert_eq!(
+        resent[0]["summary"],
+        serde_json::json!([{"type": "summary_text", "text": "weighing options"}])
+    );
+    assert_eq!(resent[0].get("id"), None);
+
+    // Reasoning stays ahead of the assistant message it produced.
+    let order: Vec<String> = second
+        .input()
+        .iter()
+        .filter_map(|item| {
+            let ty = item["type"].as_str()?;
+            match ty {
+                "message" => item["role"].as_str().map(str::to_string),
+                other => Some(other.to_string()),
+            }
+        })
+        .filter(|kind| kind != "developer")
+        .collect();
+    assert_eq!(
+        order[order.len() - 3..],
+        ["reasoning", "assistant", "user"].map(str::to_string)
+    );
+
+    Ok(())
+}
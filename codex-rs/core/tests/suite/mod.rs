@@ -19,48 +19,67 @@ mod agent_websocket;
 mod apply_patch_cli;
 #[cfg(not(target_os = "windows"))]
 mod approvals;
+mod ask;
 mod auth_refresh;
+mod blocking;
 mod cli_stream;
 mod client;
 mod client_websockets;
 mod codex_delegate;
 mod collaboration_instructions;
+mod command_explanation;
 mod compact;
 mod compact_remote;
 mod compact_resume_fork;
 mod deprecation_notice;
 mod exec;
+mod exec_audit;
 mod exec_policy;
+mod file_uploads;
 mod fork_thread;
 mod grep_files;
 mod hierarchical_agents;
+mod hooks;
 mod image_rollout;
 mod items;
 mod json_result;
 mod list_dir;
 mod list_models;
 mod live_cli;
+mod max_output_tokens;
+mod model_check;
 mod model_info_overrides;
 mod model_overrides;
 mod model_tools;
 mod models_cache_ttl;
 mod models_etag_responses;
+mod offline;
 mod otel;
 mod pending_input;
 mod permissions_messages;
 mod personality;
+mod preferred_language;
 mod prompt_caching;
+mod provider_failover;
 mod quota_exceeded;
 mod read_file;
+mod refusals;
 mod remote_models;
 mod request_compression;
 mod request_user_input;
+mod response_storage;
 mod resume;
 mod resume_warning;
 mod review;
 mod rmcp_client;
+mod rollout_format;
 mod rollout_list_find;
+mod sampling;
 mod seatbelt;
+mod service_tier;
+mod session_prewarm;
+mod session_title;
+mod shared_threads;
 mod shell_command;
 mod shell_serialization;
 mod shell_snapshot;
@@ -72,6 +91,7 @@ mod text_encoding_fix;
 mod tool_harness;
 mod tool_parallelism;
 mod tools;
+mod transcript_export;
 mod truncation;
 mod turn_state;
 mod undo;
@@ -82,3 +102,4 @@ mod user_shell_cmd;
 mod view_image;
 mod web_search;
 mod websocket_fallback;
+mod workspace_summary;
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use codex_core::protocol::ModelListSource;
+use codex_core::protocol::UnknownModel;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::Mock;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unknown_model_is_checked_against_the_provider_list_once_a_day() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "data": [
+                {"id": "gpt-5-codex", "object": "model"},
+                {"id": "gpt-4o", "object": "model"},
+                {"id": "llama3.2", "object": "model"},
+            ],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = Arc::new(TempDir::new()?);
+    let mut builder = test_codex()
+        .with_home(Arc::clone(&home))
+        .with_config(|config| config.model = Some("gpt5-codex".to_string()));
+    let test = builder.build(&server).await?;
+    let provider = test.config.model_provider.name.clone();
+    assert_eq!(
+        test.session_configured.unknown_model,
+        Some(UnknownModel {
+            model: "gpt5-codex".to_string(),
+            provider,
+            source: ModelListSource::Provider,
+            suggestions: vec![
+                "gpt-5-codex".to_string(),
+                "gpt-4o".to_string(),
+                "llama3.2".to_string(),
+            ],
+        })
+    );
+
+    // A model the provider serves passes, answered from the cached list.
+    let mut builder = test_codex()
+        .with_home(home)
+        .with_config(|config| config.model = Some("llama3.2".to_string()));
+    let test = builder.build(&server).await?;
+    assert_eq!(test.session_configured.unknown_model, None);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unknown_model_falls_back_to_the_builtin_registry() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    // No `/v1/models` mock: the provider answers 404.
+    let server = start_mock_server().await;
+    let mut builder =
+        test_codex().with_config(|config| config.model = Some("gpt5-codex".to_string()));
+    let test = builder.build(&server).await?;
+
+    let unknown = test
+        .session_configured
+        .unknown_model
+        .expect("model should be reported as unknown");
+    assert_eq!(unknown.source, ModelListSource::Builtin);
+    assert!(
+        unknown
+            .suggestions
+            .iter()
+            .any(|model| model == "gpt-5-codex"),
+        "{:?}",
+        unknown.suggestions
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builtin_models_are_not_checked_with_the_provider() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let mut builder =
+        test_codex().with_config(|config| config.model = Some("gpt-5.1-codex".to_string()));
+    let test = builder.build(&server).await?;
+    assert_eq!(test.session_configured.unknown_model, None);
+
+    Ok(())
+}
@@ -1,7 +1,16 @@
+use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
 use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
 use pretty_assertions::assert_eq;
@@ -83,3 +92,120 @@ async fn override_turn_context_does_not_create_config_file() {
         "override should not create config.toml"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn override_turn_context_rejects_reasoning_for_non_reasoning_model() {
+    let server = start_mock_server().await;
+    let mut builder = test_codex().with_config(|config| {
+        config.model = Some("gpt-4o".to_string());
+    });
+    let test = builder.build(&server).await.expect("create conversation");
+    let codex = test.codex.clone();
+
+    codex
+        .submit(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: None,
+            sandbox_policy: None,
+            windows_sandbox_level: None,
+            model: None,
+            effort: Some(Some(ReasoningEffort::High)),
+            summary: None,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await
+        .expect("submit override");
+
+    let error = wait_for_event(&codex, |ev| matches!(ev, EventMsg::Error(_))).await;
+    let EventMsg::Error(error) = error else {
+        unreachable!("wait_for_event returned a non-error event");
+    };
+    assert_eq!(
+        error.message,
+        "gpt-4o does not support reasoning; the reasoning effort and summary were not changed."
+    );
+
+    codex.submit(Op::Shutdown).await.expect("request shutdown");
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::ShutdownComplete)).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn restore_turn_settings_reverts_when_the_turn_ends() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    let server = start_mock_server().await;
+    let first = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+    let second = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-2"), ev_completed("resp-2")]),
+    )
+    .await;
+    let test = test_codex()
+        .with_model("gpt-5.1-codex")
+        .build(&server)
+        .await?;
+    let codex = test.codex.clone();
+
+    // Nothing reads events between the two submissions: the revert is driven
+    // by the turn itself, not by whoever consumes its events.
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "one-off".into(),
+                text_elements: Vec::new(),
+            }],
+            cwd: test.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: "gpt-5.1-codex".to_string(),
+            effort: Some(ReasoningEffort::High),
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            final_output_json_schema: None,
+            personality: None,
+            max_output_tokens: None,
+            service_tier: None,
+        })
+        .await?;
+    codex
+        .submit(Op::RestoreTurnSettings {
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            model: "gpt-5.1-codex".to_string(),
+            effort: Some(ReasoningEffort::Low),
+        })
+        .await?;
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "next".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let effort = |body: serde_json::Value| {
+        body.get("reasoning")
+            .and_then(|reasoning| reasoning.get("effort"))
+            .and_then(|effort| effort.as_str())
+            .map(str::to_string)
+    };
+    assert_eq!(
+        effort(first.single_request().body_json()),
+        Some("high".to_string())
+    );
+    assert_eq!(
+        effort(second.single_request().body_json()),
+        Some("low".to_string())
+    );
+
+    Ok(())
+}
@@ -100,6 +100,8 @@ async fn renews_cache_ttl_on_matching_models_etag() -> Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -176,6 +178,7 @@ fn test_remote_model(slug: &str, priority: i32) -> ModelInfo {
         upgrade: None,
         base_instructions: "base instructions".to_string(),
         model_messages: None,
+        supports_reasoning: false,
         supports_reasoning_summaries: false,
         support_verbosity: false,
         default_verbosity: None,
@@ -186,5 +189,6 @@ fn test_remote_model(slug: &str, priority: i32) -> ModelInfo {
         auto_compact_token_limit: None,
         effective_context_window_percent: 95,
         experimental_supported_tools: Vec::new(),
+        max_output_tokens: None,
     }
 }
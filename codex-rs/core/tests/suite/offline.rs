@@ -0,0 +1,87 @@
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+
+fn text_input(text: &str) -> Op {
+    Op::UserInput {
+        items: vec![UserInput::Text {
+            text: text.to_string(),
+            text_elements: Vec::new(),
+        }],
+        final_output_json_schema: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn offline_mode_fails_model_requests_immediately() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(|config| {
+            config.offline = true;
+        })
+        .build(&server)
+        .await?;
+
+    codex.submit(text_input("hello")).await?;
+
+    let EventMsg::Error(error) =
+        wait_for_event(&codex, |event| matches!(event, EventMsg::Error(_))).await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert!(error.message.contains("offline mode"));
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert!(
+        requests
+            .iter()
+            .all(|request| !request.url.path().ends_with("/responses")),
+        "offline mode should not send model requests"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unreachable_provider_queues_prompts() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    // Reserve a port and release it so connections to it are refused.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    drop(listener);
+
+    let server = start_mock_server().await;
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider.base_url = Some(format!("http://{addr}/v1"));
+        })
+        .build(&server)
+        .await?;
+
+    for expected in 1..=2 {
+        codex.submit(text_input("hello")).await?;
+        let EventMsg::PromptQueuedOffline(queued) = wait_for_event(&codex, |event| {
+            matches!(
+                event,
+                EventMsg::PromptQueuedOffline(_) | EventMsg::TurnStarted(_)
+            )
+        })
+        .await
+        else {
+            panic!("prompt should be queued instead of starting a turn");
+        };
+        assert_eq!(queued.queued_prompts, expected);
+    }
+
+    Ok(())
+}
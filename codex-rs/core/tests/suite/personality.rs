@@ -104,6 +104,8 @@ async fn user_turn_personality_none_does_not_add_update_message() -> anyhow::Res
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -151,6 +153,8 @@ async fn config_personality_some_sets_instructions_template() -> anyhow::Result<
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -208,6 +212,8 @@ async fn user_turn_personality_some_adds_update_message() -> anyhow::Result<()>
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -242,6 +248,8 @@ async fn user_turn_personality_some_adds_update_message() -> anyhow::Result<()>
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -320,6 +328,8 @@ async fn user_turn_personality_skips_if_feature_disabled() -> anyhow::Result<()>
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -354,6 +364,8 @@ async fn user_turn_personality_skips_if_feature_disabled() -> anyhow::Result<()>
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -412,6 +424,7 @@ async fn ignores_remote_model_personality_if_remote_models_disabled() -> anyhow:
                 personality_pragmatic: None,
             }),
         }),
+        supports_reasoning: false,
         supports_reasoning_summaries: false,
         support_verbosity: false,
         default_verbosity: None,
@@ -422,6 +435,7 @@ async fn ignores_remote_model_personality_if_remote_models_disabled() -> anyhow:
         auto_compact_token_limit: None,
         effective_context_window_percent: 95,
         experimental_supported_tools: Vec::new(),
+        max_output_tokens: None,
     };
 
     let _models_mock = mount_models_once(
@@ -466,6 +480,8 @@ async fn ignores_remote_model_personality_if_remote_models_disabled() -> anyhow:
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -526,6 +542,7 @@ async fn remote_model_default_personality_instructions_with_feature() -> anyhow:
                 personality_pragmatic: Some("Pragmatic variant".to_string()),
             }),
         }),
+        supports_reasoning: false,
         supports_reasoning_summaries: false,
         support_verbosity: false,
         default_verbosity: None,
@@ -536,6 +553,7 @@ async fn remote_model_default_personality_instructions_with_feature() -> anyhow:
         auto_compact_token_limit: None,
         effective_context_window_percent: 95,
         experimental_supported_tools: Vec::new(),
+        max_output_tokens: None,
     };
 
     let _models_mock = mount_models_once(
@@ -579,6 +597,8 @@ async fn remote_model_default_personality_instructions_with_feature() -> anyhow:
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -632,6 +652,7 @@ async fn user_turn_personality_remote_model_template_includes_update_message() -
                 personality_pragmatic: None,
             }),
         }),
+        supports_reasoning: false,
         supports_reasoning_summaries: false,
         support_verbosity: false,
         default_verbosity: None,
@@ -642,6 +663,7 @@ async fn user_turn_personality_remote_model_template_includes_update_message() -
         auto_compact_token_limit: None,
         effective_context_window_percent: 95,
         experimental_supported_tools: Vec::new(),
+        max_output_tokens: None,
     };
 
     let _models_mock = mount_models_once(
@@ -689,6 +711,8 @@ async fn user_turn_personality_remote_model_template_includes_update_message() -
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -723,6 +747,8 @@ async fn user_turn_personality_remote_model_template_includes_update_message() -
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -0,0 +1,113 @@
+use std::path::Path;
+
+use anyhow::Result;
+use codex_core::CodexThread;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+async fn say(codex: &CodexThread, text: &str) -> Result<()> {
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: text.into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    wait_for_event(codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    Ok(())
+}
+
+fn language_messages(texts: Vec<String>) -> Vec<String> {
+    texts
+        .into_iter()
+        .filter(|text| text.starts_with("<preferred_language>"))
+        .collect()
+}
+
+/// The language recorded by the last turn context in the rollout.
+fn recorded_language(rollout_path: &Path) -> Result<Value> {
+    let rollout = std::fs::read_to_string(rollout_path)?;
+    let turn_context = rollout
+        .lines()
+        .map(serde_json::from_str::<Value>)
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .rfind(|line| line["type"] == "turn_context")
+        .expect("turn_context record");
+    Ok(turn_context["payload"]["preferred_language"].clone())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn language_is_sent_changed_and_kept_on_resume() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response = mount_sse_sequence(
+        &server,
+        (1..=3)
+            .map(|n| {
+                let id = format!("resp-{n}");
+                sse(vec![ev_response_created(&id), ev_completed(&id)])
+            })
+            .collect(),
+    )
+    .await;
+    let mut builder =
+        test_codex().with_config(|config| config.preferred_language = Some("Japanese".to_string()));
+
+    let initial = builder.build(&server).await?;
+    let rollout_path = initial
+        .session_configured
+        .rollout_path
+        .clone()
+        .expect("rollout path");
+    let home = initial.home.clone();
+    say(&initial.codex, "hello").await?;
+    initial
+        .codex
+        .submit(Op::SetPreferredLanguage {
+            language: Some("French".to_string()),
+        })
+        .await?;
+    say(&initial.codex, "hello again").await?;
+
+    let requests = response.requests();
+    let first = language_messages(requests[0].message_input_texts("developer"));
+    assert_eq!(first.len(), 1, "{first:?}");
+    assert!(first[0].contains("meant for the user in Japanese"));
+    let second = language_messages(requests[1].message_input_texts("developer"));
+    assert_eq!(second.len(), 2, "{second:?}");
+    assert!(second[1].contains("meant for the user in French"));
+
+    initial.codex.submit(Op::Shutdown).await?;
+    wait_for_event(&initial.codex, |ev| {
+        matches!(ev, EventMsg::ShutdownComplete)
+    })
+    .await;
+    assert_eq!(recorded_language(&rollout_path)?, "French");
+
+    // The config still says Japanese; the recorded language wins.
+    let resumed = builder.resume(&server, home, rollout_path.clone()).await?;
+    say(&resumed.codex, "after resume").await?;
+    resumed.codex.submit(Op::Shutdown).await?;
+    wait_for_event(&resumed.codex, |ev| {
+        matches!(ev, EventMsg::ShutdownComplete)
+    })
+    .await;
+    assert_eq!(recorded_language(&rollout_path)?, "French");
+
+    Ok(())
+}
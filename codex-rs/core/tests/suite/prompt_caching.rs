@@ -592,6 +592,8 @@ async fn per_turn_overrides_keep_cached_prefix_and_key_constant() -> anyhow::Res
             collaboration_mode: None,
             final_output_json_schema: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
     wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
@@ -687,6 +689,8 @@ async fn send_user_turn_with_no_changes_does_not_send_environment_context() -> a
             collaboration_mode: None,
             final_output_json_schema: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
     wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
@@ -706,6 +710,8 @@ async fn send_user_turn_with_no_changes_does_not_send_environment_context() -> a
             collaboration_mode: None,
             final_output_json_schema: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
     wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
@@ -787,6 +793,8 @@ async fn send_user_turn_with_changes_sends_environment_context() -> anyhow::Resu
             collaboration_mode: None,
             final_output_json_schema: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
     wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
@@ -806,6 +814,8 @@ async fn send_user_turn_with_changes_sends_environment_context() -> anyhow::Resu
             collaboration_mode: None,
             final_output_json_schema: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
     wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
@@ -0,0 +1,157 @@
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::load_sse_fixture_with_id;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::body_string_contains;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn sse_completed(id: &str) -> String {
+    load_sse_fixture_with_id("../fixtures/completed_template.json", id)
+}
+
+fn sse_ok(id: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "text/event-stream")
+        .set_body_raw(sse_completed(id), "text/event-stream")
+}
+
+fn mock_provider(name: &str, server: &MockServer) -> ModelProviderInfo {
+    // Use an existing env var (PATH) to satisfy the auth plumbing without
+    // requiring a real secret.
+    ModelProviderInfo {
+        name: name.into(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: Some("PATH".into()),
+        env_key_instructions: None,
+        experimental_bearer_token: None,
+        wire_api: WireApi::Responses,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(5),
+        stream_idle_timeout_ms: Some(2_000),
+        first_byte_timeout_ms: None,
+        requires_openai_auth: false,
+        supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fails_over_to_fallback_provider_and_returns_to_primary_next_turn() {
+    let primary_server = MockServer::start().await;
+    let fallback_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .and(body_string_contains("first message"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(2)
+        .mount(&primary_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .and(body_string_contains("first message"))
+        .respond_with(sse_ok("resp_fallback"))
+        .expect(1)
+        .mount(&fallback_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .and(body_string_contains("follow up"))
+        .respond_with(sse_ok("resp_primary"))
+        .expect(1)
+        .mount(&primary_server)
+        .await;
+
+    let primary = ModelProviderInfo {
+        fallback_provider: Some("backup".to_string()),
+        failover_after_failures: Some(2),
+        ..mock_provider("primary", &primary_server)
+    };
+    let fallback = mock_provider("backup gateway", &fallback_server);
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = primary;
+            config
+                .model_providers
+                .insert("backup".to_string(), fallback);
+        })
+        .build(&primary_server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "first message".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let failover = wait_for_event(&codex, |ev| {
+        matches!(ev, EventMsg::Warning(warning) if warning.message.contains("fallback provider"))
+    })
+    .await;
+    let EventMsg::Warning(warning) = failover else {
+        unreachable!("wait_for_event returned a non-warning event");
+    };
+    assert!(
+        warning.message.starts_with(
+            "Switching from primary to fallback provider backup gateway after 2 failed attempts."
+        ),
+        "unexpected failover message: {}",
+        warning.message
+    );
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "follow up".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let responses_requests = |requests: Vec<wiremock::Request>| {
+        requests
+            .iter()
+            .filter(|request| request.url.path() == "/v1/responses")
+            .count()
+    };
+    let primary_requests =
+        responses_requests(primary_server.received_requests().await.unwrap_or_default());
+    let fallback_requests = responses_requests(
+        fallback_server
+            .received_requests()
+            .await
+            .unwrap_or_default(),
+    );
+    assert_eq!((primary_requests, fallback_requests), (3, 1));
+}
@@ -0,0 +1,140 @@
+use anyhow::Result;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::TurnRefusedEvent;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_incomplete;
+use core_test_support::responses::ev_refusal;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn refusal_is_reported_instead_of_agent_message() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_refusal("msg-1", "I can't help with that."),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let test = test_codex().build(&server).await?;
+
+    test.submit_turn("do the questionable thing").await?;
+
+    test.submit_turn("ok, something else then").await?;
+
+    let requests = responses.requests();
+    assert_eq!(requests.len(), 2);
+    let assistant_messages: Vec<_> = requests[1]
+        .input()
+        .into_iter()
+        .filter(|item| item["type"] == "message" && item["role"] == "assistant")
+        .map(|item| item["content"].clone())
+        .collect();
+    assert_eq!(
+        assistant_messages,
+        vec![json!([{"type": "output_text", "text": "I can't help with that."}])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn refused_turn_emits_turn_refused_and_no_agent_message() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_sse_sequence(
+        &server,
+        vec![sse(vec![
+            ev_response_created("resp-1"),
+            ev_refusal("msg-1", "I can't help with that."),
+            ev_completed("resp-1"),
+        ])],
+    )
+    .await;
+
+    let test = test_codex().build(&server).await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "do the questionable thing".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let mut refusals = Vec::new();
+    loop {
+        let event = wait_for_event(&test.codex, |_| true).await;
+        match event {
+            EventMsg::TurnRefused(TurnRefusedEvent { reason }) => refusals.push(reason),
+            EventMsg::AgentMessage(ev) => panic!("unexpected agent message: {}", ev.message),
+            EventMsg::TurnComplete(_) => break,
+            _ => {}
+        }
+    }
+    assert_eq!(refusals, vec!["I can't help with that.".to_string()]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn content_filter_incomplete_is_reported_as_refusal() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_sse_sequence(
+        &server,
+        vec![sse(vec![
+            ev_response_created("resp-1"),
+            ev_incomplete("resp-1", "content_filter"),
+        ])],
+    )
+    .await;
+
+    let test = test_codex().build(&server).await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "write the thing".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let refused = wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnRefused(_))).await;
+    let EventMsg::TurnRefused(TurnRefusedEvent { reason }) = refused else {
+        unreachable!("wait_for_event returned a different event");
+    };
+    assert_eq!(reason, "The response was blocked by the content filter.");
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    Ok(())
+}
@@ -80,6 +80,7 @@ async fn remote_models_remote_model_uses_unified_exec() -> Result<()> {
         upgrade: None,
         base_instructions: "base instructions".to_string(),
         model_messages: None,
+        supports_reasoning: false,
         supports_reasoning_summaries: false,
         support_verbosity: false,
         default_verbosity: None,
@@ -90,6 +91,7 @@ async fn remote_models_remote_model_uses_unified_exec() -> Result<()> {
         auto_compact_token_limit: None,
         effective_context_window_percent: 95,
         experimental_supported_tools: Vec::new(),
+        max_output_tokens: None,
     };
 
     let models_mock = mount_models_once(
@@ -181,6 +183,8 @@ async fn remote_models_remote_model_uses_unified_exec() -> Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -317,6 +321,7 @@ async fn remote_models_apply_remote_base_instructions() -> Result<()> {
         upgrade: None,
         base_instructions: remote_base.to_string(),
         model_messages: None,
+        supports_reasoning: false,
         supports_reasoning_summaries: false,
         support_verbosity: false,
         default_verbosity: None,
@@ -327,6 +332,7 @@ async fn remote_models_apply_remote_base_instructions() -> Result<()> {
         auto_compact_token_limit: None,
         effective_context_window_percent: 95,
         experimental_supported_tools: Vec::new(),
+        max_output_tokens: None,
     };
     mount_models_once(
         &server,
@@ -392,6 +398,8 @@ async fn remote_models_apply_remote_base_instructions() -> Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -791,6 +799,7 @@ fn test_remote_model_with_policy(
         upgrade: None,
         base_instructions: "base instructions".to_string(),
         model_messages: None,
+        supports_reasoning: false,
         supports_reasoning_summaries: false,
         support_verbosity: false,
         default_verbosity: None,
@@ -801,5 +810,6 @@ fn test_remote_model_with_policy(
         auto_compact_token_limit: None,
         effective_context_window_percent: 95,
         experimental_supported_tools: Vec::new(),
+        max_output_tokens: None,
     }
 }
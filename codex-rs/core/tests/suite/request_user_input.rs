@@ -142,6 +142,8 @@ async fn request_user_input_round_trip_resolves_pending() -> anyhow::Result<()>
                 },
             }),
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -256,6 +258,8 @@ where
             summary: ReasoningSummary::Auto,
             collaboration_mode: Some(collaboration_mode),
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
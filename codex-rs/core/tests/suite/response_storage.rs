@@ -0,0 +1,118 @@
+use codex_core::CodexThread;
+use codex_core::ModelProviderInfo;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::load_sse_fixture_with_id;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::body_string_contains;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn sse_ok(id: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "text/event-stream")
+        .set_body_raw(
+            load_sse_fixture_with_id("../fixtures/completed_template.json", id),
+            "text/event-stream",
+        )
+}
+
+/// Submits `text` and returns the warnings emitted before the turn completes.
+async fn run_turn(codex: &CodexThread, text: &str) -> Vec<String> {
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: text.into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let mut warnings = Vec::new();
+    loop {
+        match codex.next_event().await.unwrap().msg {
+            EventMsg::Warning(warning) => warnings.push(warning.message),
+            EventMsg::TurnComplete(_) => return warnings,
+            _ => {}
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn falls_back_to_full_context_when_provider_drops_stored_responses() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+
+    // The gateway accepts `store: true` but has nothing stored when the next
+    // turn refers back to it.
+    let not_found = ResponseTemplate::new(400)
+        .insert_header("content-type", "application/json")
+        .set_body_string(
+            serde_json::json!({
+                "error": {
+                    "type": "invalid_request_error",
+                    "code": "previous_response_not_found",
+                    "message": "Previous response with id 'resp_first' not found."
+                }
+            })
+            .to_string(),
+        );
+    Mock::given(method("POST"))
+        .and(path("/openai/responses"))
+        .and(body_string_contains("second turn"))
+        .and(body_string_contains(r#""store":true"#))
+        .respond_with(not_found)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/openai/responses"))
+        .and(body_string_contains(r#""store":false"#))
+        .respond_with(sse_ok("resp_stateless"))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/openai/responses"))
+        .and(body_string_contains(r#""store":true"#))
+        .respond_with(sse_ok("resp_first"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let base_url = format!("{}/openai", server.uri());
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = ModelProviderInfo {
+                name: "azure".into(),
+                base_url: Some(base_url),
+                ..config.model_provider.clone()
+            };
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    assert_eq!(run_turn(&codex, "first turn").await, Vec::<String>::new());
+
+    let warnings = run_turn(&codex, "second turn").await;
+    assert_eq!(warnings.len(), 1, "unexpected warnings: {warnings:?}");
+    assert!(
+        warnings[0].starts_with("azure does not keep stored responses"),
+        "unexpected warning: {}",
+        warnings[0]
+    );
+
+    // Later turns go straight to stateless requests without another failure.
+    assert_eq!(run_turn(&codex, "third turn").await, Vec::<String>::new());
+}
@@ -34,6 +34,7 @@ fn resume_history(
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: None,
+        preferred_language: None,
     };
 
     InitialHistory::Resumed(ResumedHistory {
@@ -0,0 +1,150 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_utils_cargo_bin::find_resource;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::MockServer;
+
+/// Copies a fixture rollout into a fresh Codex home and resumes it.
+async fn resume_fixture(server: &MockServer, name: &str) -> Result<(TestCodex, PathBuf)> {
+    let fixture = format!("tests/fixtures/rollout_formats/{name}");
+    let fixture = find_resource!(fixture)?;
+    let home = Arc::new(TempDir::new()?);
+    let rollout_path = home.path().join(name);
+    std::fs::copy(fixture, &rollout_path)?;
+    let test = test_codex()
+        .resume(server, Arc::clone(&home), rollout_path.clone())
+        .await?;
+    Ok((test, rollout_path))
+}
+
+fn resumed_messages(test: &TestCodex) -> Vec<String> {
+    test.session_configured
+        .initial_messages
+        .iter()
+        .flatten()
+        .filter_map(|event| match event {
+            EventMsg::UserMessage(event) => Some(format!("user: {}", event.message)),
+            EventMsg::AgentMessage(event) => Some(format!("agent: {}", event.message)),
+            _ => None,
+        })
+        .collect()
+}
+
+async fn shutdown(test: &TestCodex) -> Result<()> {
+    test.codex.submit(Op::Shutdown).await?;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::ShutdownComplete)).await;
+    Ok(())
+}
+
+fn read_header(path: &Path) -> Result<Value> {
+    let contents = std::fs::read_to_string(path)?;
+    let header = contents.lines().next().expect("header line");
+    Ok(serde_json::from_str(header)?)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn resumes_format0_minimal_rollout() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let (test, rollout_path) = resume_fixture(&server, "format0_minimal.jsonl").await?;
+
+    assert_eq!(
+        test.session_configured.session_id.to_string(),
+        "01987f3a-1c2b-7d4e-8f50-6a7b8c9d0e1f"
+    );
+    assert_eq!(
+        resumed_messages(&test),
+        vec![
+            "user: List the crates in this workspace.",
+            "agent: There are three crates: core, cli, and tui.",
+        ]
+    );
+
+    // Appending to an old rollout stamps it with the current format.
+    shutdown(&test).await?;
+    let header = read_header(&rollout_path)?;
+    assert_eq!(header["format"]["version"], json!(1));
+    assert_eq!(header["payload"]["cli_version"], json!("0.20.0"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn resumes_format0_rollout_with_padded_header() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let (test, _rollout_path) = resume_fixture(&server, "format0_padded_header.jsonl").await?;
+
+    assert_eq!(
+        test.session_configured.session_id.to_string(),
+        "019a7a3e-5f60-7182-93a4-b5c6d7e8f901"
+    );
+    assert_eq!(
+        resumed_messages(&test),
+        vec![
+            "user: Why does test_retry fail on CI?",
+            "agent: It sleeps for a fixed time; I switched it to a paused clock.",
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn resume_skips_and_keeps_unknown_records_and_fields() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let (test, rollout_path) = resume_fixture(&server, "format1_unknown_records.jsonl").await?;
+
+    assert_eq!(
+        resumed_messages(&test),
+        vec!["user: Bump the version.", "agent: Bumped to 1.2.0."]
+    );
+
+    shutdown(&test).await?;
+    let contents = std::fs::read_to_string(&rollout_path)?;
+    let header = read_header(&rollout_path)?;
+    assert_eq!(header["payload"]["workspace_color"], json!("teal"));
+    assert!(
+        contents.contains(r#""type":"bookmark""#),
+        "unknown records must survive the header rewrite"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn refuses_rollouts_from_newer_formats() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let Err(err) = resume_fixture(&server, "future_format.jsonl").await else {
+        panic!("a format 9 rollout should not resume");
+    };
+
+    let message = format!("{err:#}");
+    assert!(
+        message.contains(
+            "session was written by codex 1.4.0 (rollout format 9); upgrade to resume it"
+        ),
+        "unexpected error: {message}"
+    );
+
+    Ok(())
+}
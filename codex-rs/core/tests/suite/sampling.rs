@@ -0,0 +1,93 @@
+use anyhow::Result;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::config_types::SamplingParameters;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+const SAMPLING: SamplingParameters = SamplingParameters {
+    seed: Some(7),
+    temperature: Some(0.2),
+    top_p: Some(0.9),
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn sampling_parameters_are_sent_and_recorded() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_model("gpt-4o")
+        .with_config(|config| config.model_sampling = SAMPLING)
+        .build(&server)
+        .await?;
+    assert_eq!(test.session_configured.sampling, Some(SAMPLING));
+
+    test.submit_turn("hello").await?;
+
+    let body = response.single_request().body_json();
+    assert_eq!(
+        (body.get("temperature"), body.get("top_p"), body.get("seed")),
+        (Some(&json!(0.2)), Some(&json!(0.9)), None),
+        "the Responses API takes no seed"
+    );
+
+    let rollout_path = test
+        .session_configured
+        .rollout_path
+        .clone()
+        .expect("rollout path");
+    test.codex.submit(Op::Shutdown).await?;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::ShutdownComplete)).await;
+
+    let rollout = std::fs::read_to_string(rollout_path)?;
+    let header: Value = serde_json::from_str(rollout.lines().next().expect("session meta"))?;
+    assert_eq!(
+        header["payload"]["sampling"],
+        json!({"seed": 7, "temperature": 0.2, "top_p": 0.9})
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn reasoning_models_get_no_sampling_parameters() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_model("gpt-5-codex")
+        .with_config(|config| config.model_sampling = SAMPLING)
+        .build(&server)
+        .await?;
+
+    test.submit_turn("hello").await?;
+
+    let body = response.single_request().body_json();
+    for key in ["seed", "temperature", "top_p"] {
+        assert_eq!(body.get(key), None, "{key} should not be sent");
+    }
+
+    Ok(())
+}
@@ -64,7 +64,7 @@ impl TestScenario {
 }
 
 /// If the user has added a workspace root that is not a Git repo root, then
-/// the user has to specify `--skip-git-repo-check` or go through some
+/// the user has to specify `--trust-cwd` or go through some
 /// interstitial that indicates they are taking on some risk because Git
 /// cannot be used to backup their work before the agent begins.
 ///
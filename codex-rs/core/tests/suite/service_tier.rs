@@ -0,0 +1,206 @@
+use anyhow::Result;
+use codex_core::CodexThread;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::config_types::ServiceTier;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_response_sequence;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::sse_response;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::ResponseTemplate;
+
+/// Runs `op` and returns the warnings emitted before the turn completes.
+async fn run_turn(codex: &CodexThread, op: Op) -> Result<Vec<String>> {
+    codex.submit(op).await?;
+    let mut warnings = Vec::new();
+    loop {
+        match codex.next_event().await?.msg {
+            EventMsg::Warning(warning) => warnings.push(warning.message),
+            EventMsg::TurnComplete(_) => return Ok(warnings),
+            _ => {}
+        }
+    }
+}
+
+fn user_input(text: &str) -> Op {
+    Op::UserInput {
+        items: vec![UserInput::Text {
+            text: text.into(),
+            text_elements: Vec::new(),
+        }],
+        final_output_json_schema: None,
+    }
+}
+
+fn completed_with_tier(id: &str, tier: &str) -> Value {
+    let mut event = ev_completed(id);
+    event["response"]["service_tier"] = json!(tier);
+    event
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn per_turn_service_tier_applies_to_that_turn_only() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+            sse(vec![ev_response_created("resp-2"), ev_completed("resp-2")]),
+        ],
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| config.service_tier = Some(ServiceTier::Flex))
+        .build(&server)
+        .await?;
+
+    let warnings = run_turn(
+        &test.codex,
+        Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "this one is urgent".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: test.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: test.session_configured.model.clone(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+            max_output_tokens: None,
+            service_tier: Some(ServiceTier::Priority),
+        },
+    )
+    .await?;
+    assert_eq!(warnings, Vec::<String>::new());
+
+    test.submit_turn("this one can wait").await?;
+
+    let tiers: Vec<_> = responses
+        .requests()
+        .iter()
+        .map(|request| request.body_json()["service_tier"].clone())
+        .collect();
+    assert_eq!(tiers, vec![json!("priority"), json!("flex")]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn warns_when_the_provider_serves_another_tier() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                completed_with_tier("resp-1", "default"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                completed_with_tier("resp-2", "default"),
+            ]),
+        ],
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| config.service_tier = Some(ServiceTier::Priority))
+        .build(&server)
+        .await?;
+
+    assert_eq!(
+        run_turn(&test.codex, user_input("first turn")).await?,
+        vec![
+            "Requested the priority service tier, but the provider processed the response with the default tier."
+                .to_string()
+        ]
+    );
+    // The same downgrade is reported only once.
+    assert_eq!(
+        run_turn(&test.codex, user_input("second turn")).await?,
+        Vec::<String>::new()
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rejected_service_tier_falls_back_to_default() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let rejected = ResponseTemplate::new(400)
+        .insert_header("content-type", "application/json")
+        .set_body_string(
+            json!({
+                "error": {
+                    "type": "invalid_request_error",
+                    "param": "service_tier",
+                    "message": "Flex processing is not available for this model."
+                }
+            })
+            .to_string(),
+        );
+    let responses = mount_response_sequence(
+        &server,
+        vec![
+            rejected,
+            sse_response(sse(vec![
+                ev_response_created("resp-1"),
+                ev_completed("resp-1"),
+            ])),
+            sse_response(sse(vec![
+                ev_response_created("resp-2"),
+                ev_completed("resp-2"),
+            ])),
+        ],
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| config.service_tier = Some(ServiceTier::Flex))
+        .build(&server)
+        .await?;
+
+    let warnings = run_turn(&test.codex, user_input("first turn")).await?;
+    assert_eq!(warnings.len(), 1, "unexpected warnings: {warnings:?}");
+    assert!(
+        warnings[0].contains("rejected the `flex` service tier"),
+        "unexpected warning: {}",
+        warnings[0]
+    );
+    assert_eq!(
+        run_turn(&test.codex, user_input("second turn")).await?,
+        Vec::<String>::new()
+    );
+
+    let tiers: Vec<_> = responses
+        .requests()
+        .iter()
+        .map(|request| request.body_json().get("service_tier").cloned())
+        .collect();
+    assert_eq!(tiers, vec![Some(json!("flex")), None, None]);
+
+    Ok(())
+}
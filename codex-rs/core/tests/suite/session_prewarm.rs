@@ -0,0 +1,120 @@
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::PrewarmPhase;
+use codex_core::protocol::ProjectDocFile;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event_with_timeout;
+use pretty_assertions::assert_eq;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn session_reports_startup_phases_once_prewarmed() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex { codex, .. } = test_codex().build(&server).await?;
+
+    let EventMsg::SessionPrewarmed(prewarmed) = wait_for_event_with_timeout(
+        &codex,
+        |event| matches!(event, EventMsg::SessionPrewarmed(_)),
+        Duration::from_secs(10),
+    )
+    .await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+
+    let mut phases: Vec<PrewarmPhase> = prewarmed.phases.iter().map(|p| p.phase).collect();
+    phases.sort_by_key(|phase| format!("{phase:?}"));
+    assert_eq!(
+        phases,
+        vec![
+            PrewarmPhase::Auth,
+            PrewarmPhase::Config,
+            PrewarmPhase::McpServers,
+            PrewarmPhase::ModelCheck,
+            PrewarmPhase::ProjectDocs,
+            PrewarmPhase::Rollout,
+        ]
+    );
+    assert_eq!(prewarmed.degraded, Vec::new());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn session_configured_reports_startup_diagnostics() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let project = TempDir::new()?;
+    let agents_md = project.path().join("AGENTS.md");
+    std::fs::write(&agents_md, "Run the tests before committing.\n")?;
+    let cwd = project.path().to_path_buf();
+    let test = test_codex()
+        .with_config(move |config| {
+            config.cwd = cwd;
+            config.startup_probe = true;
+        })
+        .build(&server)
+        .await?;
+
+    let startup = test
+        .session_configured
+        .startup
+        .clone()
+        .expect("startup diagnostics");
+    let probe = startup.provider_probe.expect("provider probe");
+    assert!(probe.reachable);
+    assert_eq!(
+        startup.project_docs,
+        vec![ProjectDocFile {
+            path: agents_md.canonicalize()?,
+            bytes: 33,
+        }]
+    );
+    assert!(
+        startup
+            .phases
+            .iter()
+            .any(|timing| timing.phase == PrewarmPhase::ProviderProbe)
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn prewarmed_thread_activates_and_can_be_discarded() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex().build(&server).await?;
+
+    let activated = test
+        .thread_manager
+        .prewarm_thread(test.config.clone())
+        .activate()
+        .await?;
+    assert_eq!(activated.session_configured.session_id, activated.thread_id);
+    wait_for_event_with_timeout(
+        &activated.thread,
+        |event| matches!(event, EventMsg::SessionPrewarmed(_)),
+        Duration::from_secs(10),
+    )
+    .await;
+
+    test.thread_manager
+        .prewarm_thread(test.config.clone())
+        .discard()
+        .await;
+    let mut thread_ids = test.thread_manager.list_thread_ids().await;
+    thread_ids.sort_by_key(ToString::to_string);
+    let mut expected = vec![test.session_configured.session_id, activated.thread_id];
+    expected.sort_by_key(ToString::to_string);
+    assert_eq!(thread_ids, expected);
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use anyhow::Context;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::RolloutItem;
+use codex_core::protocol::RolloutLine;
+use codex_core::protocol::SessionMeta;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use std::time::Duration;
+
+/// Read the rollout once it contains `needle`; the writer task appends asynchronously.
+async fn read_rollout_containing(path: &Path, needle: &str) -> anyhow::Result<String> {
+    for _ in 0..50 {
+        if let Ok(text) = std::fs::read_to_string(path)
+            && text.contains(needle)
+        {
+            return Ok(text);
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    std::fs::read_to_string(path)
+        .with_context(|| format!("read rollout file at {}", path.display()))
+}
+
+fn header_meta(text: &str) -> anyhow::Result<SessionMeta> {
+    let first = text.lines().next().context("rollout is empty")?;
+    let line: RolloutLine = serde_json::from_str(first)?;
+    match line.item {
+        RolloutItem::SessionMeta(meta_line) => Ok(meta_line.meta),
+        _ => anyhow::bail!("rollout does not start with session metadata"),
+    }
+}
+
+async fn submit_text(codex: &codex_core::CodexThread, text: &str) -> anyhow::Result<()> {
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: text.to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    wait_for_event(codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    Ok(())
+}
+
+async fn rename(codex: &codex_core::CodexThread, name: &str) -> anyhow::Result<()> {
+    codex
+        .submit(Op::SetThreadName {
+            name: name.to_string(),
+        })
+        .await?;
+    wait_for_event(codex, |ev| matches!(ev, EventMsg::ThreadNameUpdated(_))).await;
+    Ok(())
+}
+
+fn completed_turn(id: &str) -> String {
+    sse(vec![
+        ev_response_created(id),
+        ev_assistant_message(&format!("msg-{id}"), "done"),
+        ev_completed(id),
+    ])
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn first_user_message_becomes_session_title() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_sse_sequence(&server, vec![completed_turn("resp-1")]).await;
+
+    let TestCodex {
+        codex,
+        session_configured,
+        ..
+    } = test_codex().build(&server).await?;
+    let rollout_path = session_configured.rollout_path.context("rollout path")?;
+
+    submit_text(&codex, "\n  Fix the auth   refactor\nwith more detail").await?;
+
+    let meta =
+        header_meta(&read_rollout_containing(&rollout_path, "Fix the auth refactor").await?)?;
+    assert_eq!(meta.title.as_deref(), Some("Fix the auth refactor"));
+    assert_eq!(meta.tags, Vec::<String>::new());
+    // Automatic titles stay out of the thread name.
+    assert_eq!(session_configured.thread_name, None);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn renaming_rewrites_only_the_rollout_header() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_sse_sequence(
+        &server,
+        vec![completed_turn("resp-1"), completed_turn("resp-2")],
+    )
+    .await;
+
+    let TestCodex {
+        codex,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_config(|config| {
+            config.session_title = Some(" Auth refactor ".to_string());
+            config.session_tags = vec![
+                "auth".to_string(),
+                "backend".to_string(),
+                "auth".to_string(),
+            ];
+        })
+        .build(&server)
+        .await?;
+    assert_eq!(
+        session_configured.thread_name.as_deref(),
+        Some("Auth refactor")
+    );
+    let rollout_path = session_configured.rollout_path.context("rollout path")?;
+
+    submit_text(&codex, "first turn").await?;
+    let meta = header_meta(&read_rollout_containing(&rollout_path, "first turn").await?)?;
+    assert_eq!(meta.title.as_deref(), Some("Auth refactor"));
+    assert_eq!(meta.tags, vec!["auth".to_string(), "backend".to_string()]);
+
+    // Renames are applied after every queued write, so the file is settled
+    // once the first rename is acknowledged.
+    rename(&codex, "Auth").await?;
+    let before = read_rollout_containing(&rollout_path, "first turn").await?;
+    assert_eq!(header_meta(&before)?.title.as_deref(), Some("Auth"));
+
+    // A short title fits in the header's reserved space: the rest of the file
+    // is untouched byte for byte.
+    rename(&codex, "Auth cleanup").await?;
+    let after = read_rollout_containing(&rollout_path, "first turn").await?;
+    assert_eq!(header_meta(&after)?.title.as_deref(), Some("Auth cleanup"));
+    let (before_header, before_rest) = before.split_once('\n').context("header line")?;
+    let (after_header, after_rest) = after.split_once('\n').context("header line")?;
+    assert_eq!(after_header.len(), before_header.len());
+    assert_eq!(after_rest, before_rest);
+
+    // A title that outgrows the header falls back to rewriting the file, and
+    // later turns keep appending to the new file.
+    let long_title = "a much longer title ".repeat(20).trim_end().to_string();
+    rename(&codex, &long_title).await?;
+    submit_text(&codex, "second turn").await?;
+
+    let text = read_rollout_containing(&rollout_path, "second turn").await?;
+    let meta = header_meta(&text)?;
+    assert_eq!(meta.title, Some(long_title));
+    assert_eq!(meta.tags, vec!["auth".to_string(), "backend".to_string()]);
+    assert!(text.contains("first turn"));
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_core::error::CodexErr;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event_with_timeout;
+use pretty_assertions::assert_eq;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_threads_share_auth_and_config() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex().build(&server).await?;
+    let responses = (1..=3)
+        .map(|n| {
+            sse(vec![
+                ev_response_created(&format!("resp-{n}")),
+                ev_assistant_message(&format!("msg-{n}"), "done"),
+                ev_completed(&format!("resp-{n}")),
+            ])
+        })
+        .collect();
+    let mock = mount_sse_sequence(&server, responses).await;
+
+    let config = Arc::new(test.config.clone());
+    let runs: Vec<_> = (0..3)
+        .map(|n| {
+            let manager = Arc::clone(&test.thread_manager);
+            let config = Arc::clone(&config);
+            tokio::spawn(async move {
+                let new_thread = manager.start_thread(config).await?;
+                new_thread
+                    .thread
+                    .submit(Op::UserInput {
+                        items: vec![UserInput::Text {
+                            text: format!("summarize project {n}"),
+                            text_elements: Vec::new(),
+                        }],
+                        final_output_json_schema: None,
+                    })
+                    .await?;
+                wait_for_event_with_timeout(
+                    &new_thread.thread,
+                    |event| matches!(event, EventMsg::TurnComplete(_)),
+                    Duration::from_secs(10),
+                )
+                .await;
+                anyhow::Ok(new_thread.thread_id)
+            })
+        })
+        .collect();
+    let mut thread_ids = Vec::new();
+    for run in runs {
+        thread_ids.push(run.await??);
+    }
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 3);
+    let authorization: Vec<Option<String>> = requests
+        .iter()
+        .map(|request| request.header("authorization"))
+        .collect();
+    assert_eq!(authorization, vec![Some("Bearer dummy".to_string()); 3]);
+
+    let live = test.thread_manager.list_thread_ids().await;
+    assert!(thread_ids.iter().all(|id| live.contains(id)), "{live:?}");
+
+    test.thread_manager.interrupt_thread(thread_ids[0]).await?;
+    test.thread_manager.remove_thread(&thread_ids[0]).await;
+    let err = test
+        .thread_manager
+        .interrupt_thread(thread_ids[0])
+        .await
+        .expect_err("removed thread should not be found");
+    assert!(matches!(err, CodexErr::ThreadNotFound(id) if id == thread_ids[0]));
+
+    Ok(())
+}
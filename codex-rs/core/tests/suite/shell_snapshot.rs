@@ -102,6 +102,8 @@ async fn run_snapshot_command(command: &str) -> Result<SnapshotRun> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -176,6 +178,8 @@ async fn run_shell_command_snapshot(command: &str) -> Result<SnapshotRun> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -311,6 +315,8 @@ async fn shell_command_snapshot_still_intercepts_apply_patch() -> Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
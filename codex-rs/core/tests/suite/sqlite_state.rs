@@ -92,6 +92,9 @@ async fn backfill_scans_existing_rollouts() -> Result<()> {
                     cli_version: "test".to_string(),
                     source: SessionSource::default(),
                     model_provider: None,
+                    title: None,
+                    tags: Vec::new(),
+                    sampling: None,
                     base_instructions: None,
                     dynamic_tools: None,
                 },
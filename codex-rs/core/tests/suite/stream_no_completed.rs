@@ -8,10 +8,22 @@ use codex_core::protocol::Op;
 use codex_protocol::user_input::UserInput;
 use core_test_support::load_sse_fixture;
 use core_test_support::load_sse_fixture_with_id;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_message_item_added;
+use core_test_support::responses::ev_output_text_delta;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
 use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::TestCodex;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_with_timeout;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use std::time::Duration;
 use wiremock::Mock;
 use wiremock::MockServer;
 use wiremock::Request;
@@ -80,8 +92,17 @@ async fn retries_on_early_close() {
         request_max_retries: Some(0),
         stream_max_retries: Some(1),
         stream_idle_timeout_ms: Some(2000),
+        first_byte_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        refresh_headers_per_attempt: false,
+        fallback_provider: None,
+        failover_after_failures: None,
+        disable_response_storage: false,
+        supports_file_uploads: false,
+        sandbox_mode: None,
+        approval_policy: None,
+        extra_body: None,
     };
 
     let TestCodex { codex, .. } = test_codex()
@@ -106,3 +127,111 @@ async fn retries_on_early_close() {
     // Wait until TurnComplete (should succeed after retry).
     wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
 }
+
+fn hello_input() -> Op {
+    Op::UserInput {
+        items: vec![UserInput::Text {
+            text: "hello".into(),
+            text_elements: Vec::new(),
+        }],
+        final_output_json_schema: None,
+    }
+}
+
+/// Collects assistant deltas and final messages until the turn completes.
+async fn collect_agent_output(codex: &codex_core::CodexThread) -> (String, Vec<String>) {
+    let mut deltas = String::new();
+    let mut messages = Vec::new();
+    wait_for_event_with_timeout(
+        codex,
+        |event| {
+            match event {
+                EventMsg::AgentMessageDelta(delta) => deltas.push_str(&delta.delta),
+                EventMsg::AgentMessage(message) => messages.push(message.message.clone()),
+                _ => {}
+            }
+            matches!(event, EventMsg::TurnComplete(_))
+        },
+        Duration::from_secs(10),
+    )
+    .await;
+    (deltas, messages)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn retry_continues_after_items_completed_before_the_drop() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            // No `response.completed`: the stream drops after the first item.
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "First paragraph."),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "Second paragraph."),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+    let TestCodex { codex, .. } = test_codex().build(&server).await?;
+
+    codex.submit(hello_input()).await?;
+    let (_, messages) = collect_agent_output(&codex).await;
+
+    assert_eq!(messages, vec!["First paragraph.", "Second paragraph."]);
+    let requests = responses.requests();
+    assert_eq!(requests.len(), 2);
+    let resent_first_paragraph = requests[1].input().iter().any(|item| {
+        item.get("role").and_then(Value::as_str) == Some("assistant")
+            && item["content"][0]["text"].as_str() == Some("First paragraph.")
+    });
+    assert!(
+        resent_first_paragraph,
+        "retry should include the item completed before the drop"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn retry_does_not_repeat_partially_streamed_text() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_message_item_added("msg-1", ""),
+                ev_output_text_delta("Hello, "),
+                ev_output_text_delta("wor"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_message_item_added("msg-2", ""),
+                ev_output_text_delta("Hello, "),
+                ev_output_text_delta("world"),
+                ev_output_text_delta("!"),
+                ev_assistant_message("msg-2", "Hello, world!"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+    let TestCodex { codex, .. } = test_codex().build(&server).await?;
+
+    codex.submit(hello_input()).await?;
+    let (deltas, messages) = collect_agent_output(&codex).await;
+
+    assert_eq!(deltas, "Hello, world!");
+    assert_eq!(messages, vec!["Hello, world!"]);
+
+    Ok(())
+}
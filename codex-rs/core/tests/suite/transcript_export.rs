@@ -0,0 +1,33 @@
+use codex_core::transcript_export::TranscriptExportOptions;
+use codex_core::transcript_export::export_html_transcript;
+use codex_utils_cargo_bin::find_resource;
+use pretty_assertions::assert_eq;
+
+#[tokio::test]
+async fn html_export_matches_golden_file() -> anyhow::Result<()> {
+    let rollout_path = find_resource!("tests/fixtures/transcript_export/session.jsonl")?;
+    let golden_path = find_resource!("tests/fixtures/transcript_export/session.html")?;
+    let options = TranscriptExportOptions {
+        redact_patterns: vec![r"[a-z0-9-]+\.corp\.example\.net".to_string()],
+    };
+
+    let html = export_html_transcript(&rollout_path, &options).await?;
+
+    assert_eq!(html, std::fs::read_to_string(golden_path)?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn html_export_rejects_invalid_redaction_pattern() -> anyhow::Result<()> {
+    let rollout_path = find_resource!("tests/fixtures/transcript_export/session.jsonl")?;
+    let options = TranscriptExportOptions {
+        redact_patterns: vec!["(unclosed".to_string()],
+    };
+
+    let err = export_html_transcript(&rollout_path, &options)
+        .await
+        .expect_err("invalid pattern should fail");
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    Ok(())
+}
@@ -3,6 +3,7 @@
 
 use anyhow::Context;
 use anyhow::Result;
+use codex_core::config::types::McpArgumentValidation;
 use codex_core::config::types::McpServerConfig;
 use codex_core::config::types::McpServerTransportConfig;
 use codex_core::protocol::AskForApproval;
@@ -432,6 +433,7 @@ async fn mcp_tool_call_output_exceeds_limit_truncated_for_model() -> Result<()>
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
         config
@@ -525,6 +527,7 @@ async fn mcp_image_output_preserves_image_and_no_text_summary() -> Result<()> {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
         config
@@ -551,6 +554,8 @@ async fn mcp_image_output_preserves_image_and_no_text_summary() -> Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -674,6 +679,59 @@ async fn byte_policy_marker_reports_bytes() -> Result<()> {
     Ok(())
 }
 
+// A configured line limit keeps whole head and tail lines with an elision marker.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn tool_output_max_lines_keeps_head_and_tail_lines() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let mut builder = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(|config| {
+            config.tool_output_max_lines = Some(10);
+        });
+    let fixture = builder.build(&server).await?;
+
+    let call_id = "shell-max-lines";
+    let args = json!({
+        "command": "seq 1 150",
+        "timeout_ms": 5_000,
+    });
+
+    mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call(call_id, "shell_command", &serde_json::to_string(&args)?),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let done_mock = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    fixture
+        .submit_turn_with_policy("run the shell tool", SandboxPolicy::DangerFullAccess)
+        .await?;
+
+    let output = done_mock
+        .single_request()
+        .function_call_output_text(call_id)
+        .context("shell output present")?;
+
+    let pattern = r"(?s)^Exit code: 0\nWall time: [0-9]+(?:\.[0-9]+)? seconds\nTotal output lines: 150\nOutput:\n1\n2\n3\n4\n5\n…140 lines omitted…\n146\n147\n148\n149\n150\n$";
+
+    assert_regex_match(pattern, &output);
+
+    Ok(())
+}
+
 // shell_command output should remain intact when the config opts into a large token budget.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn shell_command_output_not_truncated_with_custom_limit() -> Result<()> {
@@ -789,6 +847,7 @@ async fn mcp_tool_call_output_not_truncated_with_custom_limit() -> Result<()> {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                argument_validation: McpArgumentValidation::Strict,
             },
         );
         config
@@ -92,6 +92,8 @@ async fn user_turn_with_local_image_attaches_image() -> anyhow::Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -194,6 +196,8 @@ async fn view_image_tool_attaches_local_image() -> anyhow::Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -327,6 +331,8 @@ async fn view_image_tool_errors_when_path_is_directory() -> anyhow::Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -402,6 +408,8 @@ async fn view_image_tool_placeholder_for_non_image_files() -> anyhow::Result<()>
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -496,6 +504,8 @@ async fn view_image_tool_errors_when_file_missing() -> anyhow::Result<()> {
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -579,6 +589,8 @@ async fn replaces_invalid_local_image_after_bad_request() -> anyhow::Result<()>
             summary: ReasoningSummary::Auto,
             collaboration_mode: None,
             personality: None,
+            max_output_tokens: None,
+            service_tier: None,
         })
         .await?;
 
@@ -601,3 +613,102 @@ async fn replaces_invalid_local_image_after_bad_request() -> anyhow::Result<()>
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn user_turn_reports_downscaled_local_image() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let mock = responses::mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let TestCodex { codex, cwd, .. } = test_codex()
+        .with_config(|config| {
+            config.image_limits.max_width = 512;
+            config.image_limits.max_height = 512;
+        })
+        .build(&server)
+        .await?;
+
+    let abs_path = cwd.path().join("screenshot.png");
+    let image = ImageBuffer::from_pixel(1600, 1200, Rgba([90u8, 120, 150, 255]));
+    image.save(&abs_path)?;
+    let original_bytes = std::fs::metadata(&abs_path)?.len();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::LocalImage {
+                path: abs_path.clone(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let EventMsg::ImageDownscaled(event) = wait_for_event(&codex, |event| {
+        matches!(event, EventMsg::ImageDownscaled(_))
+    })
+    .await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert_eq!(event.path, abs_path);
+    assert_eq!((event.original_width, event.original_height), (1600, 1200));
+    assert_eq!(event.original_bytes, original_bytes);
+    assert_eq!((event.sent_width, event.sent_height), (512, 384));
+
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let body = mock.single_request().body_json();
+    assert!(find_image_message(&body).is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn oversized_request_fails_before_sending() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(|config| {
+            config.max_request_body_bytes = 64 * 1024;
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "x".repeat(128 * 1024),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let EventMsg::Error(error) =
+        wait_for_event(&codex, |event| matches!(event, EventMsg::Error(_))).await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert!(error.message.contains("max_request_body_bytes"));
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert!(
+        requests
+            .iter()
+            .all(|request| !request.url.path().ends_with("/responses")),
+        "oversized request should not reach the provider"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+use codex_core::CodexThread;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::WorkspaceSummaryEvent;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn git(repo: &Path, args: &[&str]) {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .expect("spawn git");
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn init_repo() -> TempDir {
+    let repo = TempDir::new().expect("tempdir");
+    let path = repo.path();
+    git(path, &["init", "--initial-branch=main"]);
+    git(path, &["config", "user.name", "Codex Tests"]);
+    git(path, &["config", "user.email", "codex-tests@example.com"]);
+    std::fs::write(
+        path.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+    )
+    .expect("write Cargo.toml");
+    std::fs::create_dir(path.join("src")).expect("mkdir src");
+    std::fs::write(path.join("src/main.rs"), "fn main() {}\n").expect("write main.rs");
+    std::fs::write(path.join(".gitignore"), "target/\n").expect("write .gitignore");
+    std::fs::create_dir(path.join("target")).expect("mkdir target");
+    std::fs::write(path.join("target/build.log"), "ignored\n").expect("write build.log");
+    git(path, &["add", "."]);
+    git(path, &["commit", "-m", "Add demo crate"]);
+    repo
+}
+
+async fn say_hello(codex: &CodexThread) -> Result<()> {
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    wait_for_event(codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    Ok(())
+}
+
+async fn summary_event(codex: &CodexThread) -> WorkspaceSummaryEvent {
+    wait_for_event_match(codex, |ev| match ev {
+        EventMsg::WorkspaceSummary(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn summary_is_injected_and_reused_from_cache() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+    let repo = Arc::new(init_repo());
+    let home = Arc::new(TempDir::new()?);
+    let session = || {
+        let repo = Arc::clone(&repo);
+        test_codex()
+            .with_home(Arc::clone(&home))
+            .with_config(move |config| config.cwd = repo.path().to_path_buf())
+    };
+
+    let first = session().build(&server).await?;
+    let generated = summary_event(&first.codex).await;
+    assert!(!generated.cache_hit);
+    say_hello(&first.codex).await?;
+
+    let texts = response.single_request().message_input_texts("user");
+    let summary = texts
+        .iter()
+        .find(|text| text.starts_with("<workspace_summary>"))
+        .expect("workspace summary in the initial context");
+    assert_eq!(generated.bytes, summary.len() as u64);
+    assert!(summary.contains("Languages and build systems: Rust (Cargo)"));
+    assert!(summary.contains("Add demo crate"));
+    assert!(summary.contains("src/\n  main.rs"));
+    assert!(summary.contains("name = \"demo\""));
+    assert!(!summary.contains("target/"), "ignored files are left out");
+
+    let second = session().build(&server).await?;
+    let cached = summary_event(&second.codex).await;
+    assert_eq!(
+        cached,
+        WorkspaceSummaryEvent {
+            cache_hit: true,
+            ..generated
+        }
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn summary_can_be_disabled() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+    let repo = init_repo();
+    let cwd = repo.path().to_path_buf();
+    let test = test_codex()
+        .with_config(move |config| {
+            config.cwd = cwd;
+            config.workspace_summary.enabled = false;
+        })
+        .build(&server)
+        .await?;
+    say_hello(&test.codex).await?;
+
+    let texts = response.single_request().message_input_texts("user");
+    assert!(
+        !texts
+            .iter()
+            .any(|text| text.starts_with("<workspace_summary>")),
+        "{texts:?}"
+    );
+
+    Ok(())
+}
@@ -27,6 +27,7 @@ use crate::posix::mcp::ExecParams;
 use crate::posix::socket::AsyncDatagramSocket;
 use crate::posix::socket::AsyncSocket;
 use codex_core::exec::ExecExpiration;
+use codex_core::exec_limits::ExecLimits;
 
 pub(crate) struct EscalateServer {
     bash_path: PathBuf,
@@ -91,6 +92,7 @@ impl EscalateServer {
                 windows_sandbox_level: WindowsSandboxLevel::Disabled,
                 justification: None,
                 arg0: None,
+                limits: ExecLimits::default(),
             },
             &sandbox_state.sandbox_policy,
             &sandbox_state.sandbox_cwd,
@@ -0,0 +1,725 @@
+//! `codex exec --batch`: run one prompt against many targets.
+//!
+//! Every target runs in its own `codex exec --json` child process rooted at
+//! the target directory. That keeps each session's rollout, sandbox, exit
+//! handling and `--max-tokens`/`--max-turns` budget fully independent, so a
+//! failing target never aborts the batch. With `--batch-worktree`, targets
+//! inside a git repository run in a fresh detached worktree instead.
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::BufReader;
+use tokio::process::Command;
+use tokio::task::JoinSet;
+use tracing::debug;
+use tracing::warn;
+
+use crate::cli::Cli;
+use crate::exec_events::ThreadEvent;
+use crate::exec_events::ThreadItemDetails;
+use crate::exec_events::Usage;
+
+/// A single line of the batch targets file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BatchTarget {
+    Directory(PathBuf),
+    GitUrl(String),
+}
+
+impl BatchTarget {
+    fn label(&self) -> String {
+        match self {
+            BatchTarget::Directory(path) => path.display().to_string(),
+            BatchTarget::GitUrl(url) => url.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BatchStatus {
+    Succeeded,
+    Failed,
+    TimedOut,
+    Skipped,
+}
+
+impl BatchStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            BatchStatus::Succeeded => "ok",
+            BatchStatus::Failed => "failed",
+            BatchStatus::TimedOut => "timed out",
+            BatchStatus::Skipped => "skipped",
+        }
+    }
+}
+
+/// Outcome of one target, as reported in the batch summary.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BatchTargetResult {
+    pub target: String,
+    /// Directory the session ran in. Differs from `target` for cloned URLs.
+    pub workspace: Option<PathBuf>,
+    pub status: BatchStatus,
+    pub thread_id: Option<String>,
+    pub files_changed: Vec<String>,
+    pub usage: Usage,
+    pub duration_secs: f64,
+    pub error: Option<String>,
+}
+
+impl BatchTargetResult {
+    fn new(target: &BatchTarget, status: BatchStatus) -> Self {
+        Self {
+            target: target.label(),
+            workspace: None,
+            status,
+            thread_id: None,
+            files_changed: Vec::new(),
+            usage: Usage::default(),
+            duration_secs: 0.0,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BatchSummary {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    succeeded: usize,
+    failed: usize,
+    results: Vec<BatchTargetResult>,
+}
+
+struct BatchOptions {
+    exe: PathBuf,
+    /// Leading arguments needed to reach `exec` (e.g. `["exec"]` for the multitool).
+    exe_prefix: Vec<String>,
+    child_args: Vec<String>,
+    timeout: Option<Duration>,
+    worktree: bool,
+    workspace_root: PathBuf,
+}
+
+pub(crate) async fn run_batch(cli: Cli) -> anyhow::Result<()> {
+    let Some(targets_path) = cli.batch.clone() else {
+        anyhow::bail!("--batch requires a targets file");
+    };
+    if cli.command.is_some() {
+        anyhow::bail!("--batch cannot be combined with the resume, review or ask subcommands");
+    }
+    let contents = std::fs::read_to_string(&targets_path).map_err(|err| {
+        anyhow::anyhow!(
+            "failed to read batch targets {}: {err}",
+            targets_path.display()
+        )
+    })?;
+    let base_dir = targets_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let targets = parse_targets(&contents, &base_dir);
+    if targets.is_empty() {
+        anyhow::bail!("no targets found in {}", targets_path.display());
+    }
+
+    // Resolve the prompt once so stdin is not consumed by the first child.
+    let prompt = crate::render_prompt(
+        crate::resolve_prompt_source(cli.prompt.clone(), cli.prompt_file.as_deref()),
+        &cli.vars,
+    );
+    let exe = std::env::current_exe()?;
+    let exe_prefix = if exe
+        .file_stem()
+        .is_some_and(|stem| stem.to_string_lossy().starts_with("codex-exec"))
+    {
+        Vec::new()
+    } else {
+        vec!["exec".to_string()]
+    };
+    let options = Arc::new(BatchOptions {
+        exe,
+        exe_prefix,
+        child_args: child_args(&cli, &prompt),
+        timeout: cli.batch_timeout.map(Duration::from_secs),
+        worktree: cli.batch_worktree,
+        workspace_root: std::env::temp_dir().join(format!("codex-batch-{}", uuid::Uuid::new_v4())),
+    });
+
+    let interrupted = Arc::new(AtomicBool::new(false));
+    {
+        let interrupted = Arc::clone(&interrupted);
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                // Running children receive the same SIGINT and shut down on their
+                // own; we only stop launching new targets and wait for them.
+                eprintln!("Interrupted: finishing running targets before exiting.");
+                interrupted.store(true, Ordering::SeqCst);
+            }
+        });
+    }
+
+    let jobs = usize::from(cli.batch_jobs).max(1);
+    let mut results: Vec<Option<BatchTargetResult>> = vec![None; targets.len()];
+    let mut pending = targets.iter().cloned().enumerate();
+    let mut running = JoinSet::new();
+    loop {
+        while running.len() < jobs && !interrupted.load(Ordering::SeqCst) {
+            let Some((index, target)) = pending.next() else {
+                break;
+            };
+            eprintln!("[{}/{}] {}", index + 1, targets.len(), target.label());
+            let options = Arc::clone(&options);
+            running.spawn(async move { (index, run_target(&options, target).await) });
+        }
+        match running.join_next().await {
+            Some(Ok((index, result))) => results[index] = Some(result),
+            Some(Err(err)) => warn!("batch target task failed: {err}"),
+            None => break,
+        }
+    }
+
+    let results: Vec<BatchTargetResult> = results
+        .into_iter()
+        .zip(targets.iter())
+        .map(|(result, target)| {
+            result.unwrap_or_else(|| BatchTargetResult::new(target, BatchStatus::Skipped))
+        })
+        .collect();
+    let summary = BatchSummary {
+        kind: "batch.summary",
+        succeeded: results
+            .iter()
+            .filter(|r| r.status == BatchStatus::Succeeded)
+            .count(),
+        failed: results
+            .iter()
+            .filter(|r| r.status != BatchStatus::Succeeded)
+            .count(),
+        results,
+    };
+
+    let summary_json = serde_json::to_string(&summary)?;
+    if let Some(path) = cli.batch_summary.as_ref()
+        && let Err(err) = std::fs::write(path, &summary_json)
+    {
+        eprintln!("Failed to write batch summary {}: {err}", path.display());
+    }
+    #[allow(clippy::print_stdout)]
+    if cli.json {
+        println!("{summary_json}");
+    } else {
+        print!("{}", format_summary_table(&summary));
+    }
+
+    if summary.failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Parse the targets file: one directory or git URL per line, `#` comments
+/// and blank lines ignored. Relative directories resolve against `base_dir`.
+pub(crate) fn parse_targets(contents: &str, base_dir: &Path) -> Vec<BatchTarget> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            if is_git_url(line) {
+                BatchTarget::GitUrl(line.to_string())
+            } else {
+                BatchTarget::Directory(base_dir.join(line))
+            }
+        })
+        .collect()
+}
+
+fn is_git_url(line: &str) -> bool {
+    ["https://", "http://", "ssh://", "git://", "git@", "file://"]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
+/// Rebuild the exec flags that should apply to every target. `--cd`,
+/// `--output-last-message`, the batch flags and subcommands are never forwarded.
+/// `--prompt-file` and `--var` are already applied to `prompt`.
+fn child_args(cli: &Cli, prompt: &str) -> Vec<String> {
+    let mut args = vec![
+        "--json".to_string(),
+        "--color".to_string(),
+        "never".to_string(),
+    ];
+    for image in &cli.images {
+        args.push("--image".to_string());
+        args.push(image.display().to_string());
+    }
+    for attachment in &cli.attachments {
+        args.push("--attach".to_string());
+        args.push(attachment.display().to_string());
+    }
+    if let Some(model) = &cli.model {
+        args.extend(["--model".to_string(), model.clone()]);
+    }
+    if cli.oss {
+        args.push("--oss".to_string());
+    }
+    if let Some(provider) = &cli.oss_provider {
+        args.extend(["--local-provider".to_string(), provider.clone()]);
+    }
+    if let Some(mode) = cli.sandbox_mode.and_then(|mode| mode.to_possible_value()) {
+        args.extend(["--sandbox".to_string(), mode.get_name().to_string()]);
+    }
+    if let Some(profile) = &cli.config_profile {
+        args.extend(["--profile".to_string(), profile.clone()]);
+    }
+    if cli.full_auto {
+        args.push("--full-auto".to_string());
+    }
+    if cli.dangerously_bypass_approvals_and_sandbox {
+        args.push("--dangerously-bypass-approvals-and-sandbox".to_string());
+    }
+    if cli.trust_cwd {
+        args.push("--trust-cwd".to_string());
+    }
+    if let Some(effort) = cli.reasoning_effort.and_then(|e| e.to_possible_value()) {
+        args.extend([
+            "--reasoning-effort".to_string(),
+            effort.get_name().to_string(),
+        ]);
+    }
+    if let Some(summary) = cli.reasoning_summary.and_then(|s| s.to_possible_value()) {
+        args.extend([
+            "--reasoning-summary".to_string(),
+            summary.get_name().to_string(),
+        ]);
+    }
+    if let Some(max_output_tokens) = cli.max_output_tokens {
+        args.extend([
+            "--max-output-tokens".to_string(),
+            max_output_tokens.to_string(),
+        ]);
+    }
+    if let Some(max_tokens) = cli.max_tokens {
+        args.extend(["--max-tokens".to_string(), max_tokens.to_string()]);
+    }
+    if let Some(max_turns) = cli.max_turns {
+        args.extend(["--max-turns".to_string(), max_turns.to_string()]);
+    }
+    if let Some(seed) = cli.seed {
+        args.extend(["--seed".to_string(), seed.to_string()]);
+    }
+    if let Some(temperature) = cli.temperature {
+        args.extend(["--temperature".to_string(), temperature.to_string()]);
+    }
+    if let Some(top_p) = cli.top_p {
+        args.extend(["--top-p".to_string(), top_p.to_string()]);
+    }
+    if cli.read_only_chat {
+        args.push("--read-only-chat".to_string());
+    }
+    if cli.offline {
+        args.push("--offline".to_string());
+    }
+    if cli.dry_run_exec {
+        args.push("--dry-run-exec".to_string());
+    }
+    if let Some(title) = &cli.title {
+        args.extend(["--title".to_string(), title.clone()]);
+    }
+    for tag in &cli.tags {
+        args.extend(["--tag".to_string(), tag.clone()]);
+    }
+    for dir in &cli.add_dir {
+        args.push("--add-dir".to_string());
+        args.push(dir.display().to_string());
+    }
+    if let Some(schema) = &cli.output_schema {
+        args.push("--output-schema".to_string());
+        args.push(schema.display().to_string());
+    }
+    for raw in &cli.config_overrides.raw_overrides {
+        args.extend(["-c".to_string(), raw.clone()]);
+    }
+    // `--` keeps prompts that start with `-` from being parsed as flags.
+    args.push("--".to_string());
+    args.push(prompt.to_string());
+    args
+}
+
+async fn run_target(options: &BatchOptions, target: BatchTarget) -> BatchTargetResult {
+    let started = Instant::now();
+    let mut result = BatchTargetResult::new(&target, BatchStatus::Failed);
+    let (workspace, cloned) = match &target {
+        BatchTarget::Directory(path) => match path.canonicalize() {
+            Ok(path) if path.is_dir() && options.worktree => {
+                match add_worktree(&path, &options.workspace_root).await {
+                    Ok(Some(worktree)) => (worktree, true),
+                    Ok(None) => (path, false),
+                    Err(err) => {
+                        result.error = Some(err);
+                        return result;
+                    }
+                }
+            }
+            Ok(path) if path.is_dir() => (path, false),
+            Ok(path) => {
+                result.error = Some(format!("{} is not a directory", path.display()));
+                return result;
+            }
+            Err(err) => {
+                result.error = Some(format!("{}: {err}", path.display()));
+                return result;
+            }
+        },
+        BatchTarget::GitUrl(url) => match clone_target(url, &options.workspace_root).await {
+            Ok(path) => (path, true),
+            Err(err) => {
+                result.error = Some(err);
+                return result;
+            }
+        },
+    };
+    result.workspace = Some(workspace.clone());
+
+    let mut cmd = Command::new(&options.exe);
+    cmd.args(&options.exe_prefix).arg("--cd").arg(&workspace);
+    // Clones and worktrees are created by the batch itself, so trust them
+    // for this run only.
+    if cloned {
+        cmd.arg("--trust-cwd");
+    }
+    cmd.args(&options.child_args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            result.error = Some(format!("failed to launch codex exec: {err}"));
+            return result;
+        }
+    };
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let stderr_task = tokio::spawn(async move {
+        let mut buf = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut buf).await;
+        }
+        buf
+    });
+
+    let mut files_changed = BTreeSet::new();
+    let mut last_error = None;
+    let run = async {
+        if let Some(stdout) = stdout {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                match serde_json::from_str::<ThreadEvent>(&line) {
+                    Ok(event) => {
+                        record_event(event, &mut result, &mut files_changed, &mut last_error)
+                    }
+                    Err(err) => debug!("ignoring non-event batch output: {err}"),
+                }
+            }
+        }
+        child.wait().await
+    };
+    let status = match options.timeout {
+        Some(limit) => tokio::time::timeout(limit, run).await.ok(),
+        None => Some(run.await),
+    };
+
+    result.files_changed = files_changed.into_iter().collect();
+    result.duration_secs = started.elapsed().as_secs_f64();
+    match status {
+        None => {
+            let _ = child.kill().await;
+            result.status = BatchStatus::TimedOut;
+            result.error = Some("exceeded --batch-timeout".to_string());
+        }
+        Some(Ok(status)) if status.success() && last_error.is_none() => {
+            result.status = BatchStatus::Succeeded;
+        }
+        Some(Ok(status)) => {
+            let stderr = stderr_task.await.unwrap_or_default();
+            result.error = last_error.or_else(|| {
+                stderr
+                    .lines()
+                    .rev()
+                    .find(|line| !line.trim().is_empty())
+                    .map(str::to_string)
+            });
+            if result.error.is_none() {
+                result.error = Some(format!("codex exec exited with {status}"));
+            }
+        }
+        Some(Err(err)) => {
+            result.error = Some(format!("failed waiting for codex exec: {err}"));
+        }
+    }
+    result
+}
+
+fn record_event(
+    event: ThreadEvent,
+    result: &mut BatchTargetResult,
+    files_changed: &mut BTreeSet<String>,
+    last_error: &mut Option<String>,
+) {
+    match event {
+        ThreadEvent::ThreadStarted(ev) => result.thread_id = Some(ev.thread_id),
+        ThreadEvent::TurnCompleted(ev) => {
+            // Errors reported before a completed turn were retried away.
+            *last_error = None;
+            result.usage.input_tokens += ev.usage.input_tokens;
+            result.usage.cached_input_tokens += ev.usage.cached_input_tokens;
+            result.usage.output_tokens += ev.usage.output_tokens;
+        }
+        ThreadEvent::TurnFailed(ev) => *last_error = Some(ev.error.message),
+        ThreadEvent::Error(ev) => *last_error = Some(ev.message),
+        ThreadEvent::ItemCompleted(ev) => {
+            if let ThreadItemDetails::FileChange(change) = ev.item.details {
+                files_changed.extend(change.changes.into_iter().map(|c| c.path));
+            }
+        }
+        ThreadEvent::TurnStarted(_) | ThreadEvent::ItemStarted(_) | ThreadEvent::ItemUpdated(_) => {
+        }
+    }
+}
+
+/// Add a detached worktree of the repository containing `dir` at its `HEAD`,
+/// and return the directory in it that corresponds to `dir`. Returns `None`
+/// when `dir` is not inside a git repository.
+async fn add_worktree(dir: &Path, workspace_root: &Path) -> Result<Option<PathBuf>, String> {
+    let toplevel = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|err| format!("failed to run git rev-parse: {err}"))?;
+    if !toplevel.status.success() {
+        return Ok(None);
+    }
+    let repo_root = PathBuf::from(String::from_utf8_lossy(&toplevel.stdout).trim());
+    let subdir = dir.strip_prefix(&repo_root).unwrap_or(Path::new(""));
+
+    let name = repo_root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    let dest = workspace_root.join(format!("{name}-{}", uuid::Uuid::new_v4().simple()));
+    std::fs::create_dir_all(workspace_root)
+        .map_err(|err| format!("failed to create {}: {err}", workspace_root.display()))?;
+    let output = Command::new("git")
+        .args(["worktree", "add", "--quiet", "--detach"])
+        .arg(&dest)
+        .arg("HEAD")
+        .current_dir(&repo_root)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|err| format!("failed to run git worktree add: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git worktree add failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(Some(dest.join(subdir)))
+}
+
+async fn clone_target(url: &str, workspace_root: &Path) -> Result<PathBuf, String> {
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or("repo")
+        .trim_end_matches(".git");
+    let dest = workspace_root.join(format!("{name}-{}", uuid::Uuid::new_v4().simple()));
+    std::fs::create_dir_all(workspace_root)
+        .map_err(|err| format!("failed to create {}: {err}", workspace_root.display()))?;
+    let output = Command::new("git")
+        .args(["clone", "--quiet", "--depth", "1", url])
+        .arg(&dest)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|err| format!("failed to run git clone: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git clone failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(dest)
+}
+
+pub(crate) fn format_summary_table(summary: &BatchSummary) -> String {
+    let target_width = summary
+        .results
+        .iter()
+        .map(|r| r.target.chars().count())
+        .chain(std::iter::once("TARGET".len()))
+        .max()
+        .unwrap_or(0);
+    let mut out = format!(
+        "{:<target_width$}  {:<9}  {:>5}  {:>10}  {:>10}\n",
+        "TARGET", "STATUS", "FILES", "INPUT", "OUTPUT"
+    );
+    for result in &summary.results {
+        out.push_str(&format!(
+            "{:<target_width$}  {:<9}  {:>5}  {:>10}  {:>10}\n",
+            result.target,
+            result.status.as_str(),
+            result.files_changed.len(),
+            result.usage.input_tokens,
+            result.usage.output_tokens,
+        ));
+        if let Some(error) = &result.error {
+            out.push_str(&format!("  error: {error}\n"));
+        }
+    }
+    out.push_str(&format!(
+        "{} succeeded, {} failed\n",
+        summary.succeeded, summary.failed
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec_events::ThreadErrorEvent;
+    use crate::exec_events::TurnCompletedEvent;
+    use crate::exec_events::TurnFailedEvent;
+    use crate::exec_events::TurnStartedEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_directories_urls_and_skips_comments() {
+        let targets = parse_targets(
+            "# repos to bump\nsvc-a\n\n  /abs/svc-b  \nhttps://github.com/o/r.git\ngit@github.com:o/r.git\n",
+            Path::new("/work"),
+        );
+
+        assert_eq!(
+            targets,
+            vec![
+                BatchTarget::Directory(PathBuf::from("/work/svc-a")),
+                BatchTarget::Directory(PathBuf::from("/abs/svc-b")),
+                BatchTarget::GitUrl("https://github.com/o/r.git".to_string()),
+                BatchTarget::GitUrl("git@github.com:o/r.git".to_string()),
+            ]
+        );
+    }
+
+    fn record_all(events: Vec<ThreadEvent>) -> (BatchTargetResult, Option<String>) {
+        let mut result = BatchTargetResult::new(
+            &BatchTarget::Directory(PathBuf::from("svc-a")),
+            BatchStatus::Failed,
+        );
+        let mut files_changed = BTreeSet::new();
+        let mut last_error = None;
+        for event in events {
+            record_event(event, &mut result, &mut files_changed, &mut last_error);
+        }
+        (result, last_error)
+    }
+
+    #[test]
+    fn retried_stream_errors_do_not_fail_a_completed_turn() {
+        let (result, last_error) = record_all(vec![
+            ThreadEvent::TurnStarted(TurnStartedEvent {}),
+            ThreadEvent::Error(ThreadErrorEvent {
+                message: "Reconnecting... 1/5".to_string(),
+                ..Default::default()
+            }),
+            ThreadEvent::TurnCompleted(TurnCompletedEvent {
+                usage: Usage {
+                    input_tokens: 10,
+                    cached_input_tokens: 0,
+                    output_tokens: 5,
+                },
+                system_fingerprint: None,
+            }),
+        ]);
+
+        assert_eq!(last_error, None);
+        assert_eq!(result.usage.output_tokens, 5);
+    }
+
+    #[test]
+    fn failed_turn_keeps_its_error() {
+        let (_, last_error) = record_all(vec![
+            ThreadEvent::TurnStarted(TurnStartedEvent {}),
+            ThreadEvent::Error(ThreadErrorEvent {
+                message: "Reconnecting... 1/5".to_string(),
+                ..Default::default()
+            }),
+            ThreadEvent::TurnFailed(TurnFailedEvent {
+                error: ThreadErrorEvent {
+                    message: "stream disconnected".to_string(),
+                    ..Default::default()
+                },
+            }),
+        ]);
+
+        assert_eq!(last_error, Some("stream disconnected".to_string()));
+    }
+
+    #[test]
+    fn summary_table_lists_every_target() {
+        let ok = BatchTargetResult {
+            files_changed: vec![".github/workflows/ci.yml".to_string()],
+            usage: Usage {
+                input_tokens: 1200,
+                cached_input_tokens: 0,
+                output_tokens: 80,
+            },
+            ..BatchTargetResult::new(
+                &BatchTarget::Directory(PathBuf::from("svc-a")),
+                BatchStatus::Succeeded,
+            )
+        };
+        let failed = BatchTargetResult {
+            error: Some("boom".to_string()),
+            ..BatchTargetResult::new(
+                &BatchTarget::Directory(PathBuf::from("svc-b")),
+                BatchStatus::Failed,
+            )
+        };
+        let summary = BatchSummary {
+            kind: "batch.summary",
+            succeeded: 1,
+            failed: 1,
+            results: vec![ok, failed],
+        };
+
+        assert_eq!(
+            format_summary_table(&summary),
+            "TARGET  STATUS     FILES       INPUT      OUTPUT\n\
+             svc-a   ok             1        1200          80\n\
+             svc-b   failed         0           0           0\n  \
+             error: boom\n\
+             1 succeeded, 1 failed\n"
+        );
+    }
+}
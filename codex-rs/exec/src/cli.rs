@@ -3,6 +3,8 @@ use clap::FromArgMatches;
 use clap::Parser;
 use clap::ValueEnum;
 use codex_common::CliConfigOverrides;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::openai_models::ReasoningEffort;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -22,6 +24,12 @@ pub struct Cli {
     )]
     pub images: Vec<PathBuf>,
 
+    /// File(s) to attach to the prompt. With `upload_attachments` enabled and
+    /// a provider that accepts uploads, each file is uploaded once and
+    /// referenced by id; otherwise its contents are sent inline.
+    #[arg(long = "attach", value_name = "FILE", value_delimiter = ',')]
+    pub attachments: Vec<PathBuf>,
+
     /// Model the agent should use.
     #[arg(long, short = 'm', global = true)]
     pub model: Option<String>,
@@ -63,9 +71,94 @@ pub struct Cli {
     #[clap(long = "cd", short = 'C', value_name = "DIR")]
     pub cwd: Option<PathBuf>,
 
-    /// Allow running Codex outside a Git repository.
-    #[arg(long = "skip-git-repo-check", global = true, default_value_t = false)]
-    pub skip_git_repo_check: bool,
+    /// Trust the working directory for this run without recording it in config.toml.
+    /// Required outside a git repository unless the directory was trusted from the
+    /// interactive TUI; inside an untrusted repository Codex only warns.
+    #[arg(
+        long = "trust-cwd",
+        alias = "skip-git-repo-check",
+        global = true,
+        default_value_t = false
+    )]
+    pub trust_cwd: bool,
+
+    /// Reasoning effort for this run, overriding `model_reasoning_effort`.
+    #[arg(long = "reasoning-effort", value_enum, value_name = "EFFORT")]
+    pub reasoning_effort: Option<ReasoningEffortCliArg>,
+
+    /// Reasoning summary detail for this run, overriding `model_reasoning_summary`.
+    #[arg(long = "reasoning-summary", value_enum, value_name = "SUMMARY")]
+    pub reasoning_summary: Option<ReasoningSummaryCliArg>,
+
+    /// Maximum output tokens per model response for this run, overriding
+    /// `model_max_output_tokens`. Clamped to the model's maximum.
+    #[arg(
+        long = "max-output-tokens",
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub max_output_tokens: Option<u32>,
+
+    /// Stop the run once the session has used more than N tokens in total
+    /// (input plus output, across every model response).
+    #[arg(
+        long = "max-tokens",
+        value_name = "N",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_tokens: Option<u64>,
+
+    /// Stop the run after N model responses. Each round of tool calls the
+    /// agent makes costs one.
+    #[arg(
+        long = "max-turns",
+        value_name = "N",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_turns: Option<u64>,
+
+    /// Sampling seed for this run, overriding `model_seed`. Sent only to Chat
+    /// Completions providers with non-reasoning models.
+    #[arg(long = "seed", value_name = "N", allow_negative_numbers = true)]
+    pub seed: Option<i64>,
+
+    /// Sampling temperature (0-2) for this run, overriding `model_temperature`.
+    #[arg(long = "temperature", value_name = "T", value_parser = parse_temperature)]
+    pub temperature: Option<f64>,
+
+    /// Nucleus sampling mass (greater than 0, at most 1) for this run,
+    /// overriding `model_top_p`.
+    #[arg(long = "top-p", value_name = "P", value_parser = parse_top_p)]
+    pub top_p: Option<f64>,
+
+    /// Read-only chat: disable the shell and `apply_patch` tools so the agent can only answer.
+    /// Same as `-c tools.shell=false -c tools.apply_patch=false`.
+    #[arg(long = "read-only-chat", default_value_t = false)]
+    pub read_only_chat: bool,
+
+    /// Offline mode: fail model requests immediately instead of retrying them.
+    /// Same as `-c offline=true`.
+    #[arg(long = "offline", default_value_t = false)]
+    pub offline: bool,
+
+    /// Developer flag: report how each shell tool command would be spawned,
+    /// including the sandbox wrapper and environment, without running it.
+    /// Same as `-c dry_run_exec=true`.
+    #[arg(long = "dry-run-exec", default_value_t = false)]
+    pub dry_run_exec: bool,
+
+    /// Load provider credentials from this dotenv file. Its values are never
+    /// exported to commands the agent runs. See also `load_dotenv`.
+    #[arg(long = "env-file", value_name = "PATH")]
+    pub env_file: Option<PathBuf>,
+
+    /// Title recorded for the new session, shown when listing sessions to resume.
+    #[arg(long = "title", value_name = "TITLE")]
+    pub title: Option<String>,
+
+    /// Tag the new session so it can be filtered when listing sessions. Repeatable.
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
 
     /// Additional directories that should be writable alongside the primary workspace.
     #[arg(long = "add-dir", value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
@@ -75,6 +168,37 @@ pub struct Cli {
     #[arg(long = "output-schema", value_name = "FILE")]
     pub output_schema: Option<PathBuf>,
 
+    /// Read the initial prompt from FILE instead of the PROMPT argument.
+    #[arg(
+        long = "prompt-file",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with = "prompt"
+    )]
+    pub prompt_file: Option<PathBuf>,
+
+    /// Substitute `{{KEY}}` in the prompt with VALUE. Repeatable. Any `{{KEY}}`
+    /// left without a value is an error.
+    #[arg(
+        long = "var",
+        value_name = "KEY=VALUE",
+        value_parser = crate::prompt_template::parse_var
+    )]
+    pub vars: Vec<(String, String)>,
+
+    /// After the run, draft a commit message for the files it changed and
+    /// commit them. The commit is reported with the rest of the output.
+    #[arg(long = "commit", default_value_t = false)]
+    pub commit: bool,
+
+    /// Add a `Signed-off-by` trailer to the `--commit` commit.
+    #[arg(long = "signoff", default_value_t = false, requires = "commit")]
+    pub sign_off: bool,
+
+    /// Amend HEAD instead of creating a new commit with `--commit`.
+    #[arg(long = "amend", default_value_t = false, requires = "commit")]
+    pub amend: bool,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -95,6 +219,39 @@ pub struct Cli {
     #[arg(long = "output-last-message", short = 'o', value_name = "FILE")]
     pub last_message_file: Option<PathBuf>,
 
+    /// Run the prompt once per target listed in FILE. Each non-empty line is a
+    /// directory or a git URL that is cloned into a temporary workspace.
+    #[arg(
+        long = "batch",
+        value_name = "FILE",
+        conflicts_with_all = ["cwd", "last_message_file"]
+    )]
+    pub batch: Option<PathBuf>,
+
+    /// Maximum number of batch targets to run at the same time.
+    #[arg(
+        long = "batch-jobs",
+        value_name = "N",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u16).range(1..),
+        requires = "batch"
+    )]
+    pub batch_jobs: u16,
+
+    /// Abort a batch target that runs longer than this many seconds.
+    #[arg(long = "batch-timeout", value_name = "SECS", requires = "batch")]
+    pub batch_timeout: Option<u64>,
+
+    /// Run each batch target that is inside a git repository in a new
+    /// detached worktree of that repository instead of in the directory
+    /// itself. Worktrees are kept so their changes can be reviewed.
+    #[arg(long = "batch-worktree", default_value_t = false, requires = "batch")]
+    pub batch_worktree: bool,
+
+    /// Also write the batch summary as JSON to FILE.
+    #[arg(long = "batch-summary", value_name = "FILE", requires = "batch")]
+    pub batch_summary: Option<PathBuf>,
+
     /// Initial instructions for the agent. If not provided as an argument (or
     /// if `-` is used), instructions are read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
@@ -108,6 +265,9 @@ pub enum Command {
 
     /// Run a code review against the current repository.
     Review(ReviewArgs),
+
+    /// Answer a question in a single turn without tools and print only the answer.
+    Ask(AskArgs),
 }
 
 #[derive(Args, Debug)]
@@ -236,6 +396,18 @@ pub struct ReviewArgs {
     pub prompt: Option<String>,
 }
 
+#[derive(Parser, Debug)]
+pub struct AskArgs {
+    /// Do not attach the files the question refers to as `@path`.
+    #[arg(long = "no-files", default_value_t = false)]
+    pub no_files: bool,
+
+    /// Question to answer. If not provided as an argument (or if `-` is used),
+    /// it is read from stdin.
+    #[arg(value_name = "QUESTION", value_hint = clap::ValueHint::Other)]
+    pub question: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
 pub enum Color {
@@ -245,6 +417,68 @@ pub enum Color {
     Auto,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReasoningEffortCliArg {
+    None,
+    Minimal,
+    Low,
+    Medium,
+    High,
+    Xhigh,
+}
+
+impl From<ReasoningEffortCliArg> for ReasoningEffort {
+    fn from(value: ReasoningEffortCliArg) -> Self {
+        match value {
+            ReasoningEffortCliArg::None => ReasoningEffort::None,
+            ReasoningEffortCliArg::Minimal => ReasoningEffort::Minimal,
+            ReasoningEffortCliArg::Low => ReasoningEffort::Low,
+            ReasoningEffortCliArg::Medium => ReasoningEffort::Medium,
+            ReasoningEffortCliArg::High => ReasoningEffort::High,
+            ReasoningEffortCliArg::Xhigh => ReasoningEffort::XHigh,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReasoningSummaryCliArg {
+    Auto,
+    Concise,
+    Detailed,
+    None,
+}
+
+impl From<ReasoningSummaryCliArg> for ReasoningSummary {
+    fn from(value: ReasoningSummaryCliArg) -> Self {
+        match value {
+            ReasoningSummaryCliArg::Auto => ReasoningSummary::Auto,
+            ReasoningSummaryCliArg::Concise => ReasoningSummary::Concise,
+            ReasoningSummaryCliArg::Detailed => ReasoningSummary::Detailed,
+            ReasoningSummaryCliArg::None => ReasoningSummary::None,
+        }
+    }
+}
+
+fn parse_temperature(raw: &str) -> Result<f64, String> {
+    let value: f64 = raw.parse().map_err(|err| format!("{err}"))?;
+    if (0.0..=2.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err("must be between 0 and 2".to_string())
+    }
+}
+
+fn parse_top_p(raw: &str) -> Result<f64, String> {
+    let value: f64 = raw.parse().map_err(|err| format!("{err}"))?;
+    if value > 0.0 && value <= 1.0 {
+        Ok(value)
+    } else {
+        Err("must be greater than 0 and at most 1".to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,4 +511,107 @@ mod tests {
         });
         assert_eq!(effective_prompt.as_deref(), Some(PROMPT));
     }
+
+    #[test]
+    fn skip_git_repo_check_is_an_alias_for_trust_cwd() {
+        let trust_cwd = Cli::parse_from(["codex-exec", "--trust-cwd", "hi"]);
+        let legacy = Cli::parse_from(["codex-exec", "--skip-git-repo-check", "hi"]);
+        let neither = Cli::parse_from(["codex-exec", "hi"]);
+
+        assert_eq!(
+            (trust_cwd.trust_cwd, legacy.trust_cwd, neither.trust_cwd),
+            (true, true, false)
+        );
+    }
+
+    #[test]
+    fn reasoning_flags_parse_into_protocol_values() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--reasoning-effort",
+            "high",
+            "--reasoning-summary",
+            "none",
+            "hi",
+        ]);
+
+        assert_eq!(
+            (
+                cli.reasoning_effort.map(ReasoningEffort::from),
+                cli.reasoning_summary.map(ReasoningSummary::from),
+            ),
+            (Some(ReasoningEffort::High), Some(ReasoningSummary::None))
+        );
+    }
+
+    #[test]
+    fn max_output_tokens_flag_rejects_zero() {
+        let cli = Cli::parse_from(["codex-exec", "--max-output-tokens", "4096", "hi"]);
+        assert_eq!(cli.max_output_tokens, Some(4096));
+
+        assert!(Cli::try_parse_from(["codex-exec", "--max-output-tokens", "0", "hi"]).is_err());
+    }
+
+    #[test]
+    fn sampling_flags_are_range_checked() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--seed",
+            "-3",
+            "--temperature",
+            "0.2",
+            "--top-p",
+            "1",
+            "hi",
+        ]);
+        assert_eq!(
+            (cli.seed, cli.temperature, cli.top_p),
+            (Some(-3), Some(0.2), Some(1.0))
+        );
+
+        assert!(Cli::try_parse_from(["codex-exec", "--temperature", "2.5", "hi"]).is_err());
+        assert!(Cli::try_parse_from(["codex-exec", "--top-p", "0", "hi"]).is_err());
+    }
+
+    #[test]
+    fn batch_flags_require_batch_and_reject_cd() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--batch",
+            "targets.txt",
+            "--batch-jobs",
+            "4",
+            "hi",
+        ]);
+        assert_eq!(
+            (cli.batch, cli.batch_jobs),
+            (Some(PathBuf::from("targets.txt")), 4)
+        );
+
+        assert!(Cli::try_parse_from(["codex-exec", "--batch-jobs", "2", "hi"]).is_err());
+        assert!(
+            Cli::try_parse_from(["codex-exec", "--batch", "t.txt", "--batch-jobs", "0", "hi"])
+                .is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["codex-exec", "--batch", "t.txt", "-C", "/tmp", "hi"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["codex-exec", "--batch-worktree", "hi"]).is_err());
+    }
+
+    #[test]
+    fn run_limits_reject_zero() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--max-tokens",
+            "50000",
+            "--max-turns",
+            "8",
+            "hi",
+        ]);
+        assert_eq!((cli.max_tokens, cli.max_turns), (Some(50_000), Some(8)));
+
+        assert!(Cli::try_parse_from(["codex-exec", "--max-tokens", "0", "hi"]).is_err());
+        assert!(Cli::try_parse_from(["codex-exec", "--max-turns", "0", "hi"]).is_err());
+    }
 }
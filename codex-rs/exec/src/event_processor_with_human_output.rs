@@ -4,7 +4,10 @@ use codex_core::config::Config;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::AgentStatus;
+use codex_core::protocol::ApprovalKind;
+use codex_core::protocol::ApprovalResolvedEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CancelledWork;
 use codex_core::protocol::CollabAgentInteractionBeginEvent;
 use codex_core::protocol::CollabAgentInteractionEndEvent;
 use codex_core::protocol::CollabAgentSpawnBeginEvent;
@@ -13,26 +16,35 @@ use codex_core::protocol::CollabCloseBeginEvent;
 use codex_core::protocol::CollabCloseEndEvent;
 use codex_core::protocol::CollabWaitingBeginEvent;
 use codex_core::protocol::CollabWaitingEndEvent;
+use codex_core::protocol::CommitCompletedEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
+use codex_core::protocol::ExecSpawnLimits;
 use codex_core::protocol::FileChange;
+use codex_core::protocol::HookBeginEvent;
+use codex_core::protocol::InjectionSuspectedEvent;
 use codex_core::protocol::ItemCompletedEvent;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelListSource;
+use codex_core::protocol::OutputTruncatedEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::PatchFileStatus;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnDiffEvent;
+use codex_core::protocol::TurnRefusedEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::util::truncate_graphemes;
 use codex_core::web_search::web_search_detail;
 use codex_protocol::items::TurnItem;
 use codex_protocol::num_format::format_with_separators;
@@ -161,6 +173,13 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             "session id",
             session_configured_event.session_id.to_string(),
         ));
+        if let Some(title) = &session_configured_event.thread_name {
+            entries.push(("title", title.clone()));
+        }
+        let tags = codex_core::util::normalize_session_tags(&config.session_tags);
+        if !tags.is_empty() {
+            entries.push(("tags", tags.join(", ")));
+        }
 
         for (key, value) in entries {
             eprintln!("{} {}", format!("{key}:").style(self.bold), value);
@@ -168,6 +187,14 @@ impl EventProcessor for EventProcessorWithHumanOutput {
 
         eprintln!("--------");
 
+        if let Some(unknown_model) = &session_configured_event.unknown_model {
+            let prefix = match unknown_model.source {
+                ModelListSource::Provider => "ERROR:".style(self.red),
+                ModelListSource::Builtin => "warning:".style(self.yellow).style(self.bold),
+            };
+            ts_msg!(self, "{prefix} {}", unknown_model.message());
+        }
+
         // Echo the prompt that will be sent to the agent so it is visible in the
         // transcript/logs before any events come in. Note the prompt may have been
         // read from stdin, so it may not be visible in the terminal otherwise.
@@ -177,9 +204,28 @@ impl EventProcessor for EventProcessorWithHumanOutput {
     fn process_event(&mut self, event: Event) -> CodexStatus {
         let Event { id: _, msg } = event;
         match msg {
-            EventMsg::Error(ErrorEvent { message, .. }) => {
+            EventMsg::Error(ErrorEvent {
+                message,
+                request_details,
+                hint,
+                ..
+            }) => {
                 let prefix = "ERROR:".style(self.red);
                 ts_msg!(self, "{prefix} {message}");
+                if let Some(hint) = hint {
+                    ts_msg!(self, "  {}", format!("hint: {hint}").style(self.dimmed));
+                }
+                if let Some(request_id) = request_details.and_then(|details| details.request_id) {
+                    ts_msg!(
+                        self,
+                        "  {}",
+                        format!("request id: {request_id}").style(self.dimmed)
+                    );
+                }
+            }
+            EventMsg::TurnRefused(TurnRefusedEvent { reason }) => {
+                let prefix = "refused:".style(self.red).style(self.bold);
+                ts_msg!(self, "{prefix} {reason}");
             }
             EventMsg::Warning(WarningEvent { message }) => {
                 ts_msg!(
@@ -188,6 +234,35 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "warning:".style(self.yellow).style(self.bold)
                 );
             }
+            EventMsg::InjectionSuspected(InjectionSuspectedEvent {
+                tool_name,
+                patterns,
+                approval_downgraded,
+                ..
+            }) => {
+                let patterns = patterns.join(", ");
+                let suffix = if approval_downgraded {
+                    "; approvals now required for the rest of this turn"
+                } else {
+                    ""
+                };
+                ts_msg!(
+                    self,
+                    "{} `{tool_name}` output looks like a prompt injection ({patterns}){suffix}",
+                    "warning:".style(self.yellow).style(self.bold)
+                );
+            }
+            EventMsg::OutputTruncated(OutputTruncatedEvent { max_output_tokens }) => {
+                let limit = match max_output_tokens {
+                    Some(limit) => format!("the {limit}-token output limit"),
+                    None => "the model's output token limit".to_string(),
+                };
+                ts_msg!(
+                    self,
+                    "{} response truncated at {limit}",
+                    "warning:".style(self.yellow).style(self.bold)
+                );
+            }
             EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }) => {
                 ts_msg!(
                     self,
@@ -234,6 +309,16 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 };
                 ts_msg!(self, "{} {}", "mcp startup:".style(self.cyan), joined);
             }
+            EventMsg::SessionPrewarmed(prewarmed) => {
+                for degraded in prewarmed.degraded {
+                    ts_msg!(
+                        self,
+                        "{} {}",
+                        "degraded:".style(self.yellow),
+                        degraded.detail
+                    );
+                }
+            }
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
@@ -264,7 +349,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "auto-cancelling (not supported in exec mode)".style(self.dimmed)
                 );
             }
-            EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }) => {
+            EventMsg::TurnComplete(TurnCompleteEvent {
+                last_agent_message, ..
+            }) => {
                 let last_message = last_agent_message
                     .as_deref()
                     .or(self.last_proposed_plan.as_deref());
@@ -318,10 +405,131 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     cwd.to_string_lossy(),
                 );
             }
+            EventMsg::HookBegin(HookBeginEvent { hook, command, .. }) => {
+                eprint!(
+                    "{}\n{}",
+                    format!("{hook} hook")
+                        .style(self.italic)
+                        .style(self.magenta),
+                    escape_command(&command).style(self.bold),
+                );
+            }
+            EventMsg::HookEnd(end) => {
+                let duration = format!(" in {}", format_duration(end.duration));
+                let title = match end.exit_code {
+                    _ if end.success() => format!(" succeeded{duration}:"),
+                    _ if end.timed_out => format!(" timed out{duration}:"),
+                    Some(code) => format!(" exited {code}{duration}:"),
+                    None => " could not be started:".to_string(),
+                };
+                let title = if end.aborts_turn {
+                    format!("{title} ending the turn")
+                } else {
+                    title
+                };
+                if end.success() {
+                    ts_msg!(self, "{}", title.style(self.green));
+                } else {
+                    ts_msg!(self, "{}", title.style(self.red));
+                }
+                let truncated_output = end
+                    .output
+                    .lines()
+                    .take(MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                eprintln!("{}", truncated_output.style(self.dimmed));
+            }
+            EventMsg::ApprovalResolved(ApprovalResolvedEvent {
+                kind,
+                command,
+                paths,
+                decision,
+                ..
+            }) => {
+                let subject = match kind {
+                    ApprovalKind::Exec => escape_command(&command),
+                    ApprovalKind::Patch => paths
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                };
+                ts_msg!(
+                    self,
+                    "{} {}: {subject}",
+                    "approval".style(self.magenta),
+                    decision.to_opaque_string().style(self.bold)
+                );
+            }
+            EventMsg::CommitCompleted(CommitCompletedEvent {
+                success,
+                commit,
+                message,
+                error,
+                ..
+            }) => {
+                if success {
+                    let short = commit.as_deref().map_or("", |sha| &sha[..sha.len().min(7)]);
+                    let subject = message
+                        .as_deref()
+                        .and_then(|message| message.lines().next())
+                        .unwrap_or_default();
+                    ts_msg!(
+                        self,
+                        "{} {} {subject}",
+                        "committed".style(self.green).style(self.bold),
+                        short.style(self.dimmed)
+                    );
+                } else {
+                    ts_msg!(
+                        self,
+                        "{} {}",
+                        "commit failed:".style(self.red).style(self.bold),
+                        error.unwrap_or_default()
+                    );
+                }
+            }
+            EventMsg::ExecCommandSpawn(spawn) => {
+                let mut lines = vec![format!(
+                    "  {} {} ({:?}{})",
+                    "spawn:".style(self.dimmed),
+                    escape_command(&spawn.spawn_command),
+                    spawn.sandbox,
+                    if spawn.dry_run { ", dry run" } else { "" },
+                )];
+                let ExecSpawnLimits {
+                    cpu_time_seconds,
+                    memory_bytes,
+                    max_output_bytes,
+                } = spawn.limits;
+                let limits: Vec<String> = [
+                    cpu_time_seconds.map(|secs| format!("cpu {secs}s")),
+                    memory_bytes.map(|bytes| format!("memory {bytes} bytes")),
+                    max_output_bytes.map(|bytes| format!("output {bytes} bytes")),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                if !limits.is_empty() {
+                    lines.push(format!(
+                        "  {} {}",
+                        "limits:".style(self.dimmed),
+                        limits.join(", ")
+                    ));
+                }
+                for (name, value) in &spawn.env {
+                    lines.push(format!("  {} {name}={value}", "env:".style(self.dimmed)));
+                }
+                // `ExecCommandBegin` leaves its line open for the end event.
+                eprintln!("\n{}", lines.join("\n"));
+            }
             EventMsg::ExecCommandEnd(ExecCommandEndEvent {
                 aggregated_output,
                 duration,
                 exit_code,
+                denied_by_sandbox,
+                limit_exceeded,
                 ..
             }) => {
                 let duration = format!(" in {}", format_duration(duration));
@@ -331,11 +539,22 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     .take(MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL)
                     .collect::<Vec<_>>()
                     .join("\n");
-                match exit_code {
-                    0 => {
+                match (exit_code, limit_exceeded) {
+                    (0, _) => {
                         let title = format!(" succeeded{duration}:");
                         ts_msg!(self, "{}", title.style(self.green));
                     }
+                    (_, Some(kind)) => {
+                        let title = format!(
+                            " killed: {} exceeded (exit {exit_code}){duration}:",
+                            kind.description()
+                        );
+                        ts_msg!(self, "{}", title.style(self.red));
+                    }
+                    _ if denied_by_sandbox => {
+                        let title = format!(" blocked by sandbox (exit {exit_code}){duration}:");
+                        ts_msg!(self, "{}", title.style(self.red));
+                    }
                     _ => {
                         let title = format!(" exited {exit_code}{duration}:");
                         ts_msg!(self, "{}", title.style(self.red));
@@ -361,11 +580,18 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     result,
                     invocation,
                     duration,
+                    argument_errors,
                 } = tool_call_end_event;
 
                 let duration = format!(" in {}", format_duration(duration));
 
-                let status_str = if is_success { "success" } else { "failed" };
+                let status_str = if !argument_errors.is_empty() {
+                    "rejected: invalid arguments"
+                } else if is_success {
+                    "success"
+                } else {
+                    "failed"
+                };
                 let title_style = if is_success { self.green } else { self.red };
                 let title = format!(
                     "{} {status_str}{duration}:",
@@ -374,6 +600,18 @@ impl EventProcessor for EventProcessorWithHumanOutput {
 
                 ts_msg!(self, "{}", title.style(title_style));
 
+                for error in &argument_errors {
+                    let path = if error.path.is_empty() {
+                        "/"
+                    } else {
+                        error.path.as_str()
+                    };
+                    eprintln!(
+                        "{} {}",
+                        path.style(self.red),
+                        error.message.style(self.dimmed)
+                    );
+                }
                 if let Ok(res) = result {
                     let val: serde_json::Value = res.into();
                     let pretty =
@@ -486,6 +724,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 stdout,
                 stderr,
                 success,
+                per_file,
                 ..
             }) => {
                 let patch_begin = self.call_id_to_patch.remove(&call_id);
@@ -512,6 +751,22 @@ impl EventProcessor for EventProcessorWithHumanOutput {
 
                 let title = format!("{label} exited {exit_code}{duration}:");
                 ts_msg!(self, "{}", title.style(title_style));
+                if !success {
+                    // Say which files a failed patch did and did not reach.
+                    for result in &per_file {
+                        let status = match result.status {
+                            PatchFileStatus::Applied => "applied",
+                            PatchFileStatus::Skipped => "skipped",
+                            PatchFileStatus::Aborted => "aborted",
+                            // The error output below covers the failed file.
+                            PatchFileStatus::Failed => continue,
+                        };
+                        eprintln!(
+                            "{}",
+                            format!("{status} {}", result.path.display()).style(self.dimmed)
+                        );
+                    }
+                }
                 for line in output.lines() {
                     eprintln!("{}", line.style(self.dimmed));
                 }
@@ -592,7 +847,58 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     view.path.display()
                 );
             }
+            EventMsg::ImageDownscaled(event) => {
+                ts_msg!(
+                    self,
+                    "{} {} ({})",
+                    "downscaled image".style(self.dimmed),
+                    event.path.display(),
+                    event.size_summary()
+                );
+            }
+            EventMsg::FileAttached(event) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    event.path.display(),
+                    event.summary().style(self.dimmed)
+                );
+            }
+            EventMsg::WorkspaceSummary(event) => {
+                ts_msg!(self, "{}", event.summary().style(self.dimmed));
+            }
+            EventMsg::StalePatchContext(event) => {
+                ts_msg!(self, "{}", event.summary().style(self.dimmed));
+            }
+            EventMsg::StalePatchRetry(event) => {
+                ts_msg!(self, "{}", event.summary().style(self.dimmed));
+            }
+            EventMsg::ExternalFilesChanged(event) => {
+                ts_msg!(self, "{}", event.summary().style(self.dimmed));
+            }
+            EventMsg::PromptQueuedOffline(_) => {
+                ts_msg!(
+                    self,
+                    "{}",
+                    "offline: prompt queued until the model provider is reachable"
+                        .style(self.dimmed)
+                );
+            }
+            EventMsg::ConnectivityRestored(_) => {
+                ts_msg!(
+                    self,
+                    "{}",
+                    "back online: submitting queued prompt".style(self.dimmed)
+                );
+            }
             EventMsg::TurnAborted(abort_reason) => match abort_reason.reason {
+                TurnAbortReason::Interrupted if !abort_reason.cancelled.is_empty() => {
+                    ts_msg!(
+                        self,
+                        "task interrupted; cancelled {}",
+                        format_cancelled_work(&abort_reason.cancelled)
+                    );
+                }
                 TurnAbortReason::Interrupted => {
                     ts_msg!(self, "task interrupted");
                 }
@@ -769,11 +1075,21 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::TerminalInteraction(_)
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
+            | EventMsg::HistoryCleared(_)
             | EventMsg::McpListToolsResponse(_)
+            | EventMsg::ListToolsResponse(_)
+            | EventMsg::TokenBreakdown(_)
+            | EventMsg::ApprovalGrantsRestored(_)
+            | EventMsg::SessionApprovalsRevoked(_)
+            | EventMsg::TurnFiles(_)
+            | EventMsg::ToolArgumentsRepaired(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::GhostSnapshotsResponse(_)
+            | EventMsg::GhostSnapshotFileResponse(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::RawResponseItem(_)
             | EventMsg::UserMessage(_)
+            | EventMsg::SteerDelivered(_)
             | EventMsg::EnteredReviewMode(_)
             | EventMsg::ExitedReviewMode(_)
             | EventMsg::AgentMessageDelta(_)
@@ -788,6 +1104,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::SkillsUpdateAvailable
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_)
+            | EventMsg::CommitDraft(_)
+            | EventMsg::CommandExplanation(_)
             | EventMsg::ThreadRolledBack(_)
             | EventMsg::RequestUserInput(_)
             | EventMsg::DynamicToolCallRequest(_) => {}
@@ -823,6 +1141,17 @@ fn escape_command(command: &[String]) -> String {
     try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
 }
 
+fn format_cancelled_work(cancelled: &[CancelledWork]) -> String {
+    cancelled
+        .iter()
+        .map(|work| match work {
+            CancelledWork::ModelResponse => "the model response".to_string(),
+            CancelledWork::ToolCall { tool_name, .. } => format!("`{tool_name}`"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn format_file_change(change: &FileChange) -> &'static str {
     match change {
         FileChange::Add { .. } => "A",
@@ -899,12 +1228,11 @@ fn format_receiver_list(ids: &[codex_protocol::ThreadId]) -> String {
         .join(", ")
 }
 
-fn truncate_preview(text: &str, max_chars: usize) -> String {
-    if text.chars().count() <= max_chars {
+fn truncate_preview(text: &str, max_graphemes: usize) -> String {
+    let preview = truncate_graphemes(text, max_graphemes);
+    if preview.len() == text.len() {
         return text.to_string();
     }
-
-    let preview = text.chars().take(max_chars).collect::<String>();
     format!("{preview}…")
 }
 
@@ -6,6 +6,9 @@ use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
 use crate::exec_events::AgentMessageItem;
+use crate::exec_events::ApprovalRequestKind;
+use crate::exec_events::ApprovalResolutionItem;
+use crate::exec_events::ApprovalResolvedBy;
 use crate::exec_events::CollabAgentState;
 use crate::exec_events::CollabAgentStatus;
 use crate::exec_events::CollabTool;
@@ -13,9 +16,14 @@ use crate::exec_events::CollabToolCallItem;
 use crate::exec_events::CollabToolCallStatus;
 use crate::exec_events::CommandExecutionItem;
 use crate::exec_events::CommandExecutionStatus;
+use crate::exec_events::CommitItem;
+use crate::exec_events::CommitStatus;
 use crate::exec_events::ErrorItem;
 use crate::exec_events::FileChangeItem;
+use crate::exec_events::FileChangeStatus;
+use crate::exec_events::FileCitation;
 use crate::exec_events::FileUpdateChange;
+use crate::exec_events::HookExecutionItem;
 use crate::exec_events::ItemCompletedEvent;
 use crate::exec_events::ItemStartedEvent;
 use crate::exec_events::ItemUpdatedEvent;
@@ -38,6 +46,7 @@ use crate::exec_events::TurnFailedEvent;
 use crate::exec_events::TurnStartedEvent;
 use crate::exec_events::Usage;
 use crate::exec_events::WebSearchItem;
+use codex_common::citations::find_citations;
 use codex_core::config::Config;
 use codex_core::protocol;
 use codex_core::protocol::AgentStatus as CoreAgentStatus;
@@ -69,7 +78,11 @@ pub struct EventProcessorWithJsonOutput {
     running_mcp_tool_calls: HashMap<String, RunningMcpToolCall>,
     running_collab_tool_calls: HashMap<String, RunningCollabToolCall>,
     running_web_search_calls: HashMap<String, String>,
+    // Item ids of running hooks, keyed by call_id.
+    running_hooks: HashMap<String, String>,
     last_critical_error: Option<ThreadErrorEvent>,
+    // Session working directory, used to resolve file citations.
+    cwd: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -112,7 +125,9 @@ impl EventProcessorWithJsonOutput {
             running_mcp_tool_calls: HashMap::new(),
             running_collab_tool_calls: HashMap::new(),
             running_web_search_calls: HashMap::new(),
+            running_hooks: HashMap::new(),
             last_critical_error: None,
+            cwd: None,
         }
     }
 
@@ -160,14 +175,33 @@ impl EventProcessorWithJsonOutput {
                 Vec::new()
             }
             protocol::EventMsg::TurnStarted(ev) => self.handle_task_started(ev),
-            protocol::EventMsg::TurnComplete(_) => self.handle_task_complete(),
+            protocol::EventMsg::TurnComplete(ev) => {
+                self.handle_task_complete(ev.system_fingerprint.clone())
+            }
             protocol::EventMsg::Error(ev) => {
+                let details = ev.request_details.clone().unwrap_or_default();
                 let error = ThreadErrorEvent {
                     message: ev.message.clone(),
+                    request_id: details.request_id,
+                    error_type: details.error_type,
+                    error_code: details.error_code,
+                    attempts: details.attempts,
+                    code: ev.code.clone(),
+                    hint: ev.hint.clone(),
+                    details: ev.details.clone(),
                 };
                 self.last_critical_error = Some(error.clone());
                 vec![ThreadEvent::Error(error)]
             }
+            protocol::EventMsg::TurnRefused(ev) => {
+                // Reported as `turn.failed` once the turn ends.
+                self.last_critical_error = Some(ThreadErrorEvent {
+                    message: ev.reason.clone(),
+                    code: Some("refused".to_string()),
+                    ..Default::default()
+                });
+                Vec::new()
+            }
             protocol::EventMsg::Warning(ev) => {
                 let item = ThreadItem {
                     id: self.get_next_item_id(),
@@ -184,9 +218,16 @@ impl EventProcessorWithJsonOutput {
                     }
                     _ => ev.message.clone(),
                 };
-                vec![ThreadEvent::Error(ThreadErrorEvent { message })]
+                vec![ThreadEvent::Error(ThreadErrorEvent {
+                    message,
+                    ..Default::default()
+                })]
             }
             protocol::EventMsg::PlanUpdate(ev) => self.handle_plan_update(ev),
+            protocol::EventMsg::ApprovalResolved(ev) => self.handle_approval_resolved(ev),
+            protocol::EventMsg::CommitCompleted(ev) => self.handle_commit_completed(ev),
+            protocol::EventMsg::HookBegin(ev) => self.handle_hook_begin(ev),
+            protocol::EventMsg::HookEnd(ev) => self.handle_hook_end(ev),
             _ => Vec::new(),
         }
     }
@@ -200,11 +241,13 @@ impl EventProcessorWithJsonOutput {
     }
 
     fn handle_session_configured(
-        &self,
+        &mut self,
         payload: &protocol::SessionConfiguredEvent,
     ) -> Vec<ThreadEvent> {
+        self.cwd = Some(payload.cwd.clone());
         vec![ThreadEvent::ThreadStarted(ThreadStartedEvent {
             thread_id: payload.session_id.to_string(),
+            sampling: payload.sampling,
         })]
     }
 
@@ -263,12 +306,29 @@ impl EventProcessorWithJsonOutput {
 
             details: ThreadItemDetails::AgentMessage(AgentMessageItem {
                 text: payload.message.clone(),
+                citations: self.citations(&payload.message),
             }),
         };
 
         vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
     }
 
+    fn citations(&self, text: &str) -> Vec<FileCitation> {
+        let Some(cwd) = &self.cwd else {
+            return Vec::new();
+        };
+        find_citations(text, cwd)
+            .into_iter()
+            .map(|citation| FileCitation {
+                path: citation.path.to_string_lossy().into_owned(),
+                line: citation.line,
+                end_line: citation.end_line,
+                start: citation.range.start,
+                end: citation.range.end,
+            })
+            .collect()
+    }
+
     fn handle_reasoning_event(&self, ev: &protocol::AgentReasoningEvent) -> Vec<ThreadEvent> {
         let item = ThreadItem {
             id: self.get_next_item_id(),
@@ -280,6 +340,106 @@ impl EventProcessorWithJsonOutput {
 
         vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
     }
+    fn handle_approval_resolved(&self, ev: &protocol::ApprovalResolvedEvent) -> Vec<ThreadEvent> {
+        let (kind, command) = match ev.kind {
+            protocol::ApprovalKind::Exec => (
+                ApprovalRequestKind::Exec,
+                Some(
+                    shlex::try_join(ev.command.iter().map(String::as_str))
+                        .unwrap_or_else(|_| ev.command.join(" ")),
+                ),
+            ),
+            protocol::ApprovalKind::Patch => (ApprovalRequestKind::Patch, None),
+        };
+        let resolved_by = match ev.resolved_by {
+            protocol::ApprovalResolver::User => ApprovalResolvedBy::User,
+            protocol::ApprovalResolver::Timeout => ApprovalResolvedBy::Timeout,
+            protocol::ApprovalResolver::TurnEnded => ApprovalResolvedBy::TurnEnded,
+        };
+        let item = ThreadItem {
+            id: self.get_next_item_id(),
+            details: ThreadItemDetails::ApprovalResolution(ApprovalResolutionItem {
+                kind,
+                command,
+                paths: ev
+                    .paths
+                    .iter()
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect(),
+                reason: ev.reason.clone(),
+                decision: ev.decision.to_opaque_string().to_string(),
+                resolved_by,
+                account: ev.account.clone(),
+                resolved_at: ev.resolved_at.clone(),
+            }),
+        };
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
+    }
+
+    fn handle_commit_completed(&self, ev: &protocol::CommitCompletedEvent) -> Vec<ThreadEvent> {
+        let item = ThreadItem {
+            id: self.get_next_item_id(),
+            details: ThreadItemDetails::Commit(CommitItem {
+                commit: ev.commit.clone(),
+                message: ev.message.clone(),
+                files: ev
+                    .files
+                    .iter()
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect(),
+                status: if ev.success {
+                    CommitStatus::Completed
+                } else {
+                    CommitStatus::Failed
+                },
+                error: ev.error.clone(),
+            }),
+        };
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
+    }
+
+    fn handle_hook_begin(&mut self, ev: &protocol::HookBeginEvent) -> Vec<ThreadEvent> {
+        let item_id = self.get_next_item_id();
+        self.running_hooks
+            .insert(ev.call_id.clone(), item_id.clone());
+        let item = ThreadItem {
+            id: item_id,
+            details: ThreadItemDetails::HookExecution(HookExecutionItem {
+                hook: ev.hook.to_string(),
+                command: join_command(&ev.command),
+                aggregated_output: String::new(),
+                exit_code: None,
+                status: CommandExecutionStatus::InProgress,
+                aborted_turn: false,
+            }),
+        };
+        vec![ThreadEvent::ItemStarted(ItemStartedEvent { item })]
+    }
+
+    fn handle_hook_end(&mut self, ev: &protocol::HookEndEvent) -> Vec<ThreadEvent> {
+        let item_id = self
+            .running_hooks
+            .remove(&ev.call_id)
+            .unwrap_or_else(|| self.get_next_item_id());
+        let status = if ev.success() {
+            CommandExecutionStatus::Completed
+        } else {
+            CommandExecutionStatus::Failed
+        };
+        let item = ThreadItem {
+            id: item_id,
+            details: ThreadItemDetails::HookExecution(HookExecutionItem {
+                hook: ev.hook.to_string(),
+                command: join_command(&ev.command),
+                aggregated_output: ev.output.clone(),
+                exit_code: ev.exit_code,
+                status,
+                aborted_turn: ev.aborts_turn,
+            }),
+        };
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
+    }
+
     fn handle_exec_command_begin(
         &mut self,
         ev: &protocol::ExecCommandBeginEvent,
@@ -651,20 +811,34 @@ impl EventProcessorWithJsonOutput {
             } else {
                 PatchApplyStatus::Failed
             };
+            let mut changes: Vec<FileUpdateChange> = running_patch_apply
+                .changes
+                .iter()
+                .map(|(path, change)| {
+                    let result = ev.per_file.iter().find(|result| &result.path == path);
+                    let file_status = match result.map(|result| result.status) {
+                        Some(protocol::PatchFileStatus::Applied) => FileChangeStatus::Applied,
+                        Some(protocol::PatchFileStatus::Failed) => FileChangeStatus::Failed,
+                        Some(protocol::PatchFileStatus::Skipped) => FileChangeStatus::Skipped,
+                        Some(protocol::PatchFileStatus::Aborted) => FileChangeStatus::Aborted,
+                        // Servers that predate per-file results only report
+                        // the patch as a whole.
+                        None if ev.success => FileChangeStatus::Applied,
+                        None => FileChangeStatus::Failed,
+                    };
+                    FileUpdateChange {
+                        path: path.to_str().unwrap_or("").to_string(),
+                        kind: self.map_change_kind(change),
+                        status: file_status,
+                        error: result.and_then(|result| result.error.clone()),
+                    }
+                })
+                .collect();
+            changes.sort_by(|a, b| a.path.cmp(&b.path));
             let item = ThreadItem {
                 id: self.get_next_item_id(),
 
-                details: ThreadItemDetails::FileChange(FileChangeItem {
-                    changes: running_patch_apply
-                        .changes
-                        .iter()
-                        .map(|(path, change)| FileUpdateChange {
-                            path: path.to_str().unwrap_or("").to_string(),
-                            kind: self.map_change_kind(change),
-                        })
-                        .collect(),
-                    status,
-                }),
+                details: ThreadItemDetails::FileChange(FileChangeItem { changes, status }),
             };
 
             return vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })];
@@ -749,7 +923,7 @@ impl EventProcessorWithJsonOutput {
         vec![ThreadEvent::TurnStarted(TurnStartedEvent {})]
     }
 
-    fn handle_task_complete(&mut self) -> Vec<ThreadEvent> {
+    fn handle_task_complete(&mut self, system_fingerprint: Option<String>) -> Vec<ThreadEvent> {
         let usage = if let Some(u) = &self.last_total_token_usage {
             Usage {
                 input_tokens: u.input_tokens,
@@ -790,13 +964,20 @@ impl EventProcessorWithJsonOutput {
         if let Some(error) = self.last_critical_error.take() {
             items.push(ThreadEvent::TurnFailed(TurnFailedEvent { error }));
         } else {
-            items.push(ThreadEvent::TurnCompleted(TurnCompletedEvent { usage }));
+            items.push(ThreadEvent::TurnCompleted(TurnCompletedEvent {
+                usage,
+                system_fingerprint,
+            }));
         }
 
         items
     }
 }
 
+fn join_command(command: &[String]) -> String {
+    shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
 fn is_collab_failure(status: &CoreAgentStatus) -> bool {
     matches!(
         status,
@@ -862,6 +1043,7 @@ impl EventProcessor for EventProcessorWithJsonOutput {
         match msg {
             protocol::EventMsg::TurnComplete(protocol::TurnCompleteEvent {
                 last_agent_message,
+                ..
             }) => {
                 if let Some(output_file) = self.last_message_path.as_deref() {
                     let last_message = last_agent_message
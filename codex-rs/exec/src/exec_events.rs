@@ -1,3 +1,4 @@
+use codex_protocol::config_types::SamplingParameters;
 use codex_protocol::models::WebSearchAction;
 use mcp_types::ContentBlock as McpContentBlock;
 use serde::Deserialize;
@@ -41,6 +42,9 @@ pub enum ThreadEvent {
 pub struct ThreadStartedEvent {
     /// The identified of the new thread. Can be used to resume the thread later.
     pub thread_id: String,
+    /// Sampling parameters (`seed`, `temperature`, `top_p`) configured for the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<SamplingParameters>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Default)]
@@ -50,6 +54,9 @@ pub struct TurnStartedEvent {}
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct TurnCompletedEvent {
     pub usage: Usage,
+    /// Backend fingerprint the provider reported for the turn, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -84,9 +91,32 @@ pub struct ItemUpdatedEvent {
 }
 
 /// Fatal error emitted by the stream.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
 pub struct ThreadErrorEvent {
     pub message: String,
+    /// Request id reported by the model provider, when the failure came from
+    /// a model request. Include it when reporting the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Provider error type (e.g. `invalid_request_error`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_type: Option<String>,
+    /// Provider error code, when one was returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Number of attempts made before giving up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u64>,
+    /// Codex's own classification of the error (e.g. `rate_limited`). Unlike
+    /// `error_code`, it is set for every error, not just provider ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Suggested next step for the user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// Full error text, when `message` is a shortened headline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
 }
 
 /// Canonical representation of a thread item and its domain-specific payload.
@@ -124,6 +154,14 @@ pub enum ThreadItemDetails {
     /// Tracks the agent's running to-do list. It starts when the plan is first
     /// issued, updates as steps change state, and completes when the turn ends.
     TodoList(TodoListItem),
+    /// Records how an approval request was resolved. Emitted only as a
+    /// completed event.
+    ApprovalResolution(ApprovalResolutionItem),
+    /// The result of `--commit`. Emitted only as a completed event.
+    Commit(CommitItem),
+    /// A command configured under `[hooks]`. The item starts when the hook is
+    /// spawned and completes when it exits, times out or fails to start.
+    HookExecution(HookExecutionItem),
     /// Describes a non-fatal error surfaced as an item.
     Error(ErrorItem),
 }
@@ -133,6 +171,23 @@ pub enum ThreadItemDetails {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct AgentMessageItem {
     pub text: String,
+    /// Files in the workspace that the message refers to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub citations: Vec<FileCitation>,
+}
+
+/// A reference to a file in an agent message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct FileCitation {
+    /// Absolute path of the referenced file.
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+    /// Byte offsets of the reference within `text`.
+    pub start: usize,
+    pub end: usize,
 }
 
 /// Agent's reasoning summary.
@@ -166,6 +221,24 @@ pub struct CommandExecutionItem {
 pub struct FileUpdateChange {
     pub path: String,
     pub kind: PatchChangeKind,
+    /// What happened to this file. A failed patch may still have applied
+    /// some of its files.
+    pub status: FileChangeStatus,
+    /// Why the file could not be applied, for `failed` files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The outcome for one file of a patch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChangeStatus {
+    Applied,
+    Failed,
+    /// The patch stopped at another file before reaching this one.
+    Skipped,
+    /// The turn was interrupted before the patch reported a result.
+    Aborted,
 }
 
 /// The status of a file change.
@@ -181,6 +254,7 @@ pub enum PatchApplyStatus {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct FileChangeItem {
     pub changes: Vec<FileUpdateChange>,
+    /// Whether the whole patch succeeded; see each change for its own status.
     pub status: PatchApplyStatus,
 }
 
@@ -286,6 +360,86 @@ pub struct WebSearchItem {
     pub action: WebSearchAction,
 }
 
+/// What an approval request asked to do.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRequestKind {
+    Exec,
+    Patch,
+}
+
+/// How an approval request was resolved.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalResolvedBy {
+    User,
+    Timeout,
+    TurnEnded,
+}
+
+/// The decision on a command or patch approval request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct ApprovalResolutionItem {
+    pub kind: ApprovalRequestKind,
+    /// The command, for `exec` requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// The files the patch touches, for `patch` requests.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// `approved`, `approved_with_amendment`, `approved_for_session`,
+    /// `denied` or `abort`.
+    pub decision: String,
+    pub resolved_by: ApprovalResolvedBy,
+    /// Email of the signed-in ChatGPT account, when there is one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// RFC 3339 time of the decision.
+    pub resolved_at: String,
+}
+
+/// A commit of the files the run changed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct CommitItem {
+    /// Id of the new commit, when one was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Files included in the commit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    pub status: CommitStatus,
+    /// Why no commit was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitStatus {
+    Completed,
+    Failed,
+}
+
+/// A command configured under `[hooks]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct HookExecutionItem {
+    /// Where the hook ran: `pre_turn`, `post_turn`, `pre_exec` or
+    /// `post_patch`.
+    pub hook: String,
+    pub command: String,
+    /// Combined stdout and stderr, or why the hook could not be started.
+    pub aggregated_output: String,
+    pub exit_code: Option<i32>,
+    pub status: CommandExecutionStatus,
+    /// Whether the failure ended the turn (`on_failure = "abort"`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub aborted_turn: bool,
+}
+
 /// An error notification.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ErrorItem {
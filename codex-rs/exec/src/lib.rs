@@ -4,16 +4,21 @@
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+mod batch;
 mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod prompt_template;
+mod run_limits;
 
+pub use cli::AskArgs;
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
 use codex_cloud_requirements::cloud_requirements_loader;
+use codex_common::oss::apply_oss_model_details;
 use codex_common::oss::ensure_oss_provider_ready;
 use codex_common::oss::get_default_model_for_oss_provider;
 use codex_common::oss::ollama_chat_deprecation_notice;
@@ -27,20 +32,25 @@ use codex_core::auth::enforce_login_restrictions;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::ConfigOverrides;
+use codex_core::config::READ_ONLY_CHAT_OVERRIDES;
 use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
+use codex_core::config::project_trust_target;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config::session_project_trust_override;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::models_manager::manager::RefreshStrategy;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_core::protocol::WarningEvent;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
@@ -48,11 +58,14 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 use supports_color::Stream;
 use tokio::sync::Mutex;
 use tracing::debug;
@@ -66,10 +79,15 @@ use uuid::Uuid;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::run_limits::RunLimits;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_thread_path_by_id_str;
 use codex_core::find_thread_path_by_name_str;
 
+/// Exit status when the model refused the request or the provider's content
+/// filter blocked it, so scripts can tell a refusal from other failures.
+pub const REFUSED_EXIT_CODE: i32 = 3;
+
 enum InitialOperation {
     UserTurn {
         items: Vec<UserInput>,
@@ -92,9 +110,15 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         tracing::warn!(?err, "Failed to set codex exec originator override {err:?}");
     }
 
+    if cli.batch.is_some() {
+        return batch::run_batch(cli).await;
+    }
+    let started = Instant::now();
+
     let Cli {
         command,
         images,
+        attachments,
         model: model_cli_arg,
         oss,
         oss_provider,
@@ -102,15 +126,39 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         full_auto,
         dangerously_bypass_approvals_and_sandbox,
         cwd,
-        skip_git_repo_check,
+        trust_cwd,
+        reasoning_effort,
+        reasoning_summary,
+        max_output_tokens,
+        max_tokens,
+        max_turns,
+        seed,
+        temperature,
+        top_p,
+        read_only_chat,
+        offline,
+        dry_run_exec,
+        env_file,
+        title,
+        tags,
         add_dir,
         color,
         last_message_file,
+        batch: _,
+        batch_jobs: _,
+        batch_timeout: _,
+        batch_worktree: _,
+        batch_summary: _,
         json: json_mode,
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
         output_schema: output_schema_path,
-        config_overrides,
+        prompt_file,
+        vars,
+        commit,
+        sign_off,
+        amend,
+        mut config_overrides,
     } = cli;
 
     let (stdout_with_ansi, stderr_with_ansi) = match color {
@@ -143,8 +191,14 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         sandbox_mode_cli_arg.map(Into::<SandboxMode>::into)
     };
 
+    if read_only_chat {
+        config_overrides
+            .raw_overrides
+            .extend(READ_ONLY_CHAT_OVERRIDES.map(String::from));
+    }
+
     // Parse `-c` overrides from the CLI.
-    let cli_kv_overrides = match config_overrides.parse_overrides() {
+    let mut cli_kv_overrides = match config_overrides.parse_overrides() {
         Ok(v) => v,
         #[allow(clippy::print_stderr)]
         Err(e) => {
@@ -158,6 +212,11 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         Some(path) => AbsolutePathBuf::from_absolute_path(path.canonicalize()?)?,
         None => AbsolutePathBuf::current_dir()?,
     };
+    if trust_cwd {
+        cli_kv_overrides.push(session_project_trust_override(&project_trust_target(
+            config_cwd.as_path(),
+        )));
+    }
 
     // we load config.toml here to determine project state.
     #[allow(clippy::print_stderr)]
@@ -256,10 +315,13 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         show_raw_agent_reasoning: oss.then_some(true),
         tools_web_search_request: None,
         ephemeral: None,
+        offline: offline.then_some(true),
+        dry_run_exec: dry_run_exec.then_some(true),
+        env_file,
         additional_writable_roots: add_dir,
     };
 
-    let config = ConfigBuilder::default()
+    let mut config = ConfigBuilder::default()
         .cli_overrides(cli_kv_overrides)
         .harness_overrides(overrides)
         .cloud_requirements(cloud_requirements)
@@ -334,21 +396,57 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             .await
             .map_err(|e| anyhow::anyhow!("OSS setup failed: {e}"))?;
     }
+    for message in apply_oss_model_details(&mut config).await {
+        event_processor.process_event(Event {
+            id: String::new(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        });
+    }
 
     let default_cwd = config.cwd.to_path_buf();
     let default_approval_policy = config.approval_policy.value();
     let default_sandbox_policy = config.sandbox_policy.get();
+    if let Some(effort) = reasoning_effort {
+        config.model_reasoning_effort = Some(effort.into());
+    }
+    if let Some(summary) = reasoning_summary {
+        config.model_reasoning_summary = summary.into();
+    }
+    if let Some(max_output_tokens) = max_output_tokens {
+        config.model_max_output_tokens = Some(i64::from(max_output_tokens));
+    }
+    if seed.is_some() {
+        config.model_sampling.seed = seed;
+    }
+    if temperature.is_some() {
+        config.model_sampling.temperature = temperature;
+    }
+    if top_p.is_some() {
+        config.model_sampling.top_p = top_p;
+    }
+    config.session_title = title;
+    config.session_tags = tags;
+    let ask = matches!(command, Some(ExecCommand::Ask(_)));
+    config.ask_mode = ask;
     let default_effort = config.model_reasoning_effort;
     let default_summary = config.model_reasoning_summary;
 
-    // When --yolo (dangerously_bypass_approvals_and_sandbox) is set, also skip the git repo check
-    // since the user is explicitly running in an externally sandboxed environment.
-    if !skip_git_repo_check
-        && !dangerously_bypass_approvals_and_sandbox
-        && get_git_repo_root(&default_cwd).is_none()
-    {
-        eprintln!("Not inside a trusted directory and --skip-git-repo-check was not specified.");
-        std::process::exit(1);
+    // When --yolo (dangerously_bypass_approvals_and_sandbox) is set, also skip the trust check
+    // since the user is explicitly running in an externally sandboxed environment. --trust-cwd
+    // was already folded into the config as a session-only trust override. An untrusted git
+    // repository only warns, so existing scripts keep working; outside one, it is an error.
+    if !dangerously_bypass_approvals_and_sandbox && !config.active_project.is_trusted() {
+        if get_git_repo_root(&default_cwd).is_none() {
+            eprintln!(
+                "Not inside a trusted directory or a git repository. Run `codex` in {} to trust it, or pass --trust-cwd.",
+                default_cwd.display()
+            );
+            std::process::exit(1);
+        }
+        eprintln!(
+            "Warning: {} has not been trusted, so project .codex/config.toml files are not loaded. Run `codex` there to trust it, or pass --trust-cwd.",
+            default_cwd.display()
+        );
     }
 
     let auth_manager = AuthManager::shared(
@@ -366,6 +464,22 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         .get_default_model(&config.model, &config, RefreshStrategy::OnlineIfUncached)
         .await;
 
+    let requests_reasoning = reasoning_effort
+        .is_some_and(|effort| effort != cli::ReasoningEffortCliArg::None)
+        || reasoning_summary.is_some_and(|summary| summary != cli::ReasoningSummaryCliArg::None);
+    if requests_reasoning
+        && !thread_manager
+            .get_models_manager()
+            .get_model_info(&default_model, &config)
+            .await
+            .supports_reasoning
+    {
+        eprintln!(
+            "{default_model} does not support reasoning; drop --reasoning-effort and --reasoning-summary."
+        );
+        std::process::exit(1);
+    }
+
     // Handle resume subcommand by resolving a rollout path and using explicit resume API.
     let NewThread {
         thread_id: primary_thread_id,
@@ -384,12 +498,44 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     } else {
         thread_manager.start_thread(config.clone()).await?
     };
+    let attachments: Vec<UserInput> = attachments
+        .into_iter()
+        .map(|path| UserInput::LocalFile {
+            path: default_cwd.join(path),
+        })
+        .collect();
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
             let review_request = build_review_request(review_cli)?;
             let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
             (InitialOperation::Review { review_request }, summary)
         }
+        (Some(ExecCommand::Ask(args)), _, imgs) => {
+            let question = resolve_prompt(args.question);
+            let mut items: Vec<UserInput> = imgs
+                .into_iter()
+                .map(|path| UserInput::LocalImage { path })
+                .collect();
+            items.push(UserInput::Text {
+                text: question.clone(),
+                // CLI input doesn't track UI element ranges, so none are available here.
+                text_elements: Vec::new(),
+            });
+            items.extend(attachments);
+            if !args.no_files {
+                items.extend(codex_core::ask::referenced_file_inputs(
+                    &question,
+                    &default_cwd,
+                ));
+            }
+            (
+                InitialOperation::UserTurn {
+                    items,
+                    output_schema: None,
+                },
+                question,
+            )
+        }
         (Some(ExecCommand::Resume(args)), root_prompt, imgs) => {
             let prompt_arg = args
                 .prompt
@@ -402,7 +548,10 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                     }
                 })
                 .or(root_prompt);
-            let prompt_text = resolve_prompt(prompt_arg);
+            let prompt_text = render_prompt(
+                resolve_prompt_source(prompt_arg, prompt_file.as_deref()),
+                &vars,
+            );
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
                 .chain(args.images.into_iter())
@@ -413,6 +562,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 // CLI input doesn't track UI element ranges, so none are available here.
                 text_elements: Vec::new(),
             });
+            items.extend(attachments);
             let output_schema = load_output_schema(output_schema_path.clone());
             (
                 InitialOperation::UserTurn {
@@ -423,7 +573,10 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             )
         }
         (None, root_prompt, imgs) => {
-            let prompt_text = resolve_prompt(root_prompt);
+            let prompt_text = render_prompt(
+                resolve_prompt_source(root_prompt, prompt_file.as_deref()),
+                &vars,
+            );
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
                 .map(|path| UserInput::LocalImage { path })
@@ -433,6 +586,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 // CLI input doesn't track UI element ranges, so none are available here.
                 text_elements: Vec::new(),
             });
+            items.extend(attachments);
             let output_schema = load_output_schema(output_schema_path);
             (
                 InitialOperation::UserTurn {
@@ -445,8 +599,12 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     };
 
     // Print the effective configuration and initial request so users can see what Codex
-    // is using.
-    event_processor.print_config_summary(&config, &prompt_summary, &session_configured);
+    // is using. `ask` prints nothing but the answer.
+    if ask {
+        info!("ask session ready in {:?}", started.elapsed());
+    } else {
+        event_processor.print_config_summary(&config, &prompt_summary, &session_configured);
+    }
 
     info!("Codex initialized with event: {session_configured:?}");
 
@@ -513,6 +671,8 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                     final_output_json_schema: output_schema,
                     collaboration_mode: None,
                     personality: None,
+                    max_output_tokens: None,
+                    service_tier: None,
                 })
                 .await?;
             info!("Sent prompt with event ID: {task_id}");
@@ -529,6 +689,12 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
+    let mut refused = false;
+    // With `--commit`, the primary turn's completion starts the commit task
+    // instead of shutting down; that task's own turn events are not reported.
+    let mut commit_pending = commit;
+    let mut committing = false;
+    let mut run_limits = RunLimits::new(max_tokens, max_turns);
     while let Some(envelope) = rx.recv().await {
         let ThreadEventEnvelope {
             thread_id,
@@ -548,10 +714,60 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         if matches!(event.msg, EventMsg::Error(_)) {
             error_seen = true;
         }
+        if thread_id == primary_thread_id && matches!(event.msg, EventMsg::TurnRefused(_)) {
+            refused = true;
+        }
         if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
             continue;
         }
+        if ask && !json_mode && !is_ask_output(&event.msg) {
+            continue;
+        }
+        if committing && thread_id == primary_thread_id {
+            match &event.msg {
+                EventMsg::TurnStarted(_) => continue,
+                EventMsg::TurnComplete(_) => {
+                    committing = false;
+                    thread.submit(Op::Shutdown).await?;
+                    continue;
+                }
+                EventMsg::CommitCompleted(done) if !done.success => error_seen = true,
+                _ => {}
+            }
+        }
+        if thread_id == primary_thread_id
+            && let Some(message) = run_limits.observe(&event.msg)
+        {
+            error_seen = true;
+            event_processor.process_event(Event {
+                id: event.id.clone(),
+                msg: EventMsg::Error(ErrorEvent {
+                    message,
+                    code: Some("run_limit_exceeded".to_string()),
+                    ..Default::default()
+                }),
+            });
+            thread.submit(Op::Interrupt).await?;
+        }
         let shutdown = event_processor.process_event(event);
+        if commit_pending
+            && thread_id == primary_thread_id
+            && matches!(shutdown, CodexStatus::InitiateShutdown)
+        {
+            commit_pending = false;
+            if !error_seen && !refused {
+                committing = true;
+                thread
+                    .submit(Op::Commit {
+                        message: None,
+                        files: Vec::new(),
+                        sign_off,
+                        amend,
+                    })
+                    .await?;
+                continue;
+            }
+        }
         if thread_id != primary_thread_id && matches!(shutdown, CodexStatus::InitiateShutdown) {
             continue;
         }
@@ -568,10 +784,26 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     if error_seen {
         std::process::exit(1);
     }
+    if refused {
+        std::process::exit(REFUSED_EXIT_CODE);
+    }
 
     Ok(())
 }
 
+/// Events `codex exec ask` passes to the human-readable output: errors, and
+/// the turn and shutdown events that carry the answer and end the run.
+fn is_ask_output(msg: &EventMsg) -> bool {
+    matches!(
+        msg,
+        EventMsg::Error(_)
+            | EventMsg::TurnRefused(_)
+            | EventMsg::TurnAborted(_)
+            | EventMsg::TurnComplete(_)
+            | EventMsg::ShutdownComplete
+    )
+}
+
 fn spawn_thread_listener(
     thread_id: codex_protocol::ThreadId,
     thread: Arc<codex_core::CodexThread>,
@@ -749,6 +981,49 @@ fn decode_utf16(
     String::from_utf16(&units).map_err(|_| PromptDecodeError::InvalidUtf16 { encoding })
 }
 
+/// Read the prompt from `--prompt-file` when no prompt argument was given,
+/// otherwise fall back to [`resolve_prompt`].
+fn resolve_prompt_source(prompt_arg: Option<String>, prompt_file: Option<&Path>) -> String {
+    let Some(path) = prompt_file.filter(|_| prompt_arg.is_none()) else {
+        return resolve_prompt(prompt_arg);
+    };
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("Failed to read prompt file {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    };
+    let prompt = match decode_prompt_bytes(&bytes) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to read prompt file {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    };
+    if prompt.trim().is_empty() {
+        eprintln!("Prompt file {} is empty.", path.display());
+        std::process::exit(1);
+    }
+    prompt
+}
+
+/// Apply `--var` substitutions. Prompts are left untouched when no `--var`
+/// was passed so literal `{{...}}` text keeps working.
+fn render_prompt(prompt: String, vars: &[(String, String)]) -> String {
+    if vars.is_empty() {
+        return prompt;
+    }
+    let vars: HashMap<String, String> = vars.iter().cloned().collect();
+    match prompt_template::render(&prompt, &vars) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
+
 fn resolve_prompt(prompt_arg: Option<String>) -> String {
     match prompt_arg {
         Some(p) if p != "-" => p,
@@ -57,7 +57,7 @@ mod tests {
             "--config",
             "reasoning_level=xhigh",
             "--dangerously-bypass-approvals-and-sandbox",
-            "--skip-git-repo-check",
+            "--trust-cwd",
             PROMPT,
         ]);
 
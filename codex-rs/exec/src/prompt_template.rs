@@ -0,0 +1,139 @@
+//! `{{var}}` substitution for prompts fed by `--var key=value`.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+/// Placeholders in the prompt that no `--var` supplied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UnresolvedVariables(pub Vec<String>);
+
+impl std::fmt::Display for UnresolvedVariables {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "prompt references undefined variable(s): {}. Pass them with `--var KEY=VALUE`.",
+            self.0.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnresolvedVariables {}
+
+/// Replace every `{{key}}` (surrounding whitespace inside the braces is
+/// ignored) with its value. Text that is not a well-formed placeholder, such
+/// as `{{` without a closing `}}` or `{{not a key}}`, is kept verbatim.
+pub(crate) fn render(
+    template: &str,
+    vars: &HashMap<String, String>,
+) -> Result<String, UnresolvedVariables> {
+    let mut out = String::with_capacity(template.len());
+    let mut missing = BTreeSet::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            out.push_str(&rest[start..]);
+            return finish(out, missing);
+        };
+        let key = after_open[..end].trim();
+        if is_valid_key(key) {
+            match vars.get(key) {
+                Some(value) => out.push_str(value),
+                None => {
+                    missing.insert(key.to_string());
+                }
+            }
+            rest = &after_open[end + 2..];
+        } else {
+            out.push_str("{{");
+            rest = after_open;
+        }
+    }
+    out.push_str(rest);
+    finish(out, missing)
+}
+
+/// Parse a `--var` value of the form `KEY=VALUE`.
+pub(crate) fn parse_var(raw: &str) -> Result<(String, String), String> {
+    let Some((key, value)) = raw.split_once('=') else {
+        return Err(format!("expected KEY=VALUE, got `{raw}`"));
+    };
+    let key = key.trim();
+    if !is_valid_key(key) {
+        return Err(format!(
+            "invalid variable name `{key}`: use letters, digits, `_`, `-` or `.`"
+        ));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+fn finish(out: String, missing: BTreeSet<String>) -> Result<String, UnresolvedVariables> {
+    if missing.is_empty() {
+        Ok(out)
+    } else {
+        Err(UnresolvedVariables(missing.into_iter().collect()))
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn substitutes_placeholders() {
+        let rendered = render(
+            "Fix {{ issue }} in {{file}}, then re-check {{file}}.",
+            &vars(&[("issue", "#42"), ("file", "src/lib.rs")]),
+        );
+        assert_eq!(
+            rendered,
+            Ok("Fix #42 in src/lib.rs, then re-check src/lib.rs.".to_string())
+        );
+    }
+
+    #[test]
+    fn lists_every_missing_key_once() {
+        let rendered = render("{{b}} {{a}} {{b}} {{c}}", &vars(&[("c", "x")]));
+        assert_eq!(
+            rendered,
+            Err(UnresolvedVariables(vec!["a".to_string(), "b".to_string()]))
+        );
+    }
+
+    #[test]
+    fn keeps_text_that_is_not_a_placeholder() {
+        let template = "match x {{ Some(v) => v }} and {{unterminated";
+        assert_eq!(render(template, &vars(&[])), Ok(template.to_string()));
+    }
+
+    #[test]
+    fn parses_var_flags() {
+        assert_eq!(
+            parse_var("name=a=b"),
+            Ok(("name".to_string(), "a=b".to_string()))
+        );
+        assert_eq!(
+            parse_var("empty="),
+            Ok(("empty".to_string(), String::new()))
+        );
+        assert!(parse_var("novalue").is_err());
+        assert!(parse_var("bad key=1").is_err());
+    }
+}
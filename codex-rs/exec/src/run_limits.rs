@@ -0,0 +1,104 @@
+//! `--max-tokens` and `--max-turns`: stop a run that goes over budget.
+
+use codex_core::protocol::EventMsg;
+
+/// Tracks token usage and model responses for the primary thread.
+#[derive(Debug, Default)]
+pub(crate) struct RunLimits {
+    max_tokens: Option<u64>,
+    max_turns: Option<u64>,
+    total_tokens: i64,
+    turns: u64,
+    exceeded: bool,
+}
+
+impl RunLimits {
+    pub(crate) fn new(max_tokens: Option<u64>, max_turns: Option<u64>) -> Self {
+        Self {
+            max_tokens,
+            max_turns,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the error to report when `msg` takes the run over a limit.
+    /// Reports at most once.
+    pub(crate) fn observe(&mut self, msg: &EventMsg) -> Option<String> {
+        if self.exceeded {
+            return None;
+        }
+        let EventMsg::TokenCount(ev) = msg else {
+            return None;
+        };
+        let total = ev.info.as_ref()?.total_token_usage.total_tokens;
+        // Token counts are also re-sent for rate limit updates; only a higher
+        // total means another model response.
+        if total <= self.total_tokens {
+            return None;
+        }
+        self.total_tokens = total;
+        self.turns += 1;
+
+        let message = if let Some(max) = self.max_tokens
+            && u64::try_from(total).unwrap_or(0) > max
+        {
+            format!("Stopped after using {total} tokens, over the --max-tokens limit of {max}.")
+        } else if let Some(max) = self.max_turns
+            && self.turns >= max
+        {
+            format!("Stopped after {max} model responses, the --max-turns limit.")
+        } else {
+            return None;
+        };
+        self.exceeded = true;
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::TokenCountEvent;
+    use codex_core::protocol::TokenUsage;
+    use codex_core::protocol::TokenUsageInfo;
+    use pretty_assertions::assert_eq;
+
+    fn token_count(total_tokens: i64) -> EventMsg {
+        EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: TokenUsage {
+                    total_tokens,
+                    ..Default::default()
+                },
+                last_token_usage: TokenUsage::default(),
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        })
+    }
+
+    #[test]
+    fn stops_once_over_the_token_limit() {
+        let mut limits = RunLimits::new(Some(1_000), None);
+        assert_eq!(limits.observe(&token_count(600)), None);
+        assert_eq!(
+            limits.observe(&token_count(1_200)),
+            Some(
+                "Stopped after using 1200 tokens, over the --max-tokens limit of 1000.".to_string()
+            )
+        );
+        assert_eq!(limits.observe(&token_count(1_500)), None);
+    }
+
+    #[test]
+    fn counts_only_new_responses_toward_the_turn_limit() {
+        let mut limits = RunLimits::new(None, Some(2));
+        assert_eq!(limits.observe(&token_count(100)), None);
+        // A rate limit update repeats the same total.
+        assert_eq!(limits.observe(&token_count(100)), None);
+        assert_eq!(
+            limits.observe(&token_count(250)),
+            Some("Stopped after 2 model responses, the --max-turns limit.".to_string())
+        );
+    }
+}
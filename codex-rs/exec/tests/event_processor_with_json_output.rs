@@ -1,10 +1,14 @@
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::AgentStatus;
+use codex_core::protocol::ApprovalKind;
+use codex_core::protocol::ApprovalResolvedEvent;
+use codex_core::protocol::ApprovalResolver;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::CollabAgentSpawnBeginEvent;
 use codex_core::protocol::CollabAgentSpawnEndEvent;
 use codex_core::protocol::CollabWaitingEndEvent;
+use codex_core::protocol::CommitCompletedEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -12,18 +16,29 @@ use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::FileChange;
+use codex_core::protocol::HookBeginEvent;
+use codex_core::protocol::HookEndEvent;
+use codex_core::protocol::HookPoint;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::PatchFileResult;
+use codex_core::protocol::PatchFileStatus;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::ToolToggles;
+use codex_core::protocol::TurnRefusedEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_exec::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
 use codex_exec::exec_events::AgentMessageItem;
+use codex_exec::exec_events::ApprovalRequestKind;
+use codex_exec::exec_events::ApprovalResolutionItem;
+use codex_exec::exec_events::ApprovalResolvedBy;
 use codex_exec::exec_events::CollabAgentState;
 use codex_exec::exec_events::CollabAgentStatus;
 use codex_exec::exec_events::CollabTool;
@@ -31,7 +46,14 @@ use codex_exec::exec_events::CollabToolCallItem;
 use codex_exec::exec_events::CollabToolCallStatus;
 use codex_exec::exec_events::CommandExecutionItem;
 use codex_exec::exec_events::CommandExecutionStatus;
+use codex_exec::exec_events::CommitItem;
+use codex_exec::exec_events::CommitStatus;
 use codex_exec::exec_events::ErrorItem;
+use codex_exec::exec_events::FileChangeItem;
+use codex_exec::exec_events::FileChangeStatus;
+use codex_exec::exec_events::FileCitation;
+use codex_exec::exec_events::FileUpdateChange;
+use codex_exec::exec_events::HookExecutionItem;
 use codex_exec::exec_events::ItemCompletedEvent;
 use codex_exec::exec_events::ItemStartedEvent;
 use codex_exec::exec_events::ItemUpdatedEvent;
@@ -56,6 +78,7 @@ use codex_exec::exec_events::Usage;
 use codex_exec::exec_events::WebSearchItem;
 use codex_protocol::ThreadId;
 use codex_protocol::config_types::ModeKind;
+use codex_protocol::config_types::SamplingParameters;
 use codex_protocol::models::WebSearchAction;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
@@ -100,6 +123,11 @@ fn session_configured_produces_thread_started_event() {
             history_entry_count: 0,
             initial_messages: None,
             rollout_path: Some(rollout_path),
+            tool_toggles: ToolToggles::default(),
+            base_instructions_tokens: None,
+            unknown_model: None,
+            sampling: None,
+            startup: None,
         }),
     );
     let out = ep.collect_thread_events(&ev);
@@ -107,6 +135,7 @@ fn session_configured_produces_thread_started_event() {
         out,
         vec![ThreadEvent::ThreadStarted(ThreadStartedEvent {
             thread_id: "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+            sampling: None,
         })]
     );
 }
@@ -119,6 +148,7 @@ fn task_started_produces_turn_started_event() {
         EventMsg::TurnStarted(codex_core::protocol::TurnStartedEvent {
             model_context_window: Some(32_000),
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     ));
 
@@ -309,6 +339,7 @@ fn plan_update_emits_todo_list_started_updated_and_completed() {
         "p3",
         EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
             last_agent_message: None,
+            system_fingerprint: None,
         }),
     );
     let out_complete = ep.collect_thread_events(&complete);
@@ -334,6 +365,7 @@ fn plan_update_emits_todo_list_started_updated_and_completed() {
             }),
             ThreadEvent::TurnCompleted(TurnCompletedEvent {
                 usage: Usage::default(),
+                system_fingerprint: None,
             }),
         ]
     );
@@ -384,6 +416,7 @@ fn mcp_tool_call_begin_and_end_emit_item_events() {
                 is_error: None,
                 structured_content: None,
             }),
+            argument_errors: Vec::new(),
         }),
     );
     let end_events = ep.collect_thread_events(&end);
@@ -433,6 +466,7 @@ fn mcp_tool_call_failure_sets_failed_status() {
             invocation,
             duration: Duration::from_millis(5),
             result: Err("tool exploded".to_string()),
+            argument_errors: Vec::new(),
         }),
     );
     let events = ep.collect_thread_events(&end);
@@ -505,6 +539,7 @@ fn mcp_tool_call_defaults_arguments_and_preserves_structured_content() {
                 is_error: None,
                 structured_content: Some(json!({ "status": "ok" })),
             }),
+            argument_errors: Vec::new(),
         }),
     );
     let events = ep.collect_thread_events(&end);
@@ -682,6 +717,7 @@ fn plan_update_after_complete_starts_new_todo_list_with_new_id() {
         "t2",
         EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
             last_agent_message: None,
+            system_fingerprint: None,
         }),
     );
     let _ = ep.collect_thread_events(&complete);
@@ -747,12 +783,76 @@ fn agent_message_produces_item_completed_agent_message() {
                 id: "item_0".to_string(),
                 details: ThreadItemDetails::AgentMessage(AgentMessageItem {
                     text: "hello".to_string(),
+                    citations: Vec::new(),
                 }),
             },
         })]
     );
 }
 
+#[test]
+fn agent_message_lists_citations_of_existing_files() -> anyhow::Result<()> {
+    let workspace = tempfile::TempDir::new()?;
+    std::fs::create_dir_all(workspace.path().join("src"))?;
+    std::fs::write(workspace.path().join("src/lib.rs"), "")?;
+
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::SessionConfigured(SessionConfiguredEvent {
+            session_id: codex_protocol::ThreadId::new(),
+            forked_from_id: None,
+            thread_name: None,
+            model: "codex-mini-latest".to_string(),
+            model_provider_id: "test-provider".to_string(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            cwd: workspace.path().to_path_buf(),
+            reasoning_effort: None,
+            history_log_id: 0,
+            history_entry_count: 0,
+            initial_messages: None,
+            rollout_path: None,
+            tool_toggles: ToolToggles::default(),
+            base_instructions_tokens: None,
+            unknown_model: None,
+            sampling: None,
+            startup: None,
+        }),
+    ));
+
+    let message = "Fixed in src/lib.rs:12, not src/main.rs:3.".to_string();
+    let out = ep.collect_thread_events(&event(
+        "e2",
+        EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.clone(),
+        }),
+    ));
+    assert_eq!(
+        out,
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::AgentMessage(AgentMessageItem {
+                    text: message,
+                    citations: vec![FileCitation {
+                        path: workspace
+                            .path()
+                            .join("src/lib.rs")
+                            .to_string_lossy()
+                            .into_owned(),
+                        line: Some(12),
+                        end_line: None,
+                        start: 9,
+                        end: 22,
+                    }],
+                }),
+            },
+        })]
+    );
+    Ok(())
+}
+
 #[test]
 fn error_event_produces_error() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -761,12 +861,28 @@ fn error_event_produces_error() {
         EventMsg::Error(codex_core::protocol::ErrorEvent {
             message: "boom".to_string(),
             codex_error_info: Some(CodexErrorInfo::Other),
+            request_details: Some(codex_core::protocol::RequestErrorDetails {
+                request_id: Some("req_abc123".to_string()),
+                error_type: Some("invalid_request_error".to_string()),
+                error_code: None,
+                attempts: Some(3),
+            }),
+            hint: Some("Try again in a moment.".to_string()),
+            code: Some("server_error".to_string()),
+            details: Some("unexpected status 500: boom".to_string()),
         }),
     ));
     assert_eq!(
         out,
         vec![ThreadEvent::Error(ThreadErrorEvent {
             message: "boom".to_string(),
+            request_id: Some("req_abc123".to_string()),
+            error_type: Some("invalid_request_error".to_string()),
+            error_code: None,
+            attempts: Some(3),
+            code: Some("server_error".to_string()),
+            hint: Some("Try again in a moment.".to_string()),
+            details: Some("unexpected status 500: boom".to_string()),
         })]
     );
 }
@@ -793,6 +909,164 @@ fn warning_event_produces_error_item() {
     );
 }
 
+#[test]
+fn approval_resolution_produces_item() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let out = ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::ApprovalResolved(ApprovalResolvedEvent {
+            call_id: "call-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            kind: ApprovalKind::Exec,
+            command: vec!["rm".to_string(), "-rf".to_string(), "build dir".to_string()],
+            paths: Vec::new(),
+            cwd: PathBuf::from("/work"),
+            reason: Some("needs write access".to_string()),
+            decision: ReviewDecision::Denied,
+            resolved_by: ApprovalResolver::User,
+            account: Some("dev@example.com".to_string()),
+            resolved_at: "2025-01-02T03:04:05Z".to_string(),
+        }),
+    ));
+    assert_eq!(
+        out,
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::ApprovalResolution(ApprovalResolutionItem {
+                    kind: ApprovalRequestKind::Exec,
+                    command: Some("rm -rf 'build dir'".to_string()),
+                    paths: Vec::new(),
+                    reason: Some("needs write access".to_string()),
+                    decision: "denied".to_string(),
+                    resolved_by: ApprovalResolvedBy::User,
+                    account: Some("dev@example.com".to_string()),
+                    resolved_at: "2025-01-02T03:04:05Z".to_string(),
+                }),
+            },
+        })]
+    );
+}
+
+#[test]
+fn commit_completion_produces_item() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let out = ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::CommitCompleted(CommitCompletedEvent {
+            success: true,
+            commit: Some("0123abcd".to_string()),
+            message: Some("fix(exec): flush output before exit".to_string()),
+            files: vec![PathBuf::from("/work/exec/src/lib.rs")],
+            error: None,
+        }),
+    ));
+    let refused = ep.collect_thread_events(&event(
+        "e2",
+        EventMsg::CommitCompleted(CommitCompletedEvent {
+            success: false,
+            commit: None,
+            message: None,
+            files: Vec::new(),
+            error: Some("HEAD is detached. Check out a branch before committing.".to_string()),
+        }),
+    ));
+    assert_eq!(
+        out,
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::Commit(CommitItem {
+                    commit: Some("0123abcd".to_string()),
+                    message: Some("fix(exec): flush output before exit".to_string()),
+                    files: vec!["/work/exec/src/lib.rs".to_string()],
+                    status: CommitStatus::Completed,
+                    error: None,
+                }),
+            },
+        })]
+    );
+    assert_eq!(
+        refused,
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_1".to_string(),
+                details: ThreadItemDetails::Commit(CommitItem {
+                    commit: None,
+                    message: None,
+                    files: Vec::new(),
+                    status: CommitStatus::Failed,
+                    error: Some(
+                        "HEAD is detached. Check out a branch before committing.".to_string()
+                    ),
+                }),
+            },
+        })]
+    );
+}
+
+#[test]
+fn hook_begin_and_end_produce_hook_execution_item() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let command = vec!["cargo".to_string(), "fmt".to_string()];
+    let started = ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::HookBegin(HookBeginEvent {
+            call_id: "hook-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            hook: HookPoint::PostPatch,
+            command: command.clone(),
+        }),
+    ));
+    let completed = ep.collect_thread_events(&event(
+        "e2",
+        EventMsg::HookEnd(HookEndEvent {
+            call_id: "hook-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            hook: HookPoint::PostPatch,
+            command,
+            exit_code: Some(1),
+            timed_out: false,
+            output: "error: expected `;`".to_string(),
+            duration: Duration::from_millis(30),
+            aborts_turn: true,
+        }),
+    ));
+
+    assert_eq!(
+        started,
+        vec![ThreadEvent::ItemStarted(ItemStartedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::HookExecution(HookExecutionItem {
+                    hook: "post_patch".to_string(),
+                    command: "cargo fmt".to_string(),
+                    aggregated_output: String::new(),
+                    exit_code: None,
+                    status: CommandExecutionStatus::InProgress,
+                    aborted_turn: false,
+                }),
+            },
+        })]
+    );
+    assert_eq!(
+        completed,
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::HookExecution(HookExecutionItem {
+                    hook: "post_patch".to_string(),
+                    command: "cargo fmt".to_string(),
+                    aggregated_output: "error: expected `;`".to_string(),
+                    exit_code: Some(1),
+                    status: CommandExecutionStatus::Failed,
+                    aborted_turn: true,
+                }),
+            },
+        })]
+    );
+}
+
 #[test]
 fn stream_error_event_produces_error() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -808,6 +1082,7 @@ fn stream_error_event_produces_error() {
         out,
         vec![ThreadEvent::Error(ThreadErrorEvent {
             message: "retrying".to_string(),
+            ..Default::default()
         })]
     );
 }
@@ -821,12 +1096,14 @@ fn error_followed_by_task_complete_produces_turn_failed() {
         EventMsg::Error(ErrorEvent {
             message: "boom".to_string(),
             codex_error_info: Some(CodexErrorInfo::Other),
+            ..Default::default()
         }),
     );
     assert_eq!(
         ep.collect_thread_events(&error_event),
         vec![ThreadEvent::Error(ThreadErrorEvent {
             message: "boom".to_string(),
+            ..Default::default()
         })]
     );
 
@@ -834,6 +1111,7 @@ fn error_followed_by_task_complete_produces_turn_failed() {
         "e2",
         EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
             last_agent_message: None,
+            system_fingerprint: None,
         }),
     );
     assert_eq!(
@@ -841,6 +1119,38 @@ fn error_followed_by_task_complete_produces_turn_failed() {
         vec![ThreadEvent::TurnFailed(TurnFailedEvent {
             error: ThreadErrorEvent {
                 message: "boom".to_string(),
+                ..Default::default()
+            },
+        })]
+    );
+}
+
+#[test]
+fn turn_refused_produces_turn_failed_with_refused_code() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+
+    let refused_event = event(
+        "e1",
+        EventMsg::TurnRefused(TurnRefusedEvent {
+            reason: "I can't help with that.".to_string(),
+        }),
+    );
+    assert!(ep.collect_thread_events(&refused_event).is_empty());
+
+    let complete_event = event(
+        "e2",
+        EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
+            last_agent_message: None,
+            system_fingerprint: None,
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&complete_event),
+        vec![ThreadEvent::TurnFailed(TurnFailedEvent {
+            error: ThreadErrorEvent {
+                message: "I can't help with that.".to_string(),
+                code: Some("refused".to_string()),
+                ..Default::default()
             },
         })]
     );
@@ -901,6 +1211,9 @@ fn exec_command_end_success_produces_completed_command_item() {
             exit_code: 0,
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
+            formatted_output_truncated: false,
+            denied_by_sandbox: false,
+            limit_exceeded: None,
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -988,6 +1301,9 @@ fn command_execution_output_delta_updates_item_progress() {
             exit_code: 0,
             duration: Duration::from_millis(3),
             formatted_output: String::new(),
+            formatted_output_truncated: false,
+            denied_by_sandbox: false,
+            limit_exceeded: None,
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -1061,6 +1377,9 @@ fn exec_command_end_failure_produces_failed_command_item() {
             exit_code: 1,
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
+            formatted_output_truncated: false,
+            denied_by_sandbox: false,
+            limit_exceeded: None,
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -1102,6 +1421,9 @@ fn exec_command_end_without_begin_is_ignored() {
             exit_code: 0,
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
+            formatted_output_truncated: false,
+            denied_by_sandbox: false,
+            limit_exceeded: None,
         }),
     );
     let out = ep.collect_thread_events(&end_only);
@@ -1157,6 +1479,7 @@ fn patch_apply_success_produces_item_completed_patchapply() {
             stderr: String::new(),
             success: true,
             changes: changes.clone(),
+            per_file: Vec::new(),
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -1228,6 +1551,7 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
             stderr: "failed to apply".to_string(),
             success: false,
             changes: changes.clone(),
+            per_file: Vec::new(),
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -1242,6 +1566,7 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
                     assert_eq!(file_update.changes.len(), 1);
                     assert_eq!(file_update.changes[0].path, "file.txt".to_string());
                     assert_eq!(file_update.changes[0].kind, PatchChangeKind::Update);
+                    assert_eq!(file_update.changes[0].status, FileChangeStatus::Failed);
                 }
                 other => panic!("unexpected details: {other:?}"),
             }
@@ -1250,6 +1575,86 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
     }
 }
 
+#[test]
+fn patch_apply_conflict_on_second_file_reports_per_file_statuses() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+
+    let update = || FileChange::Update {
+        unified_diff: "@@\n-old\n+new\n".to_string(),
+        move_path: None,
+    };
+    let changes = std::collections::HashMap::from([
+        (PathBuf::from("a.txt"), update()),
+        (PathBuf::from("b.txt"), update()),
+        (PathBuf::from("c.txt"), update()),
+    ]);
+    let begin = event(
+        "p1",
+        EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
+            call_id: "call-3".to_string(),
+            turn_id: "turn-3".to_string(),
+            auto_approved: true,
+            changes: changes.clone(),
+        }),
+    );
+    assert!(ep.collect_thread_events(&begin).is_empty());
+
+    let result = |path: &str, status, error: Option<&str>| PatchFileResult {
+        path: PathBuf::from(path),
+        status,
+        error: error.map(str::to_string),
+    };
+    let end = event(
+        "p2",
+        EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+            call_id: "call-3".to_string(),
+            turn_id: "turn-3".to_string(),
+            stdout: "Failed. Updated the following files before the error:\nM a.txt\n! b.txt\n"
+                .to_string(),
+            stderr: "Failed to find expected lines in b.txt:\nold\n".to_string(),
+            success: false,
+            changes,
+            per_file: vec![
+                result("a.txt", PatchFileStatus::Applied, None),
+                result(
+                    "b.txt",
+                    PatchFileStatus::Failed,
+                    Some("Failed to find expected lines in b.txt:\nold"),
+                ),
+                result("c.txt", PatchFileStatus::Skipped, None),
+            ],
+        }),
+    );
+    let out_end = ep.collect_thread_events(&end);
+
+    let change = |path: &str, status, error: Option<&str>| FileUpdateChange {
+        path: path.to_string(),
+        kind: PatchChangeKind::Update,
+        status,
+        error: error.map(str::to_string),
+    };
+    assert_eq!(
+        out_end,
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::FileChange(FileChangeItem {
+                    changes: vec![
+                        change("a.txt", FileChangeStatus::Applied, None),
+                        change(
+                            "b.txt",
+                            FileChangeStatus::Failed,
+                            Some("Failed to find expected lines in b.txt:\nold"),
+                        ),
+                        change("c.txt", FileChangeStatus::Skipped, None),
+                    ],
+                    status: PatchApplyStatus::Failed,
+                }),
+            },
+        })]
+    );
+}
+
 #[test]
 fn task_complete_produces_turn_completed_with_usage() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -1281,6 +1686,7 @@ fn task_complete_produces_turn_completed_with_usage() {
         "e2",
         EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
             last_agent_message: Some("done".to_string()),
+            system_fingerprint: None,
         }),
     );
     let out = ep.collect_thread_events(&complete_event);
@@ -1292,6 +1698,64 @@ fn task_complete_produces_turn_completed_with_usage() {
                 cached_input_tokens: 200,
                 output_tokens: 345,
             },
+            system_fingerprint: None,
+        })]
+    );
+}
+
+#[test]
+fn sampling_and_system_fingerprint_are_reported() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let session_configured = event(
+        "e1",
+        EventMsg::SessionConfigured(SessionConfiguredEvent {
+            session_id: ThreadId::from_string("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap(),
+            forked_from_id: None,
+            thread_name: None,
+            model: "gpt-4o".to_string(),
+            model_provider_id: "test-provider".to_string(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            cwd: PathBuf::from("/home/user/project"),
+            reasoning_effort: None,
+            history_log_id: 0,
+            history_entry_count: 0,
+            initial_messages: None,
+            rollout_path: None,
+            tool_toggles: ToolToggles::default(),
+            base_instructions_tokens: None,
+            unknown_model: None,
+            sampling: Some(SamplingParameters {
+                seed: Some(7),
+                temperature: Some(0.2),
+                top_p: None,
+            }),
+            startup: None,
+        }),
+    );
+    let started = ep.collect_thread_events(&session_configured);
+    assert_eq!(
+        serde_json::to_value(&started[0]).unwrap(),
+        json!({
+            "type": "thread.started",
+            "thread_id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "sampling": {"seed": 7, "temperature": 0.2},
+        })
+    );
+
+    let complete = event(
+        "e2",
+        EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
+            last_agent_message: None,
+            system_fingerprint: Some("fp_44709d6fcb".to_string()),
+        }),
+    );
+    let completed = ep.collect_thread_events(&complete);
+    assert_eq!(
+        completed,
+        vec![ThreadEvent::TurnCompleted(TurnCompletedEvent {
+            usage: Usage::default(),
+            system_fingerprint: Some("fp_44709d6fcb".to_string()),
         })]
     );
 }
@@ -23,7 +23,7 @@ async fn accepts_add_dir_flag() -> anyhow::Result<()> {
     let temp_dir2 = tempfile::tempdir()?;
 
     test.cmd_with_server(&server)
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("--sandbox")
         .arg("workspace-write")
         .arg("--add-dir")
@@ -55,7 +55,7 @@ async fn accepts_multiple_add_dir_flags() -> anyhow::Result<()> {
     let temp_dir3 = tempfile::tempdir()?;
 
     test.cmd_with_server(&server)
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("--sandbox")
         .arg("workspace-write")
         .arg("--add-dir")
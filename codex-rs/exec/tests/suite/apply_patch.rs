@@ -80,7 +80,7 @@ async fn test_apply_patch_tool() -> anyhow::Result<()> {
     mount_sse_sequence(&server, response_streams).await;
 
     test.cmd_with_server(&server)
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-s")
         .arg("danger-full-access")
         .arg("foo")
@@ -131,7 +131,7 @@ async fn test_apply_patch_freeform_tool() -> anyhow::Result<()> {
     mount_sse_sequence(&server, response_streams).await;
 
     test.cmd_with_server(&server)
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-s")
         .arg("danger-full-access")
         .arg("foo")
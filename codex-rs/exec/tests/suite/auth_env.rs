@@ -20,7 +20,7 @@ async fn exec_uses_codex_api_key_env_var() -> anyhow::Result<()> {
     .await;
 
     test.cmd_with_server(&server)
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg("echo testing codex api key")
@@ -4,6 +4,7 @@ mod apply_patch;
 mod auth_env;
 mod originator;
 mod output_schema;
+mod prompt_input;
 mod resume;
 mod sandbox;
 mod server_error_exit;
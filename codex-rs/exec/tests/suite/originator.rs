@@ -22,7 +22,7 @@ async fn send_codex_exec_originator() -> anyhow::Result<()> {
 
     test.cmd_with_server(&server)
         .env_remove(CODEX_INTERNAL_ORIGINATOR_OVERRIDE_ENV_VAR)
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("tell me something")
         .assert()
         .code(0);
@@ -45,7 +45,7 @@ async fn supports_originator_override() -> anyhow::Result<()> {
 
     test.cmd_with_server(&server)
         .env("CODEX_INTERNAL_ORIGINATOR_OVERRIDE", "codex_exec_override")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("tell me something")
         .assert()
         .code(0);
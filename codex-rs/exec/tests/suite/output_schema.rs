@@ -30,7 +30,7 @@ async fn exec_includes_output_schema_in_request() -> anyhow::Result<()> {
     let response_mock = responses::mount_sse_once(&server, body).await;
 
     test.cmd_with_server(&server)
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         // keep using -C in the test to exercise the flag as well
         .arg("-C")
         .arg(test.cwd_path())
@@ -0,0 +1,108 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+use codex_utils_cargo_bin::find_resource;
+use core_test_support::test_codex_exec::test_codex_exec;
+use predicates::str::contains;
+use serde_json::Value;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+/// Text of every user message recorded in the rollouts under `sessions_dir`.
+fn recorded_user_messages(sessions_dir: &std::path::Path) -> Vec<String> {
+    let mut messages = Vec::new();
+    for entry in WalkDir::new(sessions_dir).into_iter().flatten() {
+        if !entry.file_name().to_string_lossy().ends_with(".jsonl") {
+            continue;
+        }
+        let content = std::fs::read_to_string(entry.path()).unwrap_or_default();
+        for line in content.lines() {
+            let Ok(item) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+            let Some(payload) = item.get("payload") else {
+                continue;
+            };
+            if item.get("type").and_then(Value::as_str) != Some("response_item")
+                || payload.get("role").and_then(Value::as_str) != Some("user")
+            {
+                continue;
+            }
+            let parts = payload.get("content").and_then(Value::as_array);
+            for part in parts.into_iter().flatten() {
+                if let Some(text) = part.get("text").and_then(Value::as_str) {
+                    messages.push(text.to_string());
+                }
+            }
+        }
+    }
+    messages
+}
+
+#[test]
+fn exec_reads_templated_prompt_from_stdin() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let fixture = find_resource!("tests/fixtures/cli_responses_fixture.sse")?;
+    let marker = Uuid::new_v4().to_string();
+
+    test.cmd()
+        .env("CODEX_RS_SSE_FIXTURE", &fixture)
+        .env("OPENAI_BASE_URL", "http://unused.local")
+        .arg("--trust-cwd")
+        .arg("--var")
+        .arg(format!("marker={marker}"))
+        .arg("--var")
+        .arg("quote=it's \"quoted\"")
+        .arg("-")
+        .write_stdin("Say {{ marker }}.\nThen repeat {{quote}}\n")
+        .assert()
+        .success();
+
+    let messages = recorded_user_messages(&test.home_path().join("sessions"));
+    let expected = format!("Say {marker}.\nThen repeat it's \"quoted\"\n");
+    assert!(
+        messages.contains(&expected),
+        "rollout should record the rendered prompt, got {messages:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn exec_reads_prompt_file() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let fixture = find_resource!("tests/fixtures/cli_responses_fixture.sse")?;
+    let marker = Uuid::new_v4().to_string();
+    let prompt_path = test.cwd_path().join("prompt.md");
+    std::fs::write(&prompt_path, format!("Review {{{{file}}}} ({marker})"))?;
+
+    test.cmd()
+        .env("CODEX_RS_SSE_FIXTURE", &fixture)
+        .env("OPENAI_BASE_URL", "http://unused.local")
+        .arg("--trust-cwd")
+        .arg("--prompt-file")
+        .arg(&prompt_path)
+        .arg("--var")
+        .arg("file=src/lib.rs")
+        .assert()
+        .success();
+
+    let messages = recorded_user_messages(&test.home_path().join("sessions"));
+    assert!(
+        messages.contains(&format!("Review src/lib.rs ({marker})")),
+        "rollout should record the prompt file contents, got {messages:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn exec_lists_unresolved_prompt_variables() {
+    let test = test_codex_exec();
+
+    test.cmd()
+        .env("OPENAI_BASE_URL", "http://unused.local")
+        .arg("--trust-cwd")
+        .arg("--var")
+        .arg("name=codex")
+        .arg("Hi {{name}}, fix {{issue}} in {{file}}")
+        .assert()
+        .failure()
+        .stderr(contains("undefined variable(s): file, issue"));
+}
@@ -129,7 +129,7 @@ fn exec_resume_last_appends_to_existing_file() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg(&prompt)
@@ -148,7 +148,7 @@ fn exec_resume_last_appends_to_existing_file() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg(&prompt2)
@@ -183,7 +183,7 @@ fn exec_resume_last_accepts_prompt_after_flag_in_json_mode() -> anyhow::Result<(
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg(&prompt)
@@ -202,7 +202,7 @@ fn exec_resume_last_accepts_prompt_after_flag_in_json_mode() -> anyhow::Result<(
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg("--json")
@@ -237,7 +237,7 @@ fn exec_resume_last_respects_cwd_filter_and_all_flag() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(dir_a.path())
         .arg(&prompt_a)
@@ -249,7 +249,7 @@ fn exec_resume_last_respects_cwd_filter_and_all_flag() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(dir_b.path())
         .arg(&prompt_b)
@@ -278,7 +278,7 @@ fn exec_resume_last_respects_cwd_filter_and_all_flag() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(dir_a.path())
         .arg("resume")
@@ -300,7 +300,7 @@ fn exec_resume_last_respects_cwd_filter_and_all_flag() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(dir_a.path())
         .arg("resume")
@@ -328,7 +328,7 @@ fn exec_resume_accepts_global_flags_after_subcommand() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("echo seed-resume-session")
         .assert()
         .success();
@@ -345,7 +345,7 @@ fn exec_resume_accepts_global_flags_after_subcommand() -> anyhow::Result<()> {
         .arg("--config")
         .arg("reasoning_level=xhigh")
         .arg("--dangerously-bypass-approvals-and-sandbox")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("echo resume-with-global-flags-after-subcommand")
         .assert()
         .success();
@@ -366,7 +366,7 @@ fn exec_resume_by_id_appends_to_existing_file() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg(&prompt)
@@ -389,7 +389,7 @@ fn exec_resume_by_id_appends_to_existing_file() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg(&prompt2)
@@ -422,7 +422,7 @@ fn exec_resume_preserves_cli_configuration_overrides() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("--sandbox")
         .arg("workspace-write")
         .arg("--model")
@@ -444,7 +444,7 @@ fn exec_resume_preserves_cli_configuration_overrides() -> anyhow::Result<()> {
         .cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("--sandbox")
         .arg("workspace-write")
         .arg("--model")
@@ -498,7 +498,7 @@ fn exec_resume_accepts_images_after_subcommand() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg(&prompt)
@@ -522,7 +522,7 @@ fn exec_resume_accepts_images_after_subcommand() -> anyhow::Result<()> {
     test.cmd()
         .env("CODEX_RS_SSE_FIXTURE", &fixture)
         .env("OPENAI_BASE_URL", "http://unused.local")
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("-C")
         .arg(&repo_root)
         .arg("resume")
@@ -23,7 +23,7 @@ async fn exits_non_zero_when_server_reports_error() -> anyhow::Result<()> {
     responses::mount_sse_once(&server, body).await;
 
     test.cmd_with_server(&server)
-        .arg("--skip-git-repo-check")
+        .arg("--trust-cwd")
         .arg("tell me something")
         .arg("--experimental-json")
         .assert()
@@ -31,3 +31,26 @@ async fn exits_non_zero_when_server_reports_error() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// A refusal is not a successful turn: `codex-exec` exits with its own code.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exits_with_refused_code_when_model_refuses() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp-1"),
+        responses::ev_refusal("msg-1", "I can't help with that."),
+        responses::ev_completed("resp-1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--trust-cwd")
+        .arg("do the questionable thing")
+        .assert()
+        .code(codex_exec::REFUSED_EXIT_CODE)
+        .stderr(predicates::str::contains("I can't help with that."));
+
+    Ok(())
+}
@@ -35,12 +35,18 @@ use nucleo::pattern::AtomKind;
 use nucleo::pattern::Pattern;
 
 mod cli;
+mod rank;
 
 pub use cli::Cli;
 
+use rank::ModifiedTimes;
+use rank::RERANK_POOL_FACTOR;
+use rank::Ranker;
+
 /// A single match result returned from the search.
 ///
-/// * `score` – Relevance score returned by `nucleo`.
+/// * `score` – Relevance score: the `nucleo` score adjusted for file-name
+///   matches, path depth, and recency (see [`FileSearchOptions`]).
 /// * `path`  – Path to the matched file (relative to the search directory).
 /// * `indices` – Optional list of character indices that matched the query.
 ///   These are only filled when the caller of [`run`] sets
@@ -93,6 +99,11 @@ pub struct FileSearchOptions {
     pub threads: NonZero<usize>,
     pub compute_indices: bool,
     pub respect_gitignore: bool,
+    /// Files recently mentioned by the user, boosted in the ranking. Relative
+    /// paths are matched against paths relative to their search root.
+    pub recent_paths: Vec<PathBuf>,
+    /// Boost files modified within the last day.
+    pub boost_recently_modified: bool,
 }
 
 impl Default for FileSearchOptions {
@@ -105,6 +116,8 @@ impl Default for FileSearchOptions {
             threads: NonZero::new(2).unwrap(),
             compute_indices: false,
             respect_gitignore: true,
+            recent_paths: Vec::new(),
+            boost_recently_modified: false,
         }
     }
 }
@@ -151,6 +164,17 @@ pub fn create_session(
     )
 }
 
+/// Like [`create_session`], but walks several directories at once. Each match
+/// reports the directory it was found under in [`FileMatch::root`]; excludes
+/// are resolved against the first directory.
+pub fn create_session_for_roots(
+    search_directories: Vec<PathBuf>,
+    options: FileSearchOptions,
+    reporter: Arc<dyn SessionReporter>,
+) -> anyhow::Result<FileSearchSession> {
+    create_session_inner(search_directories, options, reporter, None)
+}
+
 fn create_session_inner(
     search_directories: Vec<PathBuf>,
     options: FileSearchOptions,
@@ -163,6 +187,8 @@ fn create_session_inner(
         threads,
         compute_indices,
         respect_gitignore,
+        recent_paths,
+        boost_recently_modified,
     } = options;
 
     let Some(primary_search_directory) = search_directories.first() else {
@@ -191,6 +217,8 @@ fn create_session_inner(
         threads: threads.get(),
         compute_indices,
         respect_gitignore,
+        recent_paths,
+        boost_recently_modified,
         cancelled: cancelled.clone(),
         shutdown: Arc::new(AtomicBool::new(false)),
         reporter,
@@ -266,6 +294,7 @@ pub async fn run_main<T: Reporter>(
             threads,
             compute_indices,
             respect_gitignore: true,
+            ..Default::default()
         },
         None,
     )?;
@@ -344,6 +373,8 @@ struct SessionInner {
     threads: usize,
     compute_indices: bool,
     respect_gitignore: bool,
+    recent_paths: Vec<PathBuf>,
+    boost_recently_modified: bool,
     cancelled: Arc<AtomicBool>,
     shutdown: Arc<AtomicBool>,
     reporter: Arc<dyn SessionReporter>,
@@ -481,6 +512,7 @@ fn matcher_worker(
     let mut next_notify = never();
     let mut will_notify = false;
     let mut walk_complete = false;
+    let mut modified_times = ModifiedTimes::default();
 
     loop {
         select! {
@@ -525,32 +557,59 @@ fn matcher_worker(
                 let status = nucleo.tick(TICK_TIMEOUT_MS);
                 if status.changed {
                     let snapshot = nucleo.snapshot();
-                    let limit = inner.limit.min(snapshot.matched_item_count() as usize);
+                    // Re-rank a pool larger than `limit` so boosted files can
+                    // surface even when `nucleo` alone would have cut them.
+                    let pool_size = inner
+                        .limit
+                        .saturating_mul(RERANK_POOL_FACTOR)
+                        .min(snapshot.matched_item_count() as usize);
                     let pattern = snapshot.pattern().column_pattern(0);
-                    let matches: Vec<_> = snapshot
+                    let mut ranker = Ranker::new(
+                        &last_query,
+                        &inner.recent_paths,
+                        inner.boost_recently_modified.then_some(&mut modified_times),
+                    );
+                    let mut candidates: Vec<RankedCandidate<'_>> = snapshot
                         .matches()
                         .iter()
-                        .take(limit)
+                        .take(pool_size)
                         .filter_map(|match_| {
                             let item = snapshot.get_item(match_.idx)?;
-                            let full_path = item.data.as_ref();
-                            let (root_idx, relative_path) = get_file_path(Path::new(full_path), &inner.search_directories)?;
-                            let indices = if let Some(indices_matcher) = indices_matcher.as_mut() {
-                                let mut idx_vec = Vec::<u32>::new();
-                                let haystack = item.matcher_columns[0].slice(..);
-                                let _ = pattern.indices(haystack, indices_matcher, &mut idx_vec);
-                                idx_vec.sort_unstable();
-                                idx_vec.dedup();
-                                Some(idx_vec)
-                            } else {
-                                None
+                            let full_path = Path::new(item.data.as_ref());
+                            let (root_idx, relative_path) = get_file_path(full_path, &inner.search_directories)?;
+                            Some(RankedCandidate {
+                                score: ranker.score(match_.score, Path::new(relative_path), full_path),
+                                item_idx: match_.idx,
+                                root_idx,
+                                relative_path,
+                            })
+                        })
+                        .collect();
+                    candidates.sort_by(cmp_by_score_desc_then_path_asc::<RankedCandidate<'_>, _, _>(
+                        |candidate| candidate.score,
+                        |candidate| candidate.relative_path,
+                    ));
+                    candidates.truncate(inner.limit);
+                    let matches: Vec<_> = candidates
+                        .into_iter()
+                        .map(|candidate| {
+                            let indices = match (indices_matcher.as_mut(), snapshot.get_item(candidate.item_idx)) {
+                                (Some(indices_matcher), Some(item)) => {
+                                    let mut idx_vec = Vec::<u32>::new();
+                                    let haystack = item.matcher_columns[0].slice(..);
+                                    let _ = pattern.indices(haystack, indices_matcher, &mut idx_vec);
+                                    idx_vec.sort_unstable();
+                                    idx_vec.dedup();
+                                    Some(idx_vec)
+                                }
+                                _ => None,
                             };
-                            Some(FileMatch {
-                                score: match_.score,
-                                path: PathBuf::from(relative_path),
-                                root: inner.search_directories[root_idx].clone(),
+                            FileMatch {
+                                score: candidate.score,
+                                path: PathBuf::from(candidate.relative_path),
+                                root: inner.search_directories[candidate.root_idx].clone(),
                                 indices,
-                            })
+                            }
                         })
                         .collect();
 
@@ -583,6 +642,13 @@ fn matcher_worker(
     Ok(())
 }
 
+struct RankedCandidate<'a> {
+    score: u32,
+    item_idx: u32,
+    root_idx: usize,
+    relative_path: &'a str,
+}
+
 #[derive(Default)]
 struct RunReporter {
     snapshot: RwLock<FileSearchSnapshot>,
@@ -895,6 +961,7 @@ mod tests {
             threads: NonZero::new(2).unwrap(),
             compute_indices: false,
             respect_gitignore: true,
+            ..Default::default()
         };
         let results =
             run("file-000", vec![dir.path().to_path_buf()], options, None).expect("run ok");
@@ -909,6 +976,54 @@ mod tests {
         );
     }
 
+    fn matched_paths(query: &str, root: &Path, recent_paths: Vec<PathBuf>) -> Vec<String> {
+        let options = FileSearchOptions {
+            recent_paths,
+            ..Default::default()
+        };
+        run(query, vec![root.to_path_buf()], options, None)
+            .expect("run ok")
+            .matches
+            .into_iter()
+            .map(|m| m.path.to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn run_orders_matches_for_representative_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        for path in [
+            "model/readme.md",
+            "src/model.rs",
+            "alpha/notes.txt",
+            "beta/notes.txt",
+        ] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        // A file name match outranks a directory name match.
+        assert_eq!(
+            matched_paths("model", dir.path(), Vec::new()),
+            vec!["src/model.rs".to_string(), "model/readme.md".to_string()]
+        );
+        // Equal matches fall back to path order...
+        assert_eq!(
+            matched_paths("notes", dir.path(), Vec::new()),
+            vec!["alpha/notes.txt".to_string(), "beta/notes.txt".to_string()]
+        );
+        // ...unless one of them was recently mentioned.
+        assert_eq!(
+            matched_paths(
+                "notes",
+                dir.path(),
+                vec![dir.path().join("beta").join("notes.txt")]
+            ),
+            vec!["beta/notes.txt".to_string(), "alpha/notes.txt".to_string()]
+        );
+    }
+
     #[test]
     fn cancel_exits_run() {
         let dir = create_temp_tree(200);
@@ -0,0 +1,210 @@
+//! Re-ranking applied on top of `nucleo` match scores.
+//!
+//! `nucleo` scores each path as a whole, so a query that happens to match a
+//! deeply nested directory name can outrank the file the user is looking for.
+//! The adjustments here favor matches in the file name, shallow paths, and
+//! files the user has recently touched.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+/// Number of `nucleo` matches considered per result slot when re-ranking.
+pub(crate) const RERANK_POOL_FACTOR: usize = 4;
+
+/// Bonus when the query is a prefix of the file name.
+const FILE_NAME_PREFIX_BONUS: u32 = 24;
+/// Bonus when the query starts at a word boundary inside the file name.
+const FILE_NAME_WORD_BOUNDARY_BONUS: u32 = 12;
+/// Penalty per directory between the search root and the file.
+const DEPTH_PENALTY: u32 = 2;
+const MAX_DEPTH_PENALTY: u32 = 20;
+/// Bonus for paths listed in [`crate::FileSearchOptions::recent_paths`].
+const RECENT_PATH_BONUS: u32 = 32;
+/// Bonus for files modified within [`RECENTLY_MODIFIED_WINDOW`].
+const RECENTLY_MODIFIED_BONUS: u32 = 16;
+const RECENTLY_MODIFIED_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Modification times of the files a session has ranked. The matcher re-ranks
+/// on every tick, so each file is stat'ed once per session instead.
+#[derive(Debug, Default)]
+pub(crate) struct ModifiedTimes(HashMap<PathBuf, Option<SystemTime>>);
+
+impl ModifiedTimes {
+    fn get(&mut self, path: &Path) -> Option<SystemTime> {
+        if let Some(modified) = self.0.get(path) {
+            return *modified;
+        }
+        let modified = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        self.0.insert(path.to_path_buf(), modified);
+        modified
+    }
+}
+
+pub(crate) struct Ranker<'a> {
+    query: String,
+    recent_paths: &'a [PathBuf],
+    /// Set when recently modified files are boosted.
+    modified_times: Option<&'a mut ModifiedTimes>,
+    now: SystemTime,
+}
+
+impl<'a> Ranker<'a> {
+    pub(crate) fn new(
+        query: &str,
+        recent_paths: &'a [PathBuf],
+        modified_times: Option<&'a mut ModifiedTimes>,
+    ) -> Self {
+        Self {
+            query: query.trim().to_lowercase(),
+            recent_paths,
+            modified_times,
+            now: SystemTime::now(),
+        }
+    }
+
+    /// Adjusts a `nucleo` score for a file at `relative_path` under its root.
+    pub(crate) fn score(&mut self, base: u32, relative_path: &Path, full_path: &Path) -> u32 {
+        let mut score = base.saturating_add(self.file_name_bonus(relative_path));
+        if self.is_recent(relative_path, full_path) {
+            score = score.saturating_add(RECENT_PATH_BONUS);
+        }
+        if self.was_recently_modified(full_path) {
+            score = score.saturating_add(RECENTLY_MODIFIED_BONUS);
+        }
+        score.saturating_sub(depth_penalty(relative_path))
+    }
+
+    fn file_name_bonus(&self, relative_path: &Path) -> u32 {
+        // Multi-atom queries are matched piecewise by nucleo; only whole-token
+        // queries get the file name bonus.
+        if self.query.is_empty() || self.query.contains(char::is_whitespace) {
+            return 0;
+        }
+        let Some(file_name) = relative_path.file_name() else {
+            return 0;
+        };
+        let file_name = file_name.to_string_lossy().to_lowercase();
+        if file_name.starts_with(&self.query) {
+            return FILE_NAME_PREFIX_BONUS;
+        }
+        let at_word_boundary = file_name.match_indices(&self.query).any(|(idx, _)| {
+            file_name[..idx]
+                .chars()
+                .next_back()
+                .is_some_and(|c| !c.is_alphanumeric())
+        });
+        if at_word_boundary {
+            FILE_NAME_WORD_BOUNDARY_BONUS
+        } else {
+            0
+        }
+    }
+
+    fn is_recent(&self, relative_path: &Path, full_path: &Path) -> bool {
+        self.recent_paths.iter().any(|recent| {
+            if recent.is_absolute() {
+                recent == full_path
+            } else {
+                recent == relative_path
+            }
+        })
+    }
+
+    fn was_recently_modified(&mut self, full_path: &Path) -> bool {
+        let now = self.now;
+        self.modified_times
+            .as_mut()
+            .and_then(|modified_times| modified_times.get(full_path))
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age <= RECENTLY_MODIFIED_WINDOW)
+    }
+}
+
+fn depth_penalty(relative_path: &Path) -> u32 {
+    let depth = relative_path.components().count().saturating_sub(1);
+    u32::try_from(depth)
+        .unwrap_or(u32::MAX)
+        .saturating_mul(DEPTH_PENALTY)
+        .min(MAX_DEPTH_PENALTY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn file_name_prefix_beats_word_boundary_beats_substring() {
+        let mut ranker = Ranker::new("main", &[], None);
+        let full = Path::new("/unused");
+
+        assert_eq!(
+            vec![
+                ranker.score(100, Path::new("main.rs"), full),
+                ranker.score(100, Path::new("app_main.rs"), full),
+                ranker.score(100, Path::new("domain.rs"), full),
+            ],
+            vec![124, 112, 100]
+        );
+    }
+
+    #[test]
+    fn deeper_paths_are_penalized_up_to_a_cap() {
+        let mut ranker = Ranker::new("zz", &[], None);
+        let full = Path::new("/unused");
+        let deep: PathBuf = std::iter::repeat_n("d", 30).collect::<PathBuf>().join("f");
+
+        assert_eq!(
+            vec![
+                ranker.score(100, Path::new("f"), full),
+                ranker.score(100, Path::new("a/b/f"), full),
+                ranker.score(100, &deep, full),
+            ],
+            vec![100, 96, 80]
+        );
+    }
+
+    #[test]
+    fn recent_paths_match_relative_or_absolute() {
+        let repo = std::env::temp_dir().join("repo");
+        let other = std::env::temp_dir().join("other");
+        let recent = vec![PathBuf::from("src/lib.rs"), repo.join("README.md")];
+        let mut ranker = Ranker::new("zz", &recent, None);
+
+        assert_eq!(
+            vec![
+                ranker.score(100, Path::new("src/lib.rs"), &repo.join("src/lib.rs")),
+                ranker.score(100, Path::new("README.md"), &repo.join("README.md")),
+                ranker.score(100, Path::new("README.md"), &other.join("README.md")),
+            ],
+            vec![130, 132, 100]
+        );
+    }
+
+    #[test]
+    fn modification_times_are_read_once_per_session() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "").expect("write");
+        let mut modified_times = ModifiedTimes::default();
+
+        let first = Ranker::new("zz", &[], Some(&mut modified_times)).score(
+            100,
+            Path::new("main.rs"),
+            &file,
+        );
+        std::fs::remove_file(&file).expect("remove");
+        let second = Ranker::new("zz", &[], Some(&mut modified_times)).score(
+            100,
+            Path::new("main.rs"),
+            &file,
+        );
+
+        assert_eq!((first, second), (116, 116));
+    }
+}
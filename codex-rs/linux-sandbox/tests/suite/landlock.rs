@@ -6,6 +6,7 @@ use codex_core::error::SandboxErr;
 use codex_core::exec::ExecParams;
 use codex_core::exec::process_exec_tool_call;
 use codex_core::exec_env::create_env;
+use codex_core::exec_limits::ExecLimits;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol_config_types::WindowsSandboxLevel;
 use codex_core::sandboxing::SandboxPermissions;
@@ -34,7 +35,7 @@ const NETWORK_TIMEOUT_MS: u64 = 10_000;
 
 fn create_env_from_core_vars() -> HashMap<String, String> {
     let policy = ShellEnvironmentPolicy::default();
-    create_env(&policy)
+    create_env(&policy, None)
 }
 
 #[expect(clippy::print_stdout)]
@@ -64,6 +65,7 @@ async fn run_cmd_output(
         windows_sandbox_level: WindowsSandboxLevel::Disabled,
         justification: None,
         arg0: None,
+        limits: ExecLimits::default(),
     };
 
     let sandbox_policy = SandboxPolicy::WorkspaceWrite {
@@ -182,6 +184,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
         windows_sandbox_level: WindowsSandboxLevel::Disabled,
         justification: None,
         arg0: None,
+        limits: ExecLimits::default(),
     };
 
     let sandbox_policy = SandboxPolicy::new_read_only_policy();
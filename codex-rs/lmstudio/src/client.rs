@@ -3,6 +3,17 @@ use codex_core::config::Config;
 use std::io;
 use std::path::Path;
 
+/// Context length and capabilities LM Studio reports for a single model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LMStudioModelDetails {
+    /// Context length the model is loaded with, or the longest it supports
+    /// when it is not loaded, in tokens.
+    pub context_window: Option<i64>,
+    /// Whether the model was trained for tool use. `None` when the server is
+    /// too old to report capabilities.
+    pub supports_tools: Option<bool>,
+}
+
 #[derive(Clone)]
 pub struct LMStudioClient {
     client: reqwest::Client,
@@ -123,6 +134,47 @@ impl LMStudioClient {
         }
     }
 
+    /// Fetch the context length and capabilities of `model` from LM Studio's
+    /// REST API, returning `None` when the server does not know the model.
+    pub async fn fetch_model_details(
+        &self,
+        model: &str,
+    ) -> io::Result<Option<LMStudioModelDetails>> {
+        let base_url = self.base_url.trim_end_matches('/');
+        let host_root = base_url.strip_suffix("/v1").unwrap_or(base_url);
+        let url = format!("{host_root}/api/v0/models/{model}");
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| io::Error::other(format!("Request failed: {e}")))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(io::Error::other(format!(
+                "Failed to fetch details for model `{model}`: {}",
+                response.status()
+            )));
+        }
+        let json: serde_json::Value = response.json().await.map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("JSON parse error: {e}"))
+        })?;
+        let context_window = json["loaded_context_length"]
+            .as_i64()
+            .or_else(|| json["max_context_length"].as_i64());
+        let supports_tools = json["capabilities"].as_array().map(|capabilities| {
+            capabilities
+                .iter()
+                .any(|capability| capability.as_str() == Some("tool_use"))
+        });
+        Ok(Some(LMStudioModelDetails {
+            context_window,
+            supports_tools,
+        }))
+    }
+
     // Find lms, checking fallback paths if not in PATH
     fn find_lms() -> std::io::Result<String> {
         Self::find_lms_with_home_dir(None)
@@ -240,6 +292,81 @@ mod tests {
         assert!(models.contains(&"openai/gpt-oss-20b".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_fetch_model_details() {
+        if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+            tracing::info!(
+                "{} is set; skipping test_fetch_model_details",
+                codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
+            );
+            return;
+        }
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/api/v0/models/qwen/qwen3-8b"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "id": "qwen/qwen3-8b",
+                        "state": "loaded",
+                        "max_context_length": 40960,
+                        "loaded_context_length": 8192,
+                        "capabilities": ["tool_use"]
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/api/v0/models/plain-model"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "id": "plain-model",
+                        "state": "not-loaded",
+                        "max_context_length": 4096,
+                        "capabilities": []
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let client = LMStudioClient::from_host_root(format!("{}/v1", server.uri()));
+        assert_eq!(
+            client
+                .fetch_model_details("qwen/qwen3-8b")
+                .await
+                .expect("fetch details"),
+            Some(LMStudioModelDetails {
+                context_window: Some(8192),
+                supports_tools: Some(true),
+            })
+        );
+        assert_eq!(
+            client
+                .fetch_model_details("plain-model")
+                .await
+                .expect("fetch details"),
+            Some(LMStudioModelDetails {
+                context_window: Some(4096),
+                supports_tools: Some(false),
+            })
+        );
+        assert_eq!(
+            client
+                .fetch_model_details("missing")
+                .await
+                .expect("fetch details"),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_fetch_models_no_data_array() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
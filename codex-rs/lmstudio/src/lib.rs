@@ -1,6 +1,7 @@
 mod client;
 
 pub use client::LMStudioClient;
+pub use client::LMStudioModelDetails;
 use codex_core::config::Config;
 
 /// Default OSS model to use when `--oss` is passed without an explicit `-m`.
@@ -44,3 +45,31 @@ pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
 
     Ok(())
 }
+
+/// Check that `model` is available on the local LM Studio server and return
+/// its context length and capabilities.
+///
+/// Fails with a hint to start LM Studio when the server is unreachable, and
+/// with the list of downloaded models when `model` is not one of them.
+pub async fn fetch_local_model_details(
+    config: &Config,
+    model: &str,
+) -> std::io::Result<LMStudioModelDetails> {
+    let client = LMStudioClient::try_from_provider(config).await?;
+    if let Some(details) = client.fetch_model_details(model).await? {
+        return Ok(details);
+    }
+
+    let installed = client.fetch_models().await.unwrap_or_default();
+    let installed = if installed.is_empty() {
+        "none".to_string()
+    } else {
+        installed.join(", ")
+    };
+    Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!(
+            "Model `{model}` is not available on the local LM Studio server (downloaded: {installed}). Download it with: `lms get {model}`"
+        ),
+    ))
+}
@@ -236,6 +236,8 @@ async fn run_codex_tool_session_inner(
                         reason: _,
                         proposed_execpolicy_amendment: _,
                         parsed_cmd,
+                        cwd_outside_workspace: _,
+                        cwd_writable: _,
                     }) => {
                         handle_exec_approval_request(
                             command,
@@ -265,6 +267,15 @@ async fn run_codex_tool_session_inner(
                         outgoing.send_response(request_id.clone(), result).await;
                         break;
                     }
+                    EventMsg::TurnRefused(refused) => {
+                        let result = create_call_tool_result_with_thread_id(
+                            thread_id,
+                            refused.reason,
+                            Some(true),
+                        );
+                        outgoing.send_response(request_id.clone(), result).await;
+                        break;
+                    }
                     EventMsg::Warning(_) => {
                         continue;
                     }
@@ -278,12 +289,14 @@ async fn run_codex_tool_session_inner(
                         reason,
                         grant_root,
                         changes,
+                        dirty_paths,
                     }) => {
                         handle_patch_approval_request(
                             call_id,
                             reason,
                             grant_root,
                             changes,
+                            dirty_paths,
                             outgoing.clone(),
                             thread.clone(),
                             request_id.clone(),
@@ -294,7 +307,9 @@ async fn run_codex_tool_session_inner(
                         .await;
                         continue;
                     }
-                    EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }) => {
+                    EventMsg::TurnComplete(TurnCompleteEvent {
+                        last_agent_message, ..
+                    }) => {
                         let text = match last_agent_message {
                             Some(msg) => msg,
                             None => "".to_string(),
@@ -320,7 +335,9 @@ async fn run_codex_tool_session_inner(
                     EventMsg::AgentReasoningDelta(_) => {
                         // TODO: think how we want to support this in the MCP
                     }
-                    EventMsg::McpStartupUpdate(_) | EventMsg::McpStartupComplete(_) => {
+                    EventMsg::McpStartupUpdate(_)
+                    | EventMsg::McpStartupComplete(_)
+                    | EventMsg::SessionPrewarmed(_) => {
                         // Ignored in MCP tool runner.
                     }
                     EventMsg::AgentMessage(AgentMessageEvent { .. }) => {
@@ -335,25 +352,48 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::McpToolCallBegin(_)
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
+                    | EventMsg::ListToolsResponse(_)
+                    | EventMsg::TokenBreakdown(_)
+                    | EventMsg::ApprovalGrantsRestored(_)
+                    | EventMsg::SessionApprovalsRevoked(_)
+                    | EventMsg::ApprovalResolved(_)
                     | EventMsg::ListCustomPromptsResponse(_)
+                    | EventMsg::GhostSnapshotsResponse(_)
+                    | EventMsg::GhostSnapshotFileResponse(_)
                     | EventMsg::ListSkillsResponse(_)
                     | EventMsg::ExecCommandBegin(_)
+                    | EventMsg::ExecCommandSpawn(_)
                     | EventMsg::TerminalInteraction(_)
                     | EventMsg::ExecCommandOutputDelta(_)
                     | EventMsg::ExecCommandEnd(_)
                     | EventMsg::BackgroundEvent(_)
+                    | EventMsg::HookBegin(_)
+                    | EventMsg::HookEnd(_)
                     | EventMsg::StreamError(_)
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
                     | EventMsg::TurnDiff(_)
+                    | EventMsg::TurnFiles(_)
+                    | EventMsg::InjectionSuspected(_)
+                    | EventMsg::ToolArgumentsRepaired(_)
+                    | EventMsg::StalePatchContext(_)
+                    | EventMsg::StalePatchRetry(_)
+                    | EventMsg::ExternalFilesChanged(_)
+                    | EventMsg::OutputTruncated(_)
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
+                    | EventMsg::HistoryCleared(_)
                     | EventMsg::PlanUpdate(_)
                     | EventMsg::TurnAborted(_)
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
                     | EventMsg::ViewImageToolCall(_)
+                    | EventMsg::ImageDownscaled(_)
+                    | EventMsg::FileAttached(_)
+                    | EventMsg::PromptQueuedOffline(_)
+                    | EventMsg::SteerDelivered(_)
+                    | EventMsg::ConnectivityRestored(_)
                     | EventMsg::RawResponseItem(_)
                     | EventMsg::EnteredReviewMode(_)
                     | EventMsg::ItemStarted(_)
@@ -364,6 +404,10 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::SkillsUpdateAvailable
                     | EventMsg::UndoStarted(_)
                     | EventMsg::UndoCompleted(_)
+                    | EventMsg::CommitDraft(_)
+                    | EventMsg::CommandExplanation(_)
+                    | EventMsg::WorkspaceSummary(_)
+                    | EventMsg::CommitCompleted(_)
                     | EventMsg::ExitedReviewMode(_)
                     | EventMsg::RequestUserInput(_)
                     | EventMsg::DynamicToolCallRequest(_)
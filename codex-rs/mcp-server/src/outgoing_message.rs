@@ -238,6 +238,7 @@ mod tests {
     use codex_core::protocol::EventMsg;
     use codex_core::protocol::SandboxPolicy;
     use codex_core::protocol::SessionConfiguredEvent;
+    use codex_core::protocol::ToolToggles;
     use codex_protocol::ThreadId;
     use codex_protocol::openai_models::ReasoningEffort;
     use pretty_assertions::assert_eq;
@@ -269,6 +270,11 @@ mod tests {
                 history_entry_count: 1000,
                 initial_messages: None,
                 rollout_path: Some(rollout_file.path().to_path_buf()),
+                tool_toggles: ToolToggles::default(),
+                base_instructions_tokens: None,
+                unknown_model: None,
+                sampling: None,
+                startup: None,
             }),
         };
 
@@ -310,6 +316,11 @@ mod tests {
             history_entry_count: 1000,
             initial_messages: None,
             rollout_path: Some(rollout_file.path().to_path_buf()),
+            tool_toggles: ToolToggles::default(),
+            base_instructions_tokens: None,
+            unknown_model: None,
+            sampling: None,
+            startup: None,
         };
         let event = Event {
             id: "1".to_string(),
@@ -348,6 +359,11 @@ mod tests {
                 "history_log_id": session_configured_event.history_log_id,
                 "history_entry_count": session_configured_event.history_entry_count,
                 "rollout_path": rollout_file.path().to_path_buf(),
+                "tool_toggles": {
+                    "shell": true,
+                    "apply_patch": true,
+                    "mcp": true,
+                },
             }
         });
         assert_eq!(params.unwrap(), expected_params);
@@ -375,6 +391,11 @@ mod tests {
             history_entry_count: 1000,
             initial_messages: None,
             rollout_path: Some(rollout_file.path().to_path_buf()),
+            tool_toggles: ToolToggles::default(),
+            base_instructions_tokens: None,
+            unknown_model: None,
+            sampling: None,
+            startup: None,
         };
         let event = Event {
             id: "1".to_string(),
@@ -414,6 +435,11 @@ mod tests {
                 "history_log_id": session_configured_event.history_log_id,
                 "history_entry_count": session_configured_event.history_entry_count,
                 "rollout_path": rollout_file.path().to_path_buf(),
+                "tool_toggles": {
+                    "shell": true,
+                    "apply_patch": true,
+                    "mcp": true,
+                },
             }
         });
         assert_eq!(params.unwrap(), expected_params);
@@ -49,6 +49,7 @@ pub(crate) async fn handle_patch_approval_request(
     reason: Option<String>,
     grant_root: Option<PathBuf>,
     changes: HashMap<PathBuf, FileChange>,
+    dirty_paths: Vec<PathBuf>,
     outgoing: Arc<OutgoingMessageSender>,
     codex: Arc<CodexThread>,
     request_id: RequestId,
@@ -60,6 +61,12 @@ pub(crate) async fn handle_patch_approval_request(
     if let Some(r) = &reason {
         message_lines.push(r.clone());
     }
+    for path in &dirty_paths {
+        message_lines.push(format!(
+            "{} has uncommitted changes that this patch will modify.",
+            path.display()
+        ));
+    }
     message_lines.push("Allow Codex to apply proposed code changes?".to_string());
 
     let params = PatchApprovalElicitRequestParams {
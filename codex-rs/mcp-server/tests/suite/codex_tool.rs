@@ -401,7 +401,14 @@ async fn codex_tool_passes_base_instructions() -> anyhow::Result<()> {
         })
     );
 
-    let requests = server.received_requests().await.unwrap();
+    // Skip the reachability probe sent before the turn.
+    let requests: Vec<_> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|request| request.method == "POST")
+        .collect();
     let request = requests[0].body_json::<serde_json::Value>()?;
     let instructions = request["messages"][0]["content"].as_str().unwrap();
     assert!(instructions.starts_with("You are a helpful assistant."));
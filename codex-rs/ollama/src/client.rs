@@ -6,6 +6,7 @@ use serde_json::Value as JsonValue;
 use std::collections::VecDeque;
 use std::io;
 
+use crate::parser::model_details_from_value;
 use crate::parser::pull_events_from_value;
 use crate::pull::PullEvent;
 use crate::pull::PullProgressReporter;
@@ -18,6 +19,16 @@ use codex_core::config::Config;
 
 const OLLAMA_CONNECTION_ERROR: &str = "No running Ollama server detected. Start it with: `ollama serve` (after installing). Install instructions: https://github.com/ollama/ollama?tab=readme-ov-file#ollama";
 
+/// Capabilities reported by `/api/show` for a single model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OllamaModelDetails {
+    /// Context length the server runs the model with, in tokens.
+    pub context_window: Option<i64>,
+    /// Whether the model accepts tool definitions. `None` when the server is
+    /// too old to report capabilities.
+    pub supports_tools: Option<bool>,
+}
+
 /// Client for interacting with a local Ollama instance.
 pub struct OllamaClient {
     client: reqwest::Client,
@@ -126,6 +137,30 @@ impl OllamaClient {
         Ok(names)
     }
 
+    /// Fetch the context length and capabilities of `model`, returning `None`
+    /// when the server does not know the model.
+    pub async fn fetch_model_details(&self, model: &str) -> io::Result<Option<OllamaModelDetails>> {
+        let show_url = format!("{}/api/show", self.host_root.trim_end_matches('/'));
+        let resp = self
+            .client
+            .post(show_url)
+            .json(&serde_json::json!({ "model": model }))
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            return Err(io::Error::other(format!(
+                "Ollama returned HTTP {} for model `{model}`",
+                resp.status()
+            )));
+        }
+        let val = resp.json::<JsonValue>().await.map_err(io::Error::other)?;
+        Ok(Some(model_details_from_value(&val)))
+    }
+
     /// Query the server for its version string, returning `None` when unavailable.
     pub async fn fetch_version(&self) -> io::Result<Option<Version>> {
         let version_url = format!("{}/api/version", self.host_root.trim_end_matches('/'));
@@ -411,4 +446,50 @@ mod tests {
             .expect("expected error");
         assert_eq!(OLLAMA_CONNECTION_ERROR, err.to_string());
     }
+
+    #[tokio::test]
+    async fn test_fetch_model_details() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/show"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({ "model": "qwen3:8b" }),
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "model_info": { "qwen3.context_length": 40960 },
+                        "capabilities": ["completion", "tools"]
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/show"))
+            .respond_with(wiremock::ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = OllamaClient::from_host_root(server.uri());
+        assert_eq!(
+            client
+                .fetch_model_details("qwen3:8b")
+                .await
+                .expect("fetch details"),
+            Some(OllamaModelDetails {
+                context_window: Some(40960),
+                supports_tools: Some(true),
+            })
+        );
+        assert_eq!(
+            client
+                .fetch_model_details("missing")
+                .await
+                .expect("fetch details"),
+            None
+        );
+    }
 }
@@ -4,6 +4,7 @@ mod pull;
 mod url;
 
 pub use client::OllamaClient;
+pub use client::OllamaModelDetails;
 use codex_core::ModelProviderInfo;
 use codex_core::WireApi;
 use codex_core::config::Config;
@@ -54,6 +55,34 @@ pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Check that `model` is available on the Ollama server behind `provider` and
+/// return its context length and capabilities.
+///
+/// Fails with a hint to start Ollama when the server is unreachable, and with
+/// the list of installed models when `model` is not one of them.
+pub async fn fetch_local_model_details(
+    provider: &ModelProviderInfo,
+    model: &str,
+) -> std::io::Result<OllamaModelDetails> {
+    let client = crate::OllamaClient::try_from_provider(provider).await?;
+    if let Some(details) = client.fetch_model_details(model).await? {
+        return Ok(details);
+    }
+
+    let installed = client.fetch_models().await.unwrap_or_default();
+    let installed = if installed.is_empty() {
+        "none".to_string()
+    } else {
+        installed.join(", ")
+    };
+    Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!(
+            "Model `{model}` is not available on the local Ollama server (installed: {installed}). Download it with: `ollama pull {model}`"
+        ),
+    ))
+}
+
 fn min_responses_version() -> Version {
     Version::new(0, 13, 4)
 }
@@ -90,6 +119,35 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[tokio::test]
+    async fn test_fetch_local_model_details_lists_installed_models_when_missing() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/api/tags"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+                serde_json::json!({ "models": [ {"name": "llama3.2:3b"}, {"name": "mistral"} ] })
+                    .to_string(),
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/show"))
+            .respond_with(wiremock::ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let provider = codex_core::create_oss_provider_with_base_url(&server.uri(), WireApi::Chat);
+
+        let err = fetch_local_model_details(&provider, "qwen3:8b")
+            .await
+            .expect_err("missing model should fail");
+
+        assert_eq!(
+            err.to_string(),
+            "Model `qwen3:8b` is not available on the local Ollama server (installed: llama3.2:3b, mistral). Download it with: `ollama pull qwen3:8b`"
+        );
+    }
+
     #[test]
     fn test_wire_api_for_version_dev_zero_keeps_responses() {
         assert_eq!(
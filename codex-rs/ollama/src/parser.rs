@@ -1,5 +1,6 @@
 use serde_json::Value as JsonValue;
 
+use crate::client::OllamaModelDetails;
 use crate::pull::PullEvent;
 
 // Convert a single JSON object representing a pull update into one or more events.
@@ -28,12 +29,91 @@ pub(crate) fn pull_events_from_value(value: &JsonValue) -> Vec<PullEvent> {
     events
 }
 
+// Extract the context window and tool support from an `/api/show` response.
+pub(crate) fn model_details_from_value(value: &JsonValue) -> OllamaModelDetails {
+    // `num_ctx` in the model's parameters is what the server actually runs
+    // with; the trained context length in `model_info` is only an upper bound.
+    let num_ctx = value
+        .get("parameters")
+        .and_then(JsonValue::as_str)
+        .and_then(|parameters| {
+            parameters.lines().find_map(|line| {
+                let mut parts = line.split_whitespace();
+                let (Some("num_ctx"), Some(value)) = (parts.next(), parts.next()) else {
+                    return None;
+                };
+                value.parse::<i64>().ok()
+            })
+        });
+    let context_length = value
+        .get("model_info")
+        .and_then(JsonValue::as_object)
+        .and_then(|info| {
+            info.iter()
+                .find(|(key, _)| key.ends_with(".context_length"))
+                .and_then(|(_, value)| value.as_i64())
+        });
+    let supports_tools =
+        value
+            .get("capabilities")
+            .and_then(JsonValue::as_array)
+            .map(|capabilities| {
+                capabilities
+                    .iter()
+                    .any(|capability| capability.as_str() == Some("tools"))
+            });
+    OllamaModelDetails {
+        context_window: num_ctx.or(context_length),
+        supports_tools,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
 
     use super::*;
 
+    #[test]
+    fn test_model_details_prefers_num_ctx_over_trained_length() {
+        let v: JsonValue = serde_json::json!({
+            "parameters": "stop \"<|end|>\"\nnum_ctx 8192",
+            "model_info": {
+                "general.architecture": "llama",
+                "llama.context_length": 131072
+            },
+            "capabilities": ["completion", "tools"]
+        });
+        assert_eq!(
+            model_details_from_value(&v),
+            OllamaModelDetails {
+                context_window: Some(8192),
+                supports_tools: Some(true),
+            }
+        );
+
+        let v2: JsonValue = serde_json::json!({
+            "model_info": { "gemma3.context_length": 32768 },
+            "capabilities": ["completion"]
+        });
+        assert_eq!(
+            model_details_from_value(&v2),
+            OllamaModelDetails {
+                context_window: Some(32768),
+                supports_tools: Some(false),
+            }
+        );
+
+        assert_eq!(
+            model_details_from_value(&serde_json::json!({})),
+            OllamaModelDetails {
+                context_window: None,
+                supports_tools: None,
+            }
+        );
+    }
+
     #[test]
     fn test_pull_events_decoder_status_and_success() {
         let v: JsonValue = serde_json::json!({"status":"verifying"});
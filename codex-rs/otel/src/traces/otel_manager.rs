@@ -479,7 +479,9 @@ impl OtelManager {
             ResponseEvent::OutputItemDone(item) => OtelManager::responses_item_type(item),
             ResponseEvent::OutputItemAdded(item) => OtelManager::responses_item_type(item),
             ResponseEvent::Completed { .. } => "completed".into(),
+            ResponseEvent::Incomplete { .. } => "incomplete".into(),
             ResponseEvent::OutputTextDelta(_) => "text_delta".into(),
+            ResponseEvent::Refusal(_) => "refusal".into(),
             ResponseEvent::ReasoningSummaryDelta { .. } => "reasoning_summary_delta".into(),
             ResponseEvent::ReasoningContentDelta { .. } => "reasoning_content_delta".into(),
             ResponseEvent::ReasoningSummaryPartAdded { .. } => {
@@ -488,6 +490,8 @@ impl OtelManager {
             ResponseEvent::ServerReasoningIncluded(_) => "server_reasoning_included".into(),
             ResponseEvent::RateLimits(_) => "rate_limits".into(),
             ResponseEvent::ModelsEtag(_) => "models_etag".into(),
+            ResponseEvent::ServiceTier(_) => "service_tier".into(),
+            ResponseEvent::SystemFingerprint(_) => "system_fingerprint".into(),
         }
     }
 
@@ -503,7 +507,7 @@ impl OtelManager {
             ResponseItem::WebSearchCall { .. } => "web_search_call".into(),
             ResponseItem::GhostSnapshot { .. } => "ghost_snapshot".into(),
             ResponseItem::Compaction { .. } => "compaction".into(),
-            ResponseItem::Other => "other".into(),
+            ResponseItem::Other(_) => "other".into(),
         }
     }
 }
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 
 use crate::parse_command::ParsedCommand;
 use crate::protocol::FileChange;
+use crate::protocol::ReviewDecision;
 use mcp_types::RequestId;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -57,6 +58,13 @@ pub struct ExecApprovalRequestEvent {
     #[ts(optional)]
     pub proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     pub parsed_cmd: Vec<ParsedCommand>,
+    /// Whether `cwd` is outside the session's working directory and its
+    /// workspace roots.
+    #[serde(default)]
+    pub cwd_outside_workspace: bool,
+    /// Whether the sandbox lets the command write to `cwd`.
+    #[serde(default)]
+    pub cwd_writable: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -92,4 +100,56 @@ pub struct ApplyPatchApprovalRequestEvent {
     /// When set, the agent is asking the user to allow writes under this root for the remainder of the session.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grant_root: Option<PathBuf>,
+    /// Paths in `changes` that already had uncommitted changes in git (modified,
+    /// staged, or untracked) when the patch was proposed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dirty_paths: Vec<PathBuf>,
+}
+
+/// Which kind of request an [`ApprovalResolvedEvent`] answers.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalKind {
+    Exec,
+    Patch,
+}
+
+/// How an approval request came to be resolved.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalResolver {
+    /// Someone answered the request.
+    User,
+    /// Nobody answered within `approval_timeout`.
+    Timeout,
+    /// The turn ended before anyone answered.
+    TurnEnded,
+}
+
+/// The outcome of an exec or patch approval request, together with what was
+/// asked. Unlike the requests themselves, these are kept in the rollout so a
+/// session can be audited after the fact.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct ApprovalResolvedEvent {
+    /// Call id of the request this resolves.
+    pub call_id: String,
+    pub turn_id: String,
+    pub kind: ApprovalKind,
+    /// The command, for exec approvals.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+    /// The files the patch touches, for patch approvals.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<PathBuf>,
+    pub cwd: PathBuf,
+    /// Reason given with the request, with sensitive strings redacted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub decision: ReviewDecision,
+    pub resolved_by: ApprovalResolver,
+    /// Email of the signed-in ChatGPT account, when there is one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// RFC 3339 time at which the decision was made.
+    pub resolved_at: String,
 }
@@ -49,6 +49,39 @@ pub enum Verbosity {
     High,
 }
 
+/// Processing tier requested from OpenAI via `service_tier`. `flex` is
+/// cheaper but slower; `priority` is faster but costs more.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Display, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ServiceTier {
+    Auto,
+    Default,
+    Flex,
+    Priority,
+}
+
+/// Sampling controls forwarded to providers that accept them. Unset fields
+/// leave the provider's defaults in place.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default, JsonSchema, TS)]
+pub struct SamplingParameters {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub seed: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub temperature: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub top_p: Option<f64>,
+}
+
+impl SamplingParameters {
+    pub fn is_empty(&self) -> bool {
+        self.seed.is_none() && self.temperature.is_none() && self.top_p.is_none()
+    }
+}
+
 #[derive(
     Deserialize, Debug, Clone, Copy, PartialEq, Default, Serialize, Display, JsonSchema, TS,
 )]
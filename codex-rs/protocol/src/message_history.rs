@@ -9,3 +9,11 @@ pub struct HistoryEntry {
     pub ts: u64,
     pub text: String,
 }
+
+/// Identifies a version of the history file and how many entries it holds.
+/// Offsets are only meaningful together with the `log_id` they came from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+pub struct HistoryLogMetadata {
+    pub log_id: u64,
+    pub entry_count: usize,
+}
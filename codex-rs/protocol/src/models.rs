@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use codex_utils_image::load_and_resize_to_fit;
+use codex_utils_image::ImageLimits;
+use codex_utils_image::load_and_resize_with_limits;
 use mcp_types::CallToolResult;
 use mcp_types::ContentBlock;
 use serde::Deserialize;
@@ -69,6 +70,8 @@ pub enum ResponseInputItem {
 pub enum ContentItem {
     InputText { text: String },
     InputImage { image_url: String },
+    /// A file uploaded to the provider, referenced by its file id.
+    InputFile { file_id: String },
     OutputText { text: String },
 }
 
@@ -165,13 +168,19 @@ pub enum ResponseItem {
     // Generated by the harness but considered exactly as a model response.
     GhostSnapshot {
         ghost_commit: GhostCommit,
+        /// Session turn (1-based) the snapshot was taken at the start of.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        turn: Option<u64>,
     },
     #[serde(alias = "compaction_summary")]
     Compaction {
         encrypted_content: String,
     },
-    #[serde(other)]
-    Other,
+    /// An item type this version does not know, kept as the raw JSON so it
+    /// survives rollouts written by a newer Codex. Never sent to the model.
+    #[serde(untagged)]
+    Other(serde_json::Value),
 }
 
 pub const BASE_INSTRUCTIONS_DEFAULT: &str = include_str!("prompts/base_instructions/default.md");
@@ -490,6 +499,70 @@ pub fn is_image_close_tag_text(text: &str) -> bool {
     text == IMAGE_CLOSE_TAG
 }
 
+const FILE_OPEN_TAG_PREFIX: &str = "<file path=\"";
+const FILE_OPEN_TAG_SUFFIX: &str = "\">";
+const FILE_CLOSE_TAG: &str = "</file>";
+
+pub fn file_open_tag_text(path: &Path) -> String {
+    format!(
+        "{FILE_OPEN_TAG_PREFIX}{}{FILE_OPEN_TAG_SUFFIX}",
+        path.display()
+    )
+}
+
+pub fn file_close_tag_text() -> String {
+    FILE_CLOSE_TAG.to_string()
+}
+
+/// The path in the `<file path="...">` tag on the first line of `text`, which
+/// starts both inline and uploaded attachments.
+pub fn file_tag_path(text: &str) -> Option<&str> {
+    text.lines()
+        .next()?
+        .strip_prefix(FILE_OPEN_TAG_PREFIX)?
+        .strip_suffix(FILE_OPEN_TAG_SUFFIX)
+}
+
+pub fn is_file_close_tag_text(text: &str) -> bool {
+    text == FILE_CLOSE_TAG
+}
+
+/// The contents of the file at `path` as a single inline text item, or a
+/// note explaining why they could not be attached.
+pub fn local_file_content_items(path: &Path) -> Vec<ContentItem> {
+    let text = match std::fs::read(path) {
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(contents) if !contents.contains('\0') => format!(
+                "{}\n{contents}\n{FILE_CLOSE_TAG}",
+                file_open_tag_text(path)
+            ),
+            _ => format!(
+                "File at `{}` is not a text file and cannot be attached inline.",
+                path.display()
+            ),
+        },
+        Err(err) => format!(
+            "File at `{}` could not be attached: {err}",
+            path.display()
+        ),
+    };
+    vec![ContentItem::InputText { text }]
+}
+
+/// The file at `path`, uploaded to the provider as `file_id`, wrapped in the
+/// same tags as an inline attachment.
+pub fn uploaded_file_content_items(path: &Path, file_id: String) -> Vec<ContentItem> {
+    vec![
+        ContentItem::InputText {
+            text: file_open_tag_text(path),
+        },
+        ContentItem::InputFile { file_id },
+        ContentItem::InputText {
+            text: file_close_tag_text(),
+        },
+    ]
+}
+
 fn invalid_image_error_placeholder(
     path: &std::path::Path,
     error: impl std::fmt::Display,
@@ -517,7 +590,17 @@ pub fn local_image_content_items_with_label_number(
     path: &std::path::Path,
     label_number: Option<usize>,
 ) -> Vec<ContentItem> {
-    match load_and_resize_to_fit(path) {
+    local_image_content_items_with_limits(path, label_number, ImageLimits::default())
+}
+
+/// Like [`local_image_content_items_with_label_number`], but downscales the
+/// image to `limits` instead of the default bounds.
+pub fn local_image_content_items_with_limits(
+    path: &std::path::Path,
+    label_number: Option<usize>,
+    limits: ImageLimits,
+) -> Vec<ContentItem> {
+    match load_and_resize_with_limits(path, limits) {
         Ok(image) => {
             let mut items = Vec::with_capacity(3);
             if let Some(label_number) = label_number {
@@ -536,7 +619,12 @@ pub fn local_image_content_items_with_label_number(
             items
         }
         Err(err) => {
-            if matches!(&err, ImageProcessingError::Read { .. }) {
+            if matches!(
+                &err,
+                ImageProcessingError::Read { .. }
+                    | ImageProcessingError::UnsupportedFormat { .. }
+                    | ImageProcessingError::TooLarge { .. }
+            ) {
                 vec![local_image_error_placeholder(path, &err)]
             } else if err.is_invalid_image() {
                 vec![invalid_image_error_placeholder(path, &err)]
@@ -654,6 +742,25 @@ pub enum ReasoningItemContent {
 
 impl From<Vec<UserInput>> for ResponseInputItem {
     fn from(items: Vec<UserInput>) -> Self {
+        Self::from_user_input(items, ImageLimits::default())
+    }
+}
+
+impl ResponseInputItem {
+    /// Build the user message for `items`, downscaling local images to
+    /// `image_limits`.
+    pub fn from_user_input(items: Vec<UserInput>, image_limits: ImageLimits) -> Self {
+        Self::from_user_input_with_uploads(items, image_limits, &HashMap::new())
+    }
+
+    /// Like [`ResponseInputItem::from_user_input`], but local files whose path
+    /// is in `uploaded_files` are referenced by the file id they were uploaded
+    /// as instead of being inlined.
+    pub fn from_user_input_with_uploads(
+        items: Vec<UserInput>,
+        image_limits: ImageLimits,
+        uploaded_files: &HashMap<std::path::PathBuf, String>,
+    ) -> Self {
         let mut image_index = 0;
         Self::Message {
             role: "user".to_string(),
@@ -672,8 +779,16 @@ impl From<Vec<UserInput>> for ResponseInputItem {
                     ],
                     UserInput::LocalImage { path } => {
                         image_index += 1;
-                        local_image_content_items_with_label_number(&path, Some(image_index))
+                        local_image_content_items_with_limits(
+                            &path,
+                            Some(image_index),
+                            image_limits,
+                        )
                     }
+                    UserInput::LocalFile { path } => match uploaded_files.get(&path) {
+                        Some(file_id) => uploaded_file_content_items(&path, file_id.clone()),
+                        None => local_file_content_items(&path),
+                    },
                     UserInput::Skill { .. } | UserInput::Mention { .. } => Vec::new(), // Tool bodies are injected later in core
                 })
                 .collect::<Vec<ContentItem>>(),
@@ -700,6 +815,10 @@ pub struct ShellToolCallParams {
     pub prefix_rule: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub justification: Option<String>,
+    /// Tighter or looser resource limits for this command than the configured defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub limits: Option<ExecLimitOverrides>,
 }
 
 /// If the `name` of a `ResponseItem::FunctionCall` is `shell_command`, the
@@ -723,6 +842,25 @@ pub struct ShellCommandToolCallParams {
     pub prefix_rule: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub justification: Option<String>,
+    /// Tighter or looser resource limits for this command than the configured defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub limits: Option<ExecLimitOverrides>,
+}
+
+/// Per-command resource limits requested by the model. Unset fields fall back
+/// to the `[exec_limits]` configuration.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema, TS)]
+pub struct ExecLimitOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cpu_time_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub memory_mb: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub max_output_bytes: Option<u64>,
 }
 
 /// Responses API compatible content items that can be returned by a tool call.
@@ -1210,12 +1348,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn unknown_response_items_round_trip() -> Result<()> {
+        let raw = serde_json::json!({
+            "type": "future_widget",
+            "id": "fw_1",
+            "payload": {"nested": [1, 2, 3]}
+        });
+
+        let item: ResponseItem = serde_json::from_value(raw.clone())?;
+        assert_eq!(item, ResponseItem::Other(raw.clone()));
+        assert_eq!(serde_json::to_value(&item)?, raw);
+        Ok(())
+    }
+
     #[test]
     fn deserialize_shell_tool_call_params() -> Result<()> {
         let json = r#"{
             "command": ["ls", "-l"],
             "workdir": "/tmp",
-            "timeout": 1000
+            "timeout": 1000,
+            "limits": { "cpu_time_seconds": 30 }
         }"#;
 
         let params: ShellToolCallParams = serde_json::from_str(json)?;
@@ -1227,6 +1380,10 @@ mod tests {
                 sandbox_permissions: None,
                 prefix_rule: None,
                 justification: None,
+                limits: Some(ExecLimitOverrides {
+                    cpu_time_seconds: Some(30),
+                    ..Default::default()
+                }),
             },
             params
         );
@@ -1357,4 +1514,56 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn local_files_are_inlined_unless_uploaded() -> Result<()> {
+        let dir = tempdir()?;
+        let notes = dir.path().join("notes.md");
+        let spec = dir.path().join("spec.md");
+        std::fs::write(&notes, "remember the milk")?;
+        std::fs::write(&spec, "the spec")?;
+
+        let uploaded = HashMap::from([(spec.clone(), "file-abc".to_string())]);
+        let item = ResponseInputItem::from_user_input_with_uploads(
+            vec![
+                UserInput::LocalFile {
+                    path: notes.clone(),
+                },
+                UserInput::LocalFile { path: spec.clone() },
+            ],
+            ImageLimits::default(),
+            &uploaded,
+        );
+
+        let ResponseInputItem::Message { content, .. } = item else {
+            panic!("expected message response");
+        };
+        assert_eq!(
+            content,
+            vec![
+                ContentItem::InputText {
+                    text: format!(
+                        "<file path=\"{}\">\nremember the milk\n</file>",
+                        notes.display()
+                    ),
+                },
+                ContentItem::InputText {
+                    text: file_open_tag_text(&spec),
+                },
+                ContentItem::InputFile {
+                    file_id: "file-abc".to_string(),
+                },
+                ContentItem::InputText {
+                    text: file_close_tag_text(),
+                },
+            ]
+        );
+        let spec_path = spec.display().to_string();
+        assert_eq!(
+            file_tag_path(&file_open_tag_text(&spec)),
+            Some(spec_path.as_str())
+        );
+
+        Ok(())
+    }
 }
@@ -172,6 +172,10 @@ const fn default_effective_context_window_percent() -> i64 {
     95
 }
 
+const fn default_supports_reasoning() -> bool {
+    true
+}
+
 /// Model metadata returned by the Codex backend `/models` endpoint.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS, JsonSchema)]
 pub struct ModelInfo {
@@ -189,6 +193,11 @@ pub struct ModelInfo {
     pub base_instructions: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model_messages: Option<ModelMessages>,
+    /// Whether this is a reasoning model: it takes a reasoning effort and
+    /// rejects sampling parameters such as `temperature`. Models served by the
+    /// backend are reasoning models unless they say otherwise.
+    #[serde(default = "default_supports_reasoning")]
+    pub supports_reasoning: bool,
     pub supports_reasoning_summaries: bool,
     pub support_verbosity: bool,
     pub default_verbosity: Option<Verbosity>,
@@ -206,6 +215,10 @@ pub struct ModelInfo {
     #[serde(default = "default_effective_context_window_percent")]
     pub effective_context_window_percent: i64,
     pub experimental_supported_tools: Vec<String>,
+    /// Largest `max_output_tokens` the model accepts. Configured caps above
+    /// this are clamped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<i64>,
 }
 
 impl ModelInfo {
@@ -450,6 +463,7 @@ mod tests {
             upgrade: None,
             base_instructions: "base".to_string(),
             model_messages: spec,
+            supports_reasoning: false,
             supports_reasoning_summaries: false,
             support_verbosity: false,
             default_verbosity: None,
@@ -460,6 +474,7 @@ mod tests {
             auto_compact_token_limit: None,
             effective_context_window_percent: 95,
             experimental_supported_tools: vec![],
+            max_output_tokens: None,
         }
     }
 
@@ -3,6 +3,7 @@
 //! Uses a SQ (Submission Queue) / EQ (Event Queue) pattern to asynchronously communicate
 //! between user and agent.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fmt;
@@ -17,6 +18,8 @@ use crate::config_types::CollaborationMode;
 use crate::config_types::ModeKind;
 use crate::config_types::Personality;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use crate::config_types::SamplingParameters;
+use crate::config_types::ServiceTier;
 use crate::config_types::WindowsSandboxLevel;
 use crate::custom_prompts::CustomPrompt;
 use crate::dynamic_tools::DynamicToolCallRequest;
@@ -24,6 +27,7 @@ use crate::dynamic_tools::DynamicToolResponse;
 use crate::dynamic_tools::DynamicToolSpec;
 use crate::items::TurnItem;
 use crate::message_history::HistoryEntry;
+use crate::message_history::HistoryLogMetadata;
 use crate::models::BaseInstructions;
 use crate::models::ContentItem;
 use crate::models::ResponseItem;
@@ -50,6 +54,9 @@ use tracing::error;
 use ts_rs::TS;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
+pub use crate::approvals::ApprovalKind;
+pub use crate::approvals::ApprovalResolvedEvent;
+pub use crate::approvals::ApprovalResolver;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
@@ -140,6 +147,40 @@ pub enum Op {
         /// Optional personality override for this turn.
         #[serde(skip_serializing_if = "Option::is_none")]
         personality: Option<Personality>,
+
+        /// Maximum output tokens for this turn, overriding
+        /// `model_max_output_tokens`. Clamped to the model's maximum.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_output_tokens: Option<i64>,
+
+        /// Processing tier for this turn, overriding `service_tier`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        service_tier: Option<ServiceTier>,
+    },
+
+    /// Guidance for the turn that is currently running, without aborting it.
+    ///
+    /// Ordering:
+    /// - While a turn is running, the text is recorded as a user message just
+    ///   before that turn's next model request, after any input submitted with
+    ///   [`Op::UserInput`] in the meantime. Several steers keep their order.
+    /// - If the turn ends without another model request, the text starts a
+    ///   new turn right after [`EventMsg::TurnComplete`], exactly as if it had
+    ///   been submitted as [`Op::UserInput`] once the turn ended.
+    /// - With no turn running, it starts a turn immediately.
+    /// - Steers that have not been delivered when the turn is interrupted are
+    ///   dropped along with the turn.
+    ///
+    /// Each delivered steer is reported with [`EventMsg::SteerDelivered`].
+    SteerTask { text: String },
+
+    /// Answer a question in a single turn without tools.
+    ///
+    /// The turn uses a trimmed instruction set, offers the model no tools and
+    /// skips the ghost snapshot. Later turns are unaffected.
+    Ask {
+        /// User input items, see `InputItem`
+        items: Vec<UserInput>,
     },
 
     /// Override parts of the persistent turn context for subsequent turns.
@@ -191,6 +232,19 @@ pub enum Op {
         personality: Option<Personality>,
     },
 
+    /// Restore turn settings once the running turn ends.
+    ///
+    /// Submitted right after an [`Op::UserTurn`] whose settings apply to that
+    /// turn only. The settings are restored when the turn completes or is
+    /// aborted, before [`EventMsg::TurnComplete`] or [`EventMsg::TurnAborted`]
+    /// is sent. With no turn running they are restored immediately.
+    RestoreTurnSettings {
+        approval_policy: AskForApproval,
+        sandbox_policy: SandboxPolicy,
+        model: String,
+        effort: Option<ReasoningEffortConfig>,
+    },
+
     /// Approve a command execution
     ExecApproval {
         /// The id of the submission we are approving
@@ -199,6 +253,17 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Ask the model for a plain-English explanation of a command awaiting
+    /// approval. Runs beside the turn without tools; replies with
+    /// `EventMsg::CommandExplanation`. The call is cancelled once the
+    /// approval is decided or the turn ends.
+    ExplainCommand {
+        /// `call_id` of the pending `ExecApprovalRequest`.
+        call_id: String,
+        command: Vec<String>,
+        cwd: PathBuf,
+    },
+
     /// Approve a code patch
     PatchApproval {
         /// The id of the submission we are approving
@@ -246,10 +311,39 @@ pub enum Op {
     /// Request a single history entry identified by `log_id` + `offset`.
     GetHistoryEntryRequest { offset: usize, log_id: u64 },
 
+    /// Delete every entry from the persistent cross-session message history.
+    /// Reply is delivered via `EventMsg::HistoryCleared`.
+    ClearHistory,
+
     /// Request the list of MCP tools available across all configured servers.
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
 
+    /// Request the tools the model could call on the next turn, exactly as
+    /// they would be sent: built-in tools, MCP tools after allowlist
+    /// filtering, and dynamic tools.
+    /// Reply is delivered via `EventMsg::ListToolsResponse`.
+    ListTools,
+
+    /// Request an estimate of how many tokens each part of the next request
+    /// (instructions, tools, and each kind of history item) takes up.
+    /// Reply is delivered via `EventMsg::TokenBreakdown`.
+    GetTokenBreakdown,
+
+    /// Enable or disable groups of tools for the rest of the session. Disabled
+    /// tools are left out of the next request's tool list and calls to them
+    /// are refused.
+    SetToolToggles { toggles: ToolToggles },
+
+    /// Set the language the model answers in from the next turn on, or clear
+    /// it with `None`. Recorded in the rollout so resumed sessions keep it.
+    SetPreferredLanguage { language: Option<String> },
+
+    /// Forget every command and file edit approved for the rest of this
+    /// session, including approvals restored on resume.
+    /// Reply is delivered via `EventMsg::SessionApprovalsRevoked`.
+    RevokeSessionApprovals,
+
     /// Request MCP servers to reinitialize and refresh cached tool lists.
     RefreshMcpServers { config: McpServerRefreshConfig },
 
@@ -282,6 +376,40 @@ pub enum Op {
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// List the ghost snapshots recorded in this session and the files each
+    /// one captured. Reply is delivered via `EventMsg::GhostSnapshotsResponse`.
+    ListGhostSnapshots,
+
+    /// Read `path` (relative to the session cwd) as captured by the ghost
+    /// snapshot `commit_id`, without touching the working tree.
+    /// Reply is delivered via `EventMsg::GhostSnapshotFileResponse`.
+    ReadGhostSnapshotFile { commit_id: String, path: PathBuf },
+
+    /// Draft a commit message for the files this session changed. Replies
+    /// with `EventMsg::CommitDraft`, or with an unsuccessful
+    /// `EventMsg::CommitCompleted` when there is nothing to commit or the
+    /// repository cannot be committed to.
+    DraftCommit {
+        #[serde(default)]
+        sign_off: bool,
+        #[serde(default)]
+        amend: bool,
+    },
+
+    /// Stage `files` and commit them with `message`. An empty `files` list
+    /// means every file this session changed, and a missing `message` is
+    /// drafted by the model first. Replies with `EventMsg::CommitCompleted`.
+    Commit {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+        #[serde(default)]
+        files: Vec<PathBuf>,
+        #[serde(default)]
+        sign_off: bool,
+        #[serde(default)]
+        amend: bool,
+    },
+
     /// Request Codex to drop the last N user turns from in-memory context.
     ///
     /// This does not attempt to revert local filesystem changes. Clients are
@@ -473,6 +601,20 @@ impl SandboxPolicy {
         }
     }
 
+    /// Adds `roots` to the writable roots of a workspace-write policy,
+    /// skipping ones it already has. Other policies are returned unchanged:
+    /// read-only stays read-only, and full access already covers them.
+    pub fn with_additional_writable_roots(mut self, roots: &[AbsolutePathBuf]) -> Self {
+        if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut self {
+            for root in roots {
+                if !writable_roots.contains(root) {
+                    writable_roots.push(root.clone());
+                }
+            }
+        }
+        self
+    }
+
     /// Always returns `true`; restricting read access is not supported.
     pub fn has_full_disk_read_access(&self) -> bool {
         true
@@ -695,12 +837,28 @@ pub enum EventMsg {
     /// indicates the turn continued but the user should still be notified.
     Warning(WarningEvent),
 
+    /// The model stopped because it reached its output token limit, so the
+    /// last response is incomplete.
+    OutputTruncated(OutputTruncatedEvent),
+
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
     /// Conversation history was rolled back by dropping the last N user turns.
     ThreadRolledBack(ThreadRolledBackEvent),
 
+    /// Approvals granted for the session before it was resumed were restored.
+    ApprovalGrantsRestored(ApprovalGrantsRestoredEvent),
+
+    /// Approvals granted for the session were forgotten.
+    SessionApprovalsRevoked(SessionApprovalsRevokedEvent),
+
+    /// A local file was attached to the user message, by upload or inline.
+    FileAttached(FileAttachedEvent),
+
+    /// A summary of the workspace was added to the initial context.
+    WorkspaceSummary(WorkspaceSummaryEvent),
+
     /// Agent has started a turn.
     /// v1 wire format uses `task_started`; accept `turn_started` for v2 interop.
     #[serde(rename = "task_started", alias = "turn_started")]
@@ -750,6 +908,10 @@ pub enum EventMsg {
     /// Aggregate MCP startup completion summary.
     McpStartupComplete(McpStartupCompleteEvent),
 
+    /// Session startup finished, with per-phase timings and any components
+    /// that came up degraded.
+    SessionPrewarmed(SessionPrewarmedEvent),
+
     McpToolCallBegin(McpToolCallBeginEvent),
 
     McpToolCallEnd(McpToolCallEndEvent),
@@ -761,6 +923,10 @@ pub enum EventMsg {
     /// Notification that the server is about to execute a command.
     ExecCommandBegin(ExecCommandBeginEvent),
 
+    /// The exact process spawned for a command, sent when `exec_audit` or
+    /// `--dry-run-exec` is enabled.
+    ExecCommandSpawn(ExecCommandSpawnEvent),
+
     /// Incremental chunk of output from a running command.
     ExecCommandOutputDelta(ExecCommandOutputDeltaEvent),
 
@@ -772,6 +938,9 @@ pub enum EventMsg {
     /// Notification that the agent attached a local image via the view_image tool.
     ViewImageToolCall(ViewImageToolCallEvent),
 
+    /// A local image was downscaled or re-encoded before being attached.
+    ImageDownscaled(ImageDownscaledEvent),
+
     ExecApprovalRequest(ExecApprovalRequestEvent),
 
     RequestUserInput(RequestUserInputEvent),
@@ -782,20 +951,57 @@ pub enum EventMsg {
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    /// An exec or patch approval request was answered, timed out, or ended
+    /// with its turn.
+    ApprovalResolved(ApprovalResolvedEvent),
+
     /// Notification advising the user that something they are using has been
     /// deprecated and should be phased out.
     DeprecationNotice(DeprecationNoticeEvent),
 
     BackgroundEvent(BackgroundEventEvent),
 
+    /// A command configured under `[hooks]` started.
+    HookBegin(HookBeginEvent),
+
+    /// A command configured under `[hooks]` finished, failed to start or
+    /// timed out.
+    HookEnd(HookEndEvent),
+
     UndoStarted(UndoStartedEvent),
 
     UndoCompleted(UndoCompletedEvent),
 
+    /// A drafted commit message, ready for the user to edit and accept.
+    CommitDraft(CommitDraftEvent),
+
+    /// Reply to `Op::ExplainCommand`.
+    CommandExplanation(CommandExplanationEvent),
+
+    /// A commit requested with `Op::Commit` or `Op::DraftCommit` finished or
+    /// was refused.
+    CommitCompleted(CommitCompletedEvent),
+
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
 
+    /// The model provider could not be reached, so the submitted prompt was
+    /// queued instead of starting a turn.
+    PromptQueuedOffline(PromptQueuedOfflineEvent),
+
+    /// The model provider is reachable again and the prompts queued while
+    /// offline were submitted.
+    ConnectivityRestored(ConnectivityRestoredEvent),
+
+    /// Guidance sent with [`Op::SteerTask`] was delivered to the model.
+    SteerDelivered(SteerDeliveredEvent),
+
+    /// The model declined the request, or the provider's content filter
+    /// blocked the response. Sent in place of an `AgentMessage`; the turn
+    /// still ends with `TurnComplete`, but produced no answer.
+    TurnRefused(TurnRefusedEvent),
+
     /// Notification that the agent is about to apply a code patch. Mirrors
     /// `ExecCommandBegin` so front‑ends can show progress indicators.
     PatchApplyBegin(PatchApplyBeginEvent),
@@ -805,18 +1011,56 @@ pub enum EventMsg {
 
     TurnDiff(TurnDiffEvent),
 
+    /// Files the turn read or wrote, sent once when the turn finishes.
+    TurnFiles(TurnFilesEvent),
+
+    /// A tool output matched the injection guard's heuristics.
+    InjectionSuspected(InjectionSuspectedEvent),
+
+    /// A function call's arguments were not valid JSON and were repaired
+    /// before the tool ran.
+    ToolArgumentsRepaired(ToolArgumentsRepairedEvent),
+
+    /// A patch did not apply because a file no longer had the lines it
+    /// expected; the current contents of that region were added to the
+    /// failure returned to the model.
+    StalePatchContext(StalePatchContextEvent),
+
+    /// The turn was about to end with a stale patch still unapplied, so the
+    /// model was asked once to regenerate it (see `retry_stale_patches`).
+    StalePatchRetry(StalePatchRetryEvent),
+
+    /// Files the session had read or written were changed by something else
+    /// (see `watch_workspace`); the model is told before its next request.
+    ExternalFilesChanged(ExternalFilesChangedEvent),
+
     /// Response to GetHistoryEntryRequest.
     GetHistoryEntryResponse(GetHistoryEntryResponseEvent),
 
+    /// Response to ClearHistory.
+    HistoryCleared(HistoryClearedEvent),
+
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
+    /// Tools the model could call on the next turn.
+    ListToolsResponse(ListToolsResponseEvent),
+
+    /// Response to GetTokenBreakdown.
+    TokenBreakdown(TokenBreakdownEvent),
+
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
     /// List of skills available to the agent.
     ListSkillsResponse(ListSkillsResponseEvent),
 
+    /// Response to ListGhostSnapshots.
+    GhostSnapshotsResponse(GhostSnapshotsResponseEvent),
+
+    /// Response to ReadGhostSnapshotFile.
+    GhostSnapshotFileResponse(GhostSnapshotFileResponseEvent),
+
     /// Notification that skill data may have been updated and clients may want to reload.
     SkillsUpdateAvailable,
 
@@ -1093,11 +1337,49 @@ pub struct ExitedReviewModeEvent {
 
 // Individual event payload types matching each `EventMsg` variant.
 
-#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ErrorEvent {
+    /// Short, user-facing headline.
     pub message: String,
     #[serde(default)]
     pub codex_error_info: Option<CodexErrorInfo>,
+    /// Identifiers of the failed model request, for support reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub request_details: Option<RequestErrorDetails>,
+    /// What the user can do about the error, when there is a known remedy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub hint: Option<String>,
+    /// Stable, machine-readable error code such as `connection_failed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub code: Option<String>,
+    /// The full underlying error, when `message` simplifies it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub details: Option<String>,
+}
+
+/// Details of a model request that failed with an HTTP error.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct RequestErrorDetails {
+    /// `x-request-id` (or equivalent) returned by the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub request_id: Option<String>,
+    /// `error.type` from the JSON error body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error_type: Option<String>,
+    /// `error.code` from the JSON error body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error_code: Option<String>,
+    /// Number of attempts made before giving up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub attempts: Option<u64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1105,12 +1387,24 @@ pub struct WarningEvent {
     pub message: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct OutputTruncatedEvent {
+    /// Output token cap sent with the request, if any. `None` means the
+    /// provider stopped at its own limit.
+    pub max_output_tokens: Option<i64>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TurnCompleteEvent {
     pub last_agent_message: Option<String>,
+    /// Backend fingerprint (`system_fingerprint`) the provider last reported
+    /// during the turn, when it reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub system_fingerprint: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1119,6 +1413,24 @@ pub struct TurnStartedEvent {
     pub model_context_window: Option<i64>,
     #[serde(default)]
     pub collaboration_mode_kind: ModeKind,
+    /// Settings in effect for this turn, including any per-turn overrides.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub settings: Option<TurnSettings>,
+}
+
+/// Model and policy settings a turn runs with.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnSettings {
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffortConfig>,
+    pub approval_policy: AskForApproval,
+    pub sandbox_policy: SandboxPolicy,
+    /// Service tier requested for this turn, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub service_tier: Option<ServiceTier>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema, TS)]
@@ -1414,6 +1726,18 @@ pub struct McpToolCallEndEvent {
     pub duration: Duration,
     /// Result of the tool call. Note this could be an error.
     pub result: Result<CallToolResult, String>,
+    /// Set when the call was rejected before reaching the server because its
+    /// arguments do not match the tool's `inputSchema`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub argument_errors: Vec<McpArgumentError>,
+}
+
+/// An MCP tool call argument that does not match the tool's `inputSchema`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq)]
+pub struct McpArgumentError {
+    /// JSON pointer to the offending value; empty for the arguments object.
+    pub path: String,
+    pub message: String,
 }
 
 impl McpToolCallEndEvent {
@@ -1535,6 +1859,23 @@ impl InitialHistory {
         }
     }
 
+    /// The preferred language of the last recorded turn, if it had one.
+    pub fn get_preferred_language(&self) -> Option<String> {
+        let items = match self {
+            InitialHistory::New => return None,
+            InitialHistory::Resumed(resumed) => &resumed.history,
+            InitialHistory::Forked(items) => items,
+        };
+        items
+            .iter()
+            .rev()
+            .find_map(|item| match item {
+                RolloutItem::TurnContext(ctx) => Some(ctx.preferred_language.clone()),
+                _ => None,
+            })
+            .flatten()
+    }
+
     pub fn get_dynamic_tools(&self) -> Option<Vec<DynamicToolSpec>> {
         match self {
             InitialHistory::New => None,
@@ -1632,6 +1973,17 @@ pub struct SessionMeta {
     #[serde(default)]
     pub source: SessionSource,
     pub model_provider: Option<String>,
+    /// User-facing title for the session, set explicitly or derived from the
+    /// first user message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Free-form tags used to organize and filter sessions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Sampling parameters (`seed`, `temperature`, `top_p`) configured for
+    /// the session, so runs can be matched to the settings they used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<SamplingParameters>,
     /// base_instructions for the session. This *should* always be present when creating a new session,
     /// but may be missing for older sessions. If not present, fall back to rendering the base_instructions
     /// from ModelsManager.
@@ -1651,6 +2003,9 @@ impl Default for SessionMeta {
             cli_version: String::new(),
             source: SessionSource::default(),
             model_provider: None,
+            title: None,
+            tags: Vec::new(),
+            sampling: None,
             base_instructions: None,
             dynamic_tools: None,
         }
@@ -1673,6 +2028,8 @@ pub enum RolloutItem {
     Compacted(CompactedItem),
     TurnContext(TurnContextItem),
     EventMsg(EventMsg),
+    ApprovalGrant(ApprovalGrantItem),
+    FileUpload(FileUploadItem),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, TS)]
@@ -1716,6 +2073,36 @@ pub struct TurnContextItem {
     pub final_output_json_schema: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub truncation_policy: Option<TruncationPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_language: Option<String>,
+}
+
+/// An approval the user granted for the rest of the session, recorded so it
+/// can be restored when the session is resumed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, TS)]
+pub struct ApprovalGrantItem {
+    /// Tool the approval applies to, e.g. `shell` or `apply_patch`.
+    pub tool: String,
+    /// Working directory of the turn in which the approval was granted.
+    pub cwd: PathBuf,
+    /// Serialized approval cache key: the command and its working directory
+    /// for command tools, or the file path for `apply_patch`.
+    pub key: String,
+}
+
+/// A file uploaded to the model provider, recorded so it can be uploaded again
+/// when the session is resumed: uploads are deleted when the session shuts
+/// down, and the provider may expire them sooner.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, TS)]
+pub struct FileUploadItem {
+    pub file_id: String,
+    pub path: PathBuf,
+    /// Hex SHA-256 of the uploaded contents, checked before re-uploading.
+    pub sha256: String,
+    pub bytes: u64,
+    /// Id of the earlier upload this one stands in for after a resume.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaces: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -1868,6 +2255,52 @@ pub struct ExecCommandBeginEvent {
     pub interaction_input: Option<String>,
 }
 
+/// Everything needed to reproduce how a command was spawned. A command can be
+/// spawned more than once per call, e.g. without the sandbox after the user
+/// approves a retry, and each attempt gets its own event.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ExecCommandSpawnEvent {
+    /// Identifier of the ExecCommandBegin this attempt belongs to.
+    pub call_id: String,
+    /// Turn ID that this command belongs to.
+    pub turn_id: String,
+    /// The command as requested, before any sandbox wrapper.
+    pub command: Vec<String>,
+    /// The argv actually spawned. Differs from `command` when a sandbox
+    /// wrapper such as `sandbox-exec` or `codex-linux-sandbox` runs it.
+    pub spawn_command: Vec<String>,
+    /// `argv[0]` given to the spawned process when it differs from the path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub arg0: Option<String>,
+    pub cwd: PathBuf,
+    /// The complete environment of the spawned process, with credentials
+    /// redacted.
+    pub env: BTreeMap<String, String>,
+    pub sandbox: ExecSandboxKind,
+    pub limits: ExecSpawnLimits,
+    /// True when the command was not run because `--dry-run-exec` is set.
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ExecSandboxKind {
+    None,
+    MacosSeatbelt,
+    LinuxSeccomp,
+    WindowsRestrictedToken,
+}
+
+/// OS resource limits applied to a spawned command; `None` is unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ExecSpawnLimits {
+    pub cpu_time_seconds: Option<u64>,
+    pub memory_bytes: Option<u64>,
+    pub max_output_bytes: Option<u64>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExecCommandEndEvent {
     /// Identifier for the ExecCommandBegin that finished.
@@ -1905,6 +2338,36 @@ pub struct ExecCommandEndEvent {
     pub duration: Duration,
     /// Formatted output from the command, as seen by the model.
     pub formatted_output: String,
+    /// Whether `formatted_output` is an abridged copy of the command output.
+    #[serde(default)]
+    pub formatted_output_truncated: bool,
+    /// Whether the sandbox blocked the command, as opposed to the command failing on its own.
+    #[serde(default)]
+    pub denied_by_sandbox: bool,
+    /// The resource limit that stopped the command, if one did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub limit_exceeded: Option<ExecLimitKind>,
+}
+
+/// A resource limit enforced on commands run by the shell tools.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecLimitKind {
+    CpuTime,
+    Memory,
+    Output,
+}
+
+impl ExecLimitKind {
+    /// Human-readable name used in messages to the user and the model.
+    pub fn description(self) -> &'static str {
+        match self {
+            ExecLimitKind::CpuTime => "CPU time limit",
+            ExecLimitKind::Memory => "memory limit",
+            ExecLimitKind::Output => "output limit",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1915,6 +2378,49 @@ pub struct ViewImageToolCallEvent {
     pub path: PathBuf,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ImageDownscaledEvent {
+    /// Local filesystem path of the image.
+    pub path: PathBuf,
+    pub original_width: u32,
+    pub original_height: u32,
+    /// Size of the file on disk.
+    pub original_bytes: u64,
+    pub sent_width: u32,
+    pub sent_height: u32,
+    /// Size of the encoded image attached to the request, before base64.
+    pub sent_bytes: u64,
+}
+
+impl ImageDownscaledEvent {
+    /// Human-readable "before → after" summary, e.g.
+    /// `4032x3024, 11.8 MiB → 2048x1536, 612 KiB`.
+    pub fn size_summary(&self) -> String {
+        format!(
+            "{}x{}, {} → {}x{}, {}",
+            self.original_width,
+            self.original_height,
+            format_image_bytes(self.original_bytes),
+            self.sent_width,
+            self.sent_height,
+            format_image_bytes(self.sent_bytes),
+        )
+    }
+}
+
+fn format_image_bytes(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = 1024 * 1024;
+
+    if bytes >= MIB {
+        return format!("{:.1} MiB", bytes as f64 / MIB as f64);
+    }
+    if bytes >= KIB {
+        return format!("{} KiB", bytes / KIB);
+    }
+    format!("{bytes} B")
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecOutputStream {
@@ -1952,6 +2458,56 @@ pub struct BackgroundEventEvent {
     pub message: String,
 }
 
+/// Points in a session at which the commands under `[hooks]` run.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, JsonSchema, TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum HookPoint {
+    /// Before the user's message is recorded and the first model request.
+    PreTurn,
+    /// After the turn's last model response, before `TurnComplete`.
+    PostTurn,
+    /// Before a shell command requested by the model is approved or run.
+    PreExec,
+    /// After a patch applied successfully, before its result reaches the
+    /// model.
+    PostPatch,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct HookBeginEvent {
+    /// Identifier pairing this event with the matching `HookEnd`.
+    pub call_id: String,
+    pub turn_id: String,
+    pub hook: HookPoint,
+    pub command: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct HookEndEvent {
+    pub call_id: String,
+    pub turn_id: String,
+    pub hook: HookPoint,
+    pub command: Vec<String>,
+    /// Exit code, or `None` when the hook could not be started.
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    /// Combined stdout and stderr, or the reason the hook could not run.
+    pub output: String,
+    #[ts(type = "string")]
+    pub duration: Duration,
+    /// Whether this failure ends the turn (`on_failure = "abort"`).
+    pub aborts_turn: bool,
+}
+
+impl HookEndEvent {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0) && !self.timed_out
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct DeprecationNoticeEvent {
     /// Concise summary of what is deprecated.
@@ -1974,12 +2530,150 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+/// Response payload for `Op::ListGhostSnapshots`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct GhostSnapshotsResponseEvent {
+    /// Snapshots in the order they were taken.
+    pub snapshots: Vec<GhostSnapshotEntry>,
+    /// Set when snapshots cannot be read at all, e.g. outside a git repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub unavailable: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct GhostSnapshotEntry {
+    pub commit_id: String,
+    /// Session turn the snapshot was taken before; `None` for snapshots
+    /// recorded before turns were tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub turn: Option<u64>,
+    /// Files captured by the snapshot, relative to the session cwd.
+    pub files: Vec<PathBuf>,
+}
+
+/// Response payload for `Op::ReadGhostSnapshotFile`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct GhostSnapshotFileResponseEvent {
+    pub commit_id: String,
+    pub path: PathBuf,
+    /// Contents at the snapshot; `None` when the snapshot does not have the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub snapshot: Option<String>,
+    /// Contents on disk now; `None` when the file no longer exists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub current: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct CommitDraftEvent {
+    pub message: String,
+    /// Files this session changed that have uncommitted changes.
+    pub files: Vec<PathBuf>,
+    pub sign_off: bool,
+    pub amend: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct CommandExplanationEvent {
+    pub call_id: String,
+    /// One paragraph on what the command does and what could go wrong.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    /// Why no explanation could be produced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct CommitCompletedEvent {
+    pub success: bool,
+    /// Id of the new commit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// The commit message, when one was written or drafted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<PathBuf>,
+    /// Why no commit was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ThreadRolledBackEvent {
     /// Number of user turns that were removed from context.
     pub num_turns: u32,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct ApprovalGrantsRestoredEvent {
+    /// Approvals that apply again in the resumed session.
+    pub grants: Vec<ApprovalGrantItem>,
+    /// Approvals that were not restored because they were granted in a
+    /// different working directory.
+    pub discarded: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct SessionApprovalsRevokedEvent {
+    /// Number of approvals that were forgotten.
+    pub revoked: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct FileAttachedEvent {
+    pub path: PathBuf,
+    /// Size of the file in bytes.
+    pub bytes: u64,
+    /// Provider file id when the file was uploaded (or an earlier upload of
+    /// the same contents was reused); `None` when it was inlined as text.
+    pub file_id: Option<String>,
+}
+
+impl FileAttachedEvent {
+    /// How the file was sent, e.g. `attached via upload (file-abc, 112 KiB)`.
+    pub fn summary(&self) -> String {
+        let size = format_image_bytes(self.bytes);
+        match &self.file_id {
+            Some(file_id) => format!("attached via upload ({file_id}, {size})"),
+            None => format!("attached inline ({size})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct WorkspaceSummaryEvent {
+    /// Size of the summary in bytes.
+    pub bytes: u64,
+    /// Estimated size of the summary in tokens.
+    pub approx_tokens: u64,
+    /// Whether the summary was reused from the cache in `codex_home`.
+    pub cache_hit: bool,
+}
+
+impl WorkspaceSummaryEvent {
+    /// E.g. `workspace summary added (~350 tokens, cached)`.
+    pub fn summary(&self) -> String {
+        let source = if self.cache_hit {
+            "cached"
+        } else {
+            "generated"
+        };
+        format!(
+            "workspace summary added (~{} tokens, {source})",
+            self.approx_tokens
+        )
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -1992,6 +2686,34 @@ pub struct StreamErrorEvent {
     pub additional_details: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PromptQueuedOfflineEvent {
+    /// Prompts waiting for connectivity, including the one just queued.
+    pub queued_prompts: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ConnectivityRestoredEvent {
+    /// Number of queued prompts that were submitted.
+    pub submitted_prompts: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnRefusedEvent {
+    /// The model's refusal text, or a description of the content-filter
+    /// block when the provider gave no text.
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SteerDeliveredEvent {
+    pub text: String,
+    /// No running turn had a model request left to take the text, so it
+    /// started a new turn instead.
+    #[serde(default)]
+    pub deferred: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamInfoEvent {
     pub message: String,
@@ -2028,6 +2750,36 @@ pub struct PatchApplyEndEvent {
     /// The changes that were applied (mirrors PatchApplyBeginEvent::changes).
     #[serde(default)]
     pub changes: HashMap<PathBuf, FileChange>,
+    /// What happened to each file in `changes`, so a partially applied patch
+    /// can be attributed. Empty when produced by an older server.
+    #[serde(default)]
+    pub per_file: Vec<PatchFileResult>,
+}
+
+/// Outcome for one file of a patch, reported in [`PatchApplyEndEvent::per_file`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct PatchFileResult {
+    /// Path of the file as keyed in `changes`.
+    pub path: PathBuf,
+    pub status: PatchFileStatus,
+    /// Why the file could not be applied, for `failed` files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchFileStatus {
+    /// The change was written to disk.
+    Applied,
+    /// Applying this file failed; see `error`.
+    Failed,
+    /// The patch stopped at another file before reaching this one.
+    Skipped,
+    /// The turn was interrupted before the patch reported a result, so the
+    /// file may or may not have been written.
+    Aborted,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2035,6 +2787,127 @@ pub struct TurnDiffEvent {
     pub unified_diff: String,
 }
 
+/// Workspace-relative, deduplicated paths a turn touched. Patch paths are
+/// exact; paths from shell commands are inferred from the command line and are
+/// advisory only.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnFilesEvent {
+    pub files_read: Vec<PathBuf>,
+    pub files_written: Vec<PathBuf>,
+}
+
+/// Emitted when a tool output looks like it is trying to instruct the model.
+/// Detection is heuristic; `approval_downgraded` reports whether the rest of
+/// the turn now asks for approval before running untrusted commands.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct InjectionSuspectedEvent {
+    pub call_id: String,
+    pub tool_name: String,
+    /// Ids of the patterns that matched.
+    pub patterns: Vec<String>,
+    /// The first matching span, with a little surrounding context.
+    pub excerpt: String,
+    pub approval_downgraded: bool,
+}
+
+/// Emitted when a function call's arguments failed strict JSON parsing but
+/// parsed after targeted fixups. The tool received the repaired arguments;
+/// the model's call is recorded as it was sent.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ToolArgumentsRepairedEvent {
+    pub call_id: String,
+    pub tool_name: String,
+    /// The fixups that were applied, in the order first needed.
+    pub repairs: Vec<ArgumentRepair>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct StalePatchContextEvent {
+    pub call_id: String,
+    /// The file whose current contents were attached.
+    pub path: PathBuf,
+    /// Size of the attached excerpt in bytes.
+    pub bytes: u64,
+}
+
+impl StalePatchContextEvent {
+    /// E.g. `patch did not apply to src/lib.rs; sent its current contents (2 KiB)`.
+    pub fn summary(&self) -> String {
+        format!(
+            "patch did not apply to {}; sent its current contents ({})",
+            self.path.display(),
+            format_image_bytes(self.bytes)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct StalePatchRetryEvent {
+    /// Files the model was asked to patch again.
+    pub paths: Vec<PathBuf>,
+}
+
+impl StalePatchRetryEvent {
+    /// E.g. `retrying the patch for src/lib.rs, src/main.rs`.
+    pub fn summary(&self) -> String {
+        let paths = self
+            .paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("retrying the patch for {paths}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ExternalFilesChangedEvent {
+    /// Changed files, relative to the turn's `cwd` when they are under it.
+    pub paths: Vec<PathBuf>,
+    /// Further changed files left out of `paths` to keep it short.
+    pub omitted: u32,
+}
+
+impl ExternalFilesChangedEvent {
+    /// E.g. `changed outside this session: src/app.rs, src/lib.rs (+2 more)`.
+    pub fn summary(&self) -> String {
+        let paths = self
+            .paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.omitted == 0 {
+            format!("changed outside this session: {paths}")
+        } else {
+            format!(
+                "changed outside this session: {paths} (+{} more)",
+                self.omitted
+            )
+        }
+    }
+}
+
+/// A fixup applied to almost-JSON function call arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgumentRepair {
+    /// The arguments were wrapped in a Markdown code fence.
+    CodeFence,
+    /// A comma before a closing `}` or `]` was dropped.
+    TrailingComma,
+    /// A single-quoted string was converted to a double-quoted one.
+    SingleQuotes,
+    /// An object key without quotes was quoted.
+    UnquotedKey,
+    /// A raw newline, tab or other control character in a string was escaped.
+    UnescapedControlCharacter,
+    /// A backslash that did not start a valid JSON escape was escaped.
+    InvalidEscape,
+    /// `True`, `False` or `None` was replaced with its JSON literal.
+    PythonLiteral,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct GetHistoryEntryResponseEvent {
     pub offset: usize,
@@ -2042,6 +2915,18 @@ pub struct GetHistoryEntryResponseEvent {
     /// The entry at the requested offset, if available and parseable.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub entry: Option<HistoryEntry>,
+    /// Set when `log_id` no longer names the current history file because it
+    /// was compacted or cleared. Clients should drop cached entries and
+    /// continue with this log id and entry count.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub refreshed: Option<HistoryLogMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct HistoryClearedEvent {
+    /// Identifier of the new, empty history file.
+    pub log_id: u64,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2056,6 +2941,87 @@ pub struct McpListToolsResponseEvent {
     pub auth_statuses: std::collections::HashMap<String, McpAuthStatus>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct ListToolsResponseEvent {
+    /// Tools in the order they are sent to the model.
+    pub tools: Vec<ToolDescription>,
+}
+
+/// Estimated token cost of each part of the next request. Estimates use the
+/// same byte-based heuristic as the context-window estimate, so they are
+/// approximate; their proportions are what matter.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct TokenBreakdownEvent {
+    /// Components with a non-zero estimate, largest first.
+    pub components: Vec<TokenComponentEstimate>,
+    pub total_tokens: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct TokenComponentEstimate {
+    pub component: TokenComponent,
+    pub tokens: i64,
+    /// Number of history items (or tools) counted in this component.
+    pub items: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum TokenComponent {
+    /// Base instructions, developer messages, `AGENTS.md` and environment
+    /// context.
+    Instructions,
+    /// Tool definitions, including their JSON schemas.
+    Tools,
+    UserMessages,
+    AssistantMessages,
+    /// Tool calls made by the model, with their arguments.
+    FunctionCalls,
+    /// Tool results sent back to the model.
+    FunctionOutputs,
+    Reasoning,
+    /// Compacted history and items of unknown kinds.
+    Other,
+}
+
+impl TokenComponent {
+    pub fn label(self) -> &'static str {
+        match self {
+            TokenComponent::Instructions => "instructions",
+            TokenComponent::Tools => "tools",
+            TokenComponent::UserMessages => "user messages",
+            TokenComponent::AssistantMessages => "assistant messages",
+            TokenComponent::FunctionCalls => "function calls",
+            TokenComponent::FunctionOutputs => "function outputs",
+            TokenComponent::Reasoning => "reasoning",
+            TokenComponent::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct ToolDescription {
+    /// Name the model uses to call the tool.
+    pub name: String,
+    /// Where the tool comes from.
+    pub source: ToolSource,
+    /// Tool definition as sent to the model, including its JSON schema.
+    pub spec: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum ToolSource {
+    /// Provided by Codex itself.
+    BuiltIn,
+    /// Provided by the named MCP server.
+    Mcp { server: String },
+    /// Registered by the client for this thread.
+    Dynamic,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpStartupUpdateEvent {
     /// Server name being started.
@@ -2087,6 +3053,140 @@ pub struct McpStartupFailure {
     pub error: String,
 }
 
+/// Emitted once everything a session sets up at startup has finished: config
+/// and instructions are loaded, auth is checked, the rollout is open and every
+/// MCP server has either connected or failed. Turns submitted earlier still
+/// run; they wait on whatever is still starting.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SessionPrewarmedEvent {
+    /// How long each startup phase took. Some phases run concurrently, so the
+    /// durations can add up to more than the wall-clock startup time.
+    pub phases: Vec<PrewarmPhaseTiming>,
+    /// Components that are usable only partially or not at all.
+    pub degraded: Vec<DegradedComponent>,
+    /// How each MCP server's startup went. Completes the pending list in
+    /// [`StartupDiagnostics::mcp_servers`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mcp_servers: Vec<McpServerStartup>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum PrewarmPhase {
+    /// Skills, rules and model metadata.
+    Config,
+    /// `AGENTS.md` discovery and user instructions.
+    ProjectDocs,
+    Auth,
+    /// Checking the configured model against the provider's model list.
+    ModelCheck,
+    Rollout,
+    McpServers,
+    /// Sandbox enforcement, as last measured by the sandbox self-test.
+    Sandbox,
+    /// Reachability probe of the model provider, when `startup_probe` is set.
+    ProviderProbe,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PrewarmPhaseTiming {
+    pub phase: PrewarmPhase,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct DegradedComponent {
+    pub phase: PrewarmPhase,
+    /// Human-readable explanation, e.g. the MCP server name and its error.
+    pub detail: String,
+}
+
+/// What a session found while starting, for clients that want to show why
+/// startup was slow. MCP servers connect after the session is configured, so
+/// they are listed here as pending and completed by
+/// [`SessionPrewarmedEvent::mcp_servers`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct StartupDiagnostics {
+    /// Phases finished before the session was configured.
+    pub phases: Vec<PrewarmPhaseTiming>,
+    pub auth: StartupAuth,
+    /// Set when `startup_probe` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub provider_probe: Option<ProviderProbe>,
+    /// `AGENTS.md` files loaded into the instructions, in load order.
+    pub project_docs: Vec<ProjectDocFile>,
+    pub mcp_servers: Vec<McpServerStartup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct StartupAuth {
+    pub mode: StartupAuthMode,
+    /// Time since the ChatGPT tokens were last refreshed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub token_age_secs: Option<u64>,
+    /// The ChatGPT tokens are old enough to be refreshed before the next
+    /// request.
+    pub refresh_due: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum StartupAuthMode {
+    ApiKey,
+    Chatgpt,
+    /// ChatGPT tokens supplied by the client rather than `codex login`.
+    ChatgptAuthTokens,
+    /// The provider's own key, read from its `env_key`.
+    ProviderKey,
+    None,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProviderProbe {
+    pub reachable: bool,
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProjectDocFile {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// One MCP server's startup. Both `connect_ms` and `error` are unset while
+/// the server is still connecting, and for a server whose startup was
+/// cancelled.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpServerStartup {
+    pub server: String,
+    /// Time until the server answered, or until it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub connect_ms: Option<u64>,
+    /// Tools the server offers after `enabled_tools`/`disabled_tools`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub tools: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+impl McpServerStartup {
+    pub fn pending(server: String) -> Self {
+        Self {
+            server,
+            connect_ms: None,
+            tools: None,
+            error: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -2248,6 +3348,120 @@ pub struct SessionConfiguredEvent {
     /// Path in which the rollout is stored. Can be `None` for ephemeral threads
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rollout_path: Option<PathBuf>,
+
+    /// Which groups of tools the model may call.
+    #[serde(default)]
+    pub tool_toggles: ToolToggles,
+
+    /// Approximate size in tokens of the session's base instructions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_instructions_tokens: Option<u64>,
+
+    /// Set when the configured model was not found in the provider's model
+    /// list, or, when that list is unavailable, in Codex's own registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub unknown_model: Option<UnknownModel>,
+
+    /// Sampling parameters configured for the session's model requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub sampling: Option<SamplingParameters>,
+
+    /// How startup went: phase timings, auth, loaded project docs and MCP
+    /// servers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub startup: Option<StartupDiagnostics>,
+}
+
+/// The configured model could not be found in the list it was checked against.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct UnknownModel {
+    pub model: String,
+    /// Display name of the provider the model was checked for.
+    pub provider: String,
+    pub source: ModelListSource,
+    /// Listed models closest to `model` by edit distance, closest first.
+    pub suggestions: Vec<String>,
+}
+
+/// Where the list of models used to validate the configured model came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelListSource {
+    /// The provider's `/models` endpoint. A model missing from it will fail.
+    Provider,
+    /// Codex's built-in registry, used when the provider could not be asked.
+    /// The model may still work.
+    Builtin,
+}
+
+impl UnknownModel {
+    /// One-paragraph explanation suitable for showing to the user.
+    pub fn message(&self) -> String {
+        let Self {
+            model,
+            provider,
+            source,
+            suggestions,
+        } = self;
+        let mut message = match source {
+            ModelListSource::Provider => {
+                format!("Model `{model}` is not available from {provider}.")
+            }
+            ModelListSource::Builtin => format!(
+                "Model `{model}` is not a model Codex knows, and {provider} did not return a model list to check it against. Requests may fail."
+            ),
+        };
+        if !suggestions.is_empty() {
+            let suggestions = suggestions
+                .iter()
+                .map(|suggestion| format!("`{suggestion}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            message.push_str(&format!(" Did you mean {suggestions}?"));
+        }
+        message
+    }
+}
+
+/// Groups of tools that can be turned off for a session, e.g. for a read-only
+/// advisory chat. Everything is enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(default)]
+pub struct ToolToggles {
+    /// Shell execution tools (`shell`, `shell_command`, `exec_command`, ...).
+    pub shell: bool,
+    /// The `apply_patch` tool, including patches run through a shell tool.
+    pub apply_patch: bool,
+    /// Tools and resources exposed by MCP servers.
+    pub mcp: bool,
+}
+
+impl Default for ToolToggles {
+    fn default() -> Self {
+        Self {
+            shell: true,
+            apply_patch: true,
+            mcp: true,
+        }
+    }
+}
+
+impl ToolToggles {
+    /// Names of the enabled groups, in display order.
+    pub fn enabled_names(&self) -> Vec<&'static str> {
+        let Self {
+            shell,
+            apply_patch,
+            mcp,
+        } = *self;
+        [("shell", shell), ("apply_patch", apply_patch), ("mcp", mcp)]
+            .into_iter()
+            .filter_map(|(name, enabled)| enabled.then_some(name))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2327,6 +3541,20 @@ pub struct Chunk {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TurnAbortedEvent {
     pub reason: TurnAbortReason,
+    /// Work that was still running and got cancelled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cancelled: Vec<CancelledWork>,
+}
+
+/// Work in progress when a turn was aborted.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CancelledWork {
+    /// The model response that was streaming.
+    ModelResponse,
+    /// A tool call that had not returned, such as a running command or MCP
+    /// tool call.
+    ToolCall { call_id: String, tool_name: String },
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
@@ -2612,6 +3840,11 @@ mod tests {
                 history_entry_count: 0,
                 initial_messages: None,
                 rollout_path: Some(rollout_file.path().to_path_buf()),
+                tool_toggles: ToolToggles::default(),
+                base_instructions_tokens: None,
+                unknown_model: None,
+                sampling: None,
+                startup: None,
             }),
         };
 
@@ -2631,12 +3864,40 @@ mod tests {
                 "history_log_id": 0,
                 "history_entry_count": 0,
                 "rollout_path": format!("{}", rollout_file.path().display()),
+                "tool_toggles": {
+                    "shell": true,
+                    "apply_patch": true,
+                    "mcp": true,
+                },
             }
         });
         assert_eq!(expected, serde_json::to_value(&event)?);
         Ok(())
     }
 
+    #[test]
+    fn startup_diagnostics_are_optional() -> Result<()> {
+        let event: SessionConfiguredEvent = serde_json::from_value(json!({
+            "session_id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "model": "codex-mini-latest",
+            "model_provider_id": "openai",
+            "approval_policy": "never",
+            "sandbox_policy": { "type": "read-only" },
+            "cwd": "/home/user/project",
+            "history_log_id": 0,
+            "history_entry_count": 0,
+        }))?;
+        assert_eq!(event.startup, None);
+
+        let prewarmed: SessionPrewarmedEvent =
+            serde_json::from_value(json!({ "phases": [], "degraded": [] }))?;
+        assert_eq!(prewarmed.mcp_servers, Vec::new());
+
+        let pending = McpServerStartup::pending("docs".to_string());
+        assert_eq!(serde_json::to_value(&pending)?, json!({ "server": "docs" }));
+        Ok(())
+    }
+
     #[test]
     fn vec_u8_as_base64_serialization_and_deserialization() -> Result<()> {
         let event = ExecCommandOutputDeltaEvent {
@@ -2697,4 +3958,74 @@ mod tests {
         assert_eq!(value["msg"]["cancelled"][0], "c");
         Ok(())
     }
+
+    #[test]
+    fn image_downscaled_size_summary() {
+        let event = ImageDownscaledEvent {
+            path: PathBuf::from("shot.png"),
+            original_width: 4032,
+            original_height: 3024,
+            original_bytes: 12_373_197,
+            sent_width: 1024,
+            sent_height: 768,
+            sent_bytes: 626_688,
+        };
+
+        assert_eq!(
+            event.size_summary(),
+            "4032x3024, 11.8 MiB → 1024x768, 612 KiB"
+        );
+    }
+
+    #[test]
+    fn file_attached_summary() {
+        let mut event = FileAttachedEvent {
+            path: PathBuf::from("docs/spec.md"),
+            bytes: 114_688,
+            file_id: Some("file-abc".to_string()),
+        };
+        assert_eq!(event.summary(), "attached via upload (file-abc, 112 KiB)");
+
+        event.file_id = None;
+        assert_eq!(event.summary(), "attached inline (112 KiB)");
+    }
+
+    #[test]
+    fn stale_patch_summaries() {
+        let context = StalePatchContextEvent {
+            call_id: "call-1".to_string(),
+            path: PathBuf::from("src/lib.rs"),
+            bytes: 2_048,
+        };
+        assert_eq!(
+            context.summary(),
+            "patch did not apply to src/lib.rs; sent its current contents (2 KiB)"
+        );
+
+        let retry = StalePatchRetryEvent {
+            paths: vec![PathBuf::from("src/lib.rs"), PathBuf::from("src/main.rs")],
+        };
+        assert_eq!(
+            retry.summary(),
+            "retrying the patch for src/lib.rs, src/main.rs"
+        );
+    }
+
+    #[test]
+    fn external_files_changed_summary() {
+        let mut event = ExternalFilesChangedEvent {
+            paths: vec![PathBuf::from("src/app.rs"), PathBuf::from("src/lib.rs")],
+            omitted: 0,
+        };
+        assert_eq!(
+            event.summary(),
+            "changed outside this session: src/app.rs, src/lib.rs"
+        );
+
+        event.omitted = 2;
+        assert_eq!(
+            event.summary(),
+            "changed outside this session: src/app.rs, src/lib.rs (+2 more)"
+        );
+    }
 }
@@ -24,6 +24,11 @@ pub enum UserInput {
     /// `Image` variant (base64 data URL) during request serialization.
     LocalImage { path: std::path::PathBuf },
 
+    /// Local file attached whole. Core uploads it to the provider when
+    /// `upload_attachments` is enabled and the provider accepts uploads, and
+    /// inlines its contents as text otherwise.
+    LocalFile { path: std::path::PathBuf },
+
     /// Skill selected by the user (name + path to SKILL.md).
     Skill {
         name: String,
@@ -25,7 +25,9 @@ use mcp_types::ReadResourceResult;
 use mcp_types::RequestId;
 use mcp_types::Tool;
 use reqwest::header::HeaderMap;
+use rmcp::model::CallToolRequest;
 use rmcp::model::CallToolRequestParam;
+use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientNotification;
 use rmcp::model::ClientRequest;
 use rmcp::model::CreateElicitationRequestParam;
@@ -37,8 +39,10 @@ use rmcp::model::InitializeRequestParam;
 use rmcp::model::PaginatedRequestParam;
 use rmcp::model::ReadResourceRequestParam;
 use rmcp::model::ServerResult;
+use rmcp::service::PeerRequestOptions;
 use rmcp::service::RoleClient;
 use rmcp::service::RunningService;
+use rmcp::service::ServiceError;
 use rmcp::service::{self};
 use rmcp::transport::StreamableHttpClientTransport;
 use rmcp::transport::auth::AuthClient;
@@ -108,6 +112,31 @@ pub struct ListToolsWithConnectorIdResult {
     pub tools: Vec<ToolWithConnectorId>,
 }
 
+/// Sends `notifications/cancelled` for `request_id` when dropped while still
+/// set. Servers that do not support cancellation ignore it.
+struct CancelRequestOnDrop {
+    service: Arc<RunningService<RoleClient, LoggingClientHandler>>,
+    request_id: Option<rmcp::model::RequestId>,
+}
+
+impl Drop for CancelRequestOnDrop {
+    fn drop(&mut self) {
+        let Some(request_id) = self.request_id.take() else {
+            return;
+        };
+        let service = Arc::clone(&self.service);
+        tokio::spawn(async move {
+            let params = CancelledNotificationParam {
+                request_id,
+                reason: Some("cancelled by the client".to_string()),
+            };
+            if let Err(err) = service.notify_cancelled(params).await {
+                warn!("failed to cancel MCP request: {err}");
+            }
+        });
+    }
+}
+
 /// MCP client implemented on top of the official `rmcp` SDK.
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
@@ -411,8 +440,25 @@ impl RmcpClient {
         let service = self.service().await?;
         let params = CallToolRequestParams { arguments, name };
         let rmcp_params: CallToolRequestParam = convert_to_rmcp(params)?;
-        let fut = service.call_tool(rmcp_params);
+        let request = ClientRequest::CallToolRequest(CallToolRequest::new(rmcp_params));
+        let handle = service
+            .send_cancellable_request(request, PeerRequestOptions::no_options())
+            .await
+            .map_err(|err| anyhow!("tools/call failed: {err}"))?;
+        // Dropping this future before the server answers, as interrupting the
+        // turn does, or timing out tells the server to stop working on it.
+        let mut cancel_on_drop = CancelRequestOnDrop {
+            service: Arc::clone(&service),
+            request_id: Some(handle.id.clone()),
+        };
+        let fut = async move {
+            match handle.await_response().await? {
+                ServerResult::CallToolResult(result) => Ok(result),
+                _ => Err(ServiceError::UnexpectedResponse),
+            }
+        };
         let rmcp_result = run_with_timeout(fut, timeout, "tools/call").await?;
+        cancel_on_drop.request_id = None;
         let converted = convert_call_tool_result(rmcp_result)?;
         self.persist_oauth_tokens().await;
         Ok(converted)
@@ -22,7 +22,9 @@ pub fn apply_rollout_item(
         RolloutItem::TurnContext(turn_ctx) => apply_turn_context(metadata, turn_ctx),
         RolloutItem::EventMsg(event) => apply_event_msg(metadata, event),
         RolloutItem::ResponseItem(item) => apply_response_item(metadata, item),
-        RolloutItem::Compacted(_) => {}
+        RolloutItem::Compacted(_)
+        | RolloutItem::ApprovalGrant(_)
+        | RolloutItem::FileUpload(_) => {}
     }
     if metadata.model_provider.is_empty() {
         metadata.model_provider = default_provider.to_string();
@@ -43,6 +45,9 @@ fn apply_session_meta_from_item(metadata: &mut ThreadMetadata, meta_line: &Sessi
     if !meta_line.meta.cwd.as_os_str().is_empty() {
         metadata.cwd = meta_line.meta.cwd.clone();
     }
+    if let Some(title) = meta_line.meta.title.as_deref() {
+        metadata.title = title.to_string();
+    }
     if let Some(git) = meta_line.git.as_ref() {
         metadata.git_sha = git.commit_hash.clone();
         metadata.git_branch = git.branch.clone();
@@ -93,7 +98,9 @@ fn extract_user_message_text(item: &ResponseItem) -> Option<String> {
         .iter()
         .filter_map(|content_item| match content_item {
             ContentItem::InputText { text } => Some(text.as_str()),
-            ContentItem::InputImage { .. } | ContentItem::OutputText { .. } => None,
+            ContentItem::InputImage { .. }
+            | ContentItem::InputFile { .. }
+            | ContentItem::OutputText { .. } => None,
         })
         .filter(|text| !is_local_image_open_tag_text(text) && !is_local_image_close_tag_text(text))
         .collect();
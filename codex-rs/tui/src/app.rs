@@ -22,6 +22,7 @@ use crate::history_cell;
 use crate::history_cell::HistoryCell;
 #[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
+use crate::history_command::SnapshotFileView;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
 use crate::model_migration::run_model_migration_prompt;
@@ -29,19 +30,31 @@ use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::SessionSelection;
+use crate::session_recording;
+use crate::session_tabs::SessionActivity;
+use crate::session_tabs::SessionTab;
+use crate::session_tabs::answers_request;
+use crate::session_tabs::is_user_request;
+use crate::session_tabs::tab_bar_line;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
+use crate::workspace_roots;
 use codex_ansi_escape::ansi_escape_line;
 use codex_app_server_protocol::ConfigLayerSource;
+use codex_common::oss::apply_oss_model_details;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
+use codex_core::CodexThread;
+use codex_core::NewThread;
+use codex_core::PrewarmedThread;
 use codex_core::ThreadManager;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::edit::ConfigEdit;
 use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::config::types::Notifications;
 use codex_core::config_loader::ConfigLayerStackOrdering;
 use codex_core::features::Feature;
 use codex_core::models_manager::manager::RefreshStrategy;
@@ -76,6 +89,7 @@ use color_eyre::eyre::WrapErr;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
+use ratatui::layout::Rect;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
@@ -193,6 +207,14 @@ fn emit_deprecation_notice(app_event_tx: &AppEventSender, notice: Option<Depreca
     )));
 }
 
+fn workspace_root_paths(config: &Config) -> Vec<PathBuf> {
+    config
+        .workspace_roots
+        .iter()
+        .map(|root| root.path.to_path_buf())
+        .collect()
+}
+
 fn emit_project_config_warnings(app_event_tx: &AppEventSender, config: &Config) {
     let mut disabled_folders = Vec::new();
 
@@ -255,6 +277,8 @@ struct ThreadEventStore {
     user_message_ids: HashSet<String>,
     capacity: usize,
     active: bool,
+    activity: SessionActivity,
+    thread_name: Option<String>,
 }
 
 impl ThreadEventStore {
@@ -265,6 +289,8 @@ impl ThreadEventStore {
             user_message_ids: HashSet::new(),
             capacity,
             active: false,
+            activity: SessionActivity::default(),
+            thread_name: None,
         }
     }
 
@@ -275,11 +301,21 @@ impl ThreadEventStore {
     }
 
     fn push_event(&mut self, event: Event) {
+        self.activity.observe(&event.msg, self.active);
         match &event.msg {
-            EventMsg::SessionConfigured(_) => {
+            EventMsg::SessionConfigured(session) => {
+                self.thread_name = session.thread_name.clone();
                 self.session_configured = Some(event);
                 return;
             }
+            EventMsg::ThreadNameUpdated(update) => {
+                self.thread_name = update.thread_name.clone();
+            }
+            EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_) => {
+                // Requests cannot outlive their turn; keep them from being
+                // shown again when the thread is replayed.
+                self.buffer.retain(|event| !is_user_request(&event.msg));
+            }
             EventMsg::ItemCompleted(completed) => {
                 if let TurnItem::UserMessage(item) = &completed.item {
                     if !event.id.is_empty() && self.user_message_ids.contains(&event.id) {
@@ -316,6 +352,18 @@ impl ThreadEventStore {
         }
     }
 
+    /// Drop the request that `op` answers, so that replaying the thread only
+    /// shows requests that are still waiting.
+    fn resolve_request(&mut self, op: &Op) {
+        if let Some(idx) = self
+            .buffer
+            .iter()
+            .position(|event| answers_request(op, event))
+        {
+            self.buffer.remove(idx);
+        }
+    }
+
     fn snapshot(&self) -> ThreadEventSnapshot {
         ThreadEventSnapshot {
             session_configured: self.session_configured.clone(),
@@ -565,6 +613,9 @@ pub(crate) struct App {
     primary_thread_id: Option<ThreadId>,
     primary_session_configured: Option<SessionConfiguredEvent>,
     pending_primary_events: VecDeque<Event>,
+    /// Top-level conversations in tab order. The primary thread comes first;
+    /// `/new` and "open in new tab" from the resume picker append to it.
+    session_thread_ids: Vec<ThreadId>,
 }
 
 #[derive(Default)]
@@ -666,6 +717,9 @@ impl App {
         if let Some(channel) = self.thread_event_channels.get_mut(&thread_id) {
             let mut store = channel.store.lock().await;
             store.active = active;
+            if active {
+                store.activity.needs_attention = false;
+            }
         }
     }
 
@@ -705,6 +759,7 @@ impl App {
         let receiver = channel.receiver.take()?;
         let mut store = channel.store.lock().await;
         store.active = true;
+        store.activity.needs_attention = false;
         let snapshot = store.snapshot();
         Some((receiver, snapshot))
     }
@@ -722,11 +777,15 @@ impl App {
             (channel.sender.clone(), Arc::clone(&channel.store))
         };
 
-        let should_send = {
+        let (should_send, activity_changed) = {
             let mut guard = store.lock().await;
+            let before = guard.activity;
             guard.push_event(event.clone());
-            guard.active
+            (guard.active, guard.activity != before)
         };
+        if activity_changed {
+            self.app_event_tx.send(AppEvent::SessionActivityChanged);
+        }
 
         if should_send {
             // Never await a bounded channel send on the main TUI loop: if the receiver falls behind,
@@ -758,6 +817,9 @@ impl App {
             let thread_id = session.session_id;
             self.primary_thread_id = Some(thread_id);
             self.primary_session_configured = Some(session.clone());
+            if !self.session_thread_ids.contains(&thread_id) {
+                self.session_thread_ids.insert(0, thread_id);
+            }
             self.ensure_thread_channel(thread_id);
             self.activate_thread_channel(thread_id).await;
 
@@ -855,6 +917,141 @@ impl App {
         Ok(())
     }
 
+    /// Track `new_thread` as another top-level session and focus it. The
+    /// sessions it is added next to keep running in the background.
+    async fn open_session_tab(&mut self, tui: &mut tui::Tui, new_thread: NewThread) -> Result<()> {
+        let NewThread {
+            thread_id,
+            thread,
+            session_configured,
+        } = new_thread;
+        let event = Event {
+            id: String::new(),
+            msg: EventMsg::SessionConfigured(session_configured),
+        };
+        self.spawn_thread_listener(thread_id, thread, event);
+        self.session_thread_ids.push(thread_id);
+        self.select_agent_thread(tui, thread_id).await
+    }
+
+    async fn focus_next_session(&mut self, tui: &mut tui::Tui) -> Result<()> {
+        if self.session_thread_ids.len() < 2 {
+            return Ok(());
+        }
+        let next = self
+            .active_thread_id
+            .and_then(|active| self.session_thread_ids.iter().position(|id| *id == active))
+            .map_or(0, |idx| (idx + 1) % self.session_thread_ids.len());
+        self.select_agent_thread(tui, self.session_thread_ids[next])
+            .await
+    }
+
+    fn session_tabs(&self) -> Vec<SessionTab> {
+        self.session_thread_ids
+            .iter()
+            .map(|thread_id| {
+                // Drawing must not wait on a listener task; a tab whose store
+                // is busy shows its default state for one frame.
+                let store = self
+                    .thread_event_channels
+                    .get(thread_id)
+                    .and_then(|channel| channel.store.try_lock().ok());
+                SessionTab {
+                    name: store.as_ref().and_then(|store| store.thread_name.clone()),
+                    activity: store.map(|store| store.activity).unwrap_or_default(),
+                    focused: self.active_thread_id == Some(*thread_id),
+                }
+            })
+            .collect()
+    }
+
+    async fn resolve_active_request(&mut self, op: &Op) {
+        let is_answer = matches!(
+            op,
+            Op::ExecApproval { .. }
+                | Op::PatchApproval { .. }
+                | Op::UserInputAnswer { .. }
+                | Op::ResolveElicitation { .. }
+        );
+        let Some(thread_id) = self.active_thread_id.filter(|_| is_answer) else {
+            return;
+        };
+        if let Some(channel) = self.thread_event_channels.get(&thread_id) {
+            channel.store.lock().await.resolve_request(op);
+        }
+    }
+
+    fn open_session_picker(&mut self) {
+        if self.session_thread_ids.len() < 2 {
+            self.chat_widget.add_info_message(
+                "Only one session is open.".to_string(),
+                Some("Use /new to start another.".to_string()),
+            );
+            return;
+        }
+
+        let mut initial_selected_idx = None;
+        let items: Vec<SelectionItem> = self
+            .session_thread_ids
+            .iter()
+            .zip(self.session_tabs())
+            .enumerate()
+            .map(|(idx, (thread_id, tab))| {
+                if tab.focused {
+                    initial_selected_idx = Some(idx);
+                }
+                let id = *thread_id;
+                let description = if tab.activity.needs_attention {
+                    Some("waiting for you".to_string())
+                } else if tab.activity.running {
+                    Some("working".to_string())
+                } else {
+                    None
+                };
+                SelectionItem {
+                    name: format!(
+                        "{}. {}",
+                        idx + 1,
+                        tab.name.unwrap_or_else(|| id.to_string())
+                    ),
+                    description,
+                    is_current: tab.focused,
+                    actions: vec![Box::new(move |tx| {
+                        tx.send(AppEvent::SelectAgentThread(id));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.chat_widget.show_selection_view(SelectionViewParams {
+            title: Some("Sessions".to_string()),
+            subtitle: Some("Select a conversation to focus".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            initial_selected_idx,
+            ..Default::default()
+        });
+    }
+
+    /// Ask every session other than the focused one to shut down. Their
+    /// remaining events, including `ShutdownComplete`, only reach their own
+    /// background buffers.
+    async fn shutdown_background_sessions(&mut self) {
+        let focused = self.chat_widget.thread_id();
+        for thread_id in std::mem::take(&mut self.session_thread_ids) {
+            if Some(thread_id) == focused {
+                continue;
+            }
+            if let Some(thread) = self.server.remove_thread(&thread_id).await
+                && let Err(err) = thread.submit(Op::Shutdown).await
+            {
+                tracing::warn!("failed to shut down session {thread_id}: {err}");
+            }
+        }
+    }
+
     fn reset_for_thread_switch(&mut self, tui: &mut tui::Tui) -> Result<()> {
         self.overlay = None;
         self.transcript_cells.clear();
@@ -869,6 +1066,7 @@ impl App {
 
     fn reset_thread_event_state(&mut self) {
         self.thread_event_channels.clear();
+        self.session_thread_ids.clear();
         self.active_thread_id = None;
         self.active_thread_rx = None;
         self.primary_thread_id = None;
@@ -917,6 +1115,8 @@ impl App {
     pub async fn run(
         tui: &mut tui::Tui,
         auth_manager: Arc<AuthManager>,
+        thread_manager: Arc<ThreadManager>,
+        prewarmed: Option<PrewarmedThread>,
         mut config: Config,
         cli_kv_overrides: Vec<(String, TomlValue)>,
         harness_overrides: ConfigOverrides,
@@ -933,15 +1133,15 @@ impl App {
         let app_event_tx = AppEventSender::new(app_event_tx);
         emit_deprecation_notice(&app_event_tx, ollama_chat_support_notice);
         emit_project_config_warnings(&app_event_tx, &config);
+        for warning in apply_oss_model_details(&mut config).await {
+            app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                crate::history_cell::new_warning_event(warning),
+            )));
+        }
         tui.set_notification_method(config.tui_notification_method);
 
         let harness_overrides =
             normalize_harness_overrides_for_cwd(harness_overrides, &config.cwd)?;
-        let thread_manager = Arc::new(ThreadManager::new(
-            config.codex_home.clone(),
-            auth_manager.clone(),
-            SessionSource::Cli,
-        ));
         let mut model = thread_manager
             .get_models_manager()
             .get_default_model(&config.model, &config, RefreshStrategy::Offline)
@@ -959,9 +1159,20 @@ impl App {
         )
         .await;
         if let Some(exit_info) = exit_info {
+            if let Some(prewarmed) = prewarmed {
+                prewarmed.discard().await;
+            }
             return Ok(exit_info);
         }
+        let mut prewarmed = prewarmed;
         if let Some(updated_model) = config.model.clone() {
+            // The prewarmed session was started with the model from before the
+            // migration prompt.
+            if updated_model != model
+                && let Some(stale) = prewarmed.take()
+            {
+                stale.discard().await;
+            }
             model = updated_model;
         }
 
@@ -990,6 +1201,14 @@ impl App {
             SessionSource::Cli,
         );
 
+        if !matches!(
+            session_selection,
+            SessionSelection::StartFresh | SessionSelection::Exit
+        ) && let Some(unused) = prewarmed.take()
+        {
+            unused.discard().await;
+        }
+
         let enhanced_keys_supported = tui.enhanced_keys_supported();
         let mut chat_widget = match session_selection {
             SessionSelection::StartFresh | SessionSelection::Exit => {
@@ -1012,9 +1231,23 @@ impl App {
                     model: Some(model.clone()),
                     otel_manager: otel_manager.clone(),
                 };
-                ChatWidget::new(init, thread_manager.clone())
+                match prewarmed {
+                    Some(prewarmed) => {
+                        let NewThread {
+                            thread,
+                            session_configured,
+                            ..
+                        } = prewarmed
+                            .activate()
+                            .await
+                            .wrap_err("Failed to initialize codex")?;
+                        ChatWidget::new_from_existing(init, thread, session_configured)
+                    }
+                    None => ChatWidget::new(init, thread_manager.clone()),
+                }
             }
-            SessionSelection::Resume(path) => {
+            // Startup pickers never offer tabs, so both mean a plain resume.
+            SessionSelection::Resume(path) | SessionSelection::ResumeInNewTab(path) => {
                 let resumed = thread_manager
                     .resume_thread_from_rollout(config.clone(), path.clone(), auth_manager.clone())
                     .await
@@ -1076,6 +1309,11 @@ impl App {
 
         chat_widget.maybe_prompt_windows_sandbox_enable();
 
+        // `--title`/`--tag` only describe the session started from the command line.
+        config.session_title = None;
+        config.session_tags.clear();
+
+        workspace_roots::set_workspace_roots(workspace_root_paths(&config));
         let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
         #[cfg(not(debug_assertions))]
         let upgrade_version = crate::updates::get_upgrade_version(&config);
@@ -1112,6 +1350,7 @@ impl App {
             primary_thread_id: None,
             primary_session_configured: None,
             pending_primary_events: VecDeque::new(),
+            session_thread_ids: Vec::new(),
         };
 
         // On startup, if Agent mode (workspace-write) or ReadOnly is active, warn about world-writable dirs on Windows.
@@ -1254,11 +1493,22 @@ impl App {
                     {
                         return Ok(AppRunControl::Continue);
                     }
+                    let tab_bar = (self.session_thread_ids.len() > 1)
+                        .then(|| tab_bar_line(&self.session_tabs()));
+                    let tab_bar_height = u16::from(tab_bar.is_some());
                     tui.draw(
-                        self.chat_widget.desired_height(tui.terminal.size()?.width),
+                        self.chat_widget
+                            .desired_height(tui.terminal.size()?.width)
+                            .saturating_add(tab_bar_height),
                         |frame| {
-                            self.chat_widget.render(frame.area(), frame.buffer);
-                            if let Some((x, y)) = self.chat_widget.cursor_pos(frame.area()) {
+                            let mut area = frame.area();
+                            if let Some(tab_bar) = tab_bar {
+                                frame.render_widget_ref(tab_bar, Rect { height: 1, ..area });
+                                area.y += tab_bar_height;
+                                area.height = area.height.saturating_sub(tab_bar_height);
+                            }
+                            self.chat_widget.render(area, frame.buffer);
+                            if let Some((x, y)) = self.chat_widget.cursor_pos(area) {
                                 frame.set_cursor_position((x, y));
                             }
                         },
@@ -1274,52 +1524,44 @@ impl App {
         Ok(AppRunControl::Continue)
     }
 
+    /// Lines `cell` contributes to the terminal scrollback.
+    pub(crate) fn scrollback_lines(
+        &self,
+        cell: &dyn HistoryCell,
+        width: u16,
+    ) -> Vec<Line<'static>> {
+        if self.config.tui_accessible {
+            cell.accessible_lines(width)
+        } else {
+            cell.display_lines(width)
+        }
+    }
+
     async fn handle_event(&mut self, tui: &mut tui::Tui, event: AppEvent) -> Result<AppRunControl> {
         match event {
             AppEvent::NewSession => {
-                let model = self.chat_widget.current_model().to_string();
-                let summary = session_summary(
-                    self.chat_widget.token_usage(),
-                    self.chat_widget.thread_id(),
-                    self.chat_widget.thread_name(),
-                );
-                self.shutdown_current_thread().await;
-                if let Err(err) = self.server.remove_and_close_all_threads().await {
-                    tracing::warn!(error = %err, "failed to close all threads");
-                }
-                let init = crate::chatwidget::ChatWidgetInit {
-                    config: self.config.clone(),
-                    frame_requester: tui.frame_requester(),
-                    app_event_tx: self.app_event_tx.clone(),
-                    // New sessions start without prefilled message content.
-                    initial_user_message: None,
-                    enhanced_keys_supported: self.enhanced_keys_supported,
-                    auth_manager: self.auth_manager.clone(),
-                    models_manager: self.server.get_models_manager(),
-                    feedback: self.feedback.clone(),
-                    is_first_run: false,
-                    feedback_audience: self.feedback_audience,
-                    model: Some(model),
-                    otel_manager: self.otel_manager.clone(),
-                };
-                self.chat_widget = ChatWidget::new(init, self.server.clone());
-                self.reset_thread_event_state();
-                if let Some(summary) = summary {
-                    let mut lines: Vec<Line<'static>> = vec![summary.usage_line.clone().into()];
-                    if let Some(command) = summary.resume_command {
-                        let spans = vec!["To continue this session, run ".into(), command.cyan()];
-                        lines.push(spans.into());
+                let mut config = self.config.clone();
+                config.model = Some(self.chat_widget.current_model().to_string());
+                self.apply_runtime_policy_overrides(&mut config);
+                match self.server.start_thread(config).await {
+                    Ok(new_thread) => self.open_session_tab(tui, new_thread).await?,
+                    Err(err) => {
+                        self.chat_widget
+                            .add_error_message(format!("Failed to start a new session: {err}"));
                     }
-                    self.chat_widget.add_plain_history_lines(lines);
                 }
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::OpenTranscript => {
+                if self.overlay.is_none() {
+                    self.open_transcript_overlay(tui);
+                }
+            }
             AppEvent::OpenResumePicker => {
-                match crate::resume_picker::run_resume_picker(
+                match crate::resume_picker::run_in_session_resume_picker(
                     tui,
                     &self.config.codex_home,
                     &self.config.model_provider_id,
-                    false,
                 )
                 .await?
                 {
@@ -1368,8 +1610,12 @@ impl App {
                         {
                             Ok(resumed) => {
                                 self.shutdown_current_thread().await;
+                                self.shutdown_background_sessions().await;
                                 self.config = resume_config;
                                 tui.set_notification_method(self.config.tui_notification_method);
+                                workspace_roots::set_workspace_roots(workspace_root_paths(
+                                    &self.config,
+                                ));
                                 self.file_search.update_search_dir(self.config.cwd.clone());
                                 let init = self.chatwidget_init_for_forked_or_resumed_thread(
                                     tui,
@@ -1402,6 +1648,29 @@ impl App {
                             }
                         }
                     }
+                    SessionSelection::ResumeInNewTab(path) => {
+                        // The new tab shares this session's working directory
+                        // and configuration.
+                        let mut resume_config = self.config.clone();
+                        self.apply_runtime_policy_overrides(&mut resume_config);
+                        match self
+                            .server
+                            .resume_thread_from_rollout(
+                                resume_config,
+                                path.clone(),
+                                self.auth_manager.clone(),
+                            )
+                            .await
+                        {
+                            Ok(resumed) => self.open_session_tab(tui, resumed).await?,
+                            Err(err) => {
+                                let path_display = path.display();
+                                self.chat_widget.add_error_message(format!(
+                                    "Failed to resume session from {path_display}: {err}"
+                                ));
+                            }
+                        }
+                    }
                     SessionSelection::Exit
                     | SessionSelection::StartFresh
                     | SessionSelection::Fork(_) => {}
@@ -1424,6 +1693,7 @@ impl App {
                     {
                         Ok(forked) => {
                             self.shutdown_current_thread().await;
+                            self.shutdown_background_sessions().await;
                             let init = self.chatwidget_init_for_forked_or_resumed_thread(
                                 tui,
                                 self.config.clone(),
@@ -1468,7 +1738,8 @@ impl App {
                     tui.frame_requester().schedule_frame();
                 }
                 self.transcript_cells.push(cell.clone());
-                let mut display = cell.display_lines(tui.terminal.last_known_screen_size.width);
+                let mut display =
+                    self.scrollback_lines(cell.as_ref(), tui.terminal.last_known_screen_size.width);
                 if !display.is_empty() {
                     // Only insert a separating blank line for new cells that are not
                     // part of an ongoing stream. Streaming continuations should not
@@ -1480,6 +1751,7 @@ impl App {
                             self.has_emitted_history_lines = true;
                         }
                     }
+                    session_recording::record_cell(cell.as_ref(), &display);
                     if self.overlay.is_some() {
                         self.deferred_history_lines.extend(display);
                     } else {
@@ -1513,7 +1785,10 @@ impl App {
                 self.enqueue_primary_event(event).await?;
             }
             AppEvent::Exit(mode) => match mode {
-                ExitMode::ShutdownFirst => self.chat_widget.submit_op(Op::Shutdown),
+                ExitMode::ShutdownFirst => {
+                    self.shutdown_background_sessions().await;
+                    self.chat_widget.submit_op(Op::Shutdown);
+                }
                 ExitMode::Immediate => {
                     return Ok(AppRunControl::Exit(ExitReason::UserRequested));
                 }
@@ -1522,6 +1797,7 @@ impl App {
                 return Ok(AppRunControl::Exit(ExitReason::Fatal(message)));
             }
             AppEvent::CodexOp(op) => {
+                self.resolve_active_request(&op).await;
                 self.chat_widget.submit_op(op);
             }
             AppEvent::DiffResult(text) => {
@@ -1561,6 +1837,9 @@ impl App {
             AppEvent::FileSearchResult { query, matches } => {
                 self.chat_widget.apply_file_search_result(query, matches);
             }
+            AppEvent::FileMentioned(path) => {
+                self.file_search.record_mention(path);
+            }
             AppEvent::RateLimitSnapshotFetched(snapshot) => {
                 self.chat_widget.on_rate_limit_snapshot(Some(snapshot));
             }
@@ -1579,6 +1858,37 @@ impl App {
             AppEvent::UpdatePersonality(personality) => {
                 self.on_update_personality(personality);
             }
+            AppEvent::RemoveProjectMemoryNote { index } => {
+                self.chat_widget.remove_project_memory_note(index);
+            }
+            AppEvent::ResolveOversizedMessage(action) => {
+                self.chat_widget.resolve_oversized_message(action);
+            }
+            AppEvent::OpenSnapshotFiles { commit_id } => {
+                self.chat_widget.open_snapshot_files(&commit_id);
+            }
+            AppEvent::ShowSnapshotFile { title, view } => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(match view {
+                    SnapshotFileView::Contents(lines) => {
+                        Overlay::new_static_with_lines(lines, title)
+                    }
+                    SnapshotFileView::Diff(changes) => Overlay::new_static_with_renderables(
+                        vec![DiffSummary::new(changes, self.config.cwd.clone()).into()],
+                        title,
+                    ),
+                });
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::OfferPasteAsFile { placeholder } => {
+                self.chat_widget.open_paste_to_file_popup(placeholder);
+            }
+            AppEvent::ConvertPasteToFile { placeholder } => {
+                self.chat_widget.convert_paste_to_file(&placeholder);
+            }
+            AppEvent::ApprovalTimedOut { id } => {
+                self.chat_widget.dismiss_timed_out_approval(&id);
+            }
             AppEvent::OpenReasoningPopup { model } => {
                 self.chat_widget.open_reasoning_popup(model);
             }
@@ -1906,6 +2216,61 @@ impl App {
                     }
                 }
             }
+            AppEvent::ApplySetupChoices(choices) => {
+                if let Some(preset) = choices.preset() {
+                    self.app_event_tx
+                        .send(AppEvent::CodexOp(Op::OverrideTurnContext {
+                            cwd: None,
+                            approval_policy: Some(preset.approval),
+                            sandbox_policy: Some(preset.sandbox.clone()),
+                            windows_sandbox_level: None,
+                            model: Some(choices.model.clone()),
+                            effort: None,
+                            summary: None,
+                            collaboration_mode: None,
+                            personality: None,
+                        }));
+                    self.app_event_tx
+                        .send(AppEvent::UpdateAskForApprovalPolicy(preset.approval));
+                    self.app_event_tx
+                        .send(AppEvent::UpdateSandboxPolicy(preset.sandbox));
+                }
+                self.app_event_tx
+                    .send(AppEvent::UpdateModel(choices.model.clone()));
+                let notifications = Notifications::Enabled(choices.notifications);
+                self.config.tui_notifications = notifications.clone();
+                self.chat_widget.set_tui_notifications(notifications);
+
+                match ConfigEditsBuilder::new(&self.config.codex_home)
+                    .set_model(
+                        Some(choices.model.as_str()),
+                        self.config.model_reasoning_effort,
+                    )
+                    .set_permissions(choices.approval, choices.sandbox_mode)
+                    .set_tui_notifications(choices.notifications)
+                    .apply()
+                    .await
+                {
+                    Ok(()) => {
+                        self.chat_widget.add_info_message(
+                            "Setup saved to config.toml".to_string(),
+                            Some(format!(
+                                "{} with {}, notifications {}",
+                                choices.model,
+                                choices
+                                    .preset()
+                                    .map_or("custom permissions", |preset| preset.label),
+                                if choices.notifications { "on" } else { "off" },
+                            )),
+                        );
+                    }
+                    Err(err) => {
+                        tracing::error!(error = %err, "failed to persist setup choices");
+                        self.chat_widget
+                            .add_error_message(format!("Failed to save setup: {err}"));
+                    }
+                }
+            }
             AppEvent::UpdateAskForApprovalPolicy(policy) => {
                 self.runtime_approval_policy_override = Some(policy);
                 if let Err(err) = self.config.approval_policy.set(policy) {
@@ -2121,6 +2486,12 @@ impl App {
             AppEvent::SelectAgentThread(thread_id) => {
                 self.select_agent_thread(tui, thread_id).await?;
             }
+            AppEvent::OpenSessionPicker => {
+                self.open_session_picker();
+            }
+            AppEvent::SessionActivityChanged => {
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::OpenSkillsList => {
                 self.chat_widget.open_skills_list();
             }
@@ -2265,12 +2636,33 @@ impl App {
                 history_entry_count: 0,
                 initial_messages: None,
                 rollout_path: thread.rollout_path(),
+                tool_toggles: config_snapshot.tool_toggles,
+                base_instructions_tokens: None,
+                unknown_model: None,
+                sampling: None,
+                startup: None,
             }),
         };
-        let channel =
-            ThreadEventChannel::new_with_session_configured(THREAD_EVENT_CHANNEL_CAPACITY, event);
+        self.spawn_thread_listener(thread_id, thread, event);
+        Ok(())
+    }
+
+    /// Buffer `thread`'s events in a new channel and forward them while the
+    /// thread is focused. Background activity changes trigger a redraw so the
+    /// session tab bar stays current.
+    fn spawn_thread_listener(
+        &mut self,
+        thread_id: ThreadId,
+        thread: Arc<CodexThread>,
+        session_configured: Event,
+    ) {
+        let channel = ThreadEventChannel::new_with_session_configured(
+            THREAD_EVENT_CHANNEL_CAPACITY,
+            session_configured,
+        );
         let sender = channel.sender.clone();
         let store = Arc::clone(&channel.store);
+        let app_event_tx = self.app_event_tx.clone();
         self.thread_event_channels.insert(thread_id, channel);
         tokio::spawn(async move {
             loop {
@@ -2281,18 +2673,21 @@ impl App {
                         break;
                     }
                 };
-                let should_send = {
+                let (should_send, activity_changed) = {
                     let mut guard = store.lock().await;
+                    let before = guard.activity;
                     guard.push_event(event.clone());
-                    guard.active
+                    (guard.active, guard.activity != before)
                 };
+                if activity_changed {
+                    app_event_tx.send(AppEvent::SessionActivityChanged);
+                }
                 if should_send && let Err(err) = sender.send(event).await {
                     tracing::debug!("external thread {thread_id} channel closed: {err}");
                     break;
                 }
             }
         });
-        Ok(())
     }
 
     fn reasoning_label(reasoning_effort: Option<ReasoningEffortConfig>) -> &'static str {
@@ -2406,12 +2801,7 @@ impl App {
                 modifiers: crossterm::event::KeyModifiers::CONTROL,
                 kind: KeyEventKind::Press,
                 ..
-            } => {
-                // Enter alternate screen and set viewport to full size.
-                let _ = tui.enter_alt_screen();
-                self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
-                tui.frame_requester().schedule_frame();
-            }
+            } => self.open_transcript_overlay(tui),
             KeyEvent {
                 code: KeyCode::Char('g'),
                 modifiers: crossterm::event::KeyModifiers::CONTROL,
@@ -2427,6 +2817,17 @@ impl App {
                     self.request_external_editor_launch(tui);
                 }
             }
+            KeyEvent {
+                code: KeyCode::Tab,
+                modifiers: crossterm::event::KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } if self.overlay.is_none() && self.session_thread_ids.len() > 1 => {
+                if let Err(err) = self.focus_next_session(tui).await {
+                    tracing::warn!("failed to switch sessions: {err}");
+                }
+                tui.frame_requester().schedule_frame();
+            }
             // Esc primes/advances backtracking only in normal (not working) mode
             // with the composer focused and empty. In any other state, forward
             // Esc so the active UI (e.g. status indicator, modals, popups)
@@ -2475,6 +2876,13 @@ impl App {
         };
     }
 
+    fn open_transcript_overlay(&mut self, tui: &mut tui::Tui) {
+        // Enter alternate screen and set viewport to full size.
+        let _ = tui.enter_alt_screen();
+        self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
+        tui.frame_requester().schedule_frame();
+    }
+
     #[cfg(target_os = "windows")]
     fn spawn_world_writable_scan(
         cwd: PathBuf,
@@ -2527,6 +2935,7 @@ mod tests {
     use codex_core::protocol::SandboxPolicy;
     use codex_core::protocol::SessionConfiguredEvent;
     use codex_core::protocol::SessionSource;
+    use codex_core::protocol::ToolToggles;
     use codex_otel::OtelManager;
     use codex_protocol::ThreadId;
     use codex_protocol::user_input::TextElement;
@@ -2599,6 +3008,50 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn background_requests_need_attention_until_answered() -> Result<()> {
+        let mut app = make_test_app().await;
+        let thread_id = ThreadId::new();
+        app.thread_event_channels
+            .insert(thread_id, ThreadEventChannel::new(8));
+        let request = Event {
+            id: "turn-1".to_string(),
+            msg: EventMsg::ExecApprovalRequest(codex_core::protocol::ExecApprovalRequestEvent {
+                call_id: "call-1".to_string(),
+                turn_id: "turn-1".to_string(),
+                command: vec!["ls".to_string()],
+                cwd: PathBuf::from("/tmp"),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                parsed_cmd: Vec::new(),
+                cwd_outside_workspace: false,
+                cwd_writable: true,
+            }),
+        };
+
+        app.enqueue_thread_event(thread_id, request).await?;
+        let store = Arc::clone(
+            &app.thread_event_channels
+                .get(&thread_id)
+                .expect("missing thread channel")
+                .store,
+        );
+        assert!(store.lock().await.activity.needs_attention);
+
+        app.set_thread_active(thread_id, true).await;
+        app.active_thread_id = Some(thread_id);
+        assert!(!store.lock().await.activity.needs_attention);
+        assert_eq!(store.lock().await.snapshot().events.len(), 1);
+
+        app.resolve_active_request(&Op::ExecApproval {
+            id: "turn-1".to_string(),
+            decision: codex_core::protocol::ReviewDecision::Approved,
+        })
+        .await;
+        assert!(store.lock().await.snapshot().events.is_empty());
+        Ok(())
+    }
+
     async fn make_test_app() -> App {
         let (chat_widget, app_event_tx, _rx, _op_rx) = make_chatwidget_manual_with_sender().await;
         let config = chat_widget.config_ref().clone();
@@ -2644,6 +3097,7 @@ mod tests {
             primary_thread_id: None,
             primary_session_configured: None,
             pending_primary_events: VecDeque::new(),
+            session_thread_ids: Vec::new(),
         }
     }
 
@@ -2697,6 +3151,7 @@ mod tests {
                 primary_thread_id: None,
                 primary_session_configured: None,
                 pending_primary_events: VecDeque::new(),
+                session_thread_ids: Vec::new(),
             },
             rx,
             op_rx,
@@ -2933,12 +3388,18 @@ mod tests {
                 history_entry_count: 0,
                 initial_messages: None,
                 rollout_path: Some(PathBuf::new()),
+                tool_toggles: ToolToggles::default(),
+                base_instructions_tokens: None,
+                unknown_model: None,
+                sampling: None,
+                startup: None,
             };
             Arc::new(new_session_info(
                 app.chat_widget.config_ref(),
                 app.chat_widget.current_model(),
                 event,
                 is_first,
+                None,
             )) as Arc<dyn HistoryCell>
         };
 
@@ -2986,6 +3447,11 @@ mod tests {
                 history_entry_count: 0,
                 initial_messages: None,
                 rollout_path: Some(PathBuf::new()),
+                tool_toggles: ToolToggles::default(),
+                base_instructions_tokens: None,
+                unknown_model: None,
+                sampling: None,
+                startup: None,
             }),
         });
 
@@ -3032,6 +3498,11 @@ mod tests {
             history_entry_count: 0,
             initial_messages: None,
             rollout_path: Some(PathBuf::new()),
+            tool_toggles: ToolToggles::default(),
+            base_instructions_tokens: None,
+            unknown_model: None,
+            sampling: None,
+            startup: None,
         };
 
         app.chat_widget.handle_codex_event(Event {
@@ -246,7 +246,7 @@ impl App {
         if !self.transcript_cells.is_empty() {
             let width = tui.terminal.last_known_screen_size.width;
             for cell in &self.transcript_cells {
-                tui.insert_history_lines(cell.display_lines(width));
+                tui.insert_history_lines(self.scrollback_lines(cell.as_ref(), width));
             }
         }
     }
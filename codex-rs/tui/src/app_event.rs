@@ -20,6 +20,8 @@ use codex_protocol::openai_models::ModelPreset;
 
 use crate::bottom_pane::ApprovalRequest;
 use crate::history_cell::HistoryCell;
+use crate::history_command::SnapshotFileView;
+use crate::setup_wizard::SetupChoices;
 
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
@@ -55,12 +57,21 @@ pub(crate) enum AppEvent {
     /// Switch the active thread to the selected agent.
     SelectAgentThread(ThreadId),
 
-    /// Start a new session.
+    /// Open the session picker for switching between top-level conversations.
+    OpenSessionPicker,
+    /// A background session started or finished a turn, or is waiting on the
+    /// user; redraw the session tab bar.
+    SessionActivityChanged,
+
+    /// Start a new session alongside the open ones.
     NewSession,
 
     /// Open the resume picker inside the running TUI session.
     OpenResumePicker,
 
+    /// Open the transcript pager, as `Ctrl-T` does.
+    OpenTranscript,
+
     /// Fork the current session into a new thread.
     ForkCurrentSession,
 
@@ -92,6 +103,9 @@ pub(crate) enum AppEvent {
         matches: Vec<FileMatch>,
     },
 
+    /// A file was picked from the `@` popup. Later searches rank it higher.
+    FileMentioned(PathBuf),
+
     /// Result of refreshing rate limits
     RateLimitSnapshotFetched(RateLimitSnapshot),
 
@@ -128,6 +142,43 @@ pub(crate) enum AppEvent {
     /// Update the current personality in the running app and widget.
     UpdatePersonality(Personality),
 
+    /// Remove a note (by position, oldest first) from the project memory file.
+    RemoveProjectMemoryNote {
+        index: usize,
+    },
+
+    /// Send the message held back for exceeding the remaining context.
+    ResolveOversizedMessage(OversizedMessageAction),
+
+    /// Show the files captured by a ghost snapshot picked in `/history files`.
+    OpenSnapshotFiles {
+        commit_id: String,
+    },
+
+    /// Open a file read back from a ghost snapshot in a read-only overlay.
+    ShowSnapshotFile {
+        title: String,
+        view: SnapshotFileView,
+    },
+
+    /// A paste reached the `tui.paste_to_file` thresholds in `ask` mode; offer
+    /// to save the text behind `placeholder` to a workspace file.
+    OfferPasteAsFile {
+        placeholder: String,
+    },
+
+    /// Save the large paste behind `placeholder` to a workspace file and
+    /// replace it with an attachment chip.
+    ConvertPasteToFile {
+        placeholder: String,
+    },
+
+    /// Core has resolved approval request `id` after `approval_timeout_seconds`;
+    /// close its prompt.
+    ApprovalTimedOut {
+        id: String,
+    },
+
     /// Persist the selected model and reasoning effort to the appropriate config.
     PersistModelSelection {
         model: String,
@@ -139,6 +190,9 @@ pub(crate) enum AppEvent {
         personality: Personality,
     },
 
+    /// Apply the answers from `/setup` to the session and persist them to config.toml.
+    ApplySetupChoices(SetupChoices),
+
     /// Open the reasoning selection popup after picking a model.
     OpenReasoningPopup {
         model: ModelPreset,
@@ -310,6 +364,17 @@ pub(crate) enum ExitMode {
     Immediate,
 }
 
+/// How to send a message that likely exceeds the remaining context window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OversizedMessageAction {
+    /// Send the first and last lines with the middle elided.
+    SendExcerpt,
+    /// Save the full text to a file and send a reference to it.
+    SendFileReference,
+    /// Send the message unchanged.
+    SendAnyway,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum FeedbackCategory {
     BadResult,
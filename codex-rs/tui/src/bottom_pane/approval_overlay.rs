@@ -1,5 +1,8 @@
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
@@ -9,6 +12,7 @@ use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
 use crate::diff_render::DiffSummary;
+use crate::diff_render::display_path_for;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
 use crate::key_hint;
@@ -16,8 +20,12 @@ use crate::key_hint::KeyBinding;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
+use crate::tui::FrameRequester;
+use codex_core::config::types::ApprovalTimeout;
+use codex_core::config::types::ApprovalTimeoutAction;
 use codex_core::features::Feature;
 use codex_core::features::Features;
+use codex_core::protocol::CommandExplanationEvent;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::FileChange;
@@ -41,7 +49,13 @@ use ratatui::widgets::Wrap;
 pub(crate) enum ApprovalRequest {
     Exec {
         id: String,
+        call_id: String,
         command: Vec<String>,
+        cwd: PathBuf,
+        /// `cwd` is outside the session cwd and the workspace roots.
+        cwd_outside_workspace: bool,
+        /// The sandbox lets the command write to `cwd`.
+        cwd_writable: bool,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     },
@@ -50,6 +64,8 @@ pub(crate) enum ApprovalRequest {
         reason: Option<String>,
         cwd: PathBuf,
         changes: HashMap<PathBuf, FileChange>,
+        /// Files in `changes` that already had uncommitted changes.
+        dirty_paths: Vec<PathBuf>,
     },
     McpElicitation {
         server_name: String,
@@ -62,13 +78,21 @@ pub(crate) enum ApprovalRequest {
 pub(crate) struct ApprovalOverlay {
     current_request: Option<ApprovalRequest>,
     current_variant: Option<ApprovalVariant>,
-    queue: Vec<ApprovalRequest>,
+    /// When core resolves the current request on its own, if
+    /// `approval_timeout` is configured.
+    current_deadline: Option<Instant>,
+    queue: Vec<(ApprovalRequest, Option<Instant>)>,
+    approval_timeout: Option<ApprovalTimeout>,
+    frame_requester: Option<FrameRequester>,
     app_event_tx: AppEventSender,
     list: ListSelectionView,
     options: Vec<ApprovalOption>,
+    /// Explanations asked for with `e`, by exec call id.
+    explanations: HashMap<String, CommandExplanation>,
     current_complete: bool,
     done: bool,
     features: Features,
+    accessible: bool,
 }
 
 impl ApprovalOverlay {
@@ -76,36 +100,168 @@ impl ApprovalOverlay {
         let mut view = Self {
             current_request: None,
             current_variant: None,
+            current_deadline: None,
             queue: Vec::new(),
+            approval_timeout: None,
+            frame_requester: None,
             app_event_tx: app_event_tx.clone(),
             list: ListSelectionView::new(Default::default(), app_event_tx),
             options: Vec::new(),
+            explanations: HashMap::new(),
             current_complete: false,
             done: false,
             features,
+            accessible: false,
         };
-        view.set_current(request);
+        view.set_current(request, None);
         view
     }
 
+    /// Shows a countdown for exec and patch requests, which core resolves on
+    /// its own once `approval_timeout` expires.
+    pub(crate) fn with_approval_timeout(
+        mut self,
+        timeout: Option<ApprovalTimeout>,
+        frame_requester: FrameRequester,
+    ) -> Self {
+        self.approval_timeout = timeout;
+        self.frame_requester = Some(frame_requester);
+        if let Some(request) = self.current_request.as_ref() {
+            self.current_deadline = self.start_deadline(request);
+        }
+        self
+    }
+
+    /// Asks in plain text for screen readers: an unstyled question followed by
+    /// the keys that answer it.
+    pub(crate) fn with_accessible(mut self, accessible: bool) -> Self {
+        self.accessible = accessible;
+        if let Some(request) = self.current_request.clone() {
+            self.set_current(request, self.current_deadline);
+        }
+        self
+    }
+
     pub fn enqueue_request(&mut self, req: ApprovalRequest) {
-        self.queue.push(req);
+        let deadline = self.start_deadline(&req);
+        self.queue.push((req, deadline));
     }
 
-    fn set_current(&mut self, request: ApprovalRequest) {
-        self.current_request = Some(request.clone());
+    /// Starts the countdown for `request` and schedules
+    /// `AppEvent::ApprovalTimedOut` for when it runs out.
+    fn start_deadline(&self, request: &ApprovalRequest) -> Option<Instant> {
+        let timeout = self.approval_timeout?;
+        let id = timed_request_id(request)?.to_string();
+        let deadline = Instant::now() + timeout.duration;
+        // Without a runtime (unit tests) the countdown is shown but never fires.
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let app_event_tx = self.app_event_tx.clone();
+            handle.spawn(async move {
+                tokio::time::sleep_until(deadline.into()).await;
+                app_event_tx.send(AppEvent::ApprovalTimedOut { id });
+            });
+        }
+        Some(deadline)
+    }
+
+    fn countdown_line(&self) -> Option<Line<'static>> {
+        if self.current_complete {
+            return None;
+        }
+        let deadline = self.current_deadline?;
+        let timeout = self.approval_timeout?;
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let action = match timeout.action {
+            ApprovalTimeoutAction::Deny => "Auto-deny",
+            ApprovalTimeoutAction::Abort => "Auto-abort",
+        };
+        let seconds = remaining.as_secs();
+        Some(
+            Line::from(format!(
+                "  {action} in {}:{:02}",
+                seconds / 60,
+                seconds % 60
+            ))
+            .dim(),
+        )
+    }
+
+    fn set_current(&mut self, request: ApprovalRequest, deadline: Option<Instant>) {
+        self.current_request = Some(request);
+        self.current_deadline = deadline;
+        self.current_complete = false;
+        self.rebuild_list(None);
+    }
+
+    /// Rebuilds the prompt for the current request, highlighting
+    /// `selected_idx` instead of the first option when given.
+    fn rebuild_list(&mut self, selected_idx: Option<usize>) {
+        let Some(request) = self.current_request.clone() else {
+            return;
+        };
         let ApprovalRequestState { variant, header } = ApprovalRequestState::from(request);
+        let header: Box<dyn Renderable> = match self.explanation_lines(&variant) {
+            Some(lines) => Box::new(ColumnRenderable::with([
+                header,
+                Line::from("").into(),
+                Box::new(Paragraph::new(lines).wrap(Wrap { trim: false })) as Box<dyn Renderable>,
+            ])),
+            None => header,
+        };
         self.current_variant = Some(variant.clone());
-        self.current_complete = false;
-        let (options, params) = Self::build_options(variant, header, &self.features);
+        let (options, mut params) =
+            Self::build_options(variant, header, &self.features, self.accessible);
+        params.initial_selected_idx = selected_idx;
         self.options = options;
         self.list = ListSelectionView::new(params, self.app_event_tx.clone());
     }
 
+    fn explanation_lines(&self, variant: &ApprovalVariant) -> Option<Vec<Line<'static>>> {
+        let ApprovalVariant::Exec { call_id, .. } = variant else {
+            return None;
+        };
+        let line = match self.explanations.get(call_id)? {
+            CommandExplanation::Pending => Line::from("Explaining this command…".dim()),
+            CommandExplanation::Ready(explanation) => {
+                Line::from(vec!["Explanation: ".into(), explanation.clone().italic()])
+            }
+            CommandExplanation::Failed(error) => {
+                Line::from(format!("Could not explain this command: {error}").red())
+            }
+        };
+        Some(vec![line])
+    }
+
+    /// Asks core to explain the current command, once per request.
+    fn request_explanation(&mut self, option_idx: usize) {
+        let Some(ApprovalVariant::Exec {
+            call_id,
+            command,
+            cwd,
+            ..
+        }) = self.current_variant.clone()
+        else {
+            return;
+        };
+        if self.explanations.contains_key(&call_id) {
+            return;
+        }
+        self.explanations
+            .insert(call_id.clone(), CommandExplanation::Pending);
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::ExplainCommand {
+                call_id,
+                command,
+                cwd,
+            }));
+        self.rebuild_list(Some(option_idx));
+    }
+
     fn build_options(
         variant: ApprovalVariant,
         header: Box<dyn Renderable>,
         features: &Features,
+        accessible: bool,
     ) -> (Vec<ApprovalOption>, SelectionViewParams) {
         let (options, title) = match &variant {
             ApprovalVariant::Exec {
@@ -125,11 +281,20 @@ impl ApprovalOverlay {
             ),
         };
 
-        let header = Box::new(ColumnRenderable::with([
-            Line::from(title.bold()).into(),
-            Line::from("").into(),
-            header,
-        ]));
+        let header: Box<dyn Renderable> = if accessible {
+            Box::new(ColumnRenderable::with([
+                Line::from(format!("Question: {title}")).into(),
+                header,
+                Box::new(Paragraph::new(accessible_answers(&options)).wrap(Wrap { trim: false }))
+                    as Box<dyn Renderable>,
+            ]))
+        } else {
+            Box::new(ColumnRenderable::with([
+                Line::from(title.bold()).into(),
+                Line::from("").into(),
+                header,
+            ]))
+        };
 
         let items = options
             .iter()
@@ -166,6 +331,10 @@ impl ApprovalOverlay {
         let Some(option) = self.options.get(actual_idx) else {
             return;
         };
+        if matches!(option.decision, ApprovalDecision::Explain) {
+            self.request_explanation(actual_idx);
+            return;
+        }
         if let Some(variant) = self.current_variant.as_ref() {
             match (variant, &option.decision) {
                 (ApprovalVariant::Exec { id, command, .. }, ApprovalDecision::Review(decision)) => {
@@ -222,8 +391,8 @@ impl ApprovalOverlay {
     }
 
     fn advance_queue(&mut self) {
-        if let Some(next) = self.queue.pop() {
-            self.set_current(next);
+        if let Some((next, deadline)) = self.queue.pop() {
+            self.set_current(next, deadline);
         } else {
             self.done = true;
         }
@@ -314,15 +483,74 @@ impl BottomPaneView for ApprovalOverlay {
         self.enqueue_request(request);
         None
     }
+
+    fn on_command_explanation(&mut self, event: &CommandExplanationEvent) -> bool {
+        let explanation = match (&event.explanation, &event.error) {
+            (Some(explanation), _) => CommandExplanation::Ready(explanation.clone()),
+            (None, Some(error)) => CommandExplanation::Failed(error.clone()),
+            (None, None) => CommandExplanation::Failed("no explanation returned".to_string()),
+        };
+        self.explanations.insert(event.call_id.clone(), explanation);
+        let is_current = matches!(
+            &self.current_variant,
+            Some(ApprovalVariant::Exec { call_id, .. }) if *call_id == event.call_id
+        );
+        if !is_current || self.current_complete {
+            return false;
+        }
+        self.rebuild_list(self.list.selected_index());
+        true
+    }
+
+    fn dismiss_timed_out_approval(&mut self, id: &str) -> bool {
+        // Core keys approvals by turn, so a later request can share `id`; only
+        // drop requests whose own deadline has passed.
+        let now = Instant::now();
+        let expired = |request: &ApprovalRequest, deadline: Option<Instant>| {
+            timed_request_id(request) == Some(id) && deadline.is_some_and(|d| d <= now)
+        };
+        if !self.current_complete
+            && let Some(request) = self.current_request.as_ref()
+            && expired(request, self.current_deadline)
+        {
+            self.current_complete = true;
+            self.advance_queue();
+            return true;
+        }
+        let queued = self.queue.len();
+        self.queue
+            .retain(|(request, deadline)| !expired(request, *deadline));
+        self.queue.len() != queued
+    }
 }
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        let countdown_height = u16::from(self.countdown_line().is_some());
+        self.list.desired_height(width) + countdown_height
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        let Some(countdown) = self.countdown_line() else {
+            self.list.render(area, buf);
+            return;
+        };
+        let list_area = Rect {
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+        self.list.render(list_area, buf);
+        countdown.render(
+            Rect {
+                y: list_area.bottom(),
+                height: area.height - list_area.height,
+                ..area
+            },
+            buf,
+        );
+        if let Some(frame_requester) = self.frame_requester.as_ref() {
+            frame_requester.schedule_frame_in(Duration::from_secs(1));
+        }
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
@@ -340,7 +568,11 @@ impl From<ApprovalRequest> for ApprovalRequestState {
         match value {
             ApprovalRequest::Exec {
                 id,
+                call_id,
                 command,
+                cwd,
+                cwd_outside_workspace,
+                cwd_writable,
                 reason,
                 proposed_execpolicy_amendment,
             } => {
@@ -349,6 +581,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     header.push(Line::from(vec!["Reason: ".into(), reason.italic()]));
                     header.push(Line::from(""));
                 }
+                header.push(directory_line(&cwd, cwd_outside_workspace, cwd_writable));
                 let full_cmd = strip_bash_lc_and_escape(&command);
                 let mut full_cmd_lines = highlight_bash_to_lines(&full_cmd);
                 if let Some(first) = full_cmd_lines.first_mut() {
@@ -358,7 +591,9 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 Self {
                     variant: ApprovalVariant::Exec {
                         id,
+                        call_id,
                         command,
+                        cwd,
                         proposed_execpolicy_amendment,
                     },
                     header: Box::new(Paragraph::new(header).wrap(Wrap { trim: false })),
@@ -369,6 +604,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 reason,
                 cwd,
                 changes,
+                dirty_paths,
             } => {
                 let mut header: Vec<Box<dyn Renderable>> = Vec::new();
                 if let Some(reason) = reason
@@ -380,6 +616,18 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     ));
                     header.push(Box::new(Line::from("")));
                 }
+                if !dirty_paths.is_empty() {
+                    let lines: Vec<Line<'static>> = dirty_paths
+                        .iter()
+                        .map(|path| {
+                            format!("{} has uncommitted changes", display_path_for(path, &cwd))
+                                .red()
+                                .into()
+                        })
+                        .collect();
+                    header.push(Box::new(Paragraph::new(lines).wrap(Wrap { trim: false })));
+                    header.push(Box::new(Line::from("")));
+                }
                 header.push(DiffSummary::new(changes, cwd).into());
                 Self {
                     variant: ApprovalVariant::ApplyPatch { id },
@@ -409,11 +657,45 @@ impl From<ApprovalRequest> for ApprovalRequestState {
     }
 }
 
+/// The directory a command runs in, in red when it is outside the workspace.
+fn directory_line(cwd: &Path, outside_workspace: bool, writable: bool) -> Line<'static> {
+    let access = if writable {
+        "writable in sandbox"
+    } else {
+        "read-only in sandbox"
+    };
+    let path = cwd.display().to_string();
+    if outside_workspace {
+        Line::from(vec![
+            "Directory: ".into(),
+            path.red().bold(),
+            format!(" (outside the workspace, {access})").red(),
+        ])
+    } else {
+        Line::from(vec![
+            "Directory: ".into(),
+            path.bold(),
+            format!(" ({access})").dim(),
+        ])
+    }
+}
+
+/// Id of a request that core resolves after `approval_timeout`; elicitations
+/// are never timed out.
+fn timed_request_id(request: &ApprovalRequest) -> Option<&str> {
+    match request {
+        ApprovalRequest::Exec { id, .. } | ApprovalRequest::ApplyPatch { id, .. } => Some(id),
+        ApprovalRequest::McpElicitation { .. } => None,
+    }
+}
+
 #[derive(Clone)]
 enum ApprovalVariant {
     Exec {
         id: String,
+        call_id: String,
         command: Vec<String>,
+        cwd: PathBuf,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     },
     ApplyPatch {
@@ -429,6 +711,15 @@ enum ApprovalVariant {
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    /// Show a model-written explanation of the command without deciding.
+    Explain,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CommandExplanation {
+    Pending,
+    Ready(String),
+    Failed(String),
 }
 
 #[derive(Clone)]
@@ -447,6 +738,23 @@ impl ApprovalOption {
     }
 }
 
+/// `Answers: y for "Yes, proceed", esc or n for "No, ...".`
+fn accessible_answers(options: &[ApprovalOption]) -> Line<'static> {
+    let answers = options
+        .iter()
+        .map(|option| {
+            let keys = option
+                .shortcuts()
+                .map(|binding| Span::from(binding).content.into_owned())
+                .collect::<Vec<_>>()
+                .join(" or ");
+            format!("{keys} for \"{}\"", option.label)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    Line::from(format!("Answers: {answers}."))
+}
+
 fn exec_options(
     proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     features: &Features,
@@ -481,12 +789,20 @@ fn exec_options(
                 })
             }),
     )
-    .chain([ApprovalOption {
-        label: "No, and tell Codex what to do differently".to_string(),
-        decision: ApprovalDecision::Review(ReviewDecision::Abort),
-        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-    }])
+    .chain([
+        ApprovalOption {
+            label: "No, and tell Codex what to do differently".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Abort),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+        ApprovalOption {
+            label: "Explain this command".to_string(),
+            decision: ApprovalDecision::Explain,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('e'))],
+        },
+    ])
     .collect()
 }
 
@@ -546,7 +862,11 @@ mod tests {
     fn make_exec_request() -> ApprovalRequest {
         ApprovalRequest::Exec {
             id: "test".to_string(),
+            call_id: "call-test".to_string(),
             command: vec!["echo".to_string(), "hi".to_string()],
+            cwd: PathBuf::from("/repo"),
+            cwd_outside_workspace: false,
+            cwd_writable: true,
             reason: Some("reason".to_string()),
             proposed_execpolicy_amendment: None,
         }
@@ -563,6 +883,35 @@ mod tests {
         assert!(view.is_complete());
     }
 
+    #[test]
+    fn approval_timeout_shows_countdown_and_dismisses_expired_request() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(make_exec_request(), tx, Features::with_defaults())
+            .with_approval_timeout(
+                Some(ApprovalTimeout {
+                    duration: Duration::from_secs(90),
+                    action: ApprovalTimeoutAction::Deny,
+                }),
+                FrameRequester::test_dummy(),
+            );
+
+        let countdown: String = view
+            .countdown_line()
+            .expect("countdown for exec request")
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(countdown, "  Auto-deny in 1:29");
+        assert!(!view.dismiss_timed_out_approval("test"));
+        assert!(!view.is_complete());
+
+        view.current_deadline = Some(Instant::now());
+        assert!(view.dismiss_timed_out_approval("test"));
+        assert!(view.is_complete());
+    }
+
     #[test]
     fn shortcut_triggers_selection() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
@@ -588,7 +937,11 @@ mod tests {
         let mut view = ApprovalOverlay::new(
             ApprovalRequest::Exec {
                 id: "test".to_string(),
+                call_id: "call-test".to_string(),
                 command: vec!["echo".to_string()],
+                cwd: PathBuf::from("/repo"),
+                cwd_outside_workspace: false,
+                cwd_writable: true,
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
@@ -626,7 +979,11 @@ mod tests {
         let mut view = ApprovalOverlay::new(
             ApprovalRequest::Exec {
                 id: "test".to_string(),
+                call_id: "call-test".to_string(),
                 command: vec!["echo".to_string()],
+                cwd: PathBuf::from("/repo"),
+                cwd_outside_workspace: false,
+                cwd_writable: true,
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
@@ -639,12 +996,64 @@ mod tests {
                 features
             },
         );
-        assert_eq!(view.options.len(), 2);
+        assert_eq!(view.options.len(), 3);
         view.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
         assert!(!view.is_complete());
         assert!(rx.try_recv().is_err());
     }
 
+    #[test]
+    fn explain_shows_the_explanation_without_deciding() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(make_exec_request(), tx, Features::with_defaults());
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+
+        let mut ops = Vec::new();
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(op) = ev {
+                ops.push(op);
+            }
+        }
+        assert_eq!(
+            ops,
+            vec![Op::ExplainCommand {
+                call_id: "call-test".to_string(),
+                command: vec!["echo".to_string(), "hi".to_string()],
+                cwd: PathBuf::from("/repo"),
+            }]
+        );
+        assert!(!view.is_complete());
+        assert!(render_rows(&view).contains("Explaining this command…"));
+
+        assert!(view.on_command_explanation(&CommandExplanationEvent {
+            call_id: "call-test".to_string(),
+            explanation: Some("Prints hi to standard output; it changes nothing.".to_string()),
+            error: None,
+        }));
+        assert!(
+            render_rows(&view)
+                .contains("Explanation: Prints hi to standard output; it changes nothing.")
+        );
+        assert!(!view.is_complete());
+    }
+
+    fn render_rows(view: &ApprovalOverlay) -> String {
+        let area = Rect::new(0, 0, 120, view.desired_height(120));
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        (0..area.height)
+            .map(|row| {
+                (0..area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     #[test]
     fn header_includes_command_snippet() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -652,7 +1061,11 @@ mod tests {
         let command = vec!["echo".into(), "hello".into(), "world".into()];
         let exec_request = ApprovalRequest::Exec {
             id: "test".into(),
+            call_id: "call-test".into(),
             command,
+            cwd: PathBuf::from("/repo"),
+            cwd_outside_workspace: false,
+            cwd_writable: true,
             reason: None,
             proposed_execpolicy_amendment: None,
         };
@@ -676,6 +1089,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accessible_prompt_is_a_plain_question_with_answers() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let view = ApprovalOverlay::new(make_exec_request(), tx, Features::with_defaults())
+            .with_accessible(true);
+        let area = Rect::new(0, 0, 200, view.desired_height(200));
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+
+        let rendered: Vec<String> = (0..buf.area.height)
+            .map(|row| {
+                (0..buf.area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            })
+            .collect();
+        for expected in [
+            "Question: Would you like to run the following command?",
+            "$ echo hi",
+            "Answers: y for \"Yes, proceed\", esc or n for \"No, and tell Codex what to do differently\", e for \"Explain this command\".",
+        ] {
+            assert!(
+                rendered.iter().any(|line| line == expected),
+                "missing {expected:?} in {rendered:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn patch_header_marks_dirty_files_in_red() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let cwd = PathBuf::from("/repo");
+        let path = cwd.join("src/lib.rs");
+        let patch_request = ApprovalRequest::ApplyPatch {
+            id: "test".into(),
+            reason: None,
+            cwd,
+            changes: HashMap::from([(
+                path.clone(),
+                FileChange::Update {
+                    unified_diff: "@@ -1 +1 @@\n-old\n+new\n".into(),
+                    move_path: None,
+                },
+            )]),
+            dirty_paths: vec![path],
+        };
+
+        let view = ApprovalOverlay::new(patch_request, tx, Features::with_defaults());
+        let area = Rect::new(0, 0, 80, view.desired_height(80));
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+
+        let warning = "src/lib.rs has uncommitted changes";
+        let (row, line) = (0..buf.area.height)
+            .map(|row| {
+                let line: String = (0..buf.area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect();
+                (row, line)
+            })
+            .find(|(_, line)| line.contains(warning))
+            .expect("header should flag the dirty file");
+        let byte_offset = line.find(warning).expect("warning column");
+        let col = line[..byte_offset].chars().count() as u16;
+        assert_eq!(buf[(col, row)].fg, ratatui::style::Color::Red);
+    }
+
+    #[test]
+    fn exec_header_flags_a_directory_outside_the_workspace() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let exec_request = ApprovalRequest::Exec {
+            id: "test".into(),
+            call_id: "call-test".into(),
+            command: vec!["cat".into(), ".ssh/id_rsa".into()],
+            cwd: PathBuf::from("/home/user"),
+            cwd_outside_workspace: true,
+            cwd_writable: false,
+            reason: None,
+            proposed_execpolicy_amendment: None,
+        };
+
+        let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
+        let area = Rect::new(0, 0, 80, view.desired_height(80));
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+
+        let notice = "Directory: /home/user (outside the workspace, read-only in sandbox)";
+        let (row, line) = (0..buf.area.height)
+            .map(|row| {
+                let line: String = (0..buf.area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect();
+                (row, line)
+            })
+            .find(|(_, line)| line.contains(notice))
+            .expect("header should show the directory");
+        let byte_offset = line.find("/home/user").expect("path column");
+        let col = line[..byte_offset].chars().count() as u16;
+        assert_eq!(buf[(col, row)].fg, ratatui::style::Color::Red);
+    }
+
     #[test]
     fn exec_history_cell_wraps_with_two_space_indent() {
         let command = vec![
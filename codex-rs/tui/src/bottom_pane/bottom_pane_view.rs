@@ -1,5 +1,6 @@
 use crate::bottom_pane::ApprovalRequest;
 use crate::render::renderable::Renderable;
+use codex_core::protocol::CommandExplanationEvent;
 use codex_protocol::request_user_input::RequestUserInputEvent;
 use crossterm::event::KeyEvent;
 
@@ -58,6 +59,18 @@ pub(crate) trait BottomPaneView: Renderable {
         Some(request)
     }
 
+    /// Drop an approval request that core resolved on its own after the
+    /// approval timeout. Return true if the view changed.
+    fn dismiss_timed_out_approval(&mut self, _id: &str) -> bool {
+        false
+    }
+
+    /// Show an explanation requested for a pending exec approval. Return
+    /// true if the view changed.
+    fn on_command_explanation(&mut self, _event: &CommandExplanationEvent) -> bool {
+        false
+    }
+
     /// Try to handle request_user_input; return the original value if not
     /// consumed.
     fn try_consume_user_input_request(
@@ -115,6 +115,7 @@ use super::footer::footer_height;
 use super::footer::footer_hint_items_width;
 use super::footer::footer_line_width;
 use super::footer::inset_footer_hint_area;
+use super::footer::reasoning_effort_span;
 use super::footer::render_context_right;
 use super::footer::render_footer_from_props;
 use super::footer::render_footer_hint_items;
@@ -130,6 +131,7 @@ use super::slash_commands;
 use crate::bottom_pane::paste_burst::FlushResult;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::find_prompt_for_command;
 use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
@@ -143,6 +145,7 @@ use codex_common::fuzzy_match::fuzzy_match;
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use codex_protocol::models::local_image_label_text;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
 
@@ -155,9 +158,13 @@ use crate::bottom_pane::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
+use crate::paste_files::remove_paste;
+use crate::paste_files::save_paste;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_chatgpt::connectors;
 use codex_chatgpt::connectors::AppInfo;
+use codex_core::config::types::PasteToFileMode;
+use codex_core::config::types::PasteToFileSettings;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
 use std::cell::RefCell;
@@ -183,6 +190,10 @@ pub enum InputResult {
         text: String,
         text_elements: Vec<TextElement>,
     },
+    /// Guidance for the running turn, sent with Alt+Enter.
+    Steer {
+        text: String,
+    },
     Command(SlashCommand),
     CommandWithArgs(SlashCommand, String),
     None,
@@ -261,6 +272,11 @@ pub(crate) struct ChatComposer {
     current_file_query: Option<String>,
     pending_pastes: Vec<(String, String)>,
     large_paste_counters: HashMap<usize, usize>,
+    paste_to_file: PasteToFileSettings,
+    /// Working directory that large pastes are saved under; `None` disables saving.
+    paste_file_cwd: Option<PathBuf>,
+    /// Chip labels of pastes saved to files in the current draft, with their paths.
+    saved_pastes: Vec<(String, PathBuf)>,
     has_focus: bool,
     /// Invariant: attached images are labeled `[Image #1]..[Image #N]` in vec order.
     attached_images: Vec<AttachedImage>,
@@ -279,6 +295,7 @@ pub(crate) struct ChatComposer {
     footer_flash: Option<FooterFlash>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
+    context_window_approximate: bool,
     skills: Option<Vec<SkillMetadata>>,
     connectors_snapshot: Option<ConnectorsSnapshot>,
     dismissed_mention_popup_token: Option<String>,
@@ -288,6 +305,7 @@ pub(crate) struct ChatComposer {
     collaboration_modes_enabled: bool,
     config: ChatComposerConfig,
     collaboration_mode_indicator: Option<CollaborationModeIndicator>,
+    reasoning_effort_indicator: Option<ReasoningEffort>,
     connectors_enabled: bool,
     personality_command_enabled: bool,
     windows_degraded_sandbox_active: bool,
@@ -355,6 +373,9 @@ impl ChatComposer {
             current_file_query: None,
             pending_pastes: Vec::new(),
             large_paste_counters: HashMap::new(),
+            paste_to_file: PasteToFileSettings::default(),
+            paste_file_cwd: None,
+            saved_pastes: Vec::new(),
             has_focus: has_input_focus,
             attached_images: Vec::new(),
             placeholder_text,
@@ -369,6 +390,7 @@ impl ChatComposer {
             footer_flash: None,
             context_window_percent: None,
             context_window_used_tokens: None,
+            context_window_approximate: false,
             skills: None,
             connectors_snapshot: None,
             dismissed_mention_popup_token: None,
@@ -377,6 +399,7 @@ impl ChatComposer {
             collaboration_modes_enabled: false,
             config,
             collaboration_mode_indicator: None,
+            reasoning_effort_indicator: None,
             connectors_enabled: false,
             personality_command_enabled: false,
             windows_degraded_sandbox_active: false,
@@ -423,6 +446,10 @@ impl ChatComposer {
         self.collaboration_mode_indicator = indicator;
     }
 
+    pub fn set_reasoning_effort_indicator(&mut self, effort: Option<ReasoningEffort>) {
+        self.reasoning_effort_indicator = effort;
+    }
+
     pub fn set_personality_command_enabled(&mut self, enabled: bool) {
         self.personality_command_enabled = enabled;
     }
@@ -512,6 +539,10 @@ impl ChatComposer {
     ///
     /// Behavior:
     ///
+    /// - If the paste reaches the `tui.paste_to_file` thresholds in `auto` mode, saves it under
+    ///   the working directory and inserts an attachment chip that expands to a reference to the
+    ///   file on submit. In `ask` mode it is handled like a large paste and
+    ///   [`AppEvent::OfferPasteAsFile`] lets the user convert it.
     /// - If the paste is larger than `LARGE_PASTE_CHAR_THRESHOLD` chars, inserts a placeholder
     ///   element (expanded on submit) and stores the full text in `pending_pastes`.
     /// - Otherwise, if the paste looks like an image path, attaches the image and inserts a
@@ -523,10 +554,20 @@ impl ChatComposer {
     pub fn handle_paste(&mut self, pasted: String) -> bool {
         let pasted = pasted.replace("\r\n", "\n").replace('\r', "\n");
         let char_count = pasted.chars().count();
-        if char_count > LARGE_PASTE_CHAR_THRESHOLD {
+        let to_file = self.paste_file_cwd.is_some() && self.paste_to_file.applies_to(&pasted);
+        if to_file
+            && self.paste_to_file.mode == PasteToFileMode::Auto
+            && self.insert_saved_paste(&pasted)
+        {
+            // The attachment chip is already in the textarea.
+        } else if to_file || char_count > LARGE_PASTE_CHAR_THRESHOLD {
             let placeholder = self.next_large_paste_placeholder(char_count);
             self.textarea.insert_element(&placeholder);
-            self.pending_pastes.push((placeholder, pasted));
+            self.pending_pastes.push((placeholder.clone(), pasted));
+            if to_file && self.paste_to_file.mode == PasteToFileMode::Ask {
+                self.app_event_tx
+                    .send(AppEvent::OfferPasteAsFile { placeholder });
+            }
         } else if char_count > 1
             && self.image_paste_enabled()
             && self.handle_paste_image_path(pasted.clone())
@@ -541,6 +582,70 @@ impl ChatComposer {
         true
     }
 
+    /// Configures how large pastes are saved to files under `cwd`.
+    pub(crate) fn set_paste_to_file(&mut self, settings: PasteToFileSettings, cwd: PathBuf) {
+        self.paste_to_file = settings;
+        self.paste_file_cwd = Some(cwd);
+    }
+
+    /// Saves the large paste behind `placeholder` to a file and swaps the placeholder for an
+    /// attachment chip. Returns false if the placeholder is no longer in the draft or the file
+    /// could not be written.
+    pub(crate) fn convert_paste_to_file(&mut self, placeholder: &str) -> bool {
+        let Some(idx) = self
+            .pending_pastes
+            .iter()
+            .position(|(ph, _)| ph == placeholder)
+        else {
+            return false;
+        };
+        let Some(cwd) = self.paste_file_cwd.as_deref() else {
+            return false;
+        };
+        let saved = match save_paste(cwd, &self.pending_pastes[idx].1) {
+            Ok(saved) => saved,
+            Err(err) => {
+                self.send_paste_save_error(&err);
+                return false;
+            }
+        };
+        let label = saved.chip_label();
+        if !self.textarea.replace_element_payload(placeholder, &label) {
+            remove_paste(&saved.path);
+            return false;
+        }
+        self.pending_pastes[idx] = (label.clone(), saved.reference());
+        self.saved_pastes.push((label, saved.path));
+        true
+    }
+
+    fn insert_saved_paste(&mut self, pasted: &str) -> bool {
+        let Some(cwd) = self.paste_file_cwd.as_deref() else {
+            return false;
+        };
+        match save_paste(cwd, pasted) {
+            Ok(saved) => {
+                let label = saved.chip_label();
+                self.textarea.insert_element(&label);
+                self.pending_pastes.push((label.clone(), saved.reference()));
+                self.saved_pastes.push((label, saved.path));
+                true
+            }
+            Err(err) => {
+                self.send_paste_save_error(&err);
+                false
+            }
+        }
+    }
+
+    fn send_paste_save_error(&self, err: &std::io::Error) {
+        self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+            history_cell::new_error_event(format!(
+                "Failed to save paste to a file; keeping it inline: {err}"
+            )),
+        )));
+    }
+
     pub fn handle_paste_image_path(&mut self, pasted: String) -> bool {
         let Some(path_buf) = normalize_pasted_path(&pasted) else {
             return false;
@@ -1073,8 +1178,7 @@ impl ChatComposer {
                 }
                 let first_line = text.lines().next().unwrap_or("");
                 if let Some((name, _rest, _rest_offset)) = parse_slash_name(first_line)
-                    && let Some(prompt_name) = name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:"))
-                    && let Some(prompt) = self.custom_prompts.iter().find(|p| p.name == prompt_name)
+                    && let Some(prompt) = find_prompt_for_command(name, &self.custom_prompts)
                     && let Some(expanded) =
                         expand_if_numeric_with_positional_args(prompt, first_line, &text_elements)
                 {
@@ -1083,6 +1187,7 @@ impl ChatComposer {
                         &expanded.text_elements,
                     );
                     self.pending_pastes.clear();
+                    self.saved_pastes.clear();
                     self.textarea.set_text_clearing_elements("");
                     return (
                         InputResult::Submitted {
@@ -1307,6 +1412,7 @@ impl ChatComposer {
                 };
 
                 let sel_path = sel.to_string_lossy().to_string();
+                self.app_event_tx.send(AppEvent::FileMentioned(sel));
                 // If selected path looks like an image (png/jpeg), attach as image instead of inserting text.
                 let is_image = Self::is_image_path(&sel_path);
                 if is_image {
@@ -1839,15 +1945,7 @@ impl ChatComposer {
                     self.windows_degraded_sandbox_active,
                 )
                 .is_some();
-                let prompt_prefix = format!("{PROMPTS_CMD_PREFIX}:");
-                let is_known_prompt = name
-                    .strip_prefix(&prompt_prefix)
-                    .map(|prompt_name| {
-                        self.custom_prompts
-                            .iter()
-                            .any(|prompt| prompt.name == prompt_name)
-                    })
-                    .unwrap_or(false);
+                let is_known_prompt = find_prompt_for_command(name, &self.custom_prompts).is_some();
                 if !is_builtin && !is_known_prompt {
                     let message = format!(
                         r#"Unrecognized command '/{name}'. Type "/" for a list of supported commands."#
@@ -1909,6 +2007,7 @@ impl ChatComposer {
             });
         }
         self.pending_pastes.clear();
+        self.saved_pastes.clear();
         Some((text, text_elements))
     }
 
@@ -2009,6 +2108,21 @@ impl ChatComposer {
         }
     }
 
+    /// Sends the composer text to the running turn without interrupting it.
+    /// Slash commands still dispatch as they would on Enter.
+    fn handle_steer(&mut self) -> (InputResult, bool) {
+        if let Some(result) = self.try_dispatch_bare_slash_command() {
+            return (result, true);
+        }
+        if let Some(result) = self.try_dispatch_slash_command_with_args() {
+            return (result, true);
+        }
+        match self.prepare_submission_text() {
+            Some((text, _text_elements)) => (InputResult::Steer { text }, true),
+            None => (InputResult::None, true),
+        }
+    }
+
     /// Check if the first line is a bare slash command (no args) and dispatch it.
     /// Returns Some(InputResult) if a command was dispatched, None otherwise.
     fn try_dispatch_bare_slash_command(&mut self) -> Option<InputResult> {
@@ -2054,7 +2168,21 @@ impl ChatComposer {
                     self.personality_command_enabled,
                     self.windows_degraded_sandbox_active,
                 )
-                && matches!(cmd, SlashCommand::Review | SlashCommand::Rename)
+                && matches!(
+                    cmd,
+                    SlashCommand::Review
+                        | SlashCommand::Rename
+                        | SlashCommand::Title
+                        | SlashCommand::Export
+                        | SlashCommand::Diff
+                        | SlashCommand::History
+                        | SlashCommand::Commit
+                        | SlashCommand::Reasoning
+                        | SlashCommand::Language
+                        | SlashCommand::Steer
+                        | SlashCommand::Ask
+                        | SlashCommand::Attach
+                )
             {
                 self.textarea.set_text_clearing_elements("");
                 return Some(InputResult::CommandWithArgs(cmd, rest.to_string()));
@@ -2128,6 +2256,12 @@ impl ChatComposer {
                 kind: KeyEventKind::Press,
                 ..
             } if self.is_task_running => self.handle_submission(true),
+            KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::ALT,
+                kind: KeyEventKind::Press,
+                ..
+            } if self.is_task_running && self.attached_images.is_empty() => self.handle_steer(),
             KeyEvent {
                 code: KeyCode::Enter,
                 modifiers: KeyModifiers::NONE,
@@ -2322,6 +2456,14 @@ impl ChatComposer {
             .filter(|payload| !elements_after.contains(payload))
         {
             self.pending_pastes.retain(|(ph, _)| ph != &removed);
+            if let Some(idx) = self
+                .saved_pastes
+                .iter()
+                .position(|(label, _)| label == &removed)
+            {
+                let (_, path) = self.saved_pastes.remove(idx);
+                remove_paste(&path);
+            }
 
             if let Some(idx) = self
                 .attached_images
@@ -2399,6 +2541,7 @@ impl ChatComposer {
             is_wsl,
             context_window_percent: self.context_window_percent,
             context_window_used_tokens: self.context_window_used_tokens,
+            context_window_approximate: self.context_window_approximate,
         }
     }
 
@@ -2781,13 +2924,15 @@ impl ChatComposer {
         self.is_task_running = running;
     }
 
-    pub(crate) fn set_context_window(&mut self, percent: Option<i64>, used_tokens: Option<i64>) {
-        if self.context_window_percent == percent && self.context_window_used_tokens == used_tokens
-        {
-            return;
-        }
+    pub(crate) fn set_context_window(
+        &mut self,
+        percent: Option<i64>,
+        used_tokens: Option<i64>,
+        approximate: bool,
+    ) {
         self.context_window_percent = percent;
         self.context_window_used_tokens = used_tokens;
+        self.context_window_approximate = approximate;
     }
 
     pub(crate) fn set_esc_backtrack_hint(&mut self, show: bool) {
@@ -2891,10 +3036,14 @@ impl ChatComposer {
                     | FooterMode::ShortcutOverlay
                     | FooterMode::EscHint => false,
                 };
-                let context_line = context_window_line(
+                let mut context_line = context_window_line(
                     footer_props.context_window_percent,
                     footer_props.context_window_used_tokens,
+                    footer_props.context_window_approximate,
                 );
+                if let Some(effort) = self.reasoning_effort_indicator {
+                    context_line.spans.insert(0, reasoning_effort_span(effort));
+                }
                 let context_width = context_line.width() as u16;
                 let custom_height = self.custom_footer_height();
                 let footer_hint_height =
@@ -3358,7 +3507,7 @@ mod tests {
         ) {
             composer.set_collaboration_modes_enabled(true);
             composer.set_collaboration_mode_indicator(indicator);
-            composer.set_context_window(Some(context_percent), None);
+            composer.set_context_window(Some(context_percent), None, false);
         }
 
         // Empty textarea, agent idle: shortcuts hint can show, and cycle hint is hidden.
@@ -4346,6 +4495,127 @@ mod tests {
         assert!(composer.pending_pastes.is_empty());
     }
 
+    /// Behavior: in `auto` mode a paste over the line threshold is saved under the working
+    /// directory and submitted as a reference to the file.
+    #[test]
+    fn paste_to_file_auto_saves_paste_and_submits_reference() {
+        use crate::paste_files::PASTES_DIR;
+        use codex_core::config::types::PasteToFileMode;
+        use codex_core::config::types::PasteToFileSettings;
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let cwd = tempdir().expect("tempdir");
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_steer_enabled(true);
+        composer.set_paste_to_file(
+            PasteToFileSettings {
+                mode: PasteToFileMode::Auto,
+                min_lines: 3,
+                min_bytes: usize::MAX,
+            },
+            cwd.path().to_path_buf(),
+        );
+
+        composer.handle_paste("one\ntwo\nthree\n".to_string());
+
+        assert_eq!(composer.saved_pastes.len(), 1);
+        let path = composer.saved_pastes[0].1.clone();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("paste file name");
+        let display_path = format!("{PASTES_DIR}/{file_name}");
+        assert_eq!(
+            composer.textarea.text(),
+            format!("[Attached {display_path} · 3 lines]")
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read saved paste"),
+            "one\ntwo\nthree\n"
+        );
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        match result {
+            InputResult::Submitted { text, .. } => assert_eq!(
+                text,
+                format!(
+                    "attached paste at {display_path}, 3 lines — read relevant sections with the shell tool"
+                )
+            ),
+            _ => panic!("expected Submitted"),
+        }
+        assert!(composer.saved_pastes.is_empty());
+        assert!(path.exists());
+    }
+
+    /// Behavior: in `ask` mode the paste stays inline until the user accepts the offer; removing
+    /// the resulting chip deletes the saved file.
+    #[test]
+    fn paste_to_file_ask_converts_on_request_and_chip_removal_deletes_file() {
+        use codex_core::config::types::PasteToFileMode;
+        use codex_core::config::types::PasteToFileSettings;
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let cwd = tempdir().expect("tempdir");
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_paste_to_file(
+            PasteToFileSettings {
+                mode: PasteToFileMode::Ask,
+                min_lines: 3,
+                min_bytes: usize::MAX,
+            },
+            cwd.path().to_path_buf(),
+        );
+
+        composer.handle_paste("a\nb\nc\nd\n".to_string());
+
+        let placeholder = "[Pasted Content 8 chars]".to_string();
+        assert_eq!(composer.textarea.text(), placeholder);
+        match rx.try_recv() {
+            Ok(AppEvent::OfferPasteAsFile {
+                placeholder: offered,
+            }) => {
+                assert_eq!(offered, placeholder);
+            }
+            other => panic!("expected OfferPasteAsFile, got {other:?}"),
+        }
+
+        assert!(composer.convert_paste_to_file(&placeholder));
+        assert_eq!(composer.saved_pastes.len(), 1);
+        let (label, path) = composer.saved_pastes[0].clone();
+        assert_eq!(composer.textarea.text(), label);
+        assert_eq!(composer.pending_pastes.len(), 1);
+        assert_eq!(composer.pending_pastes[0].0, label);
+        assert!(path.exists());
+        assert!(!composer.convert_paste_to_file(&placeholder));
+
+        composer.handle_key_event(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        assert!(composer.pending_pastes.is_empty());
+        assert!(composer.saved_pastes.is_empty());
+        assert!(!path.exists());
+    }
+
     #[test]
     fn ui_snapshots() {
         use crossterm::event::KeyCode;
@@ -54,6 +54,12 @@ impl CustomPromptView {
             complete: false,
         }
     }
+
+    /// Prefill the input, leaving the cursor after `text`.
+    pub(crate) fn with_initial_text(mut self, text: &str) -> Self {
+        self.textarea.insert_str(text);
+        self
+    }
 }
 
 impl BottomPaneView for CustomPromptView {
@@ -7,6 +7,7 @@ use ratatui::widgets::WidgetRef;
 
 use crate::render::Insets;
 use crate::render::RectExt;
+use crate::workspace_roots;
 
 use super::popup_consts::MAX_POPUP_ROWS;
 use super::scroll_state::ScrollState;
@@ -22,7 +23,7 @@ pub(crate) struct FileSearchPopup {
     pending_query: String,
     /// When `true` we are still waiting for results for `pending_query`.
     waiting: bool,
-    /// Cached matches; paths relative to the search dir.
+    /// Cached matches; paths relative to their search root.
     matches: Vec<FileMatch>,
     /// Shared selection/scroll state.
     state: ScrollState,
@@ -91,11 +92,18 @@ impl FileSearchPopup {
         self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
     }
 
-    pub(crate) fn selected_match(&self) -> Option<&PathBuf> {
-        self.state
+    /// Path to insert for the selected match: relative to the cwd, or absolute
+    /// for matches under another workspace root.
+    pub(crate) fn selected_match(&self) -> Option<PathBuf> {
+        let file_match = self
+            .state
             .selected_idx
-            .and_then(|idx| self.matches.get(idx))
-            .map(|file_match| &file_match.path)
+            .and_then(|idx| self.matches.get(idx))?;
+        if workspace_roots::root_label(&file_match.root).is_some() {
+            Some(file_match.full_path())
+        } else {
+            Some(file_match.path.clone())
+        }
     }
 
     pub(crate) fn calculate_required_height(&self) -> u16 {
@@ -117,17 +125,26 @@ impl WidgetRef for &FileSearchPopup {
         } else {
             self.matches
                 .iter()
-                .map(|m| GenericDisplayRow {
-                    name: m.path.to_string_lossy().to_string(),
-                    match_indices: m
-                        .indices
-                        .as_ref()
-                        .map(|v| v.iter().map(|&i| i as usize).collect()),
-                    display_shortcut: None,
-                    description: None,
-                    wrap_indent: None,
-                    is_disabled: false,
-                    disabled_reason: None,
+                .map(|m| {
+                    let path = m.path.to_string_lossy();
+                    // Matches under another workspace root are prefixed with
+                    // its name, shifting the highlighted characters.
+                    let (name, offset) = match workspace_roots::root_label(&m.root) {
+                        Some(label) => (format!("{label}/{path}"), label.chars().count() + 1),
+                        None => (path.to_string(), 0),
+                    };
+                    GenericDisplayRow {
+                        name,
+                        match_indices: m
+                            .indices
+                            .as_ref()
+                            .map(|v| v.iter().map(|&i| i as usize + offset).collect()),
+                        display_shortcut: None,
+                        description: None,
+                        wrap_indent: None,
+                        is_disabled: false,
+                        disabled_reason: None,
+                    }
                 })
                 .collect()
         };
@@ -37,6 +37,7 @@ use crate::key_hint::KeyBinding;
 use crate::render::line_utils::prefix_lines;
 use crate::status::format_tokens_compact;
 use crate::ui_consts::FOOTER_INDENT_COLS;
+use codex_protocol::openai_models::ReasoningEffort;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -68,6 +69,8 @@ pub(crate) struct FooterProps {
     pub(crate) quit_shortcut_key: KeyBinding,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
+    /// Whether the context values include estimated, not yet reported, input.
+    pub(crate) context_window_approximate: bool,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -741,15 +744,36 @@ fn build_columns(entries: Vec<Line<'static>>) -> Vec<Line<'static>> {
         .collect()
 }
 
-pub(crate) fn context_window_line(percent: Option<i64>, used_tokens: Option<i64>) -> Line<'static> {
+/// Dim prefix for the context indicator naming the reasoning effort chosen
+/// during this session, e.g. `high reasoning · `.
+pub(crate) fn reasoning_effort_span(effort: ReasoningEffort) -> Span<'static> {
+    Span::from(format!("{effort} reasoning · ")).dim()
+}
+
+/// Renders the context meter. `approximate` marks values that include
+/// locally estimated input not yet counted by the server.
+pub(crate) fn context_window_line(
+    percent: Option<i64>,
+    used_tokens: Option<i64>,
+    approximate: bool,
+) -> Line<'static> {
+    let (prefix, suffix) = if approximate {
+        ("~", " (est.)")
+    } else {
+        ("", "")
+    };
     if let Some(percent) = percent {
         let percent = percent.clamp(0, 100);
-        return Line::from(vec![Span::from(format!("{percent}% context left")).dim()]);
+        return Line::from(vec![
+            Span::from(format!("{prefix}{percent}% context left{suffix}")).dim(),
+        ]);
     }
 
     if let Some(tokens) = used_tokens {
         let used_fmt = format_tokens_compact(tokens);
-        return Line::from(vec![Span::from(format!("{used_fmt} used")).dim()]);
+        return Line::from(vec![
+            Span::from(format!("{prefix}{used_fmt} used{suffix}")).dim(),
+        ]);
     }
 
     Line::from(vec![Span::from("100% context left").dim()])
@@ -978,6 +1002,7 @@ mod tests {
                 let context_line = context_window_line(
                     props.context_window_percent,
                     props.context_window_used_tokens,
+                    props.context_window_approximate,
                 );
                 let context_width = context_line.width() as u16;
                 let show_cycle_hint = !props.is_task_running;
@@ -1077,6 +1102,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1093,6 +1119,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1109,6 +1136,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1125,6 +1153,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1141,6 +1170,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1157,6 +1187,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1173,6 +1204,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1189,6 +1221,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: Some(72),
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1205,6 +1238,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: Some(123_456),
+                context_window_approximate: false,
             },
         );
 
@@ -1221,6 +1255,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1237,6 +1272,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_window_approximate: false,
             },
         );
 
@@ -1251,6 +1287,7 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
+            context_window_approximate: false,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1278,6 +1315,7 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
+            context_window_approximate: false,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -282,6 +282,13 @@ impl ListSelectionView {
         self.last_selected_actual_idx.take()
     }
 
+    /// Index into the items of the highlighted row.
+    pub(crate) fn selected_index(&self) -> Option<usize> {
+        self.state
+            .selected_idx
+            .and_then(|visible_idx| self.filtered_indices.get(visible_idx).copied())
+    }
+
     fn rows_width(total_width: u16) -> u16 {
         total_width.saturating_sub(2)
     }
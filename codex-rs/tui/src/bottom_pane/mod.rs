@@ -25,11 +25,16 @@ use crate::key_hint::KeyBinding;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableItem;
+use crate::session_recording;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
+use codex_core::config::types::ApprovalTimeout;
+use codex_core::config::types::PasteToFileSettings;
 use codex_core::features::Features;
+use codex_core::protocol::CommandExplanationEvent;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::request_user_input::RequestUserInputEvent;
 use codex_protocol::user_input::TextElement;
 use crossterm::event::KeyCode;
@@ -61,6 +66,7 @@ mod file_search_popup;
 mod footer;
 mod list_selection_view;
 mod prompt_args;
+mod setup_wizard_view;
 mod skill_popup;
 mod skills_toggle_view;
 mod slash_commands;
@@ -119,6 +125,7 @@ pub(crate) use experimental_features_view::ExperimentalFeatureItem;
 pub(crate) use experimental_features_view::ExperimentalFeaturesView;
 pub(crate) use list_selection_view::SelectionAction;
 pub(crate) use list_selection_view::SelectionItem;
+pub(crate) use setup_wizard_view::SetupWizardView;
 
 /// Pane displayed in the lower half of the chat UI.
 ///
@@ -151,6 +158,11 @@ pub(crate) struct BottomPane {
     queued_user_messages: QueuedUserMessages,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
+    context_window_approximate: bool,
+    /// Countdown shown on exec and patch approvals; see `approval_timeout_seconds`.
+    approval_timeout: Option<ApprovalTimeout>,
+    /// `tui.accessible`: static status line and approvals spelled out as questions.
+    accessible: bool,
 }
 
 pub(crate) struct BottomPaneParams {
@@ -201,6 +213,9 @@ impl BottomPane {
             animations_enabled,
             context_window_percent: None,
             context_window_used_tokens: None,
+            context_window_approximate: false,
+            approval_timeout: None,
+            accessible: false,
         }
     }
 
@@ -245,6 +260,13 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Show the reasoning effort picked during this session next to the
+    /// context indicator; `None` hides it.
+    pub fn set_reasoning_effort_indicator(&mut self, effort: Option<ReasoningEffort>) {
+        self.composer.set_reasoning_effort_indicator(effort);
+        self.request_redraw();
+    }
+
     pub fn set_personality_command_enabled(&mut self, enabled: bool) {
         self.composer.set_personality_command_enabled(enabled);
         self.request_redraw();
@@ -268,6 +290,11 @@ impl BottomPane {
         self.context_window_used_tokens
     }
 
+    #[cfg(test)]
+    pub(crate) fn context_window_approximate(&self) -> bool {
+        self.context_window_approximate
+    }
+
     fn active_view(&self) -> Option<&dyn BottomPaneView> {
         self.view_stack.last().map(std::convert::AsRef::as_ref)
     }
@@ -459,12 +486,21 @@ impl BottomPane {
     /// Passing `None` clears any existing details. No-ops if the status indicator is not active.
     pub(crate) fn update_status(&mut self, header: String, details: Option<String>) {
         if let Some(status) = self.status.as_mut() {
+            session_recording::record_status(&header);
             status.update_header(header);
             status.update_details(details);
             self.request_redraw();
         }
     }
 
+    /// Update the transient subtitle shown after the status header.
+    pub(crate) fn update_status_subtitle(&mut self, subtitle: Option<String>) {
+        if let Some(status) = self.status.as_mut() {
+            status.update_subtitle(subtitle);
+            self.request_redraw();
+        }
+    }
+
     /// Show the transient "press again to quit" hint for `key`.
     ///
     /// `ChatWidget` owns the quit shortcut state machine (it decides when quit is
@@ -535,14 +571,18 @@ impl BottomPane {
         if running {
             if !was_running {
                 if self.status.is_none() {
-                    self.status = Some(StatusIndicatorWidget::new(
-                        self.app_event_tx.clone(),
-                        self.frame_requester.clone(),
-                        self.animations_enabled,
-                    ));
+                    self.status = Some(
+                        StatusIndicatorWidget::new(
+                            self.app_event_tx.clone(),
+                            self.frame_requester.clone(),
+                            self.animations_enabled,
+                        )
+                        .with_accessible(self.accessible),
+                    );
                 }
                 if let Some(status) = self.status.as_mut() {
                     status.set_interrupt_hint_visible(true);
+                    session_recording::record_status(status.header());
                 }
                 self.request_redraw();
             }
@@ -561,11 +601,14 @@ impl BottomPane {
 
     pub(crate) fn ensure_status_indicator(&mut self) {
         if self.status.is_none() {
-            self.status = Some(StatusIndicatorWidget::new(
-                self.app_event_tx.clone(),
-                self.frame_requester.clone(),
-                self.animations_enabled,
-            ));
+            self.status = Some(
+                StatusIndicatorWidget::new(
+                    self.app_event_tx.clone(),
+                    self.frame_requester.clone(),
+                    self.animations_enabled,
+                )
+                .with_accessible(self.accessible),
+            );
             self.request_redraw();
         }
     }
@@ -577,16 +620,26 @@ impl BottomPane {
         }
     }
 
-    pub(crate) fn set_context_window(&mut self, percent: Option<i64>, used_tokens: Option<i64>) {
-        if self.context_window_percent == percent && self.context_window_used_tokens == used_tokens
+    /// Updates the context meter. `approximate` marks values that include
+    /// locally estimated input not yet counted by the server.
+    pub(crate) fn set_context_window(
+        &mut self,
+        percent: Option<i64>,
+        used_tokens: Option<i64>,
+        approximate: bool,
+    ) {
+        if self.context_window_percent == percent
+            && self.context_window_used_tokens == used_tokens
+            && self.context_window_approximate == approximate
         {
             return;
         }
 
         self.context_window_percent = percent;
         self.context_window_used_tokens = used_tokens;
+        self.context_window_approximate = approximate;
         self.composer
-            .set_context_window(percent, self.context_window_used_tokens);
+            .set_context_window(percent, used_tokens, approximate);
         self.request_redraw();
     }
 
@@ -614,6 +667,26 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_paste_to_file(&mut self, settings: PasteToFileSettings, cwd: PathBuf) {
+        self.composer.set_paste_to_file(settings, cwd);
+    }
+
+    pub(crate) fn set_approval_timeout(&mut self, timeout: Option<ApprovalTimeout>) {
+        self.approval_timeout = timeout;
+    }
+
+    pub(crate) fn set_accessible(&mut self, accessible: bool) {
+        self.accessible = accessible;
+    }
+
+    /// Replaces a large-paste placeholder with an attachment chip backed by a
+    /// workspace file.
+    pub(crate) fn convert_paste_to_file(&mut self, placeholder: &str) {
+        if self.composer.convert_paste_to_file(placeholder) {
+            self.request_redraw();
+        }
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.composer.is_empty()
     }
@@ -662,11 +735,37 @@ impl BottomPane {
         };
 
         // Otherwise create a new approval modal overlay.
-        let modal = ApprovalOverlay::new(request, self.app_event_tx.clone(), features.clone());
+        let modal = ApprovalOverlay::new(request, self.app_event_tx.clone(), features.clone())
+            .with_approval_timeout(self.approval_timeout, self.frame_requester.clone())
+            .with_accessible(self.accessible);
         self.pause_status_timer_for_modal();
         self.push_view(Box::new(modal));
     }
 
+    /// Called when core resolved approval `id` after the approval timeout.
+    pub(crate) fn dismiss_timed_out_approval(&mut self, id: &str) {
+        let Some(view) = self.view_stack.last_mut() else {
+            return;
+        };
+        if !view.dismiss_timed_out_approval(id) {
+            return;
+        }
+        if view.is_complete() {
+            self.view_stack.pop();
+            self.on_active_view_complete();
+        }
+        self.request_redraw();
+    }
+
+    /// Called when core answers an `e: explain` request from an approval.
+    pub(crate) fn on_command_explanation(&mut self, event: &CommandExplanationEvent) {
+        if let Some(view) = self.view_stack.last_mut()
+            && view.on_command_explanation(event)
+        {
+            self.request_redraw();
+        }
+    }
+
     /// Called when the agent requests user input.
     pub fn push_user_input_request(&mut self, request: RequestUserInputEvent) {
         let request = if let Some(view) = self.view_stack.last_mut() {
@@ -864,7 +963,11 @@ mod tests {
     fn exec_request() -> ApprovalRequest {
         ApprovalRequest::Exec {
             id: "1".to_string(),
+            call_id: "call-1".to_string(),
             command: vec!["echo".into(), "ok".into()],
+            cwd: PathBuf::from("/repo"),
+            cwd_outside_workspace: false,
+            cwd_writable: true,
             reason: None,
             proposed_execpolicy_amendment: None,
         }
@@ -7,6 +7,9 @@ use regex_lite::Regex;
 use shlex::Shlex;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::slash_command::SlashCommand;
 
 lazy_static! {
     static ref PROMPT_ARG_REGEX: Regex =
@@ -183,10 +186,29 @@ pub fn parse_prompt_inputs(
     Ok(map)
 }
 
-/// Expands a message of the form `/prompts:name [value] [value] …` using a matching saved prompt.
+/// Returns the saved prompt invoked by the slash command `name`, if any.
 ///
-/// If the text does not start with `/prompts:`, or if no prompt named `name` exists,
-/// the function returns `Ok(None)`. On success it returns
+/// Prompts can be invoked as `/prompts:name` or, when `name` does not collide
+/// with a built-in command, as plain `/name`. Built-in commands always win.
+pub fn find_prompt_for_command<'a>(
+    name: &str,
+    custom_prompts: &'a [CustomPrompt],
+) -> Option<&'a CustomPrompt> {
+    let prompt_name = match name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:")) {
+        Some(prompt_name) => prompt_name,
+        None if SlashCommand::from_str(name).is_ok() => return None,
+        None => name,
+    };
+    custom_prompts
+        .iter()
+        .find(|prompt| prompt.name == prompt_name)
+}
+
+/// Expands a message of the form `/prompts:name [value] [value] …` (or `/name …`)
+/// using a matching saved prompt.
+///
+/// If the text is not a slash command, or no saved prompt matches it (see
+/// [`find_prompt_for_command`]), the function returns `Ok(None)`. On success it returns
 /// `Ok(Some(expanded))`; otherwise it returns a descriptive error.
 pub fn expand_custom_prompt(
     text: &str,
@@ -197,15 +219,9 @@ pub fn expand_custom_prompt(
         return Ok(None);
     };
 
-    // Only handle custom prompts when using the explicit prompts prefix with a colon.
-    let Some(prompt_name) = name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:")) else {
+    let Some(prompt) = find_prompt_for_command(name, custom_prompts) else {
         return Ok(None);
     };
-
-    let prompt = match custom_prompts.iter().find(|p| p.name == prompt_name) {
-        Some(prompt) => prompt,
-        None => return Ok(None),
-    };
     // If there are named placeholders, expect key=value inputs.
     let required = prompt_argument_names(&prompt.content);
     let local_elements: Vec<TextElement> = text_elements
@@ -283,11 +299,10 @@ pub fn extract_positional_args_for_prompt_line(
     let Some((name, rest, rest_offset)) = parse_slash_name(trimmed) else {
         return Vec::new();
     };
-    // Require the explicit prompts prefix for custom prompt invocations.
-    let Some(after_prefix) = name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:")) else {
-        return Vec::new();
-    };
-    if after_prefix != prompt_name {
+    let invoked_name = name
+        .strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:"))
+        .unwrap_or(name);
+    if invoked_name != prompt_name {
         return Vec::new();
     }
     let rest_trimmed_start = rest.trim_start();
@@ -610,6 +625,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bare_name_expands_unless_it_is_a_builtin() {
+        let prompts = vec![
+            CustomPrompt {
+                name: "fix-issue".to_string(),
+                path: "/tmp/fix-issue.md".to_string().into(),
+                content: "Fix issue $1 ($ARGUMENTS)".to_string(),
+                description: None,
+                argument_hint: None,
+            },
+            CustomPrompt {
+                name: "review".to_string(),
+                path: "/tmp/review.md".to_string().into(),
+                content: "My review checklist".to_string(),
+                description: None,
+                argument_hint: None,
+            },
+        ];
+
+        assert_eq!(
+            expand_custom_prompt("/fix-issue 42 urgent", &[], &prompts).unwrap(),
+            Some(PromptExpansion {
+                text: "Fix issue 42 (42 urgent)".to_string(),
+                text_elements: Vec::new(),
+            })
+        );
+        assert_eq!(
+            expand_custom_prompt("/review", &[], &prompts).unwrap(),
+            None
+        );
+        assert_eq!(
+            expand_custom_prompt("/prompts:review", &[], &prompts).unwrap(),
+            Some(PromptExpansion {
+                text: "My review checklist".to_string(),
+                text_elements: Vec::new(),
+            })
+        );
+        assert_eq!(find_prompt_for_command("unknown", &prompts), None);
+    }
+
     #[test]
     fn invalid_arg_token_reports_error() {
         let prompts = vec![CustomPrompt {
@@ -16,6 +16,7 @@ use crate::key_hint::KeyBinding;
 use crate::render::Insets;
 use crate::render::RectExt as _;
 use crate::style::user_message_style;
+use crate::text_formatting::truncate_display_width;
 
 use super::scroll_state::ScrollState;
 
@@ -106,18 +107,9 @@ fn truncate_line_to_width(line: Line<'static>, max_width: usize) -> Line<'static
             continue;
         }
 
-        let mut truncated = String::new();
-        for ch in text.chars() {
-            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used + ch_width > max_width {
-                break;
-            }
-            truncated.push(ch);
-            used += ch_width;
-        }
-
+        let truncated = truncate_display_width(&text, max_width - used);
         if !truncated.is_empty() {
-            spans_out.push(Span::styled(truncated, style));
+            spans_out.push(Span::styled(truncated.to_string(), style));
         }
 
         break;
@@ -227,16 +219,9 @@ fn build_full_line(row: &GenericDisplayRow, desc_col: usize) -> Line<'static> {
             }
         }
     } else {
-        for ch in row.name.chars() {
-            let ch_w = UnicodeWidthChar::width(ch).unwrap_or(0);
-            let next_width = used_width.saturating_add(ch_w);
-            if next_width > name_limit {
-                truncated = true;
-                break;
-            }
-            used_width = next_width;
-            name_spans.push(ch.to_string().into());
-        }
+        let fitted = truncate_display_width(&row.name, name_limit);
+        truncated = fitted.len() < row.name.len();
+        name_spans.push(fitted.to_string().into());
     }
 
     if truncated {
@@ -0,0 +1,116 @@
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::render::Insets;
+use crate::render::RectExt as _;
+use crate::render::renderable::Renderable;
+use crate::setup_wizard::SetupOutcome;
+use crate::setup_wizard::SetupWizard;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+
+/// `/setup`: the first-run wizard, shown in the bottom pane. Finishing it sends
+/// `AppEvent::ApplySetupChoices`; Esc on the first page or Ctrl-C closes it unchanged.
+pub(crate) struct SetupWizardView {
+    wizard: SetupWizard,
+    app_event_tx: AppEventSender,
+    complete: bool,
+}
+
+impl SetupWizardView {
+    pub(crate) fn new(wizard: SetupWizard, app_event_tx: AppEventSender) -> Self {
+        Self {
+            wizard,
+            app_event_tx,
+            complete: false,
+        }
+    }
+}
+
+impl BottomPaneView for SetupWizardView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        self.wizard.handle_key_event(key_event);
+        match self.wizard.outcome() {
+            Some(SetupOutcome::Finished(choices)) => {
+                self.app_event_tx
+                    .send(AppEvent::ApplySetupChoices(choices.clone()));
+                self.complete = true;
+            }
+            Some(SetupOutcome::Cancelled) => self.complete = true,
+            None => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn prefer_esc_to_handle_key_event(&self) -> bool {
+        // Esc steps back a page; only the first page closes the view.
+        true
+    }
+}
+
+impl Renderable for SetupWizardView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.wizard.render(area.inset(Insets::vh(1, 0)), buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.wizard.desired_height(width).saturating_add(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup_wizard::SetupChoices;
+    use codex_core::protocol::AskForApproval;
+    use codex_protocol::config_types::SandboxMode;
+    use crossterm::event::KeyCode;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    #[test]
+    fn finishing_sends_choices_and_closes() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let initial = SetupChoices {
+            approval: AskForApproval::OnRequest,
+            sandbox_mode: SandboxMode::WorkspaceWrite,
+            model: "gpt-5.2-codex".to_string(),
+            notifications: true,
+        };
+        let mut view = SetupWizardView::new(
+            SetupWizard::new(Vec::new(), &initial),
+            AppEventSender::new(tx),
+        );
+
+        for code in [KeyCode::Char('3'), KeyCode::Enter, KeyCode::Char('2')] {
+            view.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+        }
+
+        assert!(view.is_complete());
+        let Ok(AppEvent::ApplySetupChoices(choices)) = rx.try_recv() else {
+            panic!("expected ApplySetupChoices");
+        };
+        assert_eq!(
+            choices,
+            SetupChoices {
+                approval: AskForApproval::Never,
+                sandbox_mode: SandboxMode::DangerFullAccess,
+                model: "gpt-5.2-codex".to_string(),
+                notifications: false,
+            }
+        );
+    }
+}
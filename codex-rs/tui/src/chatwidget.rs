@@ -23,9 +23,12 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -33,6 +36,8 @@ use crate::version::CODEX_CLI_VERSION;
 use codex_backend_client::Client as BackendClient;
 use codex_chatgpt::connectors;
 use codex_core::config::Config;
+use codex_core::config::ConfigBuilder;
+use codex_core::config::ConfigOverrides;
 use codex_core::config::ConstraintResult;
 use codex_core::config::types::Notifications;
 use codex_core::features::FEATURES;
@@ -41,6 +46,9 @@ use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
+use codex_core::project_memory::project_memory_path;
+use codex_core::project_memory::read_notes;
+use codex_core::project_memory::remove_note;
 use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningDeltaEvent;
@@ -50,6 +58,8 @@ use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::CommitCompletedEvent;
+use codex_core::protocol::CommitDraftEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -61,8 +71,16 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandOutputDeltaEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
+use codex_core::protocol::FileAttachedEvent;
+use codex_core::protocol::GhostSnapshotEntry;
+use codex_core::protocol::GhostSnapshotFileResponseEvent;
+use codex_core::protocol::GhostSnapshotsResponseEvent;
+use codex_core::protocol::HookEndEvent;
+use codex_core::protocol::ImageDownscaledEvent;
+use codex_core::protocol::InjectionSuspectedEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
+use codex_core::protocol::ListToolsResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
 use codex_core::protocol::McpStartupCompleteEvent;
 use codex_core::protocol::McpStartupStatus;
@@ -70,18 +88,25 @@ use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::Op;
+use codex_core::protocol::OutputTruncatedEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
+use codex_core::protocol::PrewarmPhase;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SessionPrewarmedEvent;
 use codex_core::protocol::SkillMetadata as ProtocolSkillMetadata;
+use codex_core::protocol::StartupDiagnostics;
+use codex_core::protocol::SteerDeliveredEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
+use codex_core::protocol::ToolSource;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnDiffEvent;
+use codex_core::protocol::TurnRefusedEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
 use codex_core::protocol::UserMessageEvent;
@@ -90,6 +115,8 @@ use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_core::skills::model::SkillMetadata;
+use codex_core::transcript_export::TranscriptExportOptions;
+use codex_core::transcript_export::export_html_transcript;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_otel::OtelManager;
@@ -103,9 +130,11 @@ use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::Settings;
 #[cfg(target_os = "windows")]
 use codex_protocol::config_types::WindowsSandboxLevel;
+use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use codex_protocol::models::local_image_label_text;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::request_user_input::RequestUserInputEvent;
+use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
 use codex_protocol::user_input::UserInput;
 use crossterm::event::KeyCode;
@@ -135,6 +164,7 @@ const PLAN_IMPLEMENTATION_CODING_MESSAGE: &str = "Implement the plan.";
 use crate::app_event::AppEvent;
 use crate::app_event::ConnectorsSnapshot;
 use crate::app_event::ExitMode;
+use crate::app_event::OversizedMessageAction;
 #[cfg(target_os = "windows")]
 use crate::app_event::WindowsSandboxEnableMode;
 use crate::app_event::WindowsSandboxFallbackReason;
@@ -154,35 +184,51 @@ use crate::bottom_pane::QUIT_SHORTCUT_TIMEOUT;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::SetupWizardView;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::collab;
 use crate::collaboration_modes;
+use crate::diff_command::diff_text;
+use crate::diff_command::parse_diff_args;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
 use crate::exec_cell::new_active_exec_command;
 use crate::exec_command::strip_bash_lc_and_escape;
-use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::McpToolCallCell;
 use crate::history_cell::PlainHistoryCell;
 use crate::history_cell::WebSearchCell;
+use crate::history_command::HistoryFilesArgs;
+use crate::history_command::parse_history_args;
+use crate::history_command::short_id;
+use crate::history_command::snapshot_file_view;
+use crate::history_command::snapshot_label;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::markdown::append_markdown;
+use crate::paste_files::PASTES_DIR;
+use crate::paste_files::save_paste;
+use crate::reasoning_headline::ReasoningHeadlines;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
+use crate::setup_wizard::SetupChoices;
+use crate::setup_wizard::SetupWizard;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
+use crate::status::format_tokens_compact;
 use crate::text_formatting::truncate_text;
+use crate::token_estimate::ContextEstimate;
+use crate::token_estimate::estimate_tokens;
+use crate::token_estimate::excerpt_to_fit;
 use crate::tui::FrameRequester;
 mod interrupts;
 use self::interrupts::InterruptManager;
@@ -196,6 +242,7 @@ mod skills;
 use self::skills::collect_tool_mentions;
 use self::skills::find_app_mentions;
 use self::skills::find_skill_mentions_with_tool_mentions;
+use crate::status_indicator_widget::fmt_elapsed_compact;
 use crate::streaming::controller::PlanStreamController;
 use crate::streaming::controller::StreamController;
 
@@ -216,6 +263,8 @@ use strum::IntoEnumIterator;
 const USER_SHELL_COMMAND_HELP_TITLE: &str = "Prefix a command with ! to run it locally";
 const USER_SHELL_COMMAND_HELP_HINT: &str = "Example: !ls";
 const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+/// Size of the preview sent along with a saved message's file reference.
+const FILE_REFERENCE_PREVIEW_TOKENS: i64 = 200;
 // Track information about an in-flight exec command.
 struct RunningCommand {
     command: Vec<String>,
@@ -266,6 +315,37 @@ impl UnifiedExecWaitStreak {
     }
 }
 
+/// Order in which the reasoning cycle key steps through efforts.
+const REASONING_EFFORT_CYCLE: [ReasoningEffortConfig; 4] = [
+    ReasoningEffortConfig::Low,
+    ReasoningEffortConfig::Medium,
+    ReasoningEffortConfig::High,
+    ReasoningEffortConfig::None,
+];
+
+fn reasoning_cycle_key(config: &Config) -> KeyBinding {
+    let default = key_hint::ctrl(KeyCode::Char('r'));
+    let Some(spec) = config.tui_reasoning_cycle_key.as_deref() else {
+        return default;
+    };
+    key_hint::parse_binding(spec).unwrap_or_else(|| {
+        tracing::warn!("ignoring unrecognized tui.reasoning_cycle_key `{spec}`; using ctrl-r");
+        default
+    })
+}
+
+fn parse_reasoning_effort(value: &str) -> Option<ReasoningEffortConfig> {
+    match value.to_ascii_lowercase().as_str() {
+        "none" => Some(ReasoningEffortConfig::None),
+        "minimal" => Some(ReasoningEffortConfig::Minimal),
+        "low" => Some(ReasoningEffortConfig::Low),
+        "medium" => Some(ReasoningEffortConfig::Medium),
+        "high" => Some(ReasoningEffortConfig::High),
+        "xhigh" => Some(ReasoningEffortConfig::XHigh),
+        _ => None,
+    }
+}
+
 fn is_unified_exec_source(source: ExecCommandSource) -> bool {
     matches!(
         source,
@@ -439,6 +519,19 @@ pub(crate) enum ExternalEditorState {
     Active,
 }
 
+/// Destination of a key event in the main view; see [`ChatWidget::route_key`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum KeyRoute {
+    Interrupt,
+    Quit,
+    PasteImage,
+    CycleCollaborationMode,
+    CycleReasoningEffort,
+    RestoreQueuedMessage,
+    Transcript,
+    BottomPane,
+}
+
 /// Maintains the per-session UI state and interaction state machines for the chat screen.
 ///
 /// `ChatWidget` owns the state derived from the protocol event stream (history cells, streaming
@@ -479,6 +572,10 @@ pub(crate) struct ChatWidget {
     session_header: SessionHeader,
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
+    // Context usage including input submitted since the last `TokenCount`.
+    context_estimate: ContextEstimate,
+    // Message held back because it likely exceeds the remaining context.
+    pending_oversized_message: Option<UserMessage>,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
     rate_limit_warnings: RateLimitWarningState,
@@ -490,6 +587,8 @@ pub(crate) struct ChatWidget {
     plan_stream_controller: Option<PlanStreamController>,
     running_commands: HashMap<String, RunningCommand>,
     suppressed_exec_calls: HashSet<String>,
+    /// Patch call ids whose "Edited" block has already been added to history this turn.
+    rendered_patch_calls: HashSet<String>,
     skills_all: Vec<ProtocolSkillMetadata>,
     skills_initial_state: Option<HashMap<PathBuf, bool>>,
     last_unified_wait: Option<UnifiedExecWaitState>,
@@ -514,6 +613,8 @@ pub(crate) struct ChatWidget {
     reasoning_buffer: String,
     // Accumulates full reasoning content for transcript-only recording
     full_reasoning_buffer: String,
+    // Latest `**heading**` from the reasoning summary, shown in the status line
+    reasoning_headlines: ReasoningHeadlines,
     // Current status header shown in the status indicator.
     current_status_header: String,
     // Previous status header to restore after a transient stream retry.
@@ -529,6 +630,8 @@ pub(crate) struct ChatWidget {
     suppress_session_configured_redraw: bool,
     // User messages queued while a turn is in progress
     queued_user_messages: VecDeque<UserMessage>,
+    // Files added with `/attach`, sent with the next user message
+    pending_attachments: Vec<PathBuf>,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
     /// When `Some`, the user has pressed a quit shortcut and the second press
@@ -539,6 +642,8 @@ pub(crate) struct ChatWidget {
     /// We require the second press to match this key so `Ctrl+C` followed by
     /// `Ctrl+D` (or vice versa) doesn't quit accidentally.
     quit_shortcut_key: Option<KeyBinding>,
+    /// Key that cycles the reasoning effort for the next turn (`tui.reasoning_cycle_key`).
+    reasoning_cycle_key: KeyBinding,
     // Simple review mode flag; used to adjust layout and banners.
     is_review_mode: bool,
     // Snapshot of token usage to restore after review mode exits.
@@ -574,6 +679,11 @@ pub(crate) struct ChatWidget {
     feedback_audience: FeedbackAudience,
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
+    // Shared with the session info cell so MCP startup results reach it.
+    startup_diagnostics: Option<Arc<Mutex<StartupDiagnostics>>>,
+    // Last `/history files` request and the snapshots core listed for it.
+    history_files: HistoryFilesArgs,
+    ghost_snapshots: Vec<GhostSnapshotEntry>,
     external_editor_state: ExternalEditorState,
 }
 
@@ -733,6 +843,96 @@ fn remap_placeholders_for_message(message: UserMessage, next_label: &mut usize)
     }
 }
 
+/// Orders attachments by where their placeholders appear in the text and
+/// renumbers them, so the Nth `[Image #N]` in the text is also the Nth image
+/// sent, which is the label core gives it. Images whose placeholder is no
+/// longer in the text go first, as they are sent ahead of the text.
+fn order_images_by_placeholder(message: UserMessage) -> UserMessage {
+    let UserMessage {
+        text,
+        mut local_images,
+        text_elements,
+        mention_paths,
+    } = message;
+    local_images.sort_by_key(|attachment| {
+        text_elements
+            .iter()
+            .filter(|elem| elem.placeholder(&text) == Some(attachment.placeholder.as_str()))
+            .map(|elem| elem.byte_range.start)
+            .min()
+    });
+    let mut next_label = 1;
+    remap_placeholders_for_message(
+        UserMessage {
+            text,
+            local_images,
+            text_elements,
+            mention_paths,
+        },
+        &mut next_label,
+    )
+}
+
+/// Builds the input items for a turn with each image right after its
+/// placeholder, so the model reads text and images in the order they were
+/// composed. The text chunks concatenate back to `text`.
+fn user_input_items(
+    text: &str,
+    text_elements: &[TextElement],
+    local_images: &[LocalImageAttachment],
+) -> Vec<UserInput> {
+    let mut items = Vec::new();
+    let mut placed: Vec<(usize, &LocalImageAttachment)> = Vec::new();
+    for image in local_images {
+        match text_elements
+            .iter()
+            .find(|elem| elem.placeholder(text) == Some(image.placeholder.as_str()))
+        {
+            Some(elem) => placed.push((elem.byte_range.end.min(text.len()), image)),
+            None => items.push(UserInput::LocalImage {
+                path: image.path.clone(),
+            }),
+        }
+    }
+    placed.sort_by_key(|(end, _)| *end);
+
+    let mut cursor = 0;
+    for (end, image) in placed {
+        push_text_chunk(&mut items, text, text_elements, cursor..end);
+        items.push(UserInput::LocalImage {
+            path: image.path.clone(),
+        });
+        cursor = end;
+    }
+    push_text_chunk(&mut items, text, text_elements, cursor..text.len());
+    items
+}
+
+fn push_text_chunk(
+    items: &mut Vec<UserInput>,
+    text: &str,
+    text_elements: &[TextElement],
+    range: Range<usize>,
+) {
+    let Some(chunk) = text.get(range.clone()).filter(|chunk| !chunk.is_empty()) else {
+        return;
+    };
+    let text_elements = text_elements
+        .iter()
+        .filter(|elem| elem.byte_range.start >= range.start && elem.byte_range.end <= range.end)
+        .map(|elem| {
+            elem.map_range(|byte_range| ByteRange {
+                start: byte_range.start - range.start,
+                end: byte_range.end - range.start,
+            })
+        })
+        .collect();
+    items.push(UserInput::Text {
+        text: chunk.to_string(),
+        text_elements,
+    });
+}
+
 impl ChatWidget {
     /// Synchronize the bottom-pane "task running" indicator with the current lifecycles.
     ///
@@ -744,8 +944,10 @@ impl ChatWidget {
     }
 
     fn restore_reasoning_status_header(&mut self) {
-        if let Some(header) = extract_first_bold(&self.reasoning_buffer) {
-            self.set_status_header(header);
+        if self.config.tui_reasoning_headlines
+            && let Some(headline) = self.reasoning_headlines.latest()
+        {
+            self.show_reasoning_headline(headline.to_string());
         } else if self.bottom_pane.is_task_running() {
             self.set_status_header(String::from("Working"));
         }
@@ -778,6 +980,17 @@ impl ChatWidget {
         self.bottom_pane.update_status(header, details);
     }
 
+    /// Show `headline` after a "Thinking" status header, unless disabled by
+    /// `tui.reasoning_headlines`. The headline is dropped as soon as the header
+    /// changes.
+    fn show_reasoning_headline(&mut self, headline: String) {
+        if !self.config.tui_reasoning_headlines {
+            return;
+        }
+        self.set_status_header(String::from("Thinking"));
+        self.bottom_pane.update_status_subtitle(Some(headline));
+    }
+
     /// Convenience wrapper around [`Self::set_status`];
     /// updates the status indicator header and clears any existing details.
     fn set_status_header(&mut self, header: String) {
@@ -800,6 +1013,7 @@ impl ChatWidget {
         self.thread_name = event.thread_name.clone();
         self.forked_from = event.forked_from_id;
         self.current_rollout_path = event.rollout_path.clone();
+        self.config.tool_toggles = event.tool_toggles;
         let initial_messages = event.initial_messages.clone();
         let model_for_header = event.model.clone();
         self.session_header.set_model(&model_for_header);
@@ -810,11 +1024,16 @@ impl ChatWidget {
         );
         self.refresh_model_display();
         self.sync_personality_command_enabled();
+        self.startup_diagnostics = event
+            .startup
+            .clone()
+            .map(|diagnostics| Arc::new(Mutex::new(diagnostics)));
         let session_info_cell = history_cell::new_session_info(
             &self.config,
             &model_for_header,
             event,
             self.show_welcome_banner,
+            self.startup_diagnostics.clone(),
         );
         self.apply_session_info_cell(session_info_cell);
 
@@ -931,14 +1150,17 @@ impl ChatWidget {
         self.flush_active_cell();
 
         if self.plan_stream_controller.is_none() {
-            self.plan_stream_controller = Some(PlanStreamController::new(
-                self.last_rendered_width.get().map(|w| w.saturating_sub(4)),
-            ));
+            self.plan_stream_controller = Some(
+                PlanStreamController::new(
+                    self.last_rendered_width.get().map(|w| w.saturating_sub(4)),
+                )
+                .with_reduced_motion(self.config.tui_reduced_motion),
+            );
         }
         if let Some(controller) = self.plan_stream_controller.as_mut()
             && controller.push(&delta)
         {
-            self.app_event_tx.send(AppEvent::StartCommitAnimation);
+            self.start_commit_animation();
         }
         self.request_redraw();
     }
@@ -969,9 +1191,10 @@ impl ChatWidget {
 
     fn on_agent_reasoning_delta(&mut self, delta: String) {
         // For reasoning deltas, do not stream to history. Accumulate the
-        // current reasoning block and extract the first bold element
-        // (between **/**) as the chunk header. Show this header as status.
+        // current reasoning block and show the latest bold heading
+        // (between **/**) in the status line.
         self.reasoning_buffer.push_str(&delta);
+        let headline = self.reasoning_headlines.push(&delta);
 
         if self.unified_exec_wait_streak.is_some() {
             // Unified exec waiting should take precedence over reasoning-derived status headers.
@@ -979,11 +1202,8 @@ impl ChatWidget {
             return;
         }
 
-        if let Some(header) = extract_first_bold(&self.reasoning_buffer) {
-            // Update the shimmer header to the extracted reasoning chunk header.
-            self.set_status_header(header);
-        } else {
-            // Fallback while we don't yet have a bold header: leave existing header as-is.
+        if let Some(headline) = headline {
+            self.show_reasoning_headline(headline);
         }
         self.request_redraw();
     }
@@ -998,6 +1218,7 @@ impl ChatWidget {
         }
         self.reasoning_buffer.clear();
         self.full_reasoning_buffer.clear();
+        self.reasoning_headlines.reset();
         self.request_redraw();
     }
 
@@ -1006,6 +1227,7 @@ impl ChatWidget {
         self.full_reasoning_buffer.push_str(&self.reasoning_buffer);
         self.full_reasoning_buffer.push_str("\n\n");
         self.reasoning_buffer.clear();
+        self.reasoning_headlines.section_break();
     }
 
     // Raw reasoning uses the same flow as summarized reasoning
@@ -1026,6 +1248,7 @@ impl ChatWidget {
         self.set_status_header(String::from("Working"));
         self.full_reasoning_buffer.clear();
         self.reasoning_buffer.clear();
+        self.reasoning_headlines.reset();
         self.request_redraw();
     }
 
@@ -1038,11 +1261,13 @@ impl ChatWidget {
             self.add_boxed_history(cell);
         }
         self.flush_unified_exec_wait_streak();
+        self.finalize_orphaned_active_cell();
         // Mark task stopped and request redraw now that all content is in history.
         self.agent_turn_running = false;
         self.update_task_running_state();
         self.running_commands.clear();
         self.suppressed_exec_calls.clear();
+        self.rendered_patch_calls.clear();
         self.last_unified_wait = None;
         self.unified_exec_wait_streak = None;
         self.clear_unified_exec_processes();
@@ -1138,20 +1363,53 @@ impl ChatWidget {
     pub(crate) fn set_token_info(&mut self, info: Option<TokenUsageInfo>) {
         match info {
             Some(info) => self.apply_token_info(info),
-            None => {
-                self.bottom_pane.set_context_window(None, None);
-                self.token_info = None;
-            }
+            None => self.reset_token_info(),
         }
     }
 
     fn apply_token_info(&mut self, info: TokenUsageInfo) {
         let percent = self.context_remaining_percent(&info);
         let used_tokens = self.context_used_tokens(&info, percent.is_some());
-        self.bottom_pane.set_context_window(percent, used_tokens);
+        self.bottom_pane
+            .set_context_window(percent, used_tokens, false);
+        self.context_estimate
+            .set_reported(info.last_token_usage.tokens_in_context_window());
         self.token_info = Some(info);
     }
 
+    fn reset_token_info(&mut self) {
+        self.bottom_pane.set_context_window(None, None, false);
+        self.token_info = None;
+        self.context_estimate = ContextEstimate::default();
+    }
+
+    fn context_window(&self) -> Option<i64> {
+        self.token_info
+            .as_ref()
+            .and_then(|info| info.model_context_window)
+            .or(self.config.model_context_window)
+    }
+
+    /// Shows the estimated context usage until the next `TokenCount` reports
+    /// the real value. Before the first report the meter keeps its default.
+    fn refresh_estimated_context_window(&mut self) {
+        if self.token_info.is_none() || !self.context_estimate.is_approximate() {
+            return;
+        }
+        match self.context_window() {
+            Some(window) => self.bottom_pane.set_context_window(
+                Some(self.context_estimate.percent_remaining(window)),
+                None,
+                true,
+            ),
+            None => self.bottom_pane.set_context_window(
+                None,
+                Some(self.context_estimate.used_tokens()),
+                true,
+            ),
+        }
+    }
+
     fn context_remaining_percent(&self, info: &TokenUsageInfo) -> Option<i64> {
         info.model_context_window.map(|window| {
             info.last_token_usage
@@ -1171,10 +1429,7 @@ impl ChatWidget {
         if let Some(saved) = self.pre_review_token_info.take() {
             match saved {
                 Some(info) => self.apply_token_info(info),
-                None => {
-                    self.bottom_pane.set_context_window(None, None);
-                    self.token_info = None;
-                }
+                None => self.reset_token_info(),
             }
         }
     }
@@ -1258,6 +1513,7 @@ impl ChatWidget {
         self.update_task_running_state();
         self.running_commands.clear();
         self.suppressed_exec_calls.clear();
+        self.rendered_patch_calls.clear();
         self.last_unified_wait = None;
         self.unified_exec_wait_streak = None;
         self.clear_unified_exec_processes();
@@ -1284,8 +1540,15 @@ impl ChatWidget {
     }
 
     fn on_error(&mut self, message: String) {
+        self.on_request_error(ErrorEvent {
+            message,
+            ..Default::default()
+        });
+    }
+
+    fn on_request_error(&mut self, event: ErrorEvent) {
         self.finalize_turn();
-        self.add_to_history(history_cell::new_error_event(message));
+        self.add_to_history(history_cell::new_request_error_event(event));
         self.request_redraw();
 
         // After an error ends the turn, try sending the next queued input.
@@ -1297,6 +1560,30 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_injection_suspected(&mut self, ev: InjectionSuspectedEvent) {
+        let InjectionSuspectedEvent {
+            tool_name,
+            patterns,
+            approval_downgraded,
+            ..
+        } = ev;
+        let patterns = patterns.join(", ");
+        let mut message =
+            format!("Output from `{tool_name}` looks like a prompt injection ({patterns}).");
+        if approval_downgraded {
+            message.push_str(" Commands will need your approval for the rest of this turn.");
+        }
+        self.on_warning(message);
+    }
+
+    fn on_output_truncated(&mut self, ev: OutputTruncatedEvent) {
+        let message = match ev.max_output_tokens {
+            Some(limit) => format!("Response truncated at the {limit}-token output limit."),
+            None => "Response truncated at the model's output token limit.".to_string(),
+        };
+        self.on_warning(message);
+    }
+
     fn on_mcp_startup_update(&mut self, ev: McpStartupUpdateEvent) {
         let mut status = self.mcp_startup_status.take().unwrap_or_default();
         if let McpStartupStatus::Failed { error } = &ev.status {
@@ -1365,6 +1652,34 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_session_prewarmed(&mut self, ev: SessionPrewarmedEvent) {
+        let timings: Vec<String> = ev
+            .phases
+            .iter()
+            .map(|timing| format!("{:?}={}ms", timing.phase, timing.duration_ms))
+            .collect();
+        tracing::info!("session prewarmed: {}", timings.join(" "));
+        if let Some(diagnostics) = &self.startup_diagnostics {
+            let mut diagnostics = diagnostics
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            diagnostics.phases = ev.phases.clone();
+            if !ev.mcp_servers.is_empty() {
+                diagnostics.mcp_servers = ev.mcp_servers.clone();
+            }
+        }
+        // MCP failures were already reported by `on_mcp_startup_complete`, and
+        // a session prewarmed behind the login screen may have signed in since.
+        let signed_in = self.auth_manager.auth_cached().is_some();
+        for degraded in ev.degraded {
+            match degraded.phase {
+                PrewarmPhase::McpServers => {}
+                PrewarmPhase::Auth if signed_in => {}
+                _ => self.on_warning(degraded.detail),
+            }
+        }
+    }
+
     /// Handle a turn aborted due to user interrupt (Esc).
     /// When there are queued user messages, restore them into the composer
     /// separated by newlines rather than auto‑submitting the next one.
@@ -1571,6 +1886,9 @@ impl ChatWidget {
     }
 
     fn on_patch_apply_begin(&mut self, event: PatchApplyBeginEvent) {
+        if !self.rendered_patch_calls.insert(event.call_id) {
+            return;
+        }
         self.add_to_history(history_cell::new_patch_event(
             event.changes,
             &self.config.cwd,
@@ -1586,6 +1904,46 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_image_downscaled(&mut self, event: ImageDownscaledEvent) {
+        self.add_info_message(
+            format!(
+                "Downscaled {} before sending ({})",
+                event.path.display(),
+                event.size_summary()
+            ),
+            None,
+        );
+    }
+
+    fn on_file_attached(&mut self, event: FileAttachedEvent) {
+        let path = event
+            .path
+            .strip_prefix(&self.config.cwd)
+            .unwrap_or(&event.path);
+        self.add_info_message(format!("{}: {}", path.display(), event.summary()), None);
+    }
+
+    fn on_prompt_queued_offline(&mut self, queued_prompts: u32) {
+        let message = if queued_prompts > 1 {
+            format!("Offline — message queued ({queued_prompts} waiting)")
+        } else {
+            "Offline — message queued".to_string()
+        };
+        self.add_info_message(
+            message,
+            Some("It will be sent automatically once the model provider is reachable.".to_string()),
+        );
+    }
+
+    fn on_connectivity_restored(&mut self, submitted_prompts: u32) {
+        let message = if submitted_prompts > 1 {
+            format!("Back online — sending {submitted_prompts} queued messages")
+        } else {
+            "Back online — sending queued message".to_string()
+        };
+        self.add_info_message(message, None);
+    }
+
     fn on_patch_apply_end(&mut self, event: codex_core::protocol::PatchApplyEndEvent) {
         let ev2 = event.clone();
         self.defer_or_handle(
@@ -1754,7 +2112,15 @@ impl ChatWidget {
             offset,
             log_id,
             entry,
+            refreshed,
         } = event;
+        // The history was compacted or cleared since we learned `log_id`;
+        // start over with the new log so offsets line up again.
+        if let Some(refreshed) = refreshed {
+            self.bottom_pane
+                .set_history_metadata(refreshed.log_id, refreshed.entry_count);
+            return;
+        }
         self.bottom_pane
             .on_history_entry_response(log_id, offset, entry.map(|e| e.text));
     }
@@ -1841,6 +2207,16 @@ impl ChatWidget {
         }
     }
 
+    /// Starts revealing newly completed stream lines. With reduced motion they
+    /// are appended right away, so no commit animation runs.
+    fn start_commit_animation(&mut self) {
+        if self.config.tui_reduced_motion {
+            self.on_commit_tick();
+        } else {
+            self.app_event_tx.send(AppEvent::StartCommitAnimation);
+        }
+    }
+
     fn flush_interrupt_queue(&mut self) {
         let mut mgr = std::mem::take(&mut self.interrupts);
         mgr.flush_all(self);
@@ -1894,14 +2270,16 @@ impl ChatWidget {
                 // Reset the flag even if we don't show separator (no work was done)
                 self.needs_final_message_separator = false;
             }
-            self.stream_controller = Some(StreamController::new(
-                self.last_rendered_width.get().map(|w| w.saturating_sub(2)),
-            ));
+            self.stream_controller = Some(
+                StreamController::new(self.last_rendered_width.get().map(|w| w.saturating_sub(2)))
+                    .with_citations(self.config.cwd.clone())
+                    .with_reduced_motion(self.config.tui_reduced_motion),
+            );
         }
         if let Some(controller) = self.stream_controller.as_mut()
             && controller.push(&delta)
         {
-            self.app_event_tx.send(AppEvent::StartCommitAnimation);
+            self.start_commit_animation();
         }
         self.request_redraw();
     }
@@ -1929,11 +2307,12 @@ impl ChatWidget {
         let is_unified_exec_interaction =
             matches!(source, ExecCommandSource::UnifiedExecInteraction);
 
+        // An end without a matching begin in the active cell gets a cell of its own.
         let needs_new = self
             .active_cell
             .as_ref()
-            .map(|cell| cell.as_any().downcast_ref::<ExecCell>().is_none())
-            .unwrap_or(true);
+            .and_then(|cell| cell.as_any().downcast_ref::<ExecCell>())
+            .is_none_or(|cell| !cell.contains_call(&ev.call_id));
         if needs_new {
             self.flush_active_cell();
             self.active_cell = Some(Box::new(new_active_exec_command(
@@ -1956,12 +2335,16 @@ impl ChatWidget {
                     exit_code: ev.exit_code,
                     formatted_output: String::new(),
                     aggregated_output: String::new(),
+                    denied_by_sandbox: ev.denied_by_sandbox,
+                    limit_exceeded: ev.limit_exceeded,
                 }
             } else {
                 CommandOutput {
                     exit_code: ev.exit_code,
                     formatted_output: ev.formatted_output.clone(),
                     aggregated_output: ev.aggregated_output.clone(),
+                    denied_by_sandbox: ev.denied_by_sandbox,
+                    limit_exceeded: ev.limit_exceeded,
                 }
             };
             cell.complete_call(&ev.call_id, output, ev.duration);
@@ -1983,7 +2366,17 @@ impl ChatWidget {
         // If the patch was successful, just let the "Edited" block stand.
         // Otherwise, add a failure block.
         if !event.success {
-            self.add_to_history(history_cell::new_patch_apply_failure(event.stderr));
+            self.add_to_history(history_cell::new_patch_apply_failure(
+                event.stderr,
+                &event.per_file,
+                &self.config.cwd,
+            ));
+        } else if !event.changes.is_empty() && self.rendered_patch_calls.insert(event.call_id) {
+            // The begin event never arrived; render the applied changes from the end event.
+            self.add_to_history(history_cell::new_patch_event(
+                event.changes,
+                &self.config.cwd,
+            ));
         }
         // Mark that actual work was done (patch applied)
         self.had_work_activity = true;
@@ -1997,7 +2390,11 @@ impl ChatWidget {
 
         let request = ApprovalRequest::Exec {
             id,
+            call_id: ev.call_id,
             command: ev.command,
+            cwd: ev.cwd,
+            cwd_outside_workspace: ev.cwd_outside_workspace,
+            cwd_writable: ev.cwd_writable,
             reason: ev.reason,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
         };
@@ -2018,6 +2415,7 @@ impl ChatWidget {
             reason: ev.reason,
             changes: ev.changes.clone(),
             cwd: self.config.cwd.clone(),
+            dirty_paths: ev.dirty_paths,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -2083,7 +2481,21 @@ impl ChatWidget {
             return;
         }
         let interaction_input = ev.interaction_input.clone();
+        // A repeated begin for a call that is already on screen restarts it in place.
         if let Some(cell) = self
+            .active_cell
+            .as_mut()
+            .and_then(|c| c.as_any_mut().downcast_mut::<ExecCell>())
+            && cell.replace_call(
+                &ev.call_id,
+                ev.command.clone(),
+                ev.parsed_cmd.clone(),
+                ev.source,
+                interaction_input.clone(),
+            )
+        {
+            self.bump_active_cell_revision();
+        } else if let Some(cell) = self
             .active_cell
             .as_mut()
             .and_then(|c| c.as_any_mut().downcast_mut::<ExecCell>())
@@ -2116,7 +2528,15 @@ impl ChatWidget {
 
     pub(crate) fn handle_mcp_begin_now(&mut self, ev: McpToolCallBeginEvent) {
         self.flush_answer_stream_with_separator();
-        self.flush_active_cell();
+        // A repeated begin for the active call replaces it rather than leaving a stale spinner.
+        let is_duplicate = self
+            .active_cell
+            .as_ref()
+            .and_then(|cell| cell.as_any().downcast_ref::<McpToolCallCell>())
+            .is_some_and(|cell| cell.call_id() == ev.call_id);
+        if !is_duplicate {
+            self.flush_active_cell();
+        }
         self.active_cell = Some(Box::new(history_cell::new_active_mcp_tool_call(
             ev.call_id,
             ev.invocation,
@@ -2133,6 +2553,7 @@ impl ChatWidget {
             invocation,
             duration,
             result,
+            argument_errors,
         } = ev;
 
         let extra_cell = match self
@@ -2140,7 +2561,10 @@ impl ChatWidget {
             .as_mut()
             .and_then(|cell| cell.as_any_mut().downcast_mut::<McpToolCallCell>())
         {
-            Some(cell) if cell.call_id() == call_id => cell.complete(duration, result),
+            Some(cell) if cell.call_id() == call_id => {
+                cell.set_argument_errors(argument_errors);
+                cell.complete(duration, result)
+            }
             _ => {
                 self.flush_active_cell();
                 let mut cell = history_cell::new_active_mcp_tool_call(
@@ -2148,6 +2572,7 @@ impl ChatWidget {
                     invocation,
                     self.config.animations,
                 );
+                cell.set_argument_errors(argument_errors);
                 let extra_cell = cell.complete(duration, result);
                 self.active_cell = Some(Box::new(cell));
                 extra_cell
@@ -2207,6 +2632,7 @@ impl ChatWidget {
 
         let active_cell = Some(Self::placeholder_session_header_cell(&config));
 
+        let reasoning_cycle_key = reasoning_cycle_key(&config);
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
@@ -2243,6 +2669,7 @@ impl ChatWidget {
             plan_stream_controller: None,
             running_commands: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
+            rendered_patch_calls: HashSet::new(),
             last_unified_wait: None,
             unified_exec_wait_streak: None,
             task_complete_pending: false,
@@ -2253,19 +2680,24 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            reasoning_headlines: ReasoningHeadlines::default(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
             thread_id: None,
             thread_name: None,
             forked_from: None,
             queued_user_messages: VecDeque::new(),
+            pending_attachments: Vec::new(),
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
             quit_shortcut_expires_at: None,
             quit_shortcut_key: None,
+            reasoning_cycle_key,
             is_review_mode: false,
             pre_review_token_info: None,
+            context_estimate: ContextEstimate::default(),
+            pending_oversized_message: None,
             needs_final_message_separator: false,
             had_work_activity: false,
             saw_plan_update_this_turn: false,
@@ -2277,6 +2709,9 @@ impl ChatWidget {
             feedback,
             feedback_audience,
             current_rollout_path: None,
+            startup_diagnostics: None,
+            history_files: HistoryFilesArgs::default(),
+            ghost_snapshots: Vec::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2288,6 +2723,15 @@ impl ChatWidget {
             widget.config.features.enabled(Feature::CollaborationModes),
         );
         widget.sync_personality_command_enabled();
+        widget
+            .bottom_pane
+            .set_paste_to_file(widget.config.tui_paste_to_file, widget.config.cwd.clone());
+        widget
+            .bottom_pane
+            .set_approval_timeout(widget.config.approval_timeout);
+        widget
+            .bottom_pane
+            .set_accessible(widget.config.tui_accessible);
         #[cfg(target_os = "windows")]
         widget.bottom_pane.set_windows_degraded_sandbox_active(
             codex_core::windows_sandbox::ELEVATED_SANDBOX_NUX_ENABLED
@@ -2352,6 +2796,7 @@ impl ChatWidget {
 
         let active_cell = Some(Self::placeholder_session_header_cell(&config));
 
+        let reasoning_cycle_key = reasoning_cycle_key(&config);
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
@@ -2388,6 +2833,7 @@ impl ChatWidget {
             plan_stream_controller: None,
             running_commands: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
+            rendered_patch_calls: HashSet::new(),
             last_unified_wait: None,
             unified_exec_wait_streak: None,
             task_complete_pending: false,
@@ -2398,6 +2844,7 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            reasoning_headlines: ReasoningHeadlines::default(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
             thread_id: None,
@@ -2408,13 +2855,17 @@ impl ChatWidget {
             plan_delta_buffer: String::new(),
             plan_item_active: false,
             queued_user_messages: VecDeque::new(),
+            pending_attachments: Vec::new(),
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
             quit_shortcut_expires_at: None,
             quit_shortcut_key: None,
+            reasoning_cycle_key,
             is_review_mode: false,
             pre_review_token_info: None,
+            context_estimate: ContextEstimate::default(),
+            pending_oversized_message: None,
             needs_final_message_separator: false,
             had_work_activity: false,
             last_separator_elapsed_secs: None,
@@ -2422,6 +2873,9 @@ impl ChatWidget {
             feedback,
             feedback_audience,
             current_rollout_path: None,
+            startup_diagnostics: None,
+            history_files: HistoryFilesArgs::default(),
+            ghost_snapshots: Vec::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2433,6 +2887,15 @@ impl ChatWidget {
             widget.config.features.enabled(Feature::CollaborationModes),
         );
         widget.sync_personality_command_enabled();
+        widget
+            .bottom_pane
+            .set_paste_to_file(widget.config.tui_paste_to_file, widget.config.cwd.clone());
+        widget
+            .bottom_pane
+            .set_approval_timeout(widget.config.approval_timeout);
+        widget
+            .bottom_pane
+            .set_accessible(widget.config.tui_accessible);
 
         widget
     }
@@ -2486,6 +2949,7 @@ impl ChatWidget {
             settings: fallback_custom,
         };
 
+        let reasoning_cycle_key = reasoning_cycle_key(&config);
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
@@ -2522,6 +2986,7 @@ impl ChatWidget {
             plan_stream_controller: None,
             running_commands: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
+            rendered_patch_calls: HashSet::new(),
             last_unified_wait: None,
             unified_exec_wait_streak: None,
             task_complete_pending: false,
@@ -2532,19 +2997,24 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            reasoning_headlines: ReasoningHeadlines::default(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
             thread_id: None,
             thread_name: None,
             forked_from: None,
             queued_user_messages: VecDeque::new(),
+            pending_attachments: Vec::new(),
             show_welcome_banner: false,
             suppress_session_configured_redraw: true,
             pending_notification: None,
             quit_shortcut_expires_at: None,
             quit_shortcut_key: None,
+            reasoning_cycle_key,
             is_review_mode: false,
             pre_review_token_info: None,
+            context_estimate: ContextEstimate::default(),
+            pending_oversized_message: None,
             needs_final_message_separator: false,
             had_work_activity: false,
             saw_plan_update_this_turn: false,
@@ -2556,6 +3026,9 @@ impl ChatWidget {
             feedback,
             feedback_audience,
             current_rollout_path: None,
+            startup_diagnostics: None,
+            history_files: HistoryFilesArgs::default(),
+            ghost_snapshots: Vec::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2567,6 +3040,15 @@ impl ChatWidget {
             widget.config.features.enabled(Feature::CollaborationModes),
         );
         widget.sync_personality_command_enabled();
+        widget
+            .bottom_pane
+            .set_paste_to_file(widget.config.tui_paste_to_file, widget.config.cwd.clone());
+        widget
+            .bottom_pane
+            .set_approval_timeout(widget.config.approval_timeout);
+        widget
+            .bottom_pane
+            .set_accessible(widget.config.tui_accessible);
         #[cfg(target_os = "windows")]
         widget.bottom_pane.set_windows_degraded_sandbox_active(
             codex_core::windows_sandbox::ELEVATED_SANDBOX_NUX_ENABLED
@@ -2580,82 +3062,102 @@ impl ChatWidget {
         widget
     }
 
-    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) {
+    /// Decides where a key event goes. This is the one place key routing for
+    /// the main view is defined; `App` only intercepts its global keys
+    /// (`Ctrl-T`, `Ctrl-G`, `Ctrl-Tab`, and `Esc`/`Enter` while backtracking)
+    /// before forwarding everything else here.
+    ///
+    /// | Key                    | Composer, nothing open       | Modal or popup open |
+    /// |------------------------|------------------------------|---------------------|
+    /// | `Ctrl-C`               | interrupt, then quit         | same                |
+    /// | `Ctrl-D`               | quit when composer is empty  | same                |
+    /// | `Ctrl-V` / `Alt-V`     | paste image                  | same                |
+    /// | `Shift-Tab`            | change mode (when idle)      | bottom pane         |
+    /// | reasoning cycle key    | cycle reasoning effort       | bottom pane         |
+    /// | `Alt-Up`               | restore last queued message  | same                |
+    /// | `PageUp` / `PageDown`  | open the transcript pager    | bottom pane         |
+    /// | anything else          | bottom pane                  | bottom pane         |
+    ///
+    /// Key releases always go to the bottom pane.
+    pub(crate) fn route_key(&self, key_event: KeyEvent) -> KeyRoute {
+        if key_event.kind != KeyEventKind::Press {
+            return KeyRoute::BottomPane;
+        }
+        let no_modal_or_popup = self.bottom_pane.no_modal_or_popup_active();
         match key_event {
             KeyEvent {
                 code: KeyCode::Char(c),
                 modifiers,
-                kind: KeyEventKind::Press,
                 ..
             } if modifiers.contains(KeyModifiers::CONTROL) && c.eq_ignore_ascii_case(&'c') => {
-                self.on_ctrl_c();
-                return;
+                KeyRoute::Interrupt
             }
             KeyEvent {
                 code: KeyCode::Char(c),
                 modifiers,
-                kind: KeyEventKind::Press,
                 ..
             } if modifiers.contains(KeyModifiers::CONTROL) && c.eq_ignore_ascii_case(&'d') => {
-                if self.on_ctrl_d() {
-                    return;
-                }
-                self.bottom_pane.clear_quit_shortcut_hint();
-                self.quit_shortcut_expires_at = None;
-                self.quit_shortcut_key = None;
+                KeyRoute::Quit
             }
             KeyEvent {
                 code: KeyCode::Char(c),
                 modifiers,
-                kind: KeyEventKind::Press,
                 ..
             } if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
                 && c.eq_ignore_ascii_case(&'v') =>
             {
-                match paste_image_to_temp_png() {
-                    Ok((path, info)) => {
-                        tracing::debug!(
-                            "pasted image size={}x{} format={}",
-                            info.width,
-                            info.height,
-                            info.encoded_format.label()
-                        );
-                        self.attach_image(path);
-                    }
-                    Err(err) => {
-                        tracing::warn!("failed to paste image: {err}");
-                        self.add_to_history(history_cell::new_error_event(format!(
-                            "Failed to paste image: {err}",
-                        )));
-                    }
-                }
-                return;
-            }
-            other if other.kind == KeyEventKind::Press => {
-                self.bottom_pane.clear_quit_shortcut_hint();
-                self.quit_shortcut_expires_at = None;
-                self.quit_shortcut_key = None;
+                KeyRoute::PasteImage
             }
-            _ => {}
-        }
-
-        match key_event {
             KeyEvent {
                 code: KeyCode::BackTab,
-                kind: KeyEventKind::Press,
                 ..
             } if self.collaboration_modes_enabled()
                 && !self.bottom_pane.is_task_running()
-                && self.bottom_pane.no_modal_or_popup_active() =>
+                && no_modal_or_popup =>
             {
-                self.cycle_collaboration_mode();
+                KeyRoute::CycleCollaborationMode
+            }
+            _ if self.reasoning_cycle_key.is_press(key_event) && no_modal_or_popup => {
+                KeyRoute::CycleReasoningEffort
             }
             KeyEvent {
                 code: KeyCode::Up,
                 modifiers: KeyModifiers::ALT,
-                kind: KeyEventKind::Press,
                 ..
-            } if !self.queued_user_messages.is_empty() => {
+            } if !self.queued_user_messages.is_empty() => KeyRoute::RestoreQueuedMessage,
+            KeyEvent {
+                code: KeyCode::PageUp | KeyCode::PageDown,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } if no_modal_or_popup => KeyRoute::Transcript,
+            _ => KeyRoute::BottomPane,
+        }
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) {
+        let route = self.route_key(key_event);
+        match route {
+            KeyRoute::Interrupt => {
+                self.on_ctrl_c();
+                return;
+            }
+            KeyRoute::Quit if self.on_ctrl_d() => return,
+            KeyRoute::PasteImage => {
+                self.paste_image();
+                return;
+            }
+            _ => {}
+        }
+        if key_event.kind == KeyEventKind::Press {
+            self.bottom_pane.clear_quit_shortcut_hint();
+            self.quit_shortcut_expires_at = None;
+            self.quit_shortcut_key = None;
+        }
+
+        match route {
+            KeyRoute::CycleCollaborationMode => self.cycle_collaboration_mode(),
+            KeyRoute::CycleReasoningEffort => self.cycle_reasoning_effort(),
+            KeyRoute::RestoreQueuedMessage => {
                 // Prefer the most recently queued item.
                 if let Some(user_message) = self.queued_user_messages.pop_back() {
                     let local_image_paths = user_message
@@ -2672,52 +3174,83 @@ impl ChatWidget {
                     self.request_redraw();
                 }
             }
-            _ => match self.bottom_pane.handle_key_event(key_event) {
-                InputResult::Submitted {
+            KeyRoute::Transcript => self.app_event_tx.send(AppEvent::OpenTranscript),
+            KeyRoute::Interrupt | KeyRoute::Quit | KeyRoute::PasteImage | KeyRoute::BottomPane => {
+                self.forward_key_to_bottom_pane(key_event)
+            }
+        }
+    }
+
+    fn paste_image(&mut self) {
+        match paste_image_to_temp_png() {
+            Ok((path, info)) => {
+                tracing::debug!(
+                    "pasted image size={}x{} format={}",
+                    info.width,
+                    info.height,
+                    info.encoded_format.label()
+                );
+                self.attach_image(path);
+            }
+            Err(err) => {
+                tracing::warn!("failed to paste image: {err}");
+                self.add_to_history(history_cell::new_error_event(format!(
+                    "Failed to paste image: {err}",
+                )));
+            }
+        }
+    }
+
+    fn forward_key_to_bottom_pane(&mut self, key_event: KeyEvent) {
+        match self.bottom_pane.handle_key_event(key_event) {
+            InputResult::Submitted {
+                text,
+                text_elements,
+            } => {
+                let user_message = UserMessage {
                     text,
+                    local_images: self
+                        .bottom_pane
+                        .take_recent_submission_images_with_placeholders(),
                     text_elements,
-                } => {
-                    let user_message = UserMessage {
-                        text,
-                        local_images: self
-                            .bottom_pane
-                            .take_recent_submission_images_with_placeholders(),
-                        text_elements,
-                        mention_paths: self.bottom_pane.take_mention_paths(),
-                    };
-                    if self.is_session_configured() {
-                        // Submitted is only emitted when steer is enabled (Enter sends immediately).
-                        // Reset any reasoning header only when we are actually submitting a turn.
-                        self.reasoning_buffer.clear();
-                        self.full_reasoning_buffer.clear();
-                        self.set_status_header(String::from("Working"));
-                        self.submit_user_message(user_message);
-                    } else {
-                        self.queue_user_message(user_message);
-                    }
+                    mention_paths: self.bottom_pane.take_mention_paths(),
+                };
+                if self.is_session_configured() {
+                    // Submitted is only emitted when steer is enabled (Enter sends immediately).
+                    // Reset any reasoning header only when we are actually submitting a turn.
+                    self.reasoning_buffer.clear();
+                    self.full_reasoning_buffer.clear();
+                    self.reasoning_headlines.reset();
+                    self.set_status_header(String::from("Working"));
+                    self.submit_user_message(user_message);
+                } else {
+                    self.queue_user_message(user_message);
                 }
-                InputResult::Queued {
+            }
+            InputResult::Queued {
+                text,
+                text_elements,
+            } => {
+                let user_message = UserMessage {
                     text,
+                    local_images: self
+                        .bottom_pane
+                        .take_recent_submission_images_with_placeholders(),
                     text_elements,
-                } => {
-                    let user_message = UserMessage {
-                        text,
-                        local_images: self
-                            .bottom_pane
-                            .take_recent_submission_images_with_placeholders(),
-                        text_elements,
-                        mention_paths: self.bottom_pane.take_mention_paths(),
-                    };
-                    self.queue_user_message(user_message);
-                }
-                InputResult::Command(cmd) => {
-                    self.dispatch_command(cmd);
-                }
-                InputResult::CommandWithArgs(cmd, args) => {
-                    self.dispatch_command_with_args(cmd, args);
-                }
-                InputResult::None => {}
-            },
+                    mention_paths: self.bottom_pane.take_mention_paths(),
+                };
+                self.queue_user_message(user_message);
+            }
+            InputResult::Steer { text } => {
+                self.submit_steer(text);
+            }
+            InputResult::Command(cmd) => {
+                self.dispatch_command(cmd);
+            }
+            InputResult::CommandWithArgs(cmd, args) => {
+                self.dispatch_command_with_args(cmd, args);
+            }
+            InputResult::None => {}
         }
     }
 
@@ -2784,6 +3317,9 @@ impl ChatWidget {
             SlashCommand::New => {
                 self.app_event_tx.send(AppEvent::NewSession);
             }
+            SlashCommand::Sessions => {
+                self.app_event_tx.send(AppEvent::OpenSessionPicker);
+            }
             SlashCommand::Resume => {
                 self.app_event_tx.send(AppEvent::OpenResumePicker);
             }
@@ -2809,7 +3345,13 @@ impl ChatWidget {
             SlashCommand::Review => {
                 self.open_review_popup();
             }
-            SlashCommand::Rename => {
+            SlashCommand::Steer => {
+                self.add_error_message("Usage: /steer <guidance>".to_string());
+            }
+            SlashCommand::Ask => {
+                self.add_error_message("Usage: /ask <question>".to_string());
+            }
+            SlashCommand::Rename | SlashCommand::Title => {
                 self.show_rename_prompt();
             }
             SlashCommand::Model => {
@@ -2818,6 +3360,16 @@ impl ChatWidget {
             SlashCommand::Personality => {
                 self.open_personality_popup();
             }
+            SlashCommand::Reasoning => {
+                self.cycle_reasoning_effort();
+            }
+            SlashCommand::Language => {
+                let current = match &self.config.preferred_language {
+                    Some(language) => format!("Codex answers in {language}."),
+                    None => "No preferred language is set.".to_string(),
+                };
+                self.add_info_message(current, Some("Usage: /language <language>|off".to_string()));
+            }
             SlashCommand::Plan => {
                 if !self.collaboration_modes_enabled() {
                     self.add_info_message(
@@ -2899,6 +3451,9 @@ impl ChatWidget {
             SlashCommand::Experimental => {
                 self.open_experimental_popup();
             }
+            SlashCommand::Setup => {
+                self.open_setup_wizard();
+            }
             SlashCommand::Quit | SlashCommand::Exit => {
                 self.request_quit_without_confirmation();
             }
@@ -2915,30 +3470,31 @@ impl ChatWidget {
             //     self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
             // }
             SlashCommand::Diff => {
-                self.add_diff_in_progress();
-                let tx = self.app_event_tx.clone();
-                tokio::spawn(async move {
-                    let text = match get_git_diff().await {
-                        Ok((is_git_repo, diff_text)) => {
-                            if is_git_repo {
-                                diff_text
-                            } else {
-                                "`/diff` — _not inside a git repository_".to_string()
-                            }
-                        }
-                        Err(e) => format!("Failed to compute diff: {e}"),
-                    };
-                    tx.send(AppEvent::DiffResult(text));
-                });
+                self.show_git_diff("");
+            }
+            SlashCommand::History => {
+                self.show_history_files("");
+            }
+            SlashCommand::Commit => {
+                self.start_commit("");
             }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
+            SlashCommand::Attach => {
+                self.add_error_message("Usage: /attach <path>".to_string());
+            }
             SlashCommand::Skills => {
                 self.open_skills_menu();
             }
+            SlashCommand::Memory => {
+                self.open_project_memory_popup();
+            }
             SlashCommand::Status => {
                 self.add_status_output();
+                if self.thread_id.is_some() {
+                    self.submit_op(Op::GetTokenBreakdown);
+                }
             }
             SlashCommand::Ps => {
                 self.add_ps_output();
@@ -2946,9 +3502,24 @@ impl ChatWidget {
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
+            SlashCommand::Tools => {
+                self.submit_op(Op::ListTools);
+            }
+            SlashCommand::Revoke => {
+                self.submit_op(Op::RevokeSessionApprovals);
+            }
+            SlashCommand::ClearHistory => {
+                self.submit_op(Op::ClearHistory);
+            }
             SlashCommand::Apps => {
                 self.add_connectors_output();
             }
+            SlashCommand::Export => {
+                self.export_transcript("");
+            }
+            SlashCommand::Doctor => {
+                self.run_doctor();
+            }
             SlashCommand::Rollout => {
                 if let Some(path) = self.rollout_path() {
                     self.add_info_message(
@@ -3013,7 +3584,7 @@ impl ChatWidget {
 
         let trimmed = args.trim();
         match cmd {
-            SlashCommand::Rename if !trimmed.is_empty() => {
+            SlashCommand::Rename | SlashCommand::Title if !trimmed.is_empty() => {
                 let Some(name) = codex_core::util::normalize_thread_name(trimmed) else {
                     self.add_error_message("Thread name cannot be empty.".to_string());
                     return;
@@ -3028,6 +3599,33 @@ impl ChatWidget {
                 let _ = trimmed;
                 self.dispatch_command(cmd);
             }
+            SlashCommand::Export => {
+                self.export_transcript(trimmed);
+            }
+            SlashCommand::Diff => {
+                self.show_git_diff(trimmed);
+            }
+            SlashCommand::History => {
+                self.show_history_files(trimmed);
+            }
+            SlashCommand::Commit => {
+                self.start_commit(trimmed);
+            }
+            SlashCommand::Reasoning if !trimmed.is_empty() => {
+                self.set_reasoning_effort_from_command(trimmed);
+            }
+            SlashCommand::Language if !trimmed.is_empty() => {
+                self.set_preferred_language(trimmed);
+            }
+            SlashCommand::Steer if !trimmed.is_empty() => {
+                self.submit_steer(trimmed.to_string());
+            }
+            SlashCommand::Ask if !trimmed.is_empty() => {
+                self.submit_ask(trimmed.to_string());
+            }
+            SlashCommand::Attach if !trimmed.is_empty() => {
+                self.attach_file(trimmed);
+            }
             SlashCommand::Review if !trimmed.is_empty() => {
                 self.submit_op(Op::Review {
                     review_request: ReviewRequest {
@@ -3042,6 +3640,368 @@ impl ChatWidget {
         }
     }
 
+    /// Asks core to draft a commit message for the files this session changed.
+    /// The draft comes back as `CommitDraft` and opens in an editor.
+    fn start_commit(&mut self, args: &str) {
+        let mut sign_off = false;
+        let mut amend = false;
+        for arg in args.split_whitespace() {
+            match arg {
+                "--signoff" | "-s" => sign_off = true,
+                "--amend" => amend = true,
+                other => {
+                    self.add_error_message(format!(
+                        "Unknown /commit option `{other}`. Usage: /commit [--signoff] [--amend]"
+                    ));
+                    return;
+                }
+            }
+        }
+        self.submit_op(Op::DraftCommit { sign_off, amend });
+        self.bottom_pane.ensure_status_indicator();
+        self.bottom_pane.set_interrupt_hint_visible(true);
+        self.set_status_header("Drafting commit message".to_string());
+    }
+
+    fn on_commit_draft(&mut self, event: CommitDraftEvent) {
+        self.bottom_pane.hide_status_indicator();
+        let CommitDraftEvent {
+            message,
+            files,
+            sign_off,
+            amend,
+        } = event;
+        let title = if amend {
+            "Amend commit"
+        } else {
+            "Commit message"
+        };
+        let listed: Vec<String> = files
+            .iter()
+            .map(|file| {
+                file.strip_prefix(&self.config.cwd)
+                    .unwrap_or(file)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        let context = match listed.len() {
+            0 => "No files; only the message changes".to_string(),
+            1 => format!("Commits {}", listed[0]),
+            count => format!("Commits {count} files: {}", listed.join(", ")),
+        };
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            title.to_string(),
+            "Edit the message and press Enter to commit".to_string(),
+            Some(context),
+            Box::new(move |message: String| {
+                tx.send(AppEvent::CodexOp(Op::Commit {
+                    message: Some(message),
+                    files: files.clone(),
+                    sign_off,
+                    amend,
+                }));
+            }),
+        )
+        .with_initial_text(&message);
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
+    fn on_commit_completed(&mut self, event: CommitCompletedEvent) {
+        self.bottom_pane.hide_status_indicator();
+        if !event.success {
+            let error = event.error.unwrap_or_else(|| "Commit failed.".to_string());
+            self.add_error_message(error);
+            return;
+        }
+        let short = event
+            .commit
+            .as_deref()
+            .map(|sha| &sha[..sha.len().min(7)])
+            .unwrap_or_default();
+        let subject = event
+            .message
+            .as_deref()
+            .and_then(|message| message.lines().next())
+            .unwrap_or_default();
+        let files = match event.files.len() {
+            0 => None,
+            1 => Some("1 file".to_string()),
+            count => Some(format!("{count} files")),
+        };
+        self.add_info_message(format!("Committed {short} {subject}"), files);
+    }
+
+    fn on_hook_end(&mut self, event: HookEndEvent) {
+        let command = strip_bash_lc_and_escape(&event.command);
+        let hook = event.hook;
+        if event.success() {
+            let elapsed = fmt_elapsed_compact(event.duration.as_secs());
+            self.add_info_message(
+                format!("{hook} hook `{command}` finished in {elapsed}"),
+                None,
+            );
+            return;
+        }
+
+        let reason = match event.exit_code {
+            _ if event.timed_out => "timed out".to_string(),
+            Some(code) => format!("exited with code {code}"),
+            None => "could not be started".to_string(),
+        };
+        let mut message = format!("{hook} hook `{command}` {reason}");
+        if let Some(line) = event
+            .output
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+        {
+            message.push_str(": ");
+            message.push_str(line.trim());
+        }
+        if event.aborts_turn {
+            self.add_error_message(message);
+        } else {
+            self.on_warning(message);
+        }
+    }
+
+    /// Writes a redacted HTML transcript of the session next to the working
+    /// directory. `/export` and `/export --html` are equivalent; HTML is the
+    /// only format.
+    fn export_transcript(&mut self, args: &str) {
+        if !matches!(args, "" | "--html") {
+            self.add_error_message(format!(
+                "Unknown /export option `{args}`. Usage: /export --html"
+            ));
+            return;
+        }
+        let Some(rollout_path) = self.rollout_path() else {
+            self.add_info_message("Rollout path is not available yet.".to_string(), None);
+            return;
+        };
+        let file_stem = rollout_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "transcript".to_string());
+        let output_path = self.config.cwd.join(format!("{file_stem}.html"));
+        let options = TranscriptExportOptions {
+            redact_patterns: self.config.export_redact_patterns.clone(),
+        };
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = async {
+                let html = export_html_transcript(&rollout_path, &options).await?;
+                tokio::fs::write(&output_path, html).await
+            }
+            .await;
+            let cell = match result {
+                Ok(()) => history_cell::new_info_event(
+                    format!("Exported transcript to {}", output_path.display()),
+                    Some("Known secrets were redacted; review it before sharing.".to_string()),
+                ),
+                Err(err) => {
+                    history_cell::new_error_event(format!("Failed to export transcript: {err}"))
+                }
+            };
+            tx.send(AppEvent::InsertHistoryCell(Box::new(cell)));
+        });
+    }
+
+    /// Runs the installation diagnostics in the background, reloading the
+    /// configuration from disk with this session's profile and working
+    /// directory.
+    fn run_doctor(&mut self) {
+        self.add_info_message("Running diagnostics...".to_string(), None);
+        let codex_home = self.config.codex_home.clone();
+        let cwd = self.config.cwd.clone();
+        let config = ConfigBuilder::default().harness_overrides(ConfigOverrides {
+            cwd: Some(cwd.clone()),
+            config_profile: self.config.active_profile.clone(),
+            codex_linux_sandbox_exe: self.config.codex_linux_sandbox_exe.clone(),
+            ..Default::default()
+        });
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let report = codex_core::doctor::run_doctor(codex_home, cwd, config).await;
+            let cell = history_cell::new_doctor_output(&report.redacted());
+            tx.send(AppEvent::InsertHistoryCell(Box::new(cell)));
+        });
+    }
+
+    /// Computes the diff selected by the `/diff` arguments and opens it in the
+    /// pager once ready.
+    fn show_git_diff(&mut self, args: &str) {
+        let options = match parse_diff_args(args) {
+            Ok(options) => options,
+            Err(message) => {
+                self.add_error_message(message);
+                return;
+            }
+        };
+        self.add_diff_in_progress();
+        let cwd = self.config.cwd.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let text = diff_text(&cwd, &options).await;
+            tx.send(AppEvent::DiffResult(text));
+        });
+    }
+
+    /// Asks core for the session's ghost snapshots; `on_ghost_snapshots`
+    /// continues with a picker once they arrive.
+    fn show_history_files(&mut self, args: &str) {
+        match parse_history_args(args) {
+            Ok(args) => {
+                self.history_files = args;
+                self.submit_op(Op::ListGhostSnapshots);
+            }
+            Err(message) => self.add_error_message(message),
+        }
+    }
+
+    fn on_ghost_snapshots(&mut self, event: GhostSnapshotsResponseEvent) {
+        if let Some(reason) = event.unavailable {
+            self.add_info_message(reason, None);
+            return;
+        }
+        if event.snapshots.is_empty() {
+            self.add_info_message(
+                "No snapshots recorded in this session yet.".to_string(),
+                Some("Codex snapshots the workspace at the start of each turn.".to_string()),
+            );
+            return;
+        }
+        self.ghost_snapshots = event.snapshots;
+
+        if let Some(turn) = self.history_files.turn {
+            let commit_id = self
+                .ghost_snapshots
+                .iter()
+                .find(|entry| entry.turn == Some(turn))
+                .map(|entry| entry.commit_id.clone());
+            match commit_id {
+                Some(commit_id) => self.open_snapshot_files(&commit_id),
+                None => self.add_error_message(format!(
+                    "No snapshot was taken before turn {turn}. Run /history files to list snapshots."
+                )),
+            }
+            return;
+        }
+
+        let items: Vec<SelectionItem> = self
+            .ghost_snapshots
+            .iter()
+            .rev()
+            .map(|entry| {
+                let commit_id = entry.commit_id.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenSnapshotFiles {
+                        commit_id: commit_id.clone(),
+                    });
+                })];
+                let label = snapshot_label(entry.turn, &entry.commit_id);
+                let mut name = label.clone();
+                if let Some(first) = name.get_mut(..1) {
+                    first.make_ascii_uppercase();
+                }
+                SelectionItem {
+                    name,
+                    description: Some(format!(
+                        "{} · {} files",
+                        short_id(&entry.commit_id),
+                        entry.files.len()
+                    )),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Snapshots".bold()));
+        header.push(Line::from(
+            "Select a snapshot to browse the files it captured.".dim(),
+        ));
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Lists the files captured by `commit_id`; picking one asks core to read
+    /// it back.
+    pub(crate) fn open_snapshot_files(&mut self, commit_id: &str) {
+        let Some(entry) = self
+            .ghost_snapshots
+            .iter()
+            .find(|entry| entry.commit_id == commit_id)
+        else {
+            return;
+        };
+        let label = snapshot_label(entry.turn, &entry.commit_id);
+        let items: Vec<SelectionItem> = entry
+            .files
+            .iter()
+            .map(|path| {
+                let name = path.display().to_string();
+                let commit_id = entry.commit_id.clone();
+                let path = path.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::ReadGhostSnapshotFile {
+                        commit_id: commit_id.clone(),
+                        path: path.clone(),
+                    }));
+                })];
+                SelectionItem {
+                    search_value: Some(name.clone()),
+                    name,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let action = if self.history_files.diff {
+            "compare it with the current file"
+        } else {
+            "view it"
+        };
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from(format!("Files {label}").bold()));
+        header.push(Line::from(format!("Select a file to {action}.").dim()));
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search files".to_string()),
+            ..Default::default()
+        });
+    }
+
+    fn on_ghost_snapshot_file(&mut self, event: GhostSnapshotFileResponseEvent) {
+        let turn = self
+            .ghost_snapshots
+            .iter()
+            .find(|entry| entry.commit_id == event.commit_id)
+            .and_then(|entry| entry.turn);
+        let label = snapshot_label(turn, &event.commit_id);
+        match snapshot_file_view(event, &label, self.history_files.diff) {
+            Ok(view) => self.app_event_tx.send(AppEvent::ShowSnapshotFile {
+                title: "S N A P S H O T".to_string(),
+                view,
+            }),
+            Err(message) => self.add_error_message(message),
+        }
+    }
+
     fn show_rename_prompt(&mut self) {
         let tx = self.app_event_tx.clone();
         let has_name = self
@@ -3124,6 +4084,51 @@ impl ChatWidget {
         self.app_event_tx.send(AppEvent::InsertHistoryCell(cell));
     }
 
+    /// Send `text` to the running turn; the core starts a turn when none is
+    /// running.
+    fn submit_steer(&mut self, text: String) {
+        self.submit_op(Op::SteerTask { text });
+    }
+
+    /// Answers `question` in one turn without tools, attaching the files it
+    /// refers to as `@path`.
+    fn submit_ask(&mut self, question: String) {
+        let mut items = vec![UserInput::Text {
+            text: question.clone(),
+            text_elements: Vec::new(),
+        }];
+        items.extend(codex_core::ask::referenced_file_inputs(
+            &question,
+            &self.config.cwd,
+        ));
+        self.submit_op(Op::Ask { items });
+        self.add_to_history(history_cell::new_user_prompt(
+            format!("/ask {question}"),
+            Vec::new(),
+            Vec::new(),
+        ));
+        self.needs_final_message_separator = false;
+    }
+
+    /// Queues the file at `path` to be sent with the next user message.
+    fn attach_file(&mut self, path: &str) {
+        let path = self.config.cwd.join(path);
+        if !path.is_file() {
+            self.add_error_message(format!("No file at {}", path.display()));
+            return;
+        }
+        let display = path
+            .strip_prefix(&self.config.cwd)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        self.pending_attachments.push(path);
+        self.add_info_message(
+            format!("{display} will be attached to your next message"),
+            None,
+        );
+    }
+
     fn queue_user_message(&mut self, user_message: UserMessage) {
         if !self.is_session_configured()
             || self.bottom_pane.is_task_running()
@@ -3144,18 +4149,31 @@ impl ChatWidget {
             return;
         }
 
+        if let Some(window) = self.context_window()
+            && !user_message.text.starts_with('!')
+        {
+            let message_tokens = estimate_tokens(&user_message.text);
+            let remaining_tokens = self.context_estimate.remaining_tokens(window);
+            if message_tokens > remaining_tokens {
+                self.hold_oversized_message(user_message, message_tokens, remaining_tokens, window);
+                return;
+            }
+        }
+
+        self.send_user_message(user_message);
+    }
+
+    fn send_user_message(&mut self, user_message: UserMessage) {
         let UserMessage {
             text,
             local_images,
             text_elements,
             mention_paths,
-        } = user_message;
+        } = order_images_by_placeholder(user_message);
         if text.is_empty() && local_images.is_empty() {
             return;
         }
 
-        let mut items: Vec<UserInput> = Vec::new();
-
         // Special-case: "!cmd" executes a local shell command instead of sending to the model.
         if let Some(stripped) = text.strip_prefix('!') {
             let cmd = stripped.trim();
@@ -3174,18 +4192,12 @@ impl ChatWidget {
             return;
         }
 
-        for image in &local_images {
-            items.push(UserInput::LocalImage {
-                path: image.path.clone(),
-            });
-        }
-
-        if !text.is_empty() {
-            items.push(UserInput::Text {
-                text: text.clone(),
-                text_elements: text_elements.clone(),
-            });
-        }
+        let mut items = user_input_items(&text, &text_elements, &local_images);
+        items.extend(
+            self.pending_attachments
+                .drain(..)
+                .map(|path| UserInput::LocalFile { path }),
+        );
 
         let mentions = collect_tool_mentions(&text, &mention_paths);
         let mut skill_names_lower: HashSet<String> = HashSet::new();
@@ -3239,11 +4251,15 @@ impl ChatWidget {
             final_output_json_schema: None,
             collaboration_mode,
             personality,
+            max_output_tokens: None,
+            service_tier: None,
         };
 
         self.codex_op_tx.send(op).unwrap_or_else(|e| {
             tracing::error!("failed to send message: {e}");
         });
+        self.context_estimate.add_text(&text);
+        self.refresh_estimated_context_window();
 
         // Persist the text to cross-session message history.
         if !text.is_empty() {
@@ -3264,7 +4280,149 @@ impl ChatWidget {
             ));
         }
 
-        self.needs_final_message_separator = false;
+        self.needs_final_message_separator = false;
+    }
+
+    /// Puts an oversized message back in the composer and asks how to send it.
+    fn hold_oversized_message(
+        &mut self,
+        user_message: UserMessage,
+        message_tokens: i64,
+        remaining_tokens: i64,
+        context_window: i64,
+    ) {
+        let local_image_paths = user_message
+            .local_images
+            .iter()
+            .map(|img| img.path.clone())
+            .collect();
+        self.bottom_pane.set_composer_text(
+            user_message.text.clone(),
+            user_message.text_elements.clone(),
+            local_image_paths,
+        );
+        self.pending_oversized_message = Some(user_message);
+
+        let excerpt_tokens = self.context_estimate.excerpt_budget(context_window);
+        let action = |action: OversizedMessageAction| -> Vec<SelectionAction> {
+            vec![Box::new(move |tx| {
+                tx.send(AppEvent::ResolveOversizedMessage(action));
+            })]
+        };
+        let items = vec![
+            SelectionItem {
+                name: "Send an excerpt".to_string(),
+                description: Some(format!(
+                    "Keep the first and last lines, about {} tokens",
+                    format_tokens_compact(excerpt_tokens)
+                )),
+                actions: action(OversizedMessageAction::SendExcerpt),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Save to a file and send a reference".to_string(),
+                description: Some(format!(
+                    "Write the full text under {PASTES_DIR}/ and ask Codex to read it"
+                )),
+                actions: action(OversizedMessageAction::SendFileReference),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Send anyway".to_string(),
+                description: Some("The request may be rejected".to_string()),
+                actions: action(OversizedMessageAction::SendAnyway),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Keep editing".to_string(),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from(
+            "Message may not fit in the remaining context".bold(),
+        ));
+        header.push(Line::from(
+            format!(
+                "~{} tokens, with ~{} tokens left. Both are estimates.",
+                format_tokens_compact(message_tokens),
+                format_tokens_compact(remaining_tokens)
+            )
+            .dim(),
+        ));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Sends the message held by [`Self::hold_oversized_message`] as chosen in
+    /// the popup.
+    pub(crate) fn resolve_oversized_message(&mut self, action: OversizedMessageAction) {
+        let Some(mut user_message) = self.pending_oversized_message.take() else {
+            return;
+        };
+        let message_tokens = estimate_tokens(&user_message.text);
+        let excerpt_tokens = self
+            .context_window()
+            .map_or(0, |window| self.context_estimate.excerpt_budget(window));
+        match action {
+            OversizedMessageAction::SendExcerpt => {
+                let (excerpt, head_len) = excerpt_to_fit(&user_message.text, excerpt_tokens);
+                user_message.text = excerpt;
+                user_message
+                    .text_elements
+                    .retain(|element| element.byte_range.end <= head_len);
+                self.add_info_message(
+                    format!(
+                        "Sent an excerpt of your message (~{} of ~{} tokens).",
+                        format_tokens_compact(estimate_tokens(&user_message.text)),
+                        format_tokens_compact(message_tokens)
+                    ),
+                    None,
+                );
+            }
+            OversizedMessageAction::SendFileReference => {
+                let saved = match save_paste(&self.config.cwd, &user_message.text) {
+                    Ok(saved) => saved,
+                    Err(err) => {
+                        self.add_error_message(format!("Failed to save message: {err}"));
+                        self.pending_oversized_message = Some(user_message);
+                        return;
+                    }
+                };
+                let (preview, head_len) =
+                    excerpt_to_fit(&user_message.text, FILE_REFERENCE_PREVIEW_TOKENS);
+                user_message.text = format!(
+                    "My message was too large to send inline (~{} tokens), so it is saved at {}. Read that file for the full text. It starts:\n\n{preview}",
+                    format_tokens_compact(message_tokens),
+                    saved.display_path
+                );
+                let offset = user_message.text.len() - preview.len();
+                user_message.text_elements = user_message
+                    .text_elements
+                    .into_iter()
+                    .filter(|element| element.byte_range.end <= head_len)
+                    .map(|mut element| {
+                        element.byte_range.start += offset;
+                        element.byte_range.end += offset;
+                        element
+                    })
+                    .collect();
+            }
+            OversizedMessageAction::SendAnyway => {}
+        }
+        self.bottom_pane
+            .set_composer_text(String::new(), Vec::new(), Vec::new());
+        self.send_user_message(user_message);
     }
 
     /// Replay a subset of initial events into the UI to seed the transcript when
@@ -3291,11 +4449,18 @@ impl ChatWidget {
     }
 
     pub(crate) fn handle_codex_event_replay(&mut self, event: Event) {
-        let Event { msg, .. } = event;
+        let Event { id, msg } = event;
         if matches!(msg, EventMsg::ShutdownComplete) {
             return;
         }
-        self.dispatch_event_msg(None, msg, true);
+        // Thread buffers only keep unanswered approval requests, so the turn
+        // is still waiting on this id.
+        let id = matches!(
+            msg,
+            EventMsg::ExecApprovalRequest(_) | EventMsg::ApplyPatchApprovalRequest(_)
+        )
+        .then_some(id);
+        self.dispatch_event_msg(id, msg, true);
     }
 
     /// Dispatch a protocol `EventMsg` to the appropriate handler.
@@ -3339,20 +4504,17 @@ impl ChatWidget {
             }
             EventMsg::AgentReasoningSectionBreak(_) => self.on_reasoning_section_break(),
             EventMsg::TurnStarted(_) => self.on_task_started(),
-            EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }) => {
-                self.on_task_complete(last_agent_message, from_replay)
-            }
+            EventMsg::TurnComplete(TurnCompleteEvent {
+                last_agent_message, ..
+            }) => self.on_task_complete(last_agent_message, from_replay),
             EventMsg::TokenCount(ev) => {
                 self.set_token_info(ev.info);
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
-            EventMsg::Error(ErrorEvent {
-                message,
-                codex_error_info,
-            }) => {
-                if let Some(info) = codex_error_info
-                    && let Some(kind) = rate_limit_error_kind(&info)
+            EventMsg::Error(ev) => {
+                if let Some(info) = &ev.codex_error_info
+                    && let Some(kind) = rate_limit_error_kind(info)
                 {
                     match kind {
                         RateLimitErrorKind::ModelCap {
@@ -3360,15 +4522,16 @@ impl ChatWidget {
                             reset_after_seconds,
                         } => self.on_model_cap_error(model, reset_after_seconds),
                         RateLimitErrorKind::UsageLimit | RateLimitErrorKind::Generic => {
-                            self.on_error(message)
+                            self.on_request_error(ev)
                         }
                     }
                 } else {
-                    self.on_error(message);
+                    self.on_request_error(ev);
                 }
             }
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
+            EventMsg::SessionPrewarmed(ev) => self.on_session_prewarmed(ev),
             EventMsg::TurnAborted(ev) => match ev.reason {
                 TurnAbortReason::Interrupted => {
                     self.on_interrupted_turn(ev.reason);
@@ -3388,6 +4551,12 @@ impl ChatWidget {
             EventMsg::ApplyPatchApprovalRequest(ev) => {
                 self.on_apply_patch_approval_request(id.unwrap_or_default(), ev)
             }
+            EventMsg::ApprovalResolved(ev) => {
+                // Live decisions are already shown by the approval overlay.
+                if from_replay {
+                    self.add_boxed_history(history_cell::new_approval_resolution_cell(&ev));
+                }
+            }
             EventMsg::ElicitationRequest(ev) => {
                 self.on_elicitation_request(ev);
             }
@@ -3395,19 +4564,50 @@ impl ChatWidget {
                 self.on_request_user_input(ev);
             }
             EventMsg::ExecCommandBegin(ev) => self.on_exec_command_begin(ev),
+            EventMsg::ExecCommandSpawn(ev) => {
+                // Inserting a history cell here would split the running exec
+                // cell, so the spawn spec only goes to the log.
+                tracing::info!(
+                    call_id = %ev.call_id,
+                    dry_run = ev.dry_run,
+                    "exec spawn: {}",
+                    serde_json::to_string(&ev).unwrap_or_default()
+                );
+            }
             EventMsg::TerminalInteraction(delta) => self.on_terminal_interaction(delta),
             EventMsg::ExecCommandOutputDelta(delta) => self.on_exec_command_output_delta(delta),
             EventMsg::PatchApplyBegin(ev) => self.on_patch_apply_begin(ev),
             EventMsg::PatchApplyEnd(ev) => self.on_patch_apply_end(ev),
             EventMsg::ExecCommandEnd(ev) => self.on_exec_command_end(ev),
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
+            EventMsg::ImageDownscaled(ev) => self.on_image_downscaled(ev),
+            EventMsg::FileAttached(ev) => self.on_file_attached(ev),
+            EventMsg::WorkspaceSummary(ev) => self.add_info_message(ev.summary(), None),
+            EventMsg::StalePatchContext(ev) => self.add_info_message(ev.summary(), None),
+            EventMsg::StalePatchRetry(ev) => self.add_info_message(ev.summary(), None),
+            EventMsg::ExternalFilesChanged(ev) => self.add_info_message(ev.summary(), None),
+            EventMsg::PromptQueuedOffline(ev) => self.on_prompt_queued_offline(ev.queued_prompts),
+            EventMsg::ConnectivityRestored(ev) => {
+                self.on_connectivity_restored(ev.submitted_prompts)
+            }
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
+            EventMsg::HistoryCleared(ev) => {
+                self.bottom_pane.set_history_metadata(ev.log_id, 0);
+                self.add_info_message("Cleared message history".to_string(), None);
+            }
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
+            EventMsg::ListToolsResponse(ev) => self.on_list_tools(ev),
+            EventMsg::TokenBreakdown(ev) => {
+                self.add_to_history(crate::status::new_token_breakdown_output(&ev));
+                self.request_redraw();
+            }
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::GhostSnapshotsResponse(ev) => self.on_ghost_snapshots(ev),
+            EventMsg::GhostSnapshotFileResponse(ev) => self.on_ghost_snapshot_file(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
             EventMsg::SkillsUpdateAvailable => {
                 self.submit_op(Op::ListSkills {
@@ -3423,6 +4623,11 @@ impl ChatWidget {
             }
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
+            EventMsg::CommitDraft(ev) => self.on_commit_draft(ev),
+            EventMsg::CommandExplanation(ev) => self.bottom_pane.on_command_explanation(&ev),
+            EventMsg::CommitCompleted(ev) => self.on_commit_completed(ev),
+            EventMsg::HookBegin(_) => {}
+            EventMsg::HookEnd(ev) => self.on_hook_end(ev),
             EventMsg::StreamError(StreamErrorEvent {
                 message,
                 additional_details,
@@ -3433,6 +4638,8 @@ impl ChatWidget {
                     self.on_user_message_event(ev);
                 }
             }
+            EventMsg::SteerDelivered(ev) => self.on_steer_delivered(ev),
+            EventMsg::TurnRefused(ev) => self.on_turn_refused(ev),
             EventMsg::EnteredReviewMode(review_request) => {
                 self.on_entered_review_mode(review_request, from_replay)
             }
@@ -3449,12 +4656,34 @@ impl ChatWidget {
             EventMsg::CollabCloseBegin(_) => {}
             EventMsg::CollabCloseEnd(ev) => self.on_collab_event(collab::close_end(ev)),
             EventMsg::ThreadRolledBack(_) => {}
+            EventMsg::ApprovalGrantsRestored(ev) => {
+                self.add_to_history(history_cell::new_restored_approvals_event(
+                    &ev.grants,
+                    ev.discarded,
+                ));
+                self.request_redraw();
+            }
+            EventMsg::SessionApprovalsRevoked(ev) => {
+                let message = match ev.revoked {
+                    0 => "No session approvals to forget".to_string(),
+                    1 => "Forgot 1 session approval".to_string(),
+                    count => format!("Forgot {count} session approvals"),
+                };
+                self.add_info_message(
+                    message,
+                    Some("Codex will ask again before running them.".to_string()),
+                );
+            }
+            EventMsg::InjectionSuspected(ev) => self.on_injection_suspected(ev),
+            EventMsg::OutputTruncated(ev) => self.on_output_truncated(ev),
             EventMsg::RawResponseItem(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::AgentMessageContentDelta(_)
             | EventMsg::ReasoningContentDelta(_)
             | EventMsg::ReasoningRawContentDelta(_)
-            | EventMsg::DynamicToolCallRequest(_) => {}
+            | EventMsg::DynamicToolCallRequest(_)
+            | EventMsg::TurnFiles(_)
+            | EventMsg::ToolArgumentsRepaired(_) => {}
             EventMsg::ItemCompleted(event) => {
                 if let codex_protocol::items::TurnItem::Plan(plan_item) = event.item {
                     self.on_plan_item_completed(plan_item.text);
@@ -3529,6 +4758,17 @@ impl ChatWidget {
         self.needs_final_message_separator = false;
     }
 
+    fn on_turn_refused(&mut self, event: TurnRefusedEvent) {
+        self.flush_answer_stream_with_separator();
+        self.add_to_history(history_cell::new_refusal_event(event.reason));
+        self.request_redraw();
+    }
+
+    fn on_steer_delivered(&mut self, event: SteerDeliveredEvent) {
+        self.add_to_history(history_cell::new_steer_message(event.text, event.deferred));
+        self.needs_final_message_separator = false;
+    }
+
     /// Exit the UI immediately without waiting for shutdown.
     ///
     /// Prefer [`Self::request_quit_without_confirmation`] for user-initiated exits;
@@ -3570,6 +4810,29 @@ impl ChatWidget {
         }
     }
 
+    /// Close out an exec or MCP call whose end event never arrived before the turn completed.
+    fn finalize_orphaned_active_cell(&mut self) {
+        let Some(cell) = self.active_cell.as_mut() else {
+            return;
+        };
+        // Unified exec processes legitimately outlive the turn that started them.
+        if let Some(exec) = cell.as_any_mut().downcast_mut::<ExecCell>()
+            && exec.is_active()
+            && !exec
+                .iter_calls()
+                .any(|call| call.output.is_none() && is_unified_exec_source(call.source))
+        {
+            exec.mark_orphaned();
+        } else if let Some(tool) = cell.as_any_mut().downcast_mut::<McpToolCallCell>()
+            && tool.is_active()
+        {
+            tool.mark_orphaned();
+        } else {
+            return;
+        }
+        self.flush_active_cell();
+    }
+
     /// Mark the active cell as failed (✗) and flush it into history.
     fn finalize_active_cell_as_failed(&mut self) {
         if let Some(mut cell) = self.active_cell.take() {
@@ -3914,6 +5177,127 @@ impl ChatWidget {
         });
     }
 
+    fn open_project_memory_popup(&mut self) {
+        if !self.config.features.enabled(Feature::ProjectMemory) {
+            self.add_info_message(
+                "Project memory is disabled.".to_string(),
+                Some(
+                    "Set `project_memory = true` under [features] in config.toml to use /memory."
+                        .to_string(),
+                ),
+            );
+            return;
+        }
+
+        let path = project_memory_path(&self.config.cwd);
+        let notes = match read_notes(&path) {
+            Ok(notes) => notes,
+            Err(err) => {
+                self.add_error_message(format!("Failed to read {}: {err}", path.display()));
+                return;
+            }
+        };
+        if notes.is_empty() {
+            self.add_info_message(
+                "Project memory is empty.".to_string(),
+                Some(format!(
+                    "Notes Codex saves with the remember tool are stored in {}.",
+                    path.display()
+                )),
+            );
+            return;
+        }
+
+        let items: Vec<SelectionItem> = notes
+            .into_iter()
+            .enumerate()
+            .map(|(index, note)| {
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::RemoveProjectMemoryNote { index });
+                })];
+                SelectionItem {
+                    name: note,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Project Memory".bold()));
+        header.push(Line::from(
+            format!("Select a note to remove it from {}.", path.display()).dim(),
+        ));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn remove_project_memory_note(&mut self, index: usize) {
+        let path = project_memory_path(&self.config.cwd);
+        match remove_note(&path, index) {
+            Ok(note) => self.add_info_message(
+                format!("Removed from project memory: {note}"),
+                Some("New sessions will no longer see this note.".to_string()),
+            ),
+            Err(err) => {
+                self.add_error_message(format!("Failed to update {}: {err}", path.display()));
+            }
+        }
+    }
+
+    /// Offers to save the large paste behind `placeholder` to a workspace file
+    /// (`tui.paste_to_file = "ask"`).
+    pub(crate) fn open_paste_to_file_popup(&mut self, placeholder: String) {
+        let items = vec![
+            SelectionItem {
+                name: "Attach as a file".to_string(),
+                description: Some(format!(
+                    "Save it under {PASTES_DIR}/ and send a reference Codex can read"
+                )),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::ConvertPasteToFile {
+                        placeholder: placeholder.clone(),
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Keep inline".to_string(),
+                description: Some("Send the pasted text with the message".to_string()),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Large paste".bold()));
+        header.push(Line::from(
+            "Attaching it keeps the message short; the model reads only what it needs.".dim(),
+        ));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn convert_paste_to_file(&mut self, placeholder: &str) {
+        self.bottom_pane.convert_paste_to_file(placeholder);
+    }
+
+    pub(crate) fn dismiss_timed_out_approval(&mut self, id: &str) {
+        self.bottom_pane.dismiss_timed_out_approval(id);
+    }
+
     fn model_menu_header(&self, title: &str, subtitle: &str) -> Box<dyn Renderable> {
         let title = title.to_string();
         let subtitle = subtitle.to_string();
@@ -4346,6 +5730,125 @@ impl ChatWidget {
         );
     }
 
+    /// Efforts the current model accepts, or `None` when the model has no
+    /// preset and the backend has the final say.
+    fn supported_reasoning_efforts(&self) -> Option<Vec<ReasoningEffortConfig>> {
+        let model = self.current_model();
+        let models = self.models_manager.try_list_models(&self.config).ok()?;
+        let preset = models.into_iter().find(|preset| preset.model == model)?;
+        Some(
+            preset
+                .supported_reasoning_efforts
+                .into_iter()
+                .map(|preset| preset.effort)
+                .collect(),
+        )
+    }
+
+    fn reject_reasoning_change_if_unsupported(&mut self) -> bool {
+        let unsupported = self
+            .supported_reasoning_efforts()
+            .is_some_and(|efforts| efforts.is_empty());
+        if unsupported {
+            self.add_error_message(format!(
+                "{} does not support reasoning; the reasoning effort was not changed.",
+                self.current_model()
+            ));
+        }
+        unsupported
+    }
+
+    /// Step to the next effort in `REASONING_EFFORT_CYCLE` that the current
+    /// model supports. Bound to `tui.reasoning_cycle_key` and bare `/reasoning`.
+    fn cycle_reasoning_effort(&mut self) {
+        if self.reject_reasoning_change_if_unsupported() {
+            return;
+        }
+        let supported = self.supported_reasoning_efforts();
+        let choices: Vec<ReasoningEffortConfig> = REASONING_EFFORT_CYCLE
+            .into_iter()
+            .filter(|effort| {
+                *effort == ReasoningEffortConfig::None
+                    || supported
+                        .as_ref()
+                        .is_none_or(|supported| supported.contains(effort))
+            })
+            .collect();
+        let next = self
+            .effective_reasoning_effort()
+            .and_then(|current| choices.iter().position(|effort| *effort == current))
+            .map_or(choices[0], |idx| choices[(idx + 1) % choices.len()]);
+        self.apply_session_reasoning_effort(next);
+    }
+
+    /// Handle `/reasoning <effort>`.
+    fn set_reasoning_effort_from_command(&mut self, arg: &str) {
+        let Some(effort) = parse_reasoning_effort(arg) else {
+            self.add_error_message(format!(
+                "Unknown reasoning effort `{arg}`. Usage: /reasoning low|medium|high|none"
+            ));
+            return;
+        };
+        if self.reject_reasoning_change_if_unsupported() {
+            return;
+        }
+        let unsupported_effort = effort != ReasoningEffortConfig::None
+            && self
+                .supported_reasoning_efforts()
+                .is_some_and(|supported| !supported.contains(&effort));
+        if unsupported_effort {
+            self.add_error_message(format!(
+                "{} does not support {effort} reasoning.",
+                self.current_model()
+            ));
+            return;
+        }
+        self.apply_session_reasoning_effort(effort);
+    }
+
+    /// Switches the language Codex answers in from the next turn on; `off`
+    /// clears it. Like `/reasoning`, the change is not written to config.
+    fn set_preferred_language(&mut self, arg: &str) {
+        let language = match arg.to_ascii_lowercase().as_str() {
+            "off" | "none" | "default" => None,
+            _ => Some(arg.to_string()),
+        };
+        let message = match &language {
+            Some(language) => format!("Codex will answer in {language} from the next turn."),
+            None => "Preferred language cleared from the next turn.".to_string(),
+        };
+        self.config.preferred_language = language.clone();
+        self.submit_op(Op::SetPreferredLanguage { language });
+        self.add_info_message(message, None);
+    }
+
+    /// Use `effort` from the next turn on without persisting it to config.
+    fn apply_session_reasoning_effort(&mut self, effort: ReasoningEffortConfig) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::OverrideTurnContext {
+                cwd: None,
+                approval_policy: None,
+                sandbox_policy: None,
+                windows_sandbox_level: None,
+                model: None,
+                effort: Some(Some(effort)),
+                summary: None,
+                collaboration_mode: None,
+                personality: None,
+            }));
+        self.app_event_tx
+            .send(AppEvent::UpdateReasoningEffort(Some(effort)));
+        self.bottom_pane
+            .set_reasoning_effort_indicator(Some(effort));
+        self.add_info_message(
+            format!(
+                "Reasoning effort set to {} for the next turn.",
+                Self::reasoning_effort_label(effort).to_lowercase()
+            ),
+            None,
+        );
+    }
+
     /// Open a popup to choose the approvals mode (ask for approval policy + sandbox policy).
     pub(crate) fn open_approvals_popup(&mut self) {
         self.open_approval_mode_popup(true);
@@ -4502,6 +6005,36 @@ impl ChatWidget {
         self.bottom_pane.show_view(Box::new(view));
     }
 
+    pub(crate) fn open_setup_wizard(&mut self) {
+        if !self.is_session_configured() {
+            self.add_info_message(
+                "Setup is disabled until startup completes.".to_string(),
+                None,
+            );
+            return;
+        }
+        let models: Vec<ModelPreset> = match self.models_manager.try_list_models(&self.config) {
+            Ok(models) => models,
+            Err(_) => {
+                self.add_info_message(
+                    "Models are being updated; please try /setup again in a moment.".to_string(),
+                    None,
+                );
+                return;
+            }
+        };
+        let current_model = self.current_model().to_string();
+        let initial = SetupChoices {
+            model: current_model.clone(),
+            ..SetupChoices::from_config(&self.config, &current_model)
+        };
+        let view = SetupWizardView::new(
+            SetupWizard::new(models, &initial),
+            self.app_event_tx.clone(),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
     fn approval_preset_actions(
         approval: AskForApproval,
         sandbox: SandboxPolicy,
@@ -5072,6 +6605,11 @@ impl ChatWidget {
         }
     }
 
+    /// Set desktop notification preferences in the widget's config copy.
+    pub(crate) fn set_tui_notifications(&mut self, notifications: Notifications) {
+        self.config.tui_notifications = notifications;
+    }
+
     /// Set the sandbox policy in the widget's config copy.
     pub(crate) fn set_sandbox_policy(&mut self, policy: SandboxPolicy) -> ConstraintResult<()> {
         #[cfg(target_os = "windows")]
@@ -5178,6 +6716,8 @@ impl ChatWidget {
         {
             mask.model = Some(model.to_string());
         }
+        // Efforts are model-specific; drop the indicator for the old model.
+        self.bottom_pane.set_reasoning_effort_indicator(None);
         self.refresh_model_display();
     }
 
@@ -5778,9 +7318,93 @@ impl ChatWidget {
         ));
     }
 
+    /// Shows the tools from `/tools`, built-in first, then MCP tools by
+    /// server, then dynamic tools. Selecting one prints its definition.
+    fn on_list_tools(&mut self, ev: ListToolsResponseEvent) {
+        if ev.tools.is_empty() {
+            self.add_info_message("No tools are available to the model.".to_string(), None);
+            return;
+        }
+
+        let mut tools = ev.tools;
+        tools.sort_by_cached_key(|tool| match &tool.source {
+            ToolSource::BuiltIn => (0, String::new()),
+            ToolSource::Mcp { server } => (1, server.clone()),
+            ToolSource::Dynamic => (2, String::new()),
+        });
+        let total = tools.len();
+        let items: Vec<SelectionItem> = tools
+            .into_iter()
+            .map(|tool| {
+                let source_label = match &tool.source {
+                    ToolSource::BuiltIn => "built-in".to_string(),
+                    ToolSource::Mcp { server } => format!("MCP: {server}"),
+                    ToolSource::Dynamic => "dynamic".to_string(),
+                };
+                let summary = tool
+                    .spec
+                    .get("description")
+                    .and_then(serde_json::Value::as_str)
+                    .and_then(|description| description.lines().next())
+                    .unwrap_or_default();
+                let description = if summary.is_empty() {
+                    source_label.clone()
+                } else {
+                    format!("{source_label} · {summary}")
+                };
+                let name = tool.name.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        history_cell::new_tool_spec_output(&tool.name, &source_label, &tool.spec),
+                    )));
+                })];
+                SelectionItem {
+                    search_value: Some(name.clone()),
+                    name,
+                    description: Some(description),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from(format!("Tools ({total})").bold()));
+        header.push(Line::from(
+            "Every tool the model can call on the next turn. Select one to show its schema.".dim(),
+        ));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search tools".to_string()),
+            ..Default::default()
+        });
+    }
+
     fn on_list_custom_prompts(&mut self, ev: ListCustomPromptsResponseEvent) {
         let len = ev.custom_prompts.len();
         debug!("received {len} custom prompts");
+        let shadowed: Vec<String> = ev
+            .custom_prompts
+            .iter()
+            .filter(|prompt| SlashCommand::from_str(&prompt.name).is_ok())
+            .map(|prompt| format!("/{}", prompt.name))
+            .collect();
+        if !shadowed.is_empty() {
+            self.add_info_message(
+                format!(
+                    "Custom prompts {} share a name with built-in commands; the built-in runs.",
+                    shadowed.join(", ")
+                ),
+                Some(format!(
+                    "Use /{PROMPTS_CMD_PREFIX}:<name> to run the prompt instead."
+                )),
+            );
+        }
         // Forward to bottom pane so the slash popup can show them now.
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
     }
@@ -6018,6 +7642,7 @@ impl ChatWidget {
 
     pub(crate) fn clear_token_usage(&mut self) {
         self.token_info = None;
+        self.context_estimate = ContextEstimate::default();
     }
 
     fn as_renderable(&self) -> RenderableItem<'_> {
@@ -6136,36 +7761,6 @@ const PLACEHOLDERS: [&str; 8] = [
     "Use /skills to list available skills",
 ];
 
-// Extract the first bold (Markdown) element in the form **...** from `s`.
-// Returns the inner text if found; otherwise `None`.
-fn extract_first_bold(s: &str) -> Option<String> {
-    let bytes = s.as_bytes();
-    let mut i = 0usize;
-    while i + 1 < bytes.len() {
-        if bytes[i] == b'*' && bytes[i + 1] == b'*' {
-            let start = i + 2;
-            let mut j = start;
-            while j + 1 < bytes.len() {
-                if bytes[j] == b'*' && bytes[j + 1] == b'*' {
-                    // Found closing **
-                    let inner = &s[start..j];
-                    let trimmed = inner.trim();
-                    if !trimmed.is_empty() {
-                        return Some(trimmed.to_string());
-                    } else {
-                        return None;
-                    }
-                }
-                j += 1;
-            }
-            // No closing; stop searching (wait for more deltas)
-            return None;
-        }
-        i += 1;
-    }
-    None
-}
-
 async fn fetch_rate_limits(base_url: String, auth: CodexAuth) -> Option<RateLimitSnapshot> {
     match BackendClient::from_auth(base_url, &auth) {
         Ok(client) => match client.get_rate_limits().await {
@@ -29,8 +29,15 @@ use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningDeltaEvent;
 use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ApprovalKind;
+use codex_core::protocol::ApprovalResolvedEvent;
+use codex_core::protocol::ApprovalResolver;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CommitCompletedEvent;
+use codex_core::protocol::CommitDraftEvent;
+use codex_core::protocol::ConnectivityRestoredEvent;
 use codex_core::protocol::CreditsSnapshot;
+use codex_core::protocol::DegradedComponent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
@@ -40,25 +47,47 @@ use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::FileChange;
+use codex_core::protocol::GetHistoryEntryResponseEvent;
+use codex_core::protocol::HistoryClearedEvent;
+use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpServerStartup;
 use codex_core::protocol::McpStartupCompleteEvent;
 use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
+use codex_core::protocol::ModelListSource;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::PatchFileResult;
+use codex_core::protocol::PatchFileStatus;
+use codex_core::protocol::PrewarmPhase;
+use codex_core::protocol::PrewarmPhaseTiming;
+use codex_core::protocol::ProjectDocFile;
+use codex_core::protocol::PromptQueuedOfflineEvent;
 use codex_core::protocol::RateLimitWindow;
+use codex_core::protocol::RequestErrorDetails;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SessionApprovalsRevokedEvent;
+use codex_core::protocol::SessionPrewarmedEvent;
 use codex_core::protocol::SessionSource;
+use codex_core::protocol::StartupAuth;
+use codex_core::protocol::StartupAuthMode;
+use codex_core::protocol::StartupDiagnostics;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenCountEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
+use codex_core::protocol::ToolDescription;
+use codex_core::protocol::ToolToggles;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnStartedEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
+use codex_core::protocol::UnknownModel;
 use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
 use codex_otel::OtelManager;
@@ -68,6 +97,8 @@ use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::Settings;
+use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::message_history::HistoryLogMetadata;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ReasoningEffortPreset;
 use codex_protocol::parse_command::ParsedCommand;
@@ -93,6 +124,12 @@ use tokio::sync::mpsc::error::TryRecvError;
 use tokio::sync::mpsc::unbounded_channel;
 use toml::Value as TomlValue;
 
+mod harness;
+
+use harness::ScriptedSession;
+use harness::Step;
+use harness::fixtures;
+
 async fn test_config() -> Config {
     // Use base defaults to avoid depending on host state.
     let codex_home = std::env::temp_dir();
@@ -155,6 +192,11 @@ async fn resumed_initial_messages_render_history() {
             }),
         ]),
         rollout_path: Some(rollout_file.path().to_path_buf()),
+        tool_toggles: ToolToggles::default(),
+        base_instructions_tokens: None,
+        unknown_model: None,
+        sampling: None,
+        startup: None,
     };
 
     chat.handle_codex_event(Event {
@@ -184,6 +226,142 @@ async fn resumed_initial_messages_render_history() {
     );
 }
 
+#[tokio::test]
+async fn session_info_cell_reports_unknown_model_with_suggestions() {
+    let (mut chat, mut rx, _ops) = make_chatwidget_manual(None).await;
+
+    let configured = codex_core::protocol::SessionConfiguredEvent {
+        session_id: ThreadId::new(),
+        forked_from_id: None,
+        thread_name: None,
+        model: "gpt5-codex".to_string(),
+        model_provider_id: "openai".to_string(),
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::ReadOnly,
+        cwd: PathBuf::from("/home/user/project"),
+        reasoning_effort: None,
+        history_log_id: 0,
+        history_entry_count: 0,
+        initial_messages: None,
+        rollout_path: None,
+        tool_toggles: ToolToggles::default(),
+        base_instructions_tokens: None,
+        unknown_model: Some(UnknownModel {
+            model: "gpt5-codex".to_string(),
+            provider: "OpenAI".to_string(),
+            source: ModelListSource::Provider,
+            suggestions: vec!["gpt-5-codex".to_string(), "gpt-5.1-codex".to_string()],
+        }),
+        sampling: None,
+        startup: None,
+    };
+    chat.handle_codex_event(Event {
+        id: "initial".into(),
+        msg: EventMsg::SessionConfigured(configured),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(
+        cells.len(),
+        1,
+        "the warning belongs to the session info cell"
+    );
+    let text = lines_to_single_string(&cells[0]);
+    assert!(
+        text.contains(
+            "Model `gpt5-codex` is not available from OpenAI. Did you mean `gpt-5-codex`, `gpt-5.1-codex`?"
+        ),
+        "{text}"
+    );
+}
+
+#[tokio::test]
+async fn session_info_cell_summarizes_startup_and_fills_in_mcp_servers() {
+    let (mut chat, mut rx, _ops) = make_chatwidget_manual(None).await;
+
+    let configured = codex_core::protocol::SessionConfiguredEvent {
+        session_id: ThreadId::new(),
+        forked_from_id: None,
+        thread_name: None,
+        model: "gpt-5-codex".to_string(),
+        model_provider_id: "openai".to_string(),
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::ReadOnly,
+        cwd: PathBuf::from("/home/user/project"),
+        reasoning_effort: None,
+        history_log_id: 0,
+        history_entry_count: 0,
+        initial_messages: None,
+        rollout_path: None,
+        tool_toggles: ToolToggles::default(),
+        base_instructions_tokens: None,
+        unknown_model: None,
+        sampling: None,
+        startup: Some(StartupDiagnostics {
+            phases: vec![
+                PrewarmPhaseTiming {
+                    phase: PrewarmPhase::Config,
+                    duration_ms: 12,
+                },
+                PrewarmPhaseTiming {
+                    phase: PrewarmPhase::ModelCheck,
+                    duration_ms: 340,
+                },
+            ],
+            auth: StartupAuth {
+                mode: StartupAuthMode::Chatgpt,
+                token_age_secs: Some(3 * 3_600),
+                refresh_due: false,
+            },
+            provider_probe: None,
+            project_docs: vec![ProjectDocFile {
+                path: PathBuf::from("/home/user/project/AGENTS.md"),
+                bytes: 2_048,
+            }],
+            mcp_servers: vec![McpServerStartup::pending("docs".to_string())],
+        }),
+    };
+    chat.handle_codex_event(Event {
+        id: "initial".into(),
+        msg: EventMsg::SessionConfigured(configured),
+    });
+    let mut cells = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::InsertHistoryCell(cell) = ev {
+            cells.push(cell);
+        }
+    }
+    assert_eq!(cells.len(), 1);
+    let summary = lines_to_single_string(&cells[0].display_lines(200));
+    assert!(
+        summary.contains(
+            "Startup: ChatGPT, token refreshed 3 h ago · 1 AGENTS.md · 1 MCP server · slowest: model check 340 ms"
+        ),
+        "{summary}"
+    );
+    assert!(!summary.contains("AGENTS.md (2.0 KiB)"));
+
+    chat.handle_codex_event(Event {
+        id: "initial".into(),
+        msg: EventMsg::SessionPrewarmed(SessionPrewarmedEvent {
+            phases: Vec::new(),
+            degraded: Vec::new(),
+            mcp_servers: vec![McpServerStartup {
+                connect_ms: Some(40),
+                tools: Some(3),
+                ..McpServerStartup::pending("docs".to_string())
+            }],
+        }),
+    });
+
+    let details = lines_to_single_string(&cells[0].transcript_lines(200));
+    assert!(
+        details.contains("/home/user/project/AGENTS.md (2.0 KiB)"),
+        "{details}"
+    );
+    assert!(details.contains("docs: 40 ms, 3 tools"), "{details}");
+}
+
 #[tokio::test]
 async fn replayed_user_message_preserves_text_elements_and_local_images() {
     let (mut chat, mut rx, _ops) = make_chatwidget_manual(None).await;
@@ -217,6 +395,11 @@ async fn replayed_user_message_preserves_text_elements_and_local_images() {
             local_images: local_images.clone(),
         })]),
         rollout_path: Some(rollout_file.path().to_path_buf()),
+        tool_toggles: ToolToggles::default(),
+        base_instructions_tokens: None,
+        unknown_model: None,
+        sampling: None,
+        startup: None,
     };
 
     chat.handle_codex_event(Event {
@@ -265,6 +448,11 @@ async fn submission_preserves_text_elements_and_local_images() {
         history_entry_count: 0,
         initial_messages: None,
         rollout_path: Some(rollout_file.path().to_path_buf()),
+        tool_toggles: ToolToggles::default(),
+        base_instructions_tokens: None,
+        unknown_model: None,
+        sampling: None,
+        startup: None,
     };
     chat.handle_codex_event(Event {
         id: "initial".into(),
@@ -288,19 +476,21 @@ async fn submission_preserves_text_elements_and_local_images() {
         Op::UserTurn { items, .. } => items,
         other => panic!("expected Op::UserTurn, got {other:?}"),
     };
-    assert_eq!(items.len(), 2);
-    assert_eq!(
-        items[0],
-        UserInput::LocalImage {
-            path: local_images[0].clone()
-        }
-    );
     assert_eq!(
-        items[1],
-        UserInput::Text {
-            text: text.clone(),
-            text_elements: text_elements.clone(),
-        }
+        items,
+        vec![
+            UserInput::Text {
+                text: placeholder.to_string(),
+                text_elements: text_elements.clone(),
+            },
+            UserInput::LocalImage {
+                path: local_images[0].clone()
+            },
+            UserInput::Text {
+                text: " submit".to_string(),
+                text_elements: Vec::new(),
+            },
+        ]
     );
 
     let mut user_cell = None;
@@ -324,6 +514,63 @@ async fn submission_preserves_text_elements_and_local_images() {
     assert_eq!(stored_images, local_images);
 }
 
+#[test]
+fn images_are_sent_where_their_placeholders_appear() {
+    let text = "before [Image #2] and after [Image #1]".to_string();
+    let element = |placeholder: &str| {
+        let start = text.find(placeholder).unwrap_or_default();
+        TextElement::new(
+            (start..start + placeholder.len()).into(),
+            Some(placeholder.to_string()),
+        )
+    };
+    let message = UserMessage {
+        text_elements: vec![element("[Image #2]"), element("[Image #1]")],
+        local_images: vec![
+            LocalImageAttachment {
+                placeholder: "[Image #1]".to_string(),
+                path: PathBuf::from("/tmp/after.png"),
+            },
+            LocalImageAttachment {
+                placeholder: "[Image #2]".to_string(),
+                path: PathBuf::from("/tmp/before.png"),
+            },
+        ],
+        text: text.clone(),
+        mention_paths: HashMap::new(),
+    };
+
+    let message = order_images_by_placeholder(message);
+    let items = user_input_items(&message.text, &message.text_elements, &message.local_images);
+
+    assert_eq!(message.text, "before [Image #1] and after [Image #2]");
+    assert_eq!(
+        items,
+        vec![
+            UserInput::Text {
+                text: "before [Image #1]".to_string(),
+                text_elements: vec![TextElement::new(
+                    (7..17).into(),
+                    Some("[Image #1]".to_string()),
+                )],
+            },
+            UserInput::LocalImage {
+                path: PathBuf::from("/tmp/before.png"),
+            },
+            UserInput::Text {
+                text: " and after [Image #2]".to_string(),
+                text_elements: vec![TextElement::new(
+                    (11..21).into(),
+                    Some("[Image #2]".to_string()),
+                )],
+            },
+            UserInput::LocalImage {
+                path: PathBuf::from("/tmp/after.png"),
+            },
+        ]
+    );
+}
+
 #[tokio::test]
 async fn interrupted_turn_restores_queued_messages_with_images_and_elements() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -381,6 +628,7 @@ async fn interrupted_turn_restores_queued_messages_with_images_and_elements() {
         id: "interrupt".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            cancelled: Vec::new(),
         }),
     });
 
@@ -745,6 +993,134 @@ fn test_otel_manager(config: &Config, model: &str) -> OtelManager {
 }
 
 // --- Helpers for tests that need direct construction and event draining ---
+/// Routes for the key matrix in `key_routing_matrix`, for a view with no
+/// modal or popup open and for one with the approval modal open.
+fn expected_key_routes(chat: &ChatWidget) -> Vec<(KeyEvent, KeyRoute, KeyRoute)> {
+    let change_mode = if chat.collaboration_modes_enabled() {
+        KeyRoute::CycleCollaborationMode
+    } else {
+        KeyRoute::BottomPane
+    };
+    let mut release = KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE);
+    release.kind = KeyEventKind::Release;
+    vec![
+        (
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            KeyRoute::Interrupt,
+            KeyRoute::Interrupt,
+        ),
+        (
+            KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            KeyRoute::Quit,
+            KeyRoute::Quit,
+        ),
+        (
+            KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL),
+            KeyRoute::PasteImage,
+            KeyRoute::PasteImage,
+        ),
+        (
+            KeyEvent::new(KeyCode::Char('v'), KeyModifiers::ALT),
+            KeyRoute::PasteImage,
+            KeyRoute::PasteImage,
+        ),
+        (
+            KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+            change_mode,
+            KeyRoute::BottomPane,
+        ),
+        (
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+            KeyRoute::CycleReasoningEffort,
+            KeyRoute::BottomPane,
+        ),
+        (
+            KeyEvent::new(KeyCode::Up, KeyModifiers::ALT),
+            KeyRoute::RestoreQueuedMessage,
+            KeyRoute::RestoreQueuedMessage,
+        ),
+        (
+            KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE),
+            KeyRoute::Transcript,
+            KeyRoute::BottomPane,
+        ),
+        (
+            KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE),
+            KeyRoute::Transcript,
+            KeyRoute::BottomPane,
+        ),
+        (
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            KeyRoute::BottomPane,
+            KeyRoute::BottomPane,
+        ),
+        (
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+            KeyRoute::BottomPane,
+            KeyRoute::BottomPane,
+        ),
+        (
+            KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE),
+            KeyRoute::BottomPane,
+            KeyRoute::BottomPane,
+        ),
+        (
+            KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
+            KeyRoute::BottomPane,
+            KeyRoute::BottomPane,
+        ),
+        (release, KeyRoute::BottomPane, KeyRoute::BottomPane),
+    ]
+}
+
+#[tokio::test]
+async fn key_routing_matrix() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.queued_user_messages
+        .push_back(UserMessage::from("queued follow-up"));
+    let expected = expected_key_routes(&chat);
+
+    for (key, idle, _) in &expected {
+        assert_eq!(chat.route_key(*key), *idle, "empty composer: {key:?}");
+    }
+
+    chat.bottom_pane
+        .set_composer_text("draft".to_string(), Vec::new(), Vec::new());
+    for (key, idle, _) in &expected {
+        assert_eq!(chat.route_key(*key), *idle, "composer with text: {key:?}");
+    }
+
+    chat.handle_codex_event(Event {
+        id: "sub-route".into(),
+        msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+            call_id: "call-route".into(),
+            turn_id: "turn-route".into(),
+            command: vec!["echo".into(), "hi".into()],
+            cwd: PathBuf::from("/tmp"),
+            reason: None,
+            proposed_execpolicy_amendment: None,
+            parsed_cmd: vec![],
+            cwd_outside_workspace: false,
+            cwd_writable: true,
+        }),
+    });
+    for (key, _, modal) in &expected {
+        assert_eq!(chat.route_key(*key), *modal, "approval modal open: {key:?}");
+    }
+}
+
+#[tokio::test]
+async fn page_up_opens_transcript_without_touching_composer() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.bottom_pane
+        .set_composer_text("draft".to_string(), Vec::new(), Vec::new());
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE));
+
+    assert_matches!(rx.try_recv(), Ok(AppEvent::OpenTranscript));
+    assert_eq!(chat.bottom_pane.composer_text(), "draft");
+}
+
 async fn make_chatwidget_manual(
     model_override: Option<&str>,
 ) -> (
@@ -788,6 +1164,7 @@ async fn make_chatwidget_manual(
         },
     };
     let current_collaboration_mode = base_mode;
+    let reasoning_cycle_key = reasoning_cycle_key(&cfg);
     let mut widget = ChatWidget {
         app_event_tx,
         codex_op_tx: op_tx,
@@ -812,6 +1189,7 @@ async fn make_chatwidget_manual(
         plan_stream_controller: None,
         running_commands: HashMap::new(),
         suppressed_exec_calls: HashSet::new(),
+        rendered_patch_calls: HashSet::new(),
         skills_all: Vec::new(),
         skills_initial_state: None,
         last_unified_wait: None,
@@ -824,6 +1202,7 @@ async fn make_chatwidget_manual(
         interrupts: InterruptManager::new(),
         reasoning_buffer: String::new(),
         full_reasoning_buffer: String::new(),
+        reasoning_headlines: ReasoningHeadlines::default(),
         current_status_header: String::from("Working"),
         retry_status_header: None,
         thread_id: None,
@@ -832,12 +1211,16 @@ async fn make_chatwidget_manual(
         frame_requester: FrameRequester::test_dummy(),
         show_welcome_banner: true,
         queued_user_messages: VecDeque::new(),
+        pending_attachments: Vec::new(),
         suppress_session_configured_redraw: false,
         pending_notification: None,
         quit_shortcut_expires_at: None,
         quit_shortcut_key: None,
+        reasoning_cycle_key,
         is_review_mode: false,
         pre_review_token_info: None,
+        context_estimate: ContextEstimate::default(),
+        pending_oversized_message: None,
         needs_final_message_separator: false,
         had_work_activity: false,
         saw_plan_update_this_turn: false,
@@ -849,6 +1232,9 @@ async fn make_chatwidget_manual(
         feedback: codex_feedback::CodexFeedback::new(),
         feedback_audience: FeedbackAudience::External,
         current_rollout_path: None,
+        startup_diagnostics: None,
+        history_files: HistoryFilesArgs::default(),
+        ghost_snapshots: Vec::new(),
         external_editor_state: ExternalEditorState::Closed,
     };
     widget.set_model(&resolved_model);
@@ -940,6 +1326,113 @@ fn make_token_info(total_tokens: i64, context_window: i64) -> TokenUsageInfo {
     }
 }
 
+#[tokio::test]
+async fn context_meter_estimates_submitted_input_until_next_token_count() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.set_token_info(Some(make_token_info(50_000, 100_000)));
+    assert_eq!(
+        (
+            chat.bottom_pane.context_window_percent(),
+            chat.bottom_pane.context_window_approximate(),
+        ),
+        (Some(57), false)
+    );
+
+    chat.submit_user_message(UserMessage::from("x".repeat(4_000)));
+    let _ = next_submit_op(&mut op_rx);
+    assert_eq!(
+        (
+            chat.bottom_pane.context_window_percent(),
+            chat.bottom_pane.context_window_approximate(),
+        ),
+        (Some(56), true)
+    );
+
+    chat.set_token_info(Some(make_token_info(52_000, 100_000)));
+    assert_eq!(
+        (
+            chat.bottom_pane.context_window_percent(),
+            chat.bottom_pane.context_window_approximate(),
+        ),
+        (Some(55), false)
+    );
+}
+
+#[tokio::test]
+async fn oversized_message_is_held_and_can_be_sent_as_excerpt() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.set_token_info(Some(make_token_info(90_000, 100_000)));
+    let pasted: String = (0..4_000)
+        .map(|idx| format!("log line {idx:05}\n"))
+        .collect();
+
+    chat.submit_user_message(UserMessage::from(pasted.clone()));
+
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+    assert_eq!(chat.bottom_pane.composer_text(), pasted);
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("Message may not fit in the remaining context"),
+        "popup: {popup}"
+    );
+    assert!(popup.contains("Both are estimates."), "popup: {popup}");
+
+    chat.resolve_oversized_message(OversizedMessageAction::SendExcerpt);
+
+    let Op::UserTurn { items, .. } = next_submit_op(&mut op_rx) else {
+        panic!("expected Op::UserTurn");
+    };
+    let [UserInput::Text { text, .. }] = items.as_slice() else {
+        panic!("expected a single text item, got {items:?}");
+    };
+    assert!(text.starts_with("log line 00000\n"));
+    assert!(text.ends_with("log line 03999\n"));
+    assert!(text.contains("lines omitted"));
+    assert!(text.len() <= 20_000 + 64, "excerpt is {} bytes", text.len());
+    assert_eq!(chat.bottom_pane.composer_text(), "");
+}
+
+#[tokio::test]
+async fn oversized_message_file_reference_is_saved_with_workspace_pastes() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+    let cwd = tempdir().expect("tempdir");
+    chat.config.cwd = cwd.path().to_path_buf();
+    chat.thread_id = Some(ThreadId::new());
+    chat.set_token_info(Some(make_token_info(90_000, 100_000)));
+    let pasted: String = (0..4_000)
+        .map(|idx| format!("log line {idx:05}\n"))
+        .collect();
+
+    chat.submit_user_message(UserMessage::from(pasted.clone()));
+    chat.resolve_oversized_message(OversizedMessageAction::SendFileReference);
+
+    let Op::UserTurn { items, .. } = next_submit_op(&mut op_rx) else {
+        panic!("expected Op::UserTurn");
+    };
+    let [UserInput::Text { text, .. }] = items.as_slice() else {
+        panic!("expected a single text item, got {items:?}");
+    };
+    let saved: Vec<_> = std::fs::read_dir(cwd.path().join(crate::paste_files::PASTES_DIR))
+        .expect("pastes dir")
+        .map(|entry| entry.expect("dir entry").path())
+        .collect();
+    let [path] = saved.as_slice() else {
+        panic!("expected one saved paste, got {saved:?}");
+    };
+    assert_eq!(std::fs::read_to_string(path).expect("read paste"), pasted);
+    let file_name = path.file_name().expect("file name").to_string_lossy();
+    assert!(
+        text.contains(&format!(".codex/pastes/{file_name}")),
+        "text: {text}"
+    );
+    assert!(
+        text.contains("It starts:\n\nlog line 00000\n"),
+        "text: {text}"
+    );
+}
+
 #[tokio::test]
 async fn rate_limit_warnings_emit_thresholds() {
     let mut state = RateLimitWarningState::default();
@@ -1251,6 +1744,7 @@ async fn plan_implementation_popup_skips_replayed_turn_complete() {
 
     chat.replay_initial_messages(vec![EventMsg::TurnComplete(TurnCompleteEvent {
         last_agent_message: Some("Plan details".to_string()),
+        system_fingerprint: None,
     })]);
 
     let popup = render_bottom_popup(&chat, 80);
@@ -1260,6 +1754,39 @@ async fn plan_implementation_popup_skips_replayed_turn_complete() {
     );
 }
 
+#[tokio::test]
+async fn replayed_approval_resolution_is_a_read_only_cell() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let resolved = ApprovalResolvedEvent {
+        call_id: "call-1".to_string(),
+        turn_id: "turn-1".to_string(),
+        kind: ApprovalKind::Exec,
+        command: vec!["cargo".to_string(), "publish".to_string()],
+        paths: Vec::new(),
+        cwd: PathBuf::from("/work"),
+        reason: None,
+        decision: ReviewDecision::Denied,
+        resolved_by: ApprovalResolver::User,
+        account: Some("dev@example.com".to_string()),
+        resolved_at: "2025-01-02T03:04:05Z".to_string(),
+    };
+
+    chat.replay_initial_messages(vec![EventMsg::ApprovalResolved(resolved.clone())]);
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1);
+    assert_eq!(
+        lines_to_single_string(&cells[0]),
+        "✗ Denied cargo publish by dev@example.com at 2025-01-02T03:04:05Z\n"
+    );
+    assert!(chat.bottom_pane.no_modal_or_popup_active());
+
+    chat.handle_codex_event(Event {
+        id: "live".to_string(),
+        msg: EventMsg::ApprovalResolved(resolved),
+    });
+    assert!(drain_insert_history(&mut rx).is_empty());
+}
+
 #[tokio::test]
 async fn plan_implementation_popup_skips_when_messages_queued() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
@@ -1371,12 +1898,14 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
         call_id: "call-short".into(),
         turn_id: "turn-short".into(),
         command: vec!["bash".into(), "-lc".into(), "echo hello world".into()],
-        cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        cwd: PathBuf::from("/repo"),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        cwd_outside_workspace: false,
+        cwd_writable: true,
     };
     chat.handle_codex_event(Event {
         id: "sub-short".into(),
@@ -1421,6 +1950,8 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         ),
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        cwd_outside_workspace: false,
+        cwd_writable: true,
     };
     chat.handle_codex_event(Event {
         id: "sub-multi".into(),
@@ -1471,6 +2002,8 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         reason: None,
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        cwd_outside_workspace: false,
+        cwd_writable: true,
     };
     chat.handle_codex_event(Event {
         id: "sub-long".into(),
@@ -1557,6 +2090,22 @@ fn terminal_interaction(chat: &mut ChatWidget, call_id: &str, process_id: &str,
     });
 }
 
+/// Builds a begin event without sending it, for driving unpaired end events.
+fn begin_exec_event(call_id: &str, raw_cmd: &str) -> ExecCommandBeginEvent {
+    let command = vec!["bash".to_string(), "-lc".to_string(), raw_cmd.to_string()];
+    let parsed_cmd = codex_core::parse_command::parse_command(&command);
+    ExecCommandBeginEvent {
+        call_id: call_id.to_string(),
+        process_id: None,
+        turn_id: "turn-1".to_string(),
+        command,
+        cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        parsed_cmd,
+        source: ExecCommandSource::Agent,
+        interaction_input: None,
+    }
+}
+
 fn begin_exec(chat: &mut ChatWidget, call_id: &str, raw_cmd: &str) -> ExecCommandBeginEvent {
     begin_exec_with_source(chat, call_id, raw_cmd, ExecCommandSource::Agent)
 }
@@ -1600,6 +2149,9 @@ fn end_exec(
             exit_code,
             duration: std::time::Duration::from_millis(5),
             formatted_output: aggregated,
+            formatted_output_truncated: false,
+            denied_by_sandbox: false,
+            limit_exceeded: None,
         }),
     });
 }
@@ -1870,6 +2422,9 @@ async fn exec_end_without_begin_uses_event_command() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
+            formatted_output_truncated: false,
+            denied_by_sandbox: false,
+            limit_exceeded: None,
         }),
     });
 
@@ -1886,6 +2441,208 @@ async fn exec_end_without_begin_uses_event_command() {
     );
 }
 
+#[tokio::test]
+async fn exec_end_for_call_missing_from_active_cell_gets_own_cell() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    let explore = begin_exec(&mut chat, "call-explore", "ls");
+    end_exec(&mut chat, explore, "", "", 0);
+    assert!(drain_insert_history(&mut rx).is_empty());
+
+    let unpaired = begin_exec_event("call-unpaired", "echo unpaired");
+    end_exec(&mut chat, unpaired, "unpaired", "", 0);
+
+    let blobs: Vec<String> = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect();
+    assert_eq!(blobs.len(), 2, "expected explored and ran cells: {blobs:?}");
+    assert!(blobs[0].contains("List ls"), "{blobs:?}");
+    assert!(blobs[1].contains("• Ran echo unpaired"), "{blobs:?}");
+}
+
+#[tokio::test]
+async fn exec_begin_without_end_is_marked_orphaned_on_turn_complete() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.on_task_started();
+
+    begin_exec(&mut chat, "call-hung", "echo hung");
+    assert!(drain_insert_history(&mut rx).is_empty());
+
+    chat.on_task_complete(None, false);
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected orphaned exec cell to flush");
+    let blob = lines_to_single_string(&cells[0]);
+    assert!(
+        blob.contains("no completion event received"),
+        "expected orphan marker: {blob:?}"
+    );
+    assert!(chat.active_cell.is_none());
+}
+
+#[tokio::test]
+async fn duplicate_exec_begin_replaces_existing_call() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    begin_exec(&mut chat, "call-dup", "ls");
+    let begin = begin_exec(&mut chat, "call-dup", "ls");
+    let call_ids: Vec<String> = chat
+        .active_cell
+        .as_ref()
+        .and_then(|cell| cell.as_any().downcast_ref::<ExecCell>())
+        .expect("active exec cell")
+        .iter_calls()
+        .map(|call| call.call_id.clone())
+        .collect();
+    assert_eq!(call_ids, vec!["call-dup".to_string()]);
+
+    end_exec(&mut chat, begin, "", "", 0);
+    assert!(drain_insert_history(&mut rx).is_empty());
+    assert_eq!(active_blob(&chat), "• Explored\n  └ List ls\n");
+}
+
+#[tokio::test]
+async fn duplicate_mcp_begin_replaces_active_cell_and_orphan_is_closed() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.on_task_started();
+
+    for _ in 0..2 {
+        chat.handle_codex_event(Event {
+            id: "call-mcp".into(),
+            msg: EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
+                call_id: "call-mcp".into(),
+                invocation: McpInvocation {
+                    server: "search".into(),
+                    tool: "find_docs".into(),
+                    arguments: None,
+                },
+            }),
+        });
+    }
+    assert!(
+        drain_insert_history(&mut rx).is_empty(),
+        "duplicate begin should not flush a stale cell"
+    );
+
+    chat.on_task_complete(None, false);
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected orphaned MCP cell to flush");
+    let blob = lines_to_single_string(&cells[0]);
+    assert!(
+        blob.contains("no result received"),
+        "expected orphan marker: {blob:?}"
+    );
+}
+
+#[tokio::test]
+async fn patch_events_render_once_regardless_of_pairing() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let changes = HashMap::from([(
+        PathBuf::from("foo.txt"),
+        FileChange::Add {
+            content: "hello\n".to_string(),
+        },
+    )]);
+
+    // A begin delivered twice renders a single block.
+    for _ in 0..2 {
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
+                call_id: "c1".into(),
+                turn_id: "turn-c1".into(),
+                auto_approved: true,
+                changes: changes.clone(),
+            }),
+        });
+    }
+    assert_eq!(drain_insert_history(&mut rx).len(), 1);
+
+    // An end without a begin renders the applied changes itself.
+    chat.handle_codex_event(Event {
+        id: "s2".into(),
+        msg: EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+            call_id: "c2".into(),
+            turn_id: "turn-c1".into(),
+            stdout: "ok\n".into(),
+            stderr: String::new(),
+            success: true,
+            changes,
+            per_file: Vec::new(),
+        }),
+    });
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected unpaired end to render changes");
+    let blob = lines_to_single_string(&cells[0]);
+    assert!(blob.contains("foo.txt"), "expected file name: {blob:?}");
+}
+
+#[tokio::test]
+async fn patch_conflict_on_second_file_lists_per_file_results() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let cwd = chat.config.cwd.clone();
+    let update = || FileChange::Update {
+        unified_diff: "@@\n-old\n+new\n".to_string(),
+        move_path: None,
+    };
+    let changes: HashMap<PathBuf, FileChange> = ["a.txt", "b.txt", "c.txt"]
+        .into_iter()
+        .map(|name| (cwd.join(name), update()))
+        .collect();
+    let result = |name: &str, status, error: Option<&str>| PatchFileResult {
+        path: cwd.join(name),
+        status,
+        error: error.map(str::to_string),
+    };
+
+    chat.handle_codex_event(Event {
+        id: "s1".into(),
+        msg: EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
+            call_id: "c1".into(),
+            turn_id: "turn-c1".into(),
+            auto_approved: true,
+            changes: changes.clone(),
+        }),
+    });
+    drain_insert_history(&mut rx);
+
+    chat.handle_codex_event(Event {
+        id: "s1".into(),
+        msg: EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+            call_id: "c1".into(),
+            turn_id: "turn-c1".into(),
+            stdout: "Failed. Updated the following files before the error:\nM a.txt\n! b.txt\n"
+                .into(),
+            stderr: "Failed to find expected lines in b.txt:\nold\n".into(),
+            success: false,
+            changes,
+            per_file: vec![
+                result("a.txt", PatchFileStatus::Applied, None),
+                result(
+                    "b.txt",
+                    PatchFileStatus::Failed,
+                    Some("Failed to find expected lines in b.txt:\nold"),
+                ),
+                result("c.txt", PatchFileStatus::Skipped, None),
+            ],
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected a single failure cell");
+    let blob = lines_to_single_string(&cells[0]);
+    assert!(blob.contains("✘ Failed to apply patch"), "{blob:?}");
+    assert!(blob.contains("✓ a.txt"), "{blob:?}");
+    assert!(blob.contains("✗ b.txt"), "{blob:?}");
+    assert!(blob.contains("- c.txt (skipped)"), "{blob:?}");
+    assert!(
+        blob.contains("Failed to find expected lines in b.txt"),
+        "{blob:?}"
+    );
+}
+
 #[tokio::test]
 async fn exec_history_shows_unified_exec_startup_commands() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -1983,6 +2740,7 @@ async fn unified_exec_wait_after_final_agent_message_snapshot() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
 
@@ -1999,6 +2757,7 @@ async fn unified_exec_wait_after_final_agent_message_snapshot() {
         id: "turn-1".into(),
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             last_agent_message: Some("Final response.".into()),
+            system_fingerprint: None,
         }),
     });
 
@@ -2018,6 +2777,7 @@ async fn unified_exec_wait_before_streamed_agent_message_snapshot() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
 
@@ -2039,6 +2799,7 @@ async fn unified_exec_wait_before_streamed_agent_message_snapshot() {
         id: "turn-1".into(),
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             last_agent_message: None,
+            system_fingerprint: None,
         }),
     });
 
@@ -2091,6 +2852,7 @@ async fn unified_exec_waiting_multiple_empty_snapshots() {
         id: "turn-wait-1".into(),
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             last_agent_message: None,
+            system_fingerprint: None,
         }),
     });
 
@@ -2142,6 +2904,7 @@ async fn unified_exec_non_empty_then_empty_snapshots() {
         id: "turn-wait-3".into(),
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             last_agent_message: None,
+            system_fingerprint: None,
         }),
     });
 
@@ -2188,6 +2951,77 @@ async fn review_popup_custom_prompt_action_sends_event() {
     assert!(found, "expected OpenReviewCustomPrompt event to be sent");
 }
 
+#[tokio::test]
+async fn slash_memory_lists_notes_and_removes_selected_note() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let tempdir = tempdir().unwrap();
+    let memory_path = tempdir.path().join(".codex/memory.md");
+    std::fs::create_dir_all(memory_path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &memory_path,
+        "# Project memory\n\n- use `just ci`\n- tests/io.rs is flaky\n",
+    )
+    .unwrap();
+    chat.config.cwd = tempdir.path().to_path_buf();
+
+    chat.dispatch_command(SlashCommand::Memory);
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected a disabled notice");
+    assert!(lines_to_single_string(&cells[0]).contains("Project memory is disabled."));
+
+    chat.set_feature_enabled(Feature::ProjectMemory, true);
+    chat.dispatch_command(SlashCommand::Memory);
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("use `just ci`"), "popup: {popup}");
+    assert!(popup.contains("tests/io.rs is flaky"), "popup: {popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut removed_index = None;
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::RemoveProjectMemoryNote { index } = event {
+            removed_index = Some(index);
+        }
+    }
+    assert_eq!(removed_index, Some(0));
+
+    chat.remove_project_memory_note(0);
+    assert_eq!(
+        std::fs::read_to_string(&memory_path).unwrap(),
+        "# Project memory\n\n- tests/io.rs is flaky\n"
+    );
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected a removal notice");
+    assert!(
+        lines_to_single_string(&cells[0]).contains("Removed from project memory: use `just ci`")
+    );
+}
+
+#[tokio::test]
+async fn custom_prompts_shadowed_by_builtins_are_reported() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let prompt = |name: &str| CustomPrompt {
+        name: name.to_string(),
+        path: format!("/tmp/{name}.md").into(),
+        content: "body".to_string(),
+        description: None,
+        argument_hint: None,
+    };
+
+    chat.handle_codex_event(Event {
+        id: "prompts".into(),
+        msg: EventMsg::ListCustomPromptsResponse(ListCustomPromptsResponseEvent {
+            custom_prompts: vec![prompt("fix-issue"), prompt("review")],
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected a single collision notice");
+    let message = lines_to_single_string(&cells[0]);
+    assert!(message.contains("/review"), "message: {message}");
+    assert!(!message.contains("/fix-issue"), "message: {message}");
+    assert!(message.contains("/prompts:<name>"), "message: {message}");
+}
+
 #[tokio::test]
 async fn slash_init_skips_when_project_doc_exists() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
@@ -2482,56 +3316,415 @@ async fn user_turn_includes_personality_from_config() {
 }
 
 #[tokio::test]
-async fn slash_quit_requests_exit() {
-    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+async fn slash_quit_requests_exit() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::Quit);
+
+    assert_matches!(rx.try_recv(), Ok(AppEvent::Exit(ExitMode::ShutdownFirst)));
+}
+
+#[tokio::test]
+async fn slash_exit_requests_exit() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::Exit);
+
+    assert_matches!(rx.try_recv(), Ok(AppEvent::Exit(ExitMode::ShutdownFirst)));
+}
+
+#[tokio::test]
+async fn slash_resume_opens_picker() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::Resume);
+
+    assert_matches!(rx.try_recv(), Ok(AppEvent::OpenResumePicker));
+}
+
+#[tokio::test]
+async fn slash_fork_requests_current_fork() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::Fork);
+
+    assert_matches!(rx.try_recv(), Ok(AppEvent::ForkCurrentSession));
+}
+
+#[tokio::test]
+async fn slash_rollout_displays_current_path() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let rollout_path = PathBuf::from("/tmp/codex-test-rollout.jsonl");
+    chat.current_rollout_path = Some(rollout_path.clone());
+
+    chat.dispatch_command(SlashCommand::Rollout);
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected info message for rollout path");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains(&rollout_path.display().to_string()),
+        "expected rollout path to be shown: {rendered}"
+    );
+}
+
+#[tokio::test]
+async fn slash_tools_lists_tools_grouped_by_source() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::Tools);
+    assert_matches!(op_rx.try_recv(), Ok(Op::ListTools));
+
+    chat.handle_codex_event(Event {
+        id: "tools".to_string(),
+        msg: EventMsg::ListToolsResponse(ListToolsResponseEvent {
+            tools: vec![
+                ToolDescription {
+                    name: "mcp__docs__search".to_string(),
+                    source: ToolSource::Mcp {
+                        server: "docs".to_string(),
+                    },
+                    spec: serde_json::json!({
+                        "type": "function",
+                        "name": "mcp__docs__search",
+                        "description": "Search the docs",
+                        "parameters": {"type": "object", "properties": {}},
+                    }),
+                },
+                ToolDescription {
+                    name: "shell".to_string(),
+                    source: ToolSource::BuiltIn,
+                    spec: serde_json::json!({
+                        "type": "function",
+                        "name": "shell",
+                        "description": "Runs a shell command",
+                        "parameters": {
+                            "type": "object",
+                            "properties": {"command": {"type": "string"}},
+                        },
+                    }),
+                },
+            ],
+        }),
+    });
+
+    let popup = render_bottom_popup(&chat, 100);
+    let shell_at = popup.find("shell").expect("built-in tool listed");
+    let search_at = popup.find("mcp__docs__search").expect("MCP tool listed");
+    assert!(shell_at < search_at, "built-in tools come first: {popup}");
+    assert!(
+        popup.contains("MCP: docs · Search the docs"),
+        "popup: {popup}"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected the selected tool's schema");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("shell · built-in"),
+        "rendered: {rendered}"
+    );
+    assert!(rendered.contains("\"command\""), "rendered: {rendered}");
+}
+
+#[tokio::test]
+async fn slash_export_writes_redacted_html_transcript() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let tempdir = tempdir().unwrap();
+    let rollout_path = tempdir
+        .path()
+        .join("rollout-2025-01-01T00-00-00-test.jsonl");
+    let line = serde_json::json!({
+        "timestamp": "2025-01-01T00:00:00.000Z",
+        "type": "event_msg",
+        "payload": {
+            "type": "user_message",
+            "message": "use key sk-abcdefghijklmnopqrstuvwxyz",
+        },
+    });
+    std::fs::write(&rollout_path, format!("{line}\n")).unwrap();
+    chat.current_rollout_path = Some(rollout_path);
+    chat.config.cwd = tempdir.path().to_path_buf();
+
+    chat.dispatch_command_with_args(SlashCommand::Export, "--html".to_string());
+
+    let cell = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            if let Some(AppEvent::InsertHistoryCell(cell)) = rx.recv().await {
+                break cell;
+            }
+        }
+    })
+    .await
+    .expect("export result");
+    let output_path = tempdir.path().join("rollout-2025-01-01T00-00-00-test.html");
+    let rendered = lines_to_single_string(&cell.display_lines(200));
+    assert!(
+        rendered.contains(&output_path.display().to_string()),
+        "expected export path to be shown: {rendered}"
+    );
+    let html = std::fs::read_to_string(&output_path).unwrap();
+    assert!(html.contains("use key [REDACTED]"), "html: {html}");
+    assert!(!html.contains("sk-abcdefghijklmnopqrstuvwxyz"));
+}
+
+#[tokio::test]
+async fn slash_export_rejects_unknown_format() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command_with_args(SlashCommand::Export, "--pdf".to_string());
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected usage error");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("Usage: /export --html"),
+        "expected usage hint: {rendered}"
+    );
+}
+
+#[tokio::test]
+async fn slash_history_files_jumps_to_the_requested_turn() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command_with_args(SlashCommand::History, "files 3 --diff".to_string());
+    assert_matches!(op_rx.try_recv(), Ok(Op::ListGhostSnapshots));
+
+    chat.handle_codex_event(Event {
+        id: "history".to_string(),
+        msg: EventMsg::GhostSnapshotsResponse(GhostSnapshotsResponseEvent {
+            snapshots: vec![GhostSnapshotEntry {
+                commit_id: "0123456789".to_string(),
+                turn: Some(2),
+                files: vec![PathBuf::from("src/lib.rs")],
+            }],
+            unavailable: None,
+        }),
+    });
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected a missing-turn error");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("No snapshot was taken before turn 3"),
+        "unexpected message: {rendered}"
+    );
+
+    chat.handle_codex_event(Event {
+        id: "history".to_string(),
+        msg: EventMsg::GhostSnapshotFileResponse(GhostSnapshotFileResponseEvent {
+            commit_id: "0123456789".to_string(),
+            path: PathBuf::from("src/lib.rs"),
+            snapshot: Some("old\n".to_string()),
+            current: Some("new\n".to_string()),
+            error: None,
+        }),
+    });
+    let mut shown = None;
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::ShowSnapshotFile { view, .. } = event {
+            shown = Some(view);
+        }
+    }
+    assert_matches!(
+        shown,
+        Some(crate::history_command::SnapshotFileView::Diff(_))
+    );
+}
+
+#[tokio::test]
+async fn slash_history_files_reports_unavailable_snapshots() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::History);
+    chat.handle_codex_event(Event {
+        id: "history".to_string(),
+        msg: EventMsg::GhostSnapshotsResponse(GhostSnapshotsResponseEvent {
+            snapshots: Vec::new(),
+            unavailable: Some(
+                "Snapshots unavailable: /tmp is not inside a git repository.".to_string(),
+            ),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one info message");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("Snapshots unavailable"),
+        "unexpected message: {rendered}"
+    );
+}
+
+#[tokio::test]
+async fn reasoning_cycle_key_steps_to_next_effort() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
+    chat.set_reasoning_effort(Some(ReasoningEffortConfig::Low));
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+
+    let mut app_events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        app_events.push(ev);
+    }
+    assert!(
+        app_events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::CodexOp(Op::OverrideTurnContext {
+                effort: Some(Some(ReasoningEffortConfig::Medium)),
+                model: None,
+                ..
+            })
+        )),
+        "ctrl-r should override the next turn's effort"
+    );
+    assert!(
+        app_events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::UpdateReasoningEffort(Some(ReasoningEffortConfig::Medium))
+        )),
+        "ctrl-r should update the stored effort"
+    );
+    assert!(
+        !app_events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::PersistModelSelection { .. })),
+        "cycling must not persist the effort"
+    );
+}
+
+#[tokio::test]
+async fn slash_reasoning_sets_explicit_effort() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
 
-    chat.dispatch_command(SlashCommand::Quit);
+    chat.dispatch_command_with_args(SlashCommand::Reasoning, "high".to_string());
 
-    assert_matches!(rx.try_recv(), Ok(AppEvent::Exit(ExitMode::ShutdownFirst)));
+    let mut app_events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        app_events.push(ev);
+    }
+    assert!(
+        app_events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::UpdateReasoningEffort(Some(ReasoningEffortConfig::High))
+        )),
+        "/reasoning high should select high effort"
+    );
 }
 
 #[tokio::test]
-async fn slash_exit_requests_exit() {
-    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+async fn slash_reasoning_rejects_unknown_effort() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
 
-    chat.dispatch_command(SlashCommand::Exit);
+    chat.dispatch_command_with_args(SlashCommand::Reasoning, "turbo".to_string());
 
-    assert_matches!(rx.try_recv(), Ok(AppEvent::Exit(ExitMode::ShutdownFirst)));
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected usage error");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("Usage: /reasoning low|medium|high|none"),
+        "expected usage hint: {rendered}"
+    );
 }
 
 #[tokio::test]
-async fn slash_resume_opens_picker() {
-    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+async fn slash_language_sets_and_clears_preferred_language() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
 
-    chat.dispatch_command(SlashCommand::Resume);
+    chat.dispatch_command_with_args(SlashCommand::Language, "Japanese".to_string());
+    assert_matches!(
+        op_rx.try_recv(),
+        Ok(Op::SetPreferredLanguage { language: Some(language) }) if language == "Japanese"
+    );
+    assert_eq!(chat.config.preferred_language.as_deref(), Some("Japanese"));
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected confirmation");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("Codex will answer in Japanese from the next turn."),
+        "expected confirmation: {rendered}"
+    );
 
-    assert_matches!(rx.try_recv(), Ok(AppEvent::OpenResumePicker));
+    chat.dispatch_command_with_args(SlashCommand::Language, "off".to_string());
+    assert_matches!(
+        op_rx.try_recv(),
+        Ok(Op::SetPreferredLanguage { language: None })
+    );
+    assert_eq!(chat.config.preferred_language, None);
 }
 
 #[tokio::test]
-async fn slash_fork_requests_current_fork() {
-    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+async fn slash_revoke_forgets_session_approvals() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
 
-    chat.dispatch_command(SlashCommand::Fork);
+    chat.dispatch_command(SlashCommand::Revoke);
+    assert_matches!(op_rx.try_recv(), Ok(Op::RevokeSessionApprovals));
 
-    assert_matches!(rx.try_recv(), Ok(AppEvent::ForkCurrentSession));
+    chat.handle_codex_event(Event {
+        id: "revoke".to_string(),
+        msg: EventMsg::SessionApprovalsRevoked(SessionApprovalsRevokedEvent { revoked: 3 }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected revoke confirmation");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("Forgot 3 session approvals"),
+        "expected revoke count: {rendered}"
+    );
 }
 
 #[tokio::test]
-async fn slash_rollout_displays_current_path() {
-    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
-    let rollout_path = PathBuf::from("/tmp/codex-test-rollout.jsonl");
-    chat.current_rollout_path = Some(rollout_path.clone());
+async fn history_refresh_and_clear_reset_composer_history() {
+    fn history_requests(
+        rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>,
+    ) -> Vec<(usize, u64)> {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|event| match event {
+                AppEvent::CodexOp(Op::GetHistoryEntryRequest { offset, log_id }) => {
+                    Some((offset, log_id))
+                }
+                _ => None,
+            })
+            .collect()
+    }
 
-    chat.dispatch_command(SlashCommand::Rollout);
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.bottom_pane.set_history_metadata(7, 3);
+
+    // A lookup against a compacted log carries the metadata of the new one.
+    chat.handle_codex_event(Event {
+        id: "history".to_string(),
+        msg: EventMsg::GetHistoryEntryResponse(GetHistoryEntryResponseEvent {
+            offset: 2,
+            log_id: 7,
+            entry: None,
+            refreshed: Some(HistoryLogMetadata {
+                log_id: 9,
+                entry_count: 5,
+            }),
+        }),
+    });
+    chat.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    assert_eq!(history_requests(&mut rx), vec![(4, 9)]);
 
+    chat.dispatch_command(SlashCommand::ClearHistory);
+    assert_matches!(op_rx.try_recv(), Ok(Op::ClearHistory));
+
+    chat.handle_codex_event(Event {
+        id: "clear".to_string(),
+        msg: EventMsg::HistoryCleared(HistoryClearedEvent { log_id: 11 }),
+    });
     let cells = drain_insert_history(&mut rx);
-    assert_eq!(cells.len(), 1, "expected info message for rollout path");
+    assert_eq!(cells.len(), 1, "expected clear confirmation");
     let rendered = lines_to_single_string(&cells[0]);
     assert!(
-        rendered.contains(&rollout_path.display().to_string()),
-        "expected rollout path to be shown: {rendered}"
+        rendered.contains("Cleared message history"),
+        "expected confirmation: {rendered}"
     );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    assert_eq!(history_requests(&mut rx), Vec::<(usize, u64)>::new());
 }
 
 #[tokio::test]
@@ -2625,6 +3818,91 @@ async fn undo_failure_events_render_error_message() {
     );
 }
 
+#[tokio::test]
+async fn commit_draft_opens_editor_and_submits_commit() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command_with_args(SlashCommand::Commit, "--signoff".to_string());
+    let draft_op = std::iter::from_fn(|| op_rx.try_recv().ok())
+        .find(|op| matches!(op, Op::DraftCommit { .. }));
+    assert_eq!(
+        draft_op,
+        Some(Op::DraftCommit {
+            sign_off: true,
+            amend: false,
+        })
+    );
+
+    let file = chat.config.cwd.join("src").join("lib.rs");
+    chat.handle_codex_event(Event {
+        id: "commit-1".to_string(),
+        msg: EventMsg::CommitDraft(CommitDraftEvent {
+            message: "feat: add the commit command".to_string(),
+            files: vec![file.clone()],
+            sign_off: true,
+            amend: false,
+        }),
+    });
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let commit_op = std::iter::from_fn(|| rx.try_recv().ok()).find_map(|event| match event {
+        AppEvent::CodexOp(op @ Op::Commit { .. }) => Some(op),
+        _ => None,
+    });
+    assert_eq!(
+        commit_op,
+        Some(Op::Commit {
+            message: Some("feat: add the commit command".to_string()),
+            files: vec![file.clone()],
+            sign_off: true,
+            amend: false,
+        })
+    );
+
+    chat.handle_codex_event(Event {
+        id: "commit-2".to_string(),
+        msg: EventMsg::CommitCompleted(CommitCompletedEvent {
+            success: true,
+            commit: Some("0123abcdef".to_string()),
+            message: Some("feat: add the commit command".to_string()),
+            files: vec![file],
+            error: None,
+        }),
+    });
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(cells.last().expect("commit result"));
+    assert!(
+        rendered.contains("Committed 0123abc feat: add the commit command"),
+        "unexpected commit result: {rendered}"
+    );
+}
+
+#[tokio::test]
+async fn refused_commit_renders_the_reason() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "commit-1".to_string(),
+        msg: EventMsg::CommitCompleted(CommitCompletedEvent {
+            success: false,
+            commit: None,
+            message: None,
+            files: Vec::new(),
+            error: Some(
+                "A rebase is in progress. Finish or abort it before committing.".to_string(),
+            ),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1);
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("A rebase is in progress."),
+        "expected refusal reason: {rendered}"
+    );
+}
+
 #[tokio::test]
 async fn undo_started_hides_interrupt_hint() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -2781,6 +4059,7 @@ async fn interrupt_exec_marks_failed_snapshot() {
         id: "call-int".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            cancelled: Vec::new(),
         }),
     });
 
@@ -2807,6 +4086,7 @@ async fn interrupted_turn_error_message_snapshot() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
 
@@ -2815,6 +4095,7 @@ async fn interrupted_turn_error_message_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            cancelled: Vec::new(),
         }),
     });
 
@@ -3088,6 +4369,7 @@ async fn model_cap_error_does_not_switch_models() {
                 model: "boomslang".to_string(),
                 reset_after_seconds: Some(120),
             }),
+            ..Default::default()
         }),
     });
 
@@ -3551,7 +4833,7 @@ async fn approval_modal_exec_snapshot() -> anyhow::Result<()> {
         call_id: "call-approve-cmd".into(),
         turn_id: "turn-approve-cmd".into(),
         command: vec!["bash".into(), "-lc".into(), "echo hello world".into()],
-        cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        cwd: PathBuf::from("/repo"),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
@@ -3561,6 +4843,8 @@ async fn approval_modal_exec_snapshot() -> anyhow::Result<()> {
             "world".into(),
         ])),
         parsed_cmd: vec![],
+        cwd_outside_workspace: false,
+        cwd_writable: true,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve".into(),
@@ -3606,7 +4890,7 @@ async fn approval_modal_exec_without_reason_snapshot() -> anyhow::Result<()> {
         call_id: "call-approve-cmd-noreason".into(),
         turn_id: "turn-approve-cmd-noreason".into(),
         command: vec!["bash".into(), "-lc".into(), "echo hello world".into()],
-        cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        cwd: PathBuf::from("/repo"),
         reason: None,
         proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
             "echo".into(),
@@ -3614,6 +4898,8 @@ async fn approval_modal_exec_without_reason_snapshot() -> anyhow::Result<()> {
             "world".into(),
         ])),
         parsed_cmd: vec![],
+        cwd_outside_workspace: false,
+        cwd_writable: true,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-noreason".into(),
@@ -3650,10 +4936,12 @@ async fn approval_modal_exec_multiline_prefix_hides_execpolicy_option_snapshot()
         call_id: "call-approve-cmd-multiline-trunc".into(),
         turn_id: "turn-approve-cmd-multiline-trunc".into(),
         command: command.clone(),
-        cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        cwd: PathBuf::from("/repo"),
         reason: None,
         proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
         parsed_cmd: vec![],
+        cwd_outside_workspace: false,
+        cwd_writable: true,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-multiline-trunc".into(),
@@ -3739,6 +5027,7 @@ async fn interrupt_restores_queued_messages_into_composer() {
         id: "turn-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            cancelled: Vec::new(),
         }),
     });
 
@@ -3777,6 +5066,7 @@ async fn interrupt_prepends_queued_messages_before_existing_composer_text() {
         id: "turn-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            cancelled: Vec::new(),
         }),
     });
 
@@ -3805,6 +5095,7 @@ async fn interrupt_clears_unified_exec_processes() {
         id: "turn-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            cancelled: Vec::new(),
         }),
     });
 
@@ -3822,6 +5113,7 @@ async fn interrupt_clears_unified_exec_wait_streak_snapshot() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
 
@@ -3832,6 +5124,7 @@ async fn interrupt_clears_unified_exec_wait_streak_snapshot() {
         id: "turn-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            cancelled: Vec::new(),
         }),
     });
 
@@ -3858,6 +5151,7 @@ async fn turn_complete_clears_unified_exec_processes() {
         id: "turn-1".into(),
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             last_agent_message: None,
+            system_fingerprint: None,
         }),
     });
 
@@ -3896,6 +5190,7 @@ async fn ui_snapshots_small_heights_task_running() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -3928,6 +5223,7 @@ async fn status_widget_and_approval_modal_snapshot() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
     // Provide a deterministic header for the status line.
@@ -3952,6 +5248,8 @@ async fn status_widget_and_approval_modal_snapshot() {
             "hello world".into(),
         ])),
         parsed_cmd: vec![],
+        cwd_outside_workspace: false,
+        cwd_writable: true,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-exec".into(),
@@ -3981,6 +5279,7 @@ async fn status_widget_active_snapshot() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
     // Provide a deterministic header via a bold reasoning chunk.
@@ -4000,6 +5299,55 @@ async fn status_widget_active_snapshot() {
     assert_snapshot!("status_widget_active", terminal.backend());
 }
 
+#[tokio::test]
+async fn reasoning_headline_follows_latest_heading() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.on_task_started();
+
+    for delta in [
+        "**Reading",
+        " the code**\n\nLooking at",
+        " the cache.\n\n**Evaluating",
+    ] {
+        chat.handle_codex_event(Event {
+            id: "task-1".into(),
+            msg: EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent {
+                delta: delta.into(),
+            }),
+        });
+    }
+    let status = chat.bottom_pane.status_widget().expect("status indicator");
+    assert_eq!(status.header(), "Thinking");
+    assert_eq!(status.subtitle(), Some("Reading the code"));
+
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent {
+            delta: " locking strategy**".into(),
+        }),
+    });
+    let status = chat.bottom_pane.status_widget().expect("status indicator");
+    assert_eq!(status.subtitle(), Some("Evaluating locking strategy"));
+}
+
+#[tokio::test]
+async fn reasoning_headline_can_be_disabled() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.config.tui_reasoning_headlines = false;
+    chat.on_task_started();
+
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent {
+            delta: "**Evaluating locking strategy**".into(),
+        }),
+    });
+
+    let status = chat.bottom_pane.status_widget().expect("status indicator");
+    assert_eq!(status.header(), "Working");
+    assert_eq!(status.subtitle(), None);
+}
+
 #[tokio::test]
 async fn mcp_startup_header_booting_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -4031,6 +5379,7 @@ async fn mcp_startup_complete_does_not_clear_running_task() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
 
@@ -4049,6 +5398,45 @@ async fn mcp_startup_complete_does_not_clear_running_task() {
     assert!(chat.bottom_pane.status_indicator_visible());
 }
 
+#[tokio::test]
+async fn session_prewarmed_warns_only_about_unreported_degradations() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "initial".into(),
+        msg: EventMsg::SessionPrewarmed(SessionPrewarmedEvent {
+            phases: vec![PrewarmPhaseTiming {
+                phase: PrewarmPhase::McpServers,
+                duration_ms: 120,
+            }],
+            degraded: vec![
+                DegradedComponent {
+                    phase: PrewarmPhase::Config,
+                    detail: "Skill review failed to load".to_string(),
+                },
+                // The harness is signed in, so this is stale.
+                DegradedComponent {
+                    phase: PrewarmPhase::Auth,
+                    detail: "Not signed in.".to_string(),
+                },
+                DegradedComponent {
+                    phase: PrewarmPhase::McpServers,
+                    detail: "MCP server `docs` failed to start: boom".to_string(),
+                },
+            ],
+            mcp_servers: Vec::new(),
+        }),
+    });
+
+    let rendered: Vec<String> = drain_insert_history(&mut rx)
+        .iter()
+        .map(Vec::as_slice)
+        .map(lines_to_single_string)
+        .collect();
+    assert_eq!(rendered.len(), 1);
+    assert!(rendered[0].contains("Skill review failed to load"));
+}
+
 #[tokio::test]
 async fn background_event_updates_status_header() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -4065,6 +5453,100 @@ async fn background_event_updates_status_header() {
     assert!(drain_insert_history(&mut rx).is_empty());
 }
 
+#[tokio::test]
+async fn offline_queue_events_add_info_cells() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::PromptQueuedOffline(PromptQueuedOfflineEvent { queued_prompts: 1 }),
+    });
+    chat.handle_codex_event(Event {
+        id: "sub-2".into(),
+        msg: EventMsg::PromptQueuedOffline(PromptQueuedOfflineEvent { queued_prompts: 2 }),
+    });
+    chat.handle_codex_event(Event {
+        id: "auto-1".into(),
+        msg: EventMsg::ConnectivityRestored(ConnectivityRestoredEvent {
+            submitted_prompts: 2,
+        }),
+    });
+
+    let rendered: Vec<String> = drain_insert_history(&mut rx)
+        .iter()
+        .map(Vec::as_slice)
+        .map(lines_to_single_string)
+        .collect();
+    assert_eq!(rendered.len(), 3);
+    assert!(rendered[0].contains("Offline — message queued"));
+    assert!(rendered[1].contains("(2 waiting)"));
+    assert!(rendered[2].contains("Back online — sending 2 queued messages"));
+}
+
+#[tokio::test]
+async fn alt_enter_steers_running_turn_and_marks_delivery() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.bottom_pane.set_task_running(true);
+
+    chat.bottom_pane
+        .set_composer_text("use the v2 endpoint".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT));
+
+    match op_rx.try_recv() {
+        Ok(Op::SteerTask { text }) => assert_eq!(text, "use the v2 endpoint"),
+        other => panic!("expected Op::SteerTask, got {other:?}"),
+    }
+    assert!(chat.queued_user_messages.is_empty());
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::SteerDelivered(SteerDeliveredEvent {
+            text: "use the v2 endpoint".to_string(),
+            deferred: false,
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "turn-2".into(),
+        msg: EventMsg::SteerDelivered(SteerDeliveredEvent {
+            text: "and add a test".to_string(),
+            deferred: true,
+        }),
+    });
+
+    let rendered: Vec<String> = drain_insert_history(&mut rx)
+        .iter()
+        .map(Vec::as_slice)
+        .map(lines_to_single_string)
+        .collect();
+    assert_eq!(
+        rendered,
+        vec![
+            "↳ steer\n  use the v2 endpoint\n".to_string(),
+            "\n↳ steer (sent as a new turn)\n  and add a test\n".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn turn_refused_renders_refusal_cell() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TurnRefused(TurnRefusedEvent {
+            reason: "I can't help with that.".to_string(),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1);
+    assert_eq!(
+        lines_to_single_string(&cells[0]),
+        "⊘ Refused\n  I can't help with that.\n"
+    );
+    assert_eq!(cells[0][0].spans[1], "Refused".magenta().bold());
+}
+
 #[tokio::test]
 async fn apply_patch_events_emit_history_cells() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -4151,6 +5633,7 @@ async fn apply_patch_events_emit_history_cells() {
         stderr: String::new(),
         success: true,
         changes: end_changes,
+        per_file: Vec::new(),
     };
     chat.handle_codex_event(Event {
         id: "s1".into(),
@@ -4378,6 +5861,7 @@ async fn apply_patch_full_flow_integration_like() {
             stderr: String::new(),
             success: true,
             changes: end_changes,
+            per_file: Vec::new(),
         }),
     });
 }
@@ -4588,6 +6072,7 @@ async fn stream_recovery_restores_previous_status_header() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
     drain_insert_history(&mut rx);
@@ -4626,6 +6111,7 @@ async fn multiple_agent_messages_in_single_turn_emit_multiple_headers() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
 
@@ -4650,6 +6136,7 @@ async fn multiple_agent_messages_in_single_turn_emit_multiple_headers() {
         id: "s1".into(),
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             last_agent_message: None,
+            system_fingerprint: None,
         }),
     });
 
@@ -4814,6 +6301,9 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
+            formatted_output_truncated: false,
+            denied_by_sandbox: false,
+            limit_exceeded: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -4821,6 +6311,7 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -4857,6 +6348,86 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
 
+#[tokio::test]
+async fn scripted_exploration_snapshot() {
+    let mut session = ScriptedSession::new(80, 40).await;
+    session.play(fixtures::exploration());
+    session.play([Step::Compose("Summarize recent commits".to_string())]);
+
+    assert_snapshot!("scripted_exploration", session.screen());
+}
+
+#[tokio::test]
+async fn scripted_status_indicator_snapshot() {
+    let mut session = ScriptedSession::new(80, 40).await;
+    session.play(fixtures::working());
+
+    assert_snapshot!("scripted_status_indicator", session.viewport().backend());
+}
+
+#[tokio::test]
+async fn scripted_exec_approval_snapshot() {
+    let mut session = ScriptedSession::new(100, 40).await;
+    session.play(fixtures::working());
+    session.play(fixtures::exec_approval());
+    assert_snapshot!("scripted_exec_approval", session.viewport().backend());
+
+    session.play([Step::Key(KeyEvent::new(
+        KeyCode::Char('y'),
+        KeyModifiers::NONE,
+    ))]);
+    assert_eq!(
+        session.take_ops(),
+        vec![Op::ExecApproval {
+            id: harness::SCRIPT_EVENT_ID.to_string(),
+            decision: codex_core::protocol::ReviewDecision::Approved,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn scripted_patch_applied_snapshot() {
+    let mut session = ScriptedSession::new(80, 40).await;
+    session.play(fixtures::patch_applied());
+
+    assert_snapshot!("scripted_patch_applied", session.transcript());
+}
+
+#[tokio::test]
+async fn scripted_patch_approval_snapshot() {
+    let mut session = ScriptedSession::new(80, 40).await;
+    session.play(fixtures::patch_approval());
+    assert_snapshot!("scripted_patch_approval", session.screen());
+
+    session.play([Step::Key(KeyEvent::new(
+        KeyCode::Char('y'),
+        KeyModifiers::NONE,
+    ))]);
+    assert_eq!(
+        session.take_ops(),
+        vec![Op::PatchApproval {
+            id: harness::SCRIPT_EVENT_ID.to_string(),
+            decision: codex_core::protocol::ReviewDecision::Approved,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn scripted_streamed_answer_snapshot() {
+    let mut session = ScriptedSession::new(80, 40).await;
+    session.play(fixtures::streamed_answer());
+
+    assert_snapshot!("scripted_streamed_answer", session.transcript());
+}
+
+#[tokio::test]
+async fn scripted_failed_request_snapshot() {
+    let mut session = ScriptedSession::new(80, 40).await;
+    session.play(fixtures::failed_request());
+
+    assert_snapshot!("scripted_failed_request", session.transcript());
+}
+
 // E2E vt100 snapshot for complex markdown with indented and nested fenced code blocks
 #[tokio::test]
 async fn chatwidget_markdown_code_blocks_vt100_snapshot() {
@@ -4869,6 +6440,7 @@ async fn chatwidget_markdown_code_blocks_vt100_snapshot() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
     // Build a vt100 visual from the history insertions only (no UI overlay)
@@ -4940,6 +6512,7 @@ printf 'fenced within fenced\n'
         id: "t1".into(),
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             last_agent_message: None,
+            system_fingerprint: None,
         }),
     });
     for lines in drain_insert_history(&mut rx) {
@@ -4959,6 +6532,7 @@ async fn chatwidget_tall() {
         msg: EventMsg::TurnStarted(TurnStartedEvent {
             model_context_window: None,
             collaboration_mode_kind: ModeKind::Custom,
+            settings: None,
         }),
     });
     for i in 0..30 {
@@ -0,0 +1,329 @@
+//! Scripted rendering harness for `ChatWidget` snapshot tests.
+//!
+//! A [`ScriptedSession`] wraps a widget built by `make_chatwidget_manual`, so no backend is
+//! spawned: ops land on a channel the test owns. It plays a script of [`Step`]s, forwards
+//! `AppEvent::CodexOp` back into the widget the way `App` does, collects inserted history cells,
+//! and renders history plus the live viewport into a fixed-size vt100 terminal, or the live
+//! viewport alone into a `TestBackend`.
+//!
+//! The fixture scripts in [`fixtures`] cover the common shapes of a session (exploration
+//! commands, the status indicator, exec and patch approvals, an applied diff, a streamed answer,
+//! a failed request) so layout changes show up as snapshot diffs instead of being noticed by eye.
+
+use super::*;
+
+/// Event id attached to every scripted event; approval ops echo it back.
+pub(crate) const SCRIPT_EVENT_ID: &str = "scripted";
+
+pub(crate) enum Step {
+    /// Deliver a protocol event as if it came from the backend.
+    Event(EventMsg),
+    /// Press a key.
+    Key(KeyEvent),
+    /// Replace the composer contents.
+    Compose(String),
+    /// Advance streaming output by one animation tick.
+    CommitTick,
+}
+
+pub(crate) struct ScriptedSession {
+    pub(crate) chat: ChatWidget,
+    app_events: tokio::sync::mpsc::UnboundedReceiver<AppEvent>,
+    ops: tokio::sync::mpsc::UnboundedReceiver<Op>,
+    history: Vec<Vec<Line<'static>>>,
+    width: u16,
+    height: u16,
+}
+
+impl ScriptedSession {
+    pub(crate) async fn new(width: u16, height: u16) -> Self {
+        let (chat, app_events, ops) = make_chatwidget_manual(None).await;
+        Self {
+            chat,
+            app_events,
+            ops,
+            history: Vec::new(),
+            width,
+            height,
+        }
+    }
+
+    pub(crate) fn play(&mut self, steps: impl IntoIterator<Item = Step>) {
+        for step in steps {
+            match step {
+                Step::Event(msg) => self.chat.handle_codex_event(Event {
+                    id: SCRIPT_EVENT_ID.to_string(),
+                    msg,
+                }),
+                Step::Key(key) => self.chat.handle_key_event(key),
+                Step::Compose(text) => {
+                    self.chat
+                        .bottom_pane
+                        .set_composer_text(text, Vec::new(), Vec::new());
+                }
+                Step::CommitTick => self.chat.on_commit_tick(),
+            }
+            self.pump_app_events();
+        }
+    }
+
+    /// Ops the widget submitted since the last call.
+    pub(crate) fn take_ops(&mut self) -> Vec<Op> {
+        let mut ops = Vec::new();
+        while let Ok(op) = self.ops.try_recv() {
+            ops.push(op);
+        }
+        ops
+    }
+
+    /// History cells inserted so far, as plain text.
+    pub(crate) fn transcript(&self) -> String {
+        self.history
+            .iter()
+            .map(|lines| lines_to_single_string(lines))
+            .collect()
+    }
+
+    /// Full terminal contents: history above the live viewport, which sits one row above the
+    /// bottom edge. Leading blank rows are dropped so snapshots do not depend on how much
+    /// scrollback is left over.
+    pub(crate) fn screen(&self) -> String {
+        let ui_height = self
+            .chat
+            .desired_height(self.width)
+            .min(self.height.saturating_sub(1));
+        let viewport = Rect::new(0, self.height - ui_height - 1, self.width, ui_height);
+        let backend = VT100Backend::new(self.width, self.height);
+        let mut term = crate::custom_terminal::Terminal::with_options(backend).expect("terminal");
+        term.set_viewport_area(viewport);
+        for lines in &self.history {
+            crate::insert_history::insert_history_lines(&mut term, lines.clone())
+                .expect("Failed to insert history lines in test");
+        }
+        term.draw(|f| self.chat.render(f.area(), f.buffer_mut()))
+            .expect("draw scripted session");
+        term.backend()
+            .vt100()
+            .screen()
+            .contents()
+            .trim_start_matches('\n')
+            .to_string()
+    }
+
+    /// The live viewport alone (status indicator, approval modal, composer) at its desired
+    /// height.
+    pub(crate) fn viewport(&self) -> ratatui::Terminal<ratatui::backend::TestBackend> {
+        let height = self.chat.desired_height(self.width);
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(self.width, height))
+                .expect("create terminal");
+        terminal
+            .draw(|f| self.chat.render(f.area(), f.buffer_mut()))
+            .expect("draw scripted viewport");
+        terminal
+    }
+
+    fn pump_app_events(&mut self) {
+        while let Ok(event) = self.app_events.try_recv() {
+            match event {
+                AppEvent::InsertHistoryCell(cell) => {
+                    let mut lines = cell.display_lines(self.width);
+                    if !cell.is_stream_continuation()
+                        && !self.history.is_empty()
+                        && !lines.is_empty()
+                    {
+                        lines.insert(0, "".into());
+                    }
+                    self.history.push(lines);
+                }
+                AppEvent::CodexOp(op) => self.chat.submit_op(op),
+                _ => {}
+            }
+        }
+    }
+}
+
+pub(crate) mod fixtures {
+    use super::*;
+
+    /// The agent explains itself, runs a search-and-read exploration, and starts reasoning.
+    pub(crate) fn exploration() -> Vec<Step> {
+        let command = vec!["bash".into(), "-lc".into(), "rg \"Change Approved\"".into()];
+        let parsed_cmd = vec![
+            ParsedCommand::Search {
+                query: Some("Change Approved".into()),
+                path: None,
+                cmd: "rg \"Change Approved\"".into(),
+            },
+            ParsedCommand::Read {
+                name: "diff_render.rs".into(),
+                cmd: "cat diff_render.rs".into(),
+                path: "diff_render.rs".into(),
+            },
+        ];
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        vec![
+            Step::Event(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "I’m going to search the repo for where “Change Approved” is rendered to update that view.".into(),
+            })),
+            Step::Event(EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+                call_id: "c1".into(),
+                process_id: None,
+                turn_id: "turn-1".into(),
+                command: command.clone(),
+                cwd: cwd.clone(),
+                parsed_cmd: parsed_cmd.clone(),
+                source: ExecCommandSource::Agent,
+                interaction_input: None,
+            })),
+            Step::Event(EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+                call_id: "c1".into(),
+                process_id: None,
+                turn_id: "turn-1".into(),
+                command,
+                cwd,
+                parsed_cmd,
+                source: ExecCommandSource::Agent,
+                interaction_input: None,
+                stdout: String::new(),
+                stderr: String::new(),
+                aggregated_output: String::new(),
+                exit_code: 0,
+                duration: std::time::Duration::from_millis(16000),
+                formatted_output: String::new(),
+                formatted_output_truncated: false,
+                denied_by_sandbox: false,
+                limit_exceeded: None,
+            })),
+            Step::Event(EventMsg::TurnStarted(TurnStartedEvent {
+                model_context_window: None,
+                collaboration_mode_kind: ModeKind::Custom,
+                settings: None,
+            })),
+            Step::Event(EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent {
+                delta: "**Investigating rendering code**".into(),
+            })),
+        ]
+    }
+
+    /// A turn is running and the reasoning headline names what it is doing.
+    pub(crate) fn working() -> Vec<Step> {
+        vec![
+            Step::Event(EventMsg::TurnStarted(TurnStartedEvent {
+                model_context_window: None,
+                collaboration_mode_kind: ModeKind::Custom,
+                settings: None,
+            })),
+            Step::Event(EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent {
+                delta: "**Analyzing**".into(),
+            })),
+        ]
+    }
+
+    /// The agent asks to run `echo 'hello world'` in /tmp.
+    pub(crate) fn exec_approval() -> Vec<Step> {
+        vec![Step::Event(EventMsg::ExecApprovalRequest(
+            ExecApprovalRequestEvent {
+                call_id: "call-approve-exec".into(),
+                turn_id: "turn-approve-exec".into(),
+                command: vec!["echo".into(), "hello world".into()],
+                cwd: PathBuf::from("/tmp"),
+                reason: Some(
+                    "this is a test reason such as one that would be produced by the model".into(),
+                ),
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
+                    "echo".into(),
+                    "hello world".into(),
+                ])),
+                parsed_cmd: vec![],
+                cwd_outside_workspace: false,
+                cwd_writable: true,
+            },
+        ))]
+    }
+
+    /// An approved patch that adds foo.txt starts applying.
+    pub(crate) fn patch_applied() -> Vec<Step> {
+        let mut changes = HashMap::new();
+        changes.insert(
+            PathBuf::from("foo.txt"),
+            FileChange::Add {
+                content: "hello\n".into(),
+            },
+        );
+        vec![Step::Event(EventMsg::PatchApplyBegin(
+            PatchApplyBeginEvent {
+                call_id: "call-apply-patch".into(),
+                turn_id: "turn-apply-patch".into(),
+                auto_approved: true,
+                changes,
+            },
+        ))]
+    }
+
+    /// The agent asks to create README.md.
+    pub(crate) fn patch_approval() -> Vec<Step> {
+        let mut changes = HashMap::new();
+        changes.insert(
+            PathBuf::from("README.md"),
+            FileChange::Add {
+                content: "hello\nworld\n".into(),
+            },
+        );
+        vec![Step::Event(EventMsg::ApplyPatchApprovalRequest(
+            ApplyPatchApprovalRequestEvent {
+                call_id: "call-approve-patch".into(),
+                turn_id: "turn-approve-patch".into(),
+                changes,
+                reason: Some("The model wants to apply changes".into()),
+                grant_root: None,
+            },
+        ))]
+    }
+
+    /// Reasoning and answer deltas followed by the identical final message.
+    pub(crate) fn streamed_answer() -> Vec<Step> {
+        let reasoning = ["I will ", "first analyze the ", "request."].map(|delta| {
+            Step::Event(EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent {
+                delta: delta.into(),
+            }))
+        });
+        let answer = ["Here is the ", "result."].map(|delta| {
+            Step::Event(EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+                delta: delta.into(),
+            }))
+        });
+        reasoning
+            .into_iter()
+            .chain([Step::Event(EventMsg::AgentReasoning(AgentReasoningEvent {
+                text: "request.".into(),
+            }))])
+            .chain(answer.into_iter().flat_map(|step| [step, Step::CommitTick]))
+            .chain([Step::Event(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Here is the result.".into(),
+            }))])
+            .collect()
+    }
+
+    /// A turn that ends with a request error carrying the server's request id.
+    pub(crate) fn failed_request() -> Vec<Step> {
+        vec![
+            Step::Event(EventMsg::TurnStarted(TurnStartedEvent {
+                model_context_window: None,
+                collaboration_mode_kind: ModeKind::Custom,
+                settings: None,
+            })),
+            Step::Event(EventMsg::Error(ErrorEvent {
+                message: "stream disconnected before completion".into(),
+                codex_error_info: None,
+                request_details: Some(RequestErrorDetails {
+                    request_id: Some("req_scripted".into()),
+                    error_type: None,
+                    error_code: None,
+                    attempts: Some(5),
+                }),
+                ..Default::default()
+            })),
+        ]
+    }
+}
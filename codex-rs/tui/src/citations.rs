@@ -0,0 +1,146 @@
+//! Highlighting of file citations in streamed agent messages.
+//!
+//! Lines are scanned as they are committed, so each cell only knows about the
+//! citations on its own lines. Existence checks are cached for the whole
+//! message by the shared [`CitationResolver`].
+
+use std::ops::Range;
+use std::path::PathBuf;
+
+use codex_common::citations::Citation;
+use codex_common::citations::CitationResolver;
+use codex_common::citations::MAX_CITATION_SCAN_BYTES;
+use ratatui::style::Modifier;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+/// A citation found on one line of an agent message cell. `citation.range`
+/// is relative to the text of that line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LineCitation {
+    pub(crate) line_index: usize,
+    pub(crate) citation: Citation,
+}
+
+pub(crate) struct CitationHighlighter {
+    resolver: CitationResolver,
+    scanned_bytes: usize,
+}
+
+impl CitationHighlighter {
+    pub(crate) fn new(cwd: PathBuf) -> Self {
+        Self {
+            resolver: CitationResolver::new(cwd),
+            scanned_bytes: 0,
+        }
+    }
+
+    /// Underline the citations in `lines` and report where they are. Once the
+    /// message has grown past [`MAX_CITATION_SCAN_BYTES`], the remaining lines
+    /// are left alone.
+    pub(crate) fn highlight(&mut self, lines: &mut [Line<'static>]) -> Vec<LineCitation> {
+        let mut found = Vec::new();
+        for (line_index, line) in lines.iter_mut().enumerate() {
+            let text: String = line
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect();
+            self.scanned_bytes += text.len();
+            if self.scanned_bytes > MAX_CITATION_SCAN_BYTES {
+                break;
+            }
+            let citations = self.resolver.find(&text);
+            if citations.is_empty() {
+                continue;
+            }
+            let ranges: Vec<Range<usize>> = citations.iter().map(|c| c.range.clone()).collect();
+            *line = underline_ranges(line, &ranges);
+            found.extend(citations.into_iter().map(|citation| LineCitation {
+                line_index,
+                citation,
+            }));
+        }
+        found
+    }
+}
+
+/// Split `line`'s spans at the range boundaries and underline the parts that
+/// fall inside a range, keeping their existing style otherwise.
+fn underline_ranges(line: &Line<'static>, ranges: &[Range<usize>]) -> Line<'static> {
+    let mut spans = Vec::with_capacity(line.spans.len());
+    let mut offset = 0;
+    for span in &line.spans {
+        let content = span.content.as_ref();
+        let span_end = offset + content.len();
+        let mut cuts = vec![0, content.len()];
+        for range in ranges {
+            for point in [range.start, range.end] {
+                if offset < point && point < span_end {
+                    cuts.push(point - offset);
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+        for pair in cuts.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            let cited = ranges
+                .iter()
+                .any(|range| range.start <= offset + start && offset + start < range.end);
+            let style = if cited {
+                span.style.add_modifier(Modifier::UNDERLINED)
+            } else {
+                span.style
+            };
+            spans.push(Span::styled(content[start..end].to_string(), style));
+        }
+        offset = span_end;
+    }
+    Line {
+        style: line.style,
+        alignment: line.alignment,
+        spans,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Stylize;
+
+    #[test]
+    fn underlines_citations_across_spans() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        std::fs::write(dir.path().join("main.rs"), "").expect("write");
+        let mut highlighter = CitationHighlighter::new(dir.path().to_path_buf());
+        let mut lines = vec![
+            Line::from(vec![
+                "see ".into(),
+                "main.rs".cyan(),
+                ":4 and other.rs".into(),
+            ]),
+            Line::from("nothing here"),
+        ];
+
+        let found = highlighter.highlight(&mut lines);
+
+        assert_eq!(
+            found
+                .iter()
+                .map(|c| (c.line_index, c.citation.range.clone(), c.citation.line))
+                .collect::<Vec<_>>(),
+            vec![(0, 4..13, Some(4))]
+        );
+        assert_eq!(
+            lines[0].spans,
+            vec![
+                Span::from("see "),
+                "main.rs".cyan().underlined(),
+                ":4".underlined(),
+                Span::from(" and other.rs"),
+            ]
+        );
+    }
+}
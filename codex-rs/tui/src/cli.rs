@@ -98,6 +98,35 @@ pub struct Cli {
     #[arg(long = "search", default_value_t = false)]
     pub web_search: bool,
 
+    /// Read-only chat: disable the shell and `apply_patch` tools so the agent can only answer.
+    /// Same as `-c tools.shell=false -c tools.apply_patch=false`.
+    #[arg(long = "read-only-chat", default_value_t = false)]
+    pub read_only_chat: bool,
+
+    /// Offline mode: fail model requests immediately instead of retrying them.
+    /// Same as `-c offline=true`.
+    #[arg(long = "offline", default_value_t = false)]
+    pub offline: bool,
+
+    /// Developer flag: report how each shell tool command would be spawned,
+    /// including the sandbox wrapper and environment, without running it.
+    /// Same as `-c dry_run_exec=true`.
+    #[arg(long = "dry-run-exec", default_value_t = false)]
+    pub dry_run_exec: bool,
+
+    /// Load provider credentials from this dotenv file. Its values are never
+    /// exported to commands the agent runs. See also `load_dotenv`.
+    #[arg(long = "env-file", value_name = "PATH")]
+    pub env_file: Option<PathBuf>,
+
+    /// Title for the new session, shown in the resume picker. Change it later with `/title`.
+    #[arg(long = "title", value_name = "TITLE")]
+    pub title: Option<String>,
+
+    /// Tag the new session so it can be filtered in the resume picker. Repeatable.
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+
     /// Additional directories that should be writable alongside the primary workspace.
     #[arg(long = "add-dir", value_name = "DIR", value_hint = ValueHint::DirPath)]
     pub add_dir: Vec<PathBuf>,
@@ -110,6 +139,21 @@ pub struct Cli {
     #[arg(long = "no-alt-screen", default_value_t = false)]
     pub no_alt_screen: bool,
 
+    /// Record the committed transcript and status changes to PATH as an asciinema
+    /// (asciicast v2) file. Play it back with `codex replay PATH`.
+    #[arg(long = "record", value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub record: Option<PathBuf>,
+
+    /// Include user messages in the `--record` file. They are left out by default
+    /// because they may contain secrets.
+    #[arg(long = "record-input", default_value_t = false, requires = "record")]
+    pub record_input: bool,
+
+    /// Skip the first-run setup wizard and start with the built-in defaults. Passing any `-c`
+    /// override has the same effect.
+    #[arg(long = "no-wizard", default_value_t = false)]
+    pub no_wizard: bool,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 }
@@ -0,0 +1,90 @@
+//! Argument handling for the `/diff` slash command.
+//!
+//! The diff itself is computed by [`codex_core::git_diff`]; this module maps
+//! `/diff [--staged|--unstaged] [RANGE] [-- PATH...]` onto its options.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::git_diff::GitDiffOptions;
+use codex_core::git_diff::GitDiffTarget;
+use codex_core::git_diff::get_git_diff;
+
+pub(crate) const DIFF_USAGE: &str = "Usage: /diff [--staged | --unstaged | RANGE] [-- PATH...]";
+
+/// Parse the text after `/diff`. Tokens containing `..` are treated as a
+/// commit range; any other token is a path.
+pub(crate) fn parse_diff_args(args: &str) -> Result<GitDiffOptions, String> {
+    let mut options = GitDiffOptions {
+        color: true,
+        ..Default::default()
+    };
+    let mut target_set = false;
+    let mut set_target = |options: &mut GitDiffOptions, target: GitDiffTarget| {
+        if std::mem::replace(&mut target_set, true) {
+            return Err(format!("Only one diff target may be given. {DIFF_USAGE}"));
+        }
+        options.target = target;
+        Ok(())
+    };
+
+    let mut tokens = args.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token {
+            "--staged" | "--cached" => set_target(&mut options, GitDiffTarget::Staged)?,
+            "--unstaged" => set_target(&mut options, GitDiffTarget::WorkingTree)?,
+            "--" => {
+                options.paths.extend(tokens.by_ref().map(PathBuf::from));
+            }
+            flag if flag.starts_with('-') => {
+                return Err(format!("Unknown /diff option `{flag}`. {DIFF_USAGE}"));
+            }
+            range if range.contains("..") => {
+                set_target(&mut options, GitDiffTarget::Range(range.to_string()))?;
+            }
+            path => options.paths.push(PathBuf::from(path)),
+        }
+    }
+    Ok(options)
+}
+
+/// Text shown in the diff pager for `options`.
+pub(crate) async fn diff_text(cwd: &Path, options: &GitDiffOptions) -> String {
+    match get_git_diff(cwd, options).await {
+        Ok(Some(diff)) => diff.unified_diff,
+        Ok(None) => "`/diff` — _not inside a git repository_".to_string(),
+        Err(e) => format!("Failed to compute diff: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_targets_ranges_and_paths() {
+        assert_eq!(
+            parse_diff_args("").map(|o| o.target),
+            Ok(GitDiffTarget::WorkingTree)
+        );
+        assert_eq!(
+            parse_diff_args("--staged").map(|o| o.target),
+            Ok(GitDiffTarget::Staged)
+        );
+        assert_eq!(
+            parse_diff_args("HEAD~3.. -- src/lib.rs docs"),
+            Ok(GitDiffOptions {
+                target: GitDiffTarget::Range("HEAD~3..".to_string()),
+                paths: vec![PathBuf::from("src/lib.rs"), PathBuf::from("docs")],
+                color: true,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_flags_and_conflicting_targets() {
+        assert!(parse_diff_args("--stat").is_err());
+        assert!(parse_diff_args("--staged HEAD~1..").is_err());
+    }
+}
@@ -18,6 +18,7 @@ use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::workspace_roots;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
 
@@ -307,6 +308,10 @@ pub(crate) fn display_path_for(path: &Path, cwd: &Path) -> String {
         return path.display().to_string();
     }
 
+    if let Some(display) = workspace_roots::display_under_nearest_root(path, cwd) {
+        return display;
+    }
+
     if let Ok(stripped) = path.strip_prefix(cwd) {
         return stripped.display().to_string();
     }
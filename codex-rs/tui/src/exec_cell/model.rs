@@ -2,6 +2,7 @@ use std::time::Duration;
 use std::time::Instant;
 
 use codex_core::protocol::ExecCommandSource;
+use codex_core::protocol::ExecLimitKind;
 use codex_protocol::parse_command::ParsedCommand;
 
 #[derive(Clone, Debug, Default)]
@@ -11,6 +12,10 @@ pub(crate) struct CommandOutput {
     pub(crate) aggregated_output: String,
     /// The formatted output of the command, as seen by the model.
     pub(crate) formatted_output: String,
+    /// Whether the sandbox blocked the command.
+    pub(crate) denied_by_sandbox: bool,
+    /// The resource limit that stopped the command, if one did.
+    pub(crate) limit_exceeded: Option<ExecLimitKind>,
 }
 
 #[derive(Debug, Clone)]
@@ -85,6 +90,41 @@ impl ExecCell {
     }
 
     pub(crate) fn mark_failed(&mut self) {
+        self.finish_pending_calls("");
+    }
+
+    /// Closes calls whose end event never arrived before the turn completed.
+    pub(crate) fn mark_orphaned(&mut self) {
+        self.finish_pending_calls("no completion event received");
+    }
+
+    /// Replaces an existing call with the same id in place, returning whether one was found.
+    pub(crate) fn replace_call(
+        &mut self,
+        call_id: &str,
+        command: Vec<String>,
+        parsed: Vec<ParsedCommand>,
+        source: ExecCommandSource,
+        interaction_input: Option<String>,
+    ) -> bool {
+        let Some(call) = self.calls.iter_mut().rev().find(|c| c.call_id == call_id) else {
+            return false;
+        };
+        call.command = command;
+        call.parsed = parsed;
+        call.source = source;
+        call.interaction_input = interaction_input;
+        call.output = None;
+        call.start_time = Some(Instant::now());
+        call.duration = None;
+        true
+    }
+
+    pub(crate) fn contains_call(&self, call_id: &str) -> bool {
+        self.calls.iter().any(|c| c.call_id == call_id)
+    }
+
+    fn finish_pending_calls(&mut self, message: &str) {
         for call in self.calls.iter_mut() {
             if call.output.is_none() {
                 let elapsed = call
@@ -96,7 +136,9 @@ impl ExecCell {
                 call.output = Some(CommandOutput {
                     exit_code: 1,
                     formatted_output: String::new(),
-                    aggregated_output: String::new(),
+                    aggregated_output: message.to_string(),
+                    denied_by_sandbox: false,
+                    limit_exceeded: None,
                 });
             }
         }
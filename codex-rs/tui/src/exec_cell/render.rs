@@ -9,6 +9,7 @@ use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::line_utils::push_owned_lines;
 use crate::shimmer::shimmer_spans;
+use crate::text_formatting::truncate_graphemes;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use crate::wrapping::word_wrap_lines;
@@ -16,6 +17,7 @@ use codex_ansi_escape::ansi_escape_line;
 use codex_common::elapsed::format_duration;
 use codex_core::bash::extract_bash_command;
 use codex_core::protocol::ExecCommandSource;
+use codex_core::protocol::ExecLimitKind;
 use codex_protocol::parse_command::ParsedCommand;
 use itertools::Itertools;
 use ratatui::prelude::*;
@@ -26,7 +28,14 @@ use unicode_width::UnicodeWidthStr;
 
 pub(crate) const TOOL_CALL_MAX_LINES: usize = 5;
 const USER_SHELL_TOOL_CALL_MAX_LINES: usize = 50;
+/// Output lines longer than this are split before wrapping.
+const MAX_RENDERED_LINE_BYTES: usize = 2_000;
 const MAX_INTERACTION_PREVIEW_CHARS: usize = 80;
+const SANDBOX_DENIED_LABEL: &str = "🛡 blocked by sandbox";
+
+fn limit_exceeded_label(kind: ExecLimitKind) -> String {
+    format!("killed: {} exceeded", kind.description())
+}
 
 pub(crate) struct OutputLinesParams {
     pub(crate) line_limit: usize,
@@ -76,16 +85,35 @@ fn format_unified_exec_interaction(command: &[String], input: Option<&str>) -> S
 fn summarize_interaction_input(input: &str) -> String {
     let single_line = input.replace('\n', "\\n");
     let sanitized = single_line.replace('`', "\\`");
-    if sanitized.chars().count() <= MAX_INTERACTION_PREVIEW_CHARS {
+    let preview = truncate_graphemes(&sanitized, MAX_INTERACTION_PREVIEW_CHARS);
+    if preview.len() == sanitized.len() {
         return sanitized;
     }
+    format!("{preview}...")
+}
 
-    let mut preview = String::new();
-    for ch in sanitized.chars().take(MAX_INTERACTION_PREVIEW_CHARS) {
-        preview.push(ch);
-    }
-    preview.push_str("...");
-    preview
+/// Split `text` into lines, breaking any line longer than
+/// [`MAX_RENDERED_LINE_BYTES`] into pieces. A single huge line, such as
+/// minified JavaScript, then costs no more to wrap than the few pieces that
+/// are shown.
+fn split_long_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().flat_map(|line| {
+        let mut rest = Some(line);
+        std::iter::from_fn(move || {
+            let current = rest?;
+            if current.len() <= MAX_RENDERED_LINE_BYTES {
+                rest = None;
+                return Some(current);
+            }
+            let mut end = MAX_RENDERED_LINE_BYTES;
+            while !current.is_char_boundary(end) {
+                end -= 1;
+            }
+            let (head, tail) = current.split_at(end);
+            rest = Some(tail);
+            Some(head)
+        })
+    })
 }
 
 #[derive(Clone)]
@@ -123,7 +151,7 @@ pub(crate) fn output_lines(
     };
 
     let src = aggregated_output;
-    let lines: Vec<&str> = src.lines().collect();
+    let lines: Vec<&str> = split_long_lines(src).collect();
     let total = lines.len();
     let mut out: Vec<Line<'static>> = Vec::new();
 
@@ -226,7 +254,9 @@ impl HistoryCell for ExecCell {
                 if !call.is_unified_exec_interaction() {
                     let wrap_width = width.max(1) as usize;
                     let wrap_opts = RtOptions::new(wrap_width);
-                    for unwrapped in output.formatted_output.lines().map(ansi_escape_line) {
+                    for unwrapped in
+                        split_long_lines(&output.formatted_output).map(ansi_escape_line)
+                    {
                         let wrapped = word_wrap_line(&unwrapped, wrap_opts.clone());
                         push_owned_lines(&wrapped, &mut lines);
                     }
@@ -243,6 +273,14 @@ impl HistoryCell for ExecCell {
                         format!(" ({})", output.exit_code).into(),
                     ])
                 };
+                if output.denied_by_sandbox {
+                    result.push_span(" ".into());
+                    result.push_span(SANDBOX_DENIED_LABEL.magenta());
+                }
+                if let Some(kind) = output.limit_exceeded {
+                    result.push_span(" ".into());
+                    result.push_span(limit_exceeded_label(kind).red());
+                }
                 result.push_span(format!(" • {duration}").dim());
                 lines.push(result);
             }
@@ -486,6 +524,19 @@ impl ExecCell {
                     ));
                 }
             }
+
+            if output.denied_by_sandbox {
+                lines.push(Line::from(vec![
+                    Span::from(layout.output_block.subsequent_prefix),
+                    SANDBOX_DENIED_LABEL.magenta(),
+                ]));
+            }
+            if let Some(kind) = output.limit_exceeded {
+                lines.push(Line::from(vec![
+                    Span::from(layout.output_block.subsequent_prefix),
+                    limit_exceeded_label(kind).red(),
+                ]));
+            }
         }
 
         lines
@@ -613,6 +664,49 @@ mod tests {
     use super::*;
     use codex_core::protocol::ExecCommandSource;
 
+    #[test]
+    fn megabyte_line_is_split_before_rendering() {
+        let output = CommandOutput {
+            exit_code: 0,
+            aggregated_output: "x".repeat(1024 * 1024),
+            formatted_output: String::new(),
+            denied_by_sandbox: false,
+            limit_exceeded: None,
+        };
+
+        let rendered = output_lines(
+            Some(&output),
+            OutputLinesParams {
+                line_limit: TOOL_CALL_MAX_LINES,
+                only_err: false,
+                include_angle_pipe: false,
+                include_prefix: false,
+            },
+        );
+
+        assert_eq!(rendered.lines.len(), 2 * TOOL_CALL_MAX_LINES + 1);
+        assert_eq!(
+            rendered.omitted,
+            Some((1024 * 1024).div_ceil(MAX_RENDERED_LINE_BYTES) - 2 * TOOL_CALL_MAX_LINES)
+        );
+        assert!(
+            rendered
+                .lines
+                .iter()
+                .all(|line| line.width() <= MAX_RENDERED_LINE_BYTES)
+        );
+    }
+
+    #[test]
+    fn split_long_lines_respects_char_boundaries() {
+        let line = "é".repeat(MAX_RENDERED_LINE_BYTES);
+        let pieces: Vec<&str> = split_long_lines(&format!("{line}\n\nshort")).collect();
+        assert_eq!(pieces.len(), 4);
+        assert_eq!(pieces[0].len(), MAX_RENDERED_LINE_BYTES);
+        assert_eq!(pieces[1].len(), MAX_RENDERED_LINE_BYTES);
+        assert_eq!(&pieces[2..], ["", "short"]);
+    }
+
     #[test]
     fn user_shell_output_is_limited_by_screen_lines() {
         // Construct a user shell exec cell whose aggregated output consists of a
@@ -633,6 +727,8 @@ mod tests {
             exit_code: 0,
             aggregated_output,
             formatted_output: String::new(),
+            denied_by_sandbox: false,
+            limit_exceeded: None,
         };
         let width = 20;
         let layout = EXEC_DISPLAY_LAYOUT;
@@ -702,4 +798,86 @@ mod tests {
             "expected at most {USER_SHELL_TOOL_CALL_MAX_LINES} screen lines of user shell output, got {output_screen_lines}",
         );
     }
+
+    #[test]
+    fn sandbox_denied_command_shows_blocked_label() {
+        let call = ExecCall {
+            call_id: "call-id".to_string(),
+            command: vec!["bash".into(), "-lc".into(), "touch /etc/x".into()],
+            parsed: Vec::new(),
+            output: Some(CommandOutput {
+                exit_code: 1,
+                aggregated_output: "touch: /etc/x: Operation not permitted\n".to_string(),
+                formatted_output: String::new(),
+                denied_by_sandbox: true,
+                limit_exceeded: None,
+            }),
+            source: ExecCommandSource::Agent,
+            start_time: None,
+            duration: None,
+            interaction_input: None,
+        };
+        let cell = ExecCell::new(call, false);
+
+        let rendered: Vec<String> = cell
+            .command_display_lines(80)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                "• Ran touch /etc/x".to_string(),
+                "  └ touch: /etc/x: Operation not permitted".to_string(),
+                format!("    {SANDBOX_DENIED_LABEL}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn limit_exceeded_command_shows_which_limit_fired() {
+        let call = ExecCall {
+            call_id: "call-id".to_string(),
+            command: vec!["bash".into(), "-lc".into(), "yes".into()],
+            parsed: Vec::new(),
+            output: Some(CommandOutput {
+                exit_code: 137,
+                aggregated_output: "y\n".to_string(),
+                formatted_output: String::new(),
+                denied_by_sandbox: false,
+                limit_exceeded: Some(ExecLimitKind::Output),
+            }),
+            source: ExecCommandSource::Agent,
+            start_time: None,
+            duration: None,
+            interaction_input: None,
+        };
+        let cell = ExecCell::new(call, false);
+
+        let rendered: Vec<String> = cell
+            .command_display_lines(80)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                "• Ran yes".to_string(),
+                "  └ y".to_string(),
+                "    killed: output limit exceeded".to_string(),
+            ]
+        );
+    }
 }
@@ -2,20 +2,29 @@
 //!
 //! `ChatComposer` publishes every change of the `@token` as
 //! `AppEvent::StartFileSearch(query)`. This manager owns a single
-//! `codex-file-search` session for the current search root, updates the query
-//! on every keystroke, and drops the session when the query becomes empty.
+//! `codex-file-search` session spanning the cwd and the session's other
+//! workspace roots, updates the query on every keystroke, and drops the
+//! session when the query becomes empty.
+//! Files picked from the popup are remembered and ranked higher in later
+//! searches.
 
 use codex_file_search as file_search;
+use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::workspace_roots;
+
+/// Number of recently mentioned files boosted in search results.
+const MAX_RECENT_MENTIONS: usize = 32;
 
 pub(crate) struct FileSearchManager {
     state: Arc<Mutex<SearchState>>,
     search_dir: PathBuf,
+    recent_mentions: VecDeque<PathBuf>,
     app_tx: AppEventSender,
 }
 
@@ -34,6 +43,7 @@ impl FileSearchManager {
                 session_token: 0,
             })),
             search_dir,
+            recent_mentions: VecDeque::new(),
             app_tx: tx,
         }
     }
@@ -43,6 +53,19 @@ impl FileSearchManager {
     /// Drops the current session so it will be recreated with the new directory on next query.
     pub fn update_search_dir(&mut self, new_dir: PathBuf) {
         self.search_dir = new_dir;
+        self.recent_mentions.clear();
+        #[expect(clippy::unwrap_used)]
+        let mut st = self.state.lock().unwrap();
+        st.session.take();
+        st.latest_query.clear();
+    }
+
+    /// Records a file picked from the `@` popup, most recent first.
+    /// Drops the current session so the next query picks up the new ranking.
+    pub fn record_mention(&mut self, path: PathBuf) {
+        self.recent_mentions.retain(|recent| recent != &path);
+        self.recent_mentions.push_front(path);
+        self.recent_mentions.truncate(MAX_RECENT_MENTIONS);
         #[expect(clippy::unwrap_used)]
         let mut st = self.state.lock().unwrap();
         st.session.take();
@@ -80,10 +103,18 @@ impl FileSearchManager {
             app_tx: self.app_tx.clone(),
             session_token,
         });
-        let session = file_search::create_session(
-            &self.search_dir,
+        let mut search_dirs = vec![self.search_dir.clone()];
+        search_dirs.extend(
+            workspace_roots::workspace_roots()
+                .into_iter()
+                .filter(|root| root != &self.search_dir),
+        );
+        let session = file_search::create_session_for_roots(
+            search_dirs,
             file_search::FileSearchOptions {
                 compute_indices: true,
+                recent_paths: self.recent_mentions.iter().cloned().collect(),
+                boost_recently_modified: true,
                 ..Default::default()
             },
             reporter,
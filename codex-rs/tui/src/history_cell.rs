@@ -10,6 +10,7 @@
 //! bumps the active-cell revision tracked by `ChatWidget`, so the cache key changes whenever the
 //! rendered transcript output can change.
 
+use crate::citations::LineCitation;
 use crate::diff_render::create_diff_summary;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
@@ -28,6 +29,7 @@ use crate::render::renderable::Renderable;
 use crate::style::proposed_plan_style;
 use crate::style::user_message_style;
 use crate::text_formatting::format_and_truncate_tool_result;
+use crate::text_formatting::truncate_graphemes;
 use crate::text_formatting::truncate_text;
 use crate::tooltips;
 use crate::ui_consts::LIVE_PREFIX_COLS;
@@ -40,10 +42,22 @@ use base64::Engine;
 use codex_common::format_env_display::format_env_display;
 use codex_core::config::Config;
 use codex_core::config::types::McpServerTransportConfig;
+use codex_core::doctor::CheckStatus;
+use codex_core::doctor::DoctorReport;
+use codex_core::protocol::ApprovalGrantItem;
+use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::FileChange;
+use codex_core::protocol::McpArgumentError;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::ModelListSource;
+use codex_core::protocol::PatchFileResult;
+use codex_core::protocol::PatchFileStatus;
+use codex_core::protocol::PrewarmPhase;
+use codex_core::protocol::RequestErrorDetails;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::StartupAuthMode;
+use codex_core::protocol::StartupDiagnostics;
 use codex_core::web_search::web_search_detail;
 use codex_protocol::models::WebSearchAction;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
@@ -70,10 +84,11 @@ use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
 use tracing::error;
-use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 /// Represents an event to display in the conversation history. Returns its
@@ -117,6 +132,15 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
         false
     }
 
+    /// Lines written to scrollback when `tui.accessible` is set.
+    ///
+    /// Cells whose structure is only conveyed by bullets, prompts or borders
+    /// override this to lead with a plain text label (see [`accessible_label`])
+    /// instead, so a screen reader announces what kind of content follows.
+    fn accessible_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.display_lines(width)
+    }
+
     /// Returns a coarse "animation tick" when transcript output is time-dependent.
     ///
     /// The transcript overlay caches the rendered output of the in-flight active cell, so cells
@@ -132,6 +156,12 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
     }
 }
 
+/// Label line that introduces a cell in accessible mode, e.g.
+/// `--- assistant message ---`.
+pub(crate) fn accessible_label(label: &str) -> Line<'static> {
+    Line::from(format!("--- {label} ---"))
+}
+
 impl Renderable for Box<dyn HistoryCell> {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         let lines = self.display_lines(area.width);
@@ -162,6 +192,8 @@ impl dyn HistoryCell {
 
 #[derive(Debug)]
 pub(crate) struct UserHistoryCell {
+    /// Exactly what the user submitted. Rendering never rewrites it, so
+    /// backtracking and the rollout see the original text.
     pub message: String,
     pub text_elements: Vec<TextElement>,
     #[allow(dead_code)]
@@ -234,10 +266,17 @@ fn build_user_message_lines_with_elements(
     raw_lines
 }
 
-impl HistoryCell for UserHistoryCell {
-    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
-        let mut lines: Vec<Line<'static>> = Vec::new();
+/// Wrapped lines after which a user message is collapsed in the main view.
+/// The transcript overlay always shows the whole message.
+const USER_MESSAGE_MAX_LINES: usize = 20;
 
+impl UserHistoryCell {
+    /// The message wrapped to `width`, without the prompt prefix.
+    ///
+    /// Plain messages go through the same markdown renderer as agent messages,
+    /// on top of the user message background. Messages with text elements keep
+    /// the line-based renderer, since element byte ranges refer to the raw text.
+    fn wrapped_lines(&self, width: u16) -> Vec<Line<'static>> {
         let wrap_width = width
             .saturating_sub(
                 LIVE_PREFIX_COLS + 1, /* keep a one-column right margin for wrapping */
@@ -247,27 +286,35 @@ impl HistoryCell for UserHistoryCell {
         let style = user_message_style();
         let element_style = style.fg(Color::Cyan);
 
-        let wrapped = if self.text_elements.is_empty() {
-            word_wrap_lines(
-                self.message.split('\n').map(|l| Line::from(l).style(style)),
-                // Wrap algorithm matches textarea.rs.
-                RtOptions::new(usize::from(wrap_width))
-                    .wrap_algorithm(textwrap::WrapAlgorithm::FirstFit),
-            )
+        let raw_lines = if self.text_elements.is_empty() {
+            let mut rendered = Vec::new();
+            append_markdown(&self.message, None, &mut rendered);
+            rendered
+                .into_iter()
+                .map(|line| {
+                    let line_style = style.patch(line.style);
+                    line.style(line_style)
+                })
+                .collect()
         } else {
-            let raw_lines = build_user_message_lines_with_elements(
+            build_user_message_lines_with_elements(
                 &self.message,
                 &self.text_elements,
                 style,
                 element_style,
-            );
-            word_wrap_lines(
-                raw_lines,
-                RtOptions::new(usize::from(wrap_width))
-                    .wrap_algorithm(textwrap::WrapAlgorithm::FirstFit),
             )
         };
+        word_wrap_lines(
+            raw_lines,
+            // Wrap algorithm matches textarea.rs.
+            RtOptions::new(usize::from(wrap_width))
+                .wrap_algorithm(textwrap::WrapAlgorithm::FirstFit),
+        )
+    }
 
+    fn render(&self, wrapped: Vec<Line<'static>>) -> Vec<Line<'static>> {
+        let style = user_message_style();
+        let mut lines: Vec<Line<'static>> = Vec::new();
         lines.push(Line::from("").style(style));
         lines.extend(prefix_lines(wrapped, "› ".bold().dim(), "  ".into()));
         lines.push(Line::from("").style(style));
@@ -275,6 +322,35 @@ impl HistoryCell for UserHistoryCell {
     }
 }
 
+impl HistoryCell for UserHistoryCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut wrapped = self.wrapped_lines(width);
+        if wrapped.len() > USER_MESSAGE_MAX_LINES {
+            let hidden = wrapped.len() - (USER_MESSAGE_MAX_LINES - 1);
+            wrapped.truncate(USER_MESSAGE_MAX_LINES - 1);
+            wrapped.push(
+                Line::from(format!("… +{hidden} lines (ctrl + t to view all)").dim())
+                    .style(user_message_style()),
+            );
+        }
+        self.render(wrapped)
+    }
+
+    fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.render(self.wrapped_lines(width))
+    }
+
+    fn accessible_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut lines = vec![accessible_label("user message")];
+        lines.extend(
+            self.wrapped_lines(width)
+                .into_iter()
+                .map(|line| line.style(Style::default())),
+        );
+        lines
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct ReasoningSummaryCell {
     _header: String,
@@ -344,12 +420,22 @@ impl HistoryCell for ReasoningSummaryCell {
     fn desired_transcript_height(&self, width: u16) -> u16 {
         self.lines(width).len() as u16
     }
+
+    fn accessible_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if self.transcript_only {
+            return Vec::new();
+        }
+        let mut lines = vec![accessible_label("reasoning")];
+        append_markdown(&self.content, Some(width as usize), &mut lines);
+        lines
+    }
 }
 
 #[derive(Debug)]
 pub(crate) struct AgentMessageCell {
     lines: Vec<Line<'static>>,
     is_first_line: bool,
+    citations: Vec<LineCitation>,
 }
 
 impl AgentMessageCell {
@@ -357,8 +443,20 @@ impl AgentMessageCell {
         Self {
             lines,
             is_first_line,
+            citations: Vec::new(),
         }
     }
+
+    pub(crate) fn with_citations(mut self, citations: Vec<LineCitation>) -> Self {
+        self.citations = citations;
+        self
+    }
+
+    /// Files referenced on this cell's lines, for front ends that open them.
+    #[allow(dead_code)]
+    pub(crate) fn citations(&self) -> &[LineCitation] {
+        &self.citations
+    }
 }
 
 impl HistoryCell for AgentMessageCell {
@@ -378,6 +476,15 @@ impl HistoryCell for AgentMessageCell {
     fn is_stream_continuation(&self) -> bool {
         !self.is_first_line
     }
+
+    fn accessible_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        if self.is_first_line {
+            lines.push(accessible_label("assistant message"));
+        }
+        lines.extend(word_wrap_lines(&self.lines, RtOptions::new(width as usize)));
+        lines
+    }
 }
 
 #[derive(Debug)]
@@ -606,10 +713,9 @@ impl HistoryCell for UnifiedExecProcessesCell {
                     Some((first, _)) => (first, true),
                     None => (command.as_str(), false),
                 };
-                let max_graphemes = 80;
-                let mut graphemes = first_line.grapheme_indices(true);
-                if let Some((byte_index, _)) = graphemes.nth(max_graphemes) {
-                    (first_line[..byte_index].to_string(), true)
+                let snippet = truncate_graphemes(first_line, 80);
+                if snippet.len() < first_line.len() {
+                    (snippet.to_string(), true)
                 } else {
                     (first_line.to_string(), has_more_lines)
                 }
@@ -786,6 +892,51 @@ pub fn new_approval_decision_cell(
     ))
 }
 
+/// Read-only record of a resolved approval request, rebuilt from the rollout
+/// when a session is resumed.
+pub fn new_approval_resolution_cell(
+    ev: &codex_core::protocol::ApprovalResolvedEvent,
+) -> Box<dyn HistoryCell> {
+    use codex_core::protocol::ApprovalKind;
+    use codex_core::protocol::ApprovalResolver;
+    use codex_core::protocol::ReviewDecision::*;
+
+    let (symbol, verb) = match &ev.decision {
+        Approved => ("✔ ".green(), "Approved"),
+        ApprovedExecpolicyAmendment { .. } => ("✔ ".green(), "Always approved"),
+        ApprovedForSession => ("✔ ".green(), "Approved for the session"),
+        Denied => ("✗ ".red(), "Denied"),
+        Abort => ("✗ ".red(), "Canceled"),
+    };
+    let subject = match ev.kind {
+        ApprovalKind::Exec => exec_snippet(&ev.command),
+        ApprovalKind::Patch => ev
+            .paths
+            .iter()
+            .map(|path| display_path_for(path, &ev.cwd))
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+
+    let mut summary: Vec<Span<'static>> = vec![verb.bold(), " ".into(), Span::from(subject).dim()];
+    match ev.resolved_by {
+        ApprovalResolver::User => {
+            if let Some(account) = &ev.account {
+                summary.push(format!(" by {account}").into());
+            }
+        }
+        ApprovalResolver::Timeout => summary.push(" (timed out)".into()),
+        ApprovalResolver::TurnEnded => summary.push(" (turn ended)".into()),
+    }
+    summary.push(format!(" at {}", ev.resolved_at).dim());
+
+    Box::new(PrefixedWrappedHistoryCell::new(
+        Line::from(summary),
+        symbol,
+        "  ",
+    ))
+}
+
 /// Cyan history cell line showing the current review status.
 pub(crate) fn new_review_status_line(message: String) -> PlainHistoryCell {
     PlainHistoryCell {
@@ -935,6 +1086,10 @@ impl HistoryCell for SessionInfoCell {
     fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
         self.0.transcript_lines(width)
     }
+
+    fn accessible_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.0.accessible_lines(width)
+    }
 }
 
 pub(crate) fn new_session_info(
@@ -942,20 +1097,40 @@ pub(crate) fn new_session_info(
     requested_model: &str,
     event: SessionConfiguredEvent,
     is_first_event: bool,
+    startup: Option<Arc<Mutex<StartupDiagnostics>>>,
 ) -> SessionInfoCell {
     let SessionConfiguredEvent {
         model,
         reasoning_effort,
+        unknown_model,
         ..
     } = event;
+    let model_style = match unknown_model.as_ref().map(|unknown| unknown.source) {
+        Some(ModelListSource::Provider) => Style::default().red(),
+        Some(ModelListSource::Builtin) => Style::default().yellow(),
+        None => Style::default(),
+    };
     // Header box rendered as history (so it appears at the very top)
-    let header = SessionHeaderHistoryCell::new(
+    let header = SessionHeaderHistoryCell::new_with_style(
         model.clone(),
+        model_style,
         reasoning_effort,
         config.cwd.clone(),
         CODEX_CLI_VERSION,
     );
     let mut parts: Vec<Box<dyn HistoryCell>> = vec![Box::new(header)];
+    // Shown before anything else so it is read before the first prompt.
+    if let Some(unknown_model) = unknown_model {
+        let message = format!("{} Use /model to pick another.", unknown_model.message());
+        let cell: Box<dyn HistoryCell> = match unknown_model.source {
+            ModelListSource::Provider => Box::new(new_error_event(message)),
+            ModelListSource::Builtin => Box::new(new_warning_event(message)),
+        };
+        parts.push(cell);
+    }
+    if let Some(diagnostics) = startup {
+        parts.push(Box::new(StartupDiagnosticsCell { diagnostics }));
+    }
 
     if is_first_event {
         // Help lines below the header (new copy and list)
@@ -1011,6 +1186,168 @@ pub(crate) fn new_session_info(
     SessionInfoCell(CompositeHistoryCell { parts })
 }
 
+/// Startup diagnostics under the session header: a single line in the main
+/// view and the full report in the transcript overlay. MCP servers connect
+/// after the cell is inserted, so their results arrive through the shared
+/// diagnostics and only the transcript shows them.
+#[derive(Debug)]
+pub(crate) struct StartupDiagnosticsCell {
+    diagnostics: Arc<Mutex<StartupDiagnostics>>,
+}
+
+impl StartupDiagnosticsCell {
+    fn diagnostics(&self) -> StartupDiagnostics {
+        self.diagnostics
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl HistoryCell for StartupDiagnosticsCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        let diagnostics = self.diagnostics();
+        let mut summary = vec![describe_startup_auth(&diagnostics)];
+        if let Some(probe) = &diagnostics.provider_probe {
+            summary.push(if probe.reachable {
+                format!("provider {} ms", probe.latency_ms)
+            } else {
+                "provider unreachable".to_string()
+            });
+        }
+        summary.push(match diagnostics.project_docs.len() {
+            1 => "1 AGENTS.md".to_string(),
+            count => format!("{count} AGENTS.md files"),
+        });
+        match diagnostics.mcp_servers.len() {
+            0 => {}
+            1 => summary.push("1 MCP server".to_string()),
+            count => summary.push(format!("{count} MCP servers")),
+        }
+        if let Some(slowest) = diagnostics.phases.iter().max_by_key(|p| p.duration_ms) {
+            summary.push(format!(
+                "slowest: {} {} ms",
+                prewarm_phase_label(slowest.phase),
+                slowest.duration_ms
+            ));
+        }
+        vec![
+            vec![
+                "  Startup: ".dim(),
+                summary.join(" · ").dim(),
+                " · ctrl + t to view details".dim(),
+            ]
+            .into(),
+        ]
+    }
+
+    fn transcript_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        let diagnostics = self.diagnostics();
+        let mut lines: Vec<Line<'static>> = vec!["  Startup".bold().into()];
+        let phases = diagnostics
+            .phases
+            .iter()
+            .map(|p| format!("{} {} ms", prewarm_phase_label(p.phase), p.duration_ms))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(vec!["    phases: ".dim(), phases.into()].into());
+        lines.push(
+            vec![
+                "    auth: ".dim(),
+                describe_startup_auth(&diagnostics).into(),
+            ]
+            .into(),
+        );
+        if let Some(probe) = &diagnostics.provider_probe {
+            let probe = if probe.reachable {
+                format!("reachable in {} ms", probe.latency_ms)
+            } else {
+                format!("unreachable (gave up after {} ms)", probe.latency_ms)
+            };
+            lines.push(vec!["    provider: ".dim(), probe.into()].into());
+        }
+        if diagnostics.project_docs.is_empty() {
+            lines.push(vec!["    AGENTS.md: ".dim(), "none".into()].into());
+        } else {
+            lines.push("    AGENTS.md:".dim().into());
+            for doc in &diagnostics.project_docs {
+                lines.push(
+                    vec![
+                        format!("      {}", doc.path.display()).into(),
+                        format!(" ({})", format_doc_size(doc.bytes)).dim(),
+                    ]
+                    .into(),
+                );
+            }
+        }
+        if !diagnostics.mcp_servers.is_empty() {
+            lines.push("    MCP servers:".dim().into());
+            for server in &diagnostics.mcp_servers {
+                let status = match (server.connect_ms, &server.error) {
+                    (Some(ms), Some(error)) => format!("failed after {ms} ms: {error}").red(),
+                    (Some(ms), None) => match server.tools {
+                        Some(1) => format!("{ms} ms, 1 tool").into(),
+                        Some(tools) => format!("{ms} ms, {tools} tools").into(),
+                        None => format!("{ms} ms").into(),
+                    },
+                    (None, Some(error)) => error.clone().red(),
+                    (None, None) => "not connected".dim(),
+                };
+                lines.push(vec![format!("      {}: ", server.server).into(), status].into());
+            }
+        }
+        lines
+    }
+}
+
+fn describe_startup_auth(diagnostics: &StartupDiagnostics) -> String {
+    let auth = &diagnostics.auth;
+    let mode = match auth.mode {
+        StartupAuthMode::ApiKey => "API key",
+        StartupAuthMode::Chatgpt => "ChatGPT",
+        StartupAuthMode::ChatgptAuthTokens => "ChatGPT (client tokens)",
+        StartupAuthMode::ProviderKey => "provider key",
+        StartupAuthMode::None => "no credentials",
+    };
+    if auth.refresh_due {
+        return format!("{mode}, token refresh due");
+    }
+    match auth.token_age_secs {
+        Some(age) => format!("{mode}, token refreshed {} ago", format_token_age(age)),
+        None => mode.to_string(),
+    }
+}
+
+fn format_token_age(secs: u64) -> String {
+    match secs {
+        0..120 => format!("{secs} s"),
+        120..7_200 => format!("{} min", secs / 60),
+        7_200..172_800 => format!("{} h", secs / 3_600),
+        _ => format!("{} days", secs / 86_400),
+    }
+}
+
+fn format_doc_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+fn prewarm_phase_label(phase: PrewarmPhase) -> &'static str {
+    match phase {
+        PrewarmPhase::Config => "config",
+        PrewarmPhase::ProjectDocs => "project docs",
+        PrewarmPhase::Auth => "auth",
+        PrewarmPhase::ModelCheck => "model check",
+        PrewarmPhase::Rollout => "rollout",
+        PrewarmPhase::McpServers => "MCP servers",
+        PrewarmPhase::Sandbox => "sandbox",
+        PrewarmPhase::ProviderProbe => "provider probe",
+    }
+}
+
 pub(crate) fn new_user_prompt(
     message: String,
     text_elements: Vec<TextElement>,
@@ -1101,14 +1438,9 @@ impl SessionHeaderHistoryCell {
             ReasoningEffortConfig::None => "none",
         })
     }
-}
-
-impl HistoryCell for SessionHeaderHistoryCell {
-    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
-        let Some(inner_width) = card_inner_width(width, SESSION_HEADER_MAX_INNER_WIDTH) else {
-            return Vec::new();
-        };
 
+    /// Rows of the header card, without the border, fitted to `inner_width`.
+    fn rows(&self, inner_width: usize) -> Vec<Line<'static>> {
         let make_row = |spans: Vec<Span<'static>>| Line::from(spans);
 
         // Title line rendered inside the box: ">_ OpenAI Codex (vX)"
@@ -1152,14 +1484,27 @@ impl HistoryCell for SessionHeaderHistoryCell {
         let dir = self.format_directory(Some(dir_max_width));
         let dir_spans = vec![Span::from(dir_prefix).dim(), Span::from(dir)];
 
-        let lines = vec![
+        vec![
             make_row(title_spans),
             make_row(Vec::new()),
             make_row(model_spans),
             make_row(dir_spans),
-        ];
+        ]
+    }
+}
+
+impl HistoryCell for SessionHeaderHistoryCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let Some(inner_width) = card_inner_width(width, SESSION_HEADER_MAX_INNER_WIDTH) else {
+            return Vec::new();
+        };
+        with_border(self.rows(inner_width))
+    }
 
-        with_border(lines)
+    fn accessible_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut lines = vec![accessible_label("session")];
+        lines.extend(self.rows(usize::from(width)));
+        lines
     }
 }
 
@@ -1172,14 +1517,16 @@ impl CompositeHistoryCell {
     pub(crate) fn new(parts: Vec<Box<dyn HistoryCell>>) -> Self {
         Self { parts }
     }
-}
 
-impl HistoryCell for CompositeHistoryCell {
-    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+    /// Joins the non-empty `render`ed parts with blank lines.
+    fn join_parts(
+        &self,
+        render: impl Fn(&dyn HistoryCell) -> Vec<Line<'static>>,
+    ) -> Vec<Line<'static>> {
         let mut out: Vec<Line<'static>> = Vec::new();
         let mut first = true;
         for part in &self.parts {
-            let mut lines = part.display_lines(width);
+            let mut lines = render(part.as_ref());
             if !lines.is_empty() {
                 if !first {
                     out.push(Line::from(""));
@@ -1192,6 +1539,20 @@ impl HistoryCell for CompositeHistoryCell {
     }
 }
 
+impl HistoryCell for CompositeHistoryCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.join_parts(|part| part.display_lines(width))
+    }
+
+    fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.join_parts(|part| part.transcript_lines(width))
+    }
+
+    fn accessible_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.join_parts(|part| part.accessible_lines(width))
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct McpToolCallCell {
     call_id: String,
@@ -1199,6 +1560,7 @@ pub(crate) struct McpToolCallCell {
     start_time: Instant,
     duration: Option<Duration>,
     result: Option<Result<mcp_types::CallToolResult, String>>,
+    argument_errors: Vec<McpArgumentError>,
     animations_enabled: bool,
 }
 
@@ -1214,6 +1576,7 @@ impl McpToolCallCell {
             start_time: Instant::now(),
             duration: None,
             result: None,
+            argument_errors: Vec::new(),
             animations_enabled,
         }
     }
@@ -1222,6 +1585,12 @@ impl McpToolCallCell {
         &self.call_id
     }
 
+    /// Record that Codex rejected the call because its arguments did not match
+    /// the tool's schema, so it is rendered apart from server errors.
+    pub(crate) fn set_argument_errors(&mut self, argument_errors: Vec<McpArgumentError>) {
+        self.argument_errors = argument_errors;
+    }
+
     pub(crate) fn complete(
         &mut self,
         duration: Duration,
@@ -1243,9 +1612,22 @@ impl McpToolCallCell {
     }
 
     pub(crate) fn mark_failed(&mut self) {
+        self.finish_with_error("interrupted");
+    }
+
+    /// Closes a call whose end event never arrived before the turn completed.
+    pub(crate) fn mark_orphaned(&mut self) {
+        self.finish_with_error("no result received");
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.result.is_none()
+    }
+
+    fn finish_with_error(&mut self, message: &str) {
         let elapsed = self.start_time.elapsed();
         self.duration = Some(elapsed);
-        self.result = Some(Err("interrupted".to_string()));
+        self.result = Some(Err(message.to_string()));
     }
 
     fn render_content_block(block: &mcp_types::ContentBlock, width: usize) -> String {
@@ -1278,7 +1660,9 @@ impl HistoryCell for McpToolCallCell {
             Some(false) => "•".red().bold(),
             None => spinner(Some(self.start_time), self.animations_enabled),
         };
-        let header_text = if status.is_some() {
+        let header_text = if !self.argument_errors.is_empty() {
+            "Rejected"
+        } else if status.is_some() {
             "Called"
         } else {
             "Calling"
@@ -1311,7 +1695,28 @@ impl HistoryCell for McpToolCallCell {
         // Reserve four columns for the tree prefix ("  └ "/"    ") and ensure the wrapper still has at least one cell to work with.
         let detail_wrap_width = (width as usize).saturating_sub(4).max(1);
 
-        if let Some(result) = &self.result {
+        if !self.argument_errors.is_empty() {
+            detail_lines.push(Line::from(
+                "Invalid arguments, not sent to the server:".red(),
+            ));
+            for error in &self.argument_errors {
+                let field = match error.path.strip_prefix('/') {
+                    Some(path) => path.replace('/', "."),
+                    None => "arguments".to_string(),
+                };
+                let line = Line::from(vec![
+                    format!("{field}: ").red(),
+                    error.message.clone().dim(),
+                ]);
+                let wrapped = word_wrap_line(
+                    &line,
+                    RtOptions::new(detail_wrap_width)
+                        .initial_indent("".into())
+                        .subsequent_indent("    ".into()),
+                );
+                detail_lines.extend(wrapped.iter().map(line_to_static));
+            }
+        } else if let Some(result) = &self.result {
             match result {
                 Ok(mcp_types::CallToolResult { content, .. }) => {
                     if !content.is_empty() {
@@ -1556,6 +1961,41 @@ impl HistoryCell for DeprecationNoticeCell {
     }
 }
 
+/// Render the `/doctor` report: one line per check, followed by its details.
+pub(crate) fn new_doctor_output(report: &DoctorReport) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec!["/doctor".magenta().into(), "".into()];
+    for check in &report.checks {
+        let status = match check.status {
+            CheckStatus::Ok => "✓".green(),
+            CheckStatus::Warning => "!".magenta(),
+            CheckStatus::Error => "✗".red(),
+            CheckStatus::Skipped => "-".dim(),
+        };
+        lines.push(
+            vec![
+                "  ".into(),
+                status,
+                " ".into(),
+                check.name.clone().bold(),
+                "  ".into(),
+                check.summary.clone().into(),
+                format!(" ({} ms)", check.duration_ms).dim(),
+            ]
+            .into(),
+        );
+        for detail in &check.details {
+            lines.push(vec!["      ".into(), detail.clone().dim()].into());
+        }
+    }
+    lines.push("".into());
+    lines.push(
+        "  Run `codex doctor --json` for a redacted report to attach to bug reports."
+            .dim()
+            .into(),
+    );
+    PlainHistoryCell { lines }
+}
+
 /// Render a summary of configured MCP servers from the current `Config`.
 pub(crate) fn empty_mcp_output() -> PlainHistoryCell {
     let lines: Vec<Line<'static>> = vec![
@@ -1576,6 +2016,35 @@ pub(crate) fn empty_mcp_output() -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
+/// Render one tool from `/tools` with its definition as sent to the model.
+pub(crate) fn new_tool_spec_output(
+    name: &str,
+    source_label: &str,
+    spec: &serde_json::Value,
+) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        "/tools".magenta().into(),
+        "".into(),
+        vec![
+            "• ".dim(),
+            name.to_string().bold(),
+            format!(" · {source_label}").dim(),
+        ]
+        .into(),
+    ];
+    if let Some(description) = spec.get("description").and_then(serde_json::Value::as_str) {
+        for line in description.lines() {
+            lines.push(vec!["  ".into(), line.to_string().into()].into());
+        }
+    }
+    let schema = spec.get("parameters").unwrap_or(spec);
+    let pretty = serde_json::to_string_pretty(schema).unwrap_or_else(|_| schema.to_string());
+    for line in pretty.lines() {
+        lines.push(vec!["  ".into(), line.to_string().dim()].into());
+    }
+    PlainHistoryCell { lines }
+}
+
 /// Render MCP tools grouped by connection using the fully-qualified tool names.
 pub(crate) fn new_mcp_tools_output(
     config: &Config,
@@ -1751,14 +2220,176 @@ pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHist
     PlainHistoryCell { lines }
 }
 
+/// Guidance the user sent to a running turn with Alt+Enter or `/steer`.
+/// Deferred steers missed their turn and started the next one instead.
+pub(crate) fn new_steer_message(text: String, deferred: bool) -> PlainHistoryCell {
+    let mut header = vec!["↳ ".cyan(), "steer".cyan().bold()];
+    if deferred {
+        header.push(" (sent as a new turn)".dim());
+    }
+    let mut lines: Vec<Line<'static>> = vec![header.into()];
+    lines.extend(
+        text.lines()
+            .map(|line| vec!["  ".into(), line.to_string().into()].into()),
+    );
+    PlainHistoryCell { lines }
+}
+
+/// Lists the "approve for session" decisions restored on resume so the user
+/// can review them, and points at `/revoke` for forgetting them.
+pub(crate) fn new_restored_approvals_event(
+    grants: &[ApprovalGrantItem],
+    discarded: u32,
+) -> PlainHistoryCell {
+    let header = match grants.len() {
+        1 => "Restored 1 approval from earlier in this session".to_string(),
+        count => format!("Restored {count} approvals from earlier in this session"),
+    };
+    let mut lines: Vec<Line<'static>> = vec![vec!["• ".dim(), header.into()].into()];
+    for grant in grants {
+        lines.push(vec!["  └ ".dim(), describe_approval_grant(grant).into()].into());
+    }
+    if discarded > 0 {
+        lines.push(
+            format!("  {discarded} granted in a different directory were not restored")
+                .dim()
+                .into(),
+        );
+    }
+    if !grants.is_empty() {
+        lines.push("  Run /revoke to forget them.".dark_gray().into());
+    }
+    PlainHistoryCell { lines }
+}
+
+/// Approval keys are the serialized cache keys of the tool runtimes: a file
+/// path for `apply_patch`, or an object carrying `command` for command tools.
+fn describe_approval_grant(grant: &ApprovalGrantItem) -> String {
+    match serde_json::from_str::<serde_json::Value>(&grant.key) {
+        Ok(serde_json::Value::String(path)) => {
+            let path = PathBuf::from(path);
+            let relative = path.strip_prefix(&grant.cwd).unwrap_or(&path);
+            format!("edits to {}", relative.display())
+        }
+        Ok(serde_json::Value::Object(fields)) => {
+            let command: Vec<String> = fields
+                .get("command")
+                .and_then(serde_json::Value::as_array)
+                .map(|parts| {
+                    parts
+                        .iter()
+                        .filter_map(serde_json::Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            if command.is_empty() {
+                grant.tool.clone()
+            } else {
+                strip_bash_lc_and_escape(&command)
+            }
+        }
+        _ => grant.tool.clone(),
+    }
+}
+
 pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
     // Use a hair space (U+200A) to create a subtle, near-invisible separation
     // before the text. VS16 is intentionally omitted to keep spacing tighter
     // in terminals like Ghostty.
-    let lines: Vec<Line<'static>> = vec![vec![format!("■ {message}").red()].into()];
+    PlainHistoryCell {
+        lines: vec![vec![format!("■ {message}").red()].into()],
+    }
+}
+
+/// The model declined the request, or the content filter blocked it, so the
+/// turn has no answer.
+pub(crate) fn new_refusal_event(reason: String) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> =
+        vec![vec!["⊘ ".magenta(), "Refused".magenta().bold()].into()];
+    lines.extend(
+        reason
+            .lines()
+            .map(|line| vec!["  ".into(), line.to_string().italic()].into()),
+    );
     PlainHistoryCell { lines }
 }
 
+/// A failed turn: the headline, a remediation hint, and the identifiers of the
+/// failed model request. The full error text is only shown in the transcript
+/// overlay, so raw library errors do not crowd the main view.
+#[derive(Debug)]
+pub(crate) struct ErrorCell {
+    lines: Vec<Line<'static>>,
+    details: Option<String>,
+}
+
+impl HistoryCell for ErrorCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        let mut lines = self.lines.clone();
+        if self.details.is_some() {
+            lines.push("  ctrl + t to view details".dim().into());
+        }
+        lines
+    }
+
+    fn transcript_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        let mut lines = self.lines.clone();
+        if let Some(details) = &self.details {
+            lines.extend(details.lines().map(|line| format!("  {line}").dim().into()));
+        }
+        lines
+    }
+}
+
+/// Error cell that also lists the identifiers of a failed model request, so
+/// users can quote them when reporting the problem.
+pub(crate) fn new_request_error_event(event: ErrorEvent) -> ErrorCell {
+    let ErrorEvent {
+        message,
+        request_details,
+        hint,
+        details,
+        ..
+    } = event;
+    let mut lines = new_error_event(message).lines;
+    if let Some(hint) = hint {
+        lines.push(vec!["  ".into(), hint.cyan()].into());
+    }
+    let cell = |lines: Vec<Line<'static>>| ErrorCell { lines, details };
+    let Some(RequestErrorDetails {
+        request_id,
+        error_type,
+        error_code,
+        attempts,
+    }) = request_details
+    else {
+        return cell(lines);
+    };
+    if let Some(request_id) = request_id {
+        lines.push(
+            vec![
+                "  request id ".dim(),
+                request_id.into(),
+                " — include this when reporting".dim(),
+            ]
+            .into(),
+        );
+    }
+    let facts: Vec<String> = [
+        error_type.map(|error_type| format!("type {error_type}")),
+        error_code.map(|error_code| format!("code {error_code}")),
+        attempts.map(|attempts| format!("attempts {attempts}")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !facts.is_empty() {
+        lines.push(vec![format!("  {}", facts.join(" · ")).dim()].into());
+    }
+    cell(lines)
+}
+
 /// Render a user‑friendly plan update styled like a checkbox todo list.
 pub(crate) fn new_plan_update(update: UpdatePlanArgs) -> PlanUpdateCell {
     let UpdatePlanArgs { explanation, plan } = update;
@@ -1895,11 +2526,43 @@ pub(crate) fn new_patch_event(
     }
 }
 
-pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
+pub(crate) fn new_patch_apply_failure(
+    stderr: String,
+    per_file: &[PatchFileResult],
+    cwd: &Path,
+) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = Vec::new();
 
     // Failure title
-    lines.push(Line::from("✘ Failed to apply patch".magenta().bold()));
+    let interrupted = !per_file.is_empty()
+        && per_file
+            .iter()
+            .all(|result| result.status == PatchFileStatus::Aborted);
+    if interrupted {
+        lines.push(Line::from(
+            "✘ Patch interrupted before it finished".magenta().bold(),
+        ));
+    } else {
+        lines.push(Line::from("✘ Failed to apply patch".magenta().bold()));
+    }
+
+    // Per-file outcome, so a partially applied patch shows what was written.
+    if !interrupted && per_file.len() > 1 {
+        for result in per_file {
+            let path = display_path_for(&result.path, cwd);
+            let line: Line<'static> = match result.status {
+                PatchFileStatus::Applied => vec!["  ✓ ".green(), path.into()].into(),
+                PatchFileStatus::Failed => vec!["  ✗ ".red(), path.into()].into(),
+                PatchFileStatus::Skipped => {
+                    vec!["  - ".dim(), path.dim(), " (skipped)".dim()].into()
+                }
+                PatchFileStatus::Aborted => {
+                    vec!["  - ".dim(), path.dim(), " (interrupted)".dim()].into()
+                }
+            };
+            lines.push(line);
+        }
+    }
 
     if !stderr.trim().is_empty() {
         let output = output_lines(
@@ -1907,6 +2570,8 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
                 exit_code: 1,
                 formatted_output: String::new(),
                 aggregated_output: stderr,
+                denied_by_sandbox: false,
+                limit_exceeded: None,
             }),
             OutputLinesParams {
                 line_limit: TOOL_CALL_MAX_LINES,
@@ -2023,6 +2688,7 @@ mod tests {
     use crate::exec_cell::ExecCell;
     use codex_core::config::Config;
     use codex_core::config::ConfigBuilder;
+    use codex_core::config::types::McpArgumentValidation;
     use codex_core::config::types::McpServerConfig;
     use codex_core::config::types::McpServerTransportConfig;
     use codex_core::protocol::McpAuthStatus;
@@ -2183,6 +2849,7 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            argument_validation: McpArgumentValidation::Strict,
         };
         let mut servers = config.mcp_servers.get().clone();
         servers.insert("docs".to_string(), stdio_config);
@@ -2205,6 +2872,7 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            argument_validation: McpArgumentValidation::Strict,
         };
         servers.insert("http".to_string(), http_config);
         config
@@ -2476,6 +3144,38 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn mcp_tool_call_with_invalid_arguments_lists_each_field() {
+        let invocation = McpInvocation {
+            server: "search".into(),
+            tool: "find_docs".into(),
+            arguments: Some(json!({ "limit": "three" })),
+        };
+
+        let mut cell = new_active_mcp_tool_call("call-4".into(), invocation, true);
+        cell.set_argument_errors(vec![
+            McpArgumentError {
+                path: String::new(),
+                message: "\"query\" is a required property".to_string(),
+            },
+            McpArgumentError {
+                path: "/limit".to_string(),
+                message: "\"three\" is not of type \"integer\"".to_string(),
+            },
+        ]);
+        cell.complete(Duration::ZERO, Err("invalid arguments".into()));
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "• Rejected search.find_docs({\"limit\":\"three\"})",
+                "  └ Invalid arguments, not sent to the server:",
+                "    arguments: \"query\" is a required property",
+                "    limit: \"three\" is not of type \"integer\"",
+            ]
+        );
+    }
+
     #[test]
     fn completed_mcp_tool_call_multiple_outputs_snapshot() {
         let invocation = McpInvocation {
@@ -2918,6 +3618,8 @@ mod tests {
                 exit_code: 1,
                 formatted_output: String::new(),
                 aggregated_output: stderr,
+                denied_by_sandbox: false,
+                limit_exceeded: None,
             },
             Duration::from_millis(1),
         );
@@ -2966,6 +3668,8 @@ mod tests {
                 exit_code: 1,
                 formatted_output: String::new(),
                 aggregated_output: stderr,
+                denied_by_sandbox: false,
+                limit_exceeded: None,
             },
             Duration::from_millis(5),
         );
@@ -3002,6 +3706,72 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn user_history_cell_renders_fenced_code_block_snapshot() {
+        let cell = UserHistoryCell {
+            message: "Why does this fail?\n\n```rust\nfn main() {\n    let x: u8 = 256;\n}\n```\n\nIt only breaks on **release** builds.".to_string(),
+            text_elements: Vec::new(),
+            local_image_paths: Vec::new(),
+        };
+
+        let rendered = render_lines(&cell.display_lines(80)).join("\n");
+
+        insta::assert_snapshot!(rendered);
+    }
+
+    #[test]
+    fn long_user_message_collapses_outside_the_transcript() {
+        let message = (1..=30)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let cell = UserHistoryCell {
+            message: message.clone(),
+            text_elements: Vec::new(),
+            local_image_paths: Vec::new(),
+        };
+
+        let display = render_lines(&cell.display_lines(80));
+        let transcript = render_transcript(&cell);
+
+        assert_eq!(display.len(), USER_MESSAGE_MAX_LINES + 2);
+        assert_eq!(
+            display[USER_MESSAGE_MAX_LINES],
+            "  … +11 lines (ctrl + t to view all)"
+        );
+        assert_eq!(transcript.len(), 32);
+        assert_eq!(transcript[30], "  line 30");
+        assert_eq!(cell.message, message);
+    }
+
+    #[test]
+    fn accessible_transcript_snapshot() {
+        let header = SessionHeaderHistoryCell::new(
+            "gpt-5-codex".to_string(),
+            Some(ReasoningEffortConfig::High),
+            PathBuf::from("/repo"),
+            "test",
+        );
+        let user = UserHistoryCell {
+            message: "Fix the flaky test".to_string(),
+            text_elements: Vec::new(),
+            local_image_paths: Vec::new(),
+        };
+        let reasoning =
+            ReasoningSummaryCell::new(String::new(), "**Planning** the fix".to_string(), false);
+        let agent =
+            AgentMessageCell::new(vec![Line::from("Done. The test no longer races.")], true);
+
+        let cells: [&dyn HistoryCell; 4] = [&header, &user, &reasoning, &agent];
+        let rendered = cells
+            .iter()
+            .flat_map(|cell| render_lines(&cell.accessible_lines(80)))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        insta::assert_snapshot!(rendered);
+    }
+
     #[test]
     fn plan_update_with_note_and_wrapping_snapshot() {
         // Long explanation forces wrapping; include long step text to verify step wrapping and alignment.
@@ -3142,4 +3912,89 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn request_error_renders_request_details() {
+        let cell = new_request_error_event(ErrorEvent {
+            message: "unexpected status 400 Bad Request".to_string(),
+            request_details: Some(RequestErrorDetails {
+                request_id: Some("req_abc123".to_string()),
+                error_type: Some("invalid_request_error".to_string()),
+                error_code: None,
+                attempts: Some(1),
+            }),
+            ..Default::default()
+        });
+        let rendered = render_lines(&cell.display_lines(80));
+        assert_eq!(
+            rendered,
+            vec![
+                "■ unexpected status 400 Bad Request".to_string(),
+                "  request id req_abc123 — include this when reporting".to_string(),
+                "  type invalid_request_error · attempts 1".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn request_error_shows_hint_and_keeps_details_for_transcript() {
+        let cell = new_request_error_event(ErrorEvent {
+            message: "Could not connect to the model provider.".to_string(),
+            hint: Some("Check your network connection.".to_string()),
+            details: Some("error sending request for url (https://api.example.com/v1)".to_string()),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "■ Could not connect to the model provider.".to_string(),
+                "  Check your network connection.".to_string(),
+                "  ctrl + t to view details".to_string(),
+            ]
+        );
+        assert_eq!(
+            render_lines(&cell.transcript_lines(80)),
+            vec![
+                "■ Could not connect to the model provider.".to_string(),
+                "  Check your network connection.".to_string(),
+                "  error sending request for url (https://api.example.com/v1)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn restored_approvals_lists_commands_and_edits() {
+        let cwd = PathBuf::from("/repo");
+        let grants = vec![
+            ApprovalGrantItem {
+                tool: "shell".to_string(),
+                cwd: cwd.clone(),
+                key: serde_json::json!({
+                    "command": ["cargo", "test", "-p", "codex-core"],
+                    "cwd": "/repo",
+                    "sandbox_permissions": "use_default",
+                })
+                .to_string(),
+            },
+            ApprovalGrantItem {
+                tool: "apply_patch".to_string(),
+                cwd,
+                key: serde_json::json!("/repo/src/lib.rs").to_string(),
+            },
+        ];
+
+        let cell = new_restored_approvals_event(&grants, 1);
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "• Restored 2 approvals from earlier in this session".to_string(),
+                "  └ cargo test -p codex-core".to_string(),
+                "  └ edits to src/lib.rs".to_string(),
+                "  1 granted in a different directory were not restored".to_string(),
+                "  Run /revoke to forget them.".to_string(),
+            ]
+        );
+    }
 }
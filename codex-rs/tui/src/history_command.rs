@@ -0,0 +1,216 @@
+//! Argument handling and rendering for `/history files`, which shows a file as
+//! captured by the ghost snapshot taken before an earlier turn, without
+//! restoring anything.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_core::protocol::FileChange;
+use codex_core::protocol::GhostSnapshotFileResponseEvent;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use crate::text_formatting::truncate_graphemes;
+
+pub(crate) const HISTORY_USAGE: &str = "Usage: /history files [TURN] [--diff]";
+
+/// Parsed `/history files` arguments. `turn` skips the snapshot picker and
+/// `diff` compares the picked file with the current one instead of showing
+/// its old contents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct HistoryFilesArgs {
+    pub turn: Option<u64>,
+    pub diff: bool,
+}
+
+/// What the snapshot overlay shows for the picked file.
+#[derive(Debug)]
+pub(crate) enum SnapshotFileView {
+    Contents(Vec<Line<'static>>),
+    /// Changes from the snapshot to the current file, for the diff viewer.
+    Diff(HashMap<PathBuf, FileChange>),
+}
+
+/// Parse the text after `/history`. `files` is the only subcommand and may be
+/// omitted.
+pub(crate) fn parse_history_args(args: &str) -> Result<HistoryFilesArgs, String> {
+    let mut tokens = args.split_whitespace().peekable();
+    if tokens.peek() == Some(&"files") {
+        tokens.next();
+    }
+    let mut parsed = HistoryFilesArgs::default();
+    for token in tokens {
+        match token {
+            "--diff" => parsed.diff = true,
+            turn if parsed.turn.is_none() && turn.parse::<u64>().is_ok() => {
+                parsed.turn = turn.parse().ok();
+            }
+            other => return Err(format!("Unexpected `{other}`. {HISTORY_USAGE}")),
+        }
+    }
+    Ok(parsed)
+}
+
+/// How a snapshot is referred to in pickers and messages, e.g. "before turn 7".
+pub(crate) fn snapshot_label(turn: Option<u64>, commit_id: &str) -> String {
+    match turn {
+        Some(turn) => format!("before turn {turn}"),
+        None => format!("at snapshot {}", short_id(commit_id)),
+    }
+}
+
+pub(crate) fn short_id(commit_id: &str) -> &str {
+    truncate_graphemes(commit_id, 7)
+}
+
+/// Build the overlay for a file read back from a snapshot, or the message to
+/// show instead when there is nothing to display.
+pub(crate) fn snapshot_file_view(
+    event: GhostSnapshotFileResponseEvent,
+    label: &str,
+    diff: bool,
+) -> Result<SnapshotFileView, String> {
+    if let Some(error) = event.error {
+        return Err(error);
+    }
+    let display = event.path.display().to_string();
+    let header = Line::from(format!("{display} {label}").dim());
+
+    if !diff {
+        let Some(snapshot) = event.snapshot else {
+            return Err(format!("{display} did not exist {label}."));
+        };
+        let mut lines = vec![header, Line::from("")];
+        lines.extend(snapshot.lines().map(|line| Line::from(line.to_string())));
+        return Ok(SnapshotFileView::Contents(lines));
+    }
+
+    let change = match (event.snapshot, event.current) {
+        (None, None) => {
+            return Err(format!(
+                "{display} did not exist {label} and does not exist now."
+            ));
+        }
+        (Some(old), Some(new)) if old == new => {
+            return Ok(SnapshotFileView::Contents(vec![
+                header,
+                Line::from(""),
+                Line::from("Unchanged since then.".italic()),
+            ]));
+        }
+        (None, Some(content)) => FileChange::Add { content },
+        (Some(content), None) => FileChange::Delete { content },
+        (Some(old), Some(new)) => FileChange::Update {
+            unified_diff: diffy::create_patch(&old, &new).to_string(),
+            move_path: None,
+        },
+    };
+    Ok(SnapshotFileView::Diff(HashMap::from([(
+        event.path, change,
+    )])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn file_event(snapshot: Option<&str>, current: Option<&str>) -> GhostSnapshotFileResponseEvent {
+        GhostSnapshotFileResponseEvent {
+            commit_id: "0123456789abcdef".to_string(),
+            path: PathBuf::from("src/lib.rs"),
+            snapshot: snapshot.map(str::to_string),
+            current: current.map(str::to_string),
+            error: None,
+        }
+    }
+
+    fn text(view: SnapshotFileView) -> Vec<String> {
+        let SnapshotFileView::Contents(lines) = view else {
+            panic!("expected contents view");
+        };
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_turn_and_diff_flag() {
+        assert_eq!(parse_history_args(""), Ok(HistoryFilesArgs::default()));
+        assert_eq!(
+            parse_history_args("files 7 --diff"),
+            Ok(HistoryFilesArgs {
+                turn: Some(7),
+                diff: true,
+            })
+        );
+        assert!(parse_history_args("files 7 8").is_err());
+        assert!(parse_history_args("commits").is_err());
+    }
+
+    #[test]
+    fn labels_snapshots_by_turn() {
+        assert_eq!(snapshot_label(Some(7), "0123456789"), "before turn 7");
+        assert_eq!(snapshot_label(None, "0123456789"), "at snapshot 0123456");
+        assert_eq!(short_id("abcdéfgh"), "abcdéfg");
+    }
+
+    #[test]
+    fn shows_snapshot_contents_under_a_header() {
+        let view = snapshot_file_view(
+            file_event(Some("fn a() {}\nfn b() {}\n"), None),
+            "before turn 2",
+            false,
+        )
+        .expect("contents");
+        assert_eq!(
+            text(view),
+            vec!["src/lib.rs before turn 2", "", "fn a() {}", "fn b() {}"]
+        );
+    }
+
+    #[test]
+    fn diffs_against_the_current_file() {
+        let view = snapshot_file_view(
+            file_event(Some("old\n"), Some("new\n")),
+            "before turn 2",
+            true,
+        )
+        .expect("diff");
+        let SnapshotFileView::Diff(changes) = view else {
+            panic!("expected diff view");
+        };
+        let Some(FileChange::Update { unified_diff, .. }) =
+            changes.get(&PathBuf::from("src/lib.rs"))
+        else {
+            panic!("expected update for src/lib.rs: {changes:?}");
+        };
+        assert!(unified_diff.contains("-old\n+new\n"), "{unified_diff}");
+
+        let unchanged = snapshot_file_view(
+            file_event(Some("same\n"), Some("same\n")),
+            "before turn 2",
+            true,
+        )
+        .expect("unchanged");
+        assert_eq!(
+            text(unchanged),
+            vec!["src/lib.rs before turn 2", "", "Unchanged since then."]
+        );
+    }
+
+    #[test]
+    fn reports_files_missing_from_the_snapshot() {
+        let result = snapshot_file_view(file_event(None, Some("new\n")), "before turn 2", false);
+        assert_eq!(
+            result.err(),
+            Some("src/lib.rs did not exist before turn 2.".to_string())
+        );
+    }
+}
@@ -53,6 +53,38 @@ pub(crate) const fn ctrl_alt(key: KeyCode) -> KeyBinding {
     KeyBinding::new(key, KeyModifiers::CONTROL.union(KeyModifiers::ALT))
 }
 
+/// Parses a user-configured binding such as `ctrl-r`, `alt+e`, or `f5`.
+/// Returns `None` for anything it does not recognize.
+pub(crate) fn parse_binding(spec: &str) -> Option<KeyBinding> {
+    let spec = spec.trim().to_ascii_lowercase();
+    let mut parts: Vec<&str> = spec.split(['-', '+']).map(str::trim).collect();
+    let key = parts.pop()?;
+    let mut modifiers = KeyModifiers::NONE;
+    for part in parts {
+        modifiers |= match part {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "option" | "opt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+    }
+    let key = match key {
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "esc" | "escape" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        _ => {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                (Some('f'), Some(_)) => KeyCode::F(key[1..].parse().ok()?),
+                _ => return None,
+            }
+        }
+    };
+    Some(KeyBinding::new(key, modifiers))
+}
+
 fn modifiers_to_string(modifiers: KeyModifiers) -> String {
     let mut result = String::new();
     if modifiers.contains(KeyModifiers::CONTROL) {
@@ -110,3 +142,27 @@ pub(crate) fn is_altgr(mods: KeyModifiers) -> bool {
 pub(crate) fn is_altgr(_mods: KeyModifiers) -> bool {
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_binding_accepts_modifier_forms() {
+        assert_eq!(parse_binding("ctrl-r"), Some(ctrl(KeyCode::Char('r'))));
+        assert_eq!(parse_binding("Alt+E"), Some(alt(KeyCode::Char('e'))));
+        assert_eq!(
+            parse_binding("ctrl-alt-x"),
+            Some(ctrl_alt(KeyCode::Char('x')))
+        );
+        assert_eq!(parse_binding("f5"), Some(plain(KeyCode::F(5))));
+    }
+
+    #[test]
+    fn parse_binding_rejects_unknown_specs() {
+        assert_eq!(parse_binding(""), None);
+        assert_eq!(parse_binding("hyper-r"), None);
+        assert_eq!(parse_binding("ctrl-page"), None);
+    }
+}
@@ -15,22 +15,32 @@ use codex_common::oss::ollama_chat_deprecation_notice;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
+use codex_core::OLLAMA_CHAT_PROVIDER_ID;
+use codex_core::OLLAMA_OSS_PROVIDER_ID;
 use codex_core::RolloutRecorder;
+use codex_core::ThreadManager;
 use codex_core::ThreadSortKey;
 use codex_core::auth::enforce_login_restrictions;
+use codex_core::config::CONFIG_TOML_FILE;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::ConfigOverrides;
+use codex_core::config::READ_ONLY_CHAT_OVERRIDES;
 use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
+use codex_core::config::project_trust_target;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config::session_project_trust_override;
 use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
+use codex_core::features::Feature;
 use codex_core::find_thread_path_by_id_str;
 use codex_core::find_thread_path_by_name_str;
 use codex_core::path_utils;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SessionSource;
 use codex_core::read_session_meta_line;
 use codex_core::terminal::Multiplexer;
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
@@ -41,11 +51,13 @@ use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::RolloutLine;
 use codex_state::log_db;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use color_eyre::eyre::WrapErr;
 use cwd_prompt::CwdPromptAction;
 use cwd_prompt::CwdSelection;
 use std::fs::OpenOptions;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tracing::error;
 use tracing_appender::non_blocking;
 use tracing_subscriber::EnvFilter;
@@ -60,6 +72,7 @@ mod app_event_sender;
 mod ascii_animation;
 mod bottom_pane;
 mod chatwidget;
+mod citations;
 mod cli;
 mod clipboard_paste;
 mod collab;
@@ -67,14 +80,15 @@ mod collaboration_modes;
 mod color;
 pub mod custom_terminal;
 mod cwd_prompt;
+mod diff_command;
 mod diff_render;
 mod exec_cell;
 mod exec_command;
 mod external_editor;
 mod file_search;
 mod frames;
-mod get_git_diff;
 mod history_cell;
+mod history_command;
 pub mod insert_history;
 mod key_hint;
 pub mod live_wrap;
@@ -86,11 +100,16 @@ mod notifications;
 pub mod onboarding;
 mod oss_selection;
 mod pager_overlay;
+mod paste_files;
 pub mod public_widgets;
+mod reasoning_headline;
 mod render;
 mod resume_picker;
 mod selection_list;
 mod session_log;
+mod session_recording;
+mod session_tabs;
+mod setup_wizard;
 mod shimmer;
 mod skills_helpers;
 mod slash_command;
@@ -100,6 +119,7 @@ mod streaming;
 mod style;
 mod terminal_palette;
 mod text_formatting;
+mod token_estimate;
 mod tooltips;
 mod tui;
 mod ui_consts;
@@ -108,6 +128,7 @@ mod update_prompt;
 mod updates;
 mod version;
 
+mod workspace_roots;
 mod wrapping;
 
 #[cfg(test)]
@@ -121,6 +142,7 @@ pub use cli::Cli;
 pub use markdown_render::render_markdown_text;
 pub use public_widgets::composer_input::ComposerAction;
 pub use public_widgets::composer_input::ComposerInput;
+pub use session_recording::replay_recording;
 // (tests access modules directly within the crate)
 
 pub async fn run_main(
@@ -144,12 +166,22 @@ pub async fn run_main(
         )
     };
 
+    // `-c` overrides mean the user is configuring Codex by hand; don't walk them through setup.
+    if !cli.config_overrides.raw_overrides.is_empty() {
+        cli.no_wizard = true;
+    }
+
     // Map the legacy --search flag to the canonical web_search mode.
     if cli.web_search {
         cli.config_overrides
             .raw_overrides
             .push("web_search=\"live\"".to_string());
     }
+    if cli.read_only_chat {
+        cli.config_overrides
+            .raw_overrides
+            .extend(READ_ONLY_CHAT_OVERRIDES.map(String::from));
+    }
 
     // When using `--oss`, let the bootstrapper pick the model (defaulting to
     // gpt-oss:20b) and ensure it is present locally. Also, force the built‑in
@@ -266,6 +298,9 @@ pub async fn run_main(
         config_profile: cli.config_profile.clone(),
         codex_linux_sandbox_exe,
         show_raw_agent_reasoning: cli.oss.then_some(true),
+        offline: cli.offline.then_some(true),
+        dry_run_exec: cli.dry_run_exec.then_some(true),
+        env_file: cli.env_file.clone(),
         additional_writable_roots: additional_dirs,
         ..Default::default()
     };
@@ -291,6 +326,15 @@ pub async fn run_main(
         std::process::exit(1);
     }
 
+    // The session warns about unenforced restrictions from the cached report.
+    codex_core::sandbox_self_test::run_if_stale(&config, || {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("Checking sandbox enforcement on this machine (once per OS version)...");
+        }
+    })
+    .await;
+
     let log_dir = codex_core::config::log_dir(&config)?;
     std::fs::create_dir_all(&log_dir)?;
     // Open (or create) your log file, appending to it.
@@ -404,7 +448,7 @@ async fn run_ratatui_app(
     cli: Cli,
     initial_config: Config,
     overrides: ConfigOverrides,
-    cli_kv_overrides: Vec<(String, toml::Value)>,
+    mut cli_kv_overrides: Vec<(String, toml::Value)>,
     cloud_requirements: CloudRequirementsLoader,
     feedback: codex_feedback::CodexFeedback,
 ) -> color_eyre::Result<AppExitInfo> {
@@ -421,6 +465,16 @@ async fn run_ratatui_app(
         tracing::error!("panic: {info}");
         prev_hook(info);
     }));
+    // `--record`: the guard finalizes the recording on every return path; a
+    // panic hook installed by `start` covers panics.
+    let _recording = cli
+        .record
+        .as_deref()
+        .map(|path| {
+            session_recording::start(path, cli.record_input)
+                .wrap_err_with(|| format!("failed to start recording to {}", path.display()))
+        })
+        .transpose()?;
     let mut terminal = tui::init()?;
     terminal.clear()?;
 
@@ -458,22 +512,64 @@ async fn run_ratatui_app(
     );
     let login_status = get_login_status(&initial_config);
     let should_show_trust_screen_flag = should_show_trust_screen(&initial_config);
-    let should_show_onboarding =
-        should_show_onboarding(login_status, &initial_config, should_show_trust_screen_flag);
+    let should_show_setup_wizard =
+        !cli.no_wizard && !initial_config.codex_home.join(CONFIG_TOML_FILE).exists();
+    let should_show_onboarding = should_show_setup_wizard
+        || should_show_onboarding(login_status, &initial_config, should_show_trust_screen_flag);
+
+    let thread_manager = Arc::new(ThreadManager::new(
+        initial_config.codex_home.clone(),
+        auth_manager.clone(),
+        SessionSource::Cli,
+    ));
+    // Start the session while the onboarding screens are up so MCP servers and
+    // the rollout are ready by the time they close. The setup wizard and a
+    // trust decision reload the config, which discards this session below.
+    // Signing in only matters to the session for the apps MCP server.
+    let show_login_screen = should_show_login_screen(login_status, &initial_config);
+    let mut prewarmed = if should_show_onboarding
+        && !should_show_setup_wizard
+        && starts_fresh_session(&cli)
+        && !is_ollama_provider(&initial_config)
+        && !(show_login_screen && initial_config.features.enabled(Feature::Apps))
+    {
+        let mut prewarm_config = initial_config.clone();
+        prewarm_config.session_title = cli.title.clone();
+        prewarm_config.session_tags = cli.tags.clone();
+        Some(thread_manager.prewarm_thread(prewarm_config))
+    } else {
+        None
+    };
 
     let config = if should_show_onboarding {
+        let trusted_sandbox_policy = if should_show_trust_screen_flag {
+            trusted_sandbox_policy(
+                &initial_config,
+                cli_kv_overrides.clone(),
+                overrides.clone(),
+                cloud_requirements.clone(),
+            )
+            .await
+        } else {
+            initial_config.sandbox_policy.get().clone()
+        };
         let onboarding_result = run_onboarding_app(
             OnboardingScreenArgs {
-                show_login_screen: should_show_login_screen(login_status, &initial_config),
+                show_login_screen,
                 show_trust_screen: should_show_trust_screen_flag,
+                show_setup_wizard: should_show_setup_wizard,
                 login_status,
                 auth_manager: auth_manager.clone(),
                 config: initial_config.clone(),
+                trusted_sandbox_policy,
             },
             &mut tui,
         )
         .await?;
         if onboarding_result.should_exit {
+            if let Some(prewarmed) = prewarmed.take() {
+                prewarmed.discard().await;
+            }
             restore();
             session_log::log_session_end();
             let _ = tui.terminal.clear();
@@ -485,12 +581,25 @@ async fn run_ratatui_app(
                 exit_reason: ExitReason::UserRequested,
             });
         }
-        // if the user acknowledged windows or made an explicit decision ato trust the directory, reload the config accordingly
-        if onboarding_result
-            .directory_trust_decision
-            .map(|d| d == TrustDirectorySelection::Trust)
-            .unwrap_or(false)
+        // Reload the config when the setup wizard wrote config.toml or the user trusted the
+        // directory so project config and trusted-folder defaults apply. "Trust once" is carried
+        // as a session-only override so later reloads (resume, fork) keep it without touching
+        // config.toml.
+        let trust_decision = onboarding_result.directory_trust_decision;
+        if trust_decision == Some(TrustDirectorySelection::TrustOnce) {
+            cli_kv_overrides.push(session_project_trust_override(&project_trust_target(
+                &initial_config.cwd,
+            )));
+        }
+        if onboarding_result.wrote_config
+            || matches!(
+                trust_decision,
+                Some(TrustDirectorySelection::TrustAlways | TrustDirectorySelection::TrustOnce)
+            )
         {
+            if let Some(prewarmed) = prewarmed.take() {
+                prewarmed.discard().await;
+            }
             load_config_or_exit(
                 cli_kv_overrides.clone(),
                 overrides.clone(),
@@ -659,7 +768,7 @@ async fn run_ratatui_app(
         None => None,
     };
 
-    let config = match &session_selection {
+    let mut config = match &session_selection {
         resume_picker::SessionSelection::Resume(_) | resume_picker::SessionSelection::Fork(_) => {
             load_config_or_exit_with_fallback_cwd(
                 cli_kv_overrides.clone(),
@@ -671,6 +780,8 @@ async fn run_ratatui_app(
         }
         _ => config,
     };
+    config.session_title = cli.title.clone();
+    config.session_tags = cli.tags.clone();
     let active_profile = config.active_profile.clone();
     let should_show_trust_screen = should_show_trust_screen(&config);
 
@@ -687,6 +798,8 @@ async fn run_ratatui_app(
     let app_result = App::run(
         &mut tui,
         auth_manager,
+        thread_manager,
+        prewarmed,
         config,
         cli_kv_overrides.clone(),
         overrides.clone(),
@@ -877,6 +990,33 @@ async fn load_config_or_exit_with_fallback_cwd(
     }
 }
 
+/// The sandbox policy the session will run under once the user trusts the
+/// directory: the config reloaded with a trust override, so configured
+/// `sandbox_workspace_write` settings and CLI overrides are reflected.
+async fn trusted_sandbox_policy(
+    initial_config: &Config,
+    mut cli_kv_overrides: Vec<(String, toml::Value)>,
+    overrides: ConfigOverrides,
+    cloud_requirements: CloudRequirementsLoader,
+) -> SandboxPolicy {
+    cli_kv_overrides.push(session_project_trust_override(&project_trust_target(
+        &initial_config.cwd,
+    )));
+    match ConfigBuilder::default()
+        .cli_overrides(cli_kv_overrides)
+        .harness_overrides(overrides)
+        .cloud_requirements(cloud_requirements)
+        .build()
+        .await
+    {
+        Ok(config) => config.sandbox_policy.get().clone(),
+        Err(err) => {
+            tracing::warn!("failed to resolve the trusted sandbox policy: {err}");
+            initial_config.sandbox_policy.get().clone()
+        }
+    }
+}
+
 /// Determine if user has configured a sandbox / approval policy,
 /// or if the current cwd project is already trusted. If not, we need to
 /// show the trust screen.
@@ -907,6 +1047,23 @@ fn should_show_onboarding(
     should_show_login_screen(login_status, config)
 }
 
+/// Whether the CLI asked for a new session rather than a resumed or forked one.
+fn starts_fresh_session(cli: &Cli) -> bool {
+    !(cli.resume_picker
+        || cli.resume_last
+        || cli.resume_session_id.is_some()
+        || cli.fork_picker
+        || cli.fork_last
+        || cli.fork_session_id.is_some())
+}
+
+/// Ollama model details are applied to the config after onboarding, so a
+/// session started earlier would run without them.
+fn is_ollama_provider(config: &Config) -> bool {
+    config.model_provider_id == OLLAMA_OSS_PROVIDER_ID
+        || config.model_provider_id == OLLAMA_CHAT_PROVIDER_ID
+}
+
 fn should_show_login_screen(login_status: LoginStatus, config: &Config) -> bool {
     // Only show the login screen for providers that actually require OpenAI auth
     // (OpenAI or equivalents). For OSS/other providers, skip login entirely.
@@ -1021,6 +1178,7 @@ mod tests {
             developer_instructions: None,
             final_output_json_schema: None,
             truncation_policy: None,
+            preferred_language: None,
         }
     }
 
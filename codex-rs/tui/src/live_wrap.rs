@@ -1,6 +1,8 @@
-use unicode_width::UnicodeWidthChar;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+use crate::text_formatting::truncate_display_width;
+
 /// A single visual row produced by RowBuilder.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Row {
@@ -188,22 +190,9 @@ pub fn take_prefix_by_width(text: &str, max_cols: usize) -> (String, &str, usize
     if max_cols == 0 || text.is_empty() {
         return (String::new(), text, 0);
     }
-    let mut cols = 0usize;
-    let mut end_idx = 0usize;
-    for (i, ch) in text.char_indices() {
-        let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if cols.saturating_add(ch_width) > max_cols {
-            break;
-        }
-        cols += ch_width;
-        end_idx = i + ch.len_utf8();
-        if cols == max_cols {
-            break;
-        }
-    }
-    let prefix = text[..end_idx].to_string();
-    let suffix = &text[end_idx..];
-    (prefix, suffix, cols)
+    let prefix = truncate_display_width(text, max_cols);
+    let cols = prefix.graphemes(true).map(UnicodeWidthStr::width).sum();
+    (prefix.to_string(), &text[prefix.len()..], cols)
 }
 
 #[cfg(test)]
@@ -249,6 +238,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn take_prefix_by_width_keeps_grapheme_clusters_together() {
+        // The combining accent and the ZWJ sequence must stay with their base.
+        assert_eq!(
+            take_prefix_by_width("e\u{301}x", 1),
+            ("e\u{301}".to_string(), "x", 1)
+        );
+        assert_eq!(
+            take_prefix_by_width("👩‍💻 done", 3),
+            ("👩‍💻 ".to_string(), "done", 3)
+        );
+    }
+
     #[test]
     fn fragmentation_invariance_long_token() {
         let s = "ABCDEFGHIJKLMNOPQRSTUVWXYZ"; // 26 chars
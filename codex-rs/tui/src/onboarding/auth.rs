@@ -5,6 +5,7 @@ use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::CLIENT_ID;
 use codex_core::auth::login_with_api_key;
 use codex_core::auth::read_openai_api_key_from_env;
+use codex_core::provider_env::DotenvFile;
 use codex_login::DeviceCode;
 use codex_login::ServerOptions;
 use codex_login::ShutdownHandle;
@@ -170,6 +171,8 @@ pub(crate) struct AuthModeWidget {
     pub forced_chatgpt_workspace_id: Option<String>,
     pub forced_login_method: Option<ForcedLoginMethod>,
     pub animations_enabled: bool,
+    /// Dotenv file from the config, used to prefill the API key.
+    pub dotenv: Option<Arc<DotenvFile>>,
 }
 
 impl AuthModeWidget {
@@ -600,7 +603,7 @@ impl AuthModeWidget {
             return;
         }
         self.error = None;
-        let prefill_from_env = read_openai_api_key_from_env();
+        let prefill_from_env = read_openai_api_key_from_env(self.dotenv.as_deref());
         let mut guard = self.sign_in_state.write().unwrap();
         match &mut *guard {
             SignInState::ApiKeyEntry(state) => {
@@ -815,6 +818,7 @@ mod tests {
             forced_chatgpt_workspace_id: None,
             forced_login_method: Some(ForcedLoginMethod::Chatgpt),
             animations_enabled: true,
+            dotenv: None,
         };
         (widget, codex_home)
     }
@@ -1,6 +1,12 @@
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_common::summarize_sandbox_policy;
 use codex_core::AuthManager;
 use codex_core::config::Config;
+use codex_core::config_loader::ConfigLayerStackOrdering;
 use codex_core::git_info::get_git_repo_root;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_core::project_doc::discover_project_doc_paths;
+use codex_core::protocol::SandboxPolicy;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -17,9 +23,12 @@ use crate::LoginStatus;
 use crate::onboarding::auth::AuthModeWidget;
 use crate::onboarding::auth::SignInOption;
 use crate::onboarding::auth::SignInState;
+use crate::onboarding::setup::SetupWidget;
 use crate::onboarding::trust_directory::TrustDirectorySelection;
 use crate::onboarding::trust_directory::TrustDirectoryWidget;
 use crate::onboarding::welcome::WelcomeWidget;
+use crate::setup_wizard::SetupChoices;
+use crate::setup_wizard::SetupWizard;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
 use crate::tui::TuiEvent;
@@ -31,6 +40,7 @@ use std::sync::RwLock;
 enum Step {
     Welcome(WelcomeWidget),
     Auth(AuthModeWidget),
+    Setup(SetupWidget),
     TrustDirectory(TrustDirectoryWidget),
 }
 
@@ -60,13 +70,20 @@ pub(crate) struct OnboardingScreen {
 pub(crate) struct OnboardingScreenArgs {
     pub show_trust_screen: bool,
     pub show_login_screen: bool,
+    /// First launch without a `config.toml`: run the setup wizard.
+    pub show_setup_wizard: bool,
     pub login_status: LoginStatus,
     pub auth_manager: Arc<AuthManager>,
     pub config: Config,
+    /// Sandbox the session runs under once the directory is trusted, shown
+    /// on the trust screen.
+    pub trusted_sandbox_policy: SandboxPolicy,
 }
 
 pub(crate) struct OnboardingResult {
     pub directory_trust_decision: Option<TrustDirectorySelection>,
+    /// The setup wizard wrote a new `config.toml`.
+    pub wrote_config: bool,
     pub should_exit: bool,
 }
 
@@ -75,14 +92,32 @@ impl OnboardingScreen {
         let OnboardingScreenArgs {
             show_trust_screen,
             show_login_screen,
+            show_setup_wizard,
             login_status,
             auth_manager,
             config,
+            trusted_sandbox_policy,
         } = args;
         let cwd = config.cwd.clone();
+        let project_docs = if show_trust_screen {
+            discover_project_doc_paths(&config).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let project_config_folders = config
+            .config_layer_stack
+            .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true)
+            .into_iter()
+            .filter_map(|layer| match &layer.name {
+                ConfigLayerSource::Project { dot_codex_folder } => {
+                    Some(dot_codex_folder.to_path_buf())
+                }
+                _ => None,
+            })
+            .collect();
         let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
         let forced_login_method = config.forced_login_method;
-        let codex_home = config.codex_home;
+        let codex_home = config.codex_home.clone();
         let cli_auth_credentials_store_mode = config.cli_auth_credentials_store_mode;
         let mut steps: Vec<Step> = Vec::new();
         steps.push(Step::Welcome(WelcomeWidget::new(
@@ -103,30 +138,50 @@ impl OnboardingScreen {
                 codex_home: codex_home.clone(),
                 cli_auth_credentials_store_mode,
                 login_status,
-                auth_manager,
+                auth_manager: auth_manager.clone(),
                 forced_chatgpt_workspace_id,
                 forced_login_method,
                 animations_enabled: config.animations,
+                dotenv: config.dotenv.clone(),
             }))
         }
-        let is_git_repo = get_git_repo_root(&cwd).is_some();
-        let highlighted = if is_git_repo {
-            TrustDirectorySelection::Trust
-        } else {
-            // Default to not trusting the directory if it's not a git repo.
-            TrustDirectorySelection::DontTrust
-        };
+        if show_setup_wizard {
+            let models = ModelsManager::new(codex_home.clone(), auth_manager)
+                .try_list_models(&config)
+                .unwrap_or_default();
+            let default_model = models
+                .iter()
+                .find(|preset| preset.is_default)
+                .map(|preset| preset.model.clone())
+                .unwrap_or_else(|| ModelsManager::get_model_offline(None));
+            let initial = SetupChoices::from_config(&config, &default_model);
+            steps.push(Step::Setup(SetupWidget {
+                codex_home: codex_home.clone(),
+                wizard: SetupWizard::new(models, &initial),
+                written: None,
+                error: None,
+            }));
+        }
         if show_trust_screen {
+            let is_git_repo = get_git_repo_root(&cwd).is_some();
+            let highlighted = if is_git_repo {
+                TrustDirectorySelection::TrustAlways
+            } else {
+                // Folders without version control get a one-off decision by default.
+                TrustDirectorySelection::TrustOnce
+            };
             steps.push(Step::TrustDirectory(TrustDirectoryWidget {
+                is_git_repo,
+                sandbox_summary: summarize_sandbox_policy(&trusted_sandbox_policy),
+                project_docs,
+                project_config_folders,
                 cwd,
                 codex_home,
-                is_git_repo,
                 selection: None,
                 highlighted,
                 error: None,
             }))
         }
-        // TODO: add git warning.
         Self {
             request_frame: tui.frame_requester(),
             steps,
@@ -192,8 +247,20 @@ impl OnboardingScreen {
             .flatten()
     }
 
+    fn wrote_config(&self) -> bool {
+        self.steps.iter().any(|step| {
+            matches!(
+                step,
+                Step::Setup(SetupWidget {
+                    written: Some(_),
+                    ..
+                })
+            )
+        })
+    }
+
     pub fn should_exit(&self) -> bool {
-        self.should_exit
+        self.should_exit || self.directory_trust_decision() == Some(TrustDirectorySelection::Quit)
     }
 
     fn is_api_key_entry_active(&self) -> bool {
@@ -340,6 +407,7 @@ impl KeyboardHandler for Step {
         match self {
             Step::Welcome(widget) => widget.handle_key_event(key_event),
             Step::Auth(widget) => widget.handle_key_event(key_event),
+            Step::Setup(widget) => widget.handle_key_event(key_event),
             Step::TrustDirectory(widget) => widget.handle_key_event(key_event),
         }
     }
@@ -348,6 +416,7 @@ impl KeyboardHandler for Step {
         match self {
             Step::Welcome(_) => {}
             Step::Auth(widget) => widget.handle_paste(pasted),
+            Step::Setup(widget) => widget.handle_paste(pasted),
             Step::TrustDirectory(widget) => widget.handle_paste(pasted),
         }
     }
@@ -358,6 +427,7 @@ impl StepStateProvider for Step {
         match self {
             Step::Welcome(w) => w.get_step_state(),
             Step::Auth(w) => w.get_step_state(),
+            Step::Setup(w) => w.get_step_state(),
             Step::TrustDirectory(w) => w.get_step_state(),
         }
     }
@@ -372,6 +442,9 @@ impl WidgetRef for Step {
             Step::Auth(widget) => {
                 widget.render_ref(area, buf);
             }
+            Step::Setup(widget) => {
+                widget.render_ref(area, buf);
+            }
             Step::TrustDirectory(widget) => {
                 widget.render_ref(area, buf);
             }
@@ -445,6 +518,7 @@ pub(crate) async fn run_onboarding_app(
     }
     Ok(OnboardingResult {
         directory_trust_decision: onboarding_screen.directory_trust_decision(),
+        wrote_config: onboarding_screen.wrote_config(),
         should_exit: onboarding_screen.should_exit(),
     })
 }
@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::WidgetRef;
+use ratatui::widgets::Wrap;
+
+use crate::onboarding::onboarding_screen::KeyboardHandler;
+use crate::onboarding::onboarding_screen::StepStateProvider;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::render::renderable::RenderableExt as _;
+use crate::setup_wizard::SetupOutcome;
+use crate::setup_wizard::SetupWizard;
+use crate::setup_wizard::write_initial_config;
+
+use super::onboarding_screen::StepState;
+
+/// First-run setup. Writes `config.toml` once the last page is confirmed; Esc on the first page
+/// skips it and leaves the built-in defaults in place.
+pub(crate) struct SetupWidget {
+    pub codex_home: PathBuf,
+    pub wizard: SetupWizard,
+    pub written: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+impl KeyboardHandler for SetupWidget {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if self.wizard.outcome().is_some() {
+            return;
+        }
+        self.wizard.handle_key_event(key_event);
+        if let Some(SetupOutcome::Finished(choices)) = self.wizard.outcome() {
+            match write_initial_config(&self.codex_home, choices) {
+                Ok(path) => self.written = Some(path),
+                Err(err) => {
+                    tracing::error!("Failed to write config.toml: {err:?}");
+                    self.error = Some(format!("Failed to write config.toml: {err}"));
+                }
+            }
+        }
+    }
+}
+
+impl StepStateProvider for SetupWidget {
+    fn get_step_state(&self) -> StepState {
+        match self.wizard.outcome() {
+            Some(_) => StepState::Complete,
+            None => StepState::InProgress,
+        }
+    }
+}
+
+impl WidgetRef for &SetupWidget {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let Some(outcome) = self.wizard.outcome() else {
+            self.wizard.render(area, buf);
+            return;
+        };
+
+        let mut column = ColumnRenderable::new();
+        let summary = match (outcome, &self.written, &self.error) {
+            (_, _, Some(error)) => Line::from(vec!["✗ ".red(), error.clone().red()]),
+            (SetupOutcome::Finished(_), Some(path), None) => Line::from(vec![
+                "✓ ".green(),
+                "Saved your settings to ".into(),
+                path.display().to_string().cyan(),
+            ]),
+            (SetupOutcome::Finished(_), None, None) | (SetupOutcome::Cancelled, _, None) => {
+                Line::from(vec![
+                    "Setup skipped. ".dim(),
+                    "Run /setup any time to go through it.".dim(),
+                ])
+            }
+        };
+        column.push(
+            Paragraph::new(summary)
+                .wrap(Wrap { trim: true })
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup_wizard::SetupChoices;
+    use codex_core::config::CONFIG_TOML_FILE;
+    use codex_core::config::ConfigToml;
+    use codex_core::protocol::AskForApproval;
+    use codex_protocol::config_types::SandboxMode;
+    use crossterm::event::KeyCode;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn confirming_every_page_writes_config() {
+        let codex_home = TempDir::new().expect("temp home");
+        let initial = SetupChoices {
+            approval: AskForApproval::OnRequest,
+            sandbox_mode: SandboxMode::WorkspaceWrite,
+            model: "gpt-5.2-codex".to_string(),
+            notifications: true,
+        };
+        let mut widget = SetupWidget {
+            codex_home: codex_home.path().to_path_buf(),
+            wizard: SetupWizard::new(Vec::new(), &initial),
+            written: None,
+            error: None,
+        };
+
+        for _ in 0..3 {
+            assert_eq!(widget.get_step_state(), StepState::InProgress);
+            widget.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        }
+
+        assert_eq!(widget.get_step_state(), StepState::Complete);
+        let path = codex_home.path().join(CONFIG_TOML_FILE);
+        assert_eq!(widget.written, Some(path.clone()));
+        let contents = std::fs::read_to_string(path).expect("read config");
+        let parsed: ConfigToml = toml::from_str(&contents).expect("parse config");
+        assert_eq!(parsed, initial.config_toml().expect("config toml"));
+    }
+}
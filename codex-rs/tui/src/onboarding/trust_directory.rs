@@ -1,7 +1,8 @@
+use std::path::Path;
 use std::path::PathBuf;
 
+use codex_core::config::project_trust_target;
 use codex_core::config::set_project_trust_level;
-use codex_core::git_info::resolve_root_git_project_for_trust;
 use codex_protocol::config_types::TrustLevel;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -28,6 +29,12 @@ pub(crate) struct TrustDirectoryWidget {
     pub codex_home: PathBuf,
     pub cwd: PathBuf,
     pub is_git_repo: bool,
+    /// Sandbox the session will run under once the folder is trusted.
+    pub sandbox_summary: String,
+    /// AGENTS.md files that will be added to the model's instructions.
+    pub project_docs: Vec<PathBuf>,
+    /// `.codex` folders whose config.toml only loads for trusted folders.
+    pub project_config_folders: Vec<PathBuf>,
     pub selection: Option<TrustDirectorySelection>,
     pub highlighted: TrustDirectorySelection,
     pub error: Option<String>,
@@ -35,10 +42,25 @@ pub(crate) struct TrustDirectoryWidget {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TrustDirectorySelection {
-    Trust,
-    DontTrust,
+    /// Trust the folder and record the decision in config.toml.
+    TrustAlways,
+    /// Trust the folder for this session only; ask again next launch.
+    TrustOnce,
+    Quit,
 }
 
+const OPTIONS: [(&str, TrustDirectorySelection); 3] = [
+    (
+        "Trust this folder and don't ask again",
+        TrustDirectorySelection::TrustAlways,
+    ),
+    (
+        "Trust this folder for this session only",
+        TrustDirectorySelection::TrustOnce,
+    ),
+    ("Quit", TrustDirectorySelection::Quit),
+];
+
 impl WidgetRef for &TrustDirectoryWidget {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
         let mut column = ColumnRenderable::new();
@@ -50,41 +72,46 @@ impl WidgetRef for &TrustDirectoryWidget {
         ]));
         column.push("");
 
-        let guidance = if self.is_git_repo {
-            "Since this folder is version controlled, you may wish to allow Codex to work in this folder without asking for approval."
-        } else {
-            "Since this folder is not version controlled, we recommend requiring approval of all edits and commands."
-        };
-
         column.push(
-            Paragraph::new(guidance.to_string())
+            Paragraph::new("You have not trusted this folder yet. Trusting it means:")
                 .wrap(Wrap { trim: true })
                 .inset(Insets::tlbr(0, 2, 0, 0)),
         );
         column.push("");
 
-        let mut options: Vec<(&str, TrustDirectorySelection)> = Vec::new();
-        if self.is_git_repo {
-            options.push((
-                "Yes, allow Codex to work in this folder without asking for approval",
-                TrustDirectorySelection::Trust,
-            ));
-            options.push((
-                "No, ask me to approve edits and commands",
-                TrustDirectorySelection::DontTrust,
-            ));
-        } else {
-            options.push((
-                "Allow Codex to work in this folder without asking for approval",
-                TrustDirectorySelection::Trust,
-            ));
-            options.push((
-                "Require approval of edits and commands",
-                TrustDirectorySelection::DontTrust,
-            ));
+        let summary = [
+            ("Sandbox", vec![self.sandbox_summary.clone()]),
+            ("AGENTS.md", self.display_paths(&self.project_docs)),
+            (
+                "Project config",
+                self.display_paths(&self.project_config_folders),
+            ),
+        ];
+        for (label, values) in summary {
+            let value = if values.is_empty() {
+                "none found".to_string()
+            } else {
+                values.join(", ")
+            };
+            column.push(
+                Line::from(vec![format!("{label:<16}").dim(), value.into()])
+                    .inset(Insets::tlbr(0, 4, 0, 0)),
+            );
         }
+        column.push("");
 
-        for (idx, (text, selection)) in options.iter().enumerate() {
+        if !self.is_git_repo {
+            column.push(
+                Paragraph::new(
+                    "This folder is not version controlled, so changes Codex makes cannot be easily reverted.",
+                )
+                .wrap(Wrap { trim: true })
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+            column.push("");
+        }
+
+        for (idx, (text, selection)) in OPTIONS.iter().enumerate() {
             column.push(selection_option_row(
                 idx,
                 text.to_string(),
@@ -123,19 +150,23 @@ impl KeyboardHandler for TrustDirectoryWidget {
             return;
         }
 
+        let highlighted_idx = OPTIONS
+            .iter()
+            .position(|(_, selection)| *selection == self.highlighted)
+            .unwrap_or(0);
         match key_event.code {
             KeyCode::Up | KeyCode::Char('k') => {
-                self.highlighted = TrustDirectorySelection::Trust;
+                self.highlighted = OPTIONS[highlighted_idx.saturating_sub(1)].1;
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                self.highlighted = TrustDirectorySelection::DontTrust;
+                self.highlighted = OPTIONS[(highlighted_idx + 1).min(OPTIONS.len() - 1)].1;
+            }
+            KeyCode::Char('1') | KeyCode::Char('y') => {
+                self.select(TrustDirectorySelection::TrustAlways)
             }
-            KeyCode::Char('1') | KeyCode::Char('y') => self.handle_trust(),
-            KeyCode::Char('2') | KeyCode::Char('n') => self.handle_dont_trust(),
-            KeyCode::Enter => match self.highlighted {
-                TrustDirectorySelection::Trust => self.handle_trust(),
-                TrustDirectorySelection::DontTrust => self.handle_dont_trust(),
-            },
+            KeyCode::Char('2') => self.select(TrustDirectorySelection::TrustOnce),
+            KeyCode::Char('3') => self.select(TrustDirectorySelection::Quit),
+            KeyCode::Enter => self.select(self.highlighted),
             _ => {}
         }
     }
@@ -151,30 +182,37 @@ impl StepStateProvider for TrustDirectoryWidget {
 }
 
 impl TrustDirectoryWidget {
-    fn handle_trust(&mut self) {
-        let target =
-            resolve_root_git_project_for_trust(&self.cwd).unwrap_or_else(|| self.cwd.clone());
-        if let Err(e) = set_project_trust_level(&self.codex_home, &target, TrustLevel::Trusted) {
-            tracing::error!("Failed to set project trusted: {e:?}");
-            self.error = Some(format!("Failed to set trust for {}: {e}", target.display()));
+    fn select(&mut self, selection: TrustDirectorySelection) {
+        self.highlighted = selection;
+        match selection {
+            TrustDirectorySelection::TrustAlways => {
+                let target = project_trust_target(&self.cwd);
+                if let Err(e) =
+                    set_project_trust_level(&self.codex_home, &target, TrustLevel::Trusted)
+                {
+                    tracing::error!("Failed to set project trusted: {e:?}");
+                    self.error = Some(format!("Failed to set trust for {}: {e}", target.display()));
+                }
+            }
+            // Nothing is persisted; the caller trusts the folder for this session only.
+            TrustDirectorySelection::TrustOnce | TrustDirectorySelection::Quit => {}
         }
 
-        self.selection = Some(TrustDirectorySelection::Trust);
+        self.selection = Some(selection);
     }
 
-    fn handle_dont_trust(&mut self) {
-        self.highlighted = TrustDirectorySelection::DontTrust;
-        let target =
-            resolve_root_git_project_for_trust(&self.cwd).unwrap_or_else(|| self.cwd.clone());
-        if let Err(e) = set_project_trust_level(&self.codex_home, &target, TrustLevel::Untrusted) {
-            tracing::error!("Failed to set project untrusted: {e:?}");
-            self.error = Some(format!(
-                "Failed to set untrusted for {}: {e}",
-                target.display()
-            ));
-        }
+    fn display_paths(&self, paths: &[PathBuf]) -> Vec<String> {
+        paths
+            .iter()
+            .map(|path| display_relative_to(path, &self.cwd))
+            .collect()
+    }
+}
 
-        self.selection = Some(TrustDirectorySelection::DontTrust);
+fn display_relative_to(path: &Path, cwd: &Path) -> String {
+    match path.strip_prefix(cwd) {
+        Ok(relative) if !relative.as_os_str().is_empty() => relative.display().to_string(),
+        _ => path.display().to_string(),
     }
 }
 
@@ -192,17 +230,24 @@ mod tests {
     use std::path::PathBuf;
     use tempfile::TempDir;
 
+    fn widget(codex_home: &Path, cwd: &Path, is_git_repo: bool) -> TrustDirectoryWidget {
+        TrustDirectoryWidget {
+            codex_home: codex_home.to_path_buf(),
+            cwd: cwd.to_path_buf(),
+            is_git_repo,
+            sandbox_summary: "workspace-write [workdir, /tmp, $TMPDIR]".to_string(),
+            project_docs: Vec::new(),
+            project_config_folders: Vec::new(),
+            selection: None,
+            highlighted: TrustDirectorySelection::TrustOnce,
+            error: None,
+        }
+    }
+
     #[test]
     fn release_event_does_not_change_selection() {
         let codex_home = TempDir::new().expect("temp home");
-        let mut widget = TrustDirectoryWidget {
-            codex_home: codex_home.path().to_path_buf(),
-            cwd: PathBuf::from("."),
-            is_git_repo: false,
-            selection: None,
-            highlighted: TrustDirectorySelection::DontTrust,
-            error: None,
-        };
+        let mut widget = widget(codex_home.path(), Path::new("."), false);
 
         let release = KeyEvent {
             kind: KeyEventKind::Release,
@@ -213,22 +258,60 @@ mod tests {
 
         let press = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
         widget.handle_key_event(press);
-        assert_eq!(widget.selection, Some(TrustDirectorySelection::DontTrust));
+        assert_eq!(widget.selection, Some(TrustDirectorySelection::TrustOnce));
+    }
+
+    #[test]
+    fn trust_once_does_not_persist_but_trust_always_does() {
+        let codex_home = TempDir::new().expect("temp home");
+        let project = TempDir::new().expect("temp project");
+        let config_toml = codex_home.path().join("config.toml");
+
+        let mut once = widget(codex_home.path(), project.path(), false);
+        once.handle_key_event(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE));
+        assert_eq!(once.selection, Some(TrustDirectorySelection::TrustOnce));
+        assert!(!config_toml.exists());
+
+        let mut always = widget(codex_home.path(), project.path(), false);
+        always.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+        always.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(always.selection, Some(TrustDirectorySelection::TrustAlways));
+        let target = project_trust_target(project.path());
+        let contents = std::fs::read_to_string(&config_toml).expect("config.toml written");
+        assert!(
+            contents.contains(&format!(
+                "[projects.\"{}\"]",
+                target.display().to_string().replace('\\', "\\\\")
+            )),
+            "{contents}"
+        );
     }
 
     #[test]
     fn renders_snapshot_for_git_repo() {
         let codex_home = TempDir::new().expect("temp home");
+        let cwd = PathBuf::from("/workspace/project");
         let widget = TrustDirectoryWidget {
-            codex_home: codex_home.path().to_path_buf(),
-            cwd: PathBuf::from("/workspace/project"),
-            is_git_repo: true,
-            selection: None,
-            highlighted: TrustDirectorySelection::Trust,
-            error: None,
+            project_docs: vec![cwd.join("AGENTS.md")],
+            project_config_folders: vec![cwd.join(".codex")],
+            highlighted: TrustDirectorySelection::TrustAlways,
+            ..widget(codex_home.path(), &cwd, true)
         };
 
-        let mut terminal = Terminal::new(VT100Backend::new(70, 14)).expect("terminal");
+        let mut terminal = Terminal::new(VT100Backend::new(70, 16)).expect("terminal");
+        terminal
+            .draw(|f| (&widget).render_ref(f.area(), f.buffer_mut()))
+            .expect("draw");
+
+        insta::assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn renders_snapshot_for_non_git_folder() {
+        let codex_home = TempDir::new().expect("temp home");
+        let widget = widget(codex_home.path(), Path::new("/tmp/scratch"), false);
+
+        let mut terminal = Terminal::new(VT100Backend::new(70, 18)).expect("terminal");
         terminal
             .draw(|f| (&widget).render_ref(f.area(), f.buffer_mut()))
             .expect("draw");
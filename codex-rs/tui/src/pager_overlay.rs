@@ -982,6 +982,8 @@ mod tests {
                 exit_code: 0,
                 aggregated_output: "src\nREADME.md\n".into(),
                 formatted_output: "src\nREADME.md\n".into(),
+                denied_by_sandbox: false,
+                limit_exceeded: None,
             },
             Duration::from_millis(420),
         );
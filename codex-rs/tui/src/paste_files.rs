@@ -0,0 +1,165 @@
+//! Large pastes saved as workspace files.
+//!
+//! With `tui.paste_to_file` enabled, a paste over the configured size is
+//! written to `.codex/pastes/<timestamp>.txt` under the session's working
+//! directory, and the message carries a short reference to the file instead of
+//! the raw text. The file sits inside the workspace so the model can read the
+//! parts it needs with the shell tool under any sandbox mode. Messages too
+//! large for the remaining context are saved the same way when the user picks
+//! "Save to a file and send a reference". Files older than [`PASTE_RETENTION`]
+//! are removed whenever a new paste is saved.
+
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use codex_protocol::num_format::format_with_separators;
+
+/// Directory for saved pastes, relative to the working directory.
+pub(crate) const PASTES_DIR: &str = ".codex/pastes";
+
+const PASTE_RETENTION: Duration = Duration::from_secs(60 * 60 * 24 * 7); // 7 days retention.
+
+/// A paste written to disk by [`save_paste`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SavedPaste {
+    pub(crate) path: PathBuf,
+    /// Path relative to the working directory, as shown to the user and model.
+    pub(crate) display_path: String,
+    pub(crate) line_count: usize,
+}
+
+impl SavedPaste {
+    /// Label of the composer element that stands for the attachment.
+    pub(crate) fn chip_label(&self) -> String {
+        format!(
+            "[Attached {} · {} lines]",
+            self.display_path,
+            format_line_count(self.line_count)
+        )
+    }
+
+    /// Text sent to the model in place of the paste.
+    pub(crate) fn reference(&self) -> String {
+        format!(
+            "attached paste at {}, {} lines — read relevant sections with the shell tool",
+            self.display_path,
+            format_line_count(self.line_count)
+        )
+    }
+}
+
+/// Writes `text` to a new file under [`PASTES_DIR`] in `cwd`, removing saved
+/// pastes past the retention period first.
+pub(crate) fn save_paste(cwd: &Path, text: &str) -> std::io::Result<SavedPaste> {
+    let dir = cwd.join(PASTES_DIR);
+    std::fs::create_dir_all(&dir)?;
+    remove_expired_pastes(&dir, SystemTime::now());
+
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut attempt = 1usize;
+    loop {
+        let file_name = if attempt == 1 {
+            format!("{stamp}.txt")
+        } else {
+            format!("{stamp}-{attempt}.txt")
+        };
+        let path = dir.join(&file_name);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(text.as_bytes())?;
+                return Ok(SavedPaste {
+                    path,
+                    display_path: format!("{PASTES_DIR}/{file_name}"),
+                    line_count: text.lines().count(),
+                });
+            }
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => attempt += 1,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Deletes a saved paste whose attachment was removed before sending.
+pub(crate) fn remove_paste(path: &Path) {
+    if let Err(err) = std::fs::remove_file(path) {
+        tracing::warn!("Failed to delete saved paste at {path:?}: {err:?}");
+    }
+}
+
+fn remove_expired_pastes(dir: &Path, now: SystemTime) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+        let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) else {
+            continue;
+        };
+        if now
+            .duration_since(modified)
+            .is_ok_and(|age| age >= PASTE_RETENTION)
+        {
+            remove_paste(&path);
+        }
+    }
+}
+
+fn format_line_count(count: usize) -> String {
+    format_with_separators(i64::try_from(count).unwrap_or(i64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn saves_paste_under_workspace_with_reference() {
+        let cwd = TempDir::new().expect("tempdir");
+        let text: String = (0..3_214).map(|idx| format!("log line {idx}\n")).collect();
+
+        let saved = save_paste(cwd.path(), &text).expect("save paste");
+        let again = save_paste(cwd.path(), "second").expect("save second paste");
+
+        assert_eq!(
+            std::fs::read_to_string(&saved.path).expect("read paste"),
+            text
+        );
+        assert!(saved.path.starts_with(cwd.path().join(PASTES_DIR)));
+        assert_ne!(saved.path, again.path);
+        assert_eq!(saved.line_count, 3_214);
+        assert_eq!(
+            saved.reference(),
+            format!(
+                "attached paste at {}, 3,214 lines — read relevant sections with the shell tool",
+                saved.display_path
+            )
+        );
+        assert_eq!(
+            saved.chip_label(),
+            format!("[Attached {} · 3,214 lines]", saved.display_path)
+        );
+    }
+
+    #[test]
+    fn removes_pastes_past_retention() {
+        let cwd = TempDir::new().expect("tempdir");
+        let saved = save_paste(cwd.path(), "old paste").expect("save paste");
+        let dir = cwd.path().join(PASTES_DIR);
+
+        remove_expired_pastes(&dir, SystemTime::now() + Duration::from_secs(60));
+        assert!(saved.path.exists());
+
+        remove_expired_pastes(&dir, SystemTime::now() + PASTE_RETENTION);
+        assert!(!saved.path.exists());
+    }
+}
@@ -0,0 +1,115 @@
+//! Incremental extraction of `**Bold**` section headings from a streamed
+//! reasoning summary, used to show the latest heading in the status line.
+
+/// Tracks the newest completed heading across reasoning summary deltas.
+///
+/// Only the text that may still belong to an unfinished heading is retained,
+/// so the cost of each delta is proportional to its own length.
+#[derive(Debug, Default)]
+pub(crate) struct ReasoningHeadlines {
+    pending: String,
+    latest: Option<String>,
+}
+
+impl ReasoningHeadlines {
+    /// Feed the next delta. Returns the newest heading if this delta
+    /// completed one.
+    pub(crate) fn push(&mut self, delta: &str) -> Option<String> {
+        self.pending.push_str(delta);
+        let mut found = None;
+        loop {
+            let Some(open) = self.pending.find("**") else {
+                // Keep a trailing `*` in case the next delta completes `**`.
+                let keep_from = if self.pending.ends_with('*') {
+                    self.pending.len() - 1
+                } else {
+                    self.pending.len()
+                };
+                self.pending.drain(..keep_from);
+                break;
+            };
+            let start = open + 2;
+            let Some(close) = self.pending[start..].find("**") else {
+                self.pending.drain(..open);
+                break;
+            };
+            let heading = self.pending[start..start + close].trim();
+            if !heading.is_empty() {
+                found = Some(heading.to_string());
+            }
+            self.pending.drain(..start + close + 2);
+        }
+        if found.is_some() {
+            self.latest.clone_from(&found);
+        }
+        found
+    }
+
+    /// Drop any partial heading at a reasoning section boundary.
+    pub(crate) fn section_break(&mut self) {
+        self.pending.clear();
+    }
+
+    pub(crate) fn latest(&self) -> Option<&str> {
+        self.latest.as_deref()
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.pending.clear();
+        self.latest = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn heading_split_across_deltas() {
+        let mut headlines = ReasoningHeadlines::default();
+        assert_eq!(headlines.push("*"), None);
+        assert_eq!(headlines.push("*Evaluating locking"), None);
+        assert_eq!(
+            headlines.push(" strategy*"),
+            None,
+            "a single closing star is not a complete heading"
+        );
+        assert_eq!(
+            headlines.push("*\n\nThe mutex is held across"),
+            Some("Evaluating locking strategy".to_string())
+        );
+        assert_eq!(headlines.latest(), Some("Evaluating locking strategy"));
+    }
+
+    #[test]
+    fn newest_heading_wins() {
+        let mut headlines = ReasoningHeadlines::default();
+        assert_eq!(
+            headlines.push("**Reading code**\n\nbody\n\n**Planning fix**\n\nmore"),
+            Some("Planning fix".to_string())
+        );
+        assert_eq!(headlines.push(" text without headings"), None);
+        assert_eq!(headlines.latest(), Some("Planning fix"));
+    }
+
+    #[test]
+    fn empty_headings_are_ignored() {
+        let mut headlines = ReasoningHeadlines::default();
+        assert_eq!(headlines.push("** ** then text"), None);
+        assert_eq!(headlines.latest(), None);
+    }
+
+    #[test]
+    fn section_break_discards_partial_heading() {
+        let mut headlines = ReasoningHeadlines::default();
+        headlines.push("**Checking tests**");
+        headlines.push("**Unfinished");
+        headlines.section_break();
+        assert_eq!(headlines.push(" heading**"), None);
+        assert_eq!(headlines.latest(), Some("Checking tests"));
+
+        headlines.reset();
+        assert_eq!(headlines.latest(), None);
+    }
+}
@@ -44,6 +44,9 @@ const LOAD_NEAR_THRESHOLD: usize = 5;
 pub enum SessionSelection {
     StartFresh,
     Resume(PathBuf),
+    /// Resume the session next to the running ones instead of replacing them.
+    /// Only offered by the picker opened from inside a session.
+    ResumeInNewTab(PathBuf),
     Fork(PathBuf),
     Exit,
 }
@@ -51,27 +54,34 @@ pub enum SessionSelection {
 #[derive(Clone, Copy, Debug)]
 pub enum SessionPickerAction {
     Resume,
+    /// Resume from inside a running TUI, where Tab opens the selection as an
+    /// additional session.
+    ResumeInSession,
     Fork,
 }
 
 impl SessionPickerAction {
     fn title(self) -> &'static str {
         match self {
-            SessionPickerAction::Resume => "Resume a previous session",
+            SessionPickerAction::Resume | SessionPickerAction::ResumeInSession => {
+                "Resume a previous session"
+            }
             SessionPickerAction::Fork => "Fork a previous session",
         }
     }
 
     fn action_label(self) -> &'static str {
         match self {
-            SessionPickerAction::Resume => "resume",
+            SessionPickerAction::Resume | SessionPickerAction::ResumeInSession => "resume",
             SessionPickerAction::Fork => "fork",
         }
     }
 
     fn selection(self, path: PathBuf) -> SessionSelection {
         match self {
-            SessionPickerAction::Resume => SessionSelection::Resume(path),
+            SessionPickerAction::Resume | SessionPickerAction::ResumeInSession => {
+                SessionSelection::Resume(path)
+            }
             SessionPickerAction::Fork => SessionSelection::Fork(path),
         }
     }
@@ -115,6 +125,23 @@ pub async fn run_resume_picker(
     .await
 }
 
+/// The resume picker shown by `/resume`, which can also open the selected
+/// session in a new tab.
+pub async fn run_in_session_resume_picker(
+    tui: &mut Tui,
+    codex_home: &Path,
+    default_provider: &str,
+) -> Result<SessionSelection> {
+    run_session_picker(
+        tui,
+        codex_home,
+        default_provider,
+        false,
+        SessionPickerAction::ResumeInSession,
+    )
+    .await
+}
+
 pub async fn run_fork_picker(
     tui: &mut Tui,
     codex_home: &Path,
@@ -312,6 +339,8 @@ impl SearchState {
 struct Row {
     path: PathBuf,
     preview: String,
+    title: Option<String>,
+    tags: Vec<String>,
     created_at: Option<DateTime<Utc>>,
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
@@ -374,6 +403,11 @@ impl PickerState {
                     return Ok(Some(self.action.selection(row.path.clone())));
                 }
             }
+            KeyCode::Tab if matches!(self.action, SessionPickerAction::ResumeInSession) => {
+                if let Some(row) = self.filtered_rows.get(self.selected) {
+                    return Ok(Some(SessionSelection::ResumeInNewTab(row.path.clone())));
+                }
+            }
             KeyCode::Up => {
                 if self.selected > 0 {
                     self.selected -= 1;
@@ -516,11 +550,8 @@ impl PickerState {
         if self.query.is_empty() {
             self.filtered_rows = base_iter.cloned().collect();
         } else {
-            let q = self.query.to_lowercase();
-            self.filtered_rows = base_iter
-                .filter(|r| r.preview.to_lowercase().contains(&q))
-                .cloned()
-                .collect();
+            let query = SearchQuery::parse(&self.query);
+            self.filtered_rows = base_iter.filter(|r| query.matches(r)).cloned().collect();
         }
         if self.selected >= self.filtered_rows.len() {
             self.selected = self.filtered_rows.len().saturating_sub(1);
@@ -696,6 +727,44 @@ impl PickerState {
     }
 }
 
+/// Picker search input: `#tag` words must all match a session tag, and the
+/// remaining text is matched against the title and first message.
+struct SearchQuery {
+    tags: Vec<String>,
+    text: String,
+}
+
+impl SearchQuery {
+    fn parse(query: &str) -> Self {
+        let mut tags = Vec::new();
+        let mut words = Vec::new();
+        for word in query.split_whitespace() {
+            match word.strip_prefix('#') {
+                Some(tag) if !tag.is_empty() => tags.push(tag.to_lowercase()),
+                _ => words.push(word),
+            }
+        }
+        Self {
+            tags,
+            text: words.join(" ").to_lowercase(),
+        }
+    }
+
+    fn matches(&self, row: &Row) -> bool {
+        let tags_match = self
+            .tags
+            .iter()
+            .all(|wanted| row.tags.iter().any(|tag| tag.to_lowercase() == *wanted));
+        tags_match
+            && (self.text.is_empty()
+                || row.preview.to_lowercase().contains(&self.text)
+                || row
+                    .title
+                    .as_ref()
+                    .is_some_and(|title| title.to_lowercase().contains(&self.text)))
+    }
+}
+
 fn rows_from_items(items: Vec<ThreadItem>) -> Vec<Row> {
     items.into_iter().map(|item| head_to_row(&item)).collect()
 }
@@ -712,7 +781,12 @@ fn head_to_row(item: &ThreadItem) -> Row {
         .and_then(parse_timestamp_str)
         .or(created_at);
 
-    let (cwd, git_branch) = extract_session_meta_from_head(&item.head);
+    let HeadMeta {
+        cwd,
+        git_branch,
+        title,
+        tags,
+    } = extract_session_meta_from_head(&item.head);
     let preview = preview_from_head(&item.head)
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
@@ -721,6 +795,8 @@ fn head_to_row(item: &ThreadItem) -> Row {
     Row {
         path: item.path.clone(),
         preview,
+        title,
+        tags,
         created_at,
         updated_at,
         cwd,
@@ -728,15 +804,29 @@ fn head_to_row(item: &ThreadItem) -> Row {
     }
 }
 
-fn extract_session_meta_from_head(head: &[serde_json::Value]) -> (Option<PathBuf>, Option<String>) {
+#[derive(Default)]
+struct HeadMeta {
+    cwd: Option<PathBuf>,
+    git_branch: Option<String>,
+    title: Option<String>,
+    tags: Vec<String>,
+}
+
+fn extract_session_meta_from_head(head: &[serde_json::Value]) -> HeadMeta {
     for value in head {
         if let Ok(meta_line) = serde_json::from_value::<SessionMetaLine>(value.clone()) {
-            let cwd = Some(meta_line.meta.cwd);
-            let git_branch = meta_line.git.and_then(|git| git.branch);
-            return (cwd, git_branch);
+            return HeadMeta {
+                cwd: Some(meta_line.meta.cwd),
+                git_branch: meta_line.git.and_then(|git| git.branch),
+                title: meta_line
+                    .meta
+                    .title
+                    .filter(|title| !title.trim().is_empty()),
+                tags: meta_line.meta.tags,
+            };
         }
     }
-    (None, None)
+    HeadMeta::default()
 }
 
 fn paths_match(a: &Path, b: &Path) -> bool {
@@ -805,10 +895,19 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
 
         // Hint line
         let action_label = state.action.action_label();
-        let hint_line: Line = vec![
+        let mut hint_spans: Vec<Span> = vec![
             key_hint::plain(KeyCode::Enter).into(),
             format!(" to {action_label} ").dim(),
             "    ".dim(),
+        ];
+        if matches!(state.action, SessionPickerAction::ResumeInSession) {
+            hint_spans.extend([
+                key_hint::plain(KeyCode::Tab).into(),
+                " to open in new tab ".dim(),
+                "    ".dim(),
+            ]);
+        }
+        hint_spans.extend([
             key_hint::plain(KeyCode::Esc).into(),
             " to start new ".dim(),
             "    ".dim(),
@@ -819,9 +918,8 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
             "/".dim(),
             key_hint::plain(KeyCode::Down).into(),
             " to browse".dim(),
-        ]
-        .into();
-        frame.render_widget_ref(hint_line, hint);
+        ]);
+        frame.render_widget_ref(Line::from(hint_spans), hint);
     })
 }
 
@@ -909,7 +1007,19 @@ fn render_list(
         if add_leading_gap {
             preview_width = preview_width.saturating_sub(2);
         }
-        let preview = truncate_text(&row.preview, preview_width);
+        let tags_label = row
+            .tags
+            .iter()
+            .map(|tag| format!("#{tag}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let tags_width = UnicodeWidthStr::width(tags_label.as_str());
+        // Only show tags when the title still gets a reasonable amount of room.
+        let show_tags = !tags_label.is_empty() && preview_width > tags_width + 1 + 20;
+        if show_tags {
+            preview_width -= tags_width + 1;
+        }
+        let preview = truncate_text(row.title.as_deref().unwrap_or(&row.preview), preview_width);
         let mut spans: Vec<Span> = vec![marker];
         if let Some(updated) = updated_span {
             spans.push(updated);
@@ -927,6 +1037,10 @@ fn render_list(
             spans.push("  ".into());
         }
         spans.push(preview.into());
+        if show_tags {
+            spans.push(" ".into());
+            spans.push(tags_label.dim());
+        }
 
         let line: Line = spans.into();
         let rect = Rect::new(area.x, y, area.width, 1);
@@ -1252,6 +1366,55 @@ mod tests {
         assert_eq!(row.updated_at, Some(expected_updated));
     }
 
+    #[test]
+    fn row_reads_title_and_tags_from_session_meta() {
+        let mut head = head_with_ts_and_user_text("2025-01-01T00:00:00Z", &["Hello"]);
+        head.insert(
+            0,
+            json!({
+                "id": "3f941c35-29b3-493b-b0a4-e25800d9aeb0",
+                "timestamp": "2025-01-01T00:00:00Z",
+                "cwd": "/tmp",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "model_provider": "openai",
+                "title": "Auth refactor",
+                "tags": ["auth", "backend"],
+            }),
+        );
+        let item = ThreadItem {
+            path: PathBuf::from("/tmp/a.jsonl"),
+            head,
+            created_at: Some("2025-01-01T00:00:00Z".into()),
+            updated_at: Some("2025-01-01T00:00:00Z".into()),
+        };
+
+        let row = head_to_row(&item);
+        assert_eq!(row.title.as_deref(), Some("Auth refactor"));
+        assert_eq!(row.tags, vec!["auth".to_string(), "backend".to_string()]);
+        assert_eq!(row.preview, "Hello");
+    }
+
+    #[test]
+    fn search_query_filters_by_tags_and_title() {
+        let row = Row {
+            path: PathBuf::from("/tmp/a.jsonl"),
+            preview: String::from("Fix the login flow"),
+            title: Some(String::from("Auth refactor")),
+            tags: vec![String::from("Auth"), String::from("backend")],
+            created_at: None,
+            updated_at: None,
+            cwd: None,
+            git_branch: None,
+        };
+
+        assert!(SearchQuery::parse("#auth").matches(&row));
+        assert!(SearchQuery::parse("#auth #backend refactor").matches(&row));
+        assert!(SearchQuery::parse("login").matches(&row));
+        assert!(!SearchQuery::parse("#frontend").matches(&row));
+        assert!(!SearchQuery::parse("#auth billing").matches(&row));
+    }
+
     #[test]
     fn resume_table_snapshot() {
         use crate::custom_terminal::Terminal;
@@ -1275,6 +1438,8 @@ mod tests {
             Row {
                 path: PathBuf::from("/tmp/a.jsonl"),
                 preview: String::from("Fix resume picker timestamps"),
+                title: None,
+                tags: Vec::new(),
                 created_at: Some(now - Duration::minutes(16)),
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
@@ -1283,6 +1448,8 @@ mod tests {
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
                 preview: String::from("Investigate lazy pagination cap"),
+                title: None,
+                tags: Vec::new(),
                 created_at: Some(now - Duration::hours(1)),
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
@@ -1291,6 +1458,8 @@ mod tests {
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
                 preview: String::from("Explain the codebase"),
+                title: None,
+                tags: Vec::new(),
                 created_at: Some(now - Duration::hours(2)),
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
@@ -1634,6 +1803,45 @@ mod tests {
         assert_eq!(state.selected, 5);
     }
 
+    #[tokio::test]
+    async fn tab_opens_selection_in_new_tab_only_inside_a_session() {
+        for (action, expect_new_tab) in [
+            (SessionPickerAction::Resume, false),
+            (SessionPickerAction::ResumeInSession, true),
+        ] {
+            let loader: PageLoader = Arc::new(|_| {});
+            let mut state = PickerState::new(
+                PathBuf::from("/tmp"),
+                FrameRequester::test_dummy(),
+                loader,
+                String::from("openai"),
+                true,
+                None,
+                action,
+            );
+            state.reset_pagination();
+            state.ingest_page(page(
+                vec![make_item("/tmp/a.jsonl", "2025-01-01T00:00:00Z", "a")],
+                None,
+                1,
+                false,
+            ));
+
+            let selection = state
+                .handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE))
+                .await
+                .unwrap();
+            match selection {
+                Some(SessionSelection::ResumeInNewTab(path)) => {
+                    assert!(expect_new_tab);
+                    assert_eq!(path, PathBuf::from("/tmp/a.jsonl"));
+                }
+                None => assert!(!expect_new_tab),
+                other => panic!("unexpected selection: {other:?}"),
+            }
+        }
+    }
+
     #[tokio::test]
     async fn up_at_bottom_does_not_scroll_when_visible() {
         let loader: PageLoader = Arc::new(|_| {});
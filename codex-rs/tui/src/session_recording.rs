@@ -0,0 +1,348 @@
+//! `codex --record PATH.cast`: writes what the TUI commits to the scrollback,
+//! plus status line changes, as an asciicast v2 file.
+//!
+//! Events are serialized on the UI thread and handed to a writer thread, so
+//! rendering never waits on the disk. Every event is a single JSON line that
+//! is flushed as soon as it is written, which keeps the file playable even if
+//! Codex is killed; [`RecordingGuard`] and a panic hook drain the queue on the
+//! way out. User messages are replaced by a placeholder unless
+//! `--record-input` is passed, since they may contain secrets.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::mpsc;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+use ratatui::text::Line;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::history_cell::HistoryCell;
+use crate::history_cell::UserHistoryCell;
+use crate::insert_history::write_spans;
+
+const UNRECORDED_INPUT: &str = "› [message not recorded; pass --record-input to include it]";
+
+static RECORDER: OnceLock<Recorder> = OnceLock::new();
+
+struct Recorder {
+    started: Instant,
+    record_input: bool,
+    /// Last status line written, so repeated updates are recorded once.
+    status: Mutex<Option<String>>,
+    sender: Mutex<Option<mpsc::Sender<String>>>,
+    writer: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Recorder {
+    fn create(path: &Path, record_input: bool, (width, height): (u16, u16)) -> io::Result<Self> {
+        let mut opts = OpenOptions::new();
+        opts.create(true).truncate(true).write(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            opts.mode(0o600);
+        }
+        let mut file = BufWriter::new(opts.open(path)?);
+        let header = json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "timestamp": chrono::Utc::now().timestamp(),
+            "env": {
+                "SHELL": std::env::var("SHELL").ok(),
+                "TERM": std::env::var("TERM").ok(),
+            },
+        });
+        writeln!(file, "{header}")?;
+        file.flush()?;
+
+        let (sender, receiver) = mpsc::channel::<String>();
+        let writer = thread::Builder::new()
+            .name("codex-session-recording".to_string())
+            .spawn(move || write_events(file, receiver))?;
+        Ok(Self {
+            started: Instant::now(),
+            record_input,
+            status: Mutex::new(None),
+            sender: Mutex::new(Some(sender)),
+            writer: Mutex::new(Some(writer)),
+        })
+    }
+
+    fn event(&self, kind: &str, data: &str) {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let elapsed = (elapsed * 1_000_000.0).round() / 1_000_000.0;
+        let line = json!([elapsed, kind, data]).to_string();
+        if let Ok(sender) = self.sender.lock()
+            && let Some(sender) = sender.as_ref()
+        {
+            let _ = sender.send(line);
+        }
+    }
+
+    fn record_cell(&self, cell: &dyn HistoryCell, lines: &[Line<'static>]) {
+        if let Some(user) = cell.as_any().downcast_ref::<UserHistoryCell>() {
+            if !self.record_input {
+                self.record_lines(&[Line::from(""), Line::from(UNRECORDED_INPUT)]);
+                return;
+            }
+            self.event("i", &format!("{}\r", user.message));
+        }
+        self.record_lines(lines);
+    }
+
+    fn record_lines(&self, lines: &[Line<'static>]) {
+        // Clear a pending status line before the cell is written over it.
+        let mut out = b"\r\x1b[2K".to_vec();
+        for line in lines {
+            if write_spans(&mut out, line.iter()).is_err() {
+                return;
+            }
+            out.extend_from_slice(b"\r\n");
+        }
+        if let Ok(mut status) = self.status.lock() {
+            *status = None;
+        }
+        self.event("o", &String::from_utf8_lossy(&out));
+    }
+
+    fn record_status(&self, status: &str) {
+        let Ok(mut last) = self.status.lock() else {
+            return;
+        };
+        if last.as_deref() == Some(status) {
+            return;
+        }
+        *last = Some(status.to_string());
+        drop(last);
+        self.event("o", &format!("\r\x1b[2K\x1b[2m• {status}\x1b[0m"));
+    }
+
+    /// Closes the queue and waits until everything queued so far is on disk.
+    fn finish(&self) {
+        if let Ok(mut sender) = self.sender.lock() {
+            sender.take();
+        }
+        let writer = self.writer.lock().ok().and_then(|mut writer| writer.take());
+        if let Some(writer) = writer
+            && writer.thread().id() != thread::current().id()
+        {
+            let _ = writer.join();
+        }
+    }
+}
+
+fn write_events(mut file: BufWriter<File>, receiver: mpsc::Receiver<String>) {
+    for line in receiver {
+        if let Err(err) = writeln!(file, "{line}").and_then(|()| file.flush()) {
+            tracing::warn!("session recording write error: {err}");
+            return;
+        }
+    }
+}
+
+/// Finalizes the recording when dropped, including on early returns.
+pub(crate) struct RecordingGuard;
+
+impl Drop for RecordingGuard {
+    fn drop(&mut self) {
+        if let Some(recorder) = RECORDER.get() {
+            recorder.finish();
+        }
+    }
+}
+
+/// Starts recording to `path` for the rest of the process.
+pub(crate) fn start(path: &Path, record_input: bool) -> io::Result<RecordingGuard> {
+    let size = crossterm::terminal::size().unwrap_or((80, 24));
+    let recorder = Recorder::create(path, record_input, size)?;
+    if RECORDER.set(recorder).is_err() {
+        return Err(io::Error::other("a session recording is already running"));
+    }
+
+    let prev_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(recorder) = RECORDER.get() {
+            recorder.finish();
+        }
+        prev_hook(info);
+    }));
+    Ok(RecordingGuard)
+}
+
+/// Records a cell that was just inserted into the scrollback as `lines`.
+pub(crate) fn record_cell(cell: &dyn HistoryCell, lines: &[Line<'static>]) {
+    if let Some(recorder) = RECORDER.get() {
+        recorder.record_cell(cell, lines);
+    }
+}
+
+/// Records the status line header (e.g. "Working") when it changes.
+pub(crate) fn record_status(status: &str) {
+    if let Some(recorder) = RECORDER.get() {
+        recorder.record_status(status);
+    }
+}
+
+/// Plays an asciicast v2 recording back on stdout.
+///
+/// `speed` divides the recorded delays; `idle_limit` caps any single delay.
+pub fn replay_recording(path: &Path, speed: f64, idle_limit: Option<Duration>) -> io::Result<()> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut stdout = io::stdout().lock();
+    let mut previous = 0.0;
+    for (time, output) in read_output_events(reader)? {
+        let mut delay = Duration::from_secs_f64((time - previous).max(0.0) / speed);
+        if let Some(limit) = idle_limit {
+            delay = delay.min(limit);
+        }
+        previous = time;
+        thread::sleep(delay);
+        stdout.write_all(output.as_bytes())?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+/// The `"o"` events of an asciicast v2 stream, after validating its header.
+fn read_output_events(reader: impl BufRead) -> io::Result<Vec<(f64, String)>> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut lines = reader.lines();
+    let header: Value = match lines.next() {
+        Some(line) => serde_json::from_str(&line?)
+            .map_err(|err| invalid(format!("invalid asciicast header: {err}")))?,
+        None => return Err(invalid("empty recording".to_string())),
+    };
+    if header.get("version").and_then(Value::as_u64) != Some(2) {
+        return Err(invalid(
+            "only asciicast v2 recordings are supported".to_string(),
+        ));
+    }
+
+    let mut events = Vec::new();
+    for (index, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event: (f64, String, String) = serde_json::from_str(&line)
+            .map_err(|err| invalid(format!("invalid event on line {}: {err}", index + 2)))?;
+        if event.1 == "o" {
+            events.push((event.0, event.2));
+        }
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Stylize;
+
+    fn user_cell(message: &str) -> UserHistoryCell {
+        UserHistoryCell {
+            message: message.to_string(),
+            text_elements: Vec::new(),
+            local_image_paths: Vec::new(),
+        }
+    }
+
+    fn record(record_input: bool) -> Vec<Value> {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("session.cast");
+        let recorder = Recorder::create(&path, record_input, (100, 30)).expect("recorder");
+
+        let user = user_cell("my token is hunter2");
+        recorder.record_cell(&user, &user.display_lines(100));
+        recorder.record_status("Working");
+        recorder.record_status("Working");
+        recorder.record_lines(&[Line::from(vec!["• ".dim(), "Done".into()])]);
+        recorder.finish();
+
+        std::fs::read_to_string(&path)
+            .expect("read recording")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect()
+    }
+
+    #[test]
+    fn records_asciicast_without_user_input_by_default() {
+        let lines = record(false);
+
+        assert_eq!(
+            (
+                &lines[0]["version"],
+                &lines[0]["width"],
+                &lines[0]["height"]
+            ),
+            (&json!(2), &json!(100), &json!(30))
+        );
+        let events: Vec<(&str, &str)> = lines[1..]
+            .iter()
+            .map(|event| {
+                (
+                    event[1].as_str().expect("kind"),
+                    event[2].as_str().expect("data"),
+                )
+            })
+            .collect();
+        assert_eq!(events.len(), 3, "duplicate status is recorded once");
+        assert_eq!(events[0].0, "o");
+        assert!(events[0].1.contains(UNRECORDED_INPUT));
+        assert!(!events[0].1.contains("hunter2"));
+        assert!(events[1].1.contains("• Working"));
+        assert!(events[2].1.contains("Done\r\n"));
+
+        let times: Vec<f64> = lines[1..]
+            .iter()
+            .map(|event| event[0].as_f64().expect("time"))
+            .collect();
+        assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn record_input_keeps_user_messages() {
+        let lines = record(true);
+
+        assert_eq!(lines[1][1], json!("i"));
+        assert_eq!(lines[1][2], json!("my token is hunter2\r"));
+        assert!(
+            lines[2][2]
+                .as_str()
+                .expect("data")
+                .contains("my token is hunter2")
+        );
+    }
+
+    #[test]
+    fn replay_reads_output_events() {
+        let cast = r#"{"version": 2, "width": 80, "height": 24}
+[0.5, "o", "hello\r\n"]
+[0.7, "i", "ignored"]
+[1.25, "o", "world\r\n"]
+"#;
+
+        assert_eq!(
+            read_output_events(cast.as_bytes()).expect("events"),
+            vec![
+                (0.5, "hello\r\n".to_string()),
+                (1.25, "world\r\n".to_string()),
+            ]
+        );
+        assert!(read_output_events(r#"{"version": 1}"#.as_bytes()).is_err());
+    }
+}
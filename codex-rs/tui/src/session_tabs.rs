@@ -0,0 +1,212 @@
+//! Per-session state shown in the tab bar when several conversations are open.
+//!
+//! Every conversation keeps streaming into its own bounded event buffer while
+//! it is in the background. The buffers feed [`SessionActivity`] so the tab
+//! bar can show which sessions are working and which are blocked on the user.
+
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use crossterm::event::KeyCode;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::key_hint;
+use crate::text_formatting::truncate_text;
+
+const MAX_TAB_NAME_GRAPHEMES: usize = 24;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct SessionActivity {
+    /// A turn is in progress.
+    pub(crate) running: bool,
+    /// A request arrived while the session was in the background and has not
+    /// been seen yet.
+    pub(crate) needs_attention: bool,
+}
+
+impl SessionActivity {
+    pub(crate) fn observe(&mut self, msg: &EventMsg, focused: bool) {
+        match msg {
+            EventMsg::TurnStarted(_) => self.running = true,
+            EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_) | EventMsg::ShutdownComplete => {
+                self.running = false;
+                self.needs_attention = false;
+            }
+            msg if is_user_request(msg) && !focused => self.needs_attention = true,
+            _ => {}
+        }
+    }
+}
+
+/// Whether the turn that emitted `msg` is blocked until the user answers.
+pub(crate) fn is_user_request(msg: &EventMsg) -> bool {
+    matches!(
+        msg,
+        EventMsg::ExecApprovalRequest(_)
+            | EventMsg::ApplyPatchApprovalRequest(_)
+            | EventMsg::ElicitationRequest(_)
+            | EventMsg::RequestUserInput(_)
+    )
+}
+
+/// Whether `op` is the user's answer to the request carried by `event`.
+pub(crate) fn answers_request(op: &Op, event: &Event) -> bool {
+    match (op, &event.msg) {
+        (Op::ExecApproval { id, .. }, EventMsg::ExecApprovalRequest(_))
+        | (Op::PatchApproval { id, .. }, EventMsg::ApplyPatchApprovalRequest(_)) => *id == event.id,
+        (Op::UserInputAnswer { id, .. }, EventMsg::RequestUserInput(request)) => {
+            *id == request.turn_id
+        }
+        (
+            Op::ResolveElicitation {
+                server_name,
+                request_id,
+                ..
+            },
+            EventMsg::ElicitationRequest(request),
+        ) => *server_name == request.server_name && *request_id == request.id,
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SessionTab {
+    pub(crate) name: Option<String>,
+    pub(crate) activity: SessionActivity,
+    pub(crate) focused: bool,
+}
+
+/// One line listing the open sessions by number, with a cyan dot on sessions
+/// waiting for the user and a dim one on sessions that are still working.
+pub(crate) fn tab_bar_line(tabs: &[SessionTab]) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for (idx, tab) in tabs.iter().enumerate() {
+        if idx > 0 {
+            spans.push("  ".into());
+        }
+        let mut label = (idx + 1).to_string();
+        if let Some(name) = &tab.name {
+            label.push(' ');
+            label.push_str(&truncate_text(name, MAX_TAB_NAME_GRAPHEMES));
+        }
+        spans.push(if tab.focused {
+            label.cyan().bold()
+        } else {
+            label.dim()
+        });
+        if tab.activity.needs_attention {
+            spans.push(" •".cyan());
+        } else if tab.activity.running {
+            spans.push(" •".dim());
+        }
+    }
+    spans.push("   ".into());
+    spans.push(key_hint::ctrl(KeyCode::Tab).into());
+    spans.push(" or /sessions to switch".dim());
+    spans.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ExecApprovalRequestEvent;
+    use codex_core::protocol::TurnCompleteEvent;
+    use codex_core::protocol::TurnStartedEvent;
+    use codex_protocol::config_types::ModeKind;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn exec_request(turn_id: &str) -> Event {
+        Event {
+            id: turn_id.to_string(),
+            msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                call_id: "call-1".to_string(),
+                turn_id: turn_id.to_string(),
+                command: vec!["ls".to_string()],
+                cwd: PathBuf::from("/tmp"),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                parsed_cmd: Vec::new(),
+                cwd_outside_workspace: false,
+                cwd_writable: true,
+            }),
+        }
+    }
+
+    #[test]
+    fn background_requests_need_attention_until_the_turn_ends() {
+        let mut activity = SessionActivity::default();
+        activity.observe(
+            &EventMsg::TurnStarted(TurnStartedEvent {
+                model_context_window: None,
+                collaboration_mode_kind: ModeKind::Custom,
+                settings: None,
+            }),
+            false,
+        );
+        activity.observe(&exec_request("turn-1").msg, false);
+        assert_eq!(
+            activity,
+            SessionActivity {
+                running: true,
+                needs_attention: true,
+            }
+        );
+
+        activity.observe(
+            &EventMsg::TurnComplete(TurnCompleteEvent {
+                last_agent_message: None,
+                system_fingerprint: None,
+            }),
+            false,
+        );
+        assert_eq!(activity, SessionActivity::default());
+    }
+
+    #[test]
+    fn focused_requests_do_not_need_attention() {
+        let mut activity = SessionActivity::default();
+        activity.observe(&exec_request("turn-1").msg, true);
+        assert!(!activity.needs_attention);
+    }
+
+    #[test]
+    fn approvals_answer_requests_from_the_same_turn() {
+        let request = exec_request("turn-1");
+        let approve = |id: &str| Op::ExecApproval {
+            id: id.to_string(),
+            decision: codex_core::protocol::ReviewDecision::Approved,
+        };
+        assert!(answers_request(&approve("turn-1"), &request));
+        assert!(!answers_request(&approve("turn-2"), &request));
+    }
+
+    #[test]
+    fn tab_bar_marks_focus_and_activity() {
+        let line = tab_bar_line(&[
+            SessionTab {
+                name: Some("fix tests".to_string()),
+                activity: SessionActivity::default(),
+                focused: true,
+            },
+            SessionTab {
+                name: None,
+                activity: SessionActivity {
+                    running: true,
+                    needs_attention: true,
+                },
+                focused: false,
+            },
+        ]);
+        let text: String = line
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(text, "1 fix tests  2 •   ctrl + tab or /sessions to switch");
+        assert_eq!(line.spans[0], "1 fix tests".cyan().bold());
+        assert_eq!(line.spans[3], " •".cyan());
+    }
+}
@@ -0,0 +1,508 @@
+//! Setup wizard: permissions preset, default model and desktop notifications.
+//!
+//! On a first launch (no `config.toml` yet) the wizard runs as an onboarding step and its
+//! answers become a fresh, commented `config.toml`. `/setup` opens the same pages in the bottom
+//! pane and writes the answers into the existing file with `ConfigEditsBuilder`.
+
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_common::approval_presets::ApprovalPreset;
+use codex_common::approval_presets::builtin_approval_presets;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::Config;
+use codex_core::config::ConfigToml;
+use codex_core::config::types::Notifications;
+use codex_core::config::types::Tui;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::openai_models::ModelPreset;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Wrap;
+
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::render::renderable::RenderableExt as _;
+use crate::selection_list::selection_option_row;
+
+const HEADER_COMMENT: &str = "\
+# Codex configuration written by the setup wizard.
+# Run `/setup` inside Codex to change these answers, or see
+# https://github.com/openai/codex/blob/main/docs/config.md for every option.
+";
+
+/// Comment placed above each key the wizard sets.
+const KEY_COMMENTS: [(&str, &str); 4] = [
+    (
+        "approval_policy",
+        "When Codex stops to ask you first: untrusted, on-failure, on-request or never.",
+    ),
+    (
+        "model",
+        "Model used for new sessions. `/model` switches it for the current one.",
+    ),
+    (
+        "sandbox_mode",
+        "What commands may touch: read-only, workspace-write or danger-full-access.",
+    ),
+    (
+        "notifications",
+        "Desktop notifications when a turn ends or needs approval while the terminal is unfocused.",
+    ),
+];
+
+const TUI_TABLE_COMMENT: &str =
+    "# Terminal UI settings. Keys the wizard did not ask about show their defaults.";
+
+/// Answers collected by the wizard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SetupChoices {
+    pub approval: AskForApproval,
+    pub sandbox_mode: SandboxMode,
+    pub model: String,
+    pub notifications: bool,
+}
+
+impl SetupChoices {
+    /// The settings `config` currently resolves to. `default_model` is used when no model is
+    /// configured.
+    pub(crate) fn from_config(config: &Config, default_model: &str) -> Self {
+        Self {
+            approval: config.approval_policy.value(),
+            sandbox_mode: sandbox_mode_for(config.sandbox_policy.get()),
+            model: config
+                .model
+                .clone()
+                .unwrap_or_else(|| default_model.to_string()),
+            notifications: config.tui_notifications != Notifications::Enabled(false),
+        }
+    }
+
+    /// The approval preset these choices correspond to, if any.
+    pub(crate) fn preset(&self) -> Option<ApprovalPreset> {
+        builtin_approval_presets().into_iter().find(|preset| {
+            preset.approval == self.approval
+                && sandbox_mode_for(&preset.sandbox) == self.sandbox_mode
+        })
+    }
+
+    /// The choices as the `config.toml` they stand for.
+    pub(crate) fn config_toml(&self) -> std::io::Result<ConfigToml> {
+        // `Tui::default()` does not apply the serde defaults, so start from an empty table.
+        let tui: Tui = toml::from_str("").map_err(std::io::Error::other)?;
+        Ok(ConfigToml {
+            model: Some(self.model.clone()),
+            approval_policy: Some(self.approval),
+            sandbox_mode: Some(self.sandbox_mode),
+            tui: Some(Tui {
+                notifications: Notifications::Enabled(self.notifications),
+                ..tui
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+fn sandbox_mode_for(policy: &SandboxPolicy) -> SandboxMode {
+    match policy {
+        SandboxPolicy::ReadOnly => SandboxMode::ReadOnly,
+        SandboxPolicy::WorkspaceWrite { .. } => SandboxMode::WorkspaceWrite,
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+            SandboxMode::DangerFullAccess
+        }
+    }
+}
+
+/// Render `choices` as a commented `config.toml`. The body is the `ConfigToml` serialization
+/// with empty tables dropped, so parsing it back yields `choices.config_toml()` exactly.
+pub(crate) fn render_initial_config(choices: &SetupChoices) -> std::io::Result<String> {
+    let mut value = toml::Value::try_from(choices.config_toml()?).map_err(std::io::Error::other)?;
+    if let toml::Value::Table(table) = &mut value {
+        table.retain(|_, item| !matches!(item, toml::Value::Table(inner) if inner.is_empty()));
+    }
+    let body = toml::to_string(&value).map_err(std::io::Error::other)?;
+
+    let mut out = String::from(HEADER_COMMENT);
+    for line in body.lines() {
+        if line == "[tui]" {
+            out.push('\n');
+            out.push_str(TUI_TABLE_COMMENT);
+            out.push('\n');
+        } else if let Some((_, comment)) = KEY_COMMENTS
+            .iter()
+            .find(|(key, _)| line.starts_with(&format!("{key} = ")))
+        {
+            out.push_str("\n# ");
+            out.push_str(comment);
+            out.push('\n');
+        }
+        if !line.is_empty() {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// Write the first-run `config.toml` under `codex_home`. Fails instead of replacing a file
+/// that already exists.
+pub(crate) fn write_initial_config(
+    codex_home: &Path,
+    choices: &SetupChoices,
+) -> std::io::Result<PathBuf> {
+    let contents = render_initial_config(choices)?;
+    std::fs::create_dir_all(codex_home)?;
+    let path = codex_home.join(CONFIG_TOML_FILE);
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)?;
+    file.write_all(contents.as_bytes())?;
+    Ok(path)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SetupPage {
+    Permissions,
+    Model,
+    Notifications,
+}
+
+impl SetupPage {
+    const ALL: [SetupPage; 3] = [
+        SetupPage::Permissions,
+        SetupPage::Model,
+        SetupPage::Notifications,
+    ];
+
+    fn index(self) -> usize {
+        match self {
+            SetupPage::Permissions => 0,
+            SetupPage::Model => 1,
+            SetupPage::Notifications => 2,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            SetupPage::Permissions => "Permissions",
+            SetupPage::Model => "Model",
+            SetupPage::Notifications => "Notifications",
+        }
+    }
+
+    fn question(self) -> &'static str {
+        match self {
+            SetupPage::Permissions => "How much should Codex be allowed to do without asking you?",
+            SetupPage::Model => "Which model should new sessions use?",
+            SetupPage::Notifications => {
+                "Send a desktop notification when Codex finishes a turn or needs your approval while the terminal is in the background?"
+            }
+        }
+    }
+}
+
+struct ModelOption {
+    model: String,
+    display_name: String,
+    description: String,
+}
+
+/// Keyboard-driven pages of the wizard. Owners feed it keys and read [`SetupWizard::outcome`].
+pub(crate) struct SetupWizard {
+    presets: Vec<ApprovalPreset>,
+    models: Vec<ModelOption>,
+    page: SetupPage,
+    /// Highlighted row on each page, indexed by [`SetupPage::index`].
+    highlighted: [usize; 3],
+    outcome: Option<SetupOutcome>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SetupOutcome {
+    Finished(SetupChoices),
+    /// Esc on the first page.
+    Cancelled,
+}
+
+impl SetupWizard {
+    /// `models` are the picker-visible presets; `initial` decides which row starts highlighted
+    /// on each page.
+    pub(crate) fn new(models: Vec<ModelPreset>, initial: &SetupChoices) -> Self {
+        let presets = builtin_approval_presets();
+        let mut models: Vec<ModelOption> = models
+            .into_iter()
+            .filter(|preset| preset.show_in_picker)
+            .map(|preset| ModelOption {
+                model: preset.model,
+                display_name: preset.display_name,
+                description: preset.description,
+            })
+            .collect();
+        if !models.iter().any(|option| option.model == initial.model) {
+            models.insert(
+                0,
+                ModelOption {
+                    model: initial.model.clone(),
+                    display_name: initial.model.clone(),
+                    description: "Currently configured model.".to_string(),
+                },
+            );
+        }
+
+        let preset_idx = initial
+            .preset()
+            .and_then(|current| presets.iter().position(|preset| preset.id == current.id))
+            .or_else(|| presets.iter().position(|preset| preset.id == "auto"))
+            .unwrap_or(0);
+        let model_idx = models
+            .iter()
+            .position(|option| option.model == initial.model)
+            .unwrap_or(0);
+        let notifications_idx = if initial.notifications { 0 } else { 1 };
+
+        Self {
+            presets,
+            models,
+            page: SetupPage::Permissions,
+            highlighted: [preset_idx, model_idx, notifications_idx],
+            outcome: None,
+        }
+    }
+
+    pub(crate) fn outcome(&self) -> Option<&SetupOutcome> {
+        self.outcome.as_ref()
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release || self.outcome.is_some() {
+            return;
+        }
+        let len = self.option_labels().len();
+        let highlighted = &mut self.highlighted[self.page.index()];
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => *highlighted = highlighted.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                *highlighted = (*highlighted + 1).min(len.saturating_sub(1));
+            }
+            KeyCode::Char(c) => {
+                if let Some(idx) = c.to_digit(10).map(|d| d as usize)
+                    && (1..=len).contains(&idx)
+                {
+                    *highlighted = idx - 1;
+                    self.advance();
+                }
+            }
+            KeyCode::Enter | KeyCode::Right => self.advance(),
+            KeyCode::Esc | KeyCode::Left | KeyCode::Backspace => self.back(),
+            _ => {}
+        }
+    }
+
+    fn advance(&mut self) {
+        match self.page {
+            SetupPage::Permissions => self.page = SetupPage::Model,
+            SetupPage::Model => self.page = SetupPage::Notifications,
+            SetupPage::Notifications => {
+                self.outcome = Some(SetupOutcome::Finished(self.choices()));
+            }
+        }
+    }
+
+    fn back(&mut self) {
+        match self.page {
+            SetupPage::Permissions => self.outcome = Some(SetupOutcome::Cancelled),
+            SetupPage::Model => self.page = SetupPage::Permissions,
+            SetupPage::Notifications => self.page = SetupPage::Model,
+        }
+    }
+
+    fn choices(&self) -> SetupChoices {
+        let [preset_idx, model_idx, notifications_idx] = self.highlighted;
+        let preset = &self.presets[preset_idx];
+        SetupChoices {
+            approval: preset.approval,
+            sandbox_mode: sandbox_mode_for(&preset.sandbox),
+            model: self.models[model_idx].model.clone(),
+            notifications: notifications_idx == 0,
+        }
+    }
+
+    /// `(label, explanation)` for each row on the current page.
+    fn option_labels(&self) -> Vec<(String, String)> {
+        match self.page {
+            SetupPage::Permissions => self
+                .presets
+                .iter()
+                .map(|preset| (preset.label.to_string(), preset.description.to_string()))
+                .collect(),
+            SetupPage::Model => self
+                .models
+                .iter()
+                .map(|option| (option.display_name.clone(), option.description.clone()))
+                .collect(),
+            SetupPage::Notifications => vec![
+                (
+                    "Yes, notify me".to_string(),
+                    "Uses your terminal's notification support; nothing is sent anywhere else."
+                        .to_string(),
+                ),
+                (
+                    "No notifications".to_string(),
+                    "You can turn them on later with `[tui] notifications = true`.".to_string(),
+                ),
+            ],
+        }
+    }
+
+    fn content(&self) -> ColumnRenderable<'static> {
+        let mut column = ColumnRenderable::new();
+        column.push(Line::from(vec!["> ".into(), "Set up Codex".bold()]));
+        column.push(
+            Line::from(
+                format!(
+                    "Step {} of {} · {}",
+                    self.page.index() + 1,
+                    SetupPage::ALL.len(),
+                    self.page.title()
+                )
+                .dim(),
+            )
+            .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.push("");
+        column.push(
+            Paragraph::new(self.page.question())
+                .wrap(Wrap { trim: true })
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.push("");
+
+        let highlighted = self.highlighted[self.page.index()];
+        for (idx, (label, explanation)) in self.option_labels().into_iter().enumerate() {
+            column.push(selection_option_row(idx, label, idx == highlighted));
+            column.push(
+                Paragraph::new(explanation)
+                    .dim()
+                    .wrap(Wrap { trim: true })
+                    .inset(Insets::tlbr(0, 5, 0, 0)),
+            );
+        }
+        column.push("");
+
+        let back = match self.page {
+            SetupPage::Permissions => " to skip",
+            SetupPage::Model | SetupPage::Notifications => " to go back",
+        };
+        let next = match self.page {
+            SetupPage::Permissions | SetupPage::Model => " to continue · ",
+            SetupPage::Notifications => " to save · ",
+        };
+        column.push(
+            Line::from(vec![
+                "Press ".dim(),
+                key_hint::plain(KeyCode::Enter).into(),
+                next.dim(),
+                key_hint::plain(KeyCode::Esc).into(),
+                back.dim(),
+            ])
+            .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column
+    }
+}
+
+impl Renderable for SetupWizard {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.content().render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.content().desired_height(width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    fn choices() -> SetupChoices {
+        SetupChoices {
+            approval: AskForApproval::OnRequest,
+            sandbox_mode: SandboxMode::WorkspaceWrite,
+            model: "gpt-5.2-codex".to_string(),
+            notifications: false,
+        }
+    }
+
+    fn press(wizard: &mut SetupWizard, code: KeyCode) {
+        wizard.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn initial_config_round_trips_through_config_toml() {
+        let choices = choices();
+        let rendered = render_initial_config(&choices).expect("render");
+        let parsed: ConfigToml = toml::from_str(&rendered).expect("parse rendered config");
+        assert_eq!(parsed, choices.config_toml().expect("config toml"));
+        assert!(rendered.starts_with(HEADER_COMMENT), "{rendered}");
+        assert!(
+            rendered.contains("\n# When Codex stops to ask you first: untrusted, on-failure, on-request or never.\napproval_policy = \"on-request\"\n"),
+            "{rendered}"
+        );
+    }
+
+    #[test]
+    fn write_initial_config_refuses_to_replace_existing_file() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let path = write_initial_config(codex_home.path(), &choices()).expect("first write");
+        assert_eq!(path, codex_home.path().join(CONFIG_TOML_FILE));
+        let err = write_initial_config(codex_home.path(), &choices()).expect_err("second write");
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn keys_walk_the_pages_and_collect_choices() {
+        let mut wizard = SetupWizard::new(Vec::new(), &choices());
+
+        // Read Only, keep the current model, then step back and forth once.
+        press(&mut wizard, KeyCode::Char('1'));
+        press(&mut wizard, KeyCode::Enter);
+        press(&mut wizard, KeyCode::Esc);
+        assert_eq!(wizard.page, SetupPage::Model);
+        press(&mut wizard, KeyCode::Enter);
+        press(&mut wizard, KeyCode::Up);
+        press(&mut wizard, KeyCode::Enter);
+
+        assert_eq!(
+            wizard.outcome(),
+            Some(&SetupOutcome::Finished(SetupChoices {
+                approval: AskForApproval::OnRequest,
+                sandbox_mode: SandboxMode::ReadOnly,
+                model: "gpt-5.2-codex".to_string(),
+                notifications: true,
+            }))
+        );
+    }
+
+    #[test]
+    fn esc_on_first_page_cancels() {
+        let mut wizard = SetupWizard::new(Vec::new(), &choices());
+        press(&mut wizard, KeyCode::Esc);
+        assert_eq!(wizard.outcome(), Some(&SetupOutcome::Cancelled));
+    }
+}
@@ -19,9 +19,14 @@ pub enum SlashCommand {
     ElevateSandbox,
     Experimental,
     Skills,
+    Memory,
+    Steer,
+    Ask,
     Review,
     Rename,
+    Title,
     New,
+    Sessions,
     Resume,
     Fork,
     Init,
@@ -31,9 +36,17 @@ pub enum SlashCommand {
     Agent,
     // Undo,
     Diff,
+    History,
+    Commit,
+    Export,
     Mention,
+    Attach,
     Status,
     Mcp,
+    Tools,
+    Revoke,
+    ClearHistory,
+    Doctor,
     Apps,
     Logout,
     Quit,
@@ -42,6 +55,9 @@ pub enum SlashCommand {
     Rollout,
     Ps,
     Personality,
+    Reasoning,
+    Language,
+    Setup,
     TestApproval,
 }
 
@@ -50,22 +66,42 @@ impl SlashCommand {
     pub fn description(self) -> &'static str {
         match self {
             SlashCommand::Feedback => "send logs to maintainers",
-            SlashCommand::New => "start a new chat during a conversation",
+            SlashCommand::New => "start a new chat alongside this one",
+            SlashCommand::Sessions => "switch between open chats",
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
+            SlashCommand::Steer => "guide the running turn without interrupting it",
+            SlashCommand::Ask => "answer a question without running tools (/ask <question>)",
             SlashCommand::Review => "review my current changes and find issues",
             SlashCommand::Rename => "rename the current thread",
+            SlashCommand::Title => "set the session title shown in /resume",
             SlashCommand::Resume => "resume a saved chat",
             SlashCommand::Fork => "fork the current chat",
             // SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
-            SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Diff => "show git diff (--staged, a RANGE like HEAD~3.., or paths)",
+            SlashCommand::History => {
+                "view a file as it was before an earlier turn (/history files [turn] [--diff])"
+            }
+            SlashCommand::Commit => {
+                "draft a commit message for this session's changes and commit them"
+            }
+            SlashCommand::Export => "export a redacted HTML transcript of this session",
             SlashCommand::Mention => "mention a file",
+            SlashCommand::Attach => "attach a file to your next message (/attach <path>)",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
+            SlashCommand::Memory => "view and prune notes saved to project memory",
             SlashCommand::Status => "show current session configuration and token usage",
             SlashCommand::Ps => "list background terminals",
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Personality => "choose a communication style for Codex",
+            SlashCommand::Reasoning => {
+                "set reasoning effort for the next turns (low/medium/high/none)"
+            }
+            SlashCommand::Language => {
+                "set the language Codex answers in (/language <language>|off)"
+            }
+            SlashCommand::Setup => "rerun the setup wizard (permissions, model, notifications)",
             SlashCommand::Plan => "switch to Plan mode",
             SlashCommand::Collab => "change collaboration mode (experimental)",
             SlashCommand::Agent => "switch the active agent thread",
@@ -74,6 +110,10 @@ impl SlashCommand {
             SlashCommand::ElevateSandbox => "set up elevated agent sandbox",
             SlashCommand::Experimental => "toggle experimental features",
             SlashCommand::Mcp => "list configured MCP tools",
+            SlashCommand::Tools => "show every tool the model can call",
+            SlashCommand::Revoke => "forget commands and edits approved for this session",
+            SlashCommand::ClearHistory => "delete the saved prompt history used by Up/Down",
+            SlashCommand::Doctor => "diagnose config, auth, connectivity and sandbox problems",
             SlashCommand::Apps => "manage apps",
             SlashCommand::Logout => "log out of Codex",
             SlashCommand::Rollout => "print the rollout file path",
@@ -90,8 +130,7 @@ impl SlashCommand {
     /// Whether this command can be run while a task is in progress.
     pub fn available_during_task(self) -> bool {
         match self {
-            SlashCommand::New
-            | SlashCommand::Resume
+            SlashCommand::Resume
             | SlashCommand::Fork
             | SlashCommand::Init
             | SlashCommand::Compact
@@ -102,17 +141,34 @@ impl SlashCommand {
             | SlashCommand::Permissions
             | SlashCommand::ElevateSandbox
             | SlashCommand::Experimental
+            | SlashCommand::Setup
+            | SlashCommand::Ask
             | SlashCommand::Review
+            | SlashCommand::Commit
             | SlashCommand::Logout => false,
-            SlashCommand::Diff
+            SlashCommand::New
+            | SlashCommand::Sessions
+            | SlashCommand::Diff
+            | SlashCommand::History
+            | SlashCommand::Export
             | SlashCommand::Rename
+            | SlashCommand::Title
             | SlashCommand::Mention
+            | SlashCommand::Attach
             | SlashCommand::Skills
+            | SlashCommand::Memory
+            | SlashCommand::Steer
             | SlashCommand::Status
             | SlashCommand::Ps
             | SlashCommand::Mcp
+            | SlashCommand::Tools
+            | SlashCommand::Revoke
+            | SlashCommand::ClearHistory
+            | SlashCommand::Doctor
             | SlashCommand::Apps
             | SlashCommand::Feedback
+            | SlashCommand::Reasoning
+            | SlashCommand::Language
             | SlashCommand::Quit
             | SlashCommand::Exit => true,
             SlashCommand::Rollout => true,
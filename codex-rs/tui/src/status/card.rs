@@ -29,6 +29,7 @@ use super::format::truncate_line_to_width;
 use super::helpers::compose_account_display;
 use super::helpers::compose_agents_summary;
 use super::helpers::compose_model_display;
+use super::helpers::compose_tools_summary;
 use super::helpers::format_directory_display;
 use super::helpers::format_tokens_compact;
 use super::rate_limits::RateLimitSnapshotDisplay;
@@ -64,9 +65,12 @@ struct StatusHistoryCell {
     directory: PathBuf,
     approval: String,
     sandbox: String,
+    tools: Option<String>,
     agents_summary: String,
+    instructions: Option<String>,
     collaboration_mode: Option<String>,
     model_provider: Option<String>,
+    env_file: Option<PathBuf>,
     account: Option<StatusAccountDisplay>,
     thread_name: Option<String>,
     session_id: Option<String>,
@@ -155,9 +159,15 @@ impl StatusHistoryCell {
             .find(|(k, _)| *k == "approval")
             .map(|(_, v)| v.clone())
             .unwrap_or_else(|| "<unknown>".to_string());
+        let not_enforced = codex_core::sandbox_self_test::load_cached_report(&config.codex_home)
+            .is_some_and(|report| !report.unenforced().is_empty());
         let sandbox = match config.sandbox_policy.get() {
             SandboxPolicy::DangerFullAccess => "danger-full-access".to_string(),
+            SandboxPolicy::ReadOnly if not_enforced => "read-only (not enforced)".to_string(),
             SandboxPolicy::ReadOnly => "read-only".to_string(),
+            SandboxPolicy::WorkspaceWrite { .. } if not_enforced => {
+                "workspace-write (not enforced)".to_string()
+            }
             SandboxPolicy::WorkspaceWrite { .. } => "workspace-write".to_string(),
             SandboxPolicy::ExternalSandbox { network_access } => {
                 if matches!(network_access, NetworkAccess::Enabled) {
@@ -167,7 +177,17 @@ impl StatusHistoryCell {
                 }
             }
         };
+        let tools = compose_tools_summary(config.tool_toggles);
         let agents_summary = compose_agents_summary(config);
+        let instructions = config.base_instructions_tokens().map(|tokens| {
+            let budget = config.base_instructions_token_budget;
+            let size = format_tokens_compact(i64::try_from(tokens).unwrap_or(i64::MAX));
+            if tokens > budget {
+                format!("custom (~{size} tokens, over budget)")
+            } else {
+                format!("custom (~{size} tokens)")
+            }
+        });
         let model_provider = format_model_provider(config);
         let account = compose_account_display(auth_manager, plan_type);
         let session_id = session_id.as_ref().map(std::string::ToString::to_string);
@@ -197,9 +217,12 @@ impl StatusHistoryCell {
             directory: config.cwd.clone(),
             approval,
             sandbox,
+            tools,
             agents_summary,
+            instructions,
             collaboration_mode: collaboration_mode.map(ToString::to_string),
             model_provider,
+            env_file: config.dotenv.as_ref().map(|dotenv| dotenv.path.clone()),
             account,
             thread_name,
             session_id,
@@ -387,6 +410,15 @@ impl HistoryCell for StatusHistoryCell {
         if self.model_provider.is_some() {
             push_label(&mut labels, &mut seen, "Model provider");
         }
+        if self.env_file.is_some() {
+            push_label(&mut labels, &mut seen, "Env file");
+        }
+        if self.tools.is_some() {
+            push_label(&mut labels, &mut seen, "Tools");
+        }
+        if self.instructions.is_some() {
+            push_label(&mut labels, &mut seen, "Instructions");
+        }
         if account_value.is_some() {
             push_label(&mut labels, &mut seen, "Account");
         }
@@ -442,10 +474,20 @@ impl HistoryCell for StatusHistoryCell {
         if let Some(model_provider) = self.model_provider.as_ref() {
             lines.push(formatter.line("Model provider", vec![Span::from(model_provider.clone())]));
         }
+        if let Some(env_file) = self.env_file.as_ref() {
+            let env_file = format_directory_display(env_file, Some(value_width));
+            lines.push(formatter.line("Env file", vec![Span::from(env_file)]));
+        }
         lines.push(formatter.line("Directory", vec![Span::from(directory_value)]));
         lines.push(formatter.line("Approval", vec![Span::from(self.approval.clone())]));
         lines.push(formatter.line("Sandbox", vec![Span::from(self.sandbox.clone())]));
+        if let Some(tools) = self.tools.as_ref() {
+            lines.push(formatter.line("Tools", vec![Span::from(tools.clone())]));
+        }
         lines.push(formatter.line("Agents.md", vec![Span::from(self.agents_summary.clone())]));
+        if let Some(instructions) = self.instructions.as_ref() {
+            lines.push(formatter.line("Instructions", vec![Span::from(instructions.clone())]));
+        }
 
         if let Some(account_value) = account_value {
             lines.push(formatter.line("Account", vec![Span::from(account_value)]));
@@ -1,9 +1,10 @@
 use ratatui::prelude::*;
 use ratatui::style::Stylize;
 use std::collections::BTreeSet;
-use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 
+use crate::text_formatting::truncate_display_width;
+
 #[derive(Debug, Clone)]
 pub(crate) struct FieldFormatter {
     indent: &'static str,
@@ -126,18 +127,9 @@ pub(crate) fn truncate_line_to_width(line: Line<'static>, max_width: usize) -> L
             continue;
         }
 
-        let mut truncated = String::new();
-        for ch in text.chars() {
-            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used + ch_width > max_width {
-                break;
-            }
-            truncated.push(ch);
-            used += ch_width;
-        }
-
+        let truncated = truncate_display_width(&text, max_width - used);
         if !truncated.is_empty() {
-            spans_out.push(Span::styled(truncated, style));
+            spans_out.push(Span::styled(truncated.to_string(), style));
         }
 
         break;
@@ -7,6 +7,7 @@ use codex_core::CodexAuth;
 use codex_core::config::Config;
 use codex_core::project_doc::discover_project_doc_paths;
 use codex_protocol::account::PlanType;
+use codex_protocol::protocol::ToolToggles;
 use std::path::Path;
 use unicode_width::UnicodeWidthStr;
 
@@ -84,6 +85,25 @@ pub(crate) fn compose_agents_summary(config: &Config) -> String {
     }
 }
 
+/// Enabled tool groups followed by the disabled ones, or `None` when nothing is disabled.
+pub(crate) fn compose_tools_summary(toggles: ToolToggles) -> Option<String> {
+    let enabled = toggles.enabled_names();
+    let disabled: Vec<&str> = ToolToggles::default()
+        .enabled_names()
+        .into_iter()
+        .filter(|name| !enabled.contains(name))
+        .collect();
+    if disabled.is_empty() {
+        return None;
+    }
+    let enabled = if enabled.is_empty() {
+        "none".to_string()
+    } else {
+        enabled.join(", ")
+    };
+    Some(format!("{enabled} (disabled: {})", disabled.join(", ")))
+}
+
 pub(crate) fn compose_account_display(
     auth_manager: &AuthManager,
     plan: Option<PlanType>,
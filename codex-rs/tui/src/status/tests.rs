@@ -14,6 +14,7 @@ use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
+use codex_core::protocol::ToolToggles;
 use codex_protocol::ThreadId;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::openai_models::ReasoningEffort;
@@ -938,3 +939,107 @@ async fn status_context_window_uses_last_usage() {
         "context line should not use total aggregated tokens, got: {context_line}"
     );
 }
+
+#[tokio::test]
+async fn status_lists_tools_only_when_some_are_disabled() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 6, 1, 12, 0, 0)
+        .single()
+        .expect("timestamp");
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let render = |config: &Config| {
+        let composite = new_status_output(
+            config,
+            &auth_manager,
+            None,
+            &usage,
+            &None,
+            None,
+            None,
+            None,
+            None,
+            now,
+            &model_slug,
+            None,
+            None,
+        );
+        render_lines(&composite.display_lines(80))
+    };
+
+    assert!(
+        render(&config).iter().all(|line| !line.contains("Tools:")),
+        "tools row should be hidden when every tool is enabled"
+    );
+
+    config.tool_toggles = ToolToggles {
+        shell: false,
+        apply_patch: false,
+        mcp: true,
+    };
+    let tools_line = render(&config)
+        .into_iter()
+        .find(|line| line.contains("Tools:"))
+        .expect("tools line");
+    assert!(
+        tools_line.contains("mcp (disabled: shell, apply_patch)"),
+        "unexpected tools line: {tools_line}"
+    );
+}
+
+#[tokio::test]
+async fn status_reports_custom_instructions_size() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 6, 1, 12, 0, 0)
+        .single()
+        .expect("timestamp");
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let render = |config: &Config| {
+        let composite = new_status_output(
+            config,
+            &auth_manager,
+            None,
+            &usage,
+            &None,
+            None,
+            None,
+            None,
+            None,
+            now,
+            &model_slug,
+            None,
+            None,
+        );
+        render_lines(&composite.display_lines(80))
+    };
+    let instructions_line = |config: &Config| {
+        render(config)
+            .into_iter()
+            .find(|line| line.contains("Instructions:"))
+    };
+
+    assert_eq!(instructions_line(&config), None);
+
+    config.base_instructions = Some("x".repeat(8_000));
+    let line = instructions_line(&config).expect("instructions line");
+    assert!(
+        line.contains("custom (~2K tokens)"),
+        "unexpected line: {line}"
+    );
+
+    config.base_instructions_token_budget = 1_000;
+    let line = instructions_line(&config).expect("instructions line");
+    assert!(
+        line.contains("custom (~2K tokens, over budget)"),
+        "unexpected line: {line}"
+    );
+}
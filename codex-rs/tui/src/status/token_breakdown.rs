@@ -0,0 +1,142 @@
+use codex_core::protocol::TokenBreakdownEvent;
+use codex_core::protocol::TokenComponent;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use super::helpers::format_tokens_compact;
+use crate::history_cell::PlainHistoryCell;
+
+/// Share of the request above which the largest component gets a hint.
+const HINT_THRESHOLD_PERCENT: i64 = 30;
+const LABEL_WIDTH: usize = 20;
+
+/// Renders the estimated token breakdown requested by `/status` as a small
+/// table, highlighting the largest component.
+pub(crate) fn new_token_breakdown_output(ev: &TokenBreakdownEvent) -> PlainHistoryCell {
+    let total = ev.total_tokens;
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            "Token breakdown".bold(),
+            format!(
+                " (next request, ~{} tokens estimated)",
+                format_tokens_compact(total)
+            )
+            .dim(),
+        ]
+        .into(),
+    ];
+    if total <= 0 {
+        lines.push("  Nothing to estimate yet.".dim().into());
+        return PlainHistoryCell::new(lines);
+    }
+
+    for (index, estimate) in ev.components.iter().enumerate() {
+        let label = format!("  {:<LABEL_WIDTH$}", estimate.component.label());
+        let tokens = format!("{:>7}", format_tokens_compact(estimate.tokens));
+        let percent = format!("{:>5}", format!("{}%", percent_of(estimate.tokens, total)));
+        let row: Vec<Span<'static>> = if index == 0 {
+            vec![label.bold(), tokens.bold(), percent.bold()]
+        } else {
+            vec![label.into(), tokens.dim(), percent.dim()]
+        };
+        lines.push(row.into());
+    }
+
+    let largest = ev
+        .components
+        .first()
+        .map(|estimate| (estimate.component, percent_of(estimate.tokens, total)));
+    if let Some((component, percent)) = largest
+        && percent >= HINT_THRESHOLD_PERCENT
+    {
+        lines.push(
+            format!(
+                "  {}: {percent}% — {}",
+                component.label(),
+                hint_for(component)
+            )
+            .cyan()
+            .into(),
+        );
+    }
+    PlainHistoryCell::new(lines)
+}
+
+fn percent_of(tokens: i64, total: i64) -> i64 {
+    if total <= 0 {
+        return 0;
+    }
+    ((tokens as f64 / total as f64) * 100.0).round() as i64
+}
+
+fn hint_for(component: TokenComponent) -> &'static str {
+    match component {
+        TokenComponent::FunctionOutputs => {
+            "consider lowering exec output limits (`tool_output_token_limit`)"
+        }
+        TokenComponent::Tools => "consider disabling unused MCP servers or tool groups",
+        TokenComponent::Reasoning => "consider a lower reasoning effort",
+        TokenComponent::Instructions => "consider trimming AGENTS.md and custom instructions",
+        TokenComponent::UserMessages
+        | TokenComponent::AssistantMessages
+        | TokenComponent::FunctionCalls
+        | TokenComponent::Other => "consider /compact to summarize the conversation",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::HistoryCell;
+    use codex_core::protocol::TokenComponentEstimate;
+    use pretty_assertions::assert_eq;
+
+    fn render(ev: &TokenBreakdownEvent) -> Vec<String> {
+        new_token_breakdown_output(ev)
+            .display_lines(80)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn largest_component_gets_a_hint() {
+        let ev = TokenBreakdownEvent {
+            components: vec![
+                TokenComponentEstimate {
+                    component: TokenComponent::FunctionOutputs,
+                    tokens: 6_200,
+                    items: 14,
+                },
+                TokenComponentEstimate {
+                    component: TokenComponent::Tools,
+                    tokens: 2_800,
+                    items: 20,
+                },
+                TokenComponentEstimate {
+                    component: TokenComponent::Instructions,
+                    tokens: 1_000,
+                    items: 2,
+                },
+            ],
+            total_tokens: 10_000,
+        };
+
+        assert_eq!(
+            render(&ev),
+            vec![
+                "Token breakdown (next request, ~10K tokens estimated)",
+                "  function outputs       6.2K  62%",
+                "  tools                  2.8K  28%",
+                "  instructions             1K  10%",
+                "  function outputs: 62% — consider lowering exec output limits (`tool_output_token_limit`)",
+            ]
+        );
+    }
+}
@@ -23,16 +23,21 @@ use crate::key_hint;
 use crate::render::renderable::Renderable;
 use crate::shimmer::shimmer_spans;
 use crate::text_formatting::capitalize_first;
+use crate::text_formatting::truncate_display_width;
 use crate::tui::FrameRequester;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
 
 const DETAILS_MAX_LINES: usize = 3;
 const DETAILS_PREFIX: &str = "  └ ";
+const ACCESSIBLE_DETAILS_PREFIX: &str = "  ";
 
 pub(crate) struct StatusIndicatorWidget {
     /// Animated header text (defaults to "Working").
     header: String,
+    /// Transient text shown after the header, such as the latest reasoning
+    /// headline. Cleared whenever the header changes.
+    subtitle: Option<String>,
     details: Option<String>,
     show_interrupt_hint: bool,
 
@@ -42,6 +47,9 @@ pub(crate) struct StatusIndicatorWidget {
     app_event_tx: AppEventSender,
     frame_requester: FrameRequester,
     animations_enabled: bool,
+    /// Screen-reader mode: a static line without spinner or elapsed time, so
+    /// the widget only redraws when its text changes.
+    accessible: bool,
 }
 
 // Format elapsed seconds into a compact human-friendly form used by the status line.
@@ -69,6 +77,7 @@ impl StatusIndicatorWidget {
     ) -> Self {
         Self {
             header: String::from("Working"),
+            subtitle: None,
             details: None,
             show_interrupt_hint: true,
             elapsed_running: Duration::ZERO,
@@ -78,18 +87,32 @@ impl StatusIndicatorWidget {
             app_event_tx,
             frame_requester,
             animations_enabled,
+            accessible: false,
         }
     }
 
+    pub(crate) fn with_accessible(mut self, accessible: bool) -> Self {
+        self.accessible = accessible;
+        self
+    }
+
     pub(crate) fn interrupt(&self) {
         self.app_event_tx.send(AppEvent::CodexOp(Op::Interrupt));
     }
 
     /// Update the animated header label (left of the brackets).
     pub(crate) fn update_header(&mut self, header: String) {
+        if self.header != header {
+            self.subtitle = None;
+        }
         self.header = header;
     }
 
+    /// Update the transient subtitle shown after the header.
+    pub(crate) fn update_subtitle(&mut self, subtitle: Option<String>) {
+        self.subtitle = subtitle.filter(|subtitle| !subtitle.trim().is_empty());
+    }
+
     /// Update the details text shown below the header.
     pub(crate) fn update_details(&mut self, details: Option<String>) {
         self.details = details
@@ -97,11 +120,15 @@ impl StatusIndicatorWidget {
             .map(|details| capitalize_first(details.trim_start()));
     }
 
-    #[cfg(test)]
     pub(crate) fn header(&self) -> &str {
         &self.header
     }
 
+    #[cfg(test)]
+    pub(crate) fn subtitle(&self) -> Option<&str> {
+        self.subtitle.as_deref()
+    }
+
     #[cfg(test)]
     pub(crate) fn details(&self) -> Option<&str> {
         self.details.as_deref()
@@ -166,9 +193,14 @@ impl StatusIndicatorWidget {
             return Vec::new();
         }
 
-        let prefix_width = UnicodeWidthStr::width(DETAILS_PREFIX);
+        let prefix = if self.accessible {
+            ACCESSIBLE_DETAILS_PREFIX
+        } else {
+            DETAILS_PREFIX
+        };
+        let prefix_width = UnicodeWidthStr::width(prefix);
         let opts = RtOptions::new(usize::from(width))
-            .initial_indent(Line::from(DETAILS_PREFIX.dim()))
+            .initial_indent(Line::from(prefix.dim()))
             .subsequent_indent(Line::from(Span::from(" ".repeat(prefix_width)).dim()))
             .break_words(true);
 
@@ -181,7 +213,7 @@ impl StatusIndicatorWidget {
             if let Some(last) = out.last_mut()
                 && let Some(span) = last.spans.last_mut()
             {
-                let trimmed: String = span.content.as_ref().chars().take(max_base_len).collect();
+                let trimmed = truncate_display_width(span.content.as_ref(), max_base_len);
                 *span = format!("{trimmed}…").dim();
             }
         }
@@ -200,9 +232,34 @@ impl Renderable for StatusIndicatorWidget {
             return;
         }
 
-        // Schedule next animation frame.
+        let spans = if self.accessible {
+            self.accessible_spans()
+        } else {
+            self.animated_spans()
+        };
+
+        let mut lines = Vec::new();
+        lines.push(Line::from(spans));
+        if area.height > 1 {
+            // If there is enough space, add the details lines below the header.
+            let details = self.wrapped_details_lines(area.width);
+            let max_details = usize::from(area.height.saturating_sub(1));
+            lines.extend(details.into_iter().take(max_details));
+        }
+
+        Paragraph::new(Text::from(lines)).render_ref(area, buf);
+    }
+}
+
+impl StatusIndicatorWidget {
+    fn animated_spans(&self) -> Vec<Span<'static>> {
+        // Schedule the next frame; without animations only the timer changes.
         self.frame_requester
-            .schedule_frame_in(Duration::from_millis(32));
+            .schedule_frame_in(if self.animations_enabled {
+                Duration::from_millis(32)
+            } else {
+                Duration::from_secs(1)
+            });
         let now = Instant::now();
         let elapsed_duration = self.elapsed_duration_at(now);
         let pretty_elapsed = fmt_elapsed_compact(elapsed_duration.as_secs());
@@ -215,6 +272,9 @@ impl Renderable for StatusIndicatorWidget {
         } else if !self.header.is_empty() {
             spans.push(self.header.clone().into());
         }
+        if let Some(subtitle) = self.subtitle.as_deref() {
+            spans.push(format!(": {subtitle}…").into());
+        }
         spans.push(" ".into());
         if self.show_interrupt_hint {
             spans.extend(vec![
@@ -225,17 +285,26 @@ impl Renderable for StatusIndicatorWidget {
         } else {
             spans.push(format!("({pretty_elapsed})").dim());
         }
+        spans
+    }
 
-        let mut lines = Vec::new();
-        lines.push(Line::from(spans));
-        if area.height > 1 {
-            // If there is enough space, add the details lines below the header.
-            let details = self.wrapped_details_lines(area.width);
-            let max_details = usize::from(area.height.saturating_sub(1));
-            lines.extend(details.into_iter().take(max_details));
+    /// `Working: <subtitle>. Press esc to interrupt.` with no spinner or
+    /// timer, and no scheduled redraws.
+    fn accessible_spans(&self) -> Vec<Span<'static>> {
+        let mut text = self.header.clone();
+        if let Some(subtitle) = self.subtitle.as_deref() {
+            text.push_str(&format!(": {subtitle}"));
         }
-
-        Paragraph::new(Text::from(lines)).render_ref(area, buf);
+        text.push('.');
+        let mut spans = vec![Span::from(text)];
+        if self.show_interrupt_hint {
+            spans.extend([
+                " Press ".into(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " to interrupt.".into(),
+            ]);
+        }
+        spans
     }
 }
 
@@ -315,6 +384,50 @@ mod tests {
         insta::assert_snapshot!(terminal.backend());
     }
 
+    #[test]
+    fn renders_subtitle_after_header() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut w = StatusIndicatorWidget::new(tx, crate::tui::FrameRequester::test_dummy(), false);
+        w.update_header("Thinking".to_string());
+        w.update_subtitle(Some("Evaluating locking strategy".to_string()));
+        w.is_paused = true;
+        w.elapsed_running = Duration::from_secs(43);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 1)).expect("terminal");
+        terminal
+            .draw(|f| w.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        let rendered: String = (0..80)
+            .map(|x| terminal.backend().buffer()[(x, 0)].symbol().to_string())
+            .collect();
+        assert!(
+            rendered.contains("Thinking: Evaluating locking strategy… (43s"),
+            "{rendered:?}"
+        );
+
+        w.update_header("Working".to_string());
+        assert_eq!(w.subtitle(), None);
+    }
+
+    #[test]
+    fn renders_accessible_status_without_spinner_or_timer() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut w = StatusIndicatorWidget::new(tx, crate::tui::FrameRequester::test_dummy(), false)
+            .with_accessible(true);
+        w.update_header("Thinking".to_string());
+        w.update_subtitle(Some("Evaluating locking strategy".to_string()));
+        w.update_details(Some("retrying in 5s".to_string()));
+        w.elapsed_running = Duration::from_secs(43);
+
+        let mut terminal = Terminal::new(TestBackend::new(70, 2)).expect("terminal");
+        terminal
+            .draw(|f| w.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        insta::assert_snapshot!(terminal.backend());
+    }
+
     #[test]
     fn timer_pauses_when_requested() {
         let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
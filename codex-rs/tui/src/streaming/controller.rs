@@ -1,9 +1,11 @@
+use crate::citations::CitationHighlighter;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::{self};
 use crate::render::line_utils::prefix_lines;
 use crate::style::proposed_plan_style;
 use ratatui::prelude::Stylize;
 use ratatui::text::Line;
+use std::path::PathBuf;
 
 use super::StreamState;
 
@@ -13,6 +15,7 @@ pub(crate) struct StreamController {
     state: StreamState,
     finishing_after_drain: bool,
     header_emitted: bool,
+    citations: Option<CitationHighlighter>,
 }
 
 impl StreamController {
@@ -21,9 +24,22 @@ impl StreamController {
             state: StreamState::new(width),
             finishing_after_drain: false,
             header_emitted: false,
+            citations: None,
         }
     }
 
+    /// Commit all completed lines on the next tick rather than animating them.
+    pub(crate) fn with_reduced_motion(mut self, reduced_motion: bool) -> Self {
+        self.state.reveal_all = reduced_motion;
+        self
+    }
+
+    /// Highlight references to files under `cwd` as lines are committed.
+    pub(crate) fn with_citations(mut self, cwd: PathBuf) -> Self {
+        self.citations = Some(CitationHighlighter::new(cwd));
+        self
+    }
+
     /// Push a delta; if it contains a newline, commit completed lines and start animation.
     pub(crate) fn push(&mut self, delta: &str) -> bool {
         let state = &mut self.state;
@@ -71,15 +87,23 @@ impl StreamController {
         (self.emit(step), self.state.is_idle())
     }
 
-    fn emit(&mut self, lines: Vec<Line<'static>>) -> Option<Box<dyn HistoryCell>> {
+    fn emit(&mut self, mut lines: Vec<Line<'static>>) -> Option<Box<dyn HistoryCell>> {
         if lines.is_empty() {
             return None;
         }
-        Some(Box::new(history_cell::AgentMessageCell::new(lines, {
-            let header_emitted = self.header_emitted;
-            self.header_emitted = true;
-            !header_emitted
-        })))
+        let citations = self
+            .citations
+            .as_mut()
+            .map(|highlighter| highlighter.highlight(&mut lines))
+            .unwrap_or_default();
+        Some(Box::new(
+            history_cell::AgentMessageCell::new(lines, {
+                let header_emitted = self.header_emitted;
+                self.header_emitted = true;
+                !header_emitted
+            })
+            .with_citations(citations),
+        ))
     }
 }
 
@@ -99,6 +123,12 @@ impl PlanStreamController {
         }
     }
 
+    /// Commit all completed lines on the next tick rather than animating them.
+    pub(crate) fn with_reduced_motion(mut self, reduced_motion: bool) -> Self {
+        self.state.reveal_all = reduced_motion;
+        self
+    }
+
     /// Push a delta; if it contains a newline, commit completed lines and start animation.
     pub(crate) fn push(&mut self, delta: &str) -> bool {
         let state = &mut self.state;
@@ -200,6 +230,30 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn reduced_motion_commits_all_completed_lines_in_one_tick() {
+        let mut ctrl = StreamController::new(None).with_reduced_motion(true);
+        assert!(ctrl.push("First sentence.\nSecond sentence.\nThird"));
+
+        let (cell, idle) = ctrl.on_commit_tick();
+        let cell = cell.expect("completed lines are committed");
+        assert_eq!(
+            lines_to_plain_strings(&cell.accessible_lines(u16::MAX)),
+            vec![
+                "--- assistant message ---",
+                "First sentence.",
+                "Second sentence.",
+            ]
+        );
+        assert!(idle);
+
+        let cell = ctrl.finalize().expect("the partial line is flushed");
+        assert_eq!(
+            lines_to_plain_strings(&cell.accessible_lines(u16::MAX)),
+            vec!["Third"]
+        );
+    }
+
     #[tokio::test]
     async fn controller_loose_vs_tight_with_commit_ticks_matches_full() {
         let mut ctrl = StreamController::new(None);
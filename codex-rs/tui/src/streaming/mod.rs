@@ -9,6 +9,9 @@ pub(crate) struct StreamState {
     pub(crate) collector: MarkdownStreamCollector,
     queued_lines: VecDeque<Line<'static>>,
     pub(crate) has_seen_delta: bool,
+    /// Commit everything queued on each step instead of one line at a time
+    /// (`tui.reduced_motion`).
+    pub(crate) reveal_all: bool,
 }
 
 impl StreamState {
@@ -17,6 +20,7 @@ impl StreamState {
             collector: MarkdownStreamCollector::new(width),
             queued_lines: VecDeque::new(),
             has_seen_delta: false,
+            reveal_all: false,
         }
     }
     pub(crate) fn clear(&mut self) {
@@ -25,6 +29,9 @@ impl StreamState {
         self.has_seen_delta = false;
     }
     pub(crate) fn step(&mut self) -> Vec<Line<'static>> {
+        if self.reveal_all {
+            return self.drain_all();
+        }
         self.queued_lines.pop_front().into_iter().collect()
     }
     pub(crate) fn drain_all(&mut self) -> Vec<Line<'static>> {
@@ -2,6 +2,8 @@ use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 
+pub(crate) use codex_core::util::truncate_graphemes;
+
 pub(crate) fn capitalize_first(input: &str) -> String {
     let mut chars = input.chars();
     match chars.next() {
@@ -89,31 +91,33 @@ pub(crate) fn format_json_compact(text: &str) -> Option<String> {
 
 /// Truncate `text` to `max_graphemes` graphemes. Using graphemes to avoid accidentally truncating in the middle of a multi-codepoint character.
 pub(crate) fn truncate_text(text: &str, max_graphemes: usize) -> String {
-    let mut graphemes = text.grapheme_indices(true);
-
-    // Check if there's a grapheme at position max_graphemes (meaning there are more than max_graphemes total)
-    if let Some((byte_index, _)) = graphemes.nth(max_graphemes) {
-        // There are more than max_graphemes, so we need to truncate
-        if max_graphemes >= 3 {
-            // Truncate to max_graphemes - 3 and add "..." to stay within limit
-            let mut truncate_graphemes = text.grapheme_indices(true);
-            if let Some((truncate_byte_index, _)) = truncate_graphemes.nth(max_graphemes - 3) {
-                let truncated = &text[..truncate_byte_index];
-                format!("{truncated}...")
-            } else {
-                text.to_string()
-            }
-        } else {
-            // max_graphemes < 3, so just return first max_graphemes without "..."
-            let truncated = &text[..byte_index];
-            truncated.to_string()
-        }
+    let prefix = truncate_graphemes(text, max_graphemes);
+    if prefix.len() == text.len() {
+        return text.to_string();
+    }
+    if max_graphemes >= 3 {
+        // Truncate to max_graphemes - 3 and add "..." to stay within limit
+        format!("{}...", truncate_graphemes(text, max_graphemes - 3))
     } else {
-        // There are max_graphemes or fewer graphemes, return original text
-        text.to_string()
+        prefix.to_string()
     }
 }
 
+/// Return the longest prefix of `text` that renders in at most `max_width`
+/// terminal cells. Cuts only between grapheme clusters, so a wide CJK or emoji
+/// glyph that would straddle the limit is dropped whole.
+pub(crate) fn truncate_display_width(text: &str, max_width: usize) -> &str {
+    let mut used = 0usize;
+    for (byte_index, grapheme) in text.grapheme_indices(true) {
+        let width = UnicodeWidthStr::width(grapheme);
+        if used + width > max_width {
+            return &text[..byte_index];
+        }
+        used += width;
+    }
+    text
+}
+
 /// Truncate a path-like string to the given display width, keeping leading and trailing segments
 /// where possible and inserting a single Unicode ellipsis between them. If an individual segment
 /// cannot fit, it is front-truncated with an ellipsis.
@@ -332,6 +336,58 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn truncate_display_width_respects_wide_glyphs() {
+        assert_eq!(truncate_display_width("中文abc", 3), "中");
+        assert_eq!(truncate_display_width("a👩‍💻b", 3), "a👩‍💻");
+        assert_eq!(truncate_display_width("a👩‍💻b", 2), "a");
+        assert_eq!(truncate_display_width("⠋ loading", 1), "⠋");
+        assert_eq!(truncate_display_width("abc", 0), "");
+    }
+
+    #[test]
+    fn truncation_helpers_handle_random_unicode() {
+        use rand::Rng;
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        const ALPHABET: &[&str] = &[
+            "a", " ", "é", "e\u{301}", "中", "ｶ", "🚀", "👩‍💻", "🇯🇵", "⠋", "\u{200d}", "\t",
+        ];
+        // One seeded case per seed, so a failure names the seed to replay.
+        for seed in 0..500 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let len = rng.random_range(0..24);
+            let text: String = (0..len)
+                .map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())])
+                .collect();
+            let max = rng.random_range(0..30);
+
+            // The buffer lays text out one grapheme at a time, so that is
+            // the width that has to fit.
+            let rendered_width =
+                |s: &str| -> usize { s.graphemes(true).map(UnicodeWidthStr::width).sum() };
+            let fitted = truncate_display_width(&text, max);
+            assert!(text.starts_with(fitted), "seed {seed}: {text:?} at {max}");
+            assert!(
+                rendered_width(fitted) <= max,
+                "seed {seed}: {text:?} at {max}"
+            );
+            if let Some(next) = text[fitted.len()..].graphemes(true).next() {
+                assert!(
+                    rendered_width(fitted) + rendered_width(next) > max,
+                    "seed {seed}: {text:?} at {max}"
+                );
+            }
+
+            let truncated = truncate_text(&text, max);
+            assert!(
+                truncated.graphemes(true).count() <= max,
+                "seed {seed}: {text:?} at {max}"
+            );
+        }
+    }
+
     #[test]
     fn test_truncate_text() {
         let text = "Hello, world!";
@@ -0,0 +1,184 @@
+//! Approximate token counts for input that has not been sent yet.
+//!
+//! The TUI has no tokenizer for every model, so counts use the same
+//! four-bytes-per-token heuristic as core's truncation. `TokenCount` events
+//! report the real prompt size after each request; [`ContextEstimate`] adds
+//! the estimated size of everything submitted since then so the context meter
+//! and the oversized-message check stay current between reports. Anything
+//! derived from an estimate must be shown as approximate.
+
+use codex_protocol::protocol::TokenUsage;
+
+const APPROX_BYTES_PER_TOKEN: usize = 4;
+
+/// Tokens assumed for instructions and tool definitions before the first
+/// `TokenCount` reports the real prompt size.
+const BASE_PROMPT_TOKENS: i64 = 12_000;
+
+/// Fraction of the remaining context an excerpt may use, leaving room for the
+/// model's reply.
+const EXCERPT_BUDGET_PERCENT: i64 = 50;
+
+/// Estimates the number of tokens in `text`.
+pub(crate) fn estimate_tokens(text: &str) -> i64 {
+    let tokens = text.len().div_ceil(APPROX_BYTES_PER_TOKEN);
+    i64::try_from(tokens).unwrap_or(i64::MAX)
+}
+
+/// Tracks how much of the context window the next request will use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ContextEstimate {
+    /// Prompt size from the most recent `TokenCount`, if any.
+    reported_tokens: Option<i64>,
+    /// Estimated tokens submitted since `reported_tokens` was measured.
+    unreported_tokens: i64,
+}
+
+impl ContextEstimate {
+    /// Records the prompt size reported by the server, replacing the estimate.
+    pub(crate) fn set_reported(&mut self, tokens_in_context_window: i64) {
+        self.reported_tokens = Some(tokens_in_context_window);
+        self.unreported_tokens = 0;
+    }
+
+    /// Adds text that will be part of the next request.
+    pub(crate) fn add_text(&mut self, text: &str) {
+        self.unreported_tokens = self.unreported_tokens.saturating_add(estimate_tokens(text));
+    }
+
+    /// Whether [`Self::used_tokens`] includes estimated input.
+    pub(crate) fn is_approximate(&self) -> bool {
+        self.reported_tokens.is_none() || self.unreported_tokens > 0
+    }
+
+    /// Tokens the next request will use before any new input.
+    pub(crate) fn used_tokens(&self) -> i64 {
+        self.reported_tokens
+            .unwrap_or(BASE_PROMPT_TOKENS)
+            .saturating_add(self.unreported_tokens)
+    }
+
+    /// Tokens left in a window of `context_window` tokens.
+    pub(crate) fn remaining_tokens(&self, context_window: i64) -> i64 {
+        context_window.saturating_sub(self.used_tokens()).max(0)
+    }
+
+    /// Percentage of the window left, computed the same way as for reported
+    /// usage.
+    pub(crate) fn percent_remaining(&self, context_window: i64) -> i64 {
+        TokenUsage {
+            total_tokens: self.used_tokens(),
+            ..TokenUsage::default()
+        }
+        .percent_of_context_window_remaining(context_window)
+    }
+
+    /// Token budget for an excerpt of an oversized message.
+    pub(crate) fn excerpt_budget(&self, context_window: i64) -> i64 {
+        self.remaining_tokens(context_window) * EXCERPT_BUDGET_PERCENT / 100
+    }
+}
+
+/// Shortens `text` to roughly `max_tokens` by keeping whole lines from the
+/// start and end with a marker for the omitted middle. Returns the excerpt and
+/// the byte length of the kept head, which is unchanged in the excerpt.
+pub(crate) fn excerpt_to_fit(text: &str, max_tokens: i64) -> (String, usize) {
+    let budget = usize::try_from(max_tokens.max(0))
+        .unwrap_or(usize::MAX)
+        .saturating_mul(APPROX_BYTES_PER_TOKEN);
+    if text.len() <= budget {
+        return (text.to_string(), text.len());
+    }
+
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let head_budget = budget * 2 / 3;
+    let mut head_len = 0usize;
+    let mut head_lines = 0usize;
+    for line in &lines {
+        if head_len + line.len() > head_budget {
+            break;
+        }
+        head_len += line.len();
+        head_lines += 1;
+    }
+
+    let tail_budget = budget - head_len;
+    let mut tail_len = 0usize;
+    let mut tail_lines = 0usize;
+    for line in lines[head_lines..].iter().rev() {
+        if tail_len + line.len() > tail_budget {
+            break;
+        }
+        tail_len += line.len();
+        tail_lines += 1;
+    }
+
+    let omitted = lines.len() - head_lines - tail_lines;
+    let mut excerpt = String::with_capacity(head_len + tail_len + 48);
+    excerpt.push_str(&text[..head_len]);
+    if head_len > 0 && !excerpt.ends_with('\n') {
+        excerpt.push('\n');
+    }
+    excerpt.push_str(&format!("[… {omitted} lines omitted …]\n"));
+    excerpt.push_str(&text[text.len() - tail_len..]);
+    (excerpt, head_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn estimate_rounds_up_to_whole_tokens() {
+        assert_eq!(
+            vec![
+                estimate_tokens(""),
+                estimate_tokens("abc"),
+                estimate_tokens("abcd"),
+                estimate_tokens("abcde"),
+            ],
+            vec![0, 1, 1, 2]
+        );
+    }
+
+    #[test]
+    fn estimate_adds_unreported_input_to_last_report() {
+        let mut estimate = ContextEstimate::default();
+        assert_eq!(
+            (estimate.used_tokens(), estimate.is_approximate()),
+            (BASE_PROMPT_TOKENS, true)
+        );
+
+        estimate.set_reported(50_000);
+        assert_eq!(
+            (estimate.used_tokens(), estimate.is_approximate()),
+            (50_000, false)
+        );
+
+        estimate.add_text(&"x".repeat(4_000));
+        assert_eq!(
+            (
+                estimate.used_tokens(),
+                estimate.is_approximate(),
+                estimate.remaining_tokens(100_000),
+                estimate.remaining_tokens(50_000),
+            ),
+            (51_000, true, 49_000, 0)
+        );
+    }
+
+    #[test]
+    fn excerpt_keeps_head_and_tail_lines() {
+        let text: String = (0..100).map(|idx| format!("line {idx:02}\n")).collect();
+
+        let (excerpt, head_len) = excerpt_to_fit(&text, 12);
+
+        assert_eq!(
+            excerpt,
+            "line 00\nline 01\nline 02\nline 03\n[… 94 lines omitted …]\nline 98\nline 99\n"
+        );
+        assert_eq!(head_len, 32);
+        assert_eq!(excerpt_to_fit("short", 12), ("short".to_string(), 5));
+    }
+}
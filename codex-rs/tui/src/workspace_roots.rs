@@ -0,0 +1,92 @@
+//! Workspace roots of the current session besides its cwd.
+//!
+//! Paths are normally shown relative to the session cwd. Paths under one of
+//! the other workspace roots are shown as `<root name>/<path>` instead, both in
+//! the `@` file picker and wherever the transcript renders a file path.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+static WORKSPACE_ROOTS: RwLock<Vec<PathBuf>> = RwLock::new(Vec::new());
+
+/// Replaces the registered roots; called whenever a session is configured.
+pub(crate) fn set_workspace_roots(roots: Vec<PathBuf>) {
+    if let Ok(mut current) = WORKSPACE_ROOTS.write() {
+        *current = roots;
+    }
+}
+
+pub(crate) fn workspace_roots() -> Vec<PathBuf> {
+    WORKSPACE_ROOTS
+        .read()
+        .map(|roots| roots.clone())
+        .unwrap_or_default()
+}
+
+/// Label used in front of paths under `root`, or `None` when `root` is not a
+/// registered workspace root (e.g. it is the cwd).
+pub(crate) fn root_label(root: &Path) -> Option<String> {
+    workspace_roots()
+        .iter()
+        .any(|candidate| candidate == root)
+        .then(|| label_for(root))
+}
+
+/// Displays `path` relative to the registered root nearest to it, when that
+/// root is closer than `cwd`.
+pub(crate) fn display_under_nearest_root(path: &Path, cwd: &Path) -> Option<String> {
+    display_relative_to_roots(path, cwd, &workspace_roots())
+}
+
+fn display_relative_to_roots(path: &Path, cwd: &Path, roots: &[PathBuf]) -> Option<String> {
+    let cwd_depth = path.starts_with(cwd).then(|| cwd.components().count());
+    let root = roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count())?;
+    if cwd_depth.is_some_and(|depth| depth >= root.components().count()) {
+        return None;
+    }
+    let relative = path.strip_prefix(root).ok()?;
+    let label = label_for(root);
+    if relative.as_os_str().is_empty() {
+        return Some(label);
+    }
+    Some(format!("{label}/{}", relative.display()))
+}
+
+fn label_for(root: &Path) -> String {
+    root.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| root.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn paths_use_the_nearest_root() {
+        let cwd = PathBuf::from("/work/app");
+        let roots = vec![
+            PathBuf::from("/work/shared"),
+            PathBuf::from("/work/app/vendor/lib"),
+        ];
+
+        let display = |path: &str| display_relative_to_roots(Path::new(path), &cwd, &roots);
+
+        assert_eq!(display("/work/app/src/main.rs"), None);
+        assert_eq!(
+            display("/work/shared/src/util.rs"),
+            Some("shared/src/util.rs".to_string())
+        );
+        assert_eq!(
+            display("/work/app/vendor/lib/mod.rs"),
+            Some("lib/mod.rs".to_string())
+        );
+        assert_eq!(display("/work/shared"), Some("shared".to_string()));
+        assert_eq!(display("/elsewhere/file.rs"), None);
+    }
+}
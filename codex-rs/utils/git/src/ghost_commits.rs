@@ -17,6 +17,7 @@ use crate::operations::normalize_relative_path;
 use crate::operations::repo_subdir;
 use crate::operations::resolve_head;
 use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_bytes;
 use crate::operations::run_git_for_status;
 use crate::operations::run_git_for_stdout;
 use crate::operations::run_git_for_stdout_all;
@@ -493,6 +494,50 @@ fn restore_to_commit_inner(
     Ok(())
 }
 
+/// List the files captured by a ghost commit. Paths are relative to
+/// `repo_path`, and only files below it are listed.
+pub fn list_ghost_commit_files(
+    repo_path: &Path,
+    commit: &GhostCommit,
+) -> Result<Vec<PathBuf>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    // Run from a subdirectory, `ls-tree` lists only that subtree, relative to it.
+    let output = run_git_for_stdout_all(
+        repo_path,
+        ["ls-tree", "-r", "--name-only", "-z", commit.id()],
+        None,
+    )?;
+    Ok(output
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Read `path` (relative to `repo_path`) as captured by a ghost commit,
+/// without touching the working tree or the index. Returns `None` when the
+/// snapshot does not contain the file.
+pub fn read_ghost_commit_file(
+    repo_path: &Path,
+    commit: &GhostCommit,
+    path: &Path,
+) -> Result<Option<Vec<u8>>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let relative = normalize_relative_path(path)?;
+    let relative = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    // `<commit>:./<path>` resolves the path relative to the current directory.
+    let object = format!("{}:./{relative}", commit.id());
+    match run_git_for_bytes(repo_path, ["cat-file", "blob", object.as_str()]) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(GitToolingError::GitCommand { status, .. }) if status.code() == Some(128) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
 #[derive(Default)]
 struct UntrackedSnapshot {
     files: Vec<PathBuf>,
@@ -1491,6 +1536,59 @@ mod tests {
         assert_matches!(err, GitToolingError::NotAGitRepository { .. });
     }
 
+    #[test]
+    /// Snapshot contents can be read from a subdirectory without restoring them.
+    fn read_ghost_commit_file_leaves_worktree_untouched() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+
+        let workspace = repo.join("workspace");
+        std::fs::create_dir_all(workspace.join("src"))?;
+        std::fs::write(repo.join("root.txt"), "root contents\n")?;
+        std::fs::write(workspace.join("src/lib.rs"), "fn before() {}\n")?;
+        run_git_in(repo, &["add", "."]);
+        run_git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "initial",
+            ],
+        );
+        std::fs::write(workspace.join("notes.md"), "untracked\n")?;
+
+        let ghost = create_ghost_commit(&CreateGhostCommitOptions::new(&workspace))?;
+        std::fs::write(workspace.join("src/lib.rs"), "fn after() {}\n")?;
+
+        assert_eq!(
+            list_ghost_commit_files(&workspace, &ghost)?,
+            vec![PathBuf::from("notes.md"), PathBuf::from("src/lib.rs")]
+        );
+        assert_eq!(
+            read_ghost_commit_file(&workspace, &ghost, Path::new("src/lib.rs"))?,
+            Some(b"fn before() {}\n".to_vec())
+        );
+        assert_eq!(
+            read_ghost_commit_file(&workspace, &ghost, Path::new("missing.rs"))?,
+            None
+        );
+        assert_eq!(
+            std::fs::read_to_string(workspace.join("src/lib.rs"))?,
+            "fn after() {}\n"
+        );
+        assert_matches!(
+            read_ghost_commit_file(&workspace, &ghost, Path::new("../root.txt")),
+            Err(GitToolingError::PathEscapesRepository { .. })
+        );
+
+        Ok(())
+    }
+
     #[test]
     /// Restoring from a subdirectory affects only that subdirectory.
     fn restore_from_subdirectory_restores_files_relatively() -> Result<(), GitToolingError> {
@@ -25,6 +25,8 @@ pub use ghost_commits::RestoreGhostCommitOptions;
 pub use ghost_commits::capture_ghost_snapshot_report;
 pub use ghost_commits::create_ghost_commit;
 pub use ghost_commits::create_ghost_commit_with_report;
+pub use ghost_commits::list_ghost_commit_files;
+pub use ghost_commits::read_ghost_commit_file;
 pub use ghost_commits::restore_ghost_commit;
 pub use ghost_commits::restore_ghost_commit_with_options;
 pub use ghost_commits::restore_to_commit;
@@ -182,6 +182,16 @@ where
     })
 }
 
+/// Executes `git` and returns stdout as raw bytes, for commands such as
+/// `git cat-file blob` whose output need not be UTF-8.
+pub(crate) fn run_git_for_bytes<I, S>(dir: &Path, args: I) -> Result<Vec<u8>, GitToolingError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    Ok(run_git(dir, args, None)?.output.stdout)
+}
+
 fn run_git<I, S>(
     dir: &Path,
     args: I,
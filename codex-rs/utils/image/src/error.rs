@@ -17,6 +17,10 @@ pub enum ImageProcessingError {
         #[source]
         source: image::ImageError,
     },
+    #[error("{format} images are not supported; convert {path} to PNG or JPEG first")]
+    UnsupportedFormat { path: PathBuf, format: &'static str },
+    #[error("image at {path} does not fit in {max_bytes} bytes even after downscaling")]
+    TooLarge { path: PathBuf, max_bytes: usize },
     #[error("failed to encode image as {format:?}: {source}")]
     Encode {
         format: ImageFormat,
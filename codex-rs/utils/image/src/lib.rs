@@ -19,15 +19,50 @@ use image::imageops::FilterType;
 pub const MAX_WIDTH: u32 = 2048;
 /// Maximum height used when resizing images before uploading.
 pub const MAX_HEIGHT: u32 = 768;
+/// Default upper bound on the encoded size of an uploaded image.
+pub const DEFAULT_MAX_BYTES: usize = 4 * 1024 * 1024;
+
+/// JPEG qualities tried, in order, when an image is still over its byte
+/// budget after resizing.
+const JPEG_FALLBACK_QUALITIES: [u8; 3] = [85, 70, 55];
+/// Each extra downscaling step shrinks both sides to this fraction.
+const DOWNSCALE_STEP_NUMERATOR: u32 = 3;
+const DOWNSCALE_STEP_DENOMINATOR: u32 = 4;
+/// Images are never shrunk below this size on their longest side while
+/// trying to meet the byte budget.
+const MIN_DIMENSION: u32 = 256;
 
 pub mod error;
 
+/// Bounds applied to an image before it is attached to a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageLimits {
+    pub max_width: u32,
+    pub max_height: u32,
+    /// Upper bound on the encoded image, before base64.
+    pub max_bytes: usize,
+}
+
+impl Default for ImageLimits {
+    fn default() -> Self {
+        Self {
+            max_width: MAX_WIDTH,
+            max_height: MAX_HEIGHT,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct EncodedImage {
     pub bytes: Vec<u8>,
     pub mime: String,
     pub width: u32,
     pub height: u32,
+    pub original_width: u32,
+    pub original_height: u32,
+    /// Size of the file on disk.
+    pub original_bytes: usize,
 }
 
 impl EncodedImage {
@@ -35,19 +70,45 @@ impl EncodedImage {
         let encoded = BASE64_STANDARD.encode(&self.bytes);
         format!("data:{};base64,{}", self.mime, encoded)
     }
+
+    /// Whether the image that will be sent is smaller than the file on disk,
+    /// either in pixels or in bytes.
+    pub fn was_downscaled(&self) -> bool {
+        self.width != self.original_width
+            || self.height != self.original_height
+            || self.bytes.len() < self.original_bytes
+    }
 }
 
-static IMAGE_CACHE: LazyLock<BlockingLruCache<[u8; 20], EncodedImage>> =
+static IMAGE_CACHE: LazyLock<BlockingLruCache<([u8; 20], ImageLimits), EncodedImage>> =
     LazyLock::new(|| BlockingLruCache::new(NonZeroUsize::new(32).unwrap_or(NonZeroUsize::MIN)));
 
 pub fn load_and_resize_to_fit(path: &Path) -> Result<EncodedImage, ImageProcessingError> {
+    load_and_resize_with_limits(path, ImageLimits::default())
+}
+
+/// Load the image at `path`, downscaling it (preserving the aspect ratio) to
+/// fit `limits.max_width` x `limits.max_height`, then re-encoding it as JPEG
+/// and shrinking further until it fits `limits.max_bytes`. Formats the
+/// providers do not accept (such as TIFF) are converted to PNG.
+pub fn load_and_resize_with_limits(
+    path: &Path,
+    limits: ImageLimits,
+) -> Result<EncodedImage, ImageProcessingError> {
     let path_buf = path.to_path_buf();
 
     let file_bytes = read_file_bytes(path, &path_buf)?;
 
-    let key = sha1_digest(&file_bytes);
+    let key = (sha1_digest(&file_bytes), limits);
 
     IMAGE_CACHE.get_or_try_insert_with(key, move || {
+        if let Some(format) = unsupported_container_format(&file_bytes) {
+            return Err(ImageProcessingError::UnsupportedFormat {
+                path: path_buf,
+                format,
+            });
+        }
+
         let format = match image::guess_format(&file_bytes) {
             Ok(ImageFormat::Png) => Some(ImageFormat::Png),
             Ok(ImageFormat::Jpeg) => Some(ImageFormat::Jpeg),
@@ -62,43 +123,92 @@ pub fn load_and_resize_to_fit(path: &Path) -> Result<EncodedImage, ImageProcessi
         })?;
 
         let (width, height) = dynamic.dimensions();
+        let original_bytes = file_bytes.len();
 
-        let encoded = if width <= MAX_WIDTH && height <= MAX_HEIGHT {
-            if let Some(format) = format {
-                let mime = format_to_mime(format);
-                EncodedImage {
-                    bytes: file_bytes,
-                    mime,
-                    width,
-                    height,
-                }
-            } else {
-                let (bytes, output_format) = encode_image(&dynamic, ImageFormat::Png)?;
-                let mime = format_to_mime(output_format);
-                EncodedImage {
-                    bytes,
-                    mime,
-                    width,
-                    height,
-                }
-            }
+        if width <= limits.max_width
+            && height <= limits.max_height
+            && original_bytes <= limits.max_bytes
+            && let Some(format) = format
+        {
+            return Ok(EncodedImage {
+                bytes: file_bytes,
+                mime: format_to_mime(format),
+                width,
+                height,
+                original_width: width,
+                original_height: height,
+                original_bytes,
+            });
+        }
+
+        let resized = if width <= limits.max_width && height <= limits.max_height {
+            dynamic
         } else {
-            let resized = dynamic.resize(MAX_WIDTH, MAX_HEIGHT, FilterType::Triangle);
-            let target_format = format.unwrap_or(ImageFormat::Png);
-            let (bytes, output_format) = encode_image(&resized, target_format)?;
-            let mime = format_to_mime(output_format);
-            EncodedImage {
-                bytes,
-                mime,
-                width: resized.width(),
-                height: resized.height(),
-            }
+            dynamic.resize(limits.max_width, limits.max_height, FilterType::Triangle)
         };
-
-        Ok(encoded)
+        let target_format = format.unwrap_or(ImageFormat::Png);
+        let (bytes, output_format, fitted) =
+            fit_to_byte_budget(resized, target_format, limits.max_bytes, &path_buf)?;
+        Ok(EncodedImage {
+            bytes,
+            mime: format_to_mime(output_format),
+            width: fitted.width(),
+            height: fitted.height(),
+            original_width: width,
+            original_height: height,
+            original_bytes,
+        })
     })
 }
 
+/// Detect ISO-BMFF image containers (HEIC/HEIF/AVIF) that we cannot decode,
+/// so they get a clear error instead of a generic decode failure.
+fn unsupported_container_format(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.get(4..8) != Some(b"ftyp".as_slice()) {
+        return None;
+    }
+    match bytes.get(8..12)? {
+        b"heic" | b"heix" | b"hevc" | b"hevx" | b"heim" | b"heis" => Some("HEIC"),
+        b"mif1" | b"msf1" => Some("HEIF"),
+        b"avif" | b"avis" => Some("AVIF"),
+        _ => None,
+    }
+}
+
+/// Encode `image` in `preferred_format`; while the result exceeds
+/// `max_bytes`, fall back to lower JPEG qualities and then to smaller sizes.
+fn fit_to_byte_budget(
+    image: DynamicImage,
+    preferred_format: ImageFormat,
+    max_bytes: usize,
+    path: &Path,
+) -> Result<(Vec<u8>, ImageFormat, DynamicImage), ImageProcessingError> {
+    let mut current = image;
+    loop {
+        let (bytes, format) = encode_image(&current, preferred_format)?;
+        if bytes.len() <= max_bytes {
+            return Ok((bytes, format, current));
+        }
+        for quality in JPEG_FALLBACK_QUALITIES {
+            let bytes = encode_jpeg(&current, quality)?;
+            if bytes.len() <= max_bytes {
+                return Ok((bytes, ImageFormat::Jpeg, current));
+            }
+        }
+
+        let (width, height) = current.dimensions();
+        if width.max(height) <= MIN_DIMENSION {
+            return Err(ImageProcessingError::TooLarge {
+                path: path.to_path_buf(),
+                max_bytes,
+            });
+        }
+        let next_width = (width * DOWNSCALE_STEP_NUMERATOR / DOWNSCALE_STEP_DENOMINATOR).max(1);
+        let next_height = (height * DOWNSCALE_STEP_NUMERATOR / DOWNSCALE_STEP_DENOMINATOR).max(1);
+        current = current.resize(next_width, next_height, FilterType::Triangle);
+    }
+}
+
 fn read_file_bytes(path: &Path, path_for_error: &Path) -> Result<Vec<u8>, ImageProcessingError> {
     match tokio::runtime::Handle::try_current() {
         // If we're inside a Tokio runtime, avoid block_on (it panics on worker threads).
@@ -145,13 +255,7 @@ fn encode_image(
                 })?;
         }
         ImageFormat::Jpeg => {
-            let mut encoder = JpegEncoder::new_with_quality(&mut buffer, 85);
-            encoder
-                .encode_image(image)
-                .map_err(|source| ImageProcessingError::Encode {
-                    format: target_format,
-                    source,
-                })?;
+            buffer = encode_jpeg(image, JPEG_FALLBACK_QUALITIES[0])?;
         }
         _ => unreachable!("unsupported target_format should have been handled earlier"),
     }
@@ -159,6 +263,19 @@ fn encode_image(
     Ok((buffer, target_format))
 }
 
+/// JPEG has no alpha channel, so the image is flattened to RGB first.
+fn encode_jpeg(image: &DynamicImage, quality: u8) -> Result<Vec<u8>, ImageProcessingError> {
+    let mut buffer = Vec::new();
+    let mut encoder = JpegEncoder::new_with_quality(&mut buffer, quality);
+    encoder
+        .encode_image(&DynamicImage::ImageRgb8(image.to_rgb8()))
+        .map_err(|source| ImageProcessingError::Encode {
+            format: ImageFormat::Jpeg,
+            source,
+        })?;
+    Ok(buffer)
+}
+
 fn format_to_mime(format: ImageFormat) -> String {
     match format {
         ImageFormat::Jpeg => "image/jpeg".to_string(),
@@ -210,6 +327,67 @@ mod tests {
         assert_eq!(loaded.dimensions(), (processed.width, processed.height));
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reencodes_to_fit_byte_budget() {
+        let temp_file = NamedTempFile::new().expect("temp file");
+        // Noise does not compress, so the PNG is far over the budget.
+        let image = ImageBuffer::from_fn(1024, 512, |x, y| {
+            let value = (x.wrapping_mul(7919) ^ y.wrapping_mul(104_729)) as u8;
+            Rgba([value, value.wrapping_mul(3), value.wrapping_mul(5), 255])
+        });
+        image
+            .save_with_format(temp_file.path(), ImageFormat::Png)
+            .expect("write png to temp file");
+        let limits = ImageLimits {
+            max_bytes: 64 * 1024,
+            ..ImageLimits::default()
+        };
+
+        let processed =
+            load_and_resize_with_limits(temp_file.path(), limits).expect("process image");
+
+        assert!(processed.bytes.len() <= limits.max_bytes);
+        assert_eq!(processed.mime, "image/jpeg");
+        assert_eq!(
+            (processed.original_width, processed.original_height),
+            (1024, 512)
+        );
+        assert!(processed.was_downscaled());
+        // Aspect ratio is preserved while shrinking.
+        assert!(processed.width.abs_diff(processed.height * 2) <= 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn converts_tiff_to_png() {
+        let temp_file = NamedTempFile::new().expect("temp file");
+        let image = ImageBuffer::from_pixel(40, 20, Rgba([1u8, 2, 3, 255]));
+        image
+            .save_with_format(temp_file.path(), ImageFormat::Tiff)
+            .expect("write tiff to temp file");
+
+        let processed = load_and_resize_to_fit(temp_file.path()).expect("process image");
+
+        assert_eq!(processed.mime, "image/png");
+        assert_eq!((processed.width, processed.height), (40, 20));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rejects_heic_with_a_clear_error() {
+        let temp_file = NamedTempFile::new().expect("temp file");
+        let mut bytes = vec![0, 0, 0, 24];
+        bytes.extend_from_slice(b"ftypheic");
+        bytes.extend_from_slice(&[0; 12]);
+        std::fs::write(temp_file.path(), bytes).expect("write bytes");
+
+        let err = load_and_resize_to_fit(temp_file.path()).expect_err("heic should fail");
+
+        assert!(matches!(
+            err,
+            ImageProcessingError::UnsupportedFormat { format: "HEIC", .. }
+        ));
+        assert!(err.to_string().contains("convert"));
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn fails_cleanly_for_invalid_images() {
         let temp_file = NamedTempFile::new().expect("temp file");
@@ -7,6 +7,15 @@ export type ThreadStartedEvent = {
   type: "thread.started";
   /** The identifier of the new thread. Can be used to resume the thread later. */
   thread_id: string;
+  /** Sampling parameters (`seed`, `temperature`, `top_p`) configured for the run. */
+  sampling?: SamplingParameters;
+};
+
+/** Sampling controls sent to the model. Unset fields use the provider's defaults. */
+export type SamplingParameters = {
+  seed?: number;
+  temperature?: number;
+  top_p?: number;
 };
 
 /**
@@ -31,6 +40,8 @@ export type Usage = {
 export type TurnCompletedEvent = {
   type: "turn.completed";
   usage: Usage;
+  /** Backend fingerprint the provider reported for the turn, if any. */
+  system_fingerprint?: string;
 };
 
 /** Indicates that a turn failed with an error. */
@@ -60,12 +71,33 @@ export type ItemCompletedEvent = {
 /** Fatal error emitted by the stream. */
 export type ThreadError = {
   message: string;
+  request_id?: string;
+  error_type?: string;
+  error_code?: string;
+  attempts?: number;
+  code?: string;
+  hint?: string;
+  details?: string;
 };
 
 /** Represents an unrecoverable error emitted directly by the event stream. */
 export type ThreadErrorEvent = {
   type: "error";
   message: string;
+  /** Request id reported by the model provider. Include it when reporting the error. */
+  request_id?: string;
+  /** Provider error type, such as `invalid_request_error`. */
+  error_type?: string;
+  /** Provider error code, when one was returned. */
+  error_code?: string;
+  /** Number of attempts made before giving up. */
+  attempts?: number;
+  /** Codex's own error classification, such as `rate_limited`. */
+  code?: string;
+  /** Suggested next step for the user. */
+  hint?: string;
+  /** Full error text, when `message` is a shortened headline. */
+  details?: string;
 };
 
 /** Top-level JSONL events emitted by codex exec. */
@@ -10,16 +10,21 @@ export type {
   ThreadError,
   ThreadErrorEvent,
   Usage,
+  SamplingParameters,
 } from "./events";
 export type {
   ThreadItem,
   AgentMessageItem,
+  FileCitation,
   ReasoningItem,
   CommandExecutionItem,
   FileChangeItem,
   McpToolCallItem,
   WebSearchItem,
   TodoListItem,
+  ApprovalResolutionItem,
+  CommitItem,
+  HookExecutionItem,
   ErrorItem,
 } from "./items";
 
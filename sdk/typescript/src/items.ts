@@ -26,8 +26,15 @@ export type PatchChangeKind = "add" | "delete" | "update";
 export type FileUpdateChange = {
   path: string;
   kind: PatchChangeKind;
+  /** What happened to this file. A failed patch may still have applied some of its files. */
+  status: FileChangeStatus;
+  /** Why the file could not be applied, for `failed` files. */
+  error?: string;
 };
 
+/** The outcome for one file of a patch. */
+export type FileChangeStatus = "applied" | "failed" | "skipped" | "aborted";
+
 /** The status of a file change. */
 export type PatchApplyStatus = "completed" | "failed";
 
@@ -70,12 +77,25 @@ export type McpToolCallItem = {
   status: McpToolCallStatus;
 };
 
+/** A reference to a file in an agent message. */
+export type FileCitation = {
+  /** Absolute path of the referenced file. */
+  path: string;
+  line?: number;
+  end_line?: number;
+  /** Byte offsets of the reference within the message text. */
+  start: number;
+  end: number;
+};
+
 /** Response from the agent. Either natural-language text or JSON when structured output is requested. */
 export type AgentMessageItem = {
   id: string;
   type: "agent_message";
   /** Either natural-language text or JSON when structured output is requested. */
   text: string;
+  /** Files in the workspace that the message refers to. Omitted when there are none. */
+  citations?: FileCitation[];
 };
 
 /** Agent's reasoning summary. */
@@ -99,6 +119,63 @@ export type ErrorItem = {
   message: string;
 };
 
+/** Whether an approval was requested for a command or a patch. */
+export type ApprovalRequestKind = "exec" | "patch";
+
+/** Who settled an approval request. */
+export type ApprovalResolvedBy = "user" | "timeout" | "turn_ended";
+
+/** Records how an exec or patch approval request was resolved. */
+export type ApprovalResolutionItem = {
+  id: string;
+  type: "approval_resolution";
+  kind: ApprovalRequestKind;
+  /** Shell-quoted command, for exec approvals. */
+  command?: string;
+  /** Files touched by the patch, for patch approvals. */
+  paths?: string[];
+  reason?: string;
+  decision: string;
+  resolved_by: ApprovalResolvedBy;
+  /** Signed-in account that answered the request, when known. */
+  account?: string;
+  /** RFC 3339 timestamp of the decision. */
+  resolved_at: string;
+};
+
+/** Whether `--commit` made a commit. */
+export type CommitStatus = "completed" | "failed";
+
+/** The result of committing the files a run changed with `--commit`. */
+export type CommitItem = {
+  id: string;
+  type: "commit";
+  /** Id of the new commit, when one was made. */
+  commit?: string;
+  message?: string;
+  /** Files included in the commit. */
+  files?: string[];
+  status: CommitStatus;
+  /** Why no commit was made. */
+  error?: string;
+};
+
+/** A command configured under `[hooks]`. */
+export type HookExecutionItem = {
+  id: string;
+  type: "hook_execution";
+  /** Where the hook ran: `pre_turn`, `post_turn`, `pre_exec` or `post_patch`. */
+  hook: string;
+  command: string;
+  /** Aggregated stdout and stderr, or why the hook could not be started. */
+  aggregated_output: string;
+  /** Set when the hook exits; omitted while running or when it could not be started. */
+  exit_code?: number;
+  status: CommandExecutionStatus;
+  /** Set when the failure ended the turn (`on_failure = "abort"`). */
+  aborted_turn?: boolean;
+};
+
 /** An item in the agent's to-do list. */
 export type TodoItem = {
   text: string;
@@ -124,4 +201,7 @@ export type ThreadItem =
   | McpToolCallItem
   | WebSearchItem
   | TodoListItem
+  | ApprovalResolutionItem
+  | CommitItem
+  | HookExecutionItem
   | ErrorItem;